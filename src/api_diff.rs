@@ -0,0 +1,130 @@
+//! Public API diff between two databases
+//!
+//! Compares the public symbols of two indexes (normally built with
+//! `--api-only`) and classifies each difference as breaking or additive, to
+//! inform semver decisions.
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use crate::graph::query::ApiSymbol;
+use crate::CodeGraph;
+
+/// How a change affects downstream users of the API
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum ApiImpact {
+    /// Existing callers may fail to compile or behave differently
+    Breaking,
+    /// New API only; existing callers are unaffected
+    Additive,
+}
+
+/// A public symbol present in only one of the two databases
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ApiSymbolChange {
+    /// Symbol FQN (display_fqn where available)
+    pub fqn: String,
+    /// Symbol kind
+    pub kind: String,
+    /// Declaration headers recorded for the symbol
+    pub signatures: Vec<String>,
+    /// Breaking for removals, additive for additions
+    pub impact: ApiImpact,
+}
+
+/// A public symbol whose signature or kind differs between the databases
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ApiSignatureChange {
+    /// Symbol FQN (display_fqn where available)
+    pub fqn: String,
+    /// Symbol as recorded in database A
+    pub before: ApiSymbol,
+    /// Symbol as recorded in database B
+    pub after: ApiSymbol,
+    /// Always breaking: callers written against `before` may not match `after`
+    pub impact: ApiImpact,
+}
+
+/// Report of public API differences from database A to database B
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApiDiffReport {
+    /// Symbols only in B
+    pub added: Vec<ApiSymbolChange>,
+    /// Symbols only in A
+    pub removed: Vec<ApiSymbolChange>,
+    /// Symbols in both whose signature or kind changed
+    pub changed: Vec<ApiSignatureChange>,
+}
+
+impl ApiDiffReport {
+    /// Check if any change is potentially breaking
+    pub fn is_breaking(&self) -> bool {
+        !self.removed.is_empty() || !self.changed.is_empty()
+    }
+
+    /// Check if the two APIs are identical
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+/// Diff the public API of two databases
+///
+/// # Arguments
+/// * `before` - Graph for the old API (database A)
+/// * `after` - Graph for the new API (database B)
+///
+/// # Returns
+/// ApiDiffReport with changes ordered by FQN
+pub fn diff_api(before: &mut CodeGraph, after: &mut CodeGraph) -> Result<ApiDiffReport> {
+    Ok(diff_api_maps(
+        &before.api_symbol_map()?,
+        &after.api_symbol_map()?,
+    ))
+}
+
+/// Diff two FQN-to-symbol maps
+pub fn diff_api_maps(
+    before: &BTreeMap<String, ApiSymbol>,
+    after: &BTreeMap<String, ApiSymbol>,
+) -> ApiDiffReport {
+    let mut removed = Vec::new();
+    let mut changed = Vec::new();
+
+    for (fqn, old) in before {
+        match after.get(fqn) {
+            Some(new) if new != old => changed.push(ApiSignatureChange {
+                fqn: fqn.clone(),
+                before: old.clone(),
+                after: new.clone(),
+                impact: ApiImpact::Breaking,
+            }),
+            Some(_) => {}
+            None => removed.push(ApiSymbolChange {
+                fqn: fqn.clone(),
+                kind: old.kind.clone(),
+                signatures: old.signatures.clone(),
+                impact: ApiImpact::Breaking,
+            }),
+        }
+    }
+
+    let added = after
+        .iter()
+        .filter(|(fqn, _)| !before.contains_key(*fqn))
+        .map(|(fqn, new)| ApiSymbolChange {
+            fqn: fqn.clone(),
+            kind: new.kind.clone(),
+            signatures: new.signatures.clone(),
+            impact: ApiImpact::Additive,
+        })
+        .collect();
+
+    ApiDiffReport {
+        added,
+        removed,
+        changed,
+    }
+}
@@ -0,0 +1,108 @@
+//! Api-diff command implementation
+//!
+//! Compares the public API recorded in two databases (normally built with
+//! `--api-only`) and classifies each change as breaking or additive.
+
+use anyhow::Result;
+use std::path::PathBuf;
+
+use crate::{generate_execution_id, CodeGraph};
+use magellan::api_diff::ApiImpact;
+use magellan::output::command::OutputFormat;
+
+fn impact_label(impact: ApiImpact) -> &'static str {
+    match impact {
+        ApiImpact::Breaking => "breaking",
+        ApiImpact::Additive => "additive",
+    }
+}
+
+/// Run the api-diff command
+///
+/// Returns exit code 1 when any change is potentially breaking.
+pub fn run_api_diff(db_a: PathBuf, db_b: PathBuf, output_format: OutputFormat) -> Result<u8> {
+    let mut before = CodeGraph::open_readonly(&db_a)?;
+    let mut after = CodeGraph::open_readonly(&db_b)?;
+    let exec_id = generate_execution_id();
+
+    let report = magellan::api_diff::diff_api(&mut before, &mut after)?;
+    let exit_code: u8 = if report.is_breaking() { 1 } else { 0 };
+
+    match output_format {
+        OutputFormat::Json | OutputFormat::Pretty => {
+            let response = serde_json::json!({
+                "schema_version": "1.0.0",
+                "execution_id": &exec_id,
+                "data": {
+                    "db_a": db_a.to_string_lossy(),
+                    "db_b": db_b.to_string_lossy(),
+                    "added": report.added,
+                    "removed": report.removed,
+                    "changed": report.changed,
+                    "is_breaking": report.is_breaking(),
+                },
+                "tool": "magellan",
+                "timestamp": chrono::Utc::now().to_rfc3339(),
+            });
+            let json_str = match output_format {
+                OutputFormat::Pretty => serde_json::to_string_pretty(&response)?,
+                _ => serde_json::to_string(&response)?,
+            };
+            println!("{json_str}");
+        }
+        OutputFormat::Human => {
+            println!(
+                "API diff: {} -> {}",
+                db_a.to_string_lossy(),
+                db_b.to_string_lossy()
+            );
+
+            if !report.removed.is_empty() {
+                println!("Removed ({}):", report.removed.len());
+                for change in &report.removed {
+                    println!(
+                        "  - [{}] {} {}",
+                        impact_label(change.impact),
+                        change.kind,
+                        change.fqn
+                    );
+                }
+            }
+
+            if !report.changed.is_empty() {
+                println!("Changed ({}):", report.changed.len());
+                for change in &report.changed {
+                    println!("  ! [{}] {}", impact_label(change.impact), change.fqn);
+                    for signature in &change.before.signatures {
+                        println!("      before: {}", signature);
+                    }
+                    for signature in &change.after.signatures {
+                        println!("      after:  {}", signature);
+                    }
+                }
+            }
+
+            if !report.added.is_empty() {
+                println!("Added ({}):", report.added.len());
+                for change in &report.added {
+                    println!(
+                        "  + [{}] {} {}",
+                        impact_label(change.impact),
+                        change.kind,
+                        change.fqn
+                    );
+                }
+            }
+
+            if report.is_empty() {
+                println!("No public API changes.");
+            } else if report.is_breaking() {
+                println!("Potentially breaking changes found.");
+            } else {
+                println!("Additive changes only.");
+            }
+        }
+    }
+
+    Ok(exit_code)
+}
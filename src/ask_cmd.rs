@@ -1,4 +1,6 @@
 use anyhow::{bail, Context, Result};
+use magellan::ingest::test_code::TestFilter;
+use magellan::output::ColumnUnit;
 use magellan::OutputFormat;
 use std::path::PathBuf;
 
@@ -190,8 +192,12 @@ fn route_find(db_path: PathBuf, name: String, output_format: OutputFormat) -> Re
         true,  // with_callees
         true,  // with_semantics
         false, // with_checksums
+        false, // with_metrics
         3,     // context_lines
         false, // all
+        TestFilter::All,
+        None, // visibility
+        ColumnUnit::Byte,
     )
     .with_context(|| "Ask → find routing failed")
 }
@@ -216,6 +222,9 @@ fn route_refs(
         3,     // context_lines
         false, // all
         None,  // tokens
+        false, // group_by_file
+        None,  // since
+        ColumnUnit::Byte,
     )
     .with_context(|| "Ask → refs routing failed")
 }
@@ -11,7 +11,7 @@
 //! Query AST nodes for a file.
 //!
 //! ```bash
-//! magellan ast --db <FILE> --file <PATH> [--position <OFFSET>] [--output <FORMAT>]
+//! magellan ast --db <FILE> --file <PATH> [--position <OFFSET> [--subtree [--max-depth <N>]] | --query <S-EXPR>] [--output <FORMAT>]
 //! ```
 //!
 //! ### Arguments
@@ -19,6 +19,9 @@
 //! - `--db <FILE>` - Path to the Magellan database (required)
 //! - `--file <PATH>` - File path to query (required)
 //! - `--position <OFFSET>` - Byte offset in the file to find node at (optional)
+//! - `--subtree` - Emit the node at `--position` with all descendants as a nested tree
+//! - `--max-depth <N>` - Levels below the root to include with `--subtree` (default: 32)
+//! - `--query <S-EXPR>` - Re-parse the file and return the captures of a tree-sitter query
 //! - `--output <FORMAT>` - Output format: human, json, or pretty (default: human)
 //!
 //! ### Examples
@@ -38,6 +41,16 @@
 //! magellan ast --db .magellan/magellan.db --file src/main.rs --output json
 //! ```
 //!
+//! Dump the subtree at byte 100, three levels deep:
+//! ```bash
+//! magellan ast --db .magellan/magellan.db --file src/main.rs --position 100 --subtree --max-depth 3 --output json
+//! ```
+//!
+//! Capture every function name with a tree-sitter query:
+//! ```bash
+//! magellan ast --db .magellan/magellan.db --file src/main.rs --query '(function_item name: (identifier) @n)' --output json
+//! ```
+//!
 //! ## `magellan find-ast`
 //!
 //! Find AST nodes by kind across all files.
@@ -78,9 +91,9 @@
 //! - `call_expression` - Function calls
 
 use anyhow::Result;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
-use magellan::graph::AstNode;
+use magellan::graph::{run_ast_query, AstNode, AstTreeNode};
 use magellan::output::{generate_execution_id, output_json, JsonResponse, OutputFormat};
 use magellan::CodeGraph;
 
@@ -152,11 +165,44 @@ pub fn run_ast_command(
     file_path: String,
     position: Option<usize>,
     output_format: OutputFormat,
+    subtree_depth: Option<usize>,
+    query: Option<String>,
 ) -> Result<()> {
+    if let Some(query) = query {
+        return run_ast_query_command(file_path, query, output_format);
+    }
+
     let graph = CodeGraph::open(&db_path)?;
     let exec_id = generate_execution_id();
 
-    if let Some(pos) = position {
+    if let (Some(pos), Some(max_depth)) = (position, subtree_depth) {
+        // Line/column need the source; omitted if the file is unreadable
+        let source = std::fs::read(graph.disk_path(&file_path)).ok();
+        match graph.get_ast_subtree(&file_path, pos, max_depth, source.as_deref())? {
+            Some(tree) => match output_format {
+                OutputFormat::Json | OutputFormat::Pretty => {
+                    let response = JsonResponse::new(
+                        serde_json::json!({
+                            "file_path": file_path,
+                            "position": pos,
+                            "max_depth": max_depth,
+                            "tree": tree,
+                        }),
+                        &exec_id,
+                    );
+                    output_json(&response, output_format)?;
+                }
+                OutputFormat::Human => {
+                    println!("AST subtree at position {} in {}:", pos, file_path);
+                    print_subtree(&tree, 0);
+                }
+            },
+            None => {
+                eprintln!("No AST node found at position {} in {}", pos, file_path);
+                std::process::exit(1);
+            }
+        }
+    } else if let Some(pos) = position {
         // Show AST at specific position
         match graph.get_ast_node_at_position(&file_path, pos)? {
             Some(node) => match output_format {
@@ -214,6 +260,51 @@ pub fn run_ast_command(
     Ok(())
 }
 
+/// Run `ast --query`: re-parse the file and print the query's captures
+///
+/// The source is read from disk, so the file does not have to be indexed.
+fn run_ast_query_command(
+    file_path: String,
+    query: String,
+    output_format: OutputFormat,
+) -> Result<()> {
+    let source = std::fs::read(&file_path)
+        .map_err(|e| anyhow::anyhow!("Failed to read {}: {}", file_path, e))?;
+    let captures = run_ast_query(Path::new(&file_path), &source, &query)?;
+
+    match output_format {
+        OutputFormat::Json | OutputFormat::Pretty => {
+            let response = JsonResponse::new(
+                serde_json::json!({
+                    "file_path": file_path,
+                    "query": query,
+                    "count": captures.len(),
+                    "captures": captures,
+                }),
+                &generate_execution_id(),
+            );
+            output_json(&response, output_format)?;
+        }
+        OutputFormat::Human => {
+            println!("{} capture(s) in {}:", captures.len(), file_path);
+            for capture in &captures {
+                println!(
+                    "  @{} {} ({}:{}) {}:{}: {}",
+                    capture.capture,
+                    capture.kind,
+                    capture.byte_start,
+                    capture.byte_end,
+                    capture.start_line,
+                    capture.start_col,
+                    capture.text.lines().next().unwrap_or_default()
+                );
+            }
+        }
+    }
+
+    Ok(())
+}
+
 /// Run the 'find-ast' command
 ///
 /// Finds all AST nodes of a specific kind across all files.
@@ -290,6 +381,25 @@ fn print_node_tree(graph: &CodeGraph, node: &AstNode, indent: usize) -> Result<(
     Ok(())
 }
 
+/// Print a subtree with indentation (human-readable)
+fn print_subtree(node: &AstTreeNode, indent: usize) {
+    let prefix = "  ".repeat(indent);
+    let connector = if indent == 0 { "" } else { "└── " };
+    let location = match (node.start_line, node.start_col) {
+        (Some(line), Some(col)) => format!(" @ {}:{}", line, col),
+        _ => String::new(),
+    };
+    let truncated = if node.truncated { " …" } else { "" };
+
+    println!(
+        "{}{}{} ({}:{}){}{}",
+        prefix, connector, node.kind, node.byte_start, node.byte_end, location, truncated
+    );
+    for child in &node.children {
+        print_subtree(child, indent + 1);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -5,8 +5,8 @@
 use anyhow::{Context, Result};
 use std::path::{Path, PathBuf};
 
-use crate::graph::CodeGraph;
 use crate::graph::SymbolNode;
+use crate::graph::{CodeGraph, SqliteOptions};
 use crate::ingest::SymbolKind;
 use sqlitegraph::{GraphBackend, SnapshotId};
 
@@ -59,6 +59,13 @@ impl MagellanBackend {
         }
     }
 
+    /// Open or create a database with explicit SQLite PRAGMA settings
+    pub fn open_or_create_with_options(db_path: &Path, options: &SqliteOptions) -> Result<Self> {
+        let graph = CodeGraph::open_with_options(db_path, options)
+            .context("Failed to open SQLite database")?;
+        Ok(MagellanBackend::SQLite(graph))
+    }
+
     /// Open a database
     pub fn open(db_path: &Path) -> Result<Self> {
         let graph = CodeGraph::open(db_path).context("Failed to open SQLite database")?;
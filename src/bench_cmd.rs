@@ -0,0 +1,139 @@
+//! Bench command implementation
+//!
+//! Indexes a directory into a throwaway database and reports throughput, so
+//! regressions in `scan_directory`/`index_file` show up as a number. The
+//! database lives in a temporary directory that is removed after each run;
+//! no persistent database is opened.
+
+use anyhow::Result;
+use magellan::graph::filter::FileFilter;
+use magellan::output::{
+    generate_execution_id, output_json, BenchResponse, BenchRun, JsonResponse, OutputFormat,
+};
+use magellan::CodeGraph;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+/// Index `root` once into a fresh temporary database
+fn bench_once(root: &Path) -> Result<BenchRun> {
+    let temp_dir = tempfile::TempDir::new()?;
+    let mut graph = CodeGraph::open(temp_dir.path().join("bench.db"))?;
+    let filter = FileFilter::new(root, &[], &[])?;
+
+    let start = Instant::now();
+    let result = graph.scan_directory_with_filter(root, &filter, None)?;
+    let elapsed = start.elapsed().as_secs_f64();
+
+    let symbols = graph.count_symbols()?;
+    let per_sec = |count: usize| {
+        if elapsed > 0.0 {
+            count as f64 / elapsed
+        } else {
+            0.0
+        }
+    };
+    Ok(BenchRun {
+        files: result.indexed,
+        symbols,
+        wall_ms: elapsed * 1000.0,
+        files_per_sec: per_sec(result.indexed),
+        symbols_per_sec: per_sec(symbols),
+    })
+}
+
+/// Min, median, and max wall time of the runs, in milliseconds
+fn wall_time_summary(runs: &[BenchRun]) -> (f64, f64, f64) {
+    let mut times: Vec<f64> = runs.iter().map(|run| run.wall_ms).collect();
+    times.sort_by(f64::total_cmp);
+    let mid = times.len() / 2;
+    let median = if times.len().is_multiple_of(2) {
+        (times[mid - 1] + times[mid]) / 2.0
+    } else {
+        times[mid]
+    };
+    (times[0], median, times[times.len() - 1])
+}
+
+/// Run the bench command
+///
+/// # Arguments
+/// * `root` - Directory to index
+/// * `runs` - Number of times to index it (at least 1)
+/// * `output_format` - Output format (Human, Json, or Pretty)
+pub fn run_bench(root: PathBuf, runs: usize, output_format: OutputFormat) -> Result<()> {
+    if !root.is_dir() {
+        anyhow::bail!("Root is not a directory: {}", root.display());
+    }
+    let exec_id = generate_execution_id();
+
+    let mut results = Vec::with_capacity(runs);
+    for _ in 0..runs {
+        results.push(bench_once(&root)?);
+    }
+    let (min_ms, median_ms, max_ms) = wall_time_summary(&results);
+
+    match output_format {
+        OutputFormat::Json | OutputFormat::Pretty => {
+            let response = BenchResponse {
+                root: root.to_string_lossy().to_string(),
+                runs: results,
+                min_ms,
+                median_ms,
+                max_ms,
+            };
+            let json_response = JsonResponse::new(response, &exec_id);
+            output_json(&json_response, output_format)?;
+        }
+        OutputFormat::Human => {
+            println!("Benchmark: {}", root.display());
+            for (n, run) in results.iter().enumerate() {
+                println!(
+                    "  Run {}: {} files, {} symbols in {:.1} ms ({:.1} files/s, {:.1} symbols/s)",
+                    n + 1,
+                    run.files,
+                    run.symbols,
+                    run.wall_ms,
+                    run.files_per_sec,
+                    run.symbols_per_sec
+                );
+            }
+            println!(
+                "Wall time over {} run(s): min {:.1} ms, median {:.1} ms, max {:.1} ms",
+                results.len(),
+                min_ms,
+                median_ms,
+                max_ms
+            );
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(wall_ms: f64) -> BenchRun {
+        BenchRun {
+            files: 1,
+            symbols: 1,
+            wall_ms,
+            files_per_sec: 0.0,
+            symbols_per_sec: 0.0,
+        }
+    }
+
+    #[test]
+    fn test_wall_time_summary() {
+        assert_eq!(wall_time_summary(&[run(5.0)]), (5.0, 5.0, 5.0));
+        assert_eq!(
+            wall_time_summary(&[run(30.0), run(10.0), run(20.0)]),
+            (10.0, 20.0, 30.0)
+        );
+        assert_eq!(
+            wall_time_summary(&[run(40.0), run(10.0), run(20.0), run(30.0)]),
+            (10.0, 25.0, 40.0)
+        );
+    }
+}
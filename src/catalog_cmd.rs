@@ -13,6 +13,7 @@
 //!   magellan catalog describe <name> --json
 
 use anyhow::{Context, Result};
+use magellan::log_warn;
 use magellan::output::{output_json, JsonResponse, OutputFormat};
 use rusqlite::Connection;
 use serde::{Deserialize, Serialize};
@@ -114,7 +115,7 @@ fn introspect_db(name: &str, db_path: &str) -> CatalogEntry {
     let conn = match Connection::open(path) {
         Ok(c) => c,
         Err(e) => {
-            eprintln!("Warning: cannot open {} for {}: {}", db_path, name, e);
+            log_warn!("cannot open {} for {}: {}", db_path, name, e);
             return CatalogEntry {
                 name: name.to_string(),
                 db_path: db_path.to_string(),
@@ -0,0 +1,152 @@
+//! Check command implementation
+//!
+//! Runs the collision, unresolved-call and freshness analyses selected with
+//! `--fail-on` and exits with status 1 when any of them finds an entry, so a
+//! CI job can gate on the state of the index. Errors, including a missing
+//! database, exit with status 2 so they are never mistaken for a verdict.
+
+use anyhow::Result;
+use std::path::PathBuf;
+
+use crate::{generate_execution_id, CodeGraph};
+use magellan::graph::query::{collision_groups, unresolved_calls, CollisionField};
+use magellan::output::command::OutputFormat;
+use magellan::output::{output_json, CheckResponse, JsonResponse};
+
+/// Exit status when `check` could not run (bad database, missing `--root`)
+pub const CHECK_ERROR_EXIT: u8 = 2;
+
+/// An analysis that `check --fail-on` can gate on
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CheckCategory {
+    /// Groups of symbols sharing one FQN
+    Collisions,
+    /// Call sites with no edge to an indexed callee
+    Unresolved,
+    /// Files that are missing, new or modified on disk since indexing
+    Stale,
+}
+
+impl CheckCategory {
+    pub fn parse(value: &str) -> Result<Self> {
+        match value {
+            "collisions" => Ok(CheckCategory::Collisions),
+            "unresolved" => Ok(CheckCategory::Unresolved),
+            "stale" => Ok(CheckCategory::Stale),
+            other => Err(anyhow::anyhow!(
+                "Unknown --fail-on category '{}' (expected collisions, unresolved or stale)",
+                other
+            )),
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CheckCategory::Collisions => "collisions",
+            CheckCategory::Unresolved => "unresolved",
+            CheckCategory::Stale => "stale",
+        }
+    }
+}
+
+pub fn run_check(
+    db_path: PathBuf,
+    root_path: Option<PathBuf>,
+    fail_on: Vec<CheckCategory>,
+    output_format: OutputFormat,
+) -> Result<u8> {
+    let mut args = vec![
+        "check".to_string(),
+        "--db".to_string(),
+        db_path.to_string_lossy().to_string(),
+    ];
+    if let Some(ref root) = root_path {
+        args.push("--root".to_string());
+        args.push(root.to_string_lossy().to_string());
+    }
+    args.push("--fail-on".to_string());
+    args.push(
+        fail_on
+            .iter()
+            .map(|c| c.as_str())
+            .collect::<Vec<_>>()
+            .join(","),
+    );
+
+    // Read-only: a typo'd --db must fail instead of passing on a new empty index
+    let mut graph = CodeGraph::open_readonly(&db_path)?;
+    let exec_id = generate_execution_id();
+    let root_str = root_path
+        .as_ref()
+        .map(|root| root.to_string_lossy().to_string());
+    let db_path_str = db_path.to_string_lossy().to_string();
+
+    graph.execution_log().start_execution(
+        &exec_id,
+        env!("CARGO_PKG_VERSION"),
+        &args,
+        root_str.as_deref(),
+        &db_path_str,
+    )?;
+
+    let mut counts: Vec<(CheckCategory, usize)> = Vec::new();
+    for category in &fail_on {
+        let phase = format!("check_{}", category.as_str());
+        graph.telemetry().record_phase_start(&exec_id, &phase)?;
+        let count = match category {
+            CheckCategory::Collisions => {
+                collision_groups(&mut graph, CollisionField::Fqn, usize::MAX)?.len()
+            }
+            CheckCategory::Unresolved => unresolved_calls(&mut graph)?.len(),
+            CheckCategory::Stale => {
+                let root = root_path
+                    .as_ref()
+                    .ok_or_else(|| anyhow::anyhow!("--fail-on stale requires --root"))?;
+                let report = magellan::verify::verify_graph(&mut graph, root)?;
+                report.missing.len() + report.new.len() + report.modified.len()
+            }
+        };
+        graph.telemetry().record_phase_end(&exec_id, &phase)?;
+        counts.push((*category, count));
+    }
+
+    let failed: Vec<&str> = counts
+        .iter()
+        .filter(|(_, count)| *count > 0)
+        .map(|(category, _)| category.as_str())
+        .collect();
+    let exit_code: u8 = if failed.is_empty() { 0 } else { 1 };
+
+    match output_format {
+        OutputFormat::Json | OutputFormat::Pretty => {
+            let response = CheckResponse {
+                db_path: db_path_str.clone(),
+                root_path: root_str.clone(),
+                counts: counts
+                    .iter()
+                    .map(|(category, count)| (category.as_str().to_string(), *count))
+                    .collect(),
+                failed: failed.iter().map(|category| category.to_string()).collect(),
+                passed: failed.is_empty(),
+            };
+            output_json(&JsonResponse::new(response, &exec_id), output_format)?;
+        }
+        OutputFormat::Human => {
+            for (category, count) in &counts {
+                let status = if *count > 0 { "FAIL" } else { "ok" };
+                println!("{:<12} {:>6}  {}", category.as_str(), count, status);
+            }
+            if failed.is_empty() {
+                println!("Check passed.");
+            } else {
+                println!("Check failed: {}", failed.join(", "));
+            }
+        }
+    }
+
+    graph
+        .execution_log()
+        .finish_execution(&exec_id, "success", None, 0, 0, 0)?;
+
+    Ok(exit_code)
+}
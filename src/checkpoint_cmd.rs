@@ -0,0 +1,73 @@
+//! Checkpoint command implementation
+//!
+//! Runs `PRAGMA wal_checkpoint(TRUNCATE)` so the pages held in the `-wal`
+//! file are written back to the database and the WAL shrinks to zero bytes.
+
+use anyhow::Result;
+use magellan::output::{
+    generate_execution_id, output_json, CheckpointResponse, JsonResponse, OutputFormat,
+};
+use magellan::CodeGraph;
+use std::path::{Path, PathBuf};
+
+/// Size of the database's `-wal` file, or 0 when there is none
+fn wal_size(db_path: &Path) -> u64 {
+    let mut wal_path = db_path.as_os_str().to_owned();
+    wal_path.push("-wal");
+    std::fs::metadata(PathBuf::from(wal_path))
+        .map(|meta| meta.len())
+        .unwrap_or(0)
+}
+
+/// Run the checkpoint command
+///
+/// Not recorded in the execution log: logging writes new WAL frames, which
+/// would defeat the truncation this command exists for.
+///
+/// # Arguments
+/// * `db_path` - Path to the sqlitegraph database
+/// * `output_format` - Output format (Human, Json, or Pretty)
+pub fn run_checkpoint(db_path: PathBuf, output_format: OutputFormat) -> Result<()> {
+    if !db_path.exists() {
+        anyhow::bail!("Database not found: {}", db_path.display());
+    }
+    let graph = CodeGraph::open(&db_path)?;
+    let exec_id = generate_execution_id();
+
+    let wal_bytes_before = wal_size(&db_path);
+    let stats = graph.checkpoint_wal_stats()?;
+    let wal_bytes_after = wal_size(&db_path);
+
+    match output_format {
+        OutputFormat::Json | OutputFormat::Pretty => {
+            let response = CheckpointResponse {
+                busy: stats.busy,
+                log_frames: stats.log_frames,
+                checkpointed_frames: stats.checkpointed_frames,
+                wal_bytes_before,
+                wal_bytes_after,
+            };
+            let json_response = JsonResponse::new(response, &exec_id);
+            output_json(&json_response, output_format)?;
+        }
+        OutputFormat::Human => {
+            if stats.log_frames < 0 {
+                println!("Database is not in WAL mode; nothing to checkpoint");
+                return Ok(());
+            }
+            println!(
+                "Checkpointed {} of {} WAL frame(s)",
+                stats.checkpointed_frames, stats.log_frames
+            );
+            println!(
+                "  WAL size: {} -> {} bytes",
+                wal_bytes_before, wal_bytes_after
+            );
+            if stats.busy {
+                println!("  Checkpoint incomplete: database is busy (active readers or writer)");
+            }
+        }
+    }
+
+    Ok(())
+}
@@ -4,9 +4,16 @@
 //! for the actual `parse_args()` implementation.
 
 use magellan::capabilities::BackendCapabilities;
-use magellan::graph::export::ExportFilters;
-use magellan::graph::query::CollisionField;
-use magellan::{ExportFormat, OutputFormat, WatcherConfig};
+use magellan::graph::export::{ExportFilters, JsonlRecordType, JSONL_SCHEMA_VERSION};
+use magellan::graph::query::{CollisionField, SymbolSort};
+use magellan::ingest::test_code::TestFilter;
+use magellan::ingest::visibility::VisibilityFilter;
+use magellan::output::command::MAGELLAN_JSON_SCHEMA_VERSION;
+use magellan::output::ColumnUnit;
+use magellan::{
+    ExportFormat, HashAlgorithm, HashNormalization, OutputFormat, PathStyle, SqliteOptions,
+    WatcherConfig,
+};
 use std::path::PathBuf;
 
 /// Print short usage (≤25 lines) for quick reference
@@ -123,6 +130,15 @@ pub enum ContextSubcommand {
     },
 }
 
+/// Source of the path list for `index --changed-from` / `--files-from`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ChangedPaths {
+    /// Paths reported by `git diff --name-only <REF>`
+    GitRef(String),
+    /// Newline-separated paths read from a file (`-` for stdin)
+    FilesFrom(PathBuf),
+}
+
 #[derive(Debug)]
 #[allow(
     clippy::large_enum_variant,
@@ -131,12 +147,52 @@ pub enum ContextSubcommand {
 pub enum Command {
     Watch {
         root_path: PathBuf,
+        /// Further `--root` directories watched into the same database
+        extra_roots: Vec<PathBuf>,
         db_path: PathBuf,
         config: WatcherConfig,
         scan_initial: bool,
         validate: bool,
         validate_only: bool,
         compile_commands: Option<PathBuf>,
+        api_only: bool,
+        /// zstd-compress newly stored chunk content (`--compress-chunks`)
+        compress_chunks: bool,
+        /// Skip call-graph extraction (`--no-calls`)
+        no_calls: bool,
+        /// Stored path style (`--relative-paths` / `--absolute-paths`)
+        path_style: Option<PathStyle>,
+        /// Content normalization before hashing (`--normalize-eol`, ...)
+        hash_normalization: HashNormalization,
+        /// Content hash algorithm for newly indexed files (`--hash-algo`)
+        hash_algo: Option<HashAlgorithm>,
+        sqlite_options: SqliteOptions,
+        /// Config file supplying defaults (`--config` or discovered at the root)
+        config_path: Option<PathBuf>,
+        /// Skip files larger than this many bytes (`--max-file-size`)
+        max_file_size: Option<u64>,
+        /// Per-file parse timeout in milliseconds, 0 disables (`--parse-timeout-ms`)
+        parse_timeout_ms: Option<u64>,
+        /// Include globs, overriding the config file (`--include`)
+        include_patterns: Vec<String>,
+        /// Exclude globs, overriding the config file (`--exclude`)
+        exclude_patterns: Vec<String>,
+        /// Threads reading files during the initial scan (`--jobs`)
+        jobs: Option<usize>,
+        /// Run the initial scan and exit instead of watching (`--once`)
+        once: bool,
+        /// Checkpoint the WAL every N seconds while idle (`--checkpoint-interval`)
+        checkpoint_interval: Option<u64>,
+        /// Index files with a generated-file header (`--include-generated`)
+        include_generated: bool,
+        /// Print memory accounting after the initial scan (`--mem-report`)
+        mem_report: bool,
+        /// Print per-phase indexing timings on shutdown (`--profile`)
+        profile: bool,
+        /// List the files the initial scan would index, then exit (`--dry-run`)
+        dry_run: bool,
+        /// Diagnostic format (`--output json` for line-delimited JSON)
+        output_format: OutputFormat,
     },
     Export {
         db_path: PathBuf,
@@ -146,8 +202,12 @@ pub enum Command {
         include_references: bool,
         include_calls: bool,
         minify: bool,
+        gzip: bool,
         include_collisions: bool,
         collisions_field: CollisionField,
+        include_unresolved: bool,
+        /// JSONL record types to emit (`--type`, repeatable)
+        record_types: Vec<JsonlRecordType>,
         filters: ExportFilters,
         // Impact export specific parameters
         impact_symbol: Option<String>,
@@ -161,6 +221,12 @@ pub enum Command {
     Backfill {
         db_path: PathBuf,
     },
+    /// Recompute file and symbol metrics (`metrics backfill`)
+    MetricsBackfill {
+        db_path: PathBuf,
+        only: Option<String>,
+        output_format: OutputFormat,
+    },
     CrossFileRefs {
         db_path: PathBuf,
         fqn: String,
@@ -189,8 +255,14 @@ pub enum Command {
     },
     Index {
         db_path: PathBuf,
-        file_path: PathBuf,
+        file_path: Option<PathBuf>,
+        changed: Option<ChangedPaths>,
         root: Option<PathBuf>,
+        api_only: bool,
+        path_style: Option<PathStyle>,
+        hash_normalization: HashNormalization,
+        hash_algo: Option<HashAlgorithm>,
+        sqlite_options: SqliteOptions,
     },
     IngestCoverage {
         db_path: PathBuf,
@@ -229,6 +301,10 @@ pub enum Command {
         output_format: OutputFormat,
         db_path: PathBuf,
         all: bool,
+        /// Seconds between snapshots with `--watch`; `None` prints once
+        watch_interval: Option<u64>,
+        /// Include per-kind symbol counts in `--watch` snapshots (`--by-kind`)
+        by_kind: bool,
     },
     Features {
         db_path: PathBuf,
@@ -253,7 +329,21 @@ pub enum Command {
         with_callees: bool,
         with_semantics: bool,
         with_checksums: bool,
+        /// Include stored symbol metrics (`--with-metrics`)
+        with_metrics: bool,
         context_lines: usize,
+        count_only: bool,
+        sort: Option<SymbolSort>,
+        test_filter: TestFilter,
+        visibility: Option<VisibilityFilter>,
+        /// FQN prefix filter (`--defined-in`)
+        defined_in: Option<String>,
+        /// Unit of output columns (`--column-unit`)
+        column_unit: ColumnUnit,
+        /// Database to compare symbol content against (`--baseline`)
+        baseline: Option<PathBuf>,
+        /// Omit unchanged symbols from `--baseline` output
+        changed_only: bool,
     },
     Find {
         db_path: PathBuf,
@@ -270,8 +360,26 @@ pub enum Command {
         with_callees: bool,
         with_semantics: bool,
         with_checksums: bool,
+        /// Include stored symbol metrics (`--with-metrics`)
+        with_metrics: bool,
         context_lines: usize,
         all: bool,
+        count_only: bool,
+        fuzzy: Option<String>,
+        fuzzy_fqn: bool,
+        limit: usize,
+        test_filter: TestFilter,
+        visibility: Option<VisibilityFilter>,
+        /// FQN prefix filter (`--defined-in`)
+        defined_in: Option<String>,
+        /// Kind filter for `--defined-in` and `--near` listings
+        kind: Option<String>,
+        /// File and line to rank symbols by proximity to (`--near`)
+        near: Option<(PathBuf, usize)>,
+        /// Unit of output columns (`--column-unit`)
+        column_unit: ColumnUnit,
+        /// List deprecated symbols (`--deprecated`)
+        deprecated: bool,
     },
     Refs {
         db_path: PathBuf,
@@ -287,6 +395,12 @@ pub enum Command {
         context_lines: usize,
         all: bool,
         tokens: Option<usize>,
+        /// Aggregate references per file (`--group-by-file`)
+        group_by_file: bool,
+        /// Keep only references on lines changed since this git ref (`--since`)
+        since: Option<String>,
+        /// Unit of output columns (`--column-unit`)
+        column_unit: ColumnUnit,
     },
     Get {
         db_path: PathBuf,
@@ -297,10 +411,27 @@ pub enum Command {
         with_semantics: bool,
         with_checksums: bool,
         context_lines: usize,
+        /// Granularity of the returned content (`--expand-to`)
+        expand_to: crate::get_cmd::ExpandTo,
+    },
+    GetBySymbolId {
+        db_path: PathBuf,
+        symbol_id: String,
+        output_format: OutputFormat,
+        with_context: bool,
+        with_semantics: bool,
+        with_checksums: bool,
+        context_lines: usize,
+        /// Granularity of the returned content (`--expand-to`)
+        expand_to: crate::get_cmd::ExpandTo,
     },
     GetFile {
         db_path: PathBuf,
         file_path: String,
+        /// Return the nested symbol outline instead of code chunks (`--outline`)
+        outline: bool,
+        /// Return only this 1-indexed inclusive line range (`--line-range START:END`)
+        line_range: Option<(usize, usize)>,
         output_format: OutputFormat,
     },
     Files {
@@ -308,11 +439,83 @@ pub enum Command {
         output_format: OutputFormat,
         with_symbols: bool,
     },
+    /// Slowest files by recorded indexing wall time
+    Timings {
+        db_path: PathBuf,
+        top: usize,
+        output_format: OutputFormat,
+    },
+    /// Types implementing or extending a trait / interface
+    Implementors {
+        db_path: PathBuf,
+        name: String,
+        output_format: OutputFormat,
+    },
+    /// Delete side-table rows whose File node no longer exists
+    Prune {
+        db_path: PathBuf,
+        dry_run: bool,
+        output_format: OutputFormat,
+    },
+    /// Index a directory into a temporary database and report throughput
+    Bench {
+        root: PathBuf,
+        /// Number of timed runs (`--runs`, default 1)
+        runs: usize,
+        output_format: OutputFormat,
+    },
+    /// Checkpoint the SQLite WAL into the database file and truncate it
+    Checkpoint {
+        db_path: PathBuf,
+        output_format: OutputFormat,
+    },
+    /// Rewrite stored file paths from one root (or `.` for relative) to another
+    RebasePaths {
+        db_path: PathBuf,
+        from: String,
+        to: String,
+        output_format: OutputFormat,
+    },
+    /// JSON Schema documents for `--output json` response types
+    Schema {
+        type_name: Option<String>,
+        output_format: OutputFormat,
+    },
     Verify {
+        /// Filesystem root to compare against; optional with `--integrity`
+        root_path: Option<PathBuf>,
+        db_path: PathBuf,
+        /// Also run the database self-consistency checks
+        integrity: bool,
+        /// Also flag symbols whose spans cross or duplicate each other
+        overlaps: bool,
+        output_format: OutputFormat,
+    },
+    /// Reindex unchanged files and diff stable symbol IDs against the live DB
+    VerifyIds {
         root_path: PathBuf,
         db_path: PathBuf,
         output_format: OutputFormat,
     },
+    /// Exit nonzero when any selected index analysis finds entries
+    Check {
+        db_path: PathBuf,
+        root_path: Option<PathBuf>,
+        fail_on: Vec<crate::check_cmd::CheckCategory>,
+        output_format: OutputFormat,
+    },
+    /// Compare the public API of two databases
+    ApiDiff {
+        db_a: PathBuf,
+        db_b: PathBuf,
+        output_format: OutputFormat,
+    },
+    /// Compare the symbols and call edges of two databases
+    Diff {
+        old_db: PathBuf,
+        new_db: PathBuf,
+        output_format: OutputFormat,
+    },
     /// Refresh index based on git changes
     Refresh {
         db_path: PathBuf,
@@ -336,6 +539,10 @@ pub enum Command {
         db_path: PathBuf,
         field: CollisionField,
         limit: usize,
+        /// Group by the field with generic arguments stripped (`--canonicalize-generics`)
+        canonicalize_generics: bool,
+        /// Propose a disambiguating prefix for each member (`--resolve-suggestions`)
+        resolve_suggestions: bool,
         output_format: OutputFormat,
     },
     Migrate {
@@ -350,6 +557,7 @@ pub enum Command {
         output_db: PathBuf,
         export_dir: Option<PathBuf>,
         dry_run: bool,
+        resume: bool,
         output_format: OutputFormat,
     },
     TemporalSweep {
@@ -393,6 +601,10 @@ pub enum Command {
         limit: Option<usize>,
         file_filter: Option<String>,
         kind_filter: Option<String>,
+        /// Compare stored chunks with their files on disk (`--verify`)
+        verify: bool,
+        /// Root for relative stored paths (`--root`, with `--verify`)
+        root: Option<PathBuf>,
     },
     ChunkBySpan {
         db_path: PathBuf,
@@ -405,6 +617,8 @@ pub enum Command {
         db_path: PathBuf,
         symbol_name: String,
         file_filter: Option<String>,
+        /// Maximum chunks to return (`--limit`)
+        limit: Option<usize>,
         output_format: OutputFormat,
     },
     Ast {
@@ -412,6 +626,17 @@ pub enum Command {
         file_path: String,
         position: Option<usize>,
         output_format: OutputFormat,
+        /// Depth cap when dumping the subtree at `position` (`--subtree`)
+        subtree_depth: Option<usize>,
+        /// Tree-sitter query to run against the re-parsed file (`--query`)
+        query: Option<String>,
+    },
+    /// Extract symbols from one buffer without a database
+    Parse {
+        lang: Option<String>,
+        file_path: Option<PathBuf>,
+        from_stdin: bool,
+        output_format: OutputFormat,
     },
     FindAst {
         db_path: PathBuf,
@@ -423,6 +648,10 @@ pub enum Command {
         db_path: PathBuf,
         symbol_id: String,
         reverse: bool,
+        /// Emit the reachable subgraph as Graphviz DOT
+        dot: bool,
+        /// Only follow this many calls from the starting symbol (`--max-depth`)
+        max_depth: Option<usize>,
         output_format: OutputFormat,
     },
     /// Dead code detection (Phase 40)
@@ -436,11 +665,14 @@ pub enum Command {
     Condense {
         db_path: PathBuf,
         show_members: bool,
+        /// Only report supernodes with at least this many members (`--min-scc-size`)
+        min_scc_size: Option<usize>,
         output_format: OutputFormat,
     },
     DeadCode {
         db_path: PathBuf,
         entry_symbol_id: String,
+        test_filter: TestFilter,
         output_format: OutputFormat,
     },
     /// Path enumeration (Phase 40)
@@ -450,6 +682,9 @@ pub enum Command {
         end_symbol_id: Option<String>,
         max_depth: usize,
         max_paths: usize,
+        shortest: bool,
+        /// Symbols (ID or FQN) that returned paths must not pass through
+        avoid: Vec<String>,
         output_format: OutputFormat,
     },
     /// Program slicing (Phase 40)
@@ -458,6 +693,10 @@ pub enum Command {
         target: String,
         direction: String,
         verbose: bool,
+        /// Attach each symbol's stored chunk (`--with-code`)
+        with_code: bool,
+        /// Byte budget for attached code (`--max-bytes`)
+        max_bytes: Option<usize>,
         output_format: OutputFormat,
     },
     /// Source inventory for graph memory (Phase 1)
@@ -574,6 +813,107 @@ pub enum Command {
     },
 }
 
+impl Command {
+    /// `schema_version` of the command's machine-readable output
+    ///
+    /// Only output wrapped in the `JsonResponse` envelope carries the Magellan
+    /// JSON schema version. Export formats defined outside Magellan (DOT,
+    /// SCIP, LSIF, SARIF), hand-built envelopes (`api-diff`, `diff`,
+    /// `verify`, `verify-ids`, `context list|impact|affected`), watch
+    /// diagnostics, and commands printing raw JSON or only text return `None`.
+    pub fn schema_version(&self) -> Option<&'static str> {
+        match self {
+            Command::Export { format, .. } => match format {
+                ExportFormat::Json | ExportFormat::JsonL | ExportFormat::Csv => {
+                    Some(JSONL_SCHEMA_VERSION)
+                }
+                _ => None,
+            },
+            Command::Watch { dry_run, .. } => dry_run.then_some(MAGELLAN_JSON_SCHEMA_VERSION),
+            Command::Context { subcommand, .. } => match subcommand {
+                ContextSubcommand::Symbol { .. } => Some(MAGELLAN_JSON_SCHEMA_VERSION),
+                _ => None,
+            },
+            Command::MetricsBackfill { .. }
+            | Command::CrossFileRefs { .. }
+            | Command::Catalog { .. }
+            | Command::CatalogDescribe { .. }
+            | Command::Status { .. }
+            | Command::Features { .. }
+            | Command::ProjectMetadata { .. }
+            | Command::Query { .. }
+            | Command::Find { .. }
+            | Command::Refs { .. }
+            | Command::Get { .. }
+            | Command::GetBySymbolId { .. }
+            | Command::GetFile { .. }
+            | Command::Files { .. }
+            | Command::Timings { .. }
+            | Command::Implementors { .. }
+            | Command::Prune { .. }
+            | Command::Bench { .. }
+            | Command::Checkpoint { .. }
+            | Command::RebasePaths { .. }
+            | Command::Schema { .. }
+            | Command::Check { .. }
+            | Command::Label { .. }
+            | Command::Collisions { .. }
+            | Command::Migrate { .. }
+            | Command::MigrateBackend { .. }
+            | Command::TemporalSweep { .. }
+            | Command::TemporalStatus { .. }
+            | Command::TemporalBarcode { .. }
+            | Command::AsOf { .. }
+            | Command::Orient { .. }
+            | Command::Chunks { .. }
+            | Command::ChunkBySpan { .. }
+            | Command::ChunkBySymbol { .. }
+            | Command::Ast { .. }
+            | Command::FindAst { .. }
+            | Command::Reachable { .. }
+            | Command::Cycles { .. }
+            | Command::Condense { .. }
+            | Command::DeadCode { .. }
+            | Command::Paths { .. }
+            | Command::Slice { .. }
+            | Command::SourceInventory { .. }
+            | Command::CandidateFact { .. }
+            | Command::Telemetry { .. } => Some(MAGELLAN_JSON_SCHEMA_VERSION),
+            Command::ImportLsif { .. }
+            | Command::Backfill { .. }
+            | Command::ConfigShow { .. }
+            | Command::ConfigInit { .. }
+            | Command::ProjectInit { .. }
+            | Command::Delete { .. }
+            | Command::Index { .. }
+            | Command::IngestCoverage { .. }
+            | Command::Enrich { .. }
+            | Command::Score { .. }
+            | Command::Doctor { .. }
+            | Command::Verify { .. }
+            | Command::VerifyIds { .. }
+            | Command::ApiDiff { .. }
+            | Command::Diff { .. }
+            | Command::Refresh { .. }
+            | Command::Parse { .. }
+            | Command::Service { .. }
+            | Command::ServiceDaemon
+            | Command::Cypher { .. }
+            | Command::HnswCreate { .. }
+            | Command::HnswQuery { .. }
+            | Command::Ask { .. }
+            | Command::BlastScore { .. }
+            | Command::Navigate { .. }
+            | Command::Explore { .. }
+            | Command::Hopgraph { .. }
+            | Command::Embed { .. }
+            | Command::InstallHook { .. } => None,
+            #[cfg(feature = "web-ui")]
+            Command::WebUi { .. } => None,
+        }
+    }
+}
+
 // ============================================================================
 // Command Parsers — See src/cli/parsers.rs
 // ============================================================================
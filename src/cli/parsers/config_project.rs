@@ -102,12 +102,18 @@ pub fn parse_delete_args(args: &[String]) -> Result<Command> {
     })
 }
 
+/// Seconds between `status --watch` snapshots when `--interval` is not given
+const DEFAULT_STATUS_INTERVAL_SECS: u64 = 2;
+
 /// Parse the `status` command arguments
 pub fn parse_status_args(args: &[String]) -> Result<Command> {
     let mut db_path: Option<PathBuf> = None;
     let mut output_format = OutputFormat::Human;
     let mut all = false;
     let mut project: Option<String> = None;
+    let mut watch = false;
+    let mut interval: Option<u64> = None;
+    let mut by_kind = false;
     let mut i = 0;
     while i < args.len() {
         match args[i].as_str() {
@@ -116,6 +122,26 @@ pub fn parse_status_args(args: &[String]) -> Result<Command> {
                 all = true;
                 i += 1;
             }
+            "--watch" => {
+                watch = true;
+                i += 1;
+            }
+            "--interval" => {
+                let value = parse_required_arg(args, &mut i, "--interval")?;
+                interval = match value.parse() {
+                    Ok(secs) if secs > 0 => Some(secs),
+                    _ => {
+                        return Err(anyhow::anyhow!(
+                            "--interval must be a positive number of seconds, got '{}'",
+                            value
+                        ))
+                    }
+                };
+            }
+            "--by-kind" => {
+                by_kind = true;
+                i += 1;
+            }
             "--project" => {
                 project = Some(parse_required_arg(args, &mut i, "--project")?);
             }
@@ -138,11 +164,19 @@ pub fn parse_status_args(args: &[String]) -> Result<Command> {
             .ok_or_else(|| anyhow::anyhow!("project '{}' not found in registry", name))?;
         db_path = Some(entry.db.clone());
     }
+    if !watch && (interval.is_some() || by_kind) {
+        return Err(anyhow::anyhow!("--interval and --by-kind require --watch"));
+    }
+    if watch && all {
+        return Err(anyhow::anyhow!("--watch cannot be combined with --all"));
+    }
     let db_path = resolve_db_path(db_path)?;
     Ok(Command::Status {
         output_format,
         db_path,
         all,
+        watch_interval: watch.then(|| interval.unwrap_or(DEFAULT_STATUS_INTERVAL_SECS)),
+        by_kind,
     })
 }
 
@@ -1,5 +1,8 @@
 use anyhow::Result;
-use magellan::OutputFormat;
+use magellan::ingest::test_code::TestFilter;
+use magellan::ingest::visibility::VisibilityFilter;
+use magellan::output::ColumnUnit;
+use magellan::{HashAlgorithm, HashNormalization, OutputFormat, PathStyle, SqliteOptions};
 use std::path::PathBuf;
 
 // ============================================================================
@@ -23,6 +26,43 @@ pub fn parse_required_arg(args: &[String], i: &mut usize, flag: &str) -> Result<
     Ok(value)
 }
 
+/// Helper to apply `--only-test-files` / `--ignore-test-files`
+///
+/// The two flags are mutually exclusive.
+pub fn set_test_filter(filter: &mut TestFilter, flag: &str) -> Result<()> {
+    let requested = match flag {
+        "--only-test-files" => TestFilter::OnlyTests,
+        _ => TestFilter::IgnoreTests,
+    };
+    if *filter != TestFilter::All && *filter != requested {
+        return Err(anyhow::anyhow!(
+            "--only-test-files cannot be combined with --ignore-test-files"
+        ));
+    }
+    *filter = requested;
+    Ok(())
+}
+
+/// Helper to parse a `--visibility` value
+pub fn parse_visibility_filter(value: &str) -> Result<VisibilityFilter> {
+    VisibilityFilter::parse(value).ok_or_else(|| {
+        anyhow::anyhow!(
+            "Unknown --visibility '{}' (expected pub, crate or private)",
+            value
+        )
+    })
+}
+
+/// Helper to parse a `--column-unit` value
+pub fn parse_column_unit(value: &str) -> Result<ColumnUnit> {
+    ColumnUnit::parse(value).ok_or_else(|| {
+        anyhow::anyhow!(
+            "Unknown --column-unit '{}' (expected byte, char or utf16)",
+            value
+        )
+    })
+}
+
 /// Helper to parse output format from string
 ///
 /// Accepts: "human", "json", "pretty"
@@ -44,6 +84,108 @@ pub fn parse_path_arg(args: &[String], i: &mut usize, flag: &str) -> Result<Path
     Ok(PathBuf::from(value))
 }
 
+/// Helper to parse the SQLite tuning flags shared by `watch` and `index`
+///
+/// Handles `--journal-mode`, `--synchronous`, `--busy-timeout` and `--busy-retries`. Returns
+/// `Ok(false)` (without advancing) when `args[*i]` is not one of them.
+pub fn parse_sqlite_option(
+    args: &[String],
+    i: &mut usize,
+    options: &mut SqliteOptions,
+) -> Result<bool> {
+    match args[*i].as_str() {
+        "--journal-mode" => {
+            let value = parse_required_arg(args, i, "--journal-mode")?;
+            options.journal_mode = value.parse().map_err(|e: String| anyhow::anyhow!(e))?;
+        }
+        "--synchronous" => {
+            let value = parse_required_arg(args, i, "--synchronous")?;
+            options.synchronous = value.parse().map_err(|e: String| anyhow::anyhow!(e))?;
+        }
+        "--busy-timeout" => {
+            let value = parse_required_arg(args, i, "--busy-timeout")?;
+            options.busy_timeout_ms = value.parse().map_err(|_| {
+                anyhow::anyhow!("--busy-timeout must be milliseconds, got {}", value)
+            })?;
+        }
+        "--busy-retries" => {
+            let value = parse_required_arg(args, i, "--busy-retries")?;
+            options.busy_retries = value.parse().map_err(|_| {
+                anyhow::anyhow!(
+                    "--busy-retries must be a non-negative integer, got {}",
+                    value
+                )
+            })?;
+        }
+        _ => return Ok(false),
+    }
+    Ok(true)
+}
+
+/// Helper to parse the path style flags shared by `watch` and `index`
+///
+/// Handles `--relative-paths` and `--absolute-paths`, rejecting both on one
+/// command line. Returns `Ok(false)` (without advancing) when `args[*i]` is
+/// not one of them.
+pub fn parse_path_style_option(
+    args: &[String],
+    i: &mut usize,
+    style: &mut Option<PathStyle>,
+) -> Result<bool> {
+    let requested = match args[*i].as_str() {
+        "--relative-paths" => PathStyle::Relative,
+        "--absolute-paths" => PathStyle::Absolute,
+        _ => return Ok(false),
+    };
+    if style.is_some_and(|current| current != requested) {
+        return Err(anyhow::anyhow!(
+            "--relative-paths and --absolute-paths are mutually exclusive"
+        ));
+    }
+    *style = Some(requested);
+    *i += 1;
+    Ok(true)
+}
+
+/// Helper to parse the hash normalization flags shared by `watch` and `index`
+///
+/// Handles `--normalize-eol` and `--normalize-trailing-whitespace`. Returns
+/// `Ok(false)` (without advancing) when `args[*i]` is not one of them.
+pub fn parse_hash_normalization_option(
+    args: &[String],
+    i: &mut usize,
+    normalization: &mut HashNormalization,
+) -> Result<bool> {
+    match args[*i].as_str() {
+        "--normalize-eol" => normalization.eol = true,
+        "--normalize-trailing-whitespace" => normalization.trailing_whitespace = true,
+        _ => return Ok(false),
+    }
+    *i += 1;
+    Ok(true)
+}
+
+/// Helper to parse `--hash-algo`, shared by `watch` and `index`
+///
+/// Returns `Ok(false)` (without advancing) when `args[*i]` is not `--hash-algo`.
+pub fn parse_hash_algo_option(
+    args: &[String],
+    i: &mut usize,
+    algorithm: &mut Option<HashAlgorithm>,
+) -> Result<bool> {
+    if args[*i] != "--hash-algo" {
+        return Ok(false);
+    }
+    let value = parse_required_arg(args, i, "--hash-algo")?;
+    *algorithm = Some(HashAlgorithm::parse(&value).ok_or_else(|| {
+        anyhow::anyhow!(
+            "Unknown --hash-algo '{}' (expected xxh3, blake3 or sha256)",
+            value
+        )
+    })?);
+    Ok(true)
+}
+
 /// Parse comma-separated DB paths or discover .db files in a directory
 pub fn parse_db_paths(value: &str) -> Result<Vec<PathBuf>> {
     let path = PathBuf::from(value);
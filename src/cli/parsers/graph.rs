@@ -1,8 +1,10 @@
 use crate::cli::Command;
 use anyhow::Result;
+use magellan::ingest::test_code::TestFilter;
 use magellan::OutputFormat;
 use std::path::PathBuf;
 
+use crate::cli::parsers::{parse_output_format, set_test_filter};
 use crate::db_resolver::resolve_db_path;
 
 // ============================================================================
@@ -19,6 +21,9 @@ pub fn parse_ast_args(args: &[String]) -> Result<Command> {
     let mut file_path: Option<String> = None;
     let mut position: Option<usize> = None;
     let mut output_format = OutputFormat::Human;
+    let mut subtree = false;
+    let mut max_depth: Option<usize> = None;
+    let mut query: Option<String> = None;
 
     let mut i = 0;
     while i < args.len() {
@@ -44,6 +49,24 @@ pub fn parse_ast_args(args: &[String]) -> Result<Command> {
                 position = Some(args[i + 1].parse()?);
                 i += 2;
             }
+            "--subtree" => {
+                subtree = true;
+                i += 1;
+            }
+            "--query" => {
+                if i + 1 >= args.len() {
+                    return Err(anyhow::anyhow!("--query requires an argument"));
+                }
+                query = Some(args[i + 1].clone());
+                i += 2;
+            }
+            "--max-depth" => {
+                if i + 1 >= args.len() {
+                    return Err(anyhow::anyhow!("--max-depth requires an argument"));
+                }
+                max_depth = Some(args[i + 1].parse()?);
+                i += 2;
+            }
             "--output" => {
                 if i + 1 >= args.len() {
                     return Err(anyhow::anyhow!("--output requires an argument"));
@@ -68,11 +91,80 @@ pub fn parse_ast_args(args: &[String]) -> Result<Command> {
     let db_path = resolve_db_path(db_path)?;
     let file_path = file_path.ok_or_else(|| anyhow::anyhow!("--file is required"))?;
 
+    if subtree && position.is_none() {
+        return Err(anyhow::anyhow!("--subtree requires --position"));
+    }
+    if max_depth.is_some() && !subtree {
+        return Err(anyhow::anyhow!("--max-depth requires --subtree"));
+    }
+    if query.is_some() && position.is_some() {
+        return Err(anyhow::anyhow!(
+            "--query runs over the whole file and cannot be combined with --position"
+        ));
+    }
+    let subtree_depth =
+        subtree.then(|| max_depth.unwrap_or(magellan::graph::DEFAULT_SUBTREE_DEPTH));
+
     Ok(Command::Ast {
         db_path,
         file_path,
         position,
         output_format,
+        subtree_depth,
+        query,
+    })
+}
+
+/// Parse the `parse` command arguments
+pub fn parse_parse_args(args: &[String]) -> Result<Command> {
+    let mut lang: Option<String> = None;
+    let mut file_path: Option<PathBuf> = None;
+    let mut from_stdin = false;
+    let mut output_format = OutputFormat::Json;
+
+    let mut i = 0;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--lang" => {
+                if i + 1 >= args.len() {
+                    return Err(anyhow::anyhow!("--lang requires an argument"));
+                }
+                lang = Some(args[i + 1].clone());
+                i += 2;
+            }
+            "--file" | "--path" => {
+                if i + 1 >= args.len() {
+                    return Err(anyhow::anyhow!("--file requires an argument"));
+                }
+                file_path = Some(PathBuf::from(&args[i + 1]));
+                i += 2;
+            }
+            "--stdin" => {
+                from_stdin = true;
+                i += 1;
+            }
+            "--output" => {
+                if i + 1 >= args.len() {
+                    return Err(anyhow::anyhow!(
+                        "--output requires an argument (human|json|pretty)"
+                    ));
+                }
+                output_format = parse_output_format(&args[i + 1])?;
+                i += 2;
+            }
+            _ => return Err(anyhow::anyhow!("Unknown argument: {}", args[i])),
+        }
+    }
+
+    if !from_stdin && file_path.is_none() {
+        return Err(anyhow::anyhow!("Either --stdin or --file is required"));
+    }
+
+    Ok(Command::Parse {
+        lang,
+        file_path,
+        from_stdin,
+        output_format,
     })
 }
 
@@ -136,6 +228,8 @@ pub fn parse_reachable_args(args: &[String]) -> Result<Command> {
     let mut symbol_id: Option<String> = None;
     let mut reverse = false;
     let mut output_format = OutputFormat::Human;
+    let mut dot = false;
+    let mut max_depth: Option<usize> = None;
 
     let mut i = 0;
     while i < args.len() {
@@ -158,17 +252,32 @@ pub fn parse_reachable_args(args: &[String]) -> Result<Command> {
                 reverse = true;
                 i += 1;
             }
-            "--output" => {
+            "--max-depth" | "--depth-limit" => {
                 if i + 1 >= args.len() {
-                    return Err(anyhow::anyhow!("--output requires an argument"));
+                    return Err(anyhow::anyhow!("{} requires an argument", args[i]));
+                }
+                let depth = args[i + 1]
+                    .parse()
+                    .map_err(|_| anyhow::anyhow!("{} must be a non-negative integer", args[i]))?;
+                max_depth = Some(depth);
+                i += 2;
+            }
+            "--output" | "--format" => {
+                if i + 1 >= args.len() {
+                    return Err(anyhow::anyhow!("{} requires an argument", args[i]));
                 }
+                dot = false;
                 output_format = match args[i + 1].as_str() {
                     "human" => OutputFormat::Human,
                     "json" => OutputFormat::Json,
                     "pretty" => OutputFormat::Pretty,
+                    "dot" => {
+                        dot = true;
+                        OutputFormat::Human
+                    }
                     _ => {
                         return Err(anyhow::anyhow!(
-                            "Invalid output format: {}. Must be human, json, or pretty",
+                            "Invalid output format: {}. Must be human, json, pretty, or dot",
                             args[i + 1]
                         ))
                     }
@@ -186,6 +295,8 @@ pub fn parse_reachable_args(args: &[String]) -> Result<Command> {
         db_path,
         symbol_id,
         reverse,
+        dot,
+        max_depth,
         output_format,
     })
 }
@@ -194,6 +305,7 @@ pub fn parse_reachable_args(args: &[String]) -> Result<Command> {
 pub fn parse_dead_code_args(args: &[String]) -> Result<Command> {
     let mut db_path: Option<PathBuf> = None;
     let mut entry_symbol_id: Option<String> = None;
+    let mut test_filter = TestFilter::All;
     let mut output_format = OutputFormat::Human;
 
     let mut i = 0;
@@ -213,6 +325,10 @@ pub fn parse_dead_code_args(args: &[String]) -> Result<Command> {
                 entry_symbol_id = Some(args[i + 1].clone());
                 i += 2;
             }
+            "--only-test-files" | "--ignore-test-files" => {
+                set_test_filter(&mut test_filter, &args[i])?;
+                i += 1;
+            }
             "--json" => {
                 output_format = OutputFormat::Json;
                 i += 1;
@@ -244,6 +360,7 @@ pub fn parse_dead_code_args(args: &[String]) -> Result<Command> {
     Ok(Command::DeadCode {
         db_path,
         entry_symbol_id,
+        test_filter,
         output_format,
     })
 }
@@ -309,6 +426,7 @@ pub fn parse_cycles_args(args: &[String]) -> Result<Command> {
 pub fn parse_condense_args(args: &[String]) -> Result<Command> {
     let mut db_path: Option<PathBuf> = None;
     let mut show_members = false;
+    let mut min_scc_size: Option<usize> = None;
     let mut output_format = OutputFormat::Human;
 
     let mut i = 0;
@@ -325,6 +443,19 @@ pub fn parse_condense_args(args: &[String]) -> Result<Command> {
                 show_members = true;
                 i += 1;
             }
+            "--min-scc-size" => {
+                if i + 1 >= args.len() {
+                    return Err(anyhow::anyhow!("--min-scc-size requires an argument"));
+                }
+                let size: usize = args[i + 1]
+                    .parse()
+                    .map_err(|_| anyhow::anyhow!("--min-scc-size must be a number"))?;
+                if size == 0 {
+                    return Err(anyhow::anyhow!("--min-scc-size must be at least 1"));
+                }
+                min_scc_size = Some(size);
+                i += 2;
+            }
             "--output" => {
                 if i + 1 >= args.len() {
                     return Err(anyhow::anyhow!("--output requires an argument"));
@@ -351,6 +482,7 @@ pub fn parse_condense_args(args: &[String]) -> Result<Command> {
     Ok(Command::Condense {
         db_path,
         show_members,
+        min_scc_size,
         output_format,
     })
 }
@@ -362,6 +494,8 @@ pub fn parse_paths_args(args: &[String]) -> Result<Command> {
     let mut end_symbol_id: Option<String> = None;
     let mut max_depth = 100;
     let mut max_paths = 1000;
+    let mut shortest = false;
+    let mut avoid: Vec<String> = Vec::new();
     let mut output_format = OutputFormat::Human;
 
     let mut i = 0;
@@ -402,6 +536,19 @@ pub fn parse_paths_args(args: &[String]) -> Result<Command> {
                 max_paths = args[i + 1].parse()?;
                 i += 2;
             }
+            "--shortest" => {
+                shortest = true;
+                i += 1;
+            }
+            "--avoid" => {
+                if i + 1 >= args.len() {
+                    return Err(anyhow::anyhow!("--avoid requires an argument"));
+                }
+                if !avoid.contains(&args[i + 1]) {
+                    avoid.push(args[i + 1].clone());
+                }
+                i += 2;
+            }
             "--output" => {
                 if i + 1 >= args.len() {
                     return Err(anyhow::anyhow!("--output requires an argument"));
@@ -425,6 +572,9 @@ pub fn parse_paths_args(args: &[String]) -> Result<Command> {
 
     let db_path = resolve_db_path(db_path)?;
     let start_symbol_id = start_symbol_id.ok_or_else(|| anyhow::anyhow!("--start is required"))?;
+    if shortest && end_symbol_id.is_none() {
+        return Err(anyhow::anyhow!("--shortest requires --end"));
+    }
 
     Ok(Command::Paths {
         db_path,
@@ -432,6 +582,8 @@ pub fn parse_paths_args(args: &[String]) -> Result<Command> {
         end_symbol_id,
         max_depth,
         max_paths,
+        shortest,
+        avoid,
         output_format,
     })
 }
@@ -442,6 +594,8 @@ pub fn parse_slice_args(args: &[String]) -> Result<Command> {
     let mut target: Option<String> = None;
     let mut direction = "backward".to_string();
     let mut verbose = false;
+    let mut with_code = false;
+    let mut max_bytes: Option<usize> = None;
     let mut output_format = OutputFormat::Human;
 
     let mut i = 0;
@@ -478,6 +632,26 @@ pub fn parse_slice_args(args: &[String]) -> Result<Command> {
                 verbose = true;
                 i += 1;
             }
+            "--with-code" => {
+                with_code = true;
+                i += 1;
+            }
+            "--max-bytes" => {
+                if i + 1 >= args.len() {
+                    return Err(anyhow::anyhow!("--max-bytes requires an argument"));
+                }
+                let value = &args[i + 1];
+                max_bytes = match value.parse::<usize>() {
+                    Ok(max) if max > 0 => Some(max),
+                    _ => {
+                        return Err(anyhow::anyhow!(
+                            "--max-bytes must be a positive number of bytes, got '{}'",
+                            value
+                        ))
+                    }
+                };
+                i += 2;
+            }
             "--output" => {
                 if i + 1 >= args.len() {
                     return Err(anyhow::anyhow!("--output requires an argument"));
@@ -501,12 +675,17 @@ pub fn parse_slice_args(args: &[String]) -> Result<Command> {
 
     let db_path = resolve_db_path(db_path)?;
     let target = target.ok_or_else(|| anyhow::anyhow!("--target is required"))?;
+    if max_bytes.is_some() && !with_code {
+        return Err(anyhow::anyhow!("--max-bytes requires --with-code"));
+    }
 
     Ok(Command::Slice {
         db_path,
         target,
         direction,
         verbose,
+        with_code,
+        max_bytes,
         output_format,
     })
 }
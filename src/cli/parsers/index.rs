@@ -1,13 +1,19 @@
-use crate::cli::{Command, ContextSubcommand};
+use crate::cli::{ChangedPaths, Command, ContextSubcommand};
 use anyhow::{Context, Result};
-use magellan::graph::export::ExportFilters;
+use magellan::graph::export::{ExportFilters, JsonlRecordType};
+use magellan::graph::filter::DEFAULT_NON_SOURCE_EXTENSIONS;
 use magellan::graph::query::CollisionField;
-use magellan::{detect_project_root, ExportFormat, OutputFormat, WatcherConfig};
+use magellan::project_config::{ProjectConfig, WatchSection};
+use magellan::{
+    detect_project_root, format_symbol_kind, parse_symbol_kind_arg, ExportFormat,
+    HashNormalization, OutputFormat, PathStyle, SqliteOptions, WatcherConfig,
+};
 use std::path::PathBuf;
 
 use crate::cli::parsers::*;
 use crate::db_resolver::resolve_db_path;
 use crate::service::registry::Registry;
+use magellan::log_warn;
 
 // ============================================================================
 // Command Parsers - Individual command parsing functions
@@ -46,6 +52,37 @@ pub fn parse_backfill_args(args: &[String]) -> Result<Command> {
     Ok(Command::Backfill { db_path })
 }
 
+/// Parse the `metrics backfill` command arguments
+///
+/// # Arguments
+/// * `args` - The command line arguments (after "metrics backfill")
+pub fn parse_metrics_backfill_args(args: &[String]) -> Result<Command> {
+    let mut db_path: Option<PathBuf> = None;
+    let mut only: Option<String> = None;
+    let mut output_format = OutputFormat::Human;
+
+    let mut i = 0;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--db" => db_path = Some(parse_path_arg(args, &mut i, "--db")?),
+            "--only" => only = Some(parse_required_arg(args, &mut i, "--only")?),
+            "--output" => {
+                let value = parse_required_arg(args, &mut i, "--output")?;
+                output_format = parse_output_format(&value)?;
+            }
+            _ => return Err(anyhow::anyhow!("Unknown argument: {}", args[i])),
+        }
+    }
+
+    let db_path = resolve_db_path(db_path)?;
+
+    Ok(Command::MetricsBackfill {
+        db_path,
+        only,
+        output_format,
+    })
+}
+
 pub fn parse_cross_file_refs_args(args: &[String]) -> Result<Command> {
     let mut db_path: Option<PathBuf> = None;
     let mut fqn: Option<String> = None;
@@ -89,10 +126,23 @@ pub fn parse_cross_file_refs_args(args: &[String]) -> Result<Command> {
 pub fn parse_index_args(args: &[String]) -> Result<Command> {
     let mut db_path: Option<PathBuf> = None;
     let mut file_path: Option<PathBuf> = None;
+    let mut changed: Option<ChangedPaths> = None;
     let mut root: Option<PathBuf> = None;
+    let mut api_only = false;
+    let mut path_style = None;
+    let mut hash_normalization = HashNormalization::default();
+    let mut hash_algo = None;
+    let mut sqlite_options = SqliteOptions::default();
 
     let mut i = 0;
     while i < args.len() {
+        if parse_sqlite_option(args, &mut i, &mut sqlite_options)?
+            || parse_path_style_option(args, &mut i, &mut path_style)?
+            || parse_hash_normalization_option(args, &mut i, &mut hash_normalization)?
+            || parse_hash_algo_option(args, &mut i, &mut hash_algo)?
+        {
+            continue;
+        }
         match args[i].as_str() {
             "--db" => {
                 if i + 1 >= args.len() {
@@ -108,6 +158,20 @@ pub fn parse_index_args(args: &[String]) -> Result<Command> {
                 file_path = Some(PathBuf::from(&args[i + 1]));
                 i += 2;
             }
+            "--changed-from" => {
+                if i + 1 >= args.len() {
+                    return Err(anyhow::anyhow!("--changed-from requires an argument"));
+                }
+                changed = Some(ChangedPaths::GitRef(args[i + 1].clone()));
+                i += 2;
+            }
+            "--files-from" => {
+                if i + 1 >= args.len() {
+                    return Err(anyhow::anyhow!("--files-from requires an argument"));
+                }
+                changed = Some(ChangedPaths::FilesFrom(PathBuf::from(&args[i + 1])));
+                i += 2;
+            }
             "--root" => {
                 if i + 1 >= args.len() {
                     return Err(anyhow::anyhow!("--root requires an argument"));
@@ -115,39 +179,94 @@ pub fn parse_index_args(args: &[String]) -> Result<Command> {
                 root = Some(PathBuf::from(&args[i + 1]));
                 i += 2;
             }
+            "--api-only" => {
+                api_only = true;
+                i += 1;
+            }
             _ => i += 1,
         }
     }
 
     let db_path = resolve_db_path(db_path)?;
-    let file_path = file_path.ok_or_else(|| anyhow::anyhow!("--file is required"))?;
+    match (&file_path, &changed) {
+        (None, None) => {
+            return Err(anyhow::anyhow!(
+                "--file, --changed-from or --files-from is required"
+            ))
+        }
+        (Some(_), Some(_)) => {
+            return Err(anyhow::anyhow!(
+                "--file cannot be combined with --changed-from or --files-from"
+            ))
+        }
+        _ => {}
+    }
 
     Ok(Command::Index {
         db_path,
         file_path,
+        changed,
         root,
+        api_only,
+        path_style,
+        hash_normalization,
+        hash_algo,
+        sqlite_options,
     })
 }
 
 pub fn parse_watch_args(args: &[String]) -> Result<Command> {
-    let mut root_path: Option<PathBuf> = None;
+    let mut root_paths: Vec<PathBuf> = Vec::new();
     let mut db_path: Option<PathBuf> = None;
-    let mut debounce_ms: u64 = 500;
+    let mut config_path: Option<PathBuf> = None;
+    // Flags that also have config-file defaults stay None unless given
+    let mut debounce_ms: Option<u64> = None;
     let mut watch_only = false;
-    let mut scan_initial = true;
-    let mut gitignore_aware = true;
+    let mut scan_initial: Option<bool> = None;
+    let mut gitignore_aware: Option<bool> = None;
+    let mut follow_symlinks: Option<bool> = None;
+    let mut poll = false;
+    let mut poll_interval_ms: Option<u64> = None;
     let mut validate = false;
     let mut validate_only = false;
     let mut compile_commands: Option<PathBuf> = None;
+    let mut api_only = false;
+    let mut compress_chunks = false;
+    let mut no_calls = false;
+    let mut path_style = None;
+    let mut hash_normalization = HashNormalization::default();
+    let mut hash_algo = None;
+    let mut sqlite_options = SqliteOptions::default();
+    let mut max_file_size: Option<u64> = None;
+    let mut parse_timeout_ms: Option<u64> = None;
+    let mut include_patterns: Vec<String> = Vec::new();
+    let mut exclude_patterns: Vec<String> = Vec::new();
+    let mut jobs: Option<usize> = None;
+    let mut once = false;
+    let mut checkpoint_interval: Option<u64> = None;
+    let mut include_generated = false;
+    let mut index_non_source = false;
+    let mut non_source_extensions: Vec<String> = Vec::new();
+    let mut mem_report = false;
+    let mut profile = false;
+    let mut dry_run = false;
+    let mut output_format = OutputFormat::Human;
 
     let mut i = 0;
     while i < args.len() {
+        if parse_sqlite_option(args, &mut i, &mut sqlite_options)?
+            || parse_path_style_option(args, &mut i, &mut path_style)?
+            || parse_hash_normalization_option(args, &mut i, &mut hash_normalization)?
+            || parse_hash_algo_option(args, &mut i, &mut hash_algo)?
+        {
+            continue;
+        }
         match args[i].as_str() {
             "--root" => {
                 if i + 1 >= args.len() {
                     return Err(anyhow::anyhow!("--root requires an argument"));
                 }
-                root_path = Some(PathBuf::from(&args[i + 1]));
+                root_paths.push(PathBuf::from(&args[i + 1]));
                 i += 2;
             }
             "--db" => {
@@ -161,7 +280,14 @@ pub fn parse_watch_args(args: &[String]) -> Result<Command> {
                 if i + 1 >= args.len() {
                     return Err(anyhow::anyhow!("--debounce-ms requires an argument"));
                 }
-                debounce_ms = args[i + 1].parse()?;
+                debounce_ms = Some(args[i + 1].parse()?);
+                i += 2;
+            }
+            "--config" => {
+                if i + 1 >= args.len() {
+                    return Err(anyhow::anyhow!("--config requires an argument"));
+                }
+                config_path = Some(PathBuf::from(&args[i + 1]));
                 i += 2;
             }
             "--watch-only" => {
@@ -169,17 +295,38 @@ pub fn parse_watch_args(args: &[String]) -> Result<Command> {
                 i += 1;
             }
             "--scan-initial" => {
-                scan_initial = true;
+                scan_initial = Some(true);
                 i += 1;
             }
             "--gitignore-aware" => {
-                gitignore_aware = true;
+                gitignore_aware = Some(true);
                 i += 1;
             }
             "--no-gitignore" => {
-                gitignore_aware = false;
+                gitignore_aware = Some(false);
                 i += 1;
             }
+            "--follow-symlinks" => {
+                follow_symlinks = Some(true);
+                i += 1;
+            }
+            "--poll" => {
+                poll = true;
+                i += 1;
+            }
+            "--poll-interval" => {
+                let value = parse_required_arg(args, &mut i, "--poll-interval")?;
+                let interval: u64 = value.parse().map_err(|_| {
+                    anyhow::anyhow!(
+                        "--poll-interval must be a number of milliseconds, got '{}'",
+                        value
+                    )
+                })?;
+                if interval == 0 {
+                    return Err(anyhow::anyhow!("--poll-interval must be at least 1 ms"));
+                }
+                poll_interval_ms = Some(interval);
+            }
             "--validate" => {
                 validate = true;
                 i += 1;
@@ -188,6 +335,18 @@ pub fn parse_watch_args(args: &[String]) -> Result<Command> {
                 validate_only = true;
                 i += 1;
             }
+            "--api-only" => {
+                api_only = true;
+                i += 1;
+            }
+            "--compress-chunks" => {
+                compress_chunks = true;
+                i += 1;
+            }
+            "--no-calls" => {
+                no_calls = true;
+                i += 1;
+            }
 
             "--compile-commands" => {
                 if i + 1 >= args.len() {
@@ -196,42 +355,221 @@ pub fn parse_watch_args(args: &[String]) -> Result<Command> {
                 compile_commands = Some(PathBuf::from(&args[i + 1]));
                 i += 2;
             }
+            "--max-file-size" => {
+                let value = parse_required_arg(args, &mut i, "--max-file-size")?;
+                max_file_size = Some(value.parse().map_err(|_| {
+                    anyhow::anyhow!("--max-file-size must be a number of bytes, got '{}'", value)
+                })?);
+            }
+            "--parse-timeout-ms" => {
+                let value = parse_required_arg(args, &mut i, "--parse-timeout-ms")?;
+                parse_timeout_ms = Some(value.parse().map_err(|_| {
+                    anyhow::anyhow!(
+                        "--parse-timeout-ms must be a number of milliseconds, got '{}'",
+                        value
+                    )
+                })?);
+            }
+            "--include" => {
+                include_patterns.push(parse_required_arg(args, &mut i, "--include")?);
+            }
+            "--exclude" => {
+                exclude_patterns.push(parse_required_arg(args, &mut i, "--exclude")?);
+            }
+            "--jobs" => {
+                let value = parse_required_arg(args, &mut i, "--jobs")?;
+                jobs = match value.parse::<usize>() {
+                    Ok(n) if n > 0 => Some(n),
+                    _ => {
+                        return Err(anyhow::anyhow!(
+                            "--jobs must be a positive integer, got '{}'",
+                            value
+                        ))
+                    }
+                };
+            }
+            "--once" => {
+                once = true;
+                i += 1;
+            }
+            "--include-generated" => {
+                include_generated = true;
+                i += 1;
+            }
+            "--index-non-source" => {
+                index_non_source = true;
+                i += 1;
+            }
+            "--non-source-ext" => {
+                let value = parse_required_arg(args, &mut i, "--non-source-ext")?;
+                for ext in value.split(',') {
+                    non_source_extensions.push(normalize_non_source_extension(ext)?);
+                }
+            }
+            "--mem-report" => {
+                mem_report = true;
+                i += 1;
+            }
+            "--profile" => {
+                profile = true;
+                i += 1;
+            }
+            "--dry-run" => {
+                dry_run = true;
+                i += 1;
+            }
+            "--checkpoint-interval" => {
+                let value = parse_required_arg(args, &mut i, "--checkpoint-interval")?;
+                checkpoint_interval = match value.parse::<u64>() {
+                    Ok(secs) if secs > 0 => Some(secs),
+                    _ => {
+                        return Err(anyhow::anyhow!(
+                            "--checkpoint-interval must be a positive number of seconds, got '{}'",
+                            value
+                        ))
+                    }
+                };
+            }
+            "--output" => {
+                let value = parse_required_arg(args, &mut i, "--output")?;
+                output_format = parse_output_format(&value)?;
+            }
             _ => {
                 return Err(anyhow::anyhow!("Unknown argument: {}", args[i]));
             }
         }
     }
 
-    // Auto-detect project root if not specified
-    let root_path = match root_path {
-        Some(path) => path,
-        None => detect_project_root(),
+    if once && watch_only {
+        return Err(anyhow::anyhow!(
+            "--once cannot be combined with --watch-only"
+        ));
+    }
+    if dry_run && watch_only {
+        return Err(anyhow::anyhow!(
+            "--dry-run previews the initial scan and cannot be combined with --watch-only"
+        ));
+    }
+    if root_paths.len() > 1 {
+        if dry_run {
+            return Err(anyhow::anyhow!("--dry-run previews a single --root"));
+        }
+        if path_style == Some(PathStyle::Relative) {
+            return Err(anyhow::anyhow!(
+                "--relative-paths records a single root and cannot be used with several --root"
+            ));
+        }
+    }
+    let mut root_paths = root_paths.into_iter();
+    let root_path = root_paths.next();
+    let extra_roots: Vec<PathBuf> = root_paths.collect();
+
+    // Config file: explicit --config, else discovered in the CLI or detected root
+    let config_path = config_path.or_else(|| {
+        let search_root = root_path.clone().unwrap_or_else(detect_project_root);
+        ProjectConfig::discover(&search_root)
+    });
+    let file_config = match config_path {
+        Some(ref path) => {
+            let (file_config, unknown_keys) = ProjectConfig::load_from_path(path)?;
+            for key in unknown_keys {
+                log_warn!(
+                    "unknown config key '{}' in {} (ignored)",
+                    key,
+                    path.display()
+                );
+            }
+            file_config.watch
+        }
+        None => WatchSection::default(),
     };
 
-    // Require --db argument (like other commands)
-    let db_path = resolve_db_path(db_path)?;
-
-    if watch_only {
-        scan_initial = false;
+    // Precedence: CLI flag > config file > built-in default (the section defaults)
+    let root_path = root_path
+        .or(file_config.root)
+        .unwrap_or_else(detect_project_root);
+    let db_path = resolve_db_path(db_path.or(file_config.db))?;
+    // `--once` is only the scan, so it always scans
+    let scan_initial = once || (!watch_only && scan_initial.unwrap_or(file_config.scan_initial));
+    if mem_report && !scan_initial {
+        return Err(anyhow::anyhow!(
+            "--mem-report reports on the initial scan and cannot be used without one"
+        ));
     }
 
+    // `--non-source-ext` replaces the config list, and either one implies `--index-non-source`
+    let non_source_extensions = if !non_source_extensions.is_empty() {
+        non_source_extensions
+    } else if !file_config.non_source_extensions.is_empty() {
+        file_config
+            .non_source_extensions
+            .iter()
+            .map(|ext| normalize_non_source_extension(ext))
+            .collect::<Result<Vec<_>>>()?
+    } else if index_non_source {
+        DEFAULT_NON_SOURCE_EXTENSIONS
+            .iter()
+            .map(|ext| ext.to_string())
+            .collect()
+    } else {
+        Vec::new()
+    };
+
     let config = WatcherConfig {
         root_path: root_path.clone(),
-        debounce_ms,
-        gitignore_aware,
+        debounce_ms: debounce_ms.unwrap_or(file_config.debounce_ms),
+        gitignore_aware: gitignore_aware.unwrap_or(file_config.gitignore_aware),
+        follow_symlinks: follow_symlinks.unwrap_or(file_config.follow_symlinks),
+        // `--poll-interval` implies `--poll`
+        poll_interval_ms: poll_interval_ms
+            .or(poll.then_some(magellan::watcher::DEFAULT_POLL_INTERVAL_MS)),
+        non_source_extensions,
     };
 
     Ok(Command::Watch {
         root_path,
+        extra_roots,
         db_path,
         config,
         scan_initial,
         validate,
         validate_only,
         compile_commands,
+        api_only,
+        compress_chunks,
+        no_calls,
+        path_style,
+        hash_normalization,
+        hash_algo,
+        sqlite_options,
+        config_path,
+        max_file_size,
+        parse_timeout_ms,
+        include_patterns,
+        exclude_patterns,
+        jobs,
+        once,
+        checkpoint_interval,
+        include_generated,
+        mem_report,
+        profile,
+        dry_run,
+        output_format,
     })
 }
 
+/// Extension for `--non-source-ext`, lowercase and without a leading dot
+fn normalize_non_source_extension(ext: &str) -> Result<String> {
+    let ext = ext.trim().trim_start_matches('.');
+    if ext.is_empty() || ext.contains(['/', '\\', '.']) {
+        return Err(anyhow::anyhow!(
+            "Invalid non-source extension '{}': expected a file extension such as 'proto'",
+            ext
+        ));
+    }
+    Ok(ext.to_lowercase())
+}
+
 /// Parse the `export` command arguments
 pub fn parse_export_args(args: &[String]) -> Result<Command> {
     let mut db_path: Option<PathBuf> = None;
@@ -241,8 +579,11 @@ pub fn parse_export_args(args: &[String]) -> Result<Command> {
     let mut include_references = true;
     let mut include_calls = true;
     let mut minify = false;
+    let mut gzip = false;
     let mut include_collisions = false;
     let mut collisions_field = CollisionField::Fqn;
+    let mut include_unresolved = false;
+    let mut record_types: Vec<JsonlRecordType> = Vec::new();
     let mut filters = ExportFilters::default();
     let mut impact_symbol = None;
     let mut impact_file = None;
@@ -264,12 +605,14 @@ pub fn parse_export_args(args: &[String]) -> Result<Command> {
                 }
                 format = match args[i + 1].as_str() {
                     "json" => ExportFormat::Json,
-                    "jsonl" => ExportFormat::JsonL,
+                    "jsonl" | "ndjson" => ExportFormat::JsonL,
                     "csv" => ExportFormat::Csv,
                     "scip" => ExportFormat::Scip,
                     "dot" => ExportFormat::Dot,
                     "lsif" => ExportFormat::Lsif,
                     "impact" => ExportFormat::Impact,
+                    "sarif" => ExportFormat::Sarif,
+                    "sqlite-flat" => ExportFormat::SqliteFlat,
                     _ => return Err(anyhow::anyhow!("Invalid format: {}", args[i + 1])),
                 };
                 i += 2;
@@ -297,6 +640,10 @@ pub fn parse_export_args(args: &[String]) -> Result<Command> {
                 minify = true;
                 i += 1;
             }
+            "--gzip" => {
+                gzip = true;
+                i += 1;
+            }
             "--include-collisions" => {
                 include_collisions = true;
                 i += 1;
@@ -313,6 +660,25 @@ pub fn parse_export_args(args: &[String]) -> Result<Command> {
                 };
                 i += 2;
             }
+            "--include-unresolved" => {
+                include_unresolved = true;
+                i += 1;
+            }
+            "--type" => {
+                if i + 1 >= args.len() {
+                    return Err(anyhow::anyhow!("--type requires an argument"));
+                }
+                let record_type = JsonlRecordType::parse(&args[i + 1]).ok_or_else(|| {
+                    anyhow::anyhow!(
+                        "Invalid --type: {} (expected symbols, calls, references or files)",
+                        args[i + 1]
+                    )
+                })?;
+                if !record_types.contains(&record_type) {
+                    record_types.push(record_type);
+                }
+                i += 2;
+            }
             "--filter-file" | "--file" => {
                 if i + 1 >= args.len() {
                     return Err(anyhow::anyhow!("--filter-file requires an argument"));
@@ -324,13 +690,32 @@ pub fn parse_export_args(args: &[String]) -> Result<Command> {
                 if i + 1 >= args.len() {
                     return Err(anyhow::anyhow!("--filter-kind requires an argument"));
                 }
-                filters.kind = Some(args[i + 1].clone());
+                let parsed = parse_symbol_kind_arg(&args[i + 1])?;
+                let kind = format_symbol_kind(&parsed).to_string();
+                if !filters.kinds.contains(&kind) {
+                    filters.kinds.push(kind);
+                }
+                i += 2;
+            }
+            "--exclude-kind" => {
+                if i + 1 >= args.len() {
+                    return Err(anyhow::anyhow!("--exclude-kind requires an argument"));
+                }
+                let parsed = parse_symbol_kind_arg(&args[i + 1])?;
+                let kind = format_symbol_kind(&parsed).to_string();
+                if !filters.exclude_kinds.contains(&kind) {
+                    filters.exclude_kinds.push(kind);
+                }
                 i += 2;
             }
-            "--cluster" => {
+            "--cluster" | "--cluster-by-file" => {
                 filters.cluster = true;
                 i += 1;
             }
+            "--calls-only" => {
+                filters.calls_only = true;
+                i += 1;
+            }
             "--symbol" => {
                 if i + 1 >= args.len() {
                     return Err(anyhow::anyhow!("--symbol requires an argument"));
@@ -360,6 +745,32 @@ pub fn parse_export_args(args: &[String]) -> Result<Command> {
         }
     }
 
+    if format == ExportFormat::SqliteFlat {
+        if output.is_none() {
+            return Err(anyhow::anyhow!(
+                "--format sqlite-flat requires --output <PATH>"
+            ));
+        }
+        if gzip {
+            return Err(anyhow::anyhow!(
+                "--gzip cannot be combined with --format sqlite-flat"
+            ));
+        }
+    }
+
+    if !record_types.is_empty() {
+        if format != ExportFormat::JsonL {
+            return Err(anyhow::anyhow!(
+                "--type requires --format jsonl (or ndjson)"
+            ));
+        }
+        if !(include_symbols && include_references && include_calls) {
+            return Err(anyhow::anyhow!(
+                "--type cannot be combined with --no-symbols, --no-references or --no-calls"
+            ));
+        }
+    }
+
     let db_path = resolve_db_path(db_path)?;
 
     Ok(Command::Export {
@@ -370,8 +781,11 @@ pub fn parse_export_args(args: &[String]) -> Result<Command> {
         include_references,
         include_calls,
         minify,
+        gzip,
         include_collisions,
         collisions_field,
+        include_unresolved,
+        record_types,
         filters,
         impact_symbol,
         impact_file,
@@ -1,11 +1,16 @@
 use crate::cli::Command;
 use anyhow::{Context, Result};
 use magellan::graph::query::CollisionField;
-use magellan::OutputFormat;
+use magellan::ingest::test_code::TestFilter;
+use magellan::ingest::visibility::VisibilityFilter;
+use magellan::output::ColumnUnit;
+use magellan::{format_symbol_kind, parse_symbol_kind_arg, OutputFormat};
 use std::path::PathBuf;
 
+use crate::check_cmd::CheckCategory;
 use crate::cli::parsers::*;
 use crate::db_resolver::resolve_db_path;
+use crate::get_cmd::ExpandTo;
 use crate::service::registry::Registry;
 
 // ============================================================================
@@ -16,6 +21,9 @@ use crate::service::registry::Registry;
 // Query Parsers
 // ============================================================================
 
+/// Default number of ranked results for `find --fuzzy`
+const DEFAULT_FIND_LIMIT: usize = 20;
+
 /// Parse the `find` command arguments
 pub fn parse_find_args(args: &[String]) -> Result<Command> {
     let mut db_path: Option<PathBuf> = None;
@@ -34,7 +42,19 @@ pub fn parse_find_args(args: &[String]) -> Result<Command> {
     let mut with_callees = false;
     let mut with_semantics = false;
     let mut with_checksums = false;
+    let mut with_metrics = false;
     let mut context_lines: usize = 3;
+    let mut count_only = false;
+    let mut fuzzy: Option<String> = None;
+    let mut fuzzy_fqn = false;
+    let mut limit: Option<usize> = None;
+    let mut test_filter = TestFilter::All;
+    let mut visibility: Option<VisibilityFilter> = None;
+    let mut deprecated = false;
+    let mut defined_in: Option<String> = None;
+    let mut kind: Option<String> = None;
+    let mut near: Option<(PathBuf, usize)> = None;
+    let mut column_unit = ColumnUnit::Byte;
 
     let mut i = 0;
     while i < args.len() {
@@ -135,6 +155,10 @@ pub fn parse_find_args(args: &[String]) -> Result<Command> {
                 with_checksums = true;
                 i += 1;
             }
+            "--with-metrics" => {
+                with_metrics = true;
+                i += 1;
+            }
             "--context-lines" => {
                 if i + 1 >= args.len() {
                     return Err(anyhow::anyhow!("--context-lines requires an argument"));
@@ -148,10 +172,208 @@ pub fn parse_find_args(args: &[String]) -> Result<Command> {
                 }
                 i += 2;
             }
+            "--count-only" => {
+                count_only = true;
+                i += 1;
+            }
+            "--fuzzy" => {
+                fuzzy = Some(parse_required_arg(args, &mut i, "--fuzzy")?);
+            }
+            "--fuzzy-fqn" => {
+                fuzzy_fqn = true;
+                i += 1;
+            }
+            "--limit" => {
+                if i + 1 >= args.len() {
+                    return Err(anyhow::anyhow!("--limit requires an argument"));
+                }
+                limit = Some(
+                    args[i + 1]
+                        .parse()
+                        .ok()
+                        .filter(|n| *n > 0)
+                        .ok_or_else(|| anyhow::anyhow!("--limit must be a positive integer"))?,
+                );
+                i += 2;
+            }
+            "--only-test-files" | "--ignore-test-files" => {
+                set_test_filter(&mut test_filter, &args[i])?;
+                i += 1;
+            }
+            "--visibility" => {
+                let value = parse_required_arg(args, &mut i, "--visibility")?;
+                visibility = Some(parse_visibility_filter(&value)?);
+            }
+            "--deprecated" => {
+                deprecated = true;
+                i += 1;
+            }
+            "--column-unit" => {
+                let value = parse_required_arg(args, &mut i, "--column-unit")?;
+                column_unit = parse_column_unit(&value)?;
+            }
+            "--defined-in" => {
+                defined_in = Some(parse_required_arg(args, &mut i, "--defined-in")?);
+            }
+            "--kind" => {
+                let value = parse_required_arg(args, &mut i, "--kind")?;
+                kind = Some(format_symbol_kind(&parse_symbol_kind_arg(&value)?).to_string());
+            }
+            "--near" => {
+                let value = parse_required_arg(args, &mut i, "--near")?;
+                near = Some(parse_near_location(&value)?);
+            }
             _ => return Err(anyhow::anyhow!("Unknown argument: {}", args[i])),
         }
     }
 
+    if deprecated {
+        let conflicting = [
+            ("--glob", glob_pattern.is_some()),
+            ("--symbol-id", symbol_id.is_some()),
+            ("--ambiguous", ambiguous_name.is_some()),
+            ("--fuzzy", fuzzy.is_some()),
+            ("--defined-in", defined_in.is_some()),
+            ("--near", near.is_some()),
+            ("--count-only", count_only),
+            ("--all", all),
+            ("--with-context", with_context),
+            ("--with-callers", with_callers),
+            ("--with-callees", with_callees),
+            ("--with-semantics", with_semantics),
+            ("--with-checksums", with_checksums),
+            ("--with-metrics", with_metrics),
+        ];
+        if let Some((flag, _)) = conflicting.iter().find(|(_, set)| *set) {
+            return Err(anyhow::anyhow!(
+                "--deprecated cannot be combined with {}",
+                flag
+            ));
+        }
+    }
+
+    if near.is_some() {
+        let conflicting = [
+            ("--name", name.is_some()),
+            ("--path", path.is_some()),
+            ("--glob", glob_pattern.is_some()),
+            ("--symbol-id", symbol_id.is_some()),
+            ("--ambiguous", ambiguous_name.is_some()),
+            ("--fuzzy", fuzzy.is_some()),
+            ("--defined-in", defined_in.is_some()),
+            ("--count-only", count_only),
+            ("--all", all),
+            ("--with-context", with_context),
+            ("--with-callers", with_callers),
+            ("--with-callees", with_callees),
+            ("--with-semantics", with_semantics),
+            ("--with-checksums", with_checksums),
+            ("--with-metrics", with_metrics),
+        ];
+        if let Some((flag, _)) = conflicting.iter().find(|(_, set)| *set) {
+            return Err(anyhow::anyhow!("--near cannot be combined with {}", flag));
+        }
+    } else if defined_in.is_some() {
+        let conflicting = [
+            ("--name", name.is_some()),
+            ("--glob", glob_pattern.is_some()),
+            ("--symbol-id", symbol_id.is_some()),
+            ("--ambiguous", ambiguous_name.is_some()),
+            ("--fuzzy", fuzzy.is_some()),
+            ("--count-only", count_only),
+            ("--all", all),
+            ("--with-context", with_context),
+            ("--with-callers", with_callers),
+            ("--with-callees", with_callees),
+            ("--with-semantics", with_semantics),
+            ("--with-checksums", with_checksums),
+            ("--with-metrics", with_metrics),
+        ];
+        if let Some((flag, _)) = conflicting.iter().find(|(_, set)| *set) {
+            return Err(anyhow::anyhow!(
+                "--defined-in cannot be combined with {}",
+                flag
+            ));
+        }
+    } else if kind.is_some() {
+        return Err(anyhow::anyhow!("--kind requires --defined-in or --near"));
+    }
+
+    if fuzzy.is_some() {
+        let conflicting = [
+            ("--name", name.is_some()),
+            ("--glob", glob_pattern.is_some()),
+            ("--symbol-id", symbol_id.is_some()),
+            ("--ambiguous", ambiguous_name.is_some()),
+            ("--count-only", count_only),
+            ("--all", all),
+        ];
+        if let Some((flag, _)) = conflicting.iter().find(|(_, set)| *set) {
+            return Err(anyhow::anyhow!("--fuzzy cannot be combined with {}", flag));
+        }
+    } else if fuzzy_fqn {
+        return Err(anyhow::anyhow!("--fuzzy-fqn requires --fuzzy"));
+    } else if limit.is_some() && near.is_none() {
+        return Err(anyhow::anyhow!("--limit requires --fuzzy or --near"));
+    }
+
+    if count_only {
+        let conflicting = [
+            ("--with-context", with_context),
+            ("--with-callers", with_callers),
+            ("--with-callees", with_callees),
+            ("--with-semantics", with_semantics),
+            ("--with-checksums", with_checksums),
+            ("--with-metrics", with_metrics),
+            ("--glob", glob_pattern.is_some()),
+            ("--symbol-id", symbol_id.is_some()),
+            ("--ambiguous", ambiguous_name.is_some()),
+            ("--all", all),
+        ];
+        if let Some((flag, _)) = conflicting.iter().find(|(_, set)| *set) {
+            return Err(anyhow::anyhow!(
+                "--count-only cannot be combined with {}",
+                flag
+            ));
+        }
+    }
+
+    if test_filter != TestFilter::All {
+        let conflicting = [
+            ("--glob", glob_pattern.is_some()),
+            ("--symbol-id", symbol_id.is_some()),
+            ("--ambiguous", ambiguous_name.is_some()),
+            ("--all", all),
+        ];
+        if let Some((flag, _)) = conflicting.iter().find(|(_, set)| *set) {
+            return Err(anyhow::anyhow!(
+                "--only-test-files/--ignore-test-files cannot be combined with {}",
+                flag
+            ));
+        }
+    }
+
+    if visibility.is_some() {
+        let conflicting = [
+            ("--glob", glob_pattern.is_some()),
+            ("--symbol-id", symbol_id.is_some()),
+            ("--ambiguous", ambiguous_name.is_some()),
+            ("--all", all),
+        ];
+        if let Some((flag, _)) = conflicting.iter().find(|(_, set)| *set) {
+            return Err(anyhow::anyhow!(
+                "--visibility cannot be combined with {}",
+                flag
+            ));
+        }
+    }
+
+    if all && column_unit != ColumnUnit::Byte {
+        return Err(anyhow::anyhow!(
+            "--column-unit cannot be combined with --all"
+        ));
+    }
+
     if let Some(ref name) = project {
         let registry =
             Registry::load().context("Failed to load project registry for --project resolution")?;
@@ -178,11 +400,39 @@ pub fn parse_find_args(args: &[String]) -> Result<Command> {
         with_callees,
         with_semantics,
         with_checksums,
+        with_metrics,
         context_lines,
         all,
+        count_only,
+        fuzzy,
+        fuzzy_fqn,
+        limit: limit.unwrap_or(DEFAULT_FIND_LIMIT),
+        test_filter,
+        visibility,
+        defined_in,
+        kind,
+        near,
+        column_unit,
+        deprecated,
     })
 }
 
+/// Parse a `--near <FILE>:<LINE>` location
+///
+/// Splits on the last `:`, so Windows drive letters stay in the path.
+fn parse_near_location(value: &str) -> Result<(PathBuf, usize)> {
+    let (file, line) = value
+        .rsplit_once(':')
+        .filter(|(file, _)| !file.is_empty())
+        .ok_or_else(|| anyhow::anyhow!("--near expects <FILE>:<LINE>, got '{}'", value))?;
+    let line = line
+        .parse()
+        .ok()
+        .filter(|n| *n > 0)
+        .ok_or_else(|| anyhow::anyhow!("--near line must be a positive integer: '{}'", line))?;
+    Ok((PathBuf::from(file), line))
+}
+
 // ============================================================================
 // Main Argument Parser
 // ============================================================================
@@ -192,11 +442,13 @@ pub fn parse_get_args(args: &[String]) -> Result<Command> {
     let mut db_path: Option<PathBuf> = None;
     let mut file_path: Option<String> = None;
     let mut symbol_name: Option<String> = None;
+    let mut symbol_id: Option<String> = None;
     let mut output_format = OutputFormat::Human;
     let mut with_context = false;
     let mut with_semantics = false;
     let mut with_checksums = false;
     let mut context_lines = 3;
+    let mut expand_to = ExpandTo::Symbol;
 
     let mut i = 0;
     while i < args.len() {
@@ -222,6 +474,9 @@ pub fn parse_get_args(args: &[String]) -> Result<Command> {
                 symbol_name = Some(args[i + 1].clone());
                 i += 2;
             }
+            "--symbol-id" => {
+                symbol_id = Some(parse_required_arg(args, &mut i, "--symbol-id")?);
+            }
             "--output" => {
                 if i + 1 >= args.len() {
                     return Err(anyhow::anyhow!("--output requires an argument"));
@@ -258,11 +513,34 @@ pub fn parse_get_args(args: &[String]) -> Result<Command> {
                 }
                 i += 2;
             }
+            "--expand-to" => {
+                let value = parse_required_arg(args, &mut i, "--expand-to")?;
+                expand_to = ExpandTo::parse(&value)?;
+            }
             _ => return Err(anyhow::anyhow!("Unknown argument: {}", args[i])),
         }
     }
 
     let db_path = resolve_db_path(db_path)?;
+
+    if let Some(symbol_id) = symbol_id {
+        if file_path.is_some() || symbol_name.is_some() {
+            return Err(anyhow::anyhow!(
+                "--symbol-id cannot be combined with --file or --symbol"
+            ));
+        }
+        return Ok(Command::GetBySymbolId {
+            db_path,
+            symbol_id,
+            output_format,
+            with_context,
+            with_semantics,
+            with_checksums,
+            context_lines,
+            expand_to,
+        });
+    }
+
     let file_path = file_path.ok_or_else(|| anyhow::anyhow!("--file is required"))?;
     let symbol_name = symbol_name.ok_or_else(|| anyhow::anyhow!("--symbol is required"))?;
 
@@ -275,6 +553,7 @@ pub fn parse_get_args(args: &[String]) -> Result<Command> {
         with_semantics,
         with_checksums,
         context_lines,
+        expand_to,
     })
 }
 
@@ -282,6 +561,8 @@ pub fn parse_get_args(args: &[String]) -> Result<Command> {
 pub fn parse_get_file_args(args: &[String]) -> Result<Command> {
     let mut db_path: Option<PathBuf> = None;
     let mut file_path: Option<String> = None;
+    let mut outline = false;
+    let mut line_range: Option<(usize, usize)> = None;
     let mut output_format = OutputFormat::Human;
 
     let mut i = 0;
@@ -289,6 +570,14 @@ pub fn parse_get_file_args(args: &[String]) -> Result<Command> {
         match args[i].as_str() {
             "--db" => db_path = Some(parse_path_arg(args, &mut i, "--db")?),
             "--file" => file_path = Some(parse_required_arg(args, &mut i, "--file")?),
+            "--outline" => {
+                outline = true;
+                i += 1;
+            }
+            "--line-range" => {
+                let value = parse_required_arg(args, &mut i, "--line-range")?;
+                line_range = Some(parse_line_range(&value)?);
+            }
             "--output" => {
                 if i + 1 >= args.len() {
                     return Err(anyhow::anyhow!("--output requires an argument"));
@@ -302,14 +591,49 @@ pub fn parse_get_file_args(args: &[String]) -> Result<Command> {
 
     let db_path = resolve_db_path(db_path)?;
     let file_path = file_path.ok_or_else(|| anyhow::anyhow!("--file is required"))?;
+    if outline && line_range.is_some() {
+        return Err(anyhow::anyhow!(
+            "--line-range cannot be combined with --outline"
+        ));
+    }
 
     Ok(Command::GetFile {
         db_path,
         file_path,
+        outline,
+        line_range,
         output_format,
     })
 }
 
+/// Parse a `START:END` line range (1-indexed, inclusive)
+fn parse_line_range(value: &str) -> Result<(usize, usize)> {
+    let (start, end) = value
+        .split_once(':')
+        .ok_or_else(|| anyhow::anyhow!("--line-range must be START:END, got '{}'", value))?;
+    let parse = |part: &str| {
+        part.trim()
+            .parse::<usize>()
+            .ok()
+            .filter(|line| *line >= 1)
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "--line-range lines must be positive integers, got '{}'",
+                    value
+                )
+            })
+    };
+    let (start, end) = (parse(start)?, parse(end)?);
+    if start > end {
+        return Err(anyhow::anyhow!(
+            "--line-range start {} is after end {}",
+            start,
+            end
+        ));
+    }
+    Ok((start, end))
+}
+
 /// Parse the `refs` command arguments
 pub fn parse_refs_args(args: &[String]) -> Result<Command> {
     let mut db_path: Option<PathBuf> = None;
@@ -325,6 +649,9 @@ pub fn parse_refs_args(args: &[String]) -> Result<Command> {
     let mut context_lines = 3;
     let mut all = false;
     let mut tokens: Option<usize> = None;
+    let mut group_by_file = false;
+    let mut since: Option<String> = None;
+    let mut column_unit = ColumnUnit::Byte;
 
     let mut i = 0;
     while i < args.len() {
@@ -432,10 +759,33 @@ pub fn parse_refs_args(args: &[String]) -> Result<Command> {
                 );
                 i += 2;
             }
+            "--group-by-file" => {
+                group_by_file = true;
+                i += 1;
+            }
+            "--since" => since = Some(parse_required_arg(args, &mut i, "--since")?),
+            "--column-unit" => {
+                let value = parse_required_arg(args, &mut i, "--column-unit")?;
+                column_unit = parse_column_unit(&value)?;
+            }
             _ => return Err(anyhow::anyhow!("Unknown argument: {}", args[i])),
         }
     }
 
+    if all && group_by_file {
+        return Err(anyhow::anyhow!(
+            "--group-by-file cannot be combined with --all"
+        ));
+    }
+    if all && since.is_some() {
+        return Err(anyhow::anyhow!("--since cannot be combined with --all"));
+    }
+    if all && column_unit != ColumnUnit::Byte {
+        return Err(anyhow::anyhow!(
+            "--column-unit cannot be combined with --all"
+        ));
+    }
+
     let db_path = if !all {
         resolve_db_path(db_path)?
     } else {
@@ -458,6 +808,9 @@ pub fn parse_refs_args(args: &[String]) -> Result<Command> {
         context_lines,
         all,
         tokens,
+        group_by_file,
+        since,
+        column_unit,
     })
 }
 
@@ -465,6 +818,8 @@ pub fn parse_refs_args(args: &[String]) -> Result<Command> {
 pub fn parse_verify_args(args: &[String]) -> Result<Command> {
     let mut root_path: Option<PathBuf> = None;
     let mut db_path: Option<PathBuf> = None;
+    let mut integrity = false;
+    let mut overlaps = false;
     let mut output_format = OutputFormat::Human;
 
     let mut i = 0;
@@ -484,6 +839,14 @@ pub fn parse_verify_args(args: &[String]) -> Result<Command> {
                 db_path = Some(PathBuf::from(&args[i + 1]));
                 i += 2;
             }
+            "--integrity" => {
+                integrity = true;
+                i += 1;
+            }
+            "--overlaps" => {
+                overlaps = true;
+                i += 1;
+            }
             "--output" => {
                 if i + 1 >= args.len() {
                     return Err(anyhow::anyhow!(
@@ -497,12 +860,215 @@ pub fn parse_verify_args(args: &[String]) -> Result<Command> {
         }
     }
 
-    let root_path = root_path.ok_or_else(|| anyhow::anyhow!("--root is required"))?;
+    if root_path.is_none() && !integrity && !overlaps {
+        return Err(anyhow::anyhow!(
+            "--root is required unless --integrity or --overlaps is used"
+        ));
+    }
     let db_path = resolve_db_path(db_path)?;
 
     Ok(Command::Verify {
         root_path,
         db_path,
+        integrity,
+        overlaps,
+        output_format,
+    })
+}
+
+/// Parse the `verify-ids` command arguments
+pub fn parse_verify_ids_args(args: &[String]) -> Result<Command> {
+    let mut root_path: Option<PathBuf> = None;
+    let mut db_path: Option<PathBuf> = None;
+    let mut output_format = OutputFormat::Human;
+
+    let mut i = 0;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--root" => {
+                if i + 1 >= args.len() {
+                    return Err(anyhow::anyhow!("--root requires an argument"));
+                }
+                root_path = Some(PathBuf::from(&args[i + 1]));
+                i += 2;
+            }
+            "--db" => {
+                if i + 1 >= args.len() {
+                    return Err(anyhow::anyhow!("--db requires an argument"));
+                }
+                db_path = Some(PathBuf::from(&args[i + 1]));
+                i += 2;
+            }
+            "--output" => {
+                if i + 1 >= args.len() {
+                    return Err(anyhow::anyhow!(
+                        "--output requires an argument (human|json|pretty)"
+                    ));
+                }
+                output_format = parse_output_format(&args[i + 1])?;
+                i += 2;
+            }
+            _ => return Err(anyhow::anyhow!("Unknown argument: {}", args[i])),
+        }
+    }
+
+    let root_path = root_path.ok_or_else(|| anyhow::anyhow!("--root is required"))?;
+    let db_path = resolve_db_path(db_path)?;
+
+    Ok(Command::VerifyIds {
+        root_path,
+        db_path,
+        output_format,
+    })
+}
+
+/// Parse the `check` command arguments
+pub fn parse_check_args(args: &[String]) -> Result<Command> {
+    let mut db_path: Option<PathBuf> = None;
+    let mut root_path: Option<PathBuf> = None;
+    let mut fail_on: Vec<CheckCategory> = Vec::new();
+    let mut output_format = OutputFormat::Human;
+
+    let mut i = 0;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--db" => {
+                if i + 1 >= args.len() {
+                    return Err(anyhow::anyhow!("--db requires an argument"));
+                }
+                db_path = Some(PathBuf::from(&args[i + 1]));
+                i += 2;
+            }
+            "--root" => {
+                if i + 1 >= args.len() {
+                    return Err(anyhow::anyhow!("--root requires an argument"));
+                }
+                root_path = Some(PathBuf::from(&args[i + 1]));
+                i += 2;
+            }
+            "--fail-on" => {
+                if i + 1 >= args.len() {
+                    return Err(anyhow::anyhow!(
+                        "--fail-on requires an argument (collisions,unresolved,stale)"
+                    ));
+                }
+                for value in args[i + 1].split(',').map(str::trim) {
+                    let category = CheckCategory::parse(value)?;
+                    if !fail_on.contains(&category) {
+                        fail_on.push(category);
+                    }
+                }
+                i += 2;
+            }
+            "--output" => {
+                if i + 1 >= args.len() {
+                    return Err(anyhow::anyhow!(
+                        "--output requires an argument (human|json|pretty)"
+                    ));
+                }
+                output_format = parse_output_format(&args[i + 1])?;
+                i += 2;
+            }
+            _ => return Err(anyhow::anyhow!("Unknown argument: {}", args[i])),
+        }
+    }
+
+    if fail_on.is_empty() {
+        return Err(anyhow::anyhow!("--fail-on is required"));
+    }
+    if fail_on.contains(&CheckCategory::Stale) && root_path.is_none() {
+        return Err(anyhow::anyhow!("--fail-on stale requires --root"));
+    }
+    let db_path = resolve_db_path(db_path)?;
+
+    Ok(Command::Check {
+        db_path,
+        root_path,
+        fail_on,
+        output_format,
+    })
+}
+
+/// Parse the `api-diff` command arguments
+pub fn parse_api_diff_args(args: &[String]) -> Result<Command> {
+    let mut db_a: Option<PathBuf> = None;
+    let mut db_b: Option<PathBuf> = None;
+    let mut output_format = OutputFormat::Human;
+
+    let mut i = 0;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--db-a" => {
+                if i + 1 >= args.len() {
+                    return Err(anyhow::anyhow!("--db-a requires an argument"));
+                }
+                db_a = Some(PathBuf::from(&args[i + 1]));
+                i += 2;
+            }
+            "--db-b" => {
+                if i + 1 >= args.len() {
+                    return Err(anyhow::anyhow!("--db-b requires an argument"));
+                }
+                db_b = Some(PathBuf::from(&args[i + 1]));
+                i += 2;
+            }
+            "--output" => {
+                if i + 1 >= args.len() {
+                    return Err(anyhow::anyhow!(
+                        "--output requires an argument (human|json|pretty)"
+                    ));
+                }
+                output_format = parse_output_format(&args[i + 1])?;
+                i += 2;
+            }
+            _ => return Err(anyhow::anyhow!("Unknown argument: {}", args[i])),
+        }
+    }
+
+    let db_a = db_a.ok_or_else(|| anyhow::anyhow!("--db-a is required"))?;
+    let db_b = db_b.ok_or_else(|| anyhow::anyhow!("--db-b is required"))?;
+
+    Ok(Command::ApiDiff {
+        db_a,
+        db_b,
+        output_format,
+    })
+}
+
+/// Parse the `diff` command arguments
+pub fn parse_diff_args(args: &[String]) -> Result<Command> {
+    let mut old_db: Option<PathBuf> = None;
+    let mut new_db: Option<PathBuf> = None;
+    let mut output_format = OutputFormat::Human;
+
+    let mut i = 0;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--old" => {
+                old_db = Some(PathBuf::from(parse_required_arg(args, &mut i, "--old")?));
+            }
+            "--new" => {
+                new_db = Some(PathBuf::from(parse_required_arg(args, &mut i, "--new")?));
+            }
+            "--output" => {
+                if i + 1 >= args.len() {
+                    return Err(anyhow::anyhow!(
+                        "--output requires an argument (human|json|pretty)"
+                    ));
+                }
+                output_format = parse_output_format(&args[i + 1])?;
+                i += 2;
+            }
+            _ => return Err(anyhow::anyhow!("Unknown argument: {}", args[i])),
+        }
+    }
+
+    let old_db = old_db.ok_or_else(|| anyhow::anyhow!("--old is required"))?;
+    let new_db = new_db.ok_or_else(|| anyhow::anyhow!("--new is required"))?;
+
+    Ok(Command::Diff {
+        old_db,
+        new_db,
         output_format,
     })
 }
@@ -647,6 +1213,8 @@ pub fn parse_collisions_args(args: &[String]) -> Result<Command> {
     let mut db_path: Option<PathBuf> = None;
     let mut field = CollisionField::Fqn;
     let mut limit = 100;
+    let mut canonicalize_generics = false;
+    let mut resolve_suggestions = false;
     let mut output_format = OutputFormat::Human;
 
     let mut i = 0;
@@ -683,6 +1251,14 @@ pub fn parse_collisions_args(args: &[String]) -> Result<Command> {
                 limit = args[i + 1].parse()?;
                 i += 2;
             }
+            "--canonicalize-generics" => {
+                canonicalize_generics = true;
+                i += 1;
+            }
+            "--resolve-suggestions" => {
+                resolve_suggestions = true;
+                i += 1;
+            }
             "--output" => {
                 if i + 1 >= args.len() {
                     return Err(anyhow::anyhow!("--output requires an argument"));
@@ -710,6 +1286,8 @@ pub fn parse_collisions_args(args: &[String]) -> Result<Command> {
         db_path,
         field,
         limit,
+        canonicalize_generics,
+        resolve_suggestions,
         output_format,
     })
 }
@@ -776,6 +1354,7 @@ pub fn parse_migrate_backend_args(args: &[String]) -> Result<Command> {
     let mut output_db: Option<PathBuf> = None;
     let mut export_dir: Option<PathBuf> = None;
     let mut dry_run = false;
+    let mut resume = false;
     let mut output_format = OutputFormat::Human;
 
     let mut i = 0;
@@ -806,6 +1385,10 @@ pub fn parse_migrate_backend_args(args: &[String]) -> Result<Command> {
                 dry_run = true;
                 i += 1;
             }
+            "--resume" => {
+                resume = true;
+                i += 1;
+            }
             "--format" => {
                 // Legacy alias for --output
                 if i + 1 >= args.len() {
@@ -830,12 +1413,18 @@ pub fn parse_migrate_backend_args(args: &[String]) -> Result<Command> {
 
     let input_db = input_db.ok_or_else(|| anyhow::anyhow!("--input is required"))?;
     let output_db = output_db.ok_or_else(|| anyhow::anyhow!("--output is required"))?;
+    if resume && dry_run {
+        return Err(anyhow::anyhow!(
+            "--resume cannot be combined with --dry-run"
+        ));
+    }
 
     Ok(Command::MigrateBackend {
         input_db,
         output_db,
         export_dir,
         dry_run,
+        resume,
         output_format,
     })
 }
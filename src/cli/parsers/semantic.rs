@@ -1,6 +1,10 @@
 use crate::cli::Command;
 use anyhow::{Context, Result};
-use magellan::OutputFormat;
+use magellan::graph::query::{SymbolSort, SymbolSortKey};
+use magellan::ingest::test_code::TestFilter;
+use magellan::ingest::visibility::VisibilityFilter;
+use magellan::output::ColumnUnit;
+use magellan::{format_symbol_kind, parse_symbol_kind_arg, OutputFormat};
 use std::path::PathBuf;
 
 use crate::cli::parsers::*;
@@ -30,7 +34,17 @@ pub fn parse_query_args(args: &[String]) -> Result<Command> {
     let mut with_callees = false;
     let mut with_semantics = false;
     let mut with_checksums = false;
+    let mut with_metrics = false;
     let mut context_lines = 3;
+    let mut count_only = false;
+    let mut sort_key: Option<SymbolSortKey> = None;
+    let mut test_filter = TestFilter::All;
+    let mut visibility: Option<VisibilityFilter> = None;
+    let mut reverse = false;
+    let mut defined_in: Option<String> = None;
+    let mut column_unit = ColumnUnit::Byte;
+    let mut baseline: Option<PathBuf> = None;
+    let mut changed_only = false;
 
     let mut i = 0;
     while i < args.len() {
@@ -60,7 +74,8 @@ pub fn parse_query_args(args: &[String]) -> Result<Command> {
                 if i + 1 >= args.len() {
                     return Err(anyhow::anyhow!("--kind requires an argument"));
                 }
-                kind = Some(args[i + 1].clone());
+                let parsed = parse_symbol_kind_arg(&args[i + 1])?;
+                kind = Some(format_symbol_kind(&parsed).to_string());
                 i += 2;
             }
             "--explain" => {
@@ -119,6 +134,10 @@ pub fn parse_query_args(args: &[String]) -> Result<Command> {
                 with_checksums = true;
                 i += 1;
             }
+            "--with-metrics" => {
+                with_metrics = true;
+                i += 1;
+            }
             "--context-lines" => {
                 if i + 1 >= args.len() {
                     return Err(anyhow::anyhow!("--context-lines requires an argument"));
@@ -132,10 +151,129 @@ pub fn parse_query_args(args: &[String]) -> Result<Command> {
                 }
                 i += 2;
             }
+            "--count-only" => {
+                count_only = true;
+                i += 1;
+            }
+            "--sort" => {
+                if i + 1 >= args.len() {
+                    return Err(anyhow::anyhow!("--sort requires an argument"));
+                }
+                sort_key = Some(SymbolSortKey::parse(&args[i + 1]).ok_or_else(|| {
+                    anyhow::anyhow!(
+                        "Invalid sort key: {}. Must be name, line, or kind",
+                        args[i + 1]
+                    )
+                })?);
+                i += 2;
+            }
+            "--reverse" => {
+                reverse = true;
+                i += 1;
+            }
+            "--only-test-files" | "--ignore-test-files" => {
+                set_test_filter(&mut test_filter, &args[i])?;
+                i += 1;
+            }
+            "--visibility" => {
+                let value = parse_required_arg(args, &mut i, "--visibility")?;
+                visibility = Some(parse_visibility_filter(&value)?);
+            }
+            "--column-unit" => {
+                let value = parse_required_arg(args, &mut i, "--column-unit")?;
+                column_unit = parse_column_unit(&value)?;
+            }
+            "--defined-in" => {
+                defined_in = Some(parse_required_arg(args, &mut i, "--defined-in")?);
+            }
+            "--baseline" => {
+                baseline = Some(PathBuf::from(parse_required_arg(
+                    args,
+                    &mut i,
+                    "--baseline",
+                )?));
+            }
+            "--changed-only" => {
+                changed_only = true;
+                i += 1;
+            }
             _ => return Err(anyhow::anyhow!("Unknown argument: {}", args[i])),
         }
     }
 
+    if changed_only && baseline.is_none() {
+        return Err(anyhow::anyhow!("--changed-only requires --baseline <DB>"));
+    }
+    if baseline.is_some() {
+        if file_path.is_none() {
+            return Err(anyhow::anyhow!("--baseline requires --file <PATH>"));
+        }
+        let conflicting = [
+            ("--defined-in", defined_in.is_some()),
+            ("--count-only", count_only),
+            ("--show-extent", show_extent),
+            ("--explain", explain),
+            ("--symbol", symbol.is_some()),
+            ("--with-context", with_context),
+            ("--with-callers", with_callers),
+            ("--with-callees", with_callees),
+            ("--with-semantics", with_semantics),
+            ("--with-checksums", with_checksums),
+            ("--with-metrics", with_metrics),
+            ("--sort", sort_key.is_some()),
+            ("--reverse", reverse),
+            ("--column-unit", column_unit != ColumnUnit::Byte),
+            ("--visibility", visibility.is_some()),
+            (
+                "--only-test-files/--ignore-test-files",
+                test_filter != TestFilter::All,
+            ),
+        ];
+        if let Some((flag, _)) = conflicting.iter().find(|(_, set)| *set) {
+            return Err(anyhow::anyhow!(
+                "--baseline cannot be combined with {}",
+                flag
+            ));
+        }
+    }
+
+    if defined_in.is_some() {
+        let conflicting = [("--show-extent", show_extent), ("--count-only", count_only)];
+        if let Some((flag, _)) = conflicting.iter().find(|(_, set)| *set) {
+            return Err(anyhow::anyhow!(
+                "--defined-in cannot be combined with {}",
+                flag
+            ));
+        }
+    }
+
+    // --reverse alone flips the default (line) order
+    let sort = (sort_key.is_some() || reverse).then(|| SymbolSort {
+        key: sort_key.unwrap_or(SymbolSortKey::Line),
+        reverse,
+    });
+
+    if count_only {
+        let conflicting = [
+            ("--with-context", with_context),
+            ("--with-callers", with_callers),
+            ("--with-callees", with_callees),
+            ("--with-semantics", with_semantics),
+            ("--with-checksums", with_checksums),
+            ("--with-metrics", with_metrics),
+            ("--show-extent", show_extent),
+            ("--explain", explain),
+            ("--sort", sort_key.is_some()),
+            ("--reverse", reverse),
+        ];
+        if let Some((flag, _)) = conflicting.iter().find(|(_, set)| *set) {
+            return Err(anyhow::anyhow!(
+                "--count-only cannot be combined with {}",
+                flag
+            ));
+        }
+    }
+
     let db_path = resolve_db_path(db_path)?;
 
     Ok(Command::Query {
@@ -152,7 +290,16 @@ pub fn parse_query_args(args: &[String]) -> Result<Command> {
         with_callees,
         with_semantics,
         with_checksums,
+        with_metrics,
         context_lines,
+        count_only,
+        sort,
+        test_filter,
+        visibility,
+        defined_in,
+        column_unit,
+        baseline,
+        changed_only,
     })
 }
 
@@ -163,6 +310,8 @@ pub fn parse_chunks_args(args: &[String]) -> Result<Command> {
     let mut limit: Option<usize> = None;
     let mut file_filter: Option<String> = None;
     let mut kind_filter: Option<String> = None;
+    let mut verify = false;
+    let mut root: Option<PathBuf> = None;
 
     let mut i = 0;
     while i < args.len() {
@@ -209,13 +358,31 @@ pub fn parse_chunks_args(args: &[String]) -> Result<Command> {
                 if i + 1 >= args.len() {
                     return Err(anyhow::anyhow!("--kind requires an argument"));
                 }
-                kind_filter = Some(args[i + 1].clone());
+                // Chunks store the normalized key ("fn", "struct", ...)
+                let parsed = parse_symbol_kind_arg(&args[i + 1])?;
+                kind_filter = Some(parsed.normalized_key().to_string());
                 i += 2;
             }
+            "--verify" => {
+                verify = true;
+                i += 1;
+            }
+            "--root" => {
+                root = Some(parse_path_arg(args, &mut i, "--root")?);
+            }
             _ => return Err(anyhow::anyhow!("Unknown argument: {}", args[i])),
         }
     }
 
+    if verify && (limit.is_some() || file_filter.is_some() || kind_filter.is_some()) {
+        return Err(anyhow::anyhow!(
+            "--verify checks every chunk and cannot be combined with --limit, --file or --kind"
+        ));
+    }
+    if root.is_some() && !verify {
+        return Err(anyhow::anyhow!("--root requires --verify"));
+    }
+
     let db_path = resolve_db_path(db_path)?;
 
     Ok(Command::Chunks {
@@ -224,6 +391,8 @@ pub fn parse_chunks_args(args: &[String]) -> Result<Command> {
         limit,
         file_filter,
         kind_filter,
+        verify,
+        root,
     })
 }
 
@@ -306,6 +475,8 @@ pub fn parse_chunk_by_symbol_args(args: &[String]) -> Result<Command> {
     let mut db_path: Option<PathBuf> = None;
     let mut symbol_name: Option<String> = None;
     let mut file_filter: Option<String> = None;
+    let mut all_occurrences = false;
+    let mut limit: Option<usize> = None;
     let mut output_format = OutputFormat::Human;
 
     let mut i = 0;
@@ -318,6 +489,23 @@ pub fn parse_chunk_by_symbol_args(args: &[String]) -> Result<Command> {
                 db_path = Some(PathBuf::from(&args[i + 1]));
                 i += 2;
             }
+            "--all-occurrences" => {
+                all_occurrences = true;
+                i += 1;
+            }
+            "--limit" => {
+                if i + 1 >= args.len() {
+                    return Err(anyhow::anyhow!("--limit requires an argument"));
+                }
+                limit = Some(
+                    args[i + 1]
+                        .parse()
+                        .ok()
+                        .filter(|n| *n > 0)
+                        .ok_or_else(|| anyhow::anyhow!("--limit must be a positive integer"))?,
+                );
+                i += 2;
+            }
             "--symbol" => {
                 if i + 1 >= args.len() {
                     return Err(anyhow::anyhow!("--symbol requires an argument"));
@@ -355,11 +543,17 @@ pub fn parse_chunk_by_symbol_args(args: &[String]) -> Result<Command> {
 
     let db_path = resolve_db_path(db_path)?;
     let symbol_name = symbol_name.ok_or_else(|| anyhow::anyhow!("--symbol is required"))?;
+    if all_occurrences && file_filter.is_some() {
+        return Err(anyhow::anyhow!(
+            "--all-occurrences cannot be combined with --file"
+        ));
+    }
 
     Ok(Command::ChunkBySymbol {
         db_path,
         symbol_name,
         file_filter,
+        limit,
         output_format,
     })
 }
@@ -1,5 +1,6 @@
 use crate::cli::Command;
 use anyhow::Result;
+use magellan::diagnostics::{set_verbosity, Verbosity};
 use magellan::OutputFormat;
 use std::path::PathBuf;
 
@@ -22,11 +23,18 @@ use crate::db_resolver::resolve_db_path;
 ///
 /// The version display is handled via a closure passed in to avoid
 /// circular dependencies with the version module.
+///
+/// Global `-q/--quiet` and `-v/--verbose` flags before the command name
+/// set the process-wide [`Verbosity`] for stderr diagnostics. With
+/// `--expect-schema <N>`, a command whose output schema does not match
+/// fails with [`SchemaMismatch`] before it runs.
 pub fn parse_args_impl<F>(print_version: F) -> Result<Command>
 where
     F: FnOnce(),
 {
-    let args: Vec<String> = std::env::args().collect();
+    let mut args: Vec<String> = std::env::args().collect();
+    let flags = take_global_flags(&mut args)?;
+    set_verbosity(flags.verbosity);
 
     if args.len() < 2 {
         return Err(anyhow::anyhow!("Missing command"));
@@ -52,7 +60,7 @@ where
         std::process::exit(0);
     }
 
-    match command.as_str() {
+    let parsed = match command.as_str() {
         "watch" => parse_watch_args(&args[2..]),
         "backfill" => parse_backfill_args(&args[2..]),
         "cross-file-refs" => parse_cross_file_refs_args(&args[2..]),
@@ -72,7 +80,18 @@ where
         "get" => parse_get_args(&args[2..]),
         "get-file" => parse_get_file_args(&args[2..]),
         "files" => parse_files_args(&args[2..]),
+        "timings" => parse_timings_args(&args[2..]),
+        "implementors" => parse_implementors_args(&args[2..]),
+        "prune" => parse_prune_args(&args[2..]),
+        "checkpoint" => parse_checkpoint_args(&args[2..]),
+        "bench" => parse_bench_args(&args[2..]),
+        "rebase-paths" => parse_rebase_paths_args(&args[2..]),
+        "schema" => parse_schema_args(&args[2..]),
         "verify" => parse_verify_args(&args[2..]),
+        "verify-ids" => parse_verify_ids_args(&args[2..]),
+        "check" => parse_check_args(&args[2..]),
+        "api-diff" => parse_api_diff_args(&args[2..]),
+        "diff" => parse_diff_args(&args[2..]),
         "refresh" => parse_refresh_args(&args[2..]),
         "label" => parse_label_args(&args[2..]),
         "collisions" => parse_collisions_args(&args[2..]),
@@ -87,6 +106,7 @@ where
         "chunk-by-span" => parse_chunk_by_span_args(&args[2..]),
         "chunk-by-symbol" => parse_chunk_by_symbol_args(&args[2..]),
         "ast" => parse_ast_args(&args[2..]),
+        "parse" => parse_parse_args(&args[2..]),
         "find-ast" => parse_find_ast_args(&args[2..]),
         "reachable" => parse_reachable_args(&args[2..]),
         "dead-code" => parse_dead_code_args(&args[2..]),
@@ -110,6 +130,15 @@ where
                 _ => Err(anyhow::anyhow!("Unknown config subcommand: {}", args[2])),
             }
         }
+        "metrics" => {
+            if args.len() < 3 {
+                return Err(anyhow::anyhow!("metrics subcommand required: backfill"));
+            }
+            match args[2].as_str() {
+                "backfill" => parse_metrics_backfill_args(&args[3..]),
+                _ => Err(anyhow::anyhow!("Unknown metrics subcommand: {}", args[2])),
+            }
+        }
         "condense" => parse_condense_args(&args[2..]),
         "init" => parse_project_init_args(&args[2..]),
         "install-hook" => parse_install_hook_args(&args[2..]),
@@ -229,7 +258,106 @@ where
         "features" => parse_features_args(&args[2..]),
         "service-daemon" => Ok(Command::ServiceDaemon),
         _ => Err(anyhow::anyhow!("Unknown command: {}", command)),
+    }?;
+
+    if let Some(expected) = flags.expect_schema {
+        let actual = parsed.schema_version();
+        if !actual.is_some_and(|version| schema_version_matches(&expected, version)) {
+            return Err(SchemaMismatch { expected, actual }.into());
+        }
+    }
+    Ok(parsed)
+}
+
+/// Exit status when `--expect-schema` does not match the command's schema
+pub const EXIT_SCHEMA_MISMATCH: u8 = 3;
+
+/// `--expect-schema` named a version the command does not emit
+#[derive(Debug)]
+pub struct SchemaMismatch {
+    /// Version given to `--expect-schema`
+    pub expected: String,
+    /// Version the command emits, if its output is versioned
+    pub actual: Option<&'static str>,
+}
+
+impl std::fmt::Display for SchemaMismatch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.actual {
+            Some(actual) => write!(
+                f,
+                "schema version mismatch: expected {}, command emits {}",
+                self.expected, actual
+            ),
+            None => write!(
+                f,
+                "schema version mismatch: expected {}, command output is not versioned",
+                self.expected
+            ),
+        }
+    }
+}
+
+impl std::error::Error for SchemaMismatch {}
+
+/// Whether `expected` (`N`, `N.M`, or `N.M.P`) matches the leading
+/// components of `actual`
+pub fn schema_version_matches(expected: &str, actual: &str) -> bool {
+    let mut actual_parts = actual.split('.');
+    expected
+        .split('.')
+        .all(|part| actual_parts.next() == Some(part))
+}
+
+/// Flags accepted before the command name
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct GlobalFlags {
+    /// `-q/--quiet` or `-v/--verbose`
+    pub verbosity: Verbosity,
+    /// `--expect-schema <N>`
+    pub expect_schema: Option<String>,
+}
+
+/// Remove the global flags that precede the command name
+///
+/// `args[0]` is the program name. Only leading flags are global, since
+/// some commands use `-q` or `-v` for their own arguments.
+pub fn take_global_flags(args: &mut Vec<String>) -> Result<GlobalFlags> {
+    let mut flags = GlobalFlags::default();
+    while args.len() > 1 {
+        let level = match args[1].as_str() {
+            "-q" | "--quiet" => Verbosity::Quiet,
+            "-v" | "--verbose" => Verbosity::Verbose,
+            "--expect-schema" => {
+                if args.len() < 3 {
+                    return Err(anyhow::anyhow!("--expect-schema requires an argument"));
+                }
+                let version = args[2].clone();
+                let valid = version.split('.').count() <= 3
+                    && version
+                        .split('.')
+                        .all(|part| !part.is_empty() && part.bytes().all(|b| b.is_ascii_digit()));
+                if !valid {
+                    return Err(anyhow::anyhow!(
+                        "Invalid --expect-schema value: {}. Expected N, N.M, or N.M.P",
+                        version
+                    ));
+                }
+                flags.expect_schema = Some(version);
+                args.drain(1..3);
+                continue;
+            }
+            _ => break,
+        };
+        if flags.verbosity != Verbosity::Normal && flags.verbosity != level {
+            return Err(anyhow::anyhow!(
+                "--quiet and --verbose cannot be used together"
+            ));
+        }
+        flags.verbosity = level;
+        args.remove(1);
     }
+    Ok(flags)
 }
 
 pub fn parse_temporal_sweep_args(args: &[String]) -> Result<Command> {
@@ -503,3 +631,218 @@ pub fn parse_files_args(args: &[String]) -> Result<Command> {
         with_symbols,
     })
 }
+
+pub fn parse_timings_args(args: &[String]) -> Result<Command> {
+    let mut db_path: Option<PathBuf> = None;
+    let mut output_format = OutputFormat::Human;
+    let mut top = 20;
+
+    let mut i = 0;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--db" => db_path = Some(parse_path_arg(args, &mut i, "--db")?),
+            "--output" => {
+                let value = parse_required_arg(args, &mut i, "--output")?;
+                output_format = parse_output_format(&value)?;
+            }
+            "--top" => {
+                let value = parse_required_arg(args, &mut i, "--top")?;
+                top = value.parse()?;
+            }
+            _ => return Err(anyhow::anyhow!("Unknown argument: {}", args[i])),
+        }
+    }
+
+    let db_path = resolve_db_path(db_path)?;
+
+    Ok(Command::Timings {
+        db_path,
+        top,
+        output_format,
+    })
+}
+
+pub fn parse_implementors_args(args: &[String]) -> Result<Command> {
+    let mut db_path: Option<PathBuf> = None;
+    let mut name: Option<String> = None;
+    let mut output_format = OutputFormat::Human;
+
+    let mut i = 0;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--db" => db_path = Some(parse_path_arg(args, &mut i, "--db")?),
+            "--name" => name = Some(parse_required_arg(args, &mut i, "--name")?),
+            "--output" => {
+                let value = parse_required_arg(args, &mut i, "--output")?;
+                output_format = parse_output_format(&value)?;
+            }
+            _ => return Err(anyhow::anyhow!("Unknown argument: {}", args[i])),
+        }
+    }
+
+    let db_path = resolve_db_path(db_path)?;
+    let name = name.ok_or_else(|| anyhow::anyhow!("--name is required"))?;
+
+    Ok(Command::Implementors {
+        db_path,
+        name,
+        output_format,
+    })
+}
+
+pub fn parse_prune_args(args: &[String]) -> Result<Command> {
+    let mut db_path: Option<PathBuf> = None;
+    let mut output_format = OutputFormat::Human;
+    let mut dry_run = false;
+
+    let mut i = 0;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--db" => db_path = Some(parse_path_arg(args, &mut i, "--db")?),
+            "--output" => {
+                let value = parse_required_arg(args, &mut i, "--output")?;
+                output_format = parse_output_format(&value)?;
+            }
+            "--dry-run" => {
+                dry_run = true;
+                i += 1;
+            }
+            _ => return Err(anyhow::anyhow!("Unknown argument: {}", args[i])),
+        }
+    }
+
+    let db_path = resolve_db_path(db_path)?;
+
+    Ok(Command::Prune {
+        db_path,
+        dry_run,
+        output_format,
+    })
+}
+
+pub fn parse_checkpoint_args(args: &[String]) -> Result<Command> {
+    let mut db_path: Option<PathBuf> = None;
+    let mut output_format = OutputFormat::Human;
+
+    let mut i = 0;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--db" => db_path = Some(parse_path_arg(args, &mut i, "--db")?),
+            "--output" => {
+                let value = parse_required_arg(args, &mut i, "--output")?;
+                output_format = parse_output_format(&value)?;
+            }
+            _ => return Err(anyhow::anyhow!("Unknown argument: {}", args[i])),
+        }
+    }
+
+    let db_path = resolve_db_path(db_path)?;
+
+    Ok(Command::Checkpoint {
+        db_path,
+        output_format,
+    })
+}
+
+pub fn parse_bench_args(args: &[String]) -> Result<Command> {
+    let mut root: Option<PathBuf> = None;
+    let mut runs = 1;
+    let mut output_format = OutputFormat::Human;
+
+    let mut i = 0;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--root" => root = Some(parse_path_arg(args, &mut i, "--root")?),
+            "--runs" => {
+                let value = parse_required_arg(args, &mut i, "--runs")?;
+                runs = match value.parse::<usize>() {
+                    Ok(n) if n > 0 => n,
+                    _ => {
+                        return Err(anyhow::anyhow!(
+                            "--runs must be a positive integer, got '{}'",
+                            value
+                        ))
+                    }
+                };
+            }
+            "--output" => {
+                let value = parse_required_arg(args, &mut i, "--output")?;
+                output_format = parse_output_format(&value)?;
+            }
+            _ => return Err(anyhow::anyhow!("Unknown argument: {}", args[i])),
+        }
+    }
+
+    let root = root.ok_or_else(|| anyhow::anyhow!("--root is required"))?;
+
+    Ok(Command::Bench {
+        root,
+        runs,
+        output_format,
+    })
+}
+
+pub fn parse_rebase_paths_args(args: &[String]) -> Result<Command> {
+    let mut db_path: Option<PathBuf> = None;
+    let mut from: Option<String> = None;
+    let mut to: Option<String> = None;
+    let mut output_format = OutputFormat::Human;
+
+    let mut i = 0;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--db" => db_path = Some(parse_path_arg(args, &mut i, "--db")?),
+            "--from" => from = Some(parse_required_arg(args, &mut i, "--from")?),
+            "--to" => to = Some(parse_required_arg(args, &mut i, "--to")?),
+            "--output" => {
+                let value = parse_required_arg(args, &mut i, "--output")?;
+                output_format = parse_output_format(&value)?;
+            }
+            _ => return Err(anyhow::anyhow!("Unknown argument: {}", args[i])),
+        }
+    }
+
+    let db_path = resolve_db_path(db_path)?;
+    let from = from.ok_or_else(|| anyhow::anyhow!("--from is required"))?;
+    let to = to.ok_or_else(|| anyhow::anyhow!("--to is required"))?;
+
+    Ok(Command::RebasePaths {
+        db_path,
+        from,
+        to,
+        output_format,
+    })
+}
+
+pub fn parse_schema_args(args: &[String]) -> Result<Command> {
+    let mut type_name: Option<String> = None;
+    let mut output_format = OutputFormat::Human;
+
+    let mut i = 0;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--type" => {
+                let value = parse_required_arg(args, &mut i, "--type")?;
+                let known = magellan::output::schema::schema_type_names();
+                if !known.contains(&value.as_str()) {
+                    return Err(anyhow::anyhow!(
+                        "Unknown schema type '{}' (expected one of: {})",
+                        value,
+                        known.join(", ")
+                    ));
+                }
+                type_name = Some(value);
+            }
+            "--output" => {
+                let value = parse_required_arg(args, &mut i, "--output")?;
+                output_format = parse_output_format(&value)?;
+            }
+            _ => return Err(anyhow::anyhow!("Unknown argument: {}", args[i])),
+        }
+    }
+
+    Ok(Command::Schema {
+        type_name,
+        output_format,
+    })
+}
@@ -1,6 +1,11 @@
 use super::*;
-use magellan::graph::query::CollisionField;
-use magellan::{ExportFormat, OutputFormat, WatcherConfig};
+use magellan::graph::query::{CollisionField, SymbolSort, SymbolSortKey};
+use magellan::ingest::test_code::TestFilter;
+use magellan::ingest::visibility::VisibilityFilter;
+use magellan::output::ColumnUnit;
+use magellan::{
+    ExportFormat, JournalMode, OutputFormat, PathStyle, SqliteOptions, Synchronous, WatcherConfig,
+};
 use std::path::PathBuf;
 
 /// Test that short usage is ≤25 lines (usability research shows longer help is ignored)
@@ -23,16 +28,40 @@ fn test_parse_watch_command() {
     // Instead, we verify the Command enum structure is correct
     let cmd = Command::Watch {
         root_path: PathBuf::from("."),
+        extra_roots: Vec::new(),
         db_path: PathBuf::from("test.db"),
         config: WatcherConfig {
             root_path: PathBuf::from("."),
             debounce_ms: 500,
             gitignore_aware: true,
+            follow_symlinks: false,
+            poll_interval_ms: None,
+            non_source_extensions: Vec::new(),
         },
         scan_initial: true,
         validate: false,
         validate_only: false,
         compile_commands: None,
+        api_only: false,
+        compress_chunks: false,
+        no_calls: false,
+        path_style: None,
+        hash_normalization: HashNormalization::default(),
+        hash_algo: None,
+        sqlite_options: SqliteOptions::default(),
+        config_path: None,
+        max_file_size: None,
+        parse_timeout_ms: None,
+        include_patterns: Vec::new(),
+        exclude_patterns: Vec::new(),
+        jobs: None,
+        once: false,
+        checkpoint_interval: None,
+        include_generated: false,
+        mem_report: false,
+        profile: false,
+        dry_run: false,
+        output_format: OutputFormat::Human,
     };
 
     // Verify we can construct the command
@@ -65,8 +94,20 @@ fn test_parse_find_command_structure() {
         with_callees: false,
         with_semantics: false,
         with_checksums: false,
+        with_metrics: false,
         context_lines: 3,
         all: false,
+        count_only: false,
+        fuzzy: None,
+        fuzzy_fqn: false,
+        limit: 20,
+        test_filter: TestFilter::All,
+        visibility: None,
+        defined_in: None,
+        kind: None,
+        near: None,
+        column_unit: ColumnUnit::Byte,
+        deprecated: false,
     };
 
     match cmd {
@@ -114,6 +155,481 @@ fn test_parse_watch_args() {
     }
 }
 
+#[test]
+fn test_parse_watch_args_api_only() {
+    let args = vec![
+        "--root".to_string(),
+        "/home/test".to_string(),
+        "--db".to_string(),
+        "test.db".to_string(),
+        "--api-only".to_string(),
+    ];
+
+    match parse_watch_args(&args).unwrap() {
+        Command::Watch { api_only, .. } => assert!(api_only),
+        _ => panic!("Expected Watch command"),
+    }
+}
+
+#[test]
+fn test_parse_watch_args_compress_chunks() {
+    let to_args = |items: &[&str]| -> Vec<String> { items.iter().map(|s| s.to_string()).collect() };
+    let args = to_args(&[
+        "--root",
+        "/home/test",
+        "--db",
+        "test.db",
+        "--compress-chunks",
+    ]);
+    match parse_watch_args(&args).unwrap() {
+        Command::Watch {
+            compress_chunks, ..
+        } => assert!(compress_chunks),
+        _ => panic!("Expected Watch command"),
+    }
+
+    let args = to_args(&["--root", "/home/test", "--db", "test.db"]);
+    match parse_watch_args(&args).unwrap() {
+        Command::Watch {
+            compress_chunks, ..
+        } => assert!(!compress_chunks),
+        _ => panic!("Expected Watch command"),
+    }
+}
+
+#[test]
+fn test_parse_watch_args_no_calls() {
+    let args: Vec<String> = ["--root", "/home/test", "--db", "test.db", "--no-calls"]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+    match parse_watch_args(&args).unwrap() {
+        Command::Watch { no_calls, .. } => assert!(no_calls),
+        _ => panic!("Expected Watch command"),
+    }
+}
+
+#[test]
+fn test_parse_watch_args_poll() {
+    let to_args = |items: &[&str]| -> Vec<String> { items.iter().map(|s| s.to_string()).collect() };
+    let poll_interval = |items: &[&str]| match parse_watch_args(&to_args(items)).unwrap() {
+        Command::Watch { config, .. } => config.poll_interval_ms,
+        _ => panic!("Expected Watch command"),
+    };
+
+    let base = ["--root", "/home/test", "--db", "test.db"];
+    assert_eq!(poll_interval(&base), None);
+    assert_eq!(
+        poll_interval(&[&base[..], &["--poll"]].concat()),
+        Some(magellan::watcher::DEFAULT_POLL_INTERVAL_MS)
+    );
+    // --poll-interval implies --poll
+    assert_eq!(
+        poll_interval(&[&base[..], &["--poll-interval", "750"]].concat()),
+        Some(750)
+    );
+    assert!(parse_watch_args(&to_args(&[&base[..], &["--poll-interval", "0"]].concat())).is_err());
+}
+
+#[test]
+fn test_parse_hash_normalization_flags() {
+    let args: Vec<String> = ["--db", "test.db", "--file", "src/lib.rs", "--normalize-eol"]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+    match parse_index_args(&args).unwrap() {
+        Command::Index {
+            hash_normalization, ..
+        } => {
+            assert!(hash_normalization.eol);
+            assert!(!hash_normalization.trailing_whitespace);
+        }
+        _ => panic!("Expected Index command"),
+    }
+
+    let args: Vec<String> = [
+        "--root",
+        "/home/test",
+        "--db",
+        "test.db",
+        "--normalize-trailing-whitespace",
+    ]
+    .iter()
+    .map(|s| s.to_string())
+    .collect();
+    match parse_watch_args(&args).unwrap() {
+        Command::Watch {
+            hash_normalization, ..
+        } => {
+            assert!(!hash_normalization.eol);
+            assert!(hash_normalization.trailing_whitespace);
+        }
+        _ => panic!("Expected Watch command"),
+    }
+}
+
+#[test]
+fn test_parse_hash_algo_flag() {
+    let to_args = |items: &[&str]| -> Vec<String> { items.iter().map(|s| s.to_string()).collect() };
+    let args = to_args(&[
+        "--db",
+        "test.db",
+        "--file",
+        "src/lib.rs",
+        "--hash-algo",
+        "blake3",
+    ]);
+    match parse_index_args(&args).unwrap() {
+        Command::Index { hash_algo, .. } => assert_eq!(hash_algo, Some(HashAlgorithm::Blake3)),
+        _ => panic!("Expected Index command"),
+    }
+
+    let args = to_args(&["--root", "/home/test", "--db", "test.db"]);
+    match parse_watch_args(&args).unwrap() {
+        Command::Watch { hash_algo, .. } => assert_eq!(hash_algo, None),
+        _ => panic!("Expected Watch command"),
+    }
+
+    let args = to_args(&["--db", "test.db", "--file", "a.rs", "--hash-algo", "md5"]);
+    let err = parse_index_args(&args).unwrap_err().to_string();
+    assert!(err.contains("Unknown --hash-algo 'md5'"), "{}", err);
+}
+
+#[test]
+fn test_parse_path_style_flags() {
+    let base = vec![
+        "--root".to_string(),
+        "/home/test".to_string(),
+        "--db".to_string(),
+        "test.db".to_string(),
+    ];
+
+    match parse_watch_args(&base).unwrap() {
+        Command::Watch { path_style, .. } => assert_eq!(path_style, None),
+        _ => panic!("Expected Watch command"),
+    }
+
+    let mut args = base.clone();
+    args.push("--relative-paths".to_string());
+    match parse_watch_args(&args).unwrap() {
+        Command::Watch { path_style, .. } => assert_eq!(path_style, Some(PathStyle::Relative)),
+        _ => panic!("Expected Watch command"),
+    }
+
+    let index_args = vec![
+        "--db".to_string(),
+        "test.db".to_string(),
+        "--file".to_string(),
+        "src/lib.rs".to_string(),
+        "--absolute-paths".to_string(),
+    ];
+    match parse_index_args(&index_args).unwrap() {
+        Command::Index { path_style, .. } => assert_eq!(path_style, Some(PathStyle::Absolute)),
+        _ => panic!("Expected Index command"),
+    }
+
+    args.push("--absolute-paths".to_string());
+    assert!(parse_watch_args(&args).is_err());
+}
+
+#[test]
+fn test_parse_watch_args_follow_symlinks() {
+    let base = vec![
+        "--root".to_string(),
+        "/home/test".to_string(),
+        "--db".to_string(),
+        "test.db".to_string(),
+    ];
+
+    match parse_watch_args(&base).unwrap() {
+        Command::Watch { config, .. } => assert!(!config.follow_symlinks),
+        _ => panic!("Expected Watch command"),
+    }
+
+    let mut args = base.clone();
+    args.push("--follow-symlinks".to_string());
+    match parse_watch_args(&args).unwrap() {
+        Command::Watch { config, .. } => assert!(config.follow_symlinks),
+        _ => panic!("Expected Watch command"),
+    }
+}
+
+#[test]
+fn test_parse_watch_args_once() {
+    let to_args = |args: &[&str]| -> Vec<String> { args.iter().map(|s| s.to_string()).collect() };
+
+    let args = to_args(&[
+        "--root",
+        "/home/test",
+        "--db",
+        "test.db",
+        "--once",
+        "--exclude",
+        "gen/**",
+        "--exclude",
+        "*.pb.rs",
+        "--include",
+        "src/**",
+        "--jobs",
+        "4",
+    ]);
+    match parse_watch_args(&args).unwrap() {
+        Command::Watch {
+            once,
+            scan_initial,
+            include_patterns,
+            exclude_patterns,
+            jobs,
+            ..
+        } => {
+            assert!(once);
+            assert!(scan_initial, "--once always runs the initial scan");
+            assert_eq!(include_patterns, vec!["src/**".to_string()]);
+            assert_eq!(
+                exclude_patterns,
+                vec!["gen/**".to_string(), "*.pb.rs".to_string()]
+            );
+            assert_eq!(jobs, Some(4));
+        }
+        _ => panic!("Expected Watch command"),
+    }
+
+    let args = to_args(&[
+        "--root",
+        "/home/test",
+        "--db",
+        "test.db",
+        "--once",
+        "--watch-only",
+    ]);
+    assert!(parse_watch_args(&args).is_err());
+
+    let args = to_args(&["--root", "/home/test", "--db", "test.db", "--jobs", "0"]);
+    assert!(parse_watch_args(&args).is_err());
+}
+
+#[test]
+fn test_parse_watch_args_dry_run() {
+    let to_args = |args: &[&str]| -> Vec<String> { args.iter().map(|s| s.to_string()).collect() };
+
+    let args = to_args(&["--root", "/home/test", "--db", "test.db", "--dry-run"]);
+    match parse_watch_args(&args).unwrap() {
+        Command::Watch { dry_run, .. } => assert!(dry_run),
+        _ => panic!("Expected Watch command"),
+    }
+
+    let args = to_args(&[
+        "--root",
+        "/home/test",
+        "--db",
+        "test.db",
+        "--dry-run",
+        "--watch-only",
+    ]);
+    assert!(parse_watch_args(&args).is_err());
+}
+
+#[test]
+fn test_parse_watch_args_multiple_roots() {
+    let to_args = |args: &[&str]| -> Vec<String> { args.iter().map(|s| s.to_string()).collect() };
+
+    let args = to_args(&[
+        "--root",
+        "/ws/core",
+        "--root",
+        "/ws/cli",
+        "--root",
+        "/ws/macros",
+        "--db",
+        "test.db",
+    ]);
+    match parse_watch_args(&args).unwrap() {
+        Command::Watch {
+            root_path,
+            extra_roots,
+            config,
+            ..
+        } => {
+            assert_eq!(root_path, PathBuf::from("/ws/core"));
+            assert_eq!(
+                extra_roots,
+                vec![PathBuf::from("/ws/cli"), PathBuf::from("/ws/macros")]
+            );
+            assert_eq!(config.root_path, PathBuf::from("/ws/core"));
+        }
+        _ => panic!("Expected Watch command"),
+    }
+
+    // A single root leaves no extra roots
+    let args = to_args(&["--root", "/ws/core", "--db", "test.db"]);
+    match parse_watch_args(&args).unwrap() {
+        Command::Watch { extra_roots, .. } => assert!(extra_roots.is_empty()),
+        _ => panic!("Expected Watch command"),
+    }
+
+    for flag in ["--dry-run", "--relative-paths"] {
+        let args = to_args(&[
+            "--root", "/ws/core", "--root", "/ws/cli", "--db", "test.db", flag,
+        ]);
+        assert!(parse_watch_args(&args).is_err(), "{} with two roots", flag);
+    }
+}
+
+#[test]
+fn test_parse_watch_args_config_file_defaults() {
+    let dir = tempfile::TempDir::new().unwrap();
+    std::fs::write(
+        dir.path().join(".magellan.toml"),
+        "[watch]\ndebounce_ms = 1234\ngitignore_aware = false\ndb = \"code.db\"\n",
+    )
+    .unwrap();
+    let root = dir.path().to_string_lossy().to_string();
+
+    // Flag absent: value comes from the discovered config file
+    let args = vec!["--root".to_string(), root.clone()];
+    match parse_watch_args(&args).unwrap() {
+        Command::Watch {
+            db_path,
+            config,
+            config_path,
+            ..
+        } => {
+            assert_eq!(config.debounce_ms, 1234);
+            assert!(!config.gitignore_aware);
+            assert_eq!(db_path, dir.path().join("code.db"));
+            assert_eq!(config_path, Some(dir.path().join(".magellan.toml")));
+        }
+        _ => panic!("Expected Watch command"),
+    }
+
+    // Flags present: CLI wins over the file
+    let args = vec![
+        "--root".to_string(),
+        root,
+        "--db".to_string(),
+        "cli.db".to_string(),
+        "--debounce-ms".to_string(),
+        "50".to_string(),
+    ];
+    match parse_watch_args(&args).unwrap() {
+        Command::Watch {
+            db_path, config, ..
+        } => {
+            assert_eq!(config.debounce_ms, 50);
+            assert_eq!(db_path, PathBuf::from("cli.db"));
+        }
+        _ => panic!("Expected Watch command"),
+    }
+}
+
+#[test]
+fn test_parse_watch_args_explicit_config() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let config = dir.path().join("watch.json");
+    std::fs::write(
+        &config,
+        r#"{"watch": {"debounce_ms": 75, "scan_initial": false}}"#,
+    )
+    .unwrap();
+
+    let args = vec![
+        "--root".to_string(),
+        "/test".to_string(),
+        "--db".to_string(),
+        "test.db".to_string(),
+        "--config".to_string(),
+        config.to_string_lossy().to_string(),
+    ];
+    match parse_watch_args(&args).unwrap() {
+        Command::Watch {
+            config,
+            scan_initial,
+            ..
+        } => {
+            assert_eq!(config.debounce_ms, 75);
+            assert!(!scan_initial);
+        }
+        _ => panic!("Expected Watch command"),
+    }
+
+    let missing = vec![
+        "--db".to_string(),
+        "test.db".to_string(),
+        "--config".to_string(),
+        dir.path().join("nope.toml").to_string_lossy().to_string(),
+    ];
+    assert!(parse_watch_args(&missing).is_err());
+}
+
+#[test]
+fn test_parse_watch_args_sqlite_options() {
+    let base = vec![
+        "--root".to_string(),
+        "/home/test".to_string(),
+        "--db".to_string(),
+        "test.db".to_string(),
+    ];
+
+    match parse_watch_args(&base).unwrap() {
+        Command::Watch { sqlite_options, .. } => {
+            assert_eq!(sqlite_options, SqliteOptions::default())
+        }
+        _ => panic!("Expected Watch command"),
+    }
+
+    let mut args = base.clone();
+    args.extend(
+        [
+            "--journal-mode",
+            "delete",
+            "--synchronous",
+            "full",
+            "--busy-timeout",
+            "250",
+            "--busy-retries",
+            "7",
+        ]
+        .iter()
+        .map(|s| s.to_string()),
+    );
+    match parse_watch_args(&args).unwrap() {
+        Command::Watch { sqlite_options, .. } => {
+            assert_eq!(sqlite_options.journal_mode, JournalMode::Delete);
+            assert_eq!(sqlite_options.synchronous, Synchronous::Full);
+            assert_eq!(sqlite_options.busy_timeout_ms, 250);
+            assert_eq!(sqlite_options.busy_retries, 7);
+        }
+        _ => panic!("Expected Watch command"),
+    }
+
+    let mut bad = base.clone();
+    bad.extend(["--journal-mode".to_string(), "memory".to_string()]);
+    assert!(parse_watch_args(&bad).is_err());
+}
+
+#[test]
+fn test_parse_index_args_sqlite_options() {
+    let args: Vec<String> = [
+        "--db",
+        "test.db",
+        "--file",
+        "src/lib.rs",
+        "--synchronous",
+        "off",
+    ]
+    .iter()
+    .map(|s| s.to_string())
+    .collect();
+
+    match parse_index_args(&args).unwrap() {
+        Command::Index { sqlite_options, .. } => {
+            assert_eq!(sqlite_options.synchronous, Synchronous::Off);
+            assert_eq!(sqlite_options.journal_mode, JournalMode::Wal);
+        }
+        _ => panic!("Expected Index command"),
+    }
+}
+
 #[test]
 fn test_parse_watch_args_missing_required() {
     let args = vec!["--root".to_string(), "/home/test".to_string()];
@@ -150,6 +666,209 @@ fn test_parse_export_args() {
     }
 }
 
+#[test]
+fn test_parse_export_record_types() {
+    let args: Vec<String> = [
+        "--db", "test.db", "--format", "ndjson", "--type", "symbols", "--type", "calls",
+    ]
+    .iter()
+    .map(|s| s.to_string())
+    .collect();
+
+    match parse_export_args(&args).unwrap() {
+        Command::Export {
+            format,
+            record_types,
+            ..
+        } => {
+            assert_eq!(format, ExportFormat::JsonL);
+            assert_eq!(
+                record_types,
+                vec![JsonlRecordType::Symbols, JsonlRecordType::Calls]
+            );
+        }
+        _ => panic!("Expected Export command"),
+    }
+
+    // --type only applies to the JSONL stream
+    let args: Vec<String> = ["--db", "test.db", "--format", "json", "--type", "symbols"]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+    assert!(parse_export_args(&args).is_err());
+}
+
+#[test]
+fn test_parse_export_dot_call_graph_flags() {
+    let args: Vec<String> = [
+        "--db",
+        "test.db",
+        "--format",
+        "dot",
+        "--calls-only",
+        "--cluster-by-file",
+    ]
+    .iter()
+    .map(|s| s.to_string())
+    .collect();
+
+    match parse_export_args(&args).unwrap() {
+        Command::Export {
+            format, filters, ..
+        } => {
+            assert_eq!(format, ExportFormat::Dot);
+            assert!(filters.calls_only);
+            assert!(filters.cluster);
+        }
+        _ => panic!("Expected Export command"),
+    }
+}
+
+#[test]
+fn test_parse_export_kind_filters() {
+    let args: Vec<String> = [
+        "--db",
+        "test.db",
+        "--filter-kind",
+        "fn",
+        "--filter-kind",
+        "struct",
+        "--filter-kind",
+        "function",
+        "--exclude-kind",
+        "mod",
+    ]
+    .iter()
+    .map(|s| s.to_string())
+    .collect();
+
+    match parse_export_args(&args).unwrap() {
+        Command::Export { filters, .. } => {
+            // Aliases resolve to canonical kinds; duplicates collapse
+            assert_eq!(filters.kinds, vec!["Function", "Class"]);
+            assert_eq!(filters.exclude_kinds, vec!["Module"]);
+        }
+        _ => panic!("Expected Export command"),
+    }
+
+    let bad: Vec<String> = ["--db", "test.db", "--exclude-kind", "widget"]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+    assert!(parse_export_args(&bad).is_err());
+}
+
+#[test]
+fn test_parse_export_sqlite_flat() {
+    let to_args = |args: &[&str]| -> Vec<String> { args.iter().map(|s| s.to_string()).collect() };
+
+    let args = to_args(&[
+        "--db",
+        "test.db",
+        "--format",
+        "sqlite-flat",
+        "--output",
+        "flat.db",
+    ]);
+    match parse_export_args(&args).unwrap() {
+        Command::Export { format, output, .. } => {
+            assert_eq!(format, ExportFormat::SqliteFlat);
+            assert_eq!(output, Some(PathBuf::from("flat.db")));
+        }
+        _ => panic!("Expected Export command"),
+    }
+
+    let err = parse_export_args(&to_args(&["--db", "test.db", "--format", "sqlite-flat"]))
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("requires --output"), "{}", err);
+
+    let mut gzip = args.clone();
+    gzip.push("--gzip".to_string());
+    let err = parse_export_args(&gzip).unwrap_err().to_string();
+    assert!(err.contains("--gzip"), "{}", err);
+}
+
+#[test]
+fn test_take_global_flags() {
+    use magellan::diagnostics::Verbosity;
+
+    let to_args = |args: &[&str]| -> Vec<String> { args.iter().map(|s| s.to_string()).collect() };
+
+    let mut args = to_args(&["magellan", "-q", "watch", "--root", "."]);
+    assert_eq!(
+        take_global_flags(&mut args).unwrap().verbosity,
+        Verbosity::Quiet
+    );
+    assert_eq!(args, to_args(&["magellan", "watch", "--root", "."]));
+
+    let mut args = to_args(&["magellan", "--verbose", "--verbose", "index"]);
+    assert_eq!(
+        take_global_flags(&mut args).unwrap().verbosity,
+        Verbosity::Verbose
+    );
+    assert_eq!(args, to_args(&["magellan", "index"]));
+
+    // Only flags before the command are global; `query -q` keeps its meaning
+    let mut args = to_args(&["magellan", "query", "-q", "foo"]);
+    assert_eq!(
+        take_global_flags(&mut args).unwrap(),
+        GlobalFlags::default()
+    );
+    assert_eq!(args.len(), 4);
+
+    let mut args = to_args(&["magellan", "--quiet", "-v", "status"]);
+    assert!(take_global_flags(&mut args).is_err());
+}
+
+#[test]
+fn test_take_global_flags_expect_schema() {
+    let to_args = |args: &[&str]| -> Vec<String> { args.iter().map(|s| s.to_string()).collect() };
+
+    let mut args = to_args(&["magellan", "--expect-schema", "1", "-q", "status"]);
+    let flags = take_global_flags(&mut args).unwrap();
+    assert_eq!(flags.expect_schema.as_deref(), Some("1"));
+    assert_eq!(args, to_args(&["magellan", "status"]));
+
+    for bad in ["v1", "1.", "1.0.0.0", ""] {
+        let mut args = to_args(&["magellan", "--expect-schema", bad, "status"]);
+        assert!(take_global_flags(&mut args).is_err(), "{:?}", bad);
+    }
+    assert!(take_global_flags(&mut to_args(&["magellan", "--expect-schema"])).is_err());
+
+    assert!(schema_version_matches("1", "1.0.0"));
+    assert!(schema_version_matches("1.0", "1.0.0"));
+    assert!(schema_version_matches("1.0.0", "1.0.0"));
+    assert!(!schema_version_matches("1.1", "1.0.0"));
+    assert!(!schema_version_matches("99", "1.0.0"));
+    assert!(!schema_version_matches("10", "1.0.0"));
+}
+
+#[test]
+fn test_schema_version_only_for_json_response_commands() {
+    let to_args = |args: &[&str]| -> Vec<String> { args.iter().map(|s| s.to_string()).collect() };
+
+    let status = parse_status_args(&to_args(&["--db", "a.db"])).unwrap();
+    assert_eq!(status.schema_version(), Some(MAGELLAN_JSON_SCHEMA_VERSION));
+
+    // Hand-built envelopes and watch diagnostics are not versioned
+    let unversioned = [
+        parse_api_diff_args(&to_args(&["--db-a", "a.db", "--db-b", "b.db"])).unwrap(),
+        parse_diff_args(&to_args(&["--old", "a.db", "--new", "b.db"])).unwrap(),
+        parse_verify_ids_args(&to_args(&["--root", ".", "--db", "a.db"])).unwrap(),
+        parse_watch_args(&to_args(&["--root", ".", "--db", "a.db"])).unwrap(),
+    ];
+    for command in &unversioned {
+        assert_eq!(command.schema_version(), None, "{:?}", command);
+    }
+
+    let dry_run = parse_watch_args(&to_args(&["--root", ".", "--db", "a.db", "--dry-run"]));
+    assert_eq!(
+        dry_run.unwrap().schema_version(),
+        Some(MAGELLAN_JSON_SCHEMA_VERSION)
+    );
+}
+
 #[test]
 fn test_parse_status_args() {
     let args = vec!["--db".to_string(), "test.db".to_string()];
@@ -269,140 +988,845 @@ fn test_parse_refs_args() {
             assert_eq!(path, Some(PathBuf::from("src/main.rs")));
             assert_eq!(direction, "out");
         }
-        _ => panic!("Expected Refs command"),
+        _ => panic!("Expected Refs command"),
+    }
+}
+
+#[test]
+fn test_parse_refs_args_without_path() {
+    let args = vec![
+        "--db".to_string(),
+        "test.db".to_string(),
+        "--name".to_string(),
+        "my_function".to_string(),
+        "--direction".to_string(),
+        "in".to_string(),
+    ];
+
+    let result = parse_refs_args(&args).unwrap();
+    match result {
+        Command::Refs {
+            db_path,
+            name,
+            direction,
+            path,
+            ..
+        } => {
+            assert_eq!(db_path, PathBuf::from("test.db"));
+            assert_eq!(name, "my_function".to_string());
+            assert_eq!(path, None);
+            assert_eq!(direction, "in");
+        }
+        _ => panic!("Expected Refs command"),
+    }
+}
+
+#[test]
+fn test_parse_refs_args_group_by_file() {
+    let args: Vec<String> = ["--db", "test.db", "--name", "helper", "--group-by-file"]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+    match parse_refs_args(&args).unwrap() {
+        Command::Refs { group_by_file, .. } => assert!(group_by_file),
+        _ => panic!("Expected Refs command"),
+    }
+
+    let with_all: Vec<String> = ["--name", "helper", "--all", "--group-by-file"]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+    assert!(parse_refs_args(&with_all).is_err());
+}
+
+#[test]
+fn test_parse_column_unit_on_query_find_and_refs() {
+    let to_args = |args: &[&str]| -> Vec<String> { args.iter().map(|s| s.to_string()).collect() };
+
+    match parse_query_args(&to_args(&[
+        "--db",
+        "t.db",
+        "--file",
+        "a.rs",
+        "--column-unit",
+        "utf16",
+    ]))
+    .unwrap()
+    {
+        Command::Query { column_unit, .. } => assert_eq!(column_unit, ColumnUnit::Utf16),
+        _ => panic!("Expected Query command"),
+    }
+    match parse_find_args(&to_args(&[
+        "--db",
+        "t.db",
+        "--name",
+        "f",
+        "--column-unit",
+        "char",
+    ]))
+    .unwrap()
+    {
+        Command::Find { column_unit, .. } => assert_eq!(column_unit, ColumnUnit::Char),
+        _ => panic!("Expected Find command"),
+    }
+    match parse_refs_args(&to_args(&["--db", "t.db", "--name", "f"])).unwrap() {
+        Command::Refs { column_unit, .. } => assert_eq!(column_unit, ColumnUnit::Byte),
+        _ => panic!("Expected Refs command"),
+    }
+
+    assert!(parse_refs_args(&to_args(&[
+        "--db",
+        "t.db",
+        "--name",
+        "f",
+        "--column-unit",
+        "utf8"
+    ]))
+    .is_err());
+    assert!(parse_find_args(&to_args(&[
+        "--name",
+        "f",
+        "--all",
+        "--column-unit",
+        "utf16"
+    ]))
+    .is_err());
+}
+
+#[test]
+fn test_parse_query_baseline() {
+    let to_args = |args: &[&str]| -> Vec<String> { args.iter().map(|s| s.to_string()).collect() };
+
+    match parse_query_args(&to_args(&[
+        "--db",
+        "t.db",
+        "--file",
+        "a.rs",
+        "--baseline",
+        "base.db",
+        "--changed-only",
+    ]))
+    .unwrap()
+    {
+        Command::Query {
+            baseline,
+            changed_only,
+            ..
+        } => {
+            assert_eq!(baseline, Some(PathBuf::from("base.db")));
+            assert!(changed_only);
+        }
+        _ => panic!("Expected Query command"),
+    }
+
+    // --changed-only needs --baseline, which needs --file
+    assert!(parse_query_args(&to_args(&[
+        "--db",
+        "t.db",
+        "--file",
+        "a.rs",
+        "--changed-only"
+    ]))
+    .is_err());
+    assert!(parse_query_args(&to_args(&["--db", "t.db", "--baseline", "base.db"])).is_err());
+    let err = parse_query_args(&to_args(&[
+        "--db",
+        "t.db",
+        "--file",
+        "a.rs",
+        "--baseline",
+        "base.db",
+        "--count-only",
+    ]))
+    .unwrap_err();
+    assert!(err.to_string().contains("--count-only"));
+}
+
+#[test]
+fn test_parse_refs_args_since() {
+    let args: Vec<String> = ["--db", "test.db", "--name", "helper", "--since", "main"]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+    match parse_refs_args(&args).unwrap() {
+        Command::Refs { since, .. } => assert_eq!(since.as_deref(), Some("main")),
+        _ => panic!("Expected Refs command"),
+    }
+
+    let with_all: Vec<String> = ["--name", "helper", "--all", "--since", "main"]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+    assert!(parse_refs_args(&with_all).is_err());
+}
+
+#[test]
+fn test_parse_get_args() {
+    let args = vec![
+        "--db".to_string(),
+        "test.db".to_string(),
+        "--file".to_string(),
+        "src/main.rs".to_string(),
+        "--symbol".to_string(),
+        "main".to_string(),
+        "--with-context".to_string(),
+    ];
+
+    let result = parse_get_args(&args).unwrap();
+    match result {
+        Command::Get {
+            db_path,
+            file_path,
+            symbol_name,
+            with_context,
+            ..
+        } => {
+            assert_eq!(db_path, PathBuf::from("test.db"));
+            assert_eq!(file_path, "src/main.rs".to_string());
+            assert_eq!(symbol_name, "main".to_string());
+            assert!(with_context);
+        }
+        _ => panic!("Expected Get command"),
+    }
+}
+
+#[test]
+fn test_parse_get_args_expand_to() {
+    let args: Vec<String> = [
+        "--db",
+        "test.db",
+        "--file",
+        "src/main.rs",
+        "--symbol",
+        "main",
+        "--expand-to",
+        "function",
+    ]
+    .iter()
+    .map(|s| s.to_string())
+    .collect();
+
+    match parse_get_args(&args).unwrap() {
+        Command::Get { expand_to, .. } => {
+            assert_eq!(expand_to, crate::get_cmd::ExpandTo::Function);
+        }
+        _ => panic!("Expected Get command"),
+    }
+
+    // Defaults to the symbol's own span
+    match parse_get_args(&args[..6]).unwrap() {
+        Command::Get { expand_to, .. } => {
+            assert_eq!(expand_to, crate::get_cmd::ExpandTo::Symbol);
+        }
+        _ => panic!("Expected Get command"),
+    }
+
+    let mut invalid = args.clone();
+    invalid[7] = "block".to_string();
+    let err = parse_get_args(&invalid).unwrap_err();
+    assert!(err.to_string().contains("Unknown --expand-to granularity"));
+}
+
+#[test]
+fn test_parse_get_args_symbol_id() {
+    let args: Vec<String> = [
+        "--db",
+        "test.db",
+        "--symbol-id",
+        "abc123",
+        "--output",
+        "json",
+    ]
+    .iter()
+    .map(|s| s.to_string())
+    .collect();
+
+    match parse_get_args(&args).unwrap() {
+        Command::GetBySymbolId {
+            symbol_id,
+            output_format,
+            ..
+        } => {
+            assert_eq!(symbol_id, "abc123");
+            assert!(matches!(output_format, OutputFormat::Json));
+        }
+        _ => panic!("Expected GetBySymbolId command"),
+    }
+
+    let mut conflicting = args.clone();
+    conflicting.extend(["--symbol".to_string(), "main".to_string()]);
+    let err = parse_get_args(&conflicting).unwrap_err();
+    assert!(err.to_string().contains("--symbol-id cannot be combined"));
+}
+
+#[test]
+fn test_parse_get_file_args() {
+    let args = vec![
+        "--db".to_string(),
+        "test.db".to_string(),
+        "--file".to_string(),
+        "src/main.rs".to_string(),
+    ];
+
+    let result = parse_get_file_args(&args).unwrap();
+    match result {
+        Command::GetFile {
+            db_path,
+            file_path,
+            outline,
+            line_range,
+            output_format,
+        } => {
+            assert_eq!(db_path, PathBuf::from("test.db"));
+            assert_eq!(file_path, "src/main.rs".to_string());
+            assert!(!outline);
+            assert_eq!(line_range, None);
+            assert!(matches!(output_format, OutputFormat::Human));
+        }
+        _ => panic!("Expected GetFile command"),
+    }
+
+    let mut with_outline = args.clone();
+    with_outline.push("--outline".to_string());
+    match parse_get_file_args(&with_outline).unwrap() {
+        Command::GetFile { outline, .. } => assert!(outline),
+        _ => panic!("Expected GetFile command"),
+    }
+}
+
+#[test]
+fn test_parse_get_file_args_line_range() {
+    let with_range = |range: &str| {
+        let args: Vec<String> = [
+            "--db",
+            "test.db",
+            "--file",
+            "src/main.rs",
+            "--line-range",
+            range,
+        ]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+        parse_get_file_args(&args)
+    };
+
+    match with_range("10:20").unwrap() {
+        Command::GetFile { line_range, .. } => assert_eq!(line_range, Some((10, 20))),
+        _ => panic!("Expected GetFile command"),
+    }
+    match with_range("7:7").unwrap() {
+        Command::GetFile { line_range, .. } => assert_eq!(line_range, Some((7, 7))),
+        _ => panic!("Expected GetFile command"),
+    }
+
+    let err = with_range("20:10").unwrap_err();
+    assert!(
+        err.to_string().contains("start 20 is after end 10"),
+        "{}",
+        err
+    );
+    assert!(with_range("0:5").is_err());
+    assert!(with_range("5").is_err());
+    assert!(with_range("a:b").is_err());
+
+    let args: Vec<String> = [
+        "--db",
+        "test.db",
+        "--file",
+        "src/main.rs",
+        "--outline",
+        "--line-range",
+        "1:2",
+    ]
+    .iter()
+    .map(|s| s.to_string())
+    .collect();
+    let err = parse_get_file_args(&args).unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("cannot be combined with --outline"));
+}
+
+#[test]
+fn test_parse_timings_args() {
+    let args = vec![
+        "--db".to_string(),
+        "test.db".to_string(),
+        "--top".to_string(),
+        "5".to_string(),
+        "--output".to_string(),
+        "json".to_string(),
+    ];
+
+    let result = parse_timings_args(&args).unwrap();
+    match result {
+        Command::Timings {
+            db_path,
+            top,
+            output_format,
+        } => {
+            assert_eq!(db_path, PathBuf::from("test.db"));
+            assert_eq!(top, 5);
+            assert!(matches!(output_format, OutputFormat::Json));
+        }
+        _ => panic!("Expected Timings command"),
+    }
+}
+
+#[test]
+fn test_parse_metrics_backfill_args() {
+    let args = vec![
+        "--db".to_string(),
+        "test.db".to_string(),
+        "--only".to_string(),
+        "src/**/*.rs".to_string(),
+        "--output".to_string(),
+        "json".to_string(),
+    ];
+
+    match parse_metrics_backfill_args(&args).unwrap() {
+        Command::MetricsBackfill {
+            db_path,
+            only,
+            output_format,
+        } => {
+            assert_eq!(db_path, PathBuf::from("test.db"));
+            assert_eq!(only.as_deref(), Some("src/**/*.rs"));
+            assert!(matches!(output_format, OutputFormat::Json));
+        }
+        _ => panic!("Expected MetricsBackfill command"),
+    }
+
+    let args = vec!["--db".to_string(), "test.db".to_string()];
+    match parse_metrics_backfill_args(&args).unwrap() {
+        Command::MetricsBackfill { only, .. } => assert!(only.is_none()),
+        _ => panic!("Expected MetricsBackfill command"),
+    }
+}
+
+#[test]
+fn test_parse_timings_args_default_top() {
+    let args = vec!["--db".to_string(), "test.db".to_string()];
+    match parse_timings_args(&args).unwrap() {
+        Command::Timings { top, .. } => assert_eq!(top, 20),
+        _ => panic!("Expected Timings command"),
+    }
+}
+
+#[test]
+fn test_parse_implementors_args() {
+    let args = vec![
+        "--db".to_string(),
+        "test.db".to_string(),
+        "--name".to_string(),
+        "Display".to_string(),
+        "--output".to_string(),
+        "json".to_string(),
+    ];
+    match parse_implementors_args(&args).unwrap() {
+        Command::Implementors {
+            db_path,
+            name,
+            output_format,
+        } => {
+            assert_eq!(db_path, PathBuf::from("test.db"));
+            assert_eq!(name, "Display");
+            assert!(matches!(output_format, OutputFormat::Json));
+        }
+        _ => panic!("Expected Implementors command"),
+    }
+
+    let args = vec!["--db".to_string(), "test.db".to_string()];
+    assert!(parse_implementors_args(&args).is_err());
+}
+
+#[test]
+fn test_parse_prune_args() {
+    let args = vec![
+        "--db".to_string(),
+        "test.db".to_string(),
+        "--dry-run".to_string(),
+    ];
+    match parse_prune_args(&args).unwrap() {
+        Command::Prune {
+            db_path, dry_run, ..
+        } => {
+            assert_eq!(db_path, PathBuf::from("test.db"));
+            assert!(dry_run);
+        }
+        _ => panic!("Expected Prune command"),
+    }
+
+    assert!(parse_prune_args(&[
+        "--db".to_string(),
+        "t.db".to_string(),
+        "--force".to_string()
+    ])
+    .is_err());
+}
+
+#[test]
+fn test_parse_checkpoint_args() {
+    let args = vec![
+        "--db".to_string(),
+        "test.db".to_string(),
+        "--output".to_string(),
+        "json".to_string(),
+    ];
+    match parse_checkpoint_args(&args).unwrap() {
+        Command::Checkpoint {
+            db_path,
+            output_format,
+        } => {
+            assert_eq!(db_path, PathBuf::from("test.db"));
+            assert_eq!(output_format, OutputFormat::Json);
+        }
+        _ => panic!("Expected Checkpoint command"),
+    }
+
+    assert!(parse_checkpoint_args(&[
+        "--db".to_string(),
+        "t.db".to_string(),
+        "--truncate".to_string()
+    ])
+    .is_err());
+}
+
+#[test]
+fn test_parse_bench_args() {
+    let args = vec![
+        "--root".to_string(),
+        "src".to_string(),
+        "--runs".to_string(),
+        "3".to_string(),
+        "--output".to_string(),
+        "json".to_string(),
+    ];
+    match parse_bench_args(&args).unwrap() {
+        Command::Bench {
+            root,
+            runs,
+            output_format,
+        } => {
+            assert_eq!(root, PathBuf::from("src"));
+            assert_eq!(runs, 3);
+            assert_eq!(output_format, OutputFormat::Json);
+        }
+        _ => panic!("Expected Bench command"),
+    }
+
+    match parse_bench_args(&["--root".to_string(), ".".to_string()]).unwrap() {
+        Command::Bench { runs, .. } => assert_eq!(runs, 1),
+        _ => panic!("Expected Bench command"),
+    }
+    assert!(parse_bench_args(&[]).is_err());
+    assert!(parse_bench_args(&[
+        "--root".to_string(),
+        ".".to_string(),
+        "--runs".to_string(),
+        "0".to_string()
+    ])
+    .is_err());
+}
+
+#[test]
+fn test_parse_rebase_paths_args() {
+    let args = vec![
+        "--db".to_string(),
+        "test.db".to_string(),
+        "--from".to_string(),
+        "/old/root".to_string(),
+        "--to".to_string(),
+        ".".to_string(),
+    ];
+    match parse_rebase_paths_args(&args).unwrap() {
+        Command::RebasePaths {
+            db_path, from, to, ..
+        } => {
+            assert_eq!(db_path, PathBuf::from("test.db"));
+            assert_eq!(from, "/old/root");
+            assert_eq!(to, ".");
+        }
+        _ => panic!("Expected RebasePaths command"),
+    }
+
+    assert!(
+        parse_rebase_paths_args(&args[..4]).is_err(),
+        "--to is required"
+    );
+}
+
+#[test]
+fn test_parse_schema_args() {
+    match parse_schema_args(&["--type".to_string(), "status".to_string()]).unwrap() {
+        Command::Schema { type_name, .. } => assert_eq!(type_name.as_deref(), Some("status")),
+        _ => panic!("Expected Schema command"),
+    }
+    match parse_schema_args(&[]).unwrap() {
+        Command::Schema { type_name, .. } => assert!(type_name.is_none()),
+        _ => panic!("Expected Schema command"),
+    }
+
+    let err = parse_schema_args(&["--type".to_string(), "bogus".to_string()]).unwrap_err();
+    assert!(err.to_string().contains("Unknown schema type 'bogus'"));
+}
+
+#[test]
+fn test_parse_files_args() {
+    let args = vec![
+        "--db".to_string(),
+        "test.db".to_string(),
+        "--symbols".to_string(),
+        "--output".to_string(),
+        "json".to_string(),
+    ];
+
+    let result = parse_files_args(&args).unwrap();
+    match result {
+        Command::Files {
+            db_path,
+            with_symbols,
+            output_format,
+        } => {
+            assert_eq!(db_path, PathBuf::from("test.db"));
+            assert!(with_symbols);
+            assert!(matches!(output_format, OutputFormat::Json));
+        }
+        _ => panic!("Expected Files command"),
     }
 }
 
 #[test]
-fn test_parse_refs_args_without_path() {
+fn test_parse_verify_args() {
     let args = vec![
+        "--root".to_string(),
+        "/home/test".to_string(),
         "--db".to_string(),
         "test.db".to_string(),
-        "--name".to_string(),
-        "my_function".to_string(),
-        "--direction".to_string(),
-        "in".to_string(),
     ];
 
-    let result = parse_refs_args(&args).unwrap();
+    let result = parse_verify_args(&args).unwrap();
     match result {
-        Command::Refs {
+        Command::Verify {
+            root_path,
             db_path,
-            name,
-            direction,
-            path,
-            ..
+            integrity,
+            overlaps,
+            output_format,
         } => {
+            assert_eq!(root_path, Some(PathBuf::from("/home/test")));
             assert_eq!(db_path, PathBuf::from("test.db"));
-            assert_eq!(name, "my_function".to_string());
-            assert_eq!(path, None);
-            assert_eq!(direction, "in");
+            assert!(!integrity);
+            assert!(!overlaps);
+            assert!(matches!(output_format, OutputFormat::Human));
         }
-        _ => panic!("Expected Refs command"),
+        _ => panic!("Expected Verify command"),
+    }
+
+    // --integrity makes --root optional
+    let integrity_only: Vec<String> = ["--db", "test.db", "--integrity"]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+    match parse_verify_args(&integrity_only).unwrap() {
+        Command::Verify {
+            root_path,
+            integrity,
+            ..
+        } => {
+            assert_eq!(root_path, None);
+            assert!(integrity);
+        }
+        _ => panic!("Expected Verify command"),
+    }
+
+    // So does --overlaps
+    let overlaps_only: Vec<String> = ["--db", "test.db", "--overlaps"]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+    match parse_verify_args(&overlaps_only).unwrap() {
+        Command::Verify {
+            root_path,
+            integrity,
+            overlaps,
+            ..
+        } => {
+            assert_eq!(root_path, None);
+            assert!(!integrity);
+            assert!(overlaps);
+        }
+        _ => panic!("Expected Verify command"),
     }
+
+    let no_root: Vec<String> = ["--db", "test.db"].iter().map(|s| s.to_string()).collect();
+    assert!(parse_verify_args(&no_root).is_err());
 }
 
 #[test]
-fn test_parse_get_args() {
-    let args = vec![
-        "--db".to_string(),
-        "test.db".to_string(),
-        "--file".to_string(),
-        "src/main.rs".to_string(),
-        "--symbol".to_string(),
-        "main".to_string(),
-        "--with-context".to_string(),
-    ];
+fn test_parse_check_args() {
+    use crate::check_cmd::CheckCategory;
 
-    let result = parse_get_args(&args).unwrap();
-    match result {
-        Command::Get {
+    let args: Vec<String> = ["--db", "test.db", "--fail-on", "collisions,unresolved"]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+    match parse_check_args(&args).unwrap() {
+        Command::Check {
             db_path,
-            file_path,
-            symbol_name,
-            with_context,
+            root_path,
+            fail_on,
             ..
         } => {
             assert_eq!(db_path, PathBuf::from("test.db"));
-            assert_eq!(file_path, "src/main.rs".to_string());
-            assert_eq!(symbol_name, "main".to_string());
-            assert!(with_context);
+            assert_eq!(root_path, None);
+            assert_eq!(
+                fail_on,
+                vec![CheckCategory::Collisions, CheckCategory::Unresolved]
+            );
         }
-        _ => panic!("Expected Get command"),
+        _ => panic!("Expected Check command"),
     }
+
+    let stale_without_root: Vec<String> = ["--db", "test.db", "--fail-on", "stale"]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+    assert!(parse_check_args(&stale_without_root).is_err());
+
+    let unknown: Vec<String> = ["--db", "test.db", "--fail-on", "cycles"]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+    assert!(parse_check_args(&unknown).is_err());
+
+    let missing: Vec<String> = ["--db", "test.db"].iter().map(|s| s.to_string()).collect();
+    assert!(parse_check_args(&missing).is_err());
 }
 
 #[test]
-fn test_parse_get_file_args() {
-    let args = vec![
-        "--db".to_string(),
-        "test.db".to_string(),
-        "--file".to_string(),
-        "src/main.rs".to_string(),
-    ];
+fn test_parse_parse_args() {
+    let args: Vec<String> = ["--lang", "rust", "--stdin"]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+    match parse_parse_args(&args).unwrap() {
+        Command::Parse {
+            lang,
+            file_path,
+            from_stdin,
+            output_format,
+        } => {
+            assert_eq!(lang.as_deref(), Some("rust"));
+            assert_eq!(file_path, None);
+            assert!(from_stdin);
+            assert_eq!(output_format, OutputFormat::Json);
+        }
+        _ => panic!("Expected Parse command"),
+    }
 
-    let result = parse_get_file_args(&args).unwrap();
-    match result {
-        Command::GetFile {
-            db_path,
+    let file_only: Vec<String> = ["--file", "src/lib.rs", "--output", "human"]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+    match parse_parse_args(&file_only).unwrap() {
+        Command::Parse {
             file_path,
+            from_stdin,
             output_format,
+            ..
         } => {
-            assert_eq!(db_path, PathBuf::from("test.db"));
-            assert_eq!(file_path, "src/main.rs".to_string());
-            assert!(matches!(output_format, OutputFormat::Human));
+            assert_eq!(file_path, Some(PathBuf::from("src/lib.rs")));
+            assert!(!from_stdin);
+            assert_eq!(output_format, OutputFormat::Human);
         }
-        _ => panic!("Expected GetFile command"),
+        _ => panic!("Expected Parse command"),
     }
+
+    let no_source: Vec<String> = ["--lang", "rust"].iter().map(|s| s.to_string()).collect();
+    assert!(parse_parse_args(&no_source).is_err());
 }
 
 #[test]
-fn test_parse_files_args() {
+fn test_parse_verify_ids_args() {
     let args = vec![
+        "--root".to_string(),
+        "/home/test".to_string(),
         "--db".to_string(),
         "test.db".to_string(),
-        "--symbols".to_string(),
         "--output".to_string(),
         "json".to_string(),
     ];
 
-    let result = parse_files_args(&args).unwrap();
+    let result = parse_verify_ids_args(&args).unwrap();
     match result {
-        Command::Files {
+        Command::VerifyIds {
+            root_path,
             db_path,
-            with_symbols,
             output_format,
         } => {
+            assert_eq!(root_path, PathBuf::from("/home/test"));
             assert_eq!(db_path, PathBuf::from("test.db"));
-            assert!(with_symbols);
             assert!(matches!(output_format, OutputFormat::Json));
         }
-        _ => panic!("Expected Files command"),
+        _ => panic!("Expected VerifyIds command"),
     }
 }
 
 #[test]
-fn test_parse_verify_args() {
+fn test_parse_api_diff_args() {
     let args = vec![
-        "--root".to_string(),
-        "/home/test".to_string(),
-        "--db".to_string(),
-        "test.db".to_string(),
+        "--db-a".to_string(),
+        "v1.db".to_string(),
+        "--db-b".to_string(),
+        "v2.db".to_string(),
     ];
 
-    let result = parse_verify_args(&args).unwrap();
-    match result {
-        Command::Verify {
-            root_path,
-            db_path,
+    match parse_api_diff_args(&args).unwrap() {
+        Command::ApiDiff {
+            db_a,
+            db_b,
             output_format,
         } => {
-            assert_eq!(root_path, PathBuf::from("/home/test"));
-            assert_eq!(db_path, PathBuf::from("test.db"));
+            assert_eq!(db_a, PathBuf::from("v1.db"));
+            assert_eq!(db_b, PathBuf::from("v2.db"));
             assert!(matches!(output_format, OutputFormat::Human));
         }
-        _ => panic!("Expected Verify command"),
+        _ => panic!("Expected ApiDiff command"),
+    }
+
+    assert!(
+        parse_api_diff_args(&args[..2]).is_err(),
+        "--db-b is required"
+    );
+}
+
+#[test]
+fn test_parse_diff_args() {
+    let args = vec![
+        "--old".to_string(),
+        "v1.db".to_string(),
+        "--new".to_string(),
+        "v2.db".to_string(),
+        "--output".to_string(),
+        "json".to_string(),
+    ];
+
+    match parse_diff_args(&args).unwrap() {
+        Command::Diff {
+            old_db,
+            new_db,
+            output_format,
+        } => {
+            assert_eq!(old_db, PathBuf::from("v1.db"));
+            assert_eq!(new_db, PathBuf::from("v2.db"));
+            assert!(matches!(output_format, OutputFormat::Json));
+        }
+        _ => panic!("Expected Diff command"),
     }
+
+    assert!(parse_diff_args(&args[..2]).is_err(), "--new is required");
 }
 
 #[test]
@@ -462,6 +1886,47 @@ fn test_parse_collisions_args() {
     }
 }
 
+#[test]
+fn test_parse_collisions_args_canonicalize_generics() {
+    let args: Vec<String> = ["--db", "test.db", "--canonicalize-generics"]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+    match parse_collisions_args(&args).unwrap() {
+        Command::Collisions {
+            field,
+            canonicalize_generics,
+            ..
+        } => {
+            assert!(matches!(field, CollisionField::Fqn));
+            assert!(canonicalize_generics);
+        }
+        _ => panic!("Expected Collisions command"),
+    }
+}
+
+#[test]
+fn test_parse_collisions_args_resolve_suggestions() {
+    let args: Vec<String> = ["--db", "test.db"].iter().map(|s| s.to_string()).collect();
+    match parse_collisions_args(&args).unwrap() {
+        Command::Collisions {
+            resolve_suggestions,
+            ..
+        } => assert!(!resolve_suggestions),
+        _ => panic!("Expected Collisions command"),
+    }
+
+    let mut with_suggestions = args.clone();
+    with_suggestions.push("--resolve-suggestions".to_string());
+    match parse_collisions_args(&with_suggestions).unwrap() {
+        Command::Collisions {
+            resolve_suggestions,
+            ..
+        } => assert!(resolve_suggestions),
+        _ => panic!("Expected Collisions command"),
+    }
+}
+
 #[test]
 fn test_parse_migrate_args() {
     let args = vec![
@@ -511,6 +1976,33 @@ fn test_parse_migrate_backend_args() {
         }
         _ => panic!("Expected MigrateBackend command"),
     }
+
+    let args: Vec<String> = ["--input", "old.db", "--output", "new.db", "--resume"]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+    match parse_migrate_backend_args(&args).unwrap() {
+        Command::MigrateBackend {
+            resume, dry_run, ..
+        } => {
+            assert!(resume);
+            assert!(!dry_run);
+        }
+        _ => panic!("Expected MigrateBackend command"),
+    }
+
+    let args: Vec<String> = [
+        "--input",
+        "old.db",
+        "--output",
+        "new.db",
+        "--resume",
+        "--dry-run",
+    ]
+    .iter()
+    .map(|s| s.to_string())
+    .collect();
+    assert!(parse_migrate_backend_args(&args).is_err());
 }
 
 #[test]
@@ -536,13 +2028,43 @@ fn test_parse_query_args() {
         } => {
             assert_eq!(db_path, PathBuf::from("test.db"));
             assert_eq!(file_path, Some(PathBuf::from("src/main.rs")));
-            assert_eq!(kind, Some("function".to_string()));
+            assert_eq!(kind, Some("Function".to_string()));
             assert!(explain);
         }
         _ => panic!("Expected Query command"),
     }
 }
 
+#[test]
+fn test_parse_kind_aliases() {
+    let query_args: Vec<String> = ["--db", "test.db", "--file", "a.rs", "--kind", "trait"]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+    match parse_query_args(&query_args).unwrap() {
+        Command::Query { kind, .. } => assert_eq!(kind, Some("Interface".to_string())),
+        _ => panic!("Expected Query command"),
+    }
+
+    // Chunks are stored with the normalized key
+    let chunk_args: Vec<String> = ["--db", "test.db", "--kind", "def"]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+    match parse_chunks_args(&chunk_args).unwrap() {
+        Command::Chunks { kind_filter, .. } => assert_eq!(kind_filter, Some("fn".to_string())),
+        _ => panic!("Expected Chunks command"),
+    }
+
+    let bad: Vec<String> = ["--db", "test.db", "--kind", "widget"]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+    let err = parse_chunks_args(&bad).unwrap_err().to_string();
+    assert!(err.contains("Valid kinds"), "{}", err);
+    assert!(err.contains("class (struct"), "{}", err);
+}
+
 #[test]
 fn test_parse_chunks_args() {
     let args = vec![
@@ -570,6 +2092,22 @@ fn test_parse_chunks_args() {
     }
 }
 
+#[test]
+fn test_parse_chunks_verify() {
+    let to_args = |items: &[&str]| -> Vec<String> { items.iter().map(|s| s.to_string()).collect() };
+    let args = to_args(&["--db", "test.db", "--verify", "--root", "/repo"]);
+    match parse_chunks_args(&args).unwrap() {
+        Command::Chunks { verify, root, .. } => {
+            assert!(verify);
+            assert_eq!(root, Some(PathBuf::from("/repo")));
+        }
+        _ => panic!("Expected Chunks command"),
+    }
+
+    assert!(parse_chunks_args(&to_args(&["--db", "test.db", "--root", "/repo"])).is_err());
+    assert!(parse_chunks_args(&to_args(&["--db", "test.db", "--verify", "--limit", "5"])).is_err());
+}
+
 #[test]
 fn test_parse_chunk_by_span_args() {
     let args = vec![
@@ -601,6 +2139,33 @@ fn test_parse_chunk_by_span_args() {
     }
 }
 
+#[test]
+fn test_parse_chunk_by_symbol_all_occurrences_and_limit() {
+    let to_args = |extra: &[&str]| -> Vec<String> {
+        ["--db", "test.db", "--symbol", "new"]
+            .iter()
+            .chain(extra)
+            .map(|s| s.to_string())
+            .collect()
+    };
+
+    match parse_chunk_by_symbol_args(&to_args(&["--all-occurrences", "--limit", "5"])).unwrap() {
+        Command::ChunkBySymbol {
+            file_filter, limit, ..
+        } => {
+            assert_eq!(file_filter, None);
+            assert_eq!(limit, Some(5));
+        }
+        _ => panic!("Expected ChunkBySymbol command"),
+    }
+
+    assert!(parse_chunk_by_symbol_args(&to_args(&["--limit", "0"])).is_err());
+    let err = parse_chunk_by_symbol_args(&to_args(&["--all-occurrences", "--file", "a.rs"]))
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("--all-occurrences"), "{}", err);
+}
+
 #[test]
 fn test_parse_chunk_by_symbol_args() {
     let args = vec![
@@ -651,6 +2216,74 @@ fn test_parse_ast_args() {
     }
 }
 
+#[test]
+fn test_parse_ast_args_subtree() {
+    let base = vec![
+        "--db".to_string(),
+        "test.db".to_string(),
+        "--file".to_string(),
+        "src/main.rs".to_string(),
+    ];
+    let with = |extra: &[&str]| {
+        let mut args = base.clone();
+        args.extend(extra.iter().map(|s| s.to_string()));
+        parse_ast_args(&args)
+    };
+
+    match with(&["--position", "10", "--subtree"]).unwrap() {
+        Command::Ast { subtree_depth, .. } => {
+            assert_eq!(subtree_depth, Some(magellan::graph::DEFAULT_SUBTREE_DEPTH))
+        }
+        _ => panic!("Expected Ast command"),
+    }
+    match with(&["--position", "10", "--subtree", "--max-depth", "3"]).unwrap() {
+        Command::Ast { subtree_depth, .. } => assert_eq!(subtree_depth, Some(3)),
+        _ => panic!("Expected Ast command"),
+    }
+    match with(&["--position", "10"]).unwrap() {
+        Command::Ast { subtree_depth, .. } => assert_eq!(subtree_depth, None),
+        _ => panic!("Expected Ast command"),
+    }
+
+    assert!(with(&["--subtree"]).is_err());
+    assert!(with(&["--position", "10", "--max-depth", "3"]).is_err());
+}
+
+#[test]
+fn test_parse_ast_args_query() {
+    let base = vec![
+        "--db".to_string(),
+        "test.db".to_string(),
+        "--file".to_string(),
+        "src/main.rs".to_string(),
+    ];
+    let with = |extra: &[&str]| {
+        let mut args = base.clone();
+        args.extend(extra.iter().map(|s| s.to_string()));
+        parse_ast_args(&args)
+    };
+
+    match with(&["--query", "(function_item name: (identifier) @n)"]).unwrap() {
+        Command::Ast {
+            query, position, ..
+        } => {
+            assert_eq!(
+                query.as_deref(),
+                Some("(function_item name: (identifier) @n)")
+            );
+            assert_eq!(position, None);
+        }
+        _ => panic!("Expected Ast command"),
+    }
+    match with(&[]).unwrap() {
+        Command::Ast { query, .. } => assert_eq!(query, None),
+        _ => panic!("Expected Ast command"),
+    }
+
+    assert!(with(&["--query"]).is_err());
+    assert!(with(&["--query", "(_) @n", "--position", "10"]).is_err());
+}
+
 #[test]
 fn test_parse_find_ast_args() {
     let args = vec![
@@ -696,6 +2329,55 @@ fn test_parse_reachable_args() {
     }
 }
 
+#[test]
+fn test_parse_reachable_args_dot() {
+    let args: Vec<String> = ["--db", "test.db", "--symbol", "main", "--format", "dot"]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+
+    match parse_reachable_args(&args).unwrap() {
+        Command::Reachable {
+            dot, output_format, ..
+        } => {
+            assert!(dot);
+            assert_eq!(output_format, OutputFormat::Human);
+        }
+        _ => panic!("Expected Reachable command"),
+    }
+
+    let args: Vec<String> = ["--db", "test.db", "--symbol", "main", "--output", "svg"]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+    assert!(parse_reachable_args(&args).is_err());
+}
+
+#[test]
+fn test_parse_reachable_max_depth() {
+    let parse = |extra: &[&str]| {
+        let args: Vec<String> = ["--db", "test.db", "--symbol", "main"]
+            .iter()
+            .chain(extra)
+            .map(|s| s.to_string())
+            .collect();
+        parse_reachable_args(&args)
+    };
+
+    for flag in ["--max-depth", "--depth-limit"] {
+        match parse(&[flag, "2"]).unwrap() {
+            Command::Reachable { max_depth, .. } => assert_eq!(max_depth, Some(2)),
+            _ => panic!("Expected Reachable command"),
+        }
+    }
+    match parse(&[]).unwrap() {
+        Command::Reachable { max_depth, .. } => assert_eq!(max_depth, None),
+        _ => panic!("Expected Reachable command"),
+    }
+    assert!(parse(&["--max-depth", "-1"]).is_err());
+    assert!(parse(&["--max-depth"]).is_err());
+}
+
 #[test]
 fn test_parse_dead_code_args() {
     let args = vec![
@@ -762,6 +2444,24 @@ fn test_parse_condense_args() {
     }
 }
 
+#[test]
+fn test_parse_condense_min_scc_size() {
+    let args: Vec<String> = ["--db", "test.db", "--min-scc-size", "2"]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+    match parse_condense_args(&args).unwrap() {
+        Command::Condense { min_scc_size, .. } => assert_eq!(min_scc_size, Some(2)),
+        _ => panic!("Expected Condense command"),
+    }
+
+    let args: Vec<String> = ["--db", "test.db", "--min-scc-size", "0"]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+    assert!(parse_condense_args(&args).is_err());
+}
+
 #[test]
 fn test_parse_paths_args() {
     let args = vec![
@@ -793,6 +2493,51 @@ fn test_parse_paths_args() {
     }
 }
 
+#[test]
+fn test_parse_paths_shortest() {
+    let args: Vec<String> = [
+        "--db",
+        "test.db",
+        "--start",
+        "main",
+        "--end",
+        "helper",
+        "--shortest",
+    ]
+    .iter()
+    .map(|s| s.to_string())
+    .collect();
+
+    match parse_paths_args(&args).unwrap() {
+        Command::Paths { shortest, .. } => assert!(shortest),
+        _ => panic!("Expected Paths command"),
+    }
+
+    // --shortest needs a target to search for
+    let args: Vec<String> = ["--db", "test.db", "--start", "main", "--shortest"]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+    let err = parse_paths_args(&args).unwrap_err().to_string();
+    assert!(err.contains("--shortest requires --end"), "{}", err);
+}
+
+#[test]
+fn test_parse_paths_avoid() {
+    let args: Vec<String> = [
+        "--db", "test.db", "--start", "main", "--end", "target", "--avoid", "log_id", "--avoid",
+        "trace_id", "--avoid", "log_id",
+    ]
+    .iter()
+    .map(|s| s.to_string())
+    .collect();
+
+    match parse_paths_args(&args).unwrap() {
+        Command::Paths { avoid, .. } => assert_eq!(avoid, vec!["log_id", "trace_id"]),
+        _ => panic!("Expected Paths command"),
+    }
+}
+
 #[test]
 fn test_parse_slice_args() {
     let args = vec![
@@ -823,6 +2568,47 @@ fn test_parse_slice_args() {
     }
 }
 
+#[test]
+fn test_parse_slice_with_code_and_max_bytes() {
+    let to_args = |args: &[&str]| -> Vec<String> { args.iter().map(|s| s.to_string()).collect() };
+
+    let args = to_args(&[
+        "--db",
+        "test.db",
+        "--target",
+        "main",
+        "--with-code",
+        "--max-bytes",
+        "4096",
+    ]);
+    match parse_slice_args(&args).unwrap() {
+        Command::Slice {
+            with_code,
+            max_bytes,
+            ..
+        } => {
+            assert!(with_code);
+            assert_eq!(max_bytes, Some(4096));
+        }
+        _ => panic!("Expected Slice command"),
+    }
+
+    let args = to_args(&["--db", "test.db", "--target", "main", "--max-bytes", "10"]);
+    let err = parse_slice_args(&args).unwrap_err().to_string();
+    assert!(err.contains("--max-bytes requires --with-code"), "{}", err);
+
+    let args = to_args(&[
+        "--db",
+        "test.db",
+        "--target",
+        "main",
+        "--with-code",
+        "--max-bytes",
+        "0",
+    ]);
+    assert!(parse_slice_args(&args).is_err());
+}
+
 #[test]
 fn test_parse_output_format_validation() {
     // Test invalid output format is rejected
@@ -1148,6 +2934,26 @@ fn test_edge_combined_boolean_flags() {
     }
 }
 
+#[test]
+fn test_parse_with_metrics() {
+    let to_args = |items: &[&str]| -> Vec<String> { items.iter().map(|s| s.to_string()).collect() };
+    let args = to_args(&["--db", "test.db", "--file", "src/lib.rs", "--with-metrics"]);
+    match parse_query_args(&args).unwrap() {
+        Command::Query { with_metrics, .. } => assert!(with_metrics),
+        _ => panic!("Expected Query command"),
+    }
+
+    let args = to_args(&["--db", "test.db", "--name", "main", "--with-metrics"]);
+    match parse_find_args(&args).unwrap() {
+        Command::Find { with_metrics, .. } => assert!(with_metrics),
+        _ => panic!("Expected Find command"),
+    }
+
+    let args = to_args(&["--db", "test.db", "--count-only", "--with-metrics"]);
+    assert!(parse_query_args(&args).is_err());
+    assert!(parse_find_args(&args).is_err());
+}
+
 /// Test collision field variants
 #[test]
 fn test_edge_collision_field_variants() {
@@ -1467,6 +3273,347 @@ fn test_parse_find_args_all_flag() {
     }
 }
 
+#[test]
+fn test_parse_find_args_count_only() {
+    let args = vec![
+        "--db".to_string(),
+        "test.db".to_string(),
+        "--name".to_string(),
+        "foo".to_string(),
+        "--count-only".to_string(),
+    ];
+    match parse_find_args(&args).unwrap() {
+        Command::Find { count_only, .. } => assert!(count_only),
+        _ => panic!("Expected Find command"),
+    }
+
+    let mut with_context = args.clone();
+    with_context.push("--with-context".to_string());
+    let err = parse_find_args(&with_context).unwrap_err();
+    assert!(err.to_string().contains("--with-context"));
+}
+
+#[test]
+fn test_parse_find_args_fuzzy() {
+    let args = vec![
+        "--db".to_string(),
+        "test.db".to_string(),
+        "--fuzzy".to_string(),
+        "hndmsg".to_string(),
+        "--fuzzy-fqn".to_string(),
+        "--limit".to_string(),
+        "5".to_string(),
+    ];
+    match parse_find_args(&args).unwrap() {
+        Command::Find {
+            fuzzy,
+            fuzzy_fqn,
+            limit,
+            ..
+        } => {
+            assert_eq!(fuzzy, Some("hndmsg".to_string()));
+            assert!(fuzzy_fqn);
+            assert_eq!(limit, 5);
+        }
+        _ => panic!("Expected Find command"),
+    }
+
+    let mut with_name = args.clone();
+    with_name.extend(["--name".to_string(), "foo".to_string()]);
+    let err = parse_find_args(&with_name).unwrap_err();
+    assert!(err.to_string().contains("--name"));
+
+    let err = parse_find_args(&[
+        "--db".to_string(),
+        "test.db".to_string(),
+        "--fuzzy-fqn".to_string(),
+    ])
+    .unwrap_err();
+    assert!(err.to_string().contains("requires --fuzzy"));
+}
+
+#[test]
+fn test_parse_find_args_defined_in() {
+    let args: Vec<String> = [
+        "--db",
+        "test.db",
+        "--defined-in",
+        "crate::net",
+        "--kind",
+        "fn",
+    ]
+    .iter()
+    .map(|s| s.to_string())
+    .collect();
+    match parse_find_args(&args).unwrap() {
+        Command::Find {
+            defined_in, kind, ..
+        } => {
+            assert_eq!(defined_in.as_deref(), Some("crate::net"));
+            assert_eq!(kind.as_deref(), Some("Function"));
+        }
+        _ => panic!("Expected Find command"),
+    }
+
+    let mut with_name = args.clone();
+    with_name.extend(["--name".to_string(), "foo".to_string()]);
+    let err = parse_find_args(&with_name).unwrap_err();
+    assert!(err.to_string().contains("--name"), "{}", err);
+
+    let err = parse_find_args(&[
+        "--db".to_string(),
+        "test.db".to_string(),
+        "--kind".to_string(),
+        "fn".to_string(),
+    ])
+    .unwrap_err();
+    assert!(err.to_string().contains("requires --defined-in"), "{}", err);
+}
+
+#[test]
+fn test_parse_find_args_near() {
+    let args: Vec<String> = [
+        "--db",
+        "test.db",
+        "--near",
+        "src/lib.rs:42",
+        "--limit",
+        "3",
+        "--kind",
+        "fn",
+    ]
+    .iter()
+    .map(|s| s.to_string())
+    .collect();
+    match parse_find_args(&args).unwrap() {
+        Command::Find {
+            near, limit, kind, ..
+        } => {
+            assert_eq!(near, Some((PathBuf::from("src/lib.rs"), 42)));
+            assert_eq!(limit, 3);
+            assert_eq!(kind.as_deref(), Some("Function"));
+        }
+        _ => panic!("Expected Find command"),
+    }
+
+    let mut with_name = args.clone();
+    with_name.extend(["--name".to_string(), "foo".to_string()]);
+    let err = parse_find_args(&with_name).unwrap_err();
+    assert!(err.to_string().contains("--name"), "{}", err);
+
+    for bad in ["src/lib.rs", "src/lib.rs:0", "src/lib.rs:x", ":3"] {
+        let err = parse_find_args(&[
+            "--db".to_string(),
+            "test.db".to_string(),
+            "--near".to_string(),
+            bad.to_string(),
+        ])
+        .unwrap_err();
+        assert!(err.to_string().contains("--near"), "{}: {}", bad, err);
+    }
+}
+
+#[test]
+fn test_parse_query_args_defined_in() {
+    let args: Vec<String> = ["--db", "test.db", "--defined-in", "crate::net"]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+    match parse_query_args(&args).unwrap() {
+        Command::Query {
+            defined_in,
+            file_path,
+            ..
+        } => {
+            assert_eq!(defined_in.as_deref(), Some("crate::net"));
+            assert_eq!(file_path, None);
+        }
+        _ => panic!("Expected Query command"),
+    }
+
+    let mut with_count = args.clone();
+    with_count.push("--count-only".to_string());
+    let err = parse_query_args(&with_count).unwrap_err();
+    assert!(err.to_string().contains("--count-only"), "{}", err);
+}
+
+#[test]
+fn test_parse_query_args_count_only() {
+    let args = vec![
+        "--db".to_string(),
+        "test.db".to_string(),
+        "--kind".to_string(),
+        "function".to_string(),
+        "--count-only".to_string(),
+    ];
+    match parse_query_args(&args).unwrap() {
+        Command::Query {
+            count_only, kind, ..
+        } => {
+            assert!(count_only);
+            assert_eq!(kind, Some("Function".to_string()));
+        }
+        _ => panic!("Expected Query command"),
+    }
+
+    let mut with_callers = args.clone();
+    with_callers.push("--with-callers".to_string());
+    let err = parse_query_args(&with_callers).unwrap_err();
+    assert!(err.to_string().contains("--with-callers"));
+}
+
+#[test]
+fn test_parse_query_args_sort() {
+    let parse_sort = |extra: &[&str]| {
+        let mut args = vec!["--db".to_string(), "test.db".to_string()];
+        args.extend(extra.iter().map(|s| s.to_string()));
+        match parse_query_args(&args).unwrap() {
+            Command::Query { sort, .. } => sort,
+            _ => panic!("Expected Query command"),
+        }
+    };
+
+    assert_eq!(parse_sort(&[]), None);
+    assert_eq!(
+        parse_sort(&["--sort", "kind"]),
+        Some(SymbolSort {
+            key: SymbolSortKey::Kind,
+            reverse: false
+        })
+    );
+    assert_eq!(
+        parse_sort(&["--sort", "name", "--reverse"]),
+        Some(SymbolSort {
+            key: SymbolSortKey::Name,
+            reverse: true
+        })
+    );
+    assert_eq!(
+        parse_sort(&["--reverse"]),
+        Some(SymbolSort {
+            key: SymbolSortKey::Line,
+            reverse: true
+        })
+    );
+
+    let args: Vec<String> = ["--db", "test.db", "--sort", "size"]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+    let err = parse_query_args(&args).unwrap_err().to_string();
+    assert!(err.contains("Invalid sort key"), "{}", err);
+}
+
+#[test]
+fn test_parse_test_filter_flags() {
+    let to_args = |extra: &[&str]| -> Vec<String> {
+        ["--db", "test.db"]
+            .iter()
+            .chain(extra)
+            .map(|s| s.to_string())
+            .collect()
+    };
+
+    match parse_query_args(&to_args(&["--file", "src/lib.rs", "--ignore-test-files"])).unwrap() {
+        Command::Query { test_filter, .. } => assert_eq!(test_filter, TestFilter::IgnoreTests),
+        _ => panic!("Expected Query command"),
+    }
+    match parse_find_args(&to_args(&["--name", "helper", "--only-test-files"])).unwrap() {
+        Command::Find { test_filter, .. } => assert_eq!(test_filter, TestFilter::OnlyTests),
+        _ => panic!("Expected Find command"),
+    }
+    match parse_dead_code_args(&to_args(&["--entry", "main", "--ignore-test-files"])).unwrap() {
+        Command::DeadCode { test_filter, .. } => {
+            assert_eq!(test_filter, TestFilter::IgnoreTests)
+        }
+        _ => panic!("Expected DeadCode command"),
+    }
+    match parse_find_args(&to_args(&["--name", "helper"])).unwrap() {
+        Command::Find { test_filter, .. } => assert_eq!(test_filter, TestFilter::All),
+        _ => panic!("Expected Find command"),
+    }
+
+    let err = parse_query_args(&to_args(&["--only-test-files", "--ignore-test-files"]))
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("cannot be combined"), "{}", err);
+
+    let err = parse_find_args(&to_args(&["--list-glob", "h*", "--ignore-test-files"]))
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("--glob"), "{}", err);
+}
+
+#[test]
+fn test_parse_visibility_filter() {
+    let to_args = |extra: &[&str]| -> Vec<String> {
+        ["--db", "test.db"]
+            .iter()
+            .chain(extra)
+            .map(|s| s.to_string())
+            .collect()
+    };
+
+    match parse_query_args(&to_args(&["--file", "src/lib.rs", "--visibility", "crate"])).unwrap() {
+        Command::Query { visibility, .. } => {
+            assert_eq!(visibility, Some(VisibilityFilter::Crate))
+        }
+        _ => panic!("Expected Query command"),
+    }
+    match parse_find_args(&to_args(&["--name", "open", "--visibility", "pub"])).unwrap() {
+        Command::Find { visibility, .. } => assert_eq!(visibility, Some(VisibilityFilter::Pub)),
+        _ => panic!("Expected Find command"),
+    }
+    match parse_find_args(&to_args(&["--name", "open"])).unwrap() {
+        Command::Find { visibility, .. } => assert_eq!(visibility, None),
+        _ => panic!("Expected Find command"),
+    }
+
+    let err = parse_find_args(&to_args(&["--name", "open", "--visibility", "public"]))
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("Unknown --visibility"), "{}", err);
+
+    let err = parse_find_args(&to_args(&["--list-glob", "o*", "--visibility", "pub"]))
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("--glob"), "{}", err);
+}
+
+#[test]
+fn test_parse_find_args_deprecated() {
+    let to_args = |extra: &[&str]| -> Vec<String> {
+        ["--db", "test.db"]
+            .iter()
+            .chain(extra)
+            .map(|s| s.to_string())
+            .collect()
+    };
+
+    match parse_find_args(&to_args(&["--deprecated", "--name", "foo"])).unwrap() {
+        Command::Find {
+            deprecated, name, ..
+        } => {
+            assert!(deprecated);
+            assert_eq!(name.as_deref(), Some("foo"));
+        }
+        _ => panic!("Expected Find command"),
+    }
+    match parse_find_args(&to_args(&["--name", "foo"])).unwrap() {
+        Command::Find { deprecated, .. } => assert!(!deprecated),
+        _ => panic!("Expected Find command"),
+    }
+
+    let err = parse_find_args(&to_args(&["--deprecated", "--count-only"]))
+        .unwrap_err()
+        .to_string();
+    assert!(
+        err.contains("--deprecated cannot be combined with --count-only"),
+        "{}",
+        err
+    );
+}
+
 #[test]
 fn test_parse_find_args_all_false_by_default() {
     let args = vec!["--db".to_string(), "test.db".to_string()];
@@ -1487,6 +3634,46 @@ fn test_parse_status_args_all_flag() {
     }
 }
 
+#[test]
+fn test_parse_status_args_watch() {
+    let to_args = |args: &[&str]| args.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+
+    match parse_status_args(&to_args(&["--db", "test.db", "--watch"])).unwrap() {
+        Command::Status {
+            watch_interval,
+            by_kind,
+            ..
+        } => {
+            assert_eq!(watch_interval, Some(2));
+            assert!(!by_kind);
+        }
+        _ => panic!("Expected Status command"),
+    }
+
+    let args = to_args(&["--db", "test.db", "--watch", "--interval", "5", "--by-kind"]);
+    match parse_status_args(&args).unwrap() {
+        Command::Status {
+            watch_interval,
+            by_kind,
+            ..
+        } => {
+            assert_eq!(watch_interval, Some(5));
+            assert!(by_kind);
+        }
+        _ => panic!("Expected Status command"),
+    }
+
+    match parse_status_args(&to_args(&["--db", "test.db"])).unwrap() {
+        Command::Status { watch_interval, .. } => assert_eq!(watch_interval, None),
+        _ => panic!("Expected Status command"),
+    }
+
+    assert!(parse_status_args(&to_args(&["--db", "test.db", "--interval", "5"])).is_err());
+    assert!(parse_status_args(&to_args(&["--db", "test.db", "--by-kind"])).is_err());
+    assert!(parse_status_args(&to_args(&["--watch", "--interval", "0"])).is_err());
+    assert!(parse_status_args(&to_args(&["--watch", "--all"])).is_err());
+}
+
 #[test]
 fn test_parse_find_args_project_flag_unknown() {
     // --project with a name not in registry must return an error
@@ -1560,3 +3747,216 @@ fn test_parse_ask_args_project_flag_unknown() {
         err
     );
 }
+
+#[test]
+fn test_parse_index_changed_sources() {
+    let to_args = |args: &[&str]| -> Vec<String> { args.iter().map(|s| s.to_string()).collect() };
+
+    match parse_index_args(&to_args(&["--db", "test.db", "--changed-from", "HEAD~1"])).unwrap() {
+        Command::Index {
+            file_path, changed, ..
+        } => {
+            assert!(file_path.is_none());
+            assert_eq!(changed, Some(ChangedPaths::GitRef("HEAD~1".to_string())));
+        }
+        _ => panic!("Expected Index command"),
+    }
+
+    match parse_index_args(&to_args(&["--db", "test.db", "--files-from", "-"])).unwrap() {
+        Command::Index { changed, .. } => {
+            assert_eq!(changed, Some(ChangedPaths::FilesFrom(PathBuf::from("-"))));
+        }
+        _ => panic!("Expected Index command"),
+    }
+
+    assert!(parse_index_args(&to_args(&["--db", "test.db"])).is_err());
+    assert!(parse_index_args(&to_args(&[
+        "--db",
+        "test.db",
+        "--file",
+        "a.rs",
+        "--changed-from",
+        "main"
+    ]))
+    .is_err());
+}
+
+#[test]
+fn test_parse_watch_output_and_max_file_size() {
+    let args: Vec<String> = [
+        "--root",
+        "/tmp/project",
+        "--db",
+        "test.db",
+        "--output",
+        "json",
+        "--max-file-size",
+        "1048576",
+    ]
+    .iter()
+    .map(|s| s.to_string())
+    .collect();
+
+    match parse_watch_args(&args).unwrap() {
+        Command::Watch {
+            output_format,
+            max_file_size,
+            ..
+        } => {
+            assert_eq!(output_format, OutputFormat::Json);
+            assert_eq!(max_file_size, Some(1_048_576));
+        }
+        _ => panic!("Expected Watch command"),
+    }
+
+    let bad: Vec<String> = ["--db", "test.db", "--max-file-size", "1MB"]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+    assert!(parse_watch_args(&bad).is_err());
+}
+
+#[test]
+fn test_parse_watch_checkpoint_interval() {
+    let args: Vec<String> = [
+        "--root",
+        "/tmp/project",
+        "--db",
+        "test.db",
+        "--checkpoint-interval",
+        "30",
+    ]
+    .iter()
+    .map(|s| s.to_string())
+    .collect();
+
+    match parse_watch_args(&args).unwrap() {
+        Command::Watch {
+            checkpoint_interval,
+            include_generated,
+            ..
+        } => {
+            assert_eq!(checkpoint_interval, Some(30));
+            assert!(!include_generated, "generated files are skipped by default");
+        }
+        _ => panic!("Expected Watch command"),
+    }
+
+    let mut with_generated = args.clone();
+    with_generated.push("--include-generated".to_string());
+    match parse_watch_args(&with_generated).unwrap() {
+        Command::Watch {
+            include_generated, ..
+        } => assert!(include_generated),
+        _ => panic!("Expected Watch command"),
+    }
+
+    for bad in ["0", "10s"] {
+        let args: Vec<String> = ["--db", "test.db", "--checkpoint-interval", bad]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        assert!(parse_watch_args(&args).is_err());
+    }
+}
+
+#[test]
+fn test_parse_watch_index_non_source() {
+    let to_args = |items: &[&str]| -> Vec<String> { items.iter().map(|s| s.to_string()).collect() };
+    let extensions = |items: &[&str]| match parse_watch_args(&to_args(items)).unwrap() {
+        Command::Watch { config, .. } => config.non_source_extensions,
+        _ => panic!("Expected Watch command"),
+    };
+
+    let base = ["--root", "/tmp/project", "--db", "test.db"];
+    assert!(
+        extensions(&base).is_empty(),
+        "non-source files are skipped by default"
+    );
+
+    let mut enabled = base.to_vec();
+    enabled.push("--index-non-source");
+    assert_eq!(
+        extensions(&enabled),
+        magellan::graph::filter::DEFAULT_NON_SOURCE_EXTENSIONS
+    );
+
+    // An explicit set replaces the defaults and implies --index-non-source
+    let mut custom = base.to_vec();
+    custom.extend([
+        "--non-source-ext",
+        ".Proto,graphql",
+        "--non-source-ext",
+        "sql",
+    ]);
+    assert_eq!(extensions(&custom), vec!["proto", "graphql", "sql"]);
+
+    for bad in ["", "a/b", "tar.gz"] {
+        let mut args = base.to_vec();
+        args.extend(["--non-source-ext", bad]);
+        assert!(parse_watch_args(&to_args(&args)).is_err(), "{:?}", bad);
+    }
+}
+
+#[test]
+fn test_parse_watch_mem_report() {
+    let to_args = |items: &[&str]| -> Vec<String> { items.iter().map(|s| s.to_string()).collect() };
+    let args = to_args(&["--root", "/tmp/project", "--db", "test.db", "--mem-report"]);
+    match parse_watch_args(&args).unwrap() {
+        Command::Watch { mem_report, .. } => assert!(mem_report),
+        _ => panic!("Expected Watch command"),
+    }
+
+    let args = to_args(&["--root", "/tmp/project", "--db", "test.db"]);
+    match parse_watch_args(&args).unwrap() {
+        Command::Watch { mem_report, .. } => assert!(!mem_report),
+        _ => panic!("Expected Watch command"),
+    }
+
+    // The report describes the initial scan, so it needs one
+    let args = to_args(&[
+        "--root",
+        "/tmp/project",
+        "--db",
+        "test.db",
+        "--watch-only",
+        "--mem-report",
+    ]);
+    assert!(parse_watch_args(&args).is_err());
+}
+
+#[test]
+fn test_parse_watch_profile() {
+    let to_args = |items: &[&str]| -> Vec<String> { items.iter().map(|s| s.to_string()).collect() };
+    let args = to_args(&["--root", "/tmp/project", "--db", "test.db", "--profile"]);
+    match parse_watch_args(&args).unwrap() {
+        Command::Watch { profile, .. } => assert!(profile),
+        _ => panic!("Expected Watch command"),
+    }
+
+    let args = to_args(&["--root", "/tmp/project", "--db", "test.db"]);
+    match parse_watch_args(&args).unwrap() {
+        Command::Watch { profile, .. } => assert!(!profile),
+        _ => panic!("Expected Watch command"),
+    }
+}
+
+#[test]
+fn test_parse_watch_parse_timeout_ms() {
+    let args: Vec<String> = ["--db", "test.db", "--parse-timeout-ms", "250"]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+    match parse_watch_args(&args).unwrap() {
+        Command::Watch {
+            parse_timeout_ms, ..
+        } => assert_eq!(parse_timeout_ms, Some(250)),
+        _ => panic!("Expected Watch command"),
+    }
+
+    let bad: Vec<String> = ["--db", "test.db", "--parse-timeout-ms", "1s"]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+    assert!(parse_watch_args(&bad).is_err());
+}
@@ -1,23 +1,30 @@
 //! Collisions command implementation
 //!
-//! Enumerates ambiguous symbols that share the same FQN or display FQN.
+//! Enumerates ambiguous symbols that share the same FQN or display FQN,
+//! optionally with a disambiguating prefix proposed for each member.
 
 use anyhow::Result;
-use magellan::graph::query::{collision_groups, CollisionField};
+use magellan::graph::query::{
+    collision_groups, collision_groups_canonicalized, resolve_suggestions, CollisionField,
+};
 use magellan::output::{
-    generate_execution_id, output_json, CollisionCandidate, CollisionGroup, CollisionsResponse,
-    JsonResponse, OutputFormat,
+    generate_execution_id, output_json, CollisionCandidate, CollisionGroup, CollisionSuggestion,
+    CollisionsResponse, JsonResponse, OutputFormat,
 };
 use magellan::CodeGraph;
 use std::path::PathBuf;
 
 /// Run the collisions command
 ///
-/// Lists collision groups for a selected field (fqn, display_fqn, canonical_fqn).
+/// Lists collision groups for a selected field (fqn, display_fqn, canonical_fqn),
+/// optionally with generic arguments stripped from the field values. With
+/// `suggest`, each member also gets the prefix that sets it apart.
 pub fn run_collisions(
     db_path: PathBuf,
     field: CollisionField,
     limit: usize,
+    canonicalize_generics: bool,
+    suggest: bool,
     output_format: OutputFormat,
 ) -> Result<()> {
     let mut graph = CodeGraph::open(&db_path)?;
@@ -30,6 +37,12 @@ pub fn run_collisions(
     args.push(field.as_str().to_string());
     args.push("--limit".to_string());
     args.push(limit.to_string());
+    if canonicalize_generics {
+        args.push("--canonicalize-generics".to_string());
+    }
+    if suggest {
+        args.push("--resolve-suggestions".to_string());
+    }
 
     graph.execution_log().start_execution(
         &exec_id,
@@ -44,12 +57,28 @@ pub fn run_collisions(
         .telemetry()
         .record_phase_start(&exec_id, "query_collisions")?;
 
-    let groups = collision_groups(&mut graph, field, limit)?;
+    let groups = if canonicalize_generics {
+        collision_groups_canonicalized(&mut graph, field, limit)?
+    } else {
+        collision_groups(&mut graph, field, limit)?
+    };
 
     graph
         .telemetry()
         .record_phase_end(&exec_id, "query_collisions")?;
 
+    // One suggestion per candidate, in candidate order
+    let suggestions: Vec<Vec<_>> = groups
+        .iter()
+        .map(|group| {
+            if suggest {
+                resolve_suggestions(&group.candidates)
+            } else {
+                Vec::new()
+            }
+        })
+        .collect();
+
     match output_format {
         OutputFormat::Json | OutputFormat::Pretty => {
             // Phase: build_response
@@ -61,22 +90,33 @@ pub fn run_collisions(
                 field: field.as_str().to_string(),
                 groups: groups
                     .into_iter()
-                    .map(|group| CollisionGroup {
-                        field: group.field,
-                        value: group.value,
-                        count: group.count,
-                        candidates: group
-                            .candidates
-                            .into_iter()
-                            .map(|candidate| CollisionCandidate {
-                                entity_id: candidate.entity_id,
-                                symbol_id: candidate.symbol_id,
-                                canonical_fqn: candidate.canonical_fqn,
-                                display_fqn: candidate.display_fqn,
-                                name: candidate.name,
-                                file_path: candidate.file_path,
-                            })
-                            .collect(),
+                    .zip(suggestions)
+                    .map(|(group, suggestions)| {
+                        let mut suggestions = suggestions.into_iter();
+                        CollisionGroup {
+                            field: group.field,
+                            value: group.value,
+                            count: group.count,
+                            candidates: group
+                                .candidates
+                                .into_iter()
+                                .map(|candidate| CollisionCandidate {
+                                    entity_id: candidate.entity_id,
+                                    symbol_id: candidate.symbol_id,
+                                    canonical_fqn: candidate.canonical_fqn,
+                                    display_fqn: candidate.display_fqn,
+                                    name: candidate.name,
+                                    file_path: candidate.file_path,
+                                    suggestion: suggestions.next().map(|suggestion| {
+                                        CollisionSuggestion {
+                                            path_prefix: suggestion.path_prefix,
+                                            module_prefix: suggestion.module_prefix,
+                                            qualified_name: suggestion.qualified_name,
+                                        }
+                                    }),
+                                })
+                                .collect(),
+                        }
                     })
                     .collect(),
             };
@@ -95,8 +135,13 @@ pub fn run_collisions(
             if groups.is_empty() {
                 println!("No collisions found for {}", field.as_str());
             } else {
-                println!("Collisions by {}:", field.as_str());
-                for group in groups {
+                let suffix = if canonicalize_generics {
+                    " (generics stripped)"
+                } else {
+                    ""
+                };
+                println!("Collisions by {}{}:", field.as_str(), suffix);
+                for (group, suggestions) in groups.iter().zip(&suggestions) {
                     println!();
                     println!("{} ({})", group.value, group.count);
                     for (idx, candidate) in group.candidates.iter().enumerate() {
@@ -106,6 +151,15 @@ pub fn run_collisions(
 
                         println!("  [{}] {} {}", idx + 1, symbol_id, file_path);
                         println!("       {}", canonical);
+                        if let Some(suggestion) = suggestions.get(idx) {
+                            match &suggestion.qualified_name {
+                                Some(name) => println!("       suggest: {}", name),
+                                None => println!(
+                                    "       suggest: same file and scope; use --symbol-id {}",
+                                    symbol_id
+                                ),
+                            }
+                        }
                     }
                 }
             }
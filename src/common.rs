@@ -27,6 +27,9 @@ use crate::SymbolKind;
 /// - `.c` → "c"
 /// - `.cpp`, `.cc`, `.cxx`, `.hpp` → "cpp"
 /// - `.go` → "go"
+/// - `.swift` → "swift"
+/// - `.scala`, `.sc` → "scala"
+/// - `.cs` → "csharp"
 /// - `.rb` → "ruby"
 /// - `.php` → "php"
 pub fn detect_language_from_path(path: &str) -> String {
@@ -43,6 +46,9 @@ pub fn detect_language_from_path(path: &str) -> String {
         "c" => "c".to_string(),
         "cpp" | "cc" | "cxx" | "hpp" => "cpp".to_string(),
         "go" => "go".to_string(),
+        "swift" => "swift".to_string(),
+        "scala" | "sc" => "scala".to_string(),
+        "cs" => "csharp".to_string(),
         "rb" => "ruby".to_string(),
         "php" => "php".to_string(),
         _ => "unknown".to_string(),
@@ -134,41 +140,74 @@ pub fn format_symbol_kind(kind: &SymbolKind) -> &'static str {
         SymbolKind::Union => "Union",
         SymbolKind::Namespace => "Namespace",
         SymbolKind::TypeAlias => "TypeAlias",
+        SymbolKind::Macro => "Macro",
         SymbolKind::Unknown => "Unknown",
     }
 }
 
+/// Spellings accepted for each `--kind` value, canonical name first
+///
+/// Besides the canonical names this covers the terms each supported language
+/// uses for the same construct, so `--kind struct` or `--kind def` work.
+pub const SYMBOL_KIND_ALIASES: &[(SymbolKind, &[&str])] = &[
+    // Rust fn, Python def, Go/Swift func, Kotlin fun
+    (
+        SymbolKind::Function,
+        &["function", "fn", "def", "func", "fun"],
+    ),
+    // Java/C#/JS constructors are indexed as methods
+    (SymbolKind::Method, &["method", "constructor", "ctor"]),
+    // Rust/C/C++/Go/Swift struct, Java/C# record, Scala object
+    (SymbolKind::Class, &["class", "struct", "record", "object"]),
+    // Rust/Scala trait, Swift protocol
+    (SymbolKind::Interface, &["interface", "trait", "protocol"]),
+    (SymbolKind::Enum, &["enum", "enumeration"]),
+    // Rust mod, Java/Go package
+    (SymbolKind::Module, &["module", "mod", "package"]),
+    (SymbolKind::Union, &["union"]),
+    (SymbolKind::Namespace, &["namespace", "ns"]),
+    // Rust/TS type, Swift typealias, C/C++ typedef
+    (
+        SymbolKind::TypeAlias,
+        &["typealias", "type", "type alias", "type_alias", "typedef"],
+    ),
+    // Rust macro_rules!, C/C++ #define
+    (SymbolKind::Macro, &["macro", "macro_rules", "define"]),
+];
+
 /// Parse a string into a SymbolKind (case-insensitive)
 ///
 /// # Arguments
 /// * `s` - String to parse
 ///
 /// # Returns
-/// Some(SymbolKind) if recognized, None otherwise
-///
-/// # Supported values (case-insensitive)
-/// - "function", "fn" → Function
-/// - "method" → Method
-/// - "class", "struct" → Class
-/// - "interface", "trait" → Interface
-/// - "enum" → Enum
-/// - "module", "mod" → Module
-/// - "union" → Union
-/// - "namespace", "ns" → Namespace
-/// - "type", "typealias", "type alias" → TypeAlias
+/// Some(SymbolKind) if `s` is a canonical name or language alias listed in
+/// [`SYMBOL_KIND_ALIASES`], None otherwise
 pub fn parse_symbol_kind(s: &str) -> Option<SymbolKind> {
-    match s.to_lowercase().as_str() {
-        "function" | "fn" => Some(SymbolKind::Function),
-        "method" => Some(SymbolKind::Method),
-        "class" | "struct" => Some(SymbolKind::Class),
-        "interface" | "trait" => Some(SymbolKind::Interface),
-        "enum" => Some(SymbolKind::Enum),
-        "module" | "mod" => Some(SymbolKind::Module),
-        "union" => Some(SymbolKind::Union),
-        "namespace" | "ns" => Some(SymbolKind::Namespace),
-        "type" | "typealias" | "type alias" => Some(SymbolKind::TypeAlias),
-        _ => None,
-    }
+    let lowered = s.trim().to_lowercase();
+    SYMBOL_KIND_ALIASES
+        .iter()
+        .find(|(_, aliases)| aliases.contains(&lowered.as_str()))
+        .map(|(kind, _)| kind.clone())
+}
+
+/// Parse a `--kind` argument, with an error listing the accepted values
+pub fn parse_symbol_kind_arg(s: &str) -> anyhow::Result<SymbolKind> {
+    parse_symbol_kind(s).ok_or_else(|| {
+        let valid: Vec<String> = SYMBOL_KIND_ALIASES
+            .iter()
+            .map(|(_, aliases)| match aliases.split_first() {
+                Some((name, [])) => name.to_string(),
+                Some((name, rest)) => format!("{} ({})", name, rest.join(", ")),
+                None => String::new(),
+            })
+            .collect();
+        anyhow::anyhow!(
+            "Unknown symbol kind: '{}'. Valid kinds: {}",
+            s,
+            valid.join(", ")
+        )
+    })
 }
 
 /// Safely extract a byte slice from source with bounds checking
@@ -437,6 +476,7 @@ mod tests {
         assert_eq!(format_symbol_kind(&SymbolKind::Union), "Union");
         assert_eq!(format_symbol_kind(&SymbolKind::Namespace), "Namespace");
         assert_eq!(format_symbol_kind(&SymbolKind::TypeAlias), "TypeAlias");
+        assert_eq!(format_symbol_kind(&SymbolKind::Macro), "Macro");
         assert_eq!(format_symbol_kind(&SymbolKind::Unknown), "Unknown");
     }
 
@@ -452,6 +492,7 @@ mod tests {
         assert_eq!(parse_symbol_kind("union"), Some(SymbolKind::Union));
         assert_eq!(parse_symbol_kind("namespace"), Some(SymbolKind::Namespace));
         assert_eq!(parse_symbol_kind("typealias"), Some(SymbolKind::TypeAlias));
+        assert_eq!(parse_symbol_kind("macro"), Some(SymbolKind::Macro));
 
         // Test aliases
         assert_eq!(parse_symbol_kind("fn"), Some(SymbolKind::Function));
@@ -461,6 +502,7 @@ mod tests {
         assert_eq!(parse_symbol_kind("ns"), Some(SymbolKind::Namespace));
         assert_eq!(parse_symbol_kind("type"), Some(SymbolKind::TypeAlias));
         assert_eq!(parse_symbol_kind("type alias"), Some(SymbolKind::TypeAlias));
+        assert_eq!(parse_symbol_kind("macro_rules"), Some(SymbolKind::Macro));
     }
 
     #[test]
@@ -479,6 +521,55 @@ mod tests {
         assert_eq!(parse_symbol_kind("xyz"), None);
     }
 
+    #[test]
+    fn test_parse_symbol_kind_language_aliases() {
+        let cases = [
+            // Rust
+            ("fn", SymbolKind::Function),
+            ("struct", SymbolKind::Class),
+            ("trait", SymbolKind::Interface),
+            ("mod", SymbolKind::Module),
+            ("type_alias", SymbolKind::TypeAlias),
+            ("macro_rules", SymbolKind::Macro),
+            // Python
+            ("def", SymbolKind::Function),
+            // Go
+            ("func", SymbolKind::Function),
+            ("package", SymbolKind::Module),
+            // Swift
+            ("protocol", SymbolKind::Interface),
+            ("typealias", SymbolKind::TypeAlias),
+            // Kotlin
+            ("fun", SymbolKind::Function),
+            // Java / C#
+            ("record", SymbolKind::Class),
+            ("constructor", SymbolKind::Method),
+            // Scala
+            ("object", SymbolKind::Class),
+            // C / C++
+            ("typedef", SymbolKind::TypeAlias),
+            ("define", SymbolKind::Macro),
+            ("union", SymbolKind::Union),
+            ("ns", SymbolKind::Namespace),
+        ];
+        for (alias, kind) in cases {
+            assert_eq!(parse_symbol_kind(alias), Some(kind), "alias {}", alias);
+        }
+        assert_eq!(parse_symbol_kind(" Struct "), Some(SymbolKind::Class));
+    }
+
+    #[test]
+    fn test_parse_symbol_kind_arg_lists_valid_values() {
+        assert_eq!(parse_symbol_kind_arg("def").unwrap(), SymbolKind::Function);
+
+        let err = parse_symbol_kind_arg("widget").unwrap_err().to_string();
+        assert!(err.starts_with("Unknown symbol kind: 'widget'."), "{}", err);
+        for (_, aliases) in SYMBOL_KIND_ALIASES {
+            assert!(err.contains(aliases[0]), "{}", err);
+        }
+        assert!(err.contains("function (fn, def, func, fun)"), "{}", err);
+    }
+
     #[test]
     fn test_extract_symbol_content_safe_ascii() {
         let source = b"fn hello() { return 42; }";
@@ -6,14 +6,15 @@ use anyhow::Result;
 use magellan::graph::{CondensationResult, Supernode};
 use magellan::output::{output_json, JsonResponse, OutputFormat};
 use magellan::CodeGraph;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::path::PathBuf;
 
 /// Run the condense command
 ///
 /// # Arguments
 /// * `db_path` - Path to the sqlitegraph database
-/// * `show_members` - If true, show all members of each supernode
+/// * `show_members` - If true, show all members of each supernode and the calls between them
+/// * `min_scc_size` - Only report supernodes with at least this many member symbols
 /// * `output_format` - Output format (Human or Json)
 ///
 /// # Displays
@@ -21,10 +22,11 @@ use std::path::PathBuf;
 pub fn run_condense(
     db_path: PathBuf,
     show_members: bool,
+    min_scc_size: Option<usize>,
     output_format: OutputFormat,
 ) -> Result<()> {
     // Build args for execution tracking
-    let args = vec![
+    let mut args = vec![
         "condense".to_string(),
         if show_members {
             "--members".to_string()
@@ -32,6 +34,10 @@ pub fn run_condense(
             "--no-members".to_string()
         },
     ];
+    if let Some(size) = min_scc_size {
+        args.push("--min-scc-size".to_string());
+        args.push(size.to_string());
+    }
 
     let graph = CodeGraph::open(&db_path)?;
     let exec_id = magellan::output::generate_execution_id();
@@ -46,7 +52,10 @@ pub fn run_condense(
     )?;
 
     // Query condensation
-    let condensation = graph.condense_call_graph()?;
+    let mut condensation = graph.condense_call_graph()?;
+    if let Some(size) = min_scc_size {
+        retain_min_scc_size(&mut condensation, size);
+    }
 
     // Handle JSON output mode
     if output_format == OutputFormat::Json || output_format == OutputFormat::Pretty {
@@ -60,6 +69,9 @@ pub fn run_condense(
     println!("Call Graph Condensation:");
     println!("  Supernodes: {}", condensation.graph.supernodes.len());
     println!("  Edges: {}", condensation.graph.edges.len());
+    if let Some(size) = min_scc_size {
+        println!("  (supernodes with at least {} members)", size);
+    }
     println!();
 
     for supernode in &condensation.graph.supernodes {
@@ -69,7 +81,7 @@ pub fn run_condense(
             "?"
         };
 
-        if show_members && supernode.members.len() > 1 {
+        if show_members && (supernode.members.len() > 1 || !supernode.internal_edges.is_empty()) {
             println!(
                 "  [Supernode {}] {} ({} members):",
                 supernode.id,
@@ -80,6 +92,16 @@ pub fn run_condense(
                 let member_fqn = member.fqn.as_deref().unwrap_or("?");
                 println!("      - {} ({})", member_fqn, member.kind);
             }
+            if !supernode.internal_edges.is_empty() {
+                println!("    Calls:");
+                for &(from, to) in &supernode.internal_edges {
+                    println!(
+                        "      {} -> {}",
+                        supernode.members[from].fqn.as_deref().unwrap_or("?"),
+                        supernode.members[to].fqn.as_deref().unwrap_or("?")
+                    );
+                }
+            }
         } else {
             let member_count = if supernode.members.len() > 1 {
                 format!(" ({} members)", supernode.members.len())
@@ -107,6 +129,29 @@ pub fn run_condense(
     Ok(())
 }
 
+/// Drop supernodes with fewer than `min_size` member symbols
+///
+/// Edges and symbol mappings touching a dropped supernode go with it.
+fn retain_min_scc_size(condensation: &mut CondensationResult, min_size: usize) {
+    condensation
+        .graph
+        .supernodes
+        .retain(|supernode| supernode.members.len() >= min_size);
+    let kept: HashSet<i64> = condensation
+        .graph
+        .supernodes
+        .iter()
+        .map(|supernode| supernode.id)
+        .collect();
+    condensation
+        .graph
+        .edges
+        .retain(|(from, to)| kept.contains(from) && kept.contains(to));
+    condensation
+        .original_to_supernode
+        .retain(|_, supernode| kept.contains(supernode));
+}
+
 /// Response structure for condense command
 #[derive(Debug, Clone, serde::Serialize)]
 pub struct CondenseResponse {
@@ -131,6 +176,18 @@ pub struct SupernodeJson {
     pub member_count: usize,
     /// Members of this supernode
     pub members: Vec<SymbolInfoJson>,
+    /// Calls between members (with `--members`)
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub internal_edges: Vec<InternalEdgeJson>,
+}
+
+/// Call between two members of one supernode, as indices into `members`
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct InternalEdgeJson {
+    /// Index of the calling member
+    pub from: usize,
+    /// Index of the called member
+    pub to: usize,
 }
 
 /// Edge info for JSON output
@@ -187,6 +244,11 @@ impl From<Supernode> for SupernodeJson {
                 .into_iter()
                 .map(SymbolInfoJson::from)
                 .collect(),
+            internal_edges: supernode
+                .internal_edges
+                .into_iter()
+                .map(|(from, to)| InternalEdgeJson { from, to })
+                .collect(),
         }
     }
 }
@@ -205,11 +267,16 @@ impl From<magellan::graph::SymbolInfo> for SymbolInfoJson {
 /// Output condensation results in JSON format
 fn output_json_mode(
     condensation: CondensationResult,
-    _show_members: bool,
+    show_members: bool,
     exec_id: &str,
     output_format: OutputFormat,
 ) -> Result<()> {
-    let response = CondenseResponse::from(condensation);
+    let mut response = CondenseResponse::from(condensation);
+    if !show_members {
+        for supernode in &mut response.supernodes {
+            supernode.internal_edges.clear();
+        }
+    }
 
     let json_response = JsonResponse::new(response, exec_id);
     output_json(&json_response, output_format)?;
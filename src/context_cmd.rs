@@ -5,6 +5,7 @@
 use anyhow::Result;
 use magellan::context::{build_context_index, ListQuery, SymbolRelation};
 use magellan::graph::multi_db::MultiDbContext;
+use magellan::log_warn;
 use magellan::output::{generate_execution_id, ContextResponse, OutputFormat};
 use magellan::CodeGraph;
 use std::path::PathBuf;
@@ -20,18 +21,14 @@ pub fn run_context_build(db_paths: Vec<PathBuf>) -> Result<()> {
                     .telemetry()
                     .record_phase_start(&exec_id, "build_context")?;
                 if let Err(e) = build_context_index(&mut graph, db_path) {
-                    eprintln!(
-                        "Warning: failed to build index for {}: {}",
-                        db_path.display(),
-                        e
-                    );
+                    log_warn!("failed to build index for {}: {}", db_path.display(), e);
                 }
                 let _ = graph
                     .telemetry()
                     .record_phase_end(&exec_id, "build_context");
             }
             Err(e) => {
-                eprintln!("Warning: skipping {}: {}", db_path.display(), e);
+                log_warn!("skipping {}: {}", db_path.display(), e);
             }
         }
     }
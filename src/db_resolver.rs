@@ -1,10 +1,12 @@
 //! Database path resolution helper.
 //!
 //! Centralizes the "--db is optional" logic so query commands
-//! fall back to the registry or `.magellan/magellan.db` in cwd.
+//! fall back to the registry or `.magellan/magellan.db` in cwd, and the
+//! read-only open used by query commands.
 
 use anyhow::{Context, Result};
-use std::path::PathBuf;
+use magellan::CodeGraph;
+use std::path::{Path, PathBuf};
 
 use crate::service::registry::Registry;
 
@@ -34,3 +36,16 @@ pub fn resolve_db_path(explicit: Option<PathBuf>) -> Result<PathBuf> {
 
     Ok(PathBuf::from(".magellan/magellan.db"))
 }
+
+/// Open the graph for a read-only command (`find`, `query`, `refs`, `status`).
+///
+/// Uses [`CodeGraph::open_readonly`] so the command never locks or writes a
+/// database that `watch` is updating. A database that does not exist yet is
+/// still created empty, as before, so queries against it report no results.
+pub fn open_graph_for_read(db_path: &Path) -> Result<CodeGraph> {
+    if db_path.exists() {
+        CodeGraph::open_readonly(db_path)
+    } else {
+        CodeGraph::open(db_path)
+    }
+}
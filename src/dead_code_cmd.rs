@@ -4,6 +4,7 @@
 
 use anyhow::Result;
 use magellan::graph::DeadSymbol;
+use magellan::ingest::test_code::TestFilter;
 use magellan::output::{output_json, JsonResponse, OutputFormat};
 use magellan::CodeGraph;
 use std::path::PathBuf;
@@ -13,6 +14,7 @@ use std::path::PathBuf;
 /// # Arguments
 /// * `db_path` - Path to the sqlitegraph database
 /// * `entry_symbol_id` - Stable symbol ID of the entry point (e.g., main function)
+/// * `test_filter` - Keep only test or only non-test symbols
 /// * `output_format` - Output format (Human or Json)
 ///
 /// # Displays
@@ -20,14 +22,18 @@ use std::path::PathBuf;
 pub fn run_dead_code(
     db_path: PathBuf,
     entry_symbol_id: String,
+    test_filter: TestFilter,
     output_format: OutputFormat,
 ) -> Result<()> {
     // Build args for execution tracking
-    let args = vec![
+    let mut args = vec![
         "dead-code".to_string(),
         "--entry".to_string(),
         entry_symbol_id.clone(),
     ];
+    if let Some(flag) = test_filter.as_flag() {
+        args.push(flag.to_string());
+    }
 
     let graph = CodeGraph::open(&db_path)?;
     let exec_id = magellan::output::generate_execution_id();
@@ -42,7 +48,8 @@ pub fn run_dead_code(
     )?;
 
     // Query dead symbols
-    let dead_symbols = graph.dead_symbols(&entry_symbol_id)?;
+    let mut dead_symbols = graph.dead_symbols(&entry_symbol_id)?;
+    dead_symbols.retain(|dead| test_filter.matches(dead.symbol.is_test));
 
     // Handle JSON output mode
     if output_format == OutputFormat::Json || output_format == OutputFormat::Pretty {
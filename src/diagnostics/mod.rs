@@ -4,7 +4,9 @@
 //! Phase 2: Type definitions + deterministic ordering + stderr output.
 //! Phase 3: JSON output with schema_version.
 
+pub mod verbosity;
 pub mod watch_diagnostics;
 
 // Re-export main diagnostic types
+pub use verbosity::{set_verbosity, verbosity, Verbosity};
 pub use watch_diagnostics::{DiagnosticStage, SkipReason, WatchDiagnostic};
@@ -0,0 +1,87 @@
+//! Process-wide verbosity for stderr diagnostics.
+//!
+//! The CLI sets the level once from the global `-q/--quiet` and
+//! `-v/--verbose` flags. Warnings go through [`log_warn!`](crate::log_warn),
+//! which prefixes them with `Warning: `, and status notes through
+//! [`log_info!`](crate::log_info), which prints them as given; `--quiet`
+//! silences both. Per-file progress goes through
+//! [`log_verbose!`](crate::log_verbose), which only `--verbose` shows.
+//! Errors, including failed integrity checks, are always printed and do not
+//! use these macros.
+
+use std::sync::atomic::{AtomicU8, Ordering};
+
+/// How much non-error output goes to stderr
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Verbosity {
+    /// Errors only
+    Quiet,
+    /// Errors, warnings, and status notes
+    #[default]
+    Normal,
+    /// Everything in `Normal`, plus per-file progress
+    Verbose,
+}
+
+static VERBOSITY: AtomicU8 = AtomicU8::new(Verbosity::Normal as u8);
+
+/// Set the process-wide verbosity
+pub fn set_verbosity(verbosity: Verbosity) {
+    VERBOSITY.store(verbosity as u8, Ordering::Relaxed);
+}
+
+/// Current process-wide verbosity
+pub fn verbosity() -> Verbosity {
+    match VERBOSITY.load(Ordering::Relaxed) {
+        0 => Verbosity::Quiet,
+        1 => Verbosity::Normal,
+        _ => Verbosity::Verbose,
+    }
+}
+
+/// Whether output at `level` should be printed
+pub fn enabled(level: Verbosity) -> bool {
+    verbosity() >= level
+}
+
+/// Print a `Warning: `-prefixed warning to stderr unless `--quiet` is set
+#[macro_export]
+macro_rules! log_warn {
+    ($($arg:tt)*) => {
+        if $crate::diagnostics::verbosity::enabled($crate::diagnostics::Verbosity::Normal) {
+            eprintln!("Warning: {}", format_args!($($arg)*));
+        }
+    };
+}
+
+/// Print a status note to stderr unless `--quiet` is set
+#[macro_export]
+macro_rules! log_info {
+    ($($arg:tt)*) => {
+        if $crate::diagnostics::verbosity::enabled($crate::diagnostics::Verbosity::Normal) {
+            eprintln!($($arg)*);
+        }
+    };
+}
+
+/// Print per-file progress to stderr, only with `--verbose`
+#[macro_export]
+macro_rules! log_verbose {
+    ($($arg:tt)*) => {
+        if $crate::diagnostics::verbosity::enabled($crate::diagnostics::Verbosity::Verbose) {
+            eprintln!($($arg)*);
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_verbosity_ordering() {
+        assert!(Verbosity::Quiet < Verbosity::Normal);
+        assert!(Verbosity::Normal < Verbosity::Verbose);
+        assert_eq!(Verbosity::default(), Verbosity::Normal);
+    }
+}
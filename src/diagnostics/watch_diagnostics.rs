@@ -5,14 +5,15 @@
 //! - Deterministic ordering via sort_key()
 //! - Human-readable stderr output
 //!
-//! Phase 3 scope (deferred):
-//! - JSON serialization with schema_version
-//! - Stable stdout/stderr discipline
+//! Phase 3 scope:
+//! - Line-delimited JSON with schema_version (`watch --output json`)
 
 use serde::{Deserialize, Serialize};
 use std::cmp::Ordering;
 use std::fmt;
 
+use crate::output::command::MAGELLAN_JSON_SCHEMA_VERSION;
+
 /// Reason why a file was skipped during indexing.
 ///
 /// Each variant represents a deterministic decision point in the filtering pipeline.
@@ -29,6 +30,12 @@ pub enum SkipReason {
     IgnoredByGitignore,
     /// Excluded by CLI --exclude glob pattern
     ExcludedByGlob,
+    /// File is larger than the configured `--max-file-size`
+    FileTooLarge,
+    /// Parsing ran past the configured `--parse-timeout-ms`
+    ParseTimeout,
+    /// Header marks the file as generated (`@generated`, `Code generated by`, ...)
+    Generated,
 }
 
 impl SkipReason {
@@ -41,7 +48,24 @@ impl SkipReason {
             SkipReason::IgnoredByGitignore => 1,  // Then gitignore rules
             SkipReason::ExcludedByGlob => 2,      // Then CLI excludes
             SkipReason::UnsupportedLanguage => 3, // Then language detection
-            SkipReason::NotAFile => 4,            // Last
+            SkipReason::FileTooLarge => 4,        // Then size limit
+            SkipReason::Generated => 5,           // Then generated headers
+            SkipReason::ParseTimeout => 6,        // Then parse limit
+            SkipReason::NotAFile => 7,            // Last
+        }
+    }
+
+    /// Stable machine-readable code for JSON output.
+    pub fn code(&self) -> &'static str {
+        match self {
+            SkipReason::NotAFile => "not_a_file",
+            SkipReason::UnsupportedLanguage => "unsupported_language",
+            SkipReason::IgnoredInternal => "ignored_internal",
+            SkipReason::IgnoredByGitignore => "ignored_by_gitignore",
+            SkipReason::ExcludedByGlob => "excluded_by_glob",
+            SkipReason::FileTooLarge => "file_too_large",
+            SkipReason::ParseTimeout => "parse_timeout",
+            SkipReason::Generated => "generated",
         }
     }
 
@@ -53,6 +77,9 @@ impl SkipReason {
             SkipReason::IgnoredInternal => "internal ignore rule",
             SkipReason::IgnoredByGitignore => "matched by gitignore",
             SkipReason::ExcludedByGlob => "excluded by pattern",
+            SkipReason::FileTooLarge => "exceeds max file size",
+            SkipReason::ParseTimeout => "parse timed out",
+            SkipReason::Generated => "generated file",
         }
     }
 }
@@ -108,6 +135,18 @@ impl DiagnosticStage {
         }
     }
 
+    /// Stable machine-readable code for JSON output.
+    pub fn code(&self) -> &'static str {
+        match self {
+            DiagnosticStage::Read => "read",
+            DiagnosticStage::Parse => "parse",
+            DiagnosticStage::IndexSymbols => "index_symbols",
+            DiagnosticStage::IndexReferences => "index_references",
+            DiagnosticStage::IndexCalls => "index_calls",
+            DiagnosticStage::Other => "other",
+        }
+    }
+
     /// Human-readable description for stderr output.
     pub fn description(&self) -> &'static str {
         match self {
@@ -217,6 +256,37 @@ impl WatchDiagnostic {
             }
         }
     }
+
+    /// Format as one line of JSON (Phase 3).
+    ///
+    /// Skips carry `"stage": null` and the skip reason code; errors carry
+    /// the stage code and the error message as `reason`.
+    ///
+    /// Example:
+    /// `{"schema_version":"1.0.0","kind":"error","stage":"parse","path":"src/bad.rs","reason":"syntax error at 3:5"}`
+    pub fn format_json(&self) -> String {
+        let value = match self {
+            WatchDiagnostic::Skipped { path, reason } => serde_json::json!({
+                "schema_version": MAGELLAN_JSON_SCHEMA_VERSION,
+                "kind": "skipped",
+                "stage": null,
+                "path": path,
+                "reason": reason.code(),
+            }),
+            WatchDiagnostic::Error {
+                path,
+                stage,
+                message,
+            } => serde_json::json!({
+                "schema_version": MAGELLAN_JSON_SCHEMA_VERSION,
+                "kind": "error",
+                "stage": stage.code(),
+                "path": path,
+                "reason": message,
+            }),
+        };
+        value.to_string()
+    }
 }
 
 impl fmt::Display for WatchDiagnostic {
@@ -337,6 +407,33 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_format_json_skipped() {
+        let diag = WatchDiagnostic::skipped("big.rs".to_string(), SkipReason::FileTooLarge);
+        let value: serde_json::Value = serde_json::from_str(&diag.format_json()).unwrap();
+        assert_eq!(value["schema_version"], MAGELLAN_JSON_SCHEMA_VERSION);
+        assert_eq!(value["kind"], "skipped");
+        assert!(value["stage"].is_null());
+        assert_eq!(value["path"], "big.rs");
+        assert_eq!(value["reason"], "file_too_large");
+    }
+
+    #[test]
+    fn test_format_json_error() {
+        let diag = WatchDiagnostic::error(
+            "src/bad.rs".to_string(),
+            DiagnosticStage::Parse,
+            "syntax error at 3:5".to_string(),
+        );
+        let line = diag.format_json();
+        assert!(!line.contains('\n'));
+        let value: serde_json::Value = serde_json::from_str(&line).unwrap();
+        assert_eq!(value["kind"], "error");
+        assert_eq!(value["stage"], "parse");
+        assert_eq!(value["path"], "src/bad.rs");
+        assert_eq!(value["reason"], "syntax error at 3:5");
+    }
+
     #[test]
     fn test_skip_reason_display() {
         assert_eq!(
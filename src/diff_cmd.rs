@@ -0,0 +1,115 @@
+//! Diff command implementation
+//!
+//! Compares the symbols and call edges recorded in two databases, e.g.
+//! index snapshots taken at two releases.
+
+use anyhow::Result;
+use std::path::PathBuf;
+
+use crate::{generate_execution_id, CodeGraph};
+use magellan::graph::query::SymbolLocation;
+use magellan::output::command::OutputFormat;
+
+fn location_label(location: &SymbolLocation) -> String {
+    format!("{}:{}", location.file, location.start_line)
+}
+
+/// Run the diff command
+pub fn run_diff(old_db: PathBuf, new_db: PathBuf, output_format: OutputFormat) -> Result<()> {
+    let mut old = CodeGraph::open_readonly(&old_db)?;
+    let mut new = CodeGraph::open_readonly(&new_db)?;
+    let exec_id = generate_execution_id();
+
+    let report = magellan::index_diff::diff_indexes(&mut old, &mut new)?;
+
+    match output_format {
+        OutputFormat::Json | OutputFormat::Pretty => {
+            let response = serde_json::json!({
+                "schema_version": "1.0.0",
+                "execution_id": &exec_id,
+                "data": {
+                    "old_db": old_db.to_string_lossy(),
+                    "new_db": new_db.to_string_lossy(),
+                    "added": report.added,
+                    "removed": report.removed,
+                    "moved": report.moved,
+                    "unchanged": report.unchanged,
+                    "added_calls": report.added_calls,
+                    "removed_calls": report.removed_calls,
+                },
+                "tool": "magellan",
+                "timestamp": chrono::Utc::now().to_rfc3339(),
+            });
+            let json_str = match output_format {
+                OutputFormat::Pretty => serde_json::to_string_pretty(&response)?,
+                _ => serde_json::to_string(&response)?,
+            };
+            println!("{json_str}");
+        }
+        OutputFormat::Human => {
+            println!(
+                "Diff: {} -> {}",
+                old_db.to_string_lossy(),
+                new_db.to_string_lossy()
+            );
+
+            if !report.removed.is_empty() {
+                println!("Removed ({}):", report.removed.len());
+                for change in &report.removed {
+                    println!(
+                        "  - {} {} ({})",
+                        change.location.kind,
+                        change.fqn,
+                        location_label(&change.location)
+                    );
+                }
+            }
+
+            if !report.moved.is_empty() {
+                println!("Moved ({}):", report.moved.len());
+                for change in &report.moved {
+                    println!(
+                        "  ~ {} {} ({} -> {})",
+                        change.after.kind,
+                        change.fqn,
+                        location_label(&change.before),
+                        location_label(&change.after)
+                    );
+                }
+            }
+
+            if !report.added.is_empty() {
+                println!("Added ({}):", report.added.len());
+                for change in &report.added {
+                    println!(
+                        "  + {} {} ({})",
+                        change.location.kind,
+                        change.fqn,
+                        location_label(&change.location)
+                    );
+                }
+            }
+
+            if !report.removed_calls.is_empty() {
+                println!("Removed calls ({}):", report.removed_calls.len());
+                for edge in &report.removed_calls {
+                    println!("  - {} -> {} ({})", edge.caller, edge.callee, edge.file);
+                }
+            }
+
+            if !report.added_calls.is_empty() {
+                println!("Added calls ({}):", report.added_calls.len());
+                for edge in &report.added_calls {
+                    println!("  + {} -> {} ({})", edge.caller, edge.callee, edge.file);
+                }
+            }
+
+            if report.is_empty() {
+                println!("No symbol or call changes.");
+            }
+            println!("{} symbols unchanged.", report.unchanged);
+        }
+    }
+
+    Ok(())
+}
@@ -4,6 +4,7 @@
 
 use anyhow::Result;
 use magellan::common::{find_repo_root, magellan_dir};
+use magellan::log_warn;
 use magellan::output::generate_execution_id;
 use magellan::CodeGraph;
 use magellan::OutputFormat;
@@ -305,7 +306,7 @@ pub fn run_doctor(db_path: PathBuf, fix: bool, output_format: OutputFormat) -> R
                     use magellan::context::build_context_index;
                     match build_context_index(&mut graph, &db_path) {
                         Ok(_) => issues_fixed += 1,
-                        Err(e) => eprintln!("Warning: Failed to build context index: {}", e),
+                        Err(e) => log_warn!("Failed to build context index: {}", e),
                     }
                 }
                 issues_found += 1;
@@ -429,7 +430,7 @@ pub fn run_doctor(db_path: PathBuf, fix: bool, output_format: OutputFormat) -> R
                         drop(graph);
                         match CodeGraph::open(&db_path) {
                             Ok(_) => issues_fixed += 1,
-                            Err(e) => eprintln!("Warning: Failed to re-open database: {}", e),
+                            Err(e) => log_warn!("Failed to re-open database: {}", e),
                         }
                     }
                     issues_found += 1;
@@ -528,7 +529,7 @@ pub fn run_doctor(db_path: PathBuf, fix: bool, output_format: OutputFormat) -> R
                                         issues_fixed += 1;
                                     }
                                     Err(e) => {
-                                        eprintln!("Warning: Failed to rebuild FTS5 index: {}", e);
+                                        log_warn!("Failed to rebuild FTS5 index: {}", e);
                                     }
                                 }
                             }
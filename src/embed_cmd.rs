@@ -6,6 +6,7 @@
 
 use anyhow::Result;
 use magellan::graph::CodeGraph;
+use magellan::log_info;
 use magellan::output::OutputFormat;
 use serde_json::json;
 use std::path::PathBuf;
@@ -33,7 +34,7 @@ pub fn run_embed(
     let (embedded, skipped, failed) =
         graph.embed_from_db(force, batch, parallel, |path, count, idx, total| {
             if matches!(output_format, OutputFormat::Human) {
-                eprintln!("  [{}/{}] {} — {} symbols", idx + 1, total, path, count);
+                log_info!("  [{}/{}] {} — {} symbols", idx + 1, total, path, count);
             }
         })?;
 
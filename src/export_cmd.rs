@@ -1,19 +1,88 @@
 //! Export command implementation
 //!
-//! Exports graph data to JSON/JSONL/CSV/DOT/SCIP formats with stable IDs.
+//! Exports graph data to JSON/JSONL/CSV/DOT/SCIP/SARIF formats with stable IDs.
+//! Every text or SCIP format can be gzip-compressed with `--gzip`; `sqlite-flat`
+//! writes an uncompressed database file.
 
 use anyhow::Result;
+use flate2::write::GzEncoder;
+use flate2::Compression;
 use magellan::common::{find_repo_root, magellan_dir};
 use magellan::graph::export::{
-    export_graph, scip, stream_json, stream_json_minified, stream_ndjson, ExportConfig,
-    ExportFilters, ExportFormat,
+    export_graph, scip, sqlite_flat, stream_json, stream_json_minified, stream_ndjson,
+    ExportConfig, ExportFilters, ExportFormat, JsonlRecordType,
 };
 use magellan::graph::query::CollisionField;
 use magellan::output::generate_execution_id;
 use magellan::CodeGraph;
+use magellan::{log_info, log_warn};
 use std::fs::{self, File};
-use std::io::{self, Write};
-use std::path::PathBuf;
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+/// Destination of an export: a file or stdout, optionally gzip-compressed
+///
+/// Compression happens as bytes are written, so streaming exports stay
+/// streaming. Call [`ExportSink::finish`] to write the gzip trailer.
+enum ExportSink {
+    Plain(Box<dyn Write>),
+    Gzip(GzEncoder<Box<dyn Write>>),
+}
+
+impl ExportSink {
+    fn file(path: &Path, gzip: bool) -> Result<Self> {
+        let file = File::create(path)?;
+        Ok(Self::wrap(Box::new(BufWriter::new(file)), gzip))
+    }
+
+    fn stdout(gzip: bool) -> Self {
+        Self::wrap(Box::new(BufWriter::new(io::stdout().lock())), gzip)
+    }
+
+    fn wrap(inner: Box<dyn Write>, gzip: bool) -> Self {
+        if gzip {
+            ExportSink::Gzip(GzEncoder::new(inner, Compression::default()))
+        } else {
+            ExportSink::Plain(inner)
+        }
+    }
+
+    /// Complete the gzip stream (if any) and flush the destination
+    fn finish(self) -> Result<()> {
+        let mut inner = match self {
+            ExportSink::Plain(inner) => inner,
+            ExportSink::Gzip(encoder) => encoder.finish()?,
+        };
+        inner.flush()?;
+        Ok(())
+    }
+}
+
+impl Write for ExportSink {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self {
+            ExportSink::Plain(inner) => inner.write(buf),
+            ExportSink::Gzip(encoder) => encoder.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self {
+            ExportSink::Plain(inner) => inner.flush(),
+            ExportSink::Gzip(encoder) => encoder.flush(),
+        }
+    }
+}
+
+/// `path` with `.gz` appended unless it already ends in `.gz`
+fn with_gz_extension(path: PathBuf) -> PathBuf {
+    if path.extension().is_some_and(|ext| ext == "gz") {
+        return path;
+    }
+    let mut name = path.into_os_string();
+    name.push(".gz");
+    PathBuf::from(name)
+}
 
 /// Get default output path for repo-root export
 ///
@@ -104,12 +173,12 @@ fn print_export_summary(
     let calls = graph.count_calls()?;
 
     // Print summary (to stderr so it doesn't interfere with stdout exports)
-    eprintln!("Export complete: {}", output_path.display());
-    eprintln!("  Format: {}", format_name(format));
-    eprintln!("  Size: {}", size);
-    eprintln!("  Files: {}", files);
-    eprintln!("  Symbols: {}", symbols);
-    eprintln!("  Calls: {}", calls);
+    log_info!("Export complete: {}", output_path.display());
+    log_info!("  Format: {}", format_name(format));
+    log_info!("  Size: {}", size);
+    log_info!("  Files: {}", files);
+    log_info!("  Symbols: {}", symbols);
+    log_info!("  Calls: {}", calls);
 
     Ok(())
 }
@@ -123,13 +192,14 @@ fn print_export_summary(
 ///
 /// # Arguments
 /// * `db_path` - Path to the sqlitegraph database
-/// * `format` - Export format (Json, JsonL, Dot, Csv, Scip, Impact)
+/// * `format` - Export format (Json, JsonL, Dot, Csv, Scip, Impact, Sarif, SqliteFlat)
 /// * `output` - Optional file path for output
 /// * `include_symbols` - Whether to include symbols in export
 /// * `include_references` - Whether to include references in export
 /// * `include_calls` - Whether to include calls in export
 /// * `minify` - Whether to minify JSON output
-/// * `filters` - Export filters for DOT format (file, symbol, kind, max_depth, cluster)
+/// * `gzip` - Whether to gzip the output (`.gz` is appended to `output`)
+/// * `filters` - Export filters for DOT format (file, symbol, kind, max_depth, cluster, calls_only)
 /// * `impact_symbol` - Symbol name for impact export (required for Impact format)
 /// * `impact_file` - Optional file path for impact export symbol disambiguation
 /// * `impact_depth` - Max depth for impact export BFS traversal
@@ -148,8 +218,11 @@ pub fn run_export(
     include_references: bool,
     include_calls: bool,
     minify: bool,
+    gzip: bool,
     include_collisions: bool,
     collisions_field: CollisionField,
+    include_unresolved: bool,
+    record_types: Vec<JsonlRecordType>,
     filters: ExportFilters,
     impact_symbol: Option<String>,
     impact_file: Option<String>,
@@ -157,6 +230,21 @@ pub fn run_export(
 ) -> Result<()> {
     let mut graph = CodeGraph::open(&db_path)?;
     let exec_id = generate_execution_id();
+    let output = if gzip {
+        output.map(with_gz_extension)
+    } else {
+        output
+    };
+    // Repo-root default paths are per format; `--gzip` compresses those too
+    let default_output = |format: &ExportFormat| {
+        get_default_repo_root_output(&db_path, format).map(|path| {
+            if gzip {
+                with_gz_extension(path)
+            } else {
+                path
+            }
+        })
+    };
 
     // Build command args for execution tracking
     let mut args = vec!["export".to_string()];
@@ -178,6 +266,9 @@ pub fn run_export(
     if minify {
         args.push("--minify".to_string());
     }
+    if gzip {
+        args.push("--gzip".to_string());
+    }
     if include_collisions {
         args.push("--include-collisions".to_string());
         if collisions_field != CollisionField::Fqn {
@@ -185,6 +276,13 @@ pub fn run_export(
             args.push(collisions_field.as_str().to_string());
         }
     }
+    if include_unresolved {
+        args.push("--include-unresolved".to_string());
+    }
+    for record_type in &record_types {
+        args.push("--type".to_string());
+        args.push(record_type.as_str().to_string());
+    }
     if let Some(ref file) = filters.file {
         args.push("--file".to_string());
         args.push(file.clone());
@@ -193,8 +291,12 @@ pub fn run_export(
         args.push("--symbol".to_string());
         args.push(symbol.clone());
     }
-    if let Some(ref kind) = filters.kind {
-        args.push("--kind".to_string());
+    for kind in &filters.kinds {
+        args.push("--filter-kind".to_string());
+        args.push(kind.clone());
+    }
+    for kind in &filters.exclude_kinds {
+        args.push("--exclude-kind".to_string());
         args.push(kind.clone());
     }
     if let Some(max_depth) = filters.max_depth {
@@ -202,7 +304,10 @@ pub fn run_export(
         args.push(max_depth.to_string());
     }
     if filters.cluster {
-        args.push("--cluster".to_string());
+        args.push("--cluster-by-file".to_string());
+    }
+    if filters.calls_only {
+        args.push("--calls-only".to_string());
     }
 
     // Start execution tracking
@@ -229,16 +334,17 @@ pub fn run_export(
         // Write output (SCIP requires file output)
         match output {
             Some(path) => {
-                let mut file = File::create(&path)?;
-                file.write_all(&scip_bytes)?;
+                let mut sink = ExportSink::file(&path, gzip)?;
+                sink.write_all(&scip_bytes)?;
+                sink.finish()?;
                 print_export_summary(&path, format, &mut graph)?;
             }
             None => {
                 // SCIP is binary, warn user but still write to stdout
-                eprintln!(
-                    "Warning: SCIP format is binary. Use --output file.scip for proper output."
-                );
-                io::stdout().write_all(&scip_bytes)?;
+                log_warn!("SCIP format is binary. Use --output file.scip for proper output.");
+                let mut sink = ExportSink::stdout(gzip);
+                sink.write_all(&scip_bytes)?;
+                sink.finish()?;
             }
         }
     // Handle LSIF format specially (JSONL output)
@@ -249,17 +355,36 @@ pub fn run_export(
         let (package_name, package_version) = detect_package_info(&db_path);
 
         // Export to LSIF
-        match output {
-            Some(path) => {
-                let _count =
-                    lsif::export::export_lsif(&mut graph, &path, &package_name, &package_version)?;
-                print_export_summary(&path, format, &mut graph)?;
-            }
-            None => {
-                eprintln!("Warning: LSIF format requires --output file.lsif");
-                eprintln!("Use: magellan export --db code.db --format lsif --output output.lsif");
-            }
+        let path = output.ok_or_else(|| anyhow::anyhow!("LSIF export requires --output <PATH>"))?;
+        let mut sink = ExportSink::file(&path, gzip)?;
+        let _count =
+            lsif::export_lsif_to_writer(&mut graph, &mut sink, &package_name, &package_version)?;
+        sink.finish()?;
+        print_export_summary(&path, format, &mut graph)?;
+    // Handle flat SQLite specially (writes a database file)
+    } else if format == ExportFormat::SqliteFlat {
+        let path =
+            output.ok_or_else(|| anyhow::anyhow!("sqlite-flat export requires --output <PATH>"))?;
+        if path.exists() && fs::canonicalize(&path)? == fs::canonicalize(&db_path)? {
+            return Err(anyhow::anyhow!(
+                "--output {} is the source database",
+                path.display()
+            ));
         }
+        let config = ExportConfig {
+            format,
+            include_symbols,
+            include_references,
+            include_calls,
+            ..ExportConfig::default()
+        };
+        let counts = sqlite_flat::export_sqlite_flat(&mut graph, &config, &path)?;
+        log_info!("Export complete: {}", path.display());
+        log_info!("  Format: sqlite-flat");
+        log_info!("  Files: {}", counts.files);
+        log_info!("  Symbols: {}", counts.symbols);
+        log_info!("  Calls: {}", counts.calls);
+        log_info!("  References: {}", counts.references);
     // Handle Impact format specially (requires --symbol parameter)
     } else if format == ExportFormat::Impact {
         use magellan::context::impact_analysis;
@@ -293,28 +418,32 @@ pub fn run_export(
 
         match output {
             Some(path) => {
-                let mut file = File::create(&path)?;
-                file.write_all(json_str.as_bytes())?;
-                file.write_all(b"\n")?;
-                eprintln!("Export complete: {}", path.display());
-                eprintln!("  Format: impact");
-                eprintln!("  Symbol: {}", symbol_name);
-                eprintln!("  Total impacted: {}", impacted.len());
+                let mut sink = ExportSink::file(&path, gzip)?;
+                sink.write_all(json_str.as_bytes())?;
+                sink.write_all(b"\n")?;
+                sink.finish()?;
+                log_info!("Export complete: {}", path.display());
+                log_info!("  Format: impact");
+                log_info!("  Symbol: {}", symbol_name);
+                log_info!("  Total impacted: {}", impacted.len());
             }
             None => {
                 // Use repo-root convention if available, otherwise stdout
-                if let Some(default_path) = get_default_repo_root_output(&db_path, &format) {
-                    let mut file = File::create(&default_path)?;
-                    file.write_all(json_str.as_bytes())?;
-                    file.write_all(b"\n")?;
-                    eprintln!("Export complete: {}", default_path.display());
-                    eprintln!("  Format: impact");
-                    eprintln!("  Symbol: {}", symbol_name);
-                    eprintln!("  Total impacted: {}", impacted.len());
+                if let Some(default_path) = default_output(&format) {
+                    let mut sink = ExportSink::file(&default_path, gzip)?;
+                    sink.write_all(json_str.as_bytes())?;
+                    sink.write_all(b"\n")?;
+                    sink.finish()?;
+                    log_info!("Export complete: {}", default_path.display());
+                    log_info!("  Format: impact");
+                    log_info!("  Symbol: {}", symbol_name);
+                    log_info!("  Total impacted: {}", impacted.len());
                 } else {
                     // Fall back to stdout
-                    io::stdout().write_all(json_str.as_bytes())?;
-                    io::stdout().write_all(b"\n")?;
+                    let mut sink = ExportSink::stdout(gzip);
+                    sink.write_all(json_str.as_bytes())?;
+                    sink.write_all(b"\n")?;
+                    sink.finish()?;
                 }
             }
         }
@@ -329,6 +458,8 @@ pub fn run_export(
             filters,
             include_collisions,
             collisions_field,
+            include_unresolved,
+            record_types,
         };
 
         // Use streaming for JSON and JSONL formats to reduce memory for large graphs
@@ -339,35 +470,36 @@ pub fn run_export(
                 if minify {
                     match output {
                         Some(ref path) => {
-                            let mut file = File::create(path)?;
-                            stream_json_minified(&mut graph, &config, &mut file)?;
+                            let mut sink = ExportSink::file(path, gzip)?;
+                            stream_json_minified(&mut graph, &config, &mut sink)?;
+                            sink.finish()?;
                             print_export_summary(path, format, &mut graph)?;
                         }
                         None => {
-                            let stdout = io::stdout();
-                            let mut handle = stdout.lock();
-                            stream_json_minified(&mut graph, &config, &mut handle)?;
+                            let mut sink = ExportSink::stdout(gzip);
+                            stream_json_minified(&mut graph, &config, &mut sink)?;
+                            sink.finish()?;
                         }
                     }
                 } else {
                     match output {
                         Some(ref path) => {
-                            let mut file = File::create(path)?;
-                            stream_json(&mut graph, &config, &mut file)?;
+                            let mut sink = ExportSink::file(path, gzip)?;
+                            stream_json(&mut graph, &config, &mut sink)?;
+                            sink.finish()?;
                             print_export_summary(path, format, &mut graph)?;
                         }
                         None => {
                             // Use repo-root convention if available, otherwise stdout
-                            if let Some(default_path) =
-                                get_default_repo_root_output(&db_path, &format)
-                            {
-                                let mut file = File::create(&default_path)?;
-                                stream_json(&mut graph, &config, &mut file)?;
+                            if let Some(default_path) = default_output(&format) {
+                                let mut sink = ExportSink::file(&default_path, gzip)?;
+                                stream_json(&mut graph, &config, &mut sink)?;
+                                sink.finish()?;
                                 print_export_summary(&default_path, format, &mut graph)?;
                             } else {
-                                let stdout = io::stdout();
-                                let mut handle = stdout.lock();
-                                stream_json(&mut graph, &config, &mut handle)?;
+                                let mut sink = ExportSink::stdout(gzip);
+                                stream_json(&mut graph, &config, &mut sink)?;
+                                sink.finish()?;
                             }
                         }
                     }
@@ -377,39 +509,44 @@ pub fn run_export(
                 // Stream JSONL output (naturally streaming-friendly)
                 match output {
                     Some(ref path) => {
-                        let mut file = File::create(path)?;
-                        stream_ndjson(&mut graph, &config, &mut file)?;
+                        let mut sink = ExportSink::file(path, gzip)?;
+                        stream_ndjson(&mut graph, &config, &mut sink)?;
+                        sink.finish()?;
                         print_export_summary(path, format, &mut graph)?;
                     }
                     None => {
                         // Use repo-root convention if available, otherwise stdout
-                        if let Some(default_path) = get_default_repo_root_output(&db_path, &format)
-                        {
-                            let mut file = File::create(&default_path)?;
-                            stream_ndjson(&mut graph, &config, &mut file)?;
+                        if let Some(default_path) = default_output(&format) {
+                            let mut sink = ExportSink::file(&default_path, gzip)?;
+                            stream_ndjson(&mut graph, &config, &mut sink)?;
+                            sink.finish()?;
                             print_export_summary(&default_path, format, &mut graph)?;
                         } else {
-                            let stdout = io::stdout();
-                            let mut handle = stdout.lock();
-                            stream_ndjson(&mut graph, &config, &mut handle)?;
+                            let mut sink = ExportSink::stdout(gzip);
+                            stream_ndjson(&mut graph, &config, &mut sink)?;
+                            sink.finish()?;
                         }
                     }
                 }
             }
             _ => {
-                // Other formats (DOT, CSV) use the existing in-memory export
+                // Other formats (DOT, CSV, SARIF) use the existing in-memory export
                 let exported_data = export_graph(&mut graph, &config)?;
 
                 // Write output
                 match output {
                     Some(ref path) => {
-                        let mut file = File::create(path)?;
-                        file.write_all(exported_data.as_bytes())?;
-                        file.write_all(b"\n")?;
+                        let mut sink = ExportSink::file(path, gzip)?;
+                        sink.write_all(exported_data.as_bytes())?;
+                        sink.write_all(b"\n")?;
+                        sink.finish()?;
                         print_export_summary(path, format, &mut graph)?;
                     }
                     None => {
-                        println!("{}", exported_data);
+                        let mut sink = ExportSink::stdout(gzip);
+                        sink.write_all(exported_data.as_bytes())?;
+                        sink.write_all(b"\n")?;
+                        sink.finish()?;
                     }
                 }
             }
@@ -437,6 +574,8 @@ fn format_name(format: ExportFormat) -> String {
         ExportFormat::Scip => "scip".to_string(),
         ExportFormat::Lsif => "lsif".to_string(),
         ExportFormat::Impact => "impact".to_string(),
+        ExportFormat::Sarif => "sarif".to_string(),
+        ExportFormat::SqliteFlat => "sqlite-flat".to_string(),
     }
 }
 
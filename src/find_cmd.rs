@@ -3,18 +3,25 @@
 //! Finds a symbol by name, optionally limited to a specific file.
 //! Now supports multiple backends: SQLite, Geometric, V3
 
+use crate::db_resolver::open_graph_for_read;
 use crate::service::registry::Registry;
 use anyhow::{Context, Result};
 use globset::GlobBuilder;
-use magellan::common::{detect_language_from_path, format_symbol_kind, resolve_path};
-use magellan::graph::query;
+use magellan::common::{
+    detect_language_from_path, format_symbol_kind, parse_symbol_kind_arg, resolve_path,
+};
 use magellan::graph::MultiDbContext;
-use magellan::output::rich::{SpanChecksums, SpanContext};
+use magellan::graph::{fuzzy, query};
+use magellan::ingest::test_code::TestFilter;
+use magellan::ingest::visibility::VisibilityFilter;
+use magellan::log_warn;
+use magellan::output::rich::SpanContext;
 use magellan::output::{
-    output_json, CalleeInfo, CallerInfo, FindResponse, JsonResponse, OutputFormat, Span,
-    SymbolMatch,
+    output_count, output_json, CalleeInfo, CallerInfo, ColumnConverter, ColumnUnit, FindResponse,
+    JsonResponse, OutputFormat, Span, SymbolMatch,
 };
 use magellan::{CodeGraph, SymbolKind};
+use serde::Serialize;
 use std::path::PathBuf;
 
 /// Represents a found symbol with its file and node ID
@@ -37,6 +44,23 @@ struct FoundSymbol {
     display_fqn: Option<String>,
 }
 
+/// Convert found symbols' columns from bytes to `column_unit`
+fn convert_found_columns<'a>(
+    graph: &CodeGraph,
+    symbols: impl IntoIterator<Item = &'a mut FoundSymbol>,
+    column_unit: ColumnUnit,
+) {
+    if column_unit == ColumnUnit::Byte {
+        return;
+    }
+    let mut columns = ColumnConverter::for_graph(column_unit, graph);
+    for s in symbols {
+        s.col = columns.convert(&s.file, s.line, s.col);
+        s.start_col = columns.convert(&s.file, s.start_line, s.start_col);
+        s.end_col = columns.convert(&s.file, s.end_line, s.end_col);
+    }
+}
+
 /// Score a symbol based on relevance criteria
 ///
 /// Scoring criteria (highest score first):
@@ -93,10 +117,21 @@ fn score_symbol(symbol: &FoundSymbol, query: &str) -> i32 {
 /// Find a symbol in a specific file by name
 ///
 /// Returns the first matching symbol with its node ID and symbol_id
-fn find_in_file(graph: &mut CodeGraph, file_path: &str, name: &str) -> Result<Option<FoundSymbol>> {
+fn find_in_file(
+    graph: &mut CodeGraph,
+    file_path: &str,
+    name: &str,
+    test_filter: TestFilter,
+    visibility: Option<VisibilityFilter>,
+) -> Result<Option<FoundSymbol>> {
     let entries = query::symbol_nodes_in_file_with_ids(graph, file_path)?;
 
     for (node_id, symbol, symbol_id) in entries {
+        if !test_filter.matches(symbol.is_test)
+            || !visibility.is_none_or(|v| v.matches(symbol.visibility.as_deref()))
+        {
+            continue;
+        }
         if let Some(symbol_name) = &symbol.name {
             if symbol_name == name {
                 return Ok(Some(FoundSymbol {
@@ -127,8 +162,14 @@ fn find_in_file(graph: &mut CodeGraph, file_path: &str, name: &str) -> Result<Op
 /// Find a symbol across all files by name
 ///
 /// Returns matching symbols sorted by relevance score (highest first),
-/// limited to top 10 results.
-fn find_all_files(graph: &mut CodeGraph, name: &str) -> Result<Vec<FoundSymbol>> {
+/// limited to top 10 results. Equal scores fall back to candidate order:
+/// file path, then byte start, then stable symbol ID.
+fn find_all_files(
+    graph: &mut CodeGraph,
+    name: &str,
+    test_filter: TestFilter,
+    visibility: Option<VisibilityFilter>,
+) -> Result<Vec<FoundSymbol>> {
     let mut results = Vec::new();
 
     // Get all indexed files
@@ -138,6 +179,11 @@ fn find_all_files(graph: &mut CodeGraph, name: &str) -> Result<Vec<FoundSymbol>>
     for file_path in file_nodes.keys() {
         let entries = query::symbol_nodes_in_file_with_ids(graph, file_path)?;
         for (node_id, symbol, symbol_id) in entries {
+            if !test_filter.matches(symbol.is_test)
+                || !visibility.is_none_or(|v| v.matches(symbol.visibility.as_deref()))
+            {
+                continue;
+            }
             if let Some(symbol_name) = &symbol.name {
                 // Match exact name or substring
                 if symbol_name == name || symbol_name.contains(name) {
@@ -164,11 +210,16 @@ fn find_all_files(graph: &mut CodeGraph, name: &str) -> Result<Vec<FoundSymbol>>
         }
     }
 
-    // Score and sort by relevance (highest score first)
+    // Score and sort by relevance (highest score first); files come from a
+    // HashMap, so ties need the candidate order to be stable across runs
     results.sort_by(|a, b| {
         let score_a = score_symbol(a, name);
         let score_b = score_symbol(b, name);
-        score_b.cmp(&score_a) // Descending order
+        score_b
+            .cmp(&score_a) // Descending order
+            .then_with(|| a.file.cmp(&b.file))
+            .then_with(|| a.byte_start.cmp(&b.byte_start))
+            .then_with(|| a.symbol_id.cmp(&b.symbol_id))
     });
 
     // Limit to top 10 results
@@ -177,6 +228,471 @@ fn find_all_files(graph: &mut CodeGraph, name: &str) -> Result<Vec<FoundSymbol>>
     Ok(results)
 }
 
+/// Run `find --count-only`
+///
+/// Counts matches with a single `COUNT(*)` query instead of loading symbols.
+/// Matching follows `find`: exact name within `--path`, substring match
+/// across all files otherwise (and, unlike the listing, without the top-10
+/// cap). Without `--name`, every symbol in scope is counted.
+pub fn run_find_count(
+    db_path: PathBuf,
+    name: Option<String>,
+    root: Option<PathBuf>,
+    path: Option<PathBuf>,
+    test_filter: TestFilter,
+    visibility: Option<VisibilityFilter>,
+    output_format: OutputFormat,
+) -> Result<()> {
+    let mut graph = open_graph_for_read(&db_path)?;
+    let filter = query::SymbolCountFilter {
+        name_contains: path.is_none(),
+        file_path: path.as_ref().map(|p| resolve_path(p, &root)),
+        kind: None,
+        name,
+        test_filter,
+        visibility,
+    };
+    let count = graph.count_symbols_matching(&filter)?;
+    output_count(count, output_format)
+}
+
+/// Run `find --fuzzy`
+///
+/// Scores every symbol name (or FQN with `fuzzy_fqn`) in scope with
+/// [`fuzzy::fuzzy_score`] and prints the best `limit` matches. Ties are
+/// broken by shorter name, then name, file, line and column, so the ranking
+/// is deterministic.
+#[allow(
+    clippy::too_many_arguments,
+    reason = "CLI command surface: each arg maps to a flag"
+)]
+pub fn run_find_fuzzy(
+    db_path: PathBuf,
+    fuzzy_query: String,
+    fuzzy_fqn: bool,
+    root: Option<PathBuf>,
+    path: Option<PathBuf>,
+    limit: usize,
+    test_filter: TestFilter,
+    visibility: Option<VisibilityFilter>,
+    output_format: OutputFormat,
+    column_unit: ColumnUnit,
+) -> Result<()> {
+    let mut graph = open_graph_for_read(&db_path)?;
+    let exec_id = magellan::output::generate_execution_id();
+
+    let files: Vec<String> = match &path {
+        Some(p) => vec![resolve_path(p, &root)],
+        None => graph.all_file_nodes()?.into_keys().collect(),
+    };
+
+    let mut ranked: Vec<(i64, FoundSymbol)> = Vec::new();
+    for file_path in &files {
+        let entries = query::symbol_nodes_in_file_with_ids(&mut graph, file_path)?;
+        for (node_id, fact, symbol_id) in entries {
+            if !test_filter.matches(fact.is_test)
+                || !visibility.is_none_or(|v| v.matches(fact.visibility.as_deref()))
+            {
+                continue;
+            }
+            let Some(name) = &fact.name else {
+                continue;
+            };
+            let target = if fuzzy_fqn {
+                fact.display_fqn
+                    .as_deref()
+                    .or(fact.canonical_fqn.as_deref())
+                    .unwrap_or(name)
+            } else {
+                name
+            };
+            let Some(score) = fuzzy::fuzzy_score(&fuzzy_query, target) else {
+                continue;
+            };
+            ranked.push((
+                score,
+                FoundSymbol {
+                    name: name.clone(),
+                    kind: fact.kind.clone(),
+                    kind_normalized: fact.kind_normalized.clone(),
+                    file: file_path.clone(),
+                    byte_start: fact.byte_start,
+                    byte_end: fact.byte_end,
+                    line: fact.start_line,
+                    col: fact.start_col,
+                    start_line: fact.start_line,
+                    start_col: fact.start_col,
+                    end_line: fact.end_line,
+                    end_col: fact.end_col,
+                    node_id,
+                    symbol_id,
+                    canonical_fqn: fact.canonical_fqn.clone(),
+                    display_fqn: fact.display_fqn.clone(),
+                },
+            ));
+        }
+    }
+
+    ranked.sort_by(|(score_a, a), (score_b, b)| {
+        score_b
+            .cmp(score_a)
+            .then_with(|| a.name.len().cmp(&b.name.len()))
+            .then_with(|| a.name.cmp(&b.name))
+            .then_with(|| a.file.cmp(&b.file))
+            .then_with(|| a.line.cmp(&b.line))
+            .then_with(|| a.col.cmp(&b.col))
+    });
+    ranked.truncate(limit);
+    convert_found_columns(&graph, ranked.iter_mut().map(|(_, s)| s), column_unit);
+
+    if output_format == OutputFormat::Json || output_format == OutputFormat::Pretty {
+        let matches: Vec<SymbolMatch> = ranked
+            .into_iter()
+            .map(|(score, s)| {
+                let span = Span::new(
+                    s.file.clone(),
+                    s.byte_start,
+                    s.byte_end,
+                    s.start_line,
+                    s.start_col,
+                    s.end_line,
+                    s.end_col,
+                );
+                let mut symbol_match =
+                    SymbolMatch::new(s.name, s.kind_normalized, span, None, s.symbol_id);
+                symbol_match.score = Some(score);
+                symbol_match
+            })
+            .collect();
+
+        let response = FindResponse {
+            matches,
+            query_name: fuzzy_query,
+            file_filter: path.map(|p| p.to_string_lossy().to_string()),
+            column_unit: column_unit.as_str().to_string(),
+        };
+        let json_response = JsonResponse::new(response, &exec_id);
+        return output_json(&json_response, output_format);
+    }
+
+    if ranked.is_empty() {
+        println!("No symbols fuzzy-matched '{}'.", fuzzy_query);
+        return Ok(());
+    }
+
+    println!("Ranked {} matches for '{}':", ranked.len(), fuzzy_query);
+    for (rank, (score, symbol)) in ranked.iter().enumerate() {
+        println!(
+            "  [{}] {} ({}) score={} in {}:{}",
+            rank + 1,
+            symbol.name,
+            format_symbol_kind(&symbol.kind),
+            score,
+            symbol.file,
+            symbol.line
+        );
+    }
+
+    Ok(())
+}
+
+/// Run `find --near`
+///
+/// Ranks the symbols of `file` by the distance of their start line to
+/// `line` (see [`query::symbols_near_line`]), optionally narrowed by
+/// `--kind`, and prints the closest `limit`.
+#[allow(
+    clippy::too_many_arguments,
+    reason = "CLI command surface: each arg maps to a flag"
+)]
+pub fn run_find_near(
+    db_path: PathBuf,
+    file: PathBuf,
+    line: usize,
+    kind: Option<String>,
+    root: Option<PathBuf>,
+    limit: usize,
+    test_filter: TestFilter,
+    visibility: Option<VisibilityFilter>,
+    output_format: OutputFormat,
+    column_unit: ColumnUnit,
+) -> Result<()> {
+    let kind = kind.as_deref().map(parse_symbol_kind_arg).transpose()?;
+    let mut graph = open_graph_for_read(&db_path)?;
+    let exec_id = magellan::output::generate_execution_id();
+
+    let file_path = resolve_path(&file, &root);
+    let mut ranked = query::symbols_near_line(&mut graph, &file_path, line)?;
+    ranked.retain(|(_, _, fact, _)| {
+        kind.as_ref().is_none_or(|kind| fact.kind == *kind)
+            && test_filter.matches(fact.is_test)
+            && visibility.is_none_or(|v| v.matches(fact.visibility.as_deref()))
+    });
+    ranked.truncate(limit);
+    let mut columns = ColumnConverter::for_graph(column_unit, &graph);
+    for (_, _, fact, _) in &mut ranked {
+        columns.convert_symbol(fact);
+    }
+
+    let location = format!("{}:{}", file.display(), line);
+    if output_format == OutputFormat::Json || output_format == OutputFormat::Pretty {
+        let matches: Vec<SymbolMatch> = ranked
+            .into_iter()
+            .map(|(distance, _, fact, symbol_id)| {
+                let span = Span::new(
+                    fact.file_path.to_string_lossy().to_string(),
+                    fact.byte_start,
+                    fact.byte_end,
+                    fact.start_line,
+                    fact.start_col,
+                    fact.end_line,
+                    fact.end_col,
+                );
+                let name = fact.name.unwrap_or_else(|| "(unnamed)".to_string());
+                let mut symbol_match =
+                    SymbolMatch::new(name, fact.kind_normalized, span, None, symbol_id);
+                symbol_match.distance = Some(distance);
+                symbol_match
+            })
+            .collect();
+
+        let response = FindResponse {
+            matches,
+            query_name: location,
+            file_filter: Some(file.to_string_lossy().to_string()),
+            column_unit: column_unit.as_str().to_string(),
+        };
+        let json_response = JsonResponse::new(response, &exec_id);
+        return output_json(&json_response, output_format);
+    }
+
+    if ranked.is_empty() {
+        println!("No symbols found near {}.", location);
+        return Ok(());
+    }
+
+    println!("Nearest {} symbols to {}:", ranked.len(), location);
+    for (distance, _, fact, _) in &ranked {
+        println!(
+            "  {} ({}) at line {} (distance {})",
+            fact.name.as_deref().unwrap_or("(unnamed)"),
+            format_symbol_kind(&fact.kind),
+            fact.start_line,
+            distance
+        );
+    }
+
+    Ok(())
+}
+
+/// Run `find --defined-in`
+///
+/// Lists every symbol whose FQN lies under `prefix` (see
+/// [`query::symbols_defined_in`]), optionally narrowed by `--kind` and
+/// `--path`. Results are ordered by FQN, then file and line.
+#[allow(
+    clippy::too_many_arguments,
+    reason = "CLI command surface: each arg maps to a flag"
+)]
+pub fn run_find_defined_in(
+    db_path: PathBuf,
+    prefix: String,
+    kind: Option<String>,
+    root: Option<PathBuf>,
+    path: Option<PathBuf>,
+    test_filter: TestFilter,
+    visibility: Option<VisibilityFilter>,
+    output_format: OutputFormat,
+    column_unit: ColumnUnit,
+) -> Result<()> {
+    let kind = kind.as_deref().map(parse_symbol_kind_arg).transpose()?;
+    let mut graph = open_graph_for_read(&db_path)?;
+    let exec_id = magellan::output::generate_execution_id();
+
+    let file_filter = path.as_ref().map(|p| resolve_path(p, &root));
+    let mut symbols = query::symbols_defined_in(
+        &mut graph,
+        &prefix,
+        kind.as_ref(),
+        file_filter.as_deref(),
+        test_filter,
+    )?;
+    if let Some(visibility) = visibility {
+        symbols.retain(|(_, fact, _)| visibility.matches(fact.visibility.as_deref()));
+    }
+    let mut columns = ColumnConverter::for_graph(column_unit, &graph);
+    for (_, fact, _) in &mut symbols {
+        columns.convert_symbol(fact);
+    }
+
+    if output_format == OutputFormat::Json || output_format == OutputFormat::Pretty {
+        let matches: Vec<SymbolMatch> = symbols
+            .into_iter()
+            .map(|(_, fact, symbol_id)| {
+                let span = Span::new(
+                    fact.file_path.to_string_lossy().to_string(),
+                    fact.byte_start,
+                    fact.byte_end,
+                    fact.start_line,
+                    fact.start_col,
+                    fact.end_line,
+                    fact.end_col,
+                );
+                let name = fact.name.unwrap_or_else(|| "(unnamed)".to_string());
+                SymbolMatch::new(name, fact.kind_normalized, span, None, symbol_id)
+            })
+            .collect();
+
+        let response = FindResponse {
+            matches,
+            query_name: prefix,
+            file_filter: path.map(|p| p.to_string_lossy().to_string()),
+            column_unit: column_unit.as_str().to_string(),
+        };
+        let json_response = JsonResponse::new(response, &exec_id);
+        return output_json(&json_response, output_format);
+    }
+
+    if symbols.is_empty() {
+        println!("No symbols defined in '{}'.", prefix);
+        return Ok(());
+    }
+
+    println!("Found {} symbols defined in '{}':", symbols.len(), prefix);
+    for (_, fact, _) in &symbols {
+        let name = fact.name.as_deref().unwrap_or("(unnamed)");
+        println!(
+            "  {} ({}) in {}:{}",
+            fact.display_fqn
+                .as_deref()
+                .or(fact.fqn.as_deref())
+                .unwrap_or(name),
+            format_symbol_kind(&fact.kind),
+            fact.file_path.display(),
+            fact.start_line
+        );
+    }
+
+    Ok(())
+}
+
+/// JSON payload of `find --deprecated`
+#[derive(Debug, Serialize)]
+struct DeprecatedResponse {
+    /// Exact name filter (`--name`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    name_filter: Option<String>,
+    /// File filter (`--path`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    file_filter: Option<String>,
+    count: usize,
+    symbols: Vec<DeprecatedSymbol>,
+}
+
+/// One deprecated symbol with its deprecation note
+#[derive(Debug, Serialize)]
+struct DeprecatedSymbol {
+    #[serde(flatten)]
+    symbol: SymbolMatch,
+    /// Message attached to the deprecation, if any
+    message: Option<String>,
+}
+
+/// Run `find --deprecated`
+///
+/// Lists every symbol flagged deprecated at index time (see
+/// `ingest::deprecation`), optionally narrowed by `--name` and `--path`.
+/// Results are ordered by file, then line.
+#[allow(
+    clippy::too_many_arguments,
+    reason = "CLI command surface: each arg maps to a flag"
+)]
+pub fn run_find_deprecated(
+    db_path: PathBuf,
+    name: Option<String>,
+    root: Option<PathBuf>,
+    path: Option<PathBuf>,
+    test_filter: TestFilter,
+    visibility: Option<VisibilityFilter>,
+    output_format: OutputFormat,
+    column_unit: ColumnUnit,
+) -> Result<()> {
+    let mut graph = open_graph_for_read(&db_path)?;
+    let exec_id = magellan::output::generate_execution_id();
+
+    let file_filter = path.as_ref().map(|p| resolve_path(p, &root));
+    let mut symbols = query::deprecated_symbols(
+        &mut graph,
+        name.as_deref(),
+        file_filter.as_deref(),
+        test_filter,
+    )?;
+    if let Some(visibility) = visibility {
+        symbols.retain(|(_, fact, _)| visibility.matches(fact.visibility.as_deref()));
+    }
+    let mut columns = ColumnConverter::for_graph(column_unit, &graph);
+    for (_, fact, _) in &mut symbols {
+        columns.convert_symbol(fact);
+    }
+
+    if output_format == OutputFormat::Json || output_format == OutputFormat::Pretty {
+        let symbols: Vec<DeprecatedSymbol> = symbols
+            .into_iter()
+            .map(|(_, fact, symbol_id)| {
+                let span = Span::new(
+                    fact.file_path.to_string_lossy().to_string(),
+                    fact.byte_start,
+                    fact.byte_end,
+                    fact.start_line,
+                    fact.start_col,
+                    fact.end_line,
+                    fact.end_col,
+                );
+                let name = fact.name.unwrap_or_else(|| "(unnamed)".to_string());
+                DeprecatedSymbol {
+                    symbol: SymbolMatch::new(name, fact.kind_normalized, span, None, symbol_id),
+                    message: fact.deprecation_message,
+                }
+            })
+            .collect();
+
+        let response = DeprecatedResponse {
+            name_filter: name,
+            file_filter: path.map(|p| p.to_string_lossy().to_string()),
+            count: symbols.len(),
+            symbols,
+        };
+        let json_response = JsonResponse::new(response, &exec_id);
+        return output_json(&json_response, output_format);
+    }
+
+    if symbols.is_empty() {
+        println!("No deprecated symbols found.");
+        return Ok(());
+    }
+
+    println!("Found {} deprecated symbols:", symbols.len());
+    for (_, fact, _) in &symbols {
+        let name = fact.name.as_deref().unwrap_or("(unnamed)");
+        print!(
+            "  {} ({}) in {}:{}",
+            fact.display_fqn
+                .as_deref()
+                .or(fact.fqn.as_deref())
+                .unwrap_or(name),
+            format_symbol_kind(&fact.kind),
+            fact.file_path.display(),
+            fact.start_line
+        );
+        match &fact.deprecation_message {
+            Some(message) => println!(" - {}", message),
+            None => println!(),
+        }
+    }
+
+    Ok(())
+}
+
 /// Run the find command
 ///
 /// # Arguments
@@ -213,8 +729,12 @@ pub fn run_find(
     with_callees: bool,
     with_semantics: bool,
     with_checksums: bool,
+    with_metrics: bool,
     context_lines: usize,
     all: bool,
+    test_filter: TestFilter,
+    visibility: Option<VisibilityFilter>,
+    column_unit: ColumnUnit,
 ) -> Result<()> {
     if all {
         return run_find_all(
@@ -258,8 +778,22 @@ pub fn run_find(
     if first {
         args.push("--first".to_string());
     }
+    if let Some(flag) = test_filter.as_flag() {
+        args.push(flag.to_string());
+    }
+    if let Some(visibility) = visibility {
+        args.push("--visibility".to_string());
+        args.push(visibility.as_str().to_string());
+    }
+    if column_unit != ColumnUnit::Byte {
+        args.push("--column-unit".to_string());
+        args.push(column_unit.as_str().to_string());
+    }
+    if with_metrics {
+        args.push("--with-metrics".to_string());
+    }
 
-    let mut graph = CodeGraph::open(&db_path)?;
+    let mut graph = open_graph_for_read(&db_path)?;
     let exec_id = magellan::output::generate_execution_id();
     let root_str = root.as_ref().map(|p| p.to_string_lossy().to_string());
     let db_path_str = db_path.to_string_lossy().to_string();
@@ -278,7 +812,7 @@ pub fn run_find(
         .record_phase_start(&exec_id, "resolve_target")?;
 
     if let Some(pattern) = glob_pattern {
-        let result = run_glob_listing(&mut graph, &pattern, output_format, &exec_id);
+        let result = run_glob_listing(&mut graph, &pattern, output_format, &exec_id, column_unit);
         let _ = graph.execution_log().finish_execution(
             &exec_id,
             if result.is_ok() { "success" } else { "error" },
@@ -337,20 +871,21 @@ pub fn run_find(
 
     // Handle --ambiguous symbol name query (show all candidates)
     if let Some(amb_name) = ambiguous_name {
-        let result = match query::get_ambiguous_candidates(&mut graph, &amb_name) {
+        let result = match query::ambiguous_candidate_locations(&mut graph, &amb_name) {
             Ok(candidates) => {
                 if candidates.is_empty() {
                     eprintln!("No symbols found with name '{}'", amb_name);
                     Ok(())
                 } else {
-                    for (entity_id, symbol) in candidates.iter().enumerate() {
-                        let sid = symbol.1.symbol_id.as_deref().unwrap_or("<none>");
-                        let canon = symbol.1.canonical_fqn.as_deref().unwrap_or("<none>");
-                        eprintln!("  [{}]", entity_id + 1);
+                    for (index, (_, file_path, symbol)) in candidates.iter().enumerate() {
+                        let sid = symbol.symbol_id.as_deref().unwrap_or("<none>");
+                        let canon = symbol.canonical_fqn.as_deref().unwrap_or("<none>");
+                        eprintln!("  [{}]", index + 1);
                         eprintln!("    Symbol ID: {}", sid);
                         eprintln!("    Canonical: {}", canon);
-                        eprintln!("    Name: {}", symbol.1.name.as_deref().unwrap_or("<none>"));
-                        eprintln!("    Kind: {}", symbol.1.kind);
+                        eprintln!("    Name: {}", symbol.name.as_deref().unwrap_or("<none>"));
+                        eprintln!("    Kind: {}", symbol.kind);
+                        eprintln!("    File: {}:{}", file_path, symbol.start_line);
                     }
                     Ok(())
                 }
@@ -384,16 +919,17 @@ pub fn run_find(
         .record_phase_end(&exec_id, "resolve_target")?;
     graph.telemetry().record_phase_start(&exec_id, "search")?;
 
-    let results = match path.as_ref() {
+    let mut results = match path.as_ref() {
         Some(file_path) => {
             let path_str = resolve_path(file_path, &root);
-            match find_in_file(&mut graph, &path_str, &name)? {
+            match find_in_file(&mut graph, &path_str, &name, test_filter, visibility)? {
                 Some(symbol) => vec![symbol],
                 None => vec![],
             }
         }
-        None => find_all_files(&mut graph, &name)?,
+        None => find_all_files(&mut graph, &name, test_filter, visibility)?,
     };
+    convert_found_columns(&graph, &mut results, column_unit);
 
     if output_format == OutputFormat::Json || output_format == OutputFormat::Pretty {
         // End search phase, start build_response phase
@@ -414,7 +950,9 @@ pub fn run_find(
             with_callees,
             with_semantics,
             with_checksums,
+            with_metrics,
             context_lines,
+            column_unit,
         );
         let _ = graph.execution_log().finish_execution(
             &exec_id,
@@ -451,9 +989,10 @@ pub fn run_find(
         );
         println!("  Location: Line {}, Column {}", symbol.line, symbol.col);
         println!("  Node ID:  {}", symbol.node_id);
+        print_metrics(&graph, symbol.node_id, with_metrics);
     } else {
         if first {
-            eprintln!("WARNING: --first is deprecated. Use --symbol-id for precise lookups.");
+            log_warn!("--first is deprecated. Use --symbol-id for precise lookups.");
             let symbol = &results[0];
             println!("Found \"{}\" (using first match):", name);
             println!("  File:     {}", symbol.file);
@@ -464,6 +1003,7 @@ pub fn run_find(
             );
             println!("  Location: Line {}, Column {}", symbol.line, symbol.col);
             println!("  Node ID:  {}", symbol.node_id);
+            print_metrics(&graph, symbol.node_id, with_metrics);
         } else {
             eprintln!(
                 "Ambiguous symbol name '{}': found {} candidates",
@@ -516,6 +1056,19 @@ pub fn run_find(
     Ok(())
 }
 
+/// Print the `--with-metrics` line of a single match, if metrics are stored
+fn print_metrics(graph: &CodeGraph, node_id: i64, with_metrics: bool) {
+    if !with_metrics {
+        return;
+    }
+    if let Some(m) = crate::query_cmd::stored_metrics(graph, node_id) {
+        println!(
+            "  Metrics:  loc {}, fan-in {}, fan-out {}, complexity {}",
+            m.loc, m.fan_in, m.fan_out, m.cyclomatic_complexity
+        );
+    }
+}
+
 /// Output find results in JSON format
 #[allow(
     clippy::too_many_arguments,
@@ -533,8 +1086,11 @@ fn output_json_mode(
     with_callees: bool,
     with_semantics: bool,
     with_checksums: bool,
+    with_metrics: bool,
     context_lines: usize,
+    column_unit: ColumnUnit,
 ) -> Result<()> {
+    let mut columns = ColumnConverter::for_graph(column_unit, graph);
     // Sort deterministically: by file_path, start_line, start_col
     results.sort_by(|a, b| {
         a.file
@@ -573,7 +1129,7 @@ fn output_json_mode(
 
         // Add checksums if requested
         if with_checksums {
-            let checksums = SpanChecksums::compute(&s.file, s.byte_start, s.byte_end);
+            let checksums = graph.span_checksums(&s.file, s.byte_start, s.byte_end);
             span = span.with_checksums(checksums);
         }
 
@@ -583,11 +1139,14 @@ fn output_json_mode(
                 Ok(call_facts) => Some(
                     call_facts
                         .into_iter()
-                        .map(|fact| CallerInfo {
-                            name: fact.caller,
-                            file_path: fact.file_path.to_string_lossy().to_string(),
-                            line: fact.start_line,
-                            column: fact.start_col,
+                        .map(|mut fact| {
+                            columns.convert_call(&mut fact);
+                            CallerInfo {
+                                name: fact.caller,
+                                file_path: fact.file_path.to_string_lossy().to_string(),
+                                line: fact.start_line,
+                                column: fact.start_col,
+                            }
                         })
                         .collect(),
                 ),
@@ -615,9 +1174,16 @@ fn output_json_mode(
             None
         };
 
+        let metrics = if with_metrics {
+            crate::query_cmd::stored_metrics(graph, s.node_id)
+        } else {
+            None
+        };
+
         let mut symbol_match = SymbolMatch::new(s.name, s.kind_normalized, span, None, s.symbol_id);
         symbol_match.callers = callers;
         symbol_match.callees = callees;
+        symbol_match.metrics = metrics;
 
         matches.push(symbol_match);
     }
@@ -627,6 +1193,7 @@ fn output_json_mode(
         matches,
         query_name: query_name.to_string(),
         file_filter,
+        column_unit: column_unit.as_str().to_string(),
     };
 
     let json_response = JsonResponse::new(response, exec_id);
@@ -640,6 +1207,7 @@ fn run_glob_listing(
     pattern: &str,
     output_format: OutputFormat,
     exec_id: &str,
+    column_unit: ColumnUnit,
 ) -> Result<()> {
     let glob_matcher = GlobBuilder::new(pattern)
         .case_insensitive(false)
@@ -678,6 +1246,7 @@ fn run_glob_listing(
         }
     }
 
+    convert_found_columns(graph, &mut matches, column_unit);
     matches.sort_by(|a, b| {
         a.file
             .cmp(&b.file)
@@ -707,6 +1276,7 @@ fn run_glob_listing(
             matches: json_matches,
             query_name: pattern.to_string(),
             file_filter: None,
+            column_unit: column_unit.as_str().to_string(),
         };
 
         let json_response = JsonResponse::new(response, exec_id);
@@ -8,6 +8,7 @@ use serde::Deserialize;
 
 use crate::graph::query::SymbolQueryResult;
 use crate::graph::CodeGraph;
+use crate::log_warn;
 
 // ---------------------------------------------------------------------------
 // Registry reading (self-contained, no binary deps)
@@ -186,7 +187,7 @@ impl MagellanFramework {
                     graphs.insert(name, g);
                 }
                 Err(e) => {
-                    eprintln!("Warning: skipping {}: {}", db.display(), e);
+                    log_warn!("skipping {}: {}", db.display(), e);
                 }
             }
         }
@@ -216,7 +217,7 @@ impl MagellanFramework {
                     }
                 }
                 Err(e) => {
-                    eprintln!("Warning: find failed for {}: {}", proj_name, e);
+                    log_warn!("find failed for {}: {}", proj_name, e);
                 }
             }
         }
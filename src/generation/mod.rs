@@ -19,13 +19,156 @@
 
 pub mod schema;
 
+use crate::graph::pragmas::{
+    retry_on_busy, SqliteOptions, DEFAULT_BUSY_RETRIES, DEFAULT_BUSY_TIMEOUT_MS,
+};
 use anyhow::Result;
+use rusqlite::types::{Type, Value, ValueRef};
 use rusqlite::{params, OptionalExtension};
 use std::path::Path;
 use std::sync::Arc;
 
 pub use schema::CodeChunk;
 
+/// Chunk columns with `content` resolved through the `chunk_content` table.
+///
+/// Deduplicated rows leave `code_chunks.content` empty and keep the text once
+/// per `content_hash` in `chunk_content`; rows written before deduplication
+/// (or whose hash was already taken by different text) still carry their
+/// content inline. Append a `WHERE` clause referring to `code_chunks` columns,
+/// and read the content column with [`chunk_content_from_row`].
+pub const CODE_CHUNK_SELECT: &str = "SELECT c.id, c.file_path, c.byte_start, c.byte_end,
+            COALESCE(NULLIF(c.content, ''), cc.content, ''), c.content_hash,
+            c.symbol_name, c.symbol_kind, c.created_at
+     FROM code_chunks c LEFT JOIN chunk_content cc ON cc.hash = c.content_hash";
+
+/// zstd level used by `--compress-chunks`
+const CHUNK_COMPRESSION_LEVEL: i32 = 3;
+
+/// Encode chunk text for storage.
+///
+/// The SQLite storage class doubles as the compression flag: TEXT values are
+/// plain, BLOB values are zstd frames. Text is only stored compressed when
+/// `compress` is set and the frame is actually smaller.
+fn encode_chunk_content(content: &str, compress: bool) -> Value {
+    if compress && !content.is_empty() {
+        if let Ok(frame) = zstd::encode_all(content.as_bytes(), CHUNK_COMPRESSION_LEVEL) {
+            if frame.len() < content.len() {
+                return Value::Blob(frame);
+            }
+        }
+    }
+    Value::Text(content.to_string())
+}
+
+/// Decode a stored chunk content value (plain TEXT or zstd BLOB).
+fn decode_chunk_content(value: ValueRef<'_>, idx: usize) -> rusqlite::Result<String> {
+    match value {
+        ValueRef::Blob(frame) => {
+            let bytes = zstd::decode_all(frame).map_err(|e| {
+                rusqlite::Error::FromSqlConversionFailure(idx, Type::Blob, Box::new(e))
+            })?;
+            String::from_utf8(bytes).map_err(|e| {
+                rusqlite::Error::FromSqlConversionFailure(idx, Type::Blob, Box::new(e))
+            })
+        }
+        ValueRef::Null => Ok(String::new()),
+        other => other.as_str().map(str::to_string).map_err(|e| {
+            rusqlite::Error::FromSqlConversionFailure(idx, other.data_type(), Box::new(e))
+        }),
+    }
+}
+
+/// Read the chunk content column at `idx`, decompressing it if needed.
+pub fn chunk_content_from_row(row: &rusqlite::Row<'_>, idx: usize) -> rusqlite::Result<String> {
+    decode_chunk_content(row.get_ref(idx)?, idx)
+}
+
+/// Create the `chunk_content` table and the trigger that releases content
+/// no longer referenced by any `code_chunks` row.
+pub(crate) fn ensure_chunk_content_schema(conn: &rusqlite::Connection) -> Result<()> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS chunk_content (
+            hash TEXT PRIMARY KEY,
+            content TEXT NOT NULL
+        )",
+        [],
+    )
+    .map_err(|e| anyhow::anyhow!("Failed to create chunk_content table: {}", e))?;
+
+    conn.execute(
+        "CREATE TRIGGER IF NOT EXISTS code_chunks_release_content
+         AFTER DELETE ON code_chunks
+         BEGIN
+             DELETE FROM chunk_content
+             WHERE hash = OLD.content_hash
+               AND NOT EXISTS (SELECT 1 FROM code_chunks WHERE content_hash = OLD.content_hash);
+         END",
+        [],
+    )
+    .map_err(|e| anyhow::anyhow!("Failed to create chunk_content trigger: {}", e))?;
+
+    Ok(())
+}
+
+/// Insert or replace one chunk, storing its content once per hash.
+///
+/// Any existing chunk at the same span is deleted first so the release
+/// trigger sees it (`INSERT OR REPLACE` does not fire delete triggers).
+/// If the hash is already stored with different text, the content is kept
+/// inline instead of being shared. With `compress`, newly written content is
+/// stored zstd-compressed; existing rows are left as they are.
+pub(crate) fn insert_chunk(
+    conn: &rusqlite::Connection,
+    chunk: &CodeChunk,
+    compress: bool,
+) -> rusqlite::Result<i64> {
+    conn.prepare_cached(
+        "DELETE FROM code_chunks WHERE file_path = ?1 AND byte_start = ?2 AND byte_end = ?3",
+    )?
+    .execute(params![
+        chunk.file_path,
+        chunk.byte_start as i64,
+        chunk.byte_end as i64
+    ])?;
+
+    let stored_content = encode_chunk_content(&chunk.content, compress);
+    let inserted = conn
+        .prepare_cached("INSERT OR IGNORE INTO chunk_content (hash, content) VALUES (?1, ?2)")?
+        .execute(params![chunk.content_hash, stored_content])?;
+    // The stored copy may be compressed, so compare decoded text
+    let shared = inserted > 0
+        || conn
+            .prepare_cached("SELECT content FROM chunk_content WHERE hash = ?1")?
+            .query_row(params![chunk.content_hash], |row| {
+                chunk_content_from_row(row, 0)
+            })?
+            == chunk.content;
+    let inline_content = if shared {
+        Value::Text(String::new())
+    } else {
+        stored_content
+    };
+
+    conn.prepare_cached(
+        "INSERT INTO code_chunks
+            (file_path, byte_start, byte_end, content, content_hash, symbol_name, symbol_kind, created_at)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+    )?
+    .execute(params![
+        chunk.file_path,
+        chunk.byte_start as i64,
+        chunk.byte_end as i64,
+        inline_content,
+        chunk.content_hash,
+        chunk.symbol_name,
+        chunk.symbol_kind,
+        chunk.created_at,
+    ])?;
+
+    Ok(conn.last_insert_rowid())
+}
+
 /// Storage backend for ChunkStore.
 ///
 /// Supports three modes:
@@ -50,12 +193,21 @@ enum ChunkStoreBackend {
 pub struct ChunkStore {
     /// Backend - either SQLite connection or SideTables trait
     backend: ChunkStoreBackend,
+    /// Busy timeout applied to connections opened by the Owned backend
+    busy_timeout_ms: u32,
+    /// Retries for writes that fail with SQLITE_BUSY/SQLITE_LOCKED
+    busy_retries: u32,
+    /// zstd-compress content of newly stored chunks
+    compress: bool,
 }
 
 impl Clone for ChunkStore {
     fn clone(&self) -> Self {
         Self {
             backend: self.backend.clone(),
+            busy_timeout_ms: self.busy_timeout_ms,
+            busy_retries: self.busy_retries,
+            compress: self.compress,
         }
     }
 }
@@ -71,13 +223,20 @@ impl Clone for ChunkStoreBackend {
 }
 
 impl ChunkStore {
+    fn from_backend(backend: ChunkStoreBackend) -> Self {
+        Self {
+            backend,
+            busy_timeout_ms: DEFAULT_BUSY_TIMEOUT_MS,
+            busy_retries: DEFAULT_BUSY_RETRIES,
+            compress: false,
+        }
+    }
+
     /// Create a new ChunkStore with the given database path.
     ///
     /// This is the legacy constructor that opens its own connections.
     pub fn new(db_path: &Path) -> Self {
-        Self {
-            backend: ChunkStoreBackend::Owned(db_path.to_path_buf()),
-        }
+        Self::from_backend(ChunkStoreBackend::Owned(db_path.to_path_buf()))
     }
 
     /// Create a ChunkStore with a shared connection.
@@ -88,9 +247,9 @@ impl ChunkStore {
     /// # Arguments
     /// * `conn` - Shared SQLite connection wrapped in Arc<parking_lot::Mutex<>> for thread-safe interior mutability
     pub fn with_connection(conn: rusqlite::Connection) -> Self {
-        Self {
-            backend: ChunkStoreBackend::Shared(Arc::new(parking_lot::Mutex::new(conn))),
-        }
+        Self::from_backend(ChunkStoreBackend::Shared(Arc::new(
+            parking_lot::Mutex::new(conn),
+        )))
     }
 
     /// Create a ChunkStore using the SideTables abstraction.
@@ -101,9 +260,25 @@ impl ChunkStore {
     /// # Arguments
     /// * `side_tables` - `Arc<dyn SideTables>` implementation
     pub fn with_side_tables(side_tables: Arc<dyn crate::graph::side_tables::SideTables>) -> Self {
-        Self {
-            backend: ChunkStoreBackend::SideTables(side_tables),
-        }
+        Self::from_backend(ChunkStoreBackend::SideTables(side_tables))
+    }
+
+    /// Use the busy timeout and retry count from `options`.
+    ///
+    /// The timeout applies to connections the store opens itself; a shared
+    /// connection keeps the settings it was opened with.
+    pub fn with_busy_handling(mut self, options: &SqliteOptions) -> Self {
+        self.busy_timeout_ms = options.busy_timeout_ms;
+        self.busy_retries = options.busy_retries;
+        self
+    }
+
+    /// Store the content of new chunks zstd-compressed.
+    ///
+    /// Chunks already in the database stay readable whichever way they were
+    /// written. Applies to every backend, including SideTables.
+    pub fn set_compress(&mut self, enabled: bool) {
+        self.compress = enabled;
     }
 
     /// Create a stub ChunkStore using a temporary file (for testing).
@@ -198,6 +373,8 @@ impl ChunkStore {
         )
         .expect("Failed to create content_hash index");
 
+        ensure_chunk_content_schema(&conn).expect("Failed to create chunk_content table");
+
         conn.execute(
             "CREATE INDEX IF NOT EXISTS idx_ast_nodes_parent ON ast_nodes(parent_id)",
             [],
@@ -252,9 +429,7 @@ impl ChunkStore {
         )
         .expect("Failed to create cfg_edges function index");
 
-        Self {
-            backend: ChunkStoreBackend::Owned(db_path),
-        }
+        Self::from_backend(ChunkStoreBackend::Owned(db_path))
     }
 
     /// Get a connection to the database.
@@ -284,8 +459,17 @@ impl ChunkStore {
                             .to_string(),
                     )
                 })?;
-                // Open a new connection to the same database
-                rusqlite::Connection::open(path)
+                // Open a new connection to the same database, read-only
+                // if the shared one is (`CodeGraph::open_readonly`)
+                if conn.is_readonly(rusqlite::DatabaseName::Main)? {
+                    rusqlite::Connection::open_with_flags(
+                        path,
+                        rusqlite::OpenFlags::SQLITE_OPEN_READ_ONLY
+                            | rusqlite::OpenFlags::SQLITE_OPEN_NO_MUTEX,
+                    )
+                } else {
+                    rusqlite::Connection::open(path)
+                }
             }
             ChunkStoreBackend::SideTables(_) => Err(rusqlite::Error::InvalidParameterName(
                 "SQLite connection not available with V3 backend".to_string(),
@@ -321,13 +505,18 @@ impl ChunkStore {
     /// Execute a mutable operation with a connection.
     ///
     /// This helper method is for operations that need mutable access to the connection.
-    pub(crate) fn with_connection_mut<F, R>(&self, f: F) -> Result<R>
+    /// An operation failing with `SQLITE_BUSY`/`SQLITE_LOCKED` is run again
+    /// (see [`retry_on_busy`]), so `f` must be safe to repeat.
+    pub(crate) fn with_connection_mut<F, R>(&self, mut f: F) -> Result<R>
     where
-        F: FnOnce(&mut rusqlite::Connection) -> Result<R>,
+        F: FnMut(&mut rusqlite::Connection) -> Result<R>,
     {
-        match &self.backend {
+        retry_on_busy(self.busy_retries, || match &self.backend {
             ChunkStoreBackend::Owned(path) => {
                 let mut conn = rusqlite::Connection::open(path)?;
+                conn.busy_timeout(std::time::Duration::from_millis(u64::from(
+                    self.busy_timeout_ms,
+                )))?;
                 let result = f(&mut conn)?;
                 Ok(result)
             }
@@ -339,10 +528,10 @@ impl ChunkStore {
             ChunkStoreBackend::SideTables(_) => Err(anyhow::anyhow!(
                 "SQLite operations not available with V3 backend. Use SideTables trait methods."
             )),
-        }
+        })
     }
 
-    /// Ensure the code_chunks table exists.
+    /// Ensure the code_chunks and chunk_content tables exist.
     pub fn ensure_schema(&self) -> Result<()> {
         self.with_connection_mut(|conn| {
             conn.execute(
@@ -381,33 +570,18 @@ impl ChunkStore {
             )
             .map_err(|e| anyhow::anyhow!("Failed to create content_hash index: {}", e))?;
 
-            Ok(())
+            ensure_chunk_content_schema(conn)
         })
     }
 
     /// Store a code chunk in the database.
     ///
-    /// Uses INSERT OR REPLACE to handle duplicates based on (file_path, byte_start, byte_end).
+    /// Replaces any chunk with the same (file_path, byte_start, byte_end).
+    /// Content is deduplicated by `content_hash` into the chunk_content table.
     pub fn store_chunk(&self, chunk: &CodeChunk) -> Result<i64> {
         self.with_connection_mut(|conn| {
-            conn.execute(
-                "INSERT OR REPLACE INTO code_chunks
-                    (file_path, byte_start, byte_end, content, content_hash, symbol_name, symbol_kind, created_at)
-                    VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
-                params![
-                    chunk.file_path,
-                    chunk.byte_start as i64,
-                    chunk.byte_end as i64,
-                    chunk.content,
-                    chunk.content_hash,
-                    chunk.symbol_name,
-                    chunk.symbol_kind,
-                    chunk.created_at,
-                ],
-            )
-            .map_err(|e| anyhow::anyhow!("Failed to store code chunk: {}", e))?;
-
-            Ok(conn.last_insert_rowid())
+            insert_chunk(conn, chunk, self.compress)
+                .map_err(|e| anyhow::anyhow!("Failed to store code chunk: {}", e))
         })
     }
 
@@ -418,7 +592,7 @@ impl ChunkStore {
                 // V3 backend: use SideTables trait method
                 let mut ids = Vec::new();
                 for chunk in chunks {
-                    let id = tables.store_chunk(chunk)?;
+                    let id = tables.store_chunk(chunk, self.compress)?;
                     ids.push(id);
                 }
                 Ok(ids)
@@ -433,24 +607,9 @@ impl ChunkStore {
                     let mut ids = Vec::new();
 
                     for chunk in chunks {
-                        tx.execute(
-                            "INSERT OR REPLACE INTO code_chunks
-                                (file_path, byte_start, byte_end, content, content_hash, symbol_name, symbol_kind, created_at)
-                                VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
-                            params![
-                                chunk.file_path,
-                                chunk.byte_start as i64,
-                                chunk.byte_end as i64,
-                                chunk.content,
-                                chunk.content_hash,
-                                chunk.symbol_name,
-                                chunk.symbol_kind,
-                                chunk.created_at,
-                            ],
-                        )
-                        .map_err(|e| anyhow::anyhow!("Failed to store code chunk: {}", e))?;
-
-                        ids.push(tx.last_insert_rowid());
+                        let id = insert_chunk(&tx, chunk, self.compress)
+                            .map_err(|e| anyhow::anyhow!("Failed to store code chunk: {}", e))?;
+                        ids.push(id);
                     }
 
                     tx.commit()
@@ -475,12 +634,10 @@ impl ChunkStore {
             }
             _ => self.with_conn(|conn| {
                 let mut stmt = conn
-                    .prepare_cached(
-                        "SELECT id, file_path, byte_start, byte_end, content, content_hash,
-                                    symbol_name, symbol_kind, created_at
-                             FROM code_chunks
-                             WHERE file_path = ?1 AND byte_start = ?2 AND byte_end = ?3",
-                    )
+                    .prepare_cached(&format!(
+                        "{} WHERE c.file_path = ?1 AND c.byte_start = ?2 AND c.byte_end = ?3",
+                        CODE_CHUNK_SELECT
+                    ))
                     .map_err(|e| anyhow::anyhow!("Failed to prepare query: {}", e))?;
 
                 let result = stmt
@@ -492,7 +649,7 @@ impl ChunkStore {
                                 file_path: row.get(1)?,
                                 byte_start: row.get::<_, i64>(2)? as usize,
                                 byte_end: row.get::<_, i64>(3)? as usize,
-                                content: row.get(4)?,
+                                content: chunk_content_from_row(row, 4)?,
                                 content_hash: row.get(5)?,
                                 symbol_name: row.get(6)?,
                                 symbol_kind: row.get(7)?,
@@ -514,13 +671,10 @@ impl ChunkStore {
             ChunkStoreBackend::SideTables(tables) => tables.get_chunks_for_file(file_path),
             _ => self.with_conn(|conn| {
                 let mut stmt = conn
-                    .prepare_cached(
-                        "SELECT id, file_path, byte_start, byte_end, content, content_hash,
-                                    symbol_name, symbol_kind, created_at
-                             FROM code_chunks
-                             WHERE file_path = ?1
-                             ORDER BY byte_start",
-                    )
+                    .prepare_cached(&format!(
+                        "{} WHERE c.file_path = ?1 ORDER BY c.byte_start",
+                        CODE_CHUNK_SELECT
+                    ))
                     .map_err(|e| anyhow::anyhow!("Failed to prepare query: {}", e))?;
 
                 let chunks = stmt
@@ -530,7 +684,7 @@ impl ChunkStore {
                             file_path: row.get(1)?,
                             byte_start: row.get::<_, i64>(2)? as usize,
                             byte_end: row.get::<_, i64>(3)? as usize,
-                            content: row.get(4)?,
+                            content: chunk_content_from_row(row, 4)?,
                             content_hash: row.get(5)?,
                             symbol_name: row.get(6)?,
                             symbol_kind: row.get(7)?,
@@ -558,13 +712,10 @@ impl ChunkStore {
             }
             _ => self.with_conn(|conn| {
                 let mut stmt = conn
-                    .prepare_cached(
-                        "SELECT id, file_path, byte_start, byte_end, content, content_hash,
-                                    symbol_name, symbol_kind, created_at
-                             FROM code_chunks
-                             WHERE file_path = ?1 AND symbol_name = ?2
-                             ORDER BY byte_start",
-                    )
+                    .prepare_cached(&format!(
+                        "{} WHERE c.file_path = ?1 AND c.symbol_name = ?2 ORDER BY c.byte_start",
+                        CODE_CHUNK_SELECT
+                    ))
                     .map_err(|e| anyhow::anyhow!("Failed to prepare query: {}", e))?;
 
                 let chunks = stmt
@@ -574,7 +725,7 @@ impl ChunkStore {
                             file_path: row.get(1)?,
                             byte_start: row.get::<_, i64>(2)? as usize,
                             byte_end: row.get::<_, i64>(3)? as usize,
-                            content: row.get(4)?,
+                            content: chunk_content_from_row(row, 4)?,
                             content_hash: row.get(5)?,
                             symbol_name: row.get(6)?,
                             symbol_kind: row.get(7)?,
@@ -652,12 +803,10 @@ impl ChunkStore {
             ChunkStoreBackend::SideTables(tables) => tables.get_all_chunks(),
             _ => self.with_conn(|conn| {
                 let mut stmt = conn
-                    .prepare_cached(
-                        "SELECT id, file_path, byte_start, byte_end, content, content_hash,
-                                symbol_name, symbol_kind, created_at
-                         FROM code_chunks
-                         ORDER BY file_path, byte_start",
-                    )
+                    .prepare_cached(&format!(
+                        "{} ORDER BY c.file_path, c.byte_start",
+                        CODE_CHUNK_SELECT
+                    ))
                     .map_err(|e| anyhow::anyhow!("Failed to prepare query: {}", e))?;
 
                 let chunks = stmt
@@ -667,7 +816,7 @@ impl ChunkStore {
                             file_path: row.get(1)?,
                             byte_start: row.get::<_, i64>(2)? as usize,
                             byte_end: row.get::<_, i64>(3)? as usize,
-                            content: row.get(4)?,
+                            content: chunk_content_from_row(row, 4)?,
                             content_hash: row.get(5)?,
                             symbol_name: row.get(6)?,
                             symbol_kind: row.get(7)?,
@@ -703,13 +852,10 @@ impl ChunkStore {
             }
             _ => self.with_conn(|conn| {
                 let mut stmt = conn
-                    .prepare_cached(
-                        "SELECT id, file_path, byte_start, byte_end, content, content_hash,
-                                    symbol_name, symbol_kind, created_at
-                             FROM code_chunks
-                             WHERE symbol_kind = ?1
-                             ORDER BY file_path, byte_start",
-                    )
+                    .prepare_cached(&format!(
+                        "{} WHERE c.symbol_kind = ?1 ORDER BY c.file_path, c.byte_start",
+                        CODE_CHUNK_SELECT
+                    ))
                     .map_err(|e| anyhow::anyhow!("Failed to prepare query: {}", e))?;
 
                 let chunks = stmt
@@ -719,7 +865,7 @@ impl ChunkStore {
                             file_path: row.get(1)?,
                             byte_start: row.get::<_, i64>(2)? as usize,
                             byte_end: row.get::<_, i64>(3)? as usize,
-                            content: row.get(4)?,
+                            content: chunk_content_from_row(row, 4)?,
                             content_hash: row.get(5)?,
                             symbol_name: row.get(6)?,
                             symbol_kind: row.get(7)?,
@@ -763,4 +909,108 @@ mod tests {
         assert!(!chunk.content_hash.is_empty());
         assert!(chunk.id.is_none());
     }
+
+    #[test]
+    fn test_compressed_chunk_round_trip() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let db_path = dir.path().join("chunks.db");
+        let mut store = ChunkStore::new(&db_path);
+        store.ensure_schema().unwrap();
+
+        let body: String = (0..400)
+            .map(|i| format!("    let value_{i} = compute(\"ünïcode\", {i});\n"))
+            .collect();
+        let content = format!("fn large() {{\n{}}}\n", body);
+        let plain = CodeChunk::new(
+            "plain.rs".to_string(),
+            0,
+            content.len(),
+            content.clone(),
+            Some("large".to_string()),
+            Some("fn".to_string()),
+        );
+        store.store_chunk(&plain).unwrap();
+
+        store.set_compress(true);
+        let mut compressed = plain.clone();
+        compressed.file_path = "compressed.rs".to_string();
+        compressed.content = content.replace("compute", "evaluate");
+        compressed.content_hash = CodeChunk::compute_hash(&compressed.content);
+        compressed.byte_end = compressed.content.len();
+        store.store_chunk(&compressed).unwrap();
+
+        let conn = rusqlite::Connection::open(&db_path).unwrap();
+        let stored_len = |hash: &str| -> (String, usize) {
+            conn.query_row(
+                "SELECT typeof(content), length(CAST(content AS BLOB)) FROM chunk_content WHERE hash = ?1",
+                params![hash],
+                |row| Ok((row.get(0)?, row.get::<_, i64>(1)? as usize)),
+            )
+            .unwrap()
+        };
+        assert_eq!(
+            stored_len(&plain.content_hash),
+            ("text".to_string(), content.len())
+        );
+        let (kind, len) = stored_len(&compressed.content_hash);
+        assert_eq!(kind, "blob");
+        assert!(len < compressed.content.len() / 4, "stored {} bytes", len);
+
+        // Compressed and plain rows both read back byte-identical
+        let read = store
+            .get_chunk_by_span("compressed.rs", 0, compressed.byte_end)
+            .unwrap()
+            .unwrap();
+        assert_eq!(read.content.as_bytes(), compressed.content.as_bytes());
+        let read = store.get_chunks_for_file("plain.rs").unwrap();
+        assert_eq!(read[0].content, content);
+
+        // A second chunk with the same text shares the compressed copy
+        let mut duplicate = compressed.clone();
+        duplicate.file_path = "duplicate.rs".to_string();
+        store.store_chunk(&duplicate).unwrap();
+        let inline: String = conn
+            .query_row(
+                "SELECT content FROM code_chunks WHERE file_path = 'duplicate.rs'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert!(inline.is_empty());
+        let read = store.get_chunks_for_file("duplicate.rs").unwrap();
+        assert_eq!(read[0].content, compressed.content);
+    }
+
+    #[test]
+    fn test_side_tables_store_honors_compression() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let db_path = dir.path().join("chunks.db");
+        ChunkStore::new(&db_path).ensure_schema().unwrap();
+        let tables = crate::graph::side_tables::SqliteSideTables::open(&db_path).unwrap();
+        let mut store = ChunkStore::with_side_tables(Arc::new(tables));
+        store.set_compress(true);
+
+        let content = "fn repeated() {}\n".repeat(200);
+        let chunk = CodeChunk::new(
+            "side.rs".to_string(),
+            0,
+            content.len(),
+            content.clone(),
+            Some("repeated".to_string()),
+            Some("fn".to_string()),
+        );
+        store.store_chunks(std::slice::from_ref(&chunk)).unwrap();
+
+        let conn = rusqlite::Connection::open(&db_path).unwrap();
+        let kind: String = conn
+            .query_row(
+                "SELECT typeof(content) FROM chunk_content WHERE hash = ?1",
+                params![chunk.content_hash],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(kind, "blob");
+        let read = store.get_chunks_for_file("side.rs").unwrap();
+        assert_eq!(read[0].content, content);
+    }
 }
@@ -67,7 +67,7 @@ impl CodeChunk {
     }
 
     /// Compute SHA-256 hash of content.
-    fn compute_hash(content: &str) -> String {
+    pub(crate) fn compute_hash(content: &str) -> String {
         use sha2::{Digest, Sha256};
         let mut hasher = Sha256::new();
         hasher.update(content.as_bytes());
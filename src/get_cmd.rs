@@ -9,19 +9,223 @@ use std::path::PathBuf;
 use magellan::backend_router::MagellanBackend;
 use magellan::common::detect_language_from_path;
 use magellan::generation::schema::CodeChunk;
+use magellan::graph::filter::FilePattern;
+use magellan::graph::outline::{build_outline, OutlineNode};
 use magellan::graph::query;
-use magellan::output::rich::{SpanChecksums, SpanContext};
-use magellan::output::{output_json, JsonResponse, Span, SymbolMatch};
+use magellan::ingest::SymbolKind;
+use magellan::log_info;
+use magellan::output::rich::SpanContext;
+use magellan::output::{output_json, ChunksVerifyResponse, JsonResponse, Span, SymbolMatch};
 use magellan::{generate_execution_id, CodeGraph, OutputFormat};
 use serde::{Deserialize, Serialize};
 
+/// How much source `get --expand-to` returns around the symbol
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ExpandTo {
+    /// The symbol's own span
+    #[default]
+    Symbol,
+    /// The nearest function or method whose span contains the symbol
+    Function,
+    /// The whole file
+    File,
+}
+
+impl ExpandTo {
+    pub fn parse(value: &str) -> Result<Self> {
+        match value {
+            "symbol" => Ok(ExpandTo::Symbol),
+            "function" => Ok(ExpandTo::Function),
+            "file" => Ok(ExpandTo::File),
+            other => Err(anyhow::anyhow!(
+                "Unknown --expand-to granularity '{}' (expected symbol, function or file)",
+                other
+            )),
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ExpandTo::Symbol => "symbol",
+            ExpandTo::Function => "function",
+            ExpandTo::File => "file",
+        }
+    }
+}
+
+/// The span `get --expand-to` widened the symbol to
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Expansion {
+    /// Requested granularity (`function` or `file`)
+    pub granularity: String,
+    /// Name of the enclosing function; absent for `file`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Kind of the enclosing function; absent for `file`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub kind: Option<String>,
+    pub byte_start: usize,
+    pub byte_end: usize,
+    pub start_line: usize,
+    pub end_line: usize,
+}
+
 /// Response for get command with rich span data
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GetResponse {
     /// Symbol details
     pub symbol: SymbolMatch,
-    /// Source code content
+    /// Source code content; the expanded span's when `expanded` is present
     pub content: String,
+    /// Present when `--expand-to` widened the returned content
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expanded: Option<Expansion>,
+}
+
+/// Widen a symbol's span to its enclosing function or its file
+///
+/// Returns `None` for [`ExpandTo::Symbol`]. The enclosing function is the
+/// smallest function or method span containing the symbol (the symbol itself
+/// when it is one); its content comes from the stored chunk, falling back to
+/// the file on disk. The whole file is read from disk, resolving relative
+/// stored paths against the database's root; it is an error when the file
+/// cannot be read, since stored chunks do not cover the whole file.
+fn expand_symbol(
+    graph: &mut CodeGraph,
+    file_path: &str,
+    byte_start: usize,
+    byte_end: usize,
+    expand_to: ExpandTo,
+) -> Result<Option<(Expansion, String)>> {
+    match expand_to {
+        ExpandTo::Symbol => Ok(None),
+        ExpandTo::Function => {
+            let function = graph
+                .symbols_in_file(file_path)?
+                .into_iter()
+                .filter(|fact| matches!(fact.kind, SymbolKind::Function | SymbolKind::Method))
+                .filter(|fact| fact.byte_start <= byte_start && byte_end <= fact.byte_end)
+                .min_by_key(|fact| fact.byte_end - fact.byte_start)
+                .ok_or_else(|| {
+                    anyhow::anyhow!(
+                        "No function in '{}' encloses bytes {}-{}",
+                        file_path,
+                        byte_start,
+                        byte_end
+                    )
+                })?;
+            let content = match graph.get_code_chunk_by_span(
+                file_path,
+                function.byte_start,
+                function.byte_end,
+            )? {
+                Some(chunk) => chunk.content,
+                None => std::fs::read(graph.disk_path(file_path))
+                    .ok()
+                    .and_then(|bytes| {
+                        bytes
+                            .get(function.byte_start..function.byte_end)
+                            .map(<[u8]>::to_vec)
+                    })
+                    .map(|bytes| String::from_utf8_lossy(&bytes).into_owned())
+                    .ok_or_else(|| {
+                        anyhow::anyhow!(
+                            "No code chunk stored for enclosing function ({}:{}-{})",
+                            file_path,
+                            function.byte_start,
+                            function.byte_end
+                        )
+                    })?,
+            };
+            let expansion = Expansion {
+                granularity: expand_to.as_str().to_string(),
+                name: function.name,
+                kind: Some(function.kind_normalized),
+                byte_start: function.byte_start,
+                byte_end: function.byte_end,
+                start_line: function.start_line,
+                end_line: function.end_line,
+            };
+            Ok(Some((expansion, content)))
+        }
+        ExpandTo::File => {
+            let disk_path = graph.disk_path(file_path);
+            let bytes = std::fs::read(&disk_path).map_err(|e| {
+                anyhow::anyhow!(
+                    "Cannot read '{}' to expand to the whole file: {}",
+                    disk_path.display(),
+                    e
+                )
+            })?;
+            let content = String::from_utf8_lossy(&bytes).into_owned();
+            let expansion = Expansion {
+                granularity: expand_to.as_str().to_string(),
+                name: None,
+                kind: None,
+                byte_start: 0,
+                byte_end: content.len(),
+                start_line: 1,
+                end_line: content.lines().count().max(1),
+            };
+            Ok(Some((expansion, content)))
+        }
+    }
+}
+
+fn print_expansion_header(expansion: &Expansion) {
+    match expansion.name {
+        Some(ref name) => println!(
+            "// Expanded to {} {} (lines {}-{})",
+            expansion.granularity, name, expansion.start_line, expansion.end_line
+        ),
+        None => println!(
+            "// Expanded to {} (lines {}-{})",
+            expansion.granularity, expansion.start_line, expansion.end_line
+        ),
+    }
+}
+
+/// Attach the optional `--with-*` enrichments to a symbol span
+fn enrich_span(
+    graph: &CodeGraph,
+    span: Span,
+    kind: &str,
+    with_context: bool,
+    with_semantics: bool,
+    with_checksums: bool,
+    context_lines: usize,
+) -> Span {
+    let mut enriched_span = span;
+
+    // Add context if requested
+    if with_context {
+        if let Some(context) = SpanContext::extract(
+            &enriched_span.file_path,
+            enriched_span.start_line,
+            enriched_span.end_line,
+            context_lines,
+        ) {
+            enriched_span = enriched_span.with_context(context);
+        }
+    }
+
+    // Add semantics if requested
+    if with_semantics {
+        let language = detect_language_from_path(&enriched_span.file_path);
+        enriched_span = enriched_span.with_semantics_from(kind.to_string(), language);
+    }
+
+    // Add checksums if requested
+    if with_checksums {
+        let checksums = graph.span_checksums(
+            &enriched_span.file_path,
+            enriched_span.byte_start,
+            enriched_span.byte_end,
+        );
+        enriched_span = enriched_span.with_checksums(checksums);
+    }
+
+    enriched_span
 }
 
 #[allow(
@@ -37,9 +241,10 @@ pub fn run_get(
     with_semantics: bool,
     with_checksums: bool,
     context_lines: usize,
+    expand_to: ExpandTo,
 ) -> Result<()> {
     // Build args for execution tracking
-    let args = vec![
+    let mut args = vec![
         "get".to_string(),
         "--db".to_string(),
         db_path.to_string_lossy().to_string(),
@@ -48,6 +253,10 @@ pub fn run_get(
         "--symbol".to_string(),
         symbol_name.clone(),
     ];
+    if expand_to != ExpandTo::Symbol {
+        args.push("--expand-to".to_string());
+        args.push(expand_to.as_str().to_string());
+    }
 
     let backend = MagellanBackend::open(&db_path)?;
     let exec_id = generate_execution_id();
@@ -92,39 +301,15 @@ pub fn run_get(
                             symbol.end_line,
                             symbol.end_col,
                         );
-
-                        let mut enriched_span = span;
-
-                        // Add context if requested
-                        if with_context {
-                            if let Some(context) = SpanContext::extract(
-                                symbol.file_path.to_string_lossy().as_ref(),
-                                symbol.start_line,
-                                symbol.end_line,
-                                context_lines,
-                            ) {
-                                enriched_span = enriched_span.with_context(context);
-                            }
-                        }
-
-                        // Add semantics if requested
-                        if with_semantics {
-                            let kind = symbol.kind_normalized.clone();
-                            let language = detect_language_from_path(
-                                symbol.file_path.to_string_lossy().as_ref(),
-                            );
-                            enriched_span = enriched_span.with_semantics_from(kind, language);
-                        }
-
-                        // Add checksums if requested
-                        if with_checksums {
-                            let checksums = SpanChecksums::compute(
-                                symbol.file_path.to_string_lossy().as_ref(),
-                                symbol.byte_start,
-                                symbol.byte_end,
-                            );
-                            enriched_span = enriched_span.with_checksums(checksums);
-                        }
+                        let enriched_span = enrich_span(
+                            &graph,
+                            span,
+                            &symbol.kind_normalized,
+                            with_context,
+                            with_semantics,
+                            with_checksums,
+                            context_lines,
+                        );
 
                         let symbol_match = SymbolMatch::new(
                             name.clone(),
@@ -134,16 +319,29 @@ pub fn run_get(
                             symbol_id,
                         );
 
-                        // Get the content from chunks
-                        let content = chunks
-                            .iter()
-                            .map(|c| c.content.clone())
-                            .collect::<Vec<_>>()
-                            .join("\n");
+                        // Get the content from chunks, or from the expanded span
+                        let (expanded, content) = match expand_symbol(
+                            &mut graph,
+                            &file_path,
+                            symbol.byte_start,
+                            symbol.byte_end,
+                            expand_to,
+                        )? {
+                            Some((expansion, content)) => (Some(expansion), content),
+                            None => (
+                                None,
+                                chunks
+                                    .iter()
+                                    .map(|c| c.content.clone())
+                                    .collect::<Vec<_>>()
+                                    .join("\n"),
+                            ),
+                        };
 
                         let response = GetResponse {
                             symbol: symbol_match,
                             content,
+                            expanded,
                         };
 
                         let json_response = JsonResponse::new(response, &exec_id);
@@ -158,6 +356,41 @@ pub fn run_get(
     }
 
     // Human mode (backend-neutral)
+    if expand_to != ExpandTo::Symbol {
+        let mut backend = backend;
+        let MagellanBackend::SQLite(ref mut graph) = backend;
+        // Symbols in one function share its expansion; print each span once
+        let mut printed: Vec<(usize, usize)> = Vec::new();
+        for chunk in &chunks {
+            let Some((expansion, content)) = expand_symbol(
+                graph,
+                &file_path,
+                chunk.byte_start,
+                chunk.byte_end,
+                expand_to,
+            )?
+            else {
+                continue;
+            };
+            let span = (expansion.byte_start, expansion.byte_end);
+            if printed.contains(&span) {
+                continue;
+            }
+            printed.push(span);
+            println!(
+                "// Symbol: {} in {}",
+                chunk.symbol_name.as_ref().unwrap_or(&symbol_name),
+                chunk.file_path
+            );
+            println!("// Bytes: {}-{}", chunk.byte_start, chunk.byte_end);
+            print_expansion_header(&expansion);
+            println!("{}", content);
+            println!();
+        }
+        backend.finish_execution(&exec_id, "success", None, 0, 0, 0)?;
+        return Ok(());
+    }
+
     for chunk in chunks {
         println!(
             "// Symbol: {} in {}",
@@ -177,11 +410,154 @@ pub fn run_get(
     Ok(())
 }
 
+/// Run `get --symbol-id`: resolve a stable SymbolId straight to its code
+///
+/// Bypasses name lookup entirely, so it is unaffected by ambiguous names.
+/// Returns an error if the ID is not in the database.
+#[allow(
+    clippy::too_many_arguments,
+    reason = "CLI command surface: each arg maps to a flag"
+)]
+pub fn run_get_by_symbol_id(
+    db_path: PathBuf,
+    symbol_id: String,
+    output_format: OutputFormat,
+    with_context: bool,
+    with_semantics: bool,
+    with_checksums: bool,
+    context_lines: usize,
+    expand_to: ExpandTo,
+) -> Result<()> {
+    let mut args = vec![
+        "get".to_string(),
+        "--db".to_string(),
+        db_path.to_string_lossy().to_string(),
+        "--symbol-id".to_string(),
+        symbol_id.clone(),
+    ];
+    if expand_to != ExpandTo::Symbol {
+        args.push("--expand-to".to_string());
+        args.push(expand_to.as_str().to_string());
+    }
+
+    let mut graph = CodeGraph::open(&db_path)?;
+    let exec_id = generate_execution_id();
+    let db_path_str = db_path.to_string_lossy().to_string();
+
+    graph.execution_log().start_execution(
+        &exec_id,
+        env!("CARGO_PKG_VERSION"),
+        &args,
+        None,
+        &db_path_str,
+    )?;
+
+    let Some((file_path, symbol)) = query::find_symbol_location_by_id(&mut graph, &symbol_id)?
+    else {
+        let error_msg = format!("Symbol ID '{}' not found", symbol_id);
+        graph
+            .execution_log()
+            .finish_execution(&exec_id, "error", Some(&error_msg), 0, 0, 0)?;
+        return Err(anyhow::anyhow!(error_msg));
+    };
+
+    // Prefer the stored chunk; fall back to the file on disk for databases
+    // indexed before chunks were stored.
+    let content =
+        match graph.get_code_chunk_by_span(&file_path, symbol.byte_start, symbol.byte_end)? {
+            Some(chunk) => chunk.content,
+            None => std::fs::read(graph.disk_path(&file_path))
+                .ok()
+                .and_then(|bytes| {
+                    bytes
+                        .get(symbol.byte_start..symbol.byte_end)
+                        .map(<[u8]>::to_vec)
+                })
+                .map(|bytes| String::from_utf8_lossy(&bytes).into_owned())
+                .ok_or_else(|| {
+                    anyhow::anyhow!(
+                        "No code chunk stored for symbol ID '{}' ({}:{}-{})",
+                        symbol_id,
+                        file_path,
+                        symbol.byte_start,
+                        symbol.byte_end
+                    )
+                })?,
+        };
+
+    let (expanded, content) = match expand_symbol(
+        &mut graph,
+        &file_path,
+        symbol.byte_start,
+        symbol.byte_end,
+        expand_to,
+    )? {
+        Some((expansion, expanded_content)) => (Some(expansion), expanded_content),
+        None => (None, content),
+    };
+
+    let name = symbol.name.clone().unwrap_or_default();
+    let kind = symbol
+        .kind_normalized
+        .clone()
+        .unwrap_or_else(|| symbol.kind.clone());
+
+    if output_format == OutputFormat::Json || output_format == OutputFormat::Pretty {
+        let span = Span::new(
+            file_path,
+            symbol.byte_start,
+            symbol.byte_end,
+            symbol.start_line,
+            symbol.start_col,
+            symbol.end_line,
+            symbol.end_col,
+        );
+        let enriched_span = enrich_span(
+            &graph,
+            span,
+            &kind,
+            with_context,
+            with_semantics,
+            with_checksums,
+            context_lines,
+        );
+        let response = GetResponse {
+            symbol: SymbolMatch::new(name, kind, enriched_span, None, Some(symbol_id)),
+            content,
+            expanded,
+        };
+        output_json(&JsonResponse::new(response, &exec_id), output_format)?;
+    } else {
+        println!("// Symbol: {} in {}", name, file_path);
+        println!("// Kind: {}", kind);
+        println!("// Bytes: {}-{}", symbol.byte_start, symbol.byte_end);
+        if let Some(ref expansion) = expanded {
+            print_expansion_header(expansion);
+        }
+        println!("{}", content);
+        println!();
+    }
+
+    graph
+        .execution_log()
+        .finish_execution(&exec_id, "success", None, 0, 0, 0)?;
+    Ok(())
+}
+
 pub fn run_get_file(
     db_path: PathBuf,
     file_path: String,
+    outline: bool,
+    line_range: Option<(usize, usize)>,
     output_format: OutputFormat,
 ) -> Result<()> {
+    if outline {
+        return run_get_file_outline(db_path, file_path, output_format);
+    }
+    if let Some((start, end)) = line_range {
+        return run_get_file_line_range(db_path, file_path, start, end, output_format);
+    }
+
     // Build args for execution tracking
     let args = vec![
         "get-file".to_string(),
@@ -239,9 +615,285 @@ pub fn run_get_file(
     Ok(())
 }
 
+/// Response for `get-file --outline`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OutlineResponse {
+    /// File the outline describes
+    pub file_path: String,
+    /// Top-level symbols, each with its nested children
+    pub symbols: Vec<OutlineNode>,
+}
+
+fn print_outline(nodes: &[OutlineNode], depth: usize) {
+    for node in nodes {
+        println!(
+            "{}{} ({}) [{}-{}]",
+            "  ".repeat(depth),
+            node.name,
+            node.kind,
+            node.start_line,
+            node.end_line
+        );
+        print_outline(&node.children, depth + 1);
+    }
+}
+
+/// Print a file's symbols as a tree (modules, types, members) with line ranges
+fn run_get_file_outline(
+    db_path: PathBuf,
+    file_path: String,
+    output_format: OutputFormat,
+) -> Result<()> {
+    let mut graph = CodeGraph::open(&db_path)?;
+    let exec_id = generate_execution_id();
+    let args = vec![
+        "get-file".to_string(),
+        "--db".to_string(),
+        db_path.to_string_lossy().to_string(),
+        "--file".to_string(),
+        file_path.clone(),
+        "--outline".to_string(),
+    ];
+    graph.execution_log().start_execution(
+        &exec_id,
+        env!("CARGO_PKG_VERSION"),
+        &args,
+        None,
+        &db_path.to_string_lossy(),
+    )?;
+
+    let symbols = build_outline(graph.symbols_in_file(&file_path)?);
+
+    if output_format == OutputFormat::Json || output_format == OutputFormat::Pretty {
+        let response = OutlineResponse { file_path, symbols };
+        let json_response = JsonResponse::new(response, &exec_id);
+        output_json(&json_response, output_format)?;
+    } else if symbols.is_empty() {
+        eprintln!("No symbols found for file '{}'", file_path);
+    } else {
+        println!("// Outline of {}", file_path);
+        print_outline(&symbols, 0);
+    }
+
+    graph
+        .execution_log()
+        .finish_execution(&exec_id, "success", None, 0, 0, 0)?;
+    Ok(())
+}
+
+/// Response for `get-file --line-range`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LineRangeResponse {
+    /// File the lines come from
+    pub file_path: String,
+    /// First returned line (1-indexed)
+    pub start_line: usize,
+    /// Last returned line (1-indexed, inclusive)
+    pub end_line: usize,
+    /// End line as requested, before clamping
+    pub requested_end_line: usize,
+    /// Number of lines in the file; `None` when rebuilt from chunks, where
+    /// the length of the file is unknown
+    pub total_lines: Option<usize>,
+    /// Whether the requested end was past the end of the file
+    pub clamped: bool,
+    /// Where the lines were read from: `disk` or `chunks`
+    pub source: String,
+    /// The selected lines, joined with newlines
+    pub content: String,
+}
+
+/// Lines of a file, read from disk or rebuilt from its stored chunks.
+///
+/// The rebuilt form places each chunk at the start line of the symbol it
+/// was stored for; lines no chunk covers are `None`.
+fn file_lines(
+    graph: &mut CodeGraph,
+    file_path: &str,
+) -> Result<(Vec<Option<String>>, &'static str)> {
+    if let Ok(bytes) = std::fs::read(graph.disk_path(file_path)) {
+        let content = String::from_utf8_lossy(&bytes);
+        return Ok((
+            content.lines().map(|l| Some(l.to_string())).collect(),
+            "disk",
+        ));
+    }
+
+    let chunks = graph.get_code_chunks(file_path)?;
+    if chunks.is_empty() {
+        return Err(anyhow::anyhow!(
+            "Cannot read '{}' and no code chunks are stored for it",
+            file_path
+        ));
+    }
+    let symbols = graph.symbols_in_file(file_path)?;
+    let mut lines: Vec<Option<String>> = Vec::new();
+    for chunk in &chunks {
+        let Some(symbol) = symbols
+            .iter()
+            .find(|s| s.byte_start == chunk.byte_start && s.byte_end == chunk.byte_end)
+        else {
+            continue;
+        };
+        for (offset, line) in chunk.content.lines().enumerate() {
+            let index = symbol.start_line - 1 + offset;
+            if lines.len() <= index {
+                lines.resize(index + 1, None);
+            }
+            lines[index] = Some(line.to_string());
+        }
+    }
+    Ok((lines, "chunks"))
+}
+
+/// Select a 1-indexed inclusive line range of a file
+///
+/// From disk, an end past the last line is clamped to it and a start past
+/// the last line is an error. Rebuilt from chunks, every requested line must
+/// be covered by a chunk.
+fn select_line_range(
+    graph: &mut CodeGraph,
+    file_path: &str,
+    start: usize,
+    end: usize,
+) -> Result<LineRangeResponse> {
+    let (lines, source) = file_lines(graph, file_path)?;
+
+    let (end_line, total_lines) = if source == "chunks" {
+        if let Some(missing) = (start..=end).find(|&n| !matches!(lines.get(n - 1), Some(Some(_)))) {
+            return Err(anyhow::anyhow!(
+                "'{}' is not on disk and line {} is not covered by its stored chunks",
+                file_path,
+                missing
+            ));
+        }
+        (end, None)
+    } else {
+        let total_lines = lines.len();
+        if start > total_lines {
+            return Err(anyhow::anyhow!(
+                "--line-range start {} is past the end of '{}' ({} lines)",
+                start,
+                file_path,
+                total_lines
+            ));
+        }
+        (end.min(total_lines), Some(total_lines))
+    };
+
+    let content = lines[start - 1..end_line]
+        .iter()
+        .map(|line| line.as_deref().unwrap_or_default())
+        .collect::<Vec<_>>()
+        .join("\n");
+    Ok(LineRangeResponse {
+        file_path: file_path.to_string(),
+        start_line: start,
+        end_line,
+        requested_end_line: end,
+        total_lines,
+        clamped: end_line < end,
+        source: source.to_string(),
+        content,
+    })
+}
+
+/// Print a 1-indexed inclusive line range of a file
+///
+/// See [`select_line_range`] for how the range is read and checked.
+fn run_get_file_line_range(
+    db_path: PathBuf,
+    file_path: String,
+    start: usize,
+    end: usize,
+    output_format: OutputFormat,
+) -> Result<()> {
+    let mut graph = CodeGraph::open(&db_path)?;
+    let exec_id = generate_execution_id();
+    let args = vec![
+        "get-file".to_string(),
+        "--db".to_string(),
+        db_path.to_string_lossy().to_string(),
+        "--file".to_string(),
+        file_path.clone(),
+        "--line-range".to_string(),
+        format!("{}:{}", start, end),
+    ];
+    graph.execution_log().start_execution(
+        &exec_id,
+        env!("CARGO_PKG_VERSION"),
+        &args,
+        None,
+        &db_path.to_string_lossy(),
+    )?;
+
+    let response = match select_line_range(&mut graph, &file_path, start, end) {
+        Ok(response) => response,
+        Err(e) => {
+            let error_msg = e.to_string();
+            graph
+                .execution_log()
+                .finish_execution(&exec_id, "error", Some(&error_msg), 0, 0, 0)?;
+            return Err(e);
+        }
+    };
+
+    if output_format == OutputFormat::Json || output_format == OutputFormat::Pretty {
+        let json_response = JsonResponse::new(response, &exec_id);
+        output_json(&json_response, output_format)?;
+    } else {
+        if let (true, Some(total_lines)) = (response.clamped, response.total_lines) {
+            log_info!(
+                "Note: '{}' has {} lines; --line-range end {} clamped to {}",
+                file_path,
+                total_lines,
+                end,
+                response.end_line
+            );
+        }
+        println!("// Lines {}-{} of {}", start, response.end_line, file_path);
+        println!("{}", response.content);
+    }
+
+    graph
+        .execution_log()
+        .finish_execution(&exec_id, "success", None, 0, 0, 0)?;
+    Ok(())
+}
+
+/// Match a glob `--file` pattern relative to the database's recorded root.
+fn with_recorded_root(
+    conn: &rusqlite::Connection,
+    file_pattern: Option<FilePattern>,
+) -> Result<Option<FilePattern>> {
+    let root = magellan::graph::PathSettings::read(conn)?.map(|settings| settings.root);
+    Ok(file_pattern.map(|pattern| pattern.relative_to(root.as_deref())))
+}
+
+/// Keep the chunks whose file path matches a glob `--file` pattern, then apply `limit`.
+///
+/// Substring patterns are already applied in SQL (`LIKE`) along with the
+/// limit, so without a glob the chunks are returned as they are.
+fn filter_chunks_by_glob(
+    chunks: Vec<CodeChunk>,
+    glob: Option<&FilePattern>,
+    limit: Option<usize>,
+) -> Vec<CodeChunk> {
+    let Some(glob) = glob else {
+        return chunks;
+    };
+    chunks
+        .into_iter()
+        .filter(|chunk| glob.matches(&chunk.file_path))
+        .take(limit.unwrap_or(usize::MAX))
+        .collect()
+}
+
 /// List all code chunks in the database.
 ///
 /// Usage: magellan chunks --db <FILE> [--limit N] [--file PATTERN] [--kind KIND] [--output FORMAT]
+///
+/// With `--verify`, see [`run_chunks_verify`].
 pub fn run_chunks(
     db_path: PathBuf,
     output_format: OutputFormat,
@@ -283,34 +935,33 @@ pub fn run_chunks(
     )?;
 
     // For now, we need to use SQLite directly for filtering
+    let file_pattern = file_filter.as_deref().map(FilePattern::new).transpose()?;
 
     let chunks = {
         // SQLite-only path
         use rusqlite::Connection;
         let conn = Connection::open(&db_path)?;
+        let file_pattern = with_recorded_root(&conn, file_pattern)?;
 
-        let mut query = String::from(
-            "SELECT id, file_path, byte_start, byte_end, content, content_hash,
-                 symbol_name, symbol_kind, created_at
-                 FROM code_chunks
-                 WHERE 1=1",
-        );
+        let mut query = format!("{} WHERE 1=1", magellan::generation::CODE_CHUNK_SELECT);
 
         let mut params: Vec<String> = Vec::new();
 
-        if let Some(ref file_pattern) = file_filter {
-            query.push_str(&format!(" AND file_path LIKE ?{}", params.len() + 1));
+        if let Some(FilePattern::Substring(ref file_pattern)) = file_pattern {
+            query.push_str(&format!(" AND c.file_path LIKE ?{}", params.len() + 1));
             params.push(format!("%{}%", file_pattern));
         }
 
         if let Some(ref kind) = kind_filter {
-            query.push_str(&format!(" AND symbol_kind = ?{}", params.len() + 1));
+            query.push_str(&format!(" AND c.symbol_kind = ?{}", params.len() + 1));
             params.push(kind.to_string());
         }
 
-        query.push_str(" ORDER BY file_path, byte_start");
+        query.push_str(" ORDER BY c.file_path, c.byte_start");
 
-        if let Some(limit_val) = limit {
+        // Globs are matched below, so the limit applies after them
+        let glob = file_pattern.as_ref().filter(|pattern| pattern.is_glob());
+        if let (Some(limit_val), None) = (limit, glob) {
             query.push_str(&format!(" LIMIT {}", limit_val));
         }
 
@@ -324,7 +975,7 @@ pub fn run_chunks(
                 file_path: row.get(1)?,
                 byte_start: row.get(2)?,
                 byte_end: row.get(3)?,
-                content: row.get(4)?,
+                content: magellan::generation::chunk_content_from_row(row, 4)?,
                 content_hash: row.get(5)?,
                 symbol_name: row.get(6)?,
                 symbol_kind: row.get(7)?,
@@ -333,7 +984,7 @@ pub fn run_chunks(
         })?;
 
         let chunks: Result<Vec<CodeChunk>, _> = chunk_iter.collect();
-        chunks?
+        filter_chunks_by_glob(chunks?, glob, limit)
     };
 
     if chunks.is_empty() {
@@ -378,6 +1029,86 @@ pub fn run_chunks(
     Ok(())
 }
 
+/// Check stored code chunks against the files on disk.
+///
+/// Usage: magellan chunks --db <FILE> --verify [--root DIR] [--output FORMAT]
+///
+/// Returns exit code 1 when any chunk drifted from its file.
+pub fn run_chunks_verify(
+    db_path: PathBuf,
+    root: Option<PathBuf>,
+    output_format: OutputFormat,
+) -> Result<u8> {
+    let root = root.unwrap_or_else(|| PathBuf::from("."));
+    let root_str = root.to_string_lossy().to_string();
+    let args = vec![
+        "chunks".to_string(),
+        "--db".to_string(),
+        db_path.to_string_lossy().to_string(),
+        "--verify".to_string(),
+        "--root".to_string(),
+        root_str.clone(),
+    ];
+
+    let graph = CodeGraph::open(&db_path)?;
+    let exec_id = generate_execution_id();
+    graph.execution_log().start_execution(
+        &exec_id,
+        env!("CARGO_PKG_VERSION"),
+        &args,
+        Some(&root_str),
+        &db_path.to_string_lossy(),
+    )?;
+
+    let report = magellan::verify::verify_chunks(&graph, &root)?;
+    let exit_code: u8 = if report.is_clean() { 0 } else { 1 };
+
+    match output_format {
+        OutputFormat::Json | OutputFormat::Pretty => {
+            let response = ChunksVerifyResponse {
+                root: root_str,
+                chunks_checked: report.chunks_checked,
+                files_checked: report.files_checked,
+                files_missing: report.files_missing,
+                drifted: report.drifted,
+            };
+            output_json(&JsonResponse::new(response, &exec_id), output_format)?;
+        }
+        OutputFormat::Human => {
+            println!(
+                "Checked {} chunks in {} files",
+                report.chunks_checked, report.files_checked
+            );
+            if !report.files_missing.is_empty() {
+                println!("Skipped {} missing file(s):", report.files_missing.len());
+                for path in &report.files_missing {
+                    println!("  - {}", path);
+                }
+            }
+            if report.drifted.is_empty() {
+                println!("No chunk drift");
+            } else {
+                println!("Drifted chunks ({}):", report.drifted.len());
+                for drift in &report.drifted {
+                    println!(
+                        "  {}:{}-{} {}: {}",
+                        drift.file_path,
+                        drift.byte_start,
+                        drift.byte_end,
+                        drift.symbol_name.as_deref().unwrap_or("<unnamed>"),
+                        drift.kind.describe()
+                    );
+                }
+            }
+        }
+    }
+
+    graph
+        .execution_log()
+        .finish_execution(&exec_id, "success", None, 0, 0, 0)?;
+    Ok(exit_code)
+}
+
 /// Get a code chunk by file path and byte range.
 ///
 /// Usage: magellan chunk-by-span --db <FILE> --file <PATH> --start <N> --end <N> [--output FORMAT]
@@ -452,12 +1183,16 @@ pub fn run_chunk_by_span(
 
 /// Get all code chunks for a symbol name (global search across files).
 ///
-/// Usage: magellan chunk-by-symbol --db <FILE> --symbol <NAME> [--file PATTERN] [--output FORMAT]
+/// Without `file_filter` every occurrence in the database is returned.
+/// Chunks are ordered by file path, then byte start, and capped at `limit`.
+///
+/// Usage: magellan chunk-by-symbol --db <FILE> --symbol <NAME> [--file PATTERN] [--all-occurrences] [--limit N] [--output FORMAT]
 pub fn run_chunk_by_symbol(
     db_path: PathBuf,
     symbol_name: String,
     output_format: OutputFormat,
     file_filter: Option<String>,
+    limit: Option<usize>,
 ) -> Result<()> {
     // Build args for execution tracking
     let mut args = vec![
@@ -471,6 +1206,10 @@ pub fn run_chunk_by_symbol(
         args.push("--file".to_string());
         args.push(file.clone());
     }
+    if let Some(limit) = limit {
+        args.push("--limit".to_string());
+        args.push(limit.to_string());
+    }
 
     let backend = MagellanBackend::open(&db_path)?;
     let exec_id = generate_execution_id();
@@ -486,26 +1225,31 @@ pub fn run_chunk_by_symbol(
 
     // Global symbol search uses SQLite directly because the graph backend's
     // chunk lookup API requires a file_path filter.
+    let file_pattern = file_filter.as_deref().map(FilePattern::new).transpose()?;
 
     let chunks = {
         use rusqlite::Connection;
         let conn = Connection::open(&db_path)?;
+        let file_pattern = with_recorded_root(&conn, file_pattern)?;
 
-        let mut query = String::from(
-            "SELECT id, file_path, byte_start, byte_end, content, content_hash, \
-             symbol_name, symbol_kind, created_at \
-             FROM code_chunks \
-             WHERE symbol_name = ?1",
+        let mut query = format!(
+            "{} WHERE c.symbol_name = ?1",
+            magellan::generation::CODE_CHUNK_SELECT
         );
 
         let mut params: Vec<String> = vec![symbol_name.clone()];
 
-        if let Some(ref file_pattern) = file_filter {
-            query.push_str(&format!(" AND file_path LIKE ?{}", params.len() + 1));
+        if let Some(FilePattern::Substring(ref file_pattern)) = file_pattern {
+            query.push_str(&format!(" AND c.file_path LIKE ?{}", params.len() + 1));
             params.push(format!("%{}%", file_pattern));
         }
 
-        query.push_str(" ORDER BY file_path, byte_start");
+        query.push_str(" ORDER BY c.file_path, c.byte_start");
+        // Globs are matched below, so the limit applies after them
+        let glob = file_pattern.as_ref().filter(|pattern| pattern.is_glob());
+        if let (Some(limit), None) = (limit, glob) {
+            query.push_str(&format!(" LIMIT {}", limit));
+        }
 
         let mut stmt = conn.prepare(&query)?;
 
@@ -519,7 +1263,7 @@ pub fn run_chunk_by_symbol(
                 file_path: row.get(1)?,
                 byte_start: row.get(2)?,
                 byte_end: row.get(3)?,
-                content: row.get(4)?,
+                content: magellan::generation::chunk_content_from_row(row, 4)?,
                 content_hash: row.get(5)?,
                 symbol_name: row.get(6)?,
                 symbol_kind: row.get(7)?,
@@ -528,7 +1272,7 @@ pub fn run_chunk_by_symbol(
         })?;
 
         let chunks: Result<Vec<CodeChunk>, _> = chunk_iter.collect();
-        chunks?
+        filter_chunks_by_glob(chunks?, glob, limit)
     };
 
     if chunks.is_empty() {
@@ -549,9 +1293,9 @@ pub fn run_chunk_by_symbol(
     println!("// {} chunks for symbol '{}'", chunks.len(), symbol_name);
     println!();
 
-    // Group chunks by file path
-    let mut chunks_by_file: std::collections::HashMap<String, Vec<&CodeChunk>> =
-        std::collections::HashMap::new();
+    // Group chunks by file path, keeping the file order of the query
+    let mut chunks_by_file: std::collections::BTreeMap<String, Vec<&CodeChunk>> =
+        std::collections::BTreeMap::new();
     for chunk in &chunks {
         chunks_by_file
             .entry(chunk.file_path.clone())
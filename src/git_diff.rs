@@ -0,0 +1,328 @@
+//! Changed line ranges from `git diff`
+//!
+//! `refs --since <REF>` keeps only references on lines changed since a git
+//! ref. The ranges come from `git diff --unified=0 <REF>`, which compares the
+//! working tree (what `watch` indexes) against the ref, so both committed and
+//! uncommitted edits count. Files git does not track are not in the diff;
+//! they are new relative to any ref, so every line of them counts as changed.
+
+use anyhow::{Context, Result};
+use std::collections::{BTreeMap, BTreeSet};
+use std::ops::RangeInclusive;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Lines changed since a git ref, keyed by path relative to the repo root
+#[derive(Debug, Clone, Default)]
+pub struct ChangedLines {
+    /// Absolute, canonical repository root
+    repo_root: PathBuf,
+    /// New-side line ranges (1-indexed, inclusive) of each modified file
+    ranges: BTreeMap<PathBuf, Vec<RangeInclusive<usize>>>,
+    /// Untracked files, which count as changed on every line
+    untracked: BTreeSet<PathBuf>,
+}
+
+/// Run git in `dir` and return stdout
+fn git(dir: &Path, args: &[&str]) -> Result<String> {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .context("Failed to run git")?;
+    if !output.status.success() {
+        anyhow::bail!(
+            "git {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Parse the `+start[,count]` side of a hunk header into a line range
+///
+/// Returns `None` for pure deletions (`count` 0), which leave no new lines.
+fn parse_new_range(header: &str) -> Option<RangeInclusive<usize>> {
+    let new_side = header
+        .strip_prefix("@@ ")?
+        .split_whitespace()
+        .find_map(|part| part.strip_prefix('+'))?;
+    let (start, count) = match new_side.split_once(',') {
+        Some((start, count)) => (start.parse::<usize>().ok()?, count.parse::<usize>().ok()?),
+        None => (new_side.parse::<usize>().ok()?, 1),
+    };
+    if count == 0 {
+        return None;
+    }
+    Some(start..=start + count - 1)
+}
+
+/// Undo git's C-style quoting of a path (`"dir/caf\303\251.rs"`)
+///
+/// Git quotes paths holding control characters, `"` or `\` even with
+/// `core.quotepath=off`. Returns `None` if `quoted` is not a quoted string.
+fn unquote_git_path(quoted: &str) -> Option<String> {
+    let inner = quoted.strip_prefix('"')?.strip_suffix('"')?;
+    let mut bytes = Vec::with_capacity(inner.len());
+    let mut input = inner.bytes();
+    while let Some(byte) = input.next() {
+        if byte != b'\\' {
+            bytes.push(byte);
+            continue;
+        }
+        let escaped = input.next()?;
+        let unescaped = match escaped {
+            b'a' => 0x07,
+            b'b' => 0x08,
+            b'f' => 0x0c,
+            b'n' => b'\n',
+            b'r' => b'\r',
+            b't' => b'\t',
+            b'v' => 0x0b,
+            b'0'..=b'7' => {
+                // Three octal digits encode one raw byte
+                let mut value = u32::from(escaped - b'0');
+                for _ in 0..2 {
+                    let digit = input.next().filter(|d| (b'0'..=b'7').contains(d))?;
+                    value = value * 8 + u32::from(digit - b'0');
+                }
+                u8::try_from(value).ok()?
+            }
+            other => other,
+        };
+        bytes.push(unescaped);
+    }
+    Some(String::from_utf8_lossy(&bytes).into_owned())
+}
+
+/// Resolve `since` to a commit id in the repo holding `dir`
+///
+/// `--end-of-options` keeps a value starting with `-` from being read as a
+/// git option.
+fn resolve_commit(dir: &Path, since: &str) -> Result<String> {
+    let commit_spec = format!("{since}^{{commit}}");
+    let commit = git(
+        dir,
+        &["rev-parse", "--verify", "--end-of-options", &commit_spec],
+    )
+    .with_context(|| format!("'{since}' is not a commit"))?;
+    Ok(commit.trim().to_string())
+}
+
+/// Paths changed in the working tree since `since`, relative to `dir`
+///
+/// Used by `index --changed-from`. The ref is resolved with
+/// [`resolve_commit`] and names are read NUL-separated, so non-ASCII and
+/// otherwise quoted paths come back verbatim.
+pub fn changed_paths(dir: &Path, since: &str) -> Result<Vec<PathBuf>> {
+    let commit = resolve_commit(dir, since)?;
+    let names = git(
+        dir,
+        &[
+            "-c",
+            "core.quotepath=off",
+            "diff",
+            "-z",
+            "--name-only",
+            "--relative",
+            "--no-ext-diff",
+            &commit,
+            "--",
+        ],
+    )?;
+    Ok(names
+        .split('\0')
+        .filter(|path| !path.is_empty())
+        .map(PathBuf::from)
+        .collect())
+}
+
+/// The new-side path of a `+++ ` line, without its `b/` prefix
+///
+/// Returns `None` for `/dev/null` (a deleted file). Git ends the line with a
+/// tab when the unquoted path contains a space; that tab is dropped.
+fn parse_new_path(target: &str) -> Option<PathBuf> {
+    let path = if target.starts_with('"') {
+        unquote_git_path(target)?
+    } else {
+        target.trim_end_matches('\t').to_string()
+    };
+    path.strip_prefix("b/").map(PathBuf::from)
+}
+
+/// New-side changed line ranges per file from a unified diff
+///
+/// Paths are taken from the `+++ b/<path>` lines, so the diff must use git's
+/// default `a/`/`b/` prefixes; deleted files (`+++ /dev/null`) have no new
+/// side and are left out.
+pub fn parse_unified_diff(diff: &str) -> BTreeMap<PathBuf, Vec<RangeInclusive<usize>>> {
+    let mut ranges: BTreeMap<PathBuf, Vec<RangeInclusive<usize>>> = BTreeMap::new();
+    let mut current: Option<PathBuf> = None;
+    for line in diff.lines() {
+        if let Some(target) = line.strip_prefix("+++ ") {
+            current = parse_new_path(target);
+        } else if line.starts_with("@@ ") {
+            if let (Some(path), Some(range)) = (&current, parse_new_range(line)) {
+                ranges.entry(path.clone()).or_default().push(range);
+            }
+        }
+    }
+    ranges
+}
+
+impl ChangedLines {
+    /// Lines changed in the working tree since `since`, for the repo holding `dir`
+    ///
+    /// `since` must name a commit; it is resolved before the diff so a value
+    /// starting with `-` is never read as a git option. Prefixes and path
+    /// quoting are pinned, overriding `diff.noprefix`, `diff.mnemonicPrefix`
+    /// and `core.quotepath` in the user's config.
+    pub fn from_git(dir: &Path, since: &str) -> Result<Self> {
+        let top = git(dir, &["rev-parse", "--show-toplevel"])?;
+        let repo_root = PathBuf::from(top.trim());
+        let commit = resolve_commit(&repo_root, since)?;
+        let diff = git(
+            &repo_root,
+            &[
+                "-c",
+                "core.quotepath=off",
+                "diff",
+                "--unified=0",
+                "--no-color",
+                "--no-ext-diff",
+                "--src-prefix=a/",
+                "--dst-prefix=b/",
+                &commit,
+                "--",
+            ],
+        )?;
+        let untracked = git(
+            &repo_root,
+            &["ls-files", "-z", "--others", "--exclude-standard"],
+        )?;
+        Ok(Self::from_diff(
+            &repo_root,
+            &diff,
+            untracked
+                .split('\0')
+                .filter(|path| !path.is_empty())
+                .map(PathBuf::from),
+        ))
+    }
+
+    /// Build from diff text and untracked paths (both relative to `repo_root`)
+    pub fn from_diff(
+        repo_root: &Path,
+        diff: &str,
+        untracked: impl IntoIterator<Item = PathBuf>,
+    ) -> Self {
+        Self {
+            repo_root: repo_root
+                .canonicalize()
+                .unwrap_or_else(|_| repo_root.to_path_buf()),
+            ranges: parse_unified_diff(diff),
+            untracked: untracked.into_iter().collect(),
+        }
+    }
+
+    /// Path relative to the repo root, or `None` when it lies outside the repo
+    fn repo_relative(&self, path: &Path) -> Option<PathBuf> {
+        let path = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+        path.strip_prefix(&self.repo_root)
+            .ok()
+            .map(Path::to_path_buf)
+    }
+
+    /// Whether any line of `start_line..=end_line` in `path` changed
+    ///
+    /// `path` must be absolute; files outside the repository never match.
+    pub fn overlaps(&self, path: &Path, start_line: usize, end_line: usize) -> bool {
+        let Some(relative) = self.repo_relative(path) else {
+            return false;
+        };
+        if self.untracked.contains(&relative) {
+            return true;
+        }
+        self.ranges.get(&relative).is_some_and(|ranges| {
+            ranges
+                .iter()
+                .any(|range| *range.start() <= end_line && start_line <= *range.end())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FIXTURE_DIFF: &str = "\
+diff --git a/src/lib.rs b/src/lib.rs
+index 1111111..2222222 100644
+--- a/src/lib.rs
++++ b/src/lib.rs
+@@ -3,0 +4,2 @@ fn target() {}
++fn added_a() { target(); }
++fn added_b() { target(); }
+@@ -10 +12 @@ fn old() {
+-    target();
++    target(); // edited
+@@ -20,3 +21,0 @@ fn gone() {
+-fn gone() {
+-    target();
+-}
+diff --git a/src/removed.rs b/src/removed.rs
+deleted file mode 100644
+--- a/src/removed.rs
++++ /dev/null
+@@ -1 +0,0 @@
+-fn removed() { target(); }
+";
+
+    #[test]
+    fn test_parse_unified_diff_keeps_new_side_ranges() {
+        let ranges = parse_unified_diff(FIXTURE_DIFF);
+        assert_eq!(ranges.len(), 1, "deleted files have no new side");
+        assert_eq!(ranges[Path::new("src/lib.rs")], vec![4..=5, 12..=12]);
+    }
+
+    #[test]
+    fn test_parse_unified_diff_handles_spaces_and_quoted_paths() {
+        let diff = "\
+--- a/my file.rs\t
++++ b/my file.rs\t
+@@ -1 +1 @@
+-old
++new
+--- \"a/caf\\303\\251 \\\"x\\\".rs\"
++++ \"b/caf\\303\\251 \\\"x\\\".rs\"
+@@ -2 +2,2 @@
+";
+        let ranges = parse_unified_diff(diff);
+        assert_eq!(ranges[Path::new("my file.rs")], vec![1..=1]);
+        assert_eq!(ranges[Path::new("caf\u{e9} \"x\".rs")], vec![2..=3]);
+    }
+
+    #[test]
+    fn test_overlaps_only_changed_lines() {
+        let root = Path::new("/repo");
+        let changed =
+            ChangedLines::from_diff(root, FIXTURE_DIFF, vec![PathBuf::from("src/new.rs")]);
+        let lib = root.join("src/lib.rs");
+
+        // References (by line) in src/lib.rs: only 4, 5, and 12 are in a hunk
+        let kept: Vec<usize> = [1, 4, 5, 8, 12, 21]
+            .into_iter()
+            .filter(|&line| changed.overlaps(&lib, line, line))
+            .collect();
+        assert_eq!(kept, vec![4, 5, 12]);
+
+        // A multi-line reference counts when any of its lines changed
+        assert!(changed.overlaps(&lib, 10, 12));
+        // Untracked files are new, so every line counts
+        assert!(changed.overlaps(&root.join("src/new.rs"), 99, 99));
+        // Unchanged tracked files and paths outside the repo never match
+        assert!(!changed.overlaps(&root.join("src/other.rs"), 1, 1));
+        assert!(!changed.overlaps(Path::new("/elsewhere/src/lib.rs"), 4, 4));
+    }
+}
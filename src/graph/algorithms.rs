@@ -20,6 +20,17 @@
 //! Magellan uses sqlitegraph's clustered adjacency storage for ~10x graph
 //! traversal performance improvement when available.
 //!
+//! # Backends
+//!
+//! The traversals themselves (reachability, SCC collapse, path enumeration,
+//! shortest path) only use the `sqlitegraph::GraphBackend` neighbor API
+//! (`fetch_outgoing`, `fetch_incoming`, `get_node`). Entity resolution
+//! ([`CodeGraph::resolve_symbol_entity()`]), the call-graph node set used by
+//! dead-code and cycle detection, and [`CodeGraph::reachable_call_edges()`]
+//! still query the SQLite tables directly. SQLite is the only backend this
+//! crate builds; a second backend would need KV equivalents of those three
+//! queries.
+//!
 //! # Entity IDs vs Symbol IDs
 //!
 //! sqlitegraph algorithms work with **entity IDs** (i64 database row IDs),
@@ -38,9 +49,17 @@
 //! - [`CodeGraph::find_cycles_containing()`]: Find cycles containing a specific symbol
 //! - [`CodeGraph::condense_call_graph()`]: Collapse SCCs to create condensation DAG
 //! - [`CodeGraph::enumerate_paths()`]: Path enumeration between symbols
+//! - [`CodeGraph::shortest_path()`]: Single shortest call path between symbols
 //! - [`CodeGraph::backward_slice()`]: Backward program slice (what affects this symbol)
 //! - [`CodeGraph::forward_slice()`]: Forward program slice (what this symbol affects)
 //!
+//! # Cancellation
+//!
+//! Reachability and path queries poll [`CodeGraph::cancel_token()`] once per
+//! visited node. When it is set they stop and return what they found so far:
+//! [`PathEnumerationResult::cancelled`] reports it for paths, and
+//! [`CodeGraph::is_cancelled()`] for the reachability queries.
+//!
 //! # Example
 //!
 //! \`\`\`no_run
@@ -68,18 +87,26 @@ use sqlitegraph::algo::backend::graph_ops::strongly_connected_components;
 use sqlitegraph::errors::SqliteGraphError;
 use sqlitegraph::{GraphBackend, SnapshotId};
 use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 
 use crate::graph::schema::SymbolNode;
 
 use super::CodeGraph;
 
+/// Whether `cancel` is present and set
+fn is_cancelled(cancel: Option<&AtomicBool>) -> bool {
+    cancel.is_some_and(|flag| flag.load(Ordering::Relaxed))
+}
+
 /// Backend-agnostic reachable_from implementation
 ///
 /// Uses `fetch_outgoing` from GraphBackend trait instead of requiring SqliteGraph.
+/// Stops early with the nodes visited so far once `cancel` is set.
 fn reachable_from(
     backend: &dyn GraphBackend,
     start: i64,
+    cancel: Option<&AtomicBool>,
 ) -> Result<AHashSet<i64>, SqliteGraphError> {
     let mut visited = AHashSet::new();
     let mut queue = VecDeque::new();
@@ -88,6 +115,9 @@ fn reachable_from(
     queue.push_back(start);
 
     while let Some(node) = queue.pop_front() {
+        if is_cancelled(cancel) {
+            break;
+        }
         for neighbor in backend.fetch_outgoing(node)? {
             if visited.insert(neighbor) {
                 queue.push_back(neighbor);
@@ -101,9 +131,11 @@ fn reachable_from(
 /// Backend-agnostic reverse_reachable_from implementation
 ///
 /// Uses `fetch_incoming` from GraphBackend trait instead of requiring SqliteGraph.
+/// Stops early with the nodes visited so far once `cancel` is set.
 fn reverse_reachable_from(
     backend: &dyn GraphBackend,
     start: i64,
+    cancel: Option<&AtomicBool>,
 ) -> Result<AHashSet<i64>, SqliteGraphError> {
     let mut visited = AHashSet::new();
     let mut queue = VecDeque::new();
@@ -112,6 +144,9 @@ fn reverse_reachable_from(
     queue.push_back(start);
 
     while let Some(node) = queue.pop_front() {
+        if is_cancelled(cancel) {
+            break;
+        }
         for neighbor in backend.fetch_incoming(node)? {
             if visited.insert(neighbor) {
                 queue.push_back(neighbor);
@@ -122,6 +157,63 @@ fn reverse_reachable_from(
     Ok(visited)
 }
 
+/// Shortest call depth of every symbol reachable from `start`
+///
+/// Depth counts the Symbol nodes entered along a path, so the Call nodes
+/// between a caller and its callee add no hops: `start` is at depth 0 and its
+/// direct callees (direct callers with `reverse`) at depth 1. The traversal
+/// goes level by level, so each symbol keeps the depth at which it was first
+/// reached, and symbols at `max_depth` are not expanded. Stops early with the
+/// depths found so far once `cancel` is set.
+fn reachable_depths(
+    backend: &dyn GraphBackend,
+    start: i64,
+    reverse: bool,
+    max_depth: Option<usize>,
+    cancel: Option<&AtomicBool>,
+) -> Result<AHashMap<i64, usize>, SqliteGraphError> {
+    let snapshot = SnapshotId::current();
+    let mut depths = AHashMap::new();
+    let mut visited = AHashSet::new();
+
+    depths.insert(start, 0);
+    visited.insert(start);
+    let mut frontier = vec![start];
+    let mut depth = 0;
+
+    while !frontier.is_empty() && max_depth.is_none_or(|max| depth < max) {
+        let mut next = Vec::new();
+        // Walk through non-symbol nodes without adding depth
+        let mut queue: VecDeque<i64> = frontier.into_iter().collect();
+        while let Some(node) = queue.pop_front() {
+            if is_cancelled(cancel) {
+                return Ok(depths);
+            }
+            let neighbors = if reverse {
+                backend.fetch_incoming(node)?
+            } else {
+                backend.fetch_outgoing(node)?
+            };
+            for neighbor in neighbors {
+                if !visited.insert(neighbor) {
+                    continue;
+                }
+                if matches!(backend.get_node(snapshot, neighbor), Ok(node) if node.kind == "Symbol")
+                {
+                    depths.insert(neighbor, depth + 1);
+                    next.push(neighbor);
+                } else {
+                    queue.push_back(neighbor);
+                }
+            }
+        }
+        frontier = next;
+        depth += 1;
+    }
+
+    Ok(depths)
+}
+
 /// Result of SCC collapse operation
 #[derive(Debug, Clone)]
 struct SccCollapseResult {
@@ -131,6 +223,8 @@ struct SccCollapseResult {
     supernode_members: AHashMap<i64, AHashSet<i64>>,
     /// Edges between supernodes in the condensed DAG
     supernode_edges: Vec<(i64, i64)>,
+    /// Edges whose ends share a supernode, keyed by that supernode ID
+    internal_edges: AHashMap<i64, Vec<(i64, i64)>>,
     /// Total number of SCCs found
     _num_sccs: usize,
 }
@@ -148,6 +242,7 @@ fn collapse_sccs(backend: &dyn GraphBackend) -> Result<SccCollapseResult, Sqlite
             _node_to_supernode: AHashMap::new(),
             supernode_members: AHashMap::new(),
             supernode_edges: Vec::new(),
+            internal_edges: AHashMap::new(),
             _num_sccs: 0,
         });
     }
@@ -157,7 +252,8 @@ fn collapse_sccs(backend: &dyn GraphBackend) -> Result<SccCollapseResult, Sqlite
     let mut supernode_members: AHashMap<i64, AHashSet<i64>> = AHashMap::new();
 
     for component in &scc_result.components {
-        let supernode_id = component[0]; // Use first member as supernode ID
+        // The smallest member ID names the supernode, independent of traversal order
+        let supernode_id = component.iter().copied().min().unwrap_or(component[0]);
         let mut members = AHashSet::new();
         for &node in component {
             node_to_supernode.insert(node, supernode_id);
@@ -169,6 +265,7 @@ fn collapse_sccs(backend: &dyn GraphBackend) -> Result<SccCollapseResult, Sqlite
     // Step 4: Build supernode edges (condensed graph)
     let mut supernode_edges: Vec<(i64, i64)> = Vec::new();
     let mut seen_edges: HashSet<(i64, i64)> = HashSet::new();
+    let mut internal_edges: AHashMap<i64, Vec<(i64, i64)>> = AHashMap::new();
 
     for (&node, &supernode) in &node_to_supernode {
         for neighbor in backend.fetch_outgoing(node)? {
@@ -179,6 +276,11 @@ fn collapse_sccs(backend: &dyn GraphBackend) -> Result<SccCollapseResult, Sqlite
                     if seen_edges.insert(edge) {
                         supernode_edges.push(edge);
                     }
+                } else {
+                    internal_edges
+                        .entry(supernode)
+                        .or_default()
+                        .push((node, neighbor));
                 }
             }
         }
@@ -191,10 +293,47 @@ fn collapse_sccs(backend: &dyn GraphBackend) -> Result<SccCollapseResult, Sqlite
         _node_to_supernode: node_to_supernode,
         supernode_members,
         supernode_edges,
+        internal_edges,
         _num_sccs: scc_result.components.len(),
     })
 }
 
+/// Contract the edges inside one SCC to edges between its symbol members
+///
+/// `members` holds the entity IDs of the symbol members, sorted. A call runs
+/// Symbol -CALLER-> Call -CALLS-> Symbol, so an edge into a non-member node is
+/// followed one more hop to the symbols that node leads to.
+///
+/// # Returns
+/// Sorted, deduplicated `(from, to)` pairs of indices into `members`
+fn member_edges(edges: &[(i64, i64)], members: &[i64]) -> Vec<(usize, usize)> {
+    let position = |id: i64| members.binary_search(&id).ok();
+    let mut outgoing: AHashMap<i64, Vec<i64>> = AHashMap::new();
+    for &(from, to) in edges {
+        outgoing.entry(from).or_default().push(to);
+    }
+
+    let mut result = std::collections::BTreeSet::new();
+    for &(from, via) in edges {
+        let Some(from_pos) = position(from) else {
+            continue;
+        };
+        match position(via) {
+            Some(to_pos) => {
+                result.insert((from_pos, to_pos));
+            }
+            None => {
+                for &to in outgoing.get(&via).into_iter().flatten() {
+                    if let Some(to_pos) = position(to) {
+                        result.insert((from_pos, to_pos));
+                    }
+                }
+            }
+        }
+    }
+    result.into_iter().collect()
+}
+
 /// Path enumeration result for backend-agnostic implementation
 #[derive(Debug, Clone)]
 struct InternalPathEnumerationResult {
@@ -206,6 +345,8 @@ struct InternalPathEnumerationResult {
     pruned_by_bounds: usize,
     /// Maximum depth reached during enumeration
     _max_depth_reached: usize,
+    /// Whether the cancel flag cut enumeration short
+    cancelled: bool,
 }
 
 /// Configuration for path enumeration
@@ -219,8 +360,12 @@ struct PathEnumerationConfig {
     revisit_cap: usize,
     /// Optional set of nodes that terminate path exploration
     exit_nodes: Option<AHashSet<i64>>,
+    /// Nodes never entered, as if they and their edges were removed
+    avoid_nodes: AHashSet<i64>,
     /// Optional set of nodes that represent errors
     _error_nodes: Option<AHashSet<i64>>,
+    /// Optional flag that stops enumeration when set
+    cancel: Option<Arc<AtomicBool>>,
 }
 
 impl Default for PathEnumerationConfig {
@@ -230,7 +375,9 @@ impl Default for PathEnumerationConfig {
             max_paths: 1000,
             revisit_cap: 100,
             exit_nodes: None,
+            avoid_nodes: AHashSet::new(),
             _error_nodes: None,
+            cancel: None,
         }
     }
 }
@@ -267,6 +414,7 @@ fn enumerate_paths(
         total_found,
         pruned_by_bounds,
         _max_depth_reached: max_depth_reached,
+        cancelled: is_cancelled(config.cancel.as_deref()),
     })
 }
 
@@ -286,6 +434,10 @@ fn dfs_enumerate(
     pruned_by_bounds: &mut usize,
     max_depth_reached: &mut usize,
 ) -> Result<(), SqliteGraphError> {
+    if is_cancelled(config.cancel.as_deref()) {
+        return Ok(());
+    }
+
     // Update visit count for this node
     let count = visit_count.entry(node).or_insert(0);
     *count += 1;
@@ -328,6 +480,9 @@ fn dfs_enumerate(
     let mut had_successors = false;
 
     for neighbor in neighbors {
+        if config.avoid_nodes.contains(&neighbor) {
+            continue;
+        }
         had_successors = true;
         dfs_enumerate(
             backend,
@@ -357,6 +512,89 @@ fn dfs_enumerate(
     Ok(())
 }
 
+/// Result of a backend-agnostic shortest path search
+#[derive(Debug, Clone)]
+struct InternalShortestPathResult {
+    /// Node IDs from entry to target, or None when the target is unreachable
+    path: Option<Vec<i64>>,
+    /// Whether some node was left unexpanded because of max_depth
+    pruned_by_depth: bool,
+    /// Whether the cancel flag stopped the search
+    cancelled: bool,
+}
+
+/// Backend-agnostic shortest path search (BFS over `fetch_outgoing`)
+///
+/// Returns the first path reaching `target` by edge count. Neighbors are
+/// visited in ascending ID order so ties resolve deterministically. The
+/// target is matched when it is reached as a neighbor, so `entry == target`
+/// finds the shortest cycle back to the entry. `max_depth` bounds the number
+/// of nodes on a path, matching [`enumerate_paths`]. Nodes in `avoid` are
+/// never entered. Once `cancel` is set the search stops without a path.
+fn shortest_path(
+    backend: &dyn GraphBackend,
+    entry: i64,
+    target: i64,
+    max_depth: usize,
+    avoid: &AHashSet<i64>,
+    cancel: Option<&AtomicBool>,
+) -> Result<InternalShortestPathResult, SqliteGraphError> {
+    let mut predecessor: AHashMap<i64, i64> = AHashMap::new();
+    let mut visited = AHashSet::new();
+    let mut queue = VecDeque::new();
+    let mut pruned_by_depth = false;
+
+    visited.insert(entry);
+    queue.push_back((entry, 1usize));
+
+    while let Some((node, depth)) = queue.pop_front() {
+        if is_cancelled(cancel) {
+            return Ok(InternalShortestPathResult {
+                path: None,
+                pruned_by_depth,
+                cancelled: true,
+            });
+        }
+        if depth >= max_depth {
+            pruned_by_depth = true;
+            continue;
+        }
+
+        let mut neighbors = backend.fetch_outgoing(node)?;
+        neighbors.sort_unstable();
+
+        for neighbor in neighbors {
+            if avoid.contains(&neighbor) {
+                continue;
+            }
+            if neighbor == target {
+                let mut path = vec![target, node];
+                let mut current = node;
+                while let Some(&prev) = predecessor.get(&current) {
+                    path.push(prev);
+                    current = prev;
+                }
+                path.reverse();
+                return Ok(InternalShortestPathResult {
+                    path: Some(path),
+                    pruned_by_depth: false,
+                    cancelled: false,
+                });
+            }
+            if visited.insert(neighbor) {
+                predecessor.insert(neighbor, node);
+                queue.push_back((neighbor, depth + 1));
+            }
+        }
+    }
+
+    Ok(InternalShortestPathResult {
+        path: None,
+        pruned_by_depth,
+        cancelled: false,
+    })
+}
+
 /// Symbol information for algorithm results
 ///
 /// Contains the key metadata needed to identify and locate a symbol.
@@ -370,6 +608,8 @@ pub struct SymbolInfo {
     pub file_path: String,
     /// Symbol kind (Function, Method, Class, etc.)
     pub kind: String,
+    /// Whether the symbol is test code
+    pub is_test: bool,
 }
 
 /// Dead symbol information
@@ -421,10 +661,12 @@ pub struct CycleReport {
 /// Represents an SCC collapsed into a single node for DAG analysis.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Supernode {
-    /// Supernode ID (stable identifier for this SCC)
+    /// Supernode ID (smallest entity ID in this SCC)
     pub id: i64,
-    /// All symbols that are members of this SCC/supernode
+    /// All symbols that are members of this SCC/supernode, by entity ID
     pub members: Vec<SymbolInfo>,
+    /// Calls between members, as sorted `(caller, callee)` indices into `members`
+    pub internal_edges: Vec<(usize, usize)>,
 }
 
 /// Condensation graph (DAG after SCC collapse)
@@ -522,6 +764,9 @@ pub struct PathEnumerationResult {
     pub total_enumerated: usize,
     /// Whether enumeration was cut off due to bounds
     pub bounded_hit: bool,
+    /// Whether [`CodeGraph::cancel_token`] stopped the query; `paths` then
+    /// holds only what was found before
+    pub cancelled: bool,
     /// Statistics about the discovered paths
     pub statistics: PathStatistics,
 }
@@ -631,6 +876,7 @@ impl CodeGraph {
             fqn: symbol_node.fqn.or_else(|| symbol_node.display_fqn.clone()),
             file_path: node.file_path.unwrap_or_else(|| "?".to_string()),
             kind: symbol_node.kind,
+            is_test: symbol_node.is_test,
         })
     }
 
@@ -676,10 +922,11 @@ impl CodeGraph {
     ///
     /// # Arguments
     /// * `symbol_id` - Stable symbol ID to start from (or FQN as fallback)
-    /// * `max_depth` - Optional maximum depth limit (None = unlimited)
+    /// * `max_depth` - Optional maximum depth limit in calls (None = unlimited)
     ///
     /// # Returns
-    /// Vector of [`SymbolInfo`] for reachable symbols, sorted deterministically
+    /// Vector of [`SymbolInfo`] for reachable symbols, sorted deterministically.
+    /// Partial when the query was cancelled (see [`Self::is_cancelled`]).
     ///
     /// # Example
     ///
@@ -692,37 +939,14 @@ impl CodeGraph {
     pub fn reachable_symbols(
         &self,
         symbol_id: &str,
-        _max_depth: Option<usize>,
+        max_depth: Option<usize>,
     ) -> Result<Vec<SymbolInfo>> {
-        let entity_id = self.resolve_symbol_entity(symbol_id)?;
-        let backend = &*self.calls.backend;
-
-        // Use backend-agnostic reachable_from implementation
-        // This traverses outgoing edges from the start node
-        let reachable_entity_ids = reachable_from(backend, entity_id)?;
-
-        // Convert entity IDs to SymbolInfo
-        let mut symbols = Vec::new();
-        for id in reachable_entity_ids {
-            // Skip the starting symbol itself
-            if id == entity_id {
-                continue;
-            }
-
-            if let Ok(info) = self.symbol_by_entity_id(id) {
-                symbols.push(info);
-            }
-        }
-
-        // Sort deterministically for stable output
-        symbols.sort_by(|a, b| {
-            a.file_path
-                .cmp(&b.file_path)
-                .then_with(|| a.fqn.as_ref().cmp(&b.fqn.as_ref()))
-                .then_with(|| a.kind.cmp(&b.kind))
-        });
-
-        Ok(symbols)
+        Ok(self
+            .reachable_symbols_with_depth(symbol_id, false, max_depth)?
+            .into_iter()
+            .filter(|(_, depth)| *depth > 0)
+            .map(|(symbol, _)| symbol)
+            .collect())
     }
 
     /// Find all symbols that can reach a given symbol (reverse reachability)
@@ -737,10 +961,11 @@ impl CodeGraph {
     ///
     /// # Arguments
     /// * `symbol_id` - Stable symbol ID to analyze
-    /// * `max_depth` - Optional maximum depth limit (None = unlimited)
+    /// * `max_depth` - Optional maximum depth limit in calls (None = unlimited)
     ///
     /// # Returns
-    /// Vector of [`SymbolInfo`] for symbols that can reach the target, sorted deterministically
+    /// Vector of [`SymbolInfo`] for symbols that can reach the target, sorted deterministically.
+    /// Partial when the query was cancelled (see [`Self::is_cancelled`]).
     ///
     /// # Example
     ///
@@ -753,30 +978,45 @@ impl CodeGraph {
     pub fn reverse_reachable_symbols(
         &self,
         symbol_id: &str,
-        _max_depth: Option<usize>,
+        max_depth: Option<usize>,
     ) -> Result<Vec<SymbolInfo>> {
+        Ok(self
+            .reachable_symbols_with_depth(symbol_id, true, max_depth)?
+            .into_iter()
+            .filter(|(_, depth)| *depth > 0)
+            .map(|(symbol, _)| symbol)
+            .collect())
+    }
+
+    /// Reachable symbols paired with the depth at which each was first reached
+    ///
+    /// Follows callees, or callers when `reverse` is set. The depth is the
+    /// number of calls on the shortest path from the starting symbol, which is
+    /// included at depth 0. Symbols deeper than `max_depth` are left out.
+    ///
+    /// # Returns
+    /// `(symbol, depth)` pairs sorted by file path, FQN, then kind.
+    /// Partial when the query was cancelled (see [`Self::is_cancelled`]).
+    pub fn reachable_symbols_with_depth(
+        &self,
+        symbol_id: &str,
+        reverse: bool,
+        max_depth: Option<usize>,
+    ) -> Result<Vec<(SymbolInfo, usize)>> {
         let entity_id = self.resolve_symbol_entity(symbol_id)?;
         let backend = &*self.calls.backend;
-
-        // Use backend-agnostic reverse_reachable_from implementation
-        // This traverses incoming edges to the target node
-        let reachable_entity_ids = reverse_reachable_from(backend, entity_id)?;
+        let depths = reachable_depths(backend, entity_id, reverse, max_depth, Some(&self.cancel))?;
 
         // Convert entity IDs to SymbolInfo
         let mut symbols = Vec::new();
-        for id in reachable_entity_ids {
-            // Skip the starting symbol itself
-            if id == entity_id {
-                continue;
-            }
-
+        for (id, depth) in depths {
             if let Ok(info) = self.symbol_by_entity_id(id) {
-                symbols.push(info);
+                symbols.push((info, depth));
             }
         }
 
         // Sort deterministically for stable output
-        symbols.sort_by(|a, b| {
+        symbols.sort_by(|(a, _), (b, _)| {
             a.file_path
                 .cmp(&b.file_path)
                 .then_with(|| a.fqn.as_ref().cmp(&b.fqn.as_ref()))
@@ -786,6 +1026,65 @@ impl CodeGraph {
         Ok(symbols)
     }
 
+    /// Call edges among a symbol and everything reachable from it
+    ///
+    /// Uses the same traversal as [`Self::reachable_symbols`] (or
+    /// [`Self::reverse_reachable_symbols`] when `reverse` is set), keeping the
+    /// starting symbol. Each edge is returned as `(caller, callee)` regardless
+    /// of direction, and only edges with both ends in the reachable set are
+    /// included.
+    ///
+    /// # Returns
+    /// Edges sorted by caller, then callee (file path, then FQN)
+    pub fn reachable_call_edges(
+        &self,
+        symbol_id: &str,
+        reverse: bool,
+    ) -> Result<Vec<(SymbolInfo, SymbolInfo)>> {
+        let entity_id = self.resolve_symbol_entity(symbol_id)?;
+        let backend = &*self.calls.backend;
+        let reachable_entity_ids = if reverse {
+            reverse_reachable_from(backend, entity_id, Some(&self.cancel))?
+        } else {
+            reachable_from(backend, entity_id, Some(&self.cancel))?
+        };
+
+        // Symbol -CALLER-> Call -CALLS-> Symbol, collapsed to caller/callee pairs
+        let conn = self.chunks.connect()?;
+        let mut stmt = conn
+            .prepare_cached(
+                "SELECT DISTINCT caller.from_id, calls.to_id
+                 FROM graph_edges caller
+                 JOIN graph_edges calls ON calls.from_id = caller.to_id
+                 WHERE caller.edge_type = 'CALLER' AND calls.edge_type = 'CALLS'",
+            )
+            .map_err(|e| anyhow::anyhow!("Failed to prepare call edge query: {}", e))?;
+        let pairs = stmt
+            .query_map([], |row| Ok((row.get::<_, i64>(0)?, row.get::<_, i64>(1)?)))
+            .map_err(|e| anyhow::anyhow!("Failed to execute call edge query: {}", e))?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| anyhow::anyhow!("Failed to collect call edge results: {}", e))?;
+
+        let mut edges = Vec::new();
+        for (caller, callee) in pairs {
+            if !reachable_entity_ids.contains(&caller) || !reachable_entity_ids.contains(&callee) {
+                continue;
+            }
+            if let (Ok(caller), Ok(callee)) = (
+                self.symbol_by_entity_id(caller),
+                self.symbol_by_entity_id(callee),
+            ) {
+                edges.push((caller, callee));
+            }
+        }
+
+        let key = |s: &SymbolInfo| (s.file_path.clone(), s.fqn.clone(), s.symbol_id.clone());
+        edges.sort_by_key(|(caller, callee)| (key(caller), key(callee)));
+        edges.dedup();
+
+        Ok(edges)
+    }
+
     /// Find dead code unreachable from an entry point symbol
     ///
     /// Identifies all symbols in the call graph that cannot be reached from
@@ -832,7 +1131,7 @@ impl CodeGraph {
 
         // Find all entities reachable from the entry point
         let backend = &*self.calls.backend;
-        let reachable_ids = reachable_from(backend, entry_entity)?;
+        let reachable_ids = reachable_from(backend, entry_entity, None)?;
 
         // Dead symbols = all entities - reachable entities
         let reachable_set: HashSet<i64> = reachable_ids.into_iter().collect();
@@ -1050,10 +1349,23 @@ impl CodeGraph {
         let mut original_to_supernode = HashMap::new();
 
         for (&supernode_id, member_ids) in &collapse_result.supernode_members {
-            let symbol_infos: Vec<_> = member_ids
-                .iter()
-                .filter_map(|&id| self.symbol_by_entity_id(id).ok())
-                .collect();
+            let mut member_ids: Vec<i64> = member_ids.iter().copied().collect();
+            member_ids.sort_unstable();
+
+            // Non-symbol nodes (files, call sites) drop out of the member list
+            let mut symbol_ids = Vec::new();
+            let mut symbol_infos = Vec::new();
+            for id in member_ids {
+                if let Ok(info) = self.symbol_by_entity_id(id) {
+                    symbol_ids.push(id);
+                    symbol_infos.push(info);
+                }
+            }
+            let internal_edges = collapse_result
+                .internal_edges
+                .get(&supernode_id)
+                .map(|edges| member_edges(edges, &symbol_ids))
+                .unwrap_or_default();
 
             // Build mapping from symbol_id to supernode
             for symbol_info in &symbol_infos {
@@ -1065,10 +1377,11 @@ impl CodeGraph {
             supernodes.push(Supernode {
                 id: supernode_id,
                 members: symbol_infos,
+                internal_edges,
             });
         }
 
-        // Sort supernodes deterministically
+        // Sort supernodes deterministically (by smallest member ID)
         supernodes.sort_by_key(|a| a.id);
 
         let graph = CondensationGraph {
@@ -1131,7 +1444,7 @@ impl CodeGraph {
 
         // Use backend-agnostic reverse_reachable_from on call graph
         // This finds all callers that directly or indirectly call this symbol
-        let caller_entity_ids = reverse_reachable_from(backend, entity_id)?;
+        let caller_entity_ids = reverse_reachable_from(backend, entity_id, None)?;
 
         // Convert entity IDs to SymbolInfo
         let mut included_symbols = Vec::new();
@@ -1220,7 +1533,7 @@ impl CodeGraph {
 
         // Use backend-agnostic reachable_from on call graph
         // This finds all callees that this symbol directly or indirectly calls
-        let callee_entity_ids = reachable_from(backend, entity_id)?;
+        let callee_entity_ids = reachable_from(backend, entity_id, None)?;
 
         // Convert entity IDs to SymbolInfo
         let mut included_symbols = Vec::new();
@@ -1281,7 +1594,7 @@ impl CodeGraph {
     ///
     /// Returns a [`PathEnumerationResult`] containing:
     /// - All discovered paths
-    /// - Whether enumeration hit bounds
+    /// - Whether enumeration hit bounds or was cancelled
     /// - Statistics about path lengths and unique symbols
     ///
     /// # Example
@@ -1308,19 +1621,38 @@ impl CodeGraph {
         end_symbol_id: Option<&str>,
         max_depth: usize,
         max_paths: usize,
+    ) -> Result<PathEnumerationResult> {
+        self.enumerate_paths_avoiding(start_symbol_id, end_symbol_id, max_depth, max_paths, &[])
+    }
+
+    /// Enumerate execution paths that never pass through the `avoid` symbols
+    ///
+    /// Like [`Self::enumerate_paths`], with each avoided symbol (ID or FQN)
+    /// removed from the call graph along with its incident edges. A node
+    /// whose only callees are avoided ends its path as a leaf.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a symbol cannot be resolved, or if the start or
+    /// end symbol is in `avoid`.
+    pub fn enumerate_paths_avoiding(
+        &self,
+        start_symbol_id: &str,
+        end_symbol_id: Option<&str>,
+        max_depth: usize,
+        max_paths: usize,
+        avoid: &[&str],
     ) -> Result<PathEnumerationResult> {
         let start_entity_id = self.resolve_symbol_entity(start_symbol_id)?;
         let backend = &*self.calls.backend;
 
         // Build exit_nodes set for target symbol
-        let exit_nodes: Option<AHashSet<i64>> = if let Some(end_id) = end_symbol_id {
-            let end_entity_id = self.resolve_symbol_entity(end_id)?;
-            let mut set = AHashSet::new();
-            set.insert(end_entity_id);
-            Some(set)
-        } else {
-            None
-        };
+        let end_entity_id = end_symbol_id
+            .map(|end_id| self.resolve_symbol_entity(end_id))
+            .transpose()?;
+        let exit_nodes: Option<AHashSet<i64>> =
+            end_entity_id.map(|id| std::iter::once(id).collect());
+        let avoid_nodes = self.resolve_avoided_entities(avoid, start_entity_id, end_entity_id)?;
 
         // Use backend-agnostic path enumeration
         let config = PathEnumerationConfig {
@@ -1328,7 +1660,9 @@ impl CodeGraph {
             max_paths,
             revisit_cap: 100, // Prevent infinite loops in cyclic graphs
             exit_nodes,
+            avoid_nodes,
             _error_nodes: None,
+            cancel: Some(self.cancel.clone()),
         };
 
         let enum_result = enumerate_paths(backend, start_entity_id, &config)?;
@@ -1388,6 +1722,7 @@ impl CodeGraph {
             paths,
             total_enumerated: enum_result.total_found,
             bounded_hit,
+            cancelled: enum_result.cancelled,
             statistics: PathStatistics {
                 avg_length,
                 min_length: if min_length == usize::MAX {
@@ -1400,6 +1735,125 @@ impl CodeGraph {
             },
         })
     }
+
+    /// Find the shortest call path between two symbols
+    ///
+    /// Runs a breadth-first search from `start_symbol_id` and stops as soon as
+    /// `end_symbol_id` is reached, so it never enumerates alternative paths.
+    /// The path is shortest by edge count; ties resolve deterministically.
+    ///
+    /// # Arguments
+    ///
+    /// * `start_symbol_id` - Starting symbol ID or FQN
+    /// * `end_symbol_id` - Ending symbol ID or FQN
+    /// * `max_depth` - Maximum path depth, as for [`Self::enumerate_paths`]
+    ///
+    /// # Returns
+    ///
+    /// A [`PathEnumerationResult`] holding exactly one path, or no paths when
+    /// the end symbol is unreachable within `max_depth`. `bounded_hit` is set
+    /// when no path was found and the depth bound cut the search short, and
+    /// `cancelled` when [`Self::cancel_token`] stopped it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if either symbol cannot be resolved.
+    pub fn shortest_path(
+        &self,
+        start_symbol_id: &str,
+        end_symbol_id: &str,
+        max_depth: usize,
+    ) -> Result<PathEnumerationResult> {
+        self.shortest_path_avoiding(start_symbol_id, end_symbol_id, max_depth, &[])
+    }
+
+    /// Find the shortest call path that never passes through the `avoid` symbols
+    ///
+    /// Like [`Self::shortest_path`], searching the call graph with each
+    /// avoided symbol (ID or FQN) and its incident edges removed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a symbol cannot be resolved, or if the start or
+    /// end symbol is in `avoid`.
+    pub fn shortest_path_avoiding(
+        &self,
+        start_symbol_id: &str,
+        end_symbol_id: &str,
+        max_depth: usize,
+        avoid: &[&str],
+    ) -> Result<PathEnumerationResult> {
+        let start_entity_id = self.resolve_symbol_entity(start_symbol_id)?;
+        let end_entity_id = self.resolve_symbol_entity(end_symbol_id)?;
+        let avoid_nodes =
+            self.resolve_avoided_entities(avoid, start_entity_id, Some(end_entity_id))?;
+        let backend = &*self.calls.backend;
+
+        let search = shortest_path(
+            backend,
+            start_entity_id,
+            end_entity_id,
+            max_depth,
+            &avoid_nodes,
+            Some(&self.cancel),
+        )?;
+
+        let symbols: Vec<SymbolInfo> = search
+            .path
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|entity_id| self.symbol_by_entity_id(entity_id).ok())
+            .collect();
+
+        let length = symbols.len();
+        let unique_symbols = symbols
+            .iter()
+            .map(|s| s.symbol_id.clone().unwrap_or_default())
+            .collect::<HashSet<_>>()
+            .len();
+        let paths = if length > 0 {
+            vec![ExecutionPath { symbols, length }]
+        } else {
+            Vec::new()
+        };
+
+        Ok(PathEnumerationResult {
+            total_enumerated: paths.len(),
+            paths,
+            bounded_hit: search.pruned_by_depth,
+            cancelled: search.cancelled,
+            statistics: PathStatistics {
+                avg_length: length as f64,
+                min_length: length,
+                max_length: length,
+                unique_symbols,
+            },
+        })
+    }
+
+    /// Resolve the symbols a path search must not pass through
+    fn resolve_avoided_entities(
+        &self,
+        avoid: &[&str],
+        start_entity_id: i64,
+        end_entity_id: Option<i64>,
+    ) -> Result<AHashSet<i64>> {
+        let mut nodes = AHashSet::new();
+        for symbol in avoid {
+            let entity_id = self.resolve_symbol_entity(symbol)?;
+            if entity_id == start_entity_id {
+                return Err(anyhow::anyhow!(
+                    "Cannot avoid the start symbol '{}'",
+                    symbol
+                ));
+            }
+            if Some(entity_id) == end_entity_id {
+                return Err(anyhow::anyhow!("Cannot avoid the end symbol '{}'", symbol));
+            }
+            nodes.insert(entity_id);
+        }
+        Ok(nodes)
+    }
 }
 
 #[cfg(test)]
@@ -1604,4 +2058,31 @@ fn unused_function() {
         // We should have some entities in the call graph
         assert!(!entity_ids.is_empty(), "Should have call graph entities");
     }
+
+    #[test]
+    fn test_cancel_token_stops_traversals() {
+        let (graph, main_id, _unused_id) = create_test_graph().unwrap();
+        let result = graph.enumerate_paths(&main_id, None, 10, 100).unwrap();
+        assert!(!result.paths.is_empty());
+        assert!(!result.cancelled);
+
+        graph.cancel_token().store(true, Ordering::SeqCst);
+        assert!(graph.is_cancelled());
+
+        let started = std::time::Instant::now();
+        let result = graph.enumerate_paths(&main_id, None, 10, 100).unwrap();
+        assert!(result.cancelled);
+        assert!(result.paths.is_empty());
+
+        let result = graph.shortest_path(&main_id, "leaf", 10).unwrap();
+        assert!(result.cancelled);
+        assert!(result.paths.is_empty());
+
+        assert!(graph.reachable_symbols(&main_id, None).unwrap().is_empty());
+        assert!(graph
+            .reverse_reachable_symbols("leaf", None)
+            .unwrap()
+            .is_empty());
+        assert!(started.elapsed() < std::time::Duration::from_secs(5));
+    }
 }
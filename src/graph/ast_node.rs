@@ -153,6 +153,33 @@ impl From<AstNode> for AstNodeWithText {
     }
 }
 
+/// Default depth cap for `ast --subtree` when `--max-depth` is not given
+pub const DEFAULT_SUBTREE_DEPTH: usize = 32;
+
+/// AST node with its descendants, as emitted by `ast --subtree`
+///
+/// Children are in source order. Line numbers are 1-based and columns are
+/// 0-based byte offsets within the line; both are `None` when the source
+/// file could not be read.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AstTreeNode {
+    pub kind: String,
+    pub byte_start: usize,
+    pub byte_end: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start_line: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start_col: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end_line: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end_col: Option<usize>,
+    pub children: Vec<AstTreeNode>,
+    /// True when children exist below the depth cap but were omitted
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub truncated: bool,
+}
+
 /// Check if a node kind is a structural node (should be stored)
 ///
 /// Structural nodes are the "interesting" parts of the AST that provide
@@ -4,8 +4,9 @@
 //! Supports file-based queries, position-based queries, and hierarchy traversal.
 
 use anyhow::Result;
+use std::collections::HashMap;
 
-use crate::graph::{AstNode, AstNodeWithText, CodeGraph};
+use crate::graph::{AstNode, AstNodeWithText, AstTreeNode, CodeGraph};
 
 impl CodeGraph {
     /// Get all AST nodes for a specific file
@@ -22,7 +23,7 @@ impl CodeGraph {
     pub fn get_ast_nodes_by_file(&self, file_path: &str) -> Result<Vec<AstNodeWithText>> {
         // Find file_id from file_path (normalize to match index_file storage format)
         // Note: file_index lookup doesn't require &mut self since it's cached
-        let normalized_path = crate::graph::files::normalize_path_for_index(file_path);
+        let normalized_path = self.files.index_key(file_path);
         let file_id = self.files.file_index.get(&normalized_path).copied();
 
         match file_id {
@@ -80,6 +81,54 @@ impl CodeGraph {
         Ok(smallest)
     }
 
+    /// Get the AST subtree rooted at the innermost node containing `position`
+    ///
+    /// Only nodes of `file_path` are considered. Descendants deeper than
+    /// `max_depth` levels below the root are omitted and their parent is
+    /// marked `truncated`. When `source` is given, line/column positions are
+    /// computed from it.
+    pub fn get_ast_subtree(
+        &self,
+        file_path: &str,
+        position: usize,
+        max_depth: usize,
+        source: Option<&[u8]>,
+    ) -> Result<Option<AstTreeNode>> {
+        let nodes: Vec<AstNode> = self
+            .get_ast_nodes_by_file(file_path)?
+            .into_iter()
+            .map(|n| n.node)
+            .collect();
+
+        // Smallest span wins; on equal spans the later (inner) node wins
+        let root = match nodes
+            .iter()
+            .filter(|n| n.contains(position))
+            .min_by_key(|n| (n.len(), std::cmp::Reverse(n.id)))
+        {
+            Some(root) => root,
+            None => return Ok(None),
+        };
+
+        let mut children: HashMap<i64, Vec<&AstNode>> = HashMap::new();
+        for node in &nodes {
+            if let Some(parent_id) = node.parent_id {
+                children.entry(parent_id).or_default().push(node);
+            }
+        }
+        for siblings in children.values_mut() {
+            siblings.sort_by_key(|n| (n.byte_start, n.byte_end, n.id));
+        }
+
+        let lines = source.map(line_starts);
+        Ok(Some(build_subtree(
+            root,
+            &children,
+            lines.as_deref(),
+            max_depth,
+        )))
+    }
+
     /// Get all AST nodes of a specific kind
     ///
     /// # Arguments
@@ -120,6 +169,58 @@ impl CodeGraph {
     }
 }
 
+/// Byte offsets at which each line of `source` starts
+fn line_starts(source: &[u8]) -> Vec<usize> {
+    std::iter::once(0)
+        .chain(
+            source
+                .iter()
+                .enumerate()
+                .filter(|(_, &b)| b == b'\n')
+                .map(|(i, _)| i + 1),
+        )
+        .collect()
+}
+
+/// 1-based line and 0-based column of `offset`
+fn line_col(line_starts: &[usize], offset: usize) -> (usize, usize) {
+    let line = line_starts.partition_point(|&start| start <= offset).max(1);
+    (line, offset - line_starts[line - 1])
+}
+
+fn build_subtree(
+    node: &AstNode,
+    children: &HashMap<i64, Vec<&AstNode>>,
+    line_starts: Option<&[usize]>,
+    depth_left: usize,
+) -> AstTreeNode {
+    let start = line_starts.map(|ls| line_col(ls, node.byte_start));
+    let end = line_starts.map(|ls| line_col(ls, node.byte_end));
+    let kids = node
+        .id
+        .and_then(|id| children.get(&id))
+        .map(Vec::as_slice)
+        .unwrap_or_default();
+
+    AstTreeNode {
+        kind: node.kind.clone(),
+        byte_start: node.byte_start,
+        byte_end: node.byte_end,
+        start_line: start.map(|(line, _)| line),
+        start_col: start.map(|(_, col)| col),
+        end_line: end.map(|(line, _)| line),
+        end_col: end.map(|(_, col)| col),
+        children: if depth_left == 0 {
+            Vec::new()
+        } else {
+            kids.iter()
+                .map(|child| build_subtree(child, children, line_starts, depth_left - 1))
+                .collect()
+        },
+        truncated: depth_left == 0 && !kids.is_empty(),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,120 @@
+//! Tree-sitter query runner for `ast --query`
+//!
+//! Re-parses a file with the grammar its extension maps to and runs an
+//! arbitrary tree-sitter query (S-expression) against the tree. This exposes
+//! the full grammar for custom extraction without adding an extractor.
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use tree_sitter::{Query, QueryCursor, StreamingIterator};
+
+use crate::ingest::detect::detect_language;
+use crate::ingest::pool::with_parser;
+
+/// One node captured by a tree-sitter query
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AstCapture {
+    /// Capture name without the `@`
+    pub capture: String,
+    /// Index of the query pattern that matched
+    pub pattern: usize,
+    /// Grammar node kind (e.g. `identifier`)
+    pub kind: String,
+    /// Byte offset where the node starts
+    pub byte_start: usize,
+    /// Byte offset where the node ends
+    pub byte_end: usize,
+    /// Line where the node starts (1-indexed)
+    pub start_line: usize,
+    /// Column where the node starts (0-indexed, bytes)
+    pub start_col: usize,
+    /// Line where the node ends (1-indexed)
+    pub end_line: usize,
+    /// Column where the node ends (0-indexed, bytes)
+    pub end_col: usize,
+    /// Source text of the node
+    pub text: String,
+}
+
+/// Run a tree-sitter query against `source`, parsed with the grammar for `path`.
+///
+/// Captures are returned in document order, as the query cursor yields them.
+/// A query that does not compile against the grammar is an error naming the
+/// row and column of the problem.
+pub fn run_ast_query(path: &Path, source: &[u8], query: &str) -> Result<Vec<AstCapture>> {
+    let language = detect_language(path)
+        .ok_or_else(|| anyhow::anyhow!("No tree-sitter grammar for '{}'", path.display()))?;
+    let tree = with_parser(language, |parser| parser.parse(source, None))?
+        .ok_or_else(|| anyhow::anyhow!("Failed to parse '{}'", path.display()))?;
+
+    let query = Query::new(&tree.language(), query)
+        .map_err(|e| anyhow::anyhow!("Invalid tree-sitter query: {}", e))?;
+    let names = query.capture_names();
+
+    let mut cursor = QueryCursor::new();
+    let mut captures = cursor.captures(&query, tree.root_node(), source);
+    let mut results = Vec::new();
+    while let Some((query_match, index)) = captures.next() {
+        let capture = query_match.captures[*index];
+        let node = capture.node;
+        results.push(AstCapture {
+            capture: names[capture.index as usize].to_string(),
+            pattern: query_match.pattern_index,
+            kind: node.kind().to_string(),
+            byte_start: node.start_byte(),
+            byte_end: node.end_byte(),
+            start_line: node.start_position().row + 1,
+            start_col: node.start_position().column,
+            end_line: node.end_position().row + 1,
+            end_col: node.end_position().column,
+            text: String::from_utf8_lossy(&source[node.byte_range()]).into_owned(),
+        });
+    }
+    Ok(results)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_function_name_captures() {
+        let source = b"fn alpha() {}\n\nmod inner {\n    fn beta(x: i32) -> i32 { x }\n}\n";
+        let captures = run_ast_query(
+            Path::new("lib.rs"),
+            source,
+            "(function_item name: (identifier) @n)",
+        )
+        .unwrap();
+
+        let names: Vec<&str> = captures.iter().map(|c| c.text.as_str()).collect();
+        assert_eq!(names, ["alpha", "beta"]);
+        assert!(captures.iter().all(|c| c.capture == "n"));
+        assert_eq!(captures[1].kind, "identifier");
+        assert_eq!((captures[1].start_line, captures[1].start_col), (4, 7));
+        assert_eq!(
+            &source[captures[1].byte_start..captures[1].byte_end],
+            b"beta"
+        );
+    }
+
+    #[test]
+    fn test_invalid_query_is_a_compile_error() {
+        let err = run_ast_query(Path::new("lib.rs"), b"fn a() {}", "(function_item @n")
+            .unwrap_err()
+            .to_string();
+        assert!(err.starts_with("Invalid tree-sitter query"), "{}", err);
+
+        let err = run_ast_query(Path::new("lib.rs"), b"fn a() {}", "(no_such_node) @n")
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("Invalid node type"), "{}", err);
+    }
+
+    #[test]
+    fn test_unknown_extension_has_no_grammar() {
+        let err = run_ast_query(Path::new("notes.txt"), b"text", "(_) @n").unwrap_err();
+        assert!(err.to_string().contains("No tree-sitter grammar"));
+    }
+}
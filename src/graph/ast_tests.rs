@@ -550,3 +550,55 @@ fn test_break_continue_captured() {
         "Should capture continue_expression"
     );
 }
+
+/// Test subtree extraction: source-ordered children, line/col, depth cap
+#[test]
+fn test_get_ast_subtree() {
+    let dir = tempdir().unwrap();
+    let db_path = dir.path().join("test.db");
+
+    let mut graph = CodeGraph::open(&db_path).unwrap();
+
+    let source = "fn main() {\n    if a {\n        loop { break; }\n    }\n    return;\n}\n";
+    graph.index_file("subtree.rs", source.as_bytes()).unwrap();
+
+    let tree = graph
+        .get_ast_subtree("subtree.rs", 0, 32, Some(source.as_bytes()))
+        .unwrap()
+        .expect("function node at offset 0");
+    assert_eq!(tree.kind, "function_item");
+    assert_eq!((tree.start_line, tree.start_col), (Some(1), Some(0)));
+    assert!(!tree.truncated);
+
+    // Children are in source order at every level
+    fn assert_ordered(node: &crate::graph::AstTreeNode) {
+        for pair in node.children.windows(2) {
+            assert!(pair[0].byte_start <= pair[1].byte_start);
+        }
+        node.children.iter().for_each(assert_ordered);
+    }
+    assert_ordered(&tree);
+
+    let if_pos = source.find("if a").unwrap();
+    let if_tree = graph
+        .get_ast_subtree("subtree.rs", if_pos, 32, Some(source.as_bytes()))
+        .unwrap()
+        .unwrap();
+    assert_eq!(if_tree.kind, "if_expression");
+    assert_eq!((if_tree.start_line, if_tree.start_col), (Some(2), Some(4)));
+
+    // Depth 0 keeps only the root and flags the cut
+    let capped = graph
+        .get_ast_subtree("subtree.rs", 0, 0, None)
+        .unwrap()
+        .unwrap();
+    assert!(capped.children.is_empty());
+    assert!(capped.truncated);
+    assert_eq!(capped.start_line, None);
+
+    // Positions in other files never match
+    assert!(graph
+        .get_ast_subtree("other.rs", 0, 32, None)
+        .unwrap()
+        .is_none());
+}
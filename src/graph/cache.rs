@@ -14,10 +14,13 @@
 //!
 //! - `FileNodeCache` (single-threaded): accessed through `CodeGraph` with `&mut self`
 //! - Navigator caches (thread-safe): accessed through `SymbolNavigator` with `&self`
+//! - `WarmSymbolIndex` (optional, unbounded): built by `CodeGraph::warm_symbol_cache`
 
 use std::collections::{HashMap, VecDeque};
 use std::hash::Hash;
 
+use crate::graph::schema::SymbolNode;
+
 /// Cache statistics for monitoring effectiveness
 #[derive(Debug, Clone, Default)]
 pub struct CacheStats {
@@ -103,6 +106,11 @@ impl<K: Hash + Eq + Clone, V> LruCache<K, V> {
         self.map.insert(key, value);
     }
 
+    /// Check whether a key is cached without touching LRU order or stats
+    pub fn contains(&self, key: &K) -> bool {
+        self.map.contains_key(key)
+    }
+
     /// Invalidate a specific cache entry
     ///
     /// Removes the key and its value from the cache if present.
@@ -228,6 +236,91 @@ pub struct NameCacheKey(pub String);
 #[derive(Debug, Clone, Hash, Eq, PartialEq)]
 pub struct ExpandCacheKey(pub i64);
 
+/// A symbol held by [`WarmSymbolIndex`]
+#[derive(Debug, Clone)]
+pub struct CachedSymbol {
+    /// Graph entity ID of the symbol node
+    pub node_id: i64,
+    /// Defining file, as keyed in the file index
+    pub file_path: String,
+    pub node: SymbolNode,
+}
+
+/// In-memory index of every symbol by file, name, FQN and SymbolId
+///
+/// Unlike the LRU caches this holds the whole symbol table, so it is only
+/// built on request (see `CodeGraph::warm_symbol_cache`) and dropped on
+/// every `index_file`/`delete_file`.
+#[derive(Debug, Default)]
+pub struct WarmSymbolIndex {
+    symbols: Vec<CachedSymbol>,
+    by_file: HashMap<String, Vec<usize>>,
+    by_name: HashMap<String, Vec<usize>>,
+    by_fqn: HashMap<String, Vec<usize>>,
+    by_symbol_id: HashMap<String, usize>,
+}
+
+impl WarmSymbolIndex {
+    /// Add a symbol to every lookup table
+    pub fn push(&mut self, symbol: CachedSymbol) {
+        let slot = self.symbols.len();
+        self.by_file
+            .entry(symbol.file_path.clone())
+            .or_default()
+            .push(slot);
+        if let Some(name) = &symbol.node.name {
+            self.by_name.entry(name.clone()).or_default().push(slot);
+        }
+        if let Some(fqn) = &symbol.node.fqn {
+            self.by_fqn.entry(fqn.clone()).or_default().push(slot);
+        }
+        if let Some(symbol_id) = &symbol.node.symbol_id {
+            self.by_symbol_id.entry(symbol_id.clone()).or_insert(slot);
+        }
+        self.symbols.push(symbol);
+    }
+
+    pub fn len(&self) -> usize {
+        self.symbols.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.symbols.is_empty()
+    }
+
+    /// Symbols defined in `file_key` (a file index key)
+    pub fn in_file<'a>(&'a self, file_key: &str) -> impl Iterator<Item = &'a CachedSymbol> {
+        self.slots(self.by_file.get(file_key))
+    }
+
+    /// Symbols whose simple name is exactly `name`
+    pub fn named<'a>(&'a self, name: &str) -> impl Iterator<Item = &'a CachedSymbol> {
+        self.slots(self.by_name.get(name))
+    }
+
+    /// Symbols whose FQN is exactly `fqn`
+    pub fn with_fqn<'a>(&'a self, fqn: &str) -> impl Iterator<Item = &'a CachedSymbol> {
+        self.slots(self.by_fqn.get(fqn))
+    }
+
+    /// The symbol with stable SymbolId `symbol_id`
+    pub fn by_symbol_id(&self, symbol_id: &str) -> Option<&CachedSymbol> {
+        self.by_symbol_id
+            .get(symbol_id)
+            .map(|&slot| &self.symbols[slot])
+    }
+
+    fn slots<'a>(
+        &'a self,
+        slots: Option<&'a Vec<usize>>,
+    ) -> impl Iterator<Item = &'a CachedSymbol> {
+        slots
+            .into_iter()
+            .flatten()
+            .map(move |&slot| &self.symbols[slot])
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
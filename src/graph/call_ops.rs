@@ -6,19 +6,24 @@ use anyhow::Result;
 use sqlitegraph::{
     BackendDirection, EdgeSpec, GraphBackend, NeighborQuery, NodeId, NodeSpec, SnapshotId,
 };
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::path::PathBuf;
 use std::sync::Arc;
 
-use crate::graph::schema::CallNode;
+use crate::graph::module_resolver::ModuleResolver;
+use crate::graph::schema::{CallNode, ModulePathCache};
+use crate::graph::symbol_lookup::SymbolLookup;
 use crate::ingest::c::CParser;
 use crate::ingest::cpp::CppParser;
+use crate::ingest::csharp::CSharpParser;
 use crate::ingest::cuda::CudaParser;
 use crate::ingest::go::GoParser;
 use crate::ingest::java::JavaParser;
 use crate::ingest::javascript::JavaScriptParser;
 use crate::ingest::pool;
 use crate::ingest::python::PythonParser;
+use crate::ingest::scala::ScalaParser;
+use crate::ingest::swift::SwiftParser;
 use crate::ingest::typescript::TypeScriptParser;
 use crate::ingest::{detect::Language, detect_language, Parser, SymbolFact, SymbolKind};
 use crate::references::CallFact;
@@ -31,6 +36,21 @@ pub struct CallOps {
     /// Whether to use batch transactions for indexing.
     /// When false, falls back to individual per-insert auto-commit mode.
     pub batch_mode: bool,
+    /// Module-qualified Rust calls of each file and the symbol each resolved to.
+    /// `relink_qualified_calls` re-indexes a file's calls once one of
+    /// them resolves differently, so CALLS edges do not depend on indexing order.
+    pub(crate) qualified_calls: parking_lot::Mutex<BTreeMap<String, Vec<QualifiedCall>>>,
+}
+
+/// A module-qualified call such as `crate::util::helper()`
+#[derive(Debug, Clone)]
+pub(crate) struct QualifiedCall {
+    /// Simple name of the callee (`helper`)
+    name: String,
+    /// Candidate module paths the qualifier names (`crate::util`)
+    modules: Vec<String>,
+    /// Function the callee resolved to when the file's calls were indexed
+    target: Option<i64>,
 }
 
 impl CallOps {
@@ -67,6 +87,7 @@ impl CallOps {
         for id in &to_delete {
             self.backend.delete_entity(*id)?;
         }
+        self.qualified_calls.lock().remove(path);
 
         Ok(to_delete.len())
     }
@@ -83,11 +104,13 @@ impl CallOps {
     /// * `path` - File path
     /// * `source` - File contents as bytes
     /// * `symbol_ids` - Map of symbol names to their node IDs (ALL symbols in database)
+    /// * `lookup` - Symbol index used to resolve module-qualified callees across files
     pub fn index_calls(
         &self,
         path: &str,
         source: &[u8],
         symbol_ids: &HashMap<String, i64>,
+        lookup: &SymbolLookup,
     ) -> Result<usize> {
         let path_buf = PathBuf::from(path);
         let language = detect_language(&path_buf);
@@ -144,15 +167,19 @@ impl CallOps {
         symbol_facts.extend(current_file_facts);
 
         // Extract calls using parser pool (was creating fresh parsers per file)
-        let mut calls = match language {
+        let calls = match language {
             Some(Language::Rust) => pool::with_parser_opt(Language::Rust, |opt_parser| {
-                let parser = opt_parser
+                let mut parser = opt_parser
                     .take()
                     .expect("Parser pool corruption: parser was None"); // M-UNWRAP: thread-local parser pool guarantees initialized parser
-                let mut wrapper = Parser::from_parser(parser);
-                let result = wrapper.extract_calls(path_buf.clone(), source, &symbol_facts);
-                *opt_parser = Some(wrapper.parser);
-                result
+                let result = parser.parse(source, None).map(|tree| {
+                    let calls =
+                        Parser::extract_calls_from_tree(&tree, path_buf.clone(), source, &symbol_facts);
+                    self.record_qualified_calls(path, &tree, source, lookup);
+                    calls
+                });
+                *opt_parser = Some(parser);
+                result.unwrap_or_default()
             })?,
             Some(Language::Python) => pool::with_parser_opt(Language::Python, |opt_parser| {
                 let parser = opt_parser
@@ -234,74 +261,53 @@ impl CallOps {
                     result
                 })?
             }
-            None => Vec::new(),
-        };
-
-        let call_count = calls.len();
-
-        // Build a name-only fallback map for cross-file call resolution.
-        //
-        // After Phase 11 (FQN changes), symbol_ids uses FQNs as keys (e.g., "crate::module::function"),
-        // but CallFact uses simple names (e.g., "function"). This enables fallback to simple name
-        // matching for cross-file calls where the FQN might not match exactly.
-        //
-        // For example:
-        // - CallFact.callee might be "render" (simple name from widget.render())
-        // - Symbol might be stored as "Widget::render" (FQN)
-        // - This fallback enables matching "render" to "Widget::render"
-        let mut name_to_ids: HashMap<String, Vec<i64>> = HashMap::new();
-        for (fqn, &id) in symbol_ids.iter() {
-            // Extract simple name from FQN (after last :: or .)
-            let simple_name = fqn.split("::").last().unwrap_or(fqn.as_str());
-            let simple_name = simple_name.split('.').next_back().unwrap_or(simple_name);
-            name_to_ids
-                .entry(simple_name.to_string())
-                .or_default()
-                .push(id);
-        }
-
-        // Batch insert call nodes and edges for performance.
-        // First, update stable symbol_ids on all calls.
-        for call in &mut calls {
-            let caller_key = (
-                call.file_path.to_string_lossy().to_string(),
-                call.caller.clone(),
-            );
-            let callee_key = (
-                call.file_path.to_string_lossy().to_string(),
-                call.callee.clone(),
-            );
-            call.caller_symbol_id = stable_symbol_ids.get(&caller_key).and_then(|id| id.clone());
-            call.callee_symbol_id = stable_symbol_ids.get(&callee_key).and_then(|id| id.clone());
-        }
-
-        // Batch insert all call nodes
-        let call_refs: Vec<&CallFact> = calls.iter().collect();
-        let call_node_ids = self.insert_call_nodes_batch(&call_refs)?;
-
-        // Build edge lists
-        let mut caller_edges: Vec<(NodeId, NodeId)> = Vec::new();
-        let mut calls_edges: Vec<(NodeId, NodeId)> = Vec::new();
-        for (i, call) in calls.iter().enumerate() {
-            let call_id = call_node_ids[i];
-
-            let callee_symbol_id = symbol_ids
-                .get(&call.callee)
-                .or_else(|| name_to_ids.get(&call.callee).and_then(|ids| ids.first()));
-            let caller_symbol_id = symbol_ids
-                .get(&call.caller)
-                .or_else(|| name_to_ids.get(&call.caller).and_then(|ids| ids.first()));
-
-            if let Some(&caller_id) = caller_symbol_id {
-                caller_edges.push((NodeId::from(caller_id), call_id));
+            Some(Language::Swift) => {
+                pool::with_parser_opt(Language::Swift, |opt_parser| {
+                    let parser = opt_parser
+                        .take()
+                        .expect("Parser pool corruption: parser was None"); // M-UNWRAP: thread-local parser pool guarantees initialized parser
+                    let mut wrapper = SwiftParser::from_parser(parser);
+                    let result = wrapper.extract_calls(path_buf.clone(), source, &symbol_facts);
+                    *opt_parser = Some(wrapper.parser);
+                    result
+                })?
             }
-            if let Some(&callee_id) = callee_symbol_id {
-                calls_edges.push((call_id, NodeId::from(callee_id)));
+            Some(Language::Scala) => {
+                pool::with_parser_opt(Language::Scala, |opt_parser| {
+                    let parser = opt_parser
+                        .take()
+                        .expect("Parser pool corruption: parser was None"); // M-UNWRAP: thread-local parser pool guarantees initialized parser
+                    let mut wrapper = ScalaParser::from_parser(parser);
+                    let result = wrapper.extract_calls(path_buf.clone(), source, &symbol_facts);
+                    *opt_parser = Some(wrapper.parser);
+                    result
+                })?
             }
-        }
+            Some(Language::CSharp) => {
+                pool::with_parser_opt(Language::CSharp, |opt_parser| {
+                    let parser = opt_parser
+                        .take()
+                        .expect("Parser pool corruption: parser was None"); // M-UNWRAP: thread-local parser pool guarantees initialized parser
+                    let mut wrapper = CSharpParser::from_parser(parser);
+                    let result = wrapper.extract_calls(path_buf.clone(), source, &symbol_facts);
+                    *opt_parser = Some(wrapper.parser);
+                    result
+                })?
+            }
+            // Registered custom parsers only extract symbols
+            Some(Language::Custom(_)) | None => Vec::new(),
+        };
 
-        self.insert_caller_edges_batch(&caller_edges)?;
-        self.insert_calls_edges_batch(&calls_edges)?;
+        let call_count = calls.len();
+        self.link_calls(
+            path,
+            source,
+            language,
+            calls,
+            symbol_ids,
+            &stable_symbol_ids,
+            lookup,
+        )?;
 
         Ok(call_count)
     }
@@ -312,6 +318,7 @@ impl CallOps {
         path: &str,
         source: &[u8],
         symbol_ids: &HashMap<String, i64>,
+        lookup: &SymbolLookup,
         tree: &tree_sitter::Tree,
         language: Language,
     ) -> Result<usize> {
@@ -358,7 +365,7 @@ impl CallOps {
 
         symbol_facts.extend(current_file_facts);
 
-        let mut calls = match language {
+        let calls = match language {
             Language::Rust => crate::ingest::Parser::extract_calls_from_tree(
                 tree,
                 path_buf,
@@ -387,12 +394,66 @@ impl CallOps {
             Language::Cuda => {
                 CudaParser::extract_calls_from_tree(tree, path_buf, source, &symbol_facts)
             }
+            Language::Swift => {
+                SwiftParser::extract_calls_from_tree(tree, path_buf, source, &symbol_facts)
+            }
+            Language::Scala => {
+                ScalaParser::extract_calls_from_tree(tree, path_buf, source, &symbol_facts)
+            }
+            Language::CSharp => {
+                CSharpParser::extract_calls_from_tree(tree, path_buf, source, &symbol_facts)
+            }
+            Language::Custom(_) => Vec::new(),
         };
 
+        if language == Language::Rust {
+            self.record_qualified_calls(path, tree, source, lookup);
+        }
+
         let call_count = calls.len();
+        self.link_calls(
+            path,
+            source,
+            Some(language),
+            calls,
+            symbol_ids,
+            &stable_symbol_ids,
+            lookup,
+        )?;
+
+        Ok(call_count)
+    }
 
+    /// Insert Call nodes for extracted calls and link them with CALLER/CALLS edges.
+    ///
+    /// Shared tail of `index_calls` and `index_calls_with_tree`.
+    #[allow(
+        clippy::too_many_arguments,
+        reason = "shared tail of two indexers; each arg is state they already hold"
+    )]
+    fn link_calls(
+        &self,
+        path: &str,
+        source: &[u8],
+        language: Option<Language>,
+        mut calls: Vec<CallFact>,
+        symbol_ids: &HashMap<String, i64>,
+        stable_symbol_ids: &HashMap<(String, String), Option<String>>,
+        lookup: &SymbolLookup,
+    ) -> Result<()> {
+        // Build a name-only fallback map for cross-file call resolution.
+        //
+        // After Phase 11 (FQN changes), symbol_ids uses FQNs as keys (e.g., "crate::module::function"),
+        // but CallFact uses simple names (e.g., "function"). This enables fallback to simple name
+        // matching for cross-file calls where the FQN might not match exactly.
+        //
+        // For example:
+        // - CallFact.callee might be "render" (simple name from widget.render())
+        // - Symbol might be stored as "Widget::render" (FQN)
+        // - This fallback enables matching "render" to "Widget::render"
         let mut name_to_ids: HashMap<String, Vec<i64>> = HashMap::new();
         for (fqn, &id) in symbol_ids.iter() {
+            // Extract simple name from FQN (after last :: or .)
             let simple_name = fqn.split("::").last().unwrap_or(fqn.as_str());
             let simple_name = simple_name.split('.').next_back().unwrap_or(simple_name);
             name_to_ids
@@ -401,7 +462,8 @@ impl CallOps {
                 .push(id);
         }
 
-        // Batch insert call nodes and edges for performance.
+        // Resolve callees and update stable symbol_ids on all calls.
+        let mut callee_ids: Vec<Option<i64>> = Vec::with_capacity(calls.len());
         for call in &mut calls {
             let caller_key = (
                 call.file_path.to_string_lossy().to_string(),
@@ -413,19 +475,41 @@ impl CallOps {
             );
             call.caller_symbol_id = stable_symbol_ids.get(&caller_key).and_then(|id| id.clone());
             call.callee_symbol_id = stable_symbol_ids.get(&callee_key).and_then(|id| id.clone());
+
+            // A module-qualified path (`crate::util::helper()`) names the defining
+            // file, so prefer that over the first symbol sharing the callee's name.
+            let qualified = if language == Some(Language::Rust) {
+                self.resolve_qualified_callee(path, source, call, lookup)
+            } else {
+                None
+            };
+            let callee_id = match qualified {
+                Some((id, stable_id)) => {
+                    call.callee_symbol_id = stable_id;
+                    Some(id)
+                }
+                None => symbol_ids
+                    .get(&call.callee)
+                    .or_else(|| name_to_ids.get(&call.callee).and_then(|ids| ids.first()))
+                    .copied(),
+            };
+            if call.callee_symbol_id.is_none() {
+                call.callee_symbol_id =
+                    callee_id.and_then(|id| lookup.entity_to_symbol_id().get(&id).cloned());
+            }
+            callee_ids.push(callee_id);
         }
 
+        // Batch insert all call nodes
         let call_refs: Vec<&CallFact> = calls.iter().collect();
         let call_node_ids = self.insert_call_nodes_batch(&call_refs)?;
 
+        // Build edge lists
         let mut caller_edges: Vec<(NodeId, NodeId)> = Vec::new();
         let mut calls_edges: Vec<(NodeId, NodeId)> = Vec::new();
         for (i, call) in calls.iter().enumerate() {
             let call_id = call_node_ids[i];
 
-            let callee_symbol_id = symbol_ids
-                .get(&call.callee)
-                .or_else(|| name_to_ids.get(&call.callee).and_then(|ids| ids.first()));
             let caller_symbol_id = symbol_ids
                 .get(&call.caller)
                 .or_else(|| name_to_ids.get(&call.caller).and_then(|ids| ids.first()));
@@ -433,7 +517,7 @@ impl CallOps {
             if let Some(&caller_id) = caller_symbol_id {
                 caller_edges.push((NodeId::from(caller_id), call_id));
             }
-            if let Some(&callee_id) = callee_symbol_id {
+            if let Some(callee_id) = callee_ids[i] {
                 calls_edges.push((call_id, NodeId::from(callee_id)));
             }
         }
@@ -441,7 +525,121 @@ impl CallOps {
         self.insert_caller_edges_batch(&caller_edges)?;
         self.insert_calls_edges_batch(&calls_edges)?;
 
-        Ok(call_count)
+        Ok(())
+    }
+
+    /// Resolve a Rust module-qualified call to the symbol in the named module.
+    ///
+    /// Reads the call expression from `source` (e.g. `crate::util::helper(`),
+    /// maps its qualifier to candidate module paths and returns the entity id
+    /// and stable symbol_id of the same-named symbol defined in that module.
+    /// Returns None for unqualified calls or when no module matches.
+    fn resolve_qualified_callee(
+        &self,
+        path: &str,
+        source: &[u8],
+        call: &CallFact,
+        lookup: &SymbolLookup,
+    ) -> Option<(i64, Option<String>)> {
+        let text = source
+            .get(call.byte_start..call.byte_end)
+            .and_then(|bytes| std::str::from_utf8(bytes).ok())?;
+        let (name, qualifier) = split_qualified_callee(text)?;
+        if name != call.callee {
+            return None;
+        }
+        let modules = ModuleResolver::qualifier_module_paths(path, &qualifier);
+        self.resolve_in_modules(name, &modules, lookup)
+    }
+
+    /// Find the symbol named `name` defined in the first of `modules` that has one
+    fn resolve_in_modules(
+        &self,
+        name: &str,
+        modules: &[String],
+        lookup: &SymbolLookup,
+    ) -> Option<(i64, Option<String>)> {
+        let candidates = lookup.get_ids_by_name(name);
+        for module in modules {
+            for &id in candidates {
+                let node = match self.backend.get_node(SnapshotId::current(), id) {
+                    Ok(node) => node,
+                    Err(_) => continue,
+                };
+                if node.kind != "Symbol" {
+                    continue;
+                }
+                let Some(file_path) = node.file_path.as_deref() else {
+                    continue;
+                };
+                if ModulePathCache::file_path_to_module_path(file_path) == *module {
+                    let stable_id = node
+                        .data
+                        .get("symbol_id")
+                        .and_then(|v| v.as_str())
+                        .map(str::to_string);
+                    return Some((id, stable_id));
+                }
+            }
+        }
+        None
+    }
+
+    /// Remember the qualified calls in `tree` and the symbols they resolve to.
+    ///
+    /// Call extraction drops a call whose callee is not indexed yet, and a
+    /// CALLS edge goes away when its callee's file is reindexed. Keeping the
+    /// function each call resolves to now lets a later pass spot both cases.
+    fn record_qualified_calls(
+        &self,
+        path: &str,
+        tree: &tree_sitter::Tree,
+        source: &[u8],
+        lookup: &SymbolLookup,
+    ) {
+        let mut qualified = Vec::new();
+        let mut stack = vec![tree.root_node()];
+        while let Some(node) = stack.pop() {
+            if node.kind() == "call_expression" {
+                let callee = node
+                    .child_by_field_name("function")
+                    .filter(|function| function.kind() == "scoped_identifier")
+                    .and_then(|function| function.utf8_text(source).ok())
+                    .and_then(split_qualified_callee);
+                if let Some((name, qualifier)) = callee {
+                    let modules = ModuleResolver::qualifier_module_paths(path, &qualifier);
+                    let target = lookup.resolve_function_in_modules(name, &modules);
+                    qualified.push(QualifiedCall {
+                        name: name.to_string(),
+                        modules,
+                        target,
+                    });
+                }
+            }
+            let mut cursor = node.walk();
+            stack.extend(node.children(&mut cursor));
+        }
+
+        let mut qualified_calls = self.qualified_calls.lock();
+        if qualified.is_empty() {
+            qualified_calls.remove(path);
+        } else {
+            qualified_calls.insert(path.to_string(), qualified);
+        }
+    }
+
+    /// Files with a qualified call whose callee now resolves to a different symbol
+    pub(crate) fn stale_qualified_call_files(&self, lookup: &SymbolLookup) -> Vec<String> {
+        self.qualified_calls
+            .lock()
+            .iter()
+            .filter(|(_, qualified)| {
+                qualified
+                    .iter()
+                    .any(|call| lookup.resolve_function_in_modules(&call.name, &call.modules) != call.target)
+            })
+            .map(|(path, _)| path.clone())
+            .collect()
     }
 
     /// Index calls derived from LLVM IR (no source position data).
@@ -834,6 +1032,7 @@ impl CallOps {
             "Union" => SymbolKind::Union,
             "Namespace" => SymbolKind::Namespace,
             "TypeAlias" => SymbolKind::TypeAlias,
+            "Macro" => SymbolKind::Macro,
             "Unknown" => SymbolKind::Unknown,
             _ => SymbolKind::Unknown,
         };
@@ -857,6 +1056,31 @@ impl CallOps {
             start_col: symbol_node.start_col,
             end_line: symbol_node.end_line,
             end_col: symbol_node.end_col,
+            signature: symbol_node.signature.clone(),
+            is_test: symbol_node.is_test,
+            visibility: symbol_node.visibility.clone(),
+            deprecated: symbol_node.deprecated,
+            deprecation_message: symbol_node.deprecation_message.clone(),
         })
     }
 }
+
+/// Split a Rust call path such as `crate::util::helper(` into its callee name
+/// and qualifier segments. Returns None for unqualified or non-path callees.
+fn split_qualified_callee(text: &str) -> Option<(&str, Vec<&str>)> {
+    let callee_path = text.split('(').next()?;
+    // Drop a turbofish: `helper::<T>` -> `helper`
+    let callee_path = callee_path.split("::<").next()?;
+    let mut segments: Vec<&str> = callee_path.split("::").map(str::trim).collect();
+    if !segments
+        .iter()
+        .all(|s| !s.is_empty() && s.chars().all(|c| c.is_alphanumeric() || c == '_'))
+    {
+        return None;
+    }
+    let name = segments.pop()?;
+    if segments.is_empty() {
+        return None;
+    }
+    Some((name, segments))
+}
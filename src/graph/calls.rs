@@ -3,10 +3,12 @@
 //! Handles call indexing and query operations for CALLS edges.
 
 use anyhow::Result;
+use sqlitegraph::{GraphBackend, SnapshotId};
 use std::collections::HashMap;
 
 use crate::references::CallFact;
 
+use super::schema::FileNode;
 use super::CodeGraph;
 
 /// Index calls for a file into the graph
@@ -32,7 +34,9 @@ pub fn index_calls(graph: &mut CodeGraph, path: &str, source: &[u8]) -> Result<u
         .collect();
 
     // Index calls using CallOps
-    graph.calls.index_calls(path, source, &symbol_fqn_to_id)
+    graph
+        .calls
+        .index_calls(path, source, &symbol_fqn_to_id, &graph.symbols.lookup)
 }
 
 /// Index calls using a pre-parsed tree (eliminates redundant parsing).
@@ -50,9 +54,54 @@ pub fn index_calls_with_tree(
         .map(|(fqn, (id, _))| (fqn, id))
         .collect();
 
-    graph
-        .calls
-        .index_calls_with_tree(path, source, &symbol_fqn_to_id, tree, language)
+    graph.calls.index_calls_with_tree(
+        path,
+        source,
+        &symbol_fqn_to_id,
+        &graph.symbols.lookup,
+        tree,
+        language,
+    )
+}
+
+/// Re-index the calls of files whose module-qualified calls now resolve differently
+///
+/// A qualified call such as `crate::util::helper()` is dropped when its file
+/// is indexed before `util.rs`, and loses its CALLS edge when `util.rs` is
+/// reindexed. Scans and batches call this once they finish, so the edge
+/// exists regardless of indexing order. Sources are read through the
+/// database root; files changed on disk since they were indexed are left for
+/// their own reindex.
+///
+/// # Returns
+/// Number of files whose calls were re-indexed
+pub fn relink_qualified_calls(graph: &mut CodeGraph) -> Result<usize> {
+    let paths = graph.calls.stale_qualified_call_files(&graph.symbols.lookup);
+    let mut relinked = 0;
+    for path in paths {
+        let Ok(source) = std::fs::read(graph.disk_path(&path)) else {
+            continue;
+        };
+        let stored_hash = match graph.files.find_file_node(&path)? {
+            Some(file_id) => graph
+                .files
+                .backend
+                .get_node(SnapshotId::current(), file_id.as_i64())
+                .ok()
+                .and_then(|node| serde_json::from_value::<FileNode>(node.data).ok()),
+            None => None,
+        };
+        let Some(file_node) = stored_hash else {
+            continue;
+        };
+        if file_node.hash != graph.files.compute_hash_with(&source, file_node.hash_algo) {
+            continue;
+        }
+        graph.calls.delete_calls_in_file(&path)?;
+        index_calls(graph, &path, &source)?;
+        relinked += 1;
+    }
+    Ok(relinked)
 }
 
 /// Index calls derived from LLVM IR for a C/C++ file.
@@ -243,12 +243,92 @@ impl FqnBuilder {
             SymbolKind::Union => "Union",
             SymbolKind::Namespace => "Namespace",
             SymbolKind::TypeAlias => "TypeAlias",
+            SymbolKind::Macro => "Macro",
             SymbolKind::Unknown => "Unknown",
         }
         .to_string()
     }
 }
 
+/// Strip generic arguments and lifetimes from an FQN
+///
+/// Used to group generic instantiations together (`collisions
+/// --canonicalize-generics`). Rules, applied left to right:
+///
+/// - Each balanced `<...>` group is removed, nested groups included, so
+///   lifetimes and bounds inside it (`Foo<'a, T: Clone>`) go with it
+/// - A `::` directly before the group (turbofish) is removed too:
+///   `foo::<u32>` becomes `foo`
+/// - A `>` preceded by `-` (`Fn() -> T`) does not close a group
+/// - A `<` with no matching `>` is kept verbatim along with the rest of the
+///   FQN, so operator names such as `operator<` survive
+///
+/// ```rust
+/// use magellan::graph::canonical_fqn::canonicalize_generics;
+///
+/// assert_eq!(canonicalize_generics("Wrapper<u32>::get"), "Wrapper::get");
+/// assert_eq!(canonicalize_generics("foo::<String>"), "foo");
+/// ```
+pub fn canonicalize_generics(fqn: &str) -> String {
+    let mut canonical = String::with_capacity(fqn.len());
+    let mut rest = fqn;
+    while let Some(open) = rest.find('<') {
+        let Some(close) = matching_angle_bracket(&rest[open..]) else {
+            break;
+        };
+        let before = &rest[..open];
+        canonical.push_str(before.strip_suffix("::").unwrap_or(before));
+        rest = &rest[open + close + 1..];
+    }
+    canonical.push_str(rest);
+    canonical
+}
+
+/// Check whether `fqn` lies under the module path `prefix`
+///
+/// Matching is segment-aware: the prefix must be the whole FQN or be followed
+/// by a `::` or `.` separator, so `net` matches `net::connect` but not
+/// `network::ping`. A trailing separator on the prefix (`crate::net::`) is
+/// ignored, and an empty prefix matches everything. Backs `find --defined-in`
+/// and `query --defined-in`.
+///
+/// ```rust
+/// use magellan::graph::canonical_fqn::fqn_has_prefix;
+///
+/// assert!(fqn_has_prefix("net::tcp::connect", "net::tcp"));
+/// assert!(!fqn_has_prefix("network::ping", "net"));
+/// ```
+pub fn fqn_has_prefix(fqn: &str, prefix: &str) -> bool {
+    let prefix = prefix.trim_end_matches([':', '.']);
+    if prefix.is_empty() {
+        return true;
+    }
+    match fqn.strip_prefix(prefix) {
+        Some(rest) => rest.is_empty() || rest.starts_with("::") || rest.starts_with('.'),
+        None => false,
+    }
+}
+
+/// Byte offset of the `>` closing the `<` that starts `s`
+fn matching_angle_bracket(s: &str) -> Option<usize> {
+    let mut depth = 0usize;
+    let mut prev = '\0';
+    for (offset, c) in s.char_indices() {
+        match c {
+            '<' => depth += 1,
+            '>' if prev != '-' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(offset);
+                }
+            }
+            _ => {}
+        }
+        prev = c;
+    }
+    None
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -460,4 +540,49 @@ mod tests {
             "test_crate::src/test.rs::TypeAlias TA"
         );
     }
+
+    #[test]
+    fn test_canonicalize_generics_strips_arguments_and_lifetimes() {
+        assert_eq!(canonicalize_generics("foo::<u32>"), "foo");
+        assert_eq!(canonicalize_generics("foo::<String>"), "foo");
+        assert_eq!(canonicalize_generics("Wrapper<u32>::get"), "Wrapper::get");
+        assert_eq!(
+            canonicalize_generics("Map<K, Vec<V>>::insert::<'a>"),
+            "Map::insert"
+        );
+        assert_eq!(canonicalize_generics("Parser<'a>::next"), "Parser::next");
+        assert_eq!(
+            canonicalize_generics("Handler<Box<dyn Fn() -> T>>::call"),
+            "Handler::call"
+        );
+    }
+
+    #[test]
+    fn test_canonicalize_generics_leaves_other_names_alone() {
+        assert_eq!(
+            canonicalize_generics("my_mod::Type::method"),
+            "my_mod::Type::method"
+        );
+        assert_eq!(canonicalize_generics("Vec::operator<"), "Vec::operator<");
+        assert_eq!(
+            canonicalize_generics("crate::src/lib.rs::Function get"),
+            "crate::src/lib.rs::Function get"
+        );
+    }
+
+    #[test]
+    fn test_fqn_has_prefix_matches_whole_segments_only() {
+        assert!(fqn_has_prefix("net", "net"));
+        assert!(fqn_has_prefix("net::Socket::open", "net"));
+        assert!(fqn_has_prefix("net::Socket::open", "net::Socket"));
+        assert!(fqn_has_prefix("net::Socket::open", "net::"));
+        assert!(fqn_has_prefix("pkg.net.connect", "pkg.net"));
+        assert!(fqn_has_prefix("anything::at::all", ""));
+
+        assert!(!fqn_has_prefix("network::ping", "net"));
+        assert!(!fqn_has_prefix("net::Socketeer", "net::Socket"));
+        assert!(!fqn_has_prefix("pkg.network", "pkg.net"));
+        assert!(!fqn_has_prefix("net", "net::tcp"));
+        assert!(!fqn_has_prefix("my::net::x", "net"));
+    }
 }
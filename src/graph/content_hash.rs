@@ -0,0 +1,290 @@
+//! Normalization applied to file contents before computing File node hashes
+//!
+//! A database records at most one setting in the `magellan_hash_normalization`
+//! table. It is set with `--normalize-eol` / `--normalize-trailing-whitespace`
+//! on `index` and `watch` and applies to every later hash, so a checkout with
+//! `\r\n` line endings hashes the same as one with `\n`. Only the hash input
+//! is normalized: chunks, spans and symbol data keep the original bytes.
+//!
+//! The hash algorithm is chosen with `--hash-algo` and recorded in the
+//! `magellan_hash_algorithm` table; it applies to files hashed from then on.
+//! Each File node is tagged with the algorithm its hash was computed with, so
+//! change detection and `verify` rehash every file the way it was hashed and
+//! a database holding several algorithms stays consistent.
+
+use anyhow::Result;
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::borrow::Cow;
+use xxhash_rust::xxh3::Xxh3;
+
+/// Algorithm used for File node content hashes
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HashAlgorithm {
+    /// xxHash3, non-cryptographic and fastest (the default)
+    #[default]
+    Xxh3,
+    /// BLAKE3, cryptographic and fast
+    Blake3,
+    /// SHA-256, cryptographic
+    Sha256,
+}
+
+impl HashAlgorithm {
+    /// Parse a `--hash-algo` value
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.to_ascii_lowercase().as_str() {
+            "xxh3" | "xxhash" => Some(Self::Xxh3),
+            "blake3" => Some(Self::Blake3),
+            "sha256" | "sha-256" => Some(Self::Sha256),
+            _ => None,
+        }
+    }
+
+    /// Name accepted by `--hash-algo` and stored in File nodes
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Xxh3 => "xxh3",
+            Self::Blake3 => "blake3",
+            Self::Sha256 => "sha256",
+        }
+    }
+
+    /// Whether this is the default algorithm (omitted from File nodes)
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Lowercase hex digest of `bytes`
+    pub fn digest(self, bytes: &[u8]) -> String {
+        match self {
+            Self::Xxh3 => {
+                let mut hasher = Xxh3::new();
+                hasher.update(bytes);
+                format!("{:032x}", hasher.digest())
+            }
+            Self::Blake3 => blake3::hash(bytes).to_hex().to_string(),
+            Self::Sha256 => hex::encode(Sha256::digest(bytes)),
+        }
+    }
+}
+
+/// Which differences File node hashes ignore
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct HashNormalization {
+    /// Hash `\r\n` as `\n` (`--normalize-eol`)
+    pub eol: bool,
+    /// Drop spaces and tabs at the end of each line (`--normalize-trailing-whitespace`)
+    pub trailing_whitespace: bool,
+}
+
+impl HashNormalization {
+    /// Whether any normalization is applied
+    pub fn is_enabled(&self) -> bool {
+        self.eol || self.trailing_whitespace
+    }
+
+    /// The CLI flags selecting this setting
+    pub fn as_flags(&self) -> Vec<&'static str> {
+        let mut flags = Vec::new();
+        if self.eol {
+            flags.push("--normalize-eol");
+        }
+        if self.trailing_whitespace {
+            flags.push("--normalize-trailing-whitespace");
+        }
+        flags
+    }
+
+    /// The bytes that are hashed for `source`
+    ///
+    /// Borrows `source` unchanged when normalization is off.
+    pub fn apply<'a>(&self, source: &'a [u8]) -> Cow<'a, [u8]> {
+        if !self.is_enabled() {
+            return Cow::Borrowed(source);
+        }
+
+        let mut out = Vec::with_capacity(source.len());
+        for line in source.split_inclusive(|&b| b == b'\n') {
+            let (mut body, mut ending): (&[u8], &[u8]) = match line.strip_suffix(b"\r\n") {
+                Some(body) => (body, b"\r\n"),
+                None => match line.strip_suffix(b"\n") {
+                    Some(body) => (body, b"\n"),
+                    None => (line, b""),
+                },
+            };
+            if self.eol && ending == b"\r\n" {
+                ending = b"\n";
+            }
+            if self.trailing_whitespace {
+                while let [rest @ .., b' ' | b'\t'] = body {
+                    body = rest;
+                }
+            }
+            out.extend_from_slice(body);
+            out.extend_from_slice(ending);
+        }
+        Cow::Owned(out)
+    }
+}
+
+pub(crate) fn ensure_schema(conn: &Connection) -> Result<()> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS magellan_hash_normalization (
+            id INTEGER PRIMARY KEY CHECK (id = 1),
+            eol INTEGER NOT NULL,
+            trailing_whitespace INTEGER NOT NULL
+        )",
+        [],
+    )
+    .map_err(|e| anyhow::anyhow!("Failed to create magellan_hash_normalization: {}", e))?;
+    Ok(())
+}
+
+/// Normalization recorded in the database; off when none was recorded
+pub(crate) fn load(conn: &Connection) -> Result<HashNormalization> {
+    let table_exists = conn
+        .query_row(
+            "SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = 'magellan_hash_normalization'",
+            [],
+            |_| Ok(()),
+        )
+        .optional()
+        .map_err(|e| anyhow::anyhow!("Failed to inspect schema: {}", e))?
+        .is_some();
+    if !table_exists {
+        return Ok(HashNormalization::default());
+    }
+
+    let row = conn
+        .query_row(
+            "SELECT eol, trailing_whitespace FROM magellan_hash_normalization WHERE id = 1",
+            [],
+            |row| {
+                Ok(HashNormalization {
+                    eol: row.get(0)?,
+                    trailing_whitespace: row.get(1)?,
+                })
+            },
+        )
+        .optional()
+        .map_err(|e| anyhow::anyhow!("Failed to read hash normalization: {}", e))?;
+    Ok(row.unwrap_or_default())
+}
+
+pub(crate) fn store(conn: &Connection, normalization: &HashNormalization) -> Result<()> {
+    ensure_schema(conn)?;
+    conn.execute(
+        "INSERT OR REPLACE INTO magellan_hash_normalization (id, eol, trailing_whitespace)
+         VALUES (1, ?1, ?2)",
+        params![normalization.eol, normalization.trailing_whitespace],
+    )
+    .map_err(|e| anyhow::anyhow!("Failed to store hash normalization: {}", e))?;
+    Ok(())
+}
+
+/// Algorithm recorded in the database; the default when none was recorded
+pub(crate) fn load_algorithm(conn: &Connection) -> Result<HashAlgorithm> {
+    let table_exists = conn
+        .query_row(
+            "SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = 'magellan_hash_algorithm'",
+            [],
+            |_| Ok(()),
+        )
+        .optional()
+        .map_err(|e| anyhow::anyhow!("Failed to inspect schema: {}", e))?
+        .is_some();
+    if !table_exists {
+        return Ok(HashAlgorithm::default());
+    }
+
+    let name: Option<String> = conn
+        .query_row(
+            "SELECT algorithm FROM magellan_hash_algorithm WHERE id = 1",
+            [],
+            |row| row.get(0),
+        )
+        .optional()
+        .map_err(|e| anyhow::anyhow!("Failed to read hash algorithm: {}", e))?;
+    match name {
+        Some(name) => HashAlgorithm::parse(&name)
+            .ok_or_else(|| anyhow::anyhow!("Unknown hash algorithm in database: {}", name)),
+        None => Ok(HashAlgorithm::default()),
+    }
+}
+
+pub(crate) fn store_algorithm(conn: &Connection, algorithm: HashAlgorithm) -> Result<()> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS magellan_hash_algorithm (
+            id INTEGER PRIMARY KEY CHECK (id = 1),
+            algorithm TEXT NOT NULL
+        )",
+        [],
+    )
+    .map_err(|e| anyhow::anyhow!("Failed to create magellan_hash_algorithm: {}", e))?;
+    conn.execute(
+        "INSERT OR REPLACE INTO magellan_hash_algorithm (id, algorithm) VALUES (1, ?1)",
+        params![algorithm.as_str()],
+    )
+    .map_err(|e| anyhow::anyhow!("Failed to store hash algorithm: {}", e))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_apply_normalizes_line_endings_and_trailing_whitespace() {
+        let source = b"fn a() {}  \r\n\tlet x = 1;\t\r\nlast \n";
+        let off = HashNormalization::default();
+        assert_eq!(off.apply(source).as_ref(), source);
+
+        let eol = HashNormalization {
+            eol: true,
+            trailing_whitespace: false,
+        };
+        assert_eq!(
+            eol.apply(source).as_ref(),
+            b"fn a() {}  \n\tlet x = 1;\t\nlast \n"
+        );
+
+        let both = HashNormalization {
+            eol: true,
+            trailing_whitespace: true,
+        };
+        assert_eq!(
+            both.apply(source).as_ref(),
+            b"fn a() {}\n\tlet x = 1;\nlast\n"
+        );
+
+        let ws_only = HashNormalization {
+            eol: false,
+            trailing_whitespace: true,
+        };
+        assert_eq!(ws_only.apply(b"a \r\nb\t").as_ref(), b"a\r\nb");
+    }
+
+    #[test]
+    fn test_hash_algorithm_digests() {
+        let data = b"fn main() {}";
+        for algorithm in [
+            HashAlgorithm::Xxh3,
+            HashAlgorithm::Blake3,
+            HashAlgorithm::Sha256,
+        ] {
+            assert_eq!(algorithm.digest(data), algorithm.digest(data));
+            assert_ne!(algorithm.digest(data), algorithm.digest(b"fn other() {}"));
+            assert_eq!(HashAlgorithm::parse(algorithm.as_str()), Some(algorithm));
+        }
+        assert_eq!(HashAlgorithm::Xxh3.digest(data).len(), 32);
+        assert_eq!(HashAlgorithm::Blake3.digest(data).len(), 64);
+        assert_eq!(
+            HashAlgorithm::Sha256.digest(b""),
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
+        assert_eq!(HashAlgorithm::parse("md5"), None);
+    }
+}
@@ -3,6 +3,7 @@
 //! Records every CLI command execution with execution_id, timestamps,
 //! arguments, and outcome. Provides audit trail for correlating outputs.
 
+use crate::log_warn;
 use anyhow::Result;
 use rusqlite::{params, OptionalExtension};
 use std::path::Path;
@@ -45,6 +46,8 @@ enum ExecutionLogBackend {
 /// - SideTables trait abstraction (V3 backend)
 pub struct ExecutionLog {
     backend: ExecutionLogBackend,
+    /// Skip all writes (set for `CodeGraph::open_readonly`)
+    read_only: bool,
 }
 
 impl ExecutionLog {
@@ -52,6 +55,7 @@ impl ExecutionLog {
     pub fn new(db_path: &Path) -> Self {
         Self {
             backend: ExecutionLogBackend::Sqlite(db_path.to_path_buf()),
+            read_only: false,
         }
     }
 
@@ -61,13 +65,25 @@ impl ExecutionLog {
     pub fn with_connection(conn: Arc<parking_lot::Mutex<rusqlite::Connection>>) -> Self {
         let log = Self {
             backend: ExecutionLogBackend::Shared(conn),
+            read_only: false,
         };
         if let Err(e) = log.ensure_schema() {
-            eprintln!("Warning: Failed to ensure ExecutionLog schema: {}", e);
+            log_warn!("Failed to ensure ExecutionLog schema: {}", e);
         }
         log
     }
 
+    /// Create an ExecutionLog over a read-only connection.
+    ///
+    /// Executions are not recorded: `start_execution` and `finish_execution`
+    /// succeed without touching the database. Reads still work.
+    pub fn read_only(conn: Arc<parking_lot::Mutex<rusqlite::Connection>>) -> Self {
+        Self {
+            backend: ExecutionLogBackend::Shared(conn),
+            read_only: true,
+        }
+    }
+
     /// Create an ExecutionLog using the SideTables abstraction.
     ///
     /// This constructor is used for V3 backend where we want to avoid SQLite
@@ -75,6 +91,7 @@ impl ExecutionLog {
     pub fn with_side_tables(side_tables: Arc<dyn super::side_tables::SideTables>) -> Self {
         Self {
             backend: ExecutionLogBackend::SideTables(side_tables),
+            read_only: false,
         }
     }
 
@@ -95,11 +112,12 @@ impl ExecutionLog {
 
         let log = Self {
             backend: ExecutionLogBackend::Sqlite(db_path),
+            read_only: false,
         };
 
         // Ensure schema exists
         if let Err(e) = log.ensure_schema() {
-            eprintln!("Warning: Failed to ensure ExecutionLog schema: {}", e);
+            log_warn!("Failed to ensure ExecutionLog schema: {}", e);
         }
 
         log
@@ -167,6 +185,9 @@ impl ExecutionLog {
     }
 
     pub fn ensure_schema(&self) -> Result<()> {
+        if self.read_only {
+            return Ok(());
+        }
         match &self.backend {
             ExecutionLogBackend::Sqlite(_) => {
                 let conn = self.connect()?;
@@ -223,6 +244,9 @@ impl ExecutionLog {
         root: Option<&str>,
         db_path: &str,
     ) -> Result<i64> {
+        if self.read_only {
+            return Ok(0);
+        }
         match &self.backend {
             ExecutionLogBackend::Sqlite(_) => {
                 let conn = self.connect()?;
@@ -300,6 +324,9 @@ impl ExecutionLog {
         symbols_indexed: usize,
         references_indexed: usize,
     ) -> Result<()> {
+        if self.read_only {
+            return Ok(());
+        }
         match &self.backend {
             ExecutionLogBackend::Sqlite(_) => {
                 let conn = self.connect()?;
@@ -1,6 +1,7 @@
 //! Export functionality for CodeGraph
 //!
-//! Exports graph data to JSON/JSONL/CSV/SCIP format for LLM and pipeline consumption.
+//! Exports graph data to JSON/JSONL/CSV/SCIP/SARIF format for LLM and pipeline consumption,
+//! or to a flat SQLite database for ad-hoc SQL (see [`sqlite_flat`]).
 //!
 //! # Export Schema Versioning
 //!
@@ -16,19 +17,22 @@
 //!
 //! See MANUAL.md section 3.8 for detailed export documentation.
 
+pub mod sarif;
 pub mod scip;
+pub mod sqlite_flat;
 
 use anyhow::Result;
 use base64::Engine;
 use serde::{Deserialize, Serialize};
 use sqlitegraph::{BackendDirection, GraphBackend, NeighborQuery, SnapshotId};
 
-use super::{CallNode, CodeGraph, FileNode, ReferenceNode, SymbolNode};
+use super::{CallNode, CodeGraph, FileNode, ReferenceNode, SymbolInfo, SymbolNode};
+use crate::graph::filter::FilePattern;
 use crate::graph::query::{collision_groups, CollisionField};
 
 /// Export format options
 ///
-/// Dot, Csv, Scip, Lsif, and Impact are available export formats.
+/// Dot, Csv, Scip, Lsif, Impact, Sarif, and SqliteFlat are available export formats.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ExportFormat {
     /// Standard JSON array format
@@ -45,6 +49,10 @@ pub enum ExportFormat {
     Lsif,
     /// Impact analysis format (blast radius for a symbol)
     Impact,
+    /// SARIF 2.1.0 diagnostics (collisions, unresolved calls)
+    Sarif,
+    /// Denormalized SQLite database with flat tables for analytics
+    SqliteFlat,
 }
 
 impl ExportFormat {
@@ -52,12 +60,14 @@ impl ExportFormat {
     pub fn parse_format(s: &str) -> Option<Self> {
         match s.to_lowercase().as_str() {
             "json" => Some(ExportFormat::Json),
-            "jsonl" => Some(ExportFormat::JsonL),
+            "jsonl" | "ndjson" => Some(ExportFormat::JsonL),
             "dot" => Some(ExportFormat::Dot),
             "csv" => Some(ExportFormat::Csv),
             "scip" => Some(ExportFormat::Scip),
             "lsif" => Some(ExportFormat::Lsif),
             "impact" => Some(ExportFormat::Impact),
+            "sarif" => Some(ExportFormat::Sarif),
+            "sqlite-flat" => Some(ExportFormat::SqliteFlat),
             _ => None,
         }
     }
@@ -76,12 +86,53 @@ pub struct ExportConfig {
     pub include_calls: bool,
     /// Use minified JSON (no pretty-printing)
     pub minify: bool,
-    /// Filters for export (file, symbol, kind, max_depth, cluster)
+    /// Filters for export (file, symbol, kind, max_depth, cluster, calls_only)
     pub filters: ExportFilters,
     /// Include collision groups in JSON export
     pub include_collisions: bool,
     /// Field used to group collisions
     pub collisions_field: CollisionField,
+    /// Include unresolved calls in SARIF export
+    pub include_unresolved: bool,
+    /// JSONL record types to emit (empty = the mixed stream with a version header)
+    pub record_types: Vec<JsonlRecordType>,
+}
+
+/// Schema version of JSONL export records
+pub const JSONL_SCHEMA_VERSION: &str = "2.0.0";
+
+/// A JSONL record type selectable with `export --format jsonl --type`
+///
+/// Selecting types drops the version header line and stamps every record
+/// with `schema_version`, so each type forms a homogeneous stream.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JsonlRecordType {
+    Files,
+    Symbols,
+    References,
+    Calls,
+}
+
+impl JsonlRecordType {
+    /// Parse from string
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.to_lowercase().as_str() {
+            "files" => Some(JsonlRecordType::Files),
+            "symbols" => Some(JsonlRecordType::Symbols),
+            "references" => Some(JsonlRecordType::References),
+            "calls" => Some(JsonlRecordType::Calls),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            JsonlRecordType::Files => "files",
+            JsonlRecordType::Symbols => "symbols",
+            JsonlRecordType::References => "references",
+            JsonlRecordType::Calls => "calls",
+        }
+    }
 }
 
 /// Export filters for DOT export
@@ -90,16 +141,31 @@ pub struct ExportConfig {
 /// symbols, or limiting traversal depth.
 #[derive(Debug, Clone, Default)]
 pub struct ExportFilters {
-    /// Only include calls from/to symbols in this file path
+    /// Only include calls made in files matching this pattern (see [`FilePattern`])
     pub file: Option<String>,
     /// Only include calls from/to this specific symbol name
     pub symbol: Option<String>,
-    /// Only include symbols of this kind (e.g., "Function", "Method")
-    pub kind: Option<String>,
+    /// Only include symbols of these kinds (e.g., "Function", "Method"); empty = all
+    pub kinds: Vec<String>,
+    /// Drop symbols of these kinds, applied after `kinds`
+    pub exclude_kinds: Vec<String>,
     /// Maximum depth for call graph traversal (None = unlimited)
     pub max_depth: Option<usize>,
     /// Group nodes by file in subgraphs (DOT cluster feature)
     pub cluster: bool,
+    /// Only include calls resolved to an indexed symbol (a CALLS edge)
+    pub calls_only: bool,
+}
+
+impl ExportFilters {
+    /// Whether a symbol of `kind` passes the kind filters
+    ///
+    /// `kinds` is a union (empty keeps every kind); `exclude_kinds` then
+    /// removes kinds from what is left.
+    pub fn keeps_kind(&self, kind: &str) -> bool {
+        (self.kinds.is_empty() || self.kinds.iter().any(|k| k == kind))
+            && !self.exclude_kinds.iter().any(|k| k == kind)
+    }
 }
 
 /// Escape a string for use as a DOT label
@@ -237,6 +303,8 @@ mod tests {
             filters: ExportFilters::default(),
             include_collisions: true,
             collisions_field: CollisionField::Fqn,
+            include_unresolved: false,
+            record_types: Vec::new(),
         };
 
         let json = export_graph(&mut graph, &config).unwrap();
@@ -279,6 +347,8 @@ fn helper() {}
             filters: ExportFilters::default(),
             include_collisions: false,
             collisions_field: CollisionField::Fqn,
+            include_unresolved: false,
+            record_types: Vec::new(),
         };
 
         let csv = export_graph(&mut graph, &config).unwrap();
@@ -327,6 +397,31 @@ fn helper() {}
         // Verify version header is present
         assert!(csv.starts_with("# Magellan Export Version: 2.0.0"));
     }
+
+    #[test]
+    fn test_reachable_to_dot_two_nodes_one_edge() {
+        let symbol = |id: &str, fqn: &str| SymbolInfo {
+            symbol_id: Some(id.to_string()),
+            fqn: Some(fqn.to_string()),
+            file_path: "lib.rs".to_string(),
+            kind: "Function".to_string(),
+            is_test: false,
+        };
+        let a = symbol("0a", "a");
+        let b = symbol("0b", "b");
+        let edges = vec![(a.clone(), b.clone())];
+
+        let dot = reachable_to_dot(&a, std::slice::from_ref(&b), &edges, false);
+        assert!(dot.starts_with("strict digraph reachable {"));
+        assert_eq!(dot.matches(" -> ").count(), 1, "{}", dot);
+        assert!(dot.contains("  \"0a\" -> \"0b\";\n"), "{}", dot);
+        assert!(dot.contains("\"0b\" [label=\"b\\nlib.rs\"];"), "{}", dot);
+
+        // Reverse traversal from b flips the arrow
+        let dot = reachable_to_dot(&b, std::slice::from_ref(&a), &edges, true);
+        assert_eq!(dot.matches(" -> ").count(), 1, "{}", dot);
+        assert!(dot.contains("  \"0b\" -> \"0a\";\n"), "{}", dot);
+    }
 }
 
 impl Default for ExportConfig {
@@ -340,6 +435,8 @@ impl Default for ExportConfig {
             filters: ExportFilters::default(),
             include_collisions: false,
             collisions_field: CollisionField::Fqn,
+            include_unresolved: false,
+            record_types: Vec::new(),
         }
     }
 }
@@ -522,6 +619,14 @@ fn build_collision_exports(
 /// # Returns
 /// JSON string containing all files, symbols, references, and calls
 pub fn export_json(graph: &mut CodeGraph) -> Result<String> {
+    let export = collect_graph(graph)?;
+    Ok(serde_json::to_string_pretty(&export)?)
+}
+
+/// Collect all files, symbols, references, and calls into memory
+///
+/// Entries are sorted the same way as [`export_json`] output.
+pub(crate) fn collect_graph(graph: &mut CodeGraph) -> Result<GraphExport> {
     let mut files = Vec::new();
     let mut symbols = Vec::new();
     let mut references = Vec::new();
@@ -619,16 +724,14 @@ pub fn export_json(graph: &mut CodeGraph) -> Result<String> {
         .sort_by(|a, b| (&a.file, &a.referenced_symbol).cmp(&(&b.file, &b.referenced_symbol)));
     calls.sort_by(|a, b| (&a.file, &a.caller, &a.callee).cmp(&(&b.file, &b.caller, &b.callee)));
 
-    let export = GraphExport {
+    Ok(GraphExport {
         version: "2.0.0".to_string(),
         files,
         symbols,
         references,
         calls,
         collisions,
-    };
-
-    Ok(serde_json::to_string_pretty(&export)?)
+    })
 }
 
 /// Stream all graph data to JSON format with reduced memory footprint
@@ -674,6 +777,9 @@ pub fn stream_json<W: std::io::Write>(
             }
             "Symbol" if config.include_symbols => {
                 if let Ok(symbol_node) = serde_json::from_value::<SymbolNode>(entity.data.clone()) {
+                    if !config.filters.keeps_kind(&symbol_node.kind) {
+                        continue;
+                    }
                     let file = get_file_path_from_symbol(graph, entity_id)?;
                     symbols.push(SymbolExport {
                         symbol_id: symbol_node.symbol_id,
@@ -802,6 +908,9 @@ pub fn stream_json_minified<W: std::io::Write>(
             }
             "Symbol" if config.include_symbols => {
                 if let Ok(symbol_node) = serde_json::from_value::<SymbolNode>(entity.data.clone()) {
+                    if !config.filters.keeps_kind(&symbol_node.kind) {
+                        continue;
+                    }
                     let file = get_file_path_from_symbol(graph, entity_id)?;
                     symbols.push(SymbolExport {
                         symbol_id: symbol_node.symbol_id,
@@ -927,6 +1036,14 @@ enum JsonlRecord {
     Call(CallExport),
 }
 
+/// A JSONL record stamped with the schema version (typed streams)
+#[derive(Serialize)]
+struct VersionedJsonlRecord<'a> {
+    schema_version: &'static str,
+    #[serde(flatten)]
+    record: &'a JsonlRecord,
+}
+
 /// Export all graph data to JSONL format
 ///
 /// JSONL (JSON Lines) format: one compact JSON object per line.
@@ -939,7 +1056,7 @@ pub fn export_jsonl(graph: &mut CodeGraph) -> Result<String> {
 
     // Add version record first
     records.push(JsonlRecord::Version {
-        version: "2.0.0".to_string(),
+        version: JSONL_SCHEMA_VERSION.to_string(),
     });
 
     // Get all entity IDs from the graph
@@ -1070,12 +1187,26 @@ pub fn stream_ndjson<W: std::io::Write>(
     config: &ExportConfig,
     writer: &mut W,
 ) -> Result<()> {
+    let typed = !config.record_types.is_empty();
+    let wants = |record_type: JsonlRecordType| {
+        if typed {
+            return config.record_types.contains(&record_type);
+        }
+        match record_type {
+            JsonlRecordType::Files => true,
+            JsonlRecordType::Symbols => config.include_symbols,
+            JsonlRecordType::References => config.include_references,
+            JsonlRecordType::Calls => config.include_calls,
+        }
+    };
     let mut records = Vec::new();
 
-    // Add version record first
-    records.push(JsonlRecord::Version {
-        version: "2.0.0".to_string(),
-    });
+    // Add version record first (typed streams carry it on every record)
+    if !typed {
+        records.push(JsonlRecord::Version {
+            version: JSONL_SCHEMA_VERSION.to_string(),
+        });
+    }
 
     // Get all entity IDs from the graph
     let entity_ids = graph.files.backend.entity_ids()?;
@@ -1086,7 +1217,7 @@ pub fn stream_ndjson<W: std::io::Write>(
         let entity = graph.files.backend.get_node(snapshot, entity_id)?;
 
         match entity.kind.as_str() {
-            "File" => {
+            "File" if wants(JsonlRecordType::Files) => {
                 if let Ok(file_node) = serde_json::from_value::<FileNode>(entity.data.clone()) {
                     records.push(JsonlRecord::File(FileExport {
                         path: file_node.path,
@@ -1094,8 +1225,11 @@ pub fn stream_ndjson<W: std::io::Write>(
                     }));
                 }
             }
-            "Symbol" if config.include_symbols => {
+            "Symbol" if wants(JsonlRecordType::Symbols) => {
                 if let Ok(symbol_node) = serde_json::from_value::<SymbolNode>(entity.data.clone()) {
+                    if !config.filters.keeps_kind(&symbol_node.kind) {
+                        continue;
+                    }
                     let file = get_file_path_from_symbol(graph, entity_id)?;
                     records.push(JsonlRecord::Symbol(SymbolExport {
                         symbol_id: symbol_node.symbol_id,
@@ -1114,7 +1248,7 @@ pub fn stream_ndjson<W: std::io::Write>(
                     }));
                 }
             }
-            "Reference" if config.include_references => {
+            "Reference" if wants(JsonlRecordType::References) => {
                 if let Ok(ref_node) = serde_json::from_value::<ReferenceNode>(entity.data.clone()) {
                     let referenced_symbol = entity
                         .name
@@ -1135,7 +1269,7 @@ pub fn stream_ndjson<W: std::io::Write>(
                     }));
                 }
             }
-            "Call" if config.include_calls => {
+            "Call" if wants(JsonlRecordType::Calls) => {
                 if let Ok(call_node) = serde_json::from_value::<CallNode>(entity.data.clone()) {
                     records.push(JsonlRecord::Call(CallExport {
                         file: call_node.file,
@@ -1187,8 +1321,18 @@ pub fn stream_ndjson<W: std::io::Write>(
         if !first {
             writeln!(&mut *writer)?;
         }
-        serde_json::to_writer(&mut *writer, &record)
-            .map_err(|e| anyhow::anyhow!("JSON serialization error: {}", e))?;
+        let written = if typed {
+            serde_json::to_writer(
+                &mut *writer,
+                &VersionedJsonlRecord {
+                    schema_version: JSONL_SCHEMA_VERSION,
+                    record: &record,
+                },
+            )
+        } else {
+            serde_json::to_writer(&mut *writer, &record)
+        };
+        written.map_err(|e| anyhow::anyhow!("JSON serialization error: {}", e))?;
         first = false;
     }
 
@@ -1211,70 +1355,109 @@ pub fn stream_ndjson<W: std::io::Write>(
 /// - Uses "strict digraph" for deterministic output
 /// - Node labels: "{symbol_name}\n{file_path}" (newline for readability)
 /// - Uses symbol_id as internal identifier if available, fallback to sanitized name
-/// - Clusters nodes by file if config.filters.cluster is true
+/// - Keeps only calls resolved to an indexed symbol if config.filters.calls_only is true
+/// - Clusters nodes by defining file if config.filters.cluster is true; node
+///   labels are then bare symbol names, since the cluster carries the path
 pub fn export_dot(graph: &mut CodeGraph, config: &ExportConfig) -> Result<String> {
     use std::collections::{BTreeMap, BTreeSet};
 
     let mut dot_output = String::from("strict digraph call_graph {\n");
     dot_output.push_str("  node [shape=box, style=rounded];\n");
 
-    // Collect all Call nodes from the graph
+    // Collect all Call nodes from the graph, with the file defining the
+    // resolved callee (None for calls without a CALLS edge)
     let entity_ids = graph.files.backend.entity_ids()?;
     let snapshot = SnapshotId::current();
-    let mut calls = Vec::new();
+    let mut calls: Vec<(CallNode, Option<String>)> = Vec::new();
 
     for entity_id in entity_ids {
         let entity = graph.files.backend.get_node(snapshot, entity_id)?;
         if entity.kind == "Call" {
             if let Ok(call_node) = serde_json::from_value::<CallNode>(entity.data) {
-                calls.push(call_node);
+                let mut callee_ids = graph.files.backend.neighbors(
+                    snapshot,
+                    entity_id,
+                    NeighborQuery {
+                        direction: BackendDirection::Outgoing,
+                        edge_type: Some("CALLS".to_string()),
+                    },
+                )?;
+                callee_ids.sort_unstable();
+                let callee_file = match callee_ids.first() {
+                    Some(&callee_id) => Some(get_file_path_from_symbol(graph, callee_id)?),
+                    None => None,
+                };
+                calls.push((call_node, callee_file));
             }
         }
     }
 
     // Apply filters
+    if config.filters.calls_only {
+        calls.retain(|(_, callee_file)| callee_file.is_some());
+    }
     if let Some(ref file_filter) = config.filters.file {
-        calls.retain(|c| c.file.contains(file_filter));
+        let root = graph.path_settings().map(|settings| settings.root.clone());
+        let pattern = FilePattern::new(file_filter)?.relative_to(root.as_deref());
+        calls.retain(|(c, _)| pattern.matches(&c.file));
     }
     if let Some(ref symbol_filter) = config.filters.symbol {
-        calls.retain(|c| c.caller.contains(symbol_filter) || c.callee.contains(symbol_filter));
+        calls.retain(|(c, _)| c.caller.contains(symbol_filter) || c.callee.contains(symbol_filter));
     }
 
     // Sort deterministically: file, then caller, then callee
-    calls.sort_by(|a, b| {
+    calls.sort_by(|(a, _), (b, _)| {
         a.file
             .cmp(&b.file)
             .then_with(|| a.caller.cmp(&b.caller))
             .then_with(|| a.callee.cmp(&b.callee))
     });
 
-    // Collect unique nodes and organize by file if clustering
+    // Collect unique nodes and organize by defining file if clustering
     let mut nodes: BTreeSet<(String, String)> = BTreeSet::new(); // (symbol_id_or_name, label)
-    let mut file_to_nodes: BTreeMap<String, Vec<(String, String)>> = BTreeMap::new();
-
-    for call in &calls {
-        for (name, symbol_id) in [
-            (call.caller.as_str(), call.caller_symbol_id.as_ref()),
-            (call.callee.as_str(), call.callee_symbol_id.as_ref()),
+    let mut file_to_nodes: BTreeMap<String, BTreeSet<(String, String)>> = BTreeMap::new();
+
+    for (call, callee_file) in &calls {
+        for (name, symbol_id, file) in [
+            (
+                call.caller.as_str(),
+                call.caller_symbol_id.as_ref(),
+                Some(&call.file),
+            ),
+            (
+                call.callee.as_str(),
+                call.callee_symbol_id.as_ref(),
+                callee_file.as_ref(),
+            ),
         ] {
             let node_id = escape_dot_id(&symbol_id.cloned(), name);
-            let label = format!(
-                "{}\\n{}",
-                escape_dot_label(name),
-                escape_dot_label(&call.file)
-            );
-            nodes.insert((node_id.clone(), label.clone()));
 
             if config.filters.cluster {
-                file_to_nodes
-                    .entry(call.file.clone())
-                    .or_default()
-                    .push((node_id, label));
+                let label = escape_dot_label(name);
+                match file.filter(|file| !file.is_empty()) {
+                    Some(file) => {
+                        file_to_nodes
+                            .entry(file.clone())
+                            .or_default()
+                            .insert((node_id, label));
+                    }
+                    // Unresolved callees are not defined in any indexed file
+                    None => {
+                        nodes.insert((node_id, label));
+                    }
+                }
+            } else {
+                let label = format!(
+                    "{}\\n{}",
+                    escape_dot_label(name),
+                    escape_dot_label(&call.file)
+                );
+                nodes.insert((node_id, label));
             }
         }
     }
 
-    // Emit edges
+    // Emit nodes
     if config.filters.cluster {
         // Group nodes by file into subgraphs
         for (file, file_nodes) in &file_to_nodes {
@@ -1298,15 +1481,21 @@ pub fn export_dot(graph: &mut CodeGraph, config: &ExportConfig) -> Result<String
 
             dot_output.push_str("  }\n");
         }
-    } else {
-        // Emit all nodes at top level
-        for (node_id, label) in &nodes {
-            dot_output.push_str(&format!("  {} [label={}];\n", node_id, label));
-        }
+
+        // A node placed in a cluster is not repeated at top level
+        let clustered: BTreeSet<&String> = file_to_nodes
+            .values()
+            .flatten()
+            .map(|(node_id, _)| node_id)
+            .collect();
+        nodes.retain(|(node_id, _)| !clustered.contains(node_id));
+    }
+    for (node_id, label) in &nodes {
+        dot_output.push_str(&format!("  {} [label={}];\n", node_id, label));
     }
 
     // Emit edges
-    for call in &calls {
+    for (call, _) in &calls {
         let caller_id = escape_dot_id(&call.caller_symbol_id, &call.caller);
         let callee_id = escape_dot_id(&call.callee_symbol_id, &call.callee);
         dot_output.push_str(&format!("  {} -> {};\n", caller_id, callee_id));
@@ -1317,6 +1506,70 @@ pub fn export_dot(graph: &mut CodeGraph, config: &ExportConfig) -> Result<String
     Ok(dot_output)
 }
 
+/// Render a reachable set as a DOT digraph
+///
+/// Nodes are `start` plus `symbols`; `edges` are `(caller, callee)` pairs as
+/// returned by [`CodeGraph::reachable_call_edges`]. Edges point from caller to
+/// callee, or from callee to caller when `reverse` is set, so arrows follow
+/// the direction the set was traversed. Nodes and edges are emitted in sorted
+/// order for stable output.
+pub fn reachable_to_dot(
+    start: &SymbolInfo,
+    symbols: &[SymbolInfo],
+    edges: &[(SymbolInfo, SymbolInfo)],
+    reverse: bool,
+) -> String {
+    use std::collections::BTreeSet;
+
+    // Quoted, since hex symbol IDs may start with a digit
+    let node_id = |symbol: &SymbolInfo| {
+        escape_dot_label(&escape_dot_id(
+            &symbol.symbol_id,
+            symbol.fqn.as_deref().unwrap_or("?"),
+        ))
+    };
+
+    let mut dot_output = String::from("strict digraph reachable {\n");
+    dot_output.push_str("  node [shape=box, style=rounded];\n");
+
+    let nodes: BTreeSet<(String, String)> = std::iter::once(start)
+        .chain(symbols)
+        .map(|symbol| {
+            let label = format!(
+                "{}\n{}",
+                symbol.fqn.as_deref().unwrap_or("?"),
+                symbol.file_path
+            );
+            (node_id(symbol), escape_dot_label(&label))
+        })
+        .collect();
+    for (id, label) in &nodes {
+        let style = if *id == node_id(start) {
+            ", penwidth=2"
+        } else {
+            ""
+        };
+        dot_output.push_str(&format!("  {} [label={}{}];\n", id, label, style));
+    }
+
+    let edge_ids: BTreeSet<(String, String)> = edges
+        .iter()
+        .map(|(caller, callee)| {
+            if reverse {
+                (node_id(callee), node_id(caller))
+            } else {
+                (node_id(caller), node_id(callee))
+            }
+        })
+        .collect();
+    for (from, to) in &edge_ids {
+        dot_output.push_str(&format!("  {} -> {};\n", from, to));
+    }
+
+    dot_output.push_str("}\n");
+    dot_output
+}
+
 /// Export graph data with configurable format and options
 ///
 /// Dispatches to export_json(), export_jsonl(), or export_dot() based on config.format.
@@ -1329,6 +1582,11 @@ pub fn export_dot(graph: &mut CodeGraph, config: &ExportConfig) -> Result<String
 /// # Returns
 /// JSON, JSONL, or DOT string based on config.format
 pub fn export_graph(graph: &mut CodeGraph, config: &ExportConfig) -> Result<String> {
+    // SARIF reports diagnostics, not graph content, so it ignores the content filters
+    if config.format == ExportFormat::Sarif {
+        return self::sarif::export_sarif(graph, config);
+    }
+
     // Check if export should be empty based on filters
     let has_content = config.include_symbols || config.include_references || config.include_calls;
 
@@ -1393,6 +1651,9 @@ pub fn export_graph(graph: &mut CodeGraph, config: &ExportConfig) -> Result<Stri
                         if let Ok(symbol_node) =
                             serde_json::from_value::<SymbolNode>(entity.data.clone())
                         {
+                            if !config.filters.keeps_kind(&symbol_node.kind) {
+                                continue;
+                            }
                             let file = get_file_path_from_symbol(graph, entity_id)?;
                             symbols.push(SymbolExport {
                                 symbol_id: symbol_node.symbol_id,
@@ -1488,6 +1749,11 @@ pub fn export_graph(graph: &mut CodeGraph, config: &ExportConfig) -> Result<Stri
                 serde_json::to_string_pretty(&export).map_err(Into::into)
             }
         }
+        ExportFormat::JsonL if !config.record_types.is_empty() => {
+            let mut buffer = Vec::new();
+            stream_ndjson(graph, config, &mut buffer)?;
+            Ok(String::from_utf8(buffer)?)
+        }
         ExportFormat::JsonL => export_jsonl(graph),
         ExportFormat::Dot => export_dot(graph, config),
         ExportFormat::Csv => export_csv(graph, config),
@@ -1509,6 +1775,13 @@ pub fn export_graph(graph: &mut CodeGraph, config: &ExportConfig) -> Result<Stri
             // For file output, use export_cmd.rs which handles LSIF specially
             Ok(String::new())
         }
+        ExportFormat::Sarif => self::sarif::export_sarif(graph, config),
+        ExportFormat::SqliteFlat => {
+            // A database file is not text; export_cmd.rs writes it via sqlite_flat
+            Err(anyhow::anyhow!(
+                "sqlite-flat export writes a database file. Use: magellan export --db code.db --format sqlite-flat --output flat.db"
+            ))
+        }
         ExportFormat::Impact => {
             // Impact export requires --symbol parameter and is handled separately in export_cmd.rs
             Err(anyhow::anyhow!(
@@ -1580,6 +1853,9 @@ pub fn export_csv(graph: &mut CodeGraph, config: &ExportConfig) -> Result<String
         match entity.kind.as_str() {
             "Symbol" if config.include_symbols => {
                 if let Ok(symbol_node) = serde_json::from_value::<SymbolNode>(entity.data.clone()) {
+                    if !config.filters.keeps_kind(&symbol_node.kind) {
+                        continue;
+                    }
                     let file = get_file_path_from_symbol(graph, entity_id)?;
                     records.push(UnifiedCsvRow {
                         record_type: "Symbol".to_string(),
@@ -0,0 +1,494 @@
+//! SARIF export functionality
+//!
+//! Emits graph diagnostics as SARIF 2.1.0 (Static Analysis Results Interchange
+//! Format) for CI dashboards and code-scanning tools.
+//!
+//! # Rules
+//!
+//! | Rule id | Source |
+//! |---------|--------|
+//! | `magellan/ambiguous-symbol` | One result per symbol in a collision group (`collisions` command) |
+//! | `magellan/unresolved-call` | Call nodes with no `CALLS` edge to a callee symbol (opt-in) |
+//!
+//! Each result points at the file and byte region of the offending symbol or
+//! call site. Results are sorted by (rule id, file, byte offset, message) so
+//! the same database always produces byte-identical output.
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use sqlitegraph::{BackendDirection, GraphBackend, NeighborQuery, SnapshotId};
+
+use crate::graph::query::{collision_groups, unresolved_calls};
+use crate::graph::schema::{CallNode, SymbolNode};
+
+use super::{CodeGraph, ExportConfig};
+
+/// SARIF schema URI written to the `$schema` property
+pub const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+
+/// SARIF specification version
+pub const SARIF_VERSION: &str = "2.1.0";
+
+/// Rule id for symbols that share a collision field value
+pub const RULE_AMBIGUOUS_SYMBOL: &str = "magellan/ambiguous-symbol";
+
+/// Rule id for call sites whose callee could not be resolved
+pub const RULE_UNRESOLVED_CALL: &str = "magellan/unresolved-call";
+
+/// Top-level SARIF log
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SarifLog {
+    #[serde(rename = "$schema")]
+    pub schema: String,
+    pub version: String,
+    pub runs: Vec<SarifRun>,
+}
+
+/// A single analysis run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SarifRun {
+    pub tool: SarifTool,
+    pub results: Vec<SarifResult>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SarifTool {
+    pub driver: SarifDriver,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifDriver {
+    pub name: String,
+    pub version: String,
+    pub information_uri: String,
+    pub rules: Vec<SarifRule>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifRule {
+    pub id: String,
+    pub short_description: SarifMessage,
+    pub default_configuration: SarifRuleConfiguration,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SarifRuleConfiguration {
+    pub level: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SarifMessage {
+    pub text: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifResult {
+    pub rule_id: String,
+    pub rule_index: usize,
+    pub level: String,
+    pub message: SarifMessage,
+    pub locations: Vec<SarifLocation>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifLocation {
+    pub physical_location: SarifPhysicalLocation,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifPhysicalLocation {
+    pub artifact_location: SarifArtifactLocation,
+    pub region: SarifRegion,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SarifArtifactLocation {
+    pub uri: String,
+}
+
+/// Source region of a result
+///
+/// SARIF lines and columns are 1-based; Magellan columns are 0-based, so
+/// columns are shifted by one on export. Byte offsets are passed through.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifRegion {
+    pub start_line: usize,
+    pub start_column: usize,
+    pub end_line: usize,
+    pub end_column: usize,
+    pub byte_offset: usize,
+    pub byte_length: usize,
+}
+
+impl SarifRegion {
+    fn from_span(
+        byte_start: usize,
+        byte_end: usize,
+        start_line: usize,
+        start_col: usize,
+        end_line: usize,
+        end_col: usize,
+    ) -> Self {
+        Self {
+            start_line: start_line.max(1),
+            start_column: start_col + 1,
+            end_line: end_line.max(1),
+            end_column: end_col + 1,
+            byte_offset: byte_start,
+            byte_length: byte_end.saturating_sub(byte_start),
+        }
+    }
+}
+
+/// Convert a stored file path to a SARIF artifact URI
+///
+/// Absolute paths become `file://` URIs; relative paths are emitted as
+/// relative URI references (resolved against the consumer's checkout).
+/// Each segment is percent-encoded, so spaces, `#`, `%` and non-ASCII
+/// names still form a valid URI.
+fn path_to_uri(path: &str) -> String {
+    if path.starts_with('/') {
+        format!("file://{}", percent_encode_path(path))
+    } else {
+        percent_encode_path(path.trim_start_matches("./"))
+    }
+}
+
+/// Percent-encode every byte of `path` outside the RFC 3986 unreserved set,
+/// keeping `/` as the segment separator
+fn percent_encode_path(path: &str) -> String {
+    let mut encoded = String::with_capacity(path.len());
+    for byte in path.bytes() {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'.' | b'_' | b'~' | b'/') {
+            encoded.push(byte as char);
+        } else {
+            encoded.push_str(&format!("%{:02X}", byte));
+        }
+    }
+    encoded
+}
+
+fn rules() -> Vec<SarifRule> {
+    vec![
+        SarifRule {
+            id: RULE_AMBIGUOUS_SYMBOL.to_string(),
+            short_description: SarifMessage {
+                text: "Multiple symbols share the same qualified name".to_string(),
+            },
+            default_configuration: SarifRuleConfiguration {
+                level: "warning".to_string(),
+            },
+        },
+        SarifRule {
+            id: RULE_UNRESOLVED_CALL.to_string(),
+            short_description: SarifMessage {
+                text: "Call site does not resolve to an indexed symbol".to_string(),
+            },
+            default_configuration: SarifRuleConfiguration {
+                level: "note".to_string(),
+            },
+        },
+    ]
+}
+
+fn location(path: &str, region: SarifRegion) -> SarifLocation {
+    SarifLocation {
+        physical_location: SarifPhysicalLocation {
+            artifact_location: SarifArtifactLocation {
+                uri: path_to_uri(path),
+            },
+            region,
+        },
+    }
+}
+
+/// Build one `magellan/ambiguous-symbol` result per collision candidate
+fn ambiguous_symbol_results(
+    graph: &mut CodeGraph,
+    config: &ExportConfig,
+) -> Result<Vec<SarifResult>> {
+    let groups = collision_groups(graph, config.collisions_field, usize::MAX)?;
+    let snapshot = SnapshotId::current();
+    let mut results = Vec::new();
+
+    for group in groups {
+        for candidate in &group.candidates {
+            let Some(file_path) = candidate.file_path.as_deref() else {
+                continue;
+            };
+            let node = match graph.calls.backend.get_node(snapshot, candidate.entity_id) {
+                Ok(node) => node,
+                Err(_) => continue,
+            };
+            let symbol: SymbolNode = match serde_json::from_value(node.data) {
+                Ok(symbol) => symbol,
+                Err(_) => continue,
+            };
+
+            let name = candidate.name.as_deref().unwrap_or(&group.value);
+            results.push(SarifResult {
+                rule_id: RULE_AMBIGUOUS_SYMBOL.to_string(),
+                rule_index: 0,
+                level: "warning".to_string(),
+                message: SarifMessage {
+                    text: format!(
+                        "Symbol '{}' shares {} '{}' with {} other symbol(s)",
+                        name,
+                        group.field,
+                        group.value,
+                        group.count.saturating_sub(1)
+                    ),
+                },
+                locations: vec![location(
+                    file_path,
+                    SarifRegion::from_span(
+                        symbol.byte_start,
+                        symbol.byte_end,
+                        symbol.start_line,
+                        symbol.start_col,
+                        symbol.end_line,
+                        symbol.end_col,
+                    ),
+                )],
+            });
+        }
+    }
+
+    Ok(results)
+}
+
+/// Build one `magellan/unresolved-call` result per call with no callee edge
+fn unresolved_call_results(graph: &mut CodeGraph) -> Result<Vec<SarifResult>> {
+    let results = unresolved_calls(graph)?
+        .into_iter()
+        .map(|call| SarifResult {
+            rule_id: RULE_UNRESOLVED_CALL.to_string(),
+            rule_index: 1,
+            level: "note".to_string(),
+            message: SarifMessage {
+                text: format!(
+                    "Call from '{}' to '{}' does not resolve to an indexed symbol",
+                    call.caller, call.callee
+                ),
+            },
+            locations: vec![location(
+                &call.file,
+                SarifRegion::from_span(
+                    call.byte_start as usize,
+                    call.byte_end as usize,
+                    call.start_line as usize,
+                    call.start_col as usize,
+                    call.end_line as usize,
+                    call.end_col as usize,
+                ),
+            )],
+        })
+        .collect();
+
+    Ok(results)
+}
+
+/// Build the SARIF log for a graph
+///
+/// Collisions are grouped by `config.collisions_field`. Unresolved calls are
+/// only included when `config.include_unresolved` is set.
+pub fn build_sarif(graph: &mut CodeGraph, config: &ExportConfig) -> Result<SarifLog> {
+    let mut results = ambiguous_symbol_results(graph, config)?;
+    if config.include_unresolved {
+        results.extend(unresolved_call_results(graph)?);
+    }
+
+    // Sort for deterministic output
+    results.sort_by(|a, b| {
+        let loc_a = &a.locations[0].physical_location;
+        let loc_b = &b.locations[0].physical_location;
+        (
+            &a.rule_id,
+            &loc_a.artifact_location.uri,
+            loc_a.region.byte_offset,
+            &a.message.text,
+        )
+            .cmp(&(
+                &b.rule_id,
+                &loc_b.artifact_location.uri,
+                loc_b.region.byte_offset,
+                &b.message.text,
+            ))
+    });
+
+    Ok(SarifLog {
+        schema: SARIF_SCHEMA.to_string(),
+        version: SARIF_VERSION.to_string(),
+        runs: vec![SarifRun {
+            tool: SarifTool {
+                driver: SarifDriver {
+                    name: "magellan".to_string(),
+                    version: env!("CARGO_PKG_VERSION").to_string(),
+                    information_uri: "https://github.com/oldnordic/magellan".to_string(),
+                    rules: rules(),
+                },
+            },
+            results,
+        }],
+    })
+}
+
+/// Export graph diagnostics as a SARIF 2.1.0 JSON string
+pub fn export_sarif(graph: &mut CodeGraph, config: &ExportConfig) -> Result<String> {
+    let log = build_sarif(graph, config)?;
+    if config.minify {
+        serde_json::to_string(&log).map_err(Into::into)
+    } else {
+        serde_json::to_string_pretty(&log).map_err(Into::into)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::export::{ExportFilters, ExportFormat};
+    use crate::graph::query::CollisionField;
+
+    fn sarif_config(include_unresolved: bool) -> ExportConfig {
+        ExportConfig {
+            format: ExportFormat::Sarif,
+            include_symbols: true,
+            include_references: true,
+            include_calls: true,
+            minify: false,
+            filters: ExportFilters::default(),
+            include_collisions: true,
+            collisions_field: CollisionField::Fqn,
+            include_unresolved,
+            record_types: Vec::new(),
+        }
+    }
+
+    fn index_colliding_files(temp_dir: &tempfile::TempDir, graph: &mut CodeGraph) {
+        for name in ["b.rs", "a.rs"] {
+            let path = temp_dir.path().join(name);
+            std::fs::write(&path, "fn collide() {}\n").unwrap();
+            let source = std::fs::read(&path).unwrap();
+            graph.index_file(&path.to_string_lossy(), &source).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_path_to_uri() {
+        assert_eq!(path_to_uri("/repo/src/lib.rs"), "file:///repo/src/lib.rs");
+        assert_eq!(path_to_uri("./src/lib.rs"), "src/lib.rs");
+        assert_eq!(path_to_uri("src/lib.rs"), "src/lib.rs");
+    }
+
+    #[test]
+    fn test_path_to_uri_percent_encodes_segments() {
+        assert_eq!(
+            path_to_uri("/repo/my project/src/lib.rs"),
+            "file:///repo/my%20project/src/lib.rs"
+        );
+        assert_eq!(path_to_uri("./my dir/a#b%c.rs"), "my%20dir/a%23b%25c.rs");
+        assert_eq!(path_to_uri("src/café.rs"), "src/caf%C3%A9.rs");
+    }
+
+    #[test]
+    fn test_sarif_reports_ambiguous_symbols() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let mut graph = CodeGraph::open(temp_dir.path().join("test.db")).unwrap();
+        index_colliding_files(&temp_dir, &mut graph);
+
+        let log = build_sarif(&mut graph, &sarif_config(false)).unwrap();
+        assert_eq!(log.version, "2.1.0");
+        assert_eq!(log.runs.len(), 1);
+
+        let results = &log.runs[0].results;
+        assert!(!results.is_empty());
+        assert!(results.iter().all(|r| r.rule_id == RULE_AMBIGUOUS_SYMBOL));
+
+        // Sorted by file: a.rs before b.rs
+        let uris: Vec<&str> = results
+            .iter()
+            .map(|r| {
+                r.locations[0]
+                    .physical_location
+                    .artifact_location
+                    .uri
+                    .as_str()
+            })
+            .collect();
+        let mut sorted = uris.clone();
+        sorted.sort();
+        assert_eq!(uris, sorted);
+
+        let region = &results[0].locations[0].physical_location.region;
+        assert_eq!(region.start_line, 1);
+        assert_eq!(region.start_column, 1);
+        assert!(region.byte_length > 0);
+    }
+
+    #[test]
+    fn test_sarif_unresolved_calls_are_opt_in() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let mut graph = CodeGraph::open(temp_dir.path().join("test.db")).unwrap();
+        index_colliding_files(&temp_dir, &mut graph);
+
+        // Call node with no CALLS edge to a callee symbol
+        let dangling = crate::references::CallFact {
+            file_path: temp_dir.path().join("a.rs"),
+            caller: "collide".to_string(),
+            callee: "missing".to_string(),
+            caller_symbol_id: None,
+            callee_symbol_id: None,
+            byte_start: 19,
+            byte_end: 28,
+            start_line: 2,
+            start_col: 4,
+            end_line: 2,
+            end_col: 13,
+        };
+        graph.calls.insert_call_nodes_batch(&[&dangling]).unwrap();
+
+        let without = build_sarif(&mut graph, &sarif_config(false)).unwrap();
+        assert!(without.runs[0]
+            .results
+            .iter()
+            .all(|r| r.rule_id != RULE_UNRESOLVED_CALL));
+
+        let with = build_sarif(&mut graph, &sarif_config(true)).unwrap();
+        let unresolved: Vec<&SarifResult> = with.runs[0]
+            .results
+            .iter()
+            .filter(|r| r.rule_id == RULE_UNRESOLVED_CALL)
+            .collect();
+        assert_eq!(unresolved.len(), 1);
+        assert_eq!(unresolved[0].rule_index, 1);
+        let region = &unresolved[0].locations[0].physical_location.region;
+        assert_eq!(region.byte_offset, 19);
+        assert_eq!(region.byte_length, 9);
+        assert_eq!(region.start_column, 5);
+    }
+
+    #[test]
+    fn test_sarif_output_is_deterministic() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let mut graph = CodeGraph::open(temp_dir.path().join("test.db")).unwrap();
+        index_colliding_files(&temp_dir, &mut graph);
+
+        let first = export_sarif(&mut graph, &sarif_config(true)).unwrap();
+        let second = export_sarif(&mut graph, &sarif_config(true)).unwrap();
+        assert_eq!(first, second);
+
+        let parsed: serde_json::Value = serde_json::from_str(&first).unwrap();
+        assert_eq!(parsed["$schema"], SARIF_SCHEMA);
+        assert_eq!(parsed["runs"][0]["tool"]["driver"]["name"], "magellan");
+        assert_eq!(
+            parsed["runs"][0]["tool"]["driver"]["rules"][0]["id"],
+            RULE_AMBIGUOUS_SYMBOL
+        );
+    }
+}
@@ -20,10 +20,11 @@ use scip::types::{
     symbol_information::Kind, Document, Index, Metadata, Occurrence, PositionEncoding,
     SymbolInformation, SymbolRole,
 };
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 use crate::graph::schema::SymbolNode;
 use crate::ingest::detect::detect_language;
+use crate::references::ReferenceKind;
 
 use super::CodeGraph;
 
@@ -94,6 +95,18 @@ fn magellan_symbol_to_scip(fqn: &str, language: &str) -> String {
     }
 }
 
+/// SCIP symbol for a Magellan symbol, from its FQN or else its name
+fn scip_symbol_for(symbol: &SymbolNode, language: &str) -> String {
+    match symbol.fqn.as_deref() {
+        Some(fqn) if !fqn.is_empty() => magellan_symbol_to_scip(fqn, language),
+        _ => format!(
+            "magellan {}/{}.",
+            language,
+            symbol.name.as_deref().unwrap_or("")
+        ),
+    }
+}
+
 /// Map Magellan symbol kind to SCIP symbol kind
 fn map_symbol_kind(kind: &str) -> Kind {
     match kind {
@@ -111,12 +124,62 @@ fn map_symbol_kind(kind: &str) -> Kind {
     }
 }
 
+/// SCIP range for a Magellan span
+///
+/// Magellan lines are 1-indexed and SCIP lines 0-indexed; columns are byte
+/// offsets from the line start in both.
+fn scip_range(start_line: u64, start_col: u64, end_line: u64, end_col: u64) -> Vec<i32> {
+    vec![
+        start_line.saturating_sub(1) as i32,
+        start_col as i32,
+        end_line.saturating_sub(1) as i32,
+        end_col as i32,
+    ]
+}
+
+/// SCIP `symbol_roles` for a reference of `kind`
+///
+/// Usages are reads; `use` targets are imports. Magellan does not record
+/// whether a usage writes the symbol, so `WriteAccess` is never set.
+fn reference_roles(kind: ReferenceKind) -> i32 {
+    match kind {
+        ReferenceKind::Usage => SymbolRole::ReadAccess as i32,
+        ReferenceKind::Import => SymbolRole::Import as i32,
+    }
+}
+
+/// Occurrences of one document, merged by (range, symbol)
+///
+/// A call site is usually also recorded as a reference over the same span;
+/// merging ORs their roles into a single occurrence.
+type DocumentOccurrences = BTreeMap<(Vec<i32>, String), i32>;
+
+/// Symbol entity the node `entity_id` points to over an outgoing `edge_type` edge
+fn edge_target(graph: &CodeGraph, entity_id: i64, edge_type: &str) -> Result<Option<i64>> {
+    let targets = graph.files.backend.neighbors(
+        SnapshotId::current(),
+        entity_id,
+        NeighborQuery {
+            direction: BackendDirection::Outgoing,
+            edge_type: Some(edge_type.to_string()),
+        },
+    )?;
+    Ok(targets.into_iter().min())
+}
+
 /// Export graph to SCIP format
 ///
 /// Builds a SCIP index containing:
 /// - Metadata with tool info and project root
-/// - Documents per file with occurrences (definitions and references)
-/// - Proper SCIP symbol encoding
+/// - One document per file, ordered by path
+/// - Definition occurrences for symbols, and reference occurrences for
+///   references (`ReadAccess`, or `Import` for `use` targets) and call sites
+///   (`ReadAccess`) that resolve to an indexed symbol
+///
+/// Output is deterministic: documents, occurrences, and symbol information
+/// are sorted, so exporting the same database twice yields identical bytes.
+/// References and calls whose target is not indexed have no SCIP symbol and
+/// are left out.
 ///
 /// # FQN Collision Limitation
 ///
@@ -137,15 +200,8 @@ pub fn export_scip(graph: &CodeGraph, config: &ScipExportConfig) -> Result<Vec<u
     // Build metadata
     let mut metadata = Metadata::new();
 
-    // Document FQN collision limitation in SCIP export
-    // SCIP symbol format uses display_fqn (human-readable) which may have collisions
-    // when multiple symbols share the same name in different files. Use Magellan's
-    // native export formats for complete ambiguity resolution via symbol_id.
     // Note: scip 0.6.1 ToolInfo doesn't have public name/version fields
     // The metadata.project_root is the primary metadata we can set
-
-    // Set metadata fields
-    // metadata.tool_info = MessageField::some(tool_info); // This field may not exist or be different
     metadata.project_root = config.project_root.clone();
 
     // Set text encoding to UTF-8 (all sources are UTF-8)
@@ -155,19 +211,20 @@ pub fn export_scip(graph: &CodeGraph, config: &ScipExportConfig) -> Result<Vec<u
     // Set metadata on index
     index.metadata = protobuf::MessageField::some(metadata);
 
-    // Collect all entities by file
-    let mut file_to_symbols: HashMap<String, Vec<(i64, SymbolNode)>> = HashMap::new();
-    let mut file_to_references: HashMap<String, Vec<super::ReferenceNode>> = HashMap::new();
+    // Entities grouped by file, with documents ordered by path
+    let mut file_to_symbols: BTreeMap<String, Vec<SymbolNode>> = BTreeMap::new();
+    let mut file_to_references: BTreeMap<String, Vec<(i64, super::ReferenceNode)>> =
+        BTreeMap::new();
+    let mut file_to_calls: BTreeMap<String, Vec<(i64, super::CallNode)>> = BTreeMap::new();
 
-    // Global symbol map for cross-file reference resolution
-    // Maps (FQN or name) -> SCIP symbol string
-    let mut global_symbol_map: HashMap<String, String> = HashMap::new();
+    // SCIP symbol of each symbol entity, for resolving reference and call targets
+    let mut entity_symbols: HashMap<i64, String> = HashMap::new();
 
-    // Get all entity IDs
-    let entity_ids = graph.files.backend.entity_ids()?;
+    let mut entity_ids = graph.files.backend.entity_ids()?;
+    entity_ids.sort_unstable();
     let snapshot = SnapshotId::current();
 
-    // First pass: collect symbols and references by file
+    // First pass: collect symbols, references, and calls by file
     for entity_id in entity_ids {
         let entity = graph.files.backend.get_node(snapshot, entity_id)?;
 
@@ -204,39 +261,30 @@ pub fn export_scip(graph: &CodeGraph, config: &ScipExportConfig) -> Result<Vec<u
                     let language = detect_language(std::path::Path::new(&file_path))
                         .map(|l| l.as_str().to_string())
                         .unwrap_or_else(|| "unknown".to_string());
-
-                    // Build SCIP symbol for global map
-                    let fqn = symbol_node.fqn.as_deref().unwrap_or("");
-                    let scip_symbol = if !fqn.is_empty() {
-                        magellan_symbol_to_scip(fqn, &language)
-                    } else {
-                        let name = symbol_node.name.as_deref().unwrap_or("");
-                        format!("magellan {}/{}.", language, name)
-                    };
-
-                    // Add to global symbol map (keyed by both FQN and name)
-                    if !fqn.is_empty() {
-                        global_symbol_map.insert(fqn.to_string(), scip_symbol.clone());
-                    }
-                    if let Some(ref name) = symbol_node.name {
-                        global_symbol_map.insert(name.clone(), scip_symbol.clone());
-                    }
+                    entity_symbols.insert(entity_id, scip_symbol_for(&symbol_node, &language));
 
                     file_to_symbols
                         .entry(file_path)
                         .or_default()
-                        .push((entity_id, symbol_node));
+                        .push(symbol_node);
                 }
             }
             "Reference" => {
-                // Extract file from reference node
                 if let Ok(ref_node) =
                     serde_json::from_value::<super::ReferenceNode>(entity.data.clone())
                 {
                     file_to_references
                         .entry(ref_node.file.clone())
                         .or_default()
-                        .push(ref_node);
+                        .push((entity_id, ref_node));
+                }
+            }
+            "Call" => {
+                if let Ok(call_node) = serde_json::from_value::<super::CallNode>(entity.data) {
+                    file_to_calls
+                        .entry(call_node.file.clone())
+                        .or_default()
+                        .push((entity_id, call_node));
                 }
             }
             _ => {
@@ -245,90 +293,105 @@ pub fn export_scip(graph: &CodeGraph, config: &ScipExportConfig) -> Result<Vec<u
         }
     }
 
-    // Build SCIP documents
-    for (file_path, symbols) in file_to_symbols {
-        // Detect language from file path
-        let language = if let Some(lang) = detect_language(std::path::Path::new(&file_path)) {
-            lang.as_str().to_string()
-        } else {
-            "unknown".to_string()
-        };
-
-        // Create document
-        let mut document = Document::new();
-        document.relative_path = file_path.clone();
-        document.language = language.clone();
-
-        // Set position encoding to UTF-8 code units (line/col based)
-        document.position_encoding =
-            EnumOrUnknown::new(PositionEncoding::UTF8CodeUnitOffsetFromLineStart);
+    // Second pass: occurrences per file
+    let mut documents: BTreeMap<String, (DocumentOccurrences, Vec<SymbolInformation>)> =
+        BTreeMap::new();
+
+    for (file_path, symbols) in &file_to_symbols {
+        let language = detect_language(std::path::Path::new(file_path))
+            .map(|l| l.as_str().to_string())
+            .unwrap_or_else(|| "unknown".to_string());
+        let (occurrences, infos) = documents.entry(file_path.clone()).or_default();
+
+        for symbol in symbols {
+            let scip_symbol = scip_symbol_for(symbol, &language);
+            let range = scip_range(
+                symbol.start_line as u64,
+                symbol.start_col as u64,
+                symbol.end_line as u64,
+                symbol.end_col as u64,
+            );
+            *occurrences.entry((range, scip_symbol.clone())).or_default() |=
+                SymbolRole::Definition as i32;
 
-        // Add symbol occurrences (definitions)
-        for (_node_id, symbol) in &symbols {
-            let mut occurrence = Occurrence::new();
-
-            // Set range [line_start, col_start, line_end, col_end]
-            occurrence.range = vec![
-                symbol.start_line as i32,
-                symbol.start_col as i32,
-                symbol.end_line as i32,
-                symbol.end_col as i32,
-            ];
-
-            // Build SCIP symbol from FQN
-            let fqn = symbol.fqn.as_deref().unwrap_or("");
-            let scip_symbol = if !fqn.is_empty() {
-                magellan_symbol_to_scip(fqn, &language)
-            } else {
-                // Fallback to simple name
-                let name = symbol.name.as_deref().unwrap_or("");
-                format!("magellan {}/{}.", language, name)
-            };
-
-            occurrence.symbol = scip_symbol.clone();
-
-            // Set symbol roles: Definition = 1
-            occurrence.symbol_roles = SymbolRole::Definition as i32;
-
-            document.occurrences.push(occurrence);
-
-            // Add symbol information to document.symbols
             let mut sym_info = SymbolInformation::new();
             sym_info.kind = EnumOrUnknown::new(map_symbol_kind(&symbol.kind));
-
             if let Some(ref name) = symbol.name {
                 sym_info.display_name = name.clone();
             }
-
             sym_info.symbol = scip_symbol;
-
-            document.symbols.push(sym_info);
+            infos.push(sym_info);
         }
+    }
 
-        // Add reference occurrences
-        if let Some(refs) = file_to_references.get(&file_path) {
-            for ref_node in refs {
-                let mut occurrence = Occurrence::new();
+    for (file_path, refs) in &file_to_references {
+        for (entity_id, ref_node) in refs {
+            let Some(target) = edge_target(graph, *entity_id, "REFERENCES")? else {
+                continue;
+            };
+            let Some(scip_symbol) = entity_symbols.get(&target) else {
+                continue;
+            };
+            let range = scip_range(
+                ref_node.start_line,
+                ref_node.start_col,
+                ref_node.end_line,
+                ref_node.end_col,
+            );
+            let (occurrences, _) = documents.entry(file_path.clone()).or_default();
+            *occurrences.entry((range, scip_symbol.clone())).or_default() |=
+                reference_roles(ref_node.kind);
+        }
+    }
 
-                occurrence.range = vec![
-                    ref_node.start_line as i32,
-                    ref_node.start_col as i32,
-                    ref_node.end_line as i32,
-                    ref_node.end_col as i32,
-                ];
+    for (file_path, calls) in &file_to_calls {
+        for (entity_id, call_node) in calls {
+            let Some(target) = edge_target(graph, *entity_id, "CALLS")? else {
+                continue;
+            };
+            let Some(scip_symbol) = entity_symbols.get(&target) else {
+                continue;
+            };
+            let range = scip_range(
+                call_node.start_line,
+                call_node.start_col,
+                call_node.end_line,
+                call_node.end_col,
+            );
+            let (occurrences, _) = documents.entry(file_path.clone()).or_default();
+            *occurrences.entry((range, scip_symbol.clone())).or_default() |=
+                SymbolRole::ReadAccess as i32;
+        }
+    }
 
-                // Set symbol roles: ReadAccess = 8 (used for references)
-                occurrence.symbol_roles = SymbolRole::ReadAccess as i32;
+    // Build SCIP documents
+    for (file_path, (occurrences, mut infos)) in documents {
+        let mut document = Document::new();
+        document.language = detect_language(std::path::Path::new(&file_path))
+            .map(|l| l.as_str().to_string())
+            .unwrap_or_else(|| "unknown".to_string());
+        document.relative_path = file_path;
 
-                // For references, we don't have direct access to the target FQN.
-                // Use a placeholder indicating the reference couldn't be resolved.
-                // In a full implementation, we'd store the target symbol's FQN in the reference.
-                occurrence.symbol = "magellan unknown/.".to_string();
+        // Set position encoding to UTF-8 code units (line/col based)
+        document.position_encoding =
+            EnumOrUnknown::new(PositionEncoding::UTF8CodeUnitOffsetFromLineStart);
 
-                document.occurrences.push(occurrence);
-            }
+        for ((range, symbol), roles) in occurrences {
+            let mut occurrence = Occurrence::new();
+            occurrence.range = range;
+            occurrence.symbol = symbol;
+            occurrence.symbol_roles = roles;
+            document.occurrences.push(occurrence);
         }
 
+        infos.sort_by(|a, b| {
+            a.symbol
+                .cmp(&b.symbol)
+                .then_with(|| a.display_name.cmp(&b.display_name))
+        });
+        infos.dedup_by(|a, b| a.symbol == b.symbol);
+        document.symbols = infos;
+
         index.documents.push(document);
     }
 
@@ -0,0 +1,241 @@
+//! Flat SQLite export for analytics
+//!
+//! Writes a fresh SQLite database with one plain table per record type, so
+//! the graph can be queried with ordinary SQL instead of through the
+//! sqlitegraph node/edge schema. This is a one-way export: nothing reads the
+//! file back.
+//!
+//! # Tables
+//!
+//! | Table | Contents |
+//! |-------|----------|
+//! | `files` | `path`, `hash` |
+//! | `symbols` | `symbol_id`, `name`, `kind`, `kind_normalized`, `display_fqn`, `canonical_fqn`, `file_path`, span columns |
+//! | `calls` | `file_path`, `caller`, `callee`, `caller_symbol_id`, `callee_symbol_id`, span columns |
+//! | `references` | `file_path`, `referenced_symbol`, span columns |
+//!
+//! Span columns are `byte_start`, `byte_end`, `start_line`, `start_col`,
+//! `end_line`, `end_col` (lines 1-indexed, columns 0-indexed bytes). Rows are
+//! inserted in a fixed order and no timestamps are stored, so the same graph
+//! always produces the same rows. `references` is an SQL keyword and must be
+//! quoted in queries (`SELECT * FROM "references"`).
+
+use anyhow::{Context, Result};
+use rusqlite::{params, Connection};
+use std::path::{Path, PathBuf};
+
+use super::{collect_graph, CodeGraph, ExportConfig};
+
+/// Schema of the flat database
+const FLAT_SCHEMA: &str = r#"
+CREATE TABLE files (
+    path TEXT PRIMARY KEY,
+    hash TEXT NOT NULL
+);
+CREATE TABLE symbols (
+    id INTEGER PRIMARY KEY,
+    symbol_id TEXT,
+    name TEXT,
+    kind TEXT NOT NULL,
+    kind_normalized TEXT,
+    display_fqn TEXT,
+    canonical_fqn TEXT,
+    file_path TEXT NOT NULL,
+    byte_start INTEGER NOT NULL,
+    byte_end INTEGER NOT NULL,
+    start_line INTEGER NOT NULL,
+    start_col INTEGER NOT NULL,
+    end_line INTEGER NOT NULL,
+    end_col INTEGER NOT NULL
+);
+CREATE TABLE calls (
+    id INTEGER PRIMARY KEY,
+    file_path TEXT NOT NULL,
+    caller TEXT NOT NULL,
+    callee TEXT NOT NULL,
+    caller_symbol_id TEXT,
+    callee_symbol_id TEXT,
+    byte_start INTEGER NOT NULL,
+    byte_end INTEGER NOT NULL,
+    start_line INTEGER NOT NULL,
+    start_col INTEGER NOT NULL,
+    end_line INTEGER NOT NULL,
+    end_col INTEGER NOT NULL
+);
+CREATE TABLE "references" (
+    id INTEGER PRIMARY KEY,
+    file_path TEXT NOT NULL,
+    referenced_symbol TEXT NOT NULL,
+    byte_start INTEGER NOT NULL,
+    byte_end INTEGER NOT NULL,
+    start_line INTEGER NOT NULL,
+    start_col INTEGER NOT NULL,
+    end_line INTEGER NOT NULL,
+    end_col INTEGER NOT NULL
+);
+CREATE INDEX idx_symbols_file_path ON symbols(file_path);
+CREATE INDEX idx_symbols_name ON symbols(name);
+CREATE INDEX idx_symbols_symbol_id ON symbols(symbol_id);
+CREATE INDEX idx_calls_file_path ON calls(file_path);
+CREATE INDEX idx_calls_caller ON calls(caller);
+CREATE INDEX idx_calls_callee ON calls(callee);
+CREATE INDEX idx_references_file_path ON "references"(file_path);
+CREATE INDEX idx_references_symbol ON "references"(referenced_symbol);
+"#;
+
+/// Row counts written by [`export_sqlite_flat`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FlatExportCounts {
+    pub files: usize,
+    pub symbols: usize,
+    pub calls: usize,
+    pub references: usize,
+}
+
+/// Write the graph to a new flat SQLite database at `output`
+///
+/// The database is built next to `output` and renamed into place, so an
+/// existing file is replaced only once the export has succeeded.
+/// `--no-symbols`, `--no-calls` and `--no-references` leave the matching
+/// table empty; the `files` table is always filled.
+pub fn export_sqlite_flat(
+    graph: &mut CodeGraph,
+    config: &ExportConfig,
+    output: &Path,
+) -> Result<FlatExportCounts> {
+    let mut export = collect_graph(graph)?;
+
+    // collect_graph orders by name; flat tables follow source order instead
+    export.symbols.sort_by(|a, b| {
+        (&a.file, a.byte_start, a.byte_end, &a.name, &a.symbol_id).cmp(&(
+            &b.file,
+            b.byte_start,
+            b.byte_end,
+            &b.name,
+            &b.symbol_id,
+        ))
+    });
+    export.calls.sort_by(|a, b| {
+        (&a.file, a.byte_start, &a.caller, &a.callee).cmp(&(
+            &b.file,
+            b.byte_start,
+            &b.caller,
+            &b.callee,
+        ))
+    });
+    export.references.sort_by(|a, b| {
+        (&a.file, a.byte_start, &a.referenced_symbol).cmp(&(
+            &b.file,
+            b.byte_start,
+            &b.referenced_symbol,
+        ))
+    });
+
+    let staging = staging_path(output);
+    remove_if_exists(&staging)?;
+    let mut conn = Connection::open(&staging)
+        .with_context(|| format!("Failed to create {}", staging.display()))?;
+    conn.execute_batch(FLAT_SCHEMA)?;
+
+    let mut counts = FlatExportCounts::default();
+    let tx = conn.transaction()?;
+    {
+        let mut insert = tx.prepare("INSERT INTO files (path, hash) VALUES (?1, ?2)")?;
+        for file in &export.files {
+            insert.execute(params![file.path, file.hash])?;
+            counts.files += 1;
+        }
+    }
+    if config.include_symbols {
+        let mut insert = tx.prepare(
+            "INSERT INTO symbols (symbol_id, name, kind, kind_normalized, display_fqn, \
+             canonical_fqn, file_path, byte_start, byte_end, start_line, start_col, \
+             end_line, end_col) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)",
+        )?;
+        for symbol in &export.symbols {
+            insert.execute(params![
+                symbol.symbol_id,
+                symbol.name,
+                symbol.kind,
+                symbol.kind_normalized,
+                symbol.display_fqn,
+                symbol.canonical_fqn,
+                symbol.file,
+                symbol.byte_start as i64,
+                symbol.byte_end as i64,
+                symbol.start_line as i64,
+                symbol.start_col as i64,
+                symbol.end_line as i64,
+                symbol.end_col as i64,
+            ])?;
+            counts.symbols += 1;
+        }
+    }
+    if config.include_calls {
+        let mut insert = tx.prepare(
+            "INSERT INTO calls (file_path, caller, callee, caller_symbol_id, callee_symbol_id, \
+             byte_start, byte_end, start_line, start_col, end_line, end_col) \
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
+        )?;
+        for call in &export.calls {
+            insert.execute(params![
+                call.file,
+                call.caller,
+                call.callee,
+                call.caller_symbol_id,
+                call.callee_symbol_id,
+                call.byte_start as i64,
+                call.byte_end as i64,
+                call.start_line as i64,
+                call.start_col as i64,
+                call.end_line as i64,
+                call.end_col as i64,
+            ])?;
+            counts.calls += 1;
+        }
+    }
+    if config.include_references {
+        let mut insert = tx.prepare(
+            "INSERT INTO \"references\" (file_path, referenced_symbol, byte_start, byte_end, \
+             start_line, start_col, end_line, end_col) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+        )?;
+        for reference in &export.references {
+            insert.execute(params![
+                reference.file,
+                reference.referenced_symbol,
+                reference.byte_start as i64,
+                reference.byte_end as i64,
+                reference.start_line as i64,
+                reference.start_col as i64,
+                reference.end_line as i64,
+                reference.end_col as i64,
+            ])?;
+            counts.references += 1;
+        }
+    }
+    tx.commit()?;
+    conn.close().map_err(|(_, e)| e)?;
+
+    std::fs::rename(&staging, output).with_context(|| {
+        format!(
+            "Failed to move {} to {}",
+            staging.display(),
+            output.display()
+        )
+    })?;
+    Ok(counts)
+}
+
+/// Sibling path the database is built at before being renamed to `output`
+fn staging_path(output: &Path) -> PathBuf {
+    let mut name = output.file_name().unwrap_or_default().to_os_string();
+    name.push(".tmp");
+    output.with_file_name(name)
+}
+
+fn remove_if_exists(path: &Path) -> Result<()> {
+    match std::fs::remove_file(path) {
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.into()),
+        _ => Ok(()),
+    }
+}
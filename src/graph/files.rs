@@ -20,21 +20,27 @@
 //! For concurrent file operations, use external synchronization
 //! (e.g., mutex wrapper around CodeGraph).
 
+use crate::graph::content_hash::{HashAlgorithm, HashNormalization};
+use crate::graph::path_style::{clean_path, PathSettings, PathStyle};
+use crate::graph::schema::FileNode;
+use crate::ingest::{SymbolFact, SymbolKind};
 use anyhow::Result;
 use sqlitegraph::{GraphBackend, NodeId, NodeSpec, SnapshotId};
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::time::{SystemTime, UNIX_EPOCH};
-use xxhash_rust::xxh3::Xxh3;
-
-use crate::graph::schema::FileNode;
-use crate::ingest::{SymbolFact, SymbolKind};
 
 /// File operations for CodeGraph
 pub struct FileOps {
     pub backend: Arc<dyn GraphBackend>,
     pub file_index: HashMap<String, NodeId>,
+    /// Path style recorded for the database; `None` stores paths as given
+    pub(crate) path_settings: Option<PathSettings>,
+    /// Normalization applied to contents before hashing
+    pub(crate) hash_normalization: HashNormalization,
+    /// Algorithm for newly computed hashes
+    pub(crate) hash_algorithm: HashAlgorithm,
 }
 
 /// Normalize a path to absolute form for consistent indexing
@@ -56,34 +62,14 @@ pub(crate) fn normalize_path_for_index(path: &str) -> String {
     let path_buf = PathBuf::from(path);
     if path_buf.is_absolute() {
         // Strip ./ and other non-semantic components from absolute paths
-        let mut normalized = PathBuf::new();
-        for component in path_buf.components() {
-            match component {
-                std::path::Component::CurDir => {} // skip .
-                std::path::Component::ParentDir => {
-                    normalized.pop();
-                }
-                other => normalized.push(other),
-            }
-        }
-        return normalized.to_string_lossy().to_string();
+        return clean_path(&path_buf).to_string_lossy().to_string();
     }
 
     // Relative path: make absolute from current directory (don't canonicalize - file may not exist)
     if let Ok(cwd) = std::env::current_dir() {
-        let joined = cwd.join(&path_buf);
-        // Normalize the joined path to remove ./ segments
-        let mut normalized = PathBuf::new();
-        for component in joined.components() {
-            match component {
-                std::path::Component::CurDir => {}
-                std::path::Component::ParentDir => {
-                    normalized.pop();
-                }
-                other => normalized.push(other),
-            }
-        }
-        return normalized.to_string_lossy().to_string();
+        return clean_path(&cwd.join(&path_buf))
+            .to_string_lossy()
+            .to_string();
     }
 
     // Fallback: return as-is
@@ -99,10 +85,37 @@ impl FileOps {
             .as_secs() as i64
     }
 
+    /// Key for `file_index` under the current path style
+    ///
+    /// Relative style keys by the root-relative path; otherwise the key is
+    /// `normalize_path_for_index(path)`.
+    pub(crate) fn index_key(&self, path: &str) -> String {
+        match &self.path_settings {
+            Some(settings) if settings.style == PathStyle::Relative => settings.apply(path),
+            _ => normalize_path_for_index(path),
+        }
+    }
+
+    /// Form in which `path` is written to the database
+    pub(crate) fn stored_path(&self, path: &str) -> String {
+        match &self.path_settings {
+            Some(settings) => settings.apply(path),
+            None => path.to_string(),
+        }
+    }
+
+    /// Location on disk of a stored path
+    pub(crate) fn disk_path(&self, path: &str) -> PathBuf {
+        match &self.path_settings {
+            Some(settings) => settings.root.join(path),
+            None => PathBuf::from(path),
+        }
+    }
+
     /// Get filesystem modification time for a file path
     ///
     /// Returns 0 if file doesn't exist or mtime cannot be read
-    fn get_file_mtime(path: &str) -> i64 {
+    fn get_file_mtime(path: &Path) -> i64 {
         std::fs::metadata(path)
             .and_then(|m| m.modified())
             .and_then(|t| t.duration_since(UNIX_EPOCH).map_err(std::io::Error::other))
@@ -116,7 +129,7 @@ impl FileOps {
     /// should find all existing File nodes. Returns None if not found.
     pub fn find_file_node(&mut self, path: &str) -> Result<Option<NodeId>> {
         // Normalize path to match how files are stored after index_file
-        let normalized_path = normalize_path_for_index(path);
+        let normalized_path = self.index_key(path);
         Ok(self.file_index.get(&normalized_path).copied())
     }
 
@@ -126,7 +139,7 @@ impl FileOps {
     /// per path), this scans all entities and returns every File node whose path
     /// matches. Use this when cleaning up duplicates.
     pub fn find_all_file_nodes(&self, path: &str) -> Result<Vec<(NodeId, FileNode)>> {
-        let normalized_path = normalize_path_for_index(path);
+        let normalized_path = self.index_key(path);
         let mut results = Vec::new();
         let ids = self.backend.entity_ids()?;
         let snapshot = SnapshotId::current();
@@ -137,7 +150,7 @@ impl FileOps {
             };
             if node.kind == "File" {
                 if let Ok(file_node) = serde_json::from_value::<FileNode>(node.data) {
-                    let stored_path = normalize_path_for_index(&file_node.path);
+                    let stored_path = self.index_key(&file_node.path);
                     if stored_path == normalized_path {
                         results.push((NodeId::from(id), file_node));
                     }
@@ -153,10 +166,10 @@ impl FileOps {
     /// indexing bugs), all are deleted before creating the new one.
     pub fn find_or_create_file_node(&mut self, path: &str, hash: &str) -> Result<NodeId> {
         let now = Self::now();
-        let mtime = Self::get_file_mtime(path);
+        let mtime = Self::get_file_mtime(&self.disk_path(path));
 
-        // Normalize path to absolute canonical form for consistent indexing
-        let normalized_path = normalize_path_for_index(path);
+        // Normalize path to the index key (absolute unless the style is relative)
+        let normalized_path = self.index_key(path);
 
         // Find ALL file nodes with this path (not just the one in file_index)
         let all_existing = self.find_all_file_nodes(&normalized_path)?;
@@ -180,10 +193,12 @@ impl FileOps {
                 serde_json::from_value(node.data.clone()).unwrap_or_else(|_| FileNode {
                     path: path.to_string(),
                     hash: hash.to_string(),
+                    hash_algo: self.hash_algorithm,
                     last_indexed_at: now,
                     last_modified: mtime,
                 });
             file_node.hash = hash.to_string();
+            file_node.hash_algo = self.hash_algorithm;
             file_node.last_indexed_at = now;
             file_node.last_modified = mtime;
 
@@ -212,6 +227,7 @@ impl FileOps {
             let file_node = FileNode {
                 path: normalized_path.to_string(),
                 hash: hash.to_string(),
+                hash_algo: self.hash_algorithm,
                 last_indexed_at: now,
                 last_modified: mtime,
             };
@@ -249,8 +265,8 @@ impl FileOps {
 
             if node.kind == "File" {
                 if let Ok(file_node) = serde_json::from_value::<FileNode>(node.data) {
-                    // Normalize path to match normalize_path_for_index() format
-                    let normalized_path = normalize_path_for_index(&file_node.path);
+                    // Normalize path to match index_key() format
+                    let normalized_path = self.index_key(&file_node.path);
                     self.file_index.insert(normalized_path, NodeId::from(id));
                 }
             }
@@ -259,11 +275,16 @@ impl FileOps {
         Ok(())
     }
 
-    /// Compute xxHash3-128 of file contents
+    /// Hash file contents with the database's algorithm, after hash normalization
     pub fn compute_hash(&self, source: &[u8]) -> String {
-        let mut hasher = Xxh3::new();
-        hasher.update(source);
-        format!("{:032x}", hasher.digest())
+        self.compute_hash_with(source, self.hash_algorithm)
+    }
+
+    /// Hash file contents with `algorithm`, after hash normalization
+    ///
+    /// Used to compare against a File node tagged with another algorithm.
+    pub fn compute_hash_with(&self, source: &[u8], algorithm: HashAlgorithm) -> String {
+        algorithm.digest(&self.hash_normalization.apply(source))
     }
 
     /// Convert a symbol node to SymbolFact
@@ -293,6 +314,7 @@ impl FileOps {
             "Union" => SymbolKind::Union,
             "Namespace" => SymbolKind::Namespace,
             "TypeAlias" => SymbolKind::TypeAlias,
+            "Macro" => SymbolKind::Macro,
             "Unknown" => SymbolKind::Unknown,
             _ => SymbolKind::Unknown,
         };
@@ -316,6 +338,11 @@ impl FileOps {
             start_col: symbol_node.start_col,
             end_line: symbol_node.end_line,
             end_col: symbol_node.end_col,
+            signature: symbol_node.signature.clone(),
+            is_test: symbol_node.is_test,
+            visibility: symbol_node.visibility.clone(),
+            deprecated: symbol_node.deprecated,
+            deprecation_message: symbol_node.deprecation_message.clone(),
         }))
     }
 
@@ -5,6 +5,11 @@
 //! 2. Gitignore-style rules (.gitignore, .ignore)
 //! 3. CLI include patterns (if any provided)
 //! 4. CLI exclude patterns
+//! 5. Maximum file size (if set)
+//! 6. Generated-file headers (if enabled)
+//!
+//! Files in no supported language are skipped unless their extension is in
+//! the non-source set (`--index-non-source`), which indexes them without symbols.
 //!
 //! All filtering is pure function: same inputs always produce same output.
 
@@ -14,6 +19,46 @@ use std::path::{Path, PathBuf};
 
 use crate::diagnostics::{SkipReason, WatchDiagnostic};
 use crate::ingest::detect_language;
+use crate::log_warn;
+
+/// Lines at the top of a file searched for a generated-file marker.
+pub const GENERATED_HEADER_LINES: usize = 5;
+
+/// Markers that tools put in the header of files they generate.
+const GENERATED_MARKERS: &[&str] = &[
+    "@generated",
+    "Code generated by",
+    "<auto-generated",
+    "Generated by the protocol buffer compiler",
+];
+
+/// Whether the first [`GENERATED_HEADER_LINES`] lines of `source` carry a
+/// generated-file marker.
+pub fn is_generated_source(source: &[u8]) -> bool {
+    source
+        .split(|&b| b == b'\n')
+        .take(GENERATED_HEADER_LINES)
+        .map(String::from_utf8_lossy)
+        .any(|line| GENERATED_MARKERS.iter().any(|marker| line.contains(marker)))
+}
+
+/// [`is_generated_source`] on the header of the file at `path`.
+fn has_generated_header(path: &Path) -> bool {
+    use std::io::Read;
+
+    let mut header = Vec::with_capacity(4096);
+    match std::fs::File::open(path) {
+        Ok(file) => {
+            file.take(4096).read_to_end(&mut header).is_ok() && is_generated_source(&header)
+        }
+        Err(_) => false,
+    }
+}
+
+/// Extensions indexed by `--index-non-source` when no set is configured:
+/// schema, API definition and config files.
+pub const DEFAULT_NON_SOURCE_EXTENSIONS: &[&str] =
+    &["proto", "graphql", "gql", "toml", "yaml", "yml", "json"];
 
 /// Internal directories that are always ignored (hard-coded).
 const INTERNAL_IGNORE_DIRS: &[&str] = &[
@@ -48,6 +93,13 @@ pub struct FileFilter {
     include_patterns: Vec<globset::GlobMatcher>,
     /// CLI exclude patterns
     exclude_patterns: Vec<globset::GlobMatcher>,
+    /// Files larger than this many bytes are skipped (None = no limit)
+    max_file_size: Option<u64>,
+    /// Skip files whose header marks them as generated
+    skip_generated: bool,
+    /// Extensions (without the dot, lowercase) of files kept despite having
+    /// no supported language; they are indexed without symbols
+    non_source_extensions: Vec<String>,
 }
 
 impl FileFilter {
@@ -86,9 +138,39 @@ impl FileFilter {
             gitignore,
             include_patterns: include_matchers,
             exclude_patterns: exclude_matchers,
+            max_file_size: None,
+            skip_generated: false,
+            non_source_extensions: Vec::new(),
         })
     }
 
+    /// Skip files larger than `max_file_size` bytes (`None` removes the limit).
+    pub fn with_max_file_size(mut self, max_file_size: Option<u64>) -> Self {
+        self.max_file_size = max_file_size;
+        self
+    }
+
+    /// Skip files with a generated-file header (see [`is_generated_source`]).
+    pub fn with_skip_generated(mut self, skip_generated: bool) -> Self {
+        self.skip_generated = skip_generated;
+        self
+    }
+
+    /// Keep files with these extensions even though no language supports them
+    /// (`--index-non-source`); empty keeps skipping them.
+    pub fn with_non_source_extensions(mut self, extensions: &[String]) -> Self {
+        self.non_source_extensions = extensions.to_vec();
+        self
+    }
+
+    /// Drop the .gitignore/.ignore rules when `enabled` is false (`--no-gitignore`).
+    pub fn with_gitignore(mut self, enabled: bool) -> Self {
+        if !enabled {
+            self.gitignore = None;
+        }
+        self
+    }
+
     /// Load gitignore-style rules from .gitignore and .ignore files.
     fn load_gitignore(root: &Path) -> Result<Option<Gitignore>> {
         let mut builder = ignore::gitignore::GitignoreBuilder::new(root);
@@ -99,7 +181,7 @@ impl FileFilter {
             // The builder.add() returns Option<Error> - Some(Error) if failed
             if let Some(err) = builder.add(&gitignore_path) {
                 // Log but don't fail - malformed gitignore shouldn't crash indexing
-                eprintln!("Warning: Failed to load .gitignore: {}", err);
+                log_warn!("Failed to load .gitignore: {}", err);
             }
         }
 
@@ -107,7 +189,7 @@ impl FileFilter {
         let ignore_path = root.join(".ignore");
         if ignore_path.exists() {
             if let Some(err) = builder.add(&ignore_path) {
-                eprintln!("Warning: Failed to load .ignore: {}", err);
+                log_warn!("Failed to load .ignore: {}", err);
             }
         }
 
@@ -198,8 +280,8 @@ impl FileFilter {
             }
         }
 
-        // 4. Check if language is supported
-        if detect_language(path).is_none() {
+        // 4. Check if language is supported, or the file is tracked as non-source
+        if detect_language(path).is_none() && !self.is_non_source(path) {
             return Some(SkipReason::UnsupportedLanguage);
         }
 
@@ -221,10 +303,29 @@ impl FileFilter {
             }
         }
 
+        // 7. Size limit
+        if let Some(max) = self.max_file_size {
+            if std::fs::metadata(path).is_ok_and(|meta| meta.len() > max) {
+                return Some(SkipReason::FileTooLarge);
+            }
+        }
+
+        // 8. Generated-file header
+        if self.skip_generated && has_generated_header(path) {
+            return Some(SkipReason::Generated);
+        }
+
         // Passed all filters
         None
     }
 
+    /// Whether the extension of `path` is in the non-source set.
+    fn is_non_source(&self, path: &Path) -> bool {
+        path.extension()
+            .map(|ext| ext.to_string_lossy().to_lowercase())
+            .is_some_and(|ext| self.non_source_extensions.contains(&ext))
+    }
+
     /// Check if a path matches internal ignore rules.
     fn is_internal_ignore(&self, path: &Path) -> bool {
         // Check if it's a database file by looking at the full filename
@@ -282,6 +383,81 @@ impl FileFilter {
     }
 }
 
+/// Characters that make a `--file` filter a glob rather than a substring.
+const GLOB_METACHARS: &[char] = &['*', '?', '[', '{'];
+
+/// A `--file` filter over stored file paths (`chunks`, `chunk-by-symbol`,
+/// `export --filter-file`).
+///
+/// A pattern containing a glob metacharacter (`*`, `?`, `[`, `{`) is a glob
+/// matched against the path relative to the project root (see
+/// [`FilePattern::relative_to`]), where `*` stops at `/` and `**` spans
+/// directories, so `*.rs` matches only top-level paths and `src/**/*.rs`
+/// matches every Rust file under `src/`. Paths outside the root, or every
+/// path when no root is known, are matched as stored. Any other pattern
+/// matches paths containing it as a substring.
+#[derive(Debug, Clone)]
+pub enum FilePattern {
+    /// Plain string, matched as a substring
+    Substring(String),
+    /// Glob, matched against the whole root-relative path
+    Glob {
+        matcher: globset::GlobMatcher,
+        /// Project root stripped from absolute paths before matching
+        root: Option<PathBuf>,
+    },
+}
+
+impl FilePattern {
+    /// Parse `pattern`, failing if it looks like a glob but does not compile
+    pub fn new(pattern: &str) -> Result<Self> {
+        if !pattern.contains(GLOB_METACHARS) {
+            return Ok(FilePattern::Substring(pattern.to_string()));
+        }
+        let glob = globset::GlobBuilder::new(pattern)
+            .literal_separator(true)
+            .build()
+            .map_err(|e| anyhow::anyhow!("Invalid glob pattern '{}': {}", pattern, e))?;
+        Ok(FilePattern::Glob {
+            matcher: glob.compile_matcher(),
+            root: None,
+        })
+    }
+
+    /// Match globs against paths relative to `root`, the database's
+    /// recorded project root
+    pub fn relative_to(self, root: Option<&Path>) -> Self {
+        match self {
+            FilePattern::Glob { matcher, .. } => FilePattern::Glob {
+                matcher,
+                root: root.map(Path::to_path_buf),
+            },
+            substring => substring,
+        }
+    }
+
+    /// Whether the pattern is matched as a glob
+    pub fn is_glob(&self) -> bool {
+        matches!(self, FilePattern::Glob { .. })
+    }
+
+    /// Whether the stored file path `path` matches
+    pub fn matches(&self, path: &str) -> bool {
+        match self {
+            FilePattern::Substring(needle) => path.contains(needle.as_str()),
+            FilePattern::Glob { matcher, root } => {
+                let relative = root
+                    .as_deref()
+                    .and_then(|root| Path::new(path).strip_prefix(root).ok());
+                match relative {
+                    Some(relative) => matcher.is_match(relative),
+                    None => matcher.is_match(path),
+                }
+            }
+        }
+    }
+}
+
 /// Create a diagnostic for a skipped file.
 pub fn skip_diagnostic(root: &Path, path: &Path, reason: SkipReason) -> WatchDiagnostic {
     let rel_path = path
@@ -298,6 +474,49 @@ mod tests {
     use std::fs;
     use tempfile::TempDir;
 
+    #[test]
+    fn test_file_pattern_glob_and_substring() {
+        let nested = FilePattern::new("src/**/*.rs").unwrap();
+        assert!(nested.is_glob());
+        assert!(nested.matches("src/main.rs"));
+        assert!(nested.matches("src/graph/ops.rs"));
+        assert!(!nested.matches("tests/cli.rs"));
+
+        let top_level = FilePattern::new("*.rs").unwrap();
+        assert!(top_level.matches("build.rs"));
+        assert!(!top_level.matches("src/main.rs"));
+
+        // No metacharacters: substring match anywhere in the path
+        let plain = FilePattern::new("graph").unwrap();
+        assert!(!plain.is_glob());
+        assert!(plain.matches("src/graph/ops.rs"));
+        assert!(!plain.matches("src/main.rs"));
+
+        assert!(FilePattern::new("src/[a.rs").is_err());
+    }
+
+    #[test]
+    fn test_file_pattern_glob_relative_to_root() {
+        let root = Path::new("/work/proj");
+        let nested = FilePattern::new("src/**/*.rs")
+            .unwrap()
+            .relative_to(Some(root));
+        assert!(nested.matches("/work/proj/src/lib.rs"));
+        assert!(nested.matches("src/graph/ops.rs"));
+        assert!(!nested.matches("/work/proj/tests/cli.rs"));
+
+        let top_level = FilePattern::new("*.rs").unwrap().relative_to(Some(root));
+        assert!(top_level.matches("/work/proj/build.rs"));
+        assert!(!top_level.matches("/work/proj/src/lib.rs"));
+
+        // Paths outside the root are matched as stored
+        assert!(!nested.matches("/elsewhere/src/lib.rs"));
+        assert!(FilePattern::new("**/src/*.rs")
+            .unwrap()
+            .relative_to(Some(root))
+            .matches("/elsewhere/src/lib.rs"));
+    }
+
     #[test]
     fn test_internal_ignore_dirs() {
         let temp_dir = TempDir::new().unwrap();
@@ -428,6 +647,10 @@ mod tests {
             Some(SkipReason::IgnoredByGitignore),
             "build/output.rs should be ignored by build/ pattern"
         );
+
+        // --no-gitignore drops the rules
+        let filter = filter.with_gitignore(false);
+        assert_eq!(filter.should_skip(&root.join("ignored.rs")), None);
     }
 
     #[test]
@@ -542,6 +765,80 @@ mod tests {
 
         assert_eq!(diagnostic.path(), "target/lib.rs");
     }
+
+    #[test]
+    fn test_max_file_size() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        fs::write(root.join("small.rs"), "fn a() {}").unwrap();
+        fs::write(root.join("big.rs"), "fn b() {}\n".repeat(100)).unwrap();
+
+        let filter = FileFilter::new(root, &[], &[])
+            .unwrap()
+            .with_max_file_size(Some(64));
+
+        assert_eq!(filter.should_skip(&root.join("small.rs")), None);
+        assert_eq!(
+            filter.should_skip(&root.join("big.rs")),
+            Some(SkipReason::FileTooLarge)
+        );
+    }
+
+    #[test]
+    fn test_non_source_extensions_are_kept() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        fs::write(root.join("api.proto"), "syntax = \"proto3\";").unwrap();
+        fs::write(root.join("Schema.GraphQL"), "type Query { a: Int }").unwrap();
+        fs::write(root.join("notes.txt"), "hello").unwrap();
+
+        let filter = FileFilter::new(root, &[], &[]).unwrap();
+        assert_eq!(
+            filter.should_skip(&root.join("api.proto")),
+            Some(SkipReason::UnsupportedLanguage)
+        );
+
+        let filter =
+            filter.with_non_source_extensions(&["proto".to_string(), "graphql".to_string()]);
+        assert_eq!(filter.should_skip(&root.join("api.proto")), None);
+        assert_eq!(filter.should_skip(&root.join("Schema.GraphQL")), None);
+        assert_eq!(
+            filter.should_skip(&root.join("notes.txt")),
+            Some(SkipReason::UnsupportedLanguage)
+        );
+    }
+
+    #[test]
+    fn test_skip_generated() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        fs::write(root.join("gen.rs"), "// @generated by protoc\nfn a() {}").unwrap();
+        fs::write(root.join("plain.rs"), "fn b() {}").unwrap();
+
+        let filter = FileFilter::new(root, &[], &[]).unwrap();
+        assert_eq!(filter.should_skip(&root.join("gen.rs")), None);
+
+        let filter = filter.with_skip_generated(true);
+        assert_eq!(
+            filter.should_skip(&root.join("gen.rs")),
+            Some(SkipReason::Generated)
+        );
+        assert_eq!(filter.should_skip(&root.join("plain.rs")), None);
+    }
+
+    #[test]
+    fn test_is_generated_source_only_checks_header() {
+        assert!(is_generated_source(
+            b"// Code generated by protoc-gen-go. DO NOT EDIT.\npackage pb\n"
+        ));
+        assert!(is_generated_source(
+            b"# -*- coding: utf-8 -*-\n# Generated by the protocol buffer compiler.  DO NOT EDIT!\n"
+        ));
+        assert!(!is_generated_source(
+            b"fn a() {}\n\n\n\n\n\n// @generated\n"
+        ));
+        assert!(!is_generated_source(b"fn a() {}\n"));
+    }
 }
 
 #[test]
@@ -0,0 +1,145 @@
+//! Fuzzy subsequence matching for `find --fuzzy`
+//!
+//! A query matches a candidate when its characters appear in the candidate in
+//! order, compared case-insensitively (`hndmsg` matches `handle_message`).
+//! Matches are scored so that tight, prefix-anchored matches rank first:
+//!
+//! | Component | Points |
+//! |-----------|--------|
+//! | Density   | `100 * query_len / span_len`, where the span runs from the first to the last matched character |
+//! | Prefix    | `+50` when the first query character matches the candidate's first character |
+//! | Boundary  | `+10` per matched character that starts a word (after `_`, `:`, `.`, `-`, `/`, a space, or a lower-to-upper case change) |
+//! | Length    | `-1` per candidate character beyond the query length, capped at 20 |
+//!
+//! The span is the shortest one ending at the leftmost possible end: a forward
+//! pass finds where the match can end, a backward pass from there finds the
+//! latest start. Scoring is a pure function of the two strings, so results are
+//! deterministic; callers break ties by name and location.
+
+/// Score awarded when every span character is matched
+const DENSITY_POINTS: i64 = 100;
+/// Bonus when the match starts at the candidate's first character
+const PREFIX_BONUS: i64 = 50;
+/// Bonus per matched character at the start of a word
+const BOUNDARY_BONUS: i64 = 10;
+/// Maximum penalty for unmatched candidate length
+const MAX_LENGTH_PENALTY: i64 = 20;
+
+/// Score `candidate` against `query`, or `None` if it is not a subsequence match
+///
+/// Higher is better. An empty query matches nothing.
+pub fn fuzzy_score(query: &str, candidate: &str) -> Option<i64> {
+    let query: Vec<char> = query.chars().flat_map(char::to_lowercase).collect();
+    if query.is_empty() {
+        return None;
+    }
+    let chars: Vec<char> = candidate.chars().collect();
+    let folded: Vec<char> = chars
+        .iter()
+        .map(|c| c.to_lowercase().next().unwrap_or(*c))
+        .collect();
+
+    // Forward pass: leftmost position where the whole query has matched
+    let mut qi = 0;
+    let mut end = None;
+    for (i, c) in folded.iter().enumerate() {
+        if *c == query[qi] {
+            qi += 1;
+            if qi == query.len() {
+                end = Some(i);
+                break;
+            }
+        }
+    }
+    let end = end?;
+
+    // Backward pass: latest start that still matches, giving the tightest span
+    let mut positions = Vec::with_capacity(query.len());
+    let mut qi = query.len();
+    for i in (0..=end).rev() {
+        if folded[i] == query[qi - 1] {
+            positions.push(i);
+            qi -= 1;
+            if qi == 0 {
+                break;
+            }
+        }
+    }
+    positions.reverse();
+
+    let start = positions[0];
+    let span_len = (end - start + 1) as i64;
+    let mut score = DENSITY_POINTS * query.len() as i64 / span_len;
+    if start == 0 {
+        score += PREFIX_BONUS;
+    }
+    score += BOUNDARY_BONUS
+        * positions
+            .iter()
+            .filter(|&&i| is_word_start(&chars, i))
+            .count() as i64;
+    let extra = chars.len().saturating_sub(query.len()) as i64;
+    score -= extra.min(MAX_LENGTH_PENALTY);
+
+    Some(score)
+}
+
+/// Whether `chars[i]` begins a word in a snake_case, camelCase, or path-like name
+fn is_word_start(chars: &[char], i: usize) -> bool {
+    if i == 0 {
+        return true;
+    }
+    let prev = chars[i - 1];
+    matches!(prev, '_' | ':' | '.' | '-' | '/' | ' ')
+        || (prev.is_lowercase() && chars[i].is_uppercase())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_subsequence_required() {
+        assert!(fuzzy_score("hndmsg", "handle_message").is_some());
+        assert!(fuzzy_score("hndmsg", "header_timestamp").is_none());
+        assert!(fuzzy_score("", "anything").is_none());
+        assert!(fuzzy_score("abc", "ab").is_none());
+    }
+
+    #[test]
+    fn test_case_insensitive() {
+        assert_eq!(
+            fuzzy_score("hm", "HandleMessage"),
+            fuzzy_score("HM", "HandleMessage")
+        );
+        assert!(fuzzy_score("hm", "HandleMessage").is_some());
+    }
+
+    #[test]
+    fn test_hndmsg_ranks_handle_message_above_header_timestamp() {
+        let handle = fuzzy_score("hndmsg", "handle_message").unwrap();
+        let header = fuzzy_score("hndmsg", "header_timestamp").unwrap_or(i64::MIN);
+        assert!(handle > header);
+
+        // With a query both names match, the denser match still wins
+        let handle = fuzzy_score("hme", "handle_message").unwrap();
+        let header = fuzzy_score("hme", "header_timestamp").unwrap();
+        assert!(handle > header, "{} vs {}", handle, header);
+    }
+
+    #[test]
+    fn test_prefix_and_density_bonuses() {
+        // Same letters, but only one starts at the beginning
+        assert!(fuzzy_score("msg", "msg_handler") > fuzzy_score("msg", "handle_msg"));
+        // Contiguous beats scattered
+        assert!(fuzzy_score("parse", "parse_file") > fuzzy_score("parse", "p_a_r_s_e"));
+    }
+
+    #[test]
+    fn test_tightest_span_is_scored() {
+        // Forward-only matching would span "a...b" from index 0
+        let tight = fuzzy_score("ab", "a_xxxxxx_ab").unwrap();
+        let loose = fuzzy_score("ab", "a_xxxxxx_b").unwrap();
+        assert!(tight > loose);
+    }
+}
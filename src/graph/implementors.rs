@@ -0,0 +1,383 @@
+//! Trait and interface implementations (`implementors`)
+//!
+//! Every `impl Trait for Type` in Rust, and every `extends` / `implements`
+//! clause in Java and TypeScript, is recorded by name in the `trait_impls`
+//! table, whether or not the trait resolves to an indexed symbol. When both
+//! the type and the trait resolve, `index_file` also adds an IMPLEMENTS edge
+//! from the type symbol to the trait symbol. Impls recorded in other files
+//! are resolved again whenever a file defines a type or trait they name, so
+//! the edges do not depend on the order files are indexed in.
+//!
+//! Names are matched on their simple form: generics, `dyn`, references and
+//! path qualifiers are dropped, so `fmt::Display`, `std::fmt::Display` and
+//! `Display` all name the same trait.
+
+use anyhow::Result;
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::Serialize;
+
+use super::CodeGraph;
+use crate::ingest::ImplRelation;
+
+/// A type implementing (or extending) a trait or interface
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Implementor {
+    /// Implementing type as written at the impl site
+    pub type_name: String,
+    /// Trait or interface as written at the impl site (e.g. `fmt::Display`)
+    pub trait_name: String,
+    /// File containing the impl or declaration
+    pub file_path: String,
+    /// First line of the impl or declaration (1-indexed)
+    pub start_line: usize,
+    /// Last line of the impl or declaration (1-indexed)
+    pub end_line: usize,
+    /// Symbol ID of the implementing type, when it resolves
+    pub type_symbol_id: Option<i64>,
+    /// Symbol ID of the trait, or None for external / unresolved traits
+    pub trait_symbol_id: Option<i64>,
+}
+
+/// Simple form of a type or trait name used for matching
+///
+/// `&dyn std::fmt::Display` and `Iterator<Item = u8>` become `Display` and
+/// `Iterator`.
+pub fn simple_name(name: &str) -> &str {
+    let mut name = name.trim();
+    name = name.trim_start_matches('&').trim_start();
+    for prefix in ["mut ", "dyn ", "impl "] {
+        name = name.strip_prefix(prefix).unwrap_or(name).trim_start();
+    }
+    if let Some(generics) = name.find('<') {
+        name = &name[..generics];
+    }
+    let name = name.rsplit("::").next().unwrap_or(name);
+    name.rsplit('.').next().unwrap_or(name).trim()
+}
+
+pub(crate) fn ensure_schema(conn: &Connection) -> Result<()> {
+    conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS trait_impls (
+            file_path TEXT NOT NULL,
+            type_name TEXT NOT NULL,
+            trait_name TEXT NOT NULL,
+            trait_simple_name TEXT NOT NULL,
+            start_line INTEGER NOT NULL,
+            end_line INTEGER NOT NULL,
+            type_simple_name TEXT NOT NULL DEFAULT ''
+        );
+        CREATE INDEX IF NOT EXISTS idx_trait_impls_trait ON trait_impls(trait_simple_name);
+        CREATE INDEX IF NOT EXISTS idx_trait_impls_file ON trait_impls(file_path);",
+    )
+    .map_err(|e| anyhow::anyhow!("Failed to create trait_impls: {}", e))?;
+    ensure_type_simple_name(conn)?;
+    conn.execute_batch(
+        "CREATE INDEX IF NOT EXISTS idx_trait_impls_type ON trait_impls(type_simple_name);",
+    )
+    .map_err(|e| anyhow::anyhow!("Failed to create trait_impls index: {}", e))?;
+    Ok(())
+}
+
+/// Add and backfill `type_simple_name` on tables created before it existed
+fn ensure_type_simple_name(conn: &Connection) -> Result<()> {
+    let present = conn
+        .query_row(
+            "SELECT 1 FROM pragma_table_info('trait_impls') WHERE name = 'type_simple_name'",
+            [],
+            |_| Ok(()),
+        )
+        .optional()?
+        .is_some();
+    if present {
+        return Ok(());
+    }
+    let tx = conn.unchecked_transaction()?;
+    tx.execute(
+        "ALTER TABLE trait_impls ADD COLUMN type_simple_name TEXT NOT NULL DEFAULT ''",
+        [],
+    )?;
+    let rows: Vec<(i64, String)> = {
+        let mut stmt = tx.prepare("SELECT rowid, type_name FROM trait_impls")?;
+        let rows = stmt
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        rows
+    };
+    for (rowid, type_name) in rows {
+        tx.execute(
+            "UPDATE trait_impls SET type_simple_name = ?1 WHERE rowid = ?2",
+            params![simple_name(&type_name), rowid],
+        )?;
+    }
+    tx.commit()?;
+    Ok(())
+}
+
+fn table_exists(conn: &Connection) -> Result<bool> {
+    Ok(conn
+        .query_row(
+            "SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = 'trait_impls'",
+            [],
+            |_| Ok(()),
+        )
+        .optional()
+        .map_err(|e| anyhow::anyhow!("Failed to inspect schema: {}", e))?
+        .is_some())
+}
+
+/// Replace the recorded trait impls of `path` with `relations`
+///
+/// Inherent impls (no trait) are skipped.
+pub(crate) fn replace_for_file(
+    conn: &Connection,
+    path: &str,
+    relations: &[ImplRelation],
+) -> Result<()> {
+    ensure_schema(conn)?;
+    let tx = conn.unchecked_transaction()?;
+    tx.execute(
+        "DELETE FROM trait_impls WHERE file_path = ?1",
+        params![path],
+    )?;
+    {
+        let mut stmt = tx.prepare(
+            "INSERT INTO trait_impls
+             (file_path, type_name, trait_name, trait_simple_name, start_line, end_line,
+              type_simple_name)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+        )?;
+        for rel in relations {
+            let Some(ref trait_name) = rel.trait_name else {
+                continue;
+            };
+            stmt.execute(params![
+                path,
+                rel.type_name,
+                trait_name,
+                simple_name(trait_name),
+                rel.start_line as i64,
+                rel.end_line as i64,
+                simple_name(&rel.type_name),
+            ])?;
+        }
+    }
+    tx.commit()?;
+    Ok(())
+}
+
+/// Trait impls recorded outside `path` whose type or trait is one of `names`
+///
+/// Returns `(file_path, type_name, trait_name)` rows. `index_file` resolves
+/// these again after `path` defines the named types, since the impl file may
+/// have been indexed before them.
+pub(crate) fn pending_for_names(
+    conn: &Connection,
+    path: &str,
+    names: &[&str],
+) -> Result<Vec<(String, String, String)>> {
+    if names.is_empty() || !table_exists(conn)? {
+        return Ok(Vec::new());
+    }
+    let mut stmt = conn.prepare(
+        "SELECT file_path, type_name, trait_name FROM trait_impls
+         WHERE file_path != ?1 AND (trait_simple_name = ?2 OR type_simple_name = ?2)",
+    )?;
+    let mut rows = Vec::new();
+    for name in names {
+        let matched = stmt
+            .query_map(params![path, name], |row| {
+                Ok((row.get(0)?, row.get(1)?, row.get(2)?))
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        rows.extend(matched);
+    }
+    rows.sort();
+    rows.dedup();
+    Ok(rows)
+}
+
+/// Forget the recorded trait impls of `path`
+pub(crate) fn delete_for_file(conn: &Connection, path: &str) -> Result<usize> {
+    if !table_exists(conn)? {
+        return Ok(0);
+    }
+    Ok(conn.execute(
+        "DELETE FROM trait_impls WHERE file_path = ?1",
+        params![path],
+    )?)
+}
+
+impl CodeGraph {
+    /// Types implementing or extending `trait_name`
+    ///
+    /// Matches on the simple name, so `Display` also finds `impl fmt::Display
+    /// for T`. Traits that are not indexed (e.g. from the standard library)
+    /// are still found; their `trait_symbol_id` is None. Sorted by file, line
+    /// and type name.
+    pub fn implementors_of(&self, trait_name: &str) -> Result<Vec<Implementor>> {
+        let rows: Vec<(String, String, String, i64, i64)> = {
+            let conn = self.side_conn.lock();
+            if !table_exists(&conn)? {
+                return Ok(Vec::new());
+            }
+            let mut stmt = conn.prepare(
+                "SELECT file_path, type_name, trait_name, start_line, end_line
+                 FROM trait_impls WHERE trait_simple_name = ?1",
+            )?;
+            let rows = stmt
+                .query_map(params![simple_name(trait_name)], |row| {
+                    Ok((
+                        row.get(0)?,
+                        row.get(1)?,
+                        row.get(2)?,
+                        row.get(3)?,
+                        row.get(4)?,
+                    ))
+                })?
+                .collect::<rusqlite::Result<Vec<_>>>()?;
+            rows
+        };
+
+        let lookup = &self.symbols.lookup;
+        let mut implementors: Vec<Implementor> = rows
+            .into_iter()
+            .map(
+                |(file_path, type_name, trait_name, start_line, end_line)| Implementor {
+                    type_symbol_id: lookup.resolve_type_name(simple_name(&type_name), &file_path),
+                    trait_symbol_id: lookup.resolve_type_name(simple_name(&trait_name), &file_path),
+                    type_name,
+                    trait_name,
+                    file_path,
+                    start_line: start_line as usize,
+                    end_line: end_line as usize,
+                },
+            )
+            .collect();
+        implementors.sort_by(|a, b| {
+            (&a.file_path, a.start_line, &a.type_name).cmp(&(
+                &b.file_path,
+                b.start_line,
+                &b.type_name,
+            ))
+        });
+        Ok(implementors)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_simple_name() {
+        assert_eq!(simple_name("Display"), "Display");
+        assert_eq!(simple_name("std::fmt::Display"), "Display");
+        assert_eq!(simple_name("&dyn fmt::Debug"), "Debug");
+        assert_eq!(simple_name("Iterator<Item = u8>"), "Iterator");
+        assert_eq!(simple_name("java.util.List<String>"), "List");
+    }
+
+    #[test]
+    fn test_implementors_of_display() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let mut graph = CodeGraph::open(dir.path().join("test.db")).unwrap();
+        let source = b"use std::fmt;\n\
+            pub struct Meters(f64);\n\
+            pub struct Feet(f64);\n\
+            impl fmt::Display for Meters {\n    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result { write!(f, \"{}m\", self.0) }\n}\n\
+            impl std::fmt::Display for Feet {\n    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result { write!(f, \"{}ft\", self.0) }\n}\n\
+            impl Meters {\n    fn new() -> Self { Meters(0.0) }\n}\n";
+        graph.index_file("units.rs", source).unwrap();
+
+        let implementors = graph.implementors_of("Display").unwrap();
+        let types: Vec<&str> = implementors.iter().map(|i| i.type_name.as_str()).collect();
+        assert_eq!(types, vec!["Meters", "Feet"]);
+        assert!(implementors.iter().all(|i| i.type_symbol_id.is_some()));
+        // std's Display is not indexed: recorded by name only
+        assert!(implementors.iter().all(|i| i.trait_symbol_id.is_none()));
+
+        // Re-indexing replaces the file's rows rather than duplicating them
+        graph.index_file("units.rs", source).unwrap();
+        assert_eq!(graph.implementors_of("fmt::Display").unwrap().len(), 2);
+
+        graph.delete_file("units.rs").unwrap();
+        assert!(graph.implementors_of("Display").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_local_trait_gets_implements_edge() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let mut graph = CodeGraph::open(dir.path().join("test.db")).unwrap();
+        graph
+            .index_file("shape.rs", b"pub trait Shape { fn area(&self) -> f64; }\n")
+            .unwrap();
+        graph
+            .index_file(
+                "square.rs",
+                b"pub struct Square(f64);\nimpl Shape for Square {\n    fn area(&self) -> f64 { self.0 * self.0 }\n}\n",
+            )
+            .unwrap();
+
+        let implementors = graph.implementors_of("Shape").unwrap();
+        assert_eq!(implementors.len(), 1);
+        let square = implementors[0].type_symbol_id.unwrap();
+        let shape = implementors[0].trait_symbol_id.unwrap();
+
+        let snapshot = sqlitegraph::SnapshotId::current();
+        let targets = graph
+            .files
+            .backend
+            .neighbors(
+                snapshot,
+                square,
+                sqlitegraph::NeighborQuery {
+                    direction: sqlitegraph::BackendDirection::Outgoing,
+                    edge_type: Some("IMPLEMENTS".to_string()),
+                },
+            )
+            .unwrap();
+        assert_eq!(targets, vec![shape]);
+    }
+
+    #[test]
+    fn test_implements_edge_when_trait_indexed_after_impl() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let mut graph = CodeGraph::open(dir.path().join("test.db")).unwrap();
+        let trait_source = b"pub trait Shape { fn area(&self) -> f64; }\n";
+        graph
+            .index_file(
+                "square.rs",
+                b"pub struct Square(f64);\nimpl Shape for Square {\n    fn area(&self) -> f64 { self.0 * self.0 }\n}\n",
+            )
+            .unwrap();
+        graph.index_file("shape.rs", trait_source).unwrap();
+
+        let implements = |graph: &CodeGraph| {
+            let implementors = graph.implementors_of("Shape").unwrap();
+            assert_eq!(implementors.len(), 1);
+            let square = implementors[0].type_symbol_id.unwrap();
+            let shape = implementors[0].trait_symbol_id.unwrap();
+            let targets = graph
+                .symbols
+                .backend
+                .neighbors(
+                    sqlitegraph::SnapshotId::current(),
+                    square,
+                    sqlitegraph::NeighborQuery {
+                        direction: sqlitegraph::BackendDirection::Outgoing,
+                        edge_type: Some("IMPLEMENTS".to_string()),
+                    },
+                )
+                .unwrap();
+            (targets, shape)
+        };
+        let (targets, shape) = implements(&graph);
+        assert_eq!(targets, vec![shape]);
+
+        // Re-indexing the trait file rebuilds the edge to the new trait symbol
+        graph.delete_file("shape.rs").unwrap();
+        graph.index_file("shape.rs", trait_source).unwrap();
+        let (targets, shape) = implements(&graph);
+        assert_eq!(targets, vec![shape]);
+    }
+}
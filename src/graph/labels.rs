@@ -0,0 +1,119 @@
+//! Labels attached to symbols at index time
+//!
+//! Every indexed symbol carries one label per dimension below, so that
+//! `magellan label --label A --label B` (AND semantics) can intersect them:
+//!
+//! | Dimension | Labels | Source |
+//! |-----------|--------|--------|
+//! | Language | `rust`, `python`, `c`, `cpp`, `java`, ... | Language of the file |
+//! | Kind | `fn`, `method`, `struct`, `trait`, `enum`, `mod`, `union`, `namespace`, `type_alias`, `macro`, `unknown` | `SymbolKind::normalized_key` |
+//! | Kind category | `function`, `method`, `class`, `interface`, `enum`, `module`, `union`, `namespace`, `type_alias`, `macro`, `unknown` | `SymbolKind` variant |
+//! | Visibility | `pub`, `private` | `api_surface` rules; omitted for languages without a visibility model |
+//! | Test code | `test`, `non-test` | `SymbolFact::is_test` |
+//!
+//! Kind and kind category overlap where the names agree (`method`, `enum`,
+//! ...), in which case the label is attached once.
+
+use crate::ingest::{Language, SymbolFact, SymbolKind};
+
+/// Visibility label for symbols that are public API
+pub const PUB_LABEL: &str = "pub";
+/// Visibility label for symbols that are not public API
+pub const PRIVATE_LABEL: &str = "private";
+/// Label for symbols classified as test code
+pub const TEST_LABEL: &str = "test";
+/// Label for symbols not classified as test code
+pub const NON_TEST_LABEL: &str = "non-test";
+
+/// Language-neutral category label for a symbol kind
+pub fn kind_category_label(kind: &SymbolKind) -> &'static str {
+    match kind {
+        SymbolKind::Function => "function",
+        SymbolKind::Method => "method",
+        SymbolKind::Class => "class",
+        SymbolKind::Interface => "interface",
+        SymbolKind::Enum => "enum",
+        SymbolKind::Module => "module",
+        SymbolKind::Union => "union",
+        SymbolKind::Namespace => "namespace",
+        SymbolKind::TypeAlias => "type_alias",
+        SymbolKind::Macro => "macro",
+        SymbolKind::Unknown => "unknown",
+    }
+}
+
+/// All labels for one symbol fact
+///
+/// `public` is the fact's visibility, or `None` when it is not known.
+pub fn symbol_labels(
+    language: Option<Language>,
+    fact: &SymbolFact,
+    public: Option<bool>,
+) -> Vec<String> {
+    let mut labels: Vec<String> = Vec::with_capacity(5);
+    if let Some(language) = language {
+        labels.push(language.as_str().to_string());
+    }
+    labels.push(fact.kind_normalized.clone());
+    let category = kind_category_label(&fact.kind);
+    if category != fact.kind_normalized {
+        labels.push(category.to_string());
+    }
+    match public {
+        Some(true) => labels.push(PUB_LABEL.to_string()),
+        Some(false) => labels.push(PRIVATE_LABEL.to_string()),
+        None => {}
+    }
+    let test_label = if fact.is_test {
+        TEST_LABEL
+    } else {
+        NON_TEST_LABEL
+    };
+    labels.push(test_label.to_string());
+    labels
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn fact(kind: SymbolKind, is_test: bool) -> SymbolFact {
+        SymbolFact {
+            file_path: PathBuf::from("lib.rs"),
+            kind_normalized: kind.normalized_key().to_string(),
+            kind,
+            name: Some("open".to_string()),
+            fqn: None,
+            canonical_fqn: None,
+            display_fqn: None,
+            byte_start: 0,
+            byte_end: 0,
+            start_line: 1,
+            start_col: 0,
+            end_line: 1,
+            end_col: 0,
+            signature: None,
+            is_test,
+            visibility: None,
+            deprecated: false,
+            deprecation_message: None,
+        }
+    }
+
+    #[test]
+    fn test_symbol_labels_cover_every_dimension() {
+        let labels = symbol_labels(
+            Some(Language::Rust),
+            &fact(SymbolKind::Function, false),
+            Some(true),
+        );
+        assert_eq!(labels, vec!["rust", "fn", "function", "pub", "non-test"]);
+    }
+
+    #[test]
+    fn test_symbol_labels_dedupe_kind_and_skip_unknown_visibility() {
+        let labels = symbol_labels(None, &fact(SymbolKind::Method, true), None);
+        assert_eq!(labels, vec!["method", "test"]);
+    }
+}
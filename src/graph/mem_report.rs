@@ -0,0 +1,92 @@
+//! Coarse memory accounting for indexing runs (`watch --mem-report`)
+//!
+//! Counts the entries held by the in-memory structures that grow with the
+//! repository, plus the process's peak resident set size where the platform
+//! exposes it. Entry counts, not byte sizes: they are cheap to collect and
+//! enough to tell which structure to tune. Collecting the report reads state
+//! only and never changes what gets indexed.
+
+use serde::Serialize;
+
+use super::CodeGraph;
+
+/// Memory accounting for one `CodeGraph`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct MemoryReport {
+    /// Parse trees kept for incremental re-parsing
+    pub parse_trees: usize,
+    /// Entries in the warm symbol index (FQN, name and ID lookups); 0 when not built
+    pub fqn_index_entries: usize,
+    /// Entries in the path -> File node map
+    pub file_index_entries: usize,
+    /// Entries in the file node and navigator query caches
+    pub cache_entries: usize,
+    /// Peak resident set size in bytes, when the platform reports it
+    pub peak_rss_bytes: Option<u64>,
+}
+
+impl MemoryReport {
+    /// Collect the report for `graph`
+    pub fn collect(graph: &CodeGraph) -> Self {
+        Self {
+            parse_trees: graph.tree_cache.as_ref().map_or(0, |cache| cache.len()),
+            fqn_index_entries: graph.symbol_index.as_ref().map_or(0, |index| index.len()),
+            file_index_entries: graph.files.file_index.len(),
+            cache_entries: graph.full_cache_stats().size,
+            peak_rss_bytes: peak_rss_bytes(),
+        }
+    }
+}
+
+/// Peak resident set size of this process (`VmHWM` on Linux)
+#[cfg(target_os = "linux")]
+pub fn peak_rss_bytes() -> Option<u64> {
+    let status = std::fs::read_to_string("/proc/self/status").ok()?;
+    parse_vm_hwm(&status)
+}
+
+/// Peak resident set size of this process (not available on this platform)
+#[cfg(not(target_os = "linux"))]
+pub fn peak_rss_bytes() -> Option<u64> {
+    None
+}
+
+/// Bytes from the `VmHWM:` line of `/proc/self/status` (reported in kB)
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+fn parse_vm_hwm(status: &str) -> Option<u64> {
+    let line = status.lines().find(|line| line.starts_with("VmHWM:"))?;
+    let kib: u64 = line
+        .trim_start_matches("VmHWM:")
+        .split_whitespace()
+        .next()?
+        .parse()
+        .ok()?;
+    Some(kib * 1024)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_vm_hwm() {
+        let status =
+            "Name:\tmagellan\nVmPeak:\t  300000 kB\nVmHWM:\t   12345 kB\nVmRSS:\t 1000 kB\n";
+        assert_eq!(parse_vm_hwm(status), Some(12345 * 1024));
+        assert_eq!(parse_vm_hwm("Name:\tmagellan\n"), None);
+    }
+
+    #[test]
+    fn test_collect_counts_file_index() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let mut graph = CodeGraph::open(dir.path().join("test.db")).unwrap();
+        graph.enable_incremental_parse(4);
+        graph.index_file("a.rs", b"fn a() {}\n").unwrap();
+        graph.index_file("b.rs", b"fn b() {}\n").unwrap();
+
+        let report = MemoryReport::collect(&graph);
+        assert_eq!(report.file_index_entries, 2);
+        assert_eq!(report.parse_trees, 2);
+        assert_eq!(report.fqn_index_entries, 0);
+    }
+}
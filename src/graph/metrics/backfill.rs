@@ -5,8 +5,10 @@
 
 use anyhow::Result;
 use rusqlite::params;
+use std::path::PathBuf;
 
 use super::MetricsOps;
+use crate::graph::filter::FilePattern;
 
 /// Type alias for the progress callback used during backfill operations.
 pub type ProgressCallback = dyn Fn(usize, usize, &str) + Send + Sync;
@@ -18,6 +20,8 @@ pub struct BackfillResult {
     pub total: usize,
     /// Number of files successfully processed
     pub processed: usize,
+    /// Number of symbols whose metrics were stored
+    pub symbols_updated: usize,
     /// Errors encountered: (file_path, error_message)
     pub errors: Vec<(String, String)>,
 }
@@ -36,14 +40,28 @@ impl MetricsOps {
     pub fn backfill_all_metrics(
         &self,
         progress: Option<&ProgressCallback>,
+    ) -> Result<BackfillResult> {
+        self.backfill_metrics_matching(progress, None, &|path| PathBuf::from(path))
+    }
+
+    /// Backfill metrics for the indexed files matching `only`
+    ///
+    /// `only` is matched against the stored path (see [`FilePattern`]); None
+    /// selects every file. `disk_path` maps a stored path to the file to read,
+    /// so databases with relative paths can be backfilled from any directory.
+    pub fn backfill_metrics_matching(
+        &self,
+        progress: Option<&ProgressCallback>,
+        only: Option<&FilePattern>,
+        disk_path: &dyn Fn(&str) -> PathBuf,
     ) -> Result<BackfillResult> {
         // Collect file paths using with_conn to support Shared backend
-        let files: Vec<String> = self.with_conn(|conn| {
+        let mut files: Vec<String> = self.with_conn(|conn| {
             let mut stmt = conn.prepare(
-                "SELECT DISTINCT json_extract(data, '$.file_path') as file_path
+                "SELECT DISTINCT json_extract(data, '$.path') as file_path
                  FROM graph_entities
-                 WHERE kind = 'Symbol'
-                 AND json_extract(data, '$.file_path') IS NOT NULL
+                 WHERE kind = 'File'
+                 AND json_extract(data, '$.path') IS NOT NULL
                  ORDER BY file_path",
             )?;
 
@@ -56,14 +74,18 @@ impl MetricsOps {
 
             Ok(files)
         })?;
+        if let Some(pattern) = only {
+            files.retain(|path| pattern.matches(path));
+        }
 
         let total = files.len();
         let mut processed = 0;
+        let mut symbols_updated = 0;
         let mut errors = Vec::new();
 
         for file_path in files {
             // Read file from disk
-            let source = match std::fs::read(&file_path) {
+            let source = match std::fs::read(disk_path(&file_path)) {
                 Ok(s) => s,
                 Err(e) => {
                     errors.push((file_path.clone(), format!("Read error: {}", e)));
@@ -81,8 +103,9 @@ impl MetricsOps {
             };
 
             // Compute metrics (same logic as index_file)
-            if let Err(e) = self.compute_for_file(&file_path, &source, &symbols) {
-                errors.push((file_path.clone(), format!("Compute error: {}", e)));
+            match self.compute_for_file(&file_path, &source, &symbols) {
+                Ok(stored) => symbols_updated += stored,
+                Err(e) => errors.push((file_path.clone(), format!("Compute error: {}", e))),
             }
 
             processed += 1;
@@ -96,6 +119,7 @@ impl MetricsOps {
         Ok(BackfillResult {
             total,
             processed,
+            symbols_updated,
             errors,
         })
     }
@@ -113,10 +137,12 @@ impl MetricsOps {
     fn get_file_symbols(&self, file_path: &str) -> Result<Vec<crate::graph::schema::SymbolNode>> {
         self.with_conn(|conn| {
             let mut stmt = conn.prepare(
-                "SELECT data
-                 FROM graph_entities
-                 WHERE kind = 'Symbol'
-                 AND json_extract(data, '$.file_path') = ?1",
+                "SELECT s.data
+                 FROM graph_entities f
+                 JOIN graph_edges e ON e.from_id = f.id AND e.edge_type = 'DEFINES'
+                 JOIN graph_entities s ON s.id = e.to_id AND s.kind = 'Symbol'
+                 WHERE f.kind = 'File'
+                 AND json_extract(f.data, '$.path') = ?1",
             )?;
 
             let symbols = stmt
@@ -144,6 +170,7 @@ mod tests {
         let result = BackfillResult {
             total: 10,
             processed: 9,
+            symbols_updated: 42,
             errors: vec![("test.rs".to_string(), "Read error".to_string())],
         };
 
@@ -8,6 +8,7 @@ use std::time::{SystemTime, UNIX_EPOCH};
 
 use super::schema::{FileMetrics, SymbolMetrics};
 use super::MetricsOps;
+use crate::log_warn;
 
 impl MetricsOps {
     /// Compute and store metrics for a file and all its symbols
@@ -16,12 +17,15 @@ impl MetricsOps {
     /// * `file_path` - Path to the file
     /// * `source` - File contents as bytes
     /// * `symbol_facts` - Vector of SymbolNode data for all symbols in the file
+    ///
+    /// # Returns
+    /// Number of symbols whose metrics were stored
     pub fn compute_for_file(
         &self,
         file_path: &str,
         source: &[u8],
         symbol_facts: &[crate::graph::schema::SymbolNode],
-    ) -> Result<()> {
+    ) -> Result<usize> {
         self.with_conn(|conn| {
             let symbol_count = symbol_facts.len() as i64;
             let loc = memchr::memchr_iter(b'\n', source).count() as i64 + 1;
@@ -43,18 +47,22 @@ impl MetricsOps {
             };
             Self::upsert_file_metrics_conn(conn, &file_metrics)?;
 
+            let mut symbols_updated = 0;
             for symbol in symbol_facts {
-                if let Err(e) = Self::compute_and_store_symbol_metrics_conn(conn, symbol, file_path)
-                {
-                    let symbol_name = symbol.name.as_deref().unwrap_or("<unknown>");
-                    eprintln!(
-                        "Warning: Failed to compute metrics for symbol '{}': {}",
-                        symbol_name, e
-                    );
+                match Self::compute_and_store_symbol_metrics_conn(conn, symbol, file_path) {
+                    Ok(stored) => symbols_updated += usize::from(stored),
+                    Err(e) => {
+                        let symbol_name = symbol.name.as_deref().unwrap_or("<unknown>");
+                        log_warn!(
+                            "Failed to compute metrics for symbol '{}': {}",
+                            symbol_name,
+                            e
+                        );
+                    }
                 }
             }
 
-            Ok(())
+            Ok(symbols_updated)
         })
     }
 
@@ -127,15 +135,15 @@ impl MetricsOps {
         conn: &rusqlite::Connection,
         symbol: &crate::graph::schema::SymbolNode,
         file_path: &str,
-    ) -> Result<()> {
+    ) -> Result<bool> {
         let fqn = symbol.fqn.as_deref().unwrap_or("");
         if fqn.is_empty() {
-            return Ok(());
+            return Ok(false);
         }
 
         let symbol_id = match Self::find_symbol_id_conn(conn, fqn)? {
             Some(id) => id,
-            None => return Ok(()),
+            None => return Ok(false),
         };
 
         let loc = if symbol.end_line > 0 && symbol.end_line >= symbol.start_line {
@@ -170,7 +178,7 @@ impl MetricsOps {
         };
 
         Self::upsert_symbol_metrics_conn(conn, &metrics)?;
-        Ok(())
+        Ok(true)
     }
 
     fn find_symbol_id_conn(conn: &rusqlite::Connection, fqn: &str) -> Result<Option<i64>> {
@@ -10,6 +10,7 @@ use std::time::{SystemTime, UNIX_EPOCH};
 
 use super::schema::{FileMetrics, SymbolMetrics};
 use crate::graph::schema::SymbolNode;
+use crate::log_warn;
 
 /// V3 metrics computation using graph backend
 pub struct V3MetricsCompute {
@@ -79,9 +80,10 @@ impl V3MetricsCompute {
                 self.compute_and_store_symbol_metrics(symbol, file_path, &store_symbol_fn)
             {
                 let symbol_name = symbol.name.as_deref().unwrap_or("<unknown>");
-                eprintln!(
-                    "Warning: Failed to compute metrics for symbol '{}': {}",
-                    symbol_name, e
+                log_warn!(
+                    "Failed to compute metrics for symbol '{}': {}",
+                    symbol_name,
+                    e
                 );
             }
         }
@@ -17,6 +17,7 @@
 //! enforces single-threaded access. The parent `CodeGraph` instance
 //! must not be shared across threads.
 
+use crate::log_warn;
 use anyhow::Result;
 use rusqlite::{params, OptionalExtension};
 use std::path::Path;
@@ -29,7 +30,7 @@ pub mod compute_v3;
 pub mod schema;
 
 pub use backfill::BackfillResult;
-pub use schema::{FileMetrics, SymbolMetrics};
+pub use schema::{FileMetrics, FileTiming, SymbolMetrics};
 
 /// Backend storage for MetricsOps
 enum MetricsOpsBackend {
@@ -77,11 +78,20 @@ impl MetricsOps {
             backend: MetricsOpsBackend::Shared(conn),
         };
         if let Err(e) = metrics.ensure_schema() {
-            eprintln!("Warning: Failed to ensure MetricsOps schema: {}", e);
+            log_warn!("Failed to ensure MetricsOps schema: {}", e);
         }
         metrics
     }
 
+    /// Create a MetricsOps over a read-only connection.
+    ///
+    /// Skips `ensure_schema`, so opening never runs DDL. Reads still work.
+    pub fn read_only(conn: Arc<parking_lot::Mutex<rusqlite::Connection>>) -> Self {
+        Self {
+            backend: MetricsOpsBackend::Shared(conn),
+        }
+    }
+
     /// Create an in-memory MetricsOps for testing/stub usage.
     ///
     /// Uses a temporary file so that new connections can access the same data.
@@ -103,7 +113,7 @@ impl MetricsOps {
 
         // Ensure schema exists
         if let Err(e) = metrics.ensure_schema() {
-            eprintln!("Warning: Failed to ensure MetricsOps schema: {}", e);
+            log_warn!("Failed to ensure MetricsOps schema: {}", e);
         }
 
         metrics
@@ -268,9 +278,63 @@ impl MetricsOps {
         )
         .map_err(|e| anyhow::anyhow!("Failed to delete file metrics: {}", e))?;
 
+        conn.execute(
+            "DELETE FROM file_timings WHERE file_path = ?1",
+            params![file_path],
+        )
+        .map_err(|e| anyhow::anyhow!("Failed to delete file timing: {}", e))?;
+
         Ok(symbol_count)
     }
 
+    /// Record the indexing wall time for a file (insert or replace)
+    ///
+    /// No-op on the V3 (SideTables) backend, which has no timing table.
+    pub fn record_file_timing(&self, file_path: &str, index_ms: i64) -> Result<()> {
+        if let MetricsOpsBackend::SideTables(_) = self.backend {
+            return Ok(());
+        }
+        let last_updated = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs() as i64)
+            .unwrap_or(0);
+        self.with_conn(|conn| {
+            conn.execute(
+                "INSERT OR REPLACE INTO file_timings (file_path, index_ms, last_updated)
+                 VALUES (?1, ?2, ?3)",
+                params![file_path, index_ms, last_updated],
+            )
+            .map_err(|e| anyhow::anyhow!("Failed to record file timing: {}", e))?;
+            Ok(())
+        })
+    }
+
+    /// Get the slowest files by recorded indexing time
+    ///
+    /// Ordered by index_ms DESC, then file_path ASC for deterministic ties.
+    pub fn get_slowest_files(&self, limit: usize) -> Result<Vec<FileTiming>> {
+        if let MetricsOpsBackend::SideTables(_) = self.backend {
+            return Ok(Vec::new());
+        }
+        self.with_conn(|conn| {
+            let mut stmt = conn.prepare(
+                "SELECT file_path, index_ms, last_updated
+                 FROM file_timings
+                 ORDER BY index_ms DESC, file_path ASC
+                 LIMIT ?1",
+            )?;
+            let rows = stmt.query_map(params![limit as i64], |row| {
+                Ok(FileTiming {
+                    file_path: row.get(0)?,
+                    index_ms: row.get(1)?,
+                    last_updated: row.get(2)?,
+                })
+            })?;
+            rows.collect::<Result<Vec<_>, _>>()
+                .map_err(|e| anyhow::anyhow!("Failed to query file timings: {}", e))
+        })
+    }
+
     /// Get file metrics by path
     pub fn get_file_metrics(&self, file_path: &str) -> Result<Option<FileMetrics>> {
         match &self.backend {
@@ -540,4 +604,38 @@ pub mod query {
 #[cfg(test)]
 mod tests {
     use super::*;
+
+    #[test]
+    fn test_slowest_files_sorted_descending() {
+        let dir = tempfile::tempdir().unwrap();
+        let metrics = MetricsOps::new(&dir.path().join("test.db"));
+        metrics.ensure_schema().unwrap();
+
+        metrics.record_file_timing("a.rs", 5).unwrap();
+        metrics.record_file_timing("b.rs", 40).unwrap();
+        metrics.record_file_timing("c.rs", 40).unwrap();
+        metrics.record_file_timing("d.rs", 12).unwrap();
+        // Re-recording replaces the previous timing
+        metrics.record_file_timing("a.rs", 7).unwrap();
+
+        let slowest = metrics.get_slowest_files(3).unwrap();
+        let order: Vec<(&str, i64)> = slowest
+            .iter()
+            .map(|t| (t.file_path.as_str(), t.index_ms))
+            .collect();
+        assert_eq!(order, vec![("b.rs", 40), ("c.rs", 40), ("d.rs", 12)]);
+    }
+
+    #[test]
+    fn test_delete_file_metrics_removes_timing() {
+        let dir = tempfile::tempdir().unwrap();
+        // symbol_metrics references graph_entities, so open a full graph
+        let graph = crate::CodeGraph::open(dir.path().join("test.db")).unwrap();
+        let metrics = &graph.metrics;
+
+        metrics.record_file_timing("a.rs", 5).unwrap();
+        metrics.delete_file_metrics("a.rs").unwrap();
+
+        assert!(metrics.get_slowest_files(20).unwrap().is_empty());
+    }
 }
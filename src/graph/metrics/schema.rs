@@ -35,6 +35,23 @@ pub struct FileMetrics {
     pub last_updated: i64,
 }
 
+/// Per-file indexing wall time
+///
+/// Recorded by `index_file` (parse + index, including metrics computation)
+/// and stored in the file_timings table. Purely observational: timings never
+/// influence indexing results.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileTiming {
+    /// File path (PRIMARY KEY)
+    pub file_path: String,
+
+    /// Wall time of the last `index_file` call for this file, in milliseconds
+    pub index_ms: i64,
+
+    /// Unix timestamp (seconds) when the timing was recorded
+    pub last_updated: i64,
+}
+
 /// Symbol-level metrics for detailed analysis
 ///
 /// Computed during file indexing and stored in symbol_metrics table.
@@ -38,14 +38,16 @@ pub mod external_tools;
 
 mod ast_node;
 mod ast_ops;
+mod ast_query;
 
-mod cache;
+pub(crate) mod cache;
 mod call_ops;
 mod calls;
 pub mod canonical_fqn;
 pub mod cfg_edges_extract;
 mod cfg_extractor;
 mod cfg_ops;
+pub mod content_hash;
 mod count;
 pub mod crate_name;
 pub mod db_compat;
@@ -55,12 +57,21 @@ pub mod export;
 mod files;
 pub mod filter;
 mod freshness;
+pub mod fuzzy;
+pub mod implementors;
 mod imports; // Private module for import operations
+pub mod labels;
+pub mod mem_report;
 pub mod metrics;
 mod module_resolver;
 pub mod multi_db;
 pub mod navigator;
 mod ops;
+pub mod outline;
+pub mod path_style;
+pub mod pragmas;
+pub mod profile;
+pub mod prune;
 pub mod query;
 mod references;
 pub mod scan;
@@ -101,7 +112,9 @@ use std::sync::Arc;
 use crate::graph::scan::ScanResult;
 
 use crate::generation::{ChunkStore, CodeChunk};
+use crate::output::rich::SpanChecksums;
 use crate::references::{CallFact, ReferenceFact};
+use crate::{log_info, log_verbose, log_warn};
 
 // Re-export public types
 pub use algorithms::{
@@ -110,14 +123,21 @@ pub use algorithms::{
     SliceResult, SliceStatistics, Supernode, SymbolInfo,
 };
 pub use ast_extractor::{extract_ast_nodes, language_from_path, normalize_node_kind};
-pub use ast_node::{is_structural_kind, AstNode, AstNodeWithText};
+pub use ast_query::{run_ast_query, AstCapture};
+pub use ast_node::{
+    is_structural_kind, AstNode, AstNodeWithText, AstTreeNode, DEFAULT_SUBTREE_DEPTH,
+};
 // Re-export CFG types for public API
 #[deprecated(since = "10.0.0", note = "Use cfg_edges_extract instead")]
 pub use cfg_extractor::{BlockKind, CfgExtractor, TerminatorKind};
 pub use cfg_ops::CfgOps;
 pub use multi_db::MultiDbContext;
 
-pub use cache::{CacheStats, EntityCacheKey, ExpandCacheKey, NameCacheKey, ThreadSafeCache};
+pub use cache::{
+    CacheStats, CachedSymbol, EntityCacheKey, ExpandCacheKey, NameCacheKey, ThreadSafeCache,
+    WarmSymbolIndex,
+};
+pub use content_hash::{HashAlgorithm, HashNormalization};
 pub use db_compat::MAGELLAN_SCHEMA_VERSION;
 pub use db_compat::{
     ensure_ast_schema, ensure_candidate_fact_schema, ensure_cfg_schema, ensure_coverage_schema,
@@ -127,6 +147,9 @@ pub use execution_log::ExecutionLog;
 pub use export::{ExportConfig, ExportFormat};
 pub use freshness::{check_freshness, FreshnessStatus, STALE_THRESHOLD_SECS};
 pub use metrics::MetricsOps;
+pub use path_style::{PathSettings, PathStyle, RebaseReport, RebaseRoot};
+pub use pragmas::{JournalMode, SqliteOptions, Synchronous};
+pub use prune::PruneReport;
 pub use schema::{CallNode, CfgBlock, CfgEdge, CrossFileRef, FileNode, ReferenceNode, SymbolNode};
 
 /// Statistics for a CodeGraph database
@@ -163,7 +186,10 @@ pub struct DirectCallIcfgEdge {
 
 /// Progress callback for scan_directory
 ///
-/// Receives (current_count, total_count, current_file_path) as scanning progresses
+/// Receives (current_count, total_count, current_file_path) as scanning progresses.
+/// `current_count` is the number of files processed so far and never
+/// decreases; `total_count` is the number of candidate files and is the same
+/// for every call of one scan. See `indexer::progress` for the CLI renderer.
 pub type ScanProgress = dyn Fn(usize, usize, &str) + Send + Sync;
 
 /// Check if a database path is an in-memory database.
@@ -223,6 +249,11 @@ pub struct CodeGraph {
     name_cache: cache::ThreadSafeCache<cache::NameCacheKey, Vec<navigator::SymbolInfo>>,
     expand_cache: cache::ThreadSafeCache<cache::ExpandCacheKey, Vec<navigator::TypedEdgeHop>>,
 
+    /// Whole-table symbol index, enabled by `warm_symbol_cache`.
+    /// `None` until built and again after each `index_file`/`delete_file`.
+    symbol_cache_enabled: bool,
+    symbol_index: Option<cache::WarmSymbolIndex>,
+
     /// CFG block operations module
     pub cfg_ops: cfg_ops::CfgOps,
 
@@ -252,6 +283,47 @@ pub struct CodeGraph {
     pub(crate) compile_commands:
         Option<std::sync::Arc<external_tools::compile_commands::CompileCommandsDb>>,
 
+    /// Index only the public API surface (`--api-only`).
+    /// Set via `set_api_only`. Private symbols are skipped, along with the
+    /// references and calls inside them, and retained symbols carry their
+    /// declaration signature.
+    pub(crate) api_only: bool,
+
+    /// Skip call extraction (`--no-calls`).
+    /// Set via `set_skip_calls`. No CALLS/CALLER edges or call nodes are
+    /// recorded, so call-graph queries come back empty.
+    pub(crate) skip_calls: bool,
+
+    /// Follow in-root symlinks during directory scans.
+    /// Set via `set_follow_symlinks`; mirrors `WatcherConfig::follow_symlinks`.
+    pub(crate) follow_symlinks: bool,
+
+    /// Threads for the parallel read phase of directory scans (`--jobs`).
+    /// Set via `set_scan_jobs`; `None` uses rayon's global pool.
+    pub(crate) scan_jobs: Option<usize>,
+
+    /// Retries for reconcile/delete writes that fail with SQLITE_BUSY/SQLITE_LOCKED.
+    /// Set from `SqliteOptions::busy_retries` at open.
+    pub(crate) busy_retries: u32,
+
+    /// Limit for the tree-sitter parse of one file (`--parse-timeout-ms`).
+    /// Set via `set_parse_timeout`; `None` parses without a limit.
+    pub(crate) parse_timeout: Option<std::time::Duration>,
+
+    /// Set to stop call-graph traversals early; shared via `cancel_token`.
+    pub(crate) cancel: Arc<std::sync::atomic::AtomicBool>,
+
+    /// Previous parse trees for incremental re-parsing (watch mode only).
+    /// Set via `enable_incremental_parse`; `None` means every index is a full parse.
+    pub(crate) tree_cache: Option<crate::ingest::incremental::ParseTreeCache>,
+
+    /// Per-phase indexing timings (`watch --profile`).
+    /// Set via `enable_profile`; `None` records nothing.
+    pub(crate) profile: Option<profile::PhaseProfile>,
+
+    /// Opened with `open_readonly`; every connection is read-only.
+    read_only: bool,
+
     /// Database file path for re-opening connections
     db_path: PathBuf,
 }
@@ -262,6 +334,11 @@ impl CodeGraph {
         &self.db_path
     }
 
+    /// Whether the graph was opened with [`CodeGraph::open_readonly`]
+    pub fn is_read_only(&self) -> bool {
+        self.read_only
+    }
+
     /// Load and cache compile_commands.json for C/C++ LLVM IR extraction.
     ///
     /// Once set, per-file flags are looked up during indexing and passed to clang.
@@ -272,6 +349,255 @@ impl CodeGraph {
         Ok(())
     }
 
+    /// Restrict indexing to the public API surface.
+    ///
+    /// Applies to files indexed after the call; see `ingest::api_surface`
+    /// for the per-language visibility rules.
+    pub fn set_api_only(&mut self, enabled: bool) {
+        self.api_only = enabled;
+        self.references.api_only = enabled;
+    }
+
+    /// Whether indexing is restricted to the public API surface
+    pub fn api_only(&self) -> bool {
+        self.api_only
+    }
+
+    /// Skip call-graph extraction when indexing.
+    ///
+    /// Applies to files indexed after the call. Symbols, references, chunks
+    /// and metrics are still recorded.
+    pub fn set_skip_calls(&mut self, enabled: bool) {
+        self.skip_calls = enabled;
+    }
+
+    /// Whether call-graph extraction is skipped
+    pub fn skip_calls(&self) -> bool {
+        self.skip_calls
+    }
+
+    /// Store the content of newly indexed code chunks zstd-compressed.
+    ///
+    /// Existing chunks stay readable whether or not they were compressed.
+    pub fn set_compress_chunks(&mut self, enabled: bool) {
+        self.chunks.set_compress(enabled);
+    }
+
+    /// Follow symlinks during directory scans.
+    ///
+    /// Only symlinks whose canonical target stays within the scan root are
+    /// followed; each directory is entered at most once, so symlink cycles
+    /// terminate.
+    pub fn set_follow_symlinks(&mut self, enabled: bool) {
+        self.follow_symlinks = enabled;
+    }
+
+    /// Bound the number of threads that read files during directory scans.
+    ///
+    /// Graph writes stay sequential; `None` uses one thread per CPU.
+    pub fn set_scan_jobs(&mut self, jobs: Option<usize>) {
+        self.scan_jobs = jobs;
+    }
+
+    /// Abort a file's parse after `timeout` (`None` removes the limit).
+    ///
+    /// `index_file` then fails with [`crate::ingest::pool::ParseTimeout`]
+    /// without recording the file, and scans report it as skipped.
+    pub fn set_parse_timeout(&mut self, timeout: Option<std::time::Duration>) {
+        self.parse_timeout = timeout;
+    }
+
+    /// Parse timeout applied by `index_file`
+    pub fn parse_timeout(&self) -> Option<std::time::Duration> {
+        self.parse_timeout
+    }
+
+    /// Flag that cancels long-running call-graph queries.
+    ///
+    /// Once set (e.g. from a SIGINT handler), `reachable_symbols`,
+    /// `enumerate_paths`, `shortest_path` and their relatives stop at the
+    /// next node they visit and return the partial result. The flag stays
+    /// set until cleared.
+    pub fn cancel_token(&self) -> Arc<std::sync::atomic::AtomicBool> {
+        Arc::clone(&self.cancel)
+    }
+
+    /// Whether the flag from `cancel_token` has been set
+    pub fn is_cancelled(&self) -> bool {
+        self.cancel.load(std::sync::atomic::Ordering::Relaxed)
+    }
+
+    /// Record how file paths are stored from now on.
+    ///
+    /// Relative style stores paths relative to `root`; absolute style
+    /// resolves relative input against `root`. Fails when the database
+    /// already holds files in the other style; convert those first with
+    /// `rebase_paths`.
+    pub fn set_path_style(&mut self, style: PathStyle, root: &Path) -> Result<()> {
+        let root = std::fs::canonicalize(root).or_else(|_| std::path::absolute(root))?;
+        let settings = PathSettings {
+            style,
+            root: path_style::clean_path(&root),
+        };
+
+        let conn = self.side_conn.lock();
+        let conflict = match &self.files.path_settings {
+            Some(current) if current == &settings => return Ok(()),
+            Some(current) => {
+                current.style != style
+                    || (style == PathStyle::Relative && current.root != settings.root)
+            }
+            None => {
+                let (absolute, relative) = path_style::stored_path_counts(&conn)?;
+                match style {
+                    PathStyle::Absolute => relative > 0,
+                    PathStyle::Relative => absolute > 0,
+                }
+            }
+        };
+        if conflict {
+            return Err(anyhow::anyhow!(
+                "Database already stores paths in another form; convert it with \
+                 `magellan rebase-paths` before switching to {} paths under {}",
+                style.as_str(),
+                settings.root.display()
+            ));
+        }
+
+        path_style::store(&conn, &settings)?;
+        drop(conn);
+
+        self.files.path_settings = Some(settings);
+        self.files.rebuild_file_index()
+    }
+
+    /// Record `root` as the project root of a database that has none.
+    ///
+    /// `watch` stores canonical absolute paths, so this records the absolute
+    /// style: `--file` globs and source reads then resolve against `root`.
+    /// Databases that already record a style, or hold relative paths, are
+    /// left as they are.
+    pub fn record_default_root(&mut self, root: &Path) -> Result<()> {
+        if self.files.path_settings.is_some() {
+            return Ok(());
+        }
+        let (_, relative) = path_style::stored_path_counts(&self.side_conn.lock())?;
+        if relative > 0 {
+            return Ok(());
+        }
+        self.set_path_style(PathStyle::Absolute, root)
+    }
+
+    /// Path style recorded for the database, if one was set
+    pub fn path_settings(&self) -> Option<&PathSettings> {
+        self.files.path_settings.as_ref()
+    }
+
+    /// Normalize file contents before hashing from now on.
+    ///
+    /// The setting is recorded in the database, so later opens hash the same
+    /// way. Files hashed under a different setting show up as changed and are
+    /// reindexed by the next scan.
+    pub fn set_hash_normalization(&mut self, normalization: HashNormalization) -> Result<()> {
+        if self.files.hash_normalization == normalization {
+            return Ok(());
+        }
+        content_hash::store(&self.side_conn.lock(), &normalization)?;
+        self.files.hash_normalization = normalization;
+        Ok(())
+    }
+
+    /// Hash normalization recorded for the database
+    pub fn hash_normalization(&self) -> HashNormalization {
+        self.files.hash_normalization
+    }
+
+    /// Hash files indexed from now on with `algorithm`.
+    ///
+    /// The setting is recorded in the database. Existing File nodes keep the
+    /// algorithm they are tagged with until their content changes, so
+    /// switching does not make unchanged files look modified.
+    pub fn set_hash_algorithm(&mut self, algorithm: HashAlgorithm) -> Result<()> {
+        if self.files.hash_algorithm == algorithm {
+            return Ok(());
+        }
+        content_hash::store_algorithm(&self.side_conn.lock(), algorithm)?;
+        self.files.hash_algorithm = algorithm;
+        Ok(())
+    }
+
+    /// Hash algorithm recorded for the database
+    pub fn hash_algorithm(&self) -> HashAlgorithm {
+        self.files.hash_algorithm
+    }
+
+    /// Rewrite stored paths under `from` to lie under `to`.
+    ///
+    /// See `path_style::rebase_paths`.
+    pub fn rebase_paths(&mut self, from: &RebaseRoot, to: &RebaseRoot) -> Result<RebaseReport> {
+        self.invalidate_symbol_index();
+        path_style::rebase_paths(self, from, to)
+    }
+
+    /// Keep parse trees between re-indexes of the same file.
+    ///
+    /// Subsequent `index_file` calls apply the byte diff to the previous tree
+    /// and let tree-sitter reuse unchanged subtrees. At most `capacity` files
+    /// are kept (least recently indexed evicted first); deleting a file drops
+    /// its tree.
+    pub fn enable_incremental_parse(&mut self, capacity: usize) {
+        self.tree_cache = Some(crate::ingest::incremental::ParseTreeCache::new(capacity));
+    }
+
+    /// Time the indexing phases of every subsequent index.
+    ///
+    /// See [`profile::PhaseProfile`]; the timings never affect indexing.
+    pub fn enable_profile(&mut self) {
+        self.profile = Some(profile::PhaseProfile::default());
+    }
+
+    /// Phase timings collected since `enable_profile`, if enabled
+    pub fn profile(&self) -> Option<&profile::PhaseProfile> {
+        self.profile.as_ref()
+    }
+
+    pub(crate) fn record_phase(
+        &mut self,
+        phase: profile::IndexPhase,
+        elapsed: std::time::Duration,
+    ) {
+        if let Some(profile) = self.profile.as_mut() {
+            profile.record(phase, elapsed);
+        }
+    }
+
+    pub(crate) fn record_profile_batch(&mut self) {
+        if let Some(profile) = self.profile.as_mut() {
+            profile.record_batch();
+        }
+    }
+
+    /// Whether a parse tree is cached for `path`
+    pub fn has_cached_tree(&self, path: &str) -> bool {
+        let key = self.files.index_key(path);
+        self.tree_cache
+            .as_ref()
+            .is_some_and(|cache| cache.contains(&key))
+    }
+
+    /// 1-based line and column of the first syntax error in the cached tree for `path`
+    pub(crate) fn cached_syntax_error(&mut self, path: &str) -> Option<(usize, usize)> {
+        let key = self.files.index_key(path);
+        let point = self.tree_cache.as_mut()?.first_syntax_error(&key)?;
+        Some((point.row + 1, point.column + 1))
+    }
+
+    pub(crate) fn evict_cached_tree(&mut self, path: &str) {
+        if let Some(cache) = self.tree_cache.as_mut() {
+            cache.remove(&self.files.index_key(path));
+        }
+    }
+
     pub(crate) fn side_connection(&self) -> &Arc<parking_lot::Mutex<rusqlite::Connection>> {
         &self.side_conn
     }
@@ -280,6 +606,17 @@ impl CodeGraph {
         self.files.compute_hash(source)
     }
 
+    /// Content hash of `source` using `algorithm` instead of the database's
+    ///
+    /// Used to check a File node against the algorithm it is tagged with.
+    pub(crate) fn compute_content_hash_with(
+        &self,
+        source: &[u8],
+        algorithm: HashAlgorithm,
+    ) -> String {
+        self.files.compute_hash_with(source, algorithm)
+    }
+
     pub fn navigator(&self) -> navigator::SymbolNavigator<'_> {
         navigator::SymbolNavigator::new(self)
     }
@@ -336,13 +673,13 @@ impl CodeGraph {
                 let mins = stale_secs / 60;
                 let msg = if mins > 0 {
                     format!(
-                        "⚠️  Embeddings are ~{} minutes stale. Run `magellan embed --db {}` to refresh.",
+                        "Embeddings are ~{} minutes stale. Run `magellan embed --db {}` to refresh.",
                         mins,
                         self.db_path.display()
                     )
                 } else {
                     format!(
-                        "⚠️  Embeddings are ~{} seconds stale. Run `magellan embed --db {}` to refresh.",
+                        "Embeddings are ~{} seconds stale. Run `magellan embed --db {}` to refresh.",
                         stale_secs,
                         self.db_path.display()
                     )
@@ -472,8 +809,42 @@ impl CodeGraph {
     /// # Returns
     /// A new CodeGraph instance
     pub fn open<P: AsRef<Path>>(db_path: P) -> Result<Self> {
+        Self::open_with_options(db_path, &SqliteOptions::default())
+    }
+
+    /// Open a graph database with explicit SQLite PRAGMA settings
+    ///
+    /// `options` controls the journal mode, `synchronous` level and busy
+    /// timeout for the backend connection and the side-table connections.
+    /// See [`SqliteOptions`] for the durability tradeoffs.
+    pub fn open_with_options<P: AsRef<Path>>(db_path: P, options: &SqliteOptions) -> Result<Self> {
+        Self::open_impl(db_path.as_ref(), options, false)
+    }
+
+    /// Open an existing graph database without write access
+    ///
+    /// Every connection is opened `SQLITE_OPEN_READ_ONLY` (`mode=ro` for the
+    /// sqlitegraph pool) and memory-mapped, so the graph never takes a write
+    /// lock, creates a journal, or runs schema setup; it is safe to use while
+    /// `watch` writes the same file. Writes through the returned graph fail
+    /// immediately with SQLite's read-only error, and executions and
+    /// telemetry are not recorded.
+    ///
+    /// Fails if the file does not exist or its schema needs an upgrade.
+    pub fn open_readonly<P: AsRef<Path>>(db_path: P) -> Result<Self> {
+        Self::open_impl(db_path.as_ref(), &SqliteOptions::default(), true)
+    }
+
+    fn open_impl(db_path: &Path, options: &SqliteOptions, read_only: bool) -> Result<Self> {
         // Convert to PathBuf for reuse
-        let db_path_buf = db_path.as_ref().to_path_buf();
+        let db_path_buf = db_path.to_path_buf();
+
+        if read_only && (is_memory_db(&db_path_buf) || !db_path_buf.exists()) {
+            anyhow::bail!(
+                "Database not found: {} (read-only open does not create databases)",
+                db_path_buf.display()
+            );
+        }
 
         // Phase 1: read-only compatibility preflight for existing DB files.
         // This MUST run before any sqlitegraph or Magellan side-table writes occur.
@@ -493,9 +864,22 @@ impl CodeGraph {
             Option<Arc<sqlitegraph::SqliteGraphBackend>>,
         ) = {
             use sqlitegraph::{SqliteGraph, SqliteGraphBackend};
-            let cfg = sqlitegraph::SqliteConfig::new().with_pool_size(1);
-            let sqlite_graph = SqliteGraph::open_with_config(&db_path_buf, &cfg)?;
-            eprintln!("Using SQLite backend: {:?}", db_path_buf);
+            let sqlite_graph = if read_only {
+                // No migrations: they would write. The pool already sets mmap_size.
+                let cfg = sqlitegraph::SqliteConfig::new()
+                    .with_pool_size(1)
+                    .with_migrations_disabled(true)
+                    .with_pragma("busy_timeout", &options.busy_timeout_ms.to_string());
+                SqliteGraph::open_with_config(pragmas::read_only_uri(&db_path_buf), &cfg)?
+            } else {
+                let cfg = sqlitegraph::SqliteConfig::new()
+                    .with_pool_size(1)
+                    .with_pragma("journal_mode", options.journal_mode.as_str())
+                    .with_pragma("synchronous", options.synchronous.as_str())
+                    .with_pragma("busy_timeout", &options.busy_timeout_ms.to_string());
+                SqliteGraph::open_with_config(&db_path_buf, &cfg)?
+            };
+            log_info!("Using SQLite backend: {:?}", db_path_buf);
             let sqlite_backend = Arc::new(SqliteGraphBackend::from_graph(sqlite_graph));
             let backend: Arc<dyn GraphBackend> = { (sqlite_backend.clone()) as _ };
             (backend, Some(sqlite_backend))
@@ -504,9 +888,10 @@ impl CodeGraph {
         #[cfg(not(feature = "sqlite-backend"))]
         compile_error!("'sqlite-backend' feature must be enabled");
 
-        // Phase 2b: Configure SQLite performance PRAGMAs
+        // Phase 2b: Configure SQLite performance PRAGMAs (file-level, so
+        // skipped for read-only opens)
         #[cfg(feature = "sqlite-backend")]
-        {
+        if !read_only {
             // Note: sqlitegraph 1.0.0 already configures these in from_connection(),
             // but we set them explicitly here to ensure they're applied even if
             // sqlitegraph changes its defaults in future versions.
@@ -520,24 +905,21 @@ impl CodeGraph {
                 anyhow::anyhow!("Failed to open connection for PRAGMA config: {}", e)
             })?;
 
-            // WAL mode for better concurrency (allows reads during writes)
-            // query() returns the new mode value, execute() would error
+            // WAL mode (the default) allows reads during writes.
             // Note: :memory: databases don't support WAL mode (returns "memory")
-            let journal_mode = pragma_conn
-                .query_row("PRAGMA journal_mode = WAL", [], |row| {
-                    let mode: String = row.get(0)?;
-                    Ok(mode)
-                })
-                .map_err(|e| anyhow::anyhow!("Failed to set WAL mode: {}", e))?;
-            // Only assert WAL mode for file-based databases (not :memory:)
+            let journal_mode = options.apply_journal_mode(&pragma_conn)?;
+            // Only assert the requested mode for file-based databases (not :memory:)
             if !is_memory_db(&db_path_buf) {
-                debug_assert_eq!(journal_mode, "wal", "WAL mode should be enabled");
+                debug_assert_eq!(
+                    journal_mode,
+                    options.journal_mode.as_str(),
+                    "journal_mode should match the requested mode"
+                );
             }
 
-            // Faster writes (safe with WAL mode - durability still guaranteed)
-            pragma_conn
-                .execute("PRAGMA synchronous = NORMAL", [])
-                .map_err(|e| anyhow::anyhow!("Failed to set synchronous: {}", e))?;
+            // synchronous and busy_timeout are per-connection; they are applied
+            // again to each connection opened below.
+            options.apply_to_connection(&pragma_conn)?;
 
             // Increase cache (negative value = KB, -64000 = 64MB)
             // Note: sqlitegraph also sets this to -64000, ensuring 64MB cache
@@ -557,29 +939,42 @@ impl CodeGraph {
         let mut files = files::FileOps {
             backend: Arc::clone(&backend),
             file_index,
+            path_settings: None,
+            hash_normalization: HashNormalization::default(),
+            hash_algorithm: HashAlgorithm::default(),
         };
 
-        // Populate file_index with existing File nodes from database
-        files.rebuild_file_index()?;
-
         // Phase 3: SQLite-specific side-table initialization
         let (side_tables, chunks, execution_log, metrics, telemetry, needs_backfill, side_conn) = {
             // Open ONE shared connection for all Magellan side-table operations.
             // Previously each subsystem opened its own connection (~10 total).
-            let side_conn = rusqlite::Connection::open(&db_path_buf).map_err(|e| {
-                anyhow::anyhow!("Failed to open shared side-table connection: {}", e)
-            })?;
-            side_conn.pragma_update(None, "busy_timeout", 5000)?;
+            let side_conn = if read_only {
+                pragmas::open_read_only_connection(&db_path_buf, options.busy_timeout_ms)?
+            } else {
+                let conn = rusqlite::Connection::open(&db_path_buf).map_err(|e| {
+                    anyhow::anyhow!("Failed to open shared side-table connection: {}", e)
+                })?;
+                options.apply_to_connection(&conn)?;
+                conn
+            };
             let side_conn_arc = Arc::new(parking_lot::Mutex::new(side_conn));
 
             // Check whether DDL needs to run at all.
             let needs_ddl = db_compat::needs_schema_upgrade(&side_conn_arc.lock())
                 .map_err(|e| anyhow::anyhow!(e.to_string()))?;
+            if read_only && needs_ddl {
+                anyhow::bail!(
+                    "Database {} needs a schema upgrade; open it once with a writing command (e.g. `magellan watch`) first",
+                    db_path_buf.display()
+                );
+            }
 
             // Phase 3a: Magellan-owned DB compatibility metadata.
             // MUST run after sqlitegraph open and before any other Magellan side-table writes.
-            db_compat::ensure_magellan_meta(&side_conn_arc.lock(), &db_path_buf)
-                .map_err(|e| anyhow::anyhow!(e.to_string()))?;
+            if !read_only {
+                db_compat::ensure_magellan_meta(&side_conn_arc.lock(), &db_path_buf)
+                    .map_err(|e| anyhow::anyhow!(e.to_string()))?;
+            }
 
             // Create SQLite side tables reusing the shared connection
             let side_tables: Arc<dyn side_tables::SideTables> =
@@ -589,24 +984,43 @@ impl CodeGraph {
 
             // Open a shared connection for ChunkStore to enable transactional operations
             // This allows chunk operations to participate in transactions with graph operations
-            let shared_conn = rusqlite::Connection::open(&db_path_buf).map_err(|e| {
-                anyhow::anyhow!("Failed to open shared connection for ChunkStore: {}", e)
-            })?;
-            shared_conn.pragma_update(None, "busy_timeout", 5000)?;
+            let shared_conn = if read_only {
+                pragmas::open_read_only_connection(&db_path_buf, options.busy_timeout_ms)?
+            } else {
+                let conn = rusqlite::Connection::open(&db_path_buf).map_err(|e| {
+                    anyhow::anyhow!("Failed to open shared connection for ChunkStore: {}", e)
+                })?;
+                options.apply_to_connection(&conn)?;
+                conn
+            };
 
             // Initialize ChunkStore with shared connection and ensure schema exists
-            let chunks = ChunkStore::with_connection(shared_conn);
-            chunks.ensure_schema()?;
-
-            // Initialize ExecutionLog reusing the shared connection
-            let execution_log =
-                execution_log::ExecutionLog::with_connection(Arc::clone(&side_conn_arc));
+            let chunks = ChunkStore::with_connection(shared_conn).with_busy_handling(options);
+            if !read_only {
+                chunks.ensure_schema()?;
+            }
 
-            // Initialize MetricsOps reusing the shared connection
-            let metrics = metrics::MetricsOps::with_connection(Arc::clone(&side_conn_arc));
+            // Initialize ExecutionLog and TelemetryOps reusing the shared
+            // connection; read-only graphs record neither
+            let (execution_log, telemetry) = if read_only {
+                (
+                    execution_log::ExecutionLog::read_only(Arc::clone(&side_conn_arc)),
+                    telemetry::TelemetryOps::read_only(Arc::clone(&side_conn_arc)),
+                )
+            } else {
+                (
+                    execution_log::ExecutionLog::with_connection(Arc::clone(&side_conn_arc)),
+                    telemetry::TelemetryOps::with_connection(Arc::clone(&side_conn_arc)),
+                )
+            };
 
-            // Initialize TelemetryOps reusing the shared connection
-            let telemetry = telemetry::TelemetryOps::with_connection(Arc::clone(&side_conn_arc));
+            // Initialize MetricsOps reusing the shared connection; read-only
+            // graphs skip its schema setup
+            let metrics = if read_only {
+                metrics::MetricsOps::read_only(Arc::clone(&side_conn_arc))
+            } else {
+                metrics::MetricsOps::with_connection(Arc::clone(&side_conn_arc))
+            };
 
             // Only run AST / CFG / coverage DDL when the schema is new or was upgraded.
             // On warm opens this skips ~6 redundant CREATE TABLE IF NOT EXISTS calls.
@@ -627,12 +1041,16 @@ impl CodeGraph {
                     .map_err(|e| anyhow::anyhow!(e.to_string()))?;
             }
 
-            // Coverage schema is not versioned in magellan_meta; always ensure it.
-            db_compat::ensure_coverage_schema(&side_conn_arc.lock(), &db_path_buf)
-                .map_err(|e| anyhow::anyhow!(e.to_string()))?;
+            // Coverage and trait-impl schemas are not versioned in magellan_meta;
+            // always ensure them.
+            if !read_only {
+                db_compat::ensure_coverage_schema(&side_conn_arc.lock(), &db_path_buf)
+                    .map_err(|e| anyhow::anyhow!(e.to_string()))?;
+                implementors::ensure_schema(&side_conn_arc.lock())?;
+            }
 
             // Detect if this is an upgrade (metrics tables exist but are empty)
-            let needs_backfill = {
+            let needs_backfill = !read_only && {
                 // Check if metrics tables are empty
                 let metric_count: i64 = side_conn_arc
                     .lock()
@@ -664,6 +1082,13 @@ impl CodeGraph {
             )
         };
 
+        // Populate file_index with existing File nodes from database,
+        // keyed by the path style recorded for it
+        files.path_settings = path_style::load(&side_conn.lock())?;
+        files.hash_normalization = content_hash::load(&side_conn.lock())?;
+        files.hash_algorithm = content_hash::load_algorithm(&side_conn.lock())?;
+        files.rebuild_file_index()?;
+
         // Initialize file node cache with capacity of 128 entries
         let file_node_cache = cache::FileNodeCache::new(128);
 
@@ -692,11 +1117,13 @@ impl CodeGraph {
                 backend: Arc::clone(&backend),
                 sqlite_backend: sqlite_backend.clone(),
                 batch_mode: true,
+                api_only: false,
             },
             calls: call_ops::CallOps {
                 backend: Arc::clone(&backend),
                 sqlite_backend: sqlite_backend.clone(),
                 batch_mode: true,
+                qualified_calls: parking_lot::Mutex::new(std::collections::BTreeMap::new()),
             },
             imports: imports::ImportOps {
                 backend: Arc::clone(&backend),
@@ -710,6 +1137,8 @@ impl CodeGraph {
             entity_cache,
             name_cache,
             expand_cache,
+            symbol_cache_enabled: false,
+            symbol_index: None,
             cfg_ops: cfg_ops::CfgOps::new(chunks),
             side_tables,
             side_conn,
@@ -724,6 +1153,16 @@ impl CodeGraph {
                 0,
             ),
             compile_commands: None,
+            api_only: false,
+            skip_calls: false,
+            follow_symlinks: false,
+            scan_jobs: None,
+            busy_retries: options.busy_retries,
+            parse_timeout: Some(crate::ingest::pool::DEFAULT_PARSE_TIMEOUT),
+            cancel: Arc::new(std::sync::atomic::AtomicBool::new(false)),
+            tree_cache: None,
+            profile: None,
+            read_only,
             db_path: db_path_buf,
         };
 
@@ -734,7 +1173,7 @@ impl CodeGraph {
         // Build symbol lookup index for O(1) resolution
         // This is a one-time cost (~50-100ms for 10k symbols) that enables fast lookups
         if let Err(e) = graph.symbols.lookup.rebuild_from_backend(&*backend) {
-            eprintln!("Warning: Failed to build symbol lookup index: {}", e);
+            log_warn!("Failed to build symbol lookup index: {}", e);
         }
 
         // Trigger backfill if we have existing symbols but no metrics
@@ -764,6 +1203,14 @@ impl CodeGraph {
         wal::checkpoint_conn(&conn).map_err(|e| anyhow::anyhow!("WAL checkpoint failed: {}", e))
     }
 
+    /// Checkpoint the SQLite WAL and report frame counts (`magellan checkpoint`).
+    ///
+    /// A checkpoint blocked by readers is reported in the stats, not as an error.
+    pub fn checkpoint_wal_stats(&self) -> Result<wal::CheckpointStats> {
+        let conn = self.side_conn.lock();
+        wal::checkpoint_truncate(&conn).map_err(|e| anyhow::anyhow!("WAL checkpoint failed: {}", e))
+    }
+
     /// Rebuild the FTS5 search index using the existing side connection.
     ///
     /// This is the preferred method for rebuilding FTS5 during watch/indexing
@@ -1029,7 +1476,7 @@ impl CodeGraph {
                 }
             }
             let t_insert = t_insert_start.elapsed();
-            eprintln!(
+            log_verbose!(
                 "[embed timing] file={} chunks={} embed={:?} insert={:?}",
                 file_path,
                 chunks.len(),
@@ -1150,11 +1597,62 @@ impl CodeGraph {
         query::symbol_id_by_name(self, path, name)
     }
 
+    /// Count symbols matching a filter with a single `COUNT(*)` query
+    pub fn count_symbols_matching(&mut self, filter: &query::SymbolCountFilter) -> Result<usize> {
+        query::count_symbols_matching(self, filter)
+    }
+
     /// Query the persisted stable symbol ID of a specific symbol by file path and symbol name.
     pub fn stable_symbol_id_by_name(&mut self, path: &str, name: &str) -> Result<Option<String>> {
         query::stable_symbol_id_by_name(self, path, name)
     }
 
+    /// Delete code chunks and metrics rows whose File node no longer exists.
+    ///
+    /// With `dry_run` the rows are only counted. Used by `magellan prune`.
+    pub fn prune_orphans(&self, dry_run: bool) -> Result<PruneReport> {
+        prune::prune_orphans(self, dry_run)
+    }
+
+    /// Map each symbol's FQN to its persisted stable symbol IDs.
+    ///
+    /// Used by `verify-ids` to diff a live database against a fresh reindex.
+    pub fn symbol_id_map(&mut self) -> Result<std::collections::BTreeMap<String, Vec<String>>> {
+        query::symbol_id_map(self)
+    }
+
+    /// Map each public symbol's FQN to its kind and signatures.
+    ///
+    /// Used by `api-diff` to compare the API surface of two databases.
+    pub fn api_symbol_map(
+        &mut self,
+    ) -> Result<std::collections::BTreeMap<String, query::ApiSymbol>> {
+        query::api_symbol_map(self)
+    }
+
+    /// Map each symbol's FQN to its defining locations.
+    ///
+    /// Used by `diff` to compare symbols across two databases.
+    pub fn symbol_location_map(
+        &mut self,
+    ) -> Result<std::collections::BTreeMap<String, Vec<query::SymbolLocation>>> {
+        query::symbol_location_map(self)
+    }
+
+    /// Collect the distinct caller-to-callee edges.
+    ///
+    /// Used by `diff` to compare call graphs across two databases.
+    pub fn call_edge_set(&mut self) -> Result<std::collections::BTreeSet<query::CallEdge>> {
+        query::call_edge_set(self)
+    }
+
+    /// Find symbols in the same file whose byte spans cross or duplicate each other.
+    ///
+    /// Properly nested spans (a method inside its impl) are not reported.
+    pub fn overlapping_symbol_spans(&self) -> Result<Vec<schema::SpanOverlap>> {
+        query::overlapping_symbol_spans(self)
+    }
+
     /// Index references for a file into the graph
     ///
     /// # Behavior
@@ -1170,7 +1668,8 @@ impl CodeGraph {
     /// # Returns
     /// Number of references indexed
     pub fn index_references(&mut self, path: &str, source: &[u8]) -> Result<usize> {
-        query::index_references(self, path, source)
+        let path = self.files.stored_path(path);
+        query::index_references(self, &path, source)
     }
 
     /// Query all references to a specific symbol
@@ -1184,6 +1683,14 @@ impl CodeGraph {
         query::references_to_symbol(self, symbol_id)
     }
 
+    /// Query the imports of a file that resolve to indexed symbols
+    ///
+    /// Each entry pairs the `use` reference with the imported symbol's stable
+    /// ID, FQN, and defining file, for file-level dependency analysis.
+    pub fn imports_of_file(&mut self, path: &str) -> Result<Vec<query::FileImport>> {
+        query::imports_of_file(self, path)
+    }
+
     /// Lookup symbol extent (byte + line span) for a specific symbol name in a file.
     pub fn symbol_extents(
         &mut self,
@@ -1208,7 +1715,17 @@ impl CodeGraph {
     /// # Returns
     /// Number of calls indexed
     pub fn index_calls(&mut self, path: &str, source: &[u8]) -> Result<usize> {
-        calls::index_calls(self, path, source)
+        let path = self.files.stored_path(path);
+        calls::index_calls(self, &path, source)
+    }
+
+    /// Relink module-qualified calls whose callee was indexed after the caller
+    ///
+    /// Scans and `reindex_paths` run this when they finish; call it after
+    /// indexing a batch of files one by one. Returns the number of files
+    /// whose calls were re-indexed.
+    pub fn relink_qualified_calls(&mut self) -> Result<usize> {
+        calls::relink_qualified_calls(self)
     }
 
     /// Query all calls FROM a specific symbol (forward call graph)
@@ -1402,6 +1919,18 @@ impl CodeGraph {
         scan::scan_directory_with_filter(self, dir_path, filter, progress)
     }
 
+    /// Reindex only the listed paths under `root`, deleting ones that no
+    /// longer exist.
+    ///
+    /// Used for incremental reindexing driven by `git diff` or a file list.
+    pub fn reindex_paths(
+        &mut self,
+        root: &Path,
+        paths: &[PathBuf],
+    ) -> Result<scan::ReindexPathsResult> {
+        scan::reindex_paths(self, root, paths)
+    }
+
     /// Async version of scan_directory with parallel file reading
     ///
     /// Uses tokio for async file I/O, improving performance on slow filesystems.
@@ -1440,6 +1969,21 @@ impl CodeGraph {
         self.metrics.backfill_all_metrics(progress)
     }
 
+    /// Backfill metrics for the indexed files whose stored path matches `only`
+    ///
+    /// Like [`backfill_metrics`](Self::backfill_metrics), but scoped to a
+    /// glob or substring (`None` = every file), with stored paths resolved
+    /// against the database's path root before reading.
+    pub fn backfill_metrics_matching(
+        &mut self,
+        progress: Option<&ScanProgress>,
+        only: Option<&filter::FilePattern>,
+    ) -> Result<metrics::BackfillResult> {
+        let files = &self.files;
+        self.metrics
+            .backfill_metrics_matching(progress, only, &|path| files.disk_path(path))
+    }
+
     /// Export all graph data to JSON format
     ///
     /// # Returns
@@ -1496,7 +2040,8 @@ impl CodeGraph {
     /// # Returns
     /// Vector of CodeChunk for all chunks in the file
     pub fn get_code_chunks(&self, file_path: &str) -> Result<Vec<CodeChunk>> {
-        self.chunks.get_chunks_for_file(file_path)
+        self.chunks
+            .get_chunks_for_file(&self.files.stored_path(file_path))
     }
 
     /// Get code chunks for a specific symbol in a file.
@@ -1534,6 +2079,26 @@ impl CodeGraph {
             .get_chunk_by_span(file_path, byte_start, byte_end)
     }
 
+    /// Checksums for a span, with `content_sha256` from stored chunk data
+    ///
+    /// `content_sha256` is the SHA-256 of the chunk stored for exactly this
+    /// span, so it describes the indexed content even if the file has changed
+    /// since. Spans without a chunk (e.g. call sites) fall back to hashing the
+    /// same byte range on disk. `get`, `find`, `query`, and `refs` all use
+    /// this for `--with-checksums`.
+    pub fn span_checksums(
+        &self,
+        file_path: &str,
+        byte_start: usize,
+        byte_end: usize,
+    ) -> SpanChecksums {
+        let mut checksums = SpanChecksums::compute(file_path, byte_start, byte_end);
+        if let Ok(Some(chunk)) = self.get_code_chunk_by_span(file_path, byte_start, byte_end) {
+            checksums.content_sha256 = Some(SpanChecksums::sha256_hex(chunk.content.as_bytes()));
+        }
+        checksums
+    }
+
     /// Store code chunks for a file.
     ///
     /// # Arguments
@@ -1550,6 +2115,16 @@ impl CodeGraph {
         self.chunks.count_chunks()
     }
 
+    /// All stored code chunks, ordered by file path and byte offset
+    pub fn all_code_chunks(&self) -> Result<Vec<CodeChunk>> {
+        self.chunks.get_all_chunks()
+    }
+
+    /// Location on disk of a path as stored in the database
+    pub fn disk_path(&self, stored_path: &str) -> PathBuf {
+        self.files.disk_path(stored_path)
+    }
+
     /// Get the execution log for recording command execution
     pub fn execution_log(&self) -> &execution_log::ExecutionLog {
         &self.execution_log
@@ -1637,6 +2212,39 @@ impl CodeGraph {
         self.expand_cache.clear();
     }
 
+    /// Build the in-memory symbol index now and answer lookups from it
+    ///
+    /// The cache is optional and off by default. Once warmed, `find --name`
+    /// and `find --symbol-id` lookups resolve from memory instead of SQL,
+    /// which pays off in long-lived processes that serve repeated queries.
+    /// `index_file` and `delete_file` drop the index and the next lookup
+    /// rebuilds it, so it never returns symbols the database no longer has.
+    ///
+    /// # Returns
+    /// Number of symbols in the index
+    pub fn warm_symbol_cache(&mut self) -> Result<usize> {
+        self.symbol_cache_enabled = true;
+        Ok(self.symbol_index()?.map_or(0, cache::WarmSymbolIndex::len))
+    }
+
+    /// The warm symbol index, rebuilt first if a mutation dropped it
+    ///
+    /// Returns `None` unless `warm_symbol_cache` has been called.
+    pub fn symbol_index(&mut self) -> Result<Option<&cache::WarmSymbolIndex>> {
+        if !self.symbol_cache_enabled {
+            return Ok(None);
+        }
+        if self.symbol_index.is_none() {
+            self.symbol_index = Some(query::build_symbol_index(self)?);
+        }
+        Ok(self.symbol_index.as_ref())
+    }
+
+    /// Drop the warm symbol index after symbols changed
+    pub(crate) fn invalidate_symbol_index(&mut self) {
+        self.symbol_index = None;
+    }
+
     /// Get backend for testing/benchmarking
     ///
     /// This method provides access to the underlying graph backend for
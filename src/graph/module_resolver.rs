@@ -151,6 +151,46 @@ impl ModuleResolver {
             .map(|pos| module_path[..pos].to_string())
     }
 
+    /// Candidate module paths named by a path qualifier, in priority order
+    ///
+    /// Examples (from "src/foo/bar.rs"):
+    /// - ["crate", "util"] -> ["crate::util"]
+    /// - ["super", "qux"] -> ["crate::foo::qux"]
+    /// - ["self", "inner"] -> ["crate::foo::bar::inner"]
+    /// - ["util"] -> ["crate::foo::bar::util", "crate::util"]
+    pub(crate) fn qualifier_module_paths(current_file: &str, qualifier: &[&str]) -> Vec<String> {
+        let current_file = crate::graph::files::normalize_path_for_index(current_file);
+        let current_module = Self::file_path_to_module_path(&current_file);
+
+        let (mut base, mut rest) = match qualifier.first() {
+            None => return Vec::new(),
+            Some(&"crate") => ("crate".to_string(), &qualifier[1..]),
+            Some(&"self") => (current_module, &qualifier[1..]),
+            Some(&"super") => (current_module, qualifier),
+            Some(_) => {
+                let relative = std::iter::once(current_module.as_str())
+                    .chain(qualifier.iter().copied())
+                    .collect::<Vec<_>>()
+                    .join("::");
+                let absolute = format!("crate::{}", qualifier.join("::"));
+                return vec![relative, absolute];
+            }
+        };
+
+        while let Some((&"super", tail)) = rest.split_first() {
+            base = match Self::get_parent_module(&base) {
+                Some(parent) => parent,
+                None => return Vec::new(),
+            };
+            rest = tail;
+        }
+
+        vec![std::iter::once(base.as_str())
+            .chain(rest.iter().copied())
+            .collect::<Vec<_>>()
+            .join("::")]
+    }
+
     /// Clear the module cache
     pub fn _clear_cache(&mut self) {
         self.cache.clear();
@@ -208,6 +248,32 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_qualifier_module_paths() {
+        let from = "src/foo/bar.rs";
+        assert_eq!(
+            ModuleResolver::qualifier_module_paths(from, &["crate", "util"]),
+            vec!["crate::util"]
+        );
+        assert_eq!(
+            ModuleResolver::qualifier_module_paths(from, &["super", "qux"]),
+            vec!["crate::foo::qux"]
+        );
+        assert_eq!(
+            ModuleResolver::qualifier_module_paths(from, &["super", "super", "top"]),
+            vec!["crate::top"]
+        );
+        assert_eq!(
+            ModuleResolver::qualifier_module_paths(from, &["self", "inner"]),
+            vec!["crate::foo::bar::inner"]
+        );
+        assert_eq!(
+            ModuleResolver::qualifier_module_paths(from, &["util"]),
+            vec!["crate::foo::bar::util", "crate::util"]
+        );
+        assert!(ModuleResolver::qualifier_module_paths("src/lib.rs", &["super", "x"]).is_empty());
+    }
+
     #[test]
     fn test_module_path_cache() {
         let mut cache = ModulePathCache::new();
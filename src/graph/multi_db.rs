@@ -15,6 +15,7 @@ use crate::context::{
     PaginatedResult, ProjectSummary, SymbolDetail, SymbolListItem, SymbolRelation,
 };
 use crate::graph::CodeGraph;
+use crate::log_warn;
 use crate::output::{ProjectCalleeInfo, ProjectCallerInfo, ProjectSymbolMatch, Span};
 
 /// A single graph connection with its project name
@@ -36,7 +37,7 @@ impl MultiDbContext {
             let name = project_name_from_path(path);
             match CodeGraph::open(path) {
                 Ok(graph) => projects.push(ProjectGraph { name, graph }),
-                Err(e) => eprintln!("Warning: skipping {}: {}", path.display(), e),
+                Err(e) => log_warn!("skipping {}: {}", path.display(), e),
             }
         }
         Ok(Self { projects })
@@ -72,7 +73,7 @@ impl MultiDbContext {
         for project in &mut self.projects {
             match get_project_summary(&mut project.graph) {
                 Ok(summary) => results.push((project.name.clone(), summary)),
-                Err(e) => eprintln!("Warning: summary failed for {}: {}", project.name, e),
+                Err(e) => log_warn!("summary failed for {}: {}", project.name, e),
             }
         }
         results
@@ -3,13 +3,19 @@
 //! Provides file indexing and deletion operations.
 
 use anyhow::Result;
+use std::collections::HashSet;
 use std::path::{Path, PathBuf};
 
 use sqlitegraph::{GraphBackend, NodeId, SnapshotId};
 
+use super::implementors;
+use super::pragmas::retry_on_busy;
+use super::profile::IndexPhase;
 use super::query;
 use super::CodeGraph;
 use crate::common::extract_symbol_content_safe;
+use crate::ingest::SymbolKind;
+use crate::{log_info, log_warn};
 
 /// Deterministic reconcile outcome.
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -66,9 +72,10 @@ impl DeleteResult {
 ///
 /// # Behavior
 /// 1. Compute SHA-256 hash of file contents
-/// 2. Upsert File node with path and hash
-/// 3. DELETE all existing Symbol nodes and DEFINES edges for this file
-/// 4. Detect language and parse symbols from source code
+/// 2. Detect language and parse the source, failing with `ParseTimeout`
+///    if the parse runs past the graph's parse timeout
+/// 3. Upsert File node with path and hash
+/// 4. DELETE all existing Symbol nodes and DEFINES edges for this file
 /// 5. Insert new Symbol nodes
 /// 6. Create DEFINES edges from File to each Symbol
 /// 7. Extract and store code chunks for each symbol
@@ -83,85 +90,118 @@ impl DeleteResult {
 /// Number of symbols indexed
 pub fn index_file(graph: &mut CodeGraph, path: &str, source: &[u8]) -> Result<usize> {
     use crate::generation::CodeChunk;
-    use crate::ingest::c::CParser;
-    use crate::ingest::cpp::CppParser;
-    use crate::ingest::cuda::CudaParser;
-    use crate::ingest::go::GoParser;
-    use crate::ingest::java::JavaParser;
-    use crate::ingest::javascript::JavaScriptParser;
     use crate::ingest::pool;
-    use crate::ingest::python::PythonParser;
-    use crate::ingest::typescript::TypeScriptParser;
-    use crate::ingest::{detect::Language, detect_language, Parser};
+    use crate::ingest::{
+        detect::Language, detect_language, extract_symbols_for_language, registry,
+    };
 
-    let hash = graph.files.compute_hash(source);
+    // Store the path in the database's path style
+    let stored_path = graph.files.stored_path(path);
+    let path = stored_path.as_str();
+    graph.invalidate_symbol_index();
 
-    // Step 1: Find or create file node
-    let file_id = graph.files.find_or_create_file_node(path, &hash)?;
+    // Wall time for the `timings` command; observational only
+    let index_start = std::time::Instant::now();
 
-    // Step 2: Delete all existing symbols for this file (verification)
-    // Note: This is a safeguard - reconcile_file_path() already calls delete_file_facts()
-    graph.symbols.delete_file_symbols(file_id)?;
-    // Verify deletion completed (_symbols_deleted may be 0 for new files)
+    let hash = graph.files.compute_hash(source);
 
-    // Step 3: Detect language and parse symbols from source
+    // Step 1: Detect language and parse the source
     let path_buf = PathBuf::from(path);
     let language = detect_language(&path_buf);
 
     // Parse source once and share the tree across all extractors
     // This eliminates redundant parsing (was 4+ parses per file)
+    // Watch mode keeps the previous tree so unchanged subtrees are reused
+    // Parsing happens before any write, so a timed-out file is not recorded
+    let parse_timeout = graph.parse_timeout;
     let parsed_tree = match language {
-        Some(lang) => match pool::with_parser(lang, |parser| parser.parse(source, None)) {
-            Ok(tree) => tree,
-            Err(e) => {
-                eprintln!("Warning: Failed to parse {} for indexing: {}", path, e);
-                None
+        Some(lang) if lang.has_grammar() => {
+            let parse_result = match graph.tree_cache.as_mut() {
+                Some(cache) => {
+                    let key = graph.files.index_key(path);
+                    cache.parse_with_timeout(&key, lang, source, parse_timeout)
+                }
+                None => pool::with_parser(lang, |parser| {
+                    pool::parse_with_timeout(parser, source, None, parse_timeout)
+                })
+                .and_then(|tree| tree.map_err(Into::into)),
+            };
+            match parse_result {
+                Ok(tree) => tree,
+                Err(e) if e.is::<pool::ParseTimeout>() => return Err(e),
+                Err(e) => {
+                    log_warn!("Failed to parse {} for indexing: {}", path, e);
+                    None
+                }
             }
-        },
-        None => None,
+        }
+        _ => None,
     };
 
+    // Step 2: Find or create file node
+    let file_id = graph.files.find_or_create_file_node(path, &hash)?;
+
+    // Step 3: Delete all existing symbols for this file (verification)
+    // Note: This is a safeguard - reconcile_file_path() already calls delete_file_facts()
+    graph.symbols.delete_file_symbols(file_id)?;
+    // Verify deletion completed (_symbols_deleted may be 0 for new files)
+
     // Extract symbols from the pre-parsed tree, avoiding redundant parsing.
+    // Languages without a grammar come from a registered parser instead.
     let symbol_facts = match (language, &parsed_tree) {
-        (Some(Language::Rust), Some(tree)) => {
-            Parser::extract_symbols_from_tree(tree, path_buf.clone(), source)
-        }
-        (Some(Language::C), Some(tree)) => {
-            CParser::extract_symbols_from_tree(tree, path_buf.clone(), source)
-        }
-        (Some(Language::Cpp), Some(tree)) => {
-            CppParser::extract_symbols_from_tree(tree, path_buf.clone(), source)
-        }
-        (Some(Language::Java), Some(tree)) => {
-            JavaParser::extract_symbols_from_tree(tree, path_buf.clone(), source)
-        }
-        (Some(Language::Python), Some(tree)) => {
-            PythonParser::extract_symbols_from_tree(tree, path_buf.clone(), source)
-        }
-        (Some(Language::JavaScript), Some(tree)) => {
-            JavaScriptParser::extract_symbols_from_tree(tree, path_buf.clone(), source)
-        }
-        (Some(Language::TypeScript), Some(tree)) => {
-            TypeScriptParser::extract_symbols_from_tree(tree, path_buf.clone(), source)
-        }
-        (Some(Language::Go), Some(tree)) => {
-            GoParser::extract_symbols_from_tree(tree, path_buf.clone(), source)
-        }
-        (Some(Language::Cuda), Some(tree)) => {
-            CudaParser::extract_symbols_from_tree(tree, path_buf.clone(), source)
+        (Some(lang), Some(tree)) => {
+            extract_symbols_for_language(lang, tree, path_buf.clone(), source)
         }
+        (Some(lang), None) if !lang.has_grammar() => registry::parser_for_language(lang)
+            .map(|parser| parser.extract(&path_buf, source))
+            .unwrap_or_default(),
         // Unknown language or parse failure — return empty
         _ => Vec::new(),
     };
 
+    // Step 3.5: API-surface mode keeps only public symbols and records signatures
+    let symbol_facts = match (graph.api_only, language, &parsed_tree) {
+        (true, Some(lang), Some(tree)) => {
+            crate::ingest::api_surface::retain_public_api(lang, tree, source, symbol_facts)
+        }
+        _ => symbol_facts,
+    };
+
+    // Step 3.6: Classify test code, relative to the project root when known
+    let mut symbol_facts = symbol_facts;
+    let test_path = match &graph.files.path_settings {
+        Some(settings) => path_buf.strip_prefix(&settings.root).unwrap_or(&path_buf),
+        None => path_buf.as_path(),
+    };
+    crate::ingest::test_code::mark_test_symbols(
+        language,
+        parsed_tree.as_ref(),
+        test_path,
+        source,
+        &mut symbol_facts,
+    );
+
+    // Step 3.7: Record declared visibility for --visibility filters
+    crate::ingest::visibility::mark_visibility(
+        language,
+        parsed_tree.as_ref(),
+        source,
+        &mut symbol_facts,
+    );
+
+    // Step 3.8: Flag deprecated symbols for find --deprecated
+    crate::ingest::deprecation::mark_deprecated(
+        language,
+        parsed_tree.as_ref(),
+        source,
+        &mut symbol_facts,
+    );
+
     // Step 4: Insert new symbol nodes and DEFINES edges
     // Track function symbol IDs for CFG extraction
     let mut function_symbol_ids: Vec<(String, i64, i64, i64)> = Vec::new();
     let mut indexed_symbols: Vec<(crate::ingest::SymbolFact, i64)> = Vec::new();
 
-    // Detect language once for label assignment
-    let language_label = language.map(|l| l.as_str().to_string());
-
     // Batch insert symbol nodes and DEFINES edges for performance.
     // This uses sqlitegraph's bulk_insert_entities/bulk_insert_edges wrapped
     // in a TransactionGuard (BEGIN IMMEDIATE...COMMIT), reducing WAL frames
@@ -176,11 +216,16 @@ pub fn index_file(graph: &mut CodeGraph, path: &str, source: &[u8]) -> Result<us
     for (i, fact) in symbol_facts.iter().enumerate() {
         let symbol_id = symbol_ids[i];
 
-        // Add labels for the symbol (SQLite backend only)
-        if let Some(ref lang) = language_label {
-            let _ = graph.add_label(symbol_id.as_i64(), lang);
+        // Add labels for the symbol (SQLite backend only); see graph::labels
+        let public = match (language, &parsed_tree) {
+            (Some(lang), Some(tree)) => {
+                crate::ingest::api_surface::fact_visibility(lang, tree, source, fact)
+            }
+            _ => None,
+        };
+        for label in crate::graph::labels::symbol_labels(language, fact, public) {
+            let _ = graph.add_label(symbol_id.as_i64(), &label);
         }
-        let _ = graph.add_label(symbol_id.as_i64(), &fact.kind_normalized);
 
         // Track function symbols for CFG extraction (all supported languages)
         if fact.kind_normalized == "fn" || fact.kind_normalized == "method" {
@@ -278,29 +323,21 @@ pub fn index_file(graph: &mut CodeGraph, path: &str, source: &[u8]) -> Result<us
         }
     }
 
-    // Step 5.6: Extract impl relations and create IMPLEMENTS edges (Rust only)
+    // Step 5.6: Extract impl relations and create IMPLEMENTS edges
+    // (Rust impls, Java/TypeScript extends and implements clauses).
     // After symbol insertion, the lookup index has all type/trait entity IDs.
     //
-    // Impl relations contain source-level simple names (e.g., "SideTables"),
-    // but the lookup index is keyed by FQN (e.g., "SideTables::SideTables").
-    // We build a simple-name→id map from current-file FQN entries, where
-    // the simple name is the last segment of the FQN. This gives us exact
-    // same-file resolution without the false-positive risk of cross-repo
-    // get_ids_by_name.
-    if let (Some(Language::Rust), Some(ref tree)) = (language, &parsed_tree) {
+    // Every trait impl is recorded by name in trait_impls, so traits that are
+    // not indexed (std, external crates) can still be queried. An edge is
+    // added only when both names resolve to a type-like symbol: one in this
+    // file, or else the single one in the graph (see resolve_type_name).
+    // Impls recorded by other files are resolved again against the types
+    // this file defines, so the edges do not depend on indexing order.
+    if let (Some(lang), Some(ref tree)) = (language, &parsed_tree) {
         let impl_relations =
-            crate::ingest::Parser::extract_impl_relations_static(tree, source, &path_buf);
-
-        // Build simple-name → entity_id from current-file FQN entries
-        let fqn_map = graph.symbols.lookup.fqn_to_id_with_current_file(path);
-        let mut simple_name_map: std::collections::HashMap<String, i64> =
-            std::collections::HashMap::new();
-        for (fqn, (id, _is_current)) in &fqn_map {
-            if let Some(simple) = fqn.rsplit("::").next() {
-                // First definition wins — same-file dedup
-                simple_name_map.entry(simple.to_string()).or_insert(*id);
-            }
-        }
+            crate::ingest::extract_impl_relations_for_language(lang, tree, source, &path_buf);
+
+        implementors::replace_for_file(&graph.side_conn.lock(), path, &impl_relations)?;
 
         for rel in &impl_relations {
             // Only create edges for trait impls, not inherent impls
@@ -308,10 +345,9 @@ pub fn index_file(graph: &mut CodeGraph, path: &str, source: &[u8]) -> Result<us
                 continue;
             };
 
-            // Resolve by simple name within current file only.
-            // Safe: no cross-file or cross-repo candidates.
-            let type_id = simple_name_map.get(&rel.type_name).copied();
-            let trait_id = simple_name_map.get(trait_name).copied();
+            let lookup = &graph.symbols.lookup;
+            let type_id = lookup.resolve_type_name(implementors::simple_name(&rel.type_name), path);
+            let trait_id = lookup.resolve_type_name(implementors::simple_name(trait_name), path);
 
             if let (Some(type_id), Some(trait_id)) = (type_id, trait_id) {
                 let type_node_id = NodeId::from(type_id);
@@ -320,13 +356,59 @@ pub fn index_file(graph: &mut CodeGraph, path: &str, source: &[u8]) -> Result<us
                     .symbols
                     .insert_implements_edge(type_node_id, trait_node_id)
                 {
-                    eprintln!(
-                        "Warning: Failed to insert IMPLEMENTS edge {} -> {}: {}",
-                        rel.type_name, trait_name, e
+                    log_warn!(
+                        "Failed to insert IMPLEMENTS edge {} -> {}: {}",
+                        rel.type_name,
+                        trait_name,
+                        e
                     );
                 }
             }
         }
+
+        let defined_types: Vec<&str> = symbol_facts
+            .iter()
+            .filter(|fact| {
+                matches!(
+                    fact.kind,
+                    SymbolKind::Class
+                        | SymbolKind::Interface
+                        | SymbolKind::Enum
+                        | SymbolKind::Union
+                        | SymbolKind::TypeAlias
+                )
+            })
+            .filter_map(|fact| fact.name.as_deref())
+            .collect();
+        let defined_ids: HashSet<i64> = symbol_ids.iter().map(|id| id.as_i64()).collect();
+        let pending =
+            implementors::pending_for_names(&graph.side_conn.lock(), path, &defined_types)?;
+        for (impl_path, type_name, trait_name) in pending {
+            let lookup = &graph.symbols.lookup;
+            let type_id =
+                lookup.resolve_type_name(implementors::simple_name(&type_name), &impl_path);
+            let trait_id =
+                lookup.resolve_type_name(implementors::simple_name(&trait_name), &impl_path);
+            let (Some(type_id), Some(trait_id)) = (type_id, trait_id) else {
+                continue;
+            };
+            // Edges between two other files' symbols were added when they
+            // were indexed; only edges touching this file's symbols are new.
+            if !defined_ids.contains(&type_id) && !defined_ids.contains(&trait_id) {
+                continue;
+            }
+            if let Err(e) = graph
+                .symbols
+                .insert_implements_edge(NodeId::from(type_id), NodeId::from(trait_id))
+            {
+                log_warn!(
+                    "Failed to insert IMPLEMENTS edge {} -> {}: {}",
+                    type_name,
+                    trait_name,
+                    e
+                );
+            }
+        }
     }
 
     // Step 5.55: Extract and store import statements (re-use pre-parsed tree)
@@ -408,8 +490,11 @@ pub fn index_file(graph: &mut CodeGraph, path: &str, source: &[u8]) -> Result<us
     // Step 5.7: LLVM IR CFG + call graph for C/C++ (when clang available)
     // Compiles the file to LLVM IR once, extracts per-function CFGs and call edges.
     // More accurate than tree-sitter: sees macro expansion and inlined code.
+    // Call extraction time, reported apart from the rest of `index_file`
+    let mut calls_time = std::time::Duration::ZERO;
     let llvm_handled_calls = if llvm_available {
-        let source_path = std::path::Path::new(path);
+        let disk_path = graph.files.disk_path(path);
+        let source_path = disk_path.as_path();
         let extra_flags: Vec<String> = graph
             .compile_commands
             .as_ref()
@@ -437,8 +522,10 @@ pub fn index_file(graph: &mut CodeGraph, path: &str, source: &[u8]) -> Result<us
                             .insert_cfg_edges(entity_id, &cfg_with_edges.edges);
                     }
                 }
-                if !llvm_calls.is_empty() {
+                if !llvm_calls.is_empty() && !graph.skip_calls {
+                    let calls_start = std::time::Instant::now();
                     let _ = super::calls::index_calls_from_llvm(graph, path, llvm_calls);
+                    calls_time += calls_start.elapsed();
                 }
                 true
             }
@@ -454,7 +541,8 @@ pub fn index_file(graph: &mut CodeGraph, path: &str, source: &[u8]) -> Result<us
     // Step 5.8: Java bytecode CFG (when javac available)
     let is_java = path.ends_with(".java");
     if is_java && !function_symbol_ids.is_empty() {
-        let source_path = std::path::Path::new(path);
+        let disk_path = graph.files.disk_path(path);
+        let source_path = disk_path.as_path();
         if super::external_tools::java::is_javac_available() {
             match super::external_tools::java::extract_cfgs_from_java(source_path) {
                 Ok(method_cfgs) => {
@@ -483,8 +571,10 @@ pub fn index_file(graph: &mut CodeGraph, path: &str, source: &[u8]) -> Result<us
 
     // Step 6: Tree-sitter call graph (all languages; C/C++ only when LLVM didn't handle it)
     if let (Some(ref tree), Some(lang)) = (parsed_tree, language) {
-        if !llvm_handled_calls {
+        if !llvm_handled_calls && !graph.skip_calls {
+            let calls_start = std::time::Instant::now();
             let _ = super::calls::index_calls_with_tree(graph, path, source, tree, lang);
+            calls_time += calls_start.elapsed();
         }
     }
 
@@ -512,19 +602,35 @@ pub fn index_file(graph: &mut CodeGraph, path: &str, source: &[u8]) -> Result<us
                 start_col: fact.start_col,
                 end_line: fact.end_line,
                 end_col: fact.end_col,
+                signature: fact.signature.clone(),
+                is_test: fact.is_test,
+                visibility: fact.visibility.clone(),
+                deprecated: fact.deprecated,
+                deprecation_message: fact.deprecation_message.clone(),
             })
         })
         .collect();
 
     // Compute metrics using SQL queries
     if let Err(e) = graph.metrics.compute_for_file(path, source, &symbol_nodes) {
-        eprintln!("Warning: Failed to compute metrics for '{}': {}", path, e);
+        log_warn!("Failed to compute metrics for '{}': {}", path, e);
+    }
+
+    // Step 8: Record per-file indexing wall time
+    let index_ms = index_start.elapsed().as_millis() as i64;
+    if let Err(e) = graph.metrics.record_file_timing(path, index_ms) {
+        log_warn!("Failed to record timing for '{}': {}", path, e);
     }
 
     // Invalidate cache for this file since it was just modified
     graph.invalidate_cache(path);
     graph.clear_query_caches();
 
+    // Phase timings for `watch --profile`; observational only
+    let index_time = index_start.elapsed();
+    graph.record_phase(IndexPhase::IndexFile, index_time.saturating_sub(calls_time));
+    graph.record_phase(IndexPhase::IndexCalls, calls_time);
+
     Ok(symbol_facts.len())
 }
 
@@ -545,8 +651,10 @@ pub fn index_file(graph: &mut CodeGraph, path: &str, source: &[u8]) -> Result<us
 /// # Returns
 /// DeleteResult with counts of deleted entities
 pub fn delete_file(graph: &mut CodeGraph, path: &str) -> Result<DeleteResult> {
-    // Delegate to the authoritative deletion path.
-    delete_file_facts(graph, path)
+    graph.evict_cached_tree(path);
+    // Delegate to the authoritative deletion path. Deletion is idempotent,
+    // so an attempt that hit a locked database can simply run again.
+    retry_on_busy(graph.busy_retries, || delete_file_facts(graph, path))
 }
 
 /// Delete ALL facts derived from a file path.
@@ -585,6 +693,10 @@ pub fn delete_file(graph: &mut CodeGraph, path: &str) -> Result<DeleteResult> {
 /// # Returns
 /// DeleteResult with detailed counts of deleted entities.
 pub fn delete_file_facts(graph: &mut CodeGraph, path: &str) -> Result<DeleteResult> {
+    let stored_path = graph.files.stored_path(path);
+    let path = stored_path.as_str();
+    graph.invalidate_symbol_index();
+
     // === PHASE 0: Clean up duplicate file nodes ===
     // If multiple File nodes exist for the same path (from earlier indexing bugs),
     // delete all but the first one before proceeding with normal deletion.
@@ -596,7 +708,7 @@ pub fn delete_file_facts(graph: &mut CodeGraph, path: &str) -> Result<DeleteResu
             let _ = graph.files.backend.delete_entity(dup_id.as_i64());
         }
         // Rebuild file_index so find_file_node returns the remaining single node
-        let normalized_path = crate::graph::files::normalize_path_for_index(path);
+        let normalized_path = graph.files.index_key(path);
         graph.files.file_index.remove(&normalized_path);
         if let Some((remaining_id, _)) = all_file_nodes.first() {
             graph
@@ -677,7 +789,7 @@ pub fn delete_file_facts(graph: &mut CodeGraph, path: &str) -> Result<DeleteResu
             Err(sqlitegraph::SqliteGraphError::NotFound(_)) => {
                 // Stale entry in file_index - entity was deleted but index not updated
                 // Remove stale entry and return empty result
-                let normalized_path = crate::graph::files::normalize_path_for_index(path);
+                let normalized_path = graph.files.index_key(path);
                 graph.files.file_index.remove(&normalized_path);
                 return Ok(DeleteResult {
                     symbols_deleted: 0,
@@ -764,7 +876,7 @@ pub fn delete_file_facts(graph: &mut CodeGraph, path: &str) -> Result<DeleteResu
         chunks_deleted = graph.chunks.delete_chunks_for_file(path)?;
 
         // Delete AST nodes using SideTables (works with both SQLite and V3)
-        let normalized_path = crate::graph::files::normalize_path_for_index(path);
+        let normalized_path = graph.files.index_key(path);
         let file_id_for_ast = graph
             .files
             .file_index
@@ -788,10 +900,11 @@ pub fn delete_file_facts(graph: &mut CodeGraph, path: &str) -> Result<DeleteResu
 
         // Delete metrics for this file
         let _ = graph.metrics.delete_file_metrics(path);
+        let _ = implementors::delete_for_file(&graph.side_conn.lock(), path);
 
         // Remove from in-memory index AFTER successful deletions.
         // Normalize path to match how it was stored in the index
-        let normalized_path = crate::graph::files::normalize_path_for_index(path);
+        let normalized_path = graph.files.index_key(path);
         graph.files.file_index.remove(&normalized_path);
 
         // Invalidate cache for this file
@@ -836,7 +949,7 @@ pub fn delete_file_facts(graph: &mut CodeGraph, path: &str) -> Result<DeleteResu
         );
 
         // Delete AST nodes using SideTables (even if no file node, clean up orphaned data)
-        let normalized_path = crate::graph::files::normalize_path_for_index(path);
+        let normalized_path = graph.files.index_key(path);
         let file_id_for_ast = graph
             .files
             .file_index
@@ -853,6 +966,7 @@ pub fn delete_file_facts(graph: &mut CodeGraph, path: &str) -> Result<DeleteResu
 
         // Delete metrics for this file (orphan cleanup)
         let _ = graph.metrics.delete_file_metrics(path);
+        let _ = implementors::delete_for_file(&graph.side_conn.lock(), path);
 
         // Delete CFG blocks and edges for this file (orphan cleanup)
         // Note: No file node means no symbols to query for function IDs
@@ -972,6 +1086,7 @@ pub mod test_helpers {
         path: &str,
         verify_at: Option<FailPoint>,
     ) -> Result<DeleteResult> {
+        graph.invalidate_symbol_index();
         let mut deleted_entity_ids: Vec<i64> = Vec::new();
         let symbols_deleted: usize;
         let chunks_deleted: usize;
@@ -1023,7 +1138,7 @@ pub mod test_helpers {
             graph.files.backend.delete_entity(file_id.as_i64())?;
             deleted_entity_ids.push(file_id.as_i64());
             // Remove from file_index immediately to keep in-memory state consistent
-            let normalized_path = crate::graph::files::normalize_path_for_index(path);
+            let normalized_path = graph.files.index_key(path);
             graph.files.file_index.remove(&normalized_path);
 
             // Delete references in this file.
@@ -1083,7 +1198,7 @@ pub mod test_helpers {
             chunks_deleted = graph.side_tables.delete_chunks_for_file(path)?;
 
             // Remove from in-memory index after all deletions complete
-            let normalized_path = crate::graph::files::normalize_path_for_index(path);
+            let normalized_path = graph.files.index_key(path);
             graph.files.file_index.remove(&normalized_path);
 
             // Invalidate cache for this file
@@ -1212,10 +1327,7 @@ pub fn insert_ast_nodes(
                         .update_ast_node_parent(node_id, actual_parent_id)
                     {
                         // Log but don't fail - parent links are optional for some operations
-                        eprintln!(
-                            "Warning: failed to update parent link for node {}: {:?}",
-                            node_id, e
-                        );
+                        log_warn!("failed to update parent link for node {}: {:?}", node_id, e);
                     }
                 }
             }
@@ -1231,7 +1343,7 @@ pub fn insert_ast_nodes(
 /// v6: Uses file_id to efficiently count AST nodes per file.
 fn count_ast_nodes_for_file(graph: &CodeGraph, path: &str) -> usize {
     // First, get the file_id by looking up in the file_index
-    let normalized_path = crate::graph::files::normalize_path_for_index(path);
+    let normalized_path = graph.files.index_key(path);
     let file_id = match graph.files.file_index.get(&normalized_path) {
         Some(id) => id.as_i64(),
         None => return 0, // No file node, no AST nodes to count
@@ -1273,21 +1385,44 @@ fn count_cfg_blocks_for_file(graph: &CodeGraph, path: &str) -> usize {
 /// 2. If exists → compute hash, compare to stored
 /// 3. If unchanged → return Unchanged without mutating DB
 /// 4. If changed/new → delete facts, re-index, return Reindexed
+///
+/// A reconcile that fails with `SQLITE_BUSY`/`SQLITE_LOCKED` (another
+/// process writing the same database) is retried `busy_retries` times. The
+/// File node's hash is written before the symbol and chunk transactions, so
+/// a retry skips the hash comparison and always reindexes: a failed attempt
+/// may have stored the new hash without all of its facts.
 pub fn reconcile_file_path(
     graph: &mut CodeGraph,
     path: &Path,
     path_key: &str,
+) -> Result<ReconcileOutcome> {
+    let mut retrying = false;
+    retry_on_busy(graph.busy_retries, || {
+        let force = std::mem::replace(&mut retrying, true);
+        reconcile_file_path_once(graph, path, path_key, force)
+    })
+}
+
+fn reconcile_file_path_once(
+    graph: &mut CodeGraph,
+    path: &Path,
+    path_key: &str,
+    force: bool,
 ) -> Result<ReconcileOutcome> {
     use std::fs;
 
+    let stored_key = graph.files.stored_path(path_key);
+    let path_key = stored_key.as_str();
+
     // 1) Check if file exists on filesystem
     if !path.exists() {
+        graph.evict_cached_tree(path_key);
         // Delete facts for missing file
         #[cfg(debug_assertions)]
         {
             let deleted = delete_file_facts(graph, path_key)?;
             if !deleted.is_empty() {
-                eprintln!(
+                log_info!(
                     "Deleted {} symbols, {} references, {} calls for missing file {}",
                     deleted.symbols_deleted,
                     deleted.references_deleted,
@@ -1303,28 +1438,31 @@ pub fn reconcile_file_path(
         return Ok(ReconcileOutcome::Deleted);
     }
 
-    // 2) Read file and compute hash
+    // 2) Read file
     let source = fs::read(path)?;
-    let new_hash = graph.files.compute_hash(&source);
 
-    // 3) Check if hash matches stored file node
+    // 3) Check if hash matches stored file node, hashing as it was hashed
+    // (a retry cannot trust the stored hash, see `reconcile_file_path`)
     let snapshot = SnapshotId::current();
-    let unchanged = if let Some(file_id) = graph.files.find_file_node(path_key)? {
+    let unchanged = if force {
+        false
+    } else if let Some(file_id) = graph.files.find_file_node(path_key)? {
         match graph.files.backend.get_node(snapshot, file_id.as_i64()) {
             Ok(node) => {
                 let file_node: crate::graph::schema::FileNode = serde_json::from_value(node.data)
                     .unwrap_or_else(|_| crate::graph::schema::FileNode {
                         path: path_key.to_string(),
                         hash: String::new(),
+                        hash_algo: Default::default(),
                         last_indexed_at: 0,
                         last_modified: 0,
                     });
-                file_node.hash == new_hash
+                file_node.hash == graph.files.compute_hash_with(&source, file_node.hash_algo)
             }
             Err(sqlitegraph::SqliteGraphError::NotFound(_)) => {
                 // Stale entry in file_index - entity was deleted but index not updated
                 // Remove stale entry and treat as new file
-                let normalized_path = crate::graph::files::normalize_path_for_index(path_key);
+                let normalized_path = graph.files.index_key(path_key);
                 graph.files.file_index.remove(&normalized_path);
                 false // File needs to be re-indexed
             }
@@ -1344,7 +1482,7 @@ pub fn reconcile_file_path(
     {
         let deleted = delete_file_facts(graph, path_key)?;
         if !deleted.is_empty() {
-            eprintln!(
+            log_info!(
                 "Deleted {} symbols, {} references, {} calls for reindex of {}",
                 deleted.symbols_deleted,
                 deleted.references_deleted,
@@ -1378,21 +1516,39 @@ pub fn reconcile_file_path(
 
 /// Reconcile a file path using pre-read source bytes.
 ///
-/// Same behavior as `reconcile_file_path` but accepts pre-read source
-/// to avoid re-reading from disk.
+/// Same behavior as `reconcile_file_path` (including busy retries) but
+/// accepts pre-read source to avoid re-reading from disk.
 pub fn reconcile_file_path_with_source(
     graph: &mut CodeGraph,
     path: &Path,
     path_key: &str,
     source: &[u8],
 ) -> Result<ReconcileOutcome> {
+    let mut retrying = false;
+    retry_on_busy(graph.busy_retries, || {
+        let force = std::mem::replace(&mut retrying, true);
+        reconcile_file_path_with_source_once(graph, path, path_key, source, force)
+    })
+}
+
+fn reconcile_file_path_with_source_once(
+    graph: &mut CodeGraph,
+    path: &Path,
+    path_key: &str,
+    source: &[u8],
+    force: bool,
+) -> Result<ReconcileOutcome> {
+    let stored_key = graph.files.stored_path(path_key);
+    let path_key = stored_key.as_str();
+
     // 1) Check if file exists on filesystem
     if !path.exists() {
+        graph.evict_cached_tree(path_key);
         #[cfg(debug_assertions)]
         {
             let deleted = delete_file_facts(graph, path_key)?;
             if !deleted.is_empty() {
-                eprintln!(
+                log_info!(
                     "Deleted {} symbols, {} references, {} calls for missing file {}",
                     deleted.symbols_deleted,
                     deleted.references_deleted,
@@ -1409,24 +1565,26 @@ pub fn reconcile_file_path_with_source(
     }
 
     // 2) Use provided source bytes (skip fs::read)
-    let new_hash = graph.files.compute_hash(source);
-
-    // 3) Check if hash matches stored file node
+    // 3) Check if hash matches stored file node, hashing as it was hashed
+    // (a retry cannot trust the stored hash, see `reconcile_file_path`)
     let snapshot = SnapshotId::current();
-    let unchanged = if let Some(file_id) = graph.files.find_file_node(path_key)? {
+    let unchanged = if force {
+        false
+    } else if let Some(file_id) = graph.files.find_file_node(path_key)? {
         match graph.files.backend.get_node(snapshot, file_id.as_i64()) {
             Ok(node) => {
                 let file_node: crate::graph::schema::FileNode = serde_json::from_value(node.data)
                     .unwrap_or_else(|_| crate::graph::schema::FileNode {
                         path: path_key.to_string(),
                         hash: String::new(),
+                        hash_algo: Default::default(),
                         last_indexed_at: 0,
                         last_modified: 0,
                     });
-                file_node.hash == new_hash
+                file_node.hash == graph.files.compute_hash_with(source, file_node.hash_algo)
             }
             Err(sqlitegraph::SqliteGraphError::NotFound(_)) => {
-                let normalized_path = crate::graph::files::normalize_path_for_index(path_key);
+                let normalized_path = graph.files.index_key(path_key);
                 graph.files.file_index.remove(&normalized_path);
                 false
             }
@@ -1446,7 +1604,7 @@ pub fn reconcile_file_path_with_source(
     {
         let deleted = delete_file_facts(graph, path_key)?;
         if !deleted.is_empty() {
-            eprintln!(
+            log_info!(
                 "Deleted {} symbols, {} references, {} calls for reindex of {}",
                 deleted.symbols_deleted,
                 deleted.references_deleted,
@@ -1755,6 +1913,69 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_reconcile_retry_reindexes_despite_stored_hash() {
+        // A busy failure can leave the new hash stored with the file's facts
+        // missing; the retry must not take that hash as "unchanged".
+        use tempfile::tempdir;
+
+        let dir = tempdir().unwrap();
+        let file_a = dir.path().join("a.rs");
+        let source = b"fn kept() {}";
+        std::fs::write(&file_a, source).unwrap();
+
+        let db_path = dir.path().join("test.db");
+        let mut graph = crate::CodeGraph::open(&db_path).unwrap();
+        let path_key = file_a.to_string_lossy().to_string();
+        graph.index_file(&path_key, source).unwrap();
+
+        // Simulate the partial attempt: hash stored, symbols gone
+        let file_id = graph.files.find_file_node(&path_key).unwrap().unwrap();
+        graph.symbols.delete_file_symbols(file_id).unwrap();
+        assert!(graph.symbols_in_file(&path_key).unwrap().is_empty());
+
+        let outcome = super::reconcile_file_path_once(&mut graph, &file_a, &path_key, false);
+        assert!(matches!(outcome, Ok(super::ReconcileOutcome::Unchanged)));
+
+        let outcome = super::reconcile_file_path_once(&mut graph, &file_a, &path_key, true);
+        assert!(matches!(
+            outcome,
+            Ok(super::ReconcileOutcome::Reindexed { .. })
+        ));
+        assert_eq!(graph.symbols_in_file(&path_key).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_incremental_reindex_matches_full_index_and_evicts_on_delete() {
+        use std::fs;
+        use tempfile::tempdir;
+
+        let dir = tempdir().unwrap();
+        let file = dir.path().join("lib.rs");
+        let v1: &[u8] = b"fn alpha() {}\nfn beta() { alpha(); }\n";
+        let v2: &[u8] = b"fn alpha() {}\nstruct Gamma;\nfn beta() { alpha(); }\n";
+        fs::write(&file, v1).unwrap();
+
+        let mut watched = crate::CodeGraph::open(dir.path().join("watched.db")).unwrap();
+        watched.enable_incremental_parse(8);
+        watched.reconcile_file_path(&file, "lib.rs").unwrap();
+        assert!(watched.has_cached_tree("lib.rs"));
+
+        fs::write(&file, v2).unwrap();
+        watched.reconcile_file_path(&file, "lib.rs").unwrap();
+
+        let mut fresh = crate::CodeGraph::open(dir.path().join("fresh.db")).unwrap();
+        fresh.index_file("lib.rs", v2).unwrap();
+        assert_eq!(
+            watched.symbols_in_file("lib.rs").unwrap(),
+            fresh.symbols_in_file("lib.rs").unwrap()
+        );
+
+        fs::remove_file(&file).unwrap();
+        watched.reconcile_file_path(&file, "lib.rs").unwrap();
+        assert!(!watched.has_cached_tree("lib.rs"));
+    }
+
     #[test]
     fn test_no_hnsw_index_when_embeddings_disabled() {
         use tempfile::tempdir;
@@ -0,0 +1,183 @@
+//! Nested symbol outline of one file (`get-file --outline`)
+//!
+//! A symbol becomes a child of the smallest symbol whose byte span encloses
+//! it, which nests methods in classes and items in modules for most
+//! languages. Rust methods live in `impl` blocks, which are not symbols, so
+//! a symbol whose FQN names a sibling type as its owner is moved under that
+//! type.
+
+use serde::{Deserialize, Serialize};
+
+use crate::ingest::{SymbolFact, SymbolKind};
+
+/// One symbol in the outline tree
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OutlineNode {
+    /// Symbol name (`<anonymous>` when the symbol has none)
+    pub name: String,
+    /// Normalized kind (`fn`, `struct`, `mod`, ...)
+    pub kind: String,
+    /// First line of the symbol (1-indexed)
+    pub start_line: usize,
+    /// Last line of the symbol (1-indexed, inclusive)
+    pub end_line: usize,
+    /// Byte offset where the symbol starts
+    pub byte_start: usize,
+    /// Byte offset where the symbol ends (exclusive)
+    pub byte_end: usize,
+    /// Nested symbols, in source order
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub children: Vec<OutlineNode>,
+}
+
+fn is_type(kind: &SymbolKind) -> bool {
+    matches!(
+        kind,
+        SymbolKind::Class | SymbolKind::Interface | SymbolKind::Enum | SymbolKind::Union
+    )
+}
+
+/// FQN of the scope that owns a symbol (`a::Point` for `a::Point::new`)
+fn owner_fqn(fact: &SymbolFact) -> Option<&str> {
+    let fqn = fact.fqn.as_deref()?;
+    let owner = fqn.strip_suffix(fact.name.as_deref()?)?;
+    owner
+        .strip_suffix("::")
+        .or_else(|| owner.strip_suffix('.'))
+        .filter(|owner| !owner.is_empty())
+}
+
+/// Build the outline of a file from its symbols (in any order)
+pub fn build_outline(mut facts: Vec<SymbolFact>) -> Vec<OutlineNode> {
+    // Enclosing symbols sort before the symbols they contain
+    facts.sort_by(|a, b| {
+        a.byte_start
+            .cmp(&b.byte_start)
+            .then_with(|| b.byte_end.cmp(&a.byte_end))
+    });
+
+    let mut parent: Vec<Option<usize>> = vec![None; facts.len()];
+    let mut stack: Vec<usize> = Vec::new();
+    for (i, fact) in facts.iter().enumerate() {
+        while let Some(&top) = stack.last() {
+            if facts[top].byte_end >= fact.byte_end {
+                break;
+            }
+            stack.pop();
+        }
+        parent[i] = stack.last().copied();
+        stack.push(i);
+    }
+
+    for i in 0..facts.len() {
+        let Some(owner) = owner_fqn(&facts[i]) else {
+            continue;
+        };
+        let owner_type = (0..facts.len()).find(|&j| {
+            j != i
+                && parent[j] == parent[i]
+                && is_type(&facts[j].kind)
+                && facts[j].fqn.as_deref() == Some(owner)
+        });
+        if owner_type.is_some() {
+            parent[i] = owner_type;
+        }
+    }
+
+    let mut children: Vec<Vec<usize>> = vec![Vec::new(); facts.len()];
+    let mut roots = Vec::new();
+    for (i, p) in parent.iter().enumerate() {
+        match p {
+            Some(p) => children[*p].push(i),
+            None => roots.push(i),
+        }
+    }
+
+    fn build(i: usize, facts: &[SymbolFact], children: &[Vec<usize>]) -> OutlineNode {
+        let fact = &facts[i];
+        OutlineNode {
+            name: fact
+                .name
+                .clone()
+                .unwrap_or_else(|| "<anonymous>".to_string()),
+            kind: fact.kind_normalized.clone(),
+            start_line: fact.start_line,
+            end_line: fact.end_line,
+            byte_start: fact.byte_start,
+            byte_end: fact.byte_end,
+            children: children[i]
+                .iter()
+                .map(|&child| build(child, facts, children))
+                .collect(),
+        }
+    }
+
+    roots
+        .into_iter()
+        .map(|i| build(i, &facts, &children))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn fact(name: &str, kind: SymbolKind, fqn: &str, span: (usize, usize)) -> SymbolFact {
+        SymbolFact {
+            file_path: PathBuf::from("lib.rs"),
+            kind_normalized: kind.normalized_key().to_string(),
+            kind,
+            name: Some(name.to_string()),
+            fqn: Some(fqn.to_string()),
+            canonical_fqn: None,
+            display_fqn: None,
+            byte_start: span.0,
+            byte_end: span.1,
+            start_line: 1,
+            start_col: 0,
+            end_line: 1,
+            end_col: 0,
+            signature: None,
+            is_test: false,
+            visibility: None,
+            deprecated: false,
+            deprecation_message: None,
+        }
+    }
+
+    fn shape(nodes: &[OutlineNode]) -> Vec<String> {
+        nodes
+            .iter()
+            .map(|node| {
+                if node.children.is_empty() {
+                    node.name.clone()
+                } else {
+                    format!("{}({})", node.name, shape(&node.children).join(","))
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_nests_by_span_containment() {
+        let outline = build_outline(vec![
+            fact("area", SymbolKind::Method, "Shape.area", (20, 40)),
+            fact("Shape", SymbolKind::Class, "Shape", (0, 50)),
+            fact("main", SymbolKind::Function, "main", (60, 70)),
+        ]);
+        assert_eq!(shape(&outline), vec!["Shape(area)", "main"]);
+    }
+
+    #[test]
+    fn test_impl_methods_move_under_their_type() {
+        // `impl Point` is not a symbol, so `new` is only span-contained by `shapes`
+        let outline = build_outline(vec![
+            fact("shapes", SymbolKind::Module, "shapes", (0, 150)),
+            fact("Point", SymbolKind::Class, "shapes::Point", (20, 60)),
+            fact("new", SymbolKind::Function, "shapes::Point::new", (90, 140)),
+            fact("helper", SymbolKind::Function, "shapes::helper", (141, 149)),
+        ]);
+        assert_eq!(shape(&outline), vec!["shapes(Point(new),helper)"]);
+    }
+}
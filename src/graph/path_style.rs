@@ -0,0 +1,465 @@
+//! Stored path style: absolute, or relative to a project root
+//!
+//! A database records at most one style in the `magellan_path_style` table.
+//! It is set with `--absolute-paths` / `--relative-paths` on `index`, `scan`
+//! and `watch`; a single-root `watch` without either flag records the
+//! absolute style for its root. Until a style is set, paths are stored in
+//! the form the caller passed them in. `rebase_paths` converts an existing
+//! database to another root or style.
+
+use anyhow::Result;
+use rusqlite::{params, Connection, OptionalExtension};
+use std::path::{Component, Path, PathBuf};
+use std::sync::Arc;
+
+use super::CodeGraph;
+
+/// How file paths are written to the database
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PathStyle {
+    /// Absolute paths (`/home/me/proj/src/lib.rs`)
+    Absolute,
+    /// Paths relative to the project root (`src/lib.rs`)
+    Relative,
+}
+
+impl PathStyle {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PathStyle::Absolute => "absolute",
+            PathStyle::Relative => "relative",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "absolute" => Some(PathStyle::Absolute),
+            "relative" => Some(PathStyle::Relative),
+            _ => None,
+        }
+    }
+}
+
+/// Path style recorded for a database
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PathSettings {
+    pub style: PathStyle,
+    /// Project root; relative paths are resolved against it
+    pub root: PathBuf,
+}
+
+impl PathSettings {
+    /// Settings recorded in the database behind `conn`, if any
+    pub fn read(conn: &Connection) -> Result<Option<Self>> {
+        load(conn)
+    }
+
+    /// Stored form of `path` under these settings
+    ///
+    /// Relative input is taken relative to the root. In relative style,
+    /// paths outside the root stay absolute.
+    pub fn apply(&self, path: &str) -> String {
+        let absolute = clean_path(&self.root.join(path));
+        match self.style {
+            PathStyle::Absolute => absolute.to_string_lossy().to_string(),
+            PathStyle::Relative => match absolute.strip_prefix(&self.root) {
+                Ok(rest) if !rest.as_os_str().is_empty() => rest.to_string_lossy().to_string(),
+                _ => absolute.to_string_lossy().to_string(),
+            },
+        }
+    }
+}
+
+/// Lexically normalize `path`: drop `.` components and resolve `..`
+///
+/// Does not touch the filesystem, so the path need not exist.
+pub(crate) fn clean_path(path: &Path) -> PathBuf {
+    let mut cleaned = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                cleaned.pop();
+            }
+            other => cleaned.push(other),
+        }
+    }
+    cleaned
+}
+
+pub(crate) fn ensure_schema(conn: &Connection) -> Result<()> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS magellan_path_style (
+            id INTEGER PRIMARY KEY CHECK (id = 1),
+            style TEXT NOT NULL,
+            root TEXT NOT NULL
+        )",
+        [],
+    )
+    .map_err(|e| anyhow::anyhow!("Failed to create magellan_path_style: {}", e))?;
+    Ok(())
+}
+
+/// Path settings recorded in the database, if any
+pub(crate) fn load(conn: &Connection) -> Result<Option<PathSettings>> {
+    let table_exists = conn
+        .query_row(
+            "SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = 'magellan_path_style'",
+            [],
+            |_| Ok(()),
+        )
+        .optional()
+        .map_err(|e| anyhow::anyhow!("Failed to inspect schema: {}", e))?
+        .is_some();
+    if !table_exists {
+        return Ok(None);
+    }
+
+    let row = conn
+        .query_row(
+            "SELECT style, root FROM magellan_path_style WHERE id = 1",
+            [],
+            |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)),
+        )
+        .optional()
+        .map_err(|e| anyhow::anyhow!("Failed to read path style: {}", e))?;
+
+    match row {
+        Some((style, root)) => {
+            let style = PathStyle::parse(&style)
+                .ok_or_else(|| anyhow::anyhow!("Unknown path style '{}' in database", style))?;
+            Ok(Some(PathSettings {
+                style,
+                root: PathBuf::from(root),
+            }))
+        }
+        None => Ok(None),
+    }
+}
+
+pub(crate) fn store(conn: &Connection, settings: &PathSettings) -> Result<()> {
+    ensure_schema(conn)?;
+    conn.execute(
+        "INSERT OR REPLACE INTO magellan_path_style (id, style, root) VALUES (1, ?1, ?2)",
+        params![settings.style.as_str(), settings.root.to_string_lossy()],
+    )
+    .map_err(|e| anyhow::anyhow!("Failed to store path style: {}", e))?;
+    Ok(())
+}
+
+/// Number of File nodes stored with absolute and with relative paths
+pub(crate) fn stored_path_counts(conn: &Connection) -> Result<(usize, usize)> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT file_path FROM graph_entities WHERE kind = 'File' AND file_path IS NOT NULL",
+        )
+        .map_err(|e| anyhow::anyhow!("Failed to inspect stored paths: {}", e))?;
+    let paths = stmt
+        .query_map([], |row| row.get::<_, String>(0))
+        .map_err(|e| anyhow::anyhow!("Failed to inspect stored paths: {}", e))?
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+    let absolute = paths
+        .iter()
+        .filter(|path| Path::new(path).is_absolute())
+        .count();
+    Ok((absolute, paths.len() - absolute))
+}
+
+/// One side of a rebase: a root directory, or root-relative paths
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RebaseRoot {
+    /// Paths stored relative to the root (`.` on the command line)
+    Relative,
+    /// Absolute paths under this directory
+    Root(PathBuf),
+}
+
+impl RebaseRoot {
+    pub fn parse(value: &str) -> Self {
+        match value {
+            "" | "." => RebaseRoot::Relative,
+            _ => RebaseRoot::Root(clean_path(Path::new(value))),
+        }
+    }
+}
+
+/// Rows rewritten by `rebase_paths`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RebaseReport {
+    /// Graph entities (files, symbols, references, calls, imports)
+    pub entities: usize,
+    /// Side-table rows keyed by `file_path` (chunks, metrics, timings)
+    pub side_rows: usize,
+}
+
+impl RebaseReport {
+    pub fn total(&self) -> usize {
+        self.entities + self.side_rows
+    }
+}
+
+/// JSON fields of entity payloads that hold a file path
+const ENTITY_PATH_FIELDS: [&str; 3] = ["path", "file_path", "file"];
+
+/// Rebased form of `path`, or `None` when it is not under `from`
+pub(crate) fn rebase_path(path: &str, from: &RebaseRoot, to: &RebaseRoot) -> Option<String> {
+    let candidate = Path::new(path);
+    let rest = match from {
+        RebaseRoot::Relative if candidate.is_relative() => clean_path(candidate),
+        RebaseRoot::Root(root) if candidate.is_absolute() => {
+            let rest = clean_path(candidate).strip_prefix(root).ok()?.to_path_buf();
+            if rest.as_os_str().is_empty() {
+                return None;
+            }
+            rest
+        }
+        _ => return None,
+    };
+
+    let rebased = match to {
+        RebaseRoot::Relative => rest,
+        RebaseRoot::Root(root) => root.join(rest),
+    };
+    let rebased = rebased.to_string_lossy().to_string();
+    (rebased != path).then_some(rebased)
+}
+
+/// Rewrite every stored path under `from` to lie under `to`
+///
+/// Covers the File, Symbol, Reference, Call and Import entities and every
+/// side table with a `file_path` column, in one transaction. The recorded
+/// path style is updated to match the new form: relative when `to` is
+/// [`RebaseRoot::Relative`], absolute when `from` is. Stable symbol IDs are
+/// not recomputed.
+pub fn rebase_paths(
+    graph: &mut CodeGraph,
+    from: &RebaseRoot,
+    to: &RebaseRoot,
+) -> Result<RebaseReport> {
+    if from == to {
+        return Err(anyhow::anyhow!("--from and --to are the same root"));
+    }
+
+    let previous = {
+        let conn = graph.side_conn.lock();
+        load(&conn)?
+    };
+    let settings = rebased_settings(previous.as_ref(), from, to)?;
+
+    let mut conn = graph.chunks.connect()?;
+    let tx = conn
+        .transaction()
+        .map_err(|e| anyhow::anyhow!("Failed to start rebase transaction: {}", e))?;
+    let mut report = RebaseReport {
+        entities: rebase_entities(&tx, from, to)?,
+        side_rows: 0,
+    };
+    for table in file_path_tables(&tx)? {
+        report.side_rows += rebase_table(&tx, &table, from, to)?;
+    }
+    if let Some(settings) = &settings {
+        store(&tx, settings)?;
+    }
+
+    tx.commit()
+        .map_err(|e| anyhow::anyhow!("Failed to commit rebase: {}", e))?;
+
+    graph.files.path_settings = settings.or(previous);
+    graph.files.rebuild_file_index()?;
+    graph.clear_cache();
+    graph.clear_query_caches();
+    let backend = Arc::clone(&graph.files.backend);
+    graph.symbols.lookup.rebuild_from_backend(&*backend)?;
+    Ok(report)
+}
+
+/// Path settings after a rebase, or `None` to leave them unchanged
+fn rebased_settings(
+    previous: Option<&PathSettings>,
+    from: &RebaseRoot,
+    to: &RebaseRoot,
+) -> Result<Option<PathSettings>> {
+    Ok(match (from, to) {
+        (RebaseRoot::Root(root), RebaseRoot::Relative) => Some(PathSettings {
+            style: PathStyle::Relative,
+            root: root.clone(),
+        }),
+        (RebaseRoot::Relative, RebaseRoot::Root(root)) => Some(PathSettings {
+            style: PathStyle::Absolute,
+            root: root.clone(),
+        }),
+        (RebaseRoot::Root(_), RebaseRoot::Root(root)) => previous.map(|settings| PathSettings {
+            style: settings.style,
+            root: root.clone(),
+        }),
+        (RebaseRoot::Relative, RebaseRoot::Relative) => None,
+    })
+}
+
+fn rebase_entities(conn: &Connection, from: &RebaseRoot, to: &RebaseRoot) -> Result<usize> {
+    let rows: Vec<(i64, String, String, Option<String>, String)> = {
+        let mut stmt = conn
+            .prepare("SELECT id, kind, name, file_path, data FROM graph_entities")
+            .map_err(|e| anyhow::anyhow!("Failed to scan graph_entities: {}", e))?;
+        let rows = stmt
+            .query_map([], |row| {
+                Ok((
+                    row.get(0)?,
+                    row.get(1)?,
+                    row.get(2)?,
+                    row.get(3)?,
+                    row.get(4)?,
+                ))
+            })
+            .map_err(|e| anyhow::anyhow!("Failed to scan graph_entities: {}", e))?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        rows
+    };
+
+    let mut updated = 0;
+    for (id, kind, name, file_path, data) in rows {
+        let mut changed = false;
+
+        let new_file_path = file_path
+            .as_deref()
+            .and_then(|path| rebase_path(path, from, to));
+        changed |= new_file_path.is_some();
+
+        let new_name = if kind == "File" {
+            rebase_path(&name, from, to)
+        } else {
+            None
+        };
+        changed |= new_name.is_some();
+
+        let mut payload: serde_json::Value = serde_json::from_str(&data).unwrap_or_default();
+        if let Some(object) = payload.as_object_mut() {
+            for field in ENTITY_PATH_FIELDS {
+                let rebased = object
+                    .get(field)
+                    .and_then(|value| value.as_str())
+                    .and_then(|path| rebase_path(path, from, to));
+                if let Some(rebased) = rebased {
+                    object.insert(field.to_string(), serde_json::Value::String(rebased));
+                    changed = true;
+                }
+            }
+        }
+
+        if changed {
+            conn.execute(
+                "UPDATE graph_entities SET name = ?1, file_path = ?2, data = ?3 WHERE id = ?4",
+                params![
+                    new_name.unwrap_or(name),
+                    new_file_path.or(file_path),
+                    serde_json::to_string(&payload)?,
+                    id
+                ],
+            )
+            .map_err(|e| anyhow::anyhow!("Failed to rebase entity {}: {}", id, e))?;
+            updated += 1;
+        }
+    }
+    Ok(updated)
+}
+
+/// Ordinary tables other than `graph_entities` with a `file_path` column
+fn file_path_tables(conn: &Connection) -> Result<Vec<String>> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT m.name FROM sqlite_master m, pragma_table_info(m.name) c
+             WHERE m.type = 'table' AND m.sql NOT LIKE 'CREATE VIRTUAL%'
+               AND m.name <> 'graph_entities' AND c.name = 'file_path'
+             ORDER BY m.name",
+        )
+        .map_err(|e| anyhow::anyhow!("Failed to inspect schema: {}", e))?;
+    let tables = stmt
+        .query_map([], |row| row.get::<_, String>(0))
+        .map_err(|e| anyhow::anyhow!("Failed to inspect schema: {}", e))?
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+    Ok(tables)
+}
+
+fn rebase_table(
+    conn: &Connection,
+    table: &str,
+    from: &RebaseRoot,
+    to: &RebaseRoot,
+) -> Result<usize> {
+    let paths: Vec<String> = {
+        let mut stmt = conn
+            .prepare(&format!(
+                "SELECT DISTINCT file_path FROM {} WHERE file_path IS NOT NULL",
+                table
+            ))
+            .map_err(|e| anyhow::anyhow!("Failed to scan {}: {}", table, e))?;
+        let paths = stmt
+            .query_map([], |row| row.get::<_, String>(0))
+            .map_err(|e| anyhow::anyhow!("Failed to scan {}: {}", table, e))?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        paths
+    };
+
+    let mut updated = 0;
+    for path in paths {
+        if let Some(rebased) = rebase_path(&path, from, to) {
+            // OR REPLACE: a row already stored under the new path is superseded
+            updated += conn
+                .execute(
+                    &format!(
+                        "UPDATE OR REPLACE {} SET file_path = ?1 WHERE file_path = ?2",
+                        table
+                    ),
+                    params![rebased, path],
+                )
+                .map_err(|e| anyhow::anyhow!("Failed to rebase {} rows: {}", table, e))?;
+        }
+    }
+    Ok(updated)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_apply_relative_and_absolute() {
+        let relative = PathSettings {
+            style: PathStyle::Relative,
+            root: PathBuf::from("/proj"),
+        };
+        assert_eq!(relative.apply("/proj/src/lib.rs"), "src/lib.rs");
+        assert_eq!(relative.apply("src/./a/../lib.rs"), "src/lib.rs");
+        assert_eq!(relative.apply("/elsewhere/x.rs"), "/elsewhere/x.rs");
+
+        let absolute = PathSettings {
+            style: PathStyle::Absolute,
+            root: PathBuf::from("/proj"),
+        };
+        assert_eq!(absolute.apply("src/lib.rs"), "/proj/src/lib.rs");
+        assert_eq!(absolute.apply("/proj/src/lib.rs"), "/proj/src/lib.rs");
+    }
+
+    #[test]
+    fn test_rebase_path() {
+        let old = RebaseRoot::parse("/old");
+        let new = RebaseRoot::parse("/new/");
+        let relative = RebaseRoot::parse(".");
+
+        assert_eq!(
+            rebase_path("/old/src/a.rs", &old, &new).as_deref(),
+            Some("/new/src/a.rs")
+        );
+        assert_eq!(
+            rebase_path("/old/src/a.rs", &old, &relative).as_deref(),
+            Some("src/a.rs")
+        );
+        assert_eq!(
+            rebase_path("src/a.rs", &relative, &new).as_deref(),
+            Some("/new/src/a.rs")
+        );
+        assert_eq!(rebase_path("/older/a.rs", &old, &new), None);
+        assert_eq!(rebase_path("/old", &old, &relative), None);
+        assert_eq!(rebase_path("src/a.rs", &old, &new), None);
+    }
+}
@@ -0,0 +1,312 @@
+//! SQLite connection tuning for the graph database
+//!
+//! `CodeGraph::open` uses WAL journaling with `synchronous = NORMAL` and a
+//! 5 second busy timeout. `SqliteOptions` lets callers (the `watch` and
+//! `index` commands) trade some of that for stricter durability or for
+//! filesystems where WAL is unavailable.
+//!
+//! A busy timeout only covers a single statement. Writes that still fail
+//! with `SQLITE_BUSY`/`SQLITE_LOCKED` (for example a deferred transaction
+//! that cannot upgrade to a write lock) are retried as a whole by
+//! [`retry_on_busy`], `busy_retries` times with exponential backoff.
+//!
+//! `CodeGraph::open_readonly` opens every connection with
+//! [`open_read_only_connection`] (or [`read_only_uri`] for the sqlitegraph
+//! pool), so read commands never take a write lock or create a journal.
+
+use anyhow::Result;
+use rusqlite::{Connection, OpenFlags};
+use std::path::Path;
+use std::time::Duration;
+
+/// Default time (ms) a connection waits on a locked database before failing.
+pub const DEFAULT_BUSY_TIMEOUT_MS: u32 = 5000;
+
+/// Default number of times a busy write is retried before the error surfaces.
+pub const DEFAULT_BUSY_RETRIES: u32 = 3;
+
+/// First backoff delay; doubles after each busy attempt.
+const BUSY_BACKOFF_BASE_MS: u64 = 50;
+
+/// Upper bound for a single backoff delay.
+const BUSY_BACKOFF_MAX_MS: u64 = 2000;
+
+/// `mmap_size` for read-only connections (256MB, as sqlitegraph's pool uses).
+const READ_ONLY_MMAP_SIZE: i64 = 256 * 1024 * 1024;
+
+/// SQLite journal mode (`PRAGMA journal_mode`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum JournalMode {
+    /// Write-ahead log: readers never block the writer
+    #[default]
+    Wal,
+    /// Rollback journal deleted after each transaction
+    Delete,
+    /// Rollback journal truncated after each transaction
+    Truncate,
+}
+
+impl JournalMode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            JournalMode::Wal => "wal",
+            JournalMode::Delete => "delete",
+            JournalMode::Truncate => "truncate",
+        }
+    }
+}
+
+impl std::str::FromStr for JournalMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "wal" => Ok(JournalMode::Wal),
+            "delete" => Ok(JournalMode::Delete),
+            "truncate" => Ok(JournalMode::Truncate),
+            _ => Err(format!(
+                "Unknown journal mode: {} (expected wal, delete or truncate)",
+                s
+            )),
+        }
+    }
+}
+
+/// SQLite fsync policy (`PRAGMA synchronous`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Synchronous {
+    /// Never fsync; fastest, a crash can corrupt the database
+    Off,
+    /// fsync at checkpoints; with WAL a power loss can drop the latest commits
+    #[default]
+    Normal,
+    /// fsync on every commit; slowest, fully durable
+    Full,
+}
+
+impl Synchronous {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Synchronous::Off => "off",
+            Synchronous::Normal => "normal",
+            Synchronous::Full => "full",
+        }
+    }
+}
+
+impl std::str::FromStr for Synchronous {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "off" => Ok(Synchronous::Off),
+            "normal" => Ok(Synchronous::Normal),
+            "full" => Ok(Synchronous::Full),
+            _ => Err(format!(
+                "Unknown synchronous mode: {} (expected off, normal or full)",
+                s
+            )),
+        }
+    }
+}
+
+/// PRAGMA settings applied to every connection `CodeGraph` opens
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SqliteOptions {
+    pub journal_mode: JournalMode,
+    pub synchronous: Synchronous,
+    pub busy_timeout_ms: u32,
+    /// Retries for a write that still fails with a busy/locked error
+    pub busy_retries: u32,
+}
+
+impl Default for SqliteOptions {
+    fn default() -> Self {
+        Self {
+            journal_mode: JournalMode::Wal,
+            synchronous: Synchronous::Normal,
+            busy_timeout_ms: DEFAULT_BUSY_TIMEOUT_MS,
+            busy_retries: DEFAULT_BUSY_RETRIES,
+        }
+    }
+}
+
+impl SqliteOptions {
+    /// Apply the per-connection settings (`busy_timeout`, `synchronous`).
+    ///
+    /// `journal_mode` is persistent in the database file and is set once by
+    /// [`SqliteOptions::apply_journal_mode`].
+    pub fn apply_to_connection(&self, conn: &Connection) -> Result<()> {
+        conn.pragma_update(None, "busy_timeout", self.busy_timeout_ms)
+            .map_err(|e| anyhow::anyhow!("Failed to set busy_timeout: {}", e))?;
+        conn.pragma_update(None, "synchronous", self.synchronous.as_str())
+            .map_err(|e| anyhow::anyhow!("Failed to set synchronous: {}", e))?;
+        Ok(())
+    }
+
+    /// Switch the database file to the configured journal mode.
+    ///
+    /// Returns the mode SQLite reports afterwards (`memory` for in-memory
+    /// databases, which ignore the request).
+    pub fn apply_journal_mode(&self, conn: &Connection) -> Result<String> {
+        let sql = format!("PRAGMA journal_mode = {}", self.journal_mode.as_str());
+        // query_row() returns the new mode value; execute() would error
+        conn.query_row(&sql, [], |row| row.get::<_, String>(0))
+            .map_err(|e| anyhow::anyhow!("Failed to set journal_mode: {}", e))
+    }
+}
+
+/// Open a read-only connection to an existing database file.
+///
+/// The database is memory-mapped up to 256MB. `immutable=1` is deliberately
+/// not used: `watch` may be writing the same file, and an immutable reader
+/// would skip locking and could read a half-written page.
+pub fn open_read_only_connection(db_path: &Path, busy_timeout_ms: u32) -> Result<Connection> {
+    let conn = Connection::open_with_flags(
+        db_path,
+        OpenFlags::SQLITE_OPEN_READ_ONLY | OpenFlags::SQLITE_OPEN_NO_MUTEX,
+    )
+    .map_err(|e| anyhow::anyhow!("Failed to open {} read-only: {}", db_path.display(), e))?;
+    conn.pragma_update(None, "busy_timeout", busy_timeout_ms)
+        .map_err(|e| anyhow::anyhow!("Failed to set busy_timeout: {}", e))?;
+    conn.pragma_update(None, "mmap_size", READ_ONLY_MMAP_SIZE)
+        .map_err(|e| anyhow::anyhow!("Failed to set mmap_size: {}", e))?;
+    Ok(conn)
+}
+
+/// `file:` URI that opens `db_path` read-only (`mode=ro`).
+///
+/// For openers that only take a path, such as the sqlitegraph pool.
+pub fn read_only_uri(db_path: &Path) -> String {
+    let mut uri = String::from("file:");
+    for c in db_path.to_string_lossy().chars() {
+        match c {
+            '%' => uri.push_str("%25"),
+            '?' => uri.push_str("%3f"),
+            '#' => uri.push_str("%23"),
+            _ => uri.push(c),
+        }
+    }
+    uri.push_str("?mode=ro");
+    uri
+}
+
+/// Whether an error was caused by another connection holding a lock
+/// (`SQLITE_BUSY` or `SQLITE_LOCKED`).
+///
+/// sqlitegraph reports SQLite failures as strings, so its errors are
+/// matched on SQLite's own messages.
+pub fn is_busy_error(err: &anyhow::Error) -> bool {
+    err.chain().any(|cause| {
+        if let Some(rusqlite::Error::SqliteFailure(failure, _)) =
+            cause.downcast_ref::<rusqlite::Error>()
+        {
+            return matches!(
+                failure.code,
+                rusqlite::ErrorCode::DatabaseBusy | rusqlite::ErrorCode::DatabaseLocked
+            );
+        }
+        let message = cause.to_string();
+        message.contains("database is locked")
+            || message.contains("database table is locked")
+            || message.contains("database is busy")
+    })
+}
+
+/// Run `op`, retrying up to `retries` more times while it fails with a busy
+/// error.
+///
+/// Waits 50 ms before the first retry and doubles the delay each time (capped
+/// at 2 s). Any other error, or the last busy error, is returned unchanged.
+/// `op` must be safe to repeat: either it ran in a transaction that rolled
+/// back, or it is idempotent.
+pub fn retry_on_busy<T>(retries: u32, mut op: impl FnMut() -> Result<T>) -> Result<T> {
+    let mut attempt = 0;
+    loop {
+        match op() {
+            Err(err) if attempt < retries && is_busy_error(&err) => {
+                let delay_ms = BUSY_BACKOFF_BASE_MS
+                    .saturating_mul(1 << attempt.min(16))
+                    .min(BUSY_BACKOFF_MAX_MS);
+                std::thread::sleep(Duration::from_millis(delay_ms));
+                attempt += 1;
+            }
+            result => return result,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_modes() {
+        assert_eq!("WAL".parse::<JournalMode>().unwrap(), JournalMode::Wal);
+        assert_eq!(
+            "truncate".parse::<JournalMode>().unwrap(),
+            JournalMode::Truncate
+        );
+        assert!("memory".parse::<JournalMode>().is_err());
+        assert_eq!("full".parse::<Synchronous>().unwrap(), Synchronous::Full);
+        assert!("extra".parse::<Synchronous>().is_err());
+    }
+
+    #[test]
+    fn test_apply_to_connection() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let conn = Connection::open(dir.path().join("t.db")).unwrap();
+        let options = SqliteOptions {
+            journal_mode: JournalMode::Wal,
+            synchronous: Synchronous::Full,
+            busy_timeout_ms: 1234,
+            busy_retries: 0,
+        };
+        assert_eq!(options.apply_journal_mode(&conn).unwrap(), "wal");
+        options.apply_to_connection(&conn).unwrap();
+
+        let timeout: i64 = conn
+            .query_row("PRAGMA busy_timeout", [], |row| row.get(0))
+            .unwrap();
+        let synchronous: i64 = conn
+            .query_row("PRAGMA synchronous", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(timeout, 1234);
+        assert_eq!(synchronous, 2);
+    }
+
+    #[test]
+    fn test_is_busy_error() {
+        let busy = rusqlite::Error::SqliteFailure(
+            rusqlite::ffi::Error::new(rusqlite::ffi::SQLITE_BUSY),
+            None,
+        );
+        assert!(is_busy_error(&anyhow::Error::new(busy).context("writing")));
+        assert!(is_busy_error(&anyhow::anyhow!(
+            "query error: database is locked"
+        )));
+        assert!(!is_busy_error(&anyhow::anyhow!(
+            "no such table: code_chunks"
+        )));
+    }
+
+    #[test]
+    fn test_retry_on_busy_gives_up_after_retries() {
+        let mut calls = 0;
+        let result: Result<()> = retry_on_busy(2, || {
+            calls += 1;
+            Err(anyhow::anyhow!("database is locked"))
+        });
+        assert!(result.is_err());
+        assert_eq!(calls, 3);
+
+        // Other errors are not retried
+        let mut calls = 0;
+        let result: Result<()> = retry_on_busy(2, || {
+            calls += 1;
+            Err(anyhow::anyhow!("constraint failed"))
+        });
+        assert!(result.is_err());
+        assert_eq!(calls, 1);
+    }
+}
@@ -0,0 +1,135 @@
+//! Per-phase indexing timings (`watch --profile`)
+//!
+//! Accumulates wall time and call counts for the three indexing phases of a
+//! file: symbol indexing (`index_file`, excluding call extraction), reference
+//! indexing (`index_references`), and call extraction (`index_calls`). The
+//! timings are observational: recording them never changes what gets indexed.
+
+use std::time::Duration;
+
+/// One of the timed indexing phases
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IndexPhase {
+    /// Parsing, symbols, chunks, AST nodes and metrics (`index_file` minus calls)
+    IndexFile,
+    /// Reference extraction and cross-file reference tables
+    IndexReferences,
+    /// Call extraction (tree-sitter or LLVM IR)
+    IndexCalls,
+}
+
+impl IndexPhase {
+    /// All phases, in reporting order
+    pub const ALL: [IndexPhase; 3] = [
+        IndexPhase::IndexFile,
+        IndexPhase::IndexReferences,
+        IndexPhase::IndexCalls,
+    ];
+
+    /// Name used in the human and JSON reports
+    pub fn as_str(self) -> &'static str {
+        match self {
+            IndexPhase::IndexFile => "index_file",
+            IndexPhase::IndexReferences => "index_references",
+            IndexPhase::IndexCalls => "index_calls",
+        }
+    }
+}
+
+/// Accumulated time and count for one phase
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PhaseTiming {
+    /// Times the phase ran (one per file)
+    pub count: usize,
+    /// Wall time spent in the phase
+    pub total: Duration,
+}
+
+/// Phase timings aggregated over a watch session
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PhaseProfile {
+    /// Batches indexed: the initial scan and each processed watch batch
+    pub batches: usize,
+    index_file: PhaseTiming,
+    index_references: PhaseTiming,
+    index_calls: PhaseTiming,
+}
+
+impl PhaseProfile {
+    /// Add one run of `phase` that took `elapsed`
+    pub fn record(&mut self, phase: IndexPhase, elapsed: Duration) {
+        let timing = match phase {
+            IndexPhase::IndexFile => &mut self.index_file,
+            IndexPhase::IndexReferences => &mut self.index_references,
+            IndexPhase::IndexCalls => &mut self.index_calls,
+        };
+        timing.count += 1;
+        timing.total += elapsed;
+    }
+
+    /// Count one indexed batch
+    pub fn record_batch(&mut self) {
+        self.batches += 1;
+    }
+
+    /// Accumulated timing for `phase`
+    pub fn phase(&self, phase: IndexPhase) -> PhaseTiming {
+        match phase {
+            IndexPhase::IndexFile => self.index_file,
+            IndexPhase::IndexReferences => self.index_references,
+            IndexPhase::IndexCalls => self.index_calls,
+        }
+    }
+
+    /// Wall time across all phases
+    pub fn total(&self) -> Duration {
+        IndexPhase::ALL
+            .iter()
+            .map(|&phase| self.phase(phase).total)
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_accumulates_per_phase() {
+        let mut profile = PhaseProfile::default();
+        profile.record(IndexPhase::IndexFile, Duration::from_millis(5));
+        profile.record(IndexPhase::IndexFile, Duration::from_millis(7));
+        profile.record(IndexPhase::IndexCalls, Duration::from_millis(1));
+        profile.record_batch();
+
+        assert_eq!(profile.batches, 1);
+        assert_eq!(
+            profile.phase(IndexPhase::IndexFile),
+            PhaseTiming {
+                count: 2,
+                total: Duration::from_millis(12)
+            }
+        );
+        assert_eq!(profile.phase(IndexPhase::IndexReferences).count, 0);
+        assert_eq!(profile.total(), Duration::from_millis(13));
+    }
+
+    #[test]
+    fn test_graph_records_phases_when_enabled() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let mut graph = crate::CodeGraph::open(dir.path().join("test.db")).unwrap();
+        let source = b"fn a() {}\nfn b() { a(); }\n";
+
+        graph.index_file("a.rs", source).unwrap();
+        assert!(graph.profile().is_none());
+
+        graph.enable_profile();
+        graph.index_file("a.rs", source).unwrap();
+        graph.index_references("a.rs", source).unwrap();
+
+        let profile = graph.profile().unwrap();
+        assert_eq!(profile.phase(IndexPhase::IndexFile).count, 1);
+        assert_eq!(profile.phase(IndexPhase::IndexReferences).count, 1);
+        assert_eq!(profile.phase(IndexPhase::IndexCalls).count, 1);
+    }
+}
@@ -0,0 +1,136 @@
+//! Garbage collection for side-table rows whose File node is gone
+//!
+//! `delete_file` removes graph entities first and side-table rows after the
+//! graph commit, so a failure in between leaves code chunks or metrics that
+//! no File node owns. `prune_orphans` finds and removes those rows.
+
+use anyhow::Result;
+use rusqlite::{params, Connection, OptionalExtension};
+use std::collections::HashSet;
+
+use super::files::normalize_path_for_index;
+use super::CodeGraph;
+
+/// Side tables keyed by owning file path, in report order
+const PRUNABLE_TABLES: [&str; 4] = [
+    "code_chunks",
+    "file_metrics",
+    "symbol_metrics",
+    "file_timings",
+];
+
+/// Orphaned rows found (and deleted unless dry run) per side table
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PruneReport {
+    pub code_chunks: usize,
+    pub file_metrics: usize,
+    pub symbol_metrics: usize,
+    pub file_timings: usize,
+    /// Whether rows were only counted, not deleted
+    pub dry_run: bool,
+}
+
+impl PruneReport {
+    pub fn total(&self) -> usize {
+        self.code_chunks + self.file_metrics + self.symbol_metrics + self.file_timings
+    }
+
+    fn count_mut(&mut self, table: &str) -> &mut usize {
+        match table {
+            "code_chunks" => &mut self.code_chunks,
+            "file_metrics" => &mut self.file_metrics,
+            "symbol_metrics" => &mut self.symbol_metrics,
+            _ => &mut self.file_timings,
+        }
+    }
+}
+
+/// Remove side-table rows whose `file_path` has no File node
+///
+/// With `dry_run` the orphaned rows are counted but left in place. All
+/// deletes run in one transaction.
+pub fn prune_orphans(graph: &CodeGraph, dry_run: bool) -> Result<PruneReport> {
+    let mut conn = graph.chunks.connect()?;
+    let live_files = live_file_paths(&conn)?;
+
+    let tx = conn
+        .transaction()
+        .map_err(|e| anyhow::anyhow!("Failed to start prune transaction: {}", e))?;
+    let mut report = PruneReport {
+        dry_run,
+        ..PruneReport::default()
+    };
+
+    for table in PRUNABLE_TABLES {
+        if !table_exists(&tx, table)? {
+            continue;
+        }
+
+        let orphaned: Vec<String> = {
+            let mut stmt = tx
+                .prepare(&format!("SELECT DISTINCT file_path FROM {}", table))
+                .map_err(|e| anyhow::anyhow!("Failed to scan {}: {}", table, e))?;
+            let paths = stmt
+                .query_map([], |row| row.get::<_, String>(0))
+                .map_err(|e| anyhow::anyhow!("Failed to scan {}: {}", table, e))?
+                .collect::<std::result::Result<Vec<_>, _>>()?;
+            paths
+                .into_iter()
+                .filter(|path| !live_files.contains(&normalize_path_for_index(path)))
+                .collect()
+        };
+
+        for path in &orphaned {
+            let rows = if dry_run {
+                tx.query_row(
+                    &format!("SELECT COUNT(*) FROM {} WHERE file_path = ?1", table),
+                    params![path],
+                    |row| row.get::<_, i64>(0),
+                )
+                .map_err(|e| anyhow::anyhow!("Failed to count {} rows: {}", table, e))?
+                    as usize
+            } else {
+                tx.execute(
+                    &format!("DELETE FROM {} WHERE file_path = ?1", table),
+                    params![path],
+                )
+                .map_err(|e| anyhow::anyhow!("Failed to delete {} rows: {}", table, e))?
+            };
+            *report.count_mut(table) += rows;
+        }
+    }
+
+    tx.commit()
+        .map_err(|e| anyhow::anyhow!("Failed to commit prune: {}", e))?;
+
+    Ok(report)
+}
+
+/// Normalized paths of every File node in the graph
+fn live_file_paths(conn: &Connection) -> Result<HashSet<String>> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT COALESCE(json_extract(data, '$.path'), file_path)
+             FROM graph_entities WHERE kind = 'File'",
+        )
+        .map_err(|e| anyhow::anyhow!("Failed to query file nodes: {}", e))?;
+    let paths = stmt
+        .query_map([], |row| row.get::<_, Option<String>>(0))
+        .map_err(|e| anyhow::anyhow!("Failed to query file nodes: {}", e))?
+        .filter_map(|row| row.ok().flatten())
+        .map(|path| normalize_path_for_index(&path))
+        .collect();
+    Ok(paths)
+}
+
+fn table_exists(conn: &Connection, table: &str) -> Result<bool> {
+    let found = conn
+        .query_row(
+            "SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = ?1",
+            params![table],
+            |_| Ok(()),
+        )
+        .optional()
+        .map_err(|e| anyhow::anyhow!("Failed to inspect schema: {}", e))?;
+    Ok(found.is_some())
+}
@@ -9,13 +9,22 @@ use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 
 use crate::graph::ambiguity::AmbiguityOps;
-use crate::graph::schema::{EdgeEndpoints, SymbolNode};
+use crate::graph::cache::{CachedSymbol, WarmSymbolIndex};
+use crate::graph::canonical_fqn::fqn_has_prefix;
+use crate::graph::profile::IndexPhase;
+use crate::graph::schema::{
+    CallNode, CountSnapshot, DanglingEdge, EdgeEndpoints, OrphanSymbol, SpanOverlap, SpanSymbol,
+    SymbolNode,
+};
 use crate::ingest::c::CParser;
 use crate::ingest::cpp::CppParser;
 use crate::ingest::java::JavaParser;
+use crate::ingest::test_code::TestFilter;
+use crate::ingest::visibility::VisibilityFilter;
 use crate::ingest::{SymbolFact, SymbolKind};
 use crate::references::ReferenceFact;
 
+use super::files::FileOps;
 use super::CodeGraph;
 
 /// Resolve a path to absolute form, handling relative paths consistently.
@@ -24,11 +33,17 @@ use super::CodeGraph;
 /// Resolve a query path to the same format used when indexing
 ///
 /// # Arguments
+/// * `files` - File operations holding the database's path style
 /// * `path` - The path to resolve (may be relative or absolute)
 ///
 /// # Returns
 /// Resolved path as a String, in the same format the database uses
-fn resolve_query_path(path: &str) -> String {
+fn resolve_query_path(files: &FileOps, path: &str) -> String {
+    // With a path style set, stored paths follow it; see `FileOps::stored_path`
+    if files.path_settings.is_some() {
+        return files.stored_path(path);
+    }
+
     let path_buf = PathBuf::from(path);
     if path_buf.is_absolute() {
         return path.to_string();
@@ -86,7 +101,7 @@ pub fn symbols_in_file_with_kind(
 
 /// Query symbols in a file along with their node IDs for deterministic CLI output.
 pub fn symbol_nodes_in_file(graph: &mut CodeGraph, path: &str) -> Result<Vec<(i64, SymbolFact)>> {
-    let resolved_path = resolve_query_path(path);
+    let resolved_path = resolve_query_path(&graph.files, path);
     let file_id = match graph.files.find_file_node(&resolved_path)? {
         Some(id) => id,
         None => return Ok(Vec::new()),
@@ -132,82 +147,425 @@ pub fn symbol_nodes_in_file(graph: &mut CodeGraph, path: &str) -> Result<Vec<(i6
 ///
 /// # Note
 /// This function directly accesses the SymbolNode data to extract symbol_id,
-/// which is not available through SymbolFact.
+/// which is not available through SymbolFact. Served from the warm symbol
+/// index when one is enabled (see `CodeGraph::warm_symbol_cache`).
 pub fn symbol_nodes_in_file_with_ids(
     graph: &mut CodeGraph,
     path: &str,
 ) -> Result<Vec<(i64, SymbolFact, Option<String>)>> {
-    let resolved_path = resolve_query_path(path);
-    let file_id = match graph.files.find_file_node(&resolved_path)? {
-        Some(id) => id,
-        None => return Ok(Vec::new()),
+    let resolved_path = resolve_query_path(&graph.files, path);
+    let file_key = graph.files.index_key(&resolved_path);
+    let nodes = match graph.symbol_index()? {
+        Some(index) => index
+            .in_file(&file_key)
+            .map(|symbol| (symbol.node_id, symbol.node.clone()))
+            .collect(),
+        None => match graph.files.find_file_node(&resolved_path)? {
+            Some(file_id) => file_symbol_nodes(graph, file_id.as_i64())?,
+            None => return Ok(Vec::new()),
+        },
     };
 
     let path_buf = PathBuf::from(&resolved_path);
-    let snapshot = SnapshotId::current();
+    let mut entries = Vec::new();
+    for (symbol_node_id, symbol_node) in nodes {
+        // Convert to SymbolFact
+        let kind = match symbol_node.kind.as_str() {
+            "Function" => SymbolKind::Function,
+            "Method" => SymbolKind::Method,
+            "Class" => SymbolKind::Class,
+            "Interface" => SymbolKind::Interface,
+            "Enum" => SymbolKind::Enum,
+            "Module" => SymbolKind::Module,
+            "Union" => SymbolKind::Union,
+            "Namespace" => SymbolKind::Namespace,
+            "TypeAlias" => SymbolKind::TypeAlias,
+            "Macro" => SymbolKind::Macro,
+            "Unknown" => SymbolKind::Unknown,
+            _ => SymbolKind::Unknown,
+        };
+
+        let kind_normalized = symbol_node
+            .kind_normalized
+            .clone()
+            .unwrap_or_else(|| kind.normalized_key().to_string());
+
+        let fact = SymbolFact {
+            file_path: path_buf.clone(),
+            kind,
+            kind_normalized,
+            name: symbol_node.name.clone(),
+            fqn: symbol_node.fqn,
+            canonical_fqn: symbol_node.canonical_fqn,
+            display_fqn: symbol_node.display_fqn,
+            byte_start: symbol_node.byte_start,
+            byte_end: symbol_node.byte_end,
+            start_line: symbol_node.start_line,
+            start_col: symbol_node.start_col,
+            end_line: symbol_node.end_line,
+            end_col: symbol_node.end_col,
+            signature: symbol_node.signature.clone(),
+            is_test: symbol_node.is_test,
+            visibility: symbol_node.visibility.clone(),
+            deprecated: symbol_node.deprecated,
+            deprecation_message: symbol_node.deprecation_message.clone(),
+        };
 
+        entries.push((symbol_node_id, fact, symbol_node.symbol_id));
+    }
+
+    entries.sort_by(|(_, a, _), (_, b, _)| {
+        a.start_line
+            .cmp(&b.start_line)
+            .then_with(|| a.start_col.cmp(&b.start_col))
+            .then_with(|| a.byte_start.cmp(&b.byte_start))
+    });
+
+    Ok(entries)
+}
+
+/// Symbol nodes reached from a File node by `DEFINES` edges
+fn file_symbol_nodes(graph: &CodeGraph, file_id: i64) -> Result<Vec<(i64, SymbolNode)>> {
+    let snapshot = SnapshotId::current();
     let neighbor_ids = graph.files.backend.neighbors(
         snapshot,
-        file_id.as_i64(),
+        file_id,
         NeighborQuery {
             direction: BackendDirection::Outgoing,
             edge_type: Some("DEFINES".to_string()),
         },
     )?;
 
-    let mut entries = Vec::new();
+    let mut nodes = Vec::new();
     for symbol_node_id in neighbor_ids {
         if let Ok(node) = graph.files.backend.get_node(snapshot, symbol_node_id) {
-            if let Ok(symbol_node) = serde_json::from_value::<SymbolNode>(node.data.clone()) {
-                // Convert to SymbolFact
-                let kind = match symbol_node.kind.as_str() {
-                    "Function" => SymbolKind::Function,
-                    "Method" => SymbolKind::Method,
-                    "Class" => SymbolKind::Class,
-                    "Interface" => SymbolKind::Interface,
-                    "Enum" => SymbolKind::Enum,
-                    "Module" => SymbolKind::Module,
-                    "Union" => SymbolKind::Union,
-                    "Namespace" => SymbolKind::Namespace,
-                    "TypeAlias" => SymbolKind::TypeAlias,
-                    "Unknown" => SymbolKind::Unknown,
-                    _ => SymbolKind::Unknown,
-                };
+            if let Ok(symbol_node) = serde_json::from_value::<SymbolNode>(node.data) {
+                nodes.push((symbol_node_id, symbol_node));
+            }
+        }
+    }
+    Ok(nodes)
+}
 
-                let kind_normalized = symbol_node
-                    .kind_normalized
-                    .clone()
-                    .unwrap_or_else(|| kind.normalized_key().to_string());
-
-                let fact = SymbolFact {
-                    file_path: path_buf.clone(),
-                    kind,
-                    kind_normalized,
-                    name: symbol_node.name.clone(),
-                    fqn: symbol_node.fqn,
-                    canonical_fqn: symbol_node.canonical_fqn,
-                    display_fqn: symbol_node.display_fqn,
-                    byte_start: symbol_node.byte_start,
-                    byte_end: symbol_node.byte_end,
-                    start_line: symbol_node.start_line,
-                    start_col: symbol_node.start_col,
-                    end_line: symbol_node.end_line,
-                    end_col: symbol_node.end_col,
-                };
+/// Load every symbol of every indexed file into a [`WarmSymbolIndex`]
+pub(crate) fn build_symbol_index(graph: &CodeGraph) -> Result<WarmSymbolIndex> {
+    let mut files: Vec<(String, i64)> = graph
+        .files
+        .file_index
+        .iter()
+        .map(|(key, id)| (key.clone(), id.as_i64()))
+        .collect();
+    files.sort();
+
+    let mut index = WarmSymbolIndex::default();
+    for (file_key, file_id) in files {
+        for (node_id, node) in file_symbol_nodes(graph, file_id)? {
+            index.push(CachedSymbol {
+                node_id,
+                file_path: file_key.clone(),
+                node,
+            });
+        }
+    }
+    Ok(index)
+}
+
+/// Filters for [`count_symbols_matching`]
+#[derive(Debug, Clone, Default)]
+pub struct SymbolCountFilter {
+    /// Only count symbols defined in this file
+    pub file_path: Option<String>,
+    /// Only count symbols of this kind
+    pub kind: Option<SymbolKind>,
+    /// Only count symbols with this name
+    pub name: Option<String>,
+    /// Match `name` as a substring instead of exactly
+    pub name_contains: bool,
+    /// Keep only test or only non-test symbols
+    pub test_filter: TestFilter,
+    /// Only count symbols with this declared visibility
+    pub visibility: Option<VisibilityFilter>,
+}
+
+/// Count symbols matching a filter without materializing them
+///
+/// Runs a single `COUNT(*)` over `graph_entities`, so no symbol payloads,
+/// spans, or chunks are loaded. Backs `find --count-only` and
+/// `query --count-only`; the filters mirror those commands' matching rules.
+///
+/// # Returns
+/// Number of matching symbols (0 when the file is not indexed)
+pub fn count_symbols_matching(graph: &mut CodeGraph, filter: &SymbolCountFilter) -> Result<usize> {
+    use rusqlite::types::Value;
+
+    let mut sql = "SELECT COUNT(*) FROM graph_entities s".to_string();
+    let mut params: Vec<Value> = Vec::new();
+
+    if let Some(ref path) = filter.file_path {
+        let resolved_path = resolve_query_path(&graph.files, path);
+        let file_id = match graph.files.find_file_node(&resolved_path)? {
+            Some(id) => id,
+            None => return Ok(0),
+        };
+        sql.push_str(
+            " JOIN graph_edges e ON e.to_id = s.id AND e.edge_type = 'DEFINES' AND e.from_id = ?",
+        );
+        params.push(Value::Integer(file_id.as_i64()));
+    }
 
-                entries.push((symbol_node_id, fact, symbol_node.symbol_id));
+    sql.push_str(" WHERE s.kind = 'Symbol'");
+
+    if let Some(ref kind) = filter.kind {
+        sql.push_str(" AND json_extract(s.data, '$.kind') = ?");
+        params.push(Value::Text(format!("{:?}", kind)));
+    }
+
+    if let Some(ref name) = filter.name {
+        if filter.name_contains {
+            sql.push_str(" AND instr(json_extract(s.data, '$.name'), ?) > 0");
+        } else {
+            sql.push_str(" AND json_extract(s.data, '$.name') = ?");
+        }
+        params.push(Value::Text(name.clone()));
+    }
+
+    match filter.test_filter {
+        TestFilter::All => {}
+        TestFilter::OnlyTests => {
+            sql.push_str(" AND json_extract(s.data, '$.is_test') = 1");
+        }
+        TestFilter::IgnoreTests => {
+            sql.push_str(" AND COALESCE(json_extract(s.data, '$.is_test'), 0) = 0");
+        }
+    }
+
+    match filter.visibility {
+        None => {}
+        Some(VisibilityFilter::Pub) => {
+            sql.push_str(" AND json_extract(s.data, '$.visibility') = 'pub'");
+        }
+        Some(VisibilityFilter::Crate) => {
+            sql.push_str(
+                " AND (json_extract(s.data, '$.visibility') LIKE 'pub(%' \
+                 OR json_extract(s.data, '$.visibility') IN ('internal', 'package'))",
+            );
+        }
+        Some(VisibilityFilter::Private) => {
+            sql.push_str(" AND json_extract(s.data, '$.visibility') = 'private'");
+        }
+    }
+
+    let conn = graph.chunks.connect()?;
+    let count: i64 = conn
+        .query_row(&sql, rusqlite::params_from_iter(&params), |row| row.get(0))
+        .map_err(|e| anyhow::anyhow!("Failed to count symbols: {}", e))?;
+
+    Ok(count as usize)
+}
+
+/// Field a symbol listing is ordered by (`query --sort`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SymbolSortKey {
+    /// Symbol name (unnamed symbols first)
+    Name,
+    /// Position in the file (`byte_start`)
+    Line,
+    /// Normalized kind (`fn`, `struct`, ...)
+    Kind,
+}
+
+impl SymbolSortKey {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Name => "name",
+            Self::Line => "line",
+            Self::Kind => "kind",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "name" => Some(Self::Name),
+            "line" => Some(Self::Line),
+            "kind" => Some(Self::Kind),
+            _ => None,
+        }
+    }
+}
+
+/// Ordering for a symbol listing
+///
+/// Ties on the key break by `byte_start`, then name and `byte_end`, so the
+/// order is total and output is stable. `reverse` flips the whole ordering.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SymbolSort {
+    pub key: SymbolSortKey,
+    pub reverse: bool,
+}
+
+impl SymbolSort {
+    pub fn compare(&self, a: &SymbolFact, b: &SymbolFact) -> std::cmp::Ordering {
+        let primary = match self.key {
+            SymbolSortKey::Name => a.name.cmp(&b.name),
+            SymbolSortKey::Line => std::cmp::Ordering::Equal,
+            SymbolSortKey::Kind => a.kind_normalized.cmp(&b.kind_normalized),
+        };
+        let ordering = primary
+            .then_with(|| a.byte_start.cmp(&b.byte_start))
+            .then_with(|| a.name.cmp(&b.name))
+            .then_with(|| a.byte_end.cmp(&b.byte_end));
+        if self.reverse {
+            ordering.reverse()
+        } else {
+            ordering
+        }
+    }
+
+    /// Sort symbols in place
+    pub fn apply(&self, symbols: &mut [SymbolFact]) {
+        symbols.sort_by(|a, b| self.compare(a, b));
+    }
+}
+
+/// List symbols whose FQN lies under a module path (`--defined-in`)
+///
+/// A symbol matches when its `fqn` or `display_fqn` starts with `prefix` on a
+/// segment boundary (see [`fqn_has_prefix`]). A leading `crate` segment names
+/// the crate root, so `crate::net` is matched against the crate-relative
+/// `fqn` and selects the same symbols in every crate. `kind`, `file_path` and
+/// `test_filter` narrow the listing further.
+///
+/// # Returns
+/// Vector of (node_id, SymbolFact, symbol_id) tuples ordered by display FQN,
+/// then file, line and column
+pub fn symbols_defined_in(
+    graph: &mut CodeGraph,
+    prefix: &str,
+    kind: Option<&SymbolKind>,
+    file_path: Option<&str>,
+    test_filter: TestFilter,
+) -> Result<Vec<(i64, SymbolFact, Option<String>)>> {
+    let files: Vec<String> = match file_path {
+        Some(path) => vec![path.to_string()],
+        None => graph.all_file_nodes()?.into_keys().collect(),
+    };
+
+    let mut matches = Vec::new();
+    for path in &files {
+        for (node_id, fact, symbol_id) in symbol_nodes_in_file_with_ids(graph, path)? {
+            if kind.is_some_and(|kind| fact.kind != *kind)
+                || !test_filter.matches(fact.is_test)
+                || !symbol_defined_in(&fact, prefix)
+            {
+                continue;
             }
+            matches.push((node_id, fact, symbol_id));
         }
     }
 
-    entries.sort_by(|(_, a, _), (_, b, _)| {
-        a.start_line
-            .cmp(&b.start_line)
+    matches.sort_by(|(_, a, _), (_, b, _)| {
+        let fqn = |s: &SymbolFact| s.display_fqn.clone().or_else(|| s.fqn.clone());
+        fqn(a)
+            .cmp(&fqn(b))
+            .then_with(|| a.file_path.cmp(&b.file_path))
+            .then_with(|| a.start_line.cmp(&b.start_line))
+            .then_with(|| a.start_col.cmp(&b.start_col))
+    });
+
+    Ok(matches)
+}
+
+/// List symbols marked deprecated at index time (`find --deprecated`)
+///
+/// `name` keeps only symbols with that exact name; `file_path` and
+/// `test_filter` narrow the listing further.
+///
+/// # Returns
+/// Vector of (node_id, SymbolFact, symbol_id) tuples ordered by file, line
+/// and column
+pub fn deprecated_symbols(
+    graph: &mut CodeGraph,
+    name: Option<&str>,
+    file_path: Option<&str>,
+    test_filter: TestFilter,
+) -> Result<Vec<(i64, SymbolFact, Option<String>)>> {
+    let files: Vec<String> = match file_path {
+        Some(path) => vec![path.to_string()],
+        None => graph.all_file_nodes()?.into_keys().collect(),
+    };
+
+    let mut matches = Vec::new();
+    for path in &files {
+        for (node_id, fact, symbol_id) in symbol_nodes_in_file_with_ids(graph, path)? {
+            if !fact.deprecated
+                || !test_filter.matches(fact.is_test)
+                || name.is_some_and(|name| fact.name.as_deref() != Some(name))
+            {
+                continue;
+            }
+            matches.push((node_id, fact, symbol_id));
+        }
+    }
+
+    matches.sort_by(|(_, a, _), (_, b, _)| {
+        a.file_path
+            .cmp(&b.file_path)
+            .then_with(|| a.start_line.cmp(&b.start_line))
+            .then_with(|| a.start_col.cmp(&b.start_col))
+    });
+
+    Ok(matches)
+}
+
+/// A symbol ranked by [`symbols_near_line`]: (distance, node_id, fact, symbol_id)
+pub type NearSymbol = (usize, i64, SymbolFact, Option<String>);
+
+/// Rank a file's symbols by how close they start to `line` (`--near`)
+///
+/// Unlike a containment lookup, every symbol in the file is returned: the
+/// distance is `|start_line - line|`. Ties go to the symbol starting earlier
+/// (above the line), then by column, byte offset and node ID, so the order
+/// is deterministic.
+///
+/// # Returns
+/// Ranked symbols, closest first
+pub fn symbols_near_line(
+    graph: &mut CodeGraph,
+    path: &str,
+    line: usize,
+) -> Result<Vec<NearSymbol>> {
+    let mut ranked: Vec<NearSymbol> = symbol_nodes_in_file_with_ids(graph, path)?
+        .into_iter()
+        .map(|(node_id, fact, symbol_id)| {
+            (fact.start_line.abs_diff(line), node_id, fact, symbol_id)
+        })
+        .collect();
+
+    ranked.sort_by(|(dist_a, id_a, a, _), (dist_b, id_b, b, _)| {
+        dist_a
+            .cmp(dist_b)
+            .then_with(|| a.start_line.cmp(&b.start_line))
             .then_with(|| a.start_col.cmp(&b.start_col))
             .then_with(|| a.byte_start.cmp(&b.byte_start))
+            .then_with(|| id_a.cmp(id_b))
     });
 
-    Ok(entries)
+    Ok(ranked)
+}
+
+/// Whether a symbol lies under `prefix` (see [`symbols_defined_in`])
+fn symbol_defined_in(fact: &SymbolFact, prefix: &str) -> bool {
+    if fqn_has_prefix(prefix, "crate") {
+        let relative = prefix["crate".len()..].trim_start_matches(':');
+        return fact
+            .fqn
+            .as_deref()
+            .is_some_and(|fqn| fqn_has_prefix(fqn, relative));
+    }
+    [fact.fqn.as_deref(), fact.display_fqn.as_deref()]
+        .into_iter()
+        .flatten()
+        .any(|fqn| fqn_has_prefix(fqn, prefix))
 }
 
 /// Lookup symbol extents (byte + line range) by name within a file.
@@ -241,7 +599,7 @@ pub fn symbol_extents(
 /// and maintains determinism. No new indexes or caching.
 pub fn symbol_id_by_name(graph: &mut CodeGraph, path: &str, name: &str) -> Result<Option<i64>> {
     // Normalize path to match index format
-    let normalized_path = crate::graph::files::normalize_path_for_index(path);
+    let normalized_path = graph.files.index_key(path);
     let file_id = match graph.files.find_file_node(&normalized_path)? {
         Some(id) => id,
         None => return Ok(None),
@@ -301,8 +659,15 @@ pub fn stable_symbol_id_by_name(
 ///
 /// # Note
 /// SymbolId is the primary key for symbol identity. This function uses
-/// the GraphBackend trait to work with both SQLite and V3 backends.
+/// the GraphBackend trait to work with both SQLite and V3 backends, or the
+/// in-memory index once `CodeGraph::warm_symbol_cache` has been called.
 pub fn find_by_symbol_id(graph: &mut CodeGraph, symbol_id: &str) -> Result<Option<SymbolNode>> {
+    if let Some(index) = graph.symbol_index()? {
+        return Ok(index
+            .by_symbol_id(symbol_id)
+            .map(|symbol| symbol.node.clone()));
+    }
+
     // Use GraphBackend trait instead of direct SQL for V3 compatibility
     let entity_ids = graph.calls.backend.entity_ids()?;
     let snapshot = SnapshotId::current();
@@ -322,6 +687,42 @@ pub fn find_by_symbol_id(graph: &mut CodeGraph, symbol_id: &str) -> Result<Optio
     Ok(None)
 }
 
+/// Resolve a stable SymbolId to its defining file and symbol payload
+///
+/// Unlike [`find_by_symbol_id`], this also returns the path of the file that
+/// defines the symbol (taken from the entity row, or its `DEFINES` edge), so
+/// callers can fetch the symbol's code without a name lookup.
+///
+/// # Returns
+/// `Some((file_path, symbol))` if the ID exists, `None` otherwise
+pub fn find_symbol_location_by_id(
+    graph: &mut CodeGraph,
+    symbol_id: &str,
+) -> Result<Option<(String, SymbolNode)>> {
+    let conn = graph.chunks.connect()?;
+    let row = conn.query_row(
+        "SELECT COALESCE(s.file_path, json_extract(f.data, '$.path')), s.data
+         FROM graph_entities s
+         LEFT JOIN graph_edges e ON e.to_id = s.id AND e.edge_type = 'DEFINES'
+         LEFT JOIN graph_entities f ON f.id = e.from_id AND f.kind = 'File'
+         WHERE s.kind = 'Symbol' AND json_extract(s.data, '$.symbol_id') = ?1
+         LIMIT 1",
+        params![symbol_id],
+        |row| Ok((row.get::<_, Option<String>>(0)?, row.get::<_, String>(1)?)),
+    );
+
+    let (file_path, data) = match row {
+        Ok(row) => row,
+        Err(rusqlite::Error::QueryReturnedNoRows) => return Ok(None),
+        Err(e) => return Err(anyhow::anyhow!("Failed to query symbol ID: {}", e)),
+    };
+    let file_path = file_path
+        .ok_or_else(|| anyhow::anyhow!("Symbol ID '{}' has no defining file", symbol_id))?;
+    let symbol: SymbolNode = serde_json::from_str(&data)?;
+
+    Ok(Some((file_path, symbol)))
+}
+
 /// Index references for a file into the graph
 ///
 /// # Behavior
@@ -339,6 +740,9 @@ pub fn find_by_symbol_id(graph: &mut CodeGraph, symbol_id: &str) -> Result<Optio
 /// # Returns
 /// Number of references indexed
 pub fn index_references(graph: &mut CodeGraph, path: &str, source: &[u8]) -> Result<usize> {
+    // Wall time for `watch --profile`; observational only
+    let start = std::time::Instant::now();
+
     // Get file node ID
     let _file_id = match graph.files.find_file_node(path)? {
         Some(id) => id,
@@ -361,6 +765,13 @@ pub fn index_references(graph: &mut CodeGraph, path: &str, source: &[u8]) -> Res
         }
     }
 
+    // Symbols defined in this file, for attaching each reference's enclosing function
+    let file_symbols: Vec<(SymbolFact, Option<String>)> =
+        symbol_nodes_in_file_with_ids(graph, path)?
+            .into_iter()
+            .map(|(_, fact, symbol_id)| (fact, symbol_id))
+            .collect();
+
     // Index references using ReferenceOps with ALL symbols
     let count = graph.references.index_references_with_symbol_id(
         path,
@@ -368,6 +779,7 @@ pub fn index_references(graph: &mut CodeGraph, path: &str, source: &[u8]) -> Res
         &symbol_id_to_id,
         &symbol_fqn_to_id,
         &all_symbol_facts,
+        &file_symbols,
     )?;
 
     // Populate cross-file references in side tables for efficient lookup
@@ -381,6 +793,7 @@ pub fn index_references(graph: &mut CodeGraph, path: &str, source: &[u8]) -> Res
         &all_symbol_facts,
     )?;
 
+    graph.record_phase(IndexPhase::IndexReferences, start.elapsed());
     Ok(count)
 }
 
@@ -454,6 +867,11 @@ fn populate_cross_file_refs(
                 && s.byte_end >= reference.byte_end
         });
 
+        // API-only mode keeps no references from skipped private code
+        if graph.api_only && containing_symbol.is_none() {
+            continue;
+        }
+
         let from_symbol_id = containing_symbol
             .and_then(|s| s.fqn.clone())
             .unwrap_or_else(|| format!("file:{}", path));
@@ -486,6 +904,71 @@ pub fn references_to_symbol(graph: &mut CodeGraph, symbol_id: i64) -> Result<Vec
     graph.references.references_to_symbol(symbol_id)
 }
 
+/// An import in a file, resolved to the imported symbol
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileImport {
+    /// The import reference (kind [`ReferenceKind::Import`](crate::ReferenceKind::Import))
+    pub reference: ReferenceFact,
+    /// Stable ID of the imported symbol
+    pub symbol_id: Option<String>,
+    /// FQN of the imported symbol
+    pub fqn: Option<String>,
+    /// File that defines the imported symbol
+    pub target_file: Option<String>,
+}
+
+/// Query the imports of a file that resolve to indexed symbols
+///
+/// Imports of symbols outside the index (such as `std`) have no import
+/// reference; the file's Import nodes still record them.
+///
+/// # Returns
+/// One entry per imported symbol, in source order
+pub fn imports_of_file(graph: &mut CodeGraph, path: &str) -> Result<Vec<FileImport>> {
+    let resolved_path = resolve_query_path(&graph.files, path);
+    let conn = graph.chunks.connect()?;
+    let mut stmt = conn
+        .prepare(
+            "SELECT r.id, s.data, COALESCE(s.file_path, json_extract(f.data, '$.path'))
+             FROM graph_entities r
+             JOIN graph_edges re ON re.from_id = r.id AND re.edge_type = 'REFERENCES'
+             JOIN graph_entities s ON s.id = re.to_id AND s.kind = 'Symbol'
+             LEFT JOIN graph_edges d ON d.to_id = s.id AND d.edge_type = 'DEFINES'
+             LEFT JOIN graph_entities f ON f.id = d.from_id AND f.kind = 'File'
+             WHERE r.kind = 'Reference' AND r.file_path IN (?1, ?2)
+               AND json_extract(r.data, '$.kind') = 'import'
+             ORDER BY json_extract(r.data, '$.byte_start'), r.id",
+        )
+        .map_err(|e| anyhow::anyhow!("Failed to prepare imports query: {}", e))?;
+    let rows = stmt
+        // Reference nodes keep the path as given to `index_references`, which
+        // may be the unresolved spelling
+        .query_map(params![resolved_path, path], |row| {
+            Ok((
+                row.get::<_, i64>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, Option<String>>(2)?,
+            ))
+        })
+        .map_err(|e| anyhow::anyhow!("Failed to query imports: {}", e))?
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+
+    let mut imports = Vec::with_capacity(rows.len());
+    for (reference_id, symbol_data, target_file) in rows {
+        let Some(reference) = graph.references.reference_fact_from_node(reference_id)? else {
+            continue;
+        };
+        let symbol: SymbolNode = serde_json::from_str(&symbol_data)?;
+        imports.push(FileImport {
+            reference,
+            symbol_id: symbol.symbol_id,
+            fqn: symbol.fqn.or(symbol.name),
+            target_file,
+        });
+    }
+    Ok(imports)
+}
+
 /// Query cross-file references to a specific symbol
 ///
 /// This queries the side tables for cross-file references, which enables
@@ -674,6 +1157,11 @@ impl CodeGraph {
                         start_col: 0,
                         end_line: 0,
                         end_col: 0,
+                        signature: None,
+                        is_test: false,
+                        visibility: None,
+                        deprecated: false,
+                        deprecation_message: None,
                     });
 
                 results.push(SymbolQueryResult {
@@ -714,6 +1202,11 @@ impl CodeGraph {
                         start_col: 0,
                         end_line: 0,
                         end_col: 0,
+                        signature: None,
+                        is_test: false,
+                        visibility: None,
+                        deprecated: false,
+                        deprecation_message: None,
                     });
 
                 results.push(SymbolQueryResult {
@@ -776,6 +1269,11 @@ impl CodeGraph {
                         start_col: 0,
                         end_line: 0,
                         end_col: 0,
+                        signature: None,
+                        is_test: false,
+                        visibility: None,
+                        deprecated: false,
+                        deprecation_message: None,
                     });
 
                 Ok(SymbolQueryResult {
@@ -808,30 +1306,76 @@ impl CodeGraph {
 /// Display FQN can have collisions (multiple symbols with same human-readable name).
 /// This function enumerates all candidates for ambiguity resolution.
 /// Returns empty Vec when no matches found (not an error).
+///
+/// Candidates are in candidate order: defining file path, then byte start,
+/// then stable symbol ID (entity ID last, for symbols without one).
 pub fn get_ambiguous_candidates(
     graph: &mut CodeGraph,
     display_fqn: &str,
 ) -> Result<Vec<(i64, SymbolNode)>> {
-    // Use GraphBackend trait instead of direct SQL for V3 compatibility
-    let entity_ids = graph.calls.backend.entity_ids()?;
-    let snapshot = SnapshotId::current();
-    let mut candidates = Vec::new();
+    Ok(located_candidates(graph, "$.display_fqn", display_fqn)?
+        .into_iter()
+        .map(|(id, _, symbol)| (id, symbol))
+        .collect())
+}
 
-    for entity_id in entity_ids {
-        if let Ok(node) = graph.calls.backend.get_node(snapshot, entity_id) {
-            if node.kind == "Symbol" {
-                if let Ok(symbol_node) = serde_json::from_value::<SymbolNode>(node.data.clone()) {
-                    if symbol_node.display_fqn.as_deref() == Some(display_fqn) {
-                        candidates.push((entity_id, symbol_node));
-                    }
-                }
-            }
-        }
+/// Candidates for `find --ambiguous`, with their defining file paths
+///
+/// `name` is matched against display FQNs; when no symbol has that display
+/// FQN it is matched against symbol names instead. Ordered like
+/// [`get_ambiguous_candidates`].
+pub fn ambiguous_candidate_locations(
+    graph: &mut CodeGraph,
+    name: &str,
+) -> Result<Vec<(i64, String, SymbolNode)>> {
+    let candidates = located_candidates(graph, "$.display_fqn", name)?;
+    if !candidates.is_empty() {
+        return Ok(candidates);
     }
+    located_candidates(graph, "$.name", name)
+}
 
-    // Sort by ID for deterministic ordering (matches SQL ORDER BY id)
-    candidates.sort_by_key(|(id, _)| *id);
+/// Symbols whose JSON field at `json_path` equals `value`, in candidate order
+fn located_candidates(
+    graph: &mut CodeGraph,
+    json_path: &str,
+    value: &str,
+) -> Result<Vec<(i64, String, SymbolNode)>> {
+    let conn = graph.chunks.connect()?;
+    let mut stmt = conn
+        .prepare(
+            "SELECT s.id, COALESCE(s.file_path, json_extract(f.data, '$.path'), ''), s.data
+             FROM graph_entities s
+             LEFT JOIN graph_edges e ON e.to_id = s.id AND e.edge_type = 'DEFINES'
+             LEFT JOIN graph_entities f ON f.id = e.from_id AND f.kind = 'File'
+             WHERE s.kind = 'Symbol' AND json_extract(s.data, ?1) = ?2",
+        )
+        .map_err(|e| anyhow::anyhow!("Failed to prepare candidate query: {}", e))?;
+    let rows = stmt
+        .query_map(params![json_path, value], |row| {
+            Ok((
+                row.get::<_, i64>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, String>(2)?,
+            ))
+        })
+        .map_err(|e| anyhow::anyhow!("Failed to query candidates: {}", e))?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| anyhow::anyhow!("Failed to collect candidates: {}", e))?;
 
+    let mut candidates = Vec::with_capacity(rows.len());
+    for (id, file_path, data) in rows {
+        if let Ok(symbol) = serde_json::from_str::<SymbolNode>(&data) {
+            candidates.push((id, file_path, symbol));
+        }
+    }
+    candidates.sort_by(|(a_id, a_file, a), (b_id, b_file, b)| {
+        a_file
+            .cmp(b_file)
+            .then_with(|| a.byte_start.cmp(&b.byte_start))
+            .then_with(|| a.symbol_id.cmp(&b.symbol_id))
+            .then_with(|| a_id.cmp(b_id))
+    });
     Ok(candidates)
 }
 
@@ -895,6 +1439,29 @@ pub fn collision_groups(
     graph: &mut CodeGraph,
     field: CollisionField,
     limit: usize,
+) -> Result<Vec<CollisionGroup>> {
+    collect_collision_groups(graph, field, limit, false)
+}
+
+/// Query collision groups by field with generic arguments stripped
+///
+/// Values are grouped by [`canonicalize_generics`], so `Wrapper<u32>::get`
+/// and `Wrapper<String>::get` form one `Wrapper::get` group.
+///
+/// [`canonicalize_generics`]: crate::graph::canonical_fqn::canonicalize_generics
+pub fn collision_groups_canonicalized(
+    graph: &mut CodeGraph,
+    field: CollisionField,
+    limit: usize,
+) -> Result<Vec<CollisionGroup>> {
+    collect_collision_groups(graph, field, limit, true)
+}
+
+fn collect_collision_groups(
+    graph: &mut CodeGraph,
+    field: CollisionField,
+    limit: usize,
+    canonicalize_generics: bool,
 ) -> Result<Vec<CollisionGroup>> {
     // Use GraphBackend trait instead of direct SQL for V3 compatibility
     let entity_ids = graph.calls.backend.entity_ids()?;
@@ -914,6 +1481,13 @@ pub fn collision_groups(
                         CollisionField::CanonicalFqn => symbol_node.canonical_fqn.clone(),
                     };
 
+                    let field_value = match field_value {
+                        Some(value) if canonicalize_generics => {
+                            Some(crate::graph::canonical_fqn::canonicalize_generics(&value))
+                        }
+                        value => value,
+                    };
+
                     if let Some(value) = field_value {
                         groups.entry(value).or_default().push((
                             entity_id,
@@ -990,13 +1564,577 @@ pub fn collision_groups(
     Ok(results)
 }
 
+/// Suggested disambiguation for one member of a collision group
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CollisionSuggestion {
+    /// Shortest trailing part of the file path that no member in another
+    /// file shares (`None` when every member is in the same file)
+    pub path_prefix: Option<String>,
+    /// Shortest trailing part of the FQN scope that no member in the same
+    /// file shares (`None` when no other member is in the same file)
+    pub module_prefix: Option<String>,
+    /// `path_prefix::module_prefix::name`, the scope keeping its language's
+    /// separator; `None` when another member has the same file and scope, so
+    /// only the symbol ID tells them apart
+    pub qualified_name: Option<String>,
+}
+
+/// Propose a disambiguating prefix for each member of a collision group
+///
+/// Members in different files are told apart by the fewest trailing path
+/// components (`net/client.rs` vs `db/client.rs`); members in the same file
+/// additionally by the fewest trailing segments of the scope of their
+/// `display_fqn` (`a` for `crate::a::run` vs `crate::b::run`). Suggestions
+/// are returned in candidate order and depend only on the members' paths and
+/// FQNs, so the same group always gets the same suggestions.
+pub fn resolve_suggestions(candidates: &[CollisionCandidate]) -> Vec<CollisionSuggestion> {
+    let paths: Vec<Vec<&str>> = candidates
+        .iter()
+        .map(|candidate| {
+            candidate
+                .file_path
+                .as_deref()
+                .unwrap_or("")
+                .split(['/', '\\'])
+                .filter(|component| !component.is_empty())
+                .collect()
+        })
+        .collect();
+    let scopes: Vec<(Vec<&str>, &str)> = candidates.iter().map(fqn_scope).collect();
+
+    candidates
+        .iter()
+        .enumerate()
+        .map(|(i, candidate)| {
+            let other_files: Vec<&[&str]> = paths
+                .iter()
+                .filter(|path| **path != paths[i])
+                .map(Vec::as_slice)
+                .collect();
+            let same_file: Vec<&[&str]> = (0..candidates.len())
+                .filter(|&j| j != i && paths[j] == paths[i])
+                .map(|j| scopes[j].0.as_slice())
+                .collect();
+
+            let path_prefix = (!other_files.is_empty()).then(|| {
+                let depth = unique_suffix_len(&paths[i], &other_files).unwrap_or(paths[i].len());
+                paths[i][paths[i].len() - depth..].join("/")
+            });
+            let (scope, separator) = &scopes[i];
+            let module_depth = if same_file.is_empty() {
+                Some(0)
+            } else {
+                unique_suffix_len(scope, &same_file)
+            };
+            let module_prefix = module_depth
+                .filter(|&depth| depth > 0)
+                .map(|depth| scope[scope.len() - depth..].join(separator));
+
+            let name = candidate
+                .name
+                .clone()
+                .or_else(|| candidate.display_fqn.clone())
+                .unwrap_or_default();
+            let qualified_name = module_depth.map(|_| {
+                let scoped = match &module_prefix {
+                    Some(module) => format!("{}{}{}", module, separator, name),
+                    None => name,
+                };
+                match &path_prefix {
+                    Some(path) => format!("{}::{}", path, scoped),
+                    None => scoped,
+                }
+            });
+
+            CollisionSuggestion {
+                path_prefix,
+                module_prefix,
+                qualified_name,
+            }
+        })
+        .collect()
+}
+
+/// Scope segments of a candidate's `display_fqn` (everything before its name)
+/// and the separator between them
+fn fqn_scope(candidate: &CollisionCandidate) -> (Vec<&str>, &'static str) {
+    let fqn = candidate.display_fqn.as_deref().unwrap_or("");
+    let separator = if fqn.contains("::") { "::" } else { "." };
+    let scope = match candidate.name.as_deref() {
+        Some(name) => fqn
+            .strip_suffix(name)
+            .map(|rest| rest.strip_suffix(separator).unwrap_or(rest))
+            .unwrap_or(fqn),
+        None => fqn.rsplit_once(separator).map_or("", |(scope, _)| scope),
+    };
+    let segments = scope
+        .split(separator)
+        .filter(|segment| !segment.is_empty())
+        .collect();
+    (segments, separator)
+}
+
+/// Fewest trailing segments of `own` that end none of `others`
+///
+/// `None` when even all of `own` is a suffix of one of them.
+fn unique_suffix_len(own: &[&str], others: &[&[&str]]) -> Option<usize> {
+    (1..=own.len()).find(|&depth| {
+        let suffix = &own[own.len() - depth..];
+        others.iter().all(|other| !other.ends_with(suffix))
+    })
+}
+
+/// Call sites with no `CALLS` edge to an indexed callee symbol
+///
+/// Typically calls whose callee was deleted or renamed after the calling
+/// file was indexed.
+pub fn unresolved_calls(graph: &mut CodeGraph) -> Result<Vec<CallNode>> {
+    let snapshot = SnapshotId::current();
+    let mut calls = Vec::new();
+
+    for entity_id in graph.calls.backend.entity_ids()? {
+        let node = match graph.calls.backend.get_node(snapshot, entity_id) {
+            Ok(node) => node,
+            Err(_) => continue,
+        };
+        if node.kind != "Call" {
+            continue;
+        }
+        let call: CallNode = match serde_json::from_value(node.data) {
+            Ok(call) => call,
+            Err(_) => continue,
+        };
+
+        let callees = graph.calls.backend.neighbors(
+            snapshot,
+            entity_id,
+            NeighborQuery {
+                direction: BackendDirection::Outgoing,
+                edge_type: Some("CALLS".to_string()),
+            },
+        )?;
+        if callees.is_empty() {
+            calls.push(call);
+        }
+    }
+
+    Ok(calls)
+}
+
+/// Problems reported by SQLite's `PRAGMA integrity_check`
+///
+/// Empty when the database file is structurally sound.
+pub fn sqlite_integrity_errors(graph: &CodeGraph) -> Result<Vec<String>> {
+    let conn = graph.chunks.connect()?;
+    let mut stmt = conn
+        .prepare("PRAGMA integrity_check")
+        .map_err(|e| anyhow::anyhow!("Failed to prepare integrity check: {}", e))?;
+    let errors = stmt
+        .query_map([], |row| row.get::<_, String>(0))
+        .map_err(|e| anyhow::anyhow!("Integrity check query failed: {}", e))?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| anyhow::anyhow!("Integrity check query failed: {}", e))?
+        .into_iter()
+        .filter(|line| line != "ok")
+        .collect();
+    Ok(errors)
+}
+
+/// Count files, symbols, references and calls inside one read transaction
+///
+/// Takes a connection rather than the graph so a polling caller
+/// (`status --watch`) can keep one connection open across snapshots.
+/// With `by_kind`, symbols are also counted per `SymbolKind`.
+pub fn count_snapshot(conn: &rusqlite::Connection, by_kind: bool) -> Result<CountSnapshot> {
+    let tx = conn
+        .unchecked_transaction()
+        .map_err(|e| anyhow::anyhow!("Failed to begin count snapshot: {}", e))?;
+    let (files, symbols, references, calls): (i64, i64, i64, i64) = tx
+        .query_row(
+            "SELECT
+                 (SELECT COUNT(*) FROM graph_entities WHERE kind = 'File'),
+                 (SELECT COUNT(*) FROM graph_entities WHERE kind = 'Symbol'),
+                 (SELECT COUNT(*) FROM graph_entities WHERE kind = 'Reference'),
+                 (SELECT COUNT(*) FROM graph_entities WHERE kind = 'Call')",
+            [],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)),
+        )
+        .map_err(|e| anyhow::anyhow!("Failed to count entities: {}", e))?;
+
+    let symbols_by_kind = if by_kind {
+        let mut stmt = tx
+            .prepare(
+                "SELECT COALESCE(json_extract(data, '$.kind'), 'Unknown'), COUNT(*)
+                 FROM graph_entities WHERE kind = 'Symbol'
+                 GROUP BY 1",
+            )
+            .map_err(|e| anyhow::anyhow!("Failed to prepare kind counts: {}", e))?;
+        let counts = stmt
+            .query_map([], |row| {
+                Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)? as usize))
+            })
+            .map_err(|e| anyhow::anyhow!("Failed to count symbols by kind: {}", e))?
+            .collect::<Result<_, _>>()
+            .map_err(|e| anyhow::anyhow!("Failed to count symbols by kind: {}", e))?;
+        Some(counts)
+    } else {
+        None
+    };
+    tx.commit()
+        .map_err(|e| anyhow::anyhow!("Failed to end count snapshot: {}", e))?;
+
+    Ok(CountSnapshot {
+        files: files as usize,
+        symbols: symbols as usize,
+        references: references as usize,
+        calls: calls as usize,
+        symbols_by_kind,
+    })
+}
+
+/// Symbol nodes whose owning File node is missing
+///
+/// Every indexed symbol is the target of a `DEFINES` edge from its File node;
+/// a symbol without one survived the deletion of its file.
+pub fn orphan_symbols(graph: &CodeGraph) -> Result<Vec<OrphanSymbol>> {
+    let conn = graph.chunks.connect()?;
+    let mut stmt = conn
+        .prepare(
+            "SELECT s.id, s.name, s.file_path FROM graph_entities s
+             WHERE s.kind = 'Symbol'
+               AND NOT EXISTS (
+                   SELECT 1 FROM graph_edges e
+                   JOIN graph_entities f ON f.id = e.from_id AND f.kind = 'File'
+                   WHERE e.to_id = s.id AND e.edge_type = 'DEFINES'
+               )
+             ORDER BY s.id",
+        )
+        .map_err(|e| anyhow::anyhow!("Failed to prepare orphan symbol query: {}", e))?;
+    let symbols = stmt
+        .query_map([], |row| {
+            Ok(OrphanSymbol {
+                entity_id: row.get(0)?,
+                name: row.get(1)?,
+                file_path: row.get(2)?,
+            })
+        })
+        .map_err(|e| anyhow::anyhow!("Failed to query orphan symbols: {}", e))?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| anyhow::anyhow!("Failed to collect orphan symbols: {}", e))?;
+    Ok(symbols)
+}
+
+/// `CALLS` and `CALLER` edges with an endpoint that is not an existing entity
+pub fn dangling_call_edges(graph: &CodeGraph) -> Result<Vec<DanglingEdge>> {
+    let conn = graph.chunks.connect()?;
+    let mut stmt = conn
+        .prepare(
+            "SELECT e.id, e.edge_type, e.from_id, e.to_id,
+                    NOT EXISTS (SELECT 1 FROM graph_entities WHERE id = e.from_id),
+                    NOT EXISTS (SELECT 1 FROM graph_entities WHERE id = e.to_id)
+             FROM graph_edges e
+             WHERE e.edge_type IN ('CALLS', 'CALLER')
+               AND (NOT EXISTS (SELECT 1 FROM graph_entities WHERE id = e.from_id)
+                    OR NOT EXISTS (SELECT 1 FROM graph_entities WHERE id = e.to_id))
+             ORDER BY e.id",
+        )
+        .map_err(|e| anyhow::anyhow!("Failed to prepare dangling edge query: {}", e))?;
+    let edges = stmt
+        .query_map([], |row| {
+            Ok(DanglingEdge {
+                edge_id: row.get(0)?,
+                edge_type: row.get(1)?,
+                from_id: row.get(2)?,
+                to_id: row.get(3)?,
+                missing_from: row.get(4)?,
+                missing_to: row.get(5)?,
+            })
+        })
+        .map_err(|e| anyhow::anyhow!("Failed to query dangling edges: {}", e))?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| anyhow::anyhow!("Failed to collect dangling edges: {}", e))?;
+    Ok(edges)
+}
+
+/// Pairs of symbols in the same file whose spans cross or duplicate each other
+///
+/// A symbol nested entirely inside another (a method in an impl block) is
+/// fine; two symbols where one starts inside the other and ends past it, or
+/// two copies of the same symbol over the same span, indicate a broken index.
+pub fn overlapping_symbol_spans(graph: &CodeGraph) -> Result<Vec<SpanOverlap>> {
+    let conn = graph.chunks.connect()?;
+    let mut stmt = conn
+        .prepare(
+            "SELECT json_extract(f.data, '$.path'), s.id,
+                    COALESCE(json_extract(s.data, '$.name'), ''),
+                    COALESCE(json_extract(s.data, '$.kind'), ''),
+                    json_extract(s.data, '$.byte_start'),
+                    json_extract(s.data, '$.byte_end')
+             FROM graph_entities f
+             JOIN graph_edges e ON e.from_id = f.id AND e.edge_type = 'DEFINES'
+             JOIN graph_entities s ON s.id = e.to_id AND s.kind = 'Symbol'
+             WHERE f.kind = 'File'
+               AND json_extract(s.data, '$.byte_start') IS NOT NULL
+               AND json_extract(s.data, '$.byte_end') IS NOT NULL
+             ORDER BY 1, 5, 6 DESC, s.id",
+        )
+        .map_err(|e| anyhow::anyhow!("Failed to prepare symbol span query: {}", e))?;
+    let rows = stmt
+        .query_map([], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                SpanSymbol {
+                    entity_id: row.get(1)?,
+                    name: row.get(2)?,
+                    kind: row.get(3)?,
+                    byte_start: row.get::<_, i64>(4)? as u64,
+                    byte_end: row.get::<_, i64>(5)? as u64,
+                },
+            ))
+        })
+        .map_err(|e| anyhow::anyhow!("Failed to query symbol spans: {}", e))?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| anyhow::anyhow!("Failed to collect symbol spans: {}", e))?;
+
+    // Rows are sorted by start, then longest first, so a symbol can only
+    // overlap the ones after it that start before it ends
+    let mut overlaps = Vec::new();
+    for (i, (path, first)) in rows.iter().enumerate() {
+        for (other_path, second) in &rows[i + 1..] {
+            if other_path != path || second.byte_start >= first.byte_end {
+                break;
+            }
+            let duplicate = second.byte_start == first.byte_start
+                && second.byte_end == first.byte_end
+                && second.name == first.name
+                && second.kind == first.kind;
+            if duplicate || second.byte_end > first.byte_end {
+                overlaps.push(SpanOverlap {
+                    file_path: path.clone(),
+                    first: first.clone(),
+                    second: second.clone(),
+                    duplicate,
+                });
+            }
+        }
+    }
+    Ok(overlaps)
+}
+
+/// Map each symbol's FQN to its stable symbol IDs
+///
+/// Keys are `canonical_fqn`, falling back to `fqn` and then `name`. Values are
+/// sorted; more than one ID per key means several symbols share that name.
+/// Symbols without a persisted `symbol_id` are skipped.
+pub fn symbol_id_map(
+    graph: &mut CodeGraph,
+) -> Result<std::collections::BTreeMap<String, Vec<String>>> {
+    let entity_ids = graph.calls.backend.entity_ids()?;
+    let snapshot = SnapshotId::current();
+    let mut map: std::collections::BTreeMap<String, Vec<String>> =
+        std::collections::BTreeMap::new();
+
+    for entity_id in entity_ids {
+        let Ok(node) = graph.calls.backend.get_node(snapshot, entity_id) else {
+            continue;
+        };
+        if node.kind != "Symbol" {
+            continue;
+        }
+        let Ok(symbol) = serde_json::from_value::<SymbolNode>(node.data) else {
+            continue;
+        };
+        let Some(symbol_id) = symbol.symbol_id else {
+            continue;
+        };
+        let key = symbol
+            .canonical_fqn
+            .filter(|value| !value.is_empty())
+            .or(symbol.fqn.filter(|value| !value.is_empty()))
+            .or(symbol.name.filter(|value| !value.is_empty()));
+        if let Some(key) = key {
+            map.entry(key).or_default().push(symbol_id);
+        }
+    }
+
+    for ids in map.values_mut() {
+        ids.sort();
+    }
+
+    Ok(map)
+}
+
+/// A public symbol as seen by `api-diff`
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq, Eq)]
+pub struct ApiSymbol {
+    /// Symbol kind (e.g. `Function`)
+    pub kind: String,
+    /// Sorted declaration headers; several entries mean overloads or
+    /// same-named symbols in different files
+    pub signatures: Vec<String>,
+}
+
+/// Map each symbol's FQN to its kind and signatures
+///
+/// Keys are `display_fqn`, falling back to `fqn` and then `name`, so a symbol
+/// moved between files keeps its key. Intended for databases indexed with
+/// `--api-only`; otherwise every symbol is included and signatures are empty.
+pub fn api_symbol_map(
+    graph: &mut CodeGraph,
+) -> Result<std::collections::BTreeMap<String, ApiSymbol>> {
+    let entity_ids = graph.calls.backend.entity_ids()?;
+    let snapshot = SnapshotId::current();
+    let mut map: std::collections::BTreeMap<String, ApiSymbol> = std::collections::BTreeMap::new();
+
+    for entity_id in entity_ids {
+        let Ok(node) = graph.calls.backend.get_node(snapshot, entity_id) else {
+            continue;
+        };
+        if node.kind != "Symbol" {
+            continue;
+        }
+        let Ok(symbol) = serde_json::from_value::<SymbolNode>(node.data) else {
+            continue;
+        };
+        let key = symbol
+            .display_fqn
+            .filter(|value| !value.is_empty())
+            .or(symbol.fqn.filter(|value| !value.is_empty()))
+            .or(symbol.name.filter(|value| !value.is_empty()));
+        if let Some(key) = key {
+            let entry = map.entry(key).or_insert_with(|| ApiSymbol {
+                kind: symbol.kind.clone(),
+                signatures: Vec::new(),
+            });
+            entry.signatures.extend(symbol.signature);
+        }
+    }
+
+    for symbol in map.values_mut() {
+        symbol.signatures.sort();
+    }
+
+    Ok(map)
+}
+
+/// A symbol definition as seen by `diff`
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+pub struct SymbolLocation {
+    /// File defining the symbol
+    pub file: String,
+    /// Byte offset where the symbol starts
+    pub byte_start: usize,
+    /// Byte offset where the symbol ends
+    pub byte_end: usize,
+    /// Line where the symbol starts (1-indexed)
+    pub start_line: usize,
+    /// Line where the symbol ends (1-indexed)
+    pub end_line: usize,
+    /// Symbol kind (e.g. `Function`)
+    pub kind: String,
+    /// Stable symbol ID, if persisted
+    pub symbol_id: Option<String>,
+}
+
+/// Map each symbol's FQN to its definitions
+///
+/// Keys are `display_fqn`, falling back to `fqn` and then `name`, as in
+/// [`api_symbol_map`]. Values are sorted by file and byte offset.
+pub fn symbol_location_map(
+    graph: &mut CodeGraph,
+) -> Result<std::collections::BTreeMap<String, Vec<SymbolLocation>>> {
+    let entity_ids = graph.calls.backend.entity_ids()?;
+    let snapshot = SnapshotId::current();
+    let mut map: std::collections::BTreeMap<String, Vec<SymbolLocation>> =
+        std::collections::BTreeMap::new();
+
+    for entity_id in entity_ids {
+        let Ok(node) = graph.calls.backend.get_node(snapshot, entity_id) else {
+            continue;
+        };
+        if node.kind != "Symbol" {
+            continue;
+        }
+        let file = node.file_path.unwrap_or_default();
+        let Ok(symbol) = serde_json::from_value::<SymbolNode>(node.data) else {
+            continue;
+        };
+        let key = symbol
+            .display_fqn
+            .filter(|value| !value.is_empty())
+            .or(symbol.fqn.filter(|value| !value.is_empty()))
+            .or(symbol.name.filter(|value| !value.is_empty()));
+        if let Some(key) = key {
+            map.entry(key).or_default().push(SymbolLocation {
+                file,
+                byte_start: symbol.byte_start,
+                byte_end: symbol.byte_end,
+                start_line: symbol.start_line,
+                end_line: symbol.end_line,
+                kind: symbol.kind,
+                symbol_id: symbol.symbol_id,
+            });
+        }
+    }
+
+    for locations in map.values_mut() {
+        locations.sort();
+    }
+
+    Ok(map)
+}
+
+/// A caller-to-callee edge as seen by `diff`
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+pub struct CallEdge {
+    /// File containing the call
+    pub file: String,
+    /// Name of the calling symbol
+    pub caller: String,
+    /// Name of the called symbol
+    pub callee: String,
+}
+
+/// Collect the distinct call edges in the graph
+///
+/// Several call sites between the same caller and callee in one file
+/// collapse into a single edge.
+pub fn call_edge_set(graph: &mut CodeGraph) -> Result<std::collections::BTreeSet<CallEdge>> {
+    let entity_ids = graph.calls.backend.entity_ids()?;
+    let snapshot = SnapshotId::current();
+    let mut edges = std::collections::BTreeSet::new();
+
+    for entity_id in entity_ids {
+        let Ok(node) = graph.calls.backend.get_node(snapshot, entity_id) else {
+            continue;
+        };
+        if node.kind != "Call" {
+            continue;
+        }
+        let Ok(call) = serde_json::from_value::<CallNode>(node.data) else {
+            continue;
+        };
+        edges.insert(CallEdge {
+            file: call.file,
+            caller: call.caller,
+            callee: call.callee,
+        });
+    }
+
+    Ok(edges)
+}
+
 #[cfg(test)]
 mod tests {
     use crate::graph::query::{
-        collision_groups, find_by_symbol_id, get_ambiguous_candidates,
-        symbol_nodes_in_file_with_ids, symbols_in_file, CollisionField,
+        collision_groups, collision_groups_canonicalized, find_by_symbol_id,
+        get_ambiguous_candidates, resolve_suggestions, symbol_nodes_in_file_with_ids,
+        symbols_defined_in, symbols_in_file, symbols_near_line, CollisionCandidate, CollisionField,
+        SymbolSort, SymbolSortKey,
     };
     use crate::graph::schema::SymbolNode;
+    use crate::ingest::test_code::TestFilter;
+    use crate::ingest::SymbolKind;
     use sqlitegraph::{GraphBackend, SnapshotId};
 
     #[test]
@@ -1032,6 +2170,36 @@ fn bar() {
         assert!(count > 0, "Expected at least 1 reference, got {}", count);
     }
 
+    #[test]
+    fn test_symbol_sort_orders_by_each_key() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("test.db");
+        let mut graph = crate::CodeGraph::open(&db_path).unwrap();
+
+        // Line order: zeta, alpha, mid; kind order: enum, fn, struct
+        let source = "struct zeta {}\nfn alpha() {}\nenum mid { A }\n";
+        graph.index_file("sort.rs", source.as_bytes()).unwrap();
+        let symbols = symbols_in_file(&mut graph, "sort.rs").unwrap();
+        assert_eq!(symbols.len(), 3);
+
+        let names = |key, reverse| {
+            let mut sorted = symbols.clone();
+            SymbolSort { key, reverse }.apply(&mut sorted);
+            sorted
+                .into_iter()
+                .map(|s| s.name.unwrap_or_default())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(names(SymbolSortKey::Name, false), ["alpha", "mid", "zeta"]);
+        assert_eq!(names(SymbolSortKey::Line, false), ["zeta", "alpha", "mid"]);
+        assert_eq!(names(SymbolSortKey::Kind, false), ["mid", "alpha", "zeta"]);
+
+        assert_eq!(names(SymbolSortKey::Name, true), ["zeta", "mid", "alpha"]);
+        assert_eq!(names(SymbolSortKey::Line, true), ["mid", "alpha", "zeta"]);
+        assert_eq!(names(SymbolSortKey::Kind, true), ["zeta", "alpha", "mid"]);
+    }
+
     #[test]
     fn test_find_by_symbol_id_returns_none_for_nonexistent() {
         // Use persistent temp directory for V3 backend
@@ -1100,6 +2268,42 @@ fn test_function() -> i32 {
         }
     }
 
+    #[test]
+    fn test_warm_symbol_cache_follows_delete_file() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let mut graph = crate::CodeGraph::open(temp_dir.path().join("test.db")).unwrap();
+        graph
+            .index_file("lib.rs", b"fn cached() {}\nfn other() {}\n")
+            .unwrap();
+
+        let (_, _, symbol_id) = symbol_nodes_in_file_with_ids(&mut graph, "lib.rs")
+            .unwrap()
+            .into_iter()
+            .find(|(_, fact, _)| fact.name.as_deref() == Some("cached"))
+            .unwrap();
+        let symbol_id = symbol_id.unwrap();
+
+        assert_eq!(graph.warm_symbol_cache().unwrap(), 2);
+        let index = graph.symbol_index().unwrap().unwrap();
+        assert_eq!(index.named("cached").count(), 1);
+        assert!(find_by_symbol_id(&mut graph, &symbol_id).unwrap().is_some());
+        assert_eq!(
+            symbol_nodes_in_file_with_ids(&mut graph, "lib.rs")
+                .unwrap()
+                .len(),
+            2
+        );
+
+        graph.delete_file("lib.rs").unwrap();
+        assert!(find_by_symbol_id(&mut graph, &symbol_id).unwrap().is_none());
+        assert!(symbol_nodes_in_file_with_ids(&mut graph, "lib.rs")
+            .unwrap()
+            .is_empty());
+        let index = graph.symbol_index().unwrap().unwrap();
+        assert!(index.is_empty());
+        assert_eq!(index.named("cached").count(), 0);
+    }
+
     #[test]
     fn test_get_ambiguous_candidates_empty_for_no_match() {
         // Use persistent temp directory for V3 backend
@@ -1288,4 +2492,171 @@ fn test_function() -> i32 {
             .iter()
             .all(|c| c.file_path.is_some()));
     }
+
+    #[test]
+    fn test_collision_groups_canonicalized_merges_generic_instantiations() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let mut graph = crate::CodeGraph::open(temp_dir.path().join("test.db")).unwrap();
+        let source = "struct Wrapper<T>(T);\n\
+                      struct Other;\n\
+                      impl Wrapper<u32> { fn get(&self) {} }\n\
+                      impl Wrapper<String> { fn get(&self) {} }\n\
+                      impl Other { fn get(&self) {} }\n";
+        let path = temp_dir.path().join("lib.rs");
+        graph
+            .index_file(&path.to_string_lossy(), source.as_bytes())
+            .unwrap();
+
+        // Each instantiation has its own FQN
+        let groups = collision_groups(&mut graph, CollisionField::Fqn, 10).unwrap();
+        assert!(groups.iter().all(|group| !group.value.contains("get")));
+
+        let groups = collision_groups_canonicalized(&mut graph, CollisionField::Fqn, 10).unwrap();
+        let get_groups: Vec<_> = groups
+            .iter()
+            .filter(|group| group.value.ends_with("get"))
+            .collect();
+        assert_eq!(get_groups.len(), 1, "{:?}", groups);
+        assert_eq!(get_groups[0].value, "Wrapper::get");
+        assert_eq!(get_groups[0].count, 2);
+        assert!(get_groups[0].candidates.iter().all(|c| c
+            .display_fqn
+            .as_deref()
+            .unwrap()
+            .contains("Wrapper<")));
+    }
+
+    #[test]
+    fn test_symbols_defined_in_respects_segment_boundaries() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let mut graph = crate::CodeGraph::open(temp_dir.path().join("test.db")).unwrap();
+        let source = "mod net {\n\
+                      pub struct Socket;\n\
+                      impl Socket { pub fn open() {} }\n\
+                      pub fn connect() {}\n\
+                      }\n\
+                      mod network { pub fn ping() {} }\n\
+                      fn top() {}\n";
+        let path = temp_dir.path().join("lib.rs");
+        let path = path.to_string_lossy().to_string();
+        graph.index_file(&path, source.as_bytes()).unwrap();
+
+        let names = |graph: &mut crate::CodeGraph, prefix: &str, kind: Option<SymbolKind>| {
+            symbols_defined_in(graph, prefix, kind.as_ref(), None, TestFilter::All)
+                .unwrap()
+                .into_iter()
+                .map(|(_, fact, _)| fact.name.unwrap_or_default())
+                .collect::<Vec<_>>()
+        };
+
+        // `net` does not reach into `network`; results follow FQN order
+        let under_net = names(&mut graph, "crate::net", None);
+        assert_eq!(under_net, ["Socket", "open", "connect", "net"]);
+        assert_eq!(names(&mut graph, "net::", None), under_net);
+        assert_eq!(
+            names(&mut graph, "crate::network", None),
+            ["network", "ping"]
+        );
+        assert_eq!(
+            names(&mut graph, "crate::net::Socket", None),
+            ["Socket", "open"]
+        );
+        assert!(names(&mut graph, "crate::ne", None).is_empty());
+
+        assert_eq!(
+            names(&mut graph, "crate::net", Some(SymbolKind::Function)),
+            ["open", "connect"]
+        );
+        assert_eq!(names(&mut graph, "crate", None).len(), 7);
+    }
+
+    #[test]
+    fn test_symbols_near_line_ranks_by_start_distance() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let mut graph = crate::CodeGraph::open(temp_dir.path().join("test.db")).unwrap();
+        let source = "fn first() {}\n\
+                      \n\
+                      fn second() {}\n\
+                      \n\
+                      \n\
+                      \n\
+                      fn third() {}\n";
+        let path = temp_dir.path().join("lib.rs");
+        let path = path.to_string_lossy().to_string();
+        graph.index_file(&path, source.as_bytes()).unwrap();
+
+        let near = |graph: &mut crate::CodeGraph, line: usize| {
+            symbols_near_line(graph, &path, line)
+                .unwrap()
+                .into_iter()
+                .map(|(distance, _, fact, _)| (fact.name.unwrap_or_default(), distance))
+                .collect::<Vec<_>>()
+        };
+
+        // Line 6 is one line from third (7), three from second (3)
+        assert_eq!(
+            near(&mut graph, 6),
+            [
+                ("third".to_string(), 1),
+                ("second".to_string(), 3),
+                ("first".to_string(), 5)
+            ]
+        );
+        // Line 5 is two lines from both second and third: the earlier one wins
+        let tie = near(&mut graph, 5);
+        assert_eq!(tie[0], ("second".to_string(), 2));
+        assert_eq!(tie[1], ("third".to_string(), 2));
+        // A line inside no symbol still ranks; the exact start comes first
+        assert_eq!(near(&mut graph, 1)[0], ("first".to_string(), 0));
+        assert!(symbols_near_line(&mut graph, "missing.rs", 1)
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_resolve_suggestions_uses_path_then_scope() {
+        let candidate = |file: &str, fqn: &str| CollisionCandidate {
+            entity_id: 0,
+            symbol_id: None,
+            canonical_fqn: None,
+            display_fqn: Some(fqn.to_string()),
+            name: Some("run".to_string()),
+            file_path: Some(file.to_string()),
+        };
+        let suggestions = resolve_suggestions(&[
+            candidate("/repo/src/net/client.rs", "app::run"),
+            candidate("/repo/src/db/client.rs", "app::run"),
+            candidate("/repo/src/lib.rs", "app::a::run"),
+            candidate("/repo/src/lib.rs", "app::b::run"),
+            candidate("/repo/src/dup.rs", "app::run"),
+            candidate("/repo/src/dup.rs", "app::run"),
+        ]);
+        let qualified: Vec<Option<&str>> = suggestions
+            .iter()
+            .map(|s| s.qualified_name.as_deref())
+            .collect();
+        assert_eq!(
+            qualified,
+            vec![
+                Some("net/client.rs::run"),
+                Some("db/client.rs::run"),
+                Some("lib.rs::a::run"),
+                Some("lib.rs::b::run"),
+                None,
+                None,
+            ]
+        );
+        assert_eq!(suggestions[2].module_prefix.as_deref(), Some("a"));
+        assert_eq!(suggestions[4].path_prefix.as_deref(), Some("dup.rs"));
+
+        // Members of one file need no path, and Python scopes keep their dots
+        let python = |fqn: &str| CollisionCandidate {
+            file_path: Some("pkg/mod.py".to_string()),
+            ..candidate("", fqn)
+        };
+        let suggestions =
+            resolve_suggestions(&[python("pkg.mod.Reader.run"), python("pkg.mod.Writer.run")]);
+        assert_eq!(suggestions[0].path_prefix, None);
+        assert_eq!(suggestions[1].qualified_name.as_deref(), Some("Writer.run"));
+    }
 }
@@ -13,15 +13,18 @@ use std::sync::Arc;
 use crate::graph::schema::ReferenceNode;
 use crate::ingest::c::CParser;
 use crate::ingest::cpp::CppParser;
+use crate::ingest::csharp::CSharpParser;
 use crate::ingest::cuda::CudaParser;
 use crate::ingest::go::GoParser;
 use crate::ingest::java::JavaParser;
 use crate::ingest::javascript::JavaScriptParser;
 use crate::ingest::pool;
 use crate::ingest::python::PythonParser;
+use crate::ingest::scala::ScalaParser;
+use crate::ingest::swift::SwiftParser;
 use crate::ingest::typescript::TypeScriptParser;
 use crate::ingest::{detect::Language, detect_language, Parser};
-use crate::references::ReferenceFact;
+use crate::references::{enclosing_symbol, EnclosingSymbol, ReferenceFact};
 
 /// Reference operations for CodeGraph
 pub struct ReferenceOps {
@@ -31,6 +34,9 @@ pub struct ReferenceOps {
     /// Whether to use batch transactions for indexing.
     /// When false, falls back to individual per-insert auto-commit mode.
     pub batch_mode: bool,
+    /// Drop references outside this file's indexed symbols (`--api-only`),
+    /// so private function bodies contribute nothing to the graph.
+    pub api_only: bool,
 }
 
 impl ReferenceOps {
@@ -155,6 +161,11 @@ impl ReferenceOps {
                                     start_col: symbol_node.start_col,
                                     end_line: symbol_node.end_line,
                                     end_col: symbol_node.end_col,
+                                    signature: symbol_node.signature.clone(),
+                                    is_test: symbol_node.is_test,
+                                    visibility: symbol_node.visibility.clone(),
+                                    deprecated: symbol_node.deprecated,
+                                    deprecation_message: symbol_node.deprecation_message.clone(),
                                 });
                             }
                         }
@@ -202,7 +213,20 @@ impl ReferenceOps {
                 let mut parser = CudaParser::new()?;
                 parser.extract_references(path_buf.clone(), source, &all_symbol_facts)
             }
-            None => Vec::new(),
+            Some(Language::Swift) => {
+                let mut parser = SwiftParser::new()?;
+                parser.extract_references(path_buf.clone(), source, &all_symbol_facts)
+            }
+            Some(Language::Scala) => {
+                let mut parser = ScalaParser::new()?;
+                parser.extract_references(path_buf.clone(), source, &all_symbol_facts)
+            }
+            Some(Language::CSharp) => {
+                let mut parser = CSharpParser::new()?;
+                parser.extract_references(path_buf.clone(), source, &all_symbol_facts)
+            }
+            // Registered custom parsers only extract symbols
+            Some(Language::Custom(_)) | None => Vec::new(),
         };
 
         // Batch insert reference nodes and REFERENCES edges for performance.
@@ -234,6 +258,9 @@ impl ReferenceOps {
     /// * `source` - File contents as bytes
     /// * `symbol_id_to_id` - Map of SymbolId to entity_id (primary lookup)
     /// * `fqn_to_id` - Map of FQN to entity_id (fallback for backward compatibility)
+    /// * `all_symbol_facts` - All symbols in the database, for reference matching
+    /// * `file_symbols` - Symbols defined in `path` with their stable IDs, used to
+    ///   attach each reference's enclosing function
     ///
     /// # Returns
     /// Number of references indexed
@@ -244,6 +271,7 @@ impl ReferenceOps {
         symbol_id_to_id: &HashMap<String, i64>,
         fqn_to_id: &HashMap<String, i64>,
         all_symbol_facts: &[crate::ingest::SymbolFact],
+        file_symbols: &[(crate::ingest::SymbolFact, Option<String>)],
     ) -> Result<usize> {
         let path_buf = PathBuf::from(path);
         let language = detect_language(&path_buf);
@@ -343,9 +371,64 @@ impl ReferenceOps {
                     result
                 })?
             }
-            None => Vec::new(),
+            Some(Language::Swift) => {
+                pool::with_parser_opt(Language::Swift, |opt_parser| {
+                    let parser = opt_parser
+                        .take()
+                        .expect("Parser pool corruption: parser was None"); // M-UNWRAP: thread-local parser pool guarantees initialized parser
+                    let mut wrapper = SwiftParser::from_parser(parser);
+                    let result =
+                        wrapper.extract_references(path_buf.clone(), source, all_symbol_facts);
+                    *opt_parser = Some(wrapper.parser);
+                    result
+                })?
+            }
+            Some(Language::Scala) => {
+                pool::with_parser_opt(Language::Scala, |opt_parser| {
+                    let parser = opt_parser
+                        .take()
+                        .expect("Parser pool corruption: parser was None"); // M-UNWRAP: thread-local parser pool guarantees initialized parser
+                    let mut wrapper = ScalaParser::from_parser(parser);
+                    let result =
+                        wrapper.extract_references(path_buf.clone(), source, all_symbol_facts);
+                    *opt_parser = Some(wrapper.parser);
+                    result
+                })?
+            }
+            Some(Language::CSharp) => {
+                pool::with_parser_opt(Language::CSharp, |opt_parser| {
+                    let parser = opt_parser
+                        .take()
+                        .expect("Parser pool corruption: parser was None"); // M-UNWRAP: thread-local parser pool guarantees initialized parser
+                    let mut wrapper = CSharpParser::from_parser(parser);
+                    let result =
+                        wrapper.extract_references(path_buf.clone(), source, all_symbol_facts);
+                    *opt_parser = Some(wrapper.parser);
+                    result
+                })?
+            }
+            // Registered custom parsers only extract symbols
+            Some(Language::Custom(_)) | None => Vec::new(),
         };
 
+        // Attach the nearest enclosing function or method to each reference;
+        // in API-only mode, drop references from skipped private code
+        let references: Vec<ReferenceFact> = references
+            .into_iter()
+            .map(|mut reference| {
+                reference.in_symbol =
+                    enclosing_symbol(file_symbols, reference.byte_start, reference.byte_end);
+                reference
+            })
+            .filter(|reference| {
+                !self.api_only
+                    || file_symbols.iter().any(|(fact, _)| {
+                        fact.byte_start <= reference.byte_start
+                            && reference.byte_end <= fact.byte_end
+                    })
+            })
+            .collect();
+
         // Batch insert reference nodes and REFERENCES edges for performance.
         // Filter references that have a resolved target symbol first.
         let mut resolved_refs: Vec<&ReferenceFact> = Vec::new();
@@ -411,6 +494,12 @@ impl ReferenceOps {
             start_col: reference.start_col as u64,
             end_line: reference.end_line as u64,
             end_col: reference.end_col as u64,
+            in_symbol: reference.in_symbol.as_ref().map(|s| s.name.clone()),
+            in_symbol_id: reference
+                .in_symbol
+                .as_ref()
+                .and_then(|s| s.symbol_id.clone()),
+            kind: reference.kind,
         };
 
         let node_spec = NodeSpec {
@@ -483,6 +572,12 @@ impl ReferenceOps {
                     start_col: reference.start_col as u64,
                     end_line: reference.end_line as u64,
                     end_col: reference.end_col as u64,
+                    in_symbol: reference.in_symbol.as_ref().map(|s| s.name.clone()),
+                    in_symbol_id: reference
+                        .in_symbol
+                        .as_ref()
+                        .and_then(|s| s.symbol_id.clone()),
+                    kind: reference.kind,
                 };
                 sqlitegraph::GraphEntityCreate {
                     kind: "Reference".to_string(),
@@ -540,7 +635,7 @@ impl ReferenceOps {
     }
 
     /// Convert a reference node to ReferenceFact
-    fn reference_fact_from_node(&self, node_id: i64) -> Result<Option<ReferenceFact>> {
+    pub(crate) fn reference_fact_from_node(&self, node_id: i64) -> Result<Option<ReferenceFact>> {
         let snapshot = SnapshotId::current();
         let node = self.backend.get_node(snapshot, node_id)?;
 
@@ -563,6 +658,11 @@ impl ReferenceOps {
             start_col: reference_node.start_col as usize,
             end_line: reference_node.end_line as usize,
             end_col: reference_node.end_col as usize,
+            in_symbol: reference_node.in_symbol.map(|name| EnclosingSymbol {
+                name,
+                symbol_id: reference_node.in_symbol_id,
+            }),
+            kind: reference_node.kind,
         }))
     }
 }
@@ -628,6 +728,7 @@ fn bar() {
                 &symbol_id_to_id,
                 &fqn_to_id,
                 &all_symbol_facts,
+                &[],
             )
             .unwrap();
 
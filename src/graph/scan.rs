@@ -15,7 +15,7 @@ use std::sync::atomic::{AtomicUsize, Ordering};
 use super::{CodeGraph, ScanProgress};
 use crate::diagnostics::{DiagnosticStage, WatchDiagnostic};
 use crate::graph::filter::{skip_diagnostic, FileFilter};
-use crate::validation::{validate_path_within_root, PathValidationError};
+use crate::validation::{validate_path_within_root, PathValidationError, VisitedDirs};
 
 /// Scan result containing count and diagnostics.
 #[derive(Debug, Default)]
@@ -24,6 +24,9 @@ pub struct ScanResult {
     pub indexed: usize,
     /// Diagnostics for skipped files and errors
     pub diagnostics: Vec<WatchDiagnostic>,
+    /// Symbol count `index_file` reported for each indexed file, keyed by
+    /// its stored path (checked by `watch --validate`)
+    pub symbol_counts: Vec<(String, usize)>,
 }
 
 /// Async scan with parallel file reading using tokio
@@ -41,11 +44,7 @@ pub async fn scan_directory_async(
     let mut diagnostics = Vec::new();
 
     // Use walkdir to collect all files
-    for entry in walkdir::WalkDir::new(dir_path)
-        .follow_links(false)
-        .into_iter()
-        .filter_map(std::result::Result::ok)
-    {
+    for entry in walk_scan_entries(dir_path, graph.follow_symlinks) {
         let path = entry.path();
         if path.is_dir() {
             continue;
@@ -90,21 +89,28 @@ pub async fn scan_directory_async(
     let file_contents = read_files_async(candidate_files.clone()).await?;
 
     // Index files sequentially (graph is not Send)
-    for (path, content) in file_contents {
+    let mut symbol_counts = Vec::new();
+    for (processed, (path, content)) in file_contents.into_iter().enumerate() {
         let path_str = path.to_string_lossy().to_string();
 
         if let Ok(count) = crate::graph::ops::index_file(graph, &path_str, &content) {
             indexed.fetch_add(count, Ordering::SeqCst);
+            symbol_counts.push((path_str.clone(), count));
         }
 
+        // Progress counts files, like the synchronous scan
         if let Some(progress_fn) = progress {
-            progress_fn(indexed.load(Ordering::SeqCst), total, &path_str);
+            progress_fn(processed + 1, total, &path_str);
         }
     }
 
+    // Link qualified calls into files indexed after their callers
+    super::calls::relink_qualified_calls(graph)?;
+
     Ok(ScanResult {
         indexed: indexed.load(Ordering::SeqCst),
         diagnostics,
+        symbol_counts,
     })
 }
 
@@ -151,57 +157,49 @@ impl FileReadResult {
     }
 }
 
-/// Scan a directory and index all supported source files found
-///
-/// # Behavior
-/// 1. Walk directory recursively
-/// 2. Validate each path is within project root (prevents traversal attacks)
-/// 3. Apply filtering rules (internal ignores, gitignore, include/exclude)
-/// 4. Read files in parallel using rayon
-/// 5. Index each supported file (symbols + references) sequentially
-/// 6. Report progress via callback
-/// 7. Collect diagnostics for skipped files and errors
+/// Walk `dir_path` in file-name order, yielding every readable entry
 ///
-/// # Security
-/// - Path validation prevents directory traversal attacks
-/// - Symlinks are NOT followed during walk (follow_links=false in WalkDir)
-/// - Paths escaping root are rejected and logged as diagnostics
-///
-/// # Performance
-/// - File I/O is parallelized across available CPU cores
-/// - Graph writes are sequential to avoid contention on CodeGraph
-/// - Progress reporting is thread-safe using atomic counter
-///
-/// # Arguments
-/// * `graph` - CodeGraph instance (mutable for indexing)
-/// * `dir_path` - Directory to scan (treated as root boundary)
-/// * `filter` - File filter for determining which files to process
-/// * `progress` - Optional callback for progress reporting (current, total)
+/// Symlinks are only followed with `follow_symlinks`. Followed directories
+/// that resolve outside `dir_path`, or that were already entered (symlink
+/// cycles, or two links to the same directory), are pruned so the walk
+/// terminates and never leaves the root.
+fn walk_scan_entries(
+    dir_path: &Path,
+    follow_symlinks: bool,
+) -> impl Iterator<Item = walkdir::DirEntry> + '_ {
+    let mut visited_dirs = VisitedDirs::default();
+    walkdir::WalkDir::new(dir_path)
+        .follow_links(follow_symlinks)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_entry(move |entry| {
+            !follow_symlinks
+                || !entry.file_type().is_dir()
+                || (validate_path_within_root(entry.path(), dir_path).is_ok()
+                    && visited_dirs.insert(entry.path()))
+        })
+        .filter_map(std::result::Result::ok)
+}
+
+/// Walk `dir_path` and collect the files a scan with `filter` would index
 ///
-/// # Returns
-/// ScanResult with indexed count and diagnostics
+/// Returns the candidate files in sorted order along with diagnostics for
+/// the files that were skipped or could not be validated. Nothing is read
+/// beyond what the filter needs, and no graph is involved, so this also
+/// serves as a preview of a scan (`watch --dry-run`).
 ///
-/// # Guarantees
-/// - Filtering is deterministic and pure
-/// - Files are indexed in sorted order for determinism
-/// - Errors are collected as diagnostics; processing continues
-/// - No files outside dir_path are accessed
-pub fn scan_directory_with_filter(
-    graph: &mut CodeGraph,
+/// With `follow_symlinks`, symlinked directories are entered as long as they
+/// resolve inside `dir_path`; see [`walk_scan_entries`].
+pub fn collect_scan_candidates(
     dir_path: &Path,
     filter: &FileFilter,
-    progress: Option<&ScanProgress>,
-) -> Result<ScanResult> {
-    // Collect all candidate files first (for sorted order)
+    follow_symlinks: bool,
+) -> (Vec<PathBuf>, Vec<WatchDiagnostic>) {
     let mut candidate_files: Vec<PathBuf> = Vec::new();
     let mut diagnostics = Vec::new();
 
     // Use walkdir to collect all files
-    for entry in walkdir::WalkDir::new(dir_path)
-        .follow_links(false)
-        .into_iter()
-        .filter_map(std::result::Result::ok)
-    {
+    for entry in walk_scan_entries(dir_path, follow_symlinks) {
         let path = entry.path();
 
         // Skip directories and symlinks to directories
@@ -267,6 +265,56 @@ pub fn scan_directory_with_filter(
     // Sort for deterministic ordering
     candidate_files.sort();
 
+    (candidate_files, diagnostics)
+}
+
+/// Scan a directory and index all supported source files found
+///
+/// # Behavior
+/// 1. Walk directory recursively
+/// 2. Validate each path is within project root (prevents traversal attacks)
+/// 3. Apply filtering rules (internal ignores, gitignore, include/exclude)
+/// 4. Read files in parallel using rayon
+/// 5. Index each supported file (symbols + references) sequentially
+/// 6. Report progress via callback
+/// 7. Collect diagnostics for skipped files and errors
+///
+/// # Security
+/// - Path validation prevents directory traversal attacks
+/// - Symlinks are NOT followed during walk unless `CodeGraph::set_follow_symlinks`
+///   is enabled; then only in-root targets are entered, each directory once
+/// - Paths escaping root are rejected and logged as diagnostics
+///
+/// # Performance
+/// - File I/O is parallelized across available CPU cores, or across
+///   `CodeGraph::set_scan_jobs` threads when set
+/// - Graph writes are sequential to avoid contention on CodeGraph
+/// - Progress reporting is thread-safe using atomic counter
+///
+/// # Arguments
+/// * `graph` - CodeGraph instance (mutable for indexing)
+/// * `dir_path` - Directory to scan (treated as root boundary)
+/// * `filter` - File filter for determining which files to process
+/// * `progress` - Optional callback for progress reporting (current, total)
+///
+/// # Returns
+/// ScanResult with indexed count and diagnostics
+///
+/// # Guarantees
+/// - Filtering is deterministic and pure
+/// - Files are indexed in sorted order for determinism
+/// - Errors are collected as diagnostics; processing continues
+/// - No files outside dir_path are accessed
+pub fn scan_directory_with_filter(
+    graph: &mut CodeGraph,
+    dir_path: &Path,
+    filter: &FileFilter,
+    progress: Option<&ScanProgress>,
+) -> Result<ScanResult> {
+    // Collect all candidate files first (for sorted order)
+    let (candidate_files, mut diagnostics) =
+        collect_scan_candidates(dir_path, filter, graph.follow_symlinks);
+
     let total = candidate_files.len();
 
     // Phase 1: Parallel file reading (I/O bound)
@@ -286,20 +334,26 @@ pub fn scan_directory_with_filter(
 
     // Process files in parallel using rayon
     // We use rayon's parallel iterator for I/O bound file reading
-    let read_results: Vec<FileReadResult> = file_metadata
-        .par_iter() // rayon parallel iterator
-        .map(|(path, path_str, rel_path)| {
-            // Read file contents
-            match std::fs::read(path) {
-                Ok(source) => FileReadResult::ok(path_str.clone(), rel_path.clone(), source),
-                Err(e) => FileReadResult::error(rel_path.clone(), e.to_string()),
-            }
-        })
-        .collect();
+    let read_file = |(path, path_str, rel_path): &(PathBuf, String, String)| {
+        // Read file contents
+        match std::fs::read(path) {
+            Ok(source) => FileReadResult::ok(path_str.clone(), rel_path.clone(), source),
+            Err(e) => FileReadResult::error(rel_path.clone(), e.to_string()),
+        }
+    };
+    let read_results: Vec<FileReadResult> = match graph.scan_jobs {
+        // A dedicated pool bounds the readers to `--jobs` threads
+        Some(jobs) => rayon::ThreadPoolBuilder::new()
+            .num_threads(jobs)
+            .build()?
+            .install(|| file_metadata.par_iter().map(read_file).collect()),
+        None => file_metadata.par_iter().map(read_file).collect(),
+    };
 
     // Phase 2: Sequential graph writes (to avoid Mutex contention)
     // Apply all file data to the graph sequentially
     let indexed_count = AtomicUsize::new(0);
+    let mut symbol_counts = Vec::new();
 
     for result in read_results {
         // Update progress counter
@@ -335,7 +389,24 @@ pub fn scan_directory_with_filter(
 
         // Index symbols with error handling
         match graph.index_file(path_str, source) {
-            Ok(_) => {}
+            Ok(count) => {
+                symbol_counts.push((path_str.clone(), count));
+                // Only known when parse trees are cached (watch mode)
+                if let Some((line, column)) = graph.cached_syntax_error(path_str) {
+                    diagnostics.push(WatchDiagnostic::error(
+                        rel_path.clone(),
+                        DiagnosticStage::Parse,
+                        format!("syntax error at {}:{}", line, column),
+                    ));
+                }
+            }
+            Err(e) if e.is::<crate::ingest::pool::ParseTimeout>() => {
+                diagnostics.push(WatchDiagnostic::skipped(
+                    rel_path.clone(),
+                    crate::diagnostics::SkipReason::ParseTimeout,
+                ));
+                continue;
+            }
             Err(e) => {
                 diagnostics.push(WatchDiagnostic::error(
                     rel_path.clone(),
@@ -359,12 +430,16 @@ pub fn scan_directory_with_filter(
         }
     }
 
+    // Link qualified calls into files indexed after their callers
+    super::calls::relink_qualified_calls(graph)?;
+
     // Sort diagnostics for deterministic output
     diagnostics.sort();
 
     Ok(ScanResult {
         indexed: total,
         diagnostics,
+        symbol_counts,
     })
 }
 
@@ -382,6 +457,86 @@ pub fn scan_directory(
     Ok(result.indexed)
 }
 
+/// Result of reindexing an explicit list of paths.
+#[derive(Debug, Default)]
+pub struct ReindexPathsResult {
+    /// Files whose contents changed and were reindexed
+    pub reindexed: Vec<String>,
+    /// Files whose stored hash already matched
+    pub unchanged: Vec<String>,
+    /// Files gone from disk whose facts were deleted
+    pub deleted: Vec<String>,
+    /// Paths that were not touched, with the reason
+    pub skipped: Vec<(String, String)>,
+}
+
+/// Reindex only the given paths (relative to `root`, or absolute).
+///
+/// Existing files are validated to stay within `root` and reconciled by
+/// content hash. Listed paths that no longer exist are deleted from the
+/// graph via `delete_file`. Unsupported files, directories and paths
+/// escaping the root are reported in `skipped` and left untouched.
+pub fn reindex_paths(
+    graph: &mut CodeGraph,
+    root: &Path,
+    paths: &[PathBuf],
+) -> Result<ReindexPathsResult> {
+    let root = std::fs::canonicalize(root)?;
+    let mut paths: Vec<&PathBuf> = paths.iter().collect();
+    paths.sort();
+    paths.dedup();
+
+    let mut result = ReindexPathsResult::default();
+    for rel in paths {
+        let display = rel.to_string_lossy().to_string();
+        let candidate = root.join(rel);
+
+        match validate_path_within_root(&candidate, &root) {
+            Ok(canonical) => {
+                if canonical.is_dir() {
+                    result.skipped.push((display, "directory".to_string()));
+                    continue;
+                }
+                if crate::ingest::detect::detect_language(&canonical).is_none() {
+                    result
+                        .skipped
+                        .push((display, "unsupported language".to_string()));
+                    continue;
+                }
+                let key = canonical.to_string_lossy().to_string();
+                match graph.reconcile_file_path(&canonical, &key)? {
+                    super::ReconcileOutcome::Unchanged => result.unchanged.push(display),
+                    super::ReconcileOutcome::Reindexed { .. } => result.reindexed.push(display),
+                    super::ReconcileOutcome::Deleted => result.deleted.push(display),
+                }
+            }
+            Err(PathValidationError::CannotCanonicalize(_)) => {
+                // The file is gone, so only a lexical check is possible
+                let escapes = candidate
+                    .components()
+                    .any(|c| matches!(c, std::path::Component::ParentDir))
+                    || !candidate.starts_with(&root);
+                if escapes {
+                    result.skipped.push((display, "outside root".to_string()));
+                    continue;
+                }
+                let key = candidate.to_string_lossy().to_string();
+                if graph.get_file_node(&key)?.is_none() {
+                    result.skipped.push((display, "not indexed".to_string()));
+                    continue;
+                }
+                graph.delete_file(&key)?;
+                result.deleted.push(display);
+            }
+            Err(e) => result.skipped.push((display, e.to_string())),
+        }
+    }
+
+    super::calls::relink_qualified_calls(graph)?;
+
+    Ok(result)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -6,11 +6,17 @@ use serde::{Deserialize, Serialize};
 
 use anyhow::Result;
 
+use crate::graph::content_hash::HashAlgorithm;
+use crate::references::ReferenceKind;
+
 /// File node payload stored in sqlitegraph
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FileNode {
     pub path: String,
     pub hash: String,
+    /// Algorithm `hash` was computed with (omitted for the default, xxh3)
+    #[serde(default, skip_serializing_if = "HashAlgorithm::is_default")]
+    pub hash_algo: HashAlgorithm,
     /// Unix timestamp (seconds since epoch) when this file was last indexed
     pub last_indexed_at: i64,
     /// Unix timestamp (seconds since epoch) of filesystem mtime when indexed
@@ -57,6 +63,24 @@ pub struct SymbolNode {
     pub start_col: usize,
     pub end_line: usize,
     pub end_col: usize,
+    /// Declaration header, recorded by API-surface indexing (`--api-only`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<String>,
+    /// Test code: under a `tests/` directory or, in Rust, inside a
+    /// `#[cfg(test)]` item or `mod tests`
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub is_test: bool,
+    /// Declared visibility (`pub`, `pub(crate)`, `private`, ...), see
+    /// `ingest::visibility`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub visibility: Option<String>,
+    /// Marked deprecated (`#[deprecated]`, `@Deprecated`, JSDoc
+    /// `@deprecated`), see `ingest::deprecation`
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub deprecated: bool,
+    /// Message attached to the deprecation, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deprecation_message: Option<String>,
 }
 
 /// Reference node payload stored in sqlitegraph
@@ -69,6 +93,15 @@ pub struct ReferenceNode {
     pub start_col: u64,
     pub end_line: u64,
     pub end_col: u64,
+    /// Name of the nearest enclosing function or method
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub in_symbol: Option<String>,
+    /// Stable symbol ID of the nearest enclosing function or method
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub in_symbol_id: Option<String>,
+    /// Usage or import; absent on usage references
+    #[serde(default, skip_serializing_if = "ReferenceKind::is_usage")]
+    pub kind: ReferenceKind,
 }
 
 /// Cross-file reference entry for efficient lookup
@@ -118,6 +151,61 @@ pub struct EdgeEndpoints {
     pub to_id: i64,
 }
 
+/// A Symbol node with no `DEFINES` edge from an existing File node
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OrphanSymbol {
+    pub entity_id: i64,
+    pub name: String,
+    /// Path recorded on the symbol row, if any
+    pub file_path: Option<String>,
+}
+
+/// An edge whose source or target entity does not exist
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DanglingEdge {
+    pub edge_id: i64,
+    pub edge_type: String,
+    pub from_id: i64,
+    pub to_id: i64,
+    pub missing_from: bool,
+    pub missing_to: bool,
+}
+
+/// One side of a [`SpanOverlap`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SpanSymbol {
+    pub entity_id: i64,
+    pub name: String,
+    pub kind: String,
+    pub byte_start: u64,
+    pub byte_end: u64,
+}
+
+/// Two symbols in one file whose byte ranges overlap without nesting
+///
+/// `first` starts before `second` and ends inside it, or both cover the
+/// same span under the same name and kind (a duplicate).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SpanOverlap {
+    pub file_path: String,
+    pub first: SpanSymbol,
+    pub second: SpanSymbol,
+    /// Both symbols cover the same span
+    pub duplicate: bool,
+}
+
+/// Entity counts read in one transaction, so they describe a single state of the database
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CountSnapshot {
+    pub files: usize,
+    pub symbols: usize,
+    pub references: usize,
+    pub calls: usize,
+    /// Symbol counts keyed by kind, present when requested
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub symbols_by_kind: Option<std::collections::BTreeMap<String, usize>>,
+}
+
 /// Control Flow Basic Block node payload stored in database
 ///
 /// Represents a single basic block in a function's control flow graph.
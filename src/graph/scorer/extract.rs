@@ -6,6 +6,7 @@
 //! - Temporal features from symbol_versions
 //! - Derived features (conditional_density, etc.)
 
+use crate::log_warn;
 use anyhow::Result;
 use parking_lot::Mutex;
 use rusqlite::{params, OptionalExtension};
@@ -197,10 +198,7 @@ impl FeatureExtractor {
             match self.extract_for_symbol(symbol_id) {
                 Ok(features) => results.push(features),
                 Err(e) => {
-                    eprintln!(
-                        "Warning: Failed to extract features for symbol {}: {}",
-                        symbol_id, e
-                    );
+                    log_warn!("Failed to extract features for symbol {}: {}", symbol_id, e);
                 }
             }
         }
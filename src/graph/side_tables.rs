@@ -85,8 +85,8 @@ pub trait SideTables: Send + Sync {
 
     // ===== Code Chunk Methods =====
 
-    /// Store a code chunk
-    fn store_chunk(&self, chunk: &CodeChunk) -> Result<i64>;
+    /// Store a code chunk, zstd-compressing its content when `compress` is set
+    fn store_chunk(&self, chunk: &CodeChunk, compress: bool) -> Result<i64>;
 
     /// Get a code chunk by ID
     fn get_chunk(&self, chunk_id: i64) -> Result<Option<CodeChunk>>;
@@ -269,6 +269,7 @@ pub use crate::graph::telemetry::{TelemetryEvent, TelemetryEventType};
 #[cfg(feature = "sqlite-backend")]
 pub mod sqlite_impl {
     use super::*;
+    use crate::generation::{chunk_content_from_row, CODE_CHUNK_SELECT};
     use parking_lot::{Mutex, MutexGuard};
     use rusqlite::{params, Connection, OptionalExtension};
     use std::sync::Arc;
@@ -755,33 +756,16 @@ pub mod sqlite_impl {
 
         // ===== Code Chunk Methods =====
 
-        fn store_chunk(&self, chunk: &CodeChunk) -> Result<i64> {
+        fn store_chunk(&self, chunk: &CodeChunk, compress: bool) -> Result<i64> {
             let conn = self.lock_conn();
-            conn.execute(
-                "INSERT OR REPLACE INTO code_chunks
-                    (file_path, byte_start, byte_end, content, content_hash, symbol_name, symbol_kind, created_at)
-                    VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
-                params![
-                    chunk.file_path,
-                    chunk.byte_start as i64,
-                    chunk.byte_end as i64,
-                    chunk.content,
-                    chunk.content_hash,
-                    chunk.symbol_name,
-                    chunk.symbol_kind,
-                    chunk.created_at,
-                ],
-            )?;
-            Ok(conn.last_insert_rowid())
+            Ok(crate::generation::insert_chunk(&conn, chunk, compress)?)
         }
 
         fn get_chunk(&self, chunk_id: i64) -> Result<Option<CodeChunk>> {
             let conn = self.lock_conn();
             let result = conn
                 .query_row(
-                    "SELECT id, file_path, byte_start, byte_end, content, content_hash,
-                            symbol_name, symbol_kind, created_at
-                     FROM code_chunks WHERE id = ?1",
+                    &format!("{} WHERE c.id = ?1", CODE_CHUNK_SELECT),
                     params![chunk_id],
                     |row| {
                         Ok(CodeChunk {
@@ -789,7 +773,7 @@ pub mod sqlite_impl {
                             file_path: row.get(1)?,
                             byte_start: row.get::<_, i64>(2)? as usize,
                             byte_end: row.get::<_, i64>(3)? as usize,
-                            content: row.get(4)?,
+                            content: chunk_content_from_row(row, 4)?,
                             content_hash: row.get(5)?,
                             symbol_name: row.get(6)?,
                             symbol_kind: row.get(7)?,
@@ -810,9 +794,10 @@ pub mod sqlite_impl {
             let conn = self.lock_conn();
             let result = conn
                 .query_row(
-                    "SELECT id, file_path, byte_start, byte_end, content, content_hash,
-                            symbol_name, symbol_kind, created_at
-                     FROM code_chunks WHERE file_path = ?1 AND byte_start = ?2 AND byte_end = ?3",
+                    &format!(
+                        "{} WHERE c.file_path = ?1 AND c.byte_start = ?2 AND c.byte_end = ?3",
+                        CODE_CHUNK_SELECT
+                    ),
                     params![file_path, byte_start as i64, byte_end as i64],
                     |row| {
                         Ok(CodeChunk {
@@ -820,7 +805,7 @@ pub mod sqlite_impl {
                             file_path: row.get(1)?,
                             byte_start: row.get::<_, i64>(2)? as usize,
                             byte_end: row.get::<_, i64>(3)? as usize,
-                            content: row.get(4)?,
+                            content: chunk_content_from_row(row, 4)?,
                             content_hash: row.get(5)?,
                             symbol_name: row.get(6)?,
                             symbol_kind: row.get(7)?,
@@ -834,11 +819,10 @@ pub mod sqlite_impl {
 
         fn get_chunks_for_file(&self, file_path: &str) -> Result<Vec<CodeChunk>> {
             let conn = self.lock_conn();
-            let mut stmt = conn.prepare(
-                "SELECT id, file_path, byte_start, byte_end, content, content_hash,
-                        symbol_name, symbol_kind, created_at
-                 FROM code_chunks WHERE file_path = ?1 ORDER BY byte_start",
-            )?;
+            let mut stmt = conn.prepare(&format!(
+                "{} WHERE c.file_path = ?1 ORDER BY c.byte_start",
+                CODE_CHUNK_SELECT
+            ))?;
             let chunks = stmt
                 .query_map(params![file_path], |row| {
                     Ok(CodeChunk {
@@ -846,7 +830,7 @@ pub mod sqlite_impl {
                         file_path: row.get(1)?,
                         byte_start: row.get::<_, i64>(2)? as usize,
                         byte_end: row.get::<_, i64>(3)? as usize,
-                        content: row.get(4)?,
+                        content: chunk_content_from_row(row, 4)?,
                         content_hash: row.get(5)?,
                         symbol_name: row.get(6)?,
                         symbol_kind: row.get(7)?,
@@ -882,13 +866,10 @@ pub mod sqlite_impl {
             symbol_name: &str,
         ) -> Result<Vec<CodeChunk>> {
             let conn = self.lock_conn();
-            let mut stmt = conn.prepare(
-                "SELECT id, file_path, byte_start, byte_end, content, content_hash,
-                        symbol_name, symbol_kind, created_at
-                 FROM code_chunks 
-                 WHERE file_path = ?1 AND symbol_name = ?2
-                 ORDER BY byte_start",
-            )?;
+            let mut stmt = conn.prepare(&format!(
+                "{} WHERE c.file_path = ?1 AND c.symbol_name = ?2 ORDER BY c.byte_start",
+                CODE_CHUNK_SELECT
+            ))?;
             let chunks = stmt
                 .query_map(params![file_path, symbol_name], |row| {
                     Ok(CodeChunk {
@@ -896,7 +877,7 @@ pub mod sqlite_impl {
                         file_path: row.get(1)?,
                         byte_start: row.get::<_, i64>(2)? as usize,
                         byte_end: row.get::<_, i64>(3)? as usize,
-                        content: row.get(4)?,
+                        content: chunk_content_from_row(row, 4)?,
                         content_hash: row.get(5)?,
                         symbol_name: row.get(6)?,
                         symbol_kind: row.get(7)?,
@@ -909,11 +890,10 @@ pub mod sqlite_impl {
 
         fn get_all_chunks(&self) -> Result<Vec<CodeChunk>> {
             let conn = self.lock_conn();
-            let mut stmt = conn.prepare(
-                "SELECT id, file_path, byte_start, byte_end, content, content_hash,
-                        symbol_name, symbol_kind, created_at
-                 FROM code_chunks ORDER BY file_path, byte_start",
-            )?;
+            let mut stmt = conn.prepare(&format!(
+                "{} ORDER BY c.file_path, c.byte_start",
+                CODE_CHUNK_SELECT
+            ))?;
             let chunks = stmt
                 .query_map([], |row| {
                     Ok(CodeChunk {
@@ -921,7 +901,7 @@ pub mod sqlite_impl {
                         file_path: row.get(1)?,
                         byte_start: row.get::<_, i64>(2)? as usize,
                         byte_end: row.get::<_, i64>(3)? as usize,
-                        content: row.get(4)?,
+                        content: chunk_content_from_row(row, 4)?,
                         content_hash: row.get(5)?,
                         symbol_name: row.get(6)?,
                         symbol_kind: row.get(7)?,
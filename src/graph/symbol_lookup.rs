@@ -133,6 +133,11 @@ impl SymbolEntry {
             start_col: self.start_col,
             end_line: self.end_line,
             end_col: self.end_col,
+            signature: None,
+            is_test: false,
+            visibility: None,
+            deprecated: false,
+            deprecation_message: None,
         }
     }
 }
@@ -336,6 +341,66 @@ impl SymbolLookup {
             .unwrap_or(&[])
     }
 
+    /// Resolve a type, trait or interface by simple name
+    ///
+    /// Only type-like symbols are candidates. A definition in `current_file`
+    /// wins (the lowest entity ID when there are several); otherwise the name
+    /// resolves only when exactly one type-like symbol carries it.
+    pub fn resolve_type_name(&self, name: &str, current_file: &str) -> Option<i64> {
+        let mut candidates: Vec<&SymbolEntry> = self
+            .get_ids_by_name(name)
+            .iter()
+            .filter_map(|id| self.id_to_fqn.get(id))
+            .filter_map(|key| self.fqn_index.get(key))
+            .filter(|entry| {
+                matches!(
+                    entry.kind,
+                    SymbolKind::Class
+                        | SymbolKind::Interface
+                        | SymbolKind::Enum
+                        | SymbolKind::Union
+                        | SymbolKind::TypeAlias
+                )
+            })
+            .collect();
+        candidates.sort_by_key(|entry| entry.entity_id);
+        candidates.dedup_by_key(|entry| entry.entity_id);
+
+        if let Some(local) = candidates.iter().find(|e| e.file_path == current_file) {
+            return Some(local.entity_id);
+        }
+        match candidates.as_slice() {
+            [only] => Some(only.entity_id),
+            _ => None,
+        }
+    }
+
+    /// Resolve `name` to the function defined in the first of `modules` that has one
+    ///
+    /// Module paths are compared against each candidate's file, as in
+    /// `ModulePathCache::file_path_to_module_path`; the lowest entity ID wins
+    /// within a module.
+    pub fn resolve_function_in_modules(&self, name: &str, modules: &[String]) -> Option<i64> {
+        let mut candidates: Vec<&SymbolEntry> = self
+            .get_ids_by_name(name)
+            .iter()
+            .filter_map(|id| self.id_to_fqn.get(id))
+            .filter_map(|key| self.fqn_index.get(key))
+            .filter(|entry| entry.kind == SymbolKind::Function)
+            .collect();
+        candidates.sort_by_key(|entry| entry.entity_id);
+        modules.iter().find_map(|module| {
+            candidates
+                .iter()
+                .find(|entry| {
+                    crate::graph::schema::ModulePathCache::file_path_to_module_path(
+                        &entry.file_path,
+                    ) == *module
+                })
+                .map(|entry| entry.entity_id)
+        })
+    }
+
     /// Build FQN -> entity_id map with current file preference
     ///
     /// This replicates the logic from `src/graph/calls.rs:index_calls`:
@@ -470,6 +535,11 @@ impl SymbolLookup {
                         start_col: symbol_node.start_col,
                         end_line: symbol_node.end_line,
                         end_col: symbol_node.end_col,
+                        signature: symbol_node.signature.clone(),
+                        is_test: symbol_node.is_test,
+                        visibility: symbol_node.visibility.clone(),
+                        deprecated: symbol_node.deprecated,
+                        deprecation_message: symbol_node.deprecation_message.clone(),
                     };
 
                     // Extract stable symbol_id if present, use insert_with_symbol_id
@@ -513,6 +583,11 @@ mod tests {
             start_col: 0,
             end_line: 1,
             end_col: 10,
+            signature: None,
+            is_test: false,
+            visibility: None,
+            deprecated: false,
+            deprecation_message: None,
         }
     }
 
@@ -4,26 +4,33 @@
 //!
 //! # Symbol ID Generation
 //!
-//! Symbol IDs are stable identifiers derived from a symbol's defining characteristics:
+//! Symbol IDs are stable identifiers derived from a symbol's defining characteristics
+//! (see `stable_symbol_id_for_fact`):
 //! - **Language**: The programming language (e.g., "rust", "python", "javascript")
-//! - **Fully-Qualified Name (FQN)**: The complete hierarchical name of the symbol
-//! - **Span ID**: The stable identifier of the defining span in the source file
+//! - **Identity**: `repo_relative_path:kind_normalized:semantic_name`, where the
+//!   semantic name is `canonical_fqn` (falling back to `fqn`, then `name`) with
+//!   any absolute file path rewritten to the repo-relative path
+//! - **Disambiguator**: empty for named symbols; for anonymous symbols, a BLAKE3
+//!   hash of the symbol body (or a span ID if the body cannot be read)
 //!
-//! The symbol ID format is: `SHA256(language:fqn:span_id)[0..16]` (16 hex characters)
+//! The symbol ID format is: `SHA256(language:identity:disambiguator)[0..8]`
+//! (16 hex characters)
 //!
 //! ## Stability Guarantees
 //!
 //! Symbol IDs are **stable** when:
 //! - The same symbol is re-indexed after content changes elsewhere in the file
-//! - The file path remains the same (span_id depends on file path)
+//! - The symbol moves within its file (named symbols do not hash the span)
+//! - The file path relative to the repo root remains the same
 //! - The language detection is consistent
-//! - The fully-qualified name doesn't change
 //!
 //! Symbol IDs **change** when:
 //! - The symbol is renamed (FQN changes)
-//! - The symbol is moved to a different location (span_id changes)
-//! - The file is renamed or moved (span_id depends on file path)
-//! - The symbol's defining signature changes (affects FQN in future versions)
+//! - The symbol changes kind (e.g. function to method)
+//! - The file is renamed or moved
+//! - An anonymous symbol's body changes
+//!
+//! `magellan verify-ids` checks these guarantees against a live database.
 
 use anyhow::Result;
 use blake3::Hasher;
@@ -292,6 +299,11 @@ impl SymbolOps {
             start_col: fact.start_col,
             end_line: fact.end_line,
             end_col: fact.end_col,
+            signature: fact.signature.clone(),
+            is_test: fact.is_test,
+            visibility: fact.visibility.clone(),
+            deprecated: fact.deprecated,
+            deprecation_message: fact.deprecation_message.clone(),
         };
 
         let name = fact.name.clone().unwrap_or_else(|| {
@@ -370,6 +382,11 @@ impl SymbolOps {
                 start_col: fact.start_col,
                 end_line: fact.end_line,
                 end_col: fact.end_col,
+                signature: fact.signature.clone(),
+                is_test: fact.is_test,
+                visibility: fact.visibility.clone(),
+                deprecated: fact.deprecated,
+                deprecation_message: fact.deprecation_message.clone(),
             };
 
             let name = fact
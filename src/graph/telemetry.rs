@@ -4,6 +4,7 @@
 //! associated with execution IDs. Provides both historical querying (SQLite)
 //! and real-time access (in-memory ring buffer).
 
+use crate::log_warn;
 use anyhow::Result;
 use parking_lot::Mutex;
 use rusqlite::{params, OptionalExtension};
@@ -81,6 +82,8 @@ pub struct TelemetryOps {
     ring_buffer: Arc<parking_lot::Mutex<VecDeque<TelemetryEvent>>>,
     /// Maximum number of events to keep in the ring buffer
     ring_capacity: usize,
+    /// Keep events in the ring buffer only (set for `CodeGraph::open_readonly`)
+    read_only: bool,
 }
 
 impl TelemetryOps {
@@ -92,6 +95,7 @@ impl TelemetryOps {
                 DEFAULT_RING_BUFFER_CAPACITY,
             ))),
             ring_capacity: DEFAULT_RING_BUFFER_CAPACITY,
+            read_only: false,
         }
     }
 
@@ -105,13 +109,28 @@ impl TelemetryOps {
                 DEFAULT_RING_BUFFER_CAPACITY,
             ))),
             ring_capacity: DEFAULT_RING_BUFFER_CAPACITY,
+            read_only: false,
         };
         if let Err(e) = ops.ensure_schema() {
-            eprintln!("Warning: Failed to ensure TelemetryOps schema: {}", e);
+            log_warn!("Failed to ensure TelemetryOps schema: {}", e);
         }
         ops
     }
 
+    /// Create a TelemetryOps over a read-only connection
+    ///
+    /// Events go to the ring buffer only; nothing is written to the database.
+    pub fn read_only(conn: Arc<parking_lot::Mutex<rusqlite::Connection>>) -> Self {
+        Self {
+            backend: TelemetryBackend::Shared(conn),
+            ring_buffer: Arc::new(parking_lot::Mutex::new(VecDeque::with_capacity(
+                DEFAULT_RING_BUFFER_CAPACITY,
+            ))),
+            ring_capacity: DEFAULT_RING_BUFFER_CAPACITY,
+            read_only: true,
+        }
+    }
+
     /// Create an in-memory TelemetryOps for testing/stub usage
     pub fn in_memory() -> Self {
         let temp_dir = std::env::temp_dir();
@@ -131,10 +150,11 @@ impl TelemetryOps {
                 DEFAULT_RING_BUFFER_CAPACITY,
             ))),
             ring_capacity: DEFAULT_RING_BUFFER_CAPACITY,
+            read_only: false,
         };
 
         if let Err(e) = ops.ensure_schema() {
-            eprintln!("Warning: Failed to ensure TelemetryOps schema: {}", e);
+            log_warn!("Failed to ensure TelemetryOps schema: {}", e);
         }
 
         ops
@@ -192,6 +212,9 @@ impl TelemetryOps {
     }
 
     pub fn ensure_schema(&self) -> Result<()> {
+        if self.read_only {
+            return Ok(());
+        }
         match &self.backend {
             TelemetryBackend::Sqlite(_) => {
                 let conn = self.connect()?;
@@ -271,6 +294,7 @@ impl TelemetryOps {
         metadata: Option<&serde_json::Value>,
     ) -> Result<i64> {
         let row_id = match &self.backend {
+            _ if self.read_only => 0,
             TelemetryBackend::Sqlite(_) => {
                 let conn = self.connect()?;
                 Self::insert_event_sqlite(
@@ -339,6 +363,17 @@ impl TelemetryOps {
 
         // Find the matching phase_start to compute duration
         let start_ns = match &self.backend {
+            _ if self.read_only => self
+                .ring_buffer
+                .lock()
+                .iter()
+                .rev()
+                .find(|event| {
+                    event.execution_id == execution_id
+                        && event.event_type == TelemetryEventType::PhaseStart
+                        && event.event_name == phase
+                })
+                .map(|event| event.timestamp_ns),
             TelemetryBackend::Sqlite(_) => {
                 let conn = self.connect()?;
                 conn.query_row(
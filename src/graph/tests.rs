@@ -217,4 +217,28 @@ mod pragma_tests {
             );
         }
     }
+
+    #[test]
+    fn test_open_with_options_applies_to_side_connection() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("test.db");
+        let options = crate::SqliteOptions {
+            synchronous: crate::Synchronous::Full,
+            busy_timeout_ms: 1234,
+            ..crate::SqliteOptions::default()
+        };
+
+        let graph = crate::CodeGraph::open_with_options(&db_path, &options).unwrap();
+
+        // busy_timeout and synchronous are per-connection settings
+        let conn = graph.side_connection().lock();
+        let busy_timeout: i64 = conn
+            .query_row("PRAGMA busy_timeout", [], |row| row.get(0))
+            .unwrap();
+        let synchronous: i64 = conn
+            .query_row("PRAGMA synchronous", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(busy_timeout, 1234);
+        assert_eq!(synchronous, 2, "synchronous should be FULL (2)");
+    }
 }
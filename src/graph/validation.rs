@@ -348,6 +348,37 @@ fn check_orphan_calls(graph: &mut CodeGraph) -> Result<Vec<ValidationError>> {
     Ok(errors)
 }
 
+/// Check that the symbol count returned by `index_file` is queryable
+///
+/// Catches silent insertion drops: every symbol `index_file` reports as
+/// inserted must come back from `symbols_in_file` for the same path.
+/// Returns `None` when the counts agree.
+pub fn check_symbol_count_invariant(
+    graph: &mut CodeGraph,
+    path: &str,
+    indexed_count: usize,
+) -> Result<Option<ValidationError>> {
+    let queryable_count = graph.symbols_in_file(path)?.len();
+    if queryable_count == indexed_count {
+        return Ok(None);
+    }
+
+    Ok(Some(
+        ValidationError::new(
+            "SYMBOL_COUNT_MISMATCH".to_string(),
+            format!(
+                "{}: index_file reported {} symbols but {} are queryable",
+                path, indexed_count, queryable_count
+            ),
+        )
+        .with_details(serde_json::json!({
+            "file": path,
+            "indexed_count": indexed_count,
+            "queryable_count": queryable_count,
+        })),
+    ))
+}
+
 /// Pre-run validation for database and input paths
 ///
 /// Validates environment before indexing begins.
@@ -560,6 +591,9 @@ mod tests {
             start_col: 10,
             end_line: 1,
             end_col: 20,
+            in_symbol: None,
+            in_symbol_id: None,
+            kind: Default::default(),
         };
 
         let node_spec = NodeSpec {
@@ -725,6 +759,9 @@ mod tests {
             start_col: 10,
             end_line: 1,
             end_col: 20,
+            in_symbol: None,
+            in_symbol_id: None,
+            kind: Default::default(),
         };
 
         let node_spec = NodeSpec {
@@ -911,4 +948,42 @@ mod tests {
         let error3 = ValidationError::new("DB_PARENT_MISSING".to_string(), "test".to_string());
         assert_eq!(error3.code, "DB_PARENT_MISSING");
     }
+
+    #[test]
+    fn test_symbol_count_invariant_holds_after_index() {
+        let dir = tempfile::tempdir().unwrap();
+        let db_path = dir.path().join("test.db");
+        let mut graph = crate::CodeGraph::open(&db_path).unwrap();
+
+        let count = graph
+            .index_file("lib.rs", b"fn a() {}\nfn b() {}\n")
+            .unwrap();
+        assert!(count > 0);
+
+        let result = check_symbol_count_invariant(&mut graph, "lib.rs", count).unwrap();
+        assert!(result.is_none(), "counts should agree: {:?}", result);
+    }
+
+    #[test]
+    fn test_symbol_count_invariant_detects_dropped_symbol() {
+        let dir = tempfile::tempdir().unwrap();
+        let db_path = dir.path().join("test.db");
+        let mut graph = crate::CodeGraph::open(&db_path).unwrap();
+
+        let count = graph
+            .index_file("lib.rs", b"fn a() {}\nfn b() {}\n")
+            .unwrap();
+
+        // Simulate a silent insertion drop by removing one stored symbol
+        let nodes = graph.symbol_nodes_in_file("lib.rs").unwrap();
+        let (dropped_id, _) = nodes.first().expect("indexed symbols");
+        graph.symbols.backend.delete_entity(*dropped_id).unwrap();
+
+        let error = check_symbol_count_invariant(&mut graph, "lib.rs", count)
+            .unwrap()
+            .expect("mismatch should be reported");
+        assert_eq!(error.code, "SYMBOL_COUNT_MISMATCH");
+        assert_eq!(error.details["indexed_count"], count);
+        assert_eq!(error.details["queryable_count"], count - 1);
+    }
 }
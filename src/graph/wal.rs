@@ -18,6 +18,46 @@ pub fn checkpoint_wal(db_path: &Path) -> Result<(), rusqlite::Error> {
     checkpoint_conn(&conn)
 }
 
+/// Outcome of one `PRAGMA wal_checkpoint(TRUNCATE)`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CheckpointStats {
+    /// The checkpoint could not finish because of active readers or a writer
+    pub busy: bool,
+    /// Frames in the WAL when the checkpoint ran (-1 when not in WAL mode)
+    pub log_frames: i64,
+    /// Frames copied back into the database file (-1 when not in WAL mode)
+    pub checkpointed_frames: i64,
+}
+
+/// Run a truncating checkpoint and report its frame counts.
+///
+/// A successful TRUNCATE resets the WAL and reports zero frames, so the
+/// counts come from a PASSIVE checkpoint run just before it. Unlike
+/// [`checkpoint_conn`], a busy checkpoint is not an error; it is reported
+/// through [`CheckpointStats::busy`].
+pub fn checkpoint_truncate(conn: &Connection) -> Result<CheckpointStats, rusqlite::Error> {
+    let run = |mode: &str| {
+        conn.query_row(&format!("PRAGMA wal_checkpoint({})", mode), [], |row| {
+            Ok(CheckpointStats {
+                busy: row.get::<_, i32>(0)? != 0,
+                log_frames: row.get(1)?,
+                checkpointed_frames: row.get(2)?,
+            })
+        })
+    };
+    let passive = run("PASSIVE")?;
+    let truncate = run("TRUNCATE")?;
+    if truncate.busy || passive.log_frames < 0 {
+        return Ok(truncate);
+    }
+    // Every frame counted by the passive pass is now in the database file
+    Ok(CheckpointStats {
+        busy: false,
+        log_frames: passive.log_frames,
+        checkpointed_frames: passive.log_frames,
+    })
+}
+
 /// Checkpoint using an existing connection (avoids opening another connection).
 ///
 /// # Errors
@@ -1,5 +1,6 @@
 use anyhow::Result;
 use magellan::graph::CodeGraph;
+use magellan::log_warn;
 use magellan::output::{output_json, OutputFormat};
 use serde_json::json;
 use std::path::PathBuf;
@@ -15,7 +16,7 @@ pub fn run_hopgraph(
 
     // Warn if embeddings are stale compared to the graph index
     if let Ok(Some(warning)) = graph.check_embedding_staleness() {
-        eprintln!("{}", warning);
+        log_warn!("{}", warning);
     }
 
     let hits = graph.hopgraph_search(&query, k, hops)?;
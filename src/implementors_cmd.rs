@@ -0,0 +1,89 @@
+//! Implementors command implementation
+//!
+//! Lists the types implementing or extending a trait / interface, including
+//! traits that are not indexed (recorded by name only).
+
+use anyhow::Result;
+use magellan::output::{
+    generate_execution_id, output_json, ImplementorEntry, ImplementorsResponse, JsonResponse,
+    OutputFormat,
+};
+use magellan::CodeGraph;
+use std::path::PathBuf;
+
+/// Run the implementors command
+///
+/// # Arguments
+/// * `db_path` - Path to the sqlitegraph database
+/// * `name` - Trait or interface name (matched on its simple name)
+/// * `output_format` - Output format (Human, Json, or Pretty)
+pub fn run_implementors(db_path: PathBuf, name: String, output_format: OutputFormat) -> Result<()> {
+    let graph = CodeGraph::open(&db_path)?;
+    let exec_id = generate_execution_id();
+
+    let args = vec![
+        "implementors".to_string(),
+        "--db".to_string(),
+        db_path.to_string_lossy().to_string(),
+        "--name".to_string(),
+        name.clone(),
+    ];
+    graph.execution_log().start_execution(
+        &exec_id,
+        env!("CARGO_PKG_VERSION"),
+        &args,
+        None,
+        &db_path.to_string_lossy(),
+    )?;
+
+    let implementors = graph.implementors_of(&name)?;
+
+    match output_format {
+        OutputFormat::Json | OutputFormat::Pretty => {
+            let response = ImplementorsResponse {
+                trait_name: name,
+                implementors: implementors
+                    .into_iter()
+                    .map(|implementor| ImplementorEntry {
+                        trait_resolved: implementor.trait_symbol_id.is_some(),
+                        type_name: implementor.type_name,
+                        trait_name: implementor.trait_name,
+                        file_path: implementor.file_path,
+                        start_line: implementor.start_line,
+                        end_line: implementor.end_line,
+                    })
+                    .collect(),
+            };
+            let json_response = JsonResponse::new(response, &exec_id);
+            output_json(&json_response, output_format)?;
+        }
+        OutputFormat::Human => {
+            if implementors.is_empty() {
+                println!("No implementors of \"{}\" found", name);
+            } else {
+                println!("{} implementor(s) of \"{}\":", implementors.len(), name);
+                for implementor in &implementors {
+                    let external = if implementor.trait_symbol_id.is_none() {
+                        " (unresolved)"
+                    } else {
+                        ""
+                    };
+                    println!(
+                        "  {}  {}:{}  impl {}{}",
+                        implementor.type_name,
+                        implementor.file_path,
+                        implementor.start_line,
+                        implementor.trait_name,
+                        external
+                    );
+                }
+            }
+        }
+    }
+
+    graph
+        .execution_log()
+        .finish_execution(&exec_id, "success", None, 0, 0, 0)?;
+
+    Ok(())
+}
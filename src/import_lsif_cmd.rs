@@ -3,6 +3,7 @@
 //! Imports LSIF data from external packages for cross-repository symbol resolution.
 
 use anyhow::Result;
+use magellan::log_warn;
 use magellan::lsif;
 use magellan::output::generate_execution_id;
 use magellan::CodeGraph;
@@ -53,7 +54,7 @@ pub fn run_import_lsif(db_path: PathBuf, lsif_paths: Vec<PathBuf>) -> Result<()>
                 total_symbols += pkg.symbol_count;
             }
             Err(e) => {
-                eprintln!("  Warning: Failed to import {:?}: {}", lsif_path, e);
+                log_warn!("Failed to import {:?}: {}", lsif_path, e);
             }
         }
     }
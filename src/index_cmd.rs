@@ -1,18 +1,50 @@
-//! Index command - Single-file indexing
+//! Index command - Single-file and changed-path indexing
 //!
-//! Indexes a single source file into the database.
+//! Indexes a single source file into the database, or reindexes only the
+//! paths reported by `git diff` or listed in a file.
 
 use anyhow::{Context, Result};
-use magellan::CodeGraph;
-use std::path::PathBuf;
+use magellan::{CodeGraph, HashAlgorithm, HashNormalization, PathStyle, SqliteOptions};
+use std::io::Read;
+use std::path::{Path, PathBuf};
 
+use crate::cli::ChangedPaths;
 use crate::status_cmd::ExecutionTracker;
+use magellan::{log_info, log_verbose};
 
 /// Run the index command
 ///
-/// Usage: magellan index --db <FILE> --file <PATH> [--root <DIR>]
-pub fn run_index(db_path: PathBuf, file_path: PathBuf, root: Option<PathBuf>) -> Result<()> {
-    let mut graph = CodeGraph::open(&db_path)?;
+/// Usage: magellan index --db <FILE> --file <PATH> [--root <DIR>] [--api-only]
+///        [--relative-paths | --absolute-paths]
+///        [--normalize-eol] [--normalize-trailing-whitespace] [--hash-algo <ALGO>]
+///        [--journal-mode <MODE>] [--synchronous <LEVEL>] [--busy-timeout <MS>]
+#[allow(
+    clippy::too_many_arguments,
+    reason = "CLI command surface: each arg maps to a flag"
+)]
+pub fn run_index(
+    db_path: PathBuf,
+    file_path: PathBuf,
+    root: Option<PathBuf>,
+    api_only: bool,
+    path_style: Option<PathStyle>,
+    hash_normalization: HashNormalization,
+    hash_algo: Option<HashAlgorithm>,
+    sqlite_options: SqliteOptions,
+) -> Result<()> {
+    let mut graph = CodeGraph::open_with_options(&db_path, &sqlite_options)?;
+    graph.set_api_only(api_only);
+    if let Some(style) = path_style {
+        // Paths are stored relative to --root, or to the current directory
+        let style_root = root.clone().unwrap_or_else(|| PathBuf::from("."));
+        graph.set_path_style(style, &style_root)?;
+    }
+    if hash_normalization.is_enabled() {
+        graph.set_hash_normalization(hash_normalization)?;
+    }
+    if let Some(algorithm) = hash_algo {
+        graph.set_hash_algorithm(algorithm)?;
+    }
 
     let path_str = if let Some(ref root_path) = root {
         root_path.join(&file_path).to_string_lossy().to_string()
@@ -30,10 +62,109 @@ pub fn run_index(db_path: PathBuf, file_path: PathBuf, root: Option<PathBuf>) ->
     let source =
         std::fs::read(&path_str).with_context(|| format!("Failed to read file: {}", path_str))?;
 
-    let symbol_count = graph.index_file(&path_str, &source)?;
+    // With a path style set, relative input is taken relative to its root,
+    // so hand over the absolute location instead
+    let index_path = match graph.path_settings() {
+        Some(_) => magellan::normalize_path(std::path::Path::new(&path_str))?,
+        None => path_str.clone(),
+    };
+    let symbol_count = graph.index_file(&index_path, &source)?;
 
     println!("Indexed {}: {} symbols", path_str, symbol_count);
 
     tracker.finish(&graph)?;
     Ok(())
 }
+
+/// Run the index command over a changed-path list
+///
+/// Usage: magellan index --db <FILE> [--root <DIR>]
+///        (--changed-from <GIT_REF> | --files-from <PATH|->)
+#[allow(
+    clippy::too_many_arguments,
+    reason = "CLI command surface: each arg maps to a flag"
+)]
+pub fn run_index_changed(
+    db_path: PathBuf,
+    changed: ChangedPaths,
+    root: Option<PathBuf>,
+    api_only: bool,
+    path_style: Option<PathStyle>,
+    hash_normalization: HashNormalization,
+    hash_algo: Option<HashAlgorithm>,
+    sqlite_options: SqliteOptions,
+) -> Result<()> {
+    let root = root.unwrap_or_else(|| PathBuf::from("."));
+    let paths = match &changed {
+        ChangedPaths::GitRef(git_ref) => magellan::git_diff::changed_paths(&root, git_ref)?,
+        ChangedPaths::FilesFrom(list) => read_path_list(list)?,
+    };
+
+    let mut graph = CodeGraph::open_with_options(&db_path, &sqlite_options)?;
+    graph.set_api_only(api_only);
+    if let Some(style) = path_style {
+        graph.set_path_style(style, &root)?;
+    }
+    if hash_normalization.is_enabled() {
+        graph.set_hash_normalization(hash_normalization)?;
+    }
+    if let Some(algorithm) = hash_algo {
+        graph.set_hash_algorithm(algorithm)?;
+    }
+
+    let source_arg = match &changed {
+        ChangedPaths::GitRef(git_ref) => format!("--changed-from={}", git_ref),
+        ChangedPaths::FilesFrom(list) => format!("--files-from={}", list.display()),
+    };
+    let tracker = ExecutionTracker::new(
+        vec!["index".to_string(), source_arg],
+        Some(root.to_string_lossy().to_string()),
+        db_path.to_string_lossy().to_string(),
+    );
+    tracker.start(&graph)?;
+
+    let result = graph.reindex_paths(&root, &paths)?;
+    for path in &result.reindexed {
+        log_verbose!("Reindexed {}", path);
+    }
+    for path in &result.deleted {
+        log_verbose!("Deleted {}", path);
+    }
+    for (path, reason) in &result.skipped {
+        log_info!("Skipped {}: {}", path, reason);
+    }
+    println!(
+        "Reindexed {} files, {} unchanged, {} deleted, {} skipped",
+        result.reindexed.len(),
+        result.unchanged.len(),
+        result.deleted.len(),
+        result.skipped.len()
+    );
+
+    tracker.finish(&graph)?;
+    Ok(())
+}
+
+/// Read a newline-separated path list from a file, or stdin for `-`
+fn read_path_list(list: &Path) -> Result<Vec<PathBuf>> {
+    let contents = if list == Path::new("-") {
+        let mut buf = String::new();
+        std::io::stdin()
+            .read_to_string(&mut buf)
+            .context("Failed to read path list from stdin")?;
+        buf
+    } else {
+        std::fs::read_to_string(list)
+            .with_context(|| format!("Failed to read path list: {}", list.display()))?
+    };
+    Ok(parse_path_list(&contents))
+}
+
+fn parse_path_list(contents: &str) -> Vec<PathBuf> {
+    contents
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(PathBuf::from)
+        .collect()
+}
@@ -0,0 +1,450 @@
+//! Symbol and call-edge diff between two databases
+//!
+//! Compares two indexes (e.g. snapshots taken at two releases) and reports
+//! symbols that were added, removed, or moved, plus call edges that appeared
+//! or disappeared.
+//!
+//! Symbols are keyed by FQN (`display_fqn`, falling back to `fqn` and then
+//! `name`). Within one FQN, definitions are paired by stable symbol ID first
+//! and then in file and offset order. A pair whose span differs is a move;
+//! any surplus definition is an addition or removal.
+//!
+//! [`diff_file_contents`] compares the symbols of one file by content instead
+//! (`query --baseline`): definitions are paired by FQN and kind in offset
+//! order, and a pair is modified when the SHA-256 of its stored code chunk
+//! differs.
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::graph::query::{self, CallEdge, SymbolLocation};
+use crate::output::rich::SpanChecksums;
+use crate::CodeGraph;
+
+/// A symbol present in only one of the two databases
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SymbolChange {
+    /// Symbol FQN
+    pub fqn: String,
+    /// Where the symbol is defined
+    pub location: SymbolLocation,
+}
+
+/// A symbol whose FQN is unchanged but whose span differs
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SymbolMove {
+    /// Symbol FQN
+    pub fqn: String,
+    /// Definition in the old database
+    pub before: SymbolLocation,
+    /// Definition in the new database
+    pub after: SymbolLocation,
+}
+
+/// Report of differences from the old database to the new one
+///
+/// Every list is sorted by FQN (symbols) or by file, caller, and callee
+/// (edges), so the report is deterministic.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct IndexDiffReport {
+    /// Symbols only in the new database
+    pub added: Vec<SymbolChange>,
+    /// Symbols only in the old database
+    pub removed: Vec<SymbolChange>,
+    /// Symbols in both whose span changed
+    pub moved: Vec<SymbolMove>,
+    /// Number of symbols identical in both databases
+    pub unchanged: usize,
+    /// Call edges only in the new database
+    pub added_calls: Vec<CallEdge>,
+    /// Call edges only in the old database
+    pub removed_calls: Vec<CallEdge>,
+}
+
+impl IndexDiffReport {
+    /// Check if the two databases have the same symbols and call edges
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty()
+            && self.removed.is_empty()
+            && self.moved.is_empty()
+            && self.added_calls.is_empty()
+            && self.removed_calls.is_empty()
+    }
+}
+
+/// Diff the symbols and call edges of two databases
+///
+/// # Arguments
+/// * `old` - Graph for the baseline snapshot
+/// * `new` - Graph for the snapshot being compared
+pub fn diff_indexes(old: &mut CodeGraph, new: &mut CodeGraph) -> Result<IndexDiffReport> {
+    let mut report = diff_symbol_maps(&old.symbol_location_map()?, &new.symbol_location_map()?);
+    let (added_calls, removed_calls) =
+        diff_call_edges(&old.call_edge_set()?, &new.call_edge_set()?);
+    report.added_calls = added_calls;
+    report.removed_calls = removed_calls;
+    Ok(report)
+}
+
+/// Diff two FQN-to-locations maps
+///
+/// Location lists must be sorted, as returned by
+/// [`CodeGraph::symbol_location_map`].
+pub fn diff_symbol_maps(
+    old: &BTreeMap<String, Vec<SymbolLocation>>,
+    new: &BTreeMap<String, Vec<SymbolLocation>>,
+) -> IndexDiffReport {
+    let mut report = IndexDiffReport::default();
+    let fqns: BTreeSet<&String> = old.keys().chain(new.keys()).collect();
+
+    for fqn in fqns {
+        let before = old.get(fqn).map(Vec::as_slice).unwrap_or_default();
+        let after = new.get(fqn).map(Vec::as_slice).unwrap_or_default();
+
+        // Pair definitions that kept their stable ID, then the rest in order
+        let mut pairs: Vec<(&SymbolLocation, &SymbolLocation)> = Vec::new();
+        let mut remaining_after: Vec<&SymbolLocation> = after.iter().collect();
+        let mut remaining_before: Vec<&SymbolLocation> = Vec::new();
+        for from in before {
+            let matched = from.symbol_id.as_ref().and_then(|id| {
+                remaining_after
+                    .iter()
+                    .position(|to| to.symbol_id.as_ref() == Some(id))
+            });
+            match matched {
+                Some(index) => pairs.push((from, remaining_after.remove(index))),
+                None => remaining_before.push(from),
+            }
+        }
+        pairs.extend(
+            remaining_before
+                .iter()
+                .copied()
+                .zip(remaining_after.iter().copied()),
+        );
+
+        for (from, to) in pairs {
+            if from.file == to.file
+                && from.byte_start == to.byte_start
+                && from.byte_end == to.byte_end
+            {
+                report.unchanged += 1;
+            } else {
+                report.moved.push(SymbolMove {
+                    fqn: fqn.clone(),
+                    before: from.clone(),
+                    after: to.clone(),
+                });
+            }
+        }
+
+        let paired = remaining_before.len().min(remaining_after.len());
+        report
+            .removed
+            .extend(remaining_before[paired..].iter().map(|l| SymbolChange {
+                fqn: fqn.clone(),
+                location: (*l).clone(),
+            }));
+        report
+            .added
+            .extend(remaining_after[paired..].iter().map(|l| SymbolChange {
+                fqn: fqn.clone(),
+                location: (*l).clone(),
+            }));
+    }
+
+    report
+}
+
+/// Content status of a symbol relative to a baseline database
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "lowercase")]
+pub enum ContentStatus {
+    /// Only in the current database
+    Added,
+    /// Only in the baseline database
+    Removed,
+    /// In both, with different content
+    Modified,
+    /// In both, with the same content
+    Unchanged,
+}
+
+impl ContentStatus {
+    /// Lowercase name, as serialized
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ContentStatus::Added => "added",
+            ContentStatus::Removed => "removed",
+            ContentStatus::Modified => "modified",
+            ContentStatus::Unchanged => "unchanged",
+        }
+    }
+}
+
+/// A symbol definition with its stored source text
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SymbolContent {
+    /// Symbol FQN (`display_fqn`, falling back to `fqn` and then `name`)
+    pub fqn: String,
+    /// Where the symbol is defined
+    pub location: SymbolLocation,
+    /// Source text of the symbol's code chunk, if one is stored
+    pub body: Option<String>,
+}
+
+/// A symbol of one file compared by content with the baseline
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SymbolContentChange {
+    /// Whether the symbol was added, removed, modified, or is unchanged
+    pub status: ContentStatus,
+    /// Symbol FQN
+    pub fqn: String,
+    /// Definition in the current database (the baseline one when removed)
+    pub location: SymbolLocation,
+    /// SHA-256 of the current content (lowercase hex)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content_sha256: Option<String>,
+    /// SHA-256 of the baseline content (lowercase hex)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub baseline_sha256: Option<String>,
+    /// Current source text (the baseline text when removed)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body: Option<String>,
+}
+
+/// Symbols of one file with their stored content, in offset order
+pub fn file_symbol_contents(graph: &mut CodeGraph, file_path: &str) -> Result<Vec<SymbolContent>> {
+    let mut symbols = Vec::new();
+    for (_, fact, symbol_id) in query::symbol_nodes_in_file_with_ids(graph, file_path)? {
+        let Some(fqn) = [&fact.display_fqn, &fact.fqn, &fact.name]
+            .into_iter()
+            .flatten()
+            .find(|value| !value.is_empty())
+            .cloned()
+        else {
+            continue;
+        };
+        let file = fact.file_path.to_string_lossy().to_string();
+        let body = graph
+            .get_code_chunk_by_span(&file, fact.byte_start, fact.byte_end)?
+            .map(|chunk| chunk.content);
+        symbols.push(SymbolContent {
+            fqn,
+            location: SymbolLocation {
+                file,
+                byte_start: fact.byte_start,
+                byte_end: fact.byte_end,
+                start_line: fact.start_line,
+                end_line: fact.end_line,
+                kind: format!("{:?}", fact.kind),
+                symbol_id,
+            },
+            body,
+        });
+    }
+    symbols.sort_by(|a, b| a.location.cmp(&b.location));
+    Ok(symbols)
+}
+
+/// Compare the symbols of `file_path` in `current` against `baseline`
+pub fn diff_file_contents(
+    baseline: &mut CodeGraph,
+    current: &mut CodeGraph,
+    file_path: &str,
+) -> Result<Vec<SymbolContentChange>> {
+    Ok(diff_symbol_contents(
+        &file_symbol_contents(baseline, file_path)?,
+        &file_symbol_contents(current, file_path)?,
+    ))
+}
+
+/// Classify symbols by content, sorted by current (or baseline) position
+///
+/// Definitions sharing an FQN and kind are paired in the order given. A
+/// symbol without stored content matches only another without content.
+pub fn diff_symbol_contents(
+    baseline: &[SymbolContent],
+    current: &[SymbolContent],
+) -> Vec<SymbolContentChange> {
+    let hash = |symbol: &SymbolContent| {
+        symbol
+            .body
+            .as_ref()
+            .map(|body| SpanChecksums::sha256_hex(body.as_bytes()))
+    };
+    let key = |symbol: &SymbolContent| (symbol.fqn.clone(), symbol.location.kind.clone());
+
+    let mut unpaired: BTreeMap<(String, String), Vec<&SymbolContent>> = BTreeMap::new();
+    for symbol in baseline.iter().rev() {
+        unpaired.entry(key(symbol)).or_default().push(symbol);
+    }
+
+    let mut changes = Vec::new();
+    for symbol in current {
+        let content_sha256 = hash(symbol);
+        let (status, baseline_sha256) = match unpaired.get_mut(&key(symbol)).and_then(Vec::pop) {
+            Some(before) => {
+                let baseline_sha256 = hash(before);
+                if baseline_sha256 == content_sha256 {
+                    (ContentStatus::Unchanged, baseline_sha256)
+                } else {
+                    (ContentStatus::Modified, baseline_sha256)
+                }
+            }
+            None => (ContentStatus::Added, None),
+        };
+        changes.push(SymbolContentChange {
+            status,
+            fqn: symbol.fqn.clone(),
+            location: symbol.location.clone(),
+            content_sha256,
+            baseline_sha256,
+            body: symbol.body.clone(),
+        });
+    }
+    for symbol in unpaired.into_values().flatten() {
+        changes.push(SymbolContentChange {
+            status: ContentStatus::Removed,
+            fqn: symbol.fqn.clone(),
+            location: symbol.location.clone(),
+            content_sha256: None,
+            baseline_sha256: hash(symbol),
+            body: symbol.body.clone(),
+        });
+    }
+
+    changes.sort_by(|a, b| {
+        (a.location.start_line, a.location.byte_start, a.status).cmp(&(
+            b.location.start_line,
+            b.location.byte_start,
+            b.status,
+        ))
+    });
+    changes
+}
+
+/// Split two call-edge sets into `(added, removed)`
+pub fn diff_call_edges(
+    old: &BTreeSet<CallEdge>,
+    new: &BTreeSet<CallEdge>,
+) -> (Vec<CallEdge>, Vec<CallEdge>) {
+    (
+        new.difference(old).cloned().collect(),
+        old.difference(new).cloned().collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn location(file: &str, byte_start: usize, symbol_id: &str) -> SymbolLocation {
+        SymbolLocation {
+            file: file.to_string(),
+            byte_start,
+            byte_end: byte_start + 10,
+            start_line: byte_start / 10 + 1,
+            end_line: byte_start / 10 + 1,
+            kind: "Function".to_string(),
+            symbol_id: Some(symbol_id.to_string()),
+        }
+    }
+
+    #[test]
+    fn test_diff_symbol_maps_classifies_changes() {
+        let old = BTreeMap::from([
+            ("a".to_string(), vec![location("lib.rs", 0, "id-a")]),
+            ("b".to_string(), vec![location("lib.rs", 20, "id-b")]),
+            ("gone".to_string(), vec![location("lib.rs", 40, "id-gone")]),
+        ]);
+        let new = BTreeMap::from([
+            ("a".to_string(), vec![location("lib.rs", 0, "id-a")]),
+            ("b".to_string(), vec![location("lib.rs", 30, "id-b2")]),
+            (
+                "fresh".to_string(),
+                vec![location("lib.rs", 50, "id-fresh")],
+            ),
+        ]);
+
+        let report = diff_symbol_maps(&old, &new);
+        assert_eq!(report.unchanged, 1);
+        assert_eq!(report.moved.len(), 1);
+        assert_eq!(report.moved[0].fqn, "b");
+        assert_eq!(report.moved[0].after.byte_start, 30);
+        assert_eq!(report.removed.len(), 1);
+        assert_eq!(report.removed[0].fqn, "gone");
+        assert_eq!(report.added.len(), 1);
+        assert_eq!(report.added[0].fqn, "fresh");
+    }
+
+    #[test]
+    fn test_diff_symbol_maps_surplus_definitions() {
+        // Two same-named definitions become one: the first pairs, the second is removed
+        let old = BTreeMap::from([(
+            "dup".to_string(),
+            vec![location("a.rs", 0, "id-1"), location("b.rs", 0, "id-2")],
+        )]);
+        let new = BTreeMap::from([("dup".to_string(), vec![location("a.rs", 0, "id-1")])]);
+
+        let report = diff_symbol_maps(&old, &new);
+        assert_eq!(report.unchanged, 1);
+        assert!(report.moved.is_empty());
+        assert_eq!(report.removed.len(), 1);
+        assert_eq!(report.removed[0].location.file, "b.rs");
+        assert!(report.added.is_empty());
+    }
+
+    #[test]
+    fn test_diff_call_edges() {
+        let edge = |caller: &str, callee: &str| CallEdge {
+            file: "lib.rs".to_string(),
+            caller: caller.to_string(),
+            callee: callee.to_string(),
+        };
+        let old = BTreeSet::from([edge("main", "a"), edge("main", "b")]);
+        let new = BTreeSet::from([edge("main", "a"), edge("main", "c")]);
+
+        let (added, removed) = diff_call_edges(&old, &new);
+        assert_eq!(added, vec![edge("main", "c")]);
+        assert_eq!(removed, vec![edge("main", "b")]);
+    }
+
+    #[test]
+    fn test_diff_symbol_contents_classifies_by_hash() {
+        let symbol = |fqn: &str, byte_start: usize, body: &str| SymbolContent {
+            fqn: fqn.to_string(),
+            location: location("lib.rs", byte_start, fqn),
+            body: Some(body.to_string()),
+        };
+        let baseline = vec![
+            symbol("same", 0, "fn same() {}"),
+            symbol("edited", 20, "fn edited() {}"),
+            symbol("gone", 40, "fn gone() {}"),
+        ];
+        let current = vec![
+            symbol("same", 0, "fn same() {}"),
+            symbol("edited", 20, "fn edited() { 1 }"),
+            symbol("fresh", 50, "fn fresh() {}"),
+        ];
+
+        let changes = diff_symbol_contents(&baseline, &current);
+        let summary: Vec<(&str, ContentStatus)> = changes
+            .iter()
+            .map(|change| (change.fqn.as_str(), change.status))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("same", ContentStatus::Unchanged),
+                ("edited", ContentStatus::Modified),
+                ("gone", ContentStatus::Removed),
+                ("fresh", ContentStatus::Added),
+            ]
+        );
+        assert_ne!(changes[1].content_sha256, changes[1].baseline_sha256);
+        assert!(changes[2].content_sha256.is_none());
+        assert!(changes[3].baseline_sha256.is_none());
+    }
+}
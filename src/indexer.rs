@@ -26,8 +26,9 @@
 
 pub mod async_io;
 
+pub mod progress;
 pub mod watch;
-pub use watch::{run_watch_pipeline, WatchPipelineConfig};
+pub use watch::{dry_run_scan, run_watch_pipeline, WatchPipelineConfig};
 
 // L3 Cache-Aware Batching Configuration
 //
@@ -431,6 +432,9 @@ mod tests {
                 root_path: magellan_src.clone(),
                 debounce_ms: 50, // Short debounce for faster test
                 gitignore_aware: true,
+                follow_symlinks: false,
+                poll_interval_ms: None,
+                non_source_extensions: Vec::new(),
             },
             true, // scan_initial = true
         );
@@ -524,6 +528,9 @@ mod tests {
                     root_path: magellan_src.clone(),
                     debounce_ms: 50,
                     gitignore_aware: true,
+                    follow_symlinks: false,
+                    poll_interval_ms: None,
+                    non_source_extensions: Vec::new(),
                 },
                 true,
             );
@@ -0,0 +1,148 @@
+//! Scan progress rendering for the CLI
+//!
+//! Turns [`ScanProgress`](crate::graph::ScanProgress) callbacks into output
+//! on stderr. On a terminal this is a progress bar with an ETA; otherwise
+//! (logs, CI) it is one `indexed X/Y (Z%)` line per update. Either way
+//! redraws are throttled, and the final `total/total` update is always
+//! shown so a completed scan ends at 100%.
+
+use indicatif::{HumanCount, ProgressBar, ProgressDrawTarget, ProgressStyle};
+use std::io::IsTerminal;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Minimum time between two progress updates
+pub const DEFAULT_PROGRESS_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Renders scan progress to stderr, throttled to a few updates per second
+pub struct ScanProgressRenderer {
+    started: Instant,
+    interval: Duration,
+    /// When the last line was printed (line mode only)
+    last_render: Mutex<Option<Instant>>,
+    /// Progress bar when stderr is a terminal
+    bar: Mutex<Option<ProgressBar>>,
+    use_bar: bool,
+}
+
+impl ScanProgressRenderer {
+    /// Progress bar on a terminal, plain lines otherwise
+    pub fn stderr() -> Self {
+        let mut renderer = Self::lines(DEFAULT_PROGRESS_INTERVAL);
+        renderer.use_bar = std::io::stderr().is_terminal();
+        renderer
+    }
+
+    /// Plain `indexed X/Y (Z%)` lines, at most one per `interval`
+    pub fn lines(interval: Duration) -> Self {
+        Self {
+            started: Instant::now(),
+            interval,
+            last_render: Mutex::new(None),
+            bar: Mutex::new(None),
+            use_bar: false,
+        }
+    }
+
+    /// Handle one progress callback
+    ///
+    /// `current` is the number of files processed so far and `total` the
+    /// number of candidate files, fixed for the whole scan.
+    pub fn report(&self, current: usize, total: usize, file_path: &str) {
+        if self.use_bar {
+            self.report_bar(current, total, file_path);
+        } else if let Some(line) = self.line(current, total, Instant::now()) {
+            eprintln!("{}", line);
+        }
+    }
+
+    fn report_bar(&self, current: usize, total: usize, file_path: &str) {
+        let mut bar = self.bar.lock().unwrap_or_else(|e| e.into_inner());
+        let pb = bar.get_or_insert_with(|| {
+            let refresh_hz = (1000 / self.interval.as_millis().max(1)).clamp(1, 20) as u8;
+            let pb = ProgressBar::with_draw_target(
+                Some(total as u64),
+                ProgressDrawTarget::stderr_with_hz(refresh_hz),
+            );
+            pb.set_style(
+                ProgressStyle::default_bar()
+                    .template("{spinner:.green} [{elapsed_precise}] [{bar:40.cyan/blue}] {pos}/{len} ({percent}%) ETA: {eta}\n{msg}")
+                    .expect("invariant: hardcoded ProgressStyle template string is valid")
+                    .progress_chars("=>-"),
+            );
+            pb
+        });
+        pb.set_position(current as u64);
+        pb.set_message(format!("Scanning: {}", file_path));
+        if current >= total {
+            pb.finish_with_message(format!("Scanned {} files", HumanCount(total as u64)));
+        }
+    }
+
+    /// Line to print for this update, or `None` if throttled
+    fn line(&self, current: usize, total: usize, now: Instant) -> Option<String> {
+        let done = current >= total;
+        let mut last = self.last_render.lock().unwrap_or_else(|e| e.into_inner());
+        if !done && last.is_some_and(|at| now.duration_since(at) < self.interval) {
+            return None;
+        }
+        *last = Some(now);
+
+        let percent = (current * 100).checked_div(total).unwrap_or(100);
+        let mut line = format!("indexed {}/{} ({}%)", current, total, percent);
+        if !done && current > 0 {
+            let elapsed = now.duration_since(self.started);
+            let remaining = elapsed.mul_f64((total - current) as f64 / current as f64);
+            line.push_str(&format!(", ETA {}s", remaining.as_secs()));
+        }
+        Some(line)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lines_are_throttled_but_final_update_is_kept() {
+        let renderer = ScanProgressRenderer::lines(Duration::from_secs(1));
+        let start = renderer.started;
+
+        let first = renderer.line(1, 4, start).unwrap();
+        assert!(first.starts_with("indexed 1/4 (25%)"), "{}", first);
+        assert_eq!(
+            renderer.line(2, 4, start + Duration::from_millis(200)),
+            None
+        );
+        assert_eq!(
+            renderer.line(3, 4, start + Duration::from_millis(400)),
+            None
+        );
+        assert_eq!(
+            renderer.line(4, 4, start + Duration::from_millis(500)),
+            Some("indexed 4/4 (100%)".to_string())
+        );
+    }
+
+    #[test]
+    fn test_line_reports_eta_after_interval() {
+        let renderer = ScanProgressRenderer::lines(Duration::from_secs(1));
+        let start = renderer.started;
+
+        renderer.line(0, 10, start).unwrap();
+        // 5 of 10 files in 10s: about 10s to go
+        assert_eq!(
+            renderer.line(5, 10, start + Duration::from_secs(10)),
+            Some("indexed 5/10 (50%), ETA 10s".to_string())
+        );
+    }
+
+    #[test]
+    fn test_empty_scan_is_complete() {
+        let renderer = ScanProgressRenderer::lines(DEFAULT_PROGRESS_INTERVAL);
+        assert_eq!(
+            renderer.line(0, 0, Instant::now()),
+            Some("indexed 0/0 (100%)".to_string())
+        );
+    }
+}
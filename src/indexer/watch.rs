@@ -1,13 +1,24 @@
 //! Watch pipeline for SQLite backend.
 
+use crate::diagnostics::{DiagnosticStage, SkipReason, Verbosity, WatchDiagnostic};
+use crate::graph::filter::FileFilter;
+use crate::graph::mem_report::MemoryReport;
+use crate::graph::scan::collect_scan_candidates;
+use crate::graph::profile::{IndexPhase, PhaseProfile};
+use crate::indexer::progress::ScanProgressRenderer;
 use crate::indexer::{
     compute_l3_cache_batch_indices, read_batch_sources, DEFAULT_L3_CACHE_SIZE, TARGET_CACHE_USAGE,
 };
+use crate::ingest::pool::DEFAULT_PARSE_TIMEOUT;
 use crate::manifest::detect_include_paths_from_root;
+use crate::output::command::MAGELLAN_JSON_SCHEMA_VERSION;
 use crate::project_config::ProjectConfig;
-use crate::{CodeGraph, FileEvent, FileSystemWatcher, WatcherConfig};
+use crate::{log_info, log_verbose, log_warn};
+use crate::{
+    CodeGraph, FileEvent, FileSystemWatcher, HashAlgorithm, HashNormalization, OutputFormat,
+    PathStyle, SqliteOptions, WatcherConfig,
+};
 use anyhow::{Context, Result};
-use indicatif::{ProgressBar, ProgressStyle};
 use std::collections::BTreeSet;
 use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicBool, Ordering};
@@ -23,6 +34,12 @@ use std::time::{Duration, Instant};
 pub struct WatchPipelineConfig {
     /// Root directory to watch
     pub root_path: PathBuf,
+    /// Further roots watched into the same database (`--root` given more than once)
+    ///
+    /// Each root is scanned with its own `.magellan.toml` and `.gitignore`
+    /// and gets its own watcher; `root_path` stays the primary root that
+    /// path style and manifest metadata are recorded against.
+    pub extra_roots: Vec<PathBuf>,
     /// Path to the database
     pub db_path: PathBuf,
     /// Watcher configuration
@@ -35,6 +52,43 @@ pub struct WatchPipelineConfig {
     pub exclude_patterns: Vec<String>,
     /// Optional path to compile_commands.json for per-file compiler flags
     pub compile_commands_path: Option<PathBuf>,
+    /// Check that each reindexed file's symbol count is queryable (`--validate`)
+    pub validate: bool,
+    /// Index only the public API surface (`--api-only`)
+    pub api_only: bool,
+    /// zstd-compress newly stored chunk content (`--compress-chunks`)
+    pub compress_chunks: bool,
+    /// Skip call-graph extraction (`--no-calls`)
+    pub no_calls: bool,
+    /// Stored path style (`--relative-paths` / `--absolute-paths`); `None` keeps the database's
+    pub path_style: Option<PathStyle>,
+    /// Content normalization before hashing; recorded in the database when enabled
+    pub hash_normalization: HashNormalization,
+    /// Hash algorithm for newly indexed files (`--hash-algo`); `None` keeps the database's
+    pub hash_algorithm: Option<HashAlgorithm>,
+    /// SQLite PRAGMA settings (`--journal-mode`, `--synchronous`, `--busy-timeout`)
+    pub sqlite_options: SqliteOptions,
+    /// Config file to read `[index]` patterns from instead of the root's `.magellan.toml`
+    pub config_path: Option<PathBuf>,
+    /// Skip files larger than this many bytes (`--max-file-size`); `None` = no limit
+    pub max_file_size: Option<u64>,
+    /// Abandon a file whose parse runs longer than this (`--parse-timeout-ms`); `None` = no limit
+    pub parse_timeout: Option<Duration>,
+    /// Diagnostic format (`--output`): human lines or line-delimited JSON
+    pub output_format: OutputFormat,
+    /// Run the initial scan and return without watching (`--once`)
+    pub once: bool,
+    /// Threads reading files during the initial scan (`--jobs`); `None` = one per CPU
+    pub jobs: Option<usize>,
+    /// Checkpoint the WAL at most this often, during idle windows, instead of
+    /// after every batch (`--checkpoint-interval`); `None` = after every batch
+    pub checkpoint_interval: Option<Duration>,
+    /// Index files with a generated-file header instead of skipping them (`--include-generated`)
+    pub include_generated: bool,
+    /// Print memory accounting after the initial scan (`--mem-report`)
+    pub mem_report: bool,
+    /// Time the indexing phases and print the breakdown on shutdown (`--profile`)
+    pub profile: bool,
 }
 
 impl WatchPipelineConfig {
@@ -47,12 +101,31 @@ impl WatchPipelineConfig {
     ) -> Self {
         Self {
             root_path,
+            extra_roots: Vec::new(),
             db_path,
             watcher_config,
             scan_initial,
             include_patterns: Vec::new(),
             exclude_patterns: Vec::new(),
             compile_commands_path: None,
+            validate: false,
+            api_only: false,
+            compress_chunks: false,
+            no_calls: false,
+            path_style: None,
+            hash_normalization: HashNormalization::default(),
+            hash_algorithm: None,
+            sqlite_options: SqliteOptions::default(),
+            config_path: None,
+            max_file_size: None,
+            parse_timeout: Some(DEFAULT_PARSE_TIMEOUT),
+            output_format: OutputFormat::Human,
+            once: false,
+            jobs: None,
+            checkpoint_interval: None,
+            include_generated: false,
+            mem_report: false,
+            profile: false,
         }
     }
 }
@@ -141,18 +214,24 @@ impl PipelineSharedState {
 ///
 /// # Priority Order
 /// 1. CLI `--include` / `--exclude` (highest priority, override everything)
-/// 2. `.magellan.toml` index section (if CLI is empty)
+/// 2. `.magellan.toml` index section (if CLI is empty), or the `--config` file
 /// 3. `Cargo.toml` target dirs (auto-inferred when no config set)
 ///
 /// Auto-include is skipped when `--root` is already a subdirectory (e.g. `./src`)
-/// because relative glob patterns like `"src/"` won't match.
+/// because relative glob patterns like `"src/"` won't match, and when
+/// `auto_include` is false: the watch phase only applies configured patterns,
+/// so edits anywhere under a root without config are still picked up.
 fn merge_scan_config(
     scan_root: &std::path::Path,
     config: &WatchPipelineConfig,
+    auto_include: bool,
 ) -> Result<crate::project_config::ProjectConfig> {
-    let project_config = ProjectConfig::load(scan_root).context("Failed to load .magellan.toml")?;
+    let project_config = match config.config_path {
+        Some(ref path) => ProjectConfig::load_from_path(path)?.0,
+        None => ProjectConfig::load(scan_root).context("Failed to load .magellan.toml")?,
+    };
 
-    let auto_detected = if project_config.index.include.is_empty() {
+    let auto_detected = if auto_include && project_config.index.include.is_empty() {
         detect_include_paths_from_root(scan_root)
     } else {
         Vec::new()
@@ -187,6 +266,66 @@ fn merge_scan_config(
     })
 }
 
+/// File filter for the baseline scan: merged patterns plus the size,
+/// generated-file, non-source and gitignore settings
+fn scan_file_filter(
+    scan_root: &Path,
+    merged_config: &ProjectConfig,
+    config: &WatchPipelineConfig,
+) -> Result<FileFilter> {
+    Ok(merged_config
+        .to_file_filter(scan_root)?
+        .with_max_file_size(config.max_file_size)
+        .with_skip_generated(!config.include_generated)
+        .with_non_source_extensions(&config.watcher_config.non_source_extensions)
+        .with_gitignore(config.watcher_config.gitignore_aware))
+}
+
+/// Roots of the pipeline as given and canonicalized: `root_path` first, then
+/// `extra_roots` in the order given, with repeats dropped.
+///
+/// Nested roots are rejected: a file under both would be scanned and
+/// watched under two sets of filter rules.
+fn watch_roots(config: &WatchPipelineConfig) -> Result<Vec<(PathBuf, PathBuf)>> {
+    let mut roots: Vec<(PathBuf, PathBuf)> = Vec::new();
+    for root in std::iter::once(&config.root_path).chain(&config.extra_roots) {
+        let canonical = std::fs::canonicalize(root).unwrap_or_else(|_| root.clone());
+        if let Some((_, other)) = roots
+            .iter()
+            .find(|(_, other)| canonical.starts_with(other) || other.starts_with(&canonical))
+        {
+            if *other == canonical {
+                continue;
+            }
+            return Err(anyhow::anyhow!(
+                "Watch roots {} and {} overlap; give sibling directories",
+                other.display(),
+                canonical.display()
+            ));
+        }
+        roots.push((root.clone(), canonical));
+    }
+    Ok(roots)
+}
+
+/// List the files the baseline scan would index, without opening the graph.
+///
+/// Applies the same root canonicalization, pattern merging and filter as
+/// [`run_watch_pipeline`]. Returns the canonical scan root and the sorted
+/// absolute paths under it.
+pub fn dry_run_scan(config: &WatchPipelineConfig) -> Result<(PathBuf, Vec<PathBuf>)> {
+    let scan_root =
+        std::fs::canonicalize(&config.root_path).unwrap_or_else(|_| config.root_path.clone());
+    let merged_config = merge_scan_config(&scan_root, config, true)?;
+    let file_filter = scan_file_filter(&scan_root, &merged_config, config)?;
+    let (files, _diagnostics) = collect_scan_candidates(
+        &scan_root,
+        &file_filter,
+        config.watcher_config.follow_symlinks,
+    );
+    Ok((scan_root, files))
+}
+
 /// Wait for the watcher thread to finish with a timeout.
 ///
 /// Returns early if the thread times out without joining (avoids hang).
@@ -195,11 +334,11 @@ fn wait_for_watcher_thread(thread: std::thread::JoinHandle<()>, timeout: Duratio
     let start = Instant::now();
     while !thread.is_finished() {
         if start.elapsed() >= timeout {
-            eprintln!(
-                "Warning: Watcher thread did not finish within {:?}, forcing shutdown",
+            log_warn!(
+                "Watcher thread did not finish within {:?}, forcing shutdown",
                 timeout
             );
-            eprintln!(
+            log_info!(
                 "Note: Data may not be flushed. Use Ctrl+C (not timeout) for clean shutdown."
             );
             return;
@@ -230,6 +369,9 @@ fn wait_for_watcher_thread(thread: std::thread::JoinHandle<()>, timeout: Duratio
 /// 3. **Drain buffered edits** - Any edits during scan are flushed after baseline
 /// 4. **Main watch loop** - Process dirty paths in sorted order as batches arrive
 ///
+/// With `config.once`, no watcher is started and the pipeline returns after
+/// step 2.
+///
 /// # Concurrency Model
 /// - One watcher thread (notify callback with custom debounce) produces batches
 /// - One main/indexer thread performs scan and processes dirty paths
@@ -245,24 +387,68 @@ fn wait_for_watcher_thread(thread: std::thread::JoinHandle<()>, timeout: Duratio
 /// * `shutdown` - AtomicBool for graceful shutdown
 ///
 /// # Returns
-/// Number of paths processed during watch phase
+/// Number of paths processed during watch phase, or the number of files
+/// indexed by the scan with `config.once`
 pub fn run_watch_pipeline(config: WatchPipelineConfig, shutdown: Arc<AtomicBool>) -> Result<usize> {
-    // Canonicalize root so walkdir and FileFilter both use absolute paths.
+    // Canonicalize roots so walkdir and FileFilter both use absolute paths.
     // Without this, walkdir yields relative paths but FileFilter canonicalizes
     // its root, causing strip_prefix to fail and include globs to mismatch.
-    let scan_root =
-        std::fs::canonicalize(&config.root_path).unwrap_or_else(|_| config.root_path.clone());
+    let roots = watch_roots(&config)?;
+    let scan_roots: Vec<PathBuf> = roots
+        .iter()
+        .map(|(_, canonical)| canonical.clone())
+        .collect();
+    let scan_root = scan_roots[0].clone();
 
-    // Merge include/exclude patterns from .magellan.toml, Cargo.toml targets, and CLI overrides.
-    let merged_config = merge_scan_config(&scan_root, &config)?;
+    // Merge include/exclude patterns from each root's .magellan.toml, Cargo.toml
+    // targets, and CLI overrides.
+    let merged_configs = scan_roots
+        .iter()
+        .map(|root| merge_scan_config(root, &config, true))
+        .collect::<Result<Vec<_>>>()?;
+    let file_filters = scan_roots
+        .iter()
+        .zip(&merged_configs)
+        .map(|(root, merged_config)| scan_file_filter(root, merged_config, &config))
+        .collect::<Result<Vec<_>>>()?;
+    // The watch phase filters each root's edits with its configured patterns
+    let watch_filters = scan_roots
+        .iter()
+        .map(|root| {
+            let merged_config = merge_scan_config(root, &config, false)?;
+            scan_file_filter(root, &merged_config, &config)
+        })
+        .collect::<Result<Vec<_>>>()?;
 
     // Open graph
-    let mut graph = CodeGraph::open(&config.db_path)?;
+    let mut graph = CodeGraph::open_with_options(&config.db_path, &config.sqlite_options)?;
 
     // Load per-file compiler flags if compile_commands.json was provided
     if let Some(ref cc_path) = config.compile_commands_path {
         graph.set_compile_commands(cc_path)?;
     }
+    graph.set_api_only(config.api_only);
+    graph.set_compress_chunks(config.compress_chunks);
+    graph.set_skip_calls(config.no_calls);
+    graph.set_parse_timeout(config.parse_timeout);
+    graph.set_follow_symlinks(config.watcher_config.follow_symlinks);
+    graph.set_scan_jobs(config.jobs);
+    if let Some(style) = config.path_style {
+        if style == PathStyle::Relative && scan_roots.len() > 1 {
+            return Err(anyhow::anyhow!(
+                "--relative-paths records a single root and cannot be used with several watch roots"
+            ));
+        }
+        graph.set_path_style(style, &scan_root)?;
+    } else if scan_roots.len() == 1 {
+        graph.record_default_root(&scan_root)?;
+    }
+    if config.hash_normalization.is_enabled() {
+        graph.set_hash_normalization(config.hash_normalization)?;
+    }
+    if let Some(algorithm) = config.hash_algorithm {
+        graph.set_hash_algorithm(algorithm)?;
+    }
 
     // Parse Cargo.toml and store manifest metadata in magellan_meta
     if let Ok(manifest) = crate::manifest::CargoManifest::parse(&scan_root) {
@@ -280,65 +466,122 @@ pub fn run_watch_pipeline(config: WatchPipelineConfig, shutdown: Arc<AtomicBool>
     // This must happen BEFORE any file processing (scan + dirty path flush).
     graph.batch_mode = false;
 
+    // Reuse parse trees across modify events for incremental re-parsing
+    graph.enable_incremental_parse(crate::ingest::incremental::DEFAULT_TREE_CACHE_CAPACITY);
+
+    if config.profile {
+        graph.enable_profile();
+    }
+
     // Create shared state for buffering dirty paths
     let (shared_state, wakeup_rx) = PipelineSharedState::new();
 
     // Keep a reference for the main thread to drain dirty paths
     let main_state = shared_state.clone();
 
-    // Start watcher thread
-    let watcher_thread = {
-        let root_path = config.root_path.clone();
-        let watcher_config = config.watcher_config.clone();
-        let shared_state = Arc::new(shared_state);
-        let shutdown_watch = shutdown.clone();
-
-        thread::spawn(move || {
-            let result = watcher_loop(root_path, watcher_config, shared_state, shutdown_watch);
-            crate::ingest::pool::cleanup_parsers();
-            if let Err(e) = result {
-                eprintln!("Watcher thread error: {:?}", e);
-            }
-        })
+    // Start one watcher thread per root, all feeding the same dirty path set;
+    // `--once` never watches
+    let shared_state = Arc::new(shared_state);
+    let watcher_threads: Vec<_> = if config.once {
+        Vec::new()
+    } else {
+        roots
+            .iter()
+            .map(|(root_path, _)| {
+                let root_path = root_path.clone();
+                let watcher_config = config.watcher_config.clone();
+                let shared_state = shared_state.clone();
+                let shutdown_watch = shutdown.clone();
+
+                thread::spawn(move || {
+                    let result =
+                        watcher_loop(root_path, watcher_config, shared_state, shutdown_watch);
+                    crate::ingest::pool::cleanup_parsers();
+                    if let Err(e) = result {
+                        eprintln!("Watcher thread error: {:?}", e);
+                    }
+                })
+            })
+            .collect()
     };
 
     // Baseline scan if requested
+    let mut scanned = 0;
     if config.scan_initial {
-        use indicatif::HumanCount;
-
-        let file_filter = merged_config.to_file_filter(&scan_root)?;
-
-        graph.scan_directory_with_filter(
-            &scan_root,
-            &file_filter,
-            Some(&|current, total, file_path| {
-                // Progress bar is created on first call
-                static PB: std::sync::OnceLock<ProgressBar> = std::sync::OnceLock::new();
-                let pb = PB.get_or_init(|| {
-                    let pb = ProgressBar::new(total as u64);
-                    pb.set_style(
-                        ProgressStyle::default_bar()
-                            .template("{spinner:.green} [{elapsed_precise}] [{bar:40.cyan/blue}] {pos}/{len} ({percent}%) ETA: {eta}\n{msg}")
-                            .expect("invariant: hardcoded ProgressStyle template string is valid")
-                            .progress_chars("=>-"),
-                    );
-                    pb
-                });
-                pb.set_position(current as u64);
-                pb.set_message(format!("Scanning: {}", file_path));
-                if current >= total {
-                    pb.finish_with_message(format!("Scanned {} files", HumanCount(total as u64)));
+        let verbosity = crate::diagnostics::verbosity();
+        let mut scan_diagnostics = Vec::new();
+        for (root, file_filter) in scan_roots.iter().zip(&file_filters) {
+            // Progress is for people watching a terminal or log; JSON runs stay
+            // quiet. `--verbose` replaces the throttled bar with one line per file
+            // and `--quiet` drops it.
+            let renderer = (config.output_format == OutputFormat::Human
+                && verbosity == Verbosity::Normal)
+                .then(ScanProgressRenderer::stderr);
+            let report = move |current: usize, total: usize, file_path: &str| {
+                if let Some(renderer) = &renderer {
+                    renderer.report(current, total, file_path);
                 }
-            }),
-        )?;
+                log_verbose!("[{}/{}] {}", current, total, file_path);
+            };
+            let scan_result = graph.scan_directory_with_filter(root, file_filter, Some(&report))?;
+            scanned += scan_result.indexed;
+            scan_diagnostics.extend(scan_result.diagnostics);
+            graph.record_profile_batch();
+            if config.validate {
+                for (path, symbols) in &scan_result.symbol_counts {
+                    validate_symbol_count(&mut graph, path, *symbols);
+                }
+            }
+        }
 
         // Rebuild FTS5 index after bulk scan — direct inserts into graph_entities
         // don't fire FTS triggers, leaving the index empty.
         if let Err(e) = graph.rebuild_fts5() {
-            eprintln!("Warning: FTS5 rebuild after scan failed: {}", e);
+            log_warn!("FTS5 rebuild after scan failed: {}", e);
+        }
+
+        // Every ignored or unsupported file yields a skip, which is only
+        // useful to machine consumers; the human log shows errors and the
+        // files whose parse timed out, unless `--verbose` asks for every skip
+        if config.output_format == OutputFormat::Human && verbosity != Verbosity::Verbose {
+            scan_diagnostics.retain(|d| {
+                matches!(
+                    d,
+                    WatchDiagnostic::Error { .. }
+                        | WatchDiagnostic::Skipped {
+                            reason: SkipReason::ParseTimeout,
+                            ..
+                        }
+                )
+            });
+        }
+        emit_diagnostics(&mut scan_diagnostics, config.output_format);
+
+        if config.mem_report {
+            emit_memory_report(&MemoryReport::collect(&graph), config.output_format);
         }
     }
 
+    if config.once {
+        if let Err(e) = graph.checkpoint_wal() {
+            log_warn!("WAL checkpoint failed after scan: {}", e);
+        }
+        if let Some(profile) = graph.profile() {
+            emit_profile(profile, config.output_format);
+        }
+        crate::ingest::pool::cleanup_parsers();
+        return Ok(scanned);
+    }
+
+    let batch_options = BatchOptions {
+        roots: &scan_roots,
+        filters: &watch_filters,
+        validate: config.validate,
+        max_file_size: config.max_file_size,
+        skip_generated: !config.include_generated,
+    };
+    let mut diagnostics = Vec::new();
+
     // Drain any dirty paths that accumulated during scan
     let mut total_processed = 0;
     let paths_during_scan = main_state.drain_dirty_paths()?;
@@ -347,23 +590,35 @@ pub fn run_watch_pipeline(config: WatchPipelineConfig, shutdown: Arc<AtomicBool>
             "Flushing {} buffered path(s) from scan...",
             paths_during_scan.len()
         );
-        total_processed += process_dirty_paths(&mut graph, &paths_during_scan)?;
+        total_processed += process_dirty_paths(
+            &mut graph,
+            &paths_during_scan,
+            &batch_options,
+            &mut diagnostics,
+        )?;
+        emit_diagnostics(&mut diagnostics, config.output_format);
         if let Err(e) = graph.checkpoint_wal() {
-            eprintln!("Warning: WAL checkpoint failed after scan flush: {}", e);
+            log_warn!("WAL checkpoint failed after scan flush: {}", e);
         }
         // Verify database integrity after initial scan + flush
+        // A corrupt database is an error, so it is reported even with --quiet
         if let Err(e) = verify_db_integrity(&config.db_path) {
-            eprintln!(
-                "Warning: Database integrity check failed after scan flush: {}",
-                e
-            );
+            eprintln!("Error: integrity check after scan flush: {}", e);
         }
     }
 
     // Main watch loop
-    println!("Magellan watching: {}", config.root_path.display());
+    for (root, _) in &roots {
+        println!("Magellan watching: {}", root.display());
+    }
     println!("Database: {}", config.db_path.display());
 
+    // With --checkpoint-interval, batches only mark the WAL as pending and the
+    // checkpoint runs from the idle branch below. Both run on this thread, so a
+    // checkpoint can never interleave with a batch commit.
+    let mut last_checkpoint = Instant::now();
+    let mut checkpoint_pending = false;
+
     // Main watch loop
     while !shutdown.load(Ordering::SeqCst) {
         // Wait for wakeup tick with timeout
@@ -371,13 +626,32 @@ pub fn run_watch_pipeline(config: WatchPipelineConfig, shutdown: Arc<AtomicBool>
             Ok(()) => {
                 let dirty_paths = main_state.drain_dirty_paths()?;
                 if !dirty_paths.is_empty() {
-                    total_processed += process_dirty_paths(&mut graph, &dirty_paths)?;
-                    if let Err(e) = graph.checkpoint_wal() {
-                        eprintln!("Warning: WAL checkpoint failed after watch batch: {}", e);
+                    total_processed += process_dirty_paths(
+                        &mut graph,
+                        &dirty_paths,
+                        &batch_options,
+                        &mut diagnostics,
+                    )?;
+                    emit_diagnostics(&mut diagnostics, config.output_format);
+                    if config.checkpoint_interval.is_some() {
+                        checkpoint_pending = true;
+                    } else if let Err(e) = graph.checkpoint_wal() {
+                        log_warn!("WAL checkpoint failed after watch batch: {}", e);
                     }
                 }
             }
             Err(std::sync::mpsc::RecvTimeoutError::Timeout) => {
+                let due = config
+                    .checkpoint_interval
+                    .is_some_and(|interval| last_checkpoint.elapsed() >= interval);
+                if checkpoint_pending && due {
+                    // A busy checkpoint stays pending and is retried next interval
+                    match graph.checkpoint_wal() {
+                        Ok(()) => checkpoint_pending = false,
+                        Err(e) => log_warn!("idle WAL checkpoint failed: {}", e),
+                    }
+                    last_checkpoint = Instant::now();
+                }
                 continue;
             }
             Err(std::sync::mpsc::RecvTimeoutError::Disconnected) => {
@@ -386,9 +660,21 @@ pub fn run_watch_pipeline(config: WatchPipelineConfig, shutdown: Arc<AtomicBool>
         }
     }
 
+    if checkpoint_pending {
+        if let Err(e) = graph.checkpoint_wal() {
+            log_warn!("WAL checkpoint failed on shutdown: {}", e);
+        }
+    }
+
     // Wait for watcher thread to finish with extended timeout
     // Signal handler gives us 30 seconds, so we should have time to clean up
-    wait_for_watcher_thread(watcher_thread, Duration::from_secs(25));
+    for watcher_thread in watcher_threads {
+        wait_for_watcher_thread(watcher_thread, Duration::from_secs(25));
+    }
+
+    if let Some(profile) = graph.profile() {
+        emit_profile(profile, config.output_format);
+    }
 
     // Clean up main thread parsers before returning to prevent tcache_thread_shutdown crash
     crate::ingest::pool::cleanup_parsers();
@@ -427,12 +713,198 @@ fn watcher_loop(
     Ok(())
 }
 
+/// Per-cycle settings for dirty path processing.
+struct BatchOptions<'a> {
+    /// Canonical watch roots; diagnostic paths are reported relative to the
+    /// root containing them
+    roots: &'a [PathBuf],
+    /// File filter of each root (same order as `roots`), from its configured
+    /// `[index]` patterns; empty to reconcile every path
+    filters: &'a [FileFilter],
+    /// Check reindexed symbol counts (`--validate`)
+    validate: bool,
+    /// Skip files larger than this many bytes
+    max_file_size: Option<u64>,
+    /// Skip files whose header marks them as generated
+    skip_generated: bool,
+}
+
+impl BatchOptions<'_> {
+    /// Why the filter of the root containing `path` skips it, if it does
+    ///
+    /// Missing files are not skipped so their facts can still be deleted.
+    fn skip_reason(&self, path: &Path) -> Option<SkipReason> {
+        let (_, filter) = self
+            .roots
+            .iter()
+            .zip(self.filters)
+            .find(|(root, _)| path.starts_with(root))?;
+        filter
+            .should_skip(path)
+            .filter(|reason| *reason != SkipReason::NotAFile)
+    }
+}
+
+/// Print a batch of diagnostics in deterministic (path) order and clear it.
+///
+/// JSON output is one compact object per line, also for `--output pretty`.
+fn emit_diagnostics(diagnostics: &mut Vec<WatchDiagnostic>, format: OutputFormat) {
+    diagnostics.sort();
+    for diagnostic in diagnostics.drain(..) {
+        match format {
+            OutputFormat::Human => println!("{}", diagnostic),
+            OutputFormat::Json | OutputFormat::Pretty => println!("{}", diagnostic.format_json()),
+        }
+    }
+}
+
+/// Print the `--mem-report` accounting, as one JSON line for machine output
+fn emit_memory_report(report: &MemoryReport, format: OutputFormat) {
+    match format {
+        OutputFormat::Human => {
+            println!("Memory report:");
+            println!("  parse trees cached:  {}", report.parse_trees);
+            println!("  FQN index entries:   {}", report.fqn_index_entries);
+            println!("  file index entries:  {}", report.file_index_entries);
+            println!("  query cache entries: {}", report.cache_entries);
+            match report.peak_rss_bytes {
+                Some(bytes) => println!("  peak RSS:            {} bytes", bytes),
+                None => println!("  peak RSS:            unavailable"),
+            }
+        }
+        OutputFormat::Json | OutputFormat::Pretty => println!(
+            "{}",
+            serde_json::json!({
+                "schema_version": MAGELLAN_JSON_SCHEMA_VERSION,
+                "kind": "mem_report",
+                "parse_trees": report.parse_trees,
+                "fqn_index_entries": report.fqn_index_entries,
+                "file_index_entries": report.file_index_entries,
+                "cache_entries": report.cache_entries,
+                "peak_rss_bytes": report.peak_rss_bytes,
+            })
+        ),
+    }
+}
+
+/// Print the `--profile` phase breakdown, as one JSON line for machine output
+fn emit_profile(profile: &PhaseProfile, format: OutputFormat) {
+    match format {
+        OutputFormat::Human => {
+            println!("Indexing profile ({} batches):", profile.batches);
+            for phase in IndexPhase::ALL {
+                let timing = profile.phase(phase);
+                println!(
+                    "  {:<17} {:>8} ms  ({} files)",
+                    phase.as_str(),
+                    timing.total.as_millis(),
+                    timing.count
+                );
+            }
+            println!("  {:<17} {:>8} ms", "total", profile.total().as_millis());
+        }
+        OutputFormat::Json | OutputFormat::Pretty => {
+            let phases: serde_json::Map<String, serde_json::Value> = IndexPhase::ALL
+                .iter()
+                .map(|&phase| {
+                    let timing = profile.phase(phase);
+                    (
+                        phase.as_str().to_string(),
+                        serde_json::json!({
+                            "count": timing.count,
+                            "total_ms": timing.total.as_millis() as u64,
+                        }),
+                    )
+                })
+                .collect();
+            println!(
+                "{}",
+                serde_json::json!({
+                    "schema_version": MAGELLAN_JSON_SCHEMA_VERSION,
+                    "kind": "profile",
+                    "batches": profile.batches,
+                    "total_ms": profile.total().as_millis() as u64,
+                    "phases": phases,
+                })
+            )
+        }
+    }
+}
+
+/// Path of `path` relative to the watch root containing it, for diagnostics.
+fn diagnostic_path(roots: &[PathBuf], path: &Path) -> String {
+    roots
+        .iter()
+        .find_map(|root| path.strip_prefix(root).ok())
+        .unwrap_or(path)
+        .to_string_lossy()
+        .into_owned()
+}
+
 /// Process a list of dirty paths, reconciling each in sorted order.
 ///
-/// Paths are already sorted because they came from a BTreeSet.
-fn process_dirty_paths(graph: &mut CodeGraph, dirty_paths: &[PathBuf]) -> Result<usize> {
+/// Paths are already sorted because they came from a BTreeSet. Spellings that
+/// normalize to the same path key are collapsed first so that no file is
+/// reconciled twice within one cycle. Paths the root's filter skips (its
+/// `[index]` include/exclude patterns, like the baseline scan) are dropped.
+/// Skips and errors are appended to `diagnostics` for the caller to emit.
+fn process_dirty_paths(
+    graph: &mut CodeGraph,
+    dirty_paths: &[PathBuf],
+    options: &BatchOptions,
+    diagnostics: &mut Vec<WatchDiagnostic>,
+) -> Result<usize> {
+    let mut unique_paths = dedup_cycle_paths(dirty_paths);
+    unique_paths.retain(|path| match options.skip_reason(path) {
+        Some(reason) => {
+            diagnostics.push(WatchDiagnostic::skipped(
+                diagnostic_path(options.roots, path),
+                reason,
+            ));
+            false
+        }
+        None => true,
+    });
+    graph.record_profile_batch();
     // Use L3 cache-aware batching for better performance
-    process_dirty_paths_batched(graph, dirty_paths)
+    process_dirty_paths_batched(graph, &unique_paths, options, diagnostics)
+}
+
+/// Collapse dirty paths that refer to the same file within one reconciliation cycle.
+///
+/// The BTreeSet in `PipelineSharedState` only dedups identical `PathBuf`s. Paths
+/// arriving from different sources (filesystem batch, legacy per-event API, or
+/// externally pushed paths) may spell the same file differently (`./a.rs` vs
+/// `a.rs`, relative vs canonical). The first spelling in sorted order wins, so
+/// the result is deterministic.
+fn dedup_cycle_paths(dirty_paths: &[PathBuf]) -> Vec<PathBuf> {
+    let mut seen_keys = BTreeSet::new();
+    dirty_paths
+        .iter()
+        .filter(|path| {
+            let key = crate::validation::normalize_path(path)
+                .unwrap_or_else(|_| path.to_string_lossy().to_string());
+            seen_keys.insert(key)
+        })
+        .cloned()
+        .collect()
+}
+
+/// Check one file's indexed symbol count (`--validate`)
+///
+/// A mismatch is logged as a `VALIDATE` line; returns whether there was one.
+fn validate_symbol_count(graph: &mut CodeGraph, path: &str, symbols: usize) -> bool {
+    match crate::graph::validation::check_symbol_count_invariant(graph, path, symbols) {
+        Ok(Some(error)) => {
+            eprintln!("VALIDATE {} {}", error.code, error.message);
+            true
+        }
+        Ok(None) => false,
+        Err(e) => {
+            log_warn!("symbol count validation failed for {}: {}", path, e);
+            false
+        }
+    }
 }
 
 /// Process dirty paths using L3 cache-aware batching.
@@ -441,19 +913,43 @@ fn process_dirty_paths(graph: &mut CodeGraph, dirty_paths: &[PathBuf]) -> Result
 /// 1. Get file sizes for batch calculation
 /// 2. Group files into L3 cache-sized batches
 /// 3. For each batch: pre-read all sources (warms OS cache), then process
-fn process_dirty_paths_batched(graph: &mut CodeGraph, dirty_paths: &[PathBuf]) -> Result<usize> {
+///
+/// When `validate` is set, each reindexed file is checked against
+/// `check_symbol_count_invariant` and mismatches are logged as `VALIDATE` lines.
+/// Files over `max_file_size` and, with `skip_generated`, files with a
+/// generated-file header are skipped, and reindexed files whose parse tree
+/// contains a syntax error get a `Parse` diagnostic.
+fn process_dirty_paths_batched(
+    graph: &mut CodeGraph,
+    dirty_paths: &[PathBuf],
+    options: &BatchOptions,
+    diagnostics: &mut Vec<WatchDiagnostic>,
+) -> Result<usize> {
+    let validate = options.validate;
     if dirty_paths.is_empty() {
         return Ok(0);
     }
 
     let batch_start = Instant::now();
 
-    // Step 1: Get file sizes for batch calculation (only for existing files)
+    // Step 1: Get file sizes for batch calculation (only for existing files).
+    // Sizes and paths are collected together so batch indices stay aligned;
+    // missing files are handled once by the delete pass below.
     let size_start = Instant::now();
-    let sizes: Vec<usize> = dirty_paths
+    let (existing_paths, sizes): (Vec<&PathBuf>, Vec<usize>) = dirty_paths
         .iter()
-        .filter_map(|path| std::fs::metadata(path).ok().map(|meta| meta.len() as usize))
-        .collect();
+        .filter_map(|path| {
+            let len = std::fs::metadata(path).ok()?.len();
+            if options.max_file_size.is_some_and(|max| len > max) {
+                diagnostics.push(WatchDiagnostic::skipped(
+                    diagnostic_path(options.roots, path),
+                    SkipReason::FileTooLarge,
+                ));
+                return None;
+            }
+            Some((path, len as usize))
+        })
+        .unzip();
     let size_time = size_start.elapsed();
 
     // Step 2: Calculate target cache size (50% of L3)
@@ -471,8 +967,8 @@ fn process_dirty_paths_batched(graph: &mut CodeGraph, dirty_paths: &[PathBuf]) -
 
     // Step 4: Process each batch
     for batch in &batches {
-        // Index into dirty_paths directly — no intermediate path clones
-        let batch_paths: Vec<&PathBuf> = batch.iter().map(|&idx| &dirty_paths[idx]).collect();
+        // Index into existing_paths directly — no intermediate path clones
+        let batch_paths: Vec<&PathBuf> = batch.iter().map(|&idx| existing_paths[idx]).collect();
 
         // Pre-read all sources in batch to warm OS cache (data stays in L3)
         let read_start = Instant::now();
@@ -487,6 +983,18 @@ fn process_dirty_paths_batched(graph: &mut CodeGraph, dirty_paths: &[PathBuf]) -
 
         // Now process each file - use pre-read source when available
         for &path in &batch_paths {
+            if options.skip_generated
+                && source_map
+                    .get(path)
+                    .is_some_and(|source| crate::graph::filter::is_generated_source(source))
+            {
+                diagnostics.push(WatchDiagnostic::skipped(
+                    diagnostic_path(options.roots, path),
+                    SkipReason::Generated,
+                ));
+                continue;
+            }
+
             let path_key = crate::validation::normalize_path(path)
                 .unwrap_or_else(|_| path.to_string_lossy().to_string());
 
@@ -519,6 +1027,16 @@ fn process_dirty_paths_batched(graph: &mut CodeGraph, dirty_paths: &[PathBuf]) -
                                 "MODIFY {} symbols={} refs={} calls={}",
                                 path_str, symbols, references, calls
                             );
+                            if let Some((line, column)) = graph.cached_syntax_error(&path_key) {
+                                diagnostics.push(WatchDiagnostic::error(
+                                    diagnostic_path(options.roots, path),
+                                    DiagnosticStage::Parse,
+                                    format!("syntax error at {}:{}", line, column),
+                                ));
+                            }
+                            if validate {
+                                validate_symbol_count(graph, &path_key, symbols);
+                            }
                             true
                         }
                     };
@@ -528,22 +1046,46 @@ fn process_dirty_paths_batched(graph: &mut CodeGraph, dirty_paths: &[PathBuf]) -
                         total_processed += 1;
                     }
                 }
+                Err(e) if e.is::<crate::ingest::pool::ParseTimeout>() => {
+                    total_reconcile_time += reconcile_start.elapsed();
+                    diagnostics.push(WatchDiagnostic::skipped(
+                        diagnostic_path(options.roots, path),
+                        SkipReason::ParseTimeout,
+                    ));
+                }
                 Err(e) => {
                     total_reconcile_time += reconcile_start.elapsed();
-                    let path_str = path.to_string_lossy();
-                    println!("ERROR {} {}", path_str, e);
+                    let stage = if e.downcast_ref::<std::io::Error>().is_some() {
+                        DiagnosticStage::Read
+                    } else {
+                        DiagnosticStage::Other
+                    };
+                    diagnostics.push(WatchDiagnostic::error(
+                        diagnostic_path(options.roots, path),
+                        stage,
+                        e.to_string(),
+                    ));
                 }
             }
         }
     }
 
+    // Link qualified calls into files indexed after their callers
+    if let Err(e) = graph.relink_qualified_calls() {
+        log_warn!("failed to relink qualified calls: {}", e);
+    }
+
     // Also handle deleted files (paths in dirty_paths but not on filesystem)
     for path in dirty_paths {
         if !path.exists() {
             let path_key = crate::validation::normalize_path(path)
                 .unwrap_or_else(|_| path.to_string_lossy().to_string());
             if let Err(e) = graph.delete_file_facts(&path_key) {
-                eprintln!("Failed to delete file facts for {}: {}", path.display(), e);
+                diagnostics.push(WatchDiagnostic::error(
+                    diagnostic_path(options.roots, path),
+                    DiagnosticStage::Other,
+                    format!("failed to delete file facts: {}", e),
+                ));
             }
             println!("DELETE {}", path.to_string_lossy());
             total_processed += 1;
@@ -553,7 +1095,7 @@ fn process_dirty_paths_batched(graph: &mut CodeGraph, dirty_paths: &[PathBuf]) -
     let elapsed = batch_start.elapsed();
     // Only print batch stats when actual work was done (not just periodic checks)
     if total_processed > 0 {
-        eprintln!(
+        log_info!(
             "L3 Batch: {} files processed, {} batches, {}ms total (size:{}ms batch:{}ms read:{}ms reconcile:{}ms)",
             total_processed,
             batch_count,
@@ -567,7 +1109,7 @@ fn process_dirty_paths_batched(graph: &mut CodeGraph, dirty_paths: &[PathBuf]) -
         // Rebuild FTS5 index after batch processing to keep search index synchronized
         // Uses the graph's side connection to avoid uncoordinated WAL access
         if let Err(e) = graph.rebuild_fts5() {
-            eprintln!("Warning: FTS5 rebuild failed: {}", e);
+            log_warn!("FTS5 rebuild failed: {}", e);
         }
     }
 
@@ -591,3 +1133,206 @@ fn verify_db_integrity(db_path: &std::path::Path) -> Result<()> {
     }
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_watch_roots_dedups_and_rejects_nested_roots() {
+        let dir = tempfile::tempdir().unwrap();
+        let base = std::fs::canonicalize(dir.path()).unwrap();
+        for name in ["core", "cli", "core/src"] {
+            std::fs::create_dir_all(base.join(name)).unwrap();
+        }
+
+        let mut config = WatchPipelineConfig::new(
+            base.join("core"),
+            base.join("test.db"),
+            WatcherConfig::default(),
+            true,
+        );
+        config.extra_roots = vec![base.join("cli"), base.join("cli/../core")];
+        let roots: Vec<PathBuf> = watch_roots(&config)
+            .unwrap()
+            .into_iter()
+            .map(|(_, canonical)| canonical)
+            .collect();
+        assert_eq!(roots, vec![base.join("core"), base.join("cli")]);
+
+        config.extra_roots = vec![base.join("core/src")];
+        let err = watch_roots(&config).unwrap_err();
+        assert!(err.to_string().contains("overlap"), "{}", err);
+    }
+
+    #[test]
+    fn test_dedup_cycle_paths_collapses_equivalent_spellings() {
+        let paths = vec![
+            PathBuf::from("./src/lib.rs"),
+            PathBuf::from("src/lib.rs"),
+            PathBuf::from("src/main.rs"),
+        ];
+        let unique = dedup_cycle_paths(&paths);
+        assert_eq!(
+            unique,
+            vec![PathBuf::from("./src/lib.rs"), PathBuf::from("src/main.rs")]
+        );
+    }
+
+    #[test]
+    fn test_overlapping_sources_reindex_once_per_cycle() {
+        let dir = tempfile::tempdir().unwrap();
+        let db_path = dir.path().join("test.db");
+        let file = dir.path().join("a.rs");
+        std::fs::write(&file, "fn a() {}").unwrap();
+
+        let (state, _wakeup_rx) = PipelineSharedState::new();
+        // Filesystem batch
+        state.insert_dirty_paths(std::slice::from_ref(&file)).unwrap();
+        // Overlapping path pushed from a second source, spelled differently
+        std::fs::create_dir(dir.path().join("sub")).unwrap();
+        let alt_spelling = dir.path().join("sub").join("..").join("a.rs");
+        state.insert_dirty_paths(&[alt_spelling, file.clone()]).unwrap();
+
+        let drained = state.drain_dirty_paths().unwrap();
+        assert_eq!(drained.len(), 2, "BTreeSet keeps distinct spellings");
+
+        let mut graph = CodeGraph::open(&db_path).unwrap();
+        graph.batch_mode = false;
+        let options = BatchOptions {
+            roots: &[dir.path().to_path_buf()],
+            filters: &[],
+            validate: false,
+            max_file_size: None,
+            skip_generated: true,
+        };
+        let processed =
+            process_dirty_paths(&mut graph, &drained, &options, &mut Vec::new()).unwrap();
+        assert_eq!(processed, 1, "file must be reindexed exactly once");
+    }
+
+    #[test]
+    fn test_deleted_path_processed_once() {
+        let dir = tempfile::tempdir().unwrap();
+        let db_path = dir.path().join("test.db");
+        let existing = dir.path().join("b.rs");
+        std::fs::write(&existing, "fn b() {}").unwrap();
+        let missing = dir.path().join("a.rs");
+
+        let mut graph = CodeGraph::open(&db_path).unwrap();
+        graph.batch_mode = false;
+        let options = BatchOptions {
+            roots: &[dir.path().to_path_buf()],
+            filters: &[],
+            validate: true,
+            max_file_size: None,
+            skip_generated: true,
+        };
+        let processed =
+            process_dirty_paths(&mut graph, &[missing, existing], &options, &mut Vec::new())
+                .unwrap();
+        // One reindex for b.rs, one delete for a.rs
+        assert_eq!(processed, 2);
+    }
+
+    #[test]
+    fn test_validate_checks_initial_scan_symbol_counts() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = std::fs::canonicalize(dir.path()).unwrap();
+        let db_path = root.join("test.db");
+        std::fs::write(root.join("a.rs"), "fn a() {}\nfn b() {}\n").unwrap();
+
+        let mut graph = CodeGraph::open(&db_path).unwrap();
+        let filter = FileFilter::new(&root, &[], &[]).unwrap();
+        let scan = graph
+            .scan_directory_with_filter(&root, &filter, None)
+            .unwrap();
+        assert_eq!(scan.symbol_counts.len(), 1, "{:?}", scan.symbol_counts);
+        let (path, symbols) = &scan.symbol_counts[0];
+        assert_eq!(*symbols, 2);
+
+        assert!(!validate_symbol_count(&mut graph, path, *symbols));
+        // A count the graph cannot back up, as after a dropped insert
+        assert!(validate_symbol_count(&mut graph, path, symbols + 1));
+    }
+
+    #[test]
+    fn test_batch_diagnostics_for_syntax_error_and_size_limit() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = std::fs::canonicalize(dir.path()).unwrap();
+        let db_path = root.join("test.db");
+        let bad = root.join("bad.rs");
+        let big = root.join("big.rs");
+        std::fs::write(&bad, "fn ok() {}\nfn broken( {\n").unwrap();
+        std::fs::write(&big, "fn big() {}\n".repeat(50)).unwrap();
+
+        let mut graph = CodeGraph::open(&db_path).unwrap();
+        graph.batch_mode = false;
+        graph.enable_incremental_parse(8);
+        let options = BatchOptions {
+            roots: std::slice::from_ref(&root),
+            filters: &[],
+            validate: false,
+            max_file_size: Some(256),
+            skip_generated: true,
+        };
+        let mut diagnostics = Vec::new();
+        let processed =
+            process_dirty_paths(&mut graph, &[big, bad], &options, &mut diagnostics).unwrap();
+        assert_eq!(processed, 1, "only bad.rs is indexed");
+
+        diagnostics.sort();
+        let lines: Vec<serde_json::Value> = diagnostics
+            .iter()
+            .map(|d| serde_json::from_str(&d.format_json()).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0]["path"], "bad.rs");
+        assert_eq!(lines[0]["kind"], "error");
+        assert_eq!(lines[0]["stage"], "parse");
+        assert_eq!(lines[0]["reason"], "syntax error at 2:1");
+        assert_eq!(lines[1]["path"], "big.rs");
+        assert_eq!(lines[1]["kind"], "skipped");
+        assert_eq!(lines[1]["reason"], "file_too_large");
+    }
+
+    #[test]
+    fn test_batch_skips_generated_files_unless_included() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = std::fs::canonicalize(dir.path()).unwrap();
+        let db_path = root.join("test.db");
+        let generated = root.join("gen.rs");
+        std::fs::write(&generated, "// @generated\nfn gen() {}\n").unwrap();
+
+        let mut graph = CodeGraph::open(&db_path).unwrap();
+        graph.batch_mode = false;
+        let mut options = BatchOptions {
+            roots: std::slice::from_ref(&root),
+            filters: &[],
+            validate: false,
+            max_file_size: None,
+            skip_generated: true,
+        };
+        let mut diagnostics = Vec::new();
+        let processed = process_dirty_paths(
+            &mut graph,
+            std::slice::from_ref(&generated),
+            &options,
+            &mut diagnostics,
+        )
+        .unwrap();
+        assert_eq!(processed, 0);
+        assert_eq!(
+            diagnostics,
+            vec![WatchDiagnostic::skipped(
+                "gen.rs".to_string(),
+                SkipReason::Generated
+            )]
+        );
+
+        options.skip_generated = false;
+        let processed =
+            process_dirty_paths(&mut graph, &[generated], &options, &mut Vec::new()).unwrap();
+        assert_eq!(processed, 1);
+    }
+}
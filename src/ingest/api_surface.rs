@@ -0,0 +1,258 @@
+//! Public API surface filtering for `--api-only` indexing.
+//!
+//! Reduces extracted symbol facts to the items a downstream user can name,
+//! and records each retained item's declaration header as its signature.
+//!
+//! Visibility rules per language:
+//!
+//! | Language | Public when |
+//! |----------|-------------|
+//! | Rust | Item is `pub` (not `pub(crate)`/`pub(super)`) and every enclosing `mod` is `pub`; trait and trait-impl members follow the trait |
+//! | Python | Neither the name nor any enclosing class name starts with `_` (dunder names are public) |
+//! | Java | Declared with the `public` modifier |
+//! | Go | Name starts with an uppercase letter |
+//! | C, C++, CUDA, JavaScript, TypeScript, Swift, Scala, C# | Always (no per-symbol visibility model yet) |
+
+use super::{Language, SymbolFact};
+use crate::common::safe_slice;
+
+/// Keep only public API symbols and record their signatures.
+///
+/// Facts whose defining node cannot be located in `tree` are dropped, since
+/// their visibility cannot be established.
+pub fn retain_public_api(
+    language: Language,
+    tree: &tree_sitter::Tree,
+    source: &[u8],
+    facts: Vec<SymbolFact>,
+) -> Vec<SymbolFact> {
+    let root = tree.root_node();
+
+    facts
+        .into_iter()
+        .filter_map(|mut fact| {
+            let node = defining_node(&root, fact.byte_start, fact.byte_end)?;
+            if !is_public(language, &node, source, fact.name.as_deref()) {
+                return None;
+            }
+            fact.signature = signature(language, &node, source);
+            Some(fact)
+        })
+        .collect()
+}
+
+/// Visibility of one fact under the rules above, for the `pub`/`private` labels
+///
+/// `None` for languages without a visibility model, and for facts whose
+/// defining node cannot be located in `tree`.
+pub fn fact_visibility(
+    language: Language,
+    tree: &tree_sitter::Tree,
+    source: &[u8],
+    fact: &SymbolFact,
+) -> Option<bool> {
+    if !has_visibility_model(language) {
+        return None;
+    }
+    let node = defining_node(&tree.root_node(), fact.byte_start, fact.byte_end)?;
+    Some(is_public(language, &node, source, fact.name.as_deref()))
+}
+
+/// Languages whose visibility rules can tell public from private items
+fn has_visibility_model(language: Language) -> bool {
+    matches!(
+        language,
+        Language::Rust | Language::Python | Language::Java | Language::Go
+    )
+}
+
+/// Smallest node spanning exactly `[byte_start, byte_end)`
+fn defining_node<'a>(
+    root: &tree_sitter::Node<'a>,
+    byte_start: usize,
+    byte_end: usize,
+) -> Option<tree_sitter::Node<'a>> {
+    let node = root.descendant_for_byte_range(byte_start, byte_end)?;
+    (node.start_byte() == byte_start && node.end_byte() == byte_end).then_some(node)
+}
+
+fn node_text<'a>(node: &tree_sitter::Node, source: &'a [u8]) -> &'a str {
+    safe_slice(source, node.start_byte(), node.end_byte())
+        .and_then(|bytes| std::str::from_utf8(bytes).ok())
+        .unwrap_or("")
+}
+
+fn is_public(
+    language: Language,
+    node: &tree_sitter::Node,
+    source: &[u8],
+    name: Option<&str>,
+) -> bool {
+    match language {
+        Language::Rust => is_public_rust(node, source),
+        Language::Python => is_public_python(node, source, name),
+        Language::Java => has_modifier(node, source, "public"),
+        Language::Go => name
+            .and_then(|n| n.chars().next())
+            .is_some_and(|c| c.is_uppercase()),
+        Language::C
+        | Language::Cpp
+        | Language::Cuda
+        | Language::JavaScript
+        | Language::TypeScript
+        | Language::Swift
+        | Language::Scala
+        | Language::CSharp
+        | Language::Custom(_) => true,
+    }
+}
+
+/// `pub` exactly; restricted visibility such as `pub(crate)` is not public API
+fn rust_visibility_is_pub(node: &tree_sitter::Node, source: &[u8]) -> bool {
+    let mut cursor = node.walk();
+    let is_pub = node
+        .children(&mut cursor)
+        .any(|child| child.kind() == "visibility_modifier" && node_text(&child, source) == "pub");
+    is_pub
+}
+
+fn is_public_rust(node: &tree_sitter::Node, source: &[u8]) -> bool {
+    // Trait items inherit the trait's visibility; items in trait impls are
+    // public whenever the implementing type is reachable.
+    let mut in_trait_scope = false;
+    let mut ancestor = node.parent();
+    while let Some(current) = ancestor {
+        match current.kind() {
+            "mod_item" if !rust_visibility_is_pub(&current, source) => return false,
+            "trait_item" => {
+                if !rust_visibility_is_pub(&current, source) {
+                    return false;
+                }
+                in_trait_scope = true;
+            }
+            "impl_item" if current.child_by_field_name("trait").is_some() => {
+                in_trait_scope = true;
+            }
+            "function_item" => return false, // items nested inside function bodies
+            _ => {}
+        }
+        ancestor = current.parent();
+    }
+
+    in_trait_scope || rust_visibility_is_pub(node, source)
+}
+
+fn is_public_python(node: &tree_sitter::Node, source: &[u8], name: Option<&str>) -> bool {
+    fn is_private_name(name: &str) -> bool {
+        name.starts_with('_') && !(name.starts_with("__") && name.ends_with("__"))
+    }
+
+    if name.is_some_and(is_private_name) {
+        return false;
+    }
+
+    let mut ancestor = node.parent();
+    while let Some(current) = ancestor {
+        match current.kind() {
+            "class_definition" => {
+                let class_name = current
+                    .child_by_field_name("name")
+                    .map(|n| node_text(&n, source))
+                    .unwrap_or("");
+                if is_private_name(class_name) {
+                    return false;
+                }
+            }
+            "function_definition" => return false, // nested functions are local
+            _ => {}
+        }
+        ancestor = current.parent();
+    }
+
+    true
+}
+
+fn has_modifier(node: &tree_sitter::Node, source: &[u8], modifier: &str) -> bool {
+    let mut cursor = node.walk();
+    let found = node.children(&mut cursor).any(|child| {
+        child.kind() == "modifiers"
+            && node_text(&child, source)
+                .split_whitespace()
+                .any(|m| m == modifier)
+    });
+    found
+}
+
+/// Declaration header: the item text before its body, whitespace-collapsed
+fn signature(language: Language, node: &tree_sitter::Node, source: &[u8]) -> Option<String> {
+    let header_end = node
+        .child_by_field_name("body")
+        .map(|body| body.start_byte())
+        .unwrap_or_else(|| node.end_byte());
+    let bytes = safe_slice(source, node.start_byte(), header_end)?;
+    let text = std::str::from_utf8(bytes).ok()?;
+
+    let mut header = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    let trailing: &[char] = match language {
+        Language::Python => &[':', ' '],
+        _ => &[';', ' '],
+    };
+    header.truncate(header.trim_end_matches(trailing).len());
+
+    (!header.is_empty()).then_some(header)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ingest::pool;
+    use std::path::PathBuf;
+
+    fn public_facts(language: Language, path: &str, source: &[u8]) -> Vec<SymbolFact> {
+        let tree = pool::with_parser(language, |parser| parser.parse(source, None))
+            .unwrap()
+            .unwrap();
+        let facts = match language {
+            Language::Rust => {
+                crate::ingest::Parser::extract_symbols_from_tree(&tree, PathBuf::from(path), source)
+            }
+            Language::Python => crate::ingest::python::PythonParser::extract_symbols_from_tree(
+                &tree,
+                PathBuf::from(path),
+                source,
+            ),
+            _ => unreachable!(),
+        };
+        retain_public_api(language, &tree, source, facts)
+    }
+
+    fn names(facts: &[SymbolFact]) -> Vec<&str> {
+        facts.iter().filter_map(|f| f.name.as_deref()).collect()
+    }
+
+    #[test]
+    fn test_rust_keeps_only_pub_items() {
+        let source = b"pub fn open(path: &str) -> Result<()> { helper() }\n\
+fn helper() -> Result<()> { Ok(()) }\n\
+pub(crate) fn internal() {}\n\
+mod private { pub fn hidden() {} }\n\
+pub mod api { pub fn visible() {} }\n";
+        let facts = public_facts(Language::Rust, "lib.rs", source);
+
+        assert_eq!(names(&facts), vec!["open", "api", "visible"]);
+        assert_eq!(
+            facts[0].signature.as_deref(),
+            Some("pub fn open(path: &str) -> Result<()>")
+        );
+    }
+
+    #[test]
+    fn test_python_skips_underscore_names() {
+        let source =
+            b"def load(path):\n    pass\n\ndef _cache():\n    pass\n\nclass _Impl:\n    def run(self):\n        pass\n";
+        let facts = public_facts(Language::Python, "mod.py", source);
+
+        assert_eq!(names(&facts), vec!["load"]);
+        assert_eq!(facts[0].signature.as_deref(), Some("def load(path)"));
+    }
+}
@@ -3,11 +3,14 @@
 //! Extracts functions, structs, enums, and unions from C source code.
 
 use crate::graph::canonical_fqn::FqnBuilder;
-use crate::ingest::{ScopeSeparator, ScopeStack, SymbolFact, SymbolKind};
+use crate::ingest::{Language, ScopeSeparator, ScopeStack, SymbolFact, SymbolKind};
 use crate::references::{CallFact, ReferenceFact};
 use anyhow::Result;
 use std::path::{Path, PathBuf};
 
+/// FQN separator for C scopes
+const SCOPE_SEPARATOR: ScopeSeparator = ScopeSeparator::for_language(Language::C);
+
 /// Parser that extracts symbol facts from C source code.
 ///
 /// Pure function: Input (path, contents) → Output `Vec<SymbolFact>`
@@ -103,11 +106,11 @@ impl CParser {
 
         // Compute canonical and display FQNs
         // C has no namespaces, so we use an empty ScopeStack
-        let scope_stack = ScopeStack::new(ScopeSeparator::DoubleColon);
+        let scope_stack = ScopeStack::new(SCOPE_SEPARATOR);
         let builder = FqnBuilder::new(
             package_name.to_string(),
             file_path.to_string_lossy().to_string(),
-            ScopeSeparator::DoubleColon,
+            SCOPE_SEPARATOR,
         );
         let (canonical_fqn, display_fqn) = if let Some(ref name_str) = name {
             let canonical = builder.canonical(&scope_stack, symbol_kind.clone(), name_str);
@@ -134,6 +137,11 @@ impl CParser {
             start_col: node.start_position().column,
             end_line: node.end_position().row + 1,
             end_col: node.end_position().column,
+            signature: None,
+            is_test: false,
+            visibility: None,
+            deprecated: false,
+            deprecation_message: None,
         })
     }
 
@@ -252,11 +260,11 @@ impl CParser {
 
         // Compute canonical and display FQNs
         // C has no namespaces, so we use an empty ScopeStack
-        let scope_stack = ScopeStack::new(ScopeSeparator::DoubleColon);
+        let scope_stack = ScopeStack::new(SCOPE_SEPARATOR);
         let builder = FqnBuilder::new(
             package_name.to_string(),
             file_path.to_string_lossy().to_string(),
-            ScopeSeparator::DoubleColon,
+            SCOPE_SEPARATOR,
         );
         let (canonical_fqn, display_fqn) = if let Some(ref name_str) = name {
             let canonical = builder.canonical(&scope_stack, symbol_kind.clone(), name_str);
@@ -283,6 +291,11 @@ impl CParser {
             start_col: node.start_position().column,
             end_line: node.end_position().row + 1,
             end_col: node.end_position().column,
+            signature: None,
+            is_test: false,
+            visibility: None,
+            deprecated: false,
+            deprecation_message: None,
         })
     }
 
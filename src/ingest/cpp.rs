@@ -4,11 +4,14 @@
 
 use crate::common::safe_slice;
 use crate::graph::canonical_fqn::FqnBuilder;
-use crate::ingest::{ScopeSeparator, ScopeStack, SymbolFact, SymbolKind};
+use crate::ingest::{Language, ScopeSeparator, ScopeStack, SymbolFact, SymbolKind};
 use crate::references::{CallFact, ReferenceFact};
 use anyhow::Result;
 use std::path::{Path, PathBuf};
 
+/// FQN separator for Cpp scopes
+const SCOPE_SEPARATOR: ScopeSeparator = ScopeSeparator::for_language(Language::Cpp);
+
 /// Parser that extracts symbol facts from C++ source code.
 ///
 /// Pure function: Input (path, contents) → Output `Vec<SymbolFact>`
@@ -51,7 +54,7 @@ impl CppParser {
 
         let root_node = tree.root_node();
         let mut facts = Vec::new();
-        let mut scope_stack = ScopeStack::new(ScopeSeparator::DoubleColon);
+        let mut scope_stack = ScopeStack::new(SCOPE_SEPARATOR);
 
         // Per decision FQN-17, use "." as project_root placeholder for C++
         let package_name = ".";
@@ -148,7 +151,7 @@ impl CppParser {
 
         let root_node = tree.root_node();
         let mut facts = Vec::new();
-        let mut scope_stack = ScopeStack::new(ScopeSeparator::DoubleColon);
+        let mut scope_stack = ScopeStack::new(SCOPE_SEPARATOR);
 
         // Per decision FQN-17, use "." as project_root placeholder for C++
         let package_name = ".";
@@ -176,7 +179,7 @@ impl CppParser {
     ) -> Vec<SymbolFact> {
         let root_node = tree.root_node();
         let mut facts = Vec::new();
-        let mut scope_stack = ScopeStack::new(ScopeSeparator::DoubleColon);
+        let mut scope_stack = ScopeStack::new(SCOPE_SEPARATOR);
         let package_name = ".";
         Self::walk_tree_with_scope_static(
             &root_node,
@@ -300,7 +303,7 @@ impl CppParser {
         let builder = FqnBuilder::new(
             package_name.to_string(),
             file_path.to_string_lossy().to_string(),
-            ScopeSeparator::DoubleColon,
+            SCOPE_SEPARATOR,
         );
         let canonical_fqn = builder.canonical(scope_stack, symbol_kind.clone(), &name);
         let display_fqn = builder.display(scope_stack, symbol_kind.clone(), &name);
@@ -319,6 +322,11 @@ impl CppParser {
             start_col: node.start_position().column,
             end_line: node.end_position().row + 1,
             end_col: node.end_position().column,
+            signature: None,
+            is_test: false,
+            visibility: None,
+            deprecated: false,
+            deprecation_message: None,
         })
     }
 
@@ -486,7 +494,7 @@ impl CppParser {
         let builder = FqnBuilder::new(
             package_name.to_string(),
             file_path.to_string_lossy().to_string(),
-            ScopeSeparator::DoubleColon,
+            SCOPE_SEPARATOR,
         );
         let canonical_fqn = builder.canonical(scope_stack, symbol_kind.clone(), &name);
         let display_fqn = builder.display(scope_stack, symbol_kind.clone(), &name);
@@ -505,6 +513,11 @@ impl CppParser {
             start_col: node.start_position().column,
             end_line: node.end_position().row + 1,
             end_col: node.end_position().column,
+            signature: None,
+            is_test: false,
+            visibility: None,
+            deprecated: false,
+            deprecation_message: None,
         })
     }
 
@@ -0,0 +1,467 @@
+//! C# symbol extraction using tree-sitter-c-sharp.
+//!
+//! Extracts classes, records, structs, interfaces, enums, and methods from
+//! C# source code. Namespaces contribute to FQNs: a braced
+//! `namespace A.B { ... }` scopes its body, while a file-scoped
+//! `namespace A.B;` scopes the rest of the file.
+
+use crate::common::safe_slice;
+use crate::graph::canonical_fqn::FqnBuilder;
+use crate::ingest::{Language, ScopeSeparator, ScopeStack, SymbolFact, SymbolKind};
+use crate::references::{CallFact, ReferenceFact};
+use anyhow::Result;
+use std::path::{Path, PathBuf};
+
+/// FQN separator for C# scopes
+const SCOPE_SEPARATOR: ScopeSeparator = ScopeSeparator::for_language(Language::CSharp);
+
+/// Parser that extracts symbol facts from C# source code.
+///
+/// Pure function: Input (path, contents) → Output `Vec<SymbolFact>`
+/// No filesystem access. No global state. No caching.
+pub struct CSharpParser {
+    pub(crate) parser: tree_sitter::Parser,
+}
+
+impl CSharpParser {
+    /// Create a new parser for C# source code.
+    pub fn new() -> Result<Self> {
+        let mut parser = tree_sitter::Parser::new();
+        parser.set_language(&tree_sitter_c_sharp::LANGUAGE.into())?;
+        Ok(Self { parser })
+    }
+
+    /// Create parser wrapper from an existing tree-sitter parser
+    pub(crate) fn from_parser(parser: tree_sitter::Parser) -> Self {
+        Self { parser }
+    }
+
+    /// Extract symbol facts from C# source code.
+    ///
+    /// # Arguments
+    /// * `file_path` - Path to the file (for context only, not accessed)
+    /// * `source` - Source code content as bytes
+    ///
+    /// # Returns
+    /// Vector of symbol facts found in the source
+    ///
+    /// # Guarantees
+    /// - Pure function: same input → same output
+    /// - No side effects
+    /// - No filesystem access
+    pub fn extract_symbols(&mut self, file_path: PathBuf, source: &[u8]) -> Vec<SymbolFact> {
+        let tree = match self.parser.parse(source, None) {
+            Some(t) => t,
+            None => return Vec::new(), // Parse error: return empty
+        };
+        Self::extract_symbols_from_tree(&tree, file_path, source)
+    }
+
+    /// Static walk tree for symbol extraction.
+    ///
+    /// - class/record/struct_declaration: create a Class symbol and push their name
+    /// - interface_declaration / enum_declaration: create an Interface / Enum symbol and push its name
+    /// - namespace_declaration: pushes the namespace name for its body only
+    fn walk_tree_static(
+        node: &tree_sitter::Node,
+        source: &[u8],
+        file_path: &Path,
+        facts: &mut Vec<SymbolFact>,
+        scope_stack: &mut ScopeStack,
+    ) {
+        let kind = node.kind();
+
+        let scope_name = match kind {
+            "namespace_declaration" => Self::extract_namespace_name(node, source),
+            "class_declaration" | "record_declaration" | "struct_declaration" => {
+                Self::push_type_symbol(
+                    node,
+                    source,
+                    file_path,
+                    facts,
+                    scope_stack,
+                    SymbolKind::Class,
+                )
+            }
+            "interface_declaration" => Self::push_type_symbol(
+                node,
+                source,
+                file_path,
+                facts,
+                scope_stack,
+                SymbolKind::Interface,
+            ),
+            "enum_declaration" => Self::push_type_symbol(
+                node,
+                source,
+                file_path,
+                facts,
+                scope_stack,
+                SymbolKind::Enum,
+            ),
+            _ => None,
+        };
+
+        if let Some(name) = scope_name {
+            scope_stack.push(&name);
+            Self::walk_children(node, source, file_path, facts, scope_stack);
+            scope_stack.pop();
+            return;
+        }
+
+        if kind == "method_declaration" {
+            if let Some(name) = Self::extract_name(node, source) {
+                facts.push(Self::symbol_fact(
+                    node,
+                    file_path,
+                    scope_stack,
+                    SymbolKind::Method,
+                    name,
+                ));
+            }
+        }
+
+        Self::walk_children(node, source, file_path, facts, scope_stack);
+    }
+
+    /// Recurse into children.
+    ///
+    /// A file-scoped `namespace A.B;` has no body of its own: its name
+    /// scopes every following sibling up to the end of the file.
+    fn walk_children(
+        node: &tree_sitter::Node,
+        source: &[u8],
+        file_path: &Path,
+        facts: &mut Vec<SymbolFact>,
+        scope_stack: &mut ScopeStack,
+    ) {
+        let mut pushed = 0;
+        let mut cursor = node.walk();
+        for child in node.children(&mut cursor) {
+            if child.kind() == "file_scoped_namespace_declaration" {
+                if let Some(name) = Self::extract_namespace_name(&child, source) {
+                    scope_stack.push(&name);
+                    pushed += 1;
+                }
+                continue;
+            }
+            Self::walk_tree_static(&child, source, file_path, facts, scope_stack);
+        }
+        for _ in 0..pushed {
+            scope_stack.pop();
+        }
+    }
+
+    /// Record a type-level symbol and return its name for scoping.
+    fn push_type_symbol(
+        node: &tree_sitter::Node,
+        source: &[u8],
+        file_path: &Path,
+        facts: &mut Vec<SymbolFact>,
+        scope_stack: &ScopeStack,
+        symbol_kind: SymbolKind,
+    ) -> Option<String> {
+        let name = Self::extract_name(node, source)?;
+        facts.push(Self::symbol_fact(
+            node,
+            file_path,
+            scope_stack,
+            symbol_kind,
+            name.clone(),
+        ));
+        Some(name)
+    }
+
+    fn symbol_fact(
+        node: &tree_sitter::Node,
+        file_path: &Path,
+        scope_stack: &ScopeStack,
+        symbol_kind: SymbolKind,
+        name: String,
+    ) -> SymbolFact {
+        let normalized_kind = symbol_kind.normalized_key().to_string();
+        let fqn = scope_stack.fqn_for_symbol(&name);
+
+        let builder = FqnBuilder::new(
+            ".".to_string(),
+            file_path.to_string_lossy().to_string(),
+            SCOPE_SEPARATOR,
+        );
+        let canonical_fqn = builder.canonical(scope_stack, symbol_kind.clone(), &name);
+        let display_fqn = builder.display(scope_stack, symbol_kind.clone(), &name);
+
+        SymbolFact {
+            file_path: file_path.to_path_buf(),
+            kind: symbol_kind,
+            kind_normalized: normalized_kind,
+            name: Some(name),
+            fqn: Some(fqn),
+            canonical_fqn: Some(canonical_fqn),
+            display_fqn: Some(display_fqn),
+            byte_start: node.start_byte(),
+            byte_end: node.end_byte(),
+            start_line: node.start_position().row + 1,
+            start_col: node.start_position().column,
+            end_line: node.end_position().row + 1,
+            end_col: node.end_position().column,
+            signature: None,
+            is_test: false,
+            visibility: None,
+            deprecated: false,
+            deprecation_message: None,
+        }
+    }
+
+    fn node_text(node: &tree_sitter::Node, source: &[u8]) -> Option<String> {
+        let bytes = safe_slice(source, node.start_byte(), node.end_byte())?;
+        std::str::from_utf8(bytes).ok().map(|s| s.to_string())
+    }
+
+    /// Dotted namespace name (`namespace MyApp.Services` → `MyApp.Services`)
+    fn extract_namespace_name(node: &tree_sitter::Node, source: &[u8]) -> Option<String> {
+        let name_node = node.child_by_field_name("name")?;
+        let text = Self::node_text(&name_node, source)?;
+        let name: String = text.chars().filter(|c| !c.is_whitespace()).collect();
+        (!name.is_empty()).then_some(name)
+    }
+
+    /// Extract the declared name of a declaration node.
+    fn extract_name(node: &tree_sitter::Node, source: &[u8]) -> Option<String> {
+        let name_node = node.child_by_field_name("name")?;
+        Self::node_text(&name_node, source)
+    }
+
+    /// Extract symbol facts using an external parser (for parser pooling).
+    pub fn extract_symbols_with_parser(
+        parser: &mut tree_sitter::Parser,
+        file_path: PathBuf,
+        source: &[u8],
+    ) -> Vec<SymbolFact> {
+        let tree = match parser.parse(source, None) {
+            Some(t) => t,
+            None => return Vec::new(),
+        };
+        Self::extract_symbols_from_tree(&tree, file_path, source)
+    }
+
+    /// Extract symbol facts from a pre-parsed tree.
+    pub fn extract_symbols_from_tree(
+        tree: &tree_sitter::Tree,
+        file_path: PathBuf,
+        source: &[u8],
+    ) -> Vec<SymbolFact> {
+        let root_node = tree.root_node();
+        let mut facts = Vec::new();
+        let mut scope_stack = ScopeStack::new(SCOPE_SEPARATOR);
+
+        Self::walk_tree_static(&root_node, source, &file_path, &mut facts, &mut scope_stack);
+        facts
+    }
+
+    /// Extract reference facts from C# source code.
+    pub fn extract_references(
+        &mut self,
+        file_path: PathBuf,
+        source: &[u8],
+        symbols: &[SymbolFact],
+    ) -> Vec<ReferenceFact> {
+        let tree = match self.parser.parse(source, None) {
+            Some(t) => t,
+            None => return Vec::new(),
+        };
+        Self::extract_references_from_tree(&tree, file_path, source, symbols)
+    }
+
+    /// Extract reference facts from a pre-parsed tree.
+    pub fn extract_references_from_tree(
+        tree: &tree_sitter::Tree,
+        file_path: PathBuf,
+        source: &[u8],
+        symbols: &[SymbolFact],
+    ) -> Vec<ReferenceFact> {
+        use crate::ingest::generic_extraction;
+        generic_extraction::extract_references_from_tree(
+            tree,
+            file_path,
+            source,
+            symbols,
+            |node| node.kind() == "identifier",
+            |node, source| {
+                let text = std::str::from_utf8(&source[node.start_byte()..node.end_byte()]).ok()?;
+                Some((text.to_string(), node.kind()))
+            },
+        )
+    }
+
+    /// Extract method call facts from C# source code.
+    pub fn extract_calls(
+        &mut self,
+        file_path: PathBuf,
+        source: &[u8],
+        symbols: &[SymbolFact],
+    ) -> Vec<CallFact> {
+        let tree = match self.parser.parse(source, None) {
+            Some(t) => t,
+            None => return Vec::new(),
+        };
+        Self::extract_calls_from_tree(&tree, file_path, source, symbols)
+    }
+
+    pub fn extract_calls_from_tree(
+        tree: &tree_sitter::Tree,
+        file_path: PathBuf,
+        source: &[u8],
+        symbols: &[SymbolFact],
+    ) -> Vec<CallFact> {
+        use crate::ingest::generic_extraction;
+        generic_extraction::extract_calls_from_tree(
+            tree,
+            file_path,
+            source,
+            symbols,
+            |node| node.kind() == "method_declaration",
+            Self::extract_name,
+            "invocation_expression",
+            |node, source| {
+                let callee = node.child_by_field_name("function")?;
+                Self::extract_callee(&callee, source)
+            },
+        )
+    }
+
+    /// Name of a call's target (`Foo(..)`, `obj.Foo(..)`, `Foo<T>(..)` → `Foo`)
+    fn extract_callee(callee: &tree_sitter::Node, source: &[u8]) -> Option<(String, &'static str)> {
+        match callee.kind() {
+            "identifier" => Some((Self::node_text(callee, source)?, "identifier")),
+            "member_access_expression" => {
+                let name = callee.child_by_field_name("name")?;
+                let (text, _) = Self::extract_callee(&name, source)?;
+                Some((text, "member_access_expression"))
+            }
+            "generic_name" => {
+                let mut cursor = callee.walk();
+                let identifier = callee
+                    .children(&mut cursor)
+                    .find(|child| child.kind() == "identifier")?;
+                Some((Self::node_text(&identifier, source)?, "generic_name"))
+            }
+            _ => None,
+        }
+    }
+}
+
+impl Default for CSharpParser {
+    fn default() -> Self {
+        Self::new().expect("Failed to create C# parser") // M-UNWRAP: grammar is statically linked
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn symbols(source: &[u8]) -> Vec<SymbolFact> {
+        let mut parser = CSharpParser::new().unwrap();
+        parser.extract_symbols(PathBuf::from("UserService.cs"), source)
+    }
+
+    fn find<'a>(facts: &'a [SymbolFact], name: &str) -> &'a SymbolFact {
+        facts
+            .iter()
+            .find(|f| f.name.as_deref() == Some(name))
+            .unwrap_or_else(|| panic!("symbol {} not found", name))
+    }
+
+    #[test]
+    fn test_extract_type_declarations() {
+        let source = b"class Animal {}\nrecord Point(int X, int Y);\nstruct Vec2 { }\ninterface IDrawable {}\nenum Color { Red, Green }\n";
+        let facts = symbols(source);
+
+        assert_eq!(find(&facts, "Animal").kind, SymbolKind::Class);
+        assert_eq!(find(&facts, "Point").kind, SymbolKind::Class);
+        assert_eq!(find(&facts, "Vec2").kind, SymbolKind::Class);
+        assert_eq!(find(&facts, "IDrawable").kind, SymbolKind::Interface);
+        assert_eq!(find(&facts, "Color").kind, SymbolKind::Enum);
+    }
+
+    #[test]
+    fn test_namespace_and_class_prefix_method_fqn() {
+        let source = b"namespace MyApp.Services\n{\n    public class UserService\n    {\n        public User GetUser(int id) { return null; }\n    }\n}\n";
+        let facts = symbols(source);
+
+        let get_user = find(&facts, "GetUser");
+        assert_eq!(get_user.kind, SymbolKind::Method);
+        assert_eq!(
+            get_user.fqn.as_deref(),
+            Some("MyApp.Services.UserService.GetUser")
+        );
+        assert_eq!(get_user.start_line, 5);
+        assert_eq!(
+            find(&facts, "UserService").fqn.as_deref(),
+            Some("MyApp.Services.UserService")
+        );
+    }
+
+    #[test]
+    fn test_file_scoped_namespace_and_nested_types() {
+        let source = b"namespace MyApp;\n\nclass Outer\n{\n    struct Inner\n    {\n        void Run() {}\n    }\n}\n";
+        let facts = symbols(source);
+
+        assert_eq!(
+            find(&facts, "Inner").fqn.as_deref(),
+            Some("MyApp.Outer.Inner")
+        );
+        assert_eq!(
+            find(&facts, "Run").fqn.as_deref(),
+            Some("MyApp.Outer.Inner.Run")
+        );
+    }
+
+    #[test]
+    fn test_nested_namespaces_scope_only_their_body() {
+        let source = b"namespace A\n{\n    namespace B\n    {\n        class Inside {}\n    }\n}\nclass Outside {}\n";
+        let facts = symbols(source);
+
+        assert_eq!(find(&facts, "Inside").fqn.as_deref(), Some("A.B.Inside"));
+        assert_eq!(find(&facts, "Outside").fqn.as_deref(), Some("Outside"));
+    }
+
+    #[test]
+    fn test_interface_method() {
+        let facts = symbols(b"interface IShape\n{\n    double Area();\n}\n");
+
+        let area = find(&facts, "Area");
+        assert_eq!(area.kind, SymbolKind::Method);
+        assert_eq!(area.fqn.as_deref(), Some("IShape.Area"));
+    }
+
+    #[test]
+    fn test_extract_calls() {
+        let source =
+            b"class C\n{\n    void Helper() {}\n    void Run() { Helper(); this.Helper(); }\n}\n";
+        let mut parser = CSharpParser::new().unwrap();
+        let path = PathBuf::from("C.cs");
+        let facts = parser.extract_symbols(path.clone(), source);
+        let calls = parser.extract_calls(path, source, &facts);
+
+        assert_eq!(calls.len(), 2);
+        assert!(calls
+            .iter()
+            .all(|c| c.caller == "Run" && c.callee == "Helper"));
+    }
+
+    #[test]
+    fn test_empty_file() {
+        assert!(symbols(b"").is_empty());
+    }
+
+    #[test]
+    fn test_byte_spans_within_bounds() {
+        let source = b"class Foo { }";
+        let facts = symbols(source);
+
+        assert!(!facts.is_empty());
+        let fact = &facts[0];
+        assert_eq!(&source[fact.byte_start..fact.byte_end], b"class Foo { }");
+    }
+}
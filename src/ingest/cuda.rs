@@ -5,11 +5,14 @@
 
 use crate::common::safe_slice;
 use crate::graph::canonical_fqn::FqnBuilder;
-use crate::ingest::{ScopeSeparator, ScopeStack, SymbolFact, SymbolKind};
+use crate::ingest::{Language, ScopeSeparator, ScopeStack, SymbolFact, SymbolKind};
 use crate::references::{CallFact, ReferenceFact};
 use anyhow::Result;
 use std::path::{Path, PathBuf};
 
+/// FQN separator for Cuda scopes
+const SCOPE_SEPARATOR: ScopeSeparator = ScopeSeparator::for_language(Language::Cuda);
+
 /// Parser that extracts symbol facts from CUDA source code.
 ///
 /// Pure function: Input (path, contents) → Output `Vec<SymbolFact>`
@@ -52,7 +55,7 @@ impl CudaParser {
 
         let root_node = tree.root_node();
         let mut facts = Vec::new();
-        let mut scope_stack = ScopeStack::new(ScopeSeparator::DoubleColon);
+        let mut scope_stack = ScopeStack::new(SCOPE_SEPARATOR);
 
         // Per decision FQN-17, use "." as project_root placeholder for CUDA
         let package_name = ".";
@@ -135,7 +138,7 @@ impl CudaParser {
 
         let root_node = tree.root_node();
         let mut facts = Vec::new();
-        let mut scope_stack = ScopeStack::new(ScopeSeparator::DoubleColon);
+        let mut scope_stack = ScopeStack::new(SCOPE_SEPARATOR);
 
         let package_name = ".";
 
@@ -159,7 +162,7 @@ impl CudaParser {
     ) -> Vec<SymbolFact> {
         let root_node = tree.root_node();
         let mut facts = Vec::new();
-        let mut scope_stack = ScopeStack::new(ScopeSeparator::DoubleColon);
+        let mut scope_stack = ScopeStack::new(SCOPE_SEPARATOR);
         let package_name = ".";
         Self::walk_tree_with_scope_static(
             &root_node,
@@ -280,7 +283,7 @@ impl CudaParser {
         let builder = FqnBuilder::new(
             package_name.to_string(),
             file_path.to_string_lossy().to_string(),
-            ScopeSeparator::DoubleColon,
+            SCOPE_SEPARATOR,
         );
         let canonical_fqn = builder.canonical(scope_stack, symbol_kind.clone(), &name);
         let display_fqn = builder.display(scope_stack, symbol_kind.clone(), &name);
@@ -299,6 +302,11 @@ impl CudaParser {
             start_col: node.start_position().column,
             end_line: node.end_position().row + 1,
             end_col: node.end_position().column,
+            signature: None,
+            is_test: false,
+            visibility: None,
+            deprecated: false,
+            deprecation_message: None,
         })
     }
 
@@ -449,7 +457,7 @@ impl CudaParser {
         let builder = FqnBuilder::new(
             package_name.to_string(),
             file_path.to_string_lossy().to_string(),
-            ScopeSeparator::DoubleColon,
+            SCOPE_SEPARATOR,
         );
         let canonical_fqn = builder.canonical(scope_stack, symbol_kind.clone(), &name);
         let display_fqn = builder.display(scope_stack, symbol_kind.clone(), &name);
@@ -468,6 +476,11 @@ impl CudaParser {
             start_col: node.start_position().column,
             end_line: node.end_position().row + 1,
             end_col: node.end_position().column,
+            signature: None,
+            is_test: false,
+            visibility: None,
+            deprecated: false,
+            deprecation_message: None,
         })
     }
 
@@ -0,0 +1,277 @@
+//! Deprecation extraction for `find --deprecated`.
+//!
+//! Sets [`SymbolFact::deprecated`] and [`SymbolFact::deprecation_message`] at
+//! indexing time from the annotations attached to a declaration:
+//!
+//! | Language | Recognized marker | Recorded message |
+//! |----------|-------------------|------------------|
+//! | Rust | `#[deprecated]` attribute on the item | `#[deprecated = "..."]` or the `note = "..."` argument |
+//! | Java | `@Deprecated` annotation in the modifiers, or `@deprecated` in the Javadoc | Text after the Javadoc `@deprecated` tag |
+//! | JavaScript / TypeScript | `@deprecated` in the JSDoc comment | Text after the tag, up to the end of its line |
+//!
+//! Other languages are never marked deprecated.
+
+use super::{Language, SymbolFact};
+use crate::common::safe_slice;
+
+/// Record the deprecation status of the facts of one file
+pub fn mark_deprecated(
+    language: Option<Language>,
+    tree: Option<&tree_sitter::Tree>,
+    source: &[u8],
+    facts: &mut [SymbolFact],
+) {
+    let (Some(language), Some(tree)) = (language, tree) else {
+        return;
+    };
+    if !matches!(
+        language,
+        Language::Rust | Language::Java | Language::JavaScript | Language::TypeScript
+    ) {
+        return;
+    }
+
+    let root = tree.root_node();
+    for fact in facts.iter_mut() {
+        let Some(node) = root.descendant_for_byte_range(fact.byte_start, fact.byte_end) else {
+            continue;
+        };
+        if node.start_byte() != fact.byte_start || node.end_byte() != fact.byte_end {
+            continue;
+        }
+        let deprecation = match language {
+            Language::Rust => rust_deprecation(&node, source),
+            Language::Java => java_deprecation(&node, source),
+            _ => jsdoc_deprecation(&node, source),
+        };
+        if let Some(message) = deprecation {
+            fact.deprecated = true;
+            fact.deprecation_message = message;
+        }
+    }
+}
+
+fn node_text<'a>(node: &tree_sitter::Node, source: &'a [u8]) -> &'a str {
+    safe_slice(source, node.start_byte(), node.end_byte())
+        .and_then(|bytes| std::str::from_utf8(bytes).ok())
+        .unwrap_or("")
+}
+
+/// Siblings written directly before `node` that annotate it: attributes and
+/// comments, nearest first
+fn leading_siblings<'a>(node: &tree_sitter::Node<'a>) -> Vec<tree_sitter::Node<'a>> {
+    let mut siblings = Vec::new();
+    let mut current = node.prev_sibling();
+    while let Some(sibling) = current {
+        if !matches!(
+            sibling.kind(),
+            "attribute_item" | "line_comment" | "block_comment" | "comment"
+        ) {
+            break;
+        }
+        siblings.push(sibling);
+        current = sibling.prev_sibling();
+    }
+    siblings
+}
+
+/// First string literal in `text`, unquoted
+fn first_string_literal(text: &str) -> Option<String> {
+    let start = text.find('"')? + 1;
+    let mut message = String::new();
+    let mut chars = text[start..].chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => return Some(message),
+            '\\' => message.extend(chars.next()),
+            _ => message.push(c),
+        }
+    }
+    None
+}
+
+/// `Some(message)` when an outer `#[deprecated]` attribute precedes the item
+fn rust_deprecation(node: &tree_sitter::Node, source: &[u8]) -> Option<Option<String>> {
+    for sibling in leading_siblings(node) {
+        if sibling.kind() != "attribute_item" {
+            continue;
+        }
+        let text = node_text(&sibling, source);
+        let Some(inner) = text
+            .strip_prefix("#[")
+            .and_then(|rest| rest.strip_suffix(']'))
+        else {
+            continue;
+        };
+        let Some(args) = inner.trim().strip_prefix("deprecated") else {
+            continue;
+        };
+        let args = args.trim_start();
+        let message = if let Some(value) = args.strip_prefix('=') {
+            first_string_literal(value)
+        } else if args.starts_with('(') {
+            args.find("note")
+                .and_then(|at| first_string_literal(&args[at..]))
+        } else if args.is_empty() {
+            None
+        } else {
+            // `#[deprecated_foo]` or similar
+            continue;
+        };
+        return Some(message);
+    }
+    None
+}
+
+/// `Some(message)` for an `@Deprecated` annotation or Javadoc `@deprecated` tag
+fn java_deprecation(node: &tree_sitter::Node, source: &[u8]) -> Option<Option<String>> {
+    let message = doc_comment_deprecation(node, source);
+    if message.is_some() {
+        return message;
+    }
+
+    let mut cursor = node.walk();
+    let modifiers = node
+        .children(&mut cursor)
+        .find(|child| child.kind() == "modifiers")?;
+    let mut cursor = modifiers.walk();
+    let annotated = modifiers.children(&mut cursor).any(|child| {
+        matches!(child.kind(), "marker_annotation" | "annotation")
+            && child
+                .child_by_field_name("name")
+                .map(|name| node_text(&name, source))
+                .is_some_and(|name| name == "Deprecated" || name == "java.lang.Deprecated")
+    });
+    annotated.then_some(None)
+}
+
+/// `Some(message)` for a JSDoc `@deprecated` tag
+///
+/// Exported and variable declarations carry their comment on the enclosing
+/// statement, so the lookup starts there.
+fn jsdoc_deprecation(node: &tree_sitter::Node, source: &[u8]) -> Option<Option<String>> {
+    let mut target = *node;
+    while let Some(parent) = target.parent() {
+        if !matches!(
+            parent.kind(),
+            "export_statement" | "lexical_declaration" | "variable_declaration"
+        ) {
+            break;
+        }
+        target = parent;
+    }
+    doc_comment_deprecation(&target, source)
+}
+
+/// Look for a `@deprecated` tag in the `/** ... */` comment preceding `node`
+fn doc_comment_deprecation(node: &tree_sitter::Node, source: &[u8]) -> Option<Option<String>> {
+    let comment = leading_siblings(node)
+        .into_iter()
+        .find(|sibling| node_text(sibling, source).starts_with("/**"))?;
+    let text = node_text(&comment, source);
+    let at = text.find("@deprecated")?;
+    let rest = &text[at + "@deprecated".len()..];
+    let line = rest.lines().next().unwrap_or("");
+    let message = line.trim_end().trim_end_matches("*/").trim();
+    Some((!message.is_empty()).then(|| message.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ingest::pool;
+    use std::path::PathBuf;
+
+    fn rust_deprecations(source: &[u8]) -> Vec<(String, bool, Option<String>)> {
+        let tree = pool::with_parser(Language::Rust, |parser| parser.parse(source, None))
+            .unwrap()
+            .unwrap();
+        let mut facts = crate::ingest::Parser::extract_symbols_from_tree(
+            &tree,
+            PathBuf::from("lib.rs"),
+            source,
+        );
+        mark_deprecated(Some(Language::Rust), Some(&tree), source, &mut facts);
+        facts
+            .into_iter()
+            .map(|fact| {
+                (
+                    fact.name.unwrap_or_default(),
+                    fact.deprecated,
+                    fact.deprecation_message,
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_rust_deprecated_attribute_forms() {
+        let deprecations = rust_deprecations(
+            b"#[deprecated = \"use bar\"]\npub fn foo() {}\n\npub fn bar() {}\n\n#[deprecated]\n/// Old\nfn old() {}\n\n#[inline]\n#[deprecated(since = \"1.2\", note = \"use \\\"new\\\"\")]\nfn older() {}\n",
+        );
+        assert_eq!(
+            deprecations,
+            vec![
+                ("foo".to_string(), true, Some("use bar".to_string())),
+                ("bar".to_string(), false, None),
+                ("old".to_string(), true, None),
+                ("older".to_string(), true, Some("use \"new\"".to_string())),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_java_annotation_and_javadoc() {
+        let source = b"class A {\n    @Deprecated\n    public void a() {}\n\n    /** @deprecated use c */\n    public void b() {}\n\n    public void c() {}\n}\n";
+        let tree = pool::with_parser(Language::Java, |parser| parser.parse(source, None))
+            .unwrap()
+            .unwrap();
+        let mut facts = crate::ingest::java::JavaParser::extract_symbols_from_tree(
+            &tree,
+            PathBuf::from("A.java"),
+            source,
+        );
+        mark_deprecated(Some(Language::Java), Some(&tree), source, &mut facts);
+        let status = |name: &str| {
+            facts
+                .iter()
+                .find(|fact| fact.name.as_deref() == Some(name))
+                .map(|fact| (fact.deprecated, fact.deprecation_message.clone()))
+                .unwrap()
+        };
+        assert_eq!(status("a"), (true, None));
+        assert_eq!(status("b"), (true, Some("use c".to_string())));
+        assert_eq!(status("c"), (false, None));
+    }
+
+    #[test]
+    fn test_jsdoc_deprecated_tag() {
+        let source = b"/**\n * Old entry point.\n * @deprecated use start()\n */\nexport function run() {}\n\n/** Current entry point */\nfunction start() {}\n";
+        let tree = pool::with_parser(Language::JavaScript, |parser| parser.parse(source, None))
+            .unwrap()
+            .unwrap();
+        let mut facts = crate::ingest::javascript::JavaScriptParser::extract_symbols_from_tree(
+            &tree,
+            PathBuf::from("app.js"),
+            source,
+        );
+        mark_deprecated(Some(Language::JavaScript), Some(&tree), source, &mut facts);
+        let status: Vec<_> = facts
+            .iter()
+            .map(|fact| {
+                (
+                    fact.name.clone().unwrap_or_default(),
+                    fact.deprecated,
+                    fact.deprecation_message.clone(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            status,
+            vec![
+                ("run".to_string(), true, Some("use start()".to_string())),
+                ("start".to_string(), false, None),
+            ]
+        );
+    }
+}
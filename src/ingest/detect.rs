@@ -1,7 +1,8 @@
 //! Language detection from file extensions.
 //!
 //! Table-driven language detection. No heuristics, no guessing.
-//! Unknown extensions return None, never infer from content.
+//! Unknown extensions return None, never infer from content. The table is
+//! the parser registry, so extensions of registered custom parsers count.
 
 use std::path::Path;
 
@@ -26,6 +27,17 @@ pub enum Language {
     Go,
     /// CUDA (.cu, .cuh)
     Cuda,
+    /// Swift (.swift)
+    Swift,
+    /// Scala (.scala, .sc)
+    Scala,
+    /// C# (.cs)
+    CSharp,
+    /// A language added through the parser registry, identified by name
+    ///
+    /// Has no tree-sitter grammar: symbols come from its registered
+    /// [`LanguageParser`](super::registry::LanguageParser).
+    Custom(&'static str),
 }
 
 impl Language {
@@ -41,14 +53,49 @@ impl Language {
             Language::TypeScript => "typescript",
             Language::Go => "go",
             Language::Cuda => "cuda",
+            Language::Swift => "swift",
+            Language::Scala => "scala",
+            Language::CSharp => "csharp",
+            Language::Custom(name) => name,
         }
     }
+
+    /// Whether the language is parsed with a built-in tree-sitter grammar
+    pub fn has_grammar(&self) -> bool {
+        !matches!(self, Language::Custom(_))
+    }
+
+    /// Parse a language name as given to `--lang`.
+    ///
+    /// Accepts the [`as_str`](Self::as_str) identifiers and common short
+    /// names (`rs`, `py`, `c++`, `js`, `ts`, `cs`, `c#`), case-insensitively.
+    /// Scala is only accepted when its grammar is compiled in.
+    pub fn from_name(name: &str) -> Option<Language> {
+        let language = match name.to_ascii_lowercase().as_str() {
+            "rust" | "rs" => Language::Rust,
+            "python" | "py" => Language::Python,
+            "c" => Language::C,
+            "cpp" | "c++" | "cxx" => Language::Cpp,
+            "java" => Language::Java,
+            "javascript" | "js" => Language::JavaScript,
+            "typescript" | "ts" => Language::TypeScript,
+            "go" | "golang" => Language::Go,
+            "cuda" | "cu" => Language::Cuda,
+            "swift" => Language::Swift,
+            "csharp" | "cs" | "c#" => Language::CSharp,
+            "scala" => Language::Scala,
+            _ => return None,
+        };
+        Some(language)
+    }
 }
 
 /// Detect programming language from file path.
 ///
-/// Uses table-driven extension mapping. Returns None for unknown extensions.
-/// Never guesses or infers from file content.
+/// Uses the extension table of the parser registry: the built-in languages
+/// plus any parser added with
+/// [`register_parser`](super::registry::register_parser). Returns None for
+/// unknown extensions. Never guesses or infers from file content.
 ///
 /// # Examples
 ///
@@ -60,49 +107,59 @@ impl Language {
 /// assert_eq!(detect_language(Path::new("file.txt")), None);
 /// ```
 pub fn detect_language(path: &Path) -> Option<Language> {
-    // Get the file extension
-    let extension = path.extension()?.to_str()?;
-
-    // Table-driven mapping (case-sensitive)
-    let language = match extension {
-        // Rust
-        "rs" => Language::Rust,
-
-        // Python
-        "py" => Language::Python,
-
-        // C
-        "c" | "h" => Language::C,
-
-        // C++
-        "cpp" | "hpp" | "cc" | "cxx" | "hip" => Language::Cpp,
-
-        // Java
-        "java" => Language::Java,
-
-        // JavaScript
-        "js" | "mjs" | "cjs" => Language::JavaScript,
-
-        // TypeScript
-        "ts" | "tsx" => Language::TypeScript,
-
-        // Go
-        "go" => Language::Go,
-
-        // CUDA
-        "cu" | "cuh" => Language::Cuda,
-
-        // Unknown extension
-        _ => return None,
-    };
-
-    Some(language)
+    super::registry::parser_for_path(path).map(|parser| parser.language())
 }
 
+/// Extensions of the built-in languages (case-sensitive, without the dot)
+///
+/// Seeds the parser registry; see [`BuiltinParser`](super::registry::BuiltinParser).
+pub(crate) const BUILTIN_EXTENSIONS: &[(Language, &[&str])] = &[
+    (Language::Rust, &["rs"]),
+    (Language::Python, &["py"]),
+    (Language::C, &["c", "h"]),
+    (Language::Cpp, &["cpp", "hpp", "cc", "cxx", "hip"]),
+    (Language::Java, &["java"]),
+    (Language::JavaScript, &["js", "mjs", "cjs"]),
+    (Language::TypeScript, &["ts", "tsx"]),
+    (Language::Go, &["go"]),
+    (Language::Cuda, &["cu", "cuh"]),
+    (Language::Swift, &["swift"]),
+    (Language::CSharp, &["cs"]),
+    // Scala
+    (Language::Scala, &["scala", "sc"]),
+];
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_language_from_name() {
+        assert_eq!(Language::from_name("rust"), Some(Language::Rust));
+        assert_eq!(Language::from_name("Rust"), Some(Language::Rust));
+        assert_eq!(Language::from_name("py"), Some(Language::Python));
+        assert_eq!(Language::from_name("c++"), Some(Language::Cpp));
+        assert_eq!(Language::from_name("C#"), Some(Language::CSharp));
+        assert_eq!(Language::from_name("cobol"), None);
+
+        let all = [
+            Language::Rust,
+            Language::Python,
+            Language::C,
+            Language::Cpp,
+            Language::Java,
+            Language::JavaScript,
+            Language::TypeScript,
+            Language::Go,
+            Language::Cuda,
+            Language::Swift,
+            Language::CSharp,
+        ];
+        for language in all {
+            assert_eq!(Language::from_name(language.as_str()), Some(language));
+        }
+    }
+
     #[test]
     fn test_detect_rust() {
         assert_eq!(detect_language(Path::new("main.rs")), Some(Language::Rust));
@@ -183,6 +240,34 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_detect_swift() {
+        assert_eq!(
+            detect_language(Path::new("App.swift")),
+            Some(Language::Swift)
+        );
+    }
+
+    #[test]
+    fn test_detect_csharp() {
+        assert_eq!(
+            detect_language(Path::new("UserService.cs")),
+            Some(Language::CSharp)
+        );
+    }
+
+    #[test]
+    fn test_detect_scala() {
+        assert_eq!(
+            detect_language(Path::new("Main.scala")),
+            Some(Language::Scala)
+        );
+        assert_eq!(
+            detect_language(Path::new("build.sc")),
+            Some(Language::Scala)
+        );
+    }
+
     #[test]
     fn test_detect_cuda() {
         assert_eq!(
@@ -251,6 +336,9 @@ mod tests {
         assert_eq!(Language::JavaScript.as_str(), "javascript");
         assert_eq!(Language::TypeScript.as_str(), "typescript");
         assert_eq!(Language::Go.as_str(), "go");
+        assert_eq!(Language::Swift.as_str(), "swift");
+        assert_eq!(Language::Scala.as_str(), "scala");
+        assert_eq!(Language::CSharp.as_str(), "csharp");
         assert_eq!(Language::Cuda.as_str(), "cuda");
     }
 }
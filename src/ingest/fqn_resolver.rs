@@ -128,6 +128,11 @@ mod tests {
             start_col: 0,
             end_line: 1,
             end_col: 1,
+            signature: None,
+            is_test: false,
+            visibility: None,
+            deprecated: false,
+            deprecation_message: None,
             fqn: None,
             canonical_fqn: None,
             display_fqn: None,
@@ -4,7 +4,7 @@
 //! has to supply language-specific node kinds and name-extraction closures.
 
 use crate::ingest::{build_fqn_map, resolve_qualified_symbol, SymbolFact, SymbolKind};
-use crate::references::{CallFact, ReferenceFact};
+use crate::references::{CallFact, ReferenceFact, ReferenceKind};
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 
@@ -249,6 +249,8 @@ fn walk_tree_for_references(
                         start_col: node.start_position().column,
                         end_line: node.end_position().row + 1,
                         end_col: node.end_position().column,
+                        in_symbol: None,
+                        kind: ReferenceKind::Usage,
                     });
                 }
             }
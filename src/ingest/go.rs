@@ -4,11 +4,14 @@
 
 use crate::common::safe_slice;
 use crate::graph::canonical_fqn::FqnBuilder;
-use crate::ingest::{ScopeSeparator, ScopeStack, SymbolFact, SymbolKind};
+use crate::ingest::{Language, ScopeSeparator, ScopeStack, SymbolFact, SymbolKind};
 use crate::references::{CallFact, ReferenceFact};
 use anyhow::Result;
 use std::path::{Path, PathBuf};
 
+/// FQN separator for Go scopes
+const SCOPE_SEPARATOR: ScopeSeparator = ScopeSeparator::for_language(Language::Go);
+
 /// Parser that extracts symbol facts from Go source code.
 ///
 /// Pure function: Input (path, contents) → Output `Vec<SymbolFact>`
@@ -51,7 +54,7 @@ impl GoParser {
 
         let root_node = tree.root_node();
         let mut facts = Vec::new();
-        let mut scope_stack = ScopeStack::new(ScopeSeparator::Dot);
+        let mut scope_stack = ScopeStack::new(SCOPE_SEPARATOR);
 
         // Find package declaration first
         let mut cursor = root_node.walk();
@@ -166,7 +169,7 @@ impl GoParser {
         let builder = FqnBuilder::new(
             ".".to_string(),
             file_path.to_string_lossy().to_string(),
-            ScopeSeparator::Dot,
+            SCOPE_SEPARATOR,
         );
         let canonical_fqn = builder.canonical(scope_stack, symbol_kind.clone(), &name);
         let display_fqn = builder.display(scope_stack, symbol_kind.clone(), &name);
@@ -185,6 +188,11 @@ impl GoParser {
             start_col: node.start_position().column,
             end_line: node.end_position().row + 1,
             end_col: node.end_position().column,
+            signature: None,
+            is_test: false,
+            visibility: None,
+            deprecated: false,
+            deprecation_message: None,
         })
     }
 
@@ -210,7 +218,7 @@ impl GoParser {
         let builder = FqnBuilder::new(
             ".".to_string(),
             file_path.to_string_lossy().to_string(),
-            ScopeSeparator::Dot,
+            SCOPE_SEPARATOR,
         );
         let canonical_fqn = builder.canonical(scope_stack, symbol_kind.clone(), &name);
         let display_fqn = builder.display(scope_stack, symbol_kind.clone(), &name);
@@ -229,6 +237,11 @@ impl GoParser {
             start_col: node.start_position().column,
             end_line: node.end_position().row + 1,
             end_col: node.end_position().column,
+            signature: None,
+            is_test: false,
+            visibility: None,
+            deprecated: false,
+            deprecation_message: None,
         })
     }
 
@@ -268,7 +281,7 @@ impl GoParser {
     ) -> Vec<SymbolFact> {
         let root_node = tree.root_node();
         let mut facts = Vec::new();
-        let mut scope_stack = ScopeStack::new(ScopeSeparator::Dot);
+        let mut scope_stack = ScopeStack::new(SCOPE_SEPARATOR);
 
         // Find package declaration first
         let mut cursor = root_node.walk();
@@ -0,0 +1,330 @@
+//! Incremental re-parsing for watch mode
+//!
+//! Keeps the last parse tree and source bytes per file. When a file changes,
+//! the byte diff against the cached source is applied with `Tree::edit` and
+//! the new source is parsed with the edited tree, so tree-sitter reuses the
+//! unchanged subtrees. The resulting tree is identical to a full reparse.
+//!
+//! The cache is an LRU bounded by entry count. Entries are dropped when the
+//! file is deleted.
+
+use anyhow::Result;
+use std::time::Duration;
+use tree_sitter::{InputEdit, Point, Tree};
+
+use crate::graph::cache::LruCache;
+use crate::ingest::detect::Language;
+use crate::ingest::pool;
+
+/// Default number of files whose trees are kept between watch cycles
+pub const DEFAULT_TREE_CACHE_CAPACITY: usize = 256;
+
+/// Last parse of one file
+struct CachedTree {
+    language: Language,
+    source: Vec<u8>,
+    tree: Tree,
+}
+
+/// Bounded per-file cache of parse trees for incremental re-parsing
+pub struct ParseTreeCache {
+    trees: LruCache<String, CachedTree>,
+}
+
+impl ParseTreeCache {
+    /// Create a cache holding at most `capacity` files
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            trees: LruCache::new(capacity.max(1)),
+        }
+    }
+
+    /// Parse `source`, reusing the cached tree for `path` when there is one
+    ///
+    /// Falls back to a full parse when the file is not cached or was cached
+    /// under a different language. The new tree replaces the cached entry.
+    pub fn parse(&mut self, path: &str, language: Language, source: &[u8]) -> Result<Option<Tree>> {
+        self.parse_with_timeout(path, language, source, None)
+    }
+
+    /// [`parse`](Self::parse) that gives up after `timeout`
+    ///
+    /// A timed-out parse fails with [`pool::ParseTimeout`] and drops the
+    /// cached entry for `path`.
+    pub fn parse_with_timeout(
+        &mut self,
+        path: &str,
+        language: Language,
+        source: &[u8],
+        timeout: Option<Duration>,
+    ) -> Result<Option<Tree>> {
+        let key = path.to_string();
+        let previous = match self.trees.get(&key) {
+            Some(cached) if cached.language == language => {
+                let mut old_tree = cached.tree.clone();
+                match source_edit(&cached.source, source) {
+                    Some(edit) => old_tree.edit(&edit),
+                    // Unchanged bytes: the cached tree is already current
+                    None => return Ok(Some(old_tree)),
+                }
+                Some(old_tree)
+            }
+            _ => None,
+        };
+
+        let tree = match pool::with_parser(language, |parser| {
+            pool::parse_with_timeout(parser, source, previous.as_ref(), timeout)
+        })? {
+            Ok(tree) => tree,
+            Err(timeout) => {
+                self.trees.invalidate(&key);
+                return Err(timeout.into());
+            }
+        };
+        match &tree {
+            Some(tree) => self.trees.put(
+                key,
+                CachedTree {
+                    language,
+                    source: source.to_vec(),
+                    tree: tree.clone(),
+                },
+            ),
+            None => self.trees.invalidate(&key),
+        }
+        Ok(tree)
+    }
+
+    /// Drop the cached tree for `path`
+    pub fn remove(&mut self, path: &str) {
+        self.trees.invalidate(&path.to_string());
+    }
+
+    /// Position of the first syntax error in the cached tree for `path`
+    ///
+    /// Tree-sitter recovers from errors, so a file with a syntax error is
+    /// still indexed; this reports where the recovery happened.
+    pub fn first_syntax_error(&mut self, path: &str) -> Option<Point> {
+        let cached = self.trees.get(&path.to_string())?;
+        first_error_point(cached.tree.root_node())
+    }
+
+    /// Whether a tree is cached for `path`
+    pub fn contains(&self, path: &str) -> bool {
+        self.trees.contains(&path.to_string())
+    }
+
+    /// Number of cached files
+    pub fn len(&self) -> usize {
+        self.trees.stats().size
+    }
+
+    /// Whether no files are cached
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Start of the first ERROR or MISSING node under `node`, in document order
+fn first_error_point(node: tree_sitter::Node) -> Option<Point> {
+    if node.is_error() || node.is_missing() {
+        return Some(node.start_position());
+    }
+    if !node.has_error() {
+        return None;
+    }
+    let mut cursor = node.walk();
+    let children: Vec<_> = node.children(&mut cursor).collect();
+    children.into_iter().find_map(first_error_point)
+}
+
+/// Describe the change from `old` to `new` as a single tree-sitter edit
+///
+/// The edited range spans from the first differing byte to the last one,
+/// found by trimming the common prefix and suffix. Returns `None` when the
+/// sources are identical.
+pub fn source_edit(old: &[u8], new: &[u8]) -> Option<InputEdit> {
+    if old == new {
+        return None;
+    }
+
+    let prefix = old.iter().zip(new).take_while(|(a, b)| a == b).count();
+    let max_suffix = old.len().min(new.len()) - prefix;
+    let suffix = old
+        .iter()
+        .rev()
+        .zip(new.iter().rev())
+        .take(max_suffix)
+        .take_while(|(a, b)| a == b)
+        .count();
+
+    let old_end_byte = old.len() - suffix;
+    let new_end_byte = new.len() - suffix;
+    Some(InputEdit {
+        start_byte: prefix,
+        old_end_byte,
+        new_end_byte,
+        start_position: point_at(old, prefix),
+        old_end_position: point_at(old, old_end_byte),
+        new_end_position: point_at(new, new_end_byte),
+    })
+}
+
+/// Row and byte column of `byte` in `source`
+fn point_at(source: &[u8], byte: usize) -> Point {
+    let before = &source[..byte];
+    let row = before.iter().filter(|&&b| b == b'\n').count();
+    let column = match before.iter().rposition(|&b| b == b'\n') {
+        Some(newline) => byte - newline - 1,
+        None => byte,
+    };
+    Point { row, column }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ingest::Parser;
+    use std::path::PathBuf;
+
+    const BASE: &str = "mod shapes {\n    pub struct Circle { r: f64 }\n\n    impl Circle {\n        pub fn area(&self) -> f64 { 3.14 * self.r * self.r }\n    }\n}\n\nfn main() {\n    let c = shapes::Circle { r: 1.0 };\n    println!(\"{}\", c.area());\n}\n";
+
+    const SNIPPETS: [&str; 10] = [
+        "fn extra() {}\n",
+        "struct Point { x: i32, y: i32 }\n",
+        "x",
+        "{",
+        "}",
+        "\n",
+        "// comment\n",
+        "pub ",
+        "é",
+        "trait Shape { fn area(&self) -> f64; }\n",
+    ];
+
+    /// Deterministic linear congruential generator so failures reproduce
+    struct Lcg(u64);
+
+    impl Lcg {
+        fn next(&mut self, bound: usize) -> usize {
+            self.0 = self
+                .0
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            ((self.0 >> 33) as usize) % bound.max(1)
+        }
+    }
+
+    fn random_edit(rng: &mut Lcg, source: &[u8]) -> Vec<u8> {
+        let start = rng.next(source.len() + 1);
+        let end = (start + rng.next(12)).min(source.len());
+        let insert: &[u8] = match rng.next(3) {
+            0 => b"",
+            _ => SNIPPETS[rng.next(SNIPPETS.len())].as_bytes(),
+        };
+        let mut edited = source[..start].to_vec();
+        edited.extend_from_slice(insert);
+        edited.extend_from_slice(&source[end..]);
+        edited
+    }
+
+    fn full_parse(source: &[u8]) -> Tree {
+        pool::with_parser(Language::Rust, |parser| parser.parse(source, None))
+            .unwrap()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_source_edit_identical_is_none() {
+        assert!(source_edit(b"fn a() {}", b"fn a() {}").is_none());
+    }
+
+    #[test]
+    fn test_source_edit_positions() {
+        let old = b"fn a() {}\nfn b() {}\n";
+        let new = b"fn a() {}\nfn bee() {}\n";
+        let edit = source_edit(old, new).unwrap();
+        assert_eq!(edit.start_byte, 14);
+        assert_eq!(edit.old_end_byte, 14);
+        assert_eq!(edit.new_end_byte, 16);
+        assert_eq!(edit.start_position, Point { row: 1, column: 4 });
+        assert_eq!(edit.new_end_position, Point { row: 1, column: 6 });
+
+        // Repeated bytes must not let prefix and suffix overlap
+        let edit = source_edit(b"aaa", b"aaaa").unwrap();
+        assert_eq!(edit.start_byte, 3);
+        assert_eq!(edit.old_end_byte, 3);
+        assert_eq!(edit.new_end_byte, 4);
+    }
+
+    #[test]
+    fn test_incremental_matches_full_reparse_on_random_edits() {
+        let path = "src/shapes.rs";
+        let mut cache = ParseTreeCache::new(4);
+        let mut rng = Lcg(0x5eed);
+        let mut source = BASE.as_bytes().to_vec();
+        cache.parse(path, Language::Rust, &source).unwrap();
+
+        for step in 0..200 {
+            source = random_edit(&mut rng, &source);
+
+            let incremental = cache.parse(path, Language::Rust, &source).unwrap().unwrap();
+            let full = full_parse(&source);
+            assert_eq!(
+                incremental.root_node().to_sexp(),
+                full.root_node().to_sexp(),
+                "tree mismatch at step {}",
+                step
+            );
+
+            let from_incremental =
+                Parser::extract_symbols_from_tree(&incremental, PathBuf::from(path), &source);
+            let from_full = Parser::extract_symbols_from_tree(&full, PathBuf::from(path), &source);
+            assert_eq!(
+                from_incremental, from_full,
+                "symbol mismatch at step {}",
+                step
+            );
+        }
+    }
+
+    #[test]
+    fn test_cache_is_bounded() {
+        let mut cache = ParseTreeCache::new(2);
+        for path in ["a.rs", "b.rs", "c.rs"] {
+            cache.parse(path, Language::Rust, b"fn f() {}").unwrap();
+        }
+        assert_eq!(cache.len(), 2);
+        assert!(!cache.contains("a.rs"), "least recently used entry evicted");
+        assert!(cache.contains("c.rs"));
+
+        cache.remove("c.rs");
+        assert!(!cache.contains("c.rs"));
+        assert_eq!(cache.len(), 1);
+    }
+
+    #[test]
+    fn test_language_change_forces_full_parse() {
+        let mut cache = ParseTreeCache::new(2);
+        cache.parse("x", Language::Rust, b"fn f() {}").unwrap();
+        let tree = cache
+            .parse("x", Language::Python, b"def f():\n    pass\n")
+            .unwrap()
+            .unwrap();
+        assert_eq!(tree.root_node().kind(), "module");
+    }
+
+    #[test]
+    fn test_first_syntax_error() {
+        let mut cache = ParseTreeCache::new(2);
+        cache.parse("ok.rs", Language::Rust, b"fn f() {}").unwrap();
+        assert_eq!(cache.first_syntax_error("ok.rs"), None);
+
+        cache
+            .parse("bad.rs", Language::Rust, b"fn ok() {}\nfn broken( {\n")
+            .unwrap();
+        let point = cache.first_syntax_error("bad.rs").unwrap();
+        assert_eq!(point.row, 1);
+        assert_eq!(cache.first_syntax_error("missing.rs"), None);
+    }
+}
@@ -4,12 +4,15 @@
 
 use crate::common::safe_slice;
 use crate::graph::canonical_fqn::FqnBuilder;
-use crate::ingest::{ScopeSeparator, ScopeStack, SymbolFact, SymbolKind};
+use crate::ingest::{ImplRelation, Language, ScopeSeparator, ScopeStack, SymbolFact, SymbolKind};
 use crate::references::{CallFact, ReferenceFact};
 use anyhow::Result;
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 
+/// FQN separator for Java scopes
+const SCOPE_SEPARATOR: ScopeSeparator = ScopeSeparator::for_language(Language::Java);
+
 /// Parser that extracts symbol facts from Java source code.
 ///
 /// Pure function: Input (path, contents) → Output `Vec<SymbolFact>`
@@ -52,7 +55,7 @@ impl JavaParser {
 
         let root_node = tree.root_node();
         let mut facts = Vec::new();
-        let mut scope_stack = ScopeStack::new(ScopeSeparator::Dot);
+        let mut scope_stack = ScopeStack::new(SCOPE_SEPARATOR);
 
         // Find package declaration first (it comes first in the file)
         let mut pkg_name = String::new();
@@ -195,7 +198,7 @@ impl JavaParser {
         let builder = FqnBuilder::new(
             String::new(),
             file_path.to_string_lossy().to_string(),
-            ScopeSeparator::Dot,
+            SCOPE_SEPARATOR,
         );
         let canonical_fqn = builder.canonical(scope_stack, symbol_kind.clone(), &name);
         let display_fqn = builder.display(scope_stack, symbol_kind.clone(), &name);
@@ -214,6 +217,11 @@ impl JavaParser {
             start_col: node.start_position().column,
             end_line: node.end_position().row + 1,
             end_col: node.end_position().column,
+            signature: None,
+            is_test: false,
+            visibility: None,
+            deprecated: false,
+            deprecation_message: None,
         })
     }
 
@@ -269,7 +277,7 @@ impl JavaParser {
 
         let root_node = tree.root_node();
         let mut facts = Vec::new();
-        let mut scope_stack = ScopeStack::new(ScopeSeparator::Dot);
+        let mut scope_stack = ScopeStack::new(SCOPE_SEPARATOR);
 
         // Find package declaration first (it comes first in the file)
         let mut pkg_name = String::new();
@@ -321,7 +329,7 @@ impl JavaParser {
     ) -> Vec<SymbolFact> {
         let root_node = tree.root_node();
         let mut facts = Vec::new();
-        let mut scope_stack = ScopeStack::new(ScopeSeparator::Dot);
+        let mut scope_stack = ScopeStack::new(SCOPE_SEPARATOR);
         let mut pkg_name = String::new();
         let mut cursor = root_node.walk();
         for child in root_node.children(&mut cursor) {
@@ -459,7 +467,7 @@ impl JavaParser {
         let builder = FqnBuilder::new(
             String::new(),
             file_path.to_string_lossy().to_string(),
-            ScopeSeparator::Dot,
+            SCOPE_SEPARATOR,
         );
         let canonical_fqn = builder.canonical(scope_stack, symbol_kind.clone(), &name);
         let display_fqn = builder.display(scope_stack, symbol_kind.clone(), &name);
@@ -478,6 +486,11 @@ impl JavaParser {
             start_col: node.start_position().column,
             end_line: node.end_position().row + 1,
             end_col: node.end_position().column,
+            signature: None,
+            is_test: false,
+            visibility: None,
+            deprecated: false,
+            deprecation_message: None,
         })
     }
 
@@ -598,6 +611,91 @@ impl JavaParser {
         )
     }
 
+    /// Extract `extends` / `implements` relations from a pre-parsed tree.
+    ///
+    /// Classes, enums and records yield one relation per superclass and per
+    /// implemented interface; interfaces yield one per extended interface.
+    pub fn extract_impl_relations_from_tree(
+        tree: &tree_sitter::Tree,
+        source: &[u8],
+        file_path: &Path,
+    ) -> Vec<ImplRelation> {
+        fn text(node: tree_sitter::Node, source: &[u8]) -> Option<String> {
+            let bytes = safe_slice(source, node.start_byte(), node.end_byte())?;
+            std::str::from_utf8(bytes).ok().map(|s| s.to_string())
+        }
+
+        fn type_list<'t>(node: tree_sitter::Node<'t>, out: &mut Vec<tree_sitter::Node<'t>>) {
+            let mut cursor = node.walk();
+            for child in node.named_children(&mut cursor) {
+                if child.kind() == "type_list" {
+                    let mut inner = child.walk();
+                    out.extend(child.named_children(&mut inner));
+                }
+            }
+        }
+
+        fn walk(
+            node: tree_sitter::Node,
+            source: &[u8],
+            file_path: &str,
+            relations: &mut Vec<ImplRelation>,
+        ) {
+            let mut supertypes = Vec::new();
+            match node.kind() {
+                "class_declaration" | "enum_declaration" | "record_declaration" => {
+                    if let Some(superclass) = node.child_by_field_name("superclass") {
+                        supertypes.extend(superclass.named_child(0));
+                    }
+                    if let Some(interfaces) = node.child_by_field_name("interfaces") {
+                        type_list(interfaces, &mut supertypes);
+                    }
+                }
+                "interface_declaration" => {
+                    let mut cursor = node.walk();
+                    for child in node.named_children(&mut cursor) {
+                        if child.kind() == "extends_interfaces" {
+                            type_list(child, &mut supertypes);
+                        }
+                    }
+                }
+                _ => {}
+            }
+
+            if !supertypes.is_empty() {
+                if let Some(type_name) = node
+                    .child_by_field_name("name")
+                    .and_then(|n| text(n, source))
+                {
+                    for supertype in supertypes {
+                        relations.push(ImplRelation {
+                            type_name: type_name.clone(),
+                            trait_name: text(supertype, source),
+                            file_path: file_path.to_string(),
+                            byte_start: node.start_byte(),
+                            byte_end: node.end_byte(),
+                            start_line: node.start_position().row + 1,
+                            end_line: node.end_position().row + 1,
+                        });
+                    }
+                }
+            }
+
+            for child in node.children(&mut node.walk()) {
+                walk(child, source, file_path, relations);
+            }
+        }
+
+        let mut relations = Vec::new();
+        walk(
+            tree.root_node(),
+            source,
+            &file_path.to_string_lossy(),
+            &mut relations,
+        );
+        relations
+    }
+
     fn extract_method_name_static(node: &tree_sitter::Node, source: &[u8]) -> Option<String> {
         let mut cursor = node.walk();
         for child in node.children(&mut cursor) {
@@ -1045,4 +1143,33 @@ class Outer {
             "com.example.Outer.Inner.method"
         );
     }
+
+    #[test]
+    fn test_extract_impl_relations() {
+        let mut parser = JavaParser::new().unwrap();
+        let source =
+            b"class Square extends Shape implements Comparable<Square>, java.io.Serializable {}\n\
+            interface Named extends HasName, HasId {}\n\
+            enum Color implements Named {}\n";
+        let tree = parser.parser.parse(source, None).unwrap();
+        let relations =
+            JavaParser::extract_impl_relations_from_tree(&tree, source, Path::new("Shapes.java"));
+
+        let pairs: Vec<(&str, &str)> = relations
+            .iter()
+            .map(|r| (r.type_name.as_str(), r.trait_name.as_deref().unwrap()))
+            .collect();
+        assert_eq!(
+            pairs,
+            vec![
+                ("Square", "Shape"),
+                ("Square", "Comparable<Square>"),
+                ("Square", "java.io.Serializable"),
+                ("Named", "HasName"),
+                ("Named", "HasId"),
+                ("Color", "Named"),
+            ]
+        );
+        assert_eq!(relations[5].start_line, 3);
+    }
 }
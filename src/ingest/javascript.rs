@@ -4,11 +4,14 @@
 
 use crate::common::safe_slice;
 use crate::graph::canonical_fqn::FqnBuilder;
-use crate::ingest::{ScopeSeparator, ScopeStack, SymbolFact, SymbolKind};
+use crate::ingest::{Language, ScopeSeparator, ScopeStack, SymbolFact, SymbolKind};
 use crate::references::{CallFact, ReferenceFact};
 use anyhow::Result;
 use std::path::{Path, PathBuf};
 
+/// FQN separator for JavaScript scopes
+const SCOPE_SEPARATOR: ScopeSeparator = ScopeSeparator::for_language(Language::JavaScript);
+
 /// Parser that extracts symbol facts from JavaScript source code.
 ///
 /// Pure function: Input (path, contents) → Output `Vec<SymbolFact>`
@@ -51,7 +54,7 @@ impl JavaScriptParser {
 
         let root_node = tree.root_node();
         let mut facts = Vec::new();
-        let mut scope_stack = ScopeStack::new(ScopeSeparator::Dot);
+        let mut scope_stack = ScopeStack::new(SCOPE_SEPARATOR);
 
         // Use "." as project_root placeholder per decision FQN-17
         let package_name = ".";
@@ -173,7 +176,7 @@ impl JavaScriptParser {
         let builder = FqnBuilder::new(
             package_name.to_string(),
             file_path.to_string_lossy().to_string(),
-            ScopeSeparator::Dot,
+            SCOPE_SEPARATOR,
         );
         let canonical_fqn = builder.canonical(scope_stack, symbol_kind.clone(), &name);
         let display_fqn = builder.display(scope_stack, symbol_kind.clone(), &name);
@@ -192,6 +195,11 @@ impl JavaScriptParser {
             start_col: node.start_position().column,
             end_line: node.end_position().row + 1,
             end_col: node.end_position().column,
+            signature: None,
+            is_test: false,
+            visibility: None,
+            deprecated: false,
+            deprecation_message: None,
         })
     }
 
@@ -229,7 +237,7 @@ impl JavaScriptParser {
 
         let root_node = tree.root_node();
         let mut facts = Vec::new();
-        let mut scope_stack = ScopeStack::new(ScopeSeparator::Dot);
+        let mut scope_stack = ScopeStack::new(SCOPE_SEPARATOR);
 
         // Use "." as project_root placeholder per decision FQN-17
         let package_name = ".";
@@ -257,7 +265,7 @@ impl JavaScriptParser {
     ) -> Vec<SymbolFact> {
         let root_node = tree.root_node();
         let mut facts = Vec::new();
-        let mut scope_stack = ScopeStack::new(ScopeSeparator::Dot);
+        let mut scope_stack = ScopeStack::new(SCOPE_SEPARATOR);
         let package_name = ".";
         Self::walk_tree_with_scope_static(
             &root_node,
@@ -376,7 +384,7 @@ impl JavaScriptParser {
         let builder = FqnBuilder::new(
             package_name.to_string(),
             file_path.to_string_lossy().to_string(),
-            ScopeSeparator::Dot,
+            SCOPE_SEPARATOR,
         );
         let canonical_fqn = builder.canonical(scope_stack, symbol_kind.clone(), &name);
         let display_fqn = builder.display(scope_stack, symbol_kind.clone(), &name);
@@ -395,6 +403,11 @@ impl JavaScriptParser {
             start_col: node.start_position().column,
             end_line: node.end_position().row + 1,
             end_col: node.end_position().column,
+            signature: None,
+            is_test: false,
+            visibility: None,
+            deprecated: false,
+            deprecation_message: None,
         })
     }
 
@@ -1,21 +1,32 @@
+pub mod api_surface;
 pub mod c;
 pub mod cpp;
+pub mod csharp;
 pub mod cuda;
+pub mod deprecation;
 pub mod detect;
 pub mod fqn_resolver;
 pub mod generic_extraction;
 pub mod go;
 pub mod imports;
+pub mod incremental;
 pub mod java;
 pub mod javascript;
 pub mod pool;
 pub mod python;
+pub mod registry;
+pub mod scala;
+pub mod swift;
+pub mod test_code;
 pub mod typescript;
+pub mod visibility;
 
 // Re-exports from detect module
 pub use detect::{detect_language, Language};
 // Re-exports from imports module
 pub use imports::{ImportFact, ImportKind};
+// Re-exports from registry module
+pub use registry::{register_parser, LanguageParser};
 // Re-exports from fqn_resolver module
 pub use fqn_resolver::{build_fqn_map, resolve_qualified_symbol};
 
@@ -72,6 +83,9 @@ pub enum SymbolKind {
     /// Type alias
     /// Covers: TypeScript type, Rust type alias
     TypeAlias,
+    /// Macro definition
+    /// Covers: Rust `macro_rules!`
+    Macro,
     /// Unknown symbol type
     Unknown,
 }
@@ -91,6 +105,7 @@ impl SymbolKind {
             6 => SymbolKind::Union,
             7 => SymbolKind::Namespace,
             8 => SymbolKind::TypeAlias,
+            9 => SymbolKind::Macro,
             _ => SymbolKind::Unknown,
         }
     }
@@ -111,6 +126,7 @@ impl SymbolKind {
             "Union" => Some(SymbolKind::Union),
             "Namespace" => Some(SymbolKind::Namespace),
             "TypeAlias" => Some(SymbolKind::TypeAlias),
+            "Macro" => Some(SymbolKind::Macro),
             "Unknown" => Some(SymbolKind::Unknown),
             // Normalized keys
             "fn" => Some(SymbolKind::Function),
@@ -122,6 +138,7 @@ impl SymbolKind {
             "union" => Some(SymbolKind::Union),
             "namespace" => Some(SymbolKind::Namespace),
             "type_alias" => Some(SymbolKind::TypeAlias),
+            "macro" => Some(SymbolKind::Macro),
             "unknown" => Some(SymbolKind::Unknown),
             _ => None,
         }
@@ -129,15 +146,37 @@ impl SymbolKind {
 }
 
 /// Separator character for FQN construction per language
+///
+/// Applies to `fqn` and `display_fqn`. `canonical_fqn` always uses `::`
+/// between crate, file path, and kind, since it is an identity key rather
+/// than a language-level name.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ScopeSeparator {
-    /// Rust, C, C++ use :: separator
+    /// Rust, C, C++, CUDA use :: separator
     DoubleColon,
-    /// Python, Java, JavaScript, TypeScript use . separator
+    /// Python, Java, JavaScript, TypeScript, Go, Swift, Scala, C# use . separator
     Dot,
 }
 
 impl ScopeSeparator {
+    /// Separator used by a language's own qualified-name syntax
+    pub const fn for_language(language: Language) -> Self {
+        match language {
+            Language::Rust | Language::C | Language::Cpp | Language::Cuda => {
+                ScopeSeparator::DoubleColon
+            }
+            Language::Python
+            | Language::Java
+            | Language::JavaScript
+            | Language::TypeScript
+            | Language::Go
+            | Language::Swift
+            | Language::Scala
+            | Language::CSharp => ScopeSeparator::Dot,
+            Language::Custom(_) => ScopeSeparator::DoubleColon,
+        }
+    }
+
     pub fn as_str(&self) -> &'static str {
         match self {
             ScopeSeparator::DoubleColon => "::",
@@ -260,6 +299,7 @@ impl SymbolKind {
             SymbolKind::Union => "union",
             SymbolKind::Namespace => "namespace",
             SymbolKind::TypeAlias => "type_alias",
+            SymbolKind::Macro => "macro",
             SymbolKind::Unknown => "unknown",
         }
     }
@@ -305,8 +345,29 @@ pub struct SymbolFact {
     pub end_line: usize,
     /// Column where symbol ends (0-indexed, bytes)
     pub end_col: usize,
+    /// Declaration header (e.g. `pub fn parse(input: &str) -> Result<Ast>`)
+    ///
+    /// Only populated by API-surface indexing (`--api-only`).
+    #[serde(default)]
+    pub signature: Option<String>,
+    /// Whether the symbol is test code (see `ingest::test_code`)
+    #[serde(default)]
+    pub is_test: bool,
+    /// Declared visibility (see `ingest::visibility`); `None` when the
+    /// language has no visibility model or it could not be determined
+    #[serde(default)]
+    pub visibility: Option<String>,
+    /// Whether the symbol is marked deprecated (see `ingest::deprecation`)
+    #[serde(default)]
+    pub deprecated: bool,
+    /// Note attached to the deprecation (e.g. `use bar`), if any
+    #[serde(default)]
+    pub deprecation_message: Option<String>,
 }
 
+/// FQN separator for Rust scopes
+const RUST_SCOPE_SEPARATOR: ScopeSeparator = ScopeSeparator::for_language(Language::Rust);
+
 /// Parser that extracts symbol facts from Rust source code
 ///
 /// Pure function: Input (path, contents) → Output `Vec<SymbolFact>`
@@ -376,7 +437,7 @@ impl Parser {
 
         let root_node = tree.root_node();
         let mut facts = Vec::new();
-        let mut scope_stack = ScopeStack::new(ScopeSeparator::DoubleColon);
+        let mut scope_stack = ScopeStack::new(RUST_SCOPE_SEPARATOR);
 
         // Walk tree with scope tracking
         self.walk_tree_with_scope(
@@ -412,7 +473,7 @@ impl Parser {
 
         let root_node = tree.root_node();
         let mut facts = Vec::new();
-        let mut scope_stack = ScopeStack::new(ScopeSeparator::DoubleColon);
+        let mut scope_stack = ScopeStack::new(RUST_SCOPE_SEPARATOR);
 
         // Walk tree with scope tracking
         Self::walk_tree_with_scope_static(
@@ -450,7 +511,7 @@ impl Parser {
 
         let root_node = tree.root_node();
         let mut facts = Vec::new();
-        let mut scope_stack = ScopeStack::new(ScopeSeparator::DoubleColon);
+        let mut scope_stack = ScopeStack::new(RUST_SCOPE_SEPARATOR);
 
         // Walk tree with scope tracking
         Self::walk_tree_with_scope_static(
@@ -490,7 +551,7 @@ impl Parser {
                     let builder = FqnBuilder::new(
                         crate_name.to_string(),
                         file_path.to_string_lossy().to_string(),
-                        ScopeSeparator::DoubleColon,
+                        RUST_SCOPE_SEPARATOR,
                     );
                     let canonical_fqn = builder.canonical(scope_stack, symbol_kind.clone(), &name);
                     let display_fqn = builder.display(scope_stack, symbol_kind.clone(), &name);
@@ -509,6 +570,11 @@ impl Parser {
                         start_col: node.start_position().column,
                         end_line: node.end_position().row + 1,
                         end_col: node.end_position().column,
+                        signature: None,
+                        is_test: false,
+                        visibility: None,
+                        deprecated: false,
+                        deprecation_message: None,
                     });
 
                     // Recurse into children (they're in this module's scope)
@@ -559,7 +625,7 @@ impl Parser {
                     let builder = FqnBuilder::new(
                         crate_name.to_string(),
                         file_path.to_string_lossy().to_string(),
-                        ScopeSeparator::DoubleColon,
+                        RUST_SCOPE_SEPARATOR,
                     );
                     let canonical_fqn = builder.canonical(scope_stack, symbol_kind.clone(), &name);
                     let display_fqn = builder.display(scope_stack, symbol_kind.clone(), &name);
@@ -578,6 +644,11 @@ impl Parser {
                         start_col: node.start_position().column,
                         end_line: node.end_position().row + 1,
                         end_col: node.end_position().column,
+                        signature: None,
+                        is_test: false,
+                        visibility: None,
+                        deprecated: false,
+                        deprecation_message: None,
                     });
 
                     let mut cursor = node.walk();
@@ -638,6 +709,7 @@ impl Parser {
             "function_item" | "function_signature_item" => SymbolKind::Function,
             "struct_item" => SymbolKind::Class,
             "enum_item" => SymbolKind::Enum,
+            "macro_definition" => SymbolKind::Macro,
             _ => return None,
         };
 
@@ -651,7 +723,7 @@ impl Parser {
         let builder = FqnBuilder::new(
             crate_name.to_string(),
             file_path.to_string_lossy().to_string(),
-            ScopeSeparator::DoubleColon,
+            RUST_SCOPE_SEPARATOR,
         );
         let canonical_fqn = builder.canonical(scope_stack, symbol_kind.clone(), &name);
         let display_fqn = builder.display(scope_stack, symbol_kind.clone(), &name);
@@ -670,6 +742,11 @@ impl Parser {
             start_col: node.start_position().column,
             end_line: node.end_position().row + 1,
             end_col: node.end_position().column,
+            signature: None,
+            is_test: false,
+            visibility: None,
+            deprecated: false,
+            deprecation_message: None,
         })
     }
 
@@ -801,7 +878,7 @@ impl Parser {
                     let builder = FqnBuilder::new(
                         crate_name.to_string(),
                         file_path.to_string_lossy().to_string(),
-                        ScopeSeparator::DoubleColon,
+                        RUST_SCOPE_SEPARATOR,
                     );
                     let canonical_fqn = builder.canonical(scope_stack, symbol_kind.clone(), &name);
                     let display_fqn = builder.display(scope_stack, symbol_kind.clone(), &name);
@@ -820,6 +897,11 @@ impl Parser {
                         start_col: node.start_position().column,
                         end_line: node.end_position().row + 1,
                         end_col: node.end_position().column,
+                        signature: None,
+                        is_test: false,
+                        visibility: None,
+                        deprecated: false,
+                        deprecation_message: None,
                     });
 
                     // Recurse into children (they're in this module's scope)
@@ -870,7 +952,7 @@ impl Parser {
                     let builder = FqnBuilder::new(
                         crate_name.to_string(),
                         file_path.to_string_lossy().to_string(),
-                        ScopeSeparator::DoubleColon,
+                        RUST_SCOPE_SEPARATOR,
                     );
                     let canonical_fqn = builder.canonical(scope_stack, symbol_kind.clone(), &name);
                     let display_fqn = builder.display(scope_stack, symbol_kind.clone(), &name);
@@ -889,6 +971,11 @@ impl Parser {
                         start_col: node.start_position().column,
                         end_line: node.end_position().row + 1,
                         end_col: node.end_position().column,
+                        signature: None,
+                        is_test: false,
+                        visibility: None,
+                        deprecated: false,
+                        deprecation_message: None,
                     });
 
                     let mut cursor = node.walk();
@@ -947,6 +1034,7 @@ impl Parser {
             "function_item" | "function_signature_item" => SymbolKind::Function,
             "struct_item" => SymbolKind::Class,
             "enum_item" => SymbolKind::Enum,
+            "macro_definition" => SymbolKind::Macro,
             _ => return None,
         };
 
@@ -960,7 +1048,7 @@ impl Parser {
         let builder = FqnBuilder::new(
             crate_name.to_string(),
             file_path.to_string_lossy().to_string(),
-            ScopeSeparator::DoubleColon,
+            RUST_SCOPE_SEPARATOR,
         );
         let canonical_fqn = builder.canonical(scope_stack, symbol_kind.clone(), &name);
         let display_fqn = builder.display(scope_stack, symbol_kind.clone(), &name);
@@ -979,6 +1067,11 @@ impl Parser {
             start_col: node.start_position().column,
             end_line: node.end_position().row + 1,
             end_col: node.end_position().column,
+            signature: None,
+            is_test: false,
+            visibility: None,
+            deprecated: false,
+            deprecation_message: None,
         })
     }
 
@@ -1031,6 +1124,65 @@ impl Default for Parser {
     }
 }
 
+/// Extract symbol facts from a tree parsed with `language`'s grammar
+///
+/// Dispatches to the language's `extract_symbols_from_tree`. No database or
+/// filesystem access; `file_path` only labels the facts. Custom languages
+/// have no tree and yield nothing here; their
+/// [`LanguageParser`](registry::LanguageParser) extracts from the source.
+pub fn extract_symbols_for_language(
+    language: Language,
+    tree: &tree_sitter::Tree,
+    file_path: PathBuf,
+    source: &[u8],
+) -> Vec<SymbolFact> {
+    match language {
+        Language::Rust => Parser::extract_symbols_from_tree(tree, file_path, source),
+        Language::C => c::CParser::extract_symbols_from_tree(tree, file_path, source),
+        Language::Cpp => cpp::CppParser::extract_symbols_from_tree(tree, file_path, source),
+        Language::Java => java::JavaParser::extract_symbols_from_tree(tree, file_path, source),
+        Language::Python => {
+            python::PythonParser::extract_symbols_from_tree(tree, file_path, source)
+        }
+        Language::JavaScript => {
+            javascript::JavaScriptParser::extract_symbols_from_tree(tree, file_path, source)
+        }
+        Language::TypeScript => {
+            typescript::TypeScriptParser::extract_symbols_from_tree(tree, file_path, source)
+        }
+        Language::Go => go::GoParser::extract_symbols_from_tree(tree, file_path, source),
+        Language::Cuda => cuda::CudaParser::extract_symbols_from_tree(tree, file_path, source),
+        Language::Swift => swift::SwiftParser::extract_symbols_from_tree(tree, file_path, source),
+        Language::Scala => scala::ScalaParser::extract_symbols_from_tree(tree, file_path, source),
+        Language::CSharp => {
+            csharp::CSharpParser::extract_symbols_from_tree(tree, file_path, source)
+        }
+        Language::Custom(_) => Vec::new(),
+    }
+}
+
+/// Extract trait / interface implementation relations from a parsed tree
+///
+/// Rust `impl` blocks (including inherent impls) and Java / TypeScript
+/// `extends` and `implements` clauses. Other languages yield nothing.
+pub fn extract_impl_relations_for_language(
+    language: Language,
+    tree: &tree_sitter::Tree,
+    source: &[u8],
+    file_path: &Path,
+) -> Vec<ImplRelation> {
+    match language {
+        Language::Rust => Parser::extract_impl_relations_static(tree, source, file_path),
+        Language::Java => {
+            java::JavaParser::extract_impl_relations_from_tree(tree, source, file_path)
+        }
+        Language::TypeScript => {
+            typescript::TypeScriptParser::extract_impl_relations_from_tree(tree, source, file_path)
+        }
+        _ => Vec::new(),
+    }
+}
+
 #[cfg(test)]
 #[allow(
     deprecated,
@@ -1055,6 +1207,11 @@ mod tests {
             start_col: 0,
             end_line: 3,
             end_col: 1,
+            signature: None,
+            is_test: false,
+            visibility: None,
+            deprecated: false,
+            deprecation_message: None,
         };
 
         let json = serde_json::to_string(&fact).unwrap();
@@ -1186,6 +1343,32 @@ mod outer {
         assert_eq!(funcs[1].fqn, Some("outer::inner::inner_fn".to_string()));
     }
 
+    #[test]
+    fn test_fqn_distinguishes_same_name_across_modules() {
+        let mut parser = Parser::new().unwrap();
+        let source = b"
+mod a {
+    pub fn foo() {}
+}
+
+mod b {
+    pub fn foo() {}
+}
+
+fn foo() {}
+";
+        let facts = parser.extract_symbols(PathBuf::from("test.rs"), source);
+
+        let fqns: Vec<_> = facts
+            .iter()
+            .filter(|f| f.kind == SymbolKind::Function)
+            .map(|f| f.fqn.clone().unwrap())
+            .collect();
+
+        // The module scope is popped on exit, so the crate-root foo stays bare
+        assert_eq!(fqns, vec!["a::foo", "b::foo", "foo"]);
+    }
+
     #[test]
     fn test_fqn_impl_method() {
         let mut parser = Parser::new().unwrap();
@@ -1482,4 +1665,57 @@ mod scope_stack_tests {
             "com.example.MyClass.myMethod"
         );
     }
+    #[test]
+    fn test_separator_for_language() {
+        for language in [Language::Rust, Language::C, Language::Cpp, Language::Cuda] {
+            assert_eq!(
+                ScopeSeparator::for_language(language),
+                ScopeSeparator::DoubleColon
+            );
+        }
+        for language in [
+            Language::Python,
+            Language::Java,
+            Language::JavaScript,
+            Language::TypeScript,
+            Language::Go,
+            Language::Swift,
+            Language::Scala,
+            Language::CSharp,
+        ] {
+            assert_eq!(ScopeSeparator::for_language(language), ScopeSeparator::Dot);
+        }
+    }
+
+    #[test]
+    #[allow(deprecated, reason = "exercises the one-shot parser API directly")]
+    fn test_python_fqn_uses_dots_rust_uses_double_colon() {
+        let mut python = crate::ingest::python::PythonParser::new().unwrap();
+        let python_facts = python.extract_symbols(
+            PathBuf::from("shapes.py"),
+            b"class Shape:\n    def area(self):\n        pass\n",
+        );
+        let python_method = python_facts
+            .iter()
+            .find(|f| f.name.as_deref() == Some("area"))
+            .unwrap();
+        assert_eq!(python_method.fqn.as_deref(), Some("Shape.area"));
+        assert!(!python_method.display_fqn.as_ref().unwrap().contains("::"));
+
+        let mut rust = Parser::new().unwrap();
+        let rust_facts = rust.extract_symbols(
+            PathBuf::from("shapes.rs"),
+            b"struct Shape;\nimpl Shape {\n    fn area(&self) {}\n}\n",
+        );
+        let rust_method = rust_facts
+            .iter()
+            .find(|f| f.name.as_deref() == Some("area"))
+            .unwrap();
+        assert_eq!(rust_method.fqn.as_deref(), Some("Shape::area"));
+        assert!(rust_method
+            .display_fqn
+            .as_ref()
+            .unwrap()
+            .ends_with("::Shape::area"));
+    }
 }
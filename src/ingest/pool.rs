@@ -49,6 +49,20 @@
 use crate::ingest::detect::Language;
 use anyhow::Result;
 use std::cell::RefCell;
+use std::time::{Duration, Instant};
+
+/// Default limit for a single tree-sitter parse during indexing
+///
+/// Generous enough for any real source file; only pathological input that
+/// sends the parser into a long error-recovery loop should reach it.
+pub const DEFAULT_PARSE_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// A parse that was aborted because it ran past its timeout
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+#[error("parse exceeded the {}ms timeout", .timeout.as_millis())]
+pub struct ParseTimeout {
+    pub timeout: Duration,
+}
 
 // Thread-local parser storage for each supported language.
 // Each thread gets its own parser instance, avoiding lock contention.
@@ -62,6 +76,9 @@ thread_local! {
     static TYPESCRIPT_PARSER: RefCell<Option<tree_sitter::Parser>> = const { RefCell::new(None) };
     static GO_PARSER: RefCell<Option<tree_sitter::Parser>> = const { RefCell::new(None) };
     static CUDA_PARSER: RefCell<Option<tree_sitter::Parser>> = const { RefCell::new(None) };
+    static SWIFT_PARSER: RefCell<Option<tree_sitter::Parser>> = const { RefCell::new(None) };
+    static SCALA_PARSER: RefCell<Option<tree_sitter::Parser>> = const { RefCell::new(None) };
+    static CSHARP_PARSER: RefCell<Option<tree_sitter::Parser>> = const { RefCell::new(None) };
 }
 
 /// Initialize or get the thread-local Rust parser
@@ -345,6 +362,54 @@ where
     })
 }
 
+/// Initialize or get the thread-local Swift parser as Option
+fn with_swift_parser_opt<F, R>(f: F) -> Result<R>
+where
+    F: FnOnce(&mut Option<tree_sitter::Parser>) -> R,
+{
+    SWIFT_PARSER.with(|parser_cell| {
+        let mut parser_ref = parser_cell.borrow_mut();
+        if parser_ref.is_none() {
+            let mut parser = tree_sitter::Parser::new();
+            parser.set_language(&tree_sitter_swift::LANGUAGE.into())?;
+            *parser_ref = Some(parser);
+        }
+        Ok(f(&mut parser_ref))
+    })
+}
+
+/// Initialize or get the thread-local C# parser as Option
+fn with_csharp_parser_opt<F, R>(f: F) -> Result<R>
+where
+    F: FnOnce(&mut Option<tree_sitter::Parser>) -> R,
+{
+    CSHARP_PARSER.with(|parser_cell| {
+        let mut parser_ref = parser_cell.borrow_mut();
+        if parser_ref.is_none() {
+            let mut parser = tree_sitter::Parser::new();
+            parser.set_language(&tree_sitter_c_sharp::LANGUAGE.into())?;
+            *parser_ref = Some(parser);
+        }
+        Ok(f(&mut parser_ref))
+    })
+}
+
+/// Initialize or get the thread-local Scala parser as Option
+fn with_scala_parser_opt<F, R>(f: F) -> Result<R>
+where
+    F: FnOnce(&mut Option<tree_sitter::Parser>) -> R,
+{
+    SCALA_PARSER.with(|parser_cell| {
+        let mut parser_ref = parser_cell.borrow_mut();
+        if parser_ref.is_none() {
+            let mut parser = tree_sitter::Parser::new();
+            parser.set_language(&crate::ingest::scala::language()?)?;
+            *parser_ref = Some(parser);
+        }
+        Ok(f(&mut parser_ref))
+    })
+}
+
 /// Initialize or get the thread-local Go parser
 fn with_go_parser<F, R>(f: F) -> Result<R>
 where
@@ -383,6 +448,63 @@ where
     })
 }
 
+/// Initialize or get the thread-local Swift parser
+fn with_swift_parser<F, R>(f: F) -> Result<R>
+where
+    F: FnOnce(&mut tree_sitter::Parser) -> R,
+{
+    SWIFT_PARSER.with(|parser_cell| {
+        let mut parser_ref = parser_cell.borrow_mut();
+        if parser_ref.is_none() {
+            let mut parser = tree_sitter::Parser::new();
+            parser.set_language(&tree_sitter_swift::LANGUAGE.into())?;
+            *parser_ref = Some(parser);
+        }
+        let parser = parser_ref
+            .as_mut()
+            .expect("Swift parser invariant violated: Option must be Some() after initialization");
+        Ok(f(parser))
+    })
+}
+
+/// Initialize or get the thread-local C# parser
+fn with_csharp_parser<F, R>(f: F) -> Result<R>
+where
+    F: FnOnce(&mut tree_sitter::Parser) -> R,
+{
+    CSHARP_PARSER.with(|parser_cell| {
+        let mut parser_ref = parser_cell.borrow_mut();
+        if parser_ref.is_none() {
+            let mut parser = tree_sitter::Parser::new();
+            parser.set_language(&tree_sitter_c_sharp::LANGUAGE.into())?;
+            *parser_ref = Some(parser);
+        }
+        let parser = parser_ref
+            .as_mut()
+            .expect("C# parser invariant violated: Option must be Some() after initialization");
+        Ok(f(parser))
+    })
+}
+
+/// Initialize or get the thread-local Scala parser
+fn with_scala_parser<F, R>(f: F) -> Result<R>
+where
+    F: FnOnce(&mut tree_sitter::Parser) -> R,
+{
+    SCALA_PARSER.with(|parser_cell| {
+        let mut parser_ref = parser_cell.borrow_mut();
+        if parser_ref.is_none() {
+            let mut parser = tree_sitter::Parser::new();
+            parser.set_language(&crate::ingest::scala::language()?)?;
+            *parser_ref = Some(parser);
+        }
+        let parser = parser_ref
+            .as_mut()
+            .expect("Scala parser invariant violated: Option must be Some() after initialization");
+        Ok(f(parser))
+    })
+}
+
 /// Execute a function with a thread-local parser for the given language.
 /// Passes &mut Option<tree_sitter::Parser> to allow take/replace patterns.
 pub fn with_parser_opt<F, R>(language: Language, f: F) -> Result<R>
@@ -399,6 +521,11 @@ where
         Language::TypeScript => with_typescript_parser_opt(f),
         Language::Go => with_go_parser_opt(f),
         Language::Cuda => with_cuda_parser_opt(f),
+        Language::Swift => with_swift_parser_opt(f),
+        Language::Scala => with_scala_parser_opt(f),
+        Language::CSharp => with_csharp_parser_opt(f),
+        // No grammar: callers see the same `None` as an unavailable parser
+        Language::Custom(_) => Ok(f(&mut None)),
     }
 }
 
@@ -443,9 +570,47 @@ where
         Language::TypeScript => with_typescript_parser(f),
         Language::Go => with_go_parser(f),
         Language::Cuda => with_cuda_parser(f),
+        Language::Swift => with_swift_parser(f),
+        Language::Scala => with_scala_parser(f),
+        Language::CSharp => with_csharp_parser(f),
+        Language::Custom(name) => anyhow::bail!("{} has no tree-sitter grammar", name),
     }
 }
 
+/// Parse `source`, aborting once `timeout` has elapsed
+///
+/// `None` parses without a limit. An aborted parse resets `parser`, so the
+/// next call starts from scratch instead of resuming the abandoned document.
+pub fn parse_with_timeout(
+    parser: &mut tree_sitter::Parser,
+    source: &[u8],
+    old_tree: Option<&tree_sitter::Tree>,
+    timeout: Option<Duration>,
+) -> std::result::Result<Option<tree_sitter::Tree>, ParseTimeout> {
+    let Some(timeout) = timeout else {
+        return Ok(parser.parse(source, old_tree));
+    };
+
+    let deadline = Instant::now() + timeout;
+    let mut timed_out = false;
+    let mut progress = |_: &tree_sitter::ParseState| {
+        timed_out = Instant::now() >= deadline;
+        timed_out
+    };
+    let mut read = |offset: usize, _| source.get(offset..).unwrap_or_default();
+    let tree = parser.parse_with_options(
+        &mut read,
+        old_tree,
+        Some(tree_sitter::ParseOptions::new().progress_callback(&mut progress)),
+    );
+
+    if timed_out {
+        parser.reset();
+        return Err(ParseTimeout { timeout });
+    }
+    Ok(tree)
+}
+
 /// Warmup all parsers to avoid first-parse latency.
 ///
 /// This function initializes all thread-local parsers by parsing minimal
@@ -473,7 +638,7 @@ where
 /// ```
 pub fn warmup_parsers() -> Result<()> {
     // Minimal source code snippets for each language
-    let test_cases: [(Language, &[u8]); 9] = [
+    let test_cases: &[(Language, &[u8])] = &[
         (Language::Rust, b"fn test() {}"),
         (Language::Python, b"def test(): pass"),
         (Language::C, b"int test() { return 0; }"),
@@ -483,9 +648,12 @@ pub fn warmup_parsers() -> Result<()> {
         (Language::TypeScript, b"function test(): void {}"),
         (Language::Go, b"package main\nfunc test() {}"),
         (Language::Cuda, b"__global__ void test() {}"),
+        (Language::Swift, b"func test() {}"),
+        (Language::CSharp, b"class Test {}"),
+        (Language::Scala, b"def test(): Unit = ()"),
     ];
 
-    for (lang, source) in test_cases {
+    for &(lang, source) in test_cases {
         let _ = with_parser(lang, |parser| {
             parser.parse(source, None);
             Ok::<(), anyhow::Error>(())
@@ -551,6 +719,15 @@ pub fn cleanup_parsers() {
     CUDA_PARSER.with(|parser_cell| {
         parser_cell.borrow_mut().take();
     });
+    SWIFT_PARSER.with(|parser_cell| {
+        parser_cell.borrow_mut().take();
+    });
+    SCALA_PARSER.with(|parser_cell| {
+        parser_cell.borrow_mut().take();
+    });
+    CSHARP_PARSER.with(|parser_cell| {
+        parser_cell.borrow_mut().take();
+    });
 }
 
 #[cfg(test)]
@@ -578,6 +755,9 @@ mod tests {
             Language::TypeScript,
             Language::Go,
             Language::Cuda,
+            Language::Swift,
+            Language::CSharp,
+            Language::Scala,
         ];
 
         for lang in languages {
@@ -648,7 +828,7 @@ mod tests {
     #[test]
     fn test_multiple_languages_same_thread() {
         // Verify we can use multiple language parsers in the same thread
-        let test_cases: [(Language, &[u8]); 9] = [
+        let test_cases: &[(Language, &[u8])] = &[
             (Language::Rust, b"fn test() {}"),
             (Language::Python, b"def test(): pass"),
             (Language::C, b"int test() { return 0; }"),
@@ -658,9 +838,12 @@ mod tests {
             (Language::TypeScript, b"function test(): void {}"),
             (Language::Go, b"package main\nfunc test() {}"),
             (Language::Cuda, b"__global__ void test() {}"),
+            (Language::Swift, b"func test() {}"),
+            (Language::CSharp, b"class Test {}"),
+            (Language::Scala, b"def test(): Unit = ()"),
         ];
 
-        for (lang, source) in test_cases {
+        for &(lang, source) in test_cases {
             let result = with_parser(lang, |parser| parser.parse(source, None).is_some());
             assert!(
                 result.is_ok() && result.unwrap(),
@@ -708,7 +891,7 @@ mod tests {
         warmup_parsers().expect("Parser warmup should succeed");
 
         // After warmup, all parsers should be initialized
-        let test_cases: [(Language, &[u8]); 9] = [
+        let test_cases: &[(Language, &[u8])] = &[
             (Language::Rust, b"fn test() {}"),
             (Language::Python, b"def test(): pass"),
             (Language::C, b"int test() { return 0; }"),
@@ -718,9 +901,12 @@ mod tests {
             (Language::TypeScript, b"function test(): void {}"),
             (Language::Go, b"package main\nfunc test() {}"),
             (Language::Cuda, b"__global__ void test() {}"),
+            (Language::Swift, b"func test() {}"),
+            (Language::CSharp, b"class Test {}"),
+            (Language::Scala, b"def test(): Unit = ()"),
         ];
 
-        for (lang, source) in test_cases {
+        for &(lang, source) in test_cases {
             let result = with_parser(lang, |parser| parser.parse(source, None).is_some());
             assert!(
                 result.is_ok() && result.unwrap(),
@@ -737,4 +923,41 @@ mod tests {
         warmup_parsers().expect("Second warmup should succeed");
         warmup_parsers().expect("Third warmup should succeed");
     }
+
+    #[test]
+    fn test_parse_with_timeout_gives_up_and_resets() {
+        // Deeply nested expressions across many functions: far more work
+        // than a 1ms budget allows
+        let nested = format!("{}1{}", "(".repeat(200), ")".repeat(200));
+        let source: String = (0..2000)
+            .map(|i| format!("fn f{i}() -> i32 {{ {nested} }}\n"))
+            .collect();
+        let timeout = Some(Duration::from_millis(1));
+
+        let result =
+            with_rust_parser(|parser| parse_with_timeout(parser, source.as_bytes(), None, timeout))
+                .unwrap();
+        assert_eq!(
+            result.unwrap_err(),
+            ParseTimeout {
+                timeout: Duration::from_millis(1)
+            }
+        );
+
+        // The same parser starts fresh on the next input
+        let tree = with_rust_parser(|parser| {
+            parse_with_timeout(parser, b"fn small() {}", None, Some(DEFAULT_PARSE_TIMEOUT))
+        })
+        .unwrap()
+        .unwrap()
+        .unwrap();
+        assert!(!tree.root_node().has_error());
+
+        // No timeout means no limit
+        let tree =
+            with_rust_parser(|parser| parse_with_timeout(parser, source.as_bytes(), None, None))
+                .unwrap()
+                .unwrap();
+        assert!(tree.is_some());
+    }
 }
@@ -0,0 +1,133 @@
+//! Pluggable language parsers
+//!
+//! Every language Magellan indexes is a [`LanguageParser`] in one
+//! process-wide registry. The built-in tree-sitter languages are registered
+//! up front; library users add their own with [`register_parser`] before
+//! indexing. [`detect_language`](super::detect_language) consults the
+//! registry, so a registered extension is scanned, watched and indexed like a
+//! built-in one.
+
+use std::path::Path;
+use std::sync::{Arc, LazyLock};
+
+use parking_lot::RwLock;
+
+use super::detect::{Language, BUILTIN_EXTENSIONS};
+use super::{extract_symbols_for_language, pool, SymbolFact};
+
+/// Extracts symbol facts for one language
+///
+/// Implementations must be pure: no filesystem or database access, `path`
+/// only labels the facts.
+pub trait LanguageParser: Send + Sync {
+    /// Language the facts are recorded under
+    ///
+    /// Custom parsers return [`Language::Custom`] with their own name.
+    fn language(&self) -> Language;
+
+    /// File extensions handled, without the dot (case-sensitive)
+    fn extensions(&self) -> &[&str];
+
+    /// Extract the symbols defined in `source`
+    fn extract(&self, path: &Path, source: &[u8]) -> Vec<SymbolFact>;
+}
+
+/// A built-in language backed by its tree-sitter grammar
+pub struct BuiltinParser {
+    language: Language,
+    extensions: &'static [&'static str],
+}
+
+impl LanguageParser for BuiltinParser {
+    fn language(&self) -> Language {
+        self.language
+    }
+
+    fn extensions(&self) -> &[&str] {
+        self.extensions
+    }
+
+    fn extract(&self, path: &Path, source: &[u8]) -> Vec<SymbolFact> {
+        let tree = pool::with_parser(self.language, |parser| parser.parse(source, None));
+        match tree {
+            Ok(Some(tree)) => {
+                extract_symbols_for_language(self.language, &tree, path.to_path_buf(), source)
+            }
+            _ => Vec::new(),
+        }
+    }
+}
+
+/// One parser per built-in language, in detection order
+pub fn builtin_parsers() -> Vec<Arc<dyn LanguageParser>> {
+    BUILTIN_EXTENSIONS
+        .iter()
+        .map(|&(language, extensions)| {
+            Arc::new(BuiltinParser {
+                language,
+                extensions,
+            }) as Arc<dyn LanguageParser>
+        })
+        .collect()
+}
+
+static REGISTRY: LazyLock<RwLock<Vec<Arc<dyn LanguageParser>>>> =
+    LazyLock::new(|| RwLock::new(builtin_parsers()));
+
+/// Add a parser for every `CodeGraph` in the process
+///
+/// Later registrations win, so a parser claiming a built-in extension
+/// replaces the built-in language for it.
+pub fn register_parser(parser: Arc<dyn LanguageParser>) {
+    REGISTRY.write().push(parser);
+}
+
+/// The parser handling `path`'s extension, if any
+pub fn parser_for_path(path: &Path) -> Option<Arc<dyn LanguageParser>> {
+    let extension = path.extension()?.to_str()?;
+    REGISTRY
+        .read()
+        .iter()
+        .rev()
+        .find(|parser| parser.extensions().contains(&extension))
+        .cloned()
+}
+
+/// The parser registered for `language`, if any
+pub fn parser_for_language(language: Language) -> Option<Arc<dyn LanguageParser>> {
+    REGISTRY
+        .read()
+        .iter()
+        .rev()
+        .find(|parser| parser.language() == language)
+        .cloned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builtin_parser_extracts_symbols() {
+        let parser = parser_for_path(Path::new("lib.rs")).unwrap();
+        assert_eq!(parser.language(), Language::Rust);
+
+        let facts = parser.extract(Path::new("lib.rs"), b"fn main() {}\nstruct Point;\n");
+        let names: Vec<_> = facts.iter().filter_map(|f| f.name.as_deref()).collect();
+        assert_eq!(names, vec!["main", "Point"]);
+    }
+
+    #[test]
+    fn test_every_builtin_language_is_registered() {
+        for &(language, extensions) in BUILTIN_EXTENSIONS {
+            assert!(parser_for_language(language).is_some(), "{:?}", language);
+            for extension in extensions {
+                let path = format!("file.{}", extension);
+                assert_eq!(
+                    parser_for_path(Path::new(&path)).map(|p| p.language()),
+                    Some(language)
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,479 @@
+//! Scala symbol extraction using tree-sitter-scala.
+//!
+//! Extracts classes, objects, traits, methods, functions, and type aliases
+//! from Scala source code. Package clauses contribute to FQNs: a braced
+//! `package a { ... }` scopes its body, while `package a.b` scopes the rest
+//! of the file.
+
+use crate::common::safe_slice;
+use crate::graph::canonical_fqn::FqnBuilder;
+use crate::ingest::{Language, ScopeSeparator, ScopeStack, SymbolFact, SymbolKind};
+use crate::references::{CallFact, ReferenceFact};
+use anyhow::Result;
+use std::path::{Path, PathBuf};
+
+/// FQN separator for Scala scopes
+const SCOPE_SEPARATOR: ScopeSeparator = ScopeSeparator::for_language(Language::Scala);
+
+/// tree-sitter grammar for Scala
+pub(crate) fn language() -> Result<tree_sitter::Language> {
+    Ok(tree_sitter_scala::LANGUAGE.into())
+}
+
+/// Parser that extracts symbol facts from Scala source code.
+///
+/// Pure function: Input (path, contents) → Output `Vec<SymbolFact>`
+/// No filesystem access. No global state. No caching.
+pub struct ScalaParser {
+    pub(crate) parser: tree_sitter::Parser,
+}
+
+impl ScalaParser {
+    /// Create a new parser for Scala source code.
+    pub fn new() -> Result<Self> {
+        let mut parser = tree_sitter::Parser::new();
+        parser.set_language(&language()?)?;
+        Ok(Self { parser })
+    }
+
+    /// Create parser wrapper from an existing tree-sitter parser
+    pub(crate) fn from_parser(parser: tree_sitter::Parser) -> Self {
+        Self { parser }
+    }
+
+    /// Extract symbol facts from Scala source code.
+    ///
+    /// # Arguments
+    /// * `file_path` - Path to the file (for context only, not accessed)
+    /// * `source` - Source code content as bytes
+    ///
+    /// # Returns
+    /// Vector of symbol facts found in the source
+    ///
+    /// # Guarantees
+    /// - Pure function: same input → same output
+    /// - No side effects
+    /// - No filesystem access
+    pub fn extract_symbols(&mut self, file_path: PathBuf, source: &[u8]) -> Vec<SymbolFact> {
+        let tree = match self.parser.parse(source, None) {
+            Some(t) => t,
+            None => return Vec::new(), // Parse error: return empty
+        };
+        Self::extract_symbols_from_tree(&tree, file_path, source)
+    }
+
+    /// Static walk tree for symbol extraction.
+    ///
+    /// - class_definition / object_definition: create a Class symbol and push their name
+    /// - trait_definition: creates an Interface symbol and pushes its name
+    /// - package_clause with a body: pushes the package name for that body only
+    fn walk_tree_static(
+        node: &tree_sitter::Node,
+        source: &[u8],
+        file_path: &Path,
+        facts: &mut Vec<SymbolFact>,
+        scope_stack: &mut ScopeStack,
+    ) {
+        let kind = node.kind();
+
+        let scope_name = match kind {
+            "package_clause" => Self::extract_package_name(node, source),
+            "class_definition" | "object_definition" => Self::push_type_symbol(
+                node,
+                source,
+                file_path,
+                facts,
+                scope_stack,
+                SymbolKind::Class,
+            ),
+            "trait_definition" => Self::push_type_symbol(
+                node,
+                source,
+                file_path,
+                facts,
+                scope_stack,
+                SymbolKind::Interface,
+            ),
+            _ => None,
+        };
+
+        if let Some(name) = scope_name {
+            scope_stack.push(&name);
+            Self::walk_children(node, source, file_path, facts, scope_stack);
+            scope_stack.pop();
+            return;
+        }
+
+        // Extract symbol from this node if applicable
+        if let Some(fact) = Self::extract_symbol(node, source, file_path, scope_stack) {
+            facts.push(fact);
+        }
+
+        Self::walk_children(node, source, file_path, facts, scope_stack);
+    }
+
+    /// Recurse into children.
+    ///
+    /// A `package a.b` clause without braces has no body of its own: its
+    /// name scopes every following sibling up to the end of the parent.
+    fn walk_children(
+        node: &tree_sitter::Node,
+        source: &[u8],
+        file_path: &Path,
+        facts: &mut Vec<SymbolFact>,
+        scope_stack: &mut ScopeStack,
+    ) {
+        let mut pushed = 0;
+        let mut cursor = node.walk();
+        for child in node.children(&mut cursor) {
+            if child.kind() == "package_clause" && child.child_by_field_name("body").is_none() {
+                if let Some(name) = Self::extract_package_name(&child, source) {
+                    scope_stack.push(&name);
+                    pushed += 1;
+                }
+                continue;
+            }
+            Self::walk_tree_static(&child, source, file_path, facts, scope_stack);
+        }
+        for _ in 0..pushed {
+            scope_stack.pop();
+        }
+    }
+
+    /// Record a type-level symbol and return its name for scoping.
+    fn push_type_symbol(
+        node: &tree_sitter::Node,
+        source: &[u8],
+        file_path: &Path,
+        facts: &mut Vec<SymbolFact>,
+        scope_stack: &ScopeStack,
+        symbol_kind: SymbolKind,
+    ) -> Option<String> {
+        let name = Self::extract_name(node, source)?;
+        facts.push(Self::symbol_fact(
+            node,
+            file_path,
+            scope_stack,
+            symbol_kind,
+            name.clone(),
+        ));
+        Some(name)
+    }
+
+    /// Extract a def or type alias symbol fact from a tree-sitter node, if applicable.
+    fn extract_symbol(
+        node: &tree_sitter::Node,
+        source: &[u8],
+        file_path: &Path,
+        scope_stack: &ScopeStack,
+    ) -> Option<SymbolFact> {
+        let symbol_kind = match node.kind() {
+            "function_definition" | "function_declaration" if Self::is_member(node) => {
+                SymbolKind::Method
+            }
+            "function_definition" | "function_declaration" => SymbolKind::Function,
+            "type_definition" => SymbolKind::TypeAlias,
+            _ => return None,
+        };
+
+        let name = Self::extract_name(node, source)?;
+        Some(Self::symbol_fact(
+            node,
+            file_path,
+            scope_stack,
+            symbol_kind,
+            name,
+        ))
+    }
+
+    fn symbol_fact(
+        node: &tree_sitter::Node,
+        file_path: &Path,
+        scope_stack: &ScopeStack,
+        symbol_kind: SymbolKind,
+        name: String,
+    ) -> SymbolFact {
+        let normalized_kind = symbol_kind.normalized_key().to_string();
+        let fqn = scope_stack.fqn_for_symbol(&name);
+
+        let builder = FqnBuilder::new(
+            ".".to_string(),
+            file_path.to_string_lossy().to_string(),
+            SCOPE_SEPARATOR,
+        );
+        let canonical_fqn = builder.canonical(scope_stack, symbol_kind.clone(), &name);
+        let display_fqn = builder.display(scope_stack, symbol_kind.clone(), &name);
+
+        SymbolFact {
+            file_path: file_path.to_path_buf(),
+            kind: symbol_kind,
+            kind_normalized: normalized_kind,
+            name: Some(name),
+            fqn: Some(fqn),
+            canonical_fqn: Some(canonical_fqn),
+            display_fqn: Some(display_fqn),
+            byte_start: node.start_byte(),
+            byte_end: node.end_byte(),
+            start_line: node.start_position().row + 1,
+            start_col: node.start_position().column,
+            end_line: node.end_position().row + 1,
+            end_col: node.end_position().column,
+            signature: None,
+            is_test: false,
+            visibility: None,
+            deprecated: false,
+            deprecation_message: None,
+        }
+    }
+
+    /// Whether a def sits directly in a class, object, or trait body.
+    ///
+    /// Braced package bodies are also `template_body` nodes, so the body's
+    /// owner decides; defs there are top-level functions.
+    fn is_member(node: &tree_sitter::Node) -> bool {
+        node.parent()
+            .filter(|parent| parent.kind() == "template_body")
+            .and_then(|body| body.parent())
+            .is_some_and(|owner| {
+                matches!(
+                    owner.kind(),
+                    "class_definition" | "object_definition" | "trait_definition"
+                )
+            })
+    }
+
+    fn node_text(node: &tree_sitter::Node, source: &[u8]) -> Option<String> {
+        let bytes = safe_slice(source, node.start_byte(), node.end_byte())?;
+        std::str::from_utf8(bytes).ok().map(|s| s.to_string())
+    }
+
+    /// Dotted package name of a package clause (`package a.b` → `a.b`)
+    fn extract_package_name(node: &tree_sitter::Node, source: &[u8]) -> Option<String> {
+        let name_node = node.child_by_field_name("name")?;
+        let text = Self::node_text(&name_node, source)?;
+        let name: String = text.chars().filter(|c| !c.is_whitespace()).collect();
+        (!name.is_empty()).then_some(name)
+    }
+
+    /// Extract the declared name of a definition node.
+    fn extract_name(node: &tree_sitter::Node, source: &[u8]) -> Option<String> {
+        let name_node = node.child_by_field_name("name")?;
+        Self::node_text(&name_node, source)
+    }
+
+    /// Extract symbol facts using an external parser (for parser pooling).
+    pub fn extract_symbols_with_parser(
+        parser: &mut tree_sitter::Parser,
+        file_path: PathBuf,
+        source: &[u8],
+    ) -> Vec<SymbolFact> {
+        let tree = match parser.parse(source, None) {
+            Some(t) => t,
+            None => return Vec::new(),
+        };
+        Self::extract_symbols_from_tree(&tree, file_path, source)
+    }
+
+    /// Extract symbol facts from a pre-parsed tree.
+    pub fn extract_symbols_from_tree(
+        tree: &tree_sitter::Tree,
+        file_path: PathBuf,
+        source: &[u8],
+    ) -> Vec<SymbolFact> {
+        let root_node = tree.root_node();
+        let mut facts = Vec::new();
+        let mut scope_stack = ScopeStack::new(SCOPE_SEPARATOR);
+
+        Self::walk_tree_static(&root_node, source, &file_path, &mut facts, &mut scope_stack);
+        facts
+    }
+
+    /// Extract reference facts from Scala source code.
+    pub fn extract_references(
+        &mut self,
+        file_path: PathBuf,
+        source: &[u8],
+        symbols: &[SymbolFact],
+    ) -> Vec<ReferenceFact> {
+        let tree = match self.parser.parse(source, None) {
+            Some(t) => t,
+            None => return Vec::new(),
+        };
+        Self::extract_references_from_tree(&tree, file_path, source, symbols)
+    }
+
+    /// Extract reference facts from a pre-parsed tree.
+    pub fn extract_references_from_tree(
+        tree: &tree_sitter::Tree,
+        file_path: PathBuf,
+        source: &[u8],
+        symbols: &[SymbolFact],
+    ) -> Vec<ReferenceFact> {
+        use crate::ingest::generic_extraction;
+        generic_extraction::extract_references_from_tree(
+            tree,
+            file_path,
+            source,
+            symbols,
+            |node| matches!(node.kind(), "identifier" | "type_identifier"),
+            |node, source| {
+                let text = std::str::from_utf8(&source[node.start_byte()..node.end_byte()]).ok()?;
+                Some((text.to_string(), node.kind()))
+            },
+        )
+    }
+
+    /// Extract function call facts from Scala source code.
+    pub fn extract_calls(
+        &mut self,
+        file_path: PathBuf,
+        source: &[u8],
+        symbols: &[SymbolFact],
+    ) -> Vec<CallFact> {
+        let tree = match self.parser.parse(source, None) {
+            Some(t) => t,
+            None => return Vec::new(),
+        };
+        Self::extract_calls_from_tree(&tree, file_path, source, symbols)
+    }
+
+    pub fn extract_calls_from_tree(
+        tree: &tree_sitter::Tree,
+        file_path: PathBuf,
+        source: &[u8],
+        symbols: &[SymbolFact],
+    ) -> Vec<CallFact> {
+        use crate::ingest::generic_extraction;
+        generic_extraction::extract_calls_from_tree(
+            tree,
+            file_path,
+            source,
+            symbols,
+            |node| node.kind() == "function_definition",
+            Self::extract_name,
+            "call_expression",
+            |node, source| {
+                let callee = node.child_by_field_name("function")?;
+                Self::extract_callee(&callee, source)
+            },
+        )
+    }
+
+    /// Name of a call's target (`foo(..)`, `obj.foo(..)`, `foo[T](..)` → `foo`)
+    fn extract_callee(callee: &tree_sitter::Node, source: &[u8]) -> Option<(String, &'static str)> {
+        match callee.kind() {
+            "identifier" => Some((Self::node_text(callee, source)?, "identifier")),
+            "field_expression" => {
+                let field = callee.child_by_field_name("field")?;
+                Some((Self::node_text(&field, source)?, "field_expression"))
+            }
+            "generic_function" => {
+                let function = callee.child_by_field_name("function")?;
+                Self::extract_callee(&function, source)
+            }
+            _ => None,
+        }
+    }
+}
+
+impl Default for ScalaParser {
+    fn default() -> Self {
+        Self::new().expect("Failed to create Scala parser") // M-UNWRAP: tree-sitter language is a build-time invariant
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn symbols(source: &[u8]) -> Vec<SymbolFact> {
+        let mut parser = ScalaParser::new().unwrap();
+        parser.extract_symbols(PathBuf::from("Main.scala"), source)
+    }
+
+    fn find<'a>(facts: &'a [SymbolFact], name: &str) -> &'a SymbolFact {
+        facts
+            .iter()
+            .find(|f| f.name.as_deref() == Some(name))
+            .unwrap_or_else(|| panic!("symbol {} not found", name))
+    }
+
+    #[test]
+    fn test_extract_type_definitions() {
+        let source = b"class Animal\nobject Registry\ntrait Drawable\ntype Id = Long\n";
+        let facts = symbols(source);
+
+        assert_eq!(find(&facts, "Animal").kind, SymbolKind::Class);
+        assert_eq!(find(&facts, "Registry").kind, SymbolKind::Class);
+        assert_eq!(find(&facts, "Drawable").kind, SymbolKind::Interface);
+        assert_eq!(find(&facts, "Id").kind, SymbolKind::TypeAlias);
+    }
+
+    #[test]
+    fn test_methods_scoped_to_nested_objects() {
+        let source = b"object Outer {\n  class Inner {\n    def run(): Unit = ()\n  }\n  def helper(x: Int): Int = x\n}\n";
+        let facts = symbols(source);
+
+        let run = find(&facts, "run");
+        assert_eq!(run.kind, SymbolKind::Method);
+        assert_eq!(run.fqn.as_deref(), Some("Outer.Inner.run"));
+        assert_eq!(run.start_line, 3);
+
+        let helper = find(&facts, "helper");
+        assert_eq!(helper.kind, SymbolKind::Method);
+        assert_eq!(helper.fqn.as_deref(), Some("Outer.helper"));
+    }
+
+    #[test]
+    fn test_package_clause_prefixes_fqn() {
+        let source = b"package com.example\n\nobject Main {\n  def main(args: Array[String]): Unit = ()\n}\n";
+        let facts = symbols(source);
+
+        assert_eq!(
+            find(&facts, "Main").fqn.as_deref(),
+            Some("com.example.Main")
+        );
+        assert_eq!(
+            find(&facts, "main").fqn.as_deref(),
+            Some("com.example.Main.main")
+        );
+    }
+
+    #[test]
+    fn test_braced_package_scopes_only_its_body() {
+        let source = b"package a {\n  def inside(): Int = 1\n}\ndef outside(): Int = 2\n";
+        let facts = symbols(source);
+
+        let inside = find(&facts, "inside");
+        assert_eq!(inside.kind, SymbolKind::Function);
+        assert_eq!(inside.fqn.as_deref(), Some("a.inside"));
+        assert_eq!(find(&facts, "outside").fqn.as_deref(), Some("outside"));
+    }
+
+    #[test]
+    fn test_abstract_trait_method() {
+        let facts = symbols(b"trait Shape {\n  def area: Double\n}\n");
+
+        let area = find(&facts, "area");
+        assert_eq!(area.kind, SymbolKind::Method);
+        assert_eq!(area.fqn.as_deref(), Some("Shape.area"));
+    }
+
+    #[test]
+    fn test_empty_file() {
+        assert!(symbols(b"").is_empty());
+    }
+
+    #[test]
+    fn test_byte_spans_within_bounds() {
+        let source = b"def foo(): Int = 1";
+        let facts = symbols(source);
+
+        assert!(!facts.is_empty());
+        let fact = &facts[0];
+        assert_eq!(
+            &source[fact.byte_start..fact.byte_end],
+            b"def foo(): Int = 1"
+        );
+    }
+}
@@ -0,0 +1,470 @@
+//! Swift symbol extraction using tree-sitter-swift.
+//!
+//! Extracts classes, structs, enums, protocols, functions, and initializers
+//! from Swift source code. Members declared in `extension Foo { ... }` are
+//! scoped under `Foo`, the way Rust impl blocks scope their methods.
+
+use crate::common::safe_slice;
+use crate::graph::canonical_fqn::FqnBuilder;
+use crate::ingest::{Language, ScopeSeparator, ScopeStack, SymbolFact, SymbolKind};
+use crate::references::{CallFact, ReferenceFact};
+use anyhow::Result;
+use std::path::{Path, PathBuf};
+
+/// FQN separator for Swift scopes
+const SCOPE_SEPARATOR: ScopeSeparator = ScopeSeparator::for_language(Language::Swift);
+
+/// Parser that extracts symbol facts from Swift source code.
+///
+/// Pure function: Input (path, contents) → Output `Vec<SymbolFact>`
+/// No filesystem access. No global state. No caching.
+pub struct SwiftParser {
+    pub(crate) parser: tree_sitter::Parser,
+}
+
+impl SwiftParser {
+    /// Create a new parser for Swift source code.
+    pub fn new() -> Result<Self> {
+        let mut parser = tree_sitter::Parser::new();
+        parser.set_language(&tree_sitter_swift::LANGUAGE.into())?;
+        Ok(Self { parser })
+    }
+
+    /// Create parser wrapper from an existing tree-sitter parser
+    pub(crate) fn from_parser(parser: tree_sitter::Parser) -> Self {
+        Self { parser }
+    }
+
+    /// Extract symbol facts from Swift source code.
+    ///
+    /// # Arguments
+    /// * `file_path` - Path to the file (for context only, not accessed)
+    /// * `source` - Source code content as bytes
+    ///
+    /// # Returns
+    /// Vector of symbol facts found in the source
+    ///
+    /// # Guarantees
+    /// - Pure function: same input → same output
+    /// - No side effects
+    /// - No filesystem access
+    pub fn extract_symbols(&mut self, file_path: PathBuf, source: &[u8]) -> Vec<SymbolFact> {
+        let tree = match self.parser.parse(source, None) {
+            Some(t) => t,
+            None => return Vec::new(), // Parse error: return empty
+        };
+        Self::extract_symbols_from_tree(&tree, file_path, source)
+    }
+
+    /// Static walk tree for symbol extraction.
+    ///
+    /// - class_declaration: class/struct/actor/enum create a symbol and push
+    ///   their name; `extension` only pushes the extended type's name
+    /// - protocol_declaration: creates an Interface symbol and pushes its name
+    fn walk_tree_static(
+        node: &tree_sitter::Node,
+        source: &[u8],
+        file_path: &Path,
+        facts: &mut Vec<SymbolFact>,
+        scope_stack: &mut ScopeStack,
+    ) {
+        let kind = node.kind();
+
+        let scope_name = match kind {
+            "class_declaration" => {
+                let declaration_kind = node
+                    .child_by_field_name("declaration_kind")
+                    .and_then(|n| Self::node_text(&n, source));
+                match declaration_kind.as_deref() {
+                    // Extensions don't create a symbol; their members belong to the extended type
+                    Some("extension") => Self::extract_type_name(node, source),
+                    Some("enum") => Self::push_type_symbol(
+                        node,
+                        source,
+                        file_path,
+                        facts,
+                        scope_stack,
+                        SymbolKind::Enum,
+                    ),
+                    _ => Self::push_type_symbol(
+                        node,
+                        source,
+                        file_path,
+                        facts,
+                        scope_stack,
+                        SymbolKind::Class,
+                    ),
+                }
+            }
+            "protocol_declaration" => Self::push_type_symbol(
+                node,
+                source,
+                file_path,
+                facts,
+                scope_stack,
+                SymbolKind::Interface,
+            ),
+            _ => None,
+        };
+
+        if let Some(name) = scope_name {
+            scope_stack.push(&name);
+            let mut cursor = node.walk();
+            for child in node.children(&mut cursor) {
+                Self::walk_tree_static(&child, source, file_path, facts, scope_stack);
+            }
+            scope_stack.pop();
+            return;
+        }
+
+        // Extract symbol from this node if applicable
+        if let Some(fact) = Self::extract_symbol(node, source, file_path, scope_stack) {
+            facts.push(fact);
+        }
+
+        // Recurse into children
+        let mut cursor = node.walk();
+        for child in node.children(&mut cursor) {
+            Self::walk_tree_static(&child, source, file_path, facts, scope_stack);
+        }
+    }
+
+    /// Record a type-level symbol and return its name for scoping.
+    fn push_type_symbol(
+        node: &tree_sitter::Node,
+        source: &[u8],
+        file_path: &Path,
+        facts: &mut Vec<SymbolFact>,
+        scope_stack: &ScopeStack,
+        symbol_kind: SymbolKind,
+    ) -> Option<String> {
+        let name = Self::extract_type_name(node, source)?;
+        facts.push(Self::symbol_fact(
+            node,
+            file_path,
+            scope_stack,
+            symbol_kind,
+            name.clone(),
+        ));
+        Some(name)
+    }
+
+    /// Extract a function-like symbol fact from a tree-sitter node, if applicable.
+    fn extract_symbol(
+        node: &tree_sitter::Node,
+        source: &[u8],
+        file_path: &Path,
+        scope_stack: &ScopeStack,
+    ) -> Option<SymbolFact> {
+        let symbol_kind = match node.kind() {
+            "function_declaration" if Self::is_member(node) => SymbolKind::Method,
+            "function_declaration" => SymbolKind::Function,
+            "protocol_function_declaration" | "init_declaration" => SymbolKind::Method,
+            _ => return None,
+        };
+
+        let name = Self::extract_name(node, source)?;
+        Some(Self::symbol_fact(
+            node,
+            file_path,
+            scope_stack,
+            symbol_kind,
+            name,
+        ))
+    }
+
+    fn symbol_fact(
+        node: &tree_sitter::Node,
+        file_path: &Path,
+        scope_stack: &ScopeStack,
+        symbol_kind: SymbolKind,
+        name: String,
+    ) -> SymbolFact {
+        let normalized_kind = symbol_kind.normalized_key().to_string();
+        let fqn = scope_stack.fqn_for_symbol(&name);
+
+        let builder = FqnBuilder::new(
+            ".".to_string(),
+            file_path.to_string_lossy().to_string(),
+            SCOPE_SEPARATOR,
+        );
+        let canonical_fqn = builder.canonical(scope_stack, symbol_kind.clone(), &name);
+        let display_fqn = builder.display(scope_stack, symbol_kind.clone(), &name);
+
+        SymbolFact {
+            file_path: file_path.to_path_buf(),
+            kind: symbol_kind,
+            kind_normalized: normalized_kind,
+            name: Some(name),
+            fqn: Some(fqn),
+            canonical_fqn: Some(canonical_fqn),
+            display_fqn: Some(display_fqn),
+            byte_start: node.start_byte(),
+            byte_end: node.end_byte(),
+            start_line: node.start_position().row + 1,
+            start_col: node.start_position().column,
+            end_line: node.end_position().row + 1,
+            end_col: node.end_position().column,
+            signature: None,
+            is_test: false,
+            visibility: None,
+            deprecated: false,
+            deprecation_message: None,
+        }
+    }
+
+    /// Whether a declaration sits directly in a type, extension, or protocol body.
+    fn is_member(node: &tree_sitter::Node) -> bool {
+        node.parent().is_some_and(|parent| {
+            matches!(
+                parent.kind(),
+                "class_body" | "enum_class_body" | "protocol_body"
+            )
+        })
+    }
+
+    fn node_text(node: &tree_sitter::Node, source: &[u8]) -> Option<String> {
+        let bytes = safe_slice(source, node.start_byte(), node.end_byte())?;
+        std::str::from_utf8(bytes).ok().map(|s| s.to_string())
+    }
+
+    /// Extract the declared (or, for extensions, extended) type name.
+    ///
+    /// Generic arguments are dropped so `extension Array<Int>` scopes under `Array`.
+    fn extract_type_name(node: &tree_sitter::Node, source: &[u8]) -> Option<String> {
+        let name_node = node.child_by_field_name("name")?;
+        let text = Self::node_text(&name_node, source)?;
+        let name = text.split('<').next().unwrap_or("").trim();
+        (!name.is_empty()).then(|| name.to_string())
+    }
+
+    /// Extract name from a function-like node.
+    fn extract_name(node: &tree_sitter::Node, source: &[u8]) -> Option<String> {
+        if node.kind() == "init_declaration" {
+            return Some("init".to_string());
+        }
+
+        if let Some(name_node) = node.child_by_field_name("name") {
+            return Self::node_text(&name_node, source);
+        }
+
+        let mut cursor = node.walk();
+        for child in node.children(&mut cursor) {
+            if child.kind() == "simple_identifier" {
+                return Self::node_text(&child, source);
+            }
+        }
+        None
+    }
+
+    /// Extract symbol facts using an external parser (for parser pooling).
+    pub fn extract_symbols_with_parser(
+        parser: &mut tree_sitter::Parser,
+        file_path: PathBuf,
+        source: &[u8],
+    ) -> Vec<SymbolFact> {
+        let tree = match parser.parse(source, None) {
+            Some(t) => t,
+            None => return Vec::new(),
+        };
+        Self::extract_symbols_from_tree(&tree, file_path, source)
+    }
+
+    /// Extract symbol facts from a pre-parsed tree.
+    pub fn extract_symbols_from_tree(
+        tree: &tree_sitter::Tree,
+        file_path: PathBuf,
+        source: &[u8],
+    ) -> Vec<SymbolFact> {
+        let root_node = tree.root_node();
+        let mut facts = Vec::new();
+        let mut scope_stack = ScopeStack::new(SCOPE_SEPARATOR);
+
+        Self::walk_tree_static(&root_node, source, &file_path, &mut facts, &mut scope_stack);
+        facts
+    }
+
+    /// Extract reference facts from Swift source code.
+    pub fn extract_references(
+        &mut self,
+        file_path: PathBuf,
+        source: &[u8],
+        symbols: &[SymbolFact],
+    ) -> Vec<ReferenceFact> {
+        let tree = match self.parser.parse(source, None) {
+            Some(t) => t,
+            None => return Vec::new(),
+        };
+        Self::extract_references_from_tree(&tree, file_path, source, symbols)
+    }
+
+    /// Extract reference facts from a pre-parsed tree.
+    pub fn extract_references_from_tree(
+        tree: &tree_sitter::Tree,
+        file_path: PathBuf,
+        source: &[u8],
+        symbols: &[SymbolFact],
+    ) -> Vec<ReferenceFact> {
+        use crate::ingest::generic_extraction;
+        generic_extraction::extract_references_from_tree(
+            tree,
+            file_path,
+            source,
+            symbols,
+            |node| matches!(node.kind(), "simple_identifier" | "type_identifier"),
+            |node, source| {
+                let text = std::str::from_utf8(&source[node.start_byte()..node.end_byte()]).ok()?;
+                Some((text.to_string(), node.kind()))
+            },
+        )
+    }
+
+    /// Extract function call facts from Swift source code.
+    pub fn extract_calls(
+        &mut self,
+        file_path: PathBuf,
+        source: &[u8],
+        symbols: &[SymbolFact],
+    ) -> Vec<CallFact> {
+        let tree = match self.parser.parse(source, None) {
+            Some(t) => t,
+            None => return Vec::new(),
+        };
+        Self::extract_calls_from_tree(&tree, file_path, source, symbols)
+    }
+
+    pub fn extract_calls_from_tree(
+        tree: &tree_sitter::Tree,
+        file_path: PathBuf,
+        source: &[u8],
+        symbols: &[SymbolFact],
+    ) -> Vec<CallFact> {
+        use crate::ingest::generic_extraction;
+        generic_extraction::extract_calls_from_tree(
+            tree,
+            file_path,
+            source,
+            symbols,
+            |node| matches!(node.kind(), "function_declaration" | "init_declaration"),
+            Self::extract_name,
+            "call_expression",
+            |node, source| {
+                let callee = node.child(0)?;
+                match callee.kind() {
+                    "simple_identifier" => {
+                        Some((Self::node_text(&callee, source)?, "simple_identifier"))
+                    }
+                    "navigation_expression" => Some((
+                        Self::extract_navigation_name(&callee, source)?,
+                        "navigation_expression",
+                    )),
+                    _ => None,
+                }
+            },
+        )
+    }
+
+    /// Member name of a navigation expression (`obj.method` → `method`)
+    fn extract_navigation_name(node: &tree_sitter::Node, source: &[u8]) -> Option<String> {
+        let suffix = node.child_by_field_name("suffix")?;
+        let mut cursor = suffix.walk();
+        let name = suffix
+            .children(&mut cursor)
+            .find(|child| child.kind() == "simple_identifier")?;
+        Self::node_text(&name, source)
+    }
+}
+
+impl Default for SwiftParser {
+    fn default() -> Self {
+        Self::new().expect("Failed to create Swift parser") // M-UNWRAP: tree-sitter language is a build-time invariant
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn symbols(source: &[u8]) -> Vec<SymbolFact> {
+        let mut parser = SwiftParser::new().unwrap();
+        parser.extract_symbols(PathBuf::from("main.swift"), source)
+    }
+
+    fn find<'a>(facts: &'a [SymbolFact], name: &str) -> &'a SymbolFact {
+        facts
+            .iter()
+            .find(|f| f.name.as_deref() == Some(name))
+            .unwrap_or_else(|| panic!("symbol {} not found", name))
+    }
+
+    #[test]
+    fn test_extract_top_level_function() {
+        let facts = symbols(b"func greet(name: String) -> String {\n    return name\n}\n");
+
+        assert_eq!(facts.len(), 1);
+        assert_eq!(facts[0].kind, SymbolKind::Function);
+        assert_eq!(facts[0].name, Some("greet".to_string()));
+        assert_eq!(facts[0].start_line, 1);
+        assert_eq!(facts[0].end_line, 3);
+    }
+
+    #[test]
+    fn test_extract_type_declarations() {
+        let source =
+            b"class Animal {}\nstruct Point {}\nenum Color { case red }\nprotocol Drawable {}\n";
+        let facts = symbols(source);
+
+        assert_eq!(find(&facts, "Animal").kind, SymbolKind::Class);
+        assert_eq!(find(&facts, "Point").kind, SymbolKind::Class);
+        assert_eq!(find(&facts, "Color").kind, SymbolKind::Enum);
+        assert_eq!(find(&facts, "Drawable").kind, SymbolKind::Interface);
+    }
+
+    #[test]
+    fn test_methods_and_init_are_scoped_to_type() {
+        let source =
+            b"struct Point {\n    init(x: Int) {}\n    func length() -> Int { return 0 }\n}\n";
+        let facts = symbols(source);
+
+        let init = find(&facts, "init");
+        assert_eq!(init.kind, SymbolKind::Method);
+        assert_eq!(init.fqn.as_deref(), Some("Point.init"));
+
+        let length = find(&facts, "length");
+        assert_eq!(length.kind, SymbolKind::Method);
+        assert_eq!(length.fqn.as_deref(), Some("Point.length"));
+    }
+
+    #[test]
+    fn test_extension_methods_attach_to_extended_type() {
+        let source = b"struct Foo {}\nextension Foo {\n    func bar() {}\n}\n";
+        let facts = symbols(source);
+
+        // The extension itself is not a symbol
+        assert_eq!(
+            facts
+                .iter()
+                .filter(|f| f.name.as_deref() == Some("Foo"))
+                .count(),
+            1
+        );
+        let bar = find(&facts, "bar");
+        assert_eq!(bar.kind, SymbolKind::Method);
+        assert_eq!(bar.fqn.as_deref(), Some("Foo.bar"));
+    }
+
+    #[test]
+    fn test_empty_file() {
+        assert!(symbols(b"").is_empty());
+    }
+
+    #[test]
+    fn test_byte_spans_within_bounds() {
+        let source = b"func foo() {}";
+        let facts = symbols(source);
+
+        assert!(!facts.is_empty());
+        let fact = &facts[0];
+        assert_eq!(&source[fact.byte_start..fact.byte_end], b"func foo() {}");
+    }
+}
@@ -0,0 +1,209 @@
+//! Test-code classification for `--only-test-files` / `--ignore-test-files`.
+//!
+//! Sets [`SymbolFact::is_test`] at indexing time so queries can split
+//! production code from tests without re-parsing.
+//!
+//! | Language | Test code when |
+//! |----------|----------------|
+//! | All | The file is under a `tests/` directory |
+//! | Rust | The symbol is, or is nested in, a `mod tests`, an item annotated `#[cfg(test)]`, or a `#[test]` function; or the file has `#![cfg(test)]` |
+
+use super::{Language, SymbolFact};
+use crate::common::safe_slice;
+use std::path::{Component, Path};
+
+/// Which symbols `--only-test-files` / `--ignore-test-files` keep
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TestFilter {
+    /// Test and non-test code
+    #[default]
+    All,
+    /// Test code only (`--only-test-files`)
+    OnlyTests,
+    /// Non-test code only (`--ignore-test-files`)
+    IgnoreTests,
+}
+
+impl TestFilter {
+    /// Whether a symbol with this classification passes the filter
+    pub fn matches(self, is_test: bool) -> bool {
+        match self {
+            TestFilter::All => true,
+            TestFilter::OnlyTests => is_test,
+            TestFilter::IgnoreTests => !is_test,
+        }
+    }
+
+    /// The CLI flag selecting this filter, if any
+    pub fn as_flag(self) -> Option<&'static str> {
+        match self {
+            TestFilter::All => None,
+            TestFilter::OnlyTests => Some("--only-test-files"),
+            TestFilter::IgnoreTests => Some("--ignore-test-files"),
+        }
+    }
+}
+
+/// Mark the facts of one file as test or non-test code.
+///
+/// `path` should be relative to the project root when it is known, so that a
+/// `tests` directory above the root does not mark the whole project as tests.
+pub fn mark_test_symbols(
+    language: Option<Language>,
+    tree: Option<&tree_sitter::Tree>,
+    path: &Path,
+    source: &[u8],
+    facts: &mut [SymbolFact],
+) {
+    if is_test_path(path) {
+        for fact in facts.iter_mut() {
+            fact.is_test = true;
+        }
+        return;
+    }
+
+    let (Some(Language::Rust), Some(tree)) = (language, tree) else {
+        return;
+    };
+    let root = tree.root_node();
+    for fact in facts.iter_mut() {
+        if let Some(node) = root.descendant_for_byte_range(fact.byte_start, fact.byte_end) {
+            fact.is_test = in_rust_test_scope(&node, source);
+        }
+    }
+}
+
+/// Whether any directory containing `path` is named `tests`
+pub fn is_test_path(path: &Path) -> bool {
+    path.parent().is_some_and(|dir| {
+        dir.components()
+            .any(|c| matches!(c, Component::Normal(name) if name == "tests"))
+    })
+}
+
+fn node_text<'a>(node: &tree_sitter::Node, source: &'a [u8]) -> &'a str {
+    safe_slice(source, node.start_byte(), node.end_byte())
+        .and_then(|bytes| std::str::from_utf8(bytes).ok())
+        .unwrap_or("")
+}
+
+fn in_rust_test_scope(node: &tree_sitter::Node, source: &[u8]) -> bool {
+    let mut current = Some(*node);
+    while let Some(node) = current {
+        match node.kind() {
+            "mod_item"
+                if node
+                    .child_by_field_name("name")
+                    .is_some_and(|name| node_text(&name, source) == "tests") =>
+            {
+                return true;
+            }
+            "source_file" => {
+                let mut cursor = node.walk();
+                let file_is_test = node.children(&mut cursor).any(|child| {
+                    child.kind() == "inner_attribute_item"
+                        && is_test_attribute(node_text(&child, source))
+                });
+                return file_is_test;
+            }
+            _ if has_test_attribute(&node, source) => return true,
+            _ => {}
+        }
+        current = node.parent();
+    }
+    false
+}
+
+/// Whether the outer attributes directly above `node` mark it as test code
+fn has_test_attribute(node: &tree_sitter::Node, source: &[u8]) -> bool {
+    let mut sibling = node.prev_sibling();
+    while let Some(current) = sibling {
+        match current.kind() {
+            "attribute_item" if is_test_attribute(node_text(&current, source)) => return true,
+            "attribute_item" | "line_comment" | "block_comment" => {}
+            _ => return false,
+        }
+        sibling = current.prev_sibling();
+    }
+    false
+}
+
+/// `#[cfg(test)]`, `#![cfg(test)]`, `#[test]`, or a runner's `#[<path>::test]`
+fn is_test_attribute(text: &str) -> bool {
+    let text: String = text.chars().filter(|c| !c.is_whitespace()).collect();
+    let Some(inner) = text
+        .strip_prefix("#![")
+        .or_else(|| text.strip_prefix("#["))
+        .and_then(|rest| rest.strip_suffix(']'))
+    else {
+        return false;
+    };
+    inner == "cfg(test)" || inner == "test" || inner.ends_with("::test")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ingest::Parser;
+    use std::path::PathBuf;
+
+    fn classify(path: &str, source: &str) -> Vec<(String, bool)> {
+        let mut parser = tree_sitter::Parser::new();
+        parser
+            .set_language(&tree_sitter_rust::LANGUAGE.into())
+            .unwrap();
+        let tree = parser.parse(source, None).unwrap();
+        let mut facts =
+            Parser::extract_symbols_from_tree(&tree, PathBuf::from(path), source.as_bytes());
+        mark_test_symbols(
+            Some(Language::Rust),
+            Some(&tree),
+            Path::new(path),
+            source.as_bytes(),
+            &mut facts,
+        );
+        facts
+            .into_iter()
+            .map(|f| (f.name.unwrap_or_default(), f.is_test))
+            .collect()
+    }
+
+    #[test]
+    fn test_cfg_test_module_is_test_code() {
+        let facts = classify(
+            "src/lib.rs",
+            "fn run() {}\n\n#[cfg(test)]\nmod checks {\n    fn helper() {}\n\n    #[test]\n    fn it_runs() {}\n}\n",
+        );
+        assert_eq!(
+            facts,
+            vec![
+                ("run".to_string(), false),
+                ("checks".to_string(), true),
+                ("helper".to_string(), true),
+                ("it_runs".to_string(), true),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_mod_tests_and_test_attributes() {
+        let facts = classify(
+            "src/lib.rs",
+            "mod tests {\n    fn helper() {}\n}\n#[tokio::test]\nasync fn async_case() {}\n#[cfg(not(test))]\nfn prod_only() {}\n",
+        );
+        assert!(facts.contains(&("helper".to_string(), true)));
+        assert!(facts.contains(&("async_case".to_string(), true)));
+        assert!(facts.contains(&("prod_only".to_string(), false)));
+    }
+
+    #[test]
+    fn test_tests_directory() {
+        assert!(is_test_path(Path::new("tests/cli.rs")));
+        assert!(is_test_path(Path::new("crates/core/tests/util/mod.rs")));
+        assert!(!is_test_path(Path::new("src/tests.rs")));
+        assert!(!is_test_path(Path::new("src/lib.rs")));
+
+        let facts = classify("tests/cli.rs", "fn helper() {}\n");
+        assert_eq!(facts, vec![("helper".to_string(), true)]);
+    }
+}
@@ -4,11 +4,14 @@
 
 use crate::common::safe_slice;
 use crate::graph::canonical_fqn::FqnBuilder;
-use crate::ingest::{ScopeSeparator, ScopeStack, SymbolFact, SymbolKind};
+use crate::ingest::{ImplRelation, Language, ScopeSeparator, ScopeStack, SymbolFact, SymbolKind};
 use crate::references::{CallFact, ReferenceFact};
 use anyhow::Result;
 use std::path::{Path, PathBuf};
 
+/// FQN separator for TypeScript scopes
+const SCOPE_SEPARATOR: ScopeSeparator = ScopeSeparator::for_language(Language::TypeScript);
+
 /// Parser that extracts symbol facts from TypeScript source code.
 ///
 /// Pure function: Input (path, contents) → Output `Vec<SymbolFact>`
@@ -51,7 +54,7 @@ impl TypeScriptParser {
 
         let root_node = tree.root_node();
         let mut facts = Vec::new();
-        let mut scope_stack = ScopeStack::new(ScopeSeparator::Dot);
+        let mut scope_stack = ScopeStack::new(SCOPE_SEPARATOR);
 
         // Use "." as package name placeholder per FQN-17
         let package_name = ".";
@@ -183,7 +186,7 @@ impl TypeScriptParser {
         let builder = FqnBuilder::new(
             package_name.to_string(),
             file_path.to_string_lossy().to_string(),
-            ScopeSeparator::Dot,
+            SCOPE_SEPARATOR,
         );
         let canonical_fqn = builder.canonical(scope_stack, symbol_kind.clone(), &name);
         let display_fqn = builder.display(scope_stack, symbol_kind.clone(), &name);
@@ -202,6 +205,11 @@ impl TypeScriptParser {
             start_col: node.start_position().column,
             end_line: node.end_position().row + 1,
             end_col: node.end_position().column,
+            signature: None,
+            is_test: false,
+            visibility: None,
+            deprecated: false,
+            deprecation_message: None,
         })
     }
 
@@ -255,7 +263,7 @@ impl TypeScriptParser {
 
         let root_node = tree.root_node();
         let mut facts = Vec::new();
-        let mut scope_stack = ScopeStack::new(ScopeSeparator::Dot);
+        let mut scope_stack = ScopeStack::new(SCOPE_SEPARATOR);
 
         // Use "." as package name placeholder per FQN-17
         let package_name = ".";
@@ -283,7 +291,7 @@ impl TypeScriptParser {
     ) -> Vec<SymbolFact> {
         let root_node = tree.root_node();
         let mut facts = Vec::new();
-        let mut scope_stack = ScopeStack::new(ScopeSeparator::Dot);
+        let mut scope_stack = ScopeStack::new(SCOPE_SEPARATOR);
         let package_name = ".";
         Self::walk_tree_with_scope_static(
             &root_node,
@@ -411,7 +419,7 @@ impl TypeScriptParser {
         let builder = FqnBuilder::new(
             package_name.to_string(),
             file_path.to_string_lossy().to_string(),
-            ScopeSeparator::Dot,
+            SCOPE_SEPARATOR,
         );
         let canonical_fqn = builder.canonical(scope_stack, symbol_kind.clone(), &name);
         let display_fqn = builder.display(scope_stack, symbol_kind.clone(), &name);
@@ -430,6 +438,11 @@ impl TypeScriptParser {
             start_col: node.start_position().column,
             end_line: node.end_position().row + 1,
             end_col: node.end_position().column,
+            signature: None,
+            is_test: false,
+            visibility: None,
+            deprecated: false,
+            deprecation_message: None,
         })
     }
 
@@ -577,6 +590,93 @@ impl TypeScriptParser {
         )
     }
 
+    /// Extract `extends` / `implements` relations from a pre-parsed tree.
+    ///
+    /// Classes yield one relation per base class and per implemented
+    /// interface; interfaces yield one per extended interface.
+    pub fn extract_impl_relations_from_tree(
+        tree: &tree_sitter::Tree,
+        source: &[u8],
+        file_path: &Path,
+    ) -> Vec<ImplRelation> {
+        fn text(node: tree_sitter::Node, source: &[u8]) -> Option<String> {
+            let bytes = safe_slice(source, node.start_byte(), node.end_byte())?;
+            std::str::from_utf8(bytes).ok().map(|s| s.to_string())
+        }
+
+        fn walk(
+            node: tree_sitter::Node,
+            source: &[u8],
+            file_path: &str,
+            relations: &mut Vec<ImplRelation>,
+        ) {
+            let mut supertypes = Vec::new();
+            let mut cursor = node.walk();
+            match node.kind() {
+                "class_declaration" | "abstract_class_declaration" => {
+                    for heritage in node.named_children(&mut cursor) {
+                        if heritage.kind() != "class_heritage" {
+                            continue;
+                        }
+                        let mut clauses = heritage.walk();
+                        for clause in heritage.named_children(&mut clauses) {
+                            let mut types = clause.walk();
+                            match clause.kind() {
+                                "extends_clause" => supertypes
+                                    .extend(clause.children_by_field_name("value", &mut types)),
+                                "implements_clause" => {
+                                    supertypes.extend(clause.named_children(&mut types))
+                                }
+                                _ => {}
+                            }
+                        }
+                    }
+                }
+                "interface_declaration" => {
+                    for clause in node.named_children(&mut cursor) {
+                        if clause.kind() == "extends_type_clause" {
+                            let mut types = clause.walk();
+                            supertypes.extend(clause.children_by_field_name("type", &mut types));
+                        }
+                    }
+                }
+                _ => {}
+            }
+
+            if !supertypes.is_empty() {
+                if let Some(type_name) = node
+                    .child_by_field_name("name")
+                    .and_then(|n| text(n, source))
+                {
+                    for supertype in supertypes {
+                        relations.push(ImplRelation {
+                            type_name: type_name.clone(),
+                            trait_name: text(supertype, source),
+                            file_path: file_path.to_string(),
+                            byte_start: node.start_byte(),
+                            byte_end: node.end_byte(),
+                            start_line: node.start_position().row + 1,
+                            end_line: node.end_position().row + 1,
+                        });
+                    }
+                }
+            }
+
+            for child in node.children(&mut node.walk()) {
+                walk(child, source, file_path, relations);
+            }
+        }
+
+        let mut relations = Vec::new();
+        walk(
+            tree.root_node(),
+            source,
+            &file_path.to_string_lossy(),
+            &mut relations,
+        );
+        relations
+    }
+
     fn extract_function_name_static(node: &tree_sitter::Node, source: &[u8]) -> Option<String> {
         let mut cursor = node.walk();
         for child in node.children(&mut cursor) {
@@ -975,4 +1075,34 @@ interface MyInterface {
         // Note: "." as package_name with "." separator results in "..MyInterface"
         assert_eq!(interfaces[0].display_fqn.as_ref().unwrap(), "..MyInterface");
     }
+
+    #[test]
+    fn test_extract_impl_relations() {
+        let mut parser = TypeScriptParser::new().unwrap();
+        let source = b"class Square extends Shape implements Printable, Comparable<Square> {}\n\
+            abstract class Base extends mixins.Root {}\n\
+            interface Named extends HasName, HasId<string> {}\n";
+        let tree = parser.parser.parse(source, None).unwrap();
+        let relations = TypeScriptParser::extract_impl_relations_from_tree(
+            &tree,
+            source,
+            Path::new("shapes.ts"),
+        );
+
+        let pairs: Vec<(&str, &str)> = relations
+            .iter()
+            .map(|r| (r.type_name.as_str(), r.trait_name.as_deref().unwrap()))
+            .collect();
+        assert_eq!(
+            pairs,
+            vec![
+                ("Square", "Shape"),
+                ("Square", "Printable"),
+                ("Square", "Comparable<Square>"),
+                ("Base", "mixins.Root"),
+                ("Named", "HasName"),
+                ("Named", "HasId<string>"),
+            ]
+        );
+    }
 }
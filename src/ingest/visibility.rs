@@ -0,0 +1,284 @@
+//! Declared-visibility extraction for `--visibility`.
+//!
+//! Sets [`SymbolFact::visibility`] at indexing time. Access modifiers are
+//! mapped onto one vocabulary so that a single filter works across languages:
+//!
+//! | Language | Recorded visibility |
+//! |----------|---------------------|
+//! | Rust | The `visibility_modifier` as written (`pub`, `pub(crate)`, `pub(super)`, `pub(in path)`), `private` without one (`pub(self)` is also `private`); trait members, trait-impl members and macros inherit and are not recorded |
+//! | Java | `pub` (`public`), `protected`, `private`, `package` without an access modifier |
+//! | C# | `pub` (`public`), `internal`, `protected`, `private`; not recorded without a modifier |
+//! | TypeScript | Class members: `pub`, `protected`, `private` from the accessibility modifier |
+//! | Go | `pub` for exported (capitalized) names, `private` otherwise |
+//! | Python | `private` for `_name` (dunder names excepted), `pub` otherwise |
+//!
+//! Other languages record no visibility.
+
+use super::{Language, SymbolFact};
+use crate::common::safe_slice;
+
+/// Visibility class selected by `--visibility`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VisibilityFilter {
+    /// Public (`pub`, `public`, exported)
+    Pub,
+    /// Restricted to the crate or package (`pub(crate)`, `pub(super)`,
+    /// `pub(in path)`, C# `internal`, Java package-private)
+    Crate,
+    /// Private to the enclosing module or type
+    Private,
+}
+
+impl VisibilityFilter {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "pub" => Some(VisibilityFilter::Pub),
+            "crate" => Some(VisibilityFilter::Crate),
+            "private" => Some(VisibilityFilter::Private),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            VisibilityFilter::Pub => "pub",
+            VisibilityFilter::Crate => "crate",
+            VisibilityFilter::Private => "private",
+        }
+    }
+
+    /// Whether a symbol with this recorded visibility passes the filter
+    ///
+    /// Symbols without a recorded visibility never match.
+    pub fn matches(self, visibility: Option<&str>) -> bool {
+        let Some(visibility) = visibility else {
+            return false;
+        };
+        match self {
+            VisibilityFilter::Pub => visibility == "pub",
+            VisibilityFilter::Crate => {
+                visibility.starts_with("pub(")
+                    || visibility == "internal"
+                    || visibility == "package"
+            }
+            VisibilityFilter::Private => visibility == "private",
+        }
+    }
+}
+
+/// Record the declared visibility of the facts of one file
+pub fn mark_visibility(
+    language: Option<Language>,
+    tree: Option<&tree_sitter::Tree>,
+    source: &[u8],
+    facts: &mut [SymbolFact],
+) {
+    let Some(language) = language else {
+        return;
+    };
+    match language {
+        Language::Go => {
+            for fact in facts.iter_mut() {
+                fact.visibility = fact
+                    .name
+                    .as_deref()
+                    .and_then(|name| name.chars().next())
+                    .map(|c| if c.is_uppercase() { "pub" } else { "private" }.to_string());
+            }
+        }
+        Language::Python => {
+            for fact in facts.iter_mut() {
+                fact.visibility = fact
+                    .name
+                    .as_deref()
+                    .map(|name| python_visibility(name).to_string());
+            }
+        }
+        Language::Rust | Language::Java | Language::CSharp | Language::TypeScript => {
+            let Some(tree) = tree else {
+                return;
+            };
+            let root = tree.root_node();
+            for fact in facts.iter_mut() {
+                let Some(node) = root.descendant_for_byte_range(fact.byte_start, fact.byte_end)
+                else {
+                    continue;
+                };
+                if node.start_byte() != fact.byte_start || node.end_byte() != fact.byte_end {
+                    continue;
+                }
+                fact.visibility = match language {
+                    Language::Rust => rust_visibility(&node, source),
+                    Language::Java => Some(java_visibility(&node, source).to_string()),
+                    Language::CSharp => csharp_visibility(&node, source).map(str::to_string),
+                    _ => typescript_visibility(&node, source).map(str::to_string),
+                };
+            }
+        }
+        _ => {}
+    }
+}
+
+fn node_text<'a>(node: &tree_sitter::Node, source: &'a [u8]) -> &'a str {
+    safe_slice(source, node.start_byte(), node.end_byte())
+        .and_then(|bytes| std::str::from_utf8(bytes).ok())
+        .unwrap_or("")
+}
+
+fn python_visibility(name: &str) -> &'static str {
+    let dunder = name.len() > 4 && name.starts_with("__") && name.ends_with("__");
+    if name.starts_with('_') && !dunder {
+        "private"
+    } else {
+        "pub"
+    }
+}
+
+fn rust_visibility(node: &tree_sitter::Node, source: &[u8]) -> Option<String> {
+    if node.kind() == "macro_definition" {
+        return None;
+    }
+    // Members of a trait or trait impl take the trait's visibility
+    let container = node
+        .parent()
+        .filter(|parent| parent.kind() == "declaration_list")
+        .and_then(|list| list.parent());
+    if let Some(container) = container {
+        let inherits = container.kind() == "trait_item"
+            || (container.kind() == "impl_item"
+                && container.child_by_field_name("trait").is_some());
+        if inherits {
+            return None;
+        }
+    }
+
+    let mut cursor = node.walk();
+    let modifier = node
+        .children(&mut cursor)
+        .find(|child| child.kind() == "visibility_modifier");
+    let visibility = match modifier {
+        Some(modifier) => node_text(&modifier, source)
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ")
+            .replace("( ", "(")
+            .replace(" )", ")"),
+        None => return Some("private".to_string()),
+    };
+    if visibility == "pub(self)" {
+        return Some("private".to_string());
+    }
+    Some(visibility)
+}
+
+fn java_visibility(node: &tree_sitter::Node, source: &[u8]) -> &'static str {
+    let mut cursor = node.walk();
+    let modifiers = node
+        .children(&mut cursor)
+        .find(|child| child.kind() == "modifiers")
+        .map(|child| node_text(&child, source))
+        .unwrap_or("");
+    let words: Vec<&str> = modifiers.split_whitespace().collect();
+    if words.contains(&"public") {
+        "pub"
+    } else if words.contains(&"protected") {
+        "protected"
+    } else if words.contains(&"private") {
+        "private"
+    } else {
+        "package"
+    }
+}
+
+fn csharp_visibility(node: &tree_sitter::Node, source: &[u8]) -> Option<&'static str> {
+    let mut cursor = node.walk();
+    let modifiers: Vec<&str> = node
+        .children(&mut cursor)
+        .filter(|child| child.kind() == "modifier")
+        .map(|child| node_text(&child, source))
+        .collect();
+    if modifiers.contains(&"public") {
+        Some("pub")
+    } else if modifiers.contains(&"internal") {
+        Some("internal")
+    } else if modifiers.contains(&"protected") {
+        Some("protected")
+    } else if modifiers.contains(&"private") {
+        Some("private")
+    } else {
+        None
+    }
+}
+
+fn typescript_visibility(node: &tree_sitter::Node, source: &[u8]) -> Option<&'static str> {
+    let mut cursor = node.walk();
+    let modifier = node
+        .children(&mut cursor)
+        .find(|child| child.kind() == "accessibility_modifier")?;
+    match node_text(&modifier, source) {
+        "public" => Some("pub"),
+        "protected" => Some("protected"),
+        "private" => Some("private"),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ingest::pool;
+    use std::path::PathBuf;
+
+    fn rust_visibilities(source: &[u8]) -> Vec<(String, Option<String>)> {
+        let tree = pool::with_parser(Language::Rust, |parser| parser.parse(source, None))
+            .unwrap()
+            .unwrap();
+        let mut facts = crate::ingest::Parser::extract_symbols_from_tree(
+            &tree,
+            PathBuf::from("lib.rs"),
+            source,
+        );
+        mark_visibility(Some(Language::Rust), Some(&tree), source, &mut facts);
+        facts
+            .into_iter()
+            .map(|fact| (fact.name.unwrap_or_default(), fact.visibility))
+            .collect()
+    }
+
+    #[test]
+    fn test_rust_distinguishes_pub_crate_and_private() {
+        let visibilities = rust_visibilities(
+            b"pub fn open() {}\npub(crate) fn internal() {}\nfn helper() {}\npub(self) fn local() {}\n",
+        );
+        assert_eq!(
+            visibilities,
+            vec![
+                ("open".to_string(), Some("pub".to_string())),
+                ("internal".to_string(), Some("pub(crate)".to_string())),
+                ("helper".to_string(), Some("private".to_string())),
+                ("local".to_string(), Some("private".to_string())),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_rust_trait_impl_members_are_not_recorded() {
+        let visibilities = rust_visibilities(
+            b"pub struct Point;\nimpl Clone for Point { fn clone(&self) -> Self { Point } }\nimpl Point { pub fn new() -> Self { Point } }\n",
+        );
+        let clone = visibilities.iter().find(|(name, _)| name == "clone");
+        let new = visibilities.iter().find(|(name, _)| name == "new");
+        assert_eq!(clone.map(|(_, v)| v.clone()), Some(None));
+        assert_eq!(new.map(|(_, v)| v.clone()), Some(Some("pub".to_string())));
+    }
+
+    #[test]
+    fn test_filter_classes() {
+        assert!(VisibilityFilter::Pub.matches(Some("pub")));
+        assert!(!VisibilityFilter::Pub.matches(Some("pub(crate)")));
+        assert!(VisibilityFilter::Crate.matches(Some("pub(super)")));
+        assert!(VisibilityFilter::Crate.matches(Some("package")));
+        assert!(VisibilityFilter::Private.matches(Some("private")));
+        assert!(!VisibilityFilter::Private.matches(None));
+    }
+}
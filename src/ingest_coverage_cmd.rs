@@ -8,6 +8,7 @@ use rusqlite::params;
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 
+use crate::log_warn;
 use crate::CodeGraph;
 
 /// Coverage data extracted from a single LCOV file.
@@ -43,7 +44,7 @@ pub fn run_ingest_coverage(db_path: PathBuf, lcov_path: PathBuf) -> Result<()> {
 
     // Get git revision for provenance
     let source_revision = get_git_revision(&db_path).unwrap_or_else(|| {
-        eprintln!("Warning: could not determine git revision for coverage provenance");
+        log_warn!("could not determine git revision for coverage provenance");
         "unknown".to_string()
     });
     let ingested_at = std::time::SystemTime::now()
@@ -99,15 +100,12 @@ pub fn run_ingest_coverage(db_path: PathBuf, lcov_path: PathBuf) -> Result<()> {
     // Checkpoint WAL to prevent unbounded growth after bulk coverage insert
     drop(conn);
     if let Err(e) = _graph.checkpoint_wal() {
-        eprintln!(
-            "Warning: WAL checkpoint failed after coverage ingest: {}",
-            e
-        );
+        log_warn!("WAL checkpoint failed after coverage ingest: {}", e);
     }
 
     if unmapped_lines > 0 {
-        eprintln!(
-            "Warning: {} line hits could not be mapped to CFG blocks (file/line mismatch)",
+        log_warn!(
+            "{} line hits could not be mapped to CFG blocks (file/line mismatch)",
             unmapped_lines
         );
     }
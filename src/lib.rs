@@ -43,6 +43,7 @@
 //!
 //! See [MANUAL.md](../MANUAL.md) for full CLI reference.
 
+pub mod api_diff;
 pub mod backend_router;
 pub mod capabilities;
 pub mod common;
@@ -52,7 +53,9 @@ pub mod diagnostics;
 pub mod error_codes;
 pub mod framework;
 pub mod generation;
+pub mod git_diff;
 pub mod graph;
+pub mod index_diff;
 pub mod indexer;
 pub mod ingest;
 pub mod lsif;
@@ -79,7 +82,7 @@ pub mod watcher;
 pub use common::{
     detect_language_from_path, detect_project_root, extract_context_safe,
     extract_symbol_content_safe, find_repo_root, format_symbol_kind, magellan_dir,
-    normalize_repo_relative_path, parse_symbol_kind, resolve_path,
+    normalize_repo_relative_path, parse_symbol_kind, parse_symbol_kind_arg, resolve_path,
 };
 pub use diagnostics::{DiagnosticStage, SkipReason, WatchDiagnostic};
 pub use framework::{FrameworkSymbol, MagellanFramework, ProjectHandle};
@@ -92,8 +95,8 @@ pub use graph::candidate_fact::{
     ConflictType, ResolutionStatus, ValidationError, ValidationResult,
 };
 pub use graph::filter::FileFilter;
-pub use graph::query::{cross_file_references_to, SymbolQueryResult};
-pub use graph::scan::ScanResult;
+pub use graph::query::{cross_file_references_to, FileImport, SymbolQueryResult};
+pub use graph::scan::{ReindexPathsResult, ScanResult};
 pub use graph::source_inventory::{
     compute_hash, ensure_schema, extract_frontmatter, extract_metadata, extract_tags,
     extract_title, extract_wikilinks, find_stale, insert_or_update, list_by_kind,
@@ -109,16 +112,20 @@ pub use graph::{
     PathStatistics, ProgramSlice, ReconcileOutcome, ScanProgress, SliceDirection, SliceResult,
     SliceStatistics, Supernode, SymbolInfo, MAGELLAN_SCHEMA_VERSION,
 };
-pub use indexer::{run_indexer, run_indexer_n, run_watch_pipeline, WatchPipelineConfig};
+pub use graph::{HashAlgorithm, HashNormalization, PathStyle, RebaseRoot};
+pub use graph::{JournalMode, SqliteOptions, Synchronous};
+pub use indexer::{
+    dry_run_scan, run_indexer, run_indexer_n, run_watch_pipeline, WatchPipelineConfig,
+};
 pub use ingest::detect::{detect_language, Language};
 pub use ingest::pool::with_parser as parse_with_language;
 pub use ingest::{ImplRelation, Parser, SymbolFact, SymbolKind};
 pub use output::command::{MigrateResponse, ReferenceMatch, Span, SymbolMatch};
 pub use output::{generate_execution_id, output_json, JsonResponse, OutputFormat};
-pub use references::{CallFact, ReferenceFact};
+pub use references::{CallFact, EnclosingSymbol, ReferenceFact, ReferenceKind};
 pub use temporal::{SnapshotFileInput, SnapshotIngestStats, SnapshotSpec};
 pub use validation::{
     canonicalize_path, normalize_path, validate_path_within_root, PathValidationError,
 };
-pub use verify::{verify_graph, VerifyReport};
+pub use verify::{verify_graph, verify_integrity, IntegrityReport, VerifyReport};
 pub use watcher::{EventType, FileEvent, FileSystemWatcher, WatcherBatch, WatcherConfig};
@@ -25,6 +25,21 @@ pub fn export_lsif(
     output_path: &Path,
     package_name: &str,
     package_version: &str,
+) -> Result<usize> {
+    let mut file = File::create(output_path)
+        .with_context(|| format!("Failed to create LSIF file: {:?}", output_path))?;
+    export_lsif_to_writer(graph, &mut file, package_name, package_version)
+}
+
+/// Export Magellan graph to LSIF format, writing JSONL to `writer`
+///
+/// Same output as [`export_lsif`], for callers that wrap the destination
+/// (e.g. in a gzip encoder).
+pub fn export_lsif_to_writer<W: Write>(
+    graph: &mut CodeGraph,
+    writer: &mut W,
+    package_name: &str,
+    package_version: &str,
 ) -> Result<usize> {
     let mut lsif = LsifGraph::new();
     let mut counter = 0u32;
@@ -126,19 +141,15 @@ pub fn export_lsif(
         }
     }
 
-    // Write LSIF to file
-    let mut file = File::create(output_path)
-        .with_context(|| format!("Failed to create LSIF file: {:?}", output_path))?;
-
     // Write as JSONL (one JSON object per line) for streaming
     for vertex in &lsif.vertices {
         let json = serde_json::to_string(vertex)?;
-        writeln!(file, "{}", json)?;
+        writeln!(writer, "{}", json)?;
     }
 
     for edge in &lsif.edges {
         let json = serde_json::to_string(edge)?;
-        writeln!(file, "{}", json)?;
+        writeln!(writer, "{}", json)?;
     }
 
     Ok(exported_symbols)
@@ -156,6 +167,7 @@ fn magellan_symbol_to_lsif_kind(kind: &str) -> SymbolKind {
         "type_alias" => SymbolKind::TypeParameter,
         "union" => SymbolKind::Struct,
         "namespace" => SymbolKind::Namespace,
+        "macro" => SymbolKind::Function,
         _ => SymbolKind::Variable,
     }
 }
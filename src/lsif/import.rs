@@ -3,17 +3,25 @@
 //! Imports LSIF data from external packages for cross-repository symbol resolution.
 
 use anyhow::{Context, Result};
+use flate2::read::GzDecoder;
 use serde_json;
 use std::fs::File;
 use std::io::{BufRead, BufReader};
 use std::path::{Path, PathBuf};
 
 use super::schema::{PackageData, Vertex};
+use crate::log_warn;
+
+/// First two bytes of every gzip stream
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
 
 /// Import LSIF data from a file
 ///
+/// Gzip-compressed files (e.g. from `export --format lsif --gzip`) are
+/// detected by their magic bytes and decompressed while reading.
+///
 /// # Arguments
-/// * `lsif_path` - Path to LSIF JSONL file
+/// * `lsif_path` - Path to LSIF JSONL file, optionally gzipped
 ///
 /// # Returns
 /// Imported package information
@@ -21,7 +29,12 @@ pub fn import_lsif(lsif_path: &Path) -> Result<ImportedPackage> {
     let file = File::open(lsif_path)
         .with_context(|| format!("Failed to open LSIF file: {:?}", lsif_path))?;
 
-    let reader = BufReader::new(file);
+    let mut buffered = BufReader::new(file);
+    let reader: Box<dyn BufRead> = if buffered.fill_buf()?.starts_with(&GZIP_MAGIC) {
+        Box::new(BufReader::new(GzDecoder::new(buffered)))
+    } else {
+        Box::new(buffered)
+    };
     let mut package_info: Option<PackageData> = None;
     let mut symbol_count = 0usize;
     let mut document_count = 0usize;
@@ -91,10 +104,11 @@ pub fn import_lsif_directory(lsif_dir: &Path) -> Result<Vec<ImportedPackage>> {
         let entry = entry?;
         let path = entry.path();
 
-        if path.extension().and_then(|e| e.to_str()) == Some("lsif") {
+        let name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
+        if name.ends_with(".lsif") || name.ends_with(".lsif.gz") {
             match import_lsif(&path) {
                 Ok(pkg) => packages.push(pkg),
-                Err(e) => eprintln!("Warning: Failed to import {:?}: {}", path, e),
+                Err(e) => log_warn!("Failed to import {:?}: {}", path, e),
             }
         }
     }
@@ -151,4 +165,31 @@ mod tests {
         assert!(result.is_ok());
         assert_eq!(result.unwrap().len(), 1);
     }
+
+    #[test]
+    fn test_import_gzipped_lsif() {
+        use crate::lsif::export::export_lsif_to_writer;
+        use flate2::write::GzEncoder;
+        use flate2::Compression;
+
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("test.db");
+        let lsif_path = temp_dir.path().join("test.lsif.gz");
+
+        let mut graph = CodeGraph::open(&db_path).unwrap();
+        let test_file = temp_dir.path().join("test.rs");
+        std::fs::write(&test_file, "fn main() {}").unwrap();
+        let _ = graph.scan_directory(temp_dir.path(), None);
+
+        let mut encoder = GzEncoder::new(File::create(&lsif_path).unwrap(), Compression::default());
+        export_lsif_to_writer(&mut graph, &mut encoder, "test-crate", "0.1.0").unwrap();
+        encoder.finish().unwrap();
+
+        let pkg = import_lsif(&lsif_path).unwrap();
+        assert_eq!(pkg.package.name, "test-crate");
+        assert!(pkg.symbol_count >= 1);
+
+        // `.lsif.gz` files are picked up from directories too
+        assert_eq!(import_lsif_directory(temp_dir.path()).unwrap().len(), 1);
+    }
 }
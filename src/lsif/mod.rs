@@ -11,6 +11,6 @@ pub mod export;
 pub mod import;
 pub mod schema;
 
-pub use export::export_lsif;
+pub use export::{export_lsif, export_lsif_to_writer};
 pub use import::import_lsif;
 pub use schema::{Edge, LsifGraph, PackageData, Vertex};
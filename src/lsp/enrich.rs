@@ -39,6 +39,7 @@ use super::analyzer::{
     detect_available_analyzers, detect_language_from_path, AnalyzerKind, AnalyzerResult,
 };
 use crate::graph::CodeGraph;
+use crate::{log_info, log_warn};
 
 /// Configuration for symbol enrichment
 #[derive(Debug, Clone)]
@@ -106,17 +107,17 @@ pub fn enrich_symbols(graph: &mut CodeGraph, config: &EnrichConfig) -> Result<En
     let available_analyzers = detect_available_analyzers();
 
     if available_analyzers.is_empty() {
-        eprintln!("No LSP analyzers found (rust-analyzer, javac)");
-        eprintln!("Install rust-analyzer: rustup component add rust-analyzer");
-        eprintln!("Install Java JDK for Java projects");
+        log_info!("No LSP analyzers found (rust-analyzer, javac)");
+        log_info!("Install rust-analyzer: rustup component add rust-analyzer");
+        log_info!("Install Java JDK for Java projects");
         return Ok(result);
     }
 
-    eprintln!("Found {} analyzer(s):", available_analyzers.len());
+    log_info!("Found {} analyzer(s):", available_analyzers.len());
     for analyzer in &available_analyzers {
-        eprintln!("  - {}", analyzer.binary_name());
+        log_info!("  - {}", analyzer.binary_name());
     }
-    eprintln!();
+    log_info!();
 
     // Get all files from the graph
     let files = graph.all_file_nodes()?;
@@ -143,7 +144,7 @@ pub fn enrich_symbols(graph: &mut CodeGraph, config: &EnrichConfig) -> Result<En
             _ => continue, // No analyzer available for this language
         };
 
-        eprintln!(
+        log_info!(
             "Enriching {:?} with {}",
             file_path,
             analyzer_kind.binary_name()
@@ -176,14 +177,14 @@ pub fn enrich_symbols(graph: &mut CodeGraph, config: &EnrichConfig) -> Result<En
 
         result.files_processed += 1;
         result.symbols_enriched += enriched_count;
-        eprintln!("  Enriched {} symbols", enriched_count);
+        log_info!("  Enriched {} symbols", enriched_count);
     }
 
-    eprintln!();
-    eprintln!("Enrichment complete:");
-    eprintln!("  Files processed: {}", result.files_processed);
-    eprintln!("  Symbols enriched: {}", result.symbols_enriched);
-    eprintln!("  Errors: {}", result.errors);
+    log_info!();
+    log_info!("Enrichment complete:");
+    log_info!("  Files processed: {}", result.files_processed);
+    log_info!("  Symbols enriched: {}", result.symbols_enriched);
+    log_info!("  Errors: {}", result.errors);
 
     Ok(result)
 }
@@ -547,7 +548,7 @@ fn match_signatures_to_symbols(
         if let Some(ref name) = symbol.name {
             // Find matching signature
             if let Some(sig) = signatures.iter().find(|s| s.name == *name) {
-                eprintln!("    Matched '{}': {}", name, sig.signature);
+                log_info!("    Matched '{}': {}", name, sig.signature);
                 matched += 1;
             }
         }
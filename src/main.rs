@@ -2,12 +2,16 @@
 //!
 //! Usage: magellan <command> [arguments]
 
+mod api_diff_cmd;
 mod ask_cmd;
 mod ast_cmd;
 mod backfill_cmd;
+mod bench_cmd;
 mod blast_score_cmd;
 mod candidate_fact_cmd;
 mod catalog_cmd;
+mod check_cmd;
+mod checkpoint_cmd;
 mod cli;
 mod collisions_cmd;
 mod condense_cmd;
@@ -19,6 +23,7 @@ mod cypher_cmd;
 mod db_resolver;
 mod dead_code_cmd;
 mod delete_cmd;
+mod diff_cmd;
 mod doctor_cmd;
 mod embed_cmd;
 mod enrich_cmd;
@@ -31,21 +36,27 @@ mod get_cmd;
 mod hnsw_cmd;
 mod hook_cmd;
 mod hopgraph_cmd;
+mod implementors_cmd;
 mod import_lsif_cmd;
 mod index_cmd;
 mod ingest_coverage;
 mod ingest_coverage_cmd;
 mod init_cmd;
 mod label_cmd;
+mod metrics_cmd;
 mod migrate_cmd;
 mod navigate_cmd;
 mod orient_cmd;
+mod parse_cmd;
 mod path_enumeration_cmd;
 mod project_metadata_cmd;
+mod prune_cmd;
 mod query_cmd;
 mod reachable_cmd;
+mod rebase_paths_cmd;
 mod refresh_cmd;
 mod refs_cmd;
+mod schema_cmd;
 mod score_cmd;
 mod service;
 mod service_cmd;
@@ -55,7 +66,9 @@ mod status_cmd;
 mod telemetry_cmd;
 mod temporal_query_cmd;
 mod temporal_sweep_cmd;
+mod timings_cmd;
 mod verify_cmd;
+mod verify_ids_cmd;
 mod version;
 mod watch_cmd;
 
@@ -64,7 +77,8 @@ use magellan::CodeGraph;
 use std::process::ExitCode;
 
 use cli::{parse_args, Command};
-use status_cmd::run_status;
+use magellan::{log_info, log_warn};
+use status_cmd::{run_status, run_status_watch};
 
 // Re-export for other command modules that use crate::generate_execution_id
 pub use magellan::output::generate_execution_id;
@@ -134,6 +148,17 @@ fn main() -> ExitCode {
             }
             ExitCode::SUCCESS
         }
+        Ok(Command::MetricsBackfill {
+            db_path,
+            only,
+            output_format,
+        }) => {
+            if let Err(e) = metrics_cmd::run_metrics_backfill(db_path, only, output_format) {
+                eprintln!("Error: {}", e);
+                return ExitCode::from(1);
+            }
+            ExitCode::SUCCESS
+        }
         Ok(Command::CrossFileRefs {
             db_path,
             fqn,
@@ -228,9 +253,38 @@ fn main() -> ExitCode {
         Ok(Command::Index {
             db_path,
             file_path,
+            changed,
             root,
+            api_only,
+            path_style,
+            hash_normalization,
+            hash_algo,
+            sqlite_options,
         }) => {
-            if let Err(e) = index_cmd::run_index(db_path, file_path, root) {
+            let result = match (file_path, changed) {
+                (_, Some(changed)) => index_cmd::run_index_changed(
+                    db_path,
+                    changed,
+                    root,
+                    api_only,
+                    path_style,
+                    hash_normalization,
+                    hash_algo,
+                    sqlite_options,
+                ),
+                (Some(file_path), None) => index_cmd::run_index(
+                    db_path,
+                    file_path,
+                    root,
+                    api_only,
+                    path_style,
+                    hash_normalization,
+                    hash_algo,
+                    sqlite_options,
+                ),
+                (None, None) => Err(anyhow::anyhow!("--file is required")),
+            };
+            if let Err(e) = result {
                 eprintln!("Error: {}", e);
                 return ExitCode::from(1);
             }
@@ -240,9 +294,14 @@ fn main() -> ExitCode {
             output_format,
             db_path,
             all,
-            ..
+            watch_interval,
+            by_kind,
         }) => {
-            if let Err(e) = run_status(db_path, output_format, all) {
+            let result = match watch_interval {
+                Some(interval) => run_status_watch(db_path, interval, by_kind),
+                None => run_status(db_path, output_format, all),
+            };
+            if let Err(e) = result {
                 eprintln!("Error: {}", e);
                 return ExitCode::from(1);
             }
@@ -269,8 +328,11 @@ fn main() -> ExitCode {
             include_references,
             include_calls,
             minify,
+            gzip,
             include_collisions,
             collisions_field,
+            include_unresolved,
+            record_types,
             filters,
             impact_symbol,
             impact_file,
@@ -284,8 +346,11 @@ fn main() -> ExitCode {
                 include_references,
                 include_calls,
                 minify,
+                gzip,
                 include_collisions,
                 collisions_field,
+                include_unresolved,
+                record_types,
                 filters,
                 impact_symbol,
                 impact_file,
@@ -449,24 +514,63 @@ fn main() -> ExitCode {
             with_callees,
             with_semantics,
             with_checksums,
+            with_metrics,
             context_lines,
+            count_only,
+            sort,
+            test_filter,
+            visibility,
+            defined_in,
+            column_unit,
+            baseline,
+            changed_only,
         }) => {
-            if let Err(e) = query_cmd::run_query(
-                db_path,
-                file_path,
-                root,
-                kind,
-                explain,
-                symbol,
-                show_extent,
-                output_format,
-                with_context,
-                with_callers,
-                with_callees,
-                with_semantics,
-                with_checksums,
-                context_lines,
-            ) {
+            let result = if let (Some(baseline), Some(file_path)) = (baseline, file_path.clone()) {
+                query_cmd::run_query_baseline(
+                    db_path,
+                    file_path,
+                    root,
+                    baseline,
+                    kind,
+                    changed_only,
+                    output_format,
+                )
+            } else if count_only {
+                query_cmd::run_query_count(
+                    db_path,
+                    file_path,
+                    root,
+                    kind,
+                    symbol,
+                    test_filter,
+                    visibility,
+                    output_format,
+                )
+            } else {
+                query_cmd::run_query(
+                    db_path,
+                    file_path,
+                    root,
+                    kind,
+                    explain,
+                    symbol,
+                    show_extent,
+                    output_format,
+                    with_context,
+                    with_callers,
+                    with_callees,
+                    with_semantics,
+                    with_checksums,
+                    with_metrics,
+                    context_lines,
+                    sort,
+                    test_filter,
+                    visibility,
+                    defined_in,
+                    column_unit,
+                )
+            };
+            if let Err(e) = result {
                 eprintln!("Error: {}", e);
                 return ExitCode::from(1);
             }
@@ -487,27 +591,105 @@ fn main() -> ExitCode {
             with_callees,
             with_semantics,
             with_checksums,
+            with_metrics,
             context_lines,
             all,
+            count_only,
+            fuzzy,
+            fuzzy_fqn,
+            limit,
+            test_filter,
+            visibility,
+            defined_in,
+            kind,
+            near,
+            column_unit,
+            deprecated,
         }) => {
-            if let Err(e) = find_cmd::run_find(
-                db_path,
-                name,
-                root,
-                path,
-                glob_pattern,
-                symbol_id,
-                ambiguous_name,
-                first,
-                output_format,
-                with_context,
-                with_callers,
-                with_callees,
-                with_semantics,
-                with_checksums,
-                context_lines,
-                all,
-            ) {
+            let result = if let Some((file, line)) = near {
+                find_cmd::run_find_near(
+                    db_path,
+                    file,
+                    line,
+                    kind,
+                    root,
+                    limit,
+                    test_filter,
+                    visibility,
+                    output_format,
+                    column_unit,
+                )
+            } else if let Some(query) = fuzzy {
+                find_cmd::run_find_fuzzy(
+                    db_path,
+                    query,
+                    fuzzy_fqn,
+                    root,
+                    path,
+                    limit,
+                    test_filter,
+                    visibility,
+                    output_format,
+                    column_unit,
+                )
+            } else if count_only {
+                find_cmd::run_find_count(
+                    db_path,
+                    name,
+                    root,
+                    path,
+                    test_filter,
+                    visibility,
+                    output_format,
+                )
+            } else if let Some(prefix) = defined_in {
+                find_cmd::run_find_defined_in(
+                    db_path,
+                    prefix,
+                    kind,
+                    root,
+                    path,
+                    test_filter,
+                    visibility,
+                    output_format,
+                    column_unit,
+                )
+            } else if deprecated {
+                find_cmd::run_find_deprecated(
+                    db_path,
+                    name,
+                    root,
+                    path,
+                    test_filter,
+                    visibility,
+                    output_format,
+                    column_unit,
+                )
+            } else {
+                find_cmd::run_find(
+                    db_path,
+                    name,
+                    root,
+                    path,
+                    glob_pattern,
+                    symbol_id,
+                    ambiguous_name,
+                    first,
+                    output_format,
+                    with_context,
+                    with_callers,
+                    with_callees,
+                    with_semantics,
+                    with_checksums,
+                    with_metrics,
+                    context_lines,
+                    all,
+                    test_filter,
+                    visibility,
+                    column_unit,
+                )
+            };
+            if let Err(e) = result {
                 eprintln!("Error: {}", e);
                 return ExitCode::from(1);
             }
@@ -527,6 +709,9 @@ fn main() -> ExitCode {
             context_lines,
             all,
             tokens,
+            group_by_file,
+            since,
+            column_unit,
         }) => {
             if let Err(e) = refs_cmd::run_refs(
                 db_path,
@@ -542,6 +727,9 @@ fn main() -> ExitCode {
                 context_lines,
                 all,
                 tokens,
+                group_by_file,
+                since,
+                column_unit,
             ) {
                 eprintln!("Error: {}", e);
                 return ExitCode::from(1);
@@ -559,13 +747,98 @@ fn main() -> ExitCode {
             }
             ExitCode::SUCCESS
         }
+        Ok(Command::Timings {
+            db_path,
+            top,
+            output_format,
+        }) => {
+            if let Err(e) = timings_cmd::run_timings(db_path, top, output_format) {
+                eprintln!("Error: {}", e);
+                return ExitCode::from(1);
+            }
+            ExitCode::SUCCESS
+        }
+        Ok(Command::Implementors {
+            db_path,
+            name,
+            output_format,
+        }) => {
+            if let Err(e) = implementors_cmd::run_implementors(db_path, name, output_format) {
+                eprintln!("Error: {}", e);
+                return ExitCode::from(1);
+            }
+            ExitCode::SUCCESS
+        }
+        Ok(Command::Prune {
+            db_path,
+            dry_run,
+            output_format,
+        }) => {
+            if let Err(e) = prune_cmd::run_prune(db_path, dry_run, output_format) {
+                eprintln!("Error: {}", e);
+                return ExitCode::from(1);
+            }
+            ExitCode::SUCCESS
+        }
+        Ok(Command::Bench {
+            root,
+            runs,
+            output_format,
+        }) => {
+            if let Err(e) = bench_cmd::run_bench(root, runs, output_format) {
+                eprintln!("Error: {}", e);
+                return ExitCode::from(1);
+            }
+            ExitCode::SUCCESS
+        }
+        Ok(Command::Checkpoint {
+            db_path,
+            output_format,
+        }) => {
+            if let Err(e) = checkpoint_cmd::run_checkpoint(db_path, output_format) {
+                eprintln!("Error: {}", e);
+                return ExitCode::from(1);
+            }
+            ExitCode::SUCCESS
+        }
+        Ok(Command::RebasePaths {
+            db_path,
+            from,
+            to,
+            output_format,
+        }) => {
+            if let Err(e) = rebase_paths_cmd::run_rebase_paths(db_path, from, to, output_format) {
+                eprintln!("Error: {}", e);
+                return ExitCode::from(1);
+            }
+            ExitCode::SUCCESS
+        }
+        Ok(Command::Schema {
+            type_name,
+            output_format,
+        }) => {
+            if let Err(e) = schema_cmd::run_schema(type_name, output_format) {
+                eprintln!("Error: {}", e);
+                return ExitCode::from(1);
+            }
+            ExitCode::SUCCESS
+        }
         Ok(Command::Collisions {
             db_path,
             field,
             limit,
+            canonicalize_generics,
+            resolve_suggestions,
             output_format,
         }) => {
-            if let Err(e) = collisions_cmd::run_collisions(db_path, field, limit, output_format) {
+            if let Err(e) = collisions_cmd::run_collisions(
+                db_path,
+                field,
+                limit,
+                canonicalize_generics,
+                resolve_suggestions,
+                output_format,
+            ) {
                 eprintln!("Error: {}", e);
                 return ExitCode::from(1);
             }
@@ -626,10 +899,28 @@ fn main() -> ExitCode {
             output_db,
             export_dir,
             dry_run,
+            resume,
             output_format,
         }) => {
-            match magellan::migrate_backend_cmd::run_migrate_backend(
-                input_db, output_db, export_dir, dry_run,
+            let options = magellan::migrate_backend_cmd::MigrateBackendOptions {
+                dry_run,
+                resume,
+                ..Default::default()
+            };
+            match magellan::migrate_backend_cmd::run_migrate_backend_with_progress(
+                input_db,
+                output_db,
+                export_dir,
+                &options,
+                &mut |progress| {
+                    log_info!(
+                        "Migrated {}/{} rows of {}",
+                        progress.rows_copied,
+                        progress.rows_total,
+                        progress.table
+                    );
+                    Ok(())
+                },
             ) {
                 Ok(result) => {
                     match output_format {
@@ -643,6 +934,8 @@ fn main() -> ExitCode {
                                 "entities_migrated": result.entities_migrated,
                                 "edges_migrated": result.edges_migrated,
                                 "side_tables_migrated": result.side_tables_migrated,
+                                "resumed": result.resumed,
+                                "planned": result.planned,
                                 "message": result.message,
                                 "execution_id": exec_id,
                             });
@@ -654,7 +947,12 @@ fn main() -> ExitCode {
                             }
                         }
                         OutputFormat::Human => {
-                            if result.success {
+                            if result.success && !result.planned.is_empty() {
+                                println!("{}", result.message);
+                                for planned in &result.planned {
+                                    println!("  {}: {} rows", planned.table, planned.rows);
+                                }
+                            } else if result.success {
                                 println!("{}", result.message);
                                 println!(
                                     "Format: {:?} -> {:?}",
@@ -665,6 +963,9 @@ fn main() -> ExitCode {
                                 if result.side_tables_migrated {
                                     println!("Side tables: migrated");
                                 }
+                                if result.resumed {
+                                    println!("Resumed from checkpoint");
+                                }
                             } else {
                                 eprintln!("Migration failed: {}", result.message);
                                 return ExitCode::from(1);
@@ -688,6 +989,7 @@ fn main() -> ExitCode {
             with_semantics,
             with_checksums,
             context_lines,
+            expand_to,
         }) => {
             if let Err(e) = get_cmd::run_get(
                 db_path,
@@ -698,6 +1000,32 @@ fn main() -> ExitCode {
                 with_semantics,
                 with_checksums,
                 context_lines,
+                expand_to,
+            ) {
+                eprintln!("Error: {}", e);
+                return ExitCode::from(1);
+            }
+            ExitCode::SUCCESS
+        }
+        Ok(Command::GetBySymbolId {
+            db_path,
+            symbol_id,
+            output_format,
+            with_context,
+            with_semantics,
+            with_checksums,
+            context_lines,
+            expand_to,
+        }) => {
+            if let Err(e) = get_cmd::run_get_by_symbol_id(
+                db_path,
+                symbol_id,
+                output_format,
+                with_context,
+                with_semantics,
+                with_checksums,
+                context_lines,
+                expand_to,
             ) {
                 eprintln!("Error: {}", e);
                 return ExitCode::from(1);
@@ -707,9 +1035,13 @@ fn main() -> ExitCode {
         Ok(Command::GetFile {
             db_path,
             file_path,
+            outline,
+            line_range,
             output_format,
         }) => {
-            if let Err(e) = get_cmd::run_get_file(db_path, file_path, output_format) {
+            if let Err(e) =
+                get_cmd::run_get_file(db_path, file_path, outline, line_range, output_format)
+            {
                 eprintln!("Error: {}", e);
                 return ExitCode::from(1);
             }
@@ -801,12 +1133,26 @@ fn main() -> ExitCode {
             }
             ExitCode::SUCCESS
         }
+        Ok(Command::Chunks {
+            db_path,
+            output_format,
+            verify: true,
+            root,
+            ..
+        }) => match get_cmd::run_chunks_verify(db_path, root, output_format) {
+            Ok(exit_code) => ExitCode::from(exit_code),
+            Err(e) => {
+                eprintln!("Error: {}", e);
+                ExitCode::from(1)
+            }
+        },
         Ok(Command::Chunks {
             db_path,
             output_format,
             limit,
             file_filter,
             kind_filter,
+            ..
         }) => {
             if let Err(e) =
                 get_cmd::run_chunks(db_path, output_format, limit, file_filter, kind_filter)
@@ -836,10 +1182,15 @@ fn main() -> ExitCode {
             symbol_name,
             output_format,
             file_filter,
+            limit,
         }) => {
-            if let Err(e) =
-                get_cmd::run_chunk_by_symbol(db_path, symbol_name, output_format, file_filter)
-            {
+            if let Err(e) = get_cmd::run_chunk_by_symbol(
+                db_path,
+                symbol_name,
+                output_format,
+                file_filter,
+                limit,
+            ) {
                 eprintln!("Error: {}", e);
                 return ExitCode::from(1);
             }
@@ -864,31 +1215,126 @@ fn main() -> ExitCode {
         Ok(Command::Verify {
             root_path,
             db_path,
+            integrity,
+            overlaps,
+            output_format,
+        }) => match verify_cmd::run_verify(
+            root_path,
+            db_path,
+            integrity,
+            overlaps,
+            output_format,
+        ) {
+            Ok(exit_code) => ExitCode::from(exit_code),
+            Err(e) => {
+                eprintln!("Error: {}", e);
+                ExitCode::from(1)
+            }
+        },
+        Ok(Command::VerifyIds {
+            root_path,
+            db_path,
+            output_format,
+        }) => match verify_ids_cmd::run_verify_ids(root_path, db_path, output_format) {
+            Ok(exit_code) => ExitCode::from(exit_code),
+            Err(e) => {
+                eprintln!("Error: {}", e);
+                ExitCode::from(1)
+            }
+        },
+        Ok(Command::Check {
+            db_path,
+            root_path,
+            fail_on,
             output_format,
-        }) => match verify_cmd::run_verify(root_path, db_path, output_format) {
+        }) => match check_cmd::run_check(db_path, root_path, fail_on, output_format) {
+            Ok(exit_code) => ExitCode::from(exit_code),
+            Err(e) => {
+                eprintln!("Error: {}", e);
+                ExitCode::from(check_cmd::CHECK_ERROR_EXIT)
+            }
+        },
+        Ok(Command::ApiDiff {
+            db_a,
+            db_b,
+            output_format,
+        }) => match api_diff_cmd::run_api_diff(db_a, db_b, output_format) {
             Ok(exit_code) => ExitCode::from(exit_code),
             Err(e) => {
                 eprintln!("Error: {}", e);
                 ExitCode::from(1)
             }
         },
+        Ok(Command::Diff {
+            old_db,
+            new_db,
+            output_format,
+        }) => {
+            if let Err(e) = diff_cmd::run_diff(old_db, new_db, output_format) {
+                eprintln!("Error: {}", e);
+                return ExitCode::from(1);
+            }
+            ExitCode::SUCCESS
+        }
         Ok(Command::Watch {
             root_path,
+            extra_roots,
             db_path,
             config,
             scan_initial,
             validate,
             validate_only,
             compile_commands,
+            api_only,
+            compress_chunks,
+            no_calls,
+            path_style,
+            hash_normalization,
+            hash_algo,
+            sqlite_options,
+            config_path,
+            max_file_size,
+            parse_timeout_ms,
+            include_patterns,
+            exclude_patterns,
+            jobs,
+            once,
+            checkpoint_interval,
+            include_generated,
+            mem_report,
+            profile,
+            dry_run,
+            output_format,
         }) => {
             if let Err(e) = watch_cmd::run_watch(
                 root_path,
+                extra_roots,
                 db_path,
                 config,
                 scan_initial,
                 validate,
                 validate_only,
                 compile_commands,
+                api_only,
+                compress_chunks,
+                no_calls,
+                path_style,
+                hash_normalization,
+                hash_algo,
+                sqlite_options,
+                config_path,
+                max_file_size,
+                parse_timeout_ms,
+                include_patterns,
+                exclude_patterns,
+                jobs,
+                once,
+                checkpoint_interval,
+                include_generated,
+                mem_report,
+                profile,
+                dry_run,
+                output_format,
             ) {
                 eprintln!("Error: {}", e);
                 return ExitCode::from(1);
@@ -900,8 +1346,29 @@ fn main() -> ExitCode {
             file_path,
             position,
             output_format,
+            subtree_depth,
+            query,
+        }) => {
+            if let Err(e) = ast_cmd::run_ast_command(
+                db_path,
+                file_path,
+                position,
+                output_format,
+                subtree_depth,
+                query,
+            ) {
+                eprintln!("Error: {}", e);
+                return ExitCode::from(1);
+            }
+            ExitCode::SUCCESS
+        }
+        Ok(Command::Parse {
+            lang,
+            file_path,
+            from_stdin,
+            output_format,
         }) => {
-            if let Err(e) = ast_cmd::run_ast_command(db_path, file_path, position, output_format) {
+            if let Err(e) = parse_cmd::run_parse(lang, file_path, from_stdin, output_format) {
                 eprintln!("Error: {}", e);
                 return ExitCode::from(1);
             }
@@ -922,10 +1389,18 @@ fn main() -> ExitCode {
             db_path,
             symbol_id,
             reverse,
+            dot,
+            max_depth,
             output_format,
         }) => {
-            if let Err(e) = reachable_cmd::run_reachable(db_path, symbol_id, reverse, output_format)
-            {
+            if let Err(e) = reachable_cmd::run_reachable(
+                db_path,
+                symbol_id,
+                reverse,
+                dot,
+                max_depth,
+                output_format,
+            ) {
                 eprintln!("Error: {}", e);
                 return ExitCode::from(1);
             }
@@ -934,9 +1409,12 @@ fn main() -> ExitCode {
         Ok(Command::DeadCode {
             db_path,
             entry_symbol_id,
+            test_filter,
             output_format,
         }) => {
-            if let Err(e) = dead_code_cmd::run_dead_code(db_path, entry_symbol_id, output_format) {
+            if let Err(e) =
+                dead_code_cmd::run_dead_code(db_path, entry_symbol_id, test_filter, output_format)
+            {
                 eprintln!("Error: {}", e);
                 return ExitCode::from(1);
             }
@@ -948,6 +1426,8 @@ fn main() -> ExitCode {
             end_symbol_id,
             max_depth,
             max_paths,
+            shortest,
+            avoid,
             output_format,
         }) => {
             if let Err(e) = path_enumeration_cmd::run_paths(
@@ -956,6 +1436,8 @@ fn main() -> ExitCode {
                 end_symbol_id,
                 max_depth,
                 max_paths,
+                shortest,
+                avoid,
                 output_format,
             ) {
                 eprintln!("Error: {}", e);
@@ -977,9 +1459,12 @@ fn main() -> ExitCode {
         Ok(Command::Condense {
             db_path,
             show_members,
+            min_scc_size,
             output_format,
         }) => {
-            if let Err(e) = condense_cmd::run_condense(db_path, show_members, output_format) {
+            if let Err(e) =
+                condense_cmd::run_condense(db_path, show_members, min_scc_size, output_format)
+            {
                 eprintln!("Error: {}", e);
                 return ExitCode::from(1);
             }
@@ -990,6 +1475,8 @@ fn main() -> ExitCode {
             target,
             direction,
             verbose,
+            with_code,
+            max_bytes,
             output_format,
         }) => {
             let cli_direction = match slice_cmd::CliSliceDirection::from_str(&direction) {
@@ -999,9 +1486,15 @@ fn main() -> ExitCode {
                     return ExitCode::from(1);
                 }
             };
-            if let Err(e) =
-                slice_cmd::run_slice(db_path, target, cli_direction, verbose, output_format)
-            {
+            if let Err(e) = slice_cmd::run_slice(
+                db_path,
+                target,
+                cli_direction,
+                verbose,
+                with_code,
+                max_bytes,
+                output_format,
+            ) {
                 eprintln!("Error: {}", e);
                 return ExitCode::from(1);
             }
@@ -1022,7 +1515,7 @@ fn main() -> ExitCode {
                 match refresh_cmd::resolve_db_path(None) {
                     Ok(p) => p,
                     Err(e) => {
-                        eprintln!("Warning: registry lookup failed ({}), using default", e);
+                        log_warn!("registry lookup failed ({}), using default", e);
                         raw_db_path
                     }
                 }
@@ -1329,6 +1822,9 @@ fn main() -> ExitCode {
         }
         Err(e) => {
             eprintln!("Error: {}", e);
+            if e.downcast_ref::<cli::SchemaMismatch>().is_some() {
+                return ExitCode::from(cli::EXIT_SCHEMA_MISMATCH);
+            }
             print_short_usage();
             ExitCode::from(1)
         }
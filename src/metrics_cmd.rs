@@ -0,0 +1,95 @@
+//! Metrics command implementation
+//!
+//! `metrics backfill` recomputes the file and symbol metrics of indexed
+//! files from their current source, optionally scoped with `--only`.
+
+use anyhow::Result;
+use magellan::graph::filter::FilePattern;
+use magellan::log_info;
+use magellan::output::{
+    generate_execution_id, output_json, BackfillErrorEntry, JsonResponse, MetricsBackfillResponse,
+    OutputFormat,
+};
+use magellan::CodeGraph;
+use std::path::PathBuf;
+
+/// Run the metrics backfill command
+///
+/// # Arguments
+/// * `db_path` - Path to the sqlitegraph database
+/// * `only` - Glob or substring restricting the files recomputed
+/// * `output_format` - Output format (Human, Json, or Pretty)
+pub fn run_metrics_backfill(
+    db_path: PathBuf,
+    only: Option<String>,
+    output_format: OutputFormat,
+) -> Result<()> {
+    let pattern = only.as_deref().map(FilePattern::new).transpose()?;
+    let mut graph = CodeGraph::open(&db_path)?;
+    let exec_id = generate_execution_id();
+
+    let mut args = vec![
+        "metrics".to_string(),
+        "backfill".to_string(),
+        "--db".to_string(),
+        db_path.to_string_lossy().to_string(),
+    ];
+    if let Some(ref only) = only {
+        args.push("--only".to_string());
+        args.push(only.clone());
+    }
+    graph.execution_log().start_execution(
+        &exec_id,
+        env!("CARGO_PKG_VERSION"),
+        &args,
+        None,
+        &db_path.to_string_lossy(),
+    )?;
+
+    // Progress goes to stderr so JSON output on stdout stays parseable
+    let progress = |current: usize, total: usize, path: &str| {
+        log_info!("[{}/{}] {}", current, total, path);
+    };
+    let result = graph.backfill_metrics_matching(Some(&progress), pattern.as_ref())?;
+
+    match output_format {
+        OutputFormat::Json | OutputFormat::Pretty => {
+            let response = MetricsBackfillResponse {
+                only,
+                total: result.total,
+                processed: result.processed,
+                symbols_updated: result.symbols_updated,
+                errors: result
+                    .errors
+                    .iter()
+                    .map(|(file_path, message)| BackfillErrorEntry {
+                        file_path: file_path.clone(),
+                        message: message.clone(),
+                    })
+                    .collect(),
+            };
+            let json_response = JsonResponse::new(response, &exec_id);
+            output_json(&json_response, output_format)?;
+        }
+        OutputFormat::Human => {
+            println!(
+                "Backfilled metrics for {}/{} file(s), {} symbol(s) updated",
+                result.processed, result.total, result.symbols_updated
+            );
+            for (file_path, message) in &result.errors {
+                println!("  error: {}: {}", file_path, message);
+            }
+        }
+    }
+
+    graph.execution_log().finish_execution(
+        &exec_id,
+        "success",
+        None,
+        result.processed,
+        result.symbols_updated,
+        0,
+    )?;
+
+    Ok(())
+}
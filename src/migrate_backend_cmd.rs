@@ -4,20 +4,25 @@
 //!
 //! ## Architecture
 //!
-//! This module uses sqlitegraph's `GraphBackend::snapshot_export()` and
-//! `snapshot_import()` methods for graph data migration. The wrapper only
-//! handles directory creation and returns Magellan-specific metadata.
+//! Graph tables (`graph_entities`, `graph_edges`, `graph_labels`,
+//! `graph_properties`) are copied in source rowid order, a batch of rows per
+//! transaction. Each batch also commits a checkpoint, so an interrupted
+//! migration resumes from its last committed batch (`--resume`). With
+//! `--export-dir`, sqlitegraph's `GraphBackend::snapshot_export()` also
+//! writes a JSON snapshot of the graph.
 //!
 //! ## Side Tables
 //!
 //! GraphBackend does NOT handle Magellan-specific side tables:
 //! - `code_chunks` - Code snippets stored in generation module
+//! - `chunk_content` - Deduplicated chunk text, keyed by content hash
 //! - `file_metrics`, `symbol_metrics` - Pre-computed metrics
 //! - `execution_log` - Command execution tracking
 //! - `ast_nodes` - AST hierarchy storage
 //! - `cfg_blocks` - Control flow graph data
 //!
-//! These must be migrated separately via direct SQL.
+//! These are copied after the graph tables, batched and checkpointed the
+//! same way.
 
 use anyhow::Result;
 use sqlitegraph::GraphBackend;
@@ -250,6 +255,75 @@ pub fn verify_import_counts(
     Ok(())
 }
 
+/// Rows copied per transaction by default
+pub const DEFAULT_BATCH_SIZE: usize = 10_000;
+
+/// Output-database table holding the last copied source rowid of each table
+///
+/// Dropped once the migration completes, so its presence marks an
+/// interrupted migration that `--resume` can continue.
+pub const CHECKPOINT_TABLE: &str = "magellan_migration_checkpoint";
+
+/// sqlitegraph tables, copied before the side tables
+const GRAPH_TABLES: [&str; 4] = [
+    "graph_entities",
+    "graph_edges",
+    "graph_labels",
+    "graph_properties",
+];
+
+/// Magellan-specific side tables
+const SIDE_TABLES: [&str; 7] = [
+    "code_chunks",
+    "chunk_content",
+    "file_metrics",
+    "symbol_metrics",
+    "execution_log",
+    "ast_nodes",
+    "cfg_blocks",
+];
+
+/// Options for [`run_migrate_backend_with_progress`]
+#[derive(Debug, Clone, Copy)]
+pub struct MigrateBackendOptions {
+    /// Report planned row counts without creating the output database
+    pub dry_run: bool,
+    /// Continue an interrupted migration from its last committed batch
+    pub resume: bool,
+    /// Rows copied per transaction; each batch commits a checkpoint
+    pub batch_size: usize,
+}
+
+impl Default for MigrateBackendOptions {
+    fn default() -> Self {
+        Self {
+            dry_run: false,
+            resume: false,
+            batch_size: DEFAULT_BATCH_SIZE,
+        }
+    }
+}
+
+/// Progress of one table, reported after each committed batch
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MigrationProgress {
+    /// Table being copied
+    pub table: &'static str,
+    /// Rows of the table copied so far, including rows from earlier runs
+    pub rows_copied: i64,
+    /// Rows of the table in the source database
+    pub rows_total: i64,
+}
+
+/// Row count of one source table, as planned by `--dry-run`
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct TableRowCount {
+    /// Table name
+    pub table: String,
+    /// Rows in the source database
+    pub rows: i64,
+}
+
 /// Result of a backend migration operation
 #[derive(Debug, Clone)]
 pub struct BackendMigrationResult {
@@ -265,6 +339,10 @@ pub struct BackendMigrationResult {
     pub edges_migrated: i64,
     /// Whether side tables were migrated
     pub side_tables_migrated: bool,
+    /// Whether the migration continued from an earlier checkpoint
+    pub resumed: bool,
+    /// Rows per source table that would be copied (dry run only)
+    pub planned: Vec<TableRowCount>,
     /// Human-readable status message
     pub message: String,
 }
@@ -272,7 +350,10 @@ pub struct BackendMigrationResult {
 impl std::fmt::Display for BackendMigrationResult {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}", self.message)?;
-        if self.success {
+        for planned in &self.planned {
+            write!(f, "\n  {}: {} rows", planned.table, planned.rows)?;
+        }
+        if self.success && self.planned.is_empty() {
             write!(
                 f,
                 "\nFormat: {:?} -> {:?}\nEntities: {}\nEdges: {}",
@@ -281,6 +362,9 @@ impl std::fmt::Display for BackendMigrationResult {
             if self.side_tables_migrated {
                 write!(f, "\nSide tables: migrated")?;
             }
+            if self.resumed {
+                write!(f, "\nResumed from checkpoint")?;
+            }
         }
         Ok(())
     }
@@ -288,19 +372,43 @@ impl std::fmt::Display for BackendMigrationResult {
 
 /// Run a complete backend migration from source to target database
 ///
-/// This function orchestrates the full migration pipeline:
-/// 1. Open source SQLite database
-/// 2. Export graph data to snapshot format  
-/// 3. Create new target SQLite database
-/// 4. Import snapshot into target database
-/// 5. Migrate Magellan-specific side tables
+/// Equivalent to [`run_migrate_backend_with_progress`] with default batching,
+/// no resume, and no progress reporting.
 pub fn run_migrate_backend(
     input_db: PathBuf,
     output_db: PathBuf,
     export_dir: Option<PathBuf>,
     dry_run: bool,
 ) -> Result<BackendMigrationResult> {
-    let cleanup_export_dir = export_dir.is_none();
+    let options = MigrateBackendOptions {
+        dry_run,
+        ..MigrateBackendOptions::default()
+    };
+    run_migrate_backend_with_progress(input_db, output_db, export_dir, &options, &mut |_| Ok(()))
+}
+
+/// Run a backend migration, reporting progress after each committed batch
+///
+/// This function orchestrates the full migration pipeline:
+/// 1. Open source SQLite database
+/// 2. Optionally export graph data to a snapshot in `export_dir`
+/// 3. Create the target SQLite database (or reopen it with `resume`)
+/// 4. Copy graph tables in rowid order, `batch_size` rows per transaction
+/// 5. Copy Magellan-specific side tables the same way
+///
+/// Every batch records its last source rowid in [`CHECKPOINT_TABLE`] within
+/// the same transaction, so an interrupted migration loses at most the batch
+/// in flight. Rerunning with `resume` skips the committed rows and produces
+/// the same output as an uninterrupted run. An error returned by `progress`
+/// stops the migration after the batch it was reported for.
+pub fn run_migrate_backend_with_progress(
+    input_db: PathBuf,
+    output_db: PathBuf,
+    export_dir: Option<PathBuf>,
+    options: &MigrateBackendOptions,
+    progress: &mut dyn FnMut(&MigrationProgress) -> Result<()>,
+) -> Result<BackendMigrationResult> {
+    use rusqlite::Connection;
 
     // Validate input_db exists
     if !input_db.exists() {
@@ -311,6 +419,8 @@ pub fn run_migrate_backend(
             entities_migrated: 0,
             edges_migrated: 0,
             side_tables_migrated: false,
+            resumed: false,
+            planned: Vec::new(),
             message: format!("Input database not found: {}", input_db.display()),
         });
     }
@@ -332,8 +442,38 @@ pub fn run_migrate_backend(
         Arc::new(SqliteGraphBackend::from_graph(sqlite_graph))
     };
 
-    // Dry run: just return success
-    if dry_run {
+    let source_conn = Connection::open(&input_db).map_err(|e| {
+        anyhow::anyhow!(
+            "Failed to open source database '{}': {}",
+            input_db.display(),
+            e
+        )
+    })?;
+
+    // Dry run: report what would be copied
+    if options.dry_run {
+        let mut planned = Vec::new();
+        for table in GRAPH_TABLES.iter().chain(SIDE_TABLES.iter()) {
+            if let Some(rows) = count_rows(&source_conn, table)? {
+                planned.push(TableRowCount {
+                    table: table.to_string(),
+                    rows,
+                });
+            }
+        }
+        let planned_rows = |name: &str| {
+            planned
+                .iter()
+                .find(|count| count.table == name)
+                .map_or(0, |count| count.rows)
+        };
+        let message = format!(
+            "Would migrate {} entities and {} edges from {} to {} (dry run)",
+            planned_rows("graph_entities"),
+            planned_rows("graph_edges"),
+            input_db.display(),
+            output_db.display()
+        );
         return Ok(BackendMigrationResult {
             success: true,
             source_format: BackendFormat::Sqlite,
@@ -341,99 +481,113 @@ pub fn run_migrate_backend(
             entities_migrated: 0,
             edges_migrated: 0,
             side_tables_migrated: false,
-            message: format!(
-                "Would migrate from {:?} to {:?} (dry run)",
-                input_db.display(),
-                output_db.display()
-            ),
+            resumed: false,
+            planned,
+            message,
         });
     }
 
-    // Default export_dir to temp directory if not provided
-    let export_dir = export_dir.unwrap_or_else(|| {
-        let timestamp_val = chrono::Utc::now().timestamp();
-        std::env::temp_dir().join(format!("magellan_migration_{}", timestamp_val))
-    });
+    if options.batch_size == 0 {
+        anyhow::bail!("Migration batch size must be at least 1");
+    }
 
-    // Create export directory
-    fs::create_dir_all(&export_dir).map_err(|e| {
-        anyhow::anyhow!(
-            "Failed to create export directory '{}': {}",
-            export_dir.display(),
-            e
-        )
-    })?;
+    // Only an output database left behind by an interrupted run can resume
+    let has_checkpoint =
+        output_db.exists() && table_exists(&Connection::open(&output_db)?, CHECKPOINT_TABLE)?;
+    if options.resume && !has_checkpoint {
+        anyhow::bail!(
+            "No interrupted migration to resume in '{}'",
+            output_db.display()
+        );
+    }
 
-    // Export snapshot from source backend
-    let export_meta = export_snapshot(&source_backend, &export_dir).map_err(|e| {
-        anyhow::anyhow!(
-            "Failed to export snapshot from '{}': {}",
-            input_db.display(),
-            e
-        )
-    })?;
+    // Export a snapshot alongside the copy when asked to
+    if let Some(export_dir) = &export_dir {
+        export_snapshot(&source_backend, export_dir).map_err(|e| {
+            anyhow::anyhow!(
+                "Failed to export snapshot from '{}': {}",
+                input_db.display(),
+                e
+            )
+        })?;
+    }
+    drop(source_backend);
 
-    // Create target backend (SQLite)
-    let target_backend: Arc<dyn GraphBackend> = {
-        use sqlitegraph::{SqliteGraph, SqliteGraphBackend};
-        let sqlite_graph = SqliteGraph::open(&output_db).map_err(|e| {
+    // Create target backend (SQLite) so the graph schema exists
+    {
+        use sqlitegraph::SqliteGraph;
+        SqliteGraph::open(&output_db).map_err(|e| {
             anyhow::anyhow!(
                 "Failed to create SQLite database '{}': {}",
                 output_db.display(),
                 e
             )
         })?;
-        #[allow(
-            clippy::arc_with_non_send_sync,
-            reason = "backend is used single-threaded in migration"
-        )]
-        {
-            Arc::new(SqliteGraphBackend::from_graph(sqlite_graph))
-        }
-    };
+    }
 
-    // Import snapshot into target backend
-    let import_meta = import_snapshot(&target_backend, &export_dir).map_err(|e| {
+    let target_conn = Connection::open(&output_db).map_err(|e| {
         anyhow::anyhow!(
-            "Failed to import snapshot into '{}': {}",
+            "Failed to open target database '{}': {}",
             output_db.display(),
             e
         )
     })?;
 
-    // Verify data integrity (counts should match)
-    verify_import_counts(&export_meta, &import_meta).map_err(|e| {
-        anyhow::anyhow!(
-            "Data integrity verification failed: {}. Target database may be incomplete.",
-            e
+    if !options.resume {
+        // A fresh migration replaces graph data and any stale checkpoint
+        let tx = target_conn.unchecked_transaction()?;
+        tx.execute(&format!("DROP TABLE IF EXISTS {}", CHECKPOINT_TABLE), [])?;
+        for table in GRAPH_TABLES.iter().rev() {
+            tx.execute(&format!("DELETE FROM {}", table), [])?;
+        }
+        tx.commit()?;
+    }
+    create_checkpoint_table(&target_conn)?;
+
+    let mut graph_rows = std::collections::HashMap::new();
+    for table in GRAPH_TABLES {
+        let copied = copy_table_batched(
+            &source_conn,
+            &target_conn,
+            table,
+            options.batch_size,
+            progress,
         )
-    })?;
-
-    // Drop backend connections before migrating side tables
-    drop(source_backend);
-    drop(target_backend);
+        .map_err(|e| {
+            anyhow::anyhow!(
+                "Failed to migrate '{}' into '{}': {}",
+                table,
+                output_db.display(),
+                e
+            )
+        })?;
+        graph_rows.insert(table, copied);
+    }
 
     // Migrate Magellan-specific side tables
-    let side_tables_migrated = migrate_side_tables(&input_db, &output_db).map_err(|e| {
-        anyhow::anyhow!(
-            "Failed to migrate side tables from '{}' to '{}': {}",
-            input_db.display(),
-            output_db.display(),
-            e
-        )
-    })?;
-
-    if cleanup_export_dir {
-        let _ = fs::remove_dir_all(&export_dir);
-    }
+    let side_tables_migrated =
+        copy_side_tables(&source_conn, &target_conn, options.batch_size, progress).map_err(
+            |e| {
+                anyhow::anyhow!(
+                    "Failed to migrate side tables from '{}' to '{}': {}",
+                    input_db.display(),
+                    output_db.display(),
+                    e
+                )
+            },
+        )?;
+
+    target_conn.execute(&format!("DROP TABLE {}", CHECKPOINT_TABLE), [])?;
 
     Ok(BackendMigrationResult {
         success: true,
         source_format: BackendFormat::Sqlite,
         target_format: BackendFormat::Sqlite,
-        entities_migrated: import_meta.entities_imported,
-        edges_migrated: import_meta.edges_imported,
+        entities_migrated: graph_rows["graph_entities"],
+        edges_migrated: graph_rows["graph_edges"],
         side_tables_migrated,
+        resumed: options.resume,
+        planned: Vec::new(),
         message: format!(
             "Migration complete: {} -> {}",
             input_db.display(),
@@ -442,6 +596,171 @@ pub fn run_migrate_backend(
     })
 }
 
+/// Whether `table` exists in the database
+fn table_exists(conn: &rusqlite::Connection, table: &str) -> Result<bool> {
+    let count: i64 = conn.query_row(
+        "SELECT COUNT(*) FROM sqlite_master WHERE type='table' AND name=?1",
+        [table],
+        |row| row.get(0),
+    )?;
+    Ok(count > 0)
+}
+
+/// Row count of `table`, or `None` when the table does not exist
+fn count_rows(conn: &rusqlite::Connection, table: &str) -> Result<Option<i64>> {
+    if !table_exists(conn, table)? {
+        return Ok(None);
+    }
+    let count = conn.query_row(&format!("SELECT COUNT(*) FROM {}", table), [], |row| {
+        row.get(0)
+    })?;
+    Ok(Some(count))
+}
+
+fn create_checkpoint_table(conn: &rusqlite::Connection) -> Result<()> {
+    conn.execute(
+        &format!(
+            "CREATE TABLE IF NOT EXISTS {} (
+                table_name TEXT PRIMARY KEY,
+                last_rowid INTEGER NOT NULL
+            )",
+            CHECKPOINT_TABLE
+        ),
+        [],
+    )
+    .map_err(|e| anyhow::anyhow!("Failed to create migration checkpoint table: {}", e))?;
+    Ok(())
+}
+
+/// Copy `table` in source rowid order, one transaction per batch
+///
+/// Resumes after the rowid recorded in the checkpoint table, and records the
+/// last rowid of each batch in the same transaction as its rows. Returns the
+/// number of source rows copied, including rows copied by earlier runs.
+fn copy_table_batched(
+    source: &rusqlite::Connection,
+    target: &rusqlite::Connection,
+    table: &'static str,
+    batch_size: usize,
+    progress: &mut dyn FnMut(&MigrationProgress) -> Result<()>,
+) -> Result<i64> {
+    use rusqlite::types::Value;
+
+    let Some(rows_total) = count_rows(source, table)? else {
+        return Ok(0);
+    };
+
+    // Get column names
+    let mut columns: Vec<String> = Vec::new();
+    {
+        let mut stmt = source.prepare(&format!("PRAGMA table_info({})", table))?;
+        let rows = stmt.query_map([], |row| row.get::<_, String>(1))?;
+        for row in rows {
+            columns.push(row?);
+        }
+    }
+    if columns.is_empty() {
+        return Ok(0);
+    }
+
+    let column_list = columns.join(", ");
+    let placeholders = vec!["?"; columns.len()].join(", ");
+    let select_sql = format!(
+        "SELECT rowid, {} FROM {} WHERE rowid > ?1 ORDER BY rowid LIMIT ?2",
+        column_list, table
+    );
+    let insert_sql = format!(
+        "INSERT OR REPLACE INTO {} ({}) VALUES ({})",
+        table, column_list, placeholders
+    );
+    let checkpoint_sql = format!(
+        "INSERT OR REPLACE INTO {} (table_name, last_rowid) VALUES (?1, ?2)",
+        CHECKPOINT_TABLE
+    );
+
+    let mut last_rowid: i64 = target
+        .query_row(
+            &format!(
+                "SELECT last_rowid FROM {} WHERE table_name = ?1",
+                CHECKPOINT_TABLE
+            ),
+            [table],
+            |row| row.get(0),
+        )
+        .unwrap_or(i64::MIN);
+    let mut rows_copied: i64 = source.query_row(
+        &format!("SELECT COUNT(*) FROM {} WHERE rowid <= ?1", table),
+        [last_rowid],
+        |row| row.get(0),
+    )?;
+
+    let mut select_stmt = source.prepare(&select_sql)?;
+    loop {
+        let batch: Vec<(i64, Vec<Value>)> = select_stmt
+            .query_map(rusqlite::params![last_rowid, batch_size as i64], |row| {
+                let rowid: i64 = row.get(0)?;
+                let values = (1..=columns.len())
+                    .map(|i| row.get::<_, Value>(i))
+                    .collect::<rusqlite::Result<Vec<_>>>()?;
+                Ok((rowid, values))
+            })?
+            .collect::<rusqlite::Result<_>>()
+            .map_err(|e| anyhow::anyhow!("Failed to read rows from table '{}': {}", table, e))?;
+        let Some(&(batch_last_rowid, _)) = batch.last() else {
+            break;
+        };
+
+        let tx = target.unchecked_transaction()?;
+        {
+            let mut insert_stmt = tx.prepare_cached(&insert_sql)?;
+            for (_, values) in &batch {
+                insert_stmt
+                    .execute(rusqlite::params_from_iter(values))
+                    .map_err(|e| {
+                        anyhow::anyhow!("Failed to insert row into table '{}': {}", table, e)
+                    })?;
+            }
+        }
+        tx.execute(&checkpoint_sql, rusqlite::params![table, batch_last_rowid])?;
+        tx.commit()
+            .map_err(|e| anyhow::anyhow!("Failed to commit batch of table '{}': {}", table, e))?;
+
+        last_rowid = batch_last_rowid;
+        rows_copied += batch.len() as i64;
+        progress(&MigrationProgress {
+            table,
+            rows_copied,
+            rows_total,
+        })?;
+    }
+
+    Ok(rows_copied)
+}
+
+/// Copy every non-empty side table, returning whether any rows were copied
+fn copy_side_tables(
+    source: &rusqlite::Connection,
+    target: &rusqlite::Connection,
+    batch_size: usize,
+    progress: &mut dyn FnMut(&MigrationProgress) -> Result<()>,
+) -> Result<bool> {
+    let mut any_migrated = false;
+    for table in SIDE_TABLES {
+        if count_rows(source, table)?.unwrap_or(0) == 0 {
+            continue;
+        }
+
+        // Ensure schema exists in target database
+        ensure_table_schema(target, table)
+            .map_err(|e| anyhow::anyhow!("Failed to ensure schema for table '{}': {}", table, e))?;
+
+        if copy_table_batched(source, target, table, batch_size, progress)? > 0 {
+            any_migrated = true;
+        }
+    }
+    Ok(any_migrated)
+}
+
 /// Migrate Magellan-specific side tables from source to target database
 pub fn migrate_side_tables(source_db: &Path, target_db: &Path) -> Result<bool> {
     use rusqlite::Connection;
@@ -464,125 +783,12 @@ pub fn migrate_side_tables(source_db: &Path, target_db: &Path) -> Result<bool> {
         )
     })?;
 
-    // Define all side tables to migrate
-    let side_tables = [
-        "code_chunks",
-        "file_metrics",
-        "symbol_metrics",
-        "execution_log",
-        "ast_nodes",
-        "cfg_blocks",
-    ];
-
-    let mut any_migrated = false;
-
-    // Start transaction on target for atomicity
-    let tx = target_conn
-        .unchecked_transaction()
-        .map_err(|e| anyhow::anyhow!("Failed to start transaction on target database: {}", e))?;
-
-    // For each table, copy data from source to target
-    for table_name in &side_tables {
-        // Check if table exists in source database
-        let table_exists: bool = source_conn
-            .query_row(
-                "SELECT 1 FROM sqlite_master WHERE type='table' AND name=? LIMIT 1",
-                [table_name],
-                |_| Ok(true),
-            )
-            .unwrap_or(false);
-
-        if !table_exists {
-            continue;
-        }
-
-        // Get row count
-        let row_count: i64 = source_conn
-            .query_row(&format!("SELECT COUNT(*) FROM {}", table_name), [], |row| {
-                row.get(0)
-            })
-            .unwrap_or(0);
-
-        if row_count == 0 {
-            continue;
-        }
-
-        // Ensure schema exists in target database
-        ensure_table_schema(&tx, table_name).map_err(|e| {
-            anyhow::anyhow!("Failed to ensure schema for table '{}': {}", table_name, e)
-        })?;
-
-        // Get column names
-        let mut columns: Vec<String> = Vec::new();
-        {
-            let mut stmt = source_conn.prepare(&format!("PRAGMA table_info({})", table_name))?;
-            let rows = stmt.query_map([], |row| {
-                let name: String = row.get(1)?;
-                Ok(name)
-            })?;
-            for row in rows {
-                columns.push(row?);
-            }
-        }
-
-        if columns.is_empty() {
-            continue;
-        }
-
-        let column_list = columns.join(", ");
-
-        // Read rows from source and insert into target
-        let mut select_stmt = source_conn.prepare(&format!("SELECT * FROM {}", table_name))?;
-
-        let rows = select_stmt.query_map([], |row| {
-            let mut values = Vec::new();
-            for i in 0..columns.len() {
-                let value = row.get::<_, rusqlite::types::Value>(i).ok();
-                values.push(value);
-            }
-            Ok(values)
+    create_checkpoint_table(&target_conn)?;
+    let any_migrated =
+        copy_side_tables(&source_conn, &target_conn, DEFAULT_BATCH_SIZE, &mut |_| {
+            Ok(())
         })?;
-
-        let mut copied = 0;
-
-        for row_result in rows {
-            let row_values = row_result.map_err(|e| {
-                anyhow::anyhow!("Failed to read row from table '{}': {}", table_name, e)
-            })?;
-
-            let placeholders = (0..row_values.len())
-                .map(|_| "?")
-                .collect::<Vec<_>>()
-                .join(", ");
-
-            let insert_sql = format!(
-                "INSERT OR REPLACE INTO {} ({}) VALUES ({})",
-                table_name, column_list, placeholders
-            );
-
-            let tosql_refs: Vec<&dyn rusqlite::ToSql> = row_values
-                .iter()
-                .map(|v| match v {
-                    Some(val) => val as &dyn rusqlite::ToSql,
-                    None => &rusqlite::types::Value::Null as &dyn rusqlite::ToSql,
-                })
-                .collect();
-
-            tx.execute(&insert_sql, tosql_refs.as_slice())
-                .map_err(|e| {
-                    anyhow::anyhow!("Failed to insert row into table '{}': {}", table_name, e)
-                })?;
-            copied += 1;
-        }
-
-        if copied > 0 {
-            any_migrated = true;
-        }
-    }
-
-    // Commit transaction
-    tx.commit()
-        .map_err(|e| anyhow::anyhow!("Failed to commit side table migration transaction: {}", e))?;
+    target_conn.execute(&format!("DROP TABLE {}", CHECKPOINT_TABLE), [])?;
 
     Ok(any_migrated)
 }
@@ -618,6 +824,17 @@ fn ensure_table_schema(conn: &rusqlite::Connection, table_name: &str) -> Result<
             )?;
             Ok(())
         }
+        "chunk_content" => {
+            conn.execute(
+                "CREATE TABLE IF NOT EXISTS chunk_content (
+                    hash TEXT PRIMARY KEY,
+                    content TEXT NOT NULL
+                )",
+                [],
+            )
+            .map_err(|e| anyhow::anyhow!("Failed to create chunk_content table: {}", e))?;
+            Ok(())
+        }
         "file_metrics" => {
             conn.execute(
                 "CREATE TABLE IF NOT EXISTS file_metrics (
@@ -29,7 +29,8 @@ use std::path::{Path, PathBuf};
 /// v17: telemetry_events table for performance telemetry
 /// v18: repository snapshot tables for temporal tracking
 /// v19: symbol scoring tables for candidate ranking
-pub const MAGELLAN_SCHEMA_VERSION: i64 = 19;
+/// v20: file_timings, chunk_content and trait_impls side tables
+pub const MAGELLAN_SCHEMA_VERSION: i64 = 20;
 
 /// Migration result summary
 #[derive(Debug, Clone)]
@@ -557,5 +558,32 @@ fn migrate_from_version(tx: &Transaction, old_version: i64) -> Result<()> {
         )?;
     }
 
+    if old_version < 20 {
+        // v19 -> v20: file_timings and trait_impls side tables; chunk_content
+        // is created alongside code_chunks by ChunkStore on the next open
+        tx.execute(
+            "CREATE TABLE IF NOT EXISTS file_timings (
+                file_path TEXT PRIMARY KEY,
+                index_ms INTEGER NOT NULL,
+                last_updated INTEGER NOT NULL
+            )",
+            [],
+        )?;
+
+        tx.execute_batch(
+            "CREATE TABLE IF NOT EXISTS trait_impls (
+                file_path TEXT NOT NULL,
+                type_name TEXT NOT NULL,
+                trait_name TEXT NOT NULL,
+                trait_simple_name TEXT NOT NULL,
+                start_line INTEGER NOT NULL,
+                end_line INTEGER NOT NULL,
+                type_simple_name TEXT NOT NULL DEFAULT ''
+            );
+            CREATE INDEX IF NOT EXISTS idx_trait_impls_trait ON trait_impls(trait_simple_name);
+            CREATE INDEX IF NOT EXISTS idx_trait_impls_file ON trait_impls(file_path);",
+        )?;
+    }
+
     Ok(())
 }
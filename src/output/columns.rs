@@ -0,0 +1,212 @@
+//! Column units for query output (`--column-unit`)
+//!
+//! Columns are stored as UTF-8 byte offsets within a line. Editors and LSP
+//! clients count characters or UTF-16 code units instead, so `query`, `find`,
+//! and `refs` can convert columns on output. Conversion reads the line from
+//! the source file, resolving stored paths the way the graph does (relative
+//! paths against the recorded project root), and counts the units in the
+//! prefix before the byte column.
+//!
+//! Conversion is lossless or skipped: when the file cannot be read, the line
+//! is shorter than the byte column, or the prefix is not valid UTF-8 ending on
+//! a character boundary (the file changed since indexing, or is not UTF-8),
+//! the byte column is kept unchanged.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use crate::references::CallFact;
+use crate::{CodeGraph, SymbolFact};
+
+/// Unit that output columns are counted in
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ColumnUnit {
+    /// UTF-8 bytes (the stored unit)
+    #[default]
+    Byte,
+    /// Unicode scalar values
+    Char,
+    /// UTF-16 code units (the LSP default)
+    Utf16,
+}
+
+impl ColumnUnit {
+    /// Parse a `--column-unit` value
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.to_ascii_lowercase().as_str() {
+            "byte" | "bytes" => Some(Self::Byte),
+            "char" | "chars" => Some(Self::Char),
+            "utf16" | "utf-16" => Some(Self::Utf16),
+            _ => None,
+        }
+    }
+
+    /// Name accepted by `--column-unit`
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Byte => "byte",
+            Self::Char => "char",
+            Self::Utf16 => "utf16",
+        }
+    }
+}
+
+/// Convert a byte column within `line` to `unit`
+///
+/// Returns `None` when `byte_col` is past the end of the line or the prefix
+/// before it is not valid UTF-8.
+pub fn convert_column(line: &[u8], byte_col: usize, unit: ColumnUnit) -> Option<usize> {
+    let prefix = std::str::from_utf8(line.get(..byte_col)?).ok()?;
+    Some(match unit {
+        ColumnUnit::Byte => byte_col,
+        ColumnUnit::Char => prefix.chars().count(),
+        ColumnUnit::Utf16 => prefix.encode_utf16().count(),
+    })
+}
+
+/// A source file split into lines
+#[derive(Debug)]
+struct SourceLines {
+    content: Vec<u8>,
+    /// Byte offset where each line starts
+    line_starts: Vec<usize>,
+}
+
+impl SourceLines {
+    fn read(file_path: &Path) -> Option<Self> {
+        let content = std::fs::read(file_path).ok()?;
+        let line_starts = std::iter::once(0)
+            .chain(
+                content
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, byte)| **byte == b'\n')
+                    .map(|(i, _)| i + 1),
+            )
+            .collect();
+        Some(Self {
+            content,
+            line_starts,
+        })
+    }
+
+    /// Bytes of 1-indexed `line`, without its newline
+    fn line(&self, line: usize) -> Option<&[u8]> {
+        let start = *self.line_starts.get(line.checked_sub(1)?)?;
+        let end = self
+            .line_starts
+            .get(line)
+            .map_or(self.content.len(), |next| next - 1);
+        self.content.get(start..end)
+    }
+}
+
+/// Converts columns using source files read from disk, one read per file
+#[derive(Debug, Default)]
+pub struct ColumnConverter {
+    unit: ColumnUnit,
+    /// Root that relative stored paths are resolved against
+    root: Option<PathBuf>,
+    /// Source of each file seen so far; `None` when unreadable
+    files: HashMap<String, Option<SourceLines>>,
+}
+
+impl ColumnConverter {
+    /// Create a converter to `unit` that reads paths as given
+    pub fn new(unit: ColumnUnit) -> Self {
+        Self {
+            unit,
+            root: None,
+            files: HashMap::new(),
+        }
+    }
+
+    /// Create a converter to `unit` for paths stored in `graph`
+    ///
+    /// Relative paths are read from the graph's project root rather than
+    /// the current directory.
+    pub fn for_graph(unit: ColumnUnit, graph: &CodeGraph) -> Self {
+        Self {
+            root: graph.path_settings().map(|settings| settings.root.clone()),
+            ..Self::new(unit)
+        }
+    }
+
+    /// Convert the byte column `byte_col` on 1-indexed `line` of `file_path`
+    ///
+    /// Falls back to `byte_col` when the column cannot be verified.
+    pub fn convert(&mut self, file_path: &str, line: usize, byte_col: usize) -> usize {
+        if self.unit == ColumnUnit::Byte {
+            return byte_col;
+        }
+        let unit = self.unit;
+        let root = self.root.as_deref();
+        self.files
+            .entry(file_path.to_string())
+            .or_insert_with(|| match root {
+                Some(root) => SourceLines::read(&root.join(file_path)),
+                None => SourceLines::read(Path::new(file_path)),
+            })
+            .as_ref()
+            .and_then(|source| source.line(line))
+            .and_then(|text| convert_column(text, byte_col, unit))
+            .unwrap_or(byte_col)
+    }
+
+    /// Convert a symbol's start and end columns in place
+    pub fn convert_symbol(&mut self, fact: &mut SymbolFact) {
+        let file_path = fact.file_path.to_string_lossy().to_string();
+        fact.start_col = self.convert(&file_path, fact.start_line, fact.start_col);
+        fact.end_col = self.convert(&file_path, fact.end_line, fact.end_col);
+    }
+
+    /// Convert a call's start and end columns in place
+    pub fn convert_call(&mut self, call: &mut CallFact) {
+        let file_path = call.file_path.to_string_lossy().to_string();
+        call.start_col = self.convert(&file_path, call.start_line, call.start_col);
+        call.end_col = self.convert(&file_path, call.end_line, call.end_col);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_convert_column_counts_units_before_the_column() {
+        // "🦀" is 4 UTF-8 bytes, 1 char, and 2 UTF-16 code units
+        let line = "let s = \"🦀\"; call();".as_bytes();
+        let byte_col = line.windows(4).position(|w| w == b"call").unwrap();
+        assert_eq!(byte_col, 16);
+        assert_eq!(convert_column(line, byte_col, ColumnUnit::Byte), Some(16));
+        assert_eq!(convert_column(line, byte_col, ColumnUnit::Char), Some(13));
+        assert_eq!(convert_column(line, byte_col, ColumnUnit::Utf16), Some(14));
+    }
+
+    #[test]
+    fn test_convert_column_rejects_invalid_positions() {
+        let line = "🦀x".as_bytes();
+        // Inside the crab's encoding, and past the end of the line
+        assert_eq!(convert_column(line, 2, ColumnUnit::Char), None);
+        assert_eq!(convert_column(line, 9, ColumnUnit::Char), None);
+        // Invalid UTF-8 before the column
+        assert_eq!(convert_column(b"\xff\xfex", 2, ColumnUnit::Utf16), None);
+    }
+
+    #[test]
+    fn test_converter_falls_back_to_bytes() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("lib.rs");
+        std::fs::write(&path, "fn a() {}\nfn 🦀() { a(); }\n").unwrap();
+        let path = path.to_string_lossy().to_string();
+
+        let mut converter = ColumnConverter::new(ColumnUnit::Utf16);
+        // "a()" on line 2 starts at byte 12: 10 UTF-16 units
+        assert_eq!(converter.convert(&path, 2, 12), 10);
+        // Line 1 has no multibyte text
+        assert_eq!(converter.convert(&path, 1, 3), 3);
+        // Unknown line or file: the byte column is kept
+        assert_eq!(converter.convert(&path, 9, 12), 12);
+        assert_eq!(converter.convert("/no/such/file.rs", 2, 12), 12);
+    }
+}
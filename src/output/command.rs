@@ -134,6 +134,7 @@
 
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
 
 use crate::output::rich::{SpanChecksums, SpanContext, SpanRelationships, SpanSemantics};
 
@@ -284,7 +285,8 @@ pub struct Span {
     /// Start column (0-indexed, byte-based)
     ///
     /// Byte offset within `start_line` where the span begins.
-    /// This is a byte offset, not a character offset.
+    /// This is a byte offset, not a character offset, unless `--column-unit`
+    /// converted it (see [`crate::output::ColumnUnit`]).
     pub start_col: usize,
     /// End line (1-indexed)
     ///
@@ -535,6 +537,47 @@ pub struct SymbolMatch {
     /// that this symbol calls, along with their file paths and locations.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub callees: Option<Vec<CalleeInfo>>,
+    /// Fuzzy match score
+    ///
+    /// Set by `find --fuzzy`; higher is a better match. See
+    /// [`crate::graph::fuzzy`] for how it is computed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub score: Option<i64>,
+    /// Lines between the symbol's start and the requested line
+    ///
+    /// Set by `find --near`; 0 means the symbol starts on that line.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub distance: Option<usize>,
+    /// Stored size and complexity metrics
+    ///
+    /// Set by `--with-metrics` when the symbol has a `symbol_metrics` row;
+    /// absent when metrics were never computed for it.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metrics: Option<SymbolMetricsInfo>,
+}
+
+/// Per-symbol metrics included by `--with-metrics`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SymbolMetricsInfo {
+    /// Lines of code
+    pub loc: i64,
+    /// Incoming reference/call edges
+    pub fan_in: i64,
+    /// Outgoing call edges
+    pub fan_out: i64,
+    /// Cyclomatic complexity
+    pub cyclomatic_complexity: i64,
+}
+
+impl From<crate::graph::metrics::SymbolMetrics> for SymbolMetricsInfo {
+    fn from(metrics: crate::graph::metrics::SymbolMetrics) -> Self {
+        Self {
+            loc: metrics.loc,
+            fan_in: metrics.fan_in,
+            fan_out: metrics.fan_out,
+            cyclomatic_complexity: metrics.cyclomatic_complexity,
+        }
+    }
 }
 
 /// Information about a function that calls a symbol
@@ -657,6 +700,9 @@ impl SymbolMatch {
             symbol_id,
             callers: None,
             callees: None,
+            score: None,
+            distance: None,
+            metrics: None,
         }
     }
 
@@ -729,6 +775,11 @@ pub struct ReferenceMatch {
     /// Symbols indexed before this feature was added will have `None` here.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub target_symbol_id: Option<String>,
+    /// Function or method the reference occurs in
+    ///
+    /// Set for incoming references (`refs --direction in`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub in_symbol: Option<crate::references::EnclosingSymbol>,
 }
 
 impl ReferenceMatch {
@@ -817,8 +868,15 @@ impl ReferenceMatch {
             referenced_symbol,
             reference_kind,
             target_symbol_id,
+            in_symbol: None,
         }
     }
+
+    /// Attach the function or method the reference occurs in
+    pub fn with_in_symbol(mut self, in_symbol: crate::references::EnclosingSymbol) -> Self {
+        self.in_symbol = Some(in_symbol);
+        self
+    }
 }
 
 /// Response for query command
@@ -826,11 +884,35 @@ impl ReferenceMatch {
 pub struct QueryResponse {
     /// Symbols found in the queried file
     pub symbols: Vec<SymbolMatch>,
-    /// File path that was queried
+    /// File path that was queried (empty for a database-wide `--defined-in`)
     pub file_path: String,
     /// Kind filter that was applied (if any)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub kind_filter: Option<String>,
+    /// FQN prefix that was applied with `--defined-in` (if any)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub defined_in: Option<String>,
+    /// Unit of the span columns: `byte`, `char` or `utf16` (`--column-unit`)
+    pub column_unit: String,
+}
+
+/// Response for `query --baseline`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QueryBaselineResponse {
+    /// File path that was queried
+    pub file_path: String,
+    /// Baseline database the symbols were compared against
+    pub baseline_db: String,
+    /// Symbols with their change status (unchanged ones omitted with `--changed-only`)
+    pub symbols: Vec<crate::index_diff::SymbolContentChange>,
+    /// Symbols only in the current database
+    pub added: usize,
+    /// Symbols only in the baseline database
+    pub removed: usize,
+    /// Symbols whose content changed
+    pub modified: usize,
+    /// Symbols whose content is unchanged
+    pub unchanged: usize,
 }
 
 /// Response for find command
@@ -843,6 +925,17 @@ pub struct FindResponse {
     /// File filter that was applied (if any)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub file_filter: Option<String>,
+    /// Unit of the span columns: `byte`, `char` or `utf16` (`--column-unit`)
+    pub column_unit: String,
+}
+
+/// Response for `find --count-only` and `query --count-only`
+///
+/// Deliberately unwrapped: the output is exactly `{"count": N}`.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub struct CountResponse {
+    /// Number of matching symbols
+    pub count: usize,
 }
 
 /// Response for refs command
@@ -856,6 +949,59 @@ pub struct RefsResponse {
     pub file_path: String,
     /// Direction ("in" for callers, "out" for callees)
     pub direction: String,
+    /// Unit of the reference columns: `byte`, `char` or `utf16` (`--column-unit`)
+    pub column_unit: String,
+}
+
+/// References from one file in a `refs --group-by-file` response
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RefsFileGroup {
+    /// Number of references in this file
+    pub count: usize,
+    /// References in this file, in source order
+    pub references: Vec<ReferenceMatch>,
+}
+
+/// Response for `refs --group-by-file`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RefsByFileResponse {
+    /// References keyed by the file they occur in, sorted by path
+    pub files: BTreeMap<String, RefsFileGroup>,
+    /// Total references across all files
+    pub total_count: usize,
+    /// Symbol name that was queried
+    pub symbol_name: String,
+    /// File path containing the symbol
+    pub file_path: String,
+    /// Direction ("in" for callers, "out" for callees)
+    pub direction: String,
+    /// Unit of the reference columns: `byte`, `char` or `utf16` (`--column-unit`)
+    pub column_unit: String,
+}
+
+impl From<RefsResponse> for RefsByFileResponse {
+    fn from(response: RefsResponse) -> Self {
+        let total_count = response.references.len();
+        let mut files: BTreeMap<String, RefsFileGroup> = BTreeMap::new();
+        for reference in response.references {
+            let group = files
+                .entry(reference.span.file_path.clone())
+                .or_insert_with(|| RefsFileGroup {
+                    count: 0,
+                    references: Vec::new(),
+                });
+            group.count += 1;
+            group.references.push(reference);
+        }
+        RefsByFileResponse {
+            files,
+            total_count,
+            symbol_name: response.symbol_name,
+            file_path: response.file_path,
+            direction: response.direction,
+            column_unit: response.column_unit,
+        }
+    }
 }
 
 /// Caller information with project attribution for cross-project queries
@@ -942,6 +1088,20 @@ pub struct CollisionCandidate {
     pub display_fqn: Option<String>,
     pub name: Option<String>,
     pub file_path: Option<String>,
+    /// Disambiguation proposed by `--resolve-suggestions`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub suggestion: Option<CollisionSuggestion>,
+}
+
+/// Disambiguating prefix proposed for a collision group member
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CollisionSuggestion {
+    /// Shortest trailing part of the file path not shared with members in other files
+    pub path_prefix: Option<String>,
+    /// Shortest trailing part of the FQN scope not shared with members in the same file
+    pub module_prefix: Option<String>,
+    /// Prefixes joined with the name; null when only the symbol ID tells the member apart
+    pub qualified_name: Option<String>,
 }
 
 /// Collision group response entry
@@ -970,6 +1130,195 @@ pub struct FilesResponse {
     pub symbol_counts: Option<std::collections::HashMap<String, usize>>,
 }
 
+/// Response for timings command
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TimingsResponse {
+    /// Requested number of files (`--top`)
+    pub top: usize,
+    /// Slowest files, sorted by descending index time
+    pub files: Vec<FileTimingEntry>,
+}
+
+/// Indexing wall time for a single file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileTimingEntry {
+    /// File path as stored in the index
+    pub file_path: String,
+    /// Parse + index wall time in milliseconds
+    pub index_ms: i64,
+    /// Unix timestamp (seconds) when the timing was recorded
+    pub last_updated: i64,
+}
+
+/// Response for metrics backfill command
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MetricsBackfillResponse {
+    /// File filter as given (`--only`), if any
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub only: Option<String>,
+    /// Number of indexed files selected for backfill
+    pub total: usize,
+    /// Number of files whose metrics were recomputed
+    pub processed: usize,
+    /// Number of symbols whose metrics were stored
+    pub symbols_updated: usize,
+    /// Files that could not be processed
+    pub errors: Vec<BackfillErrorEntry>,
+}
+
+/// Response for `watch --dry-run`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WatchDryRunResponse {
+    /// Canonical scan root
+    pub root: String,
+    /// Number of files the scan would index
+    pub count: usize,
+    /// Files the scan would index, relative to `root`, sorted
+    pub files: Vec<String>,
+}
+
+/// A file the metrics backfill failed on
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackfillErrorEntry {
+    /// File path as stored in the index
+    pub file_path: String,
+    /// Error message
+    pub message: String,
+}
+
+/// Response for implementors command
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImplementorsResponse {
+    /// Trait or interface name as requested (`--name`)
+    pub trait_name: String,
+    /// Implementing types, sorted by file and line
+    pub implementors: Vec<ImplementorEntry>,
+}
+
+/// A type implementing (or extending) the requested trait
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImplementorEntry {
+    /// Implementing type as written at the impl site
+    pub type_name: String,
+    /// Trait as written at the impl site (e.g. `fmt::Display`)
+    pub trait_name: String,
+    /// File containing the impl or declaration
+    pub file_path: String,
+    /// First line of the impl or declaration (1-indexed)
+    pub start_line: usize,
+    /// Last line of the impl or declaration (1-indexed)
+    pub end_line: usize,
+    /// True when the trait resolves to an indexed symbol
+    pub trait_resolved: bool,
+}
+
+/// Response for prune command
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PruneResponse {
+    /// True when rows were only counted (`--dry-run`)
+    pub dry_run: bool,
+    /// Orphaned `code_chunks` rows
+    pub code_chunks: usize,
+    /// Orphaned `file_metrics` rows
+    pub file_metrics: usize,
+    /// Orphaned `symbol_metrics` rows
+    pub symbol_metrics: usize,
+    /// Orphaned `file_timings` rows
+    pub file_timings: usize,
+    /// Sum of all categories
+    pub total: usize,
+}
+
+/// One timed run of the bench command
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BenchRun {
+    /// Files indexed
+    pub files: usize,
+    /// Symbols in the database after the run
+    pub symbols: usize,
+    /// Wall time of the scan, in milliseconds
+    pub wall_ms: f64,
+    /// Files indexed per second
+    pub files_per_sec: f64,
+    /// Symbols indexed per second
+    pub symbols_per_sec: f64,
+}
+
+/// Response for bench command
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BenchResponse {
+    /// Directory that was indexed
+    pub root: String,
+    /// Each run, in the order it ran
+    pub runs: Vec<BenchRun>,
+    /// Fastest run's wall time, in milliseconds
+    pub min_ms: f64,
+    /// Median wall time, in milliseconds
+    pub median_ms: f64,
+    /// Slowest run's wall time, in milliseconds
+    pub max_ms: f64,
+}
+
+/// Response for checkpoint command
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CheckpointResponse {
+    /// True when active readers or a writer kept the WAL from being fully checkpointed
+    pub busy: bool,
+    /// Frames in the WAL when the checkpoint ran
+    pub log_frames: i64,
+    /// Frames copied back into the database file
+    pub checkpointed_frames: i64,
+    /// Size of the `-wal` file before the checkpoint, in bytes
+    pub wal_bytes_before: u64,
+    /// Size of the `-wal` file after the checkpoint, in bytes
+    pub wal_bytes_after: u64,
+}
+
+/// Response for `chunks --verify`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChunksVerifyResponse {
+    /// Root that relative stored paths were resolved against
+    pub root: String,
+    /// Chunks compared with their file on disk
+    pub chunks_checked: usize,
+    /// Files read from disk
+    pub files_checked: usize,
+    /// Stored files that no longer exist
+    pub files_missing: Vec<String>,
+    /// Chunks whose stored content drifted from the file
+    pub drifted: Vec<crate::verify::ChunkDrift>,
+}
+
+/// Response for `check`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CheckResponse {
+    /// Database that was checked
+    pub db_path: String,
+    /// Root the `stale` category compared against, when given
+    pub root_path: Option<String>,
+    /// Entries found per `--fail-on` category
+    pub counts: std::collections::BTreeMap<String, usize>,
+    /// Categories with at least one entry
+    pub failed: Vec<String>,
+    /// Whether every selected category came back empty
+    pub passed: bool,
+}
+
+/// Response for rebase-paths command
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RebasePathsResponse {
+    /// Root the paths were rebased from (`.` for root-relative paths)
+    pub from: String,
+    /// Root the paths were rebased to (`.` for root-relative paths)
+    pub to: String,
+    /// Graph entities whose paths were rewritten
+    pub entities: usize,
+    /// Side-table rows whose `file_path` was rewritten
+    pub side_rows: usize,
+    /// Sum of all categories
+    pub total: usize,
+}
+
 /// Response for status command
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StatusResponse {
@@ -1052,7 +1401,7 @@ pub struct ValidationError {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub entity_id: Option<String>,
     /// Additional structured data
-    #[serde(skip_serializing_if = "serde_json::Value::is_null")]
+    #[serde(default, skip_serializing_if = "serde_json::Value::is_null")]
     pub details: serde_json::Value,
 }
 
@@ -1067,7 +1416,7 @@ pub struct ValidationWarning {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub entity_id: Option<String>,
     /// Additional structured data
-    #[serde(skip_serializing_if = "serde_json::Value::is_null")]
+    #[serde(default, skip_serializing_if = "serde_json::Value::is_null")]
     pub details: serde_json::Value,
 }
 
@@ -1142,6 +1491,9 @@ pub struct SliceResponse {
     pub included_symbols: Vec<SymbolMatch>,
     /// Statistics about the slice
     pub statistics: SliceStats,
+    /// Source of the target and sliced symbols (`--with-code`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub code: Option<SliceCode>,
 }
 
 /// Statistics for program slice
@@ -1155,6 +1507,35 @@ pub struct SliceStats {
     pub control_dependencies: usize,
 }
 
+/// Stored code attached to a program slice by `slice --with-code`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SliceCode {
+    /// Byte budget from `--max-bytes`; `None` means unbounded
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_bytes: Option<usize>,
+    /// Bytes of code included
+    pub total_bytes: usize,
+    /// The target, then the sliced symbols in slice order
+    pub symbols: Vec<SliceSymbolCode>,
+}
+
+/// Code for one symbol of a slice
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SliceSymbolCode {
+    /// Symbol FQN
+    pub fqn: String,
+    /// File containing the symbol
+    pub file_path: String,
+    /// Stable symbol ID, if known
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub symbol_id: Option<String>,
+    /// `included`, `missing` (no stored chunk), or `over_budget`
+    pub status: String,
+    /// Stored chunk content when `status` is `included`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content: Option<String>,
+}
+
 impl OutputFormat {
     /// Parse from string
     pub fn parse(s: &str) -> Option<Self> {
@@ -1195,6 +1576,19 @@ pub fn output_json<T: Serialize>(data: &T, format: OutputFormat) -> anyhow::Resu
     Ok(())
 }
 
+/// Output a `--count-only` result
+///
+/// Human format prints the bare number; JSON formats print `{"count": N}`.
+pub fn output_count(count: usize, format: OutputFormat) -> anyhow::Result<()> {
+    match format {
+        OutputFormat::Human => {
+            println!("{}", count);
+            Ok(())
+        }
+        OutputFormat::Json | OutputFormat::Pretty => output_json(&CountResponse { count }, format),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1907,6 +2301,7 @@ mod tests {
     fn test_span_builder_with_checksums() {
         use crate::output::rich::SpanChecksums;
         let checksums = SpanChecksums {
+            content_sha256: Some("abc123".to_string()),
             checksum_before: Some("sha256:abc123".to_string()),
             file_checksum_before: Some("sha256:def456".to_string()),
         };
@@ -2058,4 +2453,10 @@ mod tests {
         assert!(value.get("tool").is_none() || value["tool"].is_null());
         assert!(value.get("timestamp").is_none() || value["timestamp"].is_null());
     }
+
+    #[test]
+    fn test_count_response_is_minimal() {
+        let json_str = serde_json::to_string(&CountResponse { count: 42 }).unwrap();
+        assert_eq!(json_str, r#"{"count":42}"#);
+    }
 }
@@ -2,13 +2,20 @@
 //!
 //! Provides schema-versioned, span-aware response types for all query commands.
 
+pub mod columns;
 pub mod command;
 pub mod rich;
+pub mod schema;
 
 pub use command::{
-    generate_execution_id, output_json, CalleeInfo, CallerInfo, CollisionCandidate, CollisionGroup,
-    CollisionsResponse, ContextResponse, CoverageInfo, ErrorResponse, FilesResponse, FindResponse,
-    JsonResponse, MigrateResponse, OutputFormat, ProjectCalleeInfo, ProjectCallerInfo,
-    ProjectSymbolMatch, QueryResponse, ReferenceMatch, RefsResponse, Span, StatusResponse,
-    SymbolMatch, ValidationError, ValidationResponse, ValidationWarning,
+    generate_execution_id, output_count, output_json, BackfillErrorEntry, CalleeInfo, CallerInfo, BenchResponse, BenchRun, CheckResponse, CheckpointResponse,
+    ChunksVerifyResponse, CollisionCandidate,
+    CollisionGroup, CollisionSuggestion, CollisionsResponse, ContextResponse, CountResponse, CoverageInfo,
+    ErrorResponse, FileTimingEntry, FilesResponse, FindResponse, ImplementorEntry,
+    ImplementorsResponse, JsonResponse, MetricsBackfillResponse, MigrateResponse,
+    OutputFormat, ProjectCalleeInfo, ProjectCallerInfo, ProjectSymbolMatch, PruneResponse,
+    QueryBaselineResponse, QueryResponse, RebasePathsResponse, ReferenceMatch, RefsByFileResponse, RefsFileGroup,
+    RefsResponse, Span, StatusResponse, SymbolMatch, SymbolMetricsInfo, TimingsResponse, ValidationError,
+    ValidationResponse, ValidationWarning, WatchDryRunResponse,
 };
+pub use columns::{ColumnConverter, ColumnUnit};
@@ -15,15 +15,15 @@ use sha2::{Digest, Sha256};
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct SpanContext {
     /// Lines before the span
-    #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub before: Vec<String>,
 
     /// Lines within the span
-    #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub selected: Vec<String>,
 
     /// Lines after the span
-    #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub after: Vec<String>,
 }
 
@@ -52,19 +52,19 @@ pub struct SymbolReference {
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Default)]
 pub struct SpanRelationships {
     /// Callers (functions that call this symbol)
-    #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub callers: Vec<SymbolReference>,
 
     /// Callees (functions called by this symbol)
-    #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub callees: Vec<SymbolReference>,
 
     /// Imports brought in by this span
-    #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub imports: Vec<SymbolReference>,
 
     /// Exports provided by this span
-    #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub exports: Vec<SymbolReference>,
 }
 
@@ -113,8 +113,17 @@ impl SpanSemantics {
 /// Checksum information for content verification
 ///
 /// Provides SHA-256 checksums for span content and entire file.
+///
+/// `content_sha256` is the field to cache on: the lowercase hex SHA-256 of
+/// the span's exact source text, taken from the stored code chunk when the
+/// index has one (see `CodeGraph::span_checksums`). Identical content always
+/// yields the same value, across reindexes and across commands.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct SpanChecksums {
+    /// SHA-256 of the span's source text (lowercase hex, no prefix)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content_sha256: Option<String>,
+
     /// SHA-256 hash of span content (with "sha256:" prefix)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub checksum_before: Option<String>,
@@ -210,6 +219,11 @@ impl SpanChecksums {
         content.get(byte_start..byte_end).map(|s| s.to_vec())
     }
 
+    /// Lowercase hex SHA-256 of `bytes`, as used for `content_sha256`
+    pub fn sha256_hex(bytes: &[u8]) -> String {
+        hex::encode(Sha256::digest(bytes))
+    }
+
     /// Compute SHA-256 checksum of span content
     ///
     /// This function computes a checksum of raw bytes, not UTF-8 strings.
@@ -226,12 +240,7 @@ impl SpanChecksums {
         byte_end: usize,
     ) -> Option<String> {
         let span_bytes = Self::extract_from_bytes(file_path, byte_start, byte_end)?;
-
-        let mut hasher = Sha256::new();
-        hasher.update(&span_bytes);
-        let result = hasher.finalize();
-
-        Some(format!("sha256:{}", hex::encode(result)))
+        Some(format!("sha256:{}", Self::sha256_hex(&span_bytes)))
     }
 
     /// Compute SHA-256 checksum of entire file
@@ -249,10 +258,18 @@ impl SpanChecksums {
         Some(format!("sha256:{}", hex::encode(result)))
     }
 
-    /// Compute both span and file checksums
+    /// Compute span and file checksums from the file on disk
+    ///
+    /// Prefer `CodeGraph::span_checksums`, which takes `content_sha256` from
+    /// the stored chunk so it matches what was indexed.
     pub fn compute(file_path: &str, byte_start: usize, byte_end: usize) -> Self {
+        let checksum_before = Self::compute_span_checksum(file_path, byte_start, byte_end);
         SpanChecksums {
-            checksum_before: Self::compute_span_checksum(file_path, byte_start, byte_end),
+            content_sha256: checksum_before
+                .as_deref()
+                .and_then(|c| c.strip_prefix("sha256:"))
+                .map(str::to_string),
+            checksum_before,
             file_checksum_before: Self::compute_file_checksum(file_path),
         }
     }
@@ -360,4 +377,19 @@ mod tests {
         assert!(value.get("kind").is_none() || value["kind"].is_null());
         assert!(value.get("language").is_none() || value["language"].is_null());
     }
+
+    #[test]
+    fn test_compute_sets_content_sha256_from_span_bytes() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let file = temp_dir.path().join("lib.rs");
+        std::fs::write(&file, "fn a() {}\nfn b() {}\n").unwrap();
+
+        let checksums = SpanChecksums::compute(file.to_str().unwrap(), 10, 19);
+        let expected = SpanChecksums::sha256_hex(b"fn b() {}");
+        assert_eq!(checksums.content_sha256.as_deref(), Some(expected.as_str()));
+        assert_eq!(
+            checksums.checksum_before,
+            Some(format!("sha256:{}", expected))
+        );
+    }
 }
@@ -0,0 +1,819 @@
+//! JSON Schema export for `--output json` response types
+//!
+//! Schemas are derived from each type's Serde structure rather than written
+//! by hand: a tracing `Deserializer` walks the `Deserialize` impl, recording
+//! the struct names, field names, and value types it is asked for. A field is
+//! required when deserialization fails without it, so `Option` fields and
+//! `#[serde(default)]` fields are optional.
+//!
+//! Output is deterministic: objects are key-sorted and every run traces the
+//! same impls in the same order.
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use serde::de::{
+    self, DeserializeOwned, DeserializeSeed, EnumAccess, IntoDeserializer, MapAccess, SeqAccess,
+    VariantAccess, Visitor,
+};
+use serde_json::{json, Map, Value};
+
+use super::command::{
+    BenchResponse, CheckResponse, CheckpointResponse, ChunksVerifyResponse, CollisionsResponse,
+    ContextResponse, CountResponse, ErrorResponse, FilesResponse, FindResponse,
+    ImplementorsResponse, JsonResponse, MetricsBackfillResponse, MigrateResponse, PruneResponse,
+    QueryBaselineResponse, QueryResponse, RebasePathsResponse, RefsByFileResponse, RefsResponse,
+    SliceResponse, StatusResponse, TimingsResponse, ValidationResponse, WatchDryRunResponse,
+    MAGELLAN_JSON_SCHEMA_VERSION,
+};
+
+/// JSON Schema dialect of the emitted documents
+pub const JSON_SCHEMA_DIALECT: &str = "https://json-schema.org/draft/2020-12/schema";
+
+/// Nesting depth after which sequences, maps, and options are traced empty
+///
+/// Only reached by recursive types; the outermost definition wins.
+const MAX_TRACE_DEPTH: usize = 32;
+
+/// A response type with an exported schema
+struct SchemaType {
+    /// Name accepted by `magellan schema --type`
+    name: &'static str,
+    /// Commands whose `--output json` payload this describes
+    commands: &'static str,
+    generate: fn(&SchemaType) -> anyhow::Result<Value>,
+}
+
+/// Exported response types, sorted by name
+const SCHEMA_TYPES: &[SchemaType] = &[
+    SchemaType {
+        name: "bench",
+        commands: "bench",
+        generate: wrapped_schema::<BenchResponse>,
+    },
+    SchemaType {
+        name: "check",
+        commands: "check",
+        generate: wrapped_schema::<CheckResponse>,
+    },
+    SchemaType {
+        name: "checkpoint",
+        commands: "checkpoint",
+        generate: wrapped_schema::<CheckpointResponse>,
+    },
+    SchemaType {
+        name: "chunks-verify",
+        commands: "chunks --verify",
+        generate: wrapped_schema::<ChunksVerifyResponse>,
+    },
+    SchemaType {
+        name: "collisions",
+        commands: "collisions",
+        generate: wrapped_schema::<CollisionsResponse>,
+    },
+    SchemaType {
+        name: "context",
+        commands: "context",
+        generate: wrapped_schema::<ContextResponse>,
+    },
+    SchemaType {
+        name: "count",
+        commands: "find --count-only, query --count-only",
+        generate: bare_schema::<CountResponse>,
+    },
+    SchemaType {
+        name: "error",
+        commands: "error responses",
+        generate: wrapped_schema::<ErrorResponse>,
+    },
+    SchemaType {
+        name: "files",
+        commands: "files",
+        generate: wrapped_schema::<FilesResponse>,
+    },
+    SchemaType {
+        name: "find",
+        commands: "find",
+        generate: wrapped_schema::<FindResponse>,
+    },
+    SchemaType {
+        name: "implementors",
+        commands: "implementors",
+        generate: wrapped_schema::<ImplementorsResponse>,
+    },
+    SchemaType {
+        name: "metrics-backfill",
+        commands: "metrics backfill",
+        generate: wrapped_schema::<MetricsBackfillResponse>,
+    },
+    SchemaType {
+        name: "migrate",
+        commands: "migrate",
+        generate: wrapped_schema::<MigrateResponse>,
+    },
+    SchemaType {
+        name: "prune",
+        commands: "prune",
+        generate: wrapped_schema::<PruneResponse>,
+    },
+    SchemaType {
+        name: "query",
+        commands: "query",
+        generate: wrapped_schema::<QueryResponse>,
+    },
+    SchemaType {
+        name: "query-baseline",
+        commands: "query --baseline",
+        generate: wrapped_schema::<QueryBaselineResponse>,
+    },
+    SchemaType {
+        name: "rebase-paths",
+        commands: "rebase-paths",
+        generate: wrapped_schema::<RebasePathsResponse>,
+    },
+    SchemaType {
+        name: "refs",
+        commands: "refs",
+        generate: wrapped_schema::<RefsResponse>,
+    },
+    SchemaType {
+        name: "refs-by-file",
+        commands: "refs --group-by-file",
+        generate: wrapped_schema::<RefsByFileResponse>,
+    },
+    SchemaType {
+        name: "slice",
+        commands: "slice",
+        generate: wrapped_schema::<SliceResponse>,
+    },
+    SchemaType {
+        name: "status",
+        commands: "status",
+        generate: wrapped_schema::<StatusResponse>,
+    },
+    SchemaType {
+        name: "timings",
+        commands: "timings",
+        generate: wrapped_schema::<TimingsResponse>,
+    },
+    SchemaType {
+        name: "validate",
+        commands: "verify, watch --validate-only",
+        generate: wrapped_schema::<ValidationResponse>,
+    },
+    SchemaType {
+        name: "watch-dry-run",
+        commands: "watch --dry-run",
+        generate: wrapped_schema::<WatchDryRunResponse>,
+    },
+];
+
+/// Names accepted by `response_schema`, sorted
+pub fn schema_type_names() -> Vec<&'static str> {
+    SCHEMA_TYPES.iter().map(|t| t.name).collect()
+}
+
+/// JSON Schema document for one response type (e.g. `"status"`)
+///
+/// Returns `Ok(None)` for unknown type names.
+pub fn response_schema(name: &str) -> anyhow::Result<Option<Value>> {
+    SCHEMA_TYPES
+        .iter()
+        .find(|t| t.name == name)
+        .map(|t| (t.generate)(t))
+        .transpose()
+}
+
+/// Schema documents for every response type, keyed by type name
+pub fn all_response_schemas() -> anyhow::Result<Value> {
+    let mut schemas = Map::new();
+    for schema_type in SCHEMA_TYPES {
+        schemas.insert(
+            schema_type.name.to_string(),
+            (schema_type.generate)(schema_type)?,
+        );
+    }
+    Ok(Value::Object(schemas))
+}
+
+/// Schema for a payload emitted inside the `JsonResponse` envelope
+fn wrapped_schema<T: DeserializeOwned>(schema_type: &SchemaType) -> anyhow::Result<Value> {
+    let (root, mut defs) = trace_type::<JsonResponse<T>>()?;
+    let mut document = take_definition(&root, &mut defs)?;
+    if let Some(version) = document
+        .get_mut("properties")
+        .and_then(|properties| properties.get_mut("schema_version"))
+    {
+        version["const"] = json!(MAGELLAN_JSON_SCHEMA_VERSION);
+    }
+    Ok(finish_document(schema_type, document, defs))
+}
+
+/// Schema for a payload emitted without the envelope
+fn bare_schema<T: DeserializeOwned>(schema_type: &SchemaType) -> anyhow::Result<Value> {
+    let (root, mut defs) = trace_type::<T>()?;
+    let document = take_definition(&root, &mut defs)?;
+    Ok(finish_document(schema_type, document, defs))
+}
+
+fn finish_document(
+    schema_type: &SchemaType,
+    mut document: Value,
+    defs: BTreeMap<String, Value>,
+) -> Value {
+    document["$schema"] = json!(JSON_SCHEMA_DIALECT);
+    document["title"] = json!(format!("magellan {} response", schema_type.name));
+    document["description"] = json!(format!(
+        "`--output json` payload of: {} (schema_version {})",
+        schema_type.commands, MAGELLAN_JSON_SCHEMA_VERSION
+    ));
+    if !defs.is_empty() {
+        document["$defs"] = Value::Object(defs.into_iter().collect());
+    }
+    document
+}
+
+/// Inline the definition a root `$ref` points at
+fn take_definition(root: &Value, defs: &mut BTreeMap<String, Value>) -> anyhow::Result<Value> {
+    let name = root
+        .get("$ref")
+        .and_then(Value::as_str)
+        .and_then(|reference| reference.strip_prefix("#/$defs/"))
+        .ok_or_else(|| anyhow::anyhow!("response type is not a struct"))?;
+    defs.remove(name)
+        .ok_or_else(|| anyhow::anyhow!("missing schema definition for {}", name))
+}
+
+/// Trace `T` into a root schema plus named struct definitions
+fn trace_type<T: DeserializeOwned>() -> anyhow::Result<(Value, BTreeMap<String, Value>)> {
+    let mut state = TraceState::default();
+    let mut root = Value::Null;
+    T::deserialize(Tracer {
+        state: &mut state,
+        out: &mut root,
+    })
+    .map_err(|e| anyhow::anyhow!("Failed to trace response type: {}", e))?;
+
+    // A field is optional when the type still deserializes without it
+    let fields = std::mem::take(&mut state.fields);
+    let mut required: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    for (struct_name, field) in fields {
+        let mut probe = TraceState {
+            omit: Some((struct_name.clone(), field.clone())),
+            recording: false,
+            ..TraceState::default()
+        };
+        let mut ignored = Value::Null;
+        let omitted = T::deserialize(Tracer {
+            state: &mut probe,
+            out: &mut ignored,
+        });
+        if omitted.is_err() {
+            required.entry(struct_name).or_default().push(field);
+        }
+    }
+
+    let mut defs = state.defs;
+    for (name, definition) in defs.iter_mut() {
+        let names = required.remove(name).unwrap_or_default();
+        if !names.is_empty() {
+            definition["required"] = json!(names);
+        }
+    }
+    Ok((root, defs))
+}
+
+struct TraceState {
+    /// Struct definitions by name
+    defs: BTreeMap<String, Value>,
+    /// Every (struct, field) pair seen, in first-seen order
+    fields: Vec<(String, String)>,
+    /// Field left out of its struct on a required-ness probe
+    omit: Option<(String, String)>,
+    /// Whether definitions are recorded (false on probes)
+    recording: bool,
+    depth: usize,
+}
+
+impl Default for TraceState {
+    fn default() -> Self {
+        Self {
+            defs: BTreeMap::new(),
+            fields: Vec::new(),
+            omit: None,
+            recording: true,
+            depth: 0,
+        }
+    }
+}
+
+#[derive(Debug)]
+struct TraceError(String);
+
+impl fmt::Display for TraceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for TraceError {}
+
+impl de::Error for TraceError {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        TraceError(msg.to_string())
+    }
+}
+
+/// Deserializer that hands out placeholder values and records their schema
+struct Tracer<'a> {
+    state: &'a mut TraceState,
+    out: &'a mut Value,
+}
+
+impl Tracer<'_> {
+    fn too_deep(&self) -> bool {
+        self.state.depth >= MAX_TRACE_DEPTH
+    }
+}
+
+macro_rules! trace_scalar {
+    ($($method:ident => $visit:ident($value:expr), $schema:expr;)*) => {
+        $(
+            fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, TraceError> {
+                *self.out = $schema;
+                visitor.$visit($value)
+            }
+        )*
+    };
+}
+
+impl<'de> de::Deserializer<'de> for Tracer<'_> {
+    type Error = TraceError;
+
+    trace_scalar! {
+        deserialize_bool => visit_bool(false), json!({"type": "boolean"});
+        deserialize_i8 => visit_i8(0), json!({"type": "integer"});
+        deserialize_i16 => visit_i16(0), json!({"type": "integer"});
+        deserialize_i32 => visit_i32(0), json!({"type": "integer"});
+        deserialize_i64 => visit_i64(0), json!({"type": "integer"});
+        deserialize_u8 => visit_u8(0), json!({"type": "integer", "minimum": 0});
+        deserialize_u16 => visit_u16(0), json!({"type": "integer", "minimum": 0});
+        deserialize_u32 => visit_u32(0), json!({"type": "integer", "minimum": 0});
+        deserialize_u64 => visit_u64(0), json!({"type": "integer", "minimum": 0});
+        deserialize_f32 => visit_f32(0.0), json!({"type": "number"});
+        deserialize_f64 => visit_f64(0.0), json!({"type": "number"});
+        deserialize_char => visit_char('_'), json!({"type": "string", "minLength": 1, "maxLength": 1});
+        deserialize_str => visit_str(""), json!({"type": "string"});
+        deserialize_string => visit_string(String::new()), json!({"type": "string"});
+        deserialize_bytes => visit_bytes(&[]), json!({"type": "array", "items": {"type": "integer"}});
+        deserialize_byte_buf => visit_byte_buf(Vec::new()), json!({"type": "array", "items": {"type": "integer"}});
+        deserialize_identifier => visit_u64(0), json!({"type": "string"});
+    }
+
+    fn deserialize_unit<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, TraceError> {
+        *self.out = json!({"type": "null"});
+        visitor.visit_unit()
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, TraceError> {
+        *self.out = json!({});
+        visitor.visit_unit()
+    }
+
+    /// Self-describing values (e.g. `serde_json::Value`) accept anything
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, TraceError> {
+        *self.out = json!({});
+        visitor.visit_unit()
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, TraceError> {
+        if self.too_deep() {
+            *self.out = json!({"type": "null"});
+            return visitor.visit_none();
+        }
+        let mut inner = Value::Null;
+        let value = visitor.visit_some(Tracer {
+            state: self.state,
+            out: &mut inner,
+        })?;
+        *self.out = json!({"anyOf": [inner, {"type": "null"}]});
+        Ok(value)
+    }
+
+    fn deserialize_unit_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, TraceError> {
+        self.deserialize_unit(visitor)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, TraceError> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, TraceError> {
+        let len = usize::from(!self.too_deep());
+        let mut items = vec![json!({}); len];
+        self.state.depth += 1;
+        let value = visitor.visit_seq(TraceSeq {
+            state: &mut *self.state,
+            items: items.iter_mut(),
+        });
+        self.state.depth -= 1;
+        let items = items.pop().unwrap_or_else(|| json!({}));
+        *self.out = json!({"type": "array", "items": items});
+        value
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(
+        self,
+        len: usize,
+        visitor: V,
+    ) -> Result<V::Value, TraceError> {
+        let mut items = vec![json!({}); len];
+        self.state.depth += 1;
+        let value = visitor.visit_seq(TraceSeq {
+            state: &mut *self.state,
+            items: items.iter_mut(),
+        });
+        self.state.depth -= 1;
+        *self.out = json!({
+            "type": "array",
+            "prefixItems": items,
+            "minItems": len,
+            "maxItems": len,
+        });
+        value
+    }
+
+    fn deserialize_tuple_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        len: usize,
+        visitor: V,
+    ) -> Result<V::Value, TraceError> {
+        self.deserialize_tuple(len, visitor)
+    }
+
+    fn deserialize_map<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, TraceError> {
+        let mut entry = if self.too_deep() {
+            None
+        } else {
+            Some((Value::Null, Value::Null))
+        };
+        self.state.depth += 1;
+        let value = visitor.visit_map(TraceMap {
+            state: &mut *self.state,
+            entry: entry.as_mut(),
+        });
+        self.state.depth -= 1;
+        let values = entry.map(|(_, value)| value).unwrap_or_else(|| json!({}));
+        *self.out = json!({"type": "object", "additionalProperties": values});
+        value
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, TraceError> {
+        for field in fields {
+            let key = (name.to_string(), field.to_string());
+            if !self.state.fields.contains(&key) {
+                self.state.fields.push(key);
+            }
+        }
+
+        let included: Vec<&'static str> = fields
+            .iter()
+            .copied()
+            .filter(|field| {
+                self.state
+                    .omit
+                    .as_ref()
+                    .is_none_or(|(s, f)| s != name || f != field)
+            })
+            .collect();
+        let mut schemas = vec![Value::Null; included.len()];
+
+        self.state.depth += 1;
+        let value = visitor.visit_map(TraceStruct {
+            state: &mut *self.state,
+            fields: included.iter().copied().zip(schemas.iter_mut()),
+            pending: None,
+        });
+        self.state.depth -= 1;
+        let value = value?;
+
+        if self.state.recording {
+            let properties: Map<String, Value> = included
+                .iter()
+                .map(|field| field.to_string())
+                .zip(schemas)
+                .collect();
+            // Outermost trace completes last, so it wins for recursive types
+            self.state.defs.insert(
+                name.to_string(),
+                json!({"type": "object", "properties": properties}),
+            );
+        }
+        *self.out = json!({"$ref": format!("#/$defs/{}", name)});
+        Ok(value)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, TraceError> {
+        let mut unit = true;
+        let value = visitor.visit_enum(TraceEnum {
+            state: self.state,
+            unit: &mut unit,
+        })?;
+        *self.out = if unit {
+            json!({"type": "string", "enum": variants})
+        } else {
+            // Externally tagged: a bare variant name or a single-key object
+            json!({"oneOf": [
+                {"type": "string", "enum": variants},
+                {
+                    "type": "object",
+                    "minProperties": 1,
+                    "maxProperties": 1,
+                    "propertyNames": {"enum": variants},
+                },
+            ]})
+        };
+        Ok(value)
+    }
+}
+
+struct TraceSeq<'a, I> {
+    state: &'a mut TraceState,
+    items: I,
+}
+
+impl<'de, 'v, I: Iterator<Item = &'v mut Value>> SeqAccess<'de> for TraceSeq<'_, I> {
+    type Error = TraceError;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, TraceError> {
+        match self.items.next() {
+            Some(out) => seed
+                .deserialize(Tracer {
+                    state: &mut *self.state,
+                    out,
+                })
+                .map(Some),
+            None => Ok(None),
+        }
+    }
+}
+
+struct TraceMap<'a, 'e> {
+    state: &'a mut TraceState,
+    entry: Option<&'e mut (Value, Value)>,
+}
+
+impl<'de> MapAccess<'de> for TraceMap<'_, '_> {
+    type Error = TraceError;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, TraceError> {
+        match self.entry.as_mut() {
+            Some(entry) => seed
+                .deserialize(Tracer {
+                    state: &mut *self.state,
+                    out: &mut entry.0,
+                })
+                .map(Some),
+            None => Ok(None),
+        }
+    }
+
+    fn next_value_seed<S: DeserializeSeed<'de>>(
+        &mut self,
+        seed: S,
+    ) -> Result<S::Value, TraceError> {
+        let entry = self
+            .entry
+            .take()
+            .ok_or_else(|| TraceError("map value requested before key".to_string()))?;
+        seed.deserialize(Tracer {
+            state: &mut *self.state,
+            out: &mut entry.1,
+        })
+    }
+}
+
+/// Struct fields presented as a map so missing fields can be probed
+struct TraceStruct<'a, I> {
+    state: &'a mut TraceState,
+    fields: I,
+    pending: Option<&'a mut Value>,
+}
+
+impl<'de, 'a, I: Iterator<Item = (&'static str, &'a mut Value)>> MapAccess<'de>
+    for TraceStruct<'a, I>
+{
+    type Error = TraceError;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, TraceError> {
+        match self.fields.next() {
+            Some((field, out)) => {
+                self.pending = Some(out);
+                seed.deserialize(IntoDeserializer::<TraceError>::into_deserializer(field))
+                    .map(Some)
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn next_value_seed<S: DeserializeSeed<'de>>(
+        &mut self,
+        seed: S,
+    ) -> Result<S::Value, TraceError> {
+        let out = self
+            .pending
+            .take()
+            .ok_or_else(|| TraceError("struct value requested before key".to_string()))?;
+        seed.deserialize(Tracer {
+            state: &mut *self.state,
+            out,
+        })
+    }
+}
+
+/// Always selects the first variant
+struct TraceEnum<'a> {
+    state: &'a mut TraceState,
+    unit: &'a mut bool,
+}
+
+impl<'de, 'a> EnumAccess<'de> for TraceEnum<'a> {
+    type Error = TraceError;
+    type Variant = Self;
+
+    fn variant_seed<S: DeserializeSeed<'de>>(
+        self,
+        seed: S,
+    ) -> Result<(S::Value, Self), TraceError> {
+        let variant = seed.deserialize(IntoDeserializer::<TraceError>::into_deserializer(0u32))?;
+        Ok((variant, self))
+    }
+}
+
+impl<'de> VariantAccess<'de> for TraceEnum<'_> {
+    type Error = TraceError;
+
+    fn unit_variant(self) -> Result<(), TraceError> {
+        Ok(())
+    }
+
+    fn newtype_variant_seed<S: DeserializeSeed<'de>>(
+        self,
+        seed: S,
+    ) -> Result<S::Value, TraceError> {
+        *self.unit = false;
+        let mut ignored = Value::Null;
+        seed.deserialize(Tracer {
+            state: self.state,
+            out: &mut ignored,
+        })
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(
+        self,
+        len: usize,
+        visitor: V,
+    ) -> Result<V::Value, TraceError> {
+        *self.unit = false;
+        let mut ignored = Value::Null;
+        de::Deserializer::deserialize_tuple(
+            Tracer {
+                state: self.state,
+                out: &mut ignored,
+            },
+            len,
+            visitor,
+        )
+    }
+
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, TraceError> {
+        *self.unit = false;
+        let mut ignored = Value::Null;
+        de::Deserializer::deserialize_struct(
+            Tracer {
+                state: self.state,
+                out: &mut ignored,
+            },
+            "",
+            fields,
+            visitor,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_status_schema_lists_count_fields() {
+        let schema = response_schema("status").unwrap().unwrap();
+        let data_ref = schema["properties"]["data"]["$ref"].as_str().unwrap();
+        assert_eq!(data_ref, "#/$defs/StatusResponse");
+
+        let status = &schema["$defs"]["StatusResponse"];
+        for field in ["files", "symbols", "references"] {
+            assert_eq!(
+                status["properties"][field]["type"], "integer",
+                "StatusResponse.{} missing: {}",
+                field, status
+            );
+        }
+        let required: Vec<&str> = status["required"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|v| v.as_str().unwrap())
+            .collect();
+        assert!(required.contains(&"files"));
+        assert!(required.contains(&"coverage"));
+    }
+
+    #[test]
+    fn test_envelope_pins_schema_version() {
+        let schema = response_schema("find").unwrap().unwrap();
+        assert_eq!(schema["$schema"], JSON_SCHEMA_DIALECT);
+        assert_eq!(
+            schema["properties"]["schema_version"]["const"],
+            MAGELLAN_JSON_SCHEMA_VERSION
+        );
+        let required = schema["required"].as_array().unwrap();
+        assert!(required.contains(&json!("schema_version")));
+        assert!(required.contains(&json!("data")));
+        assert!(
+            !required.contains(&json!("tool")),
+            "Option fields are optional"
+        );
+    }
+
+    #[test]
+    fn test_option_and_nested_fields() {
+        let schema = response_schema("find").unwrap().unwrap();
+        let defs = &schema["$defs"];
+        assert_eq!(
+            defs["FindResponse"]["properties"]["matches"]["items"]["$ref"],
+            "#/$defs/SymbolMatch"
+        );
+        assert_eq!(
+            defs["SymbolMatch"]["properties"]["parent"]["anyOf"][1]["type"],
+            "null"
+        );
+        let span_required = defs["Span"]["required"].as_array().unwrap();
+        assert!(span_required.contains(&json!("byte_start")));
+        assert!(!span_required.contains(&json!("context")));
+        // Skipped-when-empty vectors must be optional to match real output
+        assert!(defs["SpanContext"].get("required").is_none());
+    }
+
+    #[test]
+    fn test_count_response_is_unwrapped() {
+        let schema = response_schema("count").unwrap().unwrap();
+        assert!(schema["properties"].get("schema_version").is_none());
+        assert_eq!(schema["required"], json!(["count"]));
+    }
+
+    #[test]
+    fn test_schemas_are_deterministic_and_complete() {
+        let first = serde_json::to_string(&all_response_schemas().unwrap()).unwrap();
+        let second = serde_json::to_string(&all_response_schemas().unwrap()).unwrap();
+        assert_eq!(first, second);
+
+        let names = schema_type_names();
+        let mut sorted = names.clone();
+        sorted.sort_unstable();
+        assert_eq!(names, sorted);
+        assert!(response_schema("nope").unwrap().is_none());
+    }
+}
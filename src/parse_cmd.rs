@@ -0,0 +1,83 @@
+//! Parse command implementation
+//!
+//! Runs a language's symbol extractor over a single buffer (stdin or a file)
+//! and prints the resulting `SymbolFact`s. Never opens a database, so it can
+//! be used on unsaved editor buffers and for checking grammar coverage.
+
+use anyhow::Result;
+use magellan::ingest::{detect_language, registry, Language};
+use magellan::output::command::OutputFormat;
+use std::io::Read;
+use std::path::PathBuf;
+
+/// Label used for facts when no `--file` hint is given
+const STDIN_PATH: &str = "<stdin>";
+
+/// Resolve the language from `--lang`, falling back to the `--file` extension
+fn resolve_language(lang: Option<&str>, file_path: Option<&PathBuf>) -> Result<Language> {
+    if let Some(name) = lang {
+        return Language::from_name(name).ok_or_else(|| {
+            anyhow::anyhow!(
+                "Unknown language: '{}'. Valid languages: rust, python, c, cpp, java, javascript, typescript, go, cuda, swift, csharp",
+                name
+            )
+        });
+    }
+    file_path
+        .and_then(|path| detect_language(path))
+        .ok_or_else(|| {
+            anyhow::anyhow!(
+                "Cannot determine the language: pass --lang <LANG> or a --file with a known extension"
+            )
+        })
+}
+
+pub fn run_parse(
+    lang: Option<String>,
+    file_path: Option<PathBuf>,
+    from_stdin: bool,
+    output_format: OutputFormat,
+) -> Result<()> {
+    let language = resolve_language(lang.as_deref(), file_path.as_ref())?;
+
+    let source = if from_stdin {
+        let mut buffer = Vec::new();
+        std::io::stdin().read_to_end(&mut buffer)?;
+        buffer
+    } else {
+        let path = file_path
+            .as_ref()
+            .ok_or_else(|| anyhow::anyhow!("--file is required unless --stdin is used"))?;
+        std::fs::read(path)
+            .map_err(|e| anyhow::anyhow!("Failed to read {}: {}", path.display(), e))?
+    };
+
+    let label = file_path.unwrap_or_else(|| PathBuf::from(STDIN_PATH));
+    let parser = registry::parser_for_language(language)
+        .ok_or_else(|| anyhow::anyhow!("No parser registered for {}", language.as_str()))?;
+    let facts = parser.extract(&label, &source);
+
+    match output_format {
+        OutputFormat::Json => println!("{}", serde_json::to_string(&facts)?),
+        OutputFormat::Pretty => println!("{}", serde_json::to_string_pretty(&facts)?),
+        OutputFormat::Human => {
+            for fact in &facts {
+                println!(
+                    "{:<10} {:<40} {}:{}-{}:{}",
+                    format!("{:?}", fact.kind),
+                    fact.fqn
+                        .as_deref()
+                        .or(fact.name.as_deref())
+                        .unwrap_or("<anonymous>"),
+                    fact.start_line,
+                    fact.start_col,
+                    fact.end_line,
+                    fact.end_col
+                );
+            }
+            println!("{} symbols ({})", facts.len(), language.as_str());
+        }
+    }
+
+    Ok(())
+}
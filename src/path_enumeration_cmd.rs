@@ -1,6 +1,7 @@
 //! Path enumeration command implementation
 //!
-//! Enumerates execution paths between symbols using bounded DFS.
+//! Enumerates execution paths between symbols using bounded DFS, or finds the
+//! single shortest path with BFS when `--shortest` is given.
 
 use anyhow::Result;
 use magellan::graph::{ExecutionPath, PathEnumerationResult, PathStatistics, SymbolInfo};
@@ -16,16 +17,24 @@ use std::path::PathBuf;
 /// * `end_symbol_id` - Optional ending symbol ID or FQN
 /// * `max_depth` - Maximum path depth
 /// * `max_paths` - Maximum number of paths to return
+/// * `shortest` - Return only the shortest path to `end_symbol_id` (BFS)
+/// * `avoid` - Symbol IDs or FQNs that returned paths must not pass through
 /// * `output_format` - Output format (Human or Json)
 ///
 /// # Displays
 /// Human-readable list of execution paths with statistics, or JSON output
+#[allow(
+    clippy::too_many_arguments,
+    reason = "CLI command surface: each arg maps to a flag"
+)]
 pub fn run_paths(
     db_path: PathBuf,
     start_symbol_id: String,
     end_symbol_id: Option<String>,
     max_depth: usize,
     max_paths: usize,
+    shortest: bool,
+    avoid: Vec<String>,
     output_format: OutputFormat,
 ) -> Result<()> {
     // Build args for execution tracking
@@ -42,6 +51,13 @@ pub fn run_paths(
         args.push("--end".to_string());
         args.push(end.clone());
     }
+    if shortest {
+        args.push("--shortest".to_string());
+    }
+    for symbol in &avoid {
+        args.push("--avoid".to_string());
+        args.push(symbol.clone());
+    }
 
     let graph = CodeGraph::open(&db_path)?;
     let exec_id = magellan::output::generate_execution_id();
@@ -55,13 +71,32 @@ pub fn run_paths(
         &db_path_str,
     )?;
 
+    // Ctrl-C stops the search and prints the paths found so far; a second
+    // Ctrl-C exits immediately
+    #[cfg(unix)]
+    {
+        use signal_hook::consts::signal;
+        use signal_hook::flag;
+        let token = graph.cancel_token();
+        let _ = flag::register_conditional_shutdown(signal::SIGINT, 130, token.clone())?;
+        let _ = flag::register(signal::SIGINT, token)?;
+    }
+
     // Query paths
-    let result = graph.enumerate_paths(
-        &start_symbol_id,
-        end_symbol_id.as_deref(),
-        max_depth,
-        max_paths,
-    )?;
+    let avoid_refs: Vec<&str> = avoid.iter().map(String::as_str).collect();
+    let result = match (shortest, end_symbol_id.as_deref()) {
+        (true, Some(end)) => {
+            graph.shortest_path_avoiding(&start_symbol_id, end, max_depth, &avoid_refs)?
+        }
+        (true, None) => anyhow::bail!("--shortest requires --end"),
+        (false, end) => graph.enumerate_paths_avoiding(
+            &start_symbol_id,
+            end,
+            max_depth,
+            max_paths,
+            &avoid_refs,
+        )?,
+    };
 
     // Handle JSON output mode
     if output_format == OutputFormat::Json || output_format == OutputFormat::Pretty {
@@ -71,6 +106,12 @@ pub fn run_paths(
         return output_json_mode(
             &start_symbol_id,
             end_symbol_id.as_deref(),
+            PathsConfig {
+                max_depth,
+                max_paths,
+                shortest,
+                avoid,
+            },
             result,
             &exec_id,
             output_format,
@@ -83,8 +124,24 @@ pub fn run_paths(
         .map(|s| format!(" to \"{}\"", s))
         .unwrap_or_default();
 
+    if result.cancelled {
+        println!("Cancelled: results are partial");
+    }
+    if !avoid.is_empty() {
+        println!("Avoiding: {}", avoid.join(", "));
+    }
     if result.paths.is_empty() {
         println!("No paths found from \"{}\"{}", start_symbol_id, end_label);
+    } else if shortest {
+        let path = &result.paths[0];
+        println!(
+            "Shortest path from \"{}\"{} (length {}):",
+            start_symbol_id, end_label, path.length
+        );
+        for (j, symbol) in path.symbols.iter().enumerate() {
+            let fqn_display = symbol.fqn.as_deref().unwrap_or("?");
+            println!("  {}. {} ({})", j + 1, fqn_display, symbol.kind);
+        }
     } else {
         println!("Execution paths from \"{}\"{}:", start_symbol_id, end_label);
         println!("  Total paths enumerated: {}", result.total_enumerated);
@@ -136,6 +193,8 @@ pub struct PathsResponse {
     pub total_enumerated: usize,
     /// Whether enumeration hit bounds
     pub bounded_hit: bool,
+    /// Whether Ctrl-C stopped the search, leaving `paths` partial
+    pub cancelled: bool,
     /// Statistics about the discovered paths
     pub statistics: PathStatisticsJson,
 }
@@ -147,6 +206,11 @@ pub struct PathsConfig {
     pub max_depth: usize,
     /// Maximum number of paths to return
     pub max_paths: usize,
+    /// Whether only the shortest path was requested
+    pub shortest: bool,
+    /// Symbols excluded from the traversal
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub avoid: Vec<String>,
 }
 
 /// Execution path for JSON output
@@ -219,6 +283,7 @@ impl From<PathStatistics> for PathStatisticsJson {
 fn output_json_mode(
     start_symbol_id: &str,
     end_symbol_id: Option<&str>,
+    config: PathsConfig,
     result: PathEnumerationResult,
     exec_id: &str,
     output_format: OutputFormat,
@@ -232,13 +297,11 @@ fn output_json_mode(
     let response = PathsResponse {
         start_symbol_id: start_symbol_id.to_string(),
         end_symbol_id: end_symbol_id.map(|s| s.to_string()),
-        config: PathsConfig {
-            max_depth: 0, // Not tracked in result
-            max_paths: 0, // Not tracked in result
-        },
+        config,
         paths: paths_json,
         total_enumerated: result.total_enumerated,
         bounded_hit: result.bounded_hit,
+        cancelled: result.cancelled,
         statistics: PathStatisticsJson::from(result.statistics),
     };
 
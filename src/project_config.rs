@@ -1,14 +1,17 @@
 //! Per-project configuration loaded from `.magellan.toml`.
 //!
-//! Sits at the project root alongside `Cargo.toml`. Controls include/exclude
-//! paths, watcher settings, and project metadata. When absent, behaviour is
-//! identical to the pre-v4 CLI (backward compatible).
+//! Sits at the project root alongside `Cargo.toml` (`magellan.toml` is also
+//! accepted), or is passed explicitly with `--config`, which also takes JSON.
+//! Controls include/exclude paths, watcher settings, and project metadata.
+//! When absent, behaviour is identical to the pre-v4 CLI (backward compatible).
+//!
+//! Precedence for `watch`: CLI flag > config file > built-in default.
 //!
 //! Manifest auto-detection lives in [`crate::manifest`].
 
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use crate::graph::filter::FileFilter;
 use crate::manifest::detect_include_paths_from_root;
@@ -17,6 +20,27 @@ pub use crate::manifest::{CargoManifest, PyprojectManifest};
 
 const CONFIG_FILENAME: &str = ".magellan.toml";
 
+/// Non-hidden alternative, checked when `.magellan.toml` is absent.
+const ALT_CONFIG_FILENAME: &str = "magellan.toml";
+
+/// Keys recognised in each section; anything else is reported as unknown.
+const KNOWN_KEYS: &[(&str, &[&str])] = &[
+    ("project", &["name"]),
+    ("index", &["include", "exclude"]),
+    (
+        "watch",
+        &[
+            "root",
+            "db",
+            "debounce_ms",
+            "gitignore_aware",
+            "scan_initial",
+            "follow_symlinks",
+            "non_source_extensions",
+        ],
+    ),
+];
+
 fn default_debounce() -> u64 {
     500
 }
@@ -46,22 +70,41 @@ pub struct IndexSection {
 }
 
 /// `[watch]` — watcher behaviour.
+///
+/// Relative `root` and `db` paths are resolved against the config file's
+/// directory.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WatchSection {
+    /// Default for `--root`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub root: Option<PathBuf>,
+    /// Default for `--db`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub db: Option<PathBuf>,
     #[serde(default = "default_debounce")]
     pub debounce_ms: u64,
     #[serde(default = "default_true")]
     pub gitignore_aware: bool,
     #[serde(default = "default_true")]
     pub scan_initial: bool,
+    #[serde(default)]
+    pub follow_symlinks: bool,
+    /// Extensions indexed without symbols; a non-empty list turns on
+    /// `--index-non-source`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub non_source_extensions: Vec<String>,
 }
 
 impl Default for WatchSection {
     fn default() -> Self {
         Self {
+            root: None,
+            db: None,
             debounce_ms: default_debounce(),
             gitignore_aware: true,
             scan_initial: true,
+            follow_symlinks: false,
+            non_source_extensions: Vec::new(),
         }
     }
 }
@@ -80,19 +123,57 @@ pub struct ProjectConfig {
 }
 
 impl ProjectConfig {
-    /// Load `.magellan.toml` from `project_root`.
+    /// Load `.magellan.toml` (or `magellan.toml`) from `project_root`.
     ///
     /// Returns `ProjectConfig::default()` when the file is absent (not an error).
+    /// Unknown keys are ignored here; see [`ProjectConfig::load_from_path`].
     pub fn load(project_root: &Path) -> Result<Self> {
-        let path = project_root.join(CONFIG_FILENAME);
-        if !path.exists() {
-            return Ok(Self::default());
+        match Self::discover(project_root) {
+            Some(path) => Ok(Self::load_from_path(&path)?.0),
+            None => Ok(Self::default()),
         }
+    }
 
-        let content = std::fs::read_to_string(&path)
+    /// Find the config file in `project_root`, preferring `.magellan.toml`.
+    pub fn discover(project_root: &Path) -> Option<PathBuf> {
+        [CONFIG_FILENAME, ALT_CONFIG_FILENAME]
+            .iter()
+            .map(|name| project_root.join(name))
+            .find(|path| path.is_file())
+    }
+
+    /// Load a config file, TOML or JSON (by `.json` extension).
+    ///
+    /// Returns the config together with the dotted names of keys it does not
+    /// recognise (e.g. `watch.debounce`), so callers can warn about typos
+    /// instead of silently ignoring them.
+    pub fn load_from_path(path: &Path) -> Result<(Self, Vec<String>)> {
+        let content = std::fs::read_to_string(path)
             .with_context(|| format!("Failed to read {}", path.display()))?;
 
-        toml::from_str(&content).with_context(|| format!("Failed to parse {}", path.display()))
+        let is_json = path.extension().is_some_and(|ext| ext == "json");
+        let (mut config, raw): (Self, serde_json::Value) = if is_json {
+            let raw: serde_json::Value = serde_json::from_str(&content)
+                .with_context(|| format!("Failed to parse {}", path.display()))?;
+            let config = serde_json::from_value(raw.clone())
+                .with_context(|| format!("Failed to parse {}", path.display()))?;
+            (config, raw)
+        } else {
+            let config = toml::from_str(&content)
+                .with_context(|| format!("Failed to parse {}", path.display()))?;
+            let raw: toml::Value = toml::from_str(&content)
+                .with_context(|| format!("Failed to parse {}", path.display()))?;
+            (config, serde_json::to_value(raw)?)
+        };
+
+        let base = path.parent().unwrap_or_else(|| Path::new("."));
+        for field in [&mut config.watch.root, &mut config.watch.db] {
+            if let Some(p) = field.as_mut().filter(|p| p.is_relative()) {
+                *p = base.join(&*p);
+            }
+        }
+
+        Ok((config, unknown_keys(&raw)))
     }
 
     /// Write a default `.magellan.toml` into `project_root`.
@@ -145,6 +226,30 @@ impl ProjectConfig {
     }
 }
 
+/// Dotted names of keys in `raw` that are not in [`KNOWN_KEYS`].
+fn unknown_keys(raw: &serde_json::Value) -> Vec<String> {
+    let mut unknown = Vec::new();
+    let Some(sections) = raw.as_object() else {
+        return unknown;
+    };
+    for (section, body) in sections {
+        match KNOWN_KEYS.iter().find(|(name, _)| name == section) {
+            None => unknown.push(section.clone()),
+            Some((_, keys)) => {
+                if let Some(body) = body.as_object() {
+                    unknown.extend(
+                        body.keys()
+                            .filter(|key| !keys.contains(&key.as_str()))
+                            .map(|key| format!("{}.{}", section, key)),
+                    );
+                }
+            }
+        }
+    }
+    unknown.sort();
+    unknown
+}
+
 /// Expand directory patterns: `src/` → `src/**` so globs match files inside.
 fn normalize_dir_patterns(patterns: &[String]) -> Vec<String> {
     patterns
@@ -218,6 +323,52 @@ scan_initial = false
         assert!(err.contains("Failed to parse"), "got: {err}");
     }
 
+    #[test]
+    fn load_from_path_reports_unknown_keys() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(CONFIG_FILENAME);
+        fs::write(
+            &path,
+            "[watch]\ndebounce = 10\ndebounce_ms = 750\n\n[extra]\nkey = 1\n",
+        )
+        .unwrap();
+        let (cfg, unknown) = ProjectConfig::load_from_path(&path).unwrap();
+        assert_eq!(cfg.watch.debounce_ms, 750);
+        assert_eq!(unknown, vec!["extra", "watch.debounce"]);
+    }
+
+    #[test]
+    fn load_from_path_accepts_json() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("magellan.json");
+        fs::write(
+            &path,
+            r#"{"watch": {"debounce_ms": 200, "db": "out/code.db"}, "index": {"exclude": ["gen/**"]}}"#,
+        )
+        .unwrap();
+        let (cfg, unknown) = ProjectConfig::load_from_path(&path).unwrap();
+        assert!(unknown.is_empty());
+        assert_eq!(cfg.watch.debounce_ms, 200);
+        assert_eq!(cfg.watch.db, Some(dir.path().join("out/code.db")));
+        assert_eq!(cfg.index.exclude, vec!["gen/**"]);
+    }
+
+    #[test]
+    fn discover_falls_back_to_unhidden_name() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(ProjectConfig::discover(dir.path()).is_none());
+        fs::write(dir.path().join(ALT_CONFIG_FILENAME), "[watch]\n").unwrap();
+        assert_eq!(
+            ProjectConfig::discover(dir.path()),
+            Some(dir.path().join(ALT_CONFIG_FILENAME))
+        );
+        fs::write(dir.path().join(CONFIG_FILENAME), "[watch]\n").unwrap();
+        assert_eq!(
+            ProjectConfig::discover(dir.path()),
+            Some(dir.path().join(CONFIG_FILENAME))
+        );
+    }
+
     #[test]
     fn init_writes_config() {
         let dir = tempfile::tempdir().unwrap();
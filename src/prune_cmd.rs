@@ -0,0 +1,78 @@
+//! Prune command implementation
+//!
+//! Removes code chunks and metrics rows left behind when a file deletion
+//! failed part-way, reporting counts per side table.
+
+use anyhow::Result;
+use magellan::output::{
+    generate_execution_id, output_json, JsonResponse, OutputFormat, PruneResponse,
+};
+use magellan::CodeGraph;
+use std::path::PathBuf;
+
+/// Run the prune command
+///
+/// # Arguments
+/// * `db_path` - Path to the sqlitegraph database
+/// * `dry_run` - Count orphaned rows without deleting them
+/// * `output_format` - Output format (Human, Json, or Pretty)
+pub fn run_prune(db_path: PathBuf, dry_run: bool, output_format: OutputFormat) -> Result<()> {
+    let graph = CodeGraph::open(&db_path)?;
+    let exec_id = generate_execution_id();
+
+    let mut args = vec![
+        "prune".to_string(),
+        "--db".to_string(),
+        db_path.to_string_lossy().to_string(),
+    ];
+    if dry_run {
+        args.push("--dry-run".to_string());
+    }
+    graph.execution_log().start_execution(
+        &exec_id,
+        env!("CARGO_PKG_VERSION"),
+        &args,
+        None,
+        &db_path.to_string_lossy(),
+    )?;
+
+    // Phase: prune_orphans
+    graph
+        .telemetry()
+        .record_phase_start(&exec_id, "prune_orphans")?;
+
+    let report = graph.prune_orphans(dry_run)?;
+
+    graph
+        .telemetry()
+        .record_phase_end(&exec_id, "prune_orphans")?;
+
+    match output_format {
+        OutputFormat::Json | OutputFormat::Pretty => {
+            let response = PruneResponse {
+                dry_run: report.dry_run,
+                code_chunks: report.code_chunks,
+                file_metrics: report.file_metrics,
+                symbol_metrics: report.symbol_metrics,
+                file_timings: report.file_timings,
+                total: report.total(),
+            };
+            let json_response = JsonResponse::new(response, &exec_id);
+            output_json(&json_response, output_format)?;
+        }
+        OutputFormat::Human => {
+            let verb = if dry_run { "Would remove" } else { "Removed" };
+            println!("{} {} orphaned row(s):", verb, report.total());
+            println!("  code_chunks:    {}", report.code_chunks);
+            println!("  file_metrics:   {}", report.file_metrics);
+            println!("  symbol_metrics: {}", report.symbol_metrics);
+            println!("  file_timings:   {}", report.file_timings);
+        }
+    }
+
+    graph
+        .execution_log()
+        .finish_execution(&exec_id, "success", None, 0, 0, 0)?;
+
+    Ok(())
+}
@@ -1,30 +1,43 @@
 //! Query command implementation
 //!
-//! Lists symbols in a file, optionally filtered by kind.
+//! Lists symbols in a file or under an FQN prefix, optionally filtered by kind.
 
 use anyhow::Result;
 use magellan::common::{
-    detect_language_from_path, format_symbol_kind, parse_symbol_kind, resolve_path,
+    detect_language_from_path, format_symbol_kind, parse_symbol_kind_arg, resolve_path,
 };
-use magellan::output::rich::{SpanChecksums, SpanContext};
+use magellan::graph::query::{self, SymbolCountFilter, SymbolSort};
+use magellan::index_diff::ContentStatus;
+use magellan::ingest::test_code::TestFilter;
+use magellan::ingest::visibility::VisibilityFilter;
+use magellan::output::rich::SpanContext;
 use magellan::output::{
-    output_json, CalleeInfo, CallerInfo, JsonResponse, OutputFormat, QueryResponse, Span,
-    SymbolMatch,
+    output_count, output_json, CalleeInfo, CallerInfo, ColumnConverter, ColumnUnit, JsonResponse,
+    OutputFormat, QueryBaselineResponse, QueryResponse, Span, SymbolMatch, SymbolMetricsInfo,
 };
 use magellan::{CodeGraph, SymbolFact};
+use std::collections::HashMap;
 use std::path::PathBuf;
 
+use crate::db_resolver::open_graph_for_read;
+
 const QUERY_EXPLAIN_TEXT: &str = r#"Query Selector Cheatsheet
 --------------------------------
 Selectors:
-Required selectors:
+Required selectors (one of):
   --file <path>            Absolute or root-relative path to inspect.
+  --defined-in <prefix>    Symbols whose FQN is under a module path
+                           (crate::net matches net::* but not network::*).
 
 Optional filters:
   --kind <kind>            function|method|struct|trait|enum|mod|type_alias|union|namespace.
   --symbol <name>          Limit output to a specific symbol (case-sensitive).
   --show-extent            With --symbol, print byte + line/column ranges.
 
+Ordering:
+  --sort <key>             name|line|kind; ties break by position.
+  --reverse                Reverse the order (default key: line).
+
 Related helpers:
   magellan refs --name <symbol> --path <file>      Show incoming/outgoing references.
   magellan find --name <symbol>                    Locate symbol across files.
@@ -33,6 +46,7 @@ Related helpers:
 Examples:
   magellan query --db mag.db --file src/main.rs --kind function
   magellan query --db mag.db --file src/lib.rs --symbol main --show-extent
+  magellan query --db mag.db --defined-in crate::net --kind function
   magellan find  --db mag.db --list-glob \"handler_*\""#;
 
 #[allow(
@@ -53,7 +67,13 @@ pub fn run_query(
     with_callees: bool,
     with_semantics: bool,
     with_checksums: bool,
+    with_metrics: bool,
     context_lines: usize,
+    sort: Option<SymbolSort>,
+    test_filter: TestFilter,
+    visibility: Option<VisibilityFilter>,
+    defined_in: Option<String>,
+    column_unit: ColumnUnit,
 ) -> Result<()> {
     // Build args for execution tracking
     let mut args = vec!["query".to_string()];
@@ -79,8 +99,33 @@ pub fn run_query(
     if show_extent {
         args.push("--show-extent".to_string());
     }
+    if let Some(sort) = sort {
+        args.push("--sort".to_string());
+        args.push(sort.key.as_str().to_string());
+        if sort.reverse {
+            args.push("--reverse".to_string());
+        }
+    }
+    if let Some(flag) = test_filter.as_flag() {
+        args.push(flag.to_string());
+    }
+    if let Some(visibility) = visibility {
+        args.push("--visibility".to_string());
+        args.push(visibility.as_str().to_string());
+    }
+    if let Some(ref prefix) = defined_in {
+        args.push("--defined-in".to_string());
+        args.push(prefix.clone());
+    }
+    if column_unit != ColumnUnit::Byte {
+        args.push("--column-unit".to_string());
+        args.push(column_unit.as_str().to_string());
+    }
+    if with_metrics {
+        args.push("--with-metrics".to_string());
+    }
 
-    let mut graph = CodeGraph::open(&db_path)?;
+    let mut graph = open_graph_for_read(&db_path)?;
     let exec_id = magellan::output::generate_execution_id();
     let root_str = root.as_ref().map(|p| p.to_string_lossy().to_string());
     let db_path_str = db_path.to_string_lossy().to_string();
@@ -116,10 +161,10 @@ pub fn run_query(
 
     // Parse kind filter if provided
     let kind_filter = match kind_str {
-        Some(ref s) => match parse_symbol_kind(s) {
-            Some(k) => Some(k),
-            None => {
-                let err_msg = format!("Unknown symbol kind: '{}'. Valid kinds: function, method, class, interface, enum, module, union, namespace, typealias", s);
+        Some(ref s) => match parse_symbol_kind_arg(s) {
+            Ok(k) => Some(k),
+            Err(e) => {
+                let err_msg = e.to_string();
                 let _ = graph.execution_log().finish_execution(
                     &exec_id,
                     "error",
@@ -134,19 +179,15 @@ pub fn run_query(
         None => None,
     };
 
-    let file_path = match file_path {
-        Some(fp) => fp,
-        None => {
-            let err_msg = "--file is required unless --explain is used".to_string();
-            let _ =
-                graph
-                    .execution_log()
-                    .finish_execution(&exec_id, "error", Some(&err_msg), 0, 0, 0);
-            anyhow::bail!(err_msg);
-        }
-    };
+    if file_path.is_none() && defined_in.is_none() {
+        let err_msg = "--file is required unless --explain or --defined-in is used".to_string();
+        let _ = graph
+            .execution_log()
+            .finish_execution(&exec_id, "error", Some(&err_msg), 0, 0, 0);
+        anyhow::bail!(err_msg);
+    }
 
-    let path_str = resolve_path(&file_path, &root);
+    let file_path_str = file_path.as_ref().map(|fp| resolve_path(fp, &root));
 
     // End validate_args phase, start query phase
     graph
@@ -156,8 +197,17 @@ pub fn run_query(
 
     // Handle JSON output mode - use symbol_nodes_in_file_with_ids for symbol_id propagation
     if output_format == OutputFormat::Json || output_format == OutputFormat::Pretty {
-        let mut symbols_with_ids =
-            magellan::graph::query::symbol_nodes_in_file_with_ids(&mut graph, &path_str)?;
+        let mut symbols_with_ids = match (&defined_in, &file_path_str) {
+            (Some(prefix), _) => query::symbols_defined_in(
+                &mut graph,
+                prefix,
+                None,
+                file_path_str.as_deref(),
+                test_filter,
+            )?,
+            (None, Some(path_str)) => query::symbol_nodes_in_file_with_ids(&mut graph, path_str)?,
+            (None, None) => Vec::new(),
+        };
 
         // Apply kind filter
         if let Some(ref filter_kind) = kind_filter {
@@ -170,9 +220,21 @@ pub fn run_query(
                 .retain(|(_, fact, _)| fact.name.as_deref() == Some(symbol_name.as_str()));
         }
 
-        let symbols_with_ids: Vec<(SymbolFact, Option<String>)> = symbols_with_ids
+        symbols_with_ids.retain(|(_, fact, _)| test_filter.matches(fact.is_test));
+        if let Some(visibility) = visibility {
+            symbols_with_ids.retain(|(_, fact, _)| visibility.matches(fact.visibility.as_deref()));
+        }
+
+        let symbols_with_ids: Vec<QueriedSymbol> = symbols_with_ids
             .into_iter()
-            .map(|(_, fact, symbol_id)| (fact, symbol_id))
+            .map(|(node_id, fact, symbol_id)| {
+                let metrics = if with_metrics {
+                    stored_metrics(&graph, node_id)
+                } else {
+                    None
+                };
+                (fact, symbol_id, metrics)
+            })
             .collect();
 
         let _ = graph
@@ -184,9 +246,10 @@ pub fn run_query(
             .telemetry()
             .record_phase_start(&exec_id, "build_response")?;
         return output_json_mode(
-            &path_str,
+            file_path_str.as_deref().unwrap_or_default(),
             symbols_with_ids,
             kind_str,
+            defined_in,
             show_extent,
             &symbol,
             &mut graph,
@@ -198,6 +261,8 @@ pub fn run_query(
             with_semantics,
             with_checksums,
             context_lines,
+            sort,
+            column_unit,
         );
     }
 
@@ -205,14 +270,45 @@ pub fn run_query(
     // End query phase for human output
     graph.telemetry().record_phase_end(&exec_id, "query")?;
 
-    let mut symbols = graph.symbols_in_file_with_kind(&path_str, kind_filter)?;
+    let mut symbols = match (&defined_in, &file_path_str) {
+        (Some(prefix), _) => query::symbols_defined_in(
+            &mut graph,
+            prefix,
+            kind_filter.as_ref(),
+            file_path_str.as_deref(),
+            test_filter,
+        )?
+        .into_iter()
+        .map(|(_, fact, _)| fact)
+        .collect(),
+        (None, Some(path_str)) => graph.symbols_in_file_with_kind(path_str, kind_filter)?,
+        (None, None) => Vec::new(),
+    };
 
     if let Some(ref symbol_name) = symbol {
         symbols.retain(|s| s.name.as_deref() == Some(symbol_name.as_str()));
     }
+    symbols.retain(|s| test_filter.matches(s.is_test));
+    if let Some(visibility) = visibility {
+        symbols.retain(|s| visibility.matches(s.visibility.as_deref()));
+    }
+
+    if let Some(sort) = sort {
+        sort.apply(&mut symbols);
+    }
+
+    let metrics = if with_metrics {
+        metrics_by_span(&mut graph, &symbols)?
+    } else {
+        HashMap::new()
+    };
 
     // Human mode (existing behavior)
-    println!("{}:", path_str);
+    match (&defined_in, &file_path_str) {
+        (Some(prefix), Some(path_str)) => println!("{} (defined in {}):", path_str, prefix),
+        (Some(prefix), None) => println!("Defined in {}:", prefix),
+        (None, path_str) => println!("{}:", path_str.as_deref().unwrap_or_default()),
+    }
 
     if symbols.is_empty() {
         println!("  (no symbols found)");
@@ -232,10 +328,32 @@ pub fn run_query(
     for symbol in &symbols {
         let kind_str = format_symbol_kind(&symbol.kind);
         let name = symbol.name.as_deref().unwrap_or("(unnamed)");
-        println!(
-            "  Line {:4}: {:12} {:<} [{}]",
-            symbol.start_line, kind_str, name, symbol.kind_normalized
-        );
+        if defined_in.is_some() && file_path_str.is_none() {
+            println!(
+                "  {:12} {:<} [{}] in {}:{}",
+                kind_str,
+                symbol
+                    .display_fqn
+                    .as_deref()
+                    .or(symbol.fqn.as_deref())
+                    .unwrap_or(name),
+                symbol.kind_normalized,
+                symbol.file_path.display(),
+                symbol.start_line
+            );
+        } else {
+            println!(
+                "  Line {:4}: {:12} {:<} [{}]",
+                symbol.start_line, kind_str, name, symbol.kind_normalized
+            );
+        }
+
+        if let Some(m) = metrics.get(&span_key(symbol)) {
+            println!(
+                "    Metrics: loc {}, fan-in {}, fan-out {}, complexity {}",
+                m.loc, m.fan_in, m.fan_out, m.cyclomatic_complexity
+            );
+        }
 
         // Show callers if requested
         if with_callers {
@@ -269,9 +387,9 @@ pub fn run_query(
         }
     }
 
-    if show_extent {
+    if let (true, Some(path_str)) = (show_extent, &file_path_str) {
         if let Some(ref symbol_name) = symbol {
-            let mut extents = graph.symbol_extents(&path_str, symbol_name)?;
+            let mut extents = graph.symbol_extents(path_str, symbol_name)?;
             if extents.is_empty() {
                 println!("  (no extent info found for '{}')", symbol_name);
                 let _ = graph
@@ -286,7 +404,9 @@ pub fn run_query(
                     .cmp(&b.start_line)
                     .then_with(|| a.start_col.cmp(&b.start_col))
             });
-            for (node_id, fact) in extents {
+            let mut columns = ColumnConverter::for_graph(column_unit, &graph);
+            for (node_id, mut fact) in extents {
+                columns.convert_symbol(&mut fact);
                 print_extent_block(node_id, &fact);
             }
         }
@@ -303,15 +423,205 @@ pub fn run_query(
     Ok(())
 }
 
+/// A queried symbol with its stable ID and, with `--with-metrics`, its metrics
+type QueriedSymbol = (SymbolFact, Option<String>, Option<SymbolMetricsInfo>);
+
+/// Stored metrics of the symbol node `node_id`
+///
+/// None when metrics were never computed for it (including databases
+/// without a `symbol_metrics` table).
+pub(crate) fn stored_metrics(graph: &CodeGraph, node_id: i64) -> Option<SymbolMetricsInfo> {
+    graph
+        .metrics()
+        .get_symbol_metrics(node_id)
+        .ok()
+        .flatten()
+        .map(SymbolMetricsInfo::from)
+}
+
+type SpanKey = (String, usize, usize);
+
+fn span_key(symbol: &SymbolFact) -> SpanKey {
+    (
+        symbol.file_path.to_string_lossy().to_string(),
+        symbol.byte_start,
+        symbol.byte_end,
+    )
+}
+
+/// Stored metrics of `symbols`, keyed by file and byte span
+///
+/// Human output works on facts without node IDs, so the IDs are looked up
+/// once per file.
+fn metrics_by_span(
+    graph: &mut CodeGraph,
+    symbols: &[SymbolFact],
+) -> Result<HashMap<SpanKey, SymbolMetricsInfo>> {
+    let mut files: Vec<String> = symbols
+        .iter()
+        .map(|s| s.file_path.to_string_lossy().to_string())
+        .collect();
+    files.sort();
+    files.dedup();
+
+    let mut metrics = HashMap::new();
+    for file in files {
+        for (node_id, fact, _) in query::symbol_nodes_in_file_with_ids(graph, &file)? {
+            if let Some(m) = stored_metrics(graph, node_id) {
+                metrics.insert(span_key(&fact), m);
+            }
+        }
+    }
+    Ok(metrics)
+}
+
 /// Output query results in JSON format
+#[allow(
+    clippy::too_many_arguments,
+    reason = "JSON output needs all query parameters"
+)]
+/// Run `query --count-only`
+///
+/// Counts symbols with a single `COUNT(*)` query, honouring `--kind` and
+/// `--symbol`. Without `--file` the count covers the whole database.
+pub fn run_query_count(
+    db_path: PathBuf,
+    file_path: Option<PathBuf>,
+    root: Option<PathBuf>,
+    kind_str: Option<String>,
+    symbol: Option<String>,
+    test_filter: TestFilter,
+    visibility: Option<VisibilityFilter>,
+    output_format: OutputFormat,
+) -> Result<()> {
+    let kind = match kind_str {
+        Some(ref s) => Some(parse_symbol_kind_arg(s)?),
+        None => None,
+    };
+
+    let mut graph = open_graph_for_read(&db_path)?;
+    let filter = SymbolCountFilter {
+        file_path: file_path.as_ref().map(|fp| resolve_path(fp, &root)),
+        kind,
+        name: symbol,
+        name_contains: false,
+        test_filter,
+        visibility,
+    };
+    let count = graph.count_symbols_matching(&filter)?;
+    output_count(count, output_format)
+}
+
+/// Run `query --file <PATH> --baseline <DB>`
+///
+/// Compares each symbol's stored content with the same file in the baseline
+/// database and marks it added, removed, modified, or unchanged. Changed
+/// symbols are printed with their body in human output.
+pub fn run_query_baseline(
+    db_path: PathBuf,
+    file_path: PathBuf,
+    root: Option<PathBuf>,
+    baseline_db: PathBuf,
+    kind_str: Option<String>,
+    changed_only: bool,
+    output_format: OutputFormat,
+) -> Result<()> {
+    let kind = match kind_str {
+        Some(ref s) => Some(format!("{:?}", parse_symbol_kind_arg(s)?)),
+        None => None,
+    };
+    if !baseline_db.exists() {
+        anyhow::bail!("Baseline database not found: {}", baseline_db.display());
+    }
+
+    let mut graph = open_graph_for_read(&db_path)?;
+    let mut baseline = open_graph_for_read(&baseline_db)?;
+    let path_str = resolve_path(&file_path, &root);
+
+    let mut changes =
+        magellan::index_diff::diff_file_contents(&mut baseline, &mut graph, &path_str)?;
+    if let Some(ref kind) = kind {
+        changes.retain(|change| &change.location.kind == kind);
+    }
+
+    let count = |status: ContentStatus| {
+        changes
+            .iter()
+            .filter(|change| change.status == status)
+            .count()
+    };
+    let (added, removed, modified, unchanged) = (
+        count(ContentStatus::Added),
+        count(ContentStatus::Removed),
+        count(ContentStatus::Modified),
+        count(ContentStatus::Unchanged),
+    );
+    if changed_only {
+        changes.retain(|change| change.status != ContentStatus::Unchanged);
+    }
+
+    match output_format {
+        OutputFormat::Json | OutputFormat::Pretty => {
+            let exec_id = magellan::output::generate_execution_id();
+            let response = QueryBaselineResponse {
+                file_path: path_str,
+                baseline_db: baseline_db.to_string_lossy().to_string(),
+                symbols: changes,
+                added,
+                removed,
+                modified,
+                unchanged,
+            };
+            output_json(&JsonResponse::new(response, &exec_id), output_format)?;
+        }
+        OutputFormat::Human => {
+            println!("{} (baseline {}):", path_str, baseline_db.display());
+            if changes.is_empty() {
+                println!("  (no symbols found)");
+            }
+            for change in &changes {
+                let marker = match change.status {
+                    ContentStatus::Added => '+',
+                    ContentStatus::Removed => '-',
+                    ContentStatus::Modified => '~',
+                    ContentStatus::Unchanged => ' ',
+                };
+                println!(
+                    "  {} {:9} Line {:4}: {:12} {}",
+                    marker,
+                    change.status.as_str(),
+                    change.location.start_line,
+                    change.location.kind,
+                    change.fqn
+                );
+                if change.status == ContentStatus::Unchanged {
+                    continue;
+                }
+                if let Some(ref body) = change.body {
+                    for line in body.lines() {
+                        println!("      {} {}", marker, line);
+                    }
+                }
+            }
+            println!(
+                "{} added, {} removed, {} modified, {} unchanged",
+                added, removed, modified, unchanged
+            );
+        }
+    }
+
+    Ok(())
+}
+
 #[allow(
     clippy::too_many_arguments,
     reason = "JSON output needs all query parameters"
 )]
 fn output_json_mode(
     path_str: &str,
-    mut symbols_with_ids: Vec<(SymbolFact, Option<String>)>,
+    mut symbols_with_ids: Vec<QueriedSymbol>,
     kind_str: Option<String>,
+    defined_in: Option<String>,
     _show_extent: bool,
     _symbol: &Option<String>,
     graph: &mut CodeGraph,
@@ -323,20 +633,30 @@ fn output_json_mode(
     with_semantics: bool,
     with_checksums: bool,
     context_lines: usize,
+    sort: Option<SymbolSort>,
+    column_unit: ColumnUnit,
 ) -> Result<()> {
-    // Sort deterministically: by file_path, start_line, start_col, name
-    symbols_with_ids.sort_by(|(a, _), (b, _)| {
-        a.file_path
-            .cmp(&b.file_path)
-            .then_with(|| a.start_line.cmp(&b.start_line))
-            .then_with(|| a.start_col.cmp(&b.start_col))
-            .then_with(|| a.name.as_deref().cmp(&b.name.as_deref()))
-    });
-
-    // Convert (SymbolFact, Option<symbol_id>) to SymbolMatch with rich span data
+    // Sort deterministically: by file_path, start_line, start_col, name,
+    // unless --sort/--reverse asked for a specific order. --defined-in
+    // listings arrive already in FQN order.
+    match sort {
+        Some(sort) => symbols_with_ids.sort_by(|(a, ..), (b, ..)| sort.compare(a, b)),
+        None if defined_in.is_some() => {}
+        None => symbols_with_ids.sort_by(|(a, ..), (b, ..)| {
+            a.file_path
+                .cmp(&b.file_path)
+                .then_with(|| a.start_line.cmp(&b.start_line))
+                .then_with(|| a.start_col.cmp(&b.start_col))
+                .then_with(|| a.name.as_deref().cmp(&b.name.as_deref()))
+        }),
+    }
+
+    // Convert (SymbolFact, Option<symbol_id>, metrics) to SymbolMatch with rich span data
+    let mut columns = ColumnConverter::for_graph(column_unit, graph);
     let symbol_matches: Vec<SymbolMatch> = symbols_with_ids
         .into_iter()
-        .map(|(s, symbol_id)| {
+        .map(|(mut s, symbol_id, metrics)| {
+            columns.convert_symbol(&mut s);
             let file_path = s.file_path.to_string_lossy().to_string();
             let mut span = Span::new(
                 file_path.clone(),
@@ -365,7 +685,7 @@ fn output_json_mode(
 
             // Add checksums if requested
             if with_checksums {
-                let checksums = SpanChecksums::compute(&file_path, s.byte_start, s.byte_end);
+                let checksums = graph.span_checksums(&file_path, s.byte_start, s.byte_end);
                 span = span.with_checksums(checksums);
             }
 
@@ -376,11 +696,14 @@ fn output_json_mode(
                 if let Ok(call_facts) = graph.callers_of_symbol(&file_path, &symbol_name) {
                     let mut callers: Vec<CallerInfo> = call_facts
                         .into_iter()
-                        .map(|call| CallerInfo {
-                            name: call.caller,
-                            file_path: call.file_path.to_string_lossy().to_string(),
-                            line: call.start_line,
-                            column: call.start_col,
+                        .map(|mut call| {
+                            columns.convert_call(&mut call);
+                            CallerInfo {
+                                name: call.caller,
+                                file_path: call.file_path.to_string_lossy().to_string(),
+                                line: call.start_line,
+                                column: call.start_col,
+                            }
                         })
                         .collect();
                     // Sort deterministically
@@ -421,14 +744,16 @@ fn output_json_mode(
                 None
             };
 
-            SymbolMatch::new(
+            let mut symbol_match = SymbolMatch::new(
                 symbol_name,
                 s.kind_normalized,
                 span,
                 None,      // parent not tracked yet
                 symbol_id, // stable symbol ID from graph
             )
-            .with_callers_and_callees(callers_info, callees_info)
+            .with_callers_and_callees(callers_info, callees_info);
+            symbol_match.metrics = metrics;
+            symbol_match
         })
         .collect();
 
@@ -436,6 +761,8 @@ fn output_json_mode(
         symbols: symbol_matches,
         file_path: path_str.to_string(),
         kind_filter: kind_str,
+        defined_in,
+        column_unit: column_unit.as_str().to_string(),
     };
 
     let json_response = JsonResponse::new(response, exec_id);
@@ -6,6 +6,7 @@ use anyhow::Result;
 use magellan::graph::SymbolInfo;
 use magellan::output::{output_json, JsonResponse, OutputFormat};
 use magellan::CodeGraph;
+use std::collections::HashSet;
 use std::path::PathBuf;
 
 /// Run the reachable command
@@ -14,14 +15,18 @@ use std::path::PathBuf;
 /// * `db_path` - Path to the sqlitegraph database
 /// * `symbol_id` - Stable symbol ID to start from
 /// * `reverse` - If true, show callers (reverse reachability); if false, show callees
+/// * `dot` - If true, print the reachable subgraph as Graphviz DOT
+/// * `max_depth` - Only follow this many calls from the starting symbol (None = unlimited)
 /// * `output_format` - Output format (Human or Json)
 ///
 /// # Displays
-/// Human-readable list of reachable symbols or JSON output
+/// Human-readable list of reachable symbols, JSON output, or a DOT digraph
 pub fn run_reachable(
     db_path: PathBuf,
     symbol_id: String,
     reverse: bool,
+    dot: bool,
+    max_depth: Option<usize>,
     output_format: OutputFormat,
 ) -> Result<()> {
     // Build args for execution tracking
@@ -31,6 +36,14 @@ pub fn run_reachable(
     if reverse {
         args.push("--reverse".to_string());
     }
+    if let Some(max_depth) = max_depth {
+        args.push("--max-depth".to_string());
+        args.push(max_depth.to_string());
+    }
+    if dot {
+        args.push("--output".to_string());
+        args.push("dot".to_string());
+    }
 
     let graph = CodeGraph::open(&db_path)?;
     let exec_id = magellan::output::generate_execution_id();
@@ -44,19 +57,62 @@ pub fn run_reachable(
         &db_path_str,
     )?;
 
-    // Query reachability
-    let symbols = if reverse {
-        graph.reverse_reachable_symbols(&symbol_id, None)?
-    } else {
-        graph.reachable_symbols(&symbol_id, None)?
-    };
+    // Ctrl-C stops the traversal and prints what was found so far; a second
+    // Ctrl-C exits immediately
+    #[cfg(unix)]
+    {
+        use signal_hook::consts::signal;
+        use signal_hook::flag;
+        let token = graph.cancel_token();
+        let _ = flag::register_conditional_shutdown(signal::SIGINT, 130, token.clone())?;
+        let _ = flag::register(signal::SIGINT, token)?;
+    }
+
+    // Query reachability; the starting symbol is the only one at depth 0
+    let (_, symbols): (Vec<_>, Vec<_>) = graph
+        .reachable_symbols_with_depth(&symbol_id, reverse, max_depth)?
+        .into_iter()
+        .partition(|(_, depth)| *depth == 0);
+    let cancelled = graph.is_cancelled();
+
+    if dot {
+        let start = graph.symbol_by_entity_id(graph.resolve_symbol_entity(&symbol_id)?)?;
+        let mut edges = graph.reachable_call_edges(&symbol_id, reverse)?;
+        if max_depth.is_some() {
+            // Keep only edges among the symbols within the depth limit
+            let kept: HashSet<_> = symbols
+                .iter()
+                .map(|(symbol, _)| symbol.symbol_id.clone())
+                .chain([start.symbol_id.clone()])
+                .collect();
+            edges.retain(|(caller, callee)| {
+                kept.contains(&caller.symbol_id) && kept.contains(&callee.symbol_id)
+            });
+        }
+        let symbols: Vec<SymbolInfo> = symbols.into_iter().map(|(symbol, _)| symbol).collect();
+        print!(
+            "{}",
+            magellan::graph::export::reachable_to_dot(&start, &symbols, &edges, reverse)
+        );
+        graph
+            .execution_log()
+            .finish_execution(&exec_id, "success", None, 0, 0, 0)?;
+        return Ok(());
+    }
 
     // Handle JSON output mode
     if output_format == OutputFormat::Json || output_format == OutputFormat::Pretty {
         graph
             .execution_log()
             .finish_execution(&exec_id, "success", None, 0, 0, 0)?;
-        return output_json_mode(&symbol_id, reverse, symbols, &exec_id, output_format);
+        return output_json_mode(
+            &symbol_id,
+            reverse,
+            symbols,
+            cancelled,
+            &exec_id,
+            output_format,
+        );
     }
 
     // Human mode
@@ -70,14 +126,25 @@ pub fn run_reachable(
         println!("No symbols {} \"{}\"", direction_label, symbol_id);
     } else {
         println!("Symbols {} \"{}\":", direction_label, symbol_id);
-        for symbol in &symbols {
+        for (symbol, depth) in &symbols {
             let fqn_display = symbol.fqn.as_deref().unwrap_or("?");
-            println!(
-                "  {} ({}) in {}",
-                fqn_display, symbol.kind, symbol.file_path
-            );
+            // Depths are only annotated when a limit was asked for
+            if max_depth.is_some() {
+                println!(
+                    "  {} ({}) in {} [depth {}]",
+                    fqn_display, symbol.kind, symbol.file_path, depth
+                );
+            } else {
+                println!(
+                    "  {} ({}) in {}",
+                    fqn_display, symbol.kind, symbol.file_path
+                );
+            }
         }
     }
+    if cancelled {
+        println!("Cancelled: results are partial");
+    }
 
     graph
         .execution_log()
@@ -96,6 +163,8 @@ pub struct ReachableResponse {
     pub count: usize,
     /// List of reachable symbols
     pub symbols: Vec<SymbolInfoJson>,
+    /// Whether Ctrl-C stopped the traversal, leaving `symbols` partial
+    pub cancelled: bool,
 }
 
 /// Symbol info for JSON output
@@ -109,15 +178,18 @@ pub struct SymbolInfoJson {
     pub file_path: String,
     /// Symbol kind (Function, Method, Class, etc.)
     pub kind: String,
+    /// Calls on the shortest path from the starting symbol (1 = direct)
+    pub depth: usize,
 }
 
-impl From<SymbolInfo> for SymbolInfoJson {
-    fn from(info: SymbolInfo) -> Self {
+impl From<(SymbolInfo, usize)> for SymbolInfoJson {
+    fn from((info, depth): (SymbolInfo, usize)) -> Self {
         Self {
             symbol_id: info.symbol_id,
             fqn: info.fqn,
             file_path: info.file_path,
             kind: info.kind,
+            depth,
         }
     }
 }
@@ -126,7 +198,8 @@ impl From<SymbolInfo> for SymbolInfoJson {
 fn output_json_mode(
     symbol_id: &str,
     reverse: bool,
-    symbols: Vec<SymbolInfo>,
+    symbols: Vec<(SymbolInfo, usize)>,
+    cancelled: bool,
     exec_id: &str,
     output_format: OutputFormat,
 ) -> Result<()> {
@@ -139,6 +212,7 @@ fn output_json_mode(
         direction,
         count: symbols_json.len(),
         symbols: symbols_json,
+        cancelled,
     };
 
     let json_response = JsonResponse::new(response, exec_id);
@@ -0,0 +1,77 @@
+//! Rebase-paths command implementation
+//!
+//! Rewrites the file paths stored in a database from one root to another,
+//! or between absolute and root-relative form (`.`).
+
+use anyhow::Result;
+use magellan::output::{
+    generate_execution_id, output_json, JsonResponse, OutputFormat, RebasePathsResponse,
+};
+use magellan::{CodeGraph, RebaseRoot};
+use std::path::PathBuf;
+
+/// Run the rebase-paths command
+///
+/// # Arguments
+/// * `db_path` - Path to the sqlitegraph database
+/// * `from` - Root the stored paths are under, or `.` for relative paths
+/// * `to` - Root to move them under, or `.` to store them relative
+/// * `output_format` - Output format (Human, Json, or Pretty)
+pub fn run_rebase_paths(
+    db_path: PathBuf,
+    from: String,
+    to: String,
+    output_format: OutputFormat,
+) -> Result<()> {
+    let mut graph = CodeGraph::open(&db_path)?;
+    let exec_id = generate_execution_id();
+
+    let args = vec![
+        "rebase-paths".to_string(),
+        "--db".to_string(),
+        db_path.to_string_lossy().to_string(),
+        "--from".to_string(),
+        from.clone(),
+        "--to".to_string(),
+        to.clone(),
+    ];
+    graph.execution_log().start_execution(
+        &exec_id,
+        env!("CARGO_PKG_VERSION"),
+        &args,
+        None,
+        &db_path.to_string_lossy(),
+    )?;
+
+    let report = graph.rebase_paths(&RebaseRoot::parse(&from), &RebaseRoot::parse(&to))?;
+
+    match output_format {
+        OutputFormat::Json | OutputFormat::Pretty => {
+            let response = RebasePathsResponse {
+                from,
+                to,
+                entities: report.entities,
+                side_rows: report.side_rows,
+                total: report.total(),
+            };
+            let json_response = JsonResponse::new(response, &exec_id);
+            output_json(&json_response, output_format)?;
+        }
+        OutputFormat::Human => {
+            println!(
+                "Rebased {} path(s) from {} to {}:",
+                report.total(),
+                from,
+                to
+            );
+            println!("  entities:   {}", report.entities);
+            println!("  side rows:  {}", report.side_rows);
+        }
+    }
+
+    graph
+        .execution_log()
+        .finish_execution(&exec_id, "success", None, 0, 0, 0)?;
+
+    Ok(())
+}
@@ -29,11 +29,75 @@ pub struct ReferenceFact {
     pub end_line: usize,
     /// Column where reference ends (0-indexed, bytes)
     pub end_col: usize,
+    /// Nearest function or method whose span contains this reference
+    ///
+    /// Attached when references are indexed into the graph; `None` at
+    /// extraction time and for references outside any function body.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub in_symbol: Option<EnclosingSymbol>,
+    /// Whether this is an ordinary use of the symbol or an import of it
+    #[serde(default, skip_serializing_if = "ReferenceKind::is_usage")]
+    pub kind: ReferenceKind,
+}
+
+/// How a reference mentions its symbol
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ReferenceKind {
+    /// Any mention outside an import
+    #[default]
+    Usage,
+    /// A target of a `use` declaration (Rust)
+    ///
+    /// `referenced_symbol` is the imported symbol's FQN rather than its name.
+    Import,
+}
+
+impl ReferenceKind {
+    /// Whether this is the default kind (used to keep it out of serialized data)
+    pub fn is_usage(&self) -> bool {
+        *self == ReferenceKind::Usage
+    }
+}
+
+/// The function or method a reference occurs in
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct EnclosingSymbol {
+    /// Simple name of the enclosing symbol
+    pub name: String,
+    /// Stable symbol ID of the enclosing symbol, when persisted
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub symbol_id: Option<String>,
+}
+
+/// Find the nearest function or method whose span contains a byte range
+///
+/// `symbols` are the symbols defined in the reference's file, paired with their
+/// stable symbol IDs. The smallest containing span wins, so a reference inside
+/// a nested function or a method reports that item rather than an outer one.
+pub fn enclosing_symbol(
+    symbols: &[(SymbolFact, Option<String>)],
+    byte_start: usize,
+    byte_end: usize,
+) -> Option<EnclosingSymbol> {
+    symbols
+        .iter()
+        .filter(|(fact, _)| matches!(fact.kind, SymbolKind::Function | SymbolKind::Method))
+        .filter(|(fact, _)| fact.byte_start <= byte_start && byte_end <= fact.byte_end)
+        .min_by_key(|(fact, _)| fact.byte_end - fact.byte_start)
+        .and_then(|(fact, symbol_id)| {
+            Some(EnclosingSymbol {
+                name: fact.name.clone()?,
+                symbol_id: symbol_id.clone(),
+            })
+        })
 }
 
 /// A fact about a function call (forward call graph edge)
 ///
-/// Represents: caller function → callee function
+/// Represents: caller function → callee function, at one call expression.
+/// Each call site gets its own fact, so a function calling the same callee
+/// twice (or nesting `b(b())`) yields one fact per call with its own span.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct CallFact {
     /// File containing this call
@@ -208,7 +272,20 @@ impl ReferenceExtractor {
         };
 
         let referenced_symbol = referenced_symbol?;
-        let symbol_name = referenced_symbol.name.as_deref().unwrap_or(text);
+        let kind = if is_in_use_declaration(node) {
+            ReferenceKind::Import
+        } else {
+            ReferenceKind::Usage
+        };
+        // Imports point at the FQN so items nested in modules resolve exactly
+        let symbol_name = match kind {
+            ReferenceKind::Import => referenced_symbol
+                .fqn
+                .as_deref()
+                .or(referenced_symbol.name.as_deref())
+                .unwrap_or(text),
+            ReferenceKind::Usage => referenced_symbol.name.as_deref().unwrap_or(text),
+        };
 
         // Check if reference is OUTSIDE the symbol's defining span
         let ref_start = node.start_byte();
@@ -229,10 +306,24 @@ impl ReferenceExtractor {
             start_col: node.start_position().column,
             end_line: node.end_position().row + 1,
             end_col: node.end_position().column,
+            in_symbol: None,
+            kind,
         })
     }
 }
 
+/// Whether `node` is part of a `use` declaration
+fn is_in_use_declaration(node: &tree_sitter::Node) -> bool {
+    let mut current = node.parent();
+    while let Some(parent) = current {
+        if parent.kind() == "use_declaration" {
+            return true;
+        }
+        current = parent.parent();
+    }
+    false
+}
+
 impl Default for ReferenceExtractor {
     fn default() -> Self {
         Self::new().expect("Failed to create reference extractor") // M-UNWRAP: tree-sitter language is a build-time invariant
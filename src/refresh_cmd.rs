@@ -6,6 +6,7 @@
 use anyhow::{Context, Result};
 use git2::{Repository, StatusOptions};
 use magellan::output::{generate_execution_id, output_json, JsonResponse, OutputFormat};
+use magellan::{log_info, log_warn};
 use magellan::{CodeGraph, ReconcileOutcome};
 use serde::Serialize;
 use std::collections::HashSet;
@@ -188,7 +189,7 @@ pub fn run_refresh(args: &RefreshArgs) -> Result<RefreshReport> {
 
         // Rebuild FTS5 index so symbol search stays synchronized
         if let Err(e) = CodeGraph::rebuild_fts5_index(&args.db_path) {
-            eprintln!("  Warning: FTS5 rebuild failed: {}", e);
+            log_warn!("FTS5 rebuild failed: {}", e);
         }
     }
 
@@ -447,13 +448,13 @@ fn apply_changes(graph: &mut CodeGraph, delta: &FileDelta) -> Result<()> {
         let path = Path::new(path_str);
         match graph.reconcile_file_path(path, path_str) {
             Ok(ReconcileOutcome::Reindexed { symbols, .. }) => {
-                eprintln!("  Updated: {} ({} symbols)", path_str, symbols);
+                log_info!("  Updated: {} ({} symbols)", path_str, symbols);
             }
             Ok(ReconcileOutcome::Unchanged) => {
-                eprintln!("  Unchanged: {}", path_str);
+                log_info!("  Unchanged: {}", path_str);
             }
             Ok(ReconcileOutcome::Deleted) => {
-                eprintln!("  Deleted during update: {}", path_str);
+                log_info!("  Deleted during update: {}", path_str);
             }
             Err(e) => {
                 eprintln!("  Error updating {}: {}", path_str, e);
@@ -465,7 +466,7 @@ fn apply_changes(graph: &mut CodeGraph, delta: &FileDelta) -> Result<()> {
     for path_str in &delta.to_delete {
         match graph.delete_file_facts(path_str) {
             Ok(result) => {
-                eprintln!(
+                log_info!(
                     "  Deleted: {} ({} symbols, {} refs, {} calls)",
                     path_str,
                     result.symbols_deleted,
@@ -484,13 +485,13 @@ fn apply_changes(graph: &mut CodeGraph, delta: &FileDelta) -> Result<()> {
         let path = Path::new(path_str);
         match graph.reconcile_file_path(path, path_str) {
             Ok(ReconcileOutcome::Reindexed { symbols, .. }) => {
-                eprintln!("  Added: {} ({} symbols)", path_str, symbols);
+                log_info!("  Added: {} ({} symbols)", path_str, symbols);
             }
             Ok(ReconcileOutcome::Unchanged) => {
-                eprintln!("  Skipped (unchanged): {}", path_str);
+                log_info!("  Skipped (unchanged): {}", path_str);
             }
             Ok(ReconcileOutcome::Deleted) => {
-                eprintln!("  Skipped (deleted): {}", path_str);
+                log_info!("  Skipped (deleted): {}", path_str);
             }
             Err(e) => {
                 eprintln!("  Error adding {}: {}", path_str, e);
@@ -498,6 +499,9 @@ fn apply_changes(graph: &mut CodeGraph, delta: &FileDelta) -> Result<()> {
         }
     }
 
+    // Link qualified calls into files indexed after their callers
+    graph.relink_qualified_calls()?;
+
     Ok(())
 }
 
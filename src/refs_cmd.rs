@@ -2,17 +2,20 @@
 //!
 //! Shows calls (incoming/outgoing) for a symbol.
 
+use crate::db_resolver::open_graph_for_read;
 use crate::service::registry::Registry;
 use anyhow::Context;
 use anyhow::Result;
 use magellan::common::{detect_language_from_path, resolve_path};
+use magellan::git_diff::ChangedLines;
 use magellan::graph::query;
 use magellan::graph::MultiDbContext;
-use magellan::output::rich::{SpanChecksums, SpanContext};
+use magellan::output::rich::SpanContext;
 use magellan::output::{
-    output_json, JsonResponse, OutputFormat, ReferenceMatch, RefsResponse, Span,
+    output_json, ColumnConverter, ColumnUnit, JsonResponse, OutputFormat, ReferenceMatch,
+    RefsByFileResponse, RefsResponse, Span,
 };
-use magellan::{CallFact, CodeGraph};
+use magellan::{CallFact, CodeGraph, EnclosingSymbol};
 use std::path::{Path, PathBuf};
 
 /// Represents a found symbol with its file path for refs lookup
@@ -61,6 +64,9 @@ fn find_symbol_all_files(graph: &mut CodeGraph, name: &str) -> Result<Vec<RefsSy
 /// * `with_semantics` - Include semantic information (kind, language)
 /// * `with_checksums` - Include SHA-256 checksums
 /// * `context_lines` - Number of context lines before/after (capped at 100)
+/// * `group_by_file` - Aggregate references per file, sorted by path
+/// * `since` - Keep only references on lines changed since this git ref
+/// * `column_unit` - Unit that output columns are counted in
 ///
 /// # Displays
 /// Human-readable list of calls or JSON output
@@ -82,6 +88,9 @@ pub fn run_refs(
     context_lines: usize,
     all: bool,
     tokens: Option<usize>,
+    group_by_file: bool,
+    since: Option<String>,
+    column_unit: ColumnUnit,
 ) -> Result<()> {
     if all {
         return run_refs_all(
@@ -113,8 +122,19 @@ pub fn run_refs(
     }
     args.push("--direction".to_string());
     args.push(direction.clone());
+    if group_by_file {
+        args.push("--group-by-file".to_string());
+    }
+    if let Some(ref git_ref) = since {
+        args.push("--since".to_string());
+        args.push(git_ref.clone());
+    }
+    if column_unit != ColumnUnit::Byte {
+        args.push("--column-unit".to_string());
+        args.push(column_unit.as_str().to_string());
+    }
 
-    let graph = CodeGraph::open(&db_path)?;
+    let graph = open_graph_for_read(&db_path)?;
     let exec_id = magellan::output::generate_execution_id();
     let root_str = root.as_ref().map(|p| p.to_string_lossy().to_string());
     let db_path_str = db_path.to_string_lossy().to_string();
@@ -134,7 +154,7 @@ pub fn run_refs(
 
     // Handle --symbol-id alternative
     if let Some(sid) = symbol_id {
-        let mut graph_mut = CodeGraph::open(&db_path)?;
+        let mut graph_mut = open_graph_for_read(&db_path)?;
         let target_symbol = query::find_by_symbol_id(&mut graph_mut, &sid)?;
 
         match target_symbol {
@@ -183,6 +203,12 @@ pub fn run_refs(
                             anyhow::bail!(err_msg);
                         }
                     };
+                    let mut calls = match since {
+                        Some(ref git_ref) => changed_since(calls, git_ref, &root)?,
+                        None => calls,
+                    };
+                    let mut columns = ColumnConverter::for_graph(column_unit, &graph);
+                    calls.iter_mut().for_each(|call| columns.convert_call(call));
 
                     // Handle JSON output mode
                     if output_format == OutputFormat::Json || output_format == OutputFormat::Pretty
@@ -203,37 +229,13 @@ pub fn run_refs(
                             with_checksums,
                             context_lines,
                             tokens,
+                            group_by_file,
+                            column_unit,
                         );
                     }
 
                     // Human mode
-                    if direction == "in" || direction == "incoming" {
-                        if calls.is_empty() {
-                            println!("No incoming calls to \"{}\"", symbol_name);
-                        } else {
-                            println!("Calls TO \"{}\":", symbol_name);
-                            for call in &calls {
-                                println!(
-                                    "  From: {} (Function) at {}:{}",
-                                    call.caller,
-                                    call.file_path.display(),
-                                    call.start_line
-                                );
-                            }
-                        }
-                    } else if calls.is_empty() {
-                        println!("No outgoing calls from \"{}\"", symbol_name);
-                    } else {
-                        println!("Calls FROM \"{}\":", symbol_name);
-                        for call in &calls {
-                            println!(
-                                "  To: {} at {}:{}",
-                                call.callee,
-                                call.file_path.display(),
-                                call.start_line
-                            );
-                        }
-                    }
+                    print_calls_human(&symbol_name, &direction, calls, group_by_file);
 
                     graph
                         .execution_log()
@@ -285,7 +287,7 @@ pub fn run_refs(
             graph.telemetry().record_phase_start(&exec_id, "search")?;
 
             // No path provided - search all files for the symbol
-            let mut graph_mut = CodeGraph::open(&db_path)?;
+            let mut graph_mut = open_graph_for_read(&db_path)?;
             let matches = find_symbol_all_files(&mut graph_mut, &name)?;
 
             match matches.len() {
@@ -342,7 +344,7 @@ pub fn run_refs(
 
             // Get callers of this symbol
             {
-                let mut graph_mut = CodeGraph::open(&db_path)?;
+                let mut graph_mut = open_graph_for_read(&db_path)?;
                 graph_mut.callers_of_symbol(&path_str, &name)?
             }
         }
@@ -357,7 +359,7 @@ pub fn run_refs(
 
             // Get calls from this symbol
             {
-                let mut graph_mut = CodeGraph::open(&db_path)?;
+                let mut graph_mut = open_graph_for_read(&db_path)?;
                 graph_mut.calls_from_symbol(&path_str, &name)?
             }
         }
@@ -374,6 +376,12 @@ pub fn run_refs(
             anyhow::bail!(err_msg);
         }
     };
+    let mut calls = match since {
+        Some(ref git_ref) => changed_since(calls, git_ref, &root)?,
+        None => calls,
+    };
+    let mut columns = ColumnConverter::for_graph(column_unit, &graph);
+    calls.iter_mut().for_each(|call| columns.convert_call(call));
 
     // Handle JSON output mode
     if output_format == OutputFormat::Json || output_format == OutputFormat::Pretty {
@@ -399,6 +407,8 @@ pub fn run_refs(
             with_checksums,
             context_lines,
             tokens,
+            group_by_file,
+            column_unit,
         );
     }
 
@@ -406,10 +416,66 @@ pub fn run_refs(
     if direction == "in" || direction == "incoming" {
         // End query phase for human output
         graph.telemetry().record_phase_end(&exec_id, "query_refs")?;
-        if calls.is_empty() {
-            println!("No incoming calls to \"{}\"", name);
+    }
+    print_calls_human(&name, &direction, calls, group_by_file);
+
+    graph
+        .execution_log()
+        .finish_execution(&exec_id, "success", None, 0, 0, 0)?;
+
+    // Record output phase
+    graph.telemetry().record_phase_start(&exec_id, "output")?;
+    graph.telemetry().record_phase_end(&exec_id, "output")?;
+
+    Ok(())
+}
+
+/// Keep the calls whose lines changed in the working tree since `git_ref`
+///
+/// Git runs in `root` (or the current directory); stored relative paths are
+/// resolved against the same directory.
+fn changed_since(
+    calls: Vec<CallFact>,
+    git_ref: &str,
+    root: &Option<PathBuf>,
+) -> Result<Vec<CallFact>> {
+    let git_dir = match root {
+        Some(root) => root.clone(),
+        None => std::env::current_dir()?,
+    };
+    let changed = ChangedLines::from_git(&git_dir, git_ref)?;
+    Ok(calls
+        .into_iter()
+        .filter(|call| {
+            let path = PathBuf::from(resolve_path(&call.file_path, root));
+            changed.overlaps(&path, call.start_line, call.end_line)
+        })
+        .collect())
+}
+
+/// Print calls in human format, optionally grouped per file
+///
+/// Grouped output lists files sorted by path with a per-file count, then the
+/// line and caller (or callee) of each reference in source order.
+fn print_calls_human(
+    symbol_name: &str,
+    direction: &str,
+    mut calls: Vec<CallFact>,
+    group_by_file: bool,
+) {
+    let incoming = direction == "in" || direction == "incoming";
+    if calls.is_empty() {
+        if incoming {
+            println!("No incoming calls to \"{}\"", symbol_name);
         } else {
-            println!("Calls TO \"{}\":", name);
+            println!("No outgoing calls from \"{}\"", symbol_name);
+        }
+        return;
+    }
+
+    if !group_by_file {
+        if incoming {
+            println!("Calls TO \"{}\":", symbol_name);
             for call in &calls {
                 println!(
                     "  From: {} (Function) at {}:{}",
@@ -418,30 +484,74 @@ pub fn run_refs(
                     call.start_line
                 );
             }
+        } else {
+            println!("Calls FROM \"{}\":", symbol_name);
+            for call in &calls {
+                println!(
+                    "  To: {} at {}:{}",
+                    call.callee,
+                    call.file_path.display(),
+                    call.start_line
+                );
+            }
         }
-    } else if calls.is_empty() {
-        println!("No outgoing calls from \"{}\"", name);
+        return;
+    }
+
+    calls.sort_by(|a, b| {
+        a.file_path
+            .cmp(&b.file_path)
+            .then_with(|| a.byte_start.cmp(&b.byte_start))
+    });
+    let file_count = calls.chunk_by(|a, b| a.file_path == b.file_path).count();
+    if incoming {
+        println!(
+            "Calls TO \"{}\" ({} in {} files):",
+            symbol_name,
+            calls.len(),
+            file_count
+        );
     } else {
-        println!("Calls FROM \"{}\":", name);
-        for call in &calls {
-            println!(
-                "  To: {} at {}:{}",
-                call.callee,
-                call.file_path.display(),
-                call.start_line
-            );
+        println!(
+            "Calls FROM \"{}\" ({} in {} files):",
+            symbol_name,
+            calls.len(),
+            file_count
+        );
+    }
+    for group in calls.chunk_by(|a, b| a.file_path == b.file_path) {
+        println!("  {} ({})", group[0].file_path.display(), group.len());
+        for call in group {
+            let other = if incoming { &call.caller } else { &call.callee };
+            println!("    {}: {}", call.start_line, other);
         }
     }
+}
 
-    graph
-        .execution_log()
-        .finish_execution(&exec_id, "success", None, 0, 0, 0)?;
-
-    // Record output phase
-    graph.telemetry().record_phase_start(&exec_id, "output")?;
-    graph.telemetry().record_phase_end(&exec_id, "output")?;
-
-    Ok(())
+/// Build the JSON match for a call edge
+///
+/// Incoming matches name the caller and record it as `in_symbol`, the
+/// function the reference occurs in; outgoing matches name the callee.
+fn call_reference_match(call: &CallFact, span: Span, direction: &str) -> ReferenceMatch {
+    if direction == "in" || direction == "incoming" {
+        ReferenceMatch::new(
+            span,
+            call.caller.clone(),
+            Some("call".to_string()),
+            call.caller_symbol_id.clone(),
+        )
+        .with_in_symbol(EnclosingSymbol {
+            name: call.caller.clone(),
+            symbol_id: call.caller_symbol_id.clone(),
+        })
+    } else {
+        ReferenceMatch::new(
+            span,
+            call.callee.clone(),
+            Some("call".to_string()),
+            call.callee_symbol_id.clone(),
+        )
+    }
 }
 
 /// Output refs results in JSON format
@@ -450,7 +560,7 @@ pub fn run_refs(
     reason = "JSON output needs all query parameters"
 )]
 fn output_json_mode(
-    _db_path: &Path,
+    db_path: &Path,
     symbol_name: &str,
     file_path: &str,
     direction: &str,
@@ -462,7 +572,16 @@ fn output_json_mode(
     with_checksums: bool,
     context_lines: usize,
     tokens: Option<usize>,
+    group_by_file: bool,
+    column_unit: ColumnUnit,
 ) -> Result<()> {
+    // content_sha256 comes from the stored chunks when a span has one
+    let checksum_graph = if with_checksums {
+        Some(open_graph_for_read(db_path)?)
+    } else {
+        None
+    };
+
     // Sort deterministically: by file_path, byte_start
     calls.sort_by(|a, b| {
         a.file_path
@@ -513,8 +632,8 @@ fn output_json_mode(
                         }
 
                         // Add checksums if requested
-                        if with_checksums {
-                            let checksums = SpanChecksums::compute(
+                        if let Some(graph) = &checksum_graph {
+                            let checksums = graph.span_checksums(
                                 call.file_path.to_string_lossy().as_ref(),
                                 call.byte_start,
                                 call.byte_end,
@@ -522,19 +641,7 @@ fn output_json_mode(
                             span = span.with_checksums(checksums);
                         }
 
-                        let (referenced_symbol, target_symbol_id) =
-                            if direction == "in" || direction == "incoming" {
-                                (call.caller.clone(), call.caller_symbol_id.clone())
-                            } else {
-                                (call.callee.clone(), call.callee_symbol_id.clone())
-                            };
-
-                        ReferenceMatch::new(
-                            span,
-                            referenced_symbol,
-                            Some("call".to_string()),
-                            target_symbol_id,
-                        )
+                        call_reference_match(call, span, direction)
                     })
                     .collect();
 
@@ -544,6 +651,7 @@ fn output_json_mode(
                     symbol_name: symbol_name.to_string(),
                     file_path: file_path.to_string(),
                     direction: direction.to_string(),
+                    column_unit: column_unit.as_str().to_string(),
                 };
                 let test_json = serde_json::to_string(&test_response).unwrap_or_default();
                 let tokens_est = test_json.len() / 4;
@@ -603,8 +711,8 @@ fn output_json_mode(
                     }
 
                     // Add checksums if requested
-                    if with_checksums {
-                        let checksums = SpanChecksums::compute(
+                    if let Some(graph) = &checksum_graph {
+                        let checksums = graph.span_checksums(
                             call.file_path.to_string_lossy().as_ref(),
                             call.byte_start,
                             call.byte_end,
@@ -612,19 +720,7 @@ fn output_json_mode(
                         span = span.with_checksums(checksums);
                     }
 
-                    let (referenced_symbol, target_symbol_id) =
-                        if direction == "in" || direction == "incoming" {
-                            (call.caller.clone(), call.caller_symbol_id)
-                        } else {
-                            (call.callee.clone(), call.callee_symbol_id)
-                        };
-
-                    ReferenceMatch::new(
-                        span,
-                        referenced_symbol,
-                        Some("call".to_string()),
-                        target_symbol_id,
-                    )
+                    call_reference_match(&call, span, direction)
                 })
                 .collect();
 
@@ -667,8 +763,8 @@ fn output_json_mode(
                         }
 
                         // Add checksums if requested
-                        if with_checksums {
-                            let checksums = SpanChecksums::compute(
+                        if let Some(graph) = &checksum_graph {
+                            let checksums = graph.span_checksums(
                                 call.file_path.to_string_lossy().as_ref(),
                                 call.byte_start,
                                 call.byte_end,
@@ -676,19 +772,7 @@ fn output_json_mode(
                             span = span.with_checksums(checksums);
                         }
 
-                        let (referenced_symbol, target_symbol_id) =
-                            if direction == "in" || direction == "incoming" {
-                                (call.caller.clone(), call.caller_symbol_id)
-                            } else {
-                                (call.callee.clone(), call.callee_symbol_id)
-                            };
-
-                        ReferenceMatch::new(
-                            span,
-                            referenced_symbol,
-                            Some("call".to_string()),
-                            target_symbol_id,
-                        )
+                        call_reference_match(&call, span, direction)
                     })
                     .collect(),
                 false,
@@ -731,8 +815,8 @@ fn output_json_mode(
                     }
 
                     // Add checksums if requested
-                    if with_checksums {
-                        let checksums = SpanChecksums::compute(
+                    if let Some(graph) = &checksum_graph {
+                        let checksums = graph.span_checksums(
                             call.file_path.to_string_lossy().as_ref(),
                             call.byte_start,
                             call.byte_end,
@@ -740,19 +824,7 @@ fn output_json_mode(
                         span = span.with_checksums(checksums);
                     }
 
-                    let (referenced_symbol, target_symbol_id) =
-                        if direction == "in" || direction == "incoming" {
-                            (call.caller.clone(), call.caller_symbol_id)
-                        } else {
-                            (call.callee.clone(), call.callee_symbol_id)
-                        };
-
-                    ReferenceMatch::new(
-                        span,
-                        referenced_symbol,
-                        Some("call".to_string()),
-                        target_symbol_id,
-                    )
+                    call_reference_match(&call, span, direction)
                 })
                 .collect(),
             false,
@@ -764,9 +836,36 @@ fn output_json_mode(
         symbol_name: symbol_name.to_string(),
         file_path: file_path.to_string(),
         direction: direction.to_string(),
+        column_unit: column_unit.as_str().to_string(),
     };
 
-    let tokens_estimated = if tokens.is_some() {
+    if group_by_file {
+        return output_refs_json(
+            RefsByFileResponse::from(response),
+            exec_id,
+            output_format,
+            tokens.is_some(),
+            was_truncated,
+        );
+    }
+    output_refs_json(
+        response,
+        exec_id,
+        output_format,
+        tokens.is_some(),
+        was_truncated,
+    )
+}
+
+/// Wrap a refs response in the JSON envelope and print it
+fn output_refs_json<T: serde::Serialize>(
+    response: T,
+    exec_id: &str,
+    output_format: OutputFormat,
+    estimate_tokens: bool,
+    was_truncated: bool,
+) -> Result<()> {
+    let tokens_estimated = if estimate_tokens {
         let json = serde_json::to_string(&response).unwrap_or_default();
         Some(json.len() / 4)
     } else {
@@ -0,0 +1,29 @@
+//! Schema command implementation
+//!
+//! Prints JSON Schema documents for the `--output json` response types.
+//! The documents are printed as-is rather than inside the `JsonResponse`
+//! envelope, so they can be fed straight to a validator.
+
+use anyhow::Result;
+use magellan::output::schema::{all_response_schemas, response_schema};
+use magellan::output::OutputFormat;
+
+/// Run the schema command
+///
+/// # Arguments
+/// * `type_name` - Response type to print (e.g. "status"); all types when `None`
+/// * `output_format` - `Json` prints compact JSON; `Human` and `Pretty` indent it
+pub fn run_schema(type_name: Option<String>, output_format: OutputFormat) -> Result<()> {
+    let document = match type_name {
+        Some(name) => response_schema(&name)?
+            .ok_or_else(|| anyhow::anyhow!("Unknown schema type '{}'", name))?,
+        None => all_response_schemas()?,
+    };
+
+    let rendered = match output_format {
+        OutputFormat::Json => serde_json::to_string(&document)?,
+        OutputFormat::Human | OutputFormat::Pretty => serde_json::to_string_pretty(&document)?,
+    };
+    println!("{}", rendered);
+    Ok(())
+}
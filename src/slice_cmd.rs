@@ -3,9 +3,12 @@
 //! Shows program slices (backward/forward) for bug isolation and refactoring safety.
 
 use anyhow::Result;
-use magellan::output::command::{SliceResponse, SliceStats, Span, SymbolMatch};
+use magellan::graph::query;
+use magellan::output::command::{
+    SliceCode, SliceResponse, SliceStats, SliceSymbolCode, Span, SymbolMatch,
+};
 use magellan::output::{output_json, JsonResponse, OutputFormat};
-use magellan::CodeGraph;
+use magellan::{CodeGraph, SymbolInfo};
 use std::path::{Path, PathBuf};
 
 /// Resolved target information
@@ -176,12 +179,73 @@ fn resolve_target_sqlite(graph: &mut CodeGraph, target: &str) -> Result<Resolved
     ))
 }
 
+/// Stored chunks for `slice --with-code`, in the given order
+///
+/// Chunks are added until the next one would exceed `max_bytes`; that
+/// symbol and every later one with a chunk are reported as `over_budget`,
+/// so the included code is always a prefix of the slice. Symbols without a
+/// stored chunk are reported as `missing` and do not use the budget.
+fn collect_slice_code(
+    graph: &mut CodeGraph,
+    symbols: &[&SymbolInfo],
+    max_bytes: Option<usize>,
+) -> Result<SliceCode> {
+    let mut entries = Vec::with_capacity(symbols.len());
+    let mut total_bytes = 0;
+    let mut exhausted = false;
+
+    for symbol in symbols {
+        let location = match symbol.symbol_id.as_deref() {
+            Some(id) => query::find_symbol_location_by_id(graph, id)?,
+            None => None,
+        };
+        let chunk = match location {
+            Some((file_path, node)) => {
+                graph.get_code_chunk_by_span(&file_path, node.byte_start, node.byte_end)?
+            }
+            None => None,
+        };
+
+        let (status, content) = match chunk {
+            None => ("missing", None),
+            Some(chunk) => {
+                let len = chunk.content.len();
+                exhausted = exhausted || max_bytes.is_some_and(|max| total_bytes + len > max);
+                if exhausted {
+                    ("over_budget", None)
+                } else {
+                    total_bytes += len;
+                    ("included", Some(chunk.content))
+                }
+            }
+        };
+        entries.push(SliceSymbolCode {
+            fqn: symbol.fqn.clone().unwrap_or_else(|| "?".to_string()),
+            file_path: symbol.file_path.clone(),
+            symbol_id: symbol.symbol_id.clone(),
+            status: status.to_string(),
+            content,
+        });
+    }
+
+    Ok(SliceCode {
+        max_bytes,
+        total_bytes,
+        symbols: entries,
+    })
+}
+
 /// Run the slice command
+///
+/// With `with_code`, the stored chunk of the target and of every sliced
+/// symbol is attached, bounded by `max_bytes`.
 pub fn run_slice(
     db_path: PathBuf,
     target: String,
     direction: CliSliceDirection,
     verbose: bool,
+    with_code: bool,
+    max_bytes: Option<usize>,
     output_format: OutputFormat,
 ) -> Result<()> {
     let mut args = vec![
@@ -194,6 +258,13 @@ pub fn run_slice(
     if verbose {
         args.push("--verbose".to_string());
     }
+    if with_code {
+        args.push("--with-code".to_string());
+    }
+    if let Some(max) = max_bytes {
+        args.push("--max-bytes".to_string());
+        args.push(max.to_string());
+    }
 
     let mut graph = CodeGraph::open(&db_path)?;
     let exec_id = magellan::output::generate_execution_id();
@@ -267,11 +338,12 @@ pub fn run_slice(
         .telemetry()
         .record_phase_end(&exec_id, "compute_slice")?;
 
-    let target_info = magellan::graph::SymbolInfo {
+    let target_info = SymbolInfo {
         symbol_id: Some(resolved.symbol_id.clone()),
         fqn: Some(resolved.fqn.clone()),
         kind: resolved.kind.clone(),
         file_path: resolved.file_path.clone(),
+        is_test: false,
     };
 
     let slice_result = magellan::graph::SliceResult {
@@ -291,6 +363,15 @@ pub fn run_slice(
         },
     };
 
+    let code = if with_code {
+        let symbols: Vec<&SymbolInfo> = std::iter::once(&target_info)
+            .chain(&slice_result.slice.included_symbols)
+            .collect();
+        Some(collect_slice_code(&mut graph, &symbols, max_bytes)?)
+    } else {
+        None
+    };
+
     if output_format == OutputFormat::Json || output_format == OutputFormat::Pretty {
         graph
             .execution_log()
@@ -298,6 +379,7 @@ pub fn run_slice(
         return output_json_mode(
             &resolved.fqn,
             slice_result,
+            code,
             verbose,
             &exec_id,
             output_format,
@@ -347,6 +429,31 @@ pub fn run_slice(
         println!("\n  Note: Current implementation uses call-graph reachability.");
     }
 
+    if let Some(code) = code {
+        match code.max_bytes {
+            Some(max) => println!("\n  Code ({} of {} bytes):", code.total_bytes, max),
+            None => println!("\n  Code ({} bytes):", code.total_bytes),
+        }
+        for entry in &code.symbols {
+            match (entry.status.as_str(), &entry.content) {
+                ("included", Some(content)) => {
+                    println!("\n// Symbol: {} in {}", entry.fqn, entry.file_path);
+                    println!("{}", content);
+                }
+                ("over_budget", _) => {
+                    println!(
+                        "\n// Symbol: {} in {} (omitted: over --max-bytes)",
+                        entry.fqn, entry.file_path
+                    )
+                }
+                _ => println!(
+                    "\n// Symbol: {} in {} (no stored chunk)",
+                    entry.fqn, entry.file_path
+                ),
+            }
+        }
+    }
+
     graph
         .execution_log()
         .finish_execution(&exec_id, "success", None, 0, 0, 0)?;
@@ -356,6 +463,7 @@ pub fn run_slice(
 fn output_json_mode(
     _target: &str,
     slice_result: magellan::graph::SliceResult,
+    code: Option<SliceCode>,
     _verbose: bool,
     exec_id: &str,
     output_format: OutputFormat,
@@ -415,6 +523,7 @@ fn output_json_mode(
         direction,
         included_symbols,
         statistics,
+        code,
     };
 
     let json_response = JsonResponse::new(response, exec_id);
@@ -2,14 +2,20 @@
 //!
 //! Provides status query functionality and execution tracking.
 
+use crate::db_resolver::open_graph_for_read;
 use crate::service::registry::Registry;
 use anyhow::Result;
 use magellan::capabilities::capabilities_for_path;
+use magellan::graph::query::count_snapshot;
+use magellan::log_warn;
 use magellan::output::{
     generate_execution_id, output_json, CoverageInfo, JsonResponse, StatusResponse,
 };
 use magellan::{CodeGraph, OutputFormat};
 use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 /// Tracks execution metadata for logging and debugging
 pub struct ExecutionTracker {
@@ -95,7 +101,7 @@ pub fn run_status(db_path: PathBuf, output_format: OutputFormat, all: bool) -> R
 
     let backend_caps = capabilities_for_path(&db_path);
 
-    let graph = CodeGraph::open(&db_path)?;
+    let graph = open_graph_for_read(&db_path)?;
     let tracker = ExecutionTracker::new(
         vec!["status".to_string()],
         None,
@@ -120,9 +126,9 @@ pub fn run_status(db_path: PathBuf, output_format: OutputFormat, all: bool) -> R
     graph
         .telemetry()
         .record_phase_start(tracker.exec_id(), "query_coverage")?;
-    let (coverage_blocks, coverage_edges, coverage_meta) = match rusqlite::Connection::open(
-        &db_path,
-    ) {
+    let coverage_conn =
+        rusqlite::Connection::open_with_flags(&db_path, rusqlite::OpenFlags::SQLITE_OPEN_READ_ONLY);
+    let (coverage_blocks, coverage_edges, coverage_meta) = match coverage_conn {
         Ok(conn) => {
             let blocks = query_coverage_count(&conn, "cfg_block_coverage");
             let edges = query_coverage_count(&conn, "cfg_edge_coverage");
@@ -143,7 +149,7 @@ pub fn run_status(db_path: PathBuf, output_format: OutputFormat, all: bool) -> R
             (blocks, edges, meta)
         }
         Err(e) => {
-            eprintln!("Warning: could not query coverage data: {}", e);
+            log_warn!("could not query coverage data: {}", e);
             (0, 0, None)
         }
     };
@@ -257,6 +263,59 @@ pub fn run_status(db_path: PathBuf, output_format: OutputFormat, all: bool) -> R
     Ok(())
 }
 
+/// Print a count snapshot every `interval_secs` seconds until SIGINT/SIGTERM
+///
+/// Keeps one read-only connection open for the whole run and writes one
+/// compact JSON response per line, so a dashboard can follow the stream
+/// instead of re-running `status`.
+pub fn run_status_watch(db_path: PathBuf, interval_secs: u64, by_kind: bool) -> Result<()> {
+    if !db_path.exists() {
+        anyhow::bail!("Database not found: {}", db_path.display());
+    }
+
+    let graph = open_graph_for_read(&db_path)?;
+    let mut args = vec![
+        "status".to_string(),
+        "--watch".to_string(),
+        "--interval".to_string(),
+        interval_secs.to_string(),
+    ];
+    if by_kind {
+        args.push("--by-kind".to_string());
+    }
+    let tracker = ExecutionTracker::new(args, None, db_path.to_string_lossy().to_string());
+    tracker.start(&graph)?;
+
+    let shutdown = Arc::new(AtomicBool::new(false));
+    #[cfg(unix)]
+    {
+        use signal_hook::consts::signal;
+        use signal_hook::flag;
+        let _ = flag::register(signal::SIGINT, shutdown.clone())?;
+        let _ = flag::register(signal::SIGTERM, shutdown.clone())?;
+    }
+
+    let conn = rusqlite::Connection::open_with_flags(
+        &db_path,
+        rusqlite::OpenFlags::SQLITE_OPEN_READ_ONLY,
+    )?;
+    let interval = Duration::from_secs(interval_secs);
+    while !shutdown.load(Ordering::SeqCst) {
+        let snapshot = count_snapshot(&conn, by_kind)?;
+        let response = JsonResponse::new(snapshot, tracker.exec_id());
+        println!("{}", serde_json::to_string(&response)?);
+
+        // Sleep in short steps so a signal ends the run promptly
+        let next = Instant::now() + interval;
+        while Instant::now() < next && !shutdown.load(Ordering::SeqCst) {
+            std::thread::sleep(Duration::from_millis(100));
+        }
+    }
+
+    tracker.finish(&graph)?;
+    Ok(())
+}
+
 /// Run status across all enabled projects in the registry
 fn run_status_all(output_format: OutputFormat) -> Result<()> {
     let registry = Registry::load()?;
@@ -287,7 +346,7 @@ fn run_status_all(output_format: OutputFormat) -> Result<()> {
 fn query_coverage_count(conn: &rusqlite::Connection, table: &str) -> usize {
     const VALID_TABLES: &[&str] = &["cfg_block_coverage", "cfg_edge_coverage"];
     if !VALID_TABLES.contains(&table) {
-        eprintln!("Warning: invalid coverage table name: {}", table);
+        log_warn!("invalid coverage table name: {}", table);
         return 0;
     }
     let sql = format!("SELECT COUNT(*) FROM {} WHERE hit_count > 0", table);
@@ -295,7 +354,7 @@ fn query_coverage_count(conn: &rusqlite::Connection, table: &str) -> usize {
         Ok(n) => n as usize,
         Err(rusqlite::Error::SqliteFailure(_code, Some(msg))) if msg.contains("no such table") => 0,
         Err(e) => {
-            eprintln!("Warning: coverage query failed for {}: {}", table, e);
+            log_warn!("coverage query failed for {}: {}", table, e);
             0
         }
     }
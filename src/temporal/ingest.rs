@@ -5,8 +5,8 @@ use std::path::{Path, PathBuf};
 
 use crate::common::normalize_repo_relative_path;
 use crate::graph::symbols::stable_symbol_id_for_fact;
-use crate::ingest::pool;
 use crate::ingest::{detect_language, Language, Parser, SymbolFact};
+use crate::ingest::{pool, registry};
 use crate::references::CallFact;
 use crate::CodeGraph;
 
@@ -73,18 +73,23 @@ fn extract_symbols_for_source(
 ) -> Result<(Option<Language>, Option<tree_sitter::Tree>, Vec<SymbolFact>)> {
     use crate::ingest::c::CParser;
     use crate::ingest::cpp::CppParser;
+    use crate::ingest::csharp::CSharpParser;
     use crate::ingest::cuda::CudaParser;
     use crate::ingest::go::GoParser;
     use crate::ingest::java::JavaParser;
     use crate::ingest::javascript::JavaScriptParser;
     use crate::ingest::python::PythonParser;
+    use crate::ingest::scala::ScalaParser;
+    use crate::ingest::swift::SwiftParser;
     use crate::ingest::typescript::TypeScriptParser;
 
     let path_buf = file_path.to_path_buf();
     let language = detect_language(file_path);
     let parsed_tree = match language {
-        Some(lang) => pool::with_parser(lang, |parser| parser.parse(source, None))?,
-        None => None,
+        Some(lang) if lang.has_grammar() => {
+            pool::with_parser(lang, |parser| parser.parse(source, None))?
+        }
+        _ => None,
     };
 
     let symbols = match (language, &parsed_tree) {
@@ -115,6 +120,19 @@ fn extract_symbols_for_source(
         (Some(Language::Cuda), Some(tree)) => {
             CudaParser::extract_symbols_from_tree(tree, path_buf.clone(), source)
         }
+        (Some(Language::Swift), Some(tree)) => {
+            SwiftParser::extract_symbols_from_tree(tree, path_buf.clone(), source)
+        }
+        (Some(Language::Scala), Some(tree)) => {
+            ScalaParser::extract_symbols_from_tree(tree, path_buf.clone(), source)
+        }
+        (Some(Language::CSharp), Some(tree)) => {
+            CSharpParser::extract_symbols_from_tree(tree, path_buf.clone(), source)
+        }
+        // Languages without a grammar come from a registered parser instead
+        (Some(lang), None) if !lang.has_grammar() => registry::parser_for_language(lang)
+            .map(|parser| parser.extract(&path_buf, source))
+            .unwrap_or_default(),
         _ => Vec::new(),
     };
 
@@ -130,11 +148,14 @@ fn extract_calls_for_source(
 ) -> Vec<CallFact> {
     use crate::ingest::c::CParser;
     use crate::ingest::cpp::CppParser;
+    use crate::ingest::csharp::CSharpParser;
     use crate::ingest::cuda::CudaParser;
     use crate::ingest::go::GoParser;
     use crate::ingest::java::JavaParser;
     use crate::ingest::javascript::JavaScriptParser;
     use crate::ingest::python::PythonParser;
+    use crate::ingest::scala::ScalaParser;
+    use crate::ingest::swift::SwiftParser;
     use crate::ingest::typescript::TypeScriptParser;
 
     let path_buf = file_path.to_path_buf();
@@ -152,6 +173,10 @@ fn extract_calls_for_source(
         }
         Language::Go => GoParser::extract_calls_from_tree(tree, path_buf, source, symbols),
         Language::Cuda => CudaParser::extract_calls_from_tree(tree, path_buf, source, symbols),
+        Language::Swift => SwiftParser::extract_calls_from_tree(tree, path_buf, source, symbols),
+        Language::Scala => ScalaParser::extract_calls_from_tree(tree, path_buf, source, symbols),
+        Language::CSharp => CSharpParser::extract_calls_from_tree(tree, path_buf, source, symbols),
+        Language::Custom(_) => Vec::new(),
     }
 }
 
@@ -0,0 +1,83 @@
+//! Timings command implementation
+//!
+//! Lists the files that took longest to parse and index, based on the
+//! per-file wall time recorded by `index_file`.
+
+use anyhow::Result;
+use magellan::output::{
+    generate_execution_id, output_json, FileTimingEntry, JsonResponse, OutputFormat,
+    TimingsResponse,
+};
+use magellan::CodeGraph;
+use std::path::PathBuf;
+
+/// Run the timings command
+///
+/// # Arguments
+/// * `db_path` - Path to the sqlitegraph database
+/// * `top` - Number of slowest files to list
+/// * `output_format` - Output format (Human, Json, or Pretty)
+pub fn run_timings(db_path: PathBuf, top: usize, output_format: OutputFormat) -> Result<()> {
+    let graph = CodeGraph::open(&db_path)?;
+    let exec_id = generate_execution_id();
+
+    let args = vec![
+        "timings".to_string(),
+        "--db".to_string(),
+        db_path.to_string_lossy().to_string(),
+        "--top".to_string(),
+        top.to_string(),
+    ];
+    graph.execution_log().start_execution(
+        &exec_id,
+        env!("CARGO_PKG_VERSION"),
+        &args,
+        None,
+        &db_path.to_string_lossy(),
+    )?;
+
+    // Phase: query_timings
+    graph
+        .telemetry()
+        .record_phase_start(&exec_id, "query_timings")?;
+
+    let timings = graph.metrics().get_slowest_files(top)?;
+
+    graph
+        .telemetry()
+        .record_phase_end(&exec_id, "query_timings")?;
+
+    match output_format {
+        OutputFormat::Json | OutputFormat::Pretty => {
+            let response = TimingsResponse {
+                top,
+                files: timings
+                    .into_iter()
+                    .map(|timing| FileTimingEntry {
+                        file_path: timing.file_path,
+                        index_ms: timing.index_ms,
+                        last_updated: timing.last_updated,
+                    })
+                    .collect(),
+            };
+            let json_response = JsonResponse::new(response, &exec_id);
+            output_json(&json_response, output_format)?;
+        }
+        OutputFormat::Human => {
+            if timings.is_empty() {
+                println!("No indexing timings recorded (re-index files to collect them)");
+            } else {
+                println!("Slowest {} file(s) by index time:", timings.len());
+                for timing in &timings {
+                    println!("  {:>8} ms  {}", timing.index_ms, timing.file_path);
+                }
+            }
+        }
+    }
+
+    graph
+        .execution_log()
+        .finish_execution(&exec_id, "success", None, 0, 0, 0)?;
+
+    Ok(())
+}
@@ -5,6 +5,7 @@
 
 use anyhow::Result;
 use camino::Utf8Path;
+use std::collections::HashSet;
 use std::path::{Path, PathBuf};
 
 /// Error types for path validation.
@@ -240,6 +241,43 @@ pub fn is_safe_symlink(symlink_path: &Path, root: &Path) -> Result<bool, PathVal
     }
 }
 
+/// Directories already entered during a symlink-following walk.
+///
+/// Keyed by device and inode on Unix (canonical path elsewhere), so a symlink
+/// cycle that re-enters an ancestor is caught regardless of the link name used
+/// to reach it.
+#[derive(Debug, Default)]
+pub struct VisitedDirs {
+    #[cfg(unix)]
+    seen: HashSet<(u64, u64)>,
+    #[cfg(not(unix))]
+    seen: HashSet<PathBuf>,
+}
+
+impl VisitedDirs {
+    /// Record a directory visit.
+    ///
+    /// Returns `false` if the directory was already visited or cannot be
+    /// inspected, in which case the walk should not descend into it.
+    pub fn insert(&mut self, dir: &Path) -> bool {
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::MetadataExt;
+            match std::fs::metadata(dir) {
+                Ok(meta) => self.seen.insert((meta.dev(), meta.ino())),
+                Err(_) => false,
+            }
+        }
+        #[cfg(not(unix))]
+        {
+            match std::fs::canonicalize(dir) {
+                Ok(canonical) => self.seen.insert(canonical),
+                Err(_) => false,
+            }
+        }
+    }
+}
+
 /// Validate a UTF-8 path using camino's Utf8Path.
 ///
 /// This is a convenience wrapper for UTF-8 path handling.
@@ -1,15 +1,19 @@
 //! Database verification module
 //!
-//! Compares database state vs filesystem to detect inconsistencies.
+//! Compares database state vs filesystem to detect inconsistencies, and
+//! checks that stable symbol IDs are reproducible from unchanged sources.
 
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::hash::Hasher;
 use std::path::{Path, PathBuf};
 use walkdir::WalkDir;
 
+use crate::graph::query;
+use crate::graph::schema::{DanglingEdge, OrphanSymbol};
 use crate::graph::{CodeGraph, FileNode};
+use crate::CodeChunk;
 
 /// Report of database verification results
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -36,6 +40,43 @@ impl VerifyReport {
     }
 }
 
+/// Report of database self-consistency checks
+///
+/// Independent of the filesystem: only the database file is inspected.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IntegrityReport {
+    /// Problems reported by `PRAGMA integrity_check`
+    pub sqlite_errors: Vec<String>,
+    /// Symbols whose owning File node is missing
+    pub orphan_symbols: Vec<OrphanSymbol>,
+    /// CALLS/CALLER edges pointing at entities that do not exist
+    pub dangling_edges: Vec<DanglingEdge>,
+}
+
+impl IntegrityReport {
+    /// Total number of integrity problems found
+    pub fn total_issues(&self) -> usize {
+        self.sqlite_errors.len() + self.orphan_symbols.len() + self.dangling_edges.len()
+    }
+
+    /// Check if the database is self-consistent
+    pub fn is_clean(&self) -> bool {
+        self.total_issues() == 0
+    }
+}
+
+/// Check the database for corruption and dangling references
+///
+/// Runs SQLite's `PRAGMA integrity_check`, then confirms that every Symbol
+/// node still has its File node and every call edge resolves at both ends.
+pub fn verify_integrity(graph: &CodeGraph) -> Result<IntegrityReport> {
+    Ok(IntegrityReport {
+        sqlite_errors: query::sqlite_integrity_errors(graph)?,
+        orphan_symbols: query::orphan_symbols(graph)?,
+        dangling_edges: query::dangling_call_edges(graph)?,
+    })
+}
+
 /// Staleness threshold in seconds (default: 5 minutes)
 const STALE_THRESHOLD_SECS: i64 = 300;
 
@@ -56,8 +97,8 @@ pub fn verify_graph(graph: &mut CodeGraph, root: &Path) -> Result<VerifyReport>
     // Get all file paths from the database
     let db_files = get_all_db_files(graph)?;
 
-    // Get all .rs file paths from the filesystem
-    let fs_files = get_all_fs_files(root)?;
+    // Get all .rs file paths from the filesystem, plus any other indexed file
+    let fs_files = get_all_fs_files(graph, root, &db_files)?;
 
     // Convert fs paths to strings for comparison
     let fs_paths: HashSet<String> = fs_files
@@ -120,13 +161,284 @@ pub fn verify_graph(graph: &mut CodeGraph, root: &Path) -> Result<VerifyReport>
     })
 }
 
+/// A symbol whose stable ID differs between the live database and a reindex
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SymbolIdChange {
+    /// Symbol FQN (canonical_fqn where available)
+    pub fqn: String,
+    /// IDs stored in the live database
+    pub live_ids: Vec<String>,
+    /// IDs produced by reindexing the same content
+    pub reindexed_ids: Vec<String>,
+}
+
+/// Report of symbol ID stability verification
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IdVerifyReport {
+    /// Files reindexed (content unchanged since the live index)
+    pub files_checked: usize,
+    /// Files skipped because their content changed since the live index
+    pub skipped_modified: Vec<String>,
+    /// Symbols compared (FQNs present in both databases)
+    pub symbols_checked: usize,
+    /// FQNs whose stable IDs differ
+    pub changed: Vec<SymbolIdChange>,
+    /// FQNs only in the live database
+    pub only_live: Vec<String>,
+    /// FQNs only in the reindexed database
+    pub only_reindexed: Vec<String>,
+}
+
+impl IdVerifyReport {
+    /// Check if all stable IDs were reproduced
+    pub fn is_stable(&self) -> bool {
+        self.changed.is_empty() && self.only_live.is_empty() && self.only_reindexed.is_empty()
+    }
+}
+
+/// Verify that stable symbol IDs are reproducible from unchanged sources
+///
+/// Reindexes every live-database file under `root` whose content hash still
+/// matches into a temporary database, using the same path spelling, then
+/// diffs the FQN-to-ID mapping against the live database. Since a stable ID
+/// is derived only from language, repo-relative path, kind, and FQN, any
+/// difference indicates an ID that will not survive a reindex.
+///
+/// # Arguments
+/// * `graph` - Live CodeGraph to check
+/// * `root` - Root directory whose files are reindexed
+///
+/// # Returns
+/// IdVerifyReport listing FQNs whose IDs changed
+pub fn verify_symbol_ids(graph: &mut CodeGraph, root: &Path) -> Result<IdVerifyReport> {
+    let root = std::fs::canonicalize(root).unwrap_or_else(|_| root.to_path_buf());
+    let temp_dir = tempfile::TempDir::new()?;
+    let mut fresh = CodeGraph::open(temp_dir.path().join("verify-ids.db"))?;
+
+    let mut db_files: Vec<(String, FileNode)> = get_all_db_files(graph)?.into_iter().collect();
+    db_files.sort_by(|a, b| a.0.cmp(&b.0));
+
+    let mut checked_paths: Vec<String> = Vec::new();
+    let mut skipped_modified = Vec::new();
+    for (path, node) in &db_files {
+        let Ok(resolved) = std::fs::canonicalize(path) else {
+            continue;
+        };
+        if !resolved.starts_with(&root) {
+            continue;
+        }
+        let Ok(content) = std::fs::read(&resolved) else {
+            continue;
+        };
+        if graph.compute_content_hash_with(&content, node.hash_algo) != node.hash {
+            skipped_modified.push(path.clone());
+            continue;
+        }
+        fresh.index_file(path, &content)?;
+        checked_paths.push(path.clone());
+    }
+
+    // Only compare symbols from files that were reindexed
+    let checked: HashSet<&str> = checked_paths.iter().map(String::as_str).collect();
+    let live = graph
+        .symbol_id_map()?
+        .into_iter()
+        .filter(|(fqn, _)| canonical_fqn_file(fqn).is_some_and(|path| checked.contains(path)))
+        .collect();
+    let reindexed = fresh.symbol_id_map()?;
+
+    let mut report = diff_symbol_id_maps(&live, &reindexed);
+    report.files_checked = checked_paths.len();
+    report.skipped_modified = skipped_modified;
+    Ok(report)
+}
+
+/// Extract the file path from a canonical FQN (`crate::file_path::Kind name`)
+fn canonical_fqn_file(fqn: &str) -> Option<&str> {
+    let (_, rest) = fqn.split_once("::")?;
+    let (path, _) = rest.rsplit_once("::")?;
+    Some(path)
+}
+
+/// Diff two FQN-to-ID maps
+///
+/// Returns a report with `files_checked` and `skipped_modified` left empty.
+pub fn diff_symbol_id_maps(
+    live: &BTreeMap<String, Vec<String>>,
+    reindexed: &BTreeMap<String, Vec<String>>,
+) -> IdVerifyReport {
+    let mut changed = Vec::new();
+    let mut only_live = Vec::new();
+    let mut symbols_checked = 0;
+
+    for (fqn, live_ids) in live {
+        match reindexed.get(fqn) {
+            Some(reindexed_ids) => {
+                symbols_checked += 1;
+                if live_ids != reindexed_ids {
+                    changed.push(SymbolIdChange {
+                        fqn: fqn.clone(),
+                        live_ids: live_ids.clone(),
+                        reindexed_ids: reindexed_ids.clone(),
+                    });
+                }
+            }
+            None => only_live.push(fqn.clone()),
+        }
+    }
+
+    let only_reindexed = reindexed
+        .keys()
+        .filter(|fqn| !live.contains_key(*fqn))
+        .cloned()
+        .collect();
+
+    IdVerifyReport {
+        files_checked: 0,
+        skipped_modified: Vec::new(),
+        symbols_checked,
+        changed,
+        only_live,
+        only_reindexed,
+    }
+}
+
+/// Why a stored chunk no longer matches its source file
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChunkDriftKind {
+    /// The span on disk hashes differently from the stored `content_hash`
+    HashMismatch,
+    /// The span on disk matches `content_hash`, but the stored content does not
+    ContentMismatch,
+    /// The span is past the end of the file or does not start on a UTF-8 boundary
+    SpanUnreadable,
+}
+
+impl ChunkDriftKind {
+    /// Short description for human output
+    pub fn describe(self) -> &'static str {
+        match self {
+            Self::HashMismatch => "content_hash differs from disk",
+            Self::ContentMismatch => "stored content differs from disk",
+            Self::SpanUnreadable => "span no longer readable on disk",
+        }
+    }
+}
+
+/// A stored chunk whose content drifted from the file on disk
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChunkDrift {
+    /// Row ID in `code_chunks`
+    pub chunk_id: Option<i64>,
+    /// File path as stored
+    pub file_path: String,
+    pub byte_start: usize,
+    pub byte_end: usize,
+    pub symbol_name: Option<String>,
+    pub kind: ChunkDriftKind,
+    /// `content_hash` recorded for the chunk
+    pub stored_hash: String,
+    /// SHA-256 of the span as it is on disk now, when readable
+    pub disk_hash: Option<String>,
+}
+
+/// Report of stored chunks checked against their files (`chunks --verify`)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ChunkVerifyReport {
+    /// Chunks whose file exists and was compared
+    pub chunks_checked: usize,
+    /// Files read from disk
+    pub files_checked: usize,
+    /// Stored files that no longer exist (their chunks are not checked)
+    pub files_missing: Vec<String>,
+    /// Chunks that differ from the file on disk
+    pub drifted: Vec<ChunkDrift>,
+}
+
+impl ChunkVerifyReport {
+    /// Whether every checked chunk matches its file
+    pub fn is_clean(&self) -> bool {
+        self.drifted.is_empty()
+    }
+}
+
+/// Compare every stored chunk with the same byte span of its file on disk
+///
+/// The span is extracted the way indexing extracts it
+/// (`extract_symbol_content_safe`) and hashed with SHA-256, the chunk hash.
+/// A span that differs from `content_hash` means the file changed without the
+/// chunks being re-stored; stored content that does not hash to
+/// `content_hash` means the chunk row itself is corrupt. Relative stored
+/// paths resolve against the database's path root, or `root` when none is
+/// recorded. Chunks of files that no longer exist are skipped.
+pub fn verify_chunks(graph: &CodeGraph, root: &Path) -> Result<ChunkVerifyReport> {
+    let mut report = ChunkVerifyReport::default();
+    let mut sources: HashMap<String, Option<Vec<u8>>> = HashMap::new();
+
+    for chunk in graph.all_code_chunks()? {
+        let source = sources.entry(chunk.file_path.clone()).or_insert_with(|| {
+            let disk_path = graph.disk_path(&chunk.file_path);
+            let disk_path = if disk_path.is_relative() {
+                root.join(disk_path)
+            } else {
+                disk_path
+            };
+            std::fs::read(disk_path).ok()
+        });
+        let Some(source) = source else {
+            continue;
+        };
+        report.chunks_checked += 1;
+
+        let disk_hash =
+            crate::common::extract_symbol_content_safe(source, chunk.byte_start, chunk.byte_end)
+                .map(|content| CodeChunk::compute_hash(&content));
+        let kind = match &disk_hash {
+            None => ChunkDriftKind::SpanUnreadable,
+            Some(hash) if *hash != chunk.content_hash => ChunkDriftKind::HashMismatch,
+            Some(_) if CodeChunk::compute_hash(&chunk.content) != chunk.content_hash => {
+                ChunkDriftKind::ContentMismatch
+            }
+            Some(_) => continue,
+        };
+        report.drifted.push(ChunkDrift {
+            chunk_id: chunk.id,
+            file_path: chunk.file_path,
+            byte_start: chunk.byte_start,
+            byte_end: chunk.byte_end,
+            symbol_name: chunk.symbol_name,
+            kind,
+            stored_hash: chunk.content_hash,
+            disk_hash,
+        });
+    }
+
+    for (path, source) in sources {
+        match source {
+            Some(_) => report.files_checked += 1,
+            None => report.files_missing.push(path),
+        }
+    }
+    report.files_missing.sort();
+    Ok(report)
+}
+
 /// Get all files from the database as a map of path -> FileNode
 fn get_all_db_files(graph: &mut CodeGraph) -> Result<HashMap<String, FileNode>> {
     graph.all_file_nodes()
 }
 
 /// Get all .rs files from filesystem as a map of path -> hash
-fn get_all_fs_files(root: &Path) -> Result<HashMap<PathBuf, String>> {
+///
+/// Files already in the database are hashed with the algorithm their File
+/// node is tagged with, so the hashes compare equal when the content has not
+/// changed; new files use the database's current algorithm.
+fn get_all_fs_files(
+    graph: &CodeGraph,
+    root: &Path,
+    db_files: &HashMap<String, FileNode>,
+) -> Result<HashMap<PathBuf, String>> {
     let mut result = HashMap::new();
 
     for entry in WalkDir::new(root)
@@ -135,7 +447,9 @@ fn get_all_fs_files(root: &Path) -> Result<HashMap<PathBuf, String>> {
         .filter_map(std::result::Result::ok)
     {
         let path = entry.path();
-        if path.extension().and_then(|s| s.to_str()) == Some("rs") {
+        // Files indexed by `watch --index-non-source` are tracked whatever their extension
+        let tracked = db_files.contains_key(path.to_string_lossy().as_ref());
+        if tracked || path.extension().and_then(|s| s.to_str()) == Some("rs") {
             // Skip database files
             if let Some(file_name) = path.file_name() {
                 let file_name_str = file_name.to_string_lossy();
@@ -146,7 +460,10 @@ fn get_all_fs_files(root: &Path) -> Result<HashMap<PathBuf, String>> {
 
             // Read file and compute hash
             if let Ok(content) = std::fs::read(path) {
-                let hash = compute_hash(&content);
+                let hash = match db_files.get(path.to_string_lossy().as_ref()) {
+                    Some(node) => graph.compute_content_hash_with(&content, node.hash_algo),
+                    None => graph.compute_content_hash(&content),
+                };
                 result.insert(path.to_path_buf(), hash);
             }
         }
@@ -155,14 +472,6 @@ fn get_all_fs_files(root: &Path) -> Result<HashMap<PathBuf, String>> {
     Ok(result)
 }
 
-/// Compute xxHash64 of file contents (must match `files::FileOps::compute_hash`)
-fn compute_hash(content: &[u8]) -> String {
-    use xxhash_rust::xxh3::Xxh3;
-    let mut hasher = Xxh3::new();
-    hasher.update(content);
-    format!("{:032x}", hasher.digest())
-}
-
 /// Get current Unix timestamp in seconds
 ///
 /// Returns 0 if system clock is set before UNIX epoch (e.g., VM snapshots,
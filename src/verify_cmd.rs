@@ -4,56 +4,128 @@ use anyhow::Result;
 use std::path::PathBuf;
 
 use crate::{generate_execution_id, CodeGraph};
+use magellan::graph::schema::SpanOverlap;
 use magellan::output::command::OutputFormat;
+use magellan::verify::{IntegrityReport, VerifyReport};
 
-pub fn run_verify(root_path: PathBuf, db_path: PathBuf, output_format: OutputFormat) -> Result<u8> {
+pub fn run_verify(
+    root_path: Option<PathBuf>,
+    db_path: PathBuf,
+    integrity: bool,
+    overlaps: bool,
+    output_format: OutputFormat,
+) -> Result<u8> {
     // Build args for execution tracking
-    let args = vec![
-        "verify".to_string(),
-        "--root".to_string(),
-        root_path.to_string_lossy().to_string(),
-        "--db".to_string(),
-        db_path.to_string_lossy().to_string(),
-    ];
+    let mut args = vec!["verify".to_string()];
+    if let Some(ref root) = root_path {
+        args.push("--root".to_string());
+        args.push(root.to_string_lossy().to_string());
+    }
+    args.push("--db".to_string());
+    args.push(db_path.to_string_lossy().to_string());
+    if integrity {
+        args.push("--integrity".to_string());
+    }
+    if overlaps {
+        args.push("--overlaps".to_string());
+    }
 
     let graph = CodeGraph::open(&db_path)?;
     let exec_id = generate_execution_id();
-    let root_str = root_path.to_string_lossy().to_string();
+    let root_str = root_path
+        .as_ref()
+        .map(|root| root.to_string_lossy().to_string());
     let db_path_str = db_path.to_string_lossy().to_string();
 
     graph.execution_log().start_execution(
         &exec_id,
         env!("CARGO_PKG_VERSION"),
         &args,
-        Some(&root_str),
+        root_str.as_deref(),
         &db_path_str,
     )?;
 
     // Phase: verify_graph
-    graph
-        .telemetry()
-        .record_phase_start(&exec_id, "verify_graph")?;
-    let mut graph_mut = CodeGraph::open(&db_path)?;
-    let report = magellan::verify::verify_graph(&mut graph_mut, &root_path)?;
-    graph
-        .telemetry()
-        .record_phase_end(&exec_id, "verify_graph")?;
+    let report = match root_path {
+        Some(ref root) => {
+            graph
+                .telemetry()
+                .record_phase_start(&exec_id, "verify_graph")?;
+            let mut graph_mut = CodeGraph::open(&db_path)?;
+            let report = magellan::verify::verify_graph(&mut graph_mut, root)?;
+            graph
+                .telemetry()
+                .record_phase_end(&exec_id, "verify_graph")?;
+            Some(report)
+        }
+        None => None,
+    };
+
+    // Phase: verify_integrity
+    let integrity_report = if integrity {
+        graph
+            .telemetry()
+            .record_phase_start(&exec_id, "verify_integrity")?;
+        let integrity_report = magellan::verify::verify_integrity(&graph)?;
+        graph
+            .telemetry()
+            .record_phase_end(&exec_id, "verify_integrity")?;
+        Some(integrity_report)
+    } else {
+        None
+    };
+
+    // Phase: verify_overlaps
+    let span_overlaps = if overlaps {
+        graph
+            .telemetry()
+            .record_phase_start(&exec_id, "verify_overlaps")?;
+        let span_overlaps = graph.overlapping_symbol_spans()?;
+        graph
+            .telemetry()
+            .record_phase_end(&exec_id, "verify_overlaps")?;
+        Some(span_overlaps)
+    } else {
+        None
+    };
+
+    let is_clean = report.as_ref().is_none_or(VerifyReport::is_clean)
+        && integrity_report
+            .as_ref()
+            .is_none_or(IntegrityReport::is_clean)
+        && span_overlaps.as_ref().is_none_or(Vec::is_empty);
+    let exit_code: u8 = if is_clean { 0 } else { 1 };
 
     match output_format {
         OutputFormat::Json | OutputFormat::Pretty => {
+            let mut data = serde_json::json!({
+                "root_path": root_str,
+                "db_path": db_path.to_string_lossy(),
+                "is_clean": is_clean,
+            });
+            if let Some(ref report) = report {
+                data["missing"] = serde_json::json!(report.missing);
+                data["new"] = serde_json::json!(report.new);
+                data["modified"] = serde_json::json!(report.modified);
+                data["stale"] = serde_json::json!(report.stale);
+                data["total_issues"] = serde_json::json!(report.total_issues());
+            }
+            if let Some(ref integrity_report) = integrity_report {
+                data["integrity"] = serde_json::json!({
+                    "sqlite_errors": integrity_report.sqlite_errors,
+                    "orphan_symbols": integrity_report.orphan_symbols,
+                    "dangling_edges": integrity_report.dangling_edges,
+                    "is_clean": integrity_report.is_clean(),
+                    "total_issues": integrity_report.total_issues(),
+                });
+            }
+            if let Some(ref span_overlaps) = span_overlaps {
+                data["overlaps"] = serde_json::json!(span_overlaps);
+            }
             let response = serde_json::json!({
                 "schema_version": "1.0.0",
                 "execution_id": &exec_id,
-                "data": {
-                    "root_path": root_path.to_string_lossy(),
-                    "db_path": db_path.to_string_lossy(),
-                    "missing": report.missing,
-                    "new": report.new,
-                    "modified": report.modified,
-                    "stale": report.stale,
-                    "is_clean": report.is_clean(),
-                    "total_issues": report.total_issues(),
-                },
+                "data": data,
                 "tool": "magellan",
                 "timestamp": chrono::Utc::now().to_rfc3339(),
             });
@@ -62,57 +134,134 @@ pub fn run_verify(root_path: PathBuf, db_path: PathBuf, output_format: OutputFor
                 _ => serde_json::to_string(&response)?,
             };
             println!("{json_str}");
-            let exit_code: u8 = if report.is_clean() { 0 } else { 1 };
-            graph
-                .execution_log()
-                .finish_execution(&exec_id, "success", None, 0, 0, 0)?;
-            Ok(exit_code)
         }
         OutputFormat::Human => {
-            let root_str = root_path.to_string_lossy();
-            println!("Database verification: {}", root_str);
-
-            if !report.missing.is_empty() {
-                println!("Missing files ({}):", report.missing.len());
-                for path in &report.missing {
-                    println!("  - {}", path);
-                }
+            if let (Some(root), Some(report)) = (&root_str, &report) {
+                print_verify_report(root, report);
             }
-
-            if !report.new.is_empty() {
-                println!("New files ({}):", report.new.len());
-                for path in &report.new {
-                    println!("  + {}", path);
-                }
+            if let Some(ref integrity_report) = integrity_report {
+                print_integrity_report(&db_path_str, integrity_report);
             }
-
-            if !report.modified.is_empty() {
-                println!("Modified files ({}):", report.modified.len());
-                for path in &report.modified {
-                    println!("  ~ {}", path);
-                }
+            if let Some(ref span_overlaps) = span_overlaps {
+                print_span_overlaps(span_overlaps);
             }
+        }
+    }
 
-            if !report.stale.is_empty() {
-                println!("Stale files ({}):", report.stale.len());
-                for path in &report.stale {
-                    println!("  ? {}", path);
-                }
-            }
+    graph
+        .execution_log()
+        .finish_execution(&exec_id, "success", None, 0, 0, 0)?;
 
-            let exit_code = if report.is_clean() {
-                println!("All files up to date.");
-                0u8
-            } else {
-                println!("Total: {} issues", report.total_issues());
-                1u8
-            };
+    Ok(exit_code)
+}
 
-            graph
-                .execution_log()
-                .finish_execution(&exec_id, "success", None, 0, 0, 0)?;
+fn print_verify_report(root: &str, report: &VerifyReport) {
+    println!("Database verification: {}", root);
+
+    if !report.missing.is_empty() {
+        println!("Missing files ({}):", report.missing.len());
+        for path in &report.missing {
+            println!("  - {}", path);
+        }
+    }
 
-            Ok(exit_code)
+    if !report.new.is_empty() {
+        println!("New files ({}):", report.new.len());
+        for path in &report.new {
+            println!("  + {}", path);
         }
     }
+
+    if !report.modified.is_empty() {
+        println!("Modified files ({}):", report.modified.len());
+        for path in &report.modified {
+            println!("  ~ {}", path);
+        }
+    }
+
+    if !report.stale.is_empty() {
+        println!("Stale files ({}):", report.stale.len());
+        for path in &report.stale {
+            println!("  ? {}", path);
+        }
+    }
+
+    if report.is_clean() {
+        println!("All files up to date.");
+    } else {
+        println!("Total: {} issues", report.total_issues());
+    }
+}
+
+fn print_integrity_report(db_path: &str, report: &IntegrityReport) {
+    println!("Database integrity: {}", db_path);
+
+    if !report.sqlite_errors.is_empty() {
+        println!("SQLite integrity_check ({}):", report.sqlite_errors.len());
+        for line in &report.sqlite_errors {
+            println!("  ! {}", line);
+        }
+    }
+
+    if !report.orphan_symbols.is_empty() {
+        println!("Orphan symbols ({}):", report.orphan_symbols.len());
+        for symbol in &report.orphan_symbols {
+            println!(
+                "  - {} (entity {}, file {})",
+                symbol.name,
+                symbol.entity_id,
+                symbol.file_path.as_deref().unwrap_or("<none>")
+            );
+        }
+    }
+
+    if !report.dangling_edges.is_empty() {
+        println!("Dangling call edges ({}):", report.dangling_edges.len());
+        for edge in &report.dangling_edges {
+            let missing = match (edge.missing_from, edge.missing_to) {
+                (true, true) => "both ends missing",
+                (true, false) => "source missing",
+                _ => "target missing",
+            };
+            println!(
+                "  - {} edge {}: {} -> {} ({})",
+                edge.edge_type, edge.edge_id, edge.from_id, edge.to_id, missing
+            );
+        }
+    }
+
+    if report.is_clean() {
+        println!("No integrity problems found.");
+    } else {
+        println!("Total: {} integrity issues", report.total_issues());
+    }
+}
+
+fn print_span_overlaps(overlaps: &[SpanOverlap]) {
+    if overlaps.is_empty() {
+        println!("No overlapping symbol spans found.");
+        return;
+    }
+
+    println!("Overlapping symbol spans ({}):", overlaps.len());
+    for overlap in overlaps {
+        let relation = if overlap.duplicate {
+            "duplicates"
+        } else {
+            "crosses"
+        };
+        println!(
+            "  ! {}: {} {} [{}..{}] {} {} {} [{}..{}]",
+            overlap.file_path,
+            overlap.first.kind,
+            overlap.first.name,
+            overlap.first.byte_start,
+            overlap.first.byte_end,
+            relation,
+            overlap.second.kind,
+            overlap.second.name,
+            overlap.second.byte_start,
+            overlap.second.byte_end
+        );
+    }
 }
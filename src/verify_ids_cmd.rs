@@ -0,0 +1,125 @@
+//! Verify-ids command implementation
+//!
+//! Reindexes unchanged files into a temporary database and reports any symbol
+//! whose stable ID differs from the live database.
+
+use anyhow::Result;
+use std::path::PathBuf;
+
+use crate::{generate_execution_id, CodeGraph};
+use magellan::output::command::OutputFormat;
+
+pub fn run_verify_ids(
+    root_path: PathBuf,
+    db_path: PathBuf,
+    output_format: OutputFormat,
+) -> Result<u8> {
+    let args = vec![
+        "verify-ids".to_string(),
+        "--root".to_string(),
+        root_path.to_string_lossy().to_string(),
+        "--db".to_string(),
+        db_path.to_string_lossy().to_string(),
+    ];
+
+    let mut graph = CodeGraph::open(&db_path)?;
+    let exec_id = generate_execution_id();
+    let root_str = root_path.to_string_lossy().to_string();
+    let db_path_str = db_path.to_string_lossy().to_string();
+
+    graph.execution_log().start_execution(
+        &exec_id,
+        env!("CARGO_PKG_VERSION"),
+        &args,
+        Some(&root_str),
+        &db_path_str,
+    )?;
+
+    graph
+        .telemetry()
+        .record_phase_start(&exec_id, "verify_ids")?;
+    let report = magellan::verify::verify_symbol_ids(&mut graph, &root_path)?;
+    graph.telemetry().record_phase_end(&exec_id, "verify_ids")?;
+
+    let exit_code: u8 = if report.is_stable() { 0 } else { 1 };
+
+    match output_format {
+        OutputFormat::Json | OutputFormat::Pretty => {
+            let response = serde_json::json!({
+                "schema_version": "1.0.0",
+                "execution_id": &exec_id,
+                "data": {
+                    "root_path": root_path.to_string_lossy(),
+                    "db_path": db_path.to_string_lossy(),
+                    "files_checked": report.files_checked,
+                    "skipped_modified": report.skipped_modified,
+                    "symbols_checked": report.symbols_checked,
+                    "changed": report.changed,
+                    "only_live": report.only_live,
+                    "only_reindexed": report.only_reindexed,
+                    "is_stable": report.is_stable(),
+                },
+                "tool": "magellan",
+                "timestamp": chrono::Utc::now().to_rfc3339(),
+            });
+            let json_str = match output_format {
+                OutputFormat::Pretty => serde_json::to_string_pretty(&response)?,
+                _ => serde_json::to_string(&response)?,
+            };
+            println!("{json_str}");
+        }
+        OutputFormat::Human => {
+            println!("Symbol ID verification: {}", root_str);
+            println!(
+                "Checked {} symbols in {} files",
+                report.symbols_checked, report.files_checked
+            );
+
+            if !report.skipped_modified.is_empty() {
+                println!(
+                    "Skipped modified files ({}):",
+                    report.skipped_modified.len()
+                );
+                for path in &report.skipped_modified {
+                    println!("  ~ {}", path);
+                }
+            }
+
+            if !report.changed.is_empty() {
+                println!("Changed IDs ({}):", report.changed.len());
+                for change in &report.changed {
+                    println!(
+                        "  ! {}: {} -> {}",
+                        change.fqn,
+                        change.live_ids.join(","),
+                        change.reindexed_ids.join(",")
+                    );
+                }
+            }
+
+            if !report.only_live.is_empty() {
+                println!("Missing after reindex ({}):", report.only_live.len());
+                for fqn in &report.only_live {
+                    println!("  - {}", fqn);
+                }
+            }
+
+            if !report.only_reindexed.is_empty() {
+                println!("New after reindex ({}):", report.only_reindexed.len());
+                for fqn in &report.only_reindexed {
+                    println!("  + {}", fqn);
+                }
+            }
+
+            if report.is_stable() {
+                println!("All symbol IDs stable.");
+            }
+        }
+    }
+
+    graph
+        .execution_log()
+        .finish_execution(&exec_id, "success", None, 0, 0, 0)?;
+
+    Ok(exit_code)
+}
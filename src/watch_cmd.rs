@@ -4,13 +4,19 @@ use anyhow::{Context, Result};
 use std::path::PathBuf;
 use std::sync::atomic::AtomicBool;
 use std::sync::Arc;
+use std::time::Duration;
 
 use crate::generate_execution_id;
 
 use magellan::backend_router::MagellanBackend;
 use magellan::graph::validation;
+use magellan::output::{output_json, JsonResponse, WatchDryRunResponse};
+use magellan::OutputFormat;
+use magellan::PathStyle;
+use magellan::SqliteOptions;
 use magellan::WatchPipelineConfig;
 use magellan::WatcherConfig;
+use magellan::{HashAlgorithm, HashNormalization};
 use serde_json::json;
 
 /// Synchronous helper: send JSON-RPC watch request to daemon
@@ -51,15 +57,51 @@ fn send_watch_request(req_line: &str, exec_id: &str) -> Result<()> {
     }
 }
 
+#[allow(
+    clippy::too_many_arguments,
+    reason = "CLI command surface: each arg maps to a flag"
+)]
 pub fn run_watch(
     root_path: PathBuf,
+    extra_roots: Vec<PathBuf>,
     db_path: PathBuf,
     config: WatcherConfig,
     scan_initial: bool,
     validate: bool,
     validate_only: bool,
     compile_commands: Option<std::path::PathBuf>,
+    api_only: bool,
+    compress_chunks: bool,
+    no_calls: bool,
+    path_style: Option<PathStyle>,
+    hash_normalization: HashNormalization,
+    hash_algo: Option<HashAlgorithm>,
+    sqlite_options: SqliteOptions,
+    config_path: Option<PathBuf>,
+    max_file_size: Option<u64>,
+    parse_timeout_ms: Option<u64>,
+    include_patterns: Vec<String>,
+    exclude_patterns: Vec<String>,
+    jobs: Option<usize>,
+    once: bool,
+    checkpoint_interval: Option<u64>,
+    include_generated: bool,
+    mem_report: bool,
+    profile: bool,
+    dry_run: bool,
+    output_format: OutputFormat,
 ) -> Result<()> {
+    // A dry run only walks the tree; the database is never opened
+    if dry_run {
+        let mut pipeline_config = WatchPipelineConfig::new(root_path, db_path, config, true);
+        pipeline_config.config_path = config_path;
+        pipeline_config.max_file_size = max_file_size;
+        pipeline_config.include_patterns = include_patterns;
+        pipeline_config.exclude_patterns = exclude_patterns;
+        pipeline_config.include_generated = include_generated;
+        return report_dry_run(&pipeline_config, output_format);
+    }
+
     // Build args for execution tracking
     let mut args = vec![
         "watch".to_string(),
@@ -68,6 +110,10 @@ pub fn run_watch(
         "--db".to_string(),
         db_path.to_string_lossy().to_string(),
     ];
+    for root in &extra_roots {
+        args.push("--root".to_string());
+        args.push(root.to_string_lossy().to_string());
+    }
     if !scan_initial {
         args.push("--watch-only".to_string());
     }
@@ -77,19 +123,108 @@ pub fn run_watch(
     if validate_only {
         args.push("--validate-only".to_string());
     }
+    if api_only {
+        args.push("--api-only".to_string());
+    }
+    if compress_chunks {
+        args.push("--compress-chunks".to_string());
+    }
+    if no_calls {
+        args.push("--no-calls".to_string());
+    }
+    match path_style {
+        Some(PathStyle::Relative) => args.push("--relative-paths".to_string()),
+        Some(PathStyle::Absolute) => args.push("--absolute-paths".to_string()),
+        None => {}
+    }
+    args.extend(hash_normalization.as_flags().iter().map(|f| f.to_string()));
+    if let Some(algorithm) = hash_algo {
+        args.push("--hash-algo".to_string());
+        args.push(algorithm.as_str().to_string());
+    }
+    if config.follow_symlinks {
+        args.push("--follow-symlinks".to_string());
+    }
     args.push("--debounce-ms".to_string());
     args.push(config.debounce_ms.to_string());
+    if let Some(interval_ms) = config.poll_interval_ms {
+        args.push("--poll-interval".to_string());
+        args.push(interval_ms.to_string());
+    }
+    if let Some(ref path) = config_path {
+        args.push("--config".to_string());
+        args.push(path.to_string_lossy().to_string());
+    }
+    if let Some(bytes) = max_file_size {
+        args.push("--max-file-size".to_string());
+        args.push(bytes.to_string());
+    }
+    if let Some(ms) = parse_timeout_ms {
+        args.push("--parse-timeout-ms".to_string());
+        args.push(ms.to_string());
+    }
+    for pattern in &include_patterns {
+        args.push("--include".to_string());
+        args.push(pattern.clone());
+    }
+    for pattern in &exclude_patterns {
+        args.push("--exclude".to_string());
+        args.push(pattern.clone());
+    }
+    if let Some(jobs) = jobs {
+        args.push("--jobs".to_string());
+        args.push(jobs.to_string());
+    }
+    if include_generated {
+        args.push("--include-generated".to_string());
+    }
+    if !config.non_source_extensions.is_empty() {
+        args.push("--non-source-ext".to_string());
+        args.push(config.non_source_extensions.join(","));
+    }
+    if mem_report {
+        args.push("--mem-report".to_string());
+    }
+    if profile {
+        args.push("--profile".to_string());
+    }
+    if let Some(secs) = checkpoint_interval {
+        args.push("--checkpoint-interval".to_string());
+        args.push(secs.to_string());
+    }
+    if once {
+        args.push("--once".to_string());
+    }
+    if output_format != OutputFormat::Human {
+        args.push("--output".to_string());
+        args.push("json".to_string());
+    }
+    if sqlite_options != SqliteOptions::default() {
+        args.push("--journal-mode".to_string());
+        args.push(sqlite_options.journal_mode.as_str().to_string());
+        args.push("--synchronous".to_string());
+        args.push(sqlite_options.synchronous.as_str().to_string());
+        args.push("--busy-timeout".to_string());
+        args.push(sqlite_options.busy_timeout_ms.to_string());
+        args.push("--busy-retries".to_string());
+        args.push(sqlite_options.busy_retries.to_string());
+    }
 
     let exec_id = generate_execution_id();
 
-    // svc-8: if daemon is running, signal it instead of local watch
-    if crate::service::is_daemon_running() {
+    // svc-8: if daemon is running, signal it instead of local watch.
+    // `--once` always scans locally so it can report counts and exit.
+    if !once && crate::service::is_daemon_running() {
+        let paths: Vec<String> = std::iter::once(&root_path)
+            .chain(&extra_roots)
+            .map(|root| root.to_string_lossy().to_string())
+            .collect();
         return send_watch_request(
             &json!({
                 "id": exec_id,
                 "method": "watch",
                 "tag": root_path.to_string_lossy(),
-                "paths": [root_path.to_string_lossy().to_string()],
+                "paths": paths,
             })
             .to_string(),
             &exec_id,
@@ -100,7 +235,10 @@ pub fn run_watch(
     let db_path_str = db_path.to_string_lossy().to_string();
 
     // Open the backend for execution logging
-    let mut backend = Some(MagellanBackend::open_or_create(&db_path)?);
+    let mut backend = Some(MagellanBackend::open_or_create_with_options(
+        &db_path,
+        &sqlite_options,
+    )?);
 
     // Start execution log if supported (SQLite only)
     if let Some(MagellanBackend::SQLite(ref mut graph)) = &mut backend {
@@ -120,26 +258,27 @@ pub fn run_watch(
             graph
                 .telemetry()
                 .record_phase_start(&exec_id, "pre_validation")?;
-            let input_paths = vec![root_path.clone()];
-            match validation::pre_run_validate(&db_path, &root_path, &input_paths) {
-                Ok(report) if !report.passed => {
-                    let error_count = report.errors.len();
-                    let error_msg = format!("Pre-validation failed: {} errors", error_count);
-                    graph
-                        .telemetry()
-                        .record_phase_end(&exec_id, "pre_validation")?;
-                    graph.execution_log().finish_execution(
-                        &exec_id,
-                        "error",
-                        Some(&error_msg),
-                        0,
-                        0,
-                        0,
-                    )?;
-                    return Err(anyhow::anyhow!("Pre-validation failed"));
-                }
-                Ok(_) => {}
-                Err(e) => return Err(e),
+            // Each root is validated against itself
+            let mut error_count = 0;
+            for root in std::iter::once(&root_path).chain(&extra_roots) {
+                let report =
+                    validation::pre_run_validate(&db_path, root, std::slice::from_ref(root))?;
+                error_count += report.errors.len();
+            }
+            if error_count > 0 {
+                let error_msg = format!("Pre-validation failed: {} errors", error_count);
+                graph
+                    .telemetry()
+                    .record_phase_end(&exec_id, "pre_validation")?;
+                graph.execution_log().finish_execution(
+                    &exec_id,
+                    "error",
+                    Some(&error_msg),
+                    0,
+                    0,
+                    0,
+                )?;
+                return Err(anyhow::anyhow!("Pre-validation failed"));
             }
             graph
                 .telemetry()
@@ -172,7 +311,30 @@ pub fn run_watch(
     // Create pipeline configuration
     let mut pipeline_config =
         WatchPipelineConfig::new(root_path, db_path.clone(), config, scan_initial);
+    pipeline_config.extra_roots = extra_roots;
     pipeline_config.compile_commands_path = compile_commands;
+    pipeline_config.validate = validate;
+    pipeline_config.api_only = api_only;
+    pipeline_config.compress_chunks = compress_chunks;
+    pipeline_config.no_calls = no_calls;
+    pipeline_config.path_style = path_style;
+    pipeline_config.hash_normalization = hash_normalization;
+    pipeline_config.hash_algorithm = hash_algo;
+    pipeline_config.sqlite_options = sqlite_options;
+    pipeline_config.config_path = config_path;
+    pipeline_config.max_file_size = max_file_size;
+    if let Some(ms) = parse_timeout_ms {
+        pipeline_config.parse_timeout = (ms > 0).then(|| Duration::from_millis(ms));
+    }
+    pipeline_config.include_patterns = include_patterns;
+    pipeline_config.exclude_patterns = exclude_patterns;
+    pipeline_config.jobs = jobs;
+    pipeline_config.once = once;
+    pipeline_config.checkpoint_interval = checkpoint_interval.map(Duration::from_secs);
+    pipeline_config.include_generated = include_generated;
+    pipeline_config.mem_report = mem_report;
+    pipeline_config.profile = profile;
+    pipeline_config.output_format = output_format;
 
     // Run the deterministic watch pipeline
     let result = magellan::run_watch_pipeline(pipeline_config, shutdown);
@@ -191,6 +353,11 @@ pub fn run_watch(
         );
     }
 
+    if once {
+        let indexed = result?;
+        return report_once(backend.as_ref(), indexed, output_format);
+    }
+
     match result {
         Ok(count) => {
             println!("SHUTDOWN");
@@ -203,3 +370,79 @@ pub fn run_watch(
         }
     }
 }
+
+/// Print the final counts of a `watch --once` run
+fn report_once(
+    backend: Option<&MagellanBackend>,
+    indexed: usize,
+    output_format: OutputFormat,
+) -> Result<()> {
+    let Some(MagellanBackend::SQLite(graph)) = backend else {
+        println!("Indexed {} files", indexed);
+        return Ok(());
+    };
+    let files = graph.count_files()?;
+    let symbols = graph.count_symbols()?;
+    let references = graph.count_references()?;
+    let calls = graph.count_calls()?;
+
+    match output_format {
+        OutputFormat::Human => {
+            println!("Indexed {} files", indexed);
+            println!(
+                "Database: {} files, {} symbols, {} references, {} calls",
+                files, symbols, references, calls
+            );
+        }
+        OutputFormat::Json | OutputFormat::Pretty => println!(
+            "{}",
+            json!({
+                "indexed": indexed,
+                "files": files,
+                "symbols": symbols,
+                "references": references,
+                "calls": calls,
+            })
+        ),
+    }
+    Ok(())
+}
+
+/// Print the files a `watch --dry-run` scan would index
+fn report_dry_run(config: &WatchPipelineConfig, output_format: OutputFormat) -> Result<()> {
+    let (root, paths) = magellan::dry_run_scan(config)?;
+    let files: Vec<String> = paths
+        .iter()
+        .map(|path| {
+            path.strip_prefix(&root)
+                .unwrap_or(path)
+                .to_string_lossy()
+                .into_owned()
+        })
+        .collect();
+
+    match output_format {
+        OutputFormat::Human => {
+            for file in &files {
+                println!("{}", file);
+            }
+            println!(
+                "{} file(s) would be indexed under {}",
+                files.len(),
+                root.display()
+            );
+        }
+        OutputFormat::Json | OutputFormat::Pretty => {
+            let response = WatchDryRunResponse {
+                root: root.to_string_lossy().into_owned(),
+                count: files.len(),
+                files,
+            };
+            output_json(
+                &JsonResponse::new(response, &generate_execution_id()),
+                output_format,
+            )?;
+        }
+    }
+    Ok(())
+}
@@ -30,7 +30,7 @@
 pub mod async_watcher;
 
 use anyhow::Result;
-use notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use notify::{EventKind, PollWatcher, RecommendedWatcher, RecursiveMode, Watcher, WatcherKind};
 use parking_lot::Mutex;
 use serde::{Deserialize, Serialize};
 use std::collections::{BTreeSet, HashMap};
@@ -44,6 +44,7 @@ use std::time::{Duration, Instant};
 
 use crate::diagnostics::SkipReason;
 use crate::graph::filter::FileFilter;
+use crate::log_warn;
 
 /// Deterministic batch of dirty file paths.
 ///
@@ -83,21 +84,38 @@ pub struct WatcherConfig {
     pub debounce_ms: u64,
     /// Enable .gitignore filtering (default: true)
     pub gitignore_aware: bool,
+    /// Follow symlinks whose canonical target stays within root (default: false)
+    pub follow_symlinks: bool,
+    /// Rescan the tree every N ms instead of using native events (`--poll`).
+    /// `None` uses the native watcher, falling back to polling if it fails to start.
+    pub poll_interval_ms: Option<u64>,
+    /// Extensions of files indexed without symbols (`--index-non-source`).
+    /// Empty skips files in no supported language.
+    pub non_source_extensions: Vec<String>,
 }
 
+/// Rescan interval used by `--poll` without `--poll-interval`, and by the
+/// fallback when the native watcher is unavailable
+pub const DEFAULT_POLL_INTERVAL_MS: u64 = 2000;
+
 impl Default for WatcherConfig {
     fn default() -> Self {
         Self {
             root_path: PathBuf::from("."),
             debounce_ms: 500,
             gitignore_aware: true,
+            follow_symlinks: false,
+            poll_interval_ms: None,
+            non_source_extensions: Vec::new(),
         }
     }
 }
 
 /// Filesystem watcher that emits debounced batches of dirty paths.
 ///
-/// Uses `notify::RecommendedWatcher` directly with custom debouncing that
+/// Events come from `notify::RecommendedWatcher`, or from a
+/// `notify::PollWatcher` when polling is configured or native watching is
+/// unavailable (see [`WatcherConfig::poll_interval_ms`]). Custom debouncing
 /// filters out read-only events (ACCESS/OPEN/CLOSE_NOWRITE) at the source.
 /// All paths within the debounce window are collected, de-duplicated, sorted,
 /// and emitted as a single `WatcherBatch`.
@@ -347,9 +365,9 @@ fn is_mutation_event(kind: &EventKind) -> bool {
 
 /// Run the debounced watcher in a dedicated thread.
 ///
-/// Uses `notify::RecommendedWatcher` directly with custom debouncing that
-/// filters out read-only events (ACCESS/OPEN/CLOSE_NOWRITE) at the source.
-/// This prevents the infinite feedback loop where `reconcile_file_path`
+/// Events come from the backend chosen by `start_backend` (native or
+/// polling). Custom debouncing filters out read-only events
+/// (ACCESS/OPEN/CLOSE_NOWRITE) at the source. This prevents the infinite feedback loop where `reconcile_file_path`
 /// reads a file → triggers ACCESS inotify event → re-indexes → reads again.
 fn run_watcher(
     path: PathBuf,
@@ -362,9 +380,9 @@ fn run_watcher(
 
     let filter = if config.gitignore_aware {
         match FileFilter::new(&root_path, &[], &[]) {
-            Ok(f) => Some(f),
+            Ok(f) => Some(f.with_non_source_extensions(&config.non_source_extensions)),
             Err(e) => {
-                eprintln!("Warning: Failed to create gitignore filter: {}", e);
+                log_warn!("Failed to create gitignore filter: {}", e);
                 None
             }
         }
@@ -374,25 +392,8 @@ fn run_watcher(
 
     let (raw_tx, raw_rx): (Sender<Vec<PathBuf>>, Receiver<Vec<PathBuf>>) = mpsc::channel();
 
-    let mut watcher = RecommendedWatcher::new(
-        move |result: notify::Result<notify::Event>| match result {
-            Ok(event) => {
-                if !is_mutation_event(&event.kind) {
-                    return;
-                }
-                let paths: Vec<PathBuf> = event.paths;
-                if !paths.is_empty() {
-                    let _ = raw_tx.send(paths);
-                }
-            }
-            Err(error) => {
-                eprintln!("Watcher error: {:?}", error);
-            }
-        },
-        notify::Config::default(),
-    )?;
-
-    watcher.watch(&path, RecursiveMode::Recursive)?;
+    // Kept alive for the lifetime of the loop; dropping it stops the events
+    let (_watcher, _kind) = start_backend(&path, &config, raw_tx)?;
 
     let mut pending: HashMap<PathBuf, Instant> = HashMap::new();
     let idle_sleep = Duration::from_millis(50);
@@ -431,7 +432,8 @@ fn run_watcher(
         });
 
         if !expired.is_empty() {
-            let dirty_paths = filter_dirty_paths(expired, &root_path, filter.as_ref());
+            let dirty_paths =
+                filter_dirty_paths(expired, &root_path, filter.as_ref(), config.follow_symlinks);
             if !dirty_paths.is_empty() {
                 let batch = WatcherBatch::from_set(dirty_paths);
                 let _ = tx.send(batch);
@@ -446,11 +448,82 @@ fn run_watcher(
     Ok(())
 }
 
+/// Forward the paths of mutation events to the debounce loop.
+fn mutation_handler(
+    raw_tx: Sender<Vec<PathBuf>>,
+) -> impl Fn(notify::Result<notify::Event>) + Send + 'static {
+    move |result| match result {
+        Ok(event) => {
+            if !is_mutation_event(&event.kind) {
+                return;
+            }
+            let paths: Vec<PathBuf> = event.paths;
+            if !paths.is_empty() {
+                let _ = raw_tx.send(paths);
+            }
+        }
+        Err(error) => {
+            eprintln!("Watcher error: {:?}", error);
+        }
+    }
+}
+
+/// Start the event source for `path`: native events, or polling.
+///
+/// With `poll_interval_ms` set, or when the native watcher cannot start
+/// (unsupported filesystem, inotify limits), a `notify::PollWatcher`
+/// rescans the tree and reports files whose modification time changed.
+/// Both feed the same debounce loop, so batches look the same either way.
+/// Returns the watcher with the kind of backend that was started.
+fn start_backend(
+    path: &Path,
+    config: &WatcherConfig,
+    raw_tx: Sender<Vec<PathBuf>>,
+) -> Result<(Box<dyn Watcher>, WatcherKind)> {
+    let notify_config = notify::Config::default().with_follow_symlinks(config.follow_symlinks);
+
+    let interval_ms = match config.poll_interval_ms {
+        Some(interval_ms) => interval_ms,
+        None => {
+            let native = RecommendedWatcher::new(mutation_handler(raw_tx.clone()), notify_config)
+                .and_then(|mut watcher| {
+                    watcher.watch(path, RecursiveMode::Recursive)?;
+                    Ok(watcher)
+                });
+            match native {
+                Ok(watcher) => return Ok((Box::new(watcher), RecommendedWatcher::kind())),
+                Err(e) if matches!(e.kind, notify::ErrorKind::PathNotFound) => return Err(e.into()),
+                Err(e) => {
+                    log_warn!(
+                        "native file watching unavailable ({}); polling every {} ms",
+                        e,
+                        DEFAULT_POLL_INTERVAL_MS
+                    );
+                    DEFAULT_POLL_INTERVAL_MS
+                }
+            }
+        }
+    };
+
+    let mut watcher = PollWatcher::new(
+        mutation_handler(raw_tx),
+        notify_config.with_poll_interval(Duration::from_millis(interval_ms)),
+    )?;
+    watcher.watch(path, RecursiveMode::Recursive)?;
+    Ok((Box::new(watcher), PollWatcher::kind()))
+}
+
 /// Filter a set of expired paths through gitignore, database, and validation checks.
+///
+/// Existing paths are reported canonically, so one file reached through
+/// several followed links (or around a symlink cycle) yields one dirty path.
+/// With `follow_symlinks`, a path that no longer exists is resolved through
+/// its parent directory instead, and dropped if that lands outside `root`.
 fn filter_dirty_paths(
     candidates: BTreeSet<PathBuf>,
     root: &Path,
     filter: Option<&FileFilter>,
+    follow_symlinks: bool,
 ) -> BTreeSet<PathBuf> {
     let mut dirty_paths = BTreeSet::new();
 
@@ -479,19 +552,28 @@ fn filter_dirty_paths(
                 dirty_paths.insert(PathBuf::from(normalized));
             }
             Err(crate::validation::PathValidationError::OutsideRoot(p, _)) => {
-                eprintln!("WARNING: Watcher rejected path outside project root: {}", p);
+                log_warn!("Watcher rejected path outside project root: {}", p);
             }
             Err(crate::validation::PathValidationError::SuspiciousTraversal(p)) => {
-                eprintln!(
-                    "WARNING: Watcher rejected suspicious traversal pattern: {}",
-                    p
-                );
+                log_warn!("Watcher rejected suspicious traversal pattern: {}", p);
             }
             Err(crate::validation::PathValidationError::SymlinkEscape(from, to)) => {
-                eprintln!(
-                    "WARNING: Watcher rejected symlink escaping root: {} -> {}",
-                    from, to
-                );
+                log_warn!("Watcher rejected symlink escaping root: {} -> {}", from, to);
+            }
+            Err(crate::validation::PathValidationError::CannotCanonicalize(_))
+                if follow_symlinks =>
+            {
+                match resolve_through_parent(&path, root) {
+                    Some(resolved) => {
+                        dirty_paths.insert(resolved);
+                    }
+                    None => {
+                        log_warn!(
+                            "Watcher rejected path resolving outside project root: {}",
+                            path.display()
+                        );
+                    }
+                }
             }
             Err(crate::validation::PathValidationError::CannotCanonicalize(_)) => {
                 let normalized = crate::validation::normalize_path(&path)
@@ -504,6 +586,17 @@ fn filter_dirty_paths(
     dirty_paths
 }
 
+/// Resolve a path that no longer exists through its canonical parent.
+///
+/// Returns `None` when the parent cannot be resolved within `root`, e.g. a
+/// deleted file under a followed symlink that points outside the project.
+fn resolve_through_parent(path: &Path, root: &Path) -> Option<PathBuf> {
+    let parent = path.parent()?;
+    let file_name = path.file_name()?;
+    let canonical_parent = crate::validation::validate_path_within_root(parent, root).ok()?;
+    Some(canonical_parent.join(file_name))
+}
+
 /// Check if a path is a database file that should be excluded from watching.
 ///
 /// Database files are excluded because the indexer writes to them, which
@@ -559,6 +652,23 @@ impl std::fmt::Display for EventType {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_start_backend_selects_polling_only_when_configured() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let (raw_tx, _raw_rx) = mpsc::channel();
+
+        let config = WatcherConfig {
+            poll_interval_ms: Some(100),
+            ..WatcherConfig::default()
+        };
+        let (_watcher, kind) = start_backend(dir.path(), &config, raw_tx.clone()).unwrap();
+        assert_eq!(kind, WatcherKind::PollWatcher);
+
+        let (_watcher, kind) =
+            start_backend(dir.path(), &WatcherConfig::default(), raw_tx).unwrap();
+        assert_eq!(kind, RecommendedWatcher::kind());
+    }
+
     #[test]
     fn test_batch_is_empty() {
         let batch = WatcherBatch::empty();
@@ -610,6 +720,9 @@ mod tests {
             root_path: PathBuf::from("/test/root"),
             debounce_ms: 100,
             gitignore_aware: true,
+            follow_symlinks: false,
+            poll_interval_ms: None,
+            non_source_extensions: Vec::new(),
         };
 
         assert_eq!(config.root_path, PathBuf::from("/test/root"));
@@ -679,7 +792,30 @@ mod tests {
         let mut candidates = BTreeSet::new();
         candidates.insert(db_file);
 
-        let result = filter_dirty_paths(candidates, root, None);
+        let result = filter_dirty_paths(candidates, root, None, false);
         assert!(result.is_empty());
     }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_filter_dirty_paths_followed_links_stay_in_root() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let outside_dir = tempfile::TempDir::new().unwrap();
+        let root = temp_dir.path();
+        let src = root.join("src");
+        std::fs::create_dir(&src).unwrap();
+        std::fs::write(src.join("a.rs"), b"fn a() {}").unwrap();
+        std::os::unix::fs::symlink(&src, src.join("loop")).unwrap();
+        std::os::unix::fs::symlink(outside_dir.path(), root.join("ext")).unwrap();
+
+        let mut candidates = BTreeSet::new();
+        candidates.insert(src.join("a.rs"));
+        candidates.insert(src.join("loop/a.rs"));
+        candidates.insert(src.join("loop/loop/a.rs"));
+        candidates.insert(root.join("ext/deleted.rs"));
+
+        let result = filter_dirty_paths(candidates, root, None, true);
+        let expected = std::fs::canonicalize(src.join("a.rs")).unwrap();
+        assert_eq!(result.into_iter().collect::<Vec<_>>(), vec![expected]);
+    }
 }
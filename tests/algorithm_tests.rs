@@ -196,6 +196,145 @@ fn shared() {}
     );
 }
 
+#[test]
+fn test_reachable_dot_two_nodes_one_edge() {
+    // A two-node reachable set renders as two nodes and a single CALLS edge
+    use magellan::graph::export::reachable_to_dot;
+    use magellan::CodeGraph;
+
+    let temp_dir = TempDir::new().unwrap();
+    let db_path = temp_dir.path().join("test.db");
+    let path_str = temp_dir
+        .path()
+        .join("test.rs")
+        .to_string_lossy()
+        .to_string();
+    let source = "fn caller() {\n    callee();\n}\n\nfn callee() {}\n";
+
+    let mut graph = CodeGraph::open(&db_path).unwrap();
+    graph.index_file(&path_str, source.as_bytes()).unwrap();
+    graph.index_calls(&path_str, source.as_bytes()).unwrap();
+
+    let start = graph
+        .symbol_by_entity_id(graph.resolve_symbol_entity("caller").unwrap())
+        .unwrap();
+    let reachable = graph.reachable_symbols("caller", None).unwrap();
+    let edges = graph.reachable_call_edges("caller", false).unwrap();
+    assert_eq!(reachable.len(), 1, "reachable: {:?}", reachable);
+    assert_eq!(edges.len(), 1, "edges: {:?}", edges);
+    assert_eq!(edges[0].0.fqn.as_deref(), Some("caller"));
+    assert_eq!(edges[0].1.fqn.as_deref(), Some("callee"));
+
+    let dot = reachable_to_dot(&start, &reachable, &edges, false);
+    assert_eq!(dot.matches("[label=").count(), 2, "{}", dot);
+    assert_eq!(dot.matches(" -> ").count(), 1, "{}", dot);
+    assert_eq!(dot, reachable_to_dot(&start, &reachable, &edges, false));
+
+    // Reverse traversal from the callee keeps the edge, pointing back at the caller
+    let edges = graph.reachable_call_edges("callee", true).unwrap();
+    assert_eq!(edges.len(), 1, "edges: {:?}", edges);
+}
+
+#[test]
+fn test_reachable_depth_on_linear_chain() {
+    // a -> b -> c: each symbol keeps the number of calls from the start
+    use magellan::CodeGraph;
+
+    let temp_dir = TempDir::new().unwrap();
+    let db_path = temp_dir.path().join("test.db");
+    let path_str = temp_dir
+        .path()
+        .join("test.rs")
+        .to_string_lossy()
+        .to_string();
+    let source = "fn a() {\n    b();\n}\n\nfn b() {\n    c();\n}\n\nfn c() {}\n";
+
+    let mut graph = CodeGraph::open(&db_path).unwrap();
+    graph.index_file(&path_str, source.as_bytes()).unwrap();
+    graph.index_calls(&path_str, source.as_bytes()).unwrap();
+
+    let depths = |reverse: bool, start: &str, max_depth: Option<usize>| -> Vec<(String, usize)> {
+        graph
+            .reachable_symbols_with_depth(start, reverse, max_depth)
+            .unwrap()
+            .into_iter()
+            .map(|(symbol, depth)| (symbol.fqn.unwrap(), depth))
+            .collect()
+    };
+    let chain = |names: &[(&str, usize)]| -> Vec<(String, usize)> {
+        names
+            .iter()
+            .map(|(name, depth)| (name.to_string(), *depth))
+            .collect()
+    };
+
+    assert_eq!(
+        depths(false, "a", None),
+        chain(&[("a", 0), ("b", 1), ("c", 2)])
+    );
+    assert_eq!(depths(false, "a", Some(1)), chain(&[("a", 0), ("b", 1)]));
+    assert_eq!(depths(false, "a", Some(0)), chain(&[("a", 0)]));
+    assert_eq!(
+        depths(true, "c", None),
+        chain(&[("a", 2), ("b", 1), ("c", 0)])
+    );
+
+    // The depth limit also applies to the plain reachability queries
+    let reachable = graph.reachable_symbols("a", Some(1)).unwrap();
+    assert_eq!(reachable.len(), 1, "reachable: {:?}", reachable);
+    assert_eq!(reachable[0].fqn.as_deref(), Some("b"));
+    assert_eq!(graph.reachable_symbols("a", None).unwrap().len(), 2);
+}
+
+#[test]
+fn test_reachable_human_output_shows_depth_only_with_max_depth() {
+    use magellan::CodeGraph;
+
+    let temp_dir = TempDir::new().unwrap();
+    let db_path = temp_dir.path().join("test.db");
+    let path_str = temp_dir
+        .path()
+        .join("test.rs")
+        .to_string_lossy()
+        .to_string();
+    let source = "fn a() {\n    b();\n}\n\nfn b() {\n    c();\n}\n\nfn c() {}\n";
+    {
+        let mut graph = CodeGraph::open(&db_path).unwrap();
+        graph.index_file(&path_str, source.as_bytes()).unwrap();
+        graph.index_calls(&path_str, source.as_bytes()).unwrap();
+    }
+
+    let reachable = |extra: &[&str]| -> String {
+        let output = std::process::Command::new(env!("CARGO_BIN_EXE_magellan"))
+            .args(["reachable", "--symbol", "a"])
+            .args(extra)
+            .arg("--db")
+            .arg(&db_path)
+            .output()
+            .expect("Failed to execute magellan reachable");
+        assert!(output.status.success(), "{:?}", output);
+        String::from_utf8_lossy(&output.stdout).into_owned()
+    };
+
+    // Without a limit the output is the same as before depths were tracked
+    assert_eq!(
+        reachable(&[]),
+        format!(
+            "Symbols reachable from \"a\":\n  b (Function) in {path_str}\n  c (Function) in {path_str}\n"
+        )
+    );
+
+    let limited = reachable(&["--max-depth", "2"]);
+    assert!(
+        limited.contains(&format!("  b (Function) in {path_str} [depth 1]")),
+        "{limited}"
+    );
+    assert!(
+        limited.contains(&format!("  c (Function) in {path_str} [depth 2]")),
+        "{limited}"
+    );
+}
+
 #[test]
 fn test_algorithm_empty_database() {
     // Test algorithm behavior on empty database
@@ -711,6 +850,169 @@ fn target() {}
     }
 }
 
+#[test]
+fn test_shortest_path_prefers_fewest_calls() {
+    // main reaches target directly and through a longer chain; --shortest
+    // must return only the direct path
+    use magellan::CodeGraph;
+
+    let temp_dir = TempDir::new().unwrap();
+    let db_path = temp_dir.path().join("test.db");
+    let file_path = temp_dir.path().join("test.rs");
+
+    let source = r#"
+fn main() {
+    long_a();
+    target();
+}
+
+fn long_a() {
+    long_b();
+}
+
+fn long_b() {
+    target();
+}
+
+fn target() {}
+
+fn unrelated() {}
+"#;
+
+    let mut graph = CodeGraph::open(&db_path).unwrap();
+    let path_str = file_path.to_string_lossy().to_string();
+
+    graph.index_file(&path_str, source.as_bytes()).unwrap();
+    graph.index_calls(&path_str, source.as_bytes()).unwrap();
+
+    let symbols = graph.symbols_in_file(&path_str).unwrap();
+    let fqn_of = |name: &str| {
+        let symbol = symbols
+            .iter()
+            .find(|s| s.name.as_deref() == Some(name))
+            .unwrap_or_else(|| panic!("Should find {} symbol", name));
+        symbol
+            .fqn
+            .clone()
+            .or_else(|| symbol.canonical_fqn.clone())
+            .unwrap_or_else(|| panic!("{} should have FQN", name))
+    };
+    let main_fqn = fqn_of("main");
+    let target_fqn = fqn_of("target");
+
+    // Sanity check: full enumeration sees both routes
+    let all = graph
+        .enumerate_paths(&main_fqn, Some(&target_fqn), 10, 100)
+        .unwrap();
+    assert!(all.paths.len() >= 2, "Expected short and long paths");
+
+    let result = graph.shortest_path(&main_fqn, &target_fqn, 10).unwrap();
+    assert_eq!(result.paths.len(), 1);
+    assert_eq!(result.total_enumerated, 1);
+    assert!(!result.bounded_hit);
+
+    let names: Vec<&str> = result.paths[0]
+        .symbols
+        .iter()
+        .filter_map(|s| s.fqn.as_deref())
+        .collect();
+    assert_eq!(names, vec![main_fqn.as_str(), target_fqn.as_str()]);
+    assert_eq!(result.paths[0].length, 2);
+
+    // No route: an explicit empty result rather than an error
+    let unrelated_fqn = fqn_of("unrelated");
+    let none = graph.shortest_path(&main_fqn, &unrelated_fqn, 10).unwrap();
+    assert!(none.paths.is_empty());
+    assert_eq!(none.total_enumerated, 0);
+    assert_eq!(none.statistics.max_length, 0);
+}
+
+#[test]
+fn test_paths_avoiding_hub_symbol() {
+    use magellan::CodeGraph;
+
+    let temp_dir = TempDir::new().unwrap();
+    let db_path = temp_dir.path().join("test.db");
+    let file_path = temp_dir.path().join("test.rs");
+
+    // Every route from main to target goes through log_event
+    let source = r#"
+fn main() {
+    log_event();
+    helper();
+}
+
+fn helper() {
+    log_event();
+}
+
+fn log_event() {
+    target();
+}
+
+fn target() {}
+"#;
+
+    let mut graph = CodeGraph::open(&db_path).unwrap();
+    let path_str = file_path.to_string_lossy().to_string();
+
+    graph.index_file(&path_str, source.as_bytes()).unwrap();
+
+    let symbols = graph.symbols_in_file(&path_str).unwrap();
+    let fqn_of = |name: &str| {
+        let symbol = symbols
+            .iter()
+            .find(|s| s.name.as_deref() == Some(name))
+            .unwrap_or_else(|| panic!("Should find {} symbol", name));
+        symbol
+            .fqn
+            .clone()
+            .or_else(|| symbol.canonical_fqn.clone())
+            .unwrap_or_else(|| panic!("{} should have FQN", name))
+    };
+    let main_fqn = fqn_of("main");
+    let target_fqn = fqn_of("target");
+    let id_of = |name: &str| {
+        let entity_id = graph.resolve_symbol_entity(&fqn_of(name)).unwrap();
+        let info = graph.symbol_by_entity_id(entity_id).unwrap();
+        info.symbol_id.expect("symbol should have a stable ID")
+    };
+    let hub_id = id_of("log_event");
+    let helper_id = id_of("helper");
+
+    let all = graph
+        .enumerate_paths(&main_fqn, Some(&target_fqn), 10, 100)
+        .unwrap();
+    assert_eq!(all.paths.len(), 2, "direct and via-helper routes");
+
+    // Avoiding helper keeps only the direct route
+    let direct = graph
+        .enumerate_paths_avoiding(&main_fqn, Some(&target_fqn), 10, 100, &[&helper_id])
+        .unwrap();
+    assert_eq!(direct.paths.len(), 1);
+    assert!(direct.paths[0]
+        .symbols
+        .iter()
+        .all(|s| s.symbol_id.as_deref() != Some(helper_id.as_str())));
+
+    // The hub is on every route, so nothing is left
+    let none = graph
+        .enumerate_paths_avoiding(&main_fqn, Some(&target_fqn), 10, 100, &[&hub_id])
+        .unwrap();
+    assert!(none.paths.is_empty());
+    assert_eq!(none.total_enumerated, 0);
+
+    let none = graph
+        .shortest_path_avoiding(&main_fqn, &target_fqn, 10, &[&hub_id])
+        .unwrap();
+    assert!(none.paths.is_empty());
+
+    // Endpoints cannot be avoided
+    assert!(graph
+        .shortest_path_avoiding(&main_fqn, &target_fqn, 10, &[&target_fqn])
+        .is_err());
+}
+
 #[test]
 fn test_enumerate_paths_respects_bounds() {
     // Test that enumerate_paths respects max_depth and max_paths bounds
@@ -1067,6 +1369,69 @@ fn c() {}
     );
 }
 
+#[test]
+fn test_condense_call_graph_reports_cycle_edges() {
+    // A 3-node cycle collapses to one supernode whose internal edges trace the cycle
+    use magellan::CodeGraph;
+
+    let temp_dir = TempDir::new().unwrap();
+    let db_path = temp_dir.path().join("test.db");
+    let file_path = temp_dir.path().join("test.rs");
+
+    let source = r#"
+fn a() {
+    b();
+}
+
+fn b() {
+    c();
+}
+
+fn c() {
+    a();
+}
+
+fn main() {
+    a();
+}
+"#;
+
+    let mut graph = CodeGraph::open(&db_path).unwrap();
+    let path_str = file_path.to_string_lossy().to_string();
+    graph.index_file(&path_str, source.as_bytes()).unwrap();
+
+    let result = graph.condense_call_graph().unwrap();
+    let cycles: Vec<_> = result
+        .graph
+        .supernodes
+        .iter()
+        .filter(|s| s.members.len() > 1)
+        .collect();
+    assert_eq!(cycles.len(), 1, "{:?}", result.graph.supernodes);
+
+    let cycle = cycles[0];
+    let fqns: Vec<&str> = cycle
+        .members
+        .iter()
+        .map(|m| m.fqn.as_deref().unwrap())
+        .collect();
+    assert_eq!(fqns, vec!["a", "b", "c"]);
+
+    let edges: Vec<(&str, &str)> = cycle
+        .internal_edges
+        .iter()
+        .map(|&(from, to)| (fqns[from], fqns[to]))
+        .collect();
+    assert_eq!(edges, vec![("a", "b"), ("b", "c"), ("c", "a")]);
+
+    // Supernodes are ordered by their smallest member ID, and repeat runs agree
+    let ids: Vec<i64> = result.graph.supernodes.iter().map(|s| s.id).collect();
+    let mut sorted = ids.clone();
+    sorted.sort();
+    assert_eq!(ids, sorted);
+    assert_eq!(graph.condense_call_graph().unwrap(), result);
+}
+
 #[test]
 fn test_condense_call_graph_single_symbol_supernodes() {
     // Test that single symbols (not in cycles) get their own supernodes
@@ -500,3 +500,66 @@ fn test_cli_find_ambiguous_with_display_fqn() {
         "stderr should list Symbol IDs for all candidates"
     );
 }
+
+#[test]
+fn test_cli_find_ambiguous_candidate_order_is_stable() {
+    let temp_dir = TempDir::new().unwrap();
+    let db_path = temp_dir.path().join("test.db");
+
+    // Index the later path first so entity IDs disagree with candidate order
+    let later = temp_dir.path().join("zeta.rs");
+    let earlier = temp_dir.path().join("alpha.rs");
+    fs::write(&later, "fn Handler() {}").unwrap();
+    fs::write(&earlier, "fn Handler() {}").unwrap();
+    {
+        let mut graph = CodeGraph::open(&db_path).unwrap();
+        for file in [&later, &earlier] {
+            let path = file.to_string_lossy().to_string();
+            graph.index_file(&path, &fs::read(file).unwrap()).unwrap();
+        }
+    }
+
+    let run = |args: &[&str]| {
+        let output = std::process::Command::new(env!("CARGO_BIN_EXE_magellan"))
+            .arg("find")
+            .arg("--db")
+            .arg(&db_path)
+            .args(args)
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "{:?}", output);
+        output
+    };
+
+    // --ambiguous with a plain name falls back to name matching
+    let first_run = String::from_utf8(run(&["--ambiguous", "Handler"]).stderr).unwrap();
+    let alpha = first_run
+        .find("alpha.rs")
+        .expect("alpha.rs candidate listed");
+    let zeta = first_run.find("zeta.rs").expect("zeta.rs candidate listed");
+    assert!(
+        alpha < zeta,
+        "candidates ordered by file path: {}",
+        first_run
+    );
+    for _ in 0..3 {
+        let again = String::from_utf8(run(&["--ambiguous", "Handler"]).stderr).unwrap();
+        assert_eq!(again, first_run);
+    }
+
+    // The name listing and --first follow the same order
+    for _ in 0..3 {
+        let output = run(&["--name", "Handler", "--output", "json"]);
+        let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+        let files: Vec<&str> = json["data"]["matches"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|m| m["span"]["file_path"].as_str().unwrap())
+            .collect();
+        assert_eq!(files.len(), 2);
+        assert!(files[0].ends_with("alpha.rs") && files[1].ends_with("zeta.rs"));
+    }
+    let stdout = String::from_utf8(run(&["--name", "Handler", "--first"]).stdout).unwrap();
+    assert!(stdout.contains("alpha.rs"), "{}", stdout);
+}
@@ -0,0 +1,178 @@
+//! Tests for API-surface indexing (`--api-only`)
+
+use magellan::CodeGraph;
+use tempfile::TempDir;
+
+#[test]
+fn test_api_only_skips_private_functions() {
+    let temp_dir = TempDir::new().unwrap();
+    let db_path = temp_dir.path().join("test.db");
+
+    let mut graph = CodeGraph::open(&db_path).unwrap();
+    graph.set_api_only(true);
+
+    let source = b"pub fn parse(input: &str) -> Option<u32> { helper(input) }\n\
+fn helper(input: &str) -> Option<u32> { input.parse().ok() }\n\
+pub struct Config { pub depth: usize }\n";
+    graph.index_file("lib.rs", source).unwrap();
+
+    let symbols = graph.symbols_in_file("lib.rs").unwrap();
+    let mut names: Vec<_> = symbols.iter().filter_map(|s| s.name.as_deref()).collect();
+    names.sort();
+    assert_eq!(names, vec!["Config", "parse"]);
+
+    let parse = symbols
+        .iter()
+        .find(|s| s.name.as_deref() == Some("parse"))
+        .unwrap();
+    assert_eq!(
+        parse.signature.as_deref(),
+        Some("pub fn parse(input: &str) -> Option<u32>")
+    );
+}
+
+#[test]
+fn test_default_indexing_keeps_private_functions_without_signatures() {
+    let temp_dir = TempDir::new().unwrap();
+    let db_path = temp_dir.path().join("test.db");
+
+    let mut graph = CodeGraph::open(&db_path).unwrap();
+
+    let source = b"pub fn parse() {}\nfn helper() {}\n";
+    graph.index_file("lib.rs", source).unwrap();
+
+    let symbols = graph.symbols_in_file("lib.rs").unwrap();
+    assert_eq!(symbols.len(), 2);
+    assert!(symbols.iter().all(|s| s.signature.is_none()));
+}
+
+fn index_api(db_path: &std::path::Path, source: &[u8]) -> CodeGraph {
+    let mut graph = CodeGraph::open(db_path).unwrap();
+    graph.set_api_only(true);
+    graph.index_file("lib.rs", source).unwrap();
+    graph
+}
+
+#[test]
+fn test_api_diff_classifies_removed_as_breaking_and_added_as_additive() {
+    use magellan::api_diff::{diff_api, ApiImpact};
+
+    let temp_dir = TempDir::new().unwrap();
+    let mut before = index_api(
+        &temp_dir.path().join("a.db"),
+        b"pub fn kept() {}\npub fn removed() {}\nfn private_a() {}\n",
+    );
+    let mut after = index_api(
+        &temp_dir.path().join("b.db"),
+        b"pub fn kept() {}\npub fn added() {}\nfn private_b() {}\n",
+    );
+
+    let report = diff_api(&mut before, &mut after).unwrap();
+
+    assert_eq!(report.removed.len(), 1);
+    assert!(report.removed[0].fqn.ends_with("removed"));
+    assert_eq!(report.removed[0].impact, ApiImpact::Breaking);
+
+    assert_eq!(report.added.len(), 1);
+    assert!(report.added[0].fqn.ends_with("added"));
+    assert_eq!(report.added[0].impact, ApiImpact::Additive);
+
+    assert!(report.changed.is_empty(), "private fns are not API");
+    assert!(report.is_breaking());
+}
+
+#[test]
+fn test_api_diff_flags_signature_change_as_breaking() {
+    use magellan::api_diff::{diff_api, ApiImpact};
+
+    let temp_dir = TempDir::new().unwrap();
+    let mut before = index_api(
+        &temp_dir.path().join("a.db"),
+        b"pub fn open(path: &str) {}\n",
+    );
+    let mut after = index_api(
+        &temp_dir.path().join("b.db"),
+        b"pub fn open(path: &str, create: bool) {}\n",
+    );
+
+    let report = diff_api(&mut before, &mut after).unwrap();
+
+    assert!(report.added.is_empty());
+    assert!(report.removed.is_empty());
+    assert_eq!(report.changed.len(), 1);
+    assert_eq!(report.changed[0].impact, ApiImpact::Breaking);
+    assert_eq!(
+        report.changed[0].after.signatures,
+        vec!["pub fn open(path: &str, create: bool)".to_string()]
+    );
+}
+
+#[test]
+fn test_api_diff_additive_only_is_not_breaking() {
+    let temp_dir = TempDir::new().unwrap();
+    let mut before = index_api(&temp_dir.path().join("a.db"), b"pub fn kept() {}\n");
+    let mut after = index_api(
+        &temp_dir.path().join("b.db"),
+        b"pub fn kept() {}\npub fn extra() {}\n",
+    );
+
+    let report = magellan::api_diff::diff_api(&mut before, &mut after).unwrap();
+
+    assert_eq!(report.added.len(), 1);
+    assert!(!report.is_breaking());
+}
+
+#[test]
+fn test_api_diff_missing_database_fails_without_creating_it() {
+    let temp_dir = TempDir::new().unwrap();
+    let existing = temp_dir.path().join("a.db");
+    drop(index_api(&existing, b"pub fn kept() {}\n"));
+    let missing = temp_dir.path().join("typo.db");
+
+    let bin_path = std::env::var("CARGO_BIN_EXE_magellan").unwrap();
+    let output = std::process::Command::new(&bin_path)
+        .arg("api-diff")
+        .arg("--db-a")
+        .arg(&existing)
+        .arg("--db-b")
+        .arg(&missing)
+        .output()
+        .expect("Failed to run magellan api-diff");
+
+    assert!(!output.status.success(), "{:?}", output);
+    assert!(
+        String::from_utf8_lossy(&output.stderr).contains("Database not found"),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(!missing.exists(), "api-diff created {}", missing.display());
+}
+
+#[test]
+fn test_api_only_drops_references_and_calls_from_private_code() {
+    let temp_dir = TempDir::new().unwrap();
+    let db_path = temp_dir.path().join("test.db");
+
+    let mut graph = CodeGraph::open(&db_path).unwrap();
+    graph.set_api_only(true);
+
+    graph
+        .index_file(
+            "lib.rs",
+            b"pub struct Config { pub n: u32 }\npub fn shared() {}\n",
+        )
+        .unwrap();
+    let source = b"pub fn api(c: &Config) -> u32 { shared(); c.n }\n\
+fn helper(c: &Config) -> u32 { shared(); c.n }\n";
+    graph.index_file("b.rs", source).unwrap();
+    graph.index_references("b.rs", source).unwrap();
+
+    // Only the public `api` body contributes; `helper` was never indexed
+    assert_eq!(graph.count_references().unwrap(), 1);
+    assert_eq!(graph.count_calls().unwrap(), 1);
+    assert!(graph
+        .callers_of_symbol("lib.rs", "shared")
+        .unwrap()
+        .iter()
+        .all(|call| call.caller == "api"));
+}
@@ -484,24 +484,15 @@ fn test_migration_preserves_chunk_content() {
         chunk_store.store_chunk(&chunk).unwrap();
     }
 
-    // Get original content from SQLite
-    let _original_content: String = {
-        let conn = rusqlite::Connection::open(&source_db).unwrap();
-        let mut stmt = conn
-            .prepare("SELECT content FROM code_chunks WHERE file_path = 'test.rs'")
-            .unwrap();
-        stmt.query_row([], |row| row.get(0)).unwrap()
-    };
-
     // Run migration
     run_migrate_backend(source_db, native_db.clone(), None, false).unwrap();
 
-    // Verify SQLite side table copy worked
-    let conn = rusqlite::Connection::open(&native_db).unwrap();
-    let mut stmt = conn
-        .prepare("SELECT content FROM code_chunks WHERE file_path = 'test.rs'")
-        .unwrap();
-    let content: String = stmt.query_row([], |row| row.get(0)).unwrap();
+    // Verify SQLite side table copy worked (content lives in chunk_content)
+    let content = ChunkStore::new(&native_db)
+        .get_chunk_by_span("test.rs", 0, utf8_content.len())
+        .unwrap()
+        .expect("Chunk should be migrated")
+        .content;
     assert_eq!(content, utf8_content, "UTF-8 content should be preserved");
 }
 
@@ -862,3 +853,146 @@ pub fn unique_to_file2() -> i32 {
         println!("  file2.rs: {} symbols", symbols_file2.len());
     }
 }
+
+/// Test helper: every table's schema and rows, in rowid order
+fn dump_database(db_path: &Path) -> Vec<String> {
+    let conn = rusqlite::Connection::open(db_path).unwrap();
+    let mut tables: Vec<(String, String)> = conn
+        .prepare("SELECT name, sql FROM sqlite_master WHERE type='table' ORDER BY name")
+        .unwrap()
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+        .unwrap()
+        .collect::<rusqlite::Result<_>>()
+        .unwrap();
+    tables.sort();
+
+    let mut dump = Vec::new();
+    for (name, sql) in tables {
+        dump.push(sql);
+        let mut stmt = conn
+            .prepare(&format!("SELECT * FROM {} ORDER BY rowid", name))
+            .unwrap();
+        let columns = stmt.column_count();
+        let rows = stmt
+            .query_map([], |row| {
+                (0..columns)
+                    .map(|i| row.get::<_, rusqlite::types::Value>(i))
+                    .collect::<rusqlite::Result<Vec<_>>>()
+            })
+            .unwrap();
+        for row in rows {
+            dump.push(format!("{}: {:?}", name, row.unwrap()));
+        }
+    }
+    dump
+}
+
+#[test]
+fn test_interrupted_migration_resumes_to_identical_output() {
+    use magellan::migrate_backend_cmd::{
+        run_migrate_backend_with_progress, MigrateBackendOptions, CHECKPOINT_TABLE,
+    };
+
+    let temp_dir = TempDir::new().unwrap();
+    let source_db = temp_dir.path().join("source.db");
+    let resumed_db = temp_dir.path().join("resumed.db");
+    let uninterrupted_db = temp_dir.path().join("uninterrupted.db");
+
+    {
+        let mut graph = CodeGraph::open(&source_db).unwrap();
+        for i in 0..4 {
+            let source = format!(
+                "pub fn entry_{i}() {{ helper_{i}(); }}\npub fn helper_{i}() {{}}\npub struct Item{i};\n"
+            );
+            graph
+                .index_file(&format!("src/file_{i}.rs"), source.as_bytes())
+                .unwrap();
+        }
+    }
+    let (source_entities, _) = get_graph_counts(&source_db).unwrap();
+
+    // Dry run plans the source counts without creating the output
+    let dry = run_migrate_backend(source_db.clone(), resumed_db.clone(), None, true).unwrap();
+    let planned_entities = dry
+        .planned
+        .iter()
+        .find(|count| count.table == "graph_entities")
+        .unwrap()
+        .rows;
+    assert_eq!(planned_entities, source_entities);
+    assert!(!resumed_db.exists());
+
+    // Interrupt after three committed batches of two rows
+    let options = MigrateBackendOptions {
+        batch_size: 2,
+        ..MigrateBackendOptions::default()
+    };
+    let mut batches = 0;
+    let interrupted = run_migrate_backend_with_progress(
+        source_db.clone(),
+        resumed_db.clone(),
+        None,
+        &options,
+        &mut |_| {
+            batches += 1;
+            if batches == 3 {
+                anyhow::bail!("interrupted");
+            }
+            Ok(())
+        },
+    );
+    assert!(interrupted.is_err());
+    let partial_entities: i64 = rusqlite::Connection::open(&resumed_db)
+        .unwrap()
+        .query_row("SELECT COUNT(*) FROM graph_entities", [], |row| row.get(0))
+        .unwrap();
+    assert_eq!(partial_entities, 6, "three batches of two rows committed");
+
+    // Resume picks up after the last committed batch
+    let resume_options = MigrateBackendOptions {
+        resume: true,
+        ..options
+    };
+    let mut first_report = None;
+    let resumed = run_migrate_backend_with_progress(
+        source_db.clone(),
+        resumed_db.clone(),
+        None,
+        &resume_options,
+        &mut |progress| {
+            first_report.get_or_insert(progress.clone());
+            Ok(())
+        },
+    )
+    .unwrap();
+    assert!(resumed.success && resumed.resumed);
+    assert_eq!(resumed.entities_migrated, source_entities);
+    let first_report = first_report.unwrap();
+    assert_eq!(first_report.table, "graph_entities");
+    assert_eq!(first_report.rows_copied, 8);
+
+    // Nothing left to resume once complete
+    assert!(run_migrate_backend_with_progress(
+        source_db.clone(),
+        resumed_db.clone(),
+        None,
+        &resume_options,
+        &mut |_| Ok(()),
+    )
+    .is_err());
+
+    run_migrate_backend_with_progress(
+        source_db,
+        uninterrupted_db.clone(),
+        None,
+        &options,
+        &mut |_| Ok(()),
+    )
+    .unwrap();
+
+    let resumed_dump = dump_database(&resumed_db);
+    assert!(!resumed_dump
+        .iter()
+        .any(|line| line.contains(CHECKPOINT_TABLE)));
+    assert_eq!(resumed_dump, dump_database(&uninterrupted_db));
+}
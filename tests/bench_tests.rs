@@ -0,0 +1,60 @@
+//! Tests for the `magellan bench` command.
+//!
+//! Bench must report one entry per run with the files and symbols indexed,
+//! and must leave the benchmarked directory untouched.
+
+use std::fs;
+use std::process::Command;
+use tempfile::TempDir;
+
+#[test]
+fn test_bench_reports_runs_without_writing_to_root() {
+    let temp_dir = TempDir::new().unwrap();
+    let root = temp_dir.path();
+    fs::create_dir_all(root.join("src")).unwrap();
+    fs::write(root.join("src/a.rs"), b"fn a() {}\nfn b() { a(); }").unwrap();
+    fs::write(root.join("src/c.rs"), b"fn c() {}").unwrap();
+
+    let bin_path = std::env::var("CARGO_BIN_EXE_magellan").unwrap();
+    let output = Command::new(&bin_path)
+        .arg("bench")
+        .arg("--root")
+        .arg(root)
+        .args(["--runs", "3", "--output", "json"])
+        .output()
+        .expect("Failed to run magellan binary");
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let data = &json["data"];
+    let runs = data["runs"].as_array().unwrap();
+    assert_eq!(runs.len(), 3);
+    for run in runs {
+        assert_eq!(run["files"], 2);
+        assert_eq!(run["symbols"], 3);
+    }
+    let min = data["min_ms"].as_f64().unwrap();
+    let median = data["median_ms"].as_f64().unwrap();
+    let max = data["max_ms"].as_f64().unwrap();
+    assert!(min <= median && median <= max, "{}", data);
+
+    // Only the two source files remain: no database was created under root
+    let mut entries: Vec<_> = walkdir::WalkDir::new(root)
+        .into_iter()
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().is_file())
+        .map(|entry| entry.path().strip_prefix(root).unwrap().to_path_buf())
+        .collect();
+    entries.sort();
+    assert_eq!(
+        entries,
+        vec![
+            std::path::PathBuf::from("src/a.rs"),
+            std::path::PathBuf::from("src/c.rs")
+        ]
+    );
+}
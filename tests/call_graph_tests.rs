@@ -373,3 +373,79 @@ fn helper() {}
     );
     assert_eq!(calls_parse, calls_tree, "Call facts should be identical");
 }
+
+#[test]
+fn test_skip_calls_records_references_but_no_calls() {
+    use magellan::CodeGraph;
+    use tempfile::TempDir;
+
+    let temp_dir = TempDir::new().unwrap();
+    let source = r#"
+struct Config;
+
+fn main() {
+    let config = Config;
+    parse(&config);
+}
+
+fn parse(_config: &Config) {}
+"#;
+
+    let index = |name: &str, skip_calls: bool| -> CodeGraph {
+        let mut graph = CodeGraph::open(temp_dir.path().join(name)).unwrap();
+        graph.set_skip_calls(skip_calls);
+        graph.index_file("test.rs", source.as_bytes()).unwrap();
+        graph
+            .index_references("test.rs", source.as_bytes())
+            .unwrap();
+        graph
+    };
+    let full = index("full.db", false);
+    let mut no_calls = index("no_calls.db", true);
+
+    assert!(full.count_references().unwrap() > 0);
+    assert_eq!(
+        no_calls.count_references().unwrap(),
+        full.count_references().unwrap()
+    );
+    assert!(full.count_calls().unwrap() > 0);
+    assert_eq!(no_calls.count_calls().unwrap(), 0);
+    assert!(no_calls
+        .calls_from_symbol("test.rs", "main")
+        .unwrap()
+        .is_empty());
+}
+
+#[test]
+#[allow(deprecated)]
+fn test_repeated_calls_keep_distinct_call_sites() {
+    use magellan::CodeGraph;
+    use tempfile::TempDir;
+
+    let temp_dir = TempDir::new().unwrap();
+    let mut graph = CodeGraph::open(temp_dir.path().join("test.db")).unwrap();
+
+    let source =
+        "fn b(x: i32) -> i32 {\n    x\n}\n\nfn a() {\n    b(1);\n    let y = 2;\n    b(b(y));\n}\n";
+    graph.index_file("test.rs", source.as_bytes()).unwrap();
+
+    let mut sites: Vec<(usize, usize)> = graph
+        .calls_from_symbol("test.rs", "a")
+        .unwrap()
+        .iter()
+        .inspect(|call| assert_eq!(call.callee, "b"))
+        .map(|call| (call.start_line, call.start_col))
+        .collect();
+    sites.sort_unstable();
+    // Line 6 once, line 8 twice (outer and nested call)
+    assert_eq!(sites, vec![(6, 4), (8, 4), (8, 6)]);
+
+    let mut caller_lines: Vec<usize> = graph
+        .callers_of_symbol("test.rs", "b")
+        .unwrap()
+        .iter()
+        .map(|call| call.start_line)
+        .collect();
+    caller_lines.sort_unstable();
+    assert_eq!(caller_lines, vec![6, 8, 8]);
+}
@@ -0,0 +1,157 @@
+//! Tests for the `magellan check --fail-on` CI gate
+//!
+//! Each failure category must turn the exit status nonzero on its own, and a
+//! clean index must pass.
+
+use magellan::CodeGraph;
+use std::fs;
+use std::path::Path;
+use std::process::{Command, Output};
+use tempfile::TempDir;
+
+fn bin_path() -> String {
+    std::env::var("CARGO_BIN_EXE_magellan").unwrap_or_else(|_| {
+        let mut path = std::env::current_exe().unwrap();
+        path.pop();
+        path.pop();
+        path.push("magellan");
+        path.to_str().unwrap().to_string()
+    })
+}
+
+/// Write `files` under `root` and index each one into `db_path`
+fn index_files(root: &Path, db_path: &Path, files: &[(&str, &str)]) {
+    let mut graph = CodeGraph::open(db_path).unwrap();
+    for (name, source) in files {
+        let path = root.join(name);
+        fs::write(&path, source).unwrap();
+        graph
+            .index_file(&path.to_string_lossy(), source.as_bytes())
+            .unwrap();
+    }
+}
+
+fn run_check(root: &Path, db_path: &Path, fail_on: &str) -> Output {
+    Command::new(bin_path())
+        .arg("check")
+        .arg("--db")
+        .arg(db_path)
+        .arg("--root")
+        .arg(root)
+        .arg("--fail-on")
+        .arg(fail_on)
+        .arg("--output")
+        .arg("json")
+        .output()
+        .expect("Failed to execute magellan check")
+}
+
+fn counts(output: &Output) -> serde_json::Value {
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let json: serde_json::Value = serde_json::from_str(stdout.trim()).unwrap();
+    json["data"]["counts"].clone()
+}
+
+const CLEAN: &str = "fn helper() {}\n\nfn main() {\n    helper();\n}\n";
+
+#[test]
+fn test_check_passes_on_clean_index() {
+    let temp_dir = TempDir::new().unwrap();
+    let root = temp_dir.path().join("src");
+    fs::create_dir(&root).unwrap();
+    let db_path = temp_dir.path().join("test.db");
+    index_files(&root, &db_path, &[("main.rs", CLEAN)]);
+
+    let output = run_check(&root, &db_path, "collisions,unresolved,stale");
+    assert_eq!(output.status.code(), Some(0), "{:?}", output);
+    let counts = counts(&output);
+    assert_eq!(counts["collisions"], 0);
+    assert_eq!(counts["unresolved"], 0);
+    assert_eq!(counts["stale"], 0);
+}
+
+#[test]
+fn test_check_fails_on_collisions() {
+    let temp_dir = TempDir::new().unwrap();
+    let root = temp_dir.path().join("src");
+    fs::create_dir(&root).unwrap();
+    let db_path = temp_dir.path().join("test.db");
+    index_files(
+        &root,
+        &db_path,
+        &[("main.rs", CLEAN), ("other.rs", "fn helper() {}\n")],
+    );
+
+    let output = run_check(&root, &db_path, "collisions");
+    assert_eq!(output.status.code(), Some(1), "{:?}", output);
+    assert!(counts(&output)["collisions"].as_u64().unwrap() > 0);
+
+    // Categories not selected do not fail the check
+    let output = run_check(&root, &db_path, "stale");
+    assert_eq!(output.status.code(), Some(0), "{:?}", output);
+}
+
+#[test]
+fn test_check_fails_on_unresolved_calls() {
+    let temp_dir = TempDir::new().unwrap();
+    let root = temp_dir.path().join("src");
+    fs::create_dir(&root).unwrap();
+    let db_path = temp_dir.path().join("test.db");
+    index_files(
+        &root,
+        &db_path,
+        &[
+            ("util.rs", "pub fn helper() {}\n"),
+            ("main.rs", "fn main() {\n    helper();\n}\n"),
+        ],
+    );
+
+    let output = run_check(&root, &db_path, "unresolved");
+    assert_eq!(output.status.code(), Some(0), "{:?}", output);
+
+    // Removing the callee leaves the call in main.rs without a target
+    {
+        let mut graph = CodeGraph::open(&db_path).unwrap();
+        graph
+            .delete_file(&root.join("util.rs").to_string_lossy())
+            .unwrap();
+    }
+    fs::remove_file(root.join("util.rs")).unwrap();
+
+    let output = run_check(&root, &db_path, "unresolved");
+    assert_eq!(output.status.code(), Some(1), "{:?}", output);
+    assert_eq!(counts(&output)["unresolved"], 1);
+
+    let output = run_check(&root, &db_path, "collisions");
+    assert_eq!(output.status.code(), Some(0), "{:?}", output);
+}
+
+#[test]
+fn test_check_fails_on_stale_files() {
+    let temp_dir = TempDir::new().unwrap();
+    let root = temp_dir.path().join("src");
+    fs::create_dir(&root).unwrap();
+    let db_path = temp_dir.path().join("test.db");
+    index_files(&root, &db_path, &[("main.rs", CLEAN)]);
+    fs::write(root.join("main.rs"), "fn changed() {}\n").unwrap();
+
+    let output = run_check(&root, &db_path, "stale");
+    assert_eq!(output.status.code(), Some(1), "{:?}", output);
+    assert_eq!(counts(&output)["stale"], 1);
+
+    let output = run_check(&root, &db_path, "collisions,unresolved");
+    assert_eq!(output.status.code(), Some(0), "{:?}", output);
+}
+
+#[test]
+fn test_check_errors_on_missing_database() {
+    let temp_dir = TempDir::new().unwrap();
+    let root = temp_dir.path().join("src");
+    fs::create_dir(&root).unwrap();
+    let db_path = temp_dir.path().join("typo.db");
+
+    // A missing database is an error, not a passing (empty) index
+    let output = run_check(&root, &db_path, "collisions");
+    assert_eq!(output.status.code(), Some(2), "{:?}", output);
+    assert!(!db_path.exists(), "check must not create the database");
+}
@@ -22,7 +22,7 @@
 //! 2. Chunks deleted on separate connection (two-phase commit)
 //! 3. chunks_deleted count returned in DeleteResult
 
-use magellan::CodeGraph;
+use magellan::{ChunkStore, CodeChunk, CodeGraph};
 use tempfile::TempDir;
 
 /// Helper to create a temporary test database.
@@ -246,6 +246,54 @@ pub fn helper_function(x: i32) -> i32 {
     );
 }
 
+/// Test: Identical chunk content is stored once and shared by hash.
+#[test]
+fn test_identical_chunk_content_stored_once() {
+    let temp_dir = setup_test_db();
+    let db_path = temp_dir.path().join("test.db");
+    let store = ChunkStore::new(&db_path);
+    store.ensure_schema().unwrap();
+
+    let body = "fn helper() -> u32 {\n    42\n}".to_string();
+    let first = CodeChunk::new("a.rs".to_string(), 0, body.len(), body.clone(), None, None);
+    let second = CodeChunk::new(
+        "b.rs".to_string(),
+        10,
+        10 + body.len(),
+        body.clone(),
+        None,
+        None,
+    );
+    store.store_chunk(&first).unwrap();
+    store.store_chunk(&second).unwrap();
+
+    let conn = rusqlite::Connection::open(&db_path).unwrap();
+    let content_rows = |conn: &rusqlite::Connection| -> i64 {
+        conn.query_row("SELECT COUNT(*) FROM chunk_content", [], |row| row.get(0))
+            .unwrap()
+    };
+    assert_eq!(store.count_chunks().unwrap(), 2);
+    assert_eq!(
+        content_rows(&conn),
+        1,
+        "Identical content should share one row"
+    );
+
+    // Reads join the shared content back into each chunk
+    let read_back = store
+        .get_chunk_by_span("b.rs", 10, 10 + body.len())
+        .unwrap()
+        .expect("Chunk should exist");
+    assert_eq!(read_back.content, body);
+    assert_eq!(store.get_chunks_for_file("a.rs").unwrap()[0].content, body);
+
+    // Content is released once the last referencing chunk is deleted
+    store.delete_chunks_for_file("a.rs").unwrap();
+    assert_eq!(content_rows(&conn), 1);
+    store.delete_chunks_for_file("b.rs").unwrap();
+    assert_eq!(content_rows(&conn), 0);
+}
+
 /// Test: Chunk query by symbol name returns correct chunks.
 #[test]
 fn test_chunk_by_symbol_query() {
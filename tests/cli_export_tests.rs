@@ -1146,6 +1146,75 @@ fn test_export_dot_cluster() {
     );
 }
 
+#[test]
+fn test_export_dot_calls_only_cluster_by_file() {
+    let temp_dir = TempDir::new().unwrap();
+    let db_path = temp_dir.path().join("magellan.db");
+    let output_path = temp_dir.path().join("calls.dot");
+
+    let bin_path = std::env::var("CARGO_BIN_EXE_magellan").unwrap_or_else(|_| {
+        let mut path = std::env::current_exe().unwrap();
+        path.pop();
+        path.pop();
+        path.push("magellan");
+        path.to_str().unwrap().to_string()
+    });
+
+    // Callees are indexed before their callers so every call resolves,
+    // except the one into unindexed code
+    let files = [
+        ("leaf.rs", "fn func_c() { external_thing(); }"),
+        ("middle.rs", "fn func_b() { func_c(); }"),
+        ("top.rs", "fn func_a() { func_b(); }"),
+    ];
+    {
+        let mut graph = magellan::CodeGraph::open(&db_path).unwrap();
+        for (name, source) in &files {
+            let file_path = temp_dir.path().join(name);
+            fs::write(&file_path, source).unwrap();
+            graph
+                .index_file(&file_path.to_string_lossy(), source.as_bytes())
+                .unwrap();
+        }
+    }
+
+    let output = Command::new(&bin_path)
+        .arg("export")
+        .arg("--db")
+        .arg(&db_path)
+        .arg("--format")
+        .arg("dot")
+        .arg("--calls-only")
+        .arg("--cluster-by-file")
+        .arg("--output")
+        .arg(&output_path)
+        .output()
+        .expect("Failed to execute magellan export");
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let dot = fs::read_to_string(&output_path).unwrap();
+    assert_eq!(
+        dot.matches("subgraph cluster_").count(),
+        files.len(),
+        "one cluster per indexed file:\n{}",
+        dot
+    );
+    for (name, _) in &files {
+        assert!(dot.contains(name), "cluster for {} missing:\n{}", name, dot);
+    }
+    assert!(dot.contains("[label=\"func_c\"]"), "{}", dot);
+    assert!(
+        !dot.contains("external_thing"),
+        "unresolved calls are dropped:\n{}",
+        dot
+    );
+    assert_eq!(dot.matches(" -> ").count(), 2, "{}", dot);
+}
+
 #[test]
 fn test_export_dot_filter_file() {
     // Verify file filtering works
@@ -1609,3 +1678,172 @@ fn main() {
     // If the parser doesn't extract calls for the test code, the test may produce
     // an empty CSV (header only). This is acceptable - verify the header structure is correct.
 }
+
+#[test]
+fn test_export_gzip_round_trips() {
+    use flate2::read::GzDecoder;
+    use std::io::Read;
+
+    let temp_dir = TempDir::new().unwrap();
+    let db_path = temp_dir.path().join("magellan.db");
+    let file_path = temp_dir.path().join("test.rs");
+
+    let bin_path = std::env::var("CARGO_BIN_EXE_magellan").unwrap_or_else(|_| {
+        let mut path = std::env::current_exe().unwrap();
+        path.pop();
+        path.pop();
+        path.push("magellan");
+        path.to_str().unwrap().to_string()
+    });
+
+    let source = "fn helper() {}\n\nfn main() {\n    helper();\n}\n";
+    fs::write(&file_path, source).unwrap();
+    {
+        let mut graph = magellan::CodeGraph::open(&db_path).unwrap();
+        let path_str = file_path.to_string_lossy().to_string();
+        graph.index_file(&path_str, source.as_bytes()).unwrap();
+    }
+
+    let gunzip = |bytes: &[u8]| {
+        let mut decoded = Vec::new();
+        GzDecoder::new(bytes).read_to_end(&mut decoded).unwrap();
+        decoded
+    };
+
+    for format in ["json", "jsonl", "csv", "dot", "sarif"] {
+        let plain_path = temp_dir.path().join(format!("export.{}", format));
+        let output = Command::new(&bin_path)
+            .args(["export", "--format", format, "--db"])
+            .arg(&db_path)
+            .arg("--output")
+            .arg(&plain_path)
+            .output()
+            .expect("Failed to execute magellan export");
+        assert!(output.status.success(), "{:?}", output);
+
+        // `.gz` is appended to the requested path
+        let output = Command::new(&bin_path)
+            .args(["export", "--format", format, "--gzip", "--db"])
+            .arg(&db_path)
+            .arg("--output")
+            .arg(&plain_path)
+            .output()
+            .expect("Failed to execute magellan export --gzip");
+        assert!(output.status.success(), "{:?}", output);
+
+        let gz_path = temp_dir.path().join(format!("export.{}.gz", format));
+        let plain = fs::read(&plain_path).unwrap();
+        assert_eq!(
+            gunzip(&fs::read(&gz_path).unwrap()),
+            plain,
+            "{} export differs after decompression",
+            format
+        );
+    }
+
+    // Compressed stdout
+    let plain = Command::new(&bin_path)
+        .args(["export", "--format", "jsonl", "--db"])
+        .arg(&db_path)
+        .output()
+        .unwrap();
+    let gzipped = Command::new(&bin_path)
+        .args(["export", "--format", "jsonl", "--gzip", "--db"])
+        .arg(&db_path)
+        .output()
+        .unwrap();
+    assert!(gzipped.status.success());
+    assert_eq!(gunzip(&gzipped.stdout), plain.stdout);
+}
+
+#[test]
+fn test_export_sqlite_flat_writes_queryable_tables() {
+    let temp_dir = TempDir::new().unwrap();
+    let db_path = temp_dir.path().join("magellan.db");
+    let flat_path = temp_dir.path().join("flat.db");
+
+    let bin_path = std::env::var("CARGO_BIN_EXE_magellan").unwrap_or_else(|_| {
+        let mut path = std::env::current_exe().unwrap();
+        path.pop();
+        path.pop();
+        path.push("magellan");
+        path.to_str().unwrap().to_string()
+    });
+
+    let lib_path = temp_dir.path().join("lib.rs");
+    let lib_source =
+        "fn helper() {}\n\nfn main() {\n    helper();\n    util();\n}\n\nstruct Point;\n";
+    let util_path = temp_dir.path().join("util.rs");
+    let util_source = "fn util() {}\n";
+    let lib_str = lib_path.to_string_lossy().to_string();
+    let (expected_symbols, expected_references) = {
+        let mut graph = magellan::CodeGraph::open(&db_path).unwrap();
+        for (path, source) in [(&util_path, util_source), (&lib_path, lib_source)] {
+            fs::write(path, source).unwrap();
+            graph
+                .index_file(&path.to_string_lossy(), source.as_bytes())
+                .unwrap();
+            graph
+                .index_references(&path.to_string_lossy(), source.as_bytes())
+                .unwrap();
+        }
+        (
+            graph.count_symbols().unwrap(),
+            graph.count_references().unwrap(),
+        )
+    };
+
+    let export = || {
+        let output = Command::new(&bin_path)
+            .args(["export", "--format", "sqlite-flat", "--db"])
+            .arg(&db_path)
+            .arg("--output")
+            .arg(&flat_path)
+            .output()
+            .expect("Failed to execute magellan export");
+        assert!(output.status.success(), "{:?}", output);
+        fs::read(&flat_path).unwrap()
+    };
+    let first = export();
+
+    let conn = rusqlite::Connection::open(&flat_path).unwrap();
+    let count = |sql: &str| -> usize {
+        conn.query_row(sql, [], |row| row.get::<_, i64>(0)).unwrap() as usize
+    };
+    assert_eq!(count("SELECT COUNT(*) FROM symbols"), expected_symbols);
+    assert_eq!(count("SELECT COUNT(*) FROM files"), 2);
+    let mut names: Vec<String> = conn
+        .prepare("SELECT name FROM symbols WHERE file_path = ?1 ORDER BY start_line")
+        .unwrap()
+        .query_map([&lib_str], |row| row.get(0))
+        .unwrap()
+        .map(Result::unwrap)
+        .collect();
+    assert_eq!(names, ["helper", "main", "Point"]);
+    names = conn
+        .prepare("SELECT caller || '->' || callee FROM calls")
+        .unwrap()
+        .query_map([], |row| row.get(0))
+        .unwrap()
+        .map(Result::unwrap)
+        .collect();
+    assert_eq!(names, ["main->helper", "main->util"]);
+    assert!(expected_references > 0);
+    assert_eq!(
+        count("SELECT COUNT(*) FROM \"references\""),
+        expected_references
+    );
+    drop(conn);
+
+    // Re-exporting replaces the file with identical content
+    assert_eq!(export(), first);
+
+    // A database file can't go to stdout
+    let output = Command::new(&bin_path)
+        .args(["export", "--format", "sqlite-flat", "--db"])
+        .arg(&db_path)
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("requires --output"));
+}
@@ -0,0 +1,102 @@
+//! CLI parse command tests
+//!
+//! `magellan parse` extracts symbols from stdin or a file without a database.
+
+use std::fs;
+use std::io::Write;
+use std::process::{Command, Output, Stdio};
+use tempfile::TempDir;
+
+fn bin_path() -> String {
+    std::env::var("CARGO_BIN_EXE_magellan").unwrap_or_else(|_| {
+        let mut path = std::env::current_exe().unwrap();
+        path.pop();
+        path.pop();
+        path.push("magellan");
+        path.to_str().unwrap().to_string()
+    })
+}
+
+fn run_parse(args: &[&str], stdin: &str) -> Output {
+    let mut child = Command::new(bin_path())
+        .arg("parse")
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("Failed to spawn magellan parse");
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(stdin.as_bytes())
+        .unwrap();
+    child.wait_with_output().unwrap()
+}
+
+fn symbol_names(output: &Output) -> Vec<String> {
+    let facts: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    facts
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|fact| fact["name"].as_str().unwrap().to_string())
+        .collect()
+}
+
+#[test]
+fn test_parse_stdin_with_explicit_language() {
+    let output = run_parse(
+        &["--lang", "rust", "--stdin"],
+        "struct Point;\n\nfn main() {}\n",
+    );
+    assert!(output.status.success(), "{:?}", output);
+    assert_eq!(symbol_names(&output), vec!["Point", "main"]);
+
+    let facts: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(facts[0]["kind"], "Class");
+    assert_eq!(facts[0]["file_path"], "<stdin>");
+}
+
+#[test]
+fn test_parse_stdin_infers_language_from_file_hint() {
+    let output = run_parse(
+        &["--file", "scratch.py", "--stdin"],
+        "def greet():\n    pass\n",
+    );
+    assert!(output.status.success(), "{:?}", output);
+    assert_eq!(symbol_names(&output), vec!["greet"]);
+
+    let facts: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(facts[0]["file_path"], "scratch.py");
+}
+
+#[test]
+fn test_parse_file_without_database() {
+    let temp_dir = TempDir::new().unwrap();
+    let file_path = temp_dir.path().join("lib.go");
+    fs::write(
+        &file_path,
+        "package lib\n\nfunc Add(a, b int) int { return a + b }\n",
+    )
+    .unwrap();
+
+    let output = run_parse(&["--file", file_path.to_str().unwrap()], "");
+    assert!(output.status.success(), "{:?}", output);
+    assert!(symbol_names(&output).contains(&"Add".to_string()));
+
+    // Nothing but the source file was created
+    assert_eq!(fs::read_dir(temp_dir.path()).unwrap().count(), 1);
+}
+
+#[test]
+fn test_parse_requires_a_language() {
+    let output = run_parse(&["--stdin"], "fn main() {}\n");
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("--lang"));
+
+    let output = run_parse(&["--lang", "cobol", "--stdin"], "");
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Unknown language"));
+}
@@ -1645,3 +1645,1713 @@ fn test_files_with_symbol_counts() {
         assert!(count_val > 0, "Each file should have at least 1 symbol");
     }
 }
+
+#[test]
+fn test_count_only_returns_minimal_json() {
+    let temp_dir = TempDir::new().unwrap();
+    let db_path = temp_dir.path().join("magellan.db");
+    let file_path = temp_dir.path().join("test.rs");
+
+    let bin_path = std::env::var("CARGO_BIN_EXE_magellan").unwrap_or_else(|_| {
+        let mut path = std::env::current_exe().unwrap();
+        path.pop();
+        path.pop();
+        path.push("magellan");
+        path.to_str().unwrap().to_string()
+    });
+
+    let source = r#"
+fn parse() {}
+fn parse_args() {}
+struct Parser;
+"#;
+    fs::write(&file_path, source).unwrap();
+
+    {
+        let mut graph = magellan::CodeGraph::open(&db_path).unwrap();
+        let path_str = file_path.to_string_lossy().to_string();
+        graph.index_file(&path_str, source.as_bytes()).unwrap();
+    }
+
+    let run = |args: &[&str]| -> serde_json::Value {
+        let output = Command::new(&bin_path)
+            .args(args)
+            .arg("--db")
+            .arg(&db_path)
+            .arg("--count-only")
+            .arg("--output")
+            .arg("json")
+            .output()
+            .expect("Failed to execute magellan");
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        assert!(
+            output.status.success(),
+            "stdout: {}\nstderr: {}",
+            stdout,
+            String::from_utf8_lossy(&output.stderr)
+        );
+        serde_json::from_str(stdout.trim()).expect("Output should be valid JSON")
+    };
+
+    let file_arg = file_path.to_string_lossy().to_string();
+
+    assert_eq!(
+        run(&["query", "--file", &file_arg, "--kind", "function"]),
+        serde_json::json!({"count": 2})
+    );
+    assert_eq!(
+        run(&["query", "--file", &file_arg, "--symbol", "Parser"]),
+        serde_json::json!({"count": 1})
+    );
+    assert_eq!(
+        run(&["find", "--name", "parse"]),
+        serde_json::json!({"count": 2})
+    );
+    assert_eq!(
+        run(&["find", "--name", "parse", "--path", &file_arg]),
+        serde_json::json!({"count": 1})
+    );
+
+    let output = Command::new(&bin_path)
+        .args(["find", "--name", "parse", "--count-only", "--with-context"])
+        .arg("--db")
+        .arg(&db_path)
+        .output()
+        .expect("Failed to execute magellan find");
+    assert!(
+        !output.status.success(),
+        "--count-only with --with-context should fail"
+    );
+}
+
+#[test]
+fn test_find_fuzzy_ranks_by_score() {
+    let temp_dir = TempDir::new().unwrap();
+    let db_path = temp_dir.path().join("magellan.db");
+    let file_path = temp_dir.path().join("test.rs");
+
+    let bin_path = std::env::var("CARGO_BIN_EXE_magellan").unwrap_or_else(|_| {
+        let mut path = std::env::current_exe().unwrap();
+        path.pop();
+        path.pop();
+        path.push("magellan");
+        path.to_str().unwrap().to_string()
+    });
+
+    let source = r#"
+fn header_timestamp() {}
+fn handle_message() {}
+fn unrelated() {}
+"#;
+    fs::write(&file_path, source).unwrap();
+
+    {
+        let mut graph = magellan::CodeGraph::open(&db_path).unwrap();
+        let path_str = file_path.to_string_lossy().to_string();
+        graph.index_file(&path_str, source.as_bytes()).unwrap();
+    }
+
+    let run = |query: &str| -> Vec<(String, i64)> {
+        let output = Command::new(&bin_path)
+            .args(["find", "--fuzzy", query, "--output", "json"])
+            .arg("--db")
+            .arg(&db_path)
+            .output()
+            .expect("Failed to execute magellan find");
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        assert!(
+            output.status.success(),
+            "stdout: {}\nstderr: {}",
+            stdout,
+            String::from_utf8_lossy(&output.stderr)
+        );
+        let json: serde_json::Value =
+            serde_json::from_str(stdout.trim()).expect("Output should be valid JSON");
+        json["data"]["matches"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|m| {
+                (
+                    m["name"].as_str().unwrap().to_string(),
+                    m["score"].as_i64().unwrap(),
+                )
+            })
+            .collect()
+    };
+
+    let ranked = run("hndmsg");
+    assert_eq!(ranked[0].0, "handle_message", "got {:?}", ranked);
+    let header_rank = ranked
+        .iter()
+        .position(|(name, _)| name == "header_timestamp");
+    assert!(header_rank.is_none_or(|rank| rank > 0));
+
+    // Both names match "hme"; the denser match ranks first
+    let ranked = run("hme");
+    let names: Vec<&str> = ranked.iter().map(|(name, _)| name.as_str()).collect();
+    assert_eq!(names, vec!["handle_message", "header_timestamp"]);
+    assert!(ranked[0].1 > ranked[1].1);
+
+    let output = Command::new(&bin_path)
+        .args(["find", "--fuzzy", "hndmsg", "--name", "handle_message"])
+        .arg("--db")
+        .arg(&db_path)
+        .output()
+        .expect("Failed to execute magellan find");
+    assert!(!output.status.success(), "--fuzzy with --name should fail");
+}
+
+#[test]
+fn test_get_by_symbol_id_resolves_ambiguous_name() {
+    let temp_dir = TempDir::new().unwrap();
+    let db_path = temp_dir.path().join("magellan.db");
+    let file_a = temp_dir.path().join("a.rs");
+    let file_b = temp_dir.path().join("b.rs");
+
+    let bin_path = std::env::var("CARGO_BIN_EXE_magellan").unwrap_or_else(|_| {
+        let mut path = std::env::current_exe().unwrap();
+        path.pop();
+        path.pop();
+        path.push("magellan");
+        path.to_str().unwrap().to_string()
+    });
+
+    // Same name in two files: only the symbol ID tells them apart
+    let source_a = "fn helper() -> u32 { 1 }\n";
+    let source_b = "fn helper() -> u32 { 2 }\n";
+    fs::write(&file_a, source_a).unwrap();
+    fs::write(&file_b, source_b).unwrap();
+
+    let path_b = file_b.to_string_lossy().to_string();
+    let symbol_id = {
+        let mut graph = magellan::CodeGraph::open(&db_path).unwrap();
+        graph
+            .index_file(&file_a.to_string_lossy(), source_a.as_bytes())
+            .unwrap();
+        graph.index_file(&path_b, source_b.as_bytes()).unwrap();
+        graph
+            .stable_symbol_id_by_name(&path_b, "helper")
+            .unwrap()
+            .expect("helper in b.rs should have a symbol ID")
+    };
+
+    let output = Command::new(&bin_path)
+        .args(["get", "--symbol-id", &symbol_id, "--output", "json"])
+        .arg("--db")
+        .arg(&db_path)
+        .output()
+        .expect("Failed to execute magellan get");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        output.status.success(),
+        "stdout: {}\nstderr: {}",
+        stdout,
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let json: serde_json::Value = serde_json::from_str(stdout.trim()).unwrap();
+    let data = &json["data"];
+    assert_eq!(data["symbol"]["name"], "helper");
+    assert_eq!(data["symbol"]["symbol_id"], symbol_id.as_str());
+    assert_eq!(data["symbol"]["span"]["file_path"], path_b.as_str());
+    assert!(data["content"].as_str().unwrap().contains("{ 2 }"));
+
+    // Unknown IDs are an error, not an empty result
+    let output = Command::new(&bin_path)
+        .args(["get", "--symbol-id", "0000000000000000"])
+        .arg("--db")
+        .arg(&db_path)
+        .output()
+        .expect("Failed to execute magellan get");
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("not found"));
+
+    // --symbol-id is exclusive with --file/--symbol
+    let output = Command::new(&bin_path)
+        .args(["get", "--symbol-id", &symbol_id, "--file", &path_b])
+        .arg("--db")
+        .arg(&db_path)
+        .output()
+        .expect("Failed to execute magellan get");
+    assert!(!output.status.success());
+}
+
+#[test]
+fn test_ignore_test_files_excludes_cfg_test_module() {
+    let temp_dir = TempDir::new().unwrap();
+    let db_path = temp_dir.path().join("magellan.db");
+    let file_path = temp_dir.path().join("lib.rs");
+
+    let bin_path = std::env::var("CARGO_BIN_EXE_magellan").unwrap_or_else(|_| {
+        let mut path = std::env::current_exe().unwrap();
+        path.pop();
+        path.pop();
+        path.push("magellan");
+        path.to_str().unwrap().to_string()
+    });
+
+    let source = r#"
+fn main() {
+    used();
+}
+
+fn used() {}
+
+fn unused() {
+    used();
+}
+
+#[cfg(test)]
+mod tests {
+    fn helper() {
+        super::used();
+    }
+}
+"#;
+    fs::write(&file_path, source).unwrap();
+
+    let path_str = file_path.to_string_lossy().to_string();
+    let main_id = {
+        let mut graph = magellan::CodeGraph::open(&db_path).unwrap();
+        graph.index_file(&path_str, source.as_bytes()).unwrap();
+
+        let symbols = graph.symbols_in_file(&path_str).unwrap();
+        let is_test = |name: &str| {
+            symbols
+                .iter()
+                .find(|s| s.name.as_deref() == Some(name))
+                .map(|s| s.is_test)
+                .unwrap()
+        };
+        assert!(is_test("helper"));
+        assert!(is_test("tests"));
+        assert!(!is_test("unused"));
+
+        graph
+            .stable_symbol_id_by_name(&path_str, "main")
+            .unwrap()
+            .expect("main should have a symbol ID")
+    };
+
+    let run = |args: &[&str]| {
+        let output = Command::new(&bin_path)
+            .args(args)
+            .arg("--db")
+            .arg(&db_path)
+            .output()
+            .expect("Failed to execute magellan");
+        assert!(
+            output.status.success(),
+            "stderr: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        String::from_utf8_lossy(&output.stdout).to_string()
+    };
+
+    let stdout = run(&["query", "--file", &path_str]);
+    assert!(stdout.contains("helper"), "{}", stdout);
+    let stdout = run(&["query", "--file", &path_str, "--ignore-test-files"]);
+    assert!(!stdout.contains("helper"), "{}", stdout);
+    assert!(stdout.contains("unused"), "{}", stdout);
+    let stdout = run(&["query", "--file", &path_str, "--only-test-files"]);
+    assert!(stdout.contains("helper"), "{}", stdout);
+    assert!(!stdout.contains("unused"), "{}", stdout);
+
+    let stdout = run(&["find", "--name", "helper", "--ignore-test-files"]);
+    assert!(!stdout.contains("helper()"), "{}", stdout);
+    let stdout = run(&[
+        "find",
+        "--name",
+        "helper",
+        "--ignore-test-files",
+        "--count-only",
+    ]);
+    assert_eq!(stdout.trim(), "0");
+    let stdout = run(&[
+        "find",
+        "--name",
+        "helper",
+        "--only-test-files",
+        "--count-only",
+    ]);
+    assert_eq!(stdout.trim(), "1");
+
+    let stdout = run(&["dead-code", "--entry", &main_id]);
+    assert!(stdout.contains("helper"), "{}", stdout);
+    let stdout = run(&["dead-code", "--entry", &main_id, "--ignore-test-files"]);
+    assert!(!stdout.contains("helper"), "{}", stdout);
+    assert!(stdout.contains("unused"), "{}", stdout);
+}
+
+#[test]
+fn test_chunk_by_symbol_returns_occurrences_across_files() {
+    let temp_dir = TempDir::new().unwrap();
+    let db_path = temp_dir.path().join("magellan.db");
+    let file_a = temp_dir.path().join("a.rs");
+    let file_b = temp_dir.path().join("b.rs");
+
+    let bin_path = std::env::var("CARGO_BIN_EXE_magellan").unwrap_or_else(|_| {
+        let mut path = std::env::current_exe().unwrap();
+        path.pop();
+        path.pop();
+        path.push("magellan");
+        path.to_str().unwrap().to_string()
+    });
+
+    let source_a = "struct A;\nimpl A {\n    fn new() -> Self { A }\n}\n";
+    let source_b = "struct B;\nimpl B {\n    fn new() -> Self { B }\n}\n";
+    fs::write(&file_a, source_a).unwrap();
+    fs::write(&file_b, source_b).unwrap();
+    let path_a = file_a.to_string_lossy().to_string();
+    let path_b = file_b.to_string_lossy().to_string();
+    {
+        let mut graph = magellan::CodeGraph::open(&db_path).unwrap();
+        // Index out of order: results must still be sorted by file
+        graph.index_file(&path_b, source_b.as_bytes()).unwrap();
+        graph.index_file(&path_a, source_a.as_bytes()).unwrap();
+    }
+
+    let run = |extra: &[&str]| -> serde_json::Value {
+        let output = Command::new(&bin_path)
+            .args(["chunk-by-symbol", "--symbol", "new", "--output", "json"])
+            .args(extra)
+            .arg("--db")
+            .arg(&db_path)
+            .output()
+            .expect("Failed to execute magellan chunk-by-symbol");
+        assert!(
+            output.status.success(),
+            "stderr: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        serde_json::from_str(String::from_utf8_lossy(&output.stdout).trim()).unwrap()
+    };
+
+    let json = run(&["--all-occurrences"]);
+    let chunks = json["data"].as_array().unwrap();
+    assert_eq!(chunks.len(), 2, "{}", json);
+    assert_eq!(chunks[0]["file_path"], path_a.as_str());
+    assert_eq!(chunks[1]["file_path"], path_b.as_str());
+    assert!(chunks[0]["content"].as_str().unwrap().contains("{ A }"));
+    assert!(chunks[1]["content"].as_str().unwrap().contains("{ B }"));
+
+    let json = run(&["--limit", "1"]);
+    let chunks = json["data"].as_array().unwrap();
+    assert_eq!(chunks.len(), 1);
+    assert_eq!(chunks[0]["file_path"], path_a.as_str());
+}
+
+#[test]
+fn test_slice_with_code_respects_max_bytes() {
+    let temp_dir = TempDir::new().unwrap();
+    let db_path = temp_dir.path().join("magellan.db");
+    let file_path = temp_dir.path().join("lib.rs");
+
+    let bin_path = std::env::var("CARGO_BIN_EXE_magellan").unwrap_or_else(|_| {
+        let mut path = std::env::current_exe().unwrap();
+        path.pop();
+        path.pop();
+        path.push("magellan");
+        path.to_str().unwrap().to_string()
+    });
+
+    let source = "fn helper() -> i32 {\n    42\n}\n\nfn main() {\n    helper();\n}\n";
+    fs::write(&file_path, source).unwrap();
+    {
+        let mut graph = magellan::CodeGraph::open(&db_path).unwrap();
+        graph
+            .index_file(&file_path.to_string_lossy(), source.as_bytes())
+            .unwrap();
+    }
+
+    let run = |extra: &[&str]| -> serde_json::Value {
+        let output = Command::new(&bin_path)
+            .args(["slice", "--target", "helper", "--direction", "backward"])
+            .args(["--with-code", "--output", "json"])
+            .args(extra)
+            .arg("--db")
+            .arg(&db_path)
+            .output()
+            .expect("Failed to execute magellan slice");
+        assert!(
+            output.status.success(),
+            "stderr: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        serde_json::from_str(String::from_utf8_lossy(&output.stdout).trim()).unwrap()
+    };
+
+    let json = run(&[]);
+    let symbols = json["data"]["code"]["symbols"].as_array().unwrap();
+    assert!(symbols.len() >= 2, "{}", json);
+    assert_eq!(symbols[0]["status"], "included");
+    assert!(symbols[0]["content"].as_str().unwrap().contains("42"));
+    assert!(
+        symbols
+            .iter()
+            .any(|s| s["status"] == "included"
+                && s["content"].as_str().unwrap().contains("helper();"))
+    );
+
+    // The target chunk alone is larger than the budget
+    let json = run(&["--max-bytes", "5"]);
+    let code = &json["data"]["code"];
+    assert_eq!(code["max_bytes"], 5);
+    assert_eq!(code["total_bytes"], 0);
+    for symbol in code["symbols"].as_array().unwrap() {
+        assert_eq!(symbol["status"], "over_budget", "{}", json);
+        assert!(symbol.get("content").is_none());
+    }
+}
+
+#[test]
+fn test_refs_group_by_file() {
+    let temp_dir = TempDir::new().unwrap();
+    let db_path = temp_dir.path().join("magellan.db");
+
+    let bin_path = std::env::var("CARGO_BIN_EXE_magellan").unwrap_or_else(|_| {
+        let mut path = std::env::current_exe().unwrap();
+        path.pop();
+        path.pop();
+        path.push("magellan");
+        path.to_str().unwrap().to_string()
+    });
+
+    let lib_path = temp_dir.path().join("lib.rs");
+    let a_path = temp_dir.path().join("a.rs");
+    let b_path = temp_dir.path().join("b.rs");
+    let files = [
+        (&lib_path, "pub fn helper() {}\n"),
+        (
+            &a_path,
+            "fn first() {\n    helper();\n}\n\nfn second() {\n    helper();\n}\n",
+        ),
+        (&b_path, "fn third() {\n    helper();\n}\n"),
+    ];
+    {
+        let mut graph = magellan::CodeGraph::open(&db_path).unwrap();
+        for (path, source) in &files {
+            fs::write(path, source).unwrap();
+            graph
+                .index_file(&path.to_string_lossy(), source.as_bytes())
+                .unwrap();
+        }
+    }
+
+    let output = Command::new(&bin_path)
+        .args(["refs", "--name", "helper", "--direction", "in"])
+        .arg("--path")
+        .arg(&lib_path)
+        .arg("--db")
+        .arg(&db_path)
+        .args(["--group-by-file", "--output", "json"])
+        .output()
+        .expect("Failed to execute magellan refs");
+    assert!(output.status.success(), "{:?}", output);
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let files = json["data"]["files"].as_object().unwrap();
+    assert_eq!(files.len(), 2, "{}", json);
+    assert_eq!(json["data"]["total_count"], 3);
+
+    let a_group = &files[a_path.to_str().unwrap()];
+    assert_eq!(a_group["count"], 2);
+    assert_eq!(a_group["references"].as_array().unwrap().len(), 2);
+    assert_eq!(files[b_path.to_str().unwrap()]["count"], 1);
+
+    // Groups are keyed in path order
+    let keys: Vec<&String> = files.keys().collect();
+    let mut sorted = keys.clone();
+    sorted.sort();
+    assert_eq!(keys, sorted);
+
+    // Human output lists each file with its count
+    let output = Command::new(&bin_path)
+        .args(["refs", "--name", "helper", "--direction", "in"])
+        .arg("--path")
+        .arg(&lib_path)
+        .arg("--db")
+        .arg(&db_path)
+        .arg("--group-by-file")
+        .output()
+        .expect("Failed to execute magellan refs");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("(3 in 2 files)"), "{}", stdout);
+    assert!(
+        stdout.contains(&format!("{} (2)", a_path.display())),
+        "{}",
+        stdout
+    );
+    assert!(
+        stdout.contains(&format!("{} (1)", b_path.display())),
+        "{}",
+        stdout
+    );
+}
+
+#[test]
+fn test_refs_since_keeps_only_references_on_changed_lines() {
+    let temp_dir = TempDir::new().unwrap();
+    let repo = temp_dir.path().join("repo");
+    let db_path = temp_dir.path().join("magellan.db");
+    fs::create_dir_all(&repo).unwrap();
+
+    let bin_path = std::env::var("CARGO_BIN_EXE_magellan").unwrap();
+    let git = |args: &[&str]| {
+        let output = Command::new("git")
+            .arg("-C")
+            .arg(&repo)
+            .args(["-c", "user.name=test", "-c", "user.email=test@example.com"])
+            .args(args)
+            .output()
+            .expect("failed to execute git");
+        assert!(output.status.success(), "git {:?}: {:?}", args, output);
+    };
+
+    // Committed: one caller in a.rs and one in b.rs
+    fs::write(repo.join("lib.rs"), "pub fn helper() {}\n").unwrap();
+    fs::write(repo.join("a.rs"), "fn first() {\n    helper();\n}\n").unwrap();
+    fs::write(repo.join("b.rs"), "fn third() {\n    helper();\n}\n").unwrap();
+    git(&["init", "-q"]);
+    git(&["add", "."]);
+    git(&["commit", "-q", "-m", "initial"]);
+
+    // Working tree: a new caller appended to a.rs, and an untracked file
+    fs::write(
+        repo.join("a.rs"),
+        "fn first() {\n    helper();\n}\n\nfn second() {\n    helper();\n}\n",
+    )
+    .unwrap();
+    fs::write(repo.join("c.rs"), "fn fourth() {\n    helper();\n}\n").unwrap();
+    {
+        let mut graph = magellan::CodeGraph::open(&db_path).unwrap();
+        for name in ["lib.rs", "a.rs", "b.rs", "c.rs"] {
+            let path = repo.join(name);
+            let source = fs::read(&path).unwrap();
+            graph.index_file(&path.to_string_lossy(), &source).unwrap();
+        }
+    }
+
+    let refs = |extra: &[&str]| {
+        let output = Command::new(&bin_path)
+            .args(["refs", "--name", "helper", "--direction", "in"])
+            .arg("--path")
+            .arg(repo.join("lib.rs"))
+            .arg("--db")
+            .arg(&db_path)
+            .arg("--root")
+            .arg(&repo)
+            .args(["--output", "json"])
+            .args(extra)
+            .output()
+            .expect("Failed to execute magellan refs");
+        assert!(output.status.success(), "{:?}", output);
+        let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+        let mut callers: Vec<String> = json["data"]["references"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|reference| reference["referenced_symbol"].as_str().unwrap().to_string())
+            .collect();
+        callers.sort();
+        callers
+    };
+
+    assert_eq!(refs(&[]), vec!["first", "fourth", "second", "third"]);
+    // Only the appended caller and the untracked file are new since HEAD
+    assert_eq!(refs(&["--since", "HEAD"]), vec!["fourth", "second"]);
+
+    // User diff settings that change the path prefixes make no difference
+    git(&["config", "diff.noprefix", "true"]);
+    git(&["config", "diff.mnemonicPrefix", "true"]);
+    assert_eq!(refs(&["--since", "HEAD"]), vec!["fourth", "second"]);
+
+    // A ref that looks like an option is rejected, not passed to git diff
+    let output = Command::new(&bin_path)
+        .args(["refs", "--name", "helper", "--direction", "in"])
+        .arg("--path")
+        .arg(repo.join("lib.rs"))
+        .arg("--db")
+        .arg(&db_path)
+        .arg("--root")
+        .arg(&repo)
+        .args(["--since", "--output=/dev/null"])
+        .output()
+        .expect("Failed to execute magellan refs");
+    assert!(!output.status.success());
+    assert!(
+        String::from_utf8_lossy(&output.stderr).contains("is not a commit"),
+        "{:?}",
+        output
+    );
+}
+
+#[test]
+fn test_column_unit_converts_columns_after_multibyte_text() {
+    let temp_dir = TempDir::new().unwrap();
+    let db_path = temp_dir.path().join("magellan.db");
+    let file_path = temp_dir.path().join("lib.rs");
+    // The crab is 4 UTF-8 bytes, 1 char, and 2 UTF-16 code units
+    let source =
+        "fn target() {}\nfn caller() { let _s = \"🦀\"; target(); }\n/* 🦀 */ fn after() {}\n";
+    fs::write(&file_path, source).unwrap();
+    {
+        let mut graph = magellan::CodeGraph::open(&db_path).unwrap();
+        graph
+            .index_file(&file_path.to_string_lossy(), source.as_bytes())
+            .unwrap();
+    }
+
+    let bin_path = std::env::var("CARGO_BIN_EXE_magellan").unwrap();
+    let run = |args: &[&str], unit: &str| -> serde_json::Value {
+        let output = Command::new(&bin_path)
+            .args(args)
+            .arg("--db")
+            .arg(&db_path)
+            .args(["--output", "json", "--column-unit", unit])
+            .output()
+            .expect("Failed to execute magellan");
+        assert!(output.status.success(), "{:?}", output);
+        serde_json::from_slice(&output.stdout).unwrap()
+    };
+    let file = file_path.to_str().unwrap();
+
+    let query_cols = |unit: &str| {
+        let json = run(&["query", "--file", file, "--symbol", "after"], unit);
+        assert_eq!(json["data"]["column_unit"], unit);
+        let span = &json["data"]["symbols"][0]["span"];
+        (
+            span["start_col"].as_u64().unwrap(),
+            span["end_col"].as_u64().unwrap(),
+        )
+    };
+    assert_eq!(query_cols("byte"), (11, 24));
+    assert_eq!(query_cols("char"), (8, 21));
+    assert_eq!(query_cols("utf16"), (9, 22));
+
+    let find_col = |unit: &str| {
+        let json = run(&["find", "--name", "after"], unit);
+        assert_eq!(json["data"]["column_unit"], unit);
+        json["data"]["matches"][0]["span"]["start_col"]
+            .as_u64()
+            .unwrap()
+    };
+    assert_eq!(find_col("byte"), 11);
+    assert_eq!(find_col("char"), 8);
+    assert_eq!(find_col("utf16"), 9);
+
+    let refs_col = |unit: &str| {
+        let json = run(
+            &[
+                "refs",
+                "--name",
+                "target",
+                "--path",
+                file,
+                "--direction",
+                "in",
+            ],
+            unit,
+        );
+        assert_eq!(json["data"]["column_unit"], unit);
+        let span = &json["data"]["references"][0]["span"];
+        assert_eq!(span["start_line"], 2);
+        span["start_col"].as_u64().unwrap()
+    };
+    let byte_col = refs_col("byte");
+    assert_eq!(byte_col, 31);
+    assert_eq!(refs_col("char"), byte_col - 3);
+    assert_eq!(refs_col("utf16"), byte_col - 2);
+}
+
+#[test]
+fn test_column_unit_reads_relative_paths_from_root() {
+    let temp_dir = TempDir::new().unwrap();
+    let root = temp_dir.path().join("proj");
+    fs::create_dir_all(root.join("src")).unwrap();
+    fs::write(root.join("src/lib.rs"), "/* 🦀 */ fn after() {}\n").unwrap();
+    let root = fs::canonicalize(root).unwrap();
+    let db_path = temp_dir.path().join("magellan.db");
+    {
+        let mut graph = magellan::CodeGraph::open(&db_path).unwrap();
+        graph
+            .set_path_style(magellan::PathStyle::Relative, &root)
+            .unwrap();
+        graph.scan_directory(&root, None).unwrap();
+    }
+
+    // Run outside the project: the stored path only resolves from the root
+    let output = Command::new(std::env::var("CARGO_BIN_EXE_magellan").unwrap())
+        .current_dir(temp_dir.path())
+        .args(["query", "--file", "src/lib.rs", "--symbol", "after"])
+        .arg("--db")
+        .arg(&db_path)
+        .args(["--output", "json", "--column-unit", "char"])
+        .output()
+        .expect("Failed to execute magellan");
+    assert!(output.status.success(), "{:?}", output);
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["data"]["column_unit"], "char");
+    assert_eq!(json["data"]["symbols"][0]["span"]["start_col"], 8);
+}
+
+#[test]
+fn test_find_and_query_defined_in_prefix() {
+    let temp_dir = TempDir::new().unwrap();
+    let db_path = temp_dir.path().join("magellan.db");
+
+    let bin_path = std::env::var("CARGO_BIN_EXE_magellan").unwrap_or_else(|_| {
+        let mut path = std::env::current_exe().unwrap();
+        path.pop();
+        path.pop();
+        path.push("magellan");
+        path.to_str().unwrap().to_string()
+    });
+
+    let lib_path = temp_dir.path().join("lib.rs");
+    let source = "mod net {\n    pub struct Socket;\n    pub fn connect() {}\n}\n\n\
+                  mod network {\n    pub fn ping() {}\n}\n";
+    fs::write(&lib_path, source).unwrap();
+    {
+        let mut graph = magellan::CodeGraph::open(&db_path).unwrap();
+        graph
+            .index_file(&lib_path.to_string_lossy(), source.as_bytes())
+            .unwrap();
+    }
+
+    let names = |output: &std::process::Output, field: &str| -> Vec<String> {
+        let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+        json["data"][field]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|m| m["name"].as_str().unwrap().to_string())
+            .collect()
+    };
+
+    // `crate::net` stops at the segment boundary, so `network` is excluded
+    let output = Command::new(&bin_path)
+        .args(["find", "--defined-in", "crate::net", "--output", "json"])
+        .arg("--db")
+        .arg(&db_path)
+        .output()
+        .expect("Failed to execute magellan find");
+    assert!(output.status.success(), "{:?}", output);
+    assert_eq!(names(&output, "matches"), ["Socket", "connect", "net"]);
+
+    let output = Command::new(&bin_path)
+        .args(["find", "--defined-in", "crate::net", "--kind", "function"])
+        .args(["--output", "json", "--db"])
+        .arg(&db_path)
+        .output()
+        .expect("Failed to execute magellan find");
+    assert!(output.status.success(), "{:?}", output);
+    assert_eq!(names(&output, "matches"), ["connect"]);
+
+    // `query` no longer needs --file when --defined-in is given
+    let output = Command::new(&bin_path)
+        .args(["query", "--defined-in", "crate::network", "--kind", "fn"])
+        .args(["--output", "json", "--db"])
+        .arg(&db_path)
+        .output()
+        .expect("Failed to execute magellan query");
+    assert!(output.status.success(), "{:?}", output);
+    assert_eq!(names(&output, "symbols"), ["ping"]);
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["data"]["defined_in"], "crate::network");
+
+    let output = Command::new(&bin_path)
+        .args(["find", "--defined-in", "crate::ne", "--db"])
+        .arg(&db_path)
+        .output()
+        .expect("Failed to execute magellan find");
+    assert!(output.status.success(), "{:?}", output);
+    assert!(String::from_utf8_lossy(&output.stdout).contains("No symbols defined in"));
+}
+
+#[test]
+fn test_find_and_query_filter_by_visibility() {
+    let temp_dir = TempDir::new().unwrap();
+    let db_path = temp_dir.path().join("magellan.db");
+
+    let bin_path = std::env::var("CARGO_BIN_EXE_magellan").unwrap_or_else(|_| {
+        let mut path = std::env::current_exe().unwrap();
+        path.pop();
+        path.pop();
+        path.push("magellan");
+        path.to_str().unwrap().to_string()
+    });
+
+    let lib_path = temp_dir.path().join("lib.rs");
+    let source = "pub fn open() {}\npub(crate) fn open_raw() {}\nfn open_inner() {}\n";
+    fs::write(&lib_path, source).unwrap();
+    let lib_str = lib_path.to_string_lossy().to_string();
+    {
+        let mut graph = magellan::CodeGraph::open(&db_path).unwrap();
+        graph.index_file(&lib_str, source.as_bytes()).unwrap();
+
+        let symbols = graph.symbols_in_file(&lib_str).unwrap();
+        let visibility = |name: &str| {
+            symbols
+                .iter()
+                .find(|s| s.name.as_deref() == Some(name))
+                .and_then(|s| s.visibility.clone())
+        };
+        assert_eq!(visibility("open").as_deref(), Some("pub"));
+        assert_eq!(visibility("open_raw").as_deref(), Some("pub(crate)"));
+        assert_eq!(visibility("open_inner").as_deref(), Some("private"));
+    }
+
+    let run = |args: &[&str]| {
+        let output = Command::new(&bin_path)
+            .args(args)
+            .arg("--db")
+            .arg(&db_path)
+            .output()
+            .expect("Failed to execute magellan");
+        assert!(output.status.success(), "{:?}", output);
+        let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+        json
+    };
+    let names = |json: &serde_json::Value, field: &str| -> Vec<String> {
+        json["data"][field]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|m| m["name"].as_str().unwrap().to_string())
+            .collect()
+    };
+
+    for (visibility, expected) in [
+        ("pub", "open"),
+        ("crate", "open_raw"),
+        ("private", "open_inner"),
+    ] {
+        let json = run(&[
+            "query",
+            "--file",
+            &lib_str,
+            "--visibility",
+            visibility,
+            "--output",
+            "json",
+        ]);
+        assert_eq!(names(&json, "symbols"), [expected]);
+
+        let json = run(&[
+            "find",
+            "--name",
+            "open",
+            "--visibility",
+            visibility,
+            "--output",
+            "json",
+        ]);
+        assert_eq!(names(&json, "matches"), [expected]);
+
+        let json = run(&[
+            "find",
+            "--count-only",
+            "--visibility",
+            visibility,
+            "--output",
+            "json",
+        ]);
+        assert_eq!(json["count"], 1, "{}", json);
+    }
+}
+
+#[test]
+fn test_find_deprecated_lists_flagged_symbols_with_message() {
+    let temp_dir = TempDir::new().unwrap();
+    let db_path = temp_dir.path().join("magellan.db");
+
+    let bin_path = std::env::var("CARGO_BIN_EXE_magellan").unwrap_or_else(|_| {
+        let mut path = std::env::current_exe().unwrap();
+        path.pop();
+        path.pop();
+        path.push("magellan");
+        path.to_str().unwrap().to_string()
+    });
+
+    let lib_path = temp_dir.path().join("lib.rs");
+    let source = "#[deprecated = \"use bar\"]\npub fn foo() {}\n\npub fn bar() {}\n";
+    fs::write(&lib_path, source).unwrap();
+    let lib_str = lib_path.to_string_lossy().to_string();
+    {
+        let mut graph = magellan::CodeGraph::open(&db_path).unwrap();
+        graph.index_file(&lib_str, source.as_bytes()).unwrap();
+
+        let symbols = graph.symbols_in_file(&lib_str).unwrap();
+        let status = |name: &str| {
+            symbols
+                .iter()
+                .find(|s| s.name.as_deref() == Some(name))
+                .map(|s| (s.deprecated, s.deprecation_message.clone()))
+                .unwrap()
+        };
+        assert_eq!(status("foo"), (true, Some("use bar".to_string())));
+        assert_eq!(status("bar"), (false, None));
+    }
+
+    let output = Command::new(&bin_path)
+        .args(["find", "--deprecated", "--output", "json", "--db"])
+        .arg(&db_path)
+        .output()
+        .expect("Failed to execute magellan");
+    assert!(output.status.success(), "{:?}", output);
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let symbols = json["data"]["symbols"].as_array().unwrap();
+    assert_eq!(json["data"]["count"], 1, "{}", json);
+    assert_eq!(symbols[0]["name"], "foo");
+    assert_eq!(symbols[0]["message"], "use bar");
+
+    let output = Command::new(&bin_path)
+        .args(["find", "--deprecated", "--name", "bar", "--db"])
+        .arg(&db_path)
+        .output()
+        .expect("Failed to execute magellan");
+    assert!(output.status.success(), "{:?}", output);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("No deprecated symbols"), "{}", stdout);
+}
+
+#[test]
+fn test_collisions_resolve_suggestions_differentiate_modules() {
+    let temp_dir = TempDir::new().unwrap();
+    let db_path = temp_dir.path().join("magellan.db");
+
+    let bin_path = std::env::var("CARGO_BIN_EXE_magellan").unwrap_or_else(|_| {
+        let mut path = std::env::current_exe().unwrap();
+        path.pop();
+        path.pop();
+        path.push("magellan");
+        path.to_str().unwrap().to_string()
+    });
+
+    // Same function name in two modules whose files share a name
+    {
+        let mut graph = magellan::CodeGraph::open(&db_path).unwrap();
+        for module in ["net", "db"] {
+            let dir = temp_dir.path().join("src").join(module);
+            fs::create_dir_all(&dir).unwrap();
+            let path = dir.join("client.rs");
+            let source = "pub fn connect() {}\n";
+            fs::write(&path, source).unwrap();
+            graph
+                .index_file(&path.to_string_lossy(), source.as_bytes())
+                .unwrap();
+        }
+    }
+
+    let run = || {
+        let output = Command::new(&bin_path)
+            .args(["collisions", "--resolve-suggestions", "--output", "json"])
+            .arg("--db")
+            .arg(&db_path)
+            .output()
+            .expect("Failed to execute magellan");
+        assert!(output.status.success(), "{:?}", output);
+        let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+        json["data"]["groups"].clone()
+    };
+
+    let groups = run();
+    assert_eq!(groups.as_array().unwrap().len(), 1, "{}", groups);
+    let candidates = groups[0]["candidates"].as_array().unwrap();
+    let mut suggested: Vec<(String, String, String)> = candidates
+        .iter()
+        .map(|candidate| {
+            let suggestion = &candidate["suggestion"];
+            (
+                candidate["file_path"].as_str().unwrap().to_string(),
+                suggestion["path_prefix"].as_str().unwrap().to_string(),
+                suggestion["qualified_name"].as_str().unwrap().to_string(),
+            )
+        })
+        .collect();
+    suggested.sort();
+    let (db_file, db_prefix, db_name) = &suggested[0];
+    let (net_file, net_prefix, net_name) = &suggested[1];
+    assert!(db_file.ends_with("src/db/client.rs"), "{}", db_file);
+    assert!(net_file.ends_with("src/net/client.rs"), "{}", net_file);
+    assert_eq!(db_prefix, "db/client.rs");
+    assert_eq!(net_prefix, "net/client.rs");
+    assert_eq!(db_name, "db/client.rs::connect");
+    assert_eq!(net_name, "net/client.rs::connect");
+
+    // Same database, same suggestions
+    assert_eq!(run(), groups);
+}
+
+#[test]
+fn test_get_file_outline_nests_module_struct_and_method() {
+    let temp_dir = TempDir::new().unwrap();
+    let db_path = temp_dir.path().join("magellan.db");
+
+    let bin_path = std::env::var("CARGO_BIN_EXE_magellan").unwrap_or_else(|_| {
+        let mut path = std::env::current_exe().unwrap();
+        path.pop();
+        path.pop();
+        path.push("magellan");
+        path.to_str().unwrap().to_string()
+    });
+
+    let lib_path = temp_dir.path().join("lib.rs");
+    let source = "pub mod shapes {\n    pub struct Point {\n        x: i32,\n    }\n\n    impl Point {\n        pub fn new() -> Self {\n            Point { x: 0 }\n        }\n    }\n}\n\nfn main() {}\n";
+    fs::write(&lib_path, source).unwrap();
+    let lib_str = lib_path.to_string_lossy().to_string();
+    {
+        let mut graph = magellan::CodeGraph::open(&db_path).unwrap();
+        graph.index_file(&lib_str, source.as_bytes()).unwrap();
+    }
+
+    let output = Command::new(&bin_path)
+        .args([
+            "get-file",
+            "--file",
+            &lib_str,
+            "--outline",
+            "--output",
+            "json",
+        ])
+        .arg("--db")
+        .arg(&db_path)
+        .output()
+        .expect("Failed to execute magellan");
+    assert!(output.status.success(), "{:?}", output);
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let symbols = json["data"]["symbols"].as_array().unwrap();
+
+    let summary = |node: &serde_json::Value| {
+        (
+            node["name"].as_str().unwrap().to_string(),
+            node["kind"].as_str().unwrap().to_string(),
+            node["start_line"].as_u64().unwrap(),
+            node["end_line"].as_u64().unwrap(),
+        )
+    };
+    assert_eq!(symbols.len(), 2);
+    assert_eq!(summary(&symbols[0]), ("shapes".into(), "mod".into(), 1, 11));
+    assert_eq!(summary(&symbols[1]), ("main".into(), "fn".into(), 13, 13));
+
+    let module_children = symbols[0]["children"].as_array().unwrap();
+    assert_eq!(module_children.len(), 1, "method nests under its struct");
+    assert_eq!(
+        summary(&module_children[0]),
+        ("Point".into(), "struct".into(), 2, 4)
+    );
+    let methods = module_children[0]["children"].as_array().unwrap();
+    assert_eq!(methods.len(), 1);
+    assert_eq!(summary(&methods[0]), ("new".into(), "fn".into(), 7, 9));
+    assert!(methods[0].get("children").is_none());
+}
+
+#[test]
+fn test_get_by_symbol_id_without_chunk_reads_relative_path_from_root() {
+    let temp_dir = TempDir::new().unwrap();
+    let root = temp_dir.path().join("proj");
+    fs::create_dir_all(root.join("src")).unwrap();
+    let source = "pub fn helper() -> u32 { 1 }\n";
+    fs::write(root.join("src/lib.rs"), source).unwrap();
+    let root = fs::canonicalize(root).unwrap();
+    let db_path = temp_dir.path().join("magellan.db");
+    let symbol_id = {
+        let mut graph = magellan::CodeGraph::open(&db_path).unwrap();
+        graph
+            .set_path_style(magellan::PathStyle::Relative, &root)
+            .unwrap();
+        graph.scan_directory(&root, None).unwrap();
+        graph
+            .stable_symbol_id_by_name("src/lib.rs", "helper")
+            .unwrap()
+            .expect("helper should have a symbol ID")
+    };
+    // Databases indexed before chunks were stored have none to read from
+    let conn = rusqlite::Connection::open(&db_path).unwrap();
+    conn.execute("DELETE FROM code_chunks", []).unwrap();
+    drop(conn);
+
+    // Run outside the project so the stored path does not resolve from the CWD
+    let output = Command::new(env!("CARGO_BIN_EXE_magellan"))
+        .current_dir(temp_dir.path())
+        .args(["get", "--symbol-id", &symbol_id, "--output", "json"])
+        .arg("--db")
+        .arg(&db_path)
+        .output()
+        .expect("Failed to execute magellan get");
+    assert!(output.status.success(), "{:?}", output);
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["data"]["content"], source.trim_end());
+}
+
+#[test]
+fn test_ast_subtree_reads_relative_path_from_root() {
+    let temp_dir = TempDir::new().unwrap();
+    let root = temp_dir.path().join("proj");
+    fs::create_dir_all(root.join("src")).unwrap();
+    let source = "// header\npub fn helper() -> u32 { 1 }\n";
+    fs::write(root.join("src/lib.rs"), source).unwrap();
+    let root = fs::canonicalize(root).unwrap();
+    let db_path = temp_dir.path().join("magellan.db");
+    {
+        let mut graph = magellan::CodeGraph::open(&db_path).unwrap();
+        graph
+            .set_path_style(magellan::PathStyle::Relative, &root)
+            .unwrap();
+        graph.scan_directory(&root, None).unwrap();
+    }
+
+    // Run outside the project so the stored path does not resolve from the CWD
+    let position = source.find("pub fn").unwrap().to_string();
+    let output = Command::new(env!("CARGO_BIN_EXE_magellan"))
+        .current_dir(temp_dir.path())
+        .args(["ast", "--file", "src/lib.rs", "--position", &position])
+        .args(["--subtree", "--output", "json"])
+        .arg("--db")
+        .arg(&db_path)
+        .output()
+        .expect("Failed to execute magellan ast");
+    assert!(output.status.success(), "{:?}", output);
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let tree = &json["data"]["tree"];
+    assert_eq!(tree["kind"], "function_item", "{}", json);
+    // Line numbers are only known when the source was read
+    assert_eq!(tree["start_line"], 2, "{}", json);
+}
+
+#[test]
+fn test_get_file_line_range_clamps_and_rejects_inverted() {
+    let temp_dir = TempDir::new().unwrap();
+    let db_path = temp_dir.path().join("magellan.db");
+
+    let bin_path = std::env::var("CARGO_BIN_EXE_magellan").unwrap_or_else(|_| {
+        let mut path = std::env::current_exe().unwrap();
+        path.pop();
+        path.pop();
+        path.push("magellan");
+        path.to_str().unwrap().to_string()
+    });
+
+    let lib_path = temp_dir.path().join("lib.rs");
+    let source = "fn one() {}\n\nfn two() {\n    one();\n}\n\nfn three() {}\n";
+    fs::write(&lib_path, source).unwrap();
+    let lib_str = lib_path.to_string_lossy().to_string();
+    {
+        let mut graph = magellan::CodeGraph::open(&db_path).unwrap();
+        graph.index_file(&lib_str, source.as_bytes()).unwrap();
+    }
+
+    let get_file = |range: &str, extra: &[&str]| {
+        Command::new(&bin_path)
+            .args(["get-file", "--file", &lib_str, "--line-range", range])
+            .args(extra)
+            .arg("--db")
+            .arg(&db_path)
+            .output()
+            .expect("Failed to execute magellan")
+    };
+
+    let output = get_file("3:5", &["--output", "json"]);
+    assert!(output.status.success(), "{:?}", output);
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["data"]["content"], "fn two() {\n    one();\n}");
+    assert_eq!(json["data"]["start_line"], 3);
+    assert_eq!(json["data"]["end_line"], 5);
+    assert_eq!(json["data"]["total_lines"], 7);
+    assert_eq!(json["data"]["clamped"], false);
+    assert_eq!(json["data"]["source"], "disk");
+
+    // An end past the last line is clamped, and human output says so
+    let output = get_file("7:40", &["--output", "json"]);
+    assert!(output.status.success(), "{:?}", output);
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["data"]["content"], "fn three() {}");
+    assert_eq!(json["data"]["end_line"], 7);
+    assert_eq!(json["data"]["requested_end_line"], 40);
+    assert_eq!(json["data"]["clamped"], true);
+
+    let output = get_file("6:40", &[]);
+    assert!(output.status.success(), "{:?}", output);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("// Lines 6-7 of"), "{}", stdout);
+    assert!(stdout.contains("fn three() {}"), "{}", stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("clamped to 7"), "{}", stderr);
+
+    let output = get_file("5:3", &[]);
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("start 5 is after end 3"), "{}", stderr);
+
+    // Without the file on disk, lines are rebuilt from the stored chunks
+    fs::remove_file(&lib_path).unwrap();
+    let output = get_file("3:5", &["--output", "json"]);
+    assert!(output.status.success(), "{:?}", output);
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["data"]["source"], "chunks");
+    assert_eq!(json["data"]["content"], "fn two() {\n    one();\n}");
+    assert!(json["data"]["total_lines"].is_null(), "{}", json);
+
+    // Lines no chunk covers are refused rather than returned blank
+    for range in ["1:3", "7:40"] {
+        let output = get_file(range, &[]);
+        assert!(!output.status.success(), "{range}: {:?}", output);
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert!(
+            stderr.contains("is not covered by its stored chunks"),
+            "{range}: {stderr}"
+        );
+    }
+}
+
+#[test]
+fn test_get_file_line_range_reads_relative_paths_from_root() {
+    let temp_dir = TempDir::new().unwrap();
+    let root = temp_dir.path().join("proj");
+    fs::create_dir_all(root.join("src")).unwrap();
+    let source = "// header\npub fn helper() -> u32 { 1 }\n";
+    fs::write(root.join("src/lib.rs"), source).unwrap();
+    let root = fs::canonicalize(root).unwrap();
+    let db_path = temp_dir.path().join("magellan.db");
+    {
+        let mut graph = magellan::CodeGraph::open(&db_path).unwrap();
+        graph
+            .set_path_style(magellan::PathStyle::Relative, &root)
+            .unwrap();
+        graph.scan_directory(&root, None).unwrap();
+    }
+
+    // Run outside the project so the stored path does not resolve from the CWD
+    let output = Command::new(env!("CARGO_BIN_EXE_magellan"))
+        .current_dir(temp_dir.path())
+        .args(["get-file", "--file", "src/lib.rs", "--line-range", "1:2"])
+        .args(["--output", "json"])
+        .arg("--db")
+        .arg(&db_path)
+        .output()
+        .expect("Failed to execute magellan get-file");
+    assert!(output.status.success(), "{:?}", output);
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["data"]["source"], "disk");
+    assert_eq!(json["data"]["total_lines"], 2);
+    assert_eq!(
+        json["data"]["content"],
+        "// header\npub fn helper() -> u32 { 1 }"
+    );
+}
+
+#[test]
+fn test_ast_query_captures_function_names() {
+    let temp_dir = TempDir::new().unwrap();
+    let db_path = temp_dir.path().join("magellan.db");
+
+    let bin_path = std::env::var("CARGO_BIN_EXE_magellan").unwrap_or_else(|_| {
+        let mut path = std::env::current_exe().unwrap();
+        path.pop();
+        path.pop();
+        path.push("magellan");
+        path.to_str().unwrap().to_string()
+    });
+
+    let lib_path = temp_dir.path().join("lib.rs");
+    let source =
+        "fn parse() {}\n\nstruct Config;\n\nimpl Config {\n    fn load() -> Self { Config }\n}\n";
+    fs::write(&lib_path, source).unwrap();
+    let lib_str = lib_path.to_string_lossy().to_string();
+    {
+        let mut graph = magellan::CodeGraph::open(&db_path).unwrap();
+        graph.index_file(&lib_str, source.as_bytes()).unwrap();
+    }
+
+    let ast_query = |query: &str| {
+        Command::new(&bin_path)
+            .args([
+                "ast", "--file", &lib_str, "--query", query, "--output", "json",
+            ])
+            .arg("--db")
+            .arg(&db_path)
+            .output()
+            .expect("Failed to execute magellan")
+    };
+
+    let output = ast_query("(function_item name: (identifier) @n)");
+    assert!(output.status.success(), "{:?}", output);
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["data"]["count"], 2);
+    let captures = json["data"]["captures"].as_array().unwrap();
+    let names: Vec<&str> = captures
+        .iter()
+        .map(|c| c["text"].as_str().unwrap())
+        .collect();
+    assert_eq!(names, ["parse", "load"]);
+    assert_eq!(captures[0]["capture"], "n");
+    assert_eq!(captures[0]["kind"], "identifier");
+    assert_eq!(captures[0]["byte_start"], 3);
+    assert_eq!(captures[0]["byte_end"], 8);
+    assert_eq!(captures[1]["start_line"], 6);
+
+    // A query that does not compile fails with the grammar's error
+    let output = ast_query("(function_item name: (identifier) @n");
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("Invalid tree-sitter query"), "{}", stderr);
+}
+
+#[test]
+fn test_query_baseline_flags_symbol_changes() {
+    let temp_dir = TempDir::new().unwrap();
+    let file_path = temp_dir.path().join("lib.rs");
+    let path_str = file_path.to_string_lossy().to_string();
+    let baseline_db = temp_dir.path().join("baseline.db");
+    let db_path = temp_dir.path().join("magellan.db");
+
+    let bin_path = std::env::var("CARGO_BIN_EXE_magellan").unwrap_or_else(|_| {
+        let mut path = std::env::current_exe().unwrap();
+        path.pop();
+        path.pop();
+        path.push("magellan");
+        path.to_str().unwrap().to_string()
+    });
+
+    // The baseline is missing `fresh`, has an older `edit`, and still has `gone`
+    let baseline_source = "fn keep() {}\n\nfn edit() {\n    1;\n}\n\nfn gone() {}\n";
+    let current_source = "fn keep() {}\n\nfn edit() {\n    2;\n}\n\nfn fresh() {}\n";
+    for (db, source) in [(&baseline_db, baseline_source), (&db_path, current_source)] {
+        fs::write(&file_path, source).unwrap();
+        let mut graph = magellan::CodeGraph::open(db).unwrap();
+        graph.index_file(&path_str, source.as_bytes()).unwrap();
+    }
+
+    let run = |extra: &[&str]| {
+        let output = Command::new(&bin_path)
+            .arg("query")
+            .arg("--db")
+            .arg(&db_path)
+            .arg("--file")
+            .arg(&file_path)
+            .arg("--baseline")
+            .arg(&baseline_db)
+            .args(extra)
+            .output()
+            .expect("Failed to execute magellan query");
+        assert!(
+            output.status.success(),
+            "stderr: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        output
+    };
+
+    let output = run(&["--output", "json"]);
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let data = &json["data"];
+    let statuses: Vec<(String, String)> = data["symbols"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|symbol| {
+            (
+                symbol["fqn"].as_str().unwrap().to_string(),
+                symbol["status"].as_str().unwrap().to_string(),
+            )
+        })
+        .collect();
+    let status_of = |name: &str| {
+        statuses
+            .iter()
+            .find(|(fqn, _)| fqn.ends_with(name))
+            .map(|(_, status)| status.as_str())
+    };
+    assert_eq!(status_of("keep"), Some("unchanged"));
+    assert_eq!(status_of("edit"), Some("modified"));
+    assert_eq!(status_of("fresh"), Some("added"));
+    assert_eq!(status_of("gone"), Some("removed"));
+    assert_eq!(data["added"], 1);
+    assert_eq!(data["removed"], 1);
+    assert_eq!(data["modified"], 1);
+    assert_eq!(data["unchanged"], 1);
+
+    // --changed-only drops unchanged symbols; human output shows changed bodies
+    let output = run(&["--changed-only"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(!stdout.contains("keep"), "got: {}", stdout);
+    assert!(stdout.contains("~ modified"), "got: {}", stdout);
+    assert!(stdout.contains("~     2;"), "got: {}", stdout);
+    assert!(stdout.contains("+ fn fresh() {}"), "got: {}", stdout);
+    assert!(stdout.contains("1 added, 1 removed, 1 modified, 1 unchanged"));
+}
+
+#[test]
+fn test_query_with_metrics_includes_stored_complexity() {
+    let temp_dir = TempDir::new().unwrap();
+    let db_path = temp_dir.path().join("magellan.db");
+
+    let bin_path = std::env::var("CARGO_BIN_EXE_magellan").unwrap_or_else(|_| {
+        let mut path = std::env::current_exe().unwrap();
+        path.pop();
+        path.pop();
+        path.push("magellan");
+        path.to_str().unwrap().to_string()
+    });
+
+    let lib_path = temp_dir.path().join("lib.rs");
+    let source = "pub fn branchy(x: i32) -> i32 {\n    if x > 0 {\n        1\n    } else if x < 0 {\n        -1\n    } else {\n        0\n    }\n}\n\npub fn plain() {}\n";
+    fs::write(&lib_path, source).unwrap();
+    let lib_str = lib_path.to_string_lossy().to_string();
+    {
+        let mut graph = magellan::CodeGraph::open(&db_path).unwrap();
+        graph.index_file(&lib_str, source.as_bytes()).unwrap();
+        graph.metrics().backfill_all_metrics(None).unwrap();
+    }
+
+    // Forget plain()'s metrics so its block must be omitted
+    let conn = rusqlite::Connection::open(&db_path).unwrap();
+    let (loc, complexity): (i64, i64) = conn
+        .query_row(
+            "SELECT loc, cyclomatic_complexity FROM symbol_metrics WHERE symbol_name = 'branchy'",
+            [],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .unwrap();
+    conn.execute("DELETE FROM symbol_metrics WHERE symbol_name = 'plain'", [])
+        .unwrap();
+    drop(conn);
+
+    let run = |args: &[&str]| {
+        let output = Command::new(&bin_path)
+            .args(args)
+            .arg("--db")
+            .arg(&db_path)
+            .output()
+            .expect("Failed to execute magellan");
+        assert!(output.status.success(), "{:?}", output);
+        output
+    };
+    let json =
+        |args: &[&str]| -> serde_json::Value { serde_json::from_slice(&run(args).stdout).unwrap() };
+
+    let data = json(&[
+        "query",
+        "--file",
+        &lib_str,
+        "--with-metrics",
+        "--output",
+        "json",
+    ]);
+    let symbols = data["data"]["symbols"].as_array().unwrap();
+    let symbol = |name: &str| symbols.iter().find(|s| s["name"] == name).unwrap();
+    assert_eq!(
+        symbol("branchy")["metrics"]["cyclomatic_complexity"],
+        complexity
+    );
+    assert_eq!(symbol("branchy")["metrics"]["loc"], loc);
+    assert!(symbol("branchy")["metrics"]["fan_in"].is_i64());
+    assert!(symbol("plain").get("metrics").is_none());
+
+    // Without the flag no metrics are attached
+    let data = json(&["query", "--file", &lib_str, "--output", "json"]);
+    let symbols = data["data"]["symbols"].as_array().unwrap();
+    assert!(symbols.iter().all(|s| s.get("metrics").is_none()));
+
+    let data = json(&[
+        "find",
+        "--name",
+        "branchy",
+        "--with-metrics",
+        "--output",
+        "json",
+    ]);
+    assert_eq!(
+        data["data"]["matches"][0]["metrics"]["cyclomatic_complexity"],
+        complexity
+    );
+
+    let output = run(&["query", "--file", &lib_str, "--with-metrics"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains(&format!("complexity {}", complexity)),
+        "got: {}",
+        stdout
+    );
+    assert_eq!(stdout.matches("Metrics:").count(), 1, "got: {}", stdout);
+}
+
+#[test]
+fn test_get_expand_to_function_and_file() {
+    let temp_dir = TempDir::new().unwrap();
+    let db_path = temp_dir.path().join("magellan.db");
+    let file_path = temp_dir.path().join("lib.rs");
+
+    let bin_path = std::env::var("CARGO_BIN_EXE_magellan").unwrap_or_else(|_| {
+        let mut path = std::env::current_exe().unwrap();
+        path.pop();
+        path.pop();
+        path.push("magellan");
+        path.to_str().unwrap().to_string()
+    });
+
+    // Offset is an item declared inside the origin() method body
+    let source = "struct Point {\n    x: i32,\n}\n\nimpl Point {\n    fn origin() -> Self {\n        struct Offset;\n        Point { x: 0 }\n    }\n}\n\nfn main() {}\n";
+    fs::write(&file_path, source).unwrap();
+    let path = file_path.to_string_lossy().to_string();
+    {
+        let mut graph = magellan::CodeGraph::open(&db_path).unwrap();
+        graph.index_file(&path, source.as_bytes()).unwrap();
+    }
+
+    let get = |symbol: &str, expand_to: &str| {
+        Command::new(&bin_path)
+            .args(["get", "--file", &path, "--symbol", symbol])
+            .args(["--expand-to", expand_to, "--output", "json"])
+            .arg("--db")
+            .arg(&db_path)
+            .output()
+            .expect("Failed to execute magellan get")
+    };
+    let get_json = |symbol: &str, expand_to: &str| -> serde_json::Value {
+        let output = get(symbol, expand_to);
+        assert!(output.status.success(), "{:?}", output);
+        serde_json::from_slice(&output.stdout).unwrap()
+    };
+
+    // The nested item expands to the method that contains it
+    let json = get_json("Offset", "function");
+    let data = &json["data"];
+    assert_eq!(data["symbol"]["name"], "Offset");
+    assert_eq!(data["expanded"]["granularity"], "function");
+    assert_eq!(data["expanded"]["name"], "origin");
+    assert_eq!(data["expanded"]["start_line"], 6);
+    assert_eq!(data["expanded"]["end_line"], 9);
+    let content = data["content"].as_str().unwrap();
+    assert!(content.starts_with("fn origin()"), "{}", content);
+    assert!(content.contains("struct Offset;"));
+    assert!(!content.contains("fn main"));
+
+    // The method expands to the whole file
+    let json = get_json("origin", "file");
+    let data = &json["data"];
+    assert_eq!(data["symbol"]["name"], "origin");
+    assert_eq!(data["expanded"]["granularity"], "file");
+    assert_eq!(data["content"], source);
+    assert_eq!(data["expanded"]["end_line"], 12);
+
+    // The default returns the symbol's own span without an expansion
+    let json = get_json("origin", "symbol");
+    assert!(json["data"].get("expanded").is_none());
+    assert!(!json["data"]["content"]
+        .as_str()
+        .unwrap()
+        .contains("fn main"));
+
+    // A symbol outside any function has nothing to expand to
+    let output = get("Point", "function");
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("No function"));
+}
+
+#[test]
+fn test_get_expand_to_file_reads_relative_paths_from_root() {
+    let temp_dir = TempDir::new().unwrap();
+    let root = temp_dir.path().join("proj");
+    fs::create_dir_all(root.join("src")).unwrap();
+    let source = "pub fn helper() -> u32 { 1 }\n\npub fn caller() -> u32 { helper() }\n";
+    fs::write(root.join("src/lib.rs"), source).unwrap();
+    let root = fs::canonicalize(root).unwrap();
+    let db_path = temp_dir.path().join("magellan.db");
+    {
+        let mut graph = magellan::CodeGraph::open(&db_path).unwrap();
+        graph
+            .set_path_style(magellan::PathStyle::Relative, &root)
+            .unwrap();
+        graph.scan_directory(&root, None).unwrap();
+    }
+
+    let bin_path = std::env::var("CARGO_BIN_EXE_magellan").unwrap_or_else(|_| {
+        let mut path = std::env::current_exe().unwrap();
+        path.pop();
+        path.pop();
+        path.push("magellan");
+        path.to_str().unwrap().to_string()
+    });
+    // Run outside the project so the stored path does not resolve from the CWD
+    let get = || {
+        Command::new(&bin_path)
+            .current_dir(temp_dir.path())
+            .args(["get", "--file", "src/lib.rs", "--symbol", "caller"])
+            .args(["--expand-to", "file", "--output", "json"])
+            .arg("--db")
+            .arg(&db_path)
+            .output()
+            .expect("Failed to execute magellan get")
+    };
+
+    let output = get();
+    assert!(output.status.success(), "{:?}", output);
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["data"]["content"], source);
+
+    // Stored chunks do not cover the whole file, so a missing file is an error
+    fs::remove_file(root.join("src/lib.rs")).unwrap();
+    let output = get();
+    assert!(!output.status.success());
+    assert!(
+        String::from_utf8_lossy(&output.stderr).contains("Cannot read"),
+        "{:?}",
+        output
+    );
+}
+
+#[test]
+fn test_chunks_file_glob_matches_relative_to_watch_root() {
+    let temp_dir = TempDir::new().unwrap();
+    let db_path = temp_dir.path().join("magellan.db");
+    let root = temp_dir.path().join("proj");
+    fs::create_dir_all(root.join("src/graph")).unwrap();
+    fs::create_dir_all(root.join("tests")).unwrap();
+    fs::write(root.join("top.rs"), "pub fn top_fn() {}\n").unwrap();
+    fs::write(root.join("src/lib.rs"), "pub fn lib_fn() {}\n").unwrap();
+    fs::write(root.join("src/graph/ops.rs"), "pub fn ops_fn() {}\n").unwrap();
+    fs::write(root.join("tests/cli.rs"), "fn cli_test() {}\n").unwrap();
+
+    let bin_path = std::env::var("CARGO_BIN_EXE_magellan").unwrap();
+    let output = Command::new(&bin_path)
+        .arg("watch")
+        .arg("--root")
+        .arg(&root)
+        .arg("--db")
+        .arg(&db_path)
+        .args(["--once", "--include", "**/*.rs"])
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{:?}", output);
+
+    // Stored paths are absolute; globs still apply relative to the root
+    let chunk_files = |pattern: &str| -> Vec<String> {
+        let output = Command::new(&bin_path)
+            .args(["chunks", "--db"])
+            .arg(&db_path)
+            .args(["--file", pattern, "--output", "json"])
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "{:?}", output);
+        let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+        let mut files: Vec<String> = json["data"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|chunk| chunk["file_path"].as_str().unwrap().to_string())
+            .collect();
+        files.dedup();
+        files
+    };
+
+    let root = fs::canonicalize(&root).unwrap();
+    let under_root = |rel: &str| root.join(rel).to_string_lossy().to_string();
+    assert_eq!(
+        chunk_files("src/**/*.rs"),
+        vec![under_root("src/graph/ops.rs"), under_root("src/lib.rs")]
+    );
+    assert_eq!(chunk_files("*.rs"), vec![under_root("top.rs")]);
+}
@@ -169,3 +169,130 @@ fn bar() {
         calls_from_bar
     );
 }
+
+#[test]
+#[allow(deprecated)]
+fn test_module_qualified_call_picks_defining_file() {
+    // Two files define `helper`; crate::util::helper() must link to util.rs.
+    use magellan::CodeGraph;
+    use tempfile::TempDir;
+
+    let temp_dir = TempDir::new().unwrap();
+    let db_path = temp_dir.path().join("test.db");
+    let mut graph = CodeGraph::open(&db_path).unwrap();
+
+    let helper_source = r#"
+pub fn helper() -> i32 {
+    1
+}
+"#;
+
+    let main_source = r#"
+fn main() {
+    let _ = crate::util::helper();
+}
+"#;
+
+    // Index the decoy first so name-only resolution would pick it.
+    graph
+        .index_file("src/other.rs", helper_source.as_bytes())
+        .unwrap();
+    graph
+        .index_file("src/util.rs", helper_source.as_bytes())
+        .unwrap();
+    graph
+        .index_file("src/main.rs", main_source.as_bytes())
+        .unwrap();
+
+    let callers_util = graph.callers_of_symbol("src/util.rs", "helper").unwrap();
+    assert_eq!(
+        callers_util.len(),
+        1,
+        "util::helper should be called from main, got {:?}",
+        callers_util
+    );
+    assert_eq!(callers_util[0].caller, "main");
+
+    let callers_other = graph.callers_of_symbol("src/other.rs", "helper").unwrap();
+    assert!(
+        callers_other.is_empty(),
+        "other::helper must not be linked, got {:?}",
+        callers_other
+    );
+
+    let util_helper_id = graph
+        .stable_symbol_id_by_name("src/util.rs", "helper")
+        .unwrap()
+        .expect("util helper should have a stable id");
+    let calls_from_main = graph.calls_from_symbol("src/main.rs", "main").unwrap();
+    assert_eq!(calls_from_main.len(), 1);
+    assert_eq!(
+        calls_from_main[0].callee_symbol_id.as_deref(),
+        Some(util_helper_id.as_str())
+    );
+}
+
+#[test]
+#[allow(deprecated)]
+fn test_unresolvable_qualified_call_stays_unlinked() {
+    // A qualified call to a name no indexed file defines creates no CALLS edge.
+    use magellan::CodeGraph;
+    use tempfile::TempDir;
+
+    let temp_dir = TempDir::new().unwrap();
+    let db_path = temp_dir.path().join("test.db");
+    let mut graph = CodeGraph::open(&db_path).unwrap();
+
+    let main_source = r#"
+fn main() {
+    let _ = crate::util::missing();
+}
+"#;
+
+    graph
+        .index_file("src/main.rs", main_source.as_bytes())
+        .unwrap();
+
+    let calls_from_main = graph.calls_from_symbol("src/main.rs", "main").unwrap();
+    assert!(
+        calls_from_main.is_empty(),
+        "unresolved call should not be linked, got {:?}",
+        calls_from_main
+    );
+}
+
+#[test]
+#[allow(deprecated)]
+fn test_module_qualified_call_resolves_when_caller_indexed_first() {
+    // lib.rs sorts before util.rs, so the scan meets the call before helper exists.
+    use magellan::CodeGraph;
+    use tempfile::TempDir;
+
+    let temp_dir = TempDir::new().unwrap();
+    let root = temp_dir.path().join("proj");
+    std::fs::create_dir_all(root.join("src")).unwrap();
+    std::fs::write(
+        root.join("src/lib.rs"),
+        "pub fn caller() {\n    crate::util::helper();\n}\n",
+    )
+    .unwrap();
+    std::fs::write(root.join("src/util.rs"), "pub fn helper() {}\n").unwrap();
+    let root = std::fs::canonicalize(root).unwrap();
+
+    let mut graph = CodeGraph::open(temp_dir.path().join("test.db")).unwrap();
+    graph.scan_directory(&root, None).unwrap();
+
+    let util_path = root.join("src/util.rs").to_string_lossy().to_string();
+    let callers = graph.callers_of_symbol(&util_path, "helper").unwrap();
+    assert_eq!(
+        callers.len(),
+        1,
+        "helper should be called from caller, got {:?}",
+        callers
+    );
+    assert_eq!(callers[0].caller, "caller");
+
+    // Re-resolving must not duplicate the call on the next scan
+    graph.scan_directory(&root, None).unwrap();
+    assert_eq!(graph.callers_of_symbol(&util_path, "helper").unwrap().len(), 1);
+}
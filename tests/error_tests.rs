@@ -100,3 +100,75 @@ fn test_unreadable_file_prints_error_and_continues() {
         "Should have indexed 2 symbols from good.rs despite bad.rs error"
     );
 }
+
+#[test]
+fn test_watch_json_diagnostics_for_parse_error_and_too_large_file() {
+    let temp_dir = TempDir::new().unwrap();
+    let root_path = fs::canonicalize(temp_dir.path()).unwrap();
+    let db_path = root_path.join("magellan.db");
+
+    let src = root_path.join("src");
+    fs::create_dir_all(&src).unwrap();
+    fs::write(src.join("good.rs"), b"fn good() {}").unwrap();
+    fs::write(src.join("bad.rs"), b"fn ok() {}\nfn broken( {\n").unwrap();
+    fs::write(src.join("big.rs"), "fn big() {}\n".repeat(100)).unwrap();
+
+    let bin_path = std::env::var("CARGO_BIN_EXE_magellan").unwrap();
+    let mut child = Command::new(&bin_path)
+        .arg("watch")
+        .arg("--root")
+        .arg(&root_path)
+        .arg("--db")
+        .arg(&db_path)
+        .arg("--scan-initial")
+        .arg("--output")
+        .arg("json")
+        .arg("--max-file-size")
+        .arg("512")
+        .env("MAGELLAN_LOCAL", "1")
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("Failed to start magellan binary");
+
+    thread::sleep(Duration::from_millis(1500));
+    let _ = child.kill();
+    let output = child
+        .wait_with_output()
+        .expect("Failed to wait for process");
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let diagnostics: Vec<serde_json::Value> = stdout
+        .lines()
+        .filter(|line| line.starts_with('{'))
+        .map(|line| serde_json::from_str(line).expect("diagnostic line is valid JSON"))
+        .collect();
+
+    let parse_error = diagnostics
+        .iter()
+        .find(|d| d["path"] == "src/bad.rs")
+        .unwrap_or_else(|| panic!("no diagnostic for src/bad.rs in: {}", stdout));
+    assert_eq!(parse_error["kind"], "error");
+    assert_eq!(parse_error["stage"], "parse");
+    assert_eq!(parse_error["reason"], "syntax error at 2:1");
+    assert!(parse_error["schema_version"].is_string());
+
+    let too_large = diagnostics
+        .iter()
+        .find(|d| d["path"] == "src/big.rs")
+        .unwrap_or_else(|| panic!("no diagnostic for src/big.rs in: {}", stdout));
+    assert_eq!(too_large["kind"], "skipped");
+    assert!(too_large["stage"].is_null());
+    assert_eq!(too_large["reason"], "file_too_large");
+
+    assert!(!diagnostics.iter().any(|d| d["path"] == "src/good.rs"));
+
+    // One batch, sorted by path
+    let paths: Vec<&str> = diagnostics
+        .iter()
+        .map(|d| d["path"].as_str().unwrap())
+        .collect();
+    let mut sorted = paths.clone();
+    sorted.sort();
+    assert_eq!(paths, sorted);
+}
@@ -0,0 +1,83 @@
+//! Global `--expect-schema` flag tests
+
+use std::process::Command;
+use tempfile::TempDir;
+
+fn bin_path() -> String {
+    std::env::var("CARGO_BIN_EXE_magellan").unwrap_or_else(|_| {
+        let mut path = std::env::current_exe().unwrap();
+        path.pop();
+        path.pop();
+        path.push("magellan");
+        path.to_str().unwrap().to_string()
+    })
+}
+
+#[test]
+fn test_expect_schema_mismatch_fails_before_running() {
+    let temp_dir = TempDir::new().unwrap();
+    let db_path = temp_dir.path().join("test.db");
+
+    let output = Command::new(bin_path())
+        .args(["--expect-schema", "99", "status", "--db"])
+        .arg(&db_path)
+        .args(["--output", "json"])
+        .output()
+        .expect("Failed to execute magellan");
+
+    assert_eq!(output.status.code(), Some(3), "{:?}", output);
+    assert!(output.stdout.is_empty());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("expected 99, command emits 1.0.0"),
+        "{}",
+        stderr
+    );
+    // The command itself never ran, so the missing database went unnoticed
+    assert!(!stderr.contains("Database not found"), "{}", stderr);
+}
+
+#[test]
+fn test_expect_schema_match_runs_command() {
+    let temp_dir = TempDir::new().unwrap();
+    let db_path = temp_dir.path().join("test.db");
+    magellan::CodeGraph::open(&db_path).unwrap();
+
+    for expected in ["1", "1.0.0"] {
+        let output = Command::new(bin_path())
+            .args(["--expect-schema", expected, "status", "--db"])
+            .arg(&db_path)
+            .args(["--output", "json"])
+            .output()
+            .expect("Failed to execute magellan");
+
+        assert!(output.status.success(), "{:?}", output);
+        let data: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+        assert_eq!(data["schema_version"], "1.0.0");
+    }
+}
+
+#[test]
+fn test_expect_schema_uses_export_version() {
+    let temp_dir = TempDir::new().unwrap();
+    let db_path = temp_dir.path().join("test.db");
+
+    let run = |args: &[&str]| {
+        Command::new(bin_path())
+            .args(args)
+            .arg("--db")
+            .arg(&db_path)
+            .output()
+            .expect("Failed to execute magellan")
+    };
+
+    // JSONL export records are at 2.0.0, not the 1.0.0 envelope
+    let output = run(&["--expect-schema", "1", "export", "--format", "jsonl"]);
+    assert_eq!(output.status.code(), Some(3), "{:?}", output);
+    let output = run(&["--expect-schema", "2", "export", "--format", "jsonl"]);
+    assert!(output.status.success(), "{:?}", output);
+
+    // DOT output has no Magellan schema to pin
+    let output = run(&["--expect-schema", "1", "export", "--format", "dot"]);
+    assert_eq!(output.status.code(), Some(3), "{:?}", output);
+}
@@ -4,7 +4,7 @@
 
 use magellan::graph::export::{
     export_graph, export_jsonl, stream_json, stream_json_minified, stream_ndjson, ExportConfig,
-    ExportFormat,
+    ExportFormat, JsonlRecordType,
 };
 use magellan::CodeGraph;
 use tempfile::TempDir;
@@ -461,6 +461,8 @@ fn test_stream_json_respects_symbols_filter() {
         filters: Default::default(),
         include_collisions: false,
         collisions_field: magellan::graph::query::CollisionField::Fqn,
+        include_unresolved: false,
+        record_types: Vec::new(),
     };
     stream_json_minified(&mut graph, &config, &mut buffer).unwrap();
     let json = String::from_utf8(buffer).unwrap();
@@ -501,6 +503,8 @@ fn helper() {}
         filters: Default::default(),
         include_collisions: false,
         collisions_field: magellan::graph::query::CollisionField::Fqn,
+        include_unresolved: false,
+        record_types: Vec::new(),
     };
     stream_json_minified(&mut graph, &config, &mut buffer).unwrap();
     let json = String::from_utf8(buffer).unwrap();
@@ -564,6 +568,101 @@ fn test_jsonl_export_includes_version_record() {
     assert_eq!(version_record["version"], "2.0.0");
 }
 
+#[test]
+fn test_jsonl_type_symbols_emits_only_symbol_records() {
+    let temp_dir = TempDir::new().unwrap();
+    let db_path = temp_dir.path().join("test.db");
+
+    let source = "fn helper() {}\n\nfn main() {\n    helper();\n}\n";
+    let mut graph = CodeGraph::open(&db_path).unwrap();
+    graph.index_file("main.rs", source.as_bytes()).unwrap();
+
+    let mut config = ExportConfig::new(ExportFormat::JsonL);
+    config.record_types = vec![JsonlRecordType::Symbols];
+    let mut buffer = Vec::new();
+    stream_ndjson(&mut graph, &config, &mut buffer).unwrap();
+    let jsonl = String::from_utf8(buffer).unwrap();
+
+    let records: Vec<serde_json::Value> = jsonl
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert_eq!(records.len(), 2, "{}", jsonl);
+    for record in &records {
+        assert_eq!(record["type"], "Symbol", "{}", jsonl);
+        assert_eq!(record["schema_version"], "2.0.0");
+    }
+
+    // Several types stay in contiguous blocks, in a fixed order
+    config.record_types = vec![JsonlRecordType::Calls, JsonlRecordType::Files];
+    let jsonl = export_graph(&mut graph, &config).unwrap();
+    let types: Vec<String> = jsonl
+        .lines()
+        .map(|line| {
+            let record: serde_json::Value = serde_json::from_str(line).unwrap();
+            record["type"].as_str().unwrap().to_string()
+        })
+        .collect();
+    assert_eq!(types, vec!["File", "Call"]);
+}
+
+#[test]
+fn test_kind_filters_include_union_then_exclude() {
+    let temp_dir = TempDir::new().unwrap();
+    let db_path = temp_dir.path().join("test.db");
+
+    let source =
+        "mod inner {}\npub struct Config;\npub trait Shape {}\nenum Mode { A }\nfn run() {}\n";
+    let mut graph = CodeGraph::open(&db_path).unwrap();
+    graph.index_file("lib.rs", source.as_bytes()).unwrap();
+
+    let symbol_names = |graph: &mut CodeGraph, kinds: &[&str], exclude: &[&str]| {
+        let mut config = ExportConfig::new(ExportFormat::Json);
+        config.filters.kinds = kinds.iter().map(|k| k.to_string()).collect();
+        config.filters.exclude_kinds = exclude.iter().map(|k| k.to_string()).collect();
+        let mut buffer = Vec::new();
+        stream_json(graph, &config, &mut buffer).unwrap();
+        let parsed: serde_json::Value = serde_json::from_slice(&buffer).unwrap();
+        let mut names: Vec<String> = parsed["symbols"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|s| s["name"].as_str().unwrap().to_string())
+            .collect();
+        names.sort();
+        names
+    };
+
+    // Repeated kinds are a union
+    assert_eq!(
+        symbol_names(&mut graph, &["Class", "Function"], &[]),
+        vec!["Config", "run"]
+    );
+    // Exclusions alone: everything except modules
+    assert_eq!(
+        symbol_names(&mut graph, &[], &["Module"]),
+        vec!["Config", "Mode", "Shape", "run"]
+    );
+    // Exclusions apply after inclusion
+    assert_eq!(
+        symbol_names(
+            &mut graph,
+            &["Class", "Module", "Enum"],
+            &["Module", "Interface"]
+        ),
+        vec!["Config", "Mode"]
+    );
+
+    // CSV honours the same filters
+    let mut config = ExportConfig::new(ExportFormat::Csv);
+    config.include_references = false;
+    config.include_calls = false;
+    config.filters.exclude_kinds = vec!["Module".to_string(), "Enum".to_string()];
+    let csv = export_graph(&mut graph, &config).unwrap();
+    assert!(csv.contains("Config") && csv.contains("run"), "{}", csv);
+    assert!(!csv.contains("inner") && !csv.contains("Mode"), "{}", csv);
+}
+
 #[test]
 fn test_csv_export_includes_version_header() {
     let temp_dir = tempfile::TempDir::new().unwrap();
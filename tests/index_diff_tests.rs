@@ -0,0 +1,133 @@
+//! Tests for `magellan diff` between two databases
+
+use magellan::CodeGraph;
+use std::process::Command;
+use tempfile::TempDir;
+
+const BASE: &str = "fn helper() {}\nfn main() {\n    helper();\n}\n";
+
+fn index(db_path: &std::path::Path, source: &str) -> CodeGraph {
+    let mut graph = CodeGraph::open(db_path).unwrap();
+    graph.index_file("lib.rs", source.as_bytes()).unwrap();
+    graph
+}
+
+#[test]
+fn test_diff_reports_one_added_function_and_its_call() {
+    let temp_dir = TempDir::new().unwrap();
+    let mut old = index(&temp_dir.path().join("old.db"), BASE);
+    let mut new = index(
+        &temp_dir.path().join("new.db"),
+        &format!("{BASE}fn extra() {{\n    helper();\n}}\n"),
+    );
+
+    let report = magellan::index_diff::diff_indexes(&mut old, &mut new).unwrap();
+
+    assert_eq!(report.added.len(), 1, "added: {:?}", report.added);
+    assert!(report.added[0].fqn.ends_with("extra"));
+    assert_eq!(report.added[0].location.start_line, 5);
+    assert!(report.removed.is_empty(), "removed: {:?}", report.removed);
+    assert!(report.moved.is_empty(), "moved: {:?}", report.moved);
+    assert_eq!(report.unchanged, 2);
+
+    assert_eq!(report.added_calls.len(), 1);
+    assert_eq!(report.added_calls[0].caller, "extra");
+    assert_eq!(report.added_calls[0].callee, "helper");
+    assert!(report.removed_calls.is_empty());
+
+    // Reversing the direction turns additions into removals
+    let report = magellan::index_diff::diff_indexes(&mut new, &mut old).unwrap();
+    assert_eq!(report.removed.len(), 1);
+    assert_eq!(report.removed_calls.len(), 1);
+    assert!(report.added.is_empty());
+}
+
+#[test]
+fn test_diff_reports_moved_symbol() {
+    let temp_dir = TempDir::new().unwrap();
+    let mut old = index(&temp_dir.path().join("old.db"), BASE);
+    let mut new = index(&temp_dir.path().join("new.db"), &format!("\n\n{BASE}"));
+
+    let report = magellan::index_diff::diff_indexes(&mut old, &mut new).unwrap();
+
+    assert!(report.added.is_empty());
+    assert!(report.removed.is_empty());
+    assert_eq!(report.moved.len(), 2);
+    let helper = report
+        .moved
+        .iter()
+        .find(|m| m.fqn.ends_with("helper"))
+        .unwrap();
+    assert_eq!(helper.before.start_line, 1);
+    assert_eq!(helper.after.start_line, 3);
+    assert!(report.added_calls.is_empty() && report.removed_calls.is_empty());
+}
+
+#[test]
+fn test_diff_cli_json_is_deterministic() {
+    let temp_dir = TempDir::new().unwrap();
+    let old_db = temp_dir.path().join("old.db");
+    let new_db = temp_dir.path().join("new.db");
+    drop(index(&old_db, BASE));
+    drop(index(
+        &new_db,
+        &format!("{BASE}fn extra() {{\n    helper();\n}}\n"),
+    ));
+
+    let run = || -> serde_json::Value {
+        let output = Command::new(env!("CARGO_BIN_EXE_magellan"))
+            .arg("diff")
+            .arg("--old")
+            .arg(&old_db)
+            .arg("--new")
+            .arg(&new_db)
+            .args(["--output", "json"])
+            .output()
+            .expect("Failed to execute magellan diff");
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        assert!(
+            output.status.success(),
+            "stdout: {}\nstderr: {}",
+            stdout,
+            String::from_utf8_lossy(&output.stderr)
+        );
+        let json: serde_json::Value =
+            serde_json::from_str(stdout.trim()).expect("Output should be valid JSON");
+        json["data"].clone()
+    };
+
+    let data = run();
+    assert_eq!(data["added"].as_array().unwrap().len(), 1);
+    assert_eq!(data["added"][0]["location"]["file"], "lib.rs");
+    assert_eq!(data["added_calls"][0]["caller"], "extra");
+    assert_eq!(data["removed"].as_array().unwrap().len(), 0);
+    assert_eq!(data["moved"].as_array().unwrap().len(), 0);
+    assert_eq!(run(), data);
+}
+
+#[test]
+fn test_diff_cli_missing_database_fails_without_creating_it() {
+    let temp_dir = TempDir::new().unwrap();
+    let old_db = temp_dir.path().join("old.db");
+    let new_db = temp_dir.path().join("typo.db");
+    drop(index(&old_db, BASE));
+
+    for (old, new) in [(&old_db, &new_db), (&new_db, &old_db)] {
+        let output = Command::new(env!("CARGO_BIN_EXE_magellan"))
+            .arg("diff")
+            .arg("--old")
+            .arg(old)
+            .arg("--new")
+            .arg(new)
+            .output()
+            .expect("Failed to execute magellan diff");
+
+        assert!(!output.status.success(), "{:?}", output);
+        assert!(
+            String::from_utf8_lossy(&output.stderr).contains("Database not found"),
+            "{}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        assert!(!new_db.exists(), "diff created {}", new_db.display());
+    }
+}
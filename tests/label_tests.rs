@@ -0,0 +1,105 @@
+//! Tests for the symbol labels attached at index time
+//!
+//! `magellan label` intersects repeated `--label` flags, so each dimension
+//! (language, kind, visibility, test code) must be queryable on its own and
+//! in combination.
+
+use magellan::CodeGraph;
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+use tempfile::TempDir;
+
+fn bin_path() -> String {
+    std::env::var("CARGO_BIN_EXE_magellan").unwrap_or_else(|_| {
+        let mut path = std::env::current_exe().unwrap();
+        path.pop();
+        path.pop();
+        path.push("magellan");
+        path.to_str().unwrap().to_string()
+    })
+}
+
+const SOURCE: &str =
+    "pub fn open() {}\n\nfn helper() {}\n\n#[test]\nfn opens() {\n    open();\n}\n";
+
+fn index_source(temp_dir: &TempDir) -> std::path::PathBuf {
+    let db_path = temp_dir.path().join("test.db");
+    let path = temp_dir.path().join("lib.rs");
+    fs::write(&path, SOURCE).unwrap();
+    let mut graph = CodeGraph::open(&db_path).unwrap();
+    graph
+        .index_file(&path.to_string_lossy(), SOURCE.as_bytes())
+        .unwrap();
+    db_path
+}
+
+/// Run `magellan label` with JSON output and return the `data` payload
+fn run_label(db_path: &Path, args: &[&str]) -> serde_json::Value {
+    let output = Command::new(bin_path())
+        .arg("label")
+        .arg("--db")
+        .arg(db_path)
+        .args(args)
+        .arg("--output")
+        .arg("json")
+        .output()
+        .expect("Failed to execute magellan label");
+    assert!(output.status.success(), "{:?}", output);
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    json["data"].clone()
+}
+
+fn names(data: &serde_json::Value) -> Vec<String> {
+    let mut names: Vec<String> = data
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|symbol| symbol["name"].as_str().unwrap().to_string())
+        .collect();
+    names.sort();
+    names
+}
+
+#[test]
+fn test_pub_rust_function_matches_all_label_dimensions() {
+    let temp_dir = TempDir::new().unwrap();
+    let db_path = index_source(&temp_dir);
+
+    let data = run_label(
+        &db_path,
+        &["--label", "rust", "--label", "function", "--label", "pub"],
+    );
+    assert_eq!(names(&data), vec!["open"]);
+
+    let data = run_label(&db_path, &["--label", "private"]);
+    assert_eq!(names(&data), vec!["helper", "opens"]);
+
+    let data = run_label(&db_path, &["--label", "test", "--label", "private"]);
+    assert_eq!(names(&data), vec!["opens"]);
+
+    let data = run_label(&db_path, &["--label", "pub", "--label", "private"]);
+    assert!(names(&data).is_empty());
+}
+
+#[test]
+fn test_label_list_counts_every_dimension() {
+    let temp_dir = TempDir::new().unwrap();
+    let db_path = index_source(&temp_dir);
+
+    let data = run_label(&db_path, &["--list"]);
+    let count = |label: &str| {
+        data.as_array()
+            .unwrap()
+            .iter()
+            .find(|info| info["name"] == label)
+            .map(|info| info["count"].as_u64().unwrap())
+    };
+    assert_eq!(count("rust"), Some(3));
+    assert_eq!(count("fn"), Some(3));
+    assert_eq!(count("function"), Some(3));
+    assert_eq!(count("pub"), Some(1));
+    assert_eq!(count("private"), Some(2));
+    assert_eq!(count("test"), Some(1));
+    assert_eq!(count("non-test"), Some(2));
+}
@@ -0,0 +1,90 @@
+//! `metrics backfill` command tests
+
+use std::fs;
+use std::process::Command;
+use tempfile::TempDir;
+
+#[test]
+fn test_metrics_backfill_repopulates_file_metrics() {
+    let temp_dir = TempDir::new().unwrap();
+    let db_path = temp_dir.path().join("magellan.db");
+
+    let bin_path = std::env::var("CARGO_BIN_EXE_magellan").unwrap_or_else(|_| {
+        let mut path = std::env::current_exe().unwrap();
+        path.pop();
+        path.pop();
+        path.push("magellan");
+        path.to_str().unwrap().to_string()
+    });
+
+    let sources = [
+        (
+            "alpha.rs",
+            "pub fn alpha(x: i32) -> i32 {\n    if x > 0 { 1 } else { 0 }\n}\n",
+        ),
+        (
+            "beta.rs",
+            "pub fn beta() {}\n\npub fn gamma() { beta(); }\n",
+        ),
+        ("delta.rs", "pub fn delta() {}\n"),
+    ];
+    let mut paths = Vec::new();
+    {
+        let mut graph = magellan::CodeGraph::open(&db_path).unwrap();
+        for (name, source) in sources {
+            let path = temp_dir.path().join(name);
+            fs::write(&path, source).unwrap();
+            let path = path.to_string_lossy().to_string();
+            graph.index_file(&path, source.as_bytes()).unwrap();
+            paths.push(path);
+        }
+    }
+
+    // delta.rs keeps its metrics: opening a database whose metrics tables
+    // are empty would trigger the automatic upgrade backfill
+    let clear_metrics = |files: &[&String]| {
+        let conn = rusqlite::Connection::open(&db_path).unwrap();
+        for path in files {
+            conn.execute("DELETE FROM file_metrics WHERE file_path = ?1", [path])
+                .unwrap();
+            conn.execute("DELETE FROM symbol_metrics WHERE file_path = ?1", [path])
+                .unwrap();
+        }
+    };
+    let file_metrics = |path: &str| {
+        let graph = magellan::CodeGraph::open(&db_path).unwrap();
+        graph.metrics().get_file_metrics(path).unwrap()
+    };
+    let backfill = |extra: &[&str]| -> serde_json::Value {
+        let output = Command::new(&bin_path)
+            .args(["metrics", "backfill", "--db"])
+            .arg(&db_path)
+            .args(extra)
+            .args(["--output", "json"])
+            .output()
+            .expect("Failed to execute magellan");
+        assert!(output.status.success(), "{:?}", output);
+        // Progress lines go to stderr, one per file
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert!(stderr.contains("[1/"), "missing progress: {}", stderr);
+        serde_json::from_slice(&output.stdout).unwrap()
+    };
+
+    // --only scopes the recomputation to matching files
+    clear_metrics(&[&paths[0], &paths[1]]);
+    let data = backfill(&["--only", "**/beta.rs"]);
+    assert_eq!(data["data"]["only"], "**/beta.rs");
+    assert_eq!(data["data"]["total"], 1);
+    assert_eq!(data["data"]["symbols_updated"], 2);
+    assert!(file_metrics(&paths[0]).is_none());
+    let beta = file_metrics(&paths[1]).expect("beta.rs metrics after backfill");
+    assert_eq!(beta.symbol_count, 2);
+
+    let data = backfill(&[]);
+    assert_eq!(data["data"]["total"], 3);
+    assert_eq!(data["data"]["processed"], 3);
+    assert_eq!(data["data"]["symbols_updated"], 4);
+    assert!(data["data"]["errors"].as_array().unwrap().is_empty());
+    let alpha = file_metrics(&paths[0]).expect("alpha.rs metrics after backfill");
+    assert_eq!(alpha.symbol_count, 1);
+}
@@ -301,8 +301,20 @@ fn test_migration_v4_to_v18_creates_required_tables() {
     assert_eq!(result.old_version, 4);
     assert_eq!(result.new_version, MAGELLAN_SCHEMA_VERSION);
 
-    // Verify ast_nodes table exists
+    // Verify the v20 side tables exist
     let conn = rusqlite::Connection::open(&db_path).unwrap();
+    for table in ["file_timings", "trait_impls"] {
+        let has_table: bool = conn
+            .query_row(
+                "SELECT 1 FROM sqlite_master WHERE type='table' AND name=?1",
+                [table],
+                |_| Ok(true),
+            )
+            .unwrap_or(false);
+        assert!(has_table, "{table} should be created by migration (v20)");
+    }
+
+    // Verify ast_nodes table exists
     let has_table: bool = conn
         .query_row(
             "SELECT 1 FROM sqlite_master WHERE type='table' AND name='ast_nodes'",
@@ -618,3 +630,53 @@ fn test_opening_v4_database_auto_upgrades_to_v18() {
         );
     }
 }
+
+/// Test that a v19 database is detected as old, refused by read-only opens,
+/// and gains the v20 side tables when opened for writing
+#[test]
+fn test_v19_database_upgrades_to_v20_side_tables() {
+    let temp_dir = TempDir::new().unwrap();
+    let db_path = temp_dir.path().join("test_v19.db");
+    drop(magellan::CodeGraph::open(&db_path).unwrap());
+
+    // Roll the database back to v19: no file_timings yet
+    let conn = rusqlite::Connection::open(&db_path).unwrap();
+    conn.execute("DROP TABLE file_timings", []).unwrap();
+    conn.execute(
+        "UPDATE magellan_meta SET magellan_schema_version = 19 WHERE id = 1",
+        [],
+    )
+    .unwrap();
+    drop(conn);
+
+    let err = match magellan::CodeGraph::open_readonly(&db_path) {
+        Ok(_) => panic!("read-only open of a v19 database should fail"),
+        Err(err) => err.to_string(),
+    };
+    assert!(err.contains("needs a schema upgrade"), "{err}");
+
+    drop(magellan::CodeGraph::open(&db_path).unwrap());
+
+    let conn = rusqlite::Connection::open(&db_path).unwrap();
+    let version: i64 = conn
+        .query_row(
+            "SELECT magellan_schema_version FROM magellan_meta WHERE id=1",
+            [],
+            |row| row.get(0),
+        )
+        .unwrap();
+    assert_eq!(version, MAGELLAN_SCHEMA_VERSION);
+    for table in ["file_timings", "chunk_content", "trait_impls"] {
+        let has_table: bool = conn
+            .query_row(
+                "SELECT 1 FROM sqlite_master WHERE type='table' AND name=?1",
+                [table],
+                |_| Ok(true),
+            )
+            .unwrap_or(false);
+        assert!(has_table, "{table} should exist after upgrade (v20)");
+    }
+    drop(conn);
+
+    assert!(magellan::CodeGraph::open_readonly(&db_path).is_ok());
+}
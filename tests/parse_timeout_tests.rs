@@ -0,0 +1,67 @@
+//! Tests for the per-file parse timeout
+//!
+//! A file whose parse exceeds the configured budget is skipped with a
+//! `parse_timeout` diagnostic instead of stalling the scan.
+
+use magellan::{CodeGraph, FileFilter, SkipReason, WatchDiagnostic};
+use std::fs;
+use std::time::Duration;
+use tempfile::TempDir;
+
+/// Many functions of deeply nested expressions: slow enough to blow a 1ms budget
+fn slow_source() -> String {
+    let nested = format!("{}1{}", "(".repeat(200), ")".repeat(200));
+    (0..2000)
+        .map(|i| format!("fn f{i}() -> i32 {{ {nested} }}\n"))
+        .collect()
+}
+
+#[test]
+fn test_index_file_times_out_without_recording_file() {
+    let temp_dir = TempDir::new().unwrap();
+    let db_path = temp_dir.path().join("test.db");
+    let mut graph = CodeGraph::open(&db_path).unwrap();
+    graph.set_parse_timeout(Some(Duration::from_millis(1)));
+
+    let err = graph
+        .index_file("slow.rs", slow_source().as_bytes())
+        .unwrap_err();
+    assert!(err.is::<magellan::ingest::pool::ParseTimeout>(), "{err:?}");
+    assert!(graph.get_file_node("slow.rs").unwrap().is_none());
+
+    // Small files still fit in the budget
+    graph.set_parse_timeout(None);
+    assert_eq!(graph.index_file("fast.rs", b"fn fast() {}").unwrap(), 1);
+}
+
+#[test]
+fn test_scan_skips_file_with_parse_timeout() {
+    let temp_dir = TempDir::new().unwrap();
+    let root = temp_dir.path().join("src");
+    fs::create_dir(&root).unwrap();
+    fs::write(root.join("slow.rs"), slow_source()).unwrap();
+    fs::write(root.join("fast.rs"), "fn fast() {}\n").unwrap();
+
+    let mut graph = CodeGraph::open(temp_dir.path().join("test.db")).unwrap();
+    graph.set_parse_timeout(Some(Duration::from_millis(1)));
+    let filter = FileFilter::new(&root, &[], &[]).unwrap();
+    let result = graph
+        .scan_directory_with_filter(&root, &filter, None)
+        .unwrap();
+
+    let timeouts: Vec<&str> = result
+        .diagnostics
+        .iter()
+        .filter_map(|d| match d {
+            WatchDiagnostic::Skipped {
+                path,
+                reason: SkipReason::ParseTimeout,
+            } => Some(path.as_str()),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(timeouts, vec!["slow.rs"], "{:?}", result.diagnostics);
+    let files = graph.all_file_nodes().unwrap();
+    assert!(files.keys().any(|path| path.ends_with("fast.rs")));
+    assert!(!files.keys().any(|path| path.ends_with("slow.rs")));
+}
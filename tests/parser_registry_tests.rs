@@ -0,0 +1,155 @@
+//! Tests for registering a custom `LanguageParser`
+//!
+//! A library user's parser for an extension Magellan does not know is picked
+//! up by language detection, `index_file` and directory scans.
+
+use magellan::ingest::{detect_language, register_parser, Language, LanguageParser};
+use magellan::{CodeGraph, FileFilter, SnapshotFileInput, SnapshotSpec, SymbolFact, SymbolKind};
+use std::fs;
+use std::path::Path;
+use std::sync::Arc;
+use tempfile::TempDir;
+
+/// Toy DSL: each `rule <name>` line defines a function-like symbol
+struct RuleParser;
+
+impl LanguageParser for RuleParser {
+    fn language(&self) -> Language {
+        Language::Custom("rules")
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["rules"]
+    }
+
+    fn extract(&self, path: &Path, source: &[u8]) -> Vec<SymbolFact> {
+        let text = String::from_utf8_lossy(source);
+        let mut facts = Vec::new();
+        let mut offset = 0;
+        for (index, line) in text.split_inclusive('\n').enumerate() {
+            if let Some(name) = line.trim_end().strip_prefix("rule ") {
+                let len = line.trim_end().len();
+                facts.push(SymbolFact {
+                    file_path: path.to_path_buf(),
+                    kind: SymbolKind::Function,
+                    kind_normalized: SymbolKind::Function.normalized_key().to_string(),
+                    name: Some(name.to_string()),
+                    fqn: Some(name.to_string()),
+                    canonical_fqn: None,
+                    display_fqn: None,
+                    byte_start: offset,
+                    byte_end: offset + len,
+                    start_line: index + 1,
+                    start_col: 0,
+                    end_line: index + 1,
+                    end_col: len,
+                    signature: None,
+                    is_test: false,
+                    visibility: None,
+                    deprecated: false,
+                    deprecation_message: None,
+                });
+            }
+            offset += line.len();
+        }
+        facts
+    }
+}
+
+const RULES: &str = "rule allow_admin\n# comment\nrule deny_guest\n";
+
+#[test]
+fn test_custom_parser_indexes_fake_extension() {
+    register_parser(Arc::new(RuleParser));
+    assert_eq!(
+        detect_language(Path::new("policy.rules")),
+        Some(Language::Custom("rules"))
+    );
+    // Built-in languages are unaffected
+    assert_eq!(detect_language(Path::new("main.rs")), Some(Language::Rust));
+
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("policy.rules");
+    fs::write(&path, RULES).unwrap();
+    let path_str = path.to_string_lossy().to_string();
+
+    let mut graph = CodeGraph::open(temp_dir.path().join("test.db")).unwrap();
+    assert_eq!(graph.index_file(&path_str, RULES.as_bytes()).unwrap(), 2);
+
+    let names: Vec<String> = graph
+        .symbols_in_file(&path_str)
+        .unwrap()
+        .into_iter()
+        .filter_map(|fact| fact.name)
+        .collect();
+    assert_eq!(names, vec!["allow_admin", "deny_guest"]);
+
+    // The language name becomes the symbols' label
+    let labelled = graph.get_symbols_by_label("rules").unwrap();
+    assert_eq!(labelled.len(), 2);
+}
+
+#[test]
+fn test_scan_picks_up_registered_extension() {
+    register_parser(Arc::new(RuleParser));
+
+    let temp_dir = TempDir::new().unwrap();
+    let root = temp_dir.path().join("src");
+    fs::create_dir(&root).unwrap();
+    fs::write(root.join("policy.rules"), RULES).unwrap();
+    fs::write(root.join("notes.txt"), "rule ignored\n").unwrap();
+
+    let mut graph = CodeGraph::open(temp_dir.path().join("test.db")).unwrap();
+    let filter = FileFilter::new(&root, &[], &[]).unwrap();
+    graph
+        .scan_directory_with_filter(&root, &filter, None)
+        .unwrap();
+
+    let files = graph.all_file_nodes().unwrap();
+    assert_eq!(files.len(), 1, "{:?}", files.keys());
+    assert!(files.keys().all(|path| path.ends_with("policy.rules")));
+    assert_eq!(graph.count_symbols().unwrap(), 2);
+}
+
+#[test]
+fn test_snapshot_ingest_uses_registered_parser() {
+    register_parser(Arc::new(RuleParser));
+
+    let temp_dir = TempDir::new().unwrap();
+    let repo_root = temp_dir.path().join("repo");
+    fs::create_dir_all(&repo_root).unwrap();
+
+    let graph = CodeGraph::open(temp_dir.path().join("test.db")).unwrap();
+    let snapshot = graph
+        .register_snapshot(&SnapshotSpec {
+            repo_root: repo_root.clone(),
+            commit_oid: "commit-1".to_string(),
+            tree_oid: "tree-1".to_string(),
+            author_time: 1,
+            commit_time: 1,
+            commit_message: "initial".to_string(),
+            parent_oids: Vec::new(),
+        })
+        .unwrap();
+
+    // A custom-language file must not abort the rest of the snapshot
+    let stats = graph
+        .ingest_snapshot_sources(
+            snapshot,
+            &repo_root,
+            &[
+                SnapshotFileInput {
+                    path: repo_root.join("policy.rules"),
+                    source: RULES.as_bytes().to_vec(),
+                },
+                SnapshotFileInput {
+                    path: repo_root.join("lib.rs"),
+                    source: b"fn lib() {}\n".to_vec(),
+                },
+            ],
+        )
+        .unwrap();
+
+    assert_eq!(stats.files_indexed, 2);
+    assert_eq!(stats.symbol_versions, 3);
+}
@@ -69,6 +69,43 @@ fn test_enum_definition() {
     assert_eq!(fact.name, Some("MyEnum".to_string()));
 }
 
+#[test]
+#[allow(deprecated)]
+fn test_macro_definition() {
+    let mut parser = Parser::new().unwrap();
+    let source = b"fn before() {}\nmacro_rules! foo {\n    ($x:expr) => { $x + 1 };\n}\n";
+    let facts = parser.extract_symbols(PathBuf::from("test.rs"), source);
+
+    let fact = facts
+        .iter()
+        .find(|f| f.kind == SymbolKind::Macro)
+        .expect("Should extract the macro definition");
+    assert_eq!(fact.name, Some("foo".to_string()));
+    assert_eq!(fact.kind_normalized, "macro");
+    assert_eq!(fact.start_line, 2);
+    assert_eq!(fact.end_line, 4);
+    assert_eq!(
+        &source[fact.byte_start..fact.byte_start + 12],
+        b"macro_rules!"
+    );
+    assert_eq!(fact.byte_end, source.len() - 1);
+}
+
+#[test]
+fn test_macro_definition_queryable_by_kind() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let mut graph = magellan::CodeGraph::open(temp_dir.path().join("test.db")).unwrap();
+    graph
+        .index_file("lib.rs", b"macro_rules! foo { () => {} }\nfn bar() {}\n")
+        .unwrap();
+
+    let macros = graph
+        .symbols_in_file_with_kind("lib.rs", Some(SymbolKind::Macro))
+        .unwrap();
+    assert_eq!(macros.len(), 1);
+    assert_eq!(macros[0].name.as_deref(), Some("foo"));
+}
+
 #[test]
 #[allow(deprecated)]
 fn test_trait_definition() {
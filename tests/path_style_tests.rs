@@ -0,0 +1,176 @@
+//! Tests for the stored path style (`--relative-paths` / `--absolute-paths`)
+//! and `CodeGraph::rebase_paths` (the `magellan rebase-paths` command).
+//!
+//! Each test indexes a small project and reads the stored paths back with
+//! plain SQL, so the assertions cover what other tools joining on the
+//! database would see.
+
+use magellan::{CodeGraph, PathStyle, RebaseRoot};
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+
+const LIB_RS: &str = "pub fn helper() -> u32 { 1 }\n\npub fn caller() -> u32 { helper() }\n";
+
+/// Project root (canonical) containing `src/lib.rs`, plus a database path outside it
+fn project() -> (TempDir, PathBuf, PathBuf) {
+    let temp_dir = TempDir::new().unwrap();
+    let root = temp_dir.path().join("proj");
+    std::fs::create_dir_all(root.join("src")).unwrap();
+    std::fs::write(root.join("src/lib.rs"), LIB_RS).unwrap();
+    let root = std::fs::canonicalize(root).unwrap();
+    let db_path = temp_dir.path().join("test.db");
+    (temp_dir, root, db_path)
+}
+
+/// Every distinct path stored for graph entities and side tables
+fn stored_paths(db_path: &Path) -> Vec<String> {
+    let conn = rusqlite::Connection::open(db_path).unwrap();
+    let mut stmt = conn
+        .prepare(
+            "SELECT file_path FROM graph_entities WHERE file_path IS NOT NULL
+             UNION SELECT json_extract(data, '$.path') FROM graph_entities WHERE kind = 'File'
+             UNION SELECT json_extract(data, '$.file_path') FROM graph_entities WHERE kind = 'Symbol'
+             UNION SELECT file_path FROM code_chunks
+             UNION SELECT file_path FROM file_metrics",
+        )
+        .unwrap();
+    let mut paths: Vec<String> = stmt
+        .query_map([], |row| row.get::<_, Option<String>>(0))
+        .unwrap()
+        .filter_map(|row| row.unwrap())
+        .collect();
+    paths.sort();
+    paths
+}
+
+fn file_node_count(db_path: &Path) -> i64 {
+    let conn = rusqlite::Connection::open(db_path).unwrap();
+    conn.query_row(
+        "SELECT COUNT(*) FROM graph_entities WHERE kind = 'File'",
+        [],
+        |row| row.get(0),
+    )
+    .unwrap()
+}
+
+#[test]
+fn test_relative_style_stores_root_relative_paths() {
+    let (_temp_dir, root, db_path) = project();
+    let lib = root.join("src/lib.rs");
+
+    {
+        let mut graph = CodeGraph::open(&db_path).unwrap();
+        graph.set_path_style(PathStyle::Relative, &root).unwrap();
+        graph.scan_directory(&root, None).unwrap();
+    }
+    assert_eq!(stored_paths(&db_path), vec!["src/lib.rs".to_string()]);
+
+    // The style is persisted: a later watch-style reconcile with the
+    // canonical path updates the same File node
+    let mut graph = CodeGraph::open(&db_path).unwrap();
+    assert_eq!(
+        graph.path_settings().map(|settings| settings.style),
+        Some(PathStyle::Relative)
+    );
+    std::fs::write(&lib, format!("{}pub fn added() {{}}\n", LIB_RS)).unwrap();
+    graph
+        .reconcile_file_path(&lib, &lib.to_string_lossy())
+        .unwrap();
+    assert_eq!(stored_paths(&db_path), vec!["src/lib.rs".to_string()]);
+    assert_eq!(file_node_count(&db_path), 1);
+
+    // Queries accept the stored (root-relative) path and report it back
+    let symbols = graph.symbols_in_file("src/lib.rs").unwrap();
+    let names: Vec<_> = symbols.iter().filter_map(|s| s.name.clone()).collect();
+    assert!(names.contains(&"added".to_string()), "{:?}", names);
+    assert!(symbols
+        .iter()
+        .all(|s| s.file_path == Path::new("src/lib.rs")));
+
+    graph.delete_file(&lib.to_string_lossy()).unwrap();
+    assert_eq!(file_node_count(&db_path), 0);
+}
+
+#[test]
+fn test_absolute_style_resolves_relative_input_against_root() {
+    let (_temp_dir, root, db_path) = project();
+    let expected = root.join("src/lib.rs").to_string_lossy().to_string();
+
+    let mut graph = CodeGraph::open(&db_path).unwrap();
+    graph.set_path_style(PathStyle::Absolute, &root).unwrap();
+    graph.index_file("src/lib.rs", LIB_RS.as_bytes()).unwrap();
+    graph.index_file(&expected, LIB_RS.as_bytes()).unwrap();
+
+    assert_eq!(stored_paths(&db_path), vec![expected]);
+    assert_eq!(file_node_count(&db_path), 1);
+}
+
+#[test]
+fn test_switching_style_on_populated_database_requires_rebase() {
+    let (_temp_dir, root, db_path) = project();
+
+    let mut graph = CodeGraph::open(&db_path).unwrap();
+    graph.scan_directory(&root, None).unwrap();
+
+    let err = graph
+        .set_path_style(PathStyle::Relative, &root)
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("rebase-paths"), "{}", err);
+    assert!(graph.path_settings().is_none());
+}
+
+#[test]
+fn test_rebase_paths_between_roots_and_styles() {
+    let (temp_dir, root, db_path) = project();
+    let absolute = root.join("src/lib.rs").to_string_lossy().to_string();
+
+    {
+        let mut graph = CodeGraph::open(&db_path).unwrap();
+        graph.set_path_style(PathStyle::Absolute, &root).unwrap();
+        graph.scan_directory(&root, None).unwrap();
+    }
+    assert_eq!(stored_paths(&db_path), vec![absolute]);
+
+    // Absolute -> relative
+    {
+        let mut graph = CodeGraph::open(&db_path).unwrap();
+        let report = graph
+            .rebase_paths(
+                &RebaseRoot::parse(&root.to_string_lossy()),
+                &RebaseRoot::parse("."),
+            )
+            .unwrap();
+        assert!(report.entities > 0 && report.side_rows > 0, "{:?}", report);
+    }
+    assert_eq!(stored_paths(&db_path), vec!["src/lib.rs".to_string()]);
+    {
+        let mut graph = CodeGraph::open(&db_path).unwrap();
+        let settings = graph.path_settings().unwrap();
+        assert_eq!(settings.style, PathStyle::Relative);
+        assert_eq!(settings.root, root);
+        assert!(!graph.symbols_in_file("src/lib.rs").unwrap().is_empty());
+    }
+
+    // Relative -> absolute under a new checkout location
+    let moved = temp_dir.path().join("moved");
+    {
+        let mut graph = CodeGraph::open(&db_path).unwrap();
+        graph
+            .rebase_paths(
+                &RebaseRoot::parse("."),
+                &RebaseRoot::parse(&moved.to_string_lossy()),
+            )
+            .unwrap();
+    }
+    let moved_lib = moved.join("src/lib.rs").to_string_lossy().to_string();
+    assert_eq!(stored_paths(&db_path), vec![moved_lib.clone()]);
+
+    let mut graph = CodeGraph::open(&db_path).unwrap();
+    assert_eq!(
+        graph.path_settings().map(|settings| settings.style),
+        Some(PathStyle::Absolute)
+    );
+    assert!(!graph.symbols_in_file(&moved_lib).unwrap().is_empty());
+    assert_eq!(file_node_count(&db_path), 1);
+}
@@ -0,0 +1,77 @@
+//! Tests for `CodeGraph::prune_orphans` (the `magellan prune` command).
+//!
+//! A chunk row is orphaned by hand (as an interrupted `delete_file` would
+//! leave it) and prune must remove exactly that row.
+
+use magellan::CodeGraph;
+use rusqlite::params;
+use tempfile::TempDir;
+
+fn chunk_count(db_path: &std::path::Path, file_path: &str) -> i64 {
+    let conn = rusqlite::Connection::open(db_path).unwrap();
+    conn.query_row(
+        "SELECT COUNT(*) FROM code_chunks WHERE file_path = ?1",
+        params![file_path],
+        |row| row.get(0),
+    )
+    .unwrap()
+}
+
+#[test]
+fn test_prune_removes_only_orphaned_chunk() {
+    let temp_dir = TempDir::new().unwrap();
+    let db_path = temp_dir.path().join("test.db");
+    let mut graph = CodeGraph::open(&db_path).unwrap();
+
+    let source = b"fn kept() {}\nfn also_kept() {}\n";
+    graph.index_file("src/kept.rs", source).unwrap();
+    let kept_chunks = chunk_count(&db_path, "src/kept.rs");
+    assert!(kept_chunks > 0, "indexing should store chunks");
+
+    // Simulate a delete that removed the File node but not its chunk.
+    {
+        let conn = rusqlite::Connection::open(&db_path).unwrap();
+        conn.execute(
+            "INSERT INTO code_chunks
+                (file_path, byte_start, byte_end, content, content_hash,
+                 symbol_name, symbol_kind, created_at)
+             VALUES ('src/gone.rs', 0, 10, 'fn gone(){}', 'hash', 'gone', 'fn', 0)",
+            [],
+        )
+        .unwrap();
+    }
+
+    let preview = graph.prune_orphans(true).unwrap();
+    assert!(preview.dry_run);
+    assert_eq!(preview.code_chunks, 1);
+    assert_eq!(preview.total(), 1, "only the orphaned chunk: {:?}", preview);
+    assert_eq!(
+        chunk_count(&db_path, "src/gone.rs"),
+        1,
+        "dry run must not delete"
+    );
+
+    let report = graph.prune_orphans(false).unwrap();
+    assert!(!report.dry_run);
+    assert_eq!(report.code_chunks, 1);
+    assert_eq!(report.total(), 1, "only the orphaned chunk: {:?}", report);
+    assert_eq!(chunk_count(&db_path, "src/gone.rs"), 0);
+    assert_eq!(chunk_count(&db_path, "src/kept.rs"), kept_chunks);
+
+    assert_eq!(graph.prune_orphans(false).unwrap().total(), 0);
+}
+
+#[test]
+fn test_prune_clean_database_is_noop() {
+    let temp_dir = TempDir::new().unwrap();
+    let db_path = temp_dir.path().join("test.db");
+    let mut graph = CodeGraph::open(&db_path).unwrap();
+
+    graph.index_file("src/a.rs", b"fn a() {}\n").unwrap();
+    graph.index_file("src/b.rs", b"fn b() {}\n").unwrap();
+    graph.delete_file("src/b.rs").unwrap();
+
+    let report = graph.prune_orphans(false).unwrap();
+    assert_eq!(report.total(), 0, "{:?}", report);
+    assert!(chunk_count(&db_path, "src/a.rs") > 0);
+}
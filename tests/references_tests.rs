@@ -1,4 +1,4 @@
-use magellan::{CodeGraph, Parser};
+use magellan::{CodeGraph, Parser, ReferenceKind};
 use tempfile::TempDir;
 
 #[test]
@@ -184,3 +184,109 @@ fn test_scoped_identifier_reference() {
         "Reference should be after foo's definition"
     );
 }
+
+#[test]
+fn test_reference_reports_enclosing_function() {
+    let temp_dir = TempDir::new().unwrap();
+    let db_path = temp_dir.path().join("test.db");
+    let mut graph = CodeGraph::open(&db_path).unwrap();
+
+    let source = b"
+        fn foo() {}
+        fn caller() {
+            foo();
+        }
+    ";
+
+    let path = "test.rs";
+    graph.index_file(path, source).unwrap();
+    graph.index_references(path, source).unwrap();
+
+    let foo_id = graph
+        .symbol_id_by_name(path, "foo")
+        .unwrap()
+        .expect("foo symbol should exist");
+    let caller_id = graph
+        .symbol_id_by_name(path, "caller")
+        .unwrap()
+        .expect("caller symbol should exist");
+    let references = graph.references_to_symbol(foo_id).unwrap();
+    assert_eq!(
+        references.len(),
+        1,
+        "Should find exactly 1 reference to foo"
+    );
+
+    let in_symbol = references[0]
+        .in_symbol
+        .as_ref()
+        .expect("reference should record its enclosing function");
+    assert_eq!(in_symbol.name, "caller");
+
+    let caller_symbol_id = magellan::graph::query::symbol_nodes_in_file_with_ids(&mut graph, path)
+        .unwrap()
+        .into_iter()
+        .find(|(node_id, _, _)| *node_id == caller_id)
+        .and_then(|(_, _, symbol_id)| symbol_id);
+    assert_eq!(in_symbol.symbol_id, caller_symbol_id);
+}
+
+#[test]
+fn test_use_declaration_produces_import_reference() {
+    let temp_dir = TempDir::new().unwrap();
+    let db_path = temp_dir.path().join("test.db");
+    let mut graph = CodeGraph::open(&db_path).unwrap();
+
+    let foo_path = "src/foo.rs";
+    let foo_source = b"pub struct Bar;\npub fn helper() {}\n";
+    let main_path = "src/main.rs";
+    let main_source =
+        b"use crate::foo::Bar;\nuse crate::foo::{helper};\n\nfn main() {\n    helper();\n}\n";
+    graph.index_file(foo_path, foo_source).unwrap();
+    graph.index_file(main_path, main_source).unwrap();
+    graph.index_references(main_path, main_source).unwrap();
+
+    let symbol_id_of = |graph: &mut CodeGraph, name: &str| {
+        magellan::graph::query::symbol_nodes_in_file_with_ids(graph, foo_path)
+            .unwrap()
+            .into_iter()
+            .find(|(_, fact, _)| fact.name.as_deref() == Some(name))
+            .and_then(|(_, _, symbol_id)| symbol_id)
+            .expect("symbol should have a stable id")
+    };
+    let bar_id = symbol_id_of(&mut graph, "Bar");
+    let helper_id = symbol_id_of(&mut graph, "helper");
+
+    let imports = graph.imports_of_file(main_path).unwrap();
+    assert_eq!(imports.len(), 2, "{:?}", imports);
+    assert_eq!(imports[0].symbol_id.as_deref(), Some(bar_id.as_str()));
+    assert_eq!(imports[0].fqn.as_deref(), Some("Bar"));
+    assert_eq!(imports[0].reference.kind, ReferenceKind::Import);
+    assert_eq!(imports[0].reference.start_line, 1);
+    assert!(imports[0]
+        .target_file
+        .as_deref()
+        .unwrap()
+        .ends_with(foo_path));
+    assert_eq!(imports[1].symbol_id.as_deref(), Some(helper_id.as_str()));
+    assert_eq!(imports[1].reference.start_line, 2);
+
+    // The call in main stays an ordinary usage reference
+    let helper_node = graph
+        .symbol_id_by_name(foo_path, "helper")
+        .unwrap()
+        .unwrap();
+    let mut kinds: Vec<_> = graph
+        .references_to_symbol(helper_node)
+        .unwrap()
+        .into_iter()
+        .map(|r| (r.start_line, r.kind))
+        .collect();
+    kinds.sort_by_key(|(line, _)| *line);
+    assert_eq!(
+        kinds,
+        vec![(2, ReferenceKind::Import), (5, ReferenceKind::Usage)]
+    );
+
+    assert!(graph.imports_of_file(foo_path).unwrap().is_empty());
+}
@@ -488,3 +488,103 @@ fn test_get_without_flags_has_no_rich_data() {
         }
     }
 }
+
+/// Run a command with `--with-checksums --output json` and return `data`
+fn checksums_json(db_path: &std::path::Path, args: &[&str]) -> serde_json::Value {
+    let output = Command::new(env!("CARGO_BIN_EXE_magellan"))
+        .args(&args[..1])
+        .arg("--db")
+        .arg(db_path)
+        .args(&args[1..])
+        .args(["--with-checksums", "--output", "json"])
+        .output()
+        .expect("Failed to run magellan");
+    assert!(
+        output.status.success(),
+        "{:?} failed: {}",
+        args,
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    json["data"].clone()
+}
+
+#[test]
+fn test_content_sha256_agrees_across_commands_and_reindexes() {
+    use sha2::{Digest, Sha256};
+
+    let temp_dir = TempDir::new().unwrap();
+    let db_path = temp_dir.path().join("test.db");
+    let source_file = temp_dir.path().join("test.rs");
+    let helper_body = "fn helper() {\n    println!(\"Helper function\");\n}";
+    let source = format!("{helper_body}\n\nfn main() {{\n    helper();\n}}\n");
+    std::fs::write(&source_file, &source).unwrap();
+    let file = source_file.to_string_lossy().to_string();
+    let expected = hex::encode(Sha256::digest(helper_body.as_bytes()));
+
+    index_test_file(&source_file, &db_path);
+
+    let helper_checksum = |db_path: &std::path::Path| -> Vec<serde_json::Value> {
+        let get = checksums_json(db_path, &["get", "--file", &file, "--symbol", "helper"]);
+        let find = checksums_json(db_path, &["find", "--name", "helper"]);
+        let query = checksums_json(db_path, &["query", "--file", &file]);
+        let query_helper = query["symbols"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|s| s["name"] == "helper")
+            .unwrap()
+            .clone();
+        [&get["symbol"], &find["matches"][0], &query_helper]
+            .iter()
+            .map(|symbol| symbol["span"]["checksums"]["content_sha256"].clone())
+            .collect()
+    };
+
+    // get, find, and query report the SHA-256 of the exact function text
+    let checksums = helper_checksum(&db_path);
+    for checksum in &checksums {
+        assert_eq!(
+            checksum.as_str(),
+            Some(expected.as_str()),
+            "{:?}",
+            checksums
+        );
+    }
+
+    // refs uses the same algorithm and field for call-site spans
+    let refs = checksums_json(
+        &db_path,
+        &[
+            "refs",
+            "--name",
+            "helper",
+            "--path",
+            &file,
+            "--direction",
+            "in",
+        ],
+    );
+    let reference = &refs["references"][0]["span"];
+    let start = reference["byte_start"].as_u64().unwrap() as usize;
+    let end = reference["byte_end"].as_u64().unwrap() as usize;
+    assert_eq!(
+        reference["checksums"]["content_sha256"].as_str(),
+        Some(hex::encode(Sha256::digest(&source.as_bytes()[start..end])).as_str())
+    );
+
+    // Reindexing identical content yields identical checksums
+    index_test_file(&source_file, &db_path);
+    assert_eq!(helper_checksum(&db_path), checksums);
+    let fresh_db = temp_dir.path().join("fresh.db");
+    index_test_file(&source_file, &fresh_db);
+    assert_eq!(helper_checksum(&fresh_db), checksums);
+
+    // The checksum describes the stored chunk, not the file as it is now
+    std::fs::write(&source_file, source.replace("Helper", "Edited")).unwrap();
+    let find = checksums_json(&db_path, &["find", "--name", "helper"]);
+    assert_eq!(
+        find["matches"][0]["span"]["checksums"]["content_sha256"].as_str(),
+        Some(expected.as_str())
+    );
+}
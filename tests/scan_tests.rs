@@ -82,6 +82,353 @@ fn test_scan_initial_flag_indexes_all_files_on_startup() {
     assert_eq!(symbol_count, 3, "Should have 3 symbols indexed");
 }
 
+#[test]
+fn test_scan_initial_prints_progress_to_stderr() {
+    let temp_dir = TempDir::new().unwrap();
+    let root_path = temp_dir.path().to_path_buf();
+    let db_path = temp_dir.path().join("magellan.db");
+
+    fs::create_dir_all(root_path.join("src")).unwrap();
+    fs::write(root_path.join("src/a.rs"), b"fn a() {}").unwrap();
+    fs::write(root_path.join("src/b.rs"), b"fn b() {}").unwrap();
+
+    let bin_path = std::env::var("CARGO_BIN_EXE_magellan").unwrap();
+    let run = |extra_args: &[&str]| {
+        let mut child = Command::new(&bin_path)
+            .arg("watch")
+            .arg("--root")
+            .arg(&root_path)
+            .arg("--db")
+            .arg(&db_path)
+            .arg("--scan-initial")
+            .args(extra_args)
+            .env("MAGELLAN_LOCAL", "1")
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .expect("Failed to start magellan binary");
+        thread::sleep(Duration::from_millis(500));
+        let _ = child.kill();
+        let output = child
+            .wait_with_output()
+            .expect("Failed to wait for process");
+        String::from_utf8_lossy(&output.stderr).to_string()
+    };
+
+    // stderr is a pipe, so progress is plain lines; the last one is 100%
+    let stderr = run(&[]);
+    assert!(
+        stderr.lines().any(|line| line == "indexed 2/2 (100%)"),
+        "stderr: {}",
+        stderr
+    );
+
+    let stderr = run(&["--output", "json"]);
+    assert!(!stderr.contains("indexed "), "stderr: {}", stderr);
+}
+
+#[test]
+fn test_watch_once_scans_and_exits() {
+    let temp_dir = TempDir::new().unwrap();
+    let root_path = temp_dir.path().to_path_buf();
+    let db_path = temp_dir.path().join("magellan.db");
+
+    fs::create_dir_all(root_path.join("src/generated")).unwrap();
+    fs::write(root_path.join("src/a.rs"), b"fn a() {}\nfn b() { a(); }").unwrap();
+    fs::write(root_path.join("src/c.rs"), b"fn c() {}").unwrap();
+    fs::write(root_path.join("src/generated/g.rs"), b"fn g() {}").unwrap();
+    fs::write(root_path.join("src/local.rs"), b"fn local() {}").unwrap();
+    fs::write(root_path.join(".gitignore"), b"src/local.rs\n").unwrap();
+
+    let bin_path = std::env::var("CARGO_BIN_EXE_magellan").unwrap();
+    let mut child = Command::new(&bin_path)
+        .arg("watch")
+        .arg("--root")
+        .arg(&root_path)
+        .arg("--db")
+        .arg(&db_path)
+        .args(["--once", "--exclude", "src/generated/**", "--jobs", "2"])
+        .env("MAGELLAN_LOCAL", "1")
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("Failed to start magellan binary");
+
+    // Must exit on its own instead of entering the watch loop
+    let deadline = std::time::Instant::now() + Duration::from_secs(30);
+    let status = loop {
+        if let Some(status) = child.try_wait().unwrap() {
+            break status;
+        }
+        if std::time::Instant::now() >= deadline {
+            let _ = child.kill();
+            panic!("watch --once did not exit");
+        }
+        thread::sleep(Duration::from_millis(50));
+    };
+    let output = child.wait_with_output().unwrap();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(stdout.contains("Indexed 2 files"), "{}", stdout);
+    assert!(
+        stdout.contains("Database: 2 files, 3 symbols"),
+        "{}",
+        stdout
+    );
+    assert!(!stdout.contains("Magellan watching"), "{}", stdout);
+
+    // Excluded and gitignored files were skipped
+    let graph = magellan::CodeGraph::open(&db_path).unwrap();
+    assert_eq!(graph.count_files().unwrap(), 2);
+    assert_eq!(graph.count_symbols().unwrap(), 3);
+}
+
+#[test]
+fn test_watch_once_skips_generated_files_unless_included() {
+    let temp_dir = TempDir::new().unwrap();
+    let root_path = temp_dir.path().join("repo");
+    fs::create_dir_all(root_path.join("src")).unwrap();
+    fs::write(root_path.join("src/lib.rs"), b"fn handwritten() {}").unwrap();
+    fs::write(
+        root_path.join("src/bindings.rs"),
+        b"// @generated by bindgen\nfn generated_a() {}\nfn generated_b() {}",
+    )
+    .unwrap();
+
+    let bin_path = std::env::var("CARGO_BIN_EXE_magellan").unwrap();
+    let run = |db_path: &std::path::Path, extra: &[&str]| {
+        let output = Command::new(&bin_path)
+            .arg("watch")
+            .arg("--root")
+            .arg(&root_path)
+            .arg("--db")
+            .arg(db_path)
+            .arg("--once")
+            .args(extra)
+            .env("MAGELLAN_LOCAL", "1")
+            .output()
+            .expect("Failed to run magellan binary");
+        assert!(
+            output.status.success(),
+            "stderr: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        magellan::CodeGraph::open(db_path).unwrap()
+    };
+
+    // Default: the file with a generated header is not indexed
+    let graph = run(&temp_dir.path().join("default.db"), &[]);
+    assert_eq!(graph.count_files().unwrap(), 1);
+    assert_eq!(graph.count_symbols().unwrap(), 1);
+
+    let graph = run(
+        &temp_dir.path().join("included.db"),
+        &["--include-generated"],
+    );
+    assert_eq!(graph.count_files().unwrap(), 2);
+    assert_eq!(graph.count_symbols().unwrap(), 3);
+}
+
+#[test]
+fn test_watch_once_index_non_source_lists_proto_without_symbols() {
+    let temp_dir = TempDir::new().unwrap();
+    let root_path = temp_dir.path().join("repo");
+    fs::create_dir_all(root_path.join("src")).unwrap();
+    fs::write(root_path.join("src/lib.rs"), b"fn handler() {}").unwrap();
+    fs::write(
+        root_path.join("src/api.proto"),
+        b"syntax = \"proto3\";\n\nmessage Ping {\n  string id = 1;\n}\n",
+    )
+    .unwrap();
+    fs::write(root_path.join("src/notes.txt"), b"not tracked").unwrap();
+
+    let bin_path = std::env::var("CARGO_BIN_EXE_magellan").unwrap();
+    let run = |args: &[&str]| {
+        Command::new(&bin_path)
+            .args(args)
+            .env("MAGELLAN_LOCAL", "1")
+            .output()
+            .expect("Failed to run magellan binary")
+    };
+    let watch_once = |db_path: &std::path::Path, extra: &[&str]| {
+        let mut args = vec![
+            "watch",
+            "--root",
+            root_path.to_str().unwrap(),
+            "--db",
+            db_path.to_str().unwrap(),
+            "--once",
+        ];
+        args.extend(extra);
+        let output = run(&args);
+        assert!(
+            output.status.success(),
+            "stderr: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+    };
+    // (file name, symbol count) of each indexed file, from `files --symbols`
+    let symbol_counts = |db_path: &std::path::Path| {
+        let db = db_path.to_str().unwrap();
+        let output = run(&["files", "--db", db, "--symbols", "--output", "json"]);
+        assert!(output.status.success());
+        let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+        let mut counts: Vec<(String, u64)> = json["data"]["symbol_counts"]
+            .as_object()
+            .unwrap()
+            .iter()
+            .map(|(path, count)| {
+                let name = std::path::Path::new(path).file_name().unwrap();
+                (name.to_string_lossy().to_string(), count.as_u64().unwrap())
+            })
+            .collect();
+        counts.sort();
+        counts
+    };
+
+    // Default: only source files are indexed
+    let default_db = temp_dir.path().join("default.db");
+    watch_once(&default_db, &[]);
+    assert_eq!(symbol_counts(&default_db), vec![("lib.rs".to_string(), 1)]);
+
+    let db_path = temp_dir.path().join("non_source.db");
+    watch_once(&db_path, &["--index-non-source"]);
+    assert_eq!(
+        symbol_counts(&db_path),
+        vec![("api.proto".to_string(), 0), ("lib.rs".to_string(), 1)]
+    );
+
+    // verify checks the tracked .proto for changes
+    fs::write(root_path.join("src/api.proto"), b"syntax = \"proto3\";\n").unwrap();
+    let output = run(&[
+        "verify",
+        "--root",
+        root_path.to_str().unwrap(),
+        "--db",
+        db_path.to_str().unwrap(),
+    ]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("Modified files (1)") && stdout.contains("api.proto"),
+        "verify should report the edited .proto, got: {}",
+        stdout
+    );
+}
+
+#[test]
+fn test_watch_once_mem_report_is_diagnostic_only() {
+    let temp_dir = TempDir::new().unwrap();
+    let root_path = temp_dir.path().join("repo");
+    fs::create_dir_all(root_path.join("src")).unwrap();
+    fs::write(root_path.join("src/a.rs"), b"fn a() {}\nfn b() { a(); }").unwrap();
+    fs::write(root_path.join("src/c.rs"), b"fn c() {}").unwrap();
+
+    let bin_path = std::env::var("CARGO_BIN_EXE_magellan").unwrap();
+    let run = |db_name: &str, extra: &[&str]| {
+        let db_path = temp_dir.path().join(db_name);
+        let output = Command::new(&bin_path)
+            .arg("watch")
+            .arg("--root")
+            .arg(&root_path)
+            .arg("--db")
+            .arg(&db_path)
+            .args(["--once", "--output", "json"])
+            .args(extra)
+            .env("MAGELLAN_LOCAL", "1")
+            .output()
+            .expect("Failed to run magellan binary");
+        assert!(
+            output.status.success(),
+            "stderr: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        let graph = magellan::CodeGraph::open(&db_path).unwrap();
+        let counts = (
+            graph.count_files().unwrap(),
+            graph.count_symbols().unwrap(),
+            graph.count_calls().unwrap(),
+        );
+        (String::from_utf8_lossy(&output.stdout).to_string(), counts)
+    };
+
+    let (plain_stdout, plain_counts) = run("plain.db", &[]);
+    assert!(!plain_stdout.contains("mem_report"), "{}", plain_stdout);
+
+    let (stdout, counts) = run("report.db", &["--mem-report"]);
+    assert_eq!(counts, plain_counts, "--mem-report must not change results");
+    let report: serde_json::Value = stdout
+        .lines()
+        .filter_map(|line| serde_json::from_str::<serde_json::Value>(line).ok())
+        .find(|value| value["kind"] == "mem_report")
+        .unwrap_or_else(|| panic!("no mem_report line in {}", stdout));
+    assert_eq!(report["file_index_entries"], 2);
+    assert!(report["parse_trees"].is_u64());
+    assert!(report["fqn_index_entries"].is_u64());
+    assert!(report["cache_entries"].is_u64());
+    if cfg!(target_os = "linux") {
+        assert!(report["peak_rss_bytes"].as_u64().unwrap() > 0);
+    }
+}
+
+#[test]
+fn test_watch_once_profile_is_observational() {
+    let temp_dir = TempDir::new().unwrap();
+    let root_path = temp_dir.path().join("repo");
+    fs::create_dir_all(root_path.join("src")).unwrap();
+    fs::write(root_path.join("src/a.rs"), b"fn a() {}\nfn b() { a(); }").unwrap();
+    fs::write(root_path.join("src/c.rs"), b"fn c() { a(); }").unwrap();
+
+    let bin_path = std::env::var("CARGO_BIN_EXE_magellan").unwrap();
+    let run = |db_name: &str, extra: &[&str]| {
+        let db_path = temp_dir.path().join(db_name);
+        let output = Command::new(&bin_path)
+            .arg("watch")
+            .arg("--root")
+            .arg(&root_path)
+            .arg("--db")
+            .arg(&db_path)
+            .args(["--once", "--output", "json"])
+            .args(extra)
+            .env("MAGELLAN_LOCAL", "1")
+            .output()
+            .expect("Failed to run magellan binary");
+        assert!(
+            output.status.success(),
+            "stderr: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        let graph = magellan::CodeGraph::open(&db_path).unwrap();
+        let counts = (
+            graph.count_files().unwrap(),
+            graph.count_symbols().unwrap(),
+            graph.count_references().unwrap(),
+            graph.count_calls().unwrap(),
+        );
+        (String::from_utf8_lossy(&output.stdout).to_string(), counts)
+    };
+
+    let (plain_stdout, plain_counts) = run("plain.db", &[]);
+    assert!(!plain_stdout.contains("\"profile\""), "{}", plain_stdout);
+
+    let (stdout, counts) = run("profile.db", &["--profile"]);
+    assert_eq!(counts, plain_counts, "--profile must not change results");
+    let profile: serde_json::Value = stdout
+        .lines()
+        .filter_map(|line| serde_json::from_str::<serde_json::Value>(line).ok())
+        .find(|value| value["kind"] == "profile")
+        .unwrap_or_else(|| panic!("no profile line in {}", stdout));
+    assert_eq!(profile["batches"], 1);
+    for phase in ["index_file", "index_references", "index_calls"] {
+        assert_eq!(profile["phases"][phase]["count"], 2, "{}", profile);
+        assert!(profile["phases"][phase]["total_ms"].is_u64());
+    }
+    assert!(profile["total_ms"].is_u64());
+}
+
 #[test]
 fn test_scan_only_processes_rs_files() {
     // Verify that --scan-initial only processes .rs files
@@ -129,3 +476,206 @@ fn test_scan_only_processes_rs_files() {
     let file_count = graph.count_files().unwrap();
     assert_eq!(file_count, 1, "Should only have 1 .rs file indexed");
 }
+
+#[test]
+fn test_index_files_from_touches_only_listed_paths() {
+    let temp_dir = TempDir::new().unwrap();
+    let root_path = fs::canonicalize(temp_dir.path()).unwrap();
+    let db_path = root_path.join("magellan.db");
+
+    let modified = root_path.join("modified.rs");
+    let removed = root_path.join("removed.rs");
+    let unlisted = root_path.join("unlisted.rs");
+    fs::write(&modified, b"fn old_modified() {}").unwrap();
+    fs::write(&removed, b"fn removed() {}").unwrap();
+    fs::write(&unlisted, b"fn old_unlisted() {}").unwrap();
+
+    {
+        let mut graph = magellan::CodeGraph::open(&db_path).unwrap();
+        graph.scan_directory(&root_path, None).unwrap();
+    }
+
+    // Change all three, but only list two of them
+    fs::write(&modified, b"fn new_modified() {}").unwrap();
+    fs::remove_file(&removed).unwrap();
+    fs::write(&unlisted, b"fn new_unlisted() {}").unwrap();
+    let list_path = temp_dir.path().join("changed.txt");
+    fs::write(&list_path, "modified.rs\nremoved.rs\n\n../outside.rs\n").unwrap();
+
+    let bin_path = std::env::var("CARGO_BIN_EXE_magellan").unwrap();
+    let output = Command::new(&bin_path)
+        .arg("index")
+        .arg("--db")
+        .arg(&db_path)
+        .arg("--root")
+        .arg(&root_path)
+        .arg("--files-from")
+        .arg(&list_path)
+        .env("MAGELLAN_LOCAL", "1")
+        .output()
+        .expect("Failed to run magellan index");
+    assert!(
+        output.status.success(),
+        "index failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("Reindexed 1 files, 0 unchanged, 1 deleted, 1 skipped"),
+        "unexpected summary: {}",
+        stdout
+    );
+
+    let mut graph = magellan::CodeGraph::open(&db_path).unwrap();
+    let names = |graph: &mut magellan::CodeGraph, path: &std::path::Path| -> Vec<String> {
+        graph
+            .symbols_in_file(&path.to_string_lossy())
+            .unwrap()
+            .into_iter()
+            .filter_map(|s| s.name)
+            .collect()
+    };
+    assert_eq!(names(&mut graph, &modified), vec!["new_modified"]);
+    assert!(names(&mut graph, &removed).is_empty());
+    assert!(graph
+        .get_file_node(&removed.to_string_lossy())
+        .unwrap()
+        .is_none());
+    // Not listed, so the stale facts are left alone
+    assert_eq!(names(&mut graph, &unlisted), vec!["old_unlisted"]);
+}
+
+#[test]
+fn test_index_changed_from_verifies_ref_and_reads_non_ascii_paths() {
+    let temp_dir = TempDir::new().unwrap();
+    let root_path = fs::canonicalize(temp_dir.path()).unwrap().join("repo");
+    let db_path = root_path.join("magellan.db");
+    fs::create_dir_all(&root_path).unwrap();
+
+    let git = |args: &[&str]| {
+        let output = Command::new("git")
+            .arg("-C")
+            .arg(&root_path)
+            .args(["-c", "user.name=test", "-c", "user.email=test@example.com"])
+            .args(args)
+            .output()
+            .expect("failed to execute git");
+        assert!(output.status.success(), "git {:?}: {:?}", args, output);
+    };
+
+    let cafe = root_path.join("café.rs");
+    fs::write(&cafe, b"fn old_cafe() {}").unwrap();
+    fs::write(root_path.join(".gitignore"), "magellan.db*\n").unwrap();
+    git(&["init", "-q"]);
+    git(&["add", "."]);
+    git(&["commit", "-q", "-m", "initial"]);
+    {
+        let mut graph = magellan::CodeGraph::open(&db_path).unwrap();
+        graph.scan_directory(&root_path, None).unwrap();
+    }
+    fs::write(&cafe, b"fn new_cafe() {}").unwrap();
+
+    let bin_path = std::env::var("CARGO_BIN_EXE_magellan").unwrap();
+    let index = |git_ref: &str| {
+        Command::new(&bin_path)
+            .arg("index")
+            .arg("--db")
+            .arg(&db_path)
+            .arg("--root")
+            .arg(&root_path)
+            .arg("--changed-from")
+            .arg(git_ref)
+            .env("MAGELLAN_LOCAL", "1")
+            .output()
+            .expect("Failed to run magellan index")
+    };
+
+    // A ref that looks like an option is rejected, not passed to git diff
+    let injected = temp_dir.path().join("injected.txt");
+    let output = index(&format!("--output={}", injected.display()));
+    assert!(!output.status.success(), "{:?}", output);
+    assert!(
+        String::from_utf8_lossy(&output.stderr).contains("is not a commit"),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(!injected.exists());
+
+    let output = index("HEAD");
+    assert!(
+        output.status.success(),
+        "index failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("Reindexed 1 files, 0 unchanged, 0 deleted, 0 skipped"),
+        "unexpected summary: {}",
+        stdout
+    );
+
+    let mut graph = magellan::CodeGraph::open(&db_path).unwrap();
+    let names: Vec<String> = graph
+        .symbols_in_file(&cafe.to_string_lossy())
+        .unwrap()
+        .into_iter()
+        .filter_map(|s| s.name)
+        .collect();
+    assert_eq!(names, vec!["new_cafe"]);
+}
+
+#[test]
+fn test_watch_dry_run_lists_files_without_indexing() {
+    let temp_dir = TempDir::new().unwrap();
+    let root_path = temp_dir.path().join("project");
+    let db_path = temp_dir.path().join("magellan.db");
+
+    fs::create_dir_all(root_path.join("src/generated")).unwrap();
+    fs::write(root_path.join("src/a.rs"), b"fn a() {}").unwrap();
+    fs::write(root_path.join("src/b.rs"), b"fn b() {}").unwrap();
+    fs::write(root_path.join("src/generated/g.rs"), b"fn g() {}").unwrap();
+    fs::write(root_path.join("src/local.rs"), b"fn local() {}").unwrap();
+    fs::write(root_path.join(".gitignore"), b"src/local.rs\n").unwrap();
+
+    let bin_path = std::env::var("CARGO_BIN_EXE_magellan").unwrap();
+    let dry_run = |extra: &[&str]| {
+        let output = Command::new(&bin_path)
+            .arg("watch")
+            .arg("--root")
+            .arg(&root_path)
+            .arg("--db")
+            .arg(&db_path)
+            .args(["--dry-run", "--exclude", "src/generated/**"])
+            .args(extra)
+            .env("MAGELLAN_LOCAL", "1")
+            .output()
+            .expect("Failed to run magellan binary");
+        assert!(
+            output.status.success(),
+            "stderr: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        String::from_utf8_lossy(&output.stdout).to_string()
+    };
+
+    // The gitignored and excluded files are not listed
+    let stdout = dry_run(&[]);
+    let listed: Vec<&str> = stdout.lines().filter(|l| l.ends_with(".rs")).collect();
+    assert_eq!(listed, vec!["src/a.rs", "src/b.rs"], "{}", stdout);
+    assert!(stdout.contains("2 file(s) would be indexed"), "{}", stdout);
+
+    let stdout = dry_run(&["--output", "json"]);
+    let json: serde_json::Value = serde_json::from_str(stdout.trim()).unwrap();
+    assert_eq!(json["data"]["count"], 2);
+    assert_eq!(
+        json["data"]["files"],
+        serde_json::json!(["src/a.rs", "src/b.rs"])
+    );
+
+    // --no-gitignore brings the ignored file back
+    let stdout = dry_run(&["--no-gitignore"]);
+    assert!(stdout.lines().any(|l| l == "src/local.rs"), "{}", stdout);
+
+    // Nothing was written
+    assert!(!db_path.exists());
+}
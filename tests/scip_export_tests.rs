@@ -5,7 +5,7 @@
 #![allow(clippy::needless_return)] // Allow explicit return for test clarity
 
 use protobuf::Message;
-use scip::types::Index;
+use scip::types::{Index, SymbolRole};
 use std::fs;
 use tempfile::TempDir;
 
@@ -382,3 +382,71 @@ fn test_scip_empty_graph() {
         "Empty graph should have no documents"
     );
 }
+
+#[test]
+fn test_scip_occurrence_roles() {
+    // Test: a function has one definition, and its call sites are references
+    let temp_dir = TempDir::new().unwrap();
+
+    let source = r#"
+fn helper() -> i32 {
+    42
+}
+
+fn first() -> i32 {
+    helper()
+}
+
+fn second() -> i32 {
+    helper() + helper()
+}
+"#;
+
+    let (db_path, _file_path) = create_test_graph_with_symbols(&temp_dir, source);
+
+    let graph = magellan::CodeGraph::open(&db_path).unwrap();
+    let config = magellan::graph::export::scip::ScipExportConfig::default();
+    let scip_bytes = magellan::graph::export::scip::export_scip(&graph, &config)
+        .unwrap_or_else(|e| panic!("Failed to export SCIP: {}", e));
+
+    // Exporting again yields identical bytes
+    let again = magellan::graph::export::scip::export_scip(&graph, &config).unwrap();
+    assert_eq!(scip_bytes, again, "SCIP export should be deterministic");
+
+    let parsed_index = Index::parse_from_bytes(&scip_bytes)
+        .unwrap_or_else(|e| panic!("Failed to parse SCIP bytes: {}", e));
+
+    let definition = SymbolRole::Definition as i32;
+    let read = SymbolRole::ReadAccess as i32;
+    let helper: Vec<_> = parsed_index
+        .documents
+        .iter()
+        .flat_map(|d| &d.occurrences)
+        .filter(|occ| occ.symbol == "magellan rust/helper.")
+        .collect();
+
+    let definitions: Vec<_> = helper
+        .iter()
+        .filter(|occ| occ.symbol_roles & definition != 0)
+        .collect();
+    assert_eq!(definitions.len(), 1, "{:?}", helper);
+    // SCIP lines are 0-indexed: `fn helper` is on the source's second line
+    assert_eq!(definitions[0].range[0], 1);
+    assert_eq!(definitions[0].symbol_roles, definition);
+
+    // Three call sites, on 0-indexed lines 6 and 10
+    let mut references: Vec<_> = helper
+        .iter()
+        .filter(|occ| occ.symbol_roles & definition == 0)
+        .map(|occ| {
+            assert_eq!(occ.symbol_roles, read, "{:?}", occ);
+            (occ.range[0], occ.range[1])
+        })
+        .collect();
+    references.sort();
+    assert_eq!(references.len(), 3, "{:?}", helper);
+    assert_eq!(
+        references.iter().map(|r| r.0).collect::<Vec<_>>(),
+        vec![6, 10, 10]
+    );
+}
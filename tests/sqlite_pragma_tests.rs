@@ -0,0 +1,203 @@
+//! Tests for the SQLite PRAGMA options accepted by `CodeGraph::open_with_options`.
+
+use magellan::{CodeGraph, JournalMode, SqliteOptions, Synchronous};
+
+fn journal_mode(db: &std::path::Path) -> String {
+    let conn = rusqlite::Connection::open(db).unwrap();
+    conn.query_row("PRAGMA journal_mode", [], |row| row.get(0))
+        .unwrap()
+}
+
+#[test]
+fn test_open_with_options_enables_wal() {
+    let tmp = tempfile::tempdir().unwrap();
+    let db = tmp.path().join("test.db");
+
+    let _graph = CodeGraph::open_with_options(&db, &SqliteOptions::default()).unwrap();
+
+    assert_eq!(journal_mode(&db), "wal");
+}
+
+#[test]
+fn test_open_with_options_delete_journal() {
+    let tmp = tempfile::tempdir().unwrap();
+    let db = tmp.path().join("test.db");
+    let options = SqliteOptions {
+        journal_mode: JournalMode::Delete,
+        synchronous: Synchronous::Full,
+        ..SqliteOptions::default()
+    };
+
+    let mut graph = CodeGraph::open_with_options(&db, &options).unwrap();
+    graph.index_file("src/lib.rs", b"fn main() {}").unwrap();
+
+    assert_eq!(journal_mode(&db), "delete");
+}
+
+#[test]
+fn test_open_reader_does_not_block_writer() {
+    let tmp = tempfile::tempdir().unwrap();
+    let db = tmp.path().join("test.db");
+    // A short busy timeout turns any lock wait into a prompt failure
+    let options = SqliteOptions {
+        busy_timeout_ms: 100,
+        ..SqliteOptions::default()
+    };
+
+    let mut graph = CodeGraph::open_with_options(&db, &options).unwrap();
+    graph.index_file("src/a.rs", b"fn a() {}").unwrap();
+
+    // Hold a read transaction open for the duration of the write
+    let reader = rusqlite::Connection::open(&db).unwrap();
+    reader.execute_batch("BEGIN").unwrap();
+    let before: i64 = reader
+        .query_row("SELECT COUNT(*) FROM graph_entities", [], |row| row.get(0))
+        .unwrap();
+
+    graph
+        .index_file("src/b.rs", b"fn b() {}\nfn c() {}")
+        .expect("writer must not be blocked by an open reader");
+
+    // The reader keeps its snapshot until it ends the transaction
+    let during: i64 = reader
+        .query_row("SELECT COUNT(*) FROM graph_entities", [], |row| row.get(0))
+        .unwrap();
+    assert_eq!(before, during);
+    reader.execute_batch("COMMIT").unwrap();
+
+    let after: i64 = reader
+        .query_row("SELECT COUNT(*) FROM graph_entities", [], |row| row.get(0))
+        .unwrap();
+    assert!(
+        after > before,
+        "reader should see the new symbols after commit"
+    );
+}
+
+/// Hold a write lock on `db` from another connection for `hold`
+fn hold_write_lock(db: &std::path::Path, hold: std::time::Duration) -> std::thread::JoinHandle<()> {
+    let locker = rusqlite::Connection::open(db).unwrap();
+    locker.execute_batch("BEGIN IMMEDIATE").unwrap();
+    std::thread::spawn(move || {
+        std::thread::sleep(hold);
+        locker.execute_batch("COMMIT").unwrap();
+    })
+}
+
+#[test]
+fn test_busy_writer_retries_until_lock_released() {
+    let tmp = tempfile::tempdir().unwrap();
+    let db = tmp.path().join("test.db");
+    let file = tmp.path().join("lib.rs");
+    std::fs::write(&file, "fn a() {}\n").unwrap();
+    let file_key = file.to_string_lossy().to_string();
+
+    // The timeout alone is far shorter than the lock is held; only the
+    // retries (50 + 100 + 200 + 400 ms of backoff) outlast it
+    let options = SqliteOptions {
+        busy_timeout_ms: 20,
+        busy_retries: 6,
+        ..SqliteOptions::default()
+    };
+    let mut graph = CodeGraph::open_with_options(&db, &options).unwrap();
+    graph.reconcile_file_path(&file, &file_key).unwrap();
+
+    std::fs::write(&file, "fn a() {}\nfn b() {}\n").unwrap();
+    let holder = hold_write_lock(&db, std::time::Duration::from_millis(300));
+    graph
+        .reconcile_file_path(&file, &file_key)
+        .expect("retrying writer should succeed once the lock is released");
+    holder.join().unwrap();
+
+    let names: Vec<_> = graph
+        .symbols_in_file(&file_key)
+        .unwrap()
+        .into_iter()
+        .filter_map(|s| s.name)
+        .collect();
+    assert!(names.contains(&"b".to_string()), "{:?}", names);
+}
+
+#[test]
+fn test_busy_writer_without_retries_fails() {
+    let tmp = tempfile::tempdir().unwrap();
+    let db = tmp.path().join("test.db");
+    let file = tmp.path().join("lib.rs");
+    std::fs::write(&file, "fn a() {}\n").unwrap();
+    let file_key = file.to_string_lossy().to_string();
+
+    let options = SqliteOptions {
+        busy_timeout_ms: 20,
+        busy_retries: 0,
+        ..SqliteOptions::default()
+    };
+    let mut graph = CodeGraph::open_with_options(&db, &options).unwrap();
+
+    let holder = hold_write_lock(&db, std::time::Duration::from_millis(500));
+    let result = graph.reconcile_file_path(&file, &file_key);
+    holder.join().unwrap();
+
+    let err = result.expect_err("write should fail while another connection holds the lock");
+    assert!(magellan::graph::pragmas::is_busy_error(&err), "{:#}", err);
+}
+
+#[test]
+fn test_open_readonly_queries_while_watcher_holds_db() {
+    let tmp = tempfile::tempdir().unwrap();
+    let db = tmp.path().join("test.db");
+
+    // The "watcher": a writable graph that stays open, mid-write
+    let mut writer = CodeGraph::open(&db).unwrap();
+    writer.index_file("src/a.rs", b"fn a() {}\n").unwrap();
+    let holder = hold_write_lock(&db, std::time::Duration::from_millis(500));
+
+    let started = std::time::Instant::now();
+    let mut reader =
+        CodeGraph::open_readonly(&db).expect("read-only open must not wait on the writer");
+    assert!(reader.is_read_only());
+    let names: Vec<_> = reader
+        .symbols_in_file("src/a.rs")
+        .unwrap()
+        .into_iter()
+        .filter_map(|s| s.name)
+        .collect();
+    assert_eq!(names, vec!["a".to_string()]);
+    assert!(
+        started.elapsed() < std::time::Duration::from_millis(500),
+        "read-only queries should not wait for the write lock"
+    );
+    holder.join().unwrap();
+    drop(writer);
+}
+
+#[test]
+fn test_open_readonly_rejects_writes() {
+    let tmp = tempfile::tempdir().unwrap();
+    let db = tmp.path().join("test.db");
+    CodeGraph::open(&db)
+        .unwrap()
+        .index_file("src/a.rs", b"fn a() {}\n")
+        .unwrap();
+
+    let mut reader = CodeGraph::open_readonly(&db).unwrap();
+    let started = std::time::Instant::now();
+    let err = reader
+        .index_file("src/b.rs", b"fn b() {}\n")
+        .expect_err("writes through a read-only graph must fail");
+    assert!(
+        format!("{:#}", err).contains("readonly"),
+        "unexpected error: {:#}",
+        err
+    );
+    assert!(
+        started.elapsed() < std::time::Duration::from_millis(1000),
+        "read-only write should fail without waiting on busy timeouts"
+    );
+
+    // Nothing was written, and a missing database is not created
+    let mut writable = CodeGraph::open(&db).unwrap();
+    assert!(writable.symbols_in_file("src/b.rs").unwrap().is_empty());
+    let missing = tmp.path().join("missing.db");
+    assert!(CodeGraph::open_readonly(&missing).is_err());
+    assert!(!missing.exists());
+}
@@ -380,3 +380,67 @@ fn test_status_human_mode_unchanged() {
         "Should not have JSON fields in human mode"
     );
 }
+
+#[test]
+#[cfg(unix)]
+fn test_status_watch_streams_one_line_per_interval() {
+    use std::io::{BufRead, BufReader};
+    use std::process::Stdio;
+
+    let temp_dir = TempDir::new().unwrap();
+    let db_path = temp_dir.path().join("magellan.db");
+    let file_path = temp_dir.path().join("test.rs");
+    fs::write(&file_path, b"struct Point;\nfn foo() {}\n").unwrap();
+    {
+        let mut graph = magellan::CodeGraph::open(&db_path).unwrap();
+        graph
+            .index_file(&file_path.to_string_lossy(), &fs::read(&file_path).unwrap())
+            .unwrap();
+    }
+
+    let bin_path = std::env::var("CARGO_BIN_EXE_magellan").unwrap_or_else(|_| {
+        let mut path = std::env::current_exe().unwrap();
+        path.pop();
+        path.pop();
+        path.push("magellan");
+        path.to_str().unwrap().to_string()
+    });
+    let mut child = Command::new(&bin_path)
+        .arg("status")
+        .arg("--db")
+        .arg(&db_path)
+        .arg("--watch")
+        .arg("--interval")
+        .arg("1")
+        .arg("--by-kind")
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .expect("Failed to spawn magellan status --watch");
+
+    // Two intervals produce two lines: one immediately, one a second later
+    let mut lines = BufReader::new(child.stdout.take().unwrap()).lines();
+    let snapshots: Vec<serde_json::Value> = (0..2)
+        .map(|_| serde_json::from_str(&lines.next().unwrap().unwrap()).unwrap())
+        .collect();
+
+    Command::new("kill")
+        .arg("-INT")
+        .arg(child.id().to_string())
+        .status()
+        .unwrap();
+    let status = child.wait().unwrap();
+    assert!(status.success(), "SIGINT should end the stream cleanly");
+
+    for snapshot in &snapshots {
+        let data = &snapshot["data"];
+        assert_eq!(data["files"], 1);
+        assert_eq!(data["symbols"], 2);
+        assert_eq!(data["symbols_by_kind"]["Class"], 1);
+        assert_eq!(data["symbols_by_kind"]["Function"], 1);
+    }
+    assert_eq!(
+        snapshots[0]["execution_id"], snapshots[1]["execution_id"],
+        "one run, one execution"
+    );
+}
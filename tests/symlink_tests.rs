@@ -409,3 +409,92 @@ fn test_symlink_to_symlink_that_escapes() {
     let result2 = is_safe_symlink(&link, root);
     assert!(result2.is_err());
 }
+
+// =========================================================================
+// Follow-Symlinks Scan Tests
+// =========================================================================
+
+fn scan_following_symlinks(root: &Path) -> usize {
+    scan_with_follow_symlinks(root, true).0
+}
+
+/// Scan `root` and return the indexed count plus the symbol names found
+fn scan_with_follow_symlinks(root: &Path, follow: bool) -> (usize, Vec<String>) {
+    let db_dir = TempDir::new().unwrap();
+    let db_path = db_dir.path().join("test.db");
+    let root = root.to_path_buf();
+
+    // Run on a separate thread so a symlink cycle fails the test instead of hanging it
+    let (tx, rx) = std::sync::mpsc::channel();
+    std::thread::spawn(move || {
+        let mut graph = magellan::CodeGraph::open(&db_path).unwrap();
+        graph.set_follow_symlinks(follow);
+        let indexed = graph.scan_directory(&root, None).unwrap();
+        let paths: Vec<String> = graph.all_file_nodes().unwrap().into_keys().collect();
+        let mut names: Vec<String> = paths
+            .iter()
+            .flat_map(|path| graph.symbols_in_file(path).unwrap())
+            .filter_map(|symbol| symbol.name)
+            .collect();
+        names.sort();
+        let _ = tx.send((indexed, names));
+    });
+
+    rx.recv_timeout(std::time::Duration::from_secs(30))
+        .expect("scan with follow_symlinks did not terminate")
+}
+
+#[test]
+#[cfg(unix)]
+fn test_follow_symlinks_indexes_files_behind_in_root_directory_link() {
+    let temp_dir = TempDir::new().unwrap();
+    let root = temp_dir.path();
+
+    // The real directory is gitignored, so its file is only reachable
+    // through the symlink under src/
+    fs::write(root.join(".gitignore"), b"/vendor/\n").unwrap();
+    let src = root.join("src");
+    fs::create_dir(&src).unwrap();
+    fs::write(src.join("lib.rs"), b"fn lib() {}").unwrap();
+    let vendor = root.join("vendor");
+    fs::create_dir(&vendor).unwrap();
+    fs::write(vendor.join("shared.rs"), b"fn shared() {}").unwrap();
+    create_symlink(&src.join("shared"), &vendor);
+
+    let (_, names) = scan_with_follow_symlinks(root, false);
+    assert_eq!(names, vec!["lib".to_string()]);
+
+    let (_, names) = scan_with_follow_symlinks(root, true);
+    assert_eq!(names, vec!["lib".to_string(), "shared".to_string()]);
+}
+
+#[test]
+#[cfg(unix)]
+fn test_follow_symlinks_self_referential_cycle_terminates() {
+    let temp_dir = TempDir::new().unwrap();
+    let root = temp_dir.path();
+
+    let src = root.join("src");
+    fs::create_dir(&src).unwrap();
+    fs::write(src.join("lib.rs"), b"fn lib() {}").unwrap();
+
+    // Directory symlink back to its own parent, plus a link to itself
+    create_symlink(&src.join("again"), &src);
+    create_symlink(&root.join("self_link"), Path::new("self_link"));
+
+    assert_eq!(scan_following_symlinks(root), 1);
+}
+
+#[test]
+#[cfg(unix)]
+fn test_follow_symlinks_skips_targets_outside_root() {
+    let temp_dir = TempDir::new().unwrap();
+    let root = temp_dir.path();
+    fs::write(root.join("main.rs"), b"fn main() {}").unwrap();
+
+    let outside_dir = TempDir::new().unwrap();
+    fs::write(outside_dir.path().join("outside.rs"), b"fn outside() {}").unwrap();
+    create_symlink(&root.join("external"), outside_dir.path());
+
+    assert_eq!(scan_following_symlinks(root), 1);
+}
@@ -153,6 +153,7 @@ fn test_file_node_json_serialization() {
         hash: "abc123".to_string(),
         last_indexed_at: 1234567890,
         last_modified: 1234567888,
+        hash_algo: Default::default(),
     };
 
     let serialized = serde_json::to_string(&file_node).unwrap();
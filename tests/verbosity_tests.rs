@@ -0,0 +1,123 @@
+//! Global `--quiet` / `--verbose` flag tests
+
+use magellan::CodeGraph;
+use std::fs;
+use std::process::Command;
+use tempfile::TempDir;
+
+fn bin_path() -> String {
+    std::env::var("CARGO_BIN_EXE_magellan").unwrap_or_else(|_| {
+        let mut path = std::env::current_exe().unwrap();
+        path.pop();
+        path.pop();
+        path.push("magellan");
+        path.to_str().unwrap().to_string()
+    })
+}
+
+#[test]
+fn test_quiet_suppresses_deprecation_warning() {
+    let temp_dir = TempDir::new().unwrap();
+    let db_path = temp_dir.path().join("test.db");
+    {
+        // Two definitions, so `--first` has to pick one and warns
+        let mut graph = CodeGraph::open(&db_path).unwrap();
+        for name in ["handler.rs", "parser.rs"] {
+            let file = temp_dir.path().join(name);
+            fs::write(&file, "fn handler() {}").unwrap();
+            let source = fs::read(&file).unwrap();
+            graph.index_file(&file.to_string_lossy(), &source).unwrap();
+        }
+    }
+
+    let find = |global: &[&str]| {
+        let output = Command::new(bin_path())
+            .args(global)
+            .args(["find", "--db"])
+            .arg(&db_path)
+            .args(["--name", "handler", "--first"])
+            .output()
+            .expect("Failed to execute magellan");
+        assert!(output.status.success(), "{:?}", output);
+        (
+            String::from_utf8_lossy(&output.stdout).to_string(),
+            String::from_utf8_lossy(&output.stderr).to_string(),
+        )
+    };
+
+    let (stdout, stderr) = find(&[]);
+    assert!(
+        stderr.contains("Warning: --first is deprecated"),
+        "{}",
+        stderr
+    );
+    assert!(stdout.contains("handler"), "{}", stdout);
+
+    // Same result on stdout, no warning or status notes on stderr
+    let (quiet_stdout, quiet_stderr) = find(&["--quiet"]);
+    assert_eq!(quiet_stdout, stdout);
+    assert!(quiet_stderr.is_empty(), "{}", quiet_stderr);
+    let (_, short_stderr) = find(&["-q"]);
+    assert!(short_stderr.is_empty(), "{}", short_stderr);
+}
+
+#[test]
+fn test_quiet_still_reports_errors() {
+    let temp_dir = TempDir::new().unwrap();
+    let db_path = temp_dir.path().join("test.db");
+    CodeGraph::open(&db_path).unwrap();
+
+    // LSIF has nowhere to go without --output: an error, not a warning
+    let output = Command::new(bin_path())
+        .args(["--quiet", "export", "--db"])
+        .arg(&db_path)
+        .args(["--format", "lsif"])
+        .output()
+        .expect("Failed to execute magellan");
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("requires --output"), "{}", stderr);
+}
+
+#[test]
+fn test_verbose_reports_each_scanned_file() {
+    let temp_dir = TempDir::new().unwrap();
+    let root = temp_dir.path().join("src");
+    fs::create_dir(&root).unwrap();
+    fs::write(root.join("a.rs"), "fn a() {}\n").unwrap();
+    fs::write(root.join("b.rs"), "fn b() {}\n").unwrap();
+    let db_path = temp_dir.path().join("test.db");
+
+    let output = Command::new(bin_path())
+        .args(["--verbose", "watch", "--root"])
+        .arg(&root)
+        .arg("--db")
+        .arg(&db_path)
+        .arg("--once")
+        .output()
+        .expect("Failed to execute magellan");
+    assert!(output.status.success(), "{:?}", output);
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    for name in ["a.rs", "b.rs"] {
+        assert!(
+            stderr
+                .lines()
+                .any(|line| line.starts_with('[') && line.ends_with(name)),
+            "missing progress line for {}: {}",
+            name,
+            stderr
+        );
+    }
+}
+
+#[test]
+fn test_quiet_and_verbose_conflict() {
+    let output = Command::new(bin_path())
+        .args(["-q", "-v", "status", "--db", "unused.db"])
+        .output()
+        .expect("Failed to execute magellan");
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("cannot be used together"), "{}", stderr);
+}
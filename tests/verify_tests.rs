@@ -8,8 +8,11 @@
 //! - verify detects new files (on filesystem but not in DB)
 //! - verify detects modified files (hash differs from DB)
 //! - verify detects stale files (timestamp old)
+//! - verify --integrity flags orphan symbols and dangling call edges
+//! - verify --overlaps flags symbols whose spans cross without nesting
 
-use magellan::CodeGraph;
+use magellan::verify::ChunkDriftKind;
+use magellan::{CodeGraph, HashAlgorithm, HashNormalization, ReconcileOutcome};
 use std::fs;
 use std::path::{Path, PathBuf};
 use tempfile::TempDir;
@@ -169,3 +172,433 @@ fn test_verify_detects_stale_files() {
         "Stale file should be stale.rs"
     );
 }
+
+#[test]
+fn test_verify_ids_stable_for_unchanged_content() {
+    let temp_dir = TempDir::new().unwrap();
+    let root_path = temp_dir.path().to_path_buf();
+    let db_path = temp_dir.path().join("test.db");
+
+    let file_path = create_test_file(
+        &root_path,
+        "lib.rs",
+        "struct Point;\nimpl Point {\n    fn new() -> Self { Point }\n}\nfn helper() {}\n",
+    );
+    let source = fs::read(&file_path).unwrap();
+    let path_str = file_path.to_string_lossy().to_string();
+
+    let mut graph = CodeGraph::open(&db_path).unwrap();
+    graph.index_file(&path_str, &source).unwrap();
+
+    let report = magellan::verify::verify_symbol_ids(&mut graph, &root_path).unwrap();
+
+    assert_eq!(report.files_checked, 1);
+    assert!(report.symbols_checked > 0, "Should compare indexed symbols");
+    assert!(
+        report.changed.is_empty(),
+        "IDs changed: {:?}",
+        report.changed
+    );
+    assert!(report.is_stable());
+}
+
+#[test]
+fn test_verify_ids_skips_modified_files() {
+    let temp_dir = TempDir::new().unwrap();
+    let root_path = temp_dir.path().to_path_buf();
+    let db_path = temp_dir.path().join("test.db");
+
+    let file_path = create_test_file(&root_path, "edited.rs", "fn before() {}");
+    let source = fs::read(&file_path).unwrap();
+    let path_str = file_path.to_string_lossy().to_string();
+
+    let mut graph = CodeGraph::open(&db_path).unwrap();
+    graph.index_file(&path_str, &source).unwrap();
+
+    fs::write(&file_path, "fn after() {}").unwrap();
+
+    let report = magellan::verify::verify_symbol_ids(&mut graph, &root_path).unwrap();
+
+    assert_eq!(report.files_checked, 0);
+    assert_eq!(report.skipped_modified, vec![path_str]);
+    assert!(report.is_stable());
+}
+
+#[test]
+fn test_diff_symbol_id_maps_reports_changed_ids() {
+    use std::collections::BTreeMap;
+
+    let live = BTreeMap::from([
+        (
+            "c::src/a.rs::Function kept".to_string(),
+            vec!["1111".to_string()],
+        ),
+        (
+            "c::src/a.rs::Function moved".to_string(),
+            vec!["2222".to_string()],
+        ),
+        (
+            "c::src/a.rs::Function gone".to_string(),
+            vec!["3333".to_string()],
+        ),
+    ]);
+    let reindexed = BTreeMap::from([
+        (
+            "c::src/a.rs::Function kept".to_string(),
+            vec!["1111".to_string()],
+        ),
+        (
+            "c::src/a.rs::Function moved".to_string(),
+            vec!["9999".to_string()],
+        ),
+    ]);
+
+    let report = magellan::verify::diff_symbol_id_maps(&live, &reindexed);
+
+    assert_eq!(report.symbols_checked, 2);
+    assert_eq!(report.changed.len(), 1);
+    assert_eq!(report.changed[0].fqn, "c::src/a.rs::Function moved");
+    assert_eq!(report.changed[0].live_ids, vec!["2222".to_string()]);
+    assert_eq!(report.changed[0].reindexed_ids, vec!["9999".to_string()]);
+    assert_eq!(
+        report.only_live,
+        vec!["c::src/a.rs::Function gone".to_string()]
+    );
+    assert!(report.only_reindexed.is_empty());
+    assert!(!report.is_stable());
+}
+
+#[test]
+fn test_normalize_eol_hashes_crlf_and_lf_alike() {
+    let temp_dir = TempDir::new().unwrap();
+    let root_path = temp_dir.path().to_path_buf();
+    let crlf = "fn first() {}\r\nfn second() {}\r\n";
+    let lf = "fn first() {}\nfn second() {}\n";
+    let file_path = create_test_file(&root_path, "eol.rs", crlf);
+    let path_str = file_path.to_string_lossy().to_string();
+
+    let file_hash = |normalization: HashNormalization, source: &str| {
+        let db = TempDir::new().unwrap();
+        let mut graph = CodeGraph::open(db.path().join("test.db")).unwrap();
+        graph.set_hash_normalization(normalization).unwrap();
+        graph.index_file(&path_str, source.as_bytes()).unwrap();
+        graph.get_file_node(&path_str).unwrap().unwrap().hash
+    };
+
+    let off = HashNormalization::default();
+    assert_ne!(file_hash(off, crlf), file_hash(off, lf));
+
+    let eol = HashNormalization {
+        eol: true,
+        trailing_whitespace: false,
+    };
+    assert_eq!(file_hash(eol, crlf), file_hash(eol, lf));
+
+    // The setting persists, and verify sees an LF checkout of a CRLF index as unchanged
+    let db_path = temp_dir.path().join("test.db");
+    {
+        let mut graph = CodeGraph::open(&db_path).unwrap();
+        graph.set_hash_normalization(eol).unwrap();
+        graph.index_file(&path_str, crlf.as_bytes()).unwrap();
+    }
+    fs::write(&file_path, lf).unwrap();
+    let mut graph = CodeGraph::open(&db_path).unwrap();
+    assert_eq!(graph.hash_normalization(), eol);
+    let report = magellan::verify::verify_graph(&mut graph, &root_path).unwrap();
+    assert!(report.modified.is_empty(), "{:?}", report.modified);
+
+    // Spans and chunks still describe the original CRLF bytes
+    let chunks = graph.get_code_chunks(&path_str).unwrap();
+    assert!(chunks
+        .iter()
+        .any(|c| c.content == "fn second() {}" && c.byte_start == 15));
+}
+
+#[test]
+fn test_switching_hash_algorithm_keeps_change_detection_consistent() {
+    let temp_dir = TempDir::new().unwrap();
+    let root_path = temp_dir.path().to_path_buf();
+    let db_path = temp_dir.path().join("test.db");
+    let kept = create_test_file(&root_path, "kept.rs", "fn kept() {}\n");
+    let edited = create_test_file(&root_path, "edited.rs", "fn edited() {}\n");
+    let kept_str = kept.to_string_lossy().to_string();
+    let edited_str = edited.to_string_lossy().to_string();
+
+    {
+        let mut graph = CodeGraph::open(&db_path).unwrap();
+        assert_eq!(graph.hash_algorithm(), HashAlgorithm::Xxh3);
+        graph.reconcile_file_path(&kept, &kept_str).unwrap();
+        graph.reconcile_file_path(&edited, &edited_str).unwrap();
+    }
+
+    // Switch algorithms; the choice persists across reopen
+    {
+        let mut graph = CodeGraph::open(&db_path).unwrap();
+        graph.set_hash_algorithm(HashAlgorithm::Blake3).unwrap();
+    }
+    fs::write(&edited, "fn edited() { let _ = 1; }\n").unwrap();
+    let mut graph = CodeGraph::open(&db_path).unwrap();
+    assert_eq!(graph.hash_algorithm(), HashAlgorithm::Blake3);
+
+    // An unchanged file is checked against its own algorithm, not reindexed
+    assert!(matches!(
+        graph.reconcile_file_path(&kept, &kept_str).unwrap(),
+        ReconcileOutcome::Unchanged
+    ));
+    assert!(matches!(
+        graph.reconcile_file_path(&edited, &edited_str).unwrap(),
+        ReconcileOutcome::Reindexed { .. }
+    ));
+    let kept_node = graph.get_file_node(&kept_str).unwrap().unwrap();
+    let edited_node = graph.get_file_node(&edited_str).unwrap().unwrap();
+    assert_eq!(kept_node.hash_algo, HashAlgorithm::Xxh3);
+    assert_eq!(edited_node.hash_algo, HashAlgorithm::Blake3);
+    assert_eq!(
+        edited_node.hash,
+        HashAlgorithm::Blake3.digest(&fs::read(&edited).unwrap())
+    );
+
+    // A database mixing both algorithms verifies clean, then flags a real edit
+    let report = magellan::verify::verify_graph(&mut graph, &root_path).unwrap();
+    assert!(report.modified.is_empty(), "{:?}", report.modified);
+    fs::write(&kept, "fn kept() { let _ = 2; }\n").unwrap();
+    let report = magellan::verify::verify_graph(&mut graph, &root_path).unwrap();
+    assert_eq!(report.modified, vec![kept_str]);
+}
+
+#[test]
+fn test_verify_chunks_flags_corrupted_chunk() {
+    let temp_dir = TempDir::new().unwrap();
+    let root_path = temp_dir.path().to_path_buf();
+    let db_path = temp_dir.path().join("test.db");
+    let source = "fn first() {}\n\nfn second() -> u32 {\n    2\n}\n";
+    let file_path = create_test_file(&root_path, "lib.rs", source);
+    let path_str = file_path.to_string_lossy().to_string();
+    {
+        let mut graph = CodeGraph::open(&db_path).unwrap();
+        graph.index_file(&path_str, source.as_bytes()).unwrap();
+        let report = magellan::verify::verify_chunks(&graph, &root_path).unwrap();
+        assert!(report.is_clean(), "{:?}", report.drifted);
+        assert_eq!(report.chunks_checked, 2);
+        assert_eq!(report.files_checked, 1);
+    }
+
+    // Corrupt the stored content of one chunk behind the graph's back
+    let conn = rusqlite::Connection::open(&db_path).unwrap();
+    let updated = conn
+        .execute(
+            "UPDATE code_chunks SET content = 'fn second() -> u32 { 3 }' WHERE symbol_name = 'second'",
+            [],
+        )
+        .unwrap();
+    assert_eq!(updated, 1);
+    drop(conn);
+
+    let graph = CodeGraph::open(&db_path).unwrap();
+    let report = magellan::verify::verify_chunks(&graph, &root_path).unwrap();
+    assert_eq!(report.drifted.len(), 1, "{:?}", report.drifted);
+    let drift = &report.drifted[0];
+    assert_eq!(drift.symbol_name.as_deref(), Some("second"));
+    assert_eq!(drift.kind, ChunkDriftKind::ContentMismatch);
+    assert_eq!(drift.disk_hash.as_ref(), Some(&drift.stored_hash));
+
+    // A file edited without re-storing its chunks drifts by hash
+    fs::write(
+        &file_path,
+        source.replace("fn first() {}", "fn first() { 1; }"),
+    )
+    .unwrap();
+    let report = magellan::verify::verify_chunks(&graph, &root_path).unwrap();
+    let first = report
+        .drifted
+        .iter()
+        .find(|d| d.symbol_name.as_deref() == Some("first"))
+        .expect("edited chunk is flagged");
+    assert_eq!(first.kind, ChunkDriftKind::HashMismatch);
+
+    // The CLI exits nonzero on drift
+    let output = std::process::Command::new(bin_path())
+        .args(["chunks", "--db"])
+        .arg(&db_path)
+        .args(["--verify", "--root"])
+        .arg(&root_path)
+        .args(["--output", "json"])
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(1));
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["data"]["chunks_checked"], 2);
+    assert_eq!(json["data"]["drifted"].as_array().unwrap().len(), 2);
+
+    // Chunks of deleted files are skipped, not flagged
+    fs::remove_file(&file_path).unwrap();
+    let report = magellan::verify::verify_chunks(&graph, &root_path).unwrap();
+    assert!(report.is_clean());
+    assert_eq!(report.chunks_checked, 0);
+    assert_eq!(report.files_missing, vec![path_str]);
+}
+
+fn bin_path() -> String {
+    std::env::var("CARGO_BIN_EXE_magellan").unwrap_or_else(|_| {
+        let mut path = std::env::current_exe().unwrap();
+        path.pop();
+        path.pop();
+        path.push("magellan");
+        path.to_str().unwrap().to_string()
+    })
+}
+
+const CALLING_SOURCE: &str = "struct Point;\n\nimpl Point {\n    fn origin() -> Self {\n        Point\n    }\n}\n\nfn helper() {}\n\nfn main() {\n    helper();\n    Point::origin();\n}\n";
+
+#[test]
+fn test_verify_integrity_clean_database() {
+    let temp_dir = TempDir::new().unwrap();
+    let db_path = temp_dir.path().join("test.db");
+    let file_path = create_test_file(temp_dir.path(), "main.rs", CALLING_SOURCE);
+
+    let mut graph = CodeGraph::open(&db_path).unwrap();
+    graph
+        .index_file(&file_path.to_string_lossy(), CALLING_SOURCE.as_bytes())
+        .unwrap();
+
+    let report = magellan::verify::verify_integrity(&graph).unwrap();
+    assert!(report.is_clean(), "{:?}", report);
+}
+
+#[test]
+fn test_verify_integrity_flags_orphan_symbols() {
+    let temp_dir = TempDir::new().unwrap();
+    let db_path = temp_dir.path().join("test.db");
+    let file_path = create_test_file(temp_dir.path(), "main.rs", CALLING_SOURCE);
+
+    {
+        let mut graph = CodeGraph::open(&db_path).unwrap();
+        graph
+            .index_file(&file_path.to_string_lossy(), CALLING_SOURCE.as_bytes())
+            .unwrap();
+    }
+
+    // Drop the File row behind the graph's back, as a corrupted copy might
+    let conn = rusqlite::Connection::open(&db_path).unwrap();
+    let deleted = conn
+        .execute("DELETE FROM graph_entities WHERE kind = 'File'", [])
+        .unwrap();
+    assert_eq!(deleted, 1);
+    drop(conn);
+
+    let graph = CodeGraph::open(&db_path).unwrap();
+    let report = magellan::verify::verify_integrity(&graph).unwrap();
+    let mut names: Vec<&str> = report
+        .orphan_symbols
+        .iter()
+        .map(|s| s.name.as_str())
+        .collect();
+    names.sort();
+    assert_eq!(names, vec!["Point", "helper", "main", "origin"]);
+    assert!(report.sqlite_errors.is_empty());
+    drop(graph);
+
+    // The CLI exits nonzero without needing --root
+    let output = std::process::Command::new(bin_path())
+        .args(["verify", "--integrity", "--output", "json", "--db"])
+        .arg(&db_path)
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(1), "{:?}", output);
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(
+        json["data"]["integrity"]["orphan_symbols"]
+            .as_array()
+            .unwrap()
+            .len(),
+        4
+    );
+    assert_eq!(json["data"]["is_clean"], false);
+}
+
+#[test]
+fn test_verify_integrity_flags_dangling_call_edges() {
+    let temp_dir = TempDir::new().unwrap();
+    let db_path = temp_dir.path().join("test.db");
+    let file_path = create_test_file(temp_dir.path(), "main.rs", CALLING_SOURCE);
+
+    {
+        let mut graph = CodeGraph::open(&db_path).unwrap();
+        graph
+            .index_file(&file_path.to_string_lossy(), CALLING_SOURCE.as_bytes())
+            .unwrap();
+    }
+
+    let conn = rusqlite::Connection::open(&db_path).unwrap();
+    conn.execute(
+        "DELETE FROM graph_entities WHERE kind = 'Symbol' AND name = 'helper'",
+        [],
+    )
+    .unwrap();
+    drop(conn);
+
+    let graph = CodeGraph::open(&db_path).unwrap();
+    let report = magellan::verify::verify_integrity(&graph).unwrap();
+    assert!(report.orphan_symbols.is_empty(), "{:?}", report);
+    assert!(!report.dangling_edges.is_empty());
+    assert!(report
+        .dangling_edges
+        .iter()
+        .all(|edge| edge.missing_from || edge.missing_to));
+    assert!(report
+        .dangling_edges
+        .iter()
+        .any(|edge| edge.edge_type == "CALLS" && edge.missing_to));
+}
+
+#[test]
+fn test_verify_overlaps_flags_crossing_spans() {
+    let temp_dir = TempDir::new().unwrap();
+    let db_path = temp_dir.path().join("test.db");
+    let file_path = create_test_file(temp_dir.path(), "main.rs", CALLING_SOURCE);
+
+    {
+        let mut graph = CodeGraph::open(&db_path).unwrap();
+        graph
+            .index_file(&file_path.to_string_lossy(), CALLING_SOURCE.as_bytes())
+            .unwrap();
+        // origin() nests inside the Point impl, which is not an overlap
+        assert!(graph.overlapping_symbol_spans().unwrap().is_empty());
+    }
+
+    // Stretch helper() so it ends inside main(), as a bad parse might
+    let main_start = CALLING_SOURCE.find("fn main").unwrap() as i64;
+    let conn = rusqlite::Connection::open(&db_path).unwrap();
+    let updated = conn
+        .execute(
+            "UPDATE graph_entities SET data = json_set(data, '$.byte_end', ?1)
+             WHERE kind = 'Symbol' AND name = 'helper'",
+            [main_start + 4],
+        )
+        .unwrap();
+    assert_eq!(updated, 1);
+    drop(conn);
+
+    let graph = CodeGraph::open(&db_path).unwrap();
+    let overlaps = graph.overlapping_symbol_spans().unwrap();
+    assert_eq!(overlaps.len(), 1, "{:?}", overlaps);
+    assert_eq!(overlaps[0].first.name, "helper");
+    assert_eq!(overlaps[0].second.name, "main");
+    assert_eq!(overlaps[0].first.byte_end, main_start as u64 + 4);
+    assert_eq!(overlaps[0].second.byte_start, main_start as u64);
+    assert!(!overlaps[0].duplicate);
+    drop(graph);
+
+    let output = std::process::Command::new(bin_path())
+        .args(["verify", "--overlaps", "--output", "json", "--db"])
+        .arg(&db_path)
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(1), "{:?}", output);
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let reported = json["data"]["overlaps"].as_array().unwrap();
+    assert_eq!(reported.len(), 1);
+    assert_eq!(reported[0]["first"]["name"], "helper");
+    assert_eq!(reported[0]["second"]["name"], "main");
+    assert_eq!(json["data"]["is_clean"], false);
+}
@@ -42,3 +42,39 @@ fn test_wal_growth_after_bulk_insert() {
         );
     }
 }
+
+#[test]
+fn test_checkpoint_shrinks_wal_after_heavy_writes() {
+    let tmp = tempfile::tempdir().unwrap();
+    let db = tmp.path().join("test.db");
+    let wal_path = db.with_extension("db-wal");
+
+    // Keep the graph open so closing the last connection does not checkpoint
+    let mut graph = CodeGraph::open(&db).expect("open should succeed");
+    for i in 0..50 {
+        let source: String = (0..20)
+            .map(|j| format!("pub fn f_{}_{}() {{ f_{}_0(); }}\n", i, j, i))
+            .collect();
+        graph
+            .index_file(&format!("src/m{}.rs", i), source.as_bytes())
+            .expect("index should succeed");
+    }
+
+    let before = std::fs::metadata(&wal_path).map(|m| m.len()).unwrap_or(0);
+    assert!(before > 0, "heavy writes should leave frames in the WAL");
+
+    let stats = graph
+        .checkpoint_wal_stats()
+        .expect("checkpoint should succeed");
+    assert!(!stats.busy);
+    assert_eq!(stats.checkpointed_frames, stats.log_frames);
+
+    let after = std::fs::metadata(&wal_path).map(|m| m.len()).unwrap_or(0);
+    assert!(
+        after < before,
+        "checkpoint should shrink the WAL: {} -> {} bytes",
+        before,
+        after
+    );
+    assert_eq!(after, 0, "TRUNCATE checkpoint should empty the WAL");
+}
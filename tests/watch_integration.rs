@@ -2,11 +2,17 @@
 //!
 //! Verifies that project config correctly filters paths during scan,
 //! and that backward compatibility is maintained when no config exists.
+//! Also covers several `--root`s watched into one database, each with its
+//! own config and ignore rules.
 
 use std::fs;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 use magellan::project_config::ProjectConfig;
-use magellan::CodeGraph;
+use magellan::{CodeGraph, WatchPipelineConfig, WatcherConfig};
 
 fn create_temp_project() -> tempfile::TempDir {
     let dir = tempfile::tempdir().unwrap();
@@ -236,6 +242,7 @@ fn config_round_trip() {
             debounce_ms: 1000,
             gitignore_aware: false,
             scan_initial: true,
+            ..Default::default()
         },
     };
 
@@ -250,3 +257,92 @@ fn config_round_trip() {
     assert!(!loaded.watch.gitignore_aware);
     assert!(loaded.watch.scan_initial);
 }
+
+/// Names of the symbols stored for `file_path`, sorted
+fn stored_symbols(db_path: &Path, file_path: &Path) -> Vec<String> {
+    let conn = rusqlite::Connection::open(db_path).unwrap();
+    let mut stmt = conn
+        .prepare("SELECT name FROM graph_entities WHERE kind = 'Symbol' AND file_path = ?1 ORDER BY name")
+        .unwrap();
+    stmt.query_map([file_path.to_string_lossy()], |row| row.get(0))
+        .unwrap()
+        .collect::<Result<_, _>>()
+        .unwrap()
+}
+
+/// Test: two sibling roots watched into one database keep their own filters,
+/// and edits under either root are indexed under that root's path.
+#[test]
+fn watch_multiple_roots_into_one_database() {
+    let dir = tempfile::tempdir().unwrap();
+    let base = fs::canonicalize(dir.path()).unwrap();
+    let db_path = base.join("workspace.db");
+    let (core, cli) = (base.join("core"), base.join("cli"));
+    for root in [&core, &cli] {
+        fs::create_dir_all(root.join("src")).unwrap();
+        fs::write(root.join("src/scratch.rs"), "fn scratch() {}\n").unwrap();
+    }
+    // Same relative path in both roots
+    fs::write(core.join("src/lib.rs"), "fn core_v1() {}\n").unwrap();
+    fs::write(cli.join("src/lib.rs"), "fn cli_v1() {}\n").unwrap();
+    // Each root's rules apply only to that root
+    fs::write(core.join(".gitignore"), "src/scratch.rs\n").unwrap();
+    fs::write(
+        cli.join(".magellan.toml"),
+        "[index]\ninclude = [\"src/\"]\nexclude = [\"src/lib.rs\"]\n",
+    )
+    .unwrap();
+
+    // Create the schema up front so polling never races the pipeline's open
+    drop(CodeGraph::open(&db_path).unwrap());
+
+    let watcher_config = WatcherConfig {
+        debounce_ms: 100,
+        ..WatcherConfig::default()
+    };
+    let mut config = WatchPipelineConfig::new(core.clone(), db_path.clone(), watcher_config, true);
+    config.extra_roots = vec![cli.clone()];
+    let shutdown = Arc::new(AtomicBool::new(false));
+    let shutdown_pipeline = shutdown.clone();
+    let handle =
+        std::thread::spawn(move || magellan::run_watch_pipeline(config, shutdown_pipeline));
+
+    let wait_for = |file: &Path, expected: &[&str]| {
+        let deadline = Instant::now() + Duration::from_secs(15);
+        loop {
+            let symbols = stored_symbols(&db_path, file);
+            if symbols == expected {
+                return;
+            }
+            assert!(
+                Instant::now() < deadline,
+                "{}: expected {:?}, found {:?}",
+                file.display(),
+                expected,
+                symbols
+            );
+            std::thread::sleep(Duration::from_millis(100));
+        }
+    };
+
+    // Baseline scan of both roots
+    wait_for(&core.join("src/lib.rs"), &["core_v1"]);
+    wait_for(&cli.join("src/scratch.rs"), &["scratch"]);
+    assert!(stored_symbols(&db_path, &core.join("src/scratch.rs")).is_empty());
+    assert!(stored_symbols(&db_path, &cli.join("src/lib.rs")).is_empty());
+
+    // Edits in either root land on that root's file, and an edit to a file
+    // the root's config excludes stays out of the index (it is written
+    // first, so it is processed no later than the edits waited on)
+    std::thread::sleep(Duration::from_millis(300));
+    fs::write(cli.join("src/lib.rs"), "fn cli_v2() {}\n").unwrap();
+    fs::write(core.join("src/lib.rs"), "fn core_v2() {}\n").unwrap();
+    fs::write(cli.join("src/scratch.rs"), "fn cli_scratch_v2() {}\n").unwrap();
+    wait_for(&core.join("src/lib.rs"), &["core_v2"]);
+    wait_for(&cli.join("src/scratch.rs"), &["cli_scratch_v2"]);
+    assert!(stored_symbols(&db_path, &cli.join("src/lib.rs")).is_empty());
+
+    shutdown.store(true, Ordering::SeqCst);
+    let processed = handle.join().unwrap().unwrap();
+    assert!(processed >= 2, "processed {}", processed);
+}
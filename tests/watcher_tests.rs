@@ -316,6 +316,9 @@ fn test_gitignore_aware_watcher_skips_target_files() {
         root_path: root.to_path_buf(),
         debounce_ms: 100,
         gitignore_aware: true, // Enable gitignore filtering
+        follow_symlinks: false,
+        poll_interval_ms: None,
+        non_source_extensions: Vec::new(),
     };
 
     let watcher = FileSystemWatcher::new(root.to_path_buf(), config, shutdown).unwrap();
@@ -384,6 +387,9 @@ fn test_gitignore_aware_false_indexes_all_files() {
         root_path: root.to_path_buf(),
         debounce_ms: 100,
         gitignore_aware: false, // Disable gitignore filtering
+        follow_symlinks: false,
+        poll_interval_ms: None,
+        non_source_extensions: Vec::new(),
     };
 
     let watcher = FileSystemWatcher::new(root.to_path_buf(), config, shutdown).unwrap();
@@ -458,6 +464,9 @@ fn test_gitignore_aware_internal_ignored_dirs() {
         root_path: root.to_path_buf(),
         debounce_ms: 100,
         gitignore_aware: true,
+        follow_symlinks: false,
+        poll_interval_ms: None,
+        non_source_extensions: Vec::new(),
     };
 
     let watcher = FileSystemWatcher::new(root.to_path_buf(), config, shutdown).unwrap();
@@ -554,6 +563,9 @@ fn test_gitignore_complex_patterns() {
         root_path: root.to_path_buf(),
         debounce_ms: 100,
         gitignore_aware: true,
+        follow_symlinks: false,
+        poll_interval_ms: None,
+        non_source_extensions: Vec::new(),
     };
 
     let watcher = FileSystemWatcher::new(root.to_path_buf(), config, shutdown).unwrap();
@@ -664,3 +676,55 @@ fn test_gitignore_filter_matches_build_directory() {
     let result2 = filter.should_skip(&root.join("src/lib.rs"));
     assert_eq!(result2, None, "src/lib.rs should not be ignored");
 }
+
+#[test]
+fn test_poll_backend_reports_modified_file() {
+    let temp_dir = TempDir::new().unwrap();
+    let root = temp_dir.path();
+    let src_file = root.join("lib.rs");
+    fs::write(&src_file, "fn before() {}").unwrap();
+
+    // Polling replaces the native watcher entirely, so no inotify events
+    // are involved: the change is only visible to the periodic rescan
+    let shutdown = Arc::new(AtomicBool::new(false));
+    let config = WatcherConfig {
+        root_path: root.to_path_buf(),
+        debounce_ms: 50,
+        gitignore_aware: true,
+        follow_symlinks: false,
+        poll_interval_ms: Some(2000),
+        non_source_extensions: Vec::new(),
+    };
+    let watcher = FileSystemWatcher::new(root.to_path_buf(), config, shutdown.clone()).unwrap();
+
+    // Let the poller record the initial modification times
+    sleep(Duration::from_millis(300));
+
+    fs::write(&src_file, "fn after() {}").unwrap();
+    // Move the mtime well past coarse filesystem timestamp granularity
+    let later = std::time::SystemTime::now() + Duration::from_secs(5);
+    File::options()
+        .write(true)
+        .open(&src_file)
+        .unwrap()
+        .set_modified(later)
+        .unwrap();
+
+    // A native watcher would report the write within the 50 ms debounce;
+    // the poller only sees it at its next rescan, up to 2 s later
+    assert!(
+        watcher
+            .recv_batch_timeout(Duration::from_millis(500))
+            .is_err(),
+        "batch arrived before the poll interval elapsed"
+    );
+
+    let batch = watcher
+        .recv_batch_timeout(Duration::from_secs(5))
+        .expect("poll backend produced no batch")
+        .expect("watcher thread terminated");
+    assert_eq!(batch.paths.len(), 1);
+    assert!(batch.paths[0].ends_with("lib.rs"));
+
+    shutdown.store(true, std::sync::atomic::Ordering::SeqCst);
+}